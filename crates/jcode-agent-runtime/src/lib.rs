@@ -3,12 +3,26 @@ use std::sync::Arc;
 /// A soft interrupt message queued for injection at the next safe point.
 #[derive(Debug, Clone)]
 pub struct SoftInterruptMessage {
+    /// Process-unique id, so queued entries can be removed or reordered.
+    pub id: u64,
     pub content: String,
     /// If true, can skip remaining tools when injected at point C.
     pub urgent: bool,
     pub source: SoftInterruptSource,
 }
 
+impl SoftInterruptMessage {
+    pub fn new(content: String, urgent: bool, source: SoftInterruptSource) -> Self {
+        static NEXT_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
+        Self {
+            id: NEXT_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed),
+            content,
+            urgent,
+            source,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SoftInterruptSource {
     User,
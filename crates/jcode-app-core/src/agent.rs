@@ -1,14 +1,32 @@
 #![cfg_attr(test, allow(clippy::await_holding_lock))]
 
+mod aside;
+mod auto_commit;
+mod auto_debug;
+mod builder;
 mod compaction;
+mod compare;
+mod context_pruning;
 mod environment;
+mod focus;
+mod handoff;
 mod interrupts;
+mod limits;
 mod messages;
+mod post_mortem;
+mod profiles;
 mod prompting;
 mod provider;
+mod rate_limit;
+mod redaction;
 mod response_recovery;
+mod retired_model;
+mod safe_mode;
+mod skill_autoload;
 mod status;
+mod stream_stalls;
 mod streaming;
+mod tool_fallback;
 mod tools;
 mod turn_execution;
 mod turn_loops;
@@ -16,6 +34,7 @@ mod turn_streaming_mpsc;
 mod utils;
 
 use self::streaming::{send_stream_keepalive_mpsc, stream_keepalive_ticker};
+use self::tool_fallback::{ToolProtocol, apply_textual_tool_protocol, textual_protocol_messages};
 use self::tools::{
     cap_sdk_tool_content_for_history, cap_tool_output_for_history, print_tool_summary,
     tool_output_side_pane_images, tool_output_to_content_blocks,
@@ -45,11 +64,15 @@ use std::sync::{Arc, LazyLock, Mutex as StdMutex};
 use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 
+pub use builder::AgentBuilder;
+pub use compare::CompareRequest;
+pub use handoff::{HandoffSummaryRequest, create_handoff_session};
 use interrupts::{NoToolCallOutcome, PostToolInterruptOutcome};
 pub use jcode_agent_runtime::{
     BackgroundToolSignal, GracefulShutdownSignal, InterruptSignal, SoftInterruptMessage,
     SoftInterruptQueue, SoftInterruptSource, StreamError,
 };
+pub use post_mortem::PostMortemRequest;
 
 const JCODE_NATIVE_TOOLS: &[&str] = &["selfdev", "communicate"];
 static RECOVERED_TEXT_WRAPPED_TOOL_CALLS: std::sync::atomic::AtomicU64 =
@@ -162,13 +185,13 @@ pub struct TokenUsage {
     pub output_tokens: u64,
     pub cache_read_input_tokens: Option<u64>,
     pub cache_creation_input_tokens: Option<u64>,
+    /// Provider-side web searches billed for the request.
+    pub web_search_requests: Option<u64>,
 }
 
 #[derive(Debug, Clone)]
 struct RewindUndoSnapshot {
     messages: Vec<StoredMessage>,
-    provider_session_id: Option<String>,
-    session_provider_session_id: Option<String>,
     visible_message_count: usize,
 }
 
@@ -184,6 +207,9 @@ pub struct Agent {
     provider_session_id: Option<String>,
     /// Last upstream provider (OpenRouter) observed for this session
     last_upstream_provider: Option<String>,
+    /// Backend configuration reported for the response being streamed
+    /// (OpenAI `system_fingerprint`)
+    last_system_fingerprint: Option<String>,
     /// Last observed transport/connection type for this session
     last_connection_type: Option<String>,
     /// Last provider-supplied human-readable transport detail for this session
@@ -193,6 +219,11 @@ pub struct Agent {
     /// Transient reminder injected into provider requests for the current turn only.
     /// Not persisted to session history.
     current_turn_system_reminder: Option<String>,
+    /// `[prompt] git_context` snapshot taken when the current turn started, so
+    /// every request in the turn sends the same dynamic prompt.
+    turn_git_context: Option<String>,
+    /// The system prompt the last request sent, for `/prompt sections`.
+    last_system_prompt: Option<crate::prompt::SplitSystemPrompt>,
     /// Tool call ids observed in the current session transcript.
     tool_call_ids: HashSet<String>,
     /// Tool result ids observed in the current session transcript.
@@ -227,18 +258,49 @@ pub struct Agent {
     mcp_late_register_resolved: bool,
     /// Override system prompt (used by ambient mode to inject a custom prompt)
     system_prompt_override: Option<String>,
+    /// Extra instructions appended to the normal system prompt (`jcode run --append-system`)
+    appended_system_prompt: Option<String>,
     /// Whether memory features are enabled for this session
     memory_enabled: bool,
+    /// Whether each turn's file modifications are committed (`[git] auto_commit`)
+    auto_commit_enabled: bool,
     /// One-step undo snapshot captured before the most recent rewind.
     rewind_undo_snapshot: Option<RewindUndoSnapshot>,
+    /// Ask the client before tool calls outside the auto-allowed tier, without
+    /// the rest of safe mode. Set per connection (ACP); never persisted.
+    approval_gate: bool,
     /// Channel for tools to request stdin input from the user
     stdin_request_tx: Option<tokio::sync::mpsc::UnboundedSender<crate::tool::StdinInputRequest>>,
     /// Canonical reducer-backed view of runtime provider/model selection.
     provider_runtime_state: ProviderRuntimeState,
+    /// Set when the last session restore could not re-apply the stored model
+    /// route; taken by the server to tell clients which route is in use.
+    model_route_fallback: Option<crate::session::ModelRouteFallback>,
     /// When true, this session is an inline swarm worker: stream a throttled
     /// output tail to the global bus so the coordinator's inline gallery can
     /// render a live viewport. Off for normal sessions to avoid bus traffic.
     inline_output_tap: bool,
+    /// `jcode run --max-turns` override for `[agent] max_turns`.
+    max_turns_override: Option<u32>,
+    /// Limit counters for the current request.
+    turn_limits: limits::TurnLimitTracker,
+    /// `/focus` budget of the current turn. Not persisted, so a resumed
+    /// session never inherits a stale timer.
+    turn_focus: Option<focus::FocusTimer>,
+    /// `[privacy]` placeholder mapping for this session, loaded on first use.
+    redaction_map: Option<crate::redaction::RedactionMap>,
+    /// Values redacted since the last request, reported in its token usage.
+    pending_redactions: u64,
+    /// Active `[profiles.<name>]` preset.
+    profile: Option<profiles::ActiveProfile>,
+    /// Repeated tool failures and their background analyses.
+    auto_debug: auto_debug::AutoDebugTracker,
+    /// Skills loaded because their triggers matched the session
+    auto_skills: skill_autoload::AutoSkillState,
+    /// Child agent running the open `/aside`, built on its first turn.
+    aside_agent: Option<Box<Agent>>,
+    /// What this agent's provider calls are counted under in per-origin usage.
+    usage_origin: crate::usage::UsageOrigin,
 }
 
 impl Agent {
@@ -271,10 +333,13 @@ impl Agent {
             disabled_tools,
             provider_session_id: None,
             last_upstream_provider: None,
+            last_system_fingerprint: None,
             last_connection_type: None,
             last_status_detail: None,
             pending_alerts: Vec::new(),
             current_turn_system_reminder: None,
+            turn_git_context: None,
+            last_system_prompt: None,
             tool_call_ids: HashSet::new(),
             tool_result_ids: HashSet::new(),
             tool_output_scan_index: 0,
@@ -286,17 +351,27 @@ impl Agent {
             locked_tools: None,
             mcp_late_register_resolved: false,
             system_prompt_override: None,
+            appended_system_prompt: None,
             memory_enabled: crate::config::config().features.memory,
+            auto_commit_enabled: crate::config::config().git.auto_commit,
             rewind_undo_snapshot: None,
+            approval_gate: false,
             stdin_request_tx: None,
             provider_runtime_state: ProviderRuntimeState::observed(initial_provider_model),
+            model_route_fallback: None,
             inline_output_tap: false,
+            max_turns_override: None,
+            turn_limits: limits::TurnLimitTracker::default(),
+            turn_focus: None,
+            redaction_map: None,
+            pending_redactions: 0,
+            profile: None,
+            auto_debug: auto_debug::AutoDebugTracker::default(),
+            auto_skills: skill_autoload::AutoSkillState::default(),
+            aside_agent: None,
+            usage_origin: crate::usage::UsageOrigin::Interactive,
         };
-        crate::tool::set_session_tool_policy(
-            &agent.session.id,
-            agent.allowed_tools.clone(),
-            agent.disabled_tools.clone(),
-        );
+        agent.sync_session_tool_policy();
         agent
     }
 
@@ -329,9 +404,11 @@ impl Agent {
         agent.session.model = Some(agent.provider.model());
         agent.session.provider_key =
             crate::session::derive_session_provider_key(agent.provider.name());
+        agent.record_session_model_route();
         agent.session.ensure_initial_session_context_message();
         agent.seed_compaction_from_session();
         agent.log_env_snapshot("create");
+        agent.record_session_build();
         agent.fire_session_lifecycle_hook("session_start", "create");
         crate::telemetry::begin_session_with_parent(
             agent.provider.name(),
@@ -368,30 +445,15 @@ impl Agent {
             agent.session.provider_key =
                 crate::session::derive_session_provider_key(agent.provider.name());
         }
-        if let Some(model) = agent.session.model.clone() {
-            let model_request =
-                crate::provider::MultiProvider::model_switch_request_for_session_route(
-                    &model,
-                    agent.session.provider_key.as_deref(),
-                    agent.session.route_api_method.as_deref(),
-                );
-            if let Err(e) = crate::provider::set_model_with_auth_refresh(
-                agent.provider.as_ref(),
-                &model_request,
-            ) {
-                logging::error(&format!(
-                    "Failed to restore session model '{}' via '{}': {}",
-                    model, model_request, e
-                ));
-            }
-        } else {
-            agent.session.model = Some(agent.provider.model());
-        }
+        agent.restore_model_route_from_session();
         agent.restore_reasoning_effort_from_session();
+        agent.restore_profile_from_session();
+        agent.apply_safe_mode();
         agent.session.ensure_initial_session_context_message();
         agent.sync_memory_dedup_state_from_session();
         agent.seed_compaction_from_session();
         agent.log_env_snapshot("attach");
+        agent.record_session_build();
         agent.fire_session_lifecycle_hook("session_start", "attach");
         crate::telemetry::begin_session_with_parent(
             agent.provider.name(),
@@ -418,6 +480,7 @@ impl Agent {
             }
         };
         manager.reset();
+        manager.set_usage_session(&self.session.id);
         let budget = self.provider.context_window();
         manager.set_budget(budget);
         if let Some(state) = self.session.compaction.as_ref() {
@@ -515,10 +578,16 @@ impl Agent {
     fn reset_runtime_state_for_session_change(&mut self) {
         self.active_skill = None;
         self.last_upstream_provider = None;
+        self.last_system_fingerprint = None;
         self.last_connection_type = None;
         self.last_status_detail = None;
         self.pending_alerts.clear();
         self.current_turn_system_reminder = None;
+        self.turn_focus = None;
+        self.redaction_map = None;
+        self.pending_redactions = 0;
+        self.turn_git_context = None;
+        self.last_system_prompt = None;
         self.reset_tool_output_tracking();
         if let Ok(mut queue) = self.soft_interrupt_queue.lock() {
             queue.clear();
@@ -530,6 +599,8 @@ impl Agent {
         self.locked_tools = None;
         self.mcp_late_register_resolved = false;
         self.rewind_undo_snapshot = None;
+        self.reset_auto_debug();
+        self.reset_auto_skills();
     }
 
     fn sync_session_compaction_state_from_manager(
@@ -586,11 +657,8 @@ impl Agent {
             manager.restore_persisted_stored_state_with(&state, &self.session.messages);
         }
 
-        self.cache_tracker.reset();
-        self.locked_tools = None;
+        self.note_history_rewritten();
         self.mcp_late_register_resolved = false;
-        self.provider_session_id = None;
-        self.session.provider_session_id = None;
         self.session.save()?;
         crate::runtime_memory_log::emit_event(
             crate::runtime_memory_log::RuntimeMemoryLogEvent::new(
@@ -640,7 +708,7 @@ impl Agent {
                         self.sync_session_compaction_state_from_manager(&manager);
                     }
                     if event.is_some() {
-                        self.note_compaction_applied();
+                        self.note_history_rewritten();
                         self.persist_session_best_effort("compaction completion");
                     }
                     let user_count = messages
@@ -825,6 +893,17 @@ impl Agent {
         &self.session.id
     }
 
+    /// Record where this session was started (see `Session::origin`).
+    pub fn set_session_origin(&mut self, origin: &str) {
+        self.session.origin = Some(origin.to_string());
+    }
+
+    /// Count this agent's provider calls under `origin` (a subagent or an
+    /// ambient run) instead of as interactive turns.
+    pub fn set_usage_origin(&mut self, origin: crate::usage::UsageOrigin) {
+        self.usage_origin = origin;
+    }
+
     pub(crate) fn set_working_dir_for_pending_context(&mut self, working_dir: Option<String>) {
         if working_dir.is_some() {
             self.session.working_dir = working_dir;
@@ -863,6 +942,12 @@ impl Agent {
         crate::hooks::dispatch_observer(event);
     }
 
+    /// Record a move to another binary in the transcript and persist it.
+    pub fn record_migration(&mut self, from_hash: &str, to_hash: &str, reason: &str) {
+        self.session.record_migration(from_hash, to_hash, reason);
+        self.persist_session_best_effort("migration boundary");
+    }
+
     pub fn mark_crashed(&mut self, message: Option<String>) {
         crate::telemetry::record_crash(
             self.provider.name(),
@@ -887,71 +972,7 @@ impl Agent {
 
     /// Export the full conversation as a markdown transcript.
     pub fn export_conversation_markdown(&self) -> String {
-        let mut md = String::new();
-        for msg in &self.session.messages {
-            let role_label = match msg.role {
-                Role::User => "User",
-                Role::Assistant => "Assistant",
-            };
-            md.push_str(&format!("### {}\n\n", role_label));
-            for block in &msg.content {
-                match block {
-                    ContentBlock::Text { text, .. } => {
-                        md.push_str(text);
-                        md.push_str("\n\n");
-                    }
-                    ContentBlock::Reasoning { text } => {
-                        md.push_str(&format!("*Thinking:* {}\n\n", text));
-                    }
-                    ContentBlock::ReasoningTrace { text } => {
-                        md.push_str(&format!("*Thinking:* {}\n\n", text));
-                    }
-                    ContentBlock::AnthropicThinking { thinking, .. } => {
-                        md.push_str(&format!("*Thinking:* {}\n\n", thinking));
-                    }
-                    ContentBlock::OpenAIReasoning { summary, .. } => {
-                        if !summary.is_empty() {
-                            md.push_str(&format!("*Thinking:* {}\n\n", summary.join("\n")));
-                        }
-                    }
-                    ContentBlock::ToolUse { name, input, .. } => {
-                        let input_str = serde_json::to_string_pretty(input)
-                            .unwrap_or_else(|_| input.to_string());
-                        md.push_str(&format!(
-                            "**Tool: `{}`**\n```json\n{}\n```\n\n",
-                            name, input_str
-                        ));
-                    }
-                    ContentBlock::ToolResult {
-                        content, is_error, ..
-                    } => {
-                        let label = if is_error == &Some(true) {
-                            "Error"
-                        } else {
-                            "Result"
-                        };
-                        // Truncate very long results
-                        let display = if content.len() > 2000 {
-                            format!(
-                                "{}... (truncated, {} chars total)",
-                                crate::util::truncate_str(content, 2000),
-                                content.len()
-                            )
-                        } else {
-                            content.clone()
-                        };
-                        md.push_str(&format!("**{}:**\n```\n{}\n```\n\n", label, display));
-                    }
-                    ContentBlock::Image { .. } => {
-                        md.push_str("[Image]\n\n");
-                    }
-                    ContentBlock::OpenAICompaction { .. } => {
-                        md.push_str("[OpenAI native compaction]\n\n");
-                    }
-                }
-            }
-        }
-        md
+        crate::session::conversation_markdown(&self.session.messages)
     }
 }
 
@@ -0,0 +1,193 @@
+//! `/aside`: a temporary scratch thread beside the main conversation.
+//!
+//! While an aside is open, user turns run on a child agent with its own
+//! in-memory session: it starts from the main session-context message, can
+//! only use read-only tools, and never touches the main transcript or its
+//! provider cache. After every aside turn the child's messages are copied into
+//! `Session::asides`, so an open aside survives a reload. Closing the aside
+//! appends only the user-edited summary to the main conversation.
+
+use super::*;
+
+/// Tools the aside's child agent may use.
+pub const ASIDE_TOOLS: &[&str] = &["read", "agentgrep", "ls", "tree"];
+
+/// Most recent transcript characters sent to the summarizer.
+const SUMMARY_TRANSCRIPT_MAX_CHARS: usize = 24_000;
+
+const SUMMARY_SYSTEM_PROMPT: &str = "You summarize a side conversation so it can be handed back to \
+the main thread of a coding session. Reply with a short summary (at most five bullet points) of \
+the conclusions, decisions, and facts worth keeping. Leave out dead ends and chit-chat. No preamble.";
+
+/// A summary request for the open aside, built under the agent lock and run
+/// without it.
+pub struct AsideSummaryRequest {
+    provider: Arc<dyn Provider>,
+    session_id: String,
+    transcript: String,
+}
+
+impl AsideSummaryRequest {
+    pub async fn run(self) -> Result<String> {
+        let prompt = format!(
+            "Summarize this side conversation for the main thread:\n\n{}",
+            self.transcript
+        );
+        let summary = crate::background_model::complete_simple(
+            self.provider.as_ref(),
+            &crate::usage::UsageOrigin::Aside,
+            Some(&self.session_id),
+            &prompt,
+            SUMMARY_SYSTEM_PROMPT,
+        )
+        .await?;
+        Ok(summary.trim().to_string())
+    }
+}
+
+impl Agent {
+    pub fn aside_active(&self) -> bool {
+        self.session.active_aside().is_some()
+    }
+
+    /// Open a new aside. Later user turns go to it until it is closed.
+    pub fn start_aside(&mut self) -> Result<()> {
+        if self.session.begin_aside().is_none() {
+            anyhow::bail!("an aside is already open; use /aside end to close it");
+        }
+        self.aside_agent = None;
+        self.session.save()
+    }
+
+    /// Run one user turn inside the open aside.
+    pub(super) async fn run_aside_turn(
+        &mut self,
+        user_message: &str,
+        images: Vec<(String, String)>,
+        event_tx: mpsc::UnboundedSender<ServerEvent>,
+    ) -> Result<()> {
+        if self.aside_agent.is_none() {
+            let child = self.build_aside_agent().await;
+            self.aside_agent = Some(Box::new(child));
+        }
+        let Some(child) = self.aside_agent.as_mut() else {
+            anyhow::bail!("aside agent unavailable");
+        };
+        // The child runs the same turn loop as this agent, so the future has
+        // to be boxed to break the recursion.
+        let turn: std::pin::Pin<Box<dyn std::future::Future<Output = Result<()>> + Send + '_>> =
+            Box::pin(child.run_once_streaming_mpsc(user_message, images, None, event_tx));
+        let result = turn.await;
+        let messages = child.session.messages.clone();
+        self.session.set_aside_messages(messages);
+        self.persist_session_best_effort("aside transcript");
+        result
+    }
+
+    pub(super) async fn build_aside_agent(&self) -> Agent {
+        let provider = self.provider.fork();
+        let registry = Registry::new(Arc::clone(&provider)).await;
+        let mut session = Session::create(Some(self.session.id.clone()), Some("Aside".to_string()));
+        session.set_ephemeral();
+        session.working_dir = self.session.working_dir.clone();
+        session.workspace_roots = self.session.workspace_roots.clone();
+        session.tool_scope = self.session.tool_scope.clone();
+        session.instructions = self.session.instructions.clone();
+        session.replace_messages(self.session.aside_seed_messages());
+        let allowed = ASIDE_TOOLS
+            .iter()
+            .map(|name| name.to_string())
+            .filter(|name| {
+                self.allowed_tools
+                    .as_ref()
+                    .is_none_or(|allowed| allowed.contains(name))
+                    && !self.disabled_tools.contains(name)
+            })
+            .collect();
+        let mut child = Agent::new_with_session(provider, registry, session, Some(allowed));
+        child.set_memory_enabled(false);
+        child.set_auto_commit_enabled(false);
+        // Share the turn controls so cancel, soft interrupts, and stdin
+        // prompts reach the aside while it runs.
+        child.soft_interrupt_queue = Arc::clone(&self.soft_interrupt_queue);
+        child.background_tool_signal = self.background_tool_signal.clone();
+        child.graceful_shutdown = self.graceful_shutdown.clone();
+        child.stdin_request_tx = self.stdin_request_tx.clone();
+        child
+    }
+
+    /// Build a summary request for the open aside.
+    pub fn prepare_aside_summary(&self) -> Result<AsideSummaryRequest> {
+        let Some(aside) = self.session.active_aside() else {
+            anyhow::bail!("no aside is open");
+        };
+        let transcript = aside_transcript(&aside.messages);
+        if transcript.trim().is_empty() {
+            anyhow::bail!("the aside is empty; use /aside discard to close it");
+        }
+        Ok(AsideSummaryRequest {
+            provider: self.provider.fork(),
+            session_id: self.session.id.clone(),
+            transcript,
+        })
+    }
+
+    /// Close the open aside. A non-empty `summary` is appended to the main
+    /// conversation; without one the aside is discarded from the context.
+    pub fn close_aside(&mut self, summary: Option<String>) -> Result<()> {
+        let summary = summary
+            .map(|summary| summary.trim().to_string())
+            .filter(|summary| !summary.is_empty());
+        if !self.session.end_aside(summary.clone()) {
+            anyhow::bail!("no aside is open");
+        }
+        self.aside_agent = None;
+        if let Some(summary) = summary {
+            self.add_message_with_display_role(
+                Role::User,
+                vec![ContentBlock::Text {
+                    text: format!("[Aside summary]\n{}", summary),
+                    cache_control: None,
+                }],
+                Some(StoredDisplayRole::System),
+            );
+        }
+        self.session.save()
+    }
+}
+
+/// Plain-text transcript of an aside for the summarizer, newest turns kept
+/// when it is too long.
+fn aside_transcript(messages: &[StoredMessage]) -> String {
+    let mut transcript = String::new();
+    for message in messages {
+        if message.display_role.is_some() {
+            continue;
+        }
+        let label = match message.role {
+            Role::User => "User",
+            Role::Assistant => "Assistant",
+        };
+        for block in &message.content {
+            match block {
+                ContentBlock::Text { text, .. } if !text.trim().is_empty() => {
+                    transcript.push_str(&format!("{}: {}\n\n", label, text.trim()));
+                }
+                ContentBlock::ToolUse { name, .. } => {
+                    transcript.push_str(&format!("{}: [used {}]\n\n", label, name));
+                }
+                _ => {}
+            }
+        }
+    }
+    if transcript.len() > SUMMARY_TRANSCRIPT_MAX_CHARS {
+        let cut = transcript.len() - SUMMARY_TRANSCRIPT_MAX_CHARS;
+        let start = transcript
+            .char_indices()
+            .map(|(index, _)| index)
+            .find(|index| *index >= cut)
+            .unwrap_or(transcript.len());
+        transcript.drain(..start);
+    }
+    transcript
+}
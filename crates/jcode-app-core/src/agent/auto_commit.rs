@@ -0,0 +1,216 @@
+//! `[git] auto_commit`: commit the files each turn modified.
+//!
+//! File tools publish a `FileTouch` on the bus for every write. While a turn
+//! runs, a listener collects this session's modifications; when the turn ends
+//! they are committed through `crate::git_auto_commit`, the SHA is recorded in
+//! the session as a system message, and clients get `ServerEvent::AutoCommit`.
+//! Shell commands publish nothing, so a turn that ran `bash` also commits the
+//! files whose git status changed between the start and the end of the turn.
+//! A failed commit is logged and never fails the turn. With `[git]
+//! provenance` on, the updated `.jcode/provenance.jsonl` is committed too.
+
+use super::*;
+use crate::git_auto_commit::WorktreeSnapshot;
+use std::collections::BTreeSet;
+use std::path::Path;
+use tokio::sync::{broadcast, oneshot};
+
+/// Collects the paths this session's tools modify during one turn.
+pub(super) struct TurnFileTracker {
+    stop: oneshot::Sender<()>,
+    paths: tokio::task::JoinHandle<BTreeSet<PathBuf>>,
+    /// Git status when the turn started, to find what shell commands wrote.
+    worktree: Option<WorktreeSnapshot>,
+}
+
+impl TurnFileTracker {
+    fn start(session_id: String, working_dir: Option<&Path>) -> Self {
+        let worktree = working_dir.and_then(|dir| WorktreeSnapshot::capture(dir).ok());
+        let mut events = Bus::global().subscribe();
+        let (stop, mut stopped) = oneshot::channel();
+        let paths = tokio::spawn(async move {
+            let mut paths = BTreeSet::new();
+            loop {
+                tokio::select! {
+                    biased;
+                    _ = &mut stopped => break,
+                    event = events.recv() => match event {
+                        Ok(event) => collect_touch(&session_id, event, &mut paths),
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            logging::warn(&format!(
+                                "[auto-commit] missed {} bus events; the commit may be incomplete",
+                                skipped
+                            ));
+                        }
+                        Err(broadcast::error::RecvError::Closed) => return paths,
+                    },
+                }
+            }
+            // Tools publish before they return, so anything left is already
+            // queued.
+            while let Ok(event) = events.try_recv() {
+                collect_touch(&session_id, event, &mut paths);
+            }
+            paths
+        });
+        Self {
+            stop,
+            paths,
+            worktree,
+        }
+    }
+
+    async fn finish(self) -> (Vec<PathBuf>, Option<WorktreeSnapshot>) {
+        let _ = self.stop.send(());
+        let paths = self
+            .paths
+            .await
+            .map(|paths| paths.into_iter().collect())
+            .unwrap_or_default();
+        (paths, self.worktree)
+    }
+}
+
+fn collect_touch(session_id: &str, event: BusEvent, paths: &mut BTreeSet<PathBuf>) {
+    if let BusEvent::FileTouch(touch) = event
+        && touch.session_id == session_id
+        && touch.op.is_modification()
+    {
+        paths.insert(touch.path);
+    }
+}
+
+impl Agent {
+    /// Enable or disable per-turn auto-commit for this session.
+    pub fn set_auto_commit_enabled(&mut self, enabled: bool) {
+        self.auto_commit_enabled = enabled;
+    }
+
+    pub fn auto_commit_enabled(&self) -> bool {
+        self.auto_commit_enabled
+    }
+
+    /// Start tracking file modifications for the turn that is starting, when
+    /// auto-commit is on.
+    pub(super) fn track_turn_file_changes(&self) -> Option<TurnFileTracker> {
+        self.auto_commit_enabled.then(|| {
+            TurnFileTracker::start(self.session.id.clone(), self.working_dir().map(Path::new))
+        })
+    }
+
+    /// Commit what the turn modified. Sends `ServerEvent::AutoCommit` ahead of
+    /// `Done` when a commit was made and the turn streams events.
+    pub(super) async fn auto_commit_turn(
+        &mut self,
+        tracker: TurnFileTracker,
+        user_message: &str,
+        start_message_index: usize,
+        event_tx: Option<&mpsc::UnboundedSender<ServerEvent>>,
+    ) {
+        let (mut paths, worktree) = tracker.finish().await;
+        let Some(working_dir) = self.working_dir().map(PathBuf::from) else {
+            return;
+        };
+        let tool_counts = self.turn_tool_counts(start_message_index);
+        if let Some(before) = worktree
+            && tool_counts.iter().any(|(name, _)| name == "bash")
+        {
+            let dir = working_dir.clone();
+            let changed = tokio::task::spawn_blocking(move || {
+                WorktreeSnapshot::capture(&dir).map(|after| after.changed_since(&before))
+            })
+            .await;
+            match changed {
+                Ok(Ok(changed)) => paths.extend(changed),
+                Ok(Err(error)) => logging::warn(&format!(
+                    "[auto-commit] session={} could not read git status: {:#}",
+                    self.session.id, error
+                )),
+                Err(error) => logging::warn(&format!(
+                    "[auto-commit] session={} could not read git status: {}",
+                    self.session.id, error
+                )),
+            }
+        }
+        if paths.is_empty() {
+            return;
+        }
+        // The turn's provenance ranges travel with its commit.
+        if crate::config::config().git.provenance
+            && let Some(mapping) = crate::provenance::mapping_file(&working_dir)
+            && mapping.exists()
+        {
+            paths.push(mapping);
+        }
+        let message =
+            crate::git_auto_commit::commit_message(user_message, &tool_counts, &self.session.id);
+        let branch = crate::config::config().git.auto_commit_branch.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            crate::git_auto_commit::commit_paths(&working_dir, &paths, &message, branch.as_deref())
+        })
+        .await
+        .map_err(anyhow::Error::from)
+        .and_then(|result| result);
+        let commit = match result {
+            Ok(Some(commit)) => commit,
+            Ok(None) => return,
+            Err(error) => {
+                logging::warn(&format!(
+                    "[auto-commit] session={} commit failed: {:#}",
+                    self.session.id, error
+                ));
+                return;
+            }
+        };
+
+        logging::info(&format!(
+            "[auto-commit] session={} {} on {} ({} files)",
+            self.session.id,
+            commit.sha,
+            commit.branch,
+            commit.files.len()
+        ));
+        self.add_message_with_display_role(
+            Role::User,
+            vec![ContentBlock::Text {
+                text: format!(
+                    "[Auto-committed {} on {}: {}]",
+                    commit.sha, commit.branch, commit.subject
+                ),
+                cache_control: None,
+            }],
+            Some(StoredDisplayRole::System),
+        );
+        self.persist_session_best_effort("auto-commit record");
+        if let Some(event_tx) = event_tx {
+            let _ = event_tx.send(ServerEvent::AutoCommit {
+                sha: commit.sha,
+                branch: commit.branch,
+                subject: commit.subject,
+                files: commit.files.len(),
+            });
+        }
+    }
+
+    /// Tool calls made since `start_index`, counted per tool in first-use order.
+    fn turn_tool_counts(&self, start_index: usize) -> Vec<(String, usize)> {
+        let mut counts: Vec<(String, usize)> = Vec::new();
+        let names = self
+            .session
+            .messages
+            .iter()
+            .skip(start_index)
+            .flat_map(|message| message.content.iter())
+            .filter_map(|block| match block {
+                ContentBlock::ToolUse { name, .. } => Some(name),
+                _ => None,
+            });
+        for name in names {
+            match counts.iter_mut().find(|(known, _)| known == name) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((name.clone(), 1)),
+            }
+        }
+        counts
+    }
+}
@@ -0,0 +1,387 @@
+//! Auto-debug for tools that keep failing (`[autodebug]` config).
+//!
+//! Failed tool calls are grouped by tool and normalized error. When a group
+//! reaches `failure_threshold`, a background sidecar call looks at the failed
+//! inputs, the errors, and excerpts of the files they name, and its short
+//! diagnosis is queued as a system soft interrupt (`auto-debug: ...`) for the
+//! next safe point. Analyses are recorded in the session as replay events when
+//! their note is injected; recorded ones count toward
+//! `max_analyses_per_session` after a resume too.
+
+use super::*;
+use crate::sidecar::Sidecar;
+use serde_json::Value;
+
+/// Failed calls kept per group for the analysis prompt.
+const MAX_SAMPLES: usize = 3;
+const SIGNATURE_MAX_BYTES: usize = 160;
+const SAMPLE_INPUT_MAX_BYTES: usize = 2_000;
+const SAMPLE_ERROR_MAX_BYTES: usize = 1_000;
+const EXCERPT_LINES: usize = 40;
+/// Files larger than this are not excerpted.
+const EXCERPT_MAX_FILE_BYTES: u64 = 2 * 1024 * 1024;
+const DIAGNOSIS_MAX_BYTES: usize = 600;
+
+/// Input keys naming the file a tool call works on.
+const PATH_KEYS: &[&str] = &["file_path", "path"];
+
+const ANALYSIS_SYSTEM_PROMPT: &str = "You diagnose why a coding agent's tool call keeps failing. \
+Reply with one or two sentences naming the concrete cause and what the agent should do instead. \
+In file excerpts, tabs are shown as \\t. No preamble.";
+
+#[derive(Debug, Clone)]
+struct FailureSample {
+    input: Value,
+    error: String,
+}
+
+#[derive(Debug, Default)]
+struct FailureGroup {
+    count: u32,
+    samples: Vec<FailureSample>,
+}
+
+/// A group that reached the threshold, handed to the analysis.
+#[derive(Debug)]
+struct FailureBurst {
+    signature: String,
+    count: u32,
+    samples: Vec<FailureSample>,
+}
+
+#[derive(Debug)]
+struct FinishedAnalysis {
+    tool_name: String,
+    failure_count: u32,
+    error_signature: String,
+    /// `None` when the sidecar call failed
+    diagnosis: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct AutoDebugTracker {
+    groups: HashMap<(String, String), FailureGroup>,
+    /// Analyses started but not yet drained from `finished`.
+    pending: usize,
+    finished: Arc<StdMutex<Vec<FinishedAnalysis>>>,
+}
+
+impl AutoDebugTracker {
+    /// Count a failure; returns the group once it reaches `threshold`, which
+    /// also resets it so another `threshold` failures can trigger again.
+    fn record_failure(
+        &mut self,
+        tool_name: &str,
+        input: &Value,
+        error: &str,
+        threshold: u32,
+    ) -> Option<FailureBurst> {
+        let signature = error_signature(error);
+        let key = (tool_name.to_string(), signature.clone());
+        let group = self.groups.entry(key.clone()).or_default();
+        group.count += 1;
+        group.samples.push(FailureSample {
+            input: input.clone(),
+            error: error.to_string(),
+        });
+        if group.samples.len() > MAX_SAMPLES {
+            group.samples.remove(0);
+        }
+        if group.count < threshold.max(1) {
+            return None;
+        }
+        let group = self.groups.remove(&key)?;
+        Some(FailureBurst {
+            signature,
+            count: group.count,
+            samples: group.samples,
+        })
+    }
+}
+
+/// Error text with the parts that vary between attempts (numbers, quoted
+/// strings) masked, so "similar" errors compare equal.
+fn error_signature(error: &str) -> String {
+    let mut signature = String::with_capacity(error.len().min(SIGNATURE_MAX_BYTES));
+    let mut quote: Option<char> = None;
+    let mut last_space = true;
+    for ch in error.chars() {
+        if let Some(open) = quote {
+            if ch == open {
+                quote = None;
+                signature.push(ch);
+            }
+            continue;
+        }
+        match ch {
+            '\'' | '"' | '`' => {
+                quote = Some(ch);
+                signature.push(ch);
+                signature.push('…');
+                last_space = false;
+            }
+            ch if ch.is_ascii_digit() => {
+                if !signature.ends_with('#') {
+                    signature.push('#');
+                }
+                last_space = false;
+            }
+            ch if ch.is_whitespace() => {
+                if !last_space {
+                    signature.push(' ');
+                }
+                last_space = true;
+            }
+            ch => {
+                signature.extend(ch.to_lowercase());
+                last_space = false;
+            }
+        }
+        if signature.len() >= SIGNATURE_MAX_BYTES {
+            break;
+        }
+    }
+    signature.trim_end().to_string()
+}
+
+fn analysis_prompt(tool_name: &str, burst: &FailureBurst, working_dir: Option<&str>) -> String {
+    let mut prompt = format!(
+        "Tool `{}` failed {} times with a similar error.\n",
+        tool_name, burst.count
+    );
+    for (index, sample) in burst.samples.iter().enumerate() {
+        let input = serde_json::to_string(&sample.input).unwrap_or_default();
+        prompt.push_str(&format!(
+            "\nAttempt {}\nInput: {}\nError: {}\n",
+            index + 1,
+            crate::util::truncate_str(&input, SAMPLE_INPUT_MAX_BYTES),
+            crate::util::truncate_str(&sample.error, SAMPLE_ERROR_MAX_BYTES)
+        ));
+    }
+    if let Some(latest) = burst.samples.last()
+        && let Some(excerpt) = file_excerpt(&latest.input, working_dir)
+    {
+        prompt.push_str("\nFile excerpt:\n");
+        prompt.push_str(&excerpt);
+    }
+    prompt
+}
+
+/// Lines of the file the call names, centred on the first line of its
+/// `old_string` when the file has it, with tabs made visible.
+fn file_excerpt(input: &Value, working_dir: Option<&str>) -> Option<String> {
+    let path = PATH_KEYS
+        .iter()
+        .find_map(|key| input.get(*key).and_then(Value::as_str))?;
+    let mut resolved = PathBuf::from(path);
+    if resolved.is_relative()
+        && let Some(dir) = working_dir
+    {
+        resolved = PathBuf::from(dir).join(resolved);
+    }
+    if std::fs::metadata(&resolved).ok()?.len() > EXCERPT_MAX_FILE_BYTES {
+        return None;
+    }
+    let text = std::fs::read_to_string(&resolved).ok()?;
+    let lines: Vec<&str> = text.lines().collect();
+    let anchor = input
+        .get("old_string")
+        .and_then(Value::as_str)
+        .and_then(|old| old.lines().map(str::trim).find(|line| !line.is_empty()))
+        .and_then(|needle| lines.iter().position(|line| line.trim() == needle))
+        .unwrap_or(0);
+    let start = anchor.saturating_sub(EXCERPT_LINES / 4);
+    let end = (start + EXCERPT_LINES).min(lines.len());
+    let mut excerpt = format!("--- {} (lines {}-{})\n", path, start + 1, end);
+    for (offset, line) in lines[start..end].iter().enumerate() {
+        excerpt.push_str(&format!(
+            "{:>5} {}\n",
+            start + offset + 1,
+            line.replace('\t', "\\t")
+        ));
+    }
+    Some(excerpt)
+}
+
+fn clean_diagnosis(text: &str) -> Option<String> {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let text = text.strip_prefix("auto-debug:").unwrap_or(&text).trim();
+    (!text.is_empty()).then(|| crate::util::truncate_str(text, DIAGNOSIS_MAX_BYTES).to_string())
+}
+
+impl Agent {
+    /// Count a failed tool call and start a background analysis once the same
+    /// tool has failed with a similar error `failure_threshold` times.
+    pub(super) fn note_tool_failure(&mut self, tc: &ToolCall, error: &str) {
+        let config = crate::config::config().autodebug.clone();
+        if !config.enabled {
+            return;
+        }
+        let Some(burst) =
+            self.auto_debug
+                .record_failure(&tc.name, &tc.input, error, config.failure_threshold)
+        else {
+            return;
+        };
+        let used = self.session.auto_debug_event_count() + self.auto_debug.pending;
+        if used >= config.max_analyses_per_session as usize {
+            logging::info(&format!(
+                "AUTO_DEBUG_SKIPPED session={} tool={} reason=session_cap used={}",
+                self.session.id, tc.name, used
+            ));
+            return;
+        }
+        self.auto_debug.pending += 1;
+        logging::info(&format!(
+            "AUTO_DEBUG_START session={} tool={} failures={} signature={}",
+            self.session.id, tc.name, burst.count, burst.signature
+        ));
+
+        let prompt = analysis_prompt(&tc.name, &burst, self.working_dir());
+        let queue = self.soft_interrupt_queue();
+        let finished = Arc::clone(&self.auto_debug.finished);
+        let tool_name = tc.name.clone();
+        let session_id = self.session.id.clone();
+        tokio::spawn(async move {
+            let diagnosis = match Sidecar::new()
+                .with_usage_origin(crate::usage::UsageOrigin::AutoDebug)
+                .for_session(session_id)
+                .complete(ANALYSIS_SYSTEM_PROMPT, &prompt)
+                .await
+            {
+                Ok(text) => clean_diagnosis(&text),
+                Err(err) => {
+                    logging::warn(&format!(
+                        "AUTO_DEBUG_FAILED tool={} error={}",
+                        tool_name, err
+                    ));
+                    None
+                }
+            };
+            let note = diagnosis
+                .as_ref()
+                .map(|diagnosis| format!("auto-debug: {}", diagnosis));
+            // Recorded before the note is queued, so the injection that
+            // delivers the note also finds the analysis to record.
+            if let Ok(mut finished) = finished.lock() {
+                finished.push(FinishedAnalysis {
+                    tool_name,
+                    failure_count: burst.count,
+                    error_signature: burst.signature,
+                    diagnosis,
+                });
+            }
+            if let Some(content) = note
+                && let Ok(mut queue) = queue.lock()
+            {
+                queue.push(SoftInterruptMessage::new(
+                    content,
+                    false,
+                    SoftInterruptSource::System,
+                ));
+            }
+        });
+    }
+
+    /// Record finished analyses in the session.
+    pub(super) fn record_finished_auto_debug(&mut self) {
+        let finished: Vec<FinishedAnalysis> = match self.auto_debug.finished.lock() {
+            Ok(mut finished) => finished.drain(..).collect(),
+            Err(_) => return,
+        };
+        for analysis in finished {
+            self.auto_debug.pending = self.auto_debug.pending.saturating_sub(1);
+            if let Some(diagnosis) = analysis.diagnosis {
+                self.session.record_auto_debug_event(
+                    analysis.tool_name,
+                    analysis.failure_count,
+                    analysis.error_signature,
+                    diagnosis,
+                );
+            }
+        }
+    }
+
+    /// Forget failure counts, e.g. when the agent switches sessions.
+    pub(super) fn reset_auto_debug(&mut self) {
+        self.auto_debug = AutoDebugTracker::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn similar_errors_share_a_signature() {
+        assert_eq!(
+            error_signature("Error: old_string not found in 'src/a.rs' (line 12)"),
+            error_signature("Error:  old_string not found in 'src/b.rs' (line 340)")
+        );
+        assert_ne!(
+            error_signature("Error: old_string not found"),
+            error_signature("Error: permission denied")
+        );
+    }
+
+    #[test]
+    fn threshold_triggers_once_then_resets() {
+        let mut tracker = AutoDebugTracker::default();
+        let input = json!({ "file_path": "a.rs" });
+        assert!(
+            tracker
+                .record_failure("edit", &input, "not found: 'x'", 3)
+                .is_none()
+        );
+        assert!(
+            tracker
+                .record_failure("bash", &input, "not found: 'x'", 3)
+                .is_none()
+        );
+        assert!(
+            tracker
+                .record_failure("edit", &input, "not found: 'y'", 3)
+                .is_none()
+        );
+        let burst = tracker
+            .record_failure("edit", &input, "not found: 'z'", 3)
+            .expect("third similar edit failure triggers");
+        assert_eq!(burst.count, 3);
+        assert_eq!(burst.samples.len(), 3);
+        assert!(
+            tracker
+                .record_failure("edit", &input, "not found: 'w'", 3)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn excerpt_centres_on_old_string_and_shows_tabs() {
+        let dir = tempfile::tempdir().unwrap();
+        let body: String = (1..=100)
+            .map(|n| {
+                if n == 60 {
+                    "\tlet target = 1;\n".to_string()
+                } else {
+                    format!("line {}\n", n)
+                }
+            })
+            .collect();
+        std::fs::write(dir.path().join("lib.rs"), body).unwrap();
+
+        let input = json!({ "file_path": "lib.rs", "old_string": "    let target = 1;" });
+        let excerpt = file_excerpt(&input, dir.path().to_str()).unwrap();
+        assert!(excerpt.starts_with("--- lib.rs (lines 50-89)\n"));
+        assert!(excerpt.contains("   60 \\tlet target = 1;\n"));
+    }
+
+    #[test]
+    fn diagnosis_is_single_line_without_prefix() {
+        assert_eq!(
+            clean_diagnosis("auto-debug: The file uses tabs,\nnot spaces.").as_deref(),
+            Some("The file uses tabs, not spaces.")
+        );
+        assert_eq!(clean_diagnosis("  \n"), None);
+    }
+}
@@ -0,0 +1,114 @@
+//! Builder for embedding an [`Agent`] in another program.
+//!
+//! Wraps [`Agent::new`] / [`Agent::new_with_session`] plus the setters that
+//! embedders usually need, so a custom provider and a handful of custom tools
+//! can be wired up without touching config files or the server.
+
+use super::Agent;
+use crate::provider::Provider;
+use crate::session::Session;
+use crate::tool::{Registry, Tool};
+use std::sync::Arc;
+
+pub struct AgentBuilder {
+    provider: Arc<dyn Provider>,
+    registry: Option<Registry>,
+    builtin_tools: bool,
+    tools: Vec<Arc<dyn Tool>>,
+    session: Option<Session>,
+    system_prompt: Option<String>,
+    instructions: Option<String>,
+    max_turns: Option<u32>,
+    memory_enabled: Option<bool>,
+}
+
+impl AgentBuilder {
+    /// Start a builder for `provider`. By default the agent gets no tools
+    /// besides the ones added with [`AgentBuilder::tool`].
+    pub fn new(provider: Arc<dyn Provider>) -> Self {
+        Self {
+            provider,
+            registry: None,
+            builtin_tools: false,
+            tools: Vec::new(),
+            session: None,
+            system_prompt: None,
+            instructions: None,
+            max_turns: None,
+            memory_enabled: None,
+        }
+    }
+
+    /// Use an existing registry instead of building one.
+    pub fn registry(mut self, registry: Registry) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    /// Register jcode's built-in tools (read, write, bash, ...). Ignored when a
+    /// registry is supplied with [`AgentBuilder::registry`].
+    pub fn builtin_tools(mut self, enabled: bool) -> Self {
+        self.builtin_tools = enabled;
+        self
+    }
+
+    /// Register a custom tool under its [`Tool::name`].
+    pub fn tool(mut self, tool: Arc<dyn Tool>) -> Self {
+        self.tools.push(tool);
+        self
+    }
+
+    /// Attach to an existing session instead of creating a new one.
+    pub fn session(mut self, session: Session) -> Self {
+        self.session = Some(session);
+        self
+    }
+
+    /// Replace the system prompt entirely.
+    pub fn system_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.system_prompt = Some(prompt.into());
+        self
+    }
+
+    /// Append instructions to the normal system prompt.
+    pub fn instructions(mut self, instructions: impl Into<String>) -> Self {
+        self.instructions = Some(instructions.into());
+        self
+    }
+
+    /// Override `[agent] max_turns` for this agent.
+    pub fn max_turns(mut self, max_turns: u32) -> Self {
+        self.max_turns = Some(max_turns);
+        self
+    }
+
+    pub fn memory_enabled(mut self, enabled: bool) -> Self {
+        self.memory_enabled = Some(enabled);
+        self
+    }
+
+    pub async fn build(self) -> Agent {
+        let registry = match self.registry {
+            Some(registry) => registry,
+            None if self.builtin_tools => Registry::new(self.provider.clone()).await,
+            None => Registry::empty(),
+        };
+        for tool in self.tools {
+            registry.register(tool.name().to_string(), tool).await;
+        }
+
+        let mut agent = match self.session {
+            Some(session) => Agent::new_with_session(self.provider, registry, session, None),
+            None => Agent::new(self.provider, registry),
+        };
+        if let Some(prompt) = self.system_prompt {
+            agent.set_system_prompt(&prompt);
+        }
+        agent.set_appended_system_prompt(self.instructions);
+        agent.set_max_turns(self.max_turns);
+        if let Some(enabled) = self.memory_enabled {
+            agent.set_memory_enabled(enabled);
+        }
+        agent
+    }
+}
@@ -1,7 +1,99 @@
 use super::*;
 
+/// How many of the largest messages to name when an overflow cannot be fixed.
+const CONTEXT_OVERFLOW_LARGEST_MESSAGES: usize = 5;
+
+/// What was trimmed to get an oversized request under the provider's limit.
+pub(super) struct ContextRecovery {
+    /// One-line explanation of what was trimmed, shown before the retry.
+    pub(super) status: String,
+    event: CompactionEvent,
+}
+
+impl ContextRecovery {
+    fn retry(status: impl Into<String>) -> Self {
+        Self {
+            status: status.into(),
+            event: CompactionEvent {
+                trigger: "auto_recovery".to_string(),
+                pre_tokens: None,
+                post_tokens: None,
+                tokens_saved: None,
+                duration_ms: None,
+                messages_dropped: None,
+                messages_compacted: None,
+                summary_chars: None,
+                active_messages: None,
+                tool_results_trimmed: None,
+            },
+        }
+    }
+
+    fn trimmed(
+        pre_tokens: u64,
+        post_tokens: Option<u64>,
+        messages_dropped: Option<usize>,
+        tool_results_trimmed: Option<usize>,
+    ) -> Self {
+        let mut parts = Vec::new();
+        if let Some(dropped) = messages_dropped {
+            parts.push(format!("dropped {} older message(s)", dropped));
+        }
+        if let Some(trimmed) = tool_results_trimmed {
+            parts.push(format!("shortened {} old tool result(s)", trimmed));
+        }
+        let sizes = match post_tokens {
+            Some(post) => format!(
+                "~{} → ~{} tokens",
+                format_token_count(pre_tokens),
+                format_token_count(post)
+            ),
+            None => format!("was ~{} tokens", format_token_count(pre_tokens)),
+        };
+        let mut recovery = Self::retry(format!(
+            "Context window exceeded: {} ({}); retrying the same message.",
+            parts.join(" and "),
+            sizes
+        ));
+        recovery.event.pre_tokens = Some(pre_tokens);
+        recovery.event.post_tokens = post_tokens;
+        recovery.event.tokens_saved = post_tokens.map(|post| pre_tokens.saturating_sub(post));
+        recovery.event.messages_dropped = messages_dropped;
+        recovery.event.tool_results_trimmed = tool_results_trimmed;
+        recovery
+    }
+
+    pub(super) fn server_event(&self) -> ServerEvent {
+        let event = self.event.clone();
+        ServerEvent::Compaction {
+            trigger: event.trigger,
+            pre_tokens: event.pre_tokens,
+            post_tokens: event.post_tokens,
+            tokens_saved: event.tokens_saved,
+            duration_ms: event.duration_ms,
+            messages_dropped: event.messages_dropped,
+            messages_compacted: event.messages_compacted,
+            summary_chars: event.summary_chars,
+            active_messages: event.active_messages,
+            tool_results_trimmed: event.tool_results_trimmed,
+        }
+    }
+}
+
+fn format_token_count(tokens: u64) -> String {
+    if tokens >= 10_000 {
+        format!("{}k", tokens / 1000)
+    } else {
+        tokens.to_string()
+    }
+}
+
 impl Agent {
-    pub(super) fn note_compaction_applied(&mut self) {
+    /// Invariant: every operation that rewrites session history (compaction,
+    /// payload trimming, rewind, rewind undo) ends here, dropping the provider
+    /// resume id so the next turn sends the full rebuilt context rather than
+    /// resuming a provider-side conversation that still holds the old history.
+    pub(super) fn note_history_rewritten(&mut self) {
         self.cache_tracker.reset();
         self.locked_tools = None;
         self.provider_session_id = None;
@@ -23,7 +115,7 @@ impl Agent {
         };
 
         if event.is_some() {
-            self.note_compaction_applied();
+            self.note_history_rewritten();
             self.persist_session_best_effort("compaction completion");
         }
 
@@ -87,99 +179,246 @@ impl Agent {
         }
     }
 
-    fn is_context_limit_error(error: &str) -> bool {
-        let lower = error.to_lowercase();
-        lower.contains("context length")
-            || lower.contains("context window")
-            || lower.contains("maximum context")
-            || lower.contains("max context")
-            || lower.contains("token limit")
-            || lower.contains("too many tokens")
-            || lower.contains("prompt is too long")
-            || lower.contains("input is too long")
-            || lower.contains("request too large")
-            || lower.contains("length limit")
-            || lower.contains("maximum tokens")
-            || (lower.contains("exceeded") && lower.contains("tokens"))
-    }
-
-    /// Best-effort emergency recovery after a context-limit error.
+    /// Best-effort recovery after an oversized request, so the caller can retry
+    /// the same turn immediately.
     ///
-    /// Performs a synchronous hard compaction and resets provider session state,
-    /// allowing the caller to retry the same turn immediately.
-    pub(super) fn try_auto_compact_after_context_limit(&mut self, error: &str) -> bool {
+    /// Handles oversized native compaction state, HTTP 413 image payloads, and
+    /// context-window overflows. Returns what was trimmed, or `None` when the
+    /// error is not recoverable this way.
+    pub(super) fn try_auto_compact_after_context_limit(
+        &mut self,
+        error: &str,
+    ) -> Option<ContextRecovery> {
         if crate::provider::openai_request::is_openai_encrypted_content_too_large_error(error)
             && self.try_recover_oversized_openai_native_compaction()
         {
-            return true;
+            return Some(ContextRecovery::retry(
+                "Discarded oversized native compaction state; retrying with the text summary.",
+            ));
         }
         // A provider HTTP 413 ("request too large") is a *byte-size* failure
         // driven by inline base64 images, not a token-context overflow. Token
         // accounting deliberately undercounts images, so ordinary compaction
         // would not shrink the payload and the retry would 413 again. Strip
         // oversized images first.
-        if self.try_recover_after_payload_too_large(error) {
-            return true;
+        if let Some(stripped) = self.try_recover_after_payload_too_large(error) {
+            return Some(ContextRecovery::retry(format!(
+                "Request body too large: dropped {} oversized image(s); retrying.",
+                stripped
+            )));
         }
-        if !Self::is_context_limit_error(error) {
-            return false;
+        if !crate::compaction::is_context_overflow_error(error) {
+            return None;
         }
-        if !self.provider.supports_compaction() {
-            return false;
+        let reported_tokens =
+            crate::compaction::context_overflow_token_counts(error).map(|(used, _)| used);
+        self.recover_context_overflow("context_limit_auto_compaction", reported_tokens)
+    }
+
+    /// Check the estimated request size before sending it. When it is already
+    /// over the provider's context window, recover up front instead of waiting
+    /// for the provider to reject the request.
+    pub(super) fn preflight_context_recovery(
+        &mut self,
+        messages: &[Message],
+        system_prompt: &crate::prompt::SplitSystemPrompt,
+        tools: &[ToolDefinition],
+    ) -> Option<ContextRecovery> {
+        let context_limit = self.provider.context_window();
+        if context_limit == 0 {
+            return None;
+        }
+        let prompt_tokens = crate::util::estimate_tokens(&system_prompt.static_part)
+            + crate::util::estimate_tokens(&system_prompt.dynamic_part);
+        let tool_tokens = tools
+            .iter()
+            .map(|tool| {
+                serde_json::to_string(tool)
+                    .map(|json| json.len())
+                    .unwrap_or(0)
+            })
+            .sum::<usize>()
+            / crate::compaction::CHARS_PER_TOKEN;
+        let message_tokens = messages
+            .iter()
+            .map(crate::compaction::message_char_count)
+            .sum::<usize>()
+            / crate::compaction::CHARS_PER_TOKEN;
+        let estimate = prompt_tokens + tool_tokens + message_tokens;
+        if estimate <= context_limit {
+            return None;
         }
+        logging::warn(&format!(
+            "Pre-flight estimate ~{} tokens exceeds {} context window ({}); recovering before sending",
+            estimate,
+            self.provider.model(),
+            context_limit
+        ));
+        self.recover_context_overflow("context_limit_preflight", Some(estimate as u64))
+    }
 
+    /// Drop older messages via hard compaction, then shorten the oldest tool
+    /// results outside the current turn if that was not enough (or compaction
+    /// is unavailable).
+    fn recover_context_overflow(
+        &mut self,
+        reason: &str,
+        reported_tokens: Option<u64>,
+    ) -> Option<ContextRecovery> {
         let context_limit = self.provider.context_window() as u64;
         let compaction = self.registry.compaction();
-
-        let (dropped, usage_pct) = match compaction.try_write() {
-            Ok(mut manager) => {
-                let (dropped, usage_pct) = {
-                    let all_messages = self.session.provider_messages();
-                    manager.update_observed_input_tokens(context_limit);
-                    let usage_pct = manager.context_usage_with(all_messages) * 100.0;
-                    let dropped = match manager.hard_compact_with(all_messages) {
-                        Ok(dropped) => dropped,
-                        Err(reason) => {
-                            logging::warn(&format!(
-                                "Context-limit auto-recovery failed: hard compact failed ({})",
-                                reason
-                            ));
-                            return false;
-                        }
-                    };
-                    (dropped, usage_pct)
-                };
-                self.sync_session_compaction_state_from_manager(&manager);
-                (dropped, usage_pct)
-            }
+        let mut manager = match compaction.try_write() {
+            Ok(manager) => manager,
             Err(_) => {
                 logging::warn("Context-limit auto-recovery skipped: compaction manager lock busy");
-                return false;
+                return None;
             }
         };
 
-        self.cache_tracker.reset();
-        self.locked_tools = None;
-        self.provider_session_id = None;
-        self.session.provider_session_id = None;
+        let estimated_tokens =
+            manager.effective_token_count_with(self.session.provider_messages()) as u64;
+        let pre_tokens = reported_tokens.unwrap_or(estimated_tokens);
+        let mut messages_dropped = None;
+        if self.provider.supports_compaction() {
+            let all_messages = self.session.provider_messages();
+            manager.update_observed_input_tokens(context_limit);
+            match manager.hard_compact_with(all_messages) {
+                Ok(dropped) => {
+                    messages_dropped = Some(dropped);
+                    // Reported below as part of this recovery.
+                    let _ = manager.take_compaction_event();
+                }
+                Err(err) => logging::warn(&format!(
+                    "Context-limit auto-recovery: hard compact failed ({})",
+                    err
+                )),
+            }
+            self.sync_session_compaction_state_from_manager(&manager);
+        }
 
-        logging::warn(&format!(
-            "Context limit exceeded; auto-compacted and retrying (dropped {} messages, usage was {:.1}%)",
-            dropped, usage_pct
-        ));
+        let still_over_budget = messages_dropped.is_none()
+            || manager.context_usage_with(self.session.provider_messages()) > 1.0;
+        let mut tool_results_trimmed = 0;
+        if still_over_budget {
+            // Leave room for the system prompt, tools, summary, and the reply.
+            let target_tokens =
+                (manager.token_budget() as f32 * crate::compaction::COMPACTION_THRESHOLD) as usize;
+            let reserved = crate::compaction::SYSTEM_OVERHEAD_TOKENS
+                + manager.summary_chars() / crate::compaction::CHARS_PER_TOKEN;
+            let target_chars =
+                target_tokens.saturating_sub(reserved) * crate::compaction::CHARS_PER_TOKEN;
+            let start = manager.compacted_count();
+            drop(manager);
+            tool_results_trimmed = self.session.trim_oldest_tool_results(start, target_chars);
+            if tool_results_trimmed > 0 {
+                self.reseed_compaction_from_session();
+            }
+        } else {
+            drop(manager);
+        }
+
+        if messages_dropped.unwrap_or(0) == 0 && tool_results_trimmed == 0 {
+            logging::warn("Context-limit auto-recovery found nothing to drop or trim");
+            return None;
+        }
+
+        self.note_history_rewritten();
+        self.persist_session_best_effort("context overflow recovery");
+
+        let post_tokens = self
+            .registry
+            .compaction()
+            .try_read()
+            .map(|manager| manager.effective_token_count_with(self.session.provider_messages()))
+            .ok()
+            .map(|tokens| tokens as u64);
+        let recovery = ContextRecovery::trimmed(
+            pre_tokens,
+            post_tokens,
+            messages_dropped.filter(|dropped| *dropped > 0),
+            (tool_results_trimmed > 0).then_some(tool_results_trimmed),
+        );
+        logging::warn(&recovery.status);
         crate::runtime_memory_log::emit_event(
             crate::runtime_memory_log::RuntimeMemoryLogEvent::new(
                 "auto_compaction_applied",
-                "context_limit_auto_compaction",
+                reason,
             )
             .with_session_id(self.session.id.clone())
             .with_detail(format!(
-                "dropped_messages={dropped},usage_pct={usage_pct:.1}"
+                "dropped_messages={},tool_results_trimmed={},pre_tokens={}",
+                messages_dropped.unwrap_or(0),
+                tool_results_trimmed,
+                pre_tokens
             ))
             .force_attribution(),
         );
 
-        true
+        Some(recovery)
+    }
+
+    /// The error to surface once recovery is exhausted: context overflows and
+    /// retired models get a precise explanation, anything else passes through
+    /// unchanged.
+    pub(super) fn context_limit_failure(&mut self, error: anyhow::Error) -> anyhow::Error {
+        // Every terminal provider failure funnels through here, so this is
+        // also where rate limits the agent did not wait out get recorded.
+        self.note_rate_limit_failure(&error);
+        let message = error.to_string();
+        if let Some(failure) = self.retired_model_failure(&message) {
+            return failure;
+        }
+        if crate::compaction::is_context_overflow_error(&message)
+            && !crate::compaction::is_request_payload_too_large_error(&message)
+        {
+            self.context_overflow_error(&message)
+        } else {
+            error
+        }
+    }
+
+    /// Explain an overflow that recovery could not fix: the request size
+    /// against the limit and the largest messages still being sent.
+    fn context_overflow_error(&mut self, error: &str) -> anyhow::Error {
+        let compaction = self.registry.compaction();
+        let (start, estimated_tokens) = match compaction.try_read() {
+            Ok(manager) => (
+                manager.compacted_count(),
+                manager.effective_token_count_with(self.session.provider_messages()) as u64,
+            ),
+            Err(_) => (0, 0),
+        };
+        let (used, limit) = crate::compaction::context_overflow_token_counts(error)
+            .unwrap_or((estimated_tokens, self.provider.context_window() as u64));
+
+        let all_messages = self.session.provider_messages();
+        let start = start.min(all_messages.len());
+        let largest = crate::compaction::largest_messages(
+            &all_messages[start..],
+            CONTEXT_OVERFLOW_LARGEST_MESSAGES,
+        );
+
+        let mut message = format!(
+            "Context window exceeded: request is ~{} tokens, {} accepts {}. Automatic recovery could not shrink it enough.",
+            format_token_count(used),
+            self.provider.model(),
+            format_token_count(limit)
+        );
+        if !largest.is_empty() {
+            message.push_str("\nLargest messages:");
+            for (rank, entry) in largest.iter().enumerate() {
+                message.push_str(&format!(
+                    "\n  {}. #{} {} · ~{} tokens",
+                    rank + 1,
+                    start + entry.index + 1,
+                    entry.label,
+                    format_token_count(entry.tokens as u64)
+                ));
+            }
+            message.push_str("\nUse /rewind or /compact to prune them, then retry.");
+        }
+        message.push_str(&format!("\nProvider error: {}", error));
+        anyhow::anyhow!(message)
     }
 
     /// Best-effort recovery after a provider HTTP 413 "request too large" error.
@@ -189,9 +428,9 @@ impl Agent {
     /// the token context window. We strip oversized images from the persisted
     /// transcript, oldest-first, down to a conservative byte budget and reset the
     /// provider session/cache so the caller can retry the same turn immediately.
-    fn try_recover_after_payload_too_large(&mut self, error: &str) -> bool {
+    fn try_recover_after_payload_too_large(&mut self, error: &str) -> Option<usize> {
         if !crate::compaction::is_request_payload_too_large_error(error) {
-            return false;
+            return None;
         }
 
         let stripped = self
@@ -201,28 +440,13 @@ impl Agent {
             logging::warn(
                 "Request-too-large recovery skipped: no oversized inline images to strip",
             );
-            return false;
+            return None;
         }
 
         // The transcript changed; reseed compaction bookkeeping and reset
         // provider session/cache state so the retry sends the reduced payload.
-        let compaction = self.registry.compaction();
-        if let Ok(mut manager) = compaction.try_write() {
-            let provider_messages = self.session.messages_for_provider();
-            manager.reset();
-            manager.set_budget(self.provider.context_window());
-            if let Some(state) = self.session.compaction.as_ref() {
-                manager.restore_persisted_state_with(state, &provider_messages);
-            } else {
-                manager.seed_restored_messages_with(&provider_messages);
-            }
-            self.sync_session_compaction_state_from_manager(&manager);
-        }
-
-        self.cache_tracker.reset();
-        self.locked_tools = None;
-        self.provider_session_id = None;
-        self.session.provider_session_id = None;
+        self.reseed_compaction_from_session();
+        self.note_history_rewritten();
 
         logging::warn(&format!(
             "Request body exceeded provider size limit; stripped {} oversized inline image(s) and retrying",
@@ -238,7 +462,24 @@ impl Agent {
             .force_attribution(),
         );
 
-        true
+        Some(stripped)
+    }
+
+    /// Rebuild compaction bookkeeping after the stored transcript was edited
+    /// in place.
+    fn reseed_compaction_from_session(&mut self) {
+        let compaction = self.registry.compaction();
+        if let Ok(mut manager) = compaction.try_write() {
+            let provider_messages = self.session.messages_for_provider();
+            manager.reset();
+            manager.set_budget(self.provider.context_window());
+            if let Some(state) = self.session.compaction.as_ref() {
+                manager.restore_persisted_state_with(state, &provider_messages);
+            } else {
+                manager.seed_restored_messages_with(&provider_messages);
+            }
+            self.sync_session_compaction_state_from_manager(&manager);
+        }
     }
 
     fn try_recover_oversized_openai_native_compaction(&mut self) -> bool {
@@ -263,10 +504,7 @@ impl Agent {
             return false;
         }
 
-        self.cache_tracker.reset();
-        self.locked_tools = None;
-        self.provider_session_id = None;
-        self.session.provider_session_id = None;
+        self.note_history_rewritten();
 
         logging::warn(
             "OpenAI native compaction payload exceeded provider size limit; discarded native state and retrying with text fallback",
@@ -283,7 +521,7 @@ impl Agent {
         true
     }
 
-    fn effective_context_tokens_from_usage(
+    pub(super) fn effective_context_tokens_from_usage(
         &self,
         input_tokens: u64,
         cache_read_input_tokens: Option<u64>,
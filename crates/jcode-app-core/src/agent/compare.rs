@@ -0,0 +1,158 @@
+//! `/compare`: one prompt, several models, side by side.
+//!
+//! Every model gets a fork of the session's provider with the same history
+//! and system prompt and no tool definitions, so nothing runs on the user's
+//! machine. Nothing is written until the user keeps an answer: then the
+//! prompt and that answer join the conversation as a normal exchange, and the
+//! other answers go to `Session::alternatives`.
+
+use super::*;
+use crate::protocol::CompareRun;
+
+/// A comparison built under the agent lock and run without it.
+pub struct CompareRequest {
+    provider: Arc<dyn Provider>,
+    models: Vec<String>,
+    messages: Vec<Message>,
+    system: crate::prompt::SplitSystemPrompt,
+}
+
+impl CompareRequest {
+    /// `messages` already ends with the prompt being compared.
+    pub fn new(
+        provider: Arc<dyn Provider>,
+        models: Vec<String>,
+        messages: Vec<Message>,
+        system: crate::prompt::SplitSystemPrompt,
+    ) -> Self {
+        Self {
+            provider,
+            models,
+            messages,
+            system,
+        }
+    }
+
+    /// Run every model at once. Runs come back in the order the models were
+    /// given; a failed model yields a run with `error` set.
+    pub async fn run(self) -> Vec<CompareRun> {
+        let runs = self
+            .models
+            .iter()
+            .map(|model| self.run_model(model.clone()));
+        futures::future::join_all(runs).await
+    }
+
+    async fn run_model(&self, model: String) -> CompareRun {
+        let mut run = CompareRun {
+            model,
+            text: String::new(),
+            input_tokens: None,
+            output_tokens: None,
+            latency_ms: 0,
+            error: None,
+        };
+        let provider = self.provider.fork();
+        if let Err(error) = provider.set_model(&run.model) {
+            run.error = Some(crate::util::format_error_chain(&error));
+            return run;
+        }
+        let started = Instant::now();
+        let result = async {
+            let mut stream = provider
+                .complete_split(
+                    &self.messages,
+                    &[],
+                    &self.system.static_part,
+                    &self.system.dynamic_part,
+                    None,
+                )
+                .await?;
+            let mut text_cursor = crate::message::StreamTextCursor::default();
+            while let Some(event) = stream.next().await {
+                let Some(event) = text_cursor.normalize(event?) else {
+                    continue;
+                };
+                match event {
+                    StreamEvent::TextDelta(text) => run.text.push_str(&text),
+                    StreamEvent::RetryRollback { .. } => run.text.clear(),
+                    StreamEvent::TokenUsage {
+                        input_tokens,
+                        output_tokens,
+                        ..
+                    } => {
+                        run.input_tokens = input_tokens.or(run.input_tokens);
+                        run.output_tokens = output_tokens.or(run.output_tokens);
+                    }
+                    StreamEvent::Error { message, .. } => anyhow::bail!(message),
+                    _ => {}
+                }
+            }
+            Ok(())
+        }
+        .await;
+        run.latency_ms = started.elapsed().as_millis() as u64;
+        run.text = run.text.trim().to_string();
+        if let Err(error) = result {
+            run.error = Some(crate::util::format_error_chain(&error));
+        }
+        run
+    }
+}
+
+impl Agent {
+    /// Build a comparison of `prompt` across `models` from this session's
+    /// history and system prompt.
+    pub fn prepare_compare(&mut self, prompt: &str, models: Vec<String>) -> Result<CompareRequest> {
+        if models.len() < 2 {
+            anyhow::bail!("compare needs at least two models");
+        }
+        if prompt.trim().is_empty() {
+            anyhow::bail!("nothing to compare: the prompt is empty");
+        }
+        if self.aside_active() {
+            anyhow::bail!("close the open aside before comparing models");
+        }
+        let mut messages = self.session.messages_for_provider();
+        messages.push(Message::user(prompt));
+        Ok(CompareRequest::new(
+            self.provider.clone(),
+            models,
+            messages,
+            self.build_system_prompt_split(None),
+        ))
+    }
+
+    /// Add `prompt` and `runs[chosen]` to the conversation and keep the other
+    /// runs as alternatives of the new assistant message.
+    pub fn commit_compare(
+        &mut self,
+        prompt: &str,
+        chosen: usize,
+        mut runs: Vec<CompareRun>,
+    ) -> Result<()> {
+        if chosen >= runs.len() {
+            anyhow::bail!("compare has no answer #{}", chosen + 1);
+        }
+        let kept = runs.remove(chosen);
+        if kept.error.is_some() || kept.text.is_empty() {
+            anyhow::bail!("{} has no answer to keep", kept.model);
+        }
+        self.add_message(
+            Role::User,
+            vec![ContentBlock::Text {
+                text: prompt.to_string(),
+                cache_control: None,
+            }],
+        );
+        let message_id = self.add_message(
+            Role::Assistant,
+            vec![ContentBlock::Text {
+                text: kept.text,
+                cache_control: None,
+            }],
+        );
+        self.session.record_alternatives(&message_id, runs);
+        self.session.save()
+    }
+}
@@ -0,0 +1,256 @@
+//! Outbound pruning of file reads superseded by later reads or edits
+//! (`[compaction] prune_superseded_reads`, off by default).
+//!
+//! The body of a `read` result is replaced with a one-line stub once a later
+//! full read, write or edit of the same path makes it stale. Only the request
+//! view is rewritten: the session keeps every result, and each tool_result
+//! block stays in place so tool_use/tool_result pairing is unchanged.
+
+use super::*;
+use std::path::{Component, Path};
+
+/// Results shorter than this are kept; the stub would save almost nothing.
+const MIN_PRUNED_RESULT_CHARS: usize = 400;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileTouch {
+    /// A read of the whole file.
+    FullRead,
+    /// A read of a line range; superseded like any read, but never supersedes.
+    PartialRead,
+    Edit,
+}
+
+struct TouchEvent<'a> {
+    tool_use_id: &'a str,
+    path: PathBuf,
+    touch: FileTouch,
+    turn: usize,
+}
+
+fn classify_tool_use(name: &str, input: &serde_json::Value) -> Option<FileTouch> {
+    match name {
+        "read" => {
+            let ranged = ["start_line", "end_line", "offset", "limit"]
+                .iter()
+                .any(|key| input.get(key).is_some_and(|value| !value.is_null()));
+            Some(if ranged {
+                FileTouch::PartialRead
+            } else {
+                FileTouch::FullRead
+            })
+        }
+        "write" | "edit" | "multiedit" => Some(FileTouch::Edit),
+        _ => None,
+    }
+}
+
+/// Lexically normalize `path`, resolving relative paths against `working_dir`.
+fn normalize_path(path: &str, working_dir: Option<&Path>) -> PathBuf {
+    let path = Path::new(path);
+    let joined = match working_dir {
+        Some(dir) if path.is_relative() => dir.join(path),
+        _ => path.to_path_buf(),
+    };
+    let mut normalized = PathBuf::new();
+    for component in joined.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// Stub out superseded `read` results in `messages`. Returns the estimated
+/// number of tokens removed.
+pub(super) fn prune_superseded_reads(messages: &mut [Message], working_dir: Option<&Path>) -> u64 {
+    let mut events = Vec::new();
+    let mut turn = 0;
+    for message in messages.iter() {
+        if message.role != Role::Assistant {
+            continue;
+        }
+        turn += 1;
+        for block in &message.content {
+            let ContentBlock::ToolUse {
+                id, name, input, ..
+            } = block
+            else {
+                continue;
+            };
+            let Some(touch) = classify_tool_use(name, input) else {
+                continue;
+            };
+            let Some(path) = input.get("file_path").and_then(|value| value.as_str()) else {
+                continue;
+            };
+            events.push(TouchEvent {
+                tool_use_id: id,
+                path: normalize_path(path, working_dir),
+                touch,
+                turn,
+            });
+        }
+    }
+
+    let mut superseded: HashMap<String, String> = HashMap::new();
+    for (index, event) in events.iter().enumerate() {
+        if event.touch == FileTouch::Edit {
+            continue;
+        }
+        let later = events[index + 1..]
+            .iter()
+            .find(|later| later.path == event.path && later.touch != FileTouch::PartialRead);
+        if let Some(later) = later {
+            let action = match later.touch {
+                FileTouch::Edit => "edit",
+                _ => "read",
+            };
+            superseded.insert(
+                event.tool_use_id.to_string(),
+                format!(
+                    "[{} omitted: superseded by later {} at turn {}]",
+                    event.path.display(),
+                    action,
+                    later.turn
+                ),
+            );
+        }
+    }
+    if superseded.is_empty() {
+        return 0;
+    }
+
+    let mut saved = 0u64;
+    for message in messages.iter_mut() {
+        for block in &mut message.content {
+            let ContentBlock::ToolResult {
+                tool_use_id,
+                content,
+                is_error,
+            } = block
+            else {
+                continue;
+            };
+            if *is_error == Some(true) || content.len() < MIN_PRUNED_RESULT_CHARS {
+                continue;
+            }
+            let Some(stub) = superseded.remove(tool_use_id.as_str()) else {
+                continue;
+            };
+            let before = crate::util::estimate_tokens(content);
+            let after = crate::util::estimate_tokens(&stub);
+            saved += before.saturating_sub(after) as u64;
+            *content = stub;
+        }
+    }
+    saved
+}
+
+impl Agent {
+    /// Apply superseded-read pruning to an outbound request when enabled.
+    /// Returns the estimated tokens saved.
+    pub(super) fn prune_outbound_context(&self, messages: &mut [Message]) -> u64 {
+        if !crate::config::config().compaction.prune_superseded_reads {
+            return 0;
+        }
+        let working_dir = self.session.working_dir.as_deref().map(Path::new);
+        let saved = prune_superseded_reads(messages, working_dir);
+        if saved > 0 {
+            logging::info(&format!(
+                "Pruned superseded file reads from request (~{} tokens)",
+                saved
+            ));
+        }
+        saved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn tool_use(id: &str, name: &str, input: serde_json::Value) -> Message {
+        Message {
+            role: Role::Assistant,
+            content: vec![ContentBlock::ToolUse {
+                id: id.to_string(),
+                name: name.to_string(),
+                input,
+                thought_signature: None,
+            }],
+            timestamp: None,
+            tool_duration_ms: None,
+        }
+    }
+
+    fn tool_result(id: &str, content: &str) -> Message {
+        Message {
+            role: Role::User,
+            content: vec![ContentBlock::ToolResult {
+                tool_use_id: id.to_string(),
+                content: content.to_string(),
+                is_error: None,
+            }],
+            timestamp: None,
+            tool_duration_ms: None,
+        }
+    }
+
+    fn result_text(message: &Message) -> &str {
+        match &message.content[0] {
+            ContentBlock::ToolResult { content, .. } => content,
+            other => panic!("expected tool result, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn stubs_reads_superseded_by_later_edits_and_reads() {
+        let body = "fn main() {}\n".repeat(100);
+        let mut messages = vec![
+            tool_use("r1", "read", json!({ "file_path": "src/main.rs" })),
+            tool_result("r1", &body),
+            tool_use(
+                "e1",
+                "edit",
+                json!({ "file_path": "./src/main.rs", "old_string": "a", "new_string": "b" }),
+            ),
+            tool_result("e1", "Edited src/main.rs"),
+            tool_use("r2", "read", json!({ "file_path": "/repo/src/main.rs" })),
+            tool_result("r2", &body),
+        ];
+
+        let saved = prune_superseded_reads(&mut messages, Some(Path::new("/repo")));
+
+        assert!(saved > 0);
+        assert_eq!(
+            result_text(&messages[1]),
+            "[/repo/src/main.rs omitted: superseded by later edit at turn 2]"
+        );
+        assert_eq!(result_text(&messages[3]), "Edited src/main.rs");
+        assert_eq!(result_text(&messages[5]), body);
+    }
+
+    #[test]
+    fn partial_reads_do_not_supersede_full_reads() {
+        let body = "line\n".repeat(200);
+        let mut messages = vec![
+            tool_use("r1", "read", json!({ "file_path": "notes.md" })),
+            tool_result("r1", &body),
+            tool_use(
+                "r2",
+                "read",
+                json!({ "file_path": "notes.md", "start_line": 1, "end_line": 5 }),
+            ),
+            tool_result("r2", &body),
+        ];
+
+        assert_eq!(prune_superseded_reads(&mut messages, None), 0);
+        assert_eq!(result_text(&messages[1]), body);
+    }
+}
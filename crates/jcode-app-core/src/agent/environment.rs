@@ -49,6 +49,20 @@ impl Agent {
         }
     }
 
+    /// Remember which installed build this top-level session runs on, so
+    /// `jcode rollback --list` can show the sessions a bad build touched.
+    pub(super) fn record_session_build(&self) {
+        if self.session.parent_id.is_some() {
+            return;
+        }
+        let session_id = self.session.id.clone();
+        std::thread::spawn(move || {
+            if let Err(error) = crate::build::record_session_on_running_build(&session_id) {
+                logging::warn(&format!("Failed to record session build: {}", error));
+            }
+        });
+    }
+
     pub(super) fn env_snapshot_detail(&self) -> EnvSnapshotDetail {
         if self.session.visible_conversation_message_count() == 0 {
             EnvSnapshotDetail::Minimal
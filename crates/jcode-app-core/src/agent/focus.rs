@@ -0,0 +1,265 @@
+//! Time-boxed `/focus` turns.
+//!
+//! A focus turn carries a wall-clock budget in its request metadata. At the
+//! halfway mark the agent is soft-interrupted for a short progress summary and
+//! a call on whether continuing is worthwhile. At expiry it gets one more
+//! model call to record progress with the todo tool and summarize; any other
+//! tool call in that reply is skipped and the turn ends with a checkpoint. The
+//! timer lives only on the running turn, so a resumed session never inherits
+//! it.
+
+use super::*;
+use crate::todo::TodoItem;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FocusStage {
+    Working,
+    /// The halfway progress summary has been requested.
+    HalfwayReported,
+    /// The budget ran out and the wrap-up message has been queued.
+    WrappingUp,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct FocusTimer {
+    budget: Duration,
+    started_at: Instant,
+    task: String,
+    stage: FocusStage,
+}
+
+impl FocusTimer {
+    fn new(budget: Duration, task: &str) -> Self {
+        Self {
+            budget,
+            started_at: Instant::now(),
+            task: task.trim().to_string(),
+            stage: FocusStage::Working,
+        }
+    }
+
+    /// The checkpoint that is due after `elapsed`, if any. Each one fires
+    /// once; expiry wins over a halfway mark that was never reported.
+    fn next_checkpoint(&mut self, elapsed: Duration) -> Option<FocusStage> {
+        let due = match self.stage {
+            FocusStage::WrappingUp => None,
+            _ if elapsed >= self.budget => Some(FocusStage::WrappingUp),
+            FocusStage::Working if elapsed >= self.budget / 2 => Some(FocusStage::HalfwayReported),
+            _ => None,
+        }?;
+        self.stage = due;
+        Some(due)
+    }
+}
+
+/// `45s`, `20m` or `1h05m`.
+fn short_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs < 60 {
+        format!("{}s", secs)
+    } else if secs < 3600 {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{}h{:02}m", secs / 3600, secs % 3600 / 60)
+    }
+}
+
+fn is_open_todo(todo: &TodoItem) -> bool {
+    todo.status != "completed" && todo.status != "cancelled"
+}
+
+impl Agent {
+    /// Time-box the next turn to `budget` (`/focus`). Cleared when the turn
+    /// ends.
+    pub fn set_turn_focus(&mut self, budget: Duration, task: &str) {
+        self.turn_focus = Some(FocusTimer::new(budget, task));
+    }
+
+    /// True for tool calls in the wrap-up reply after the focus budget ran
+    /// out. Only todo updates still run.
+    pub(super) fn focus_skips_tool(&self, name: &str) -> bool {
+        self.focus_wrapping_up() && name != "todo"
+    }
+
+    /// True once the focus wrap-up message has been sent; the turn ends after
+    /// the reply to it.
+    pub(super) fn focus_wrapping_up(&self) -> bool {
+        self.turn_focus
+            .as_ref()
+            .is_some_and(|focus| focus.stage == FocusStage::WrappingUp)
+    }
+
+    /// Record a tool call that was not executed because focus time is up.
+    pub(super) fn push_focus_skipped_result(&mut self, tool_use_id: String) {
+        self.add_message(
+            Role::User,
+            vec![ContentBlock::ToolResult {
+                tool_use_id,
+                content: "[Skipped: focus time is up]".to_string(),
+                is_error: Some(true),
+            }],
+        );
+    }
+
+    /// Queue the halfway or expiry soft interrupt when one is due. Call at a
+    /// safe injection point, after all tool results are recorded.
+    pub(super) fn queue_focus_checkpoint(&mut self) {
+        let Some(focus) = self.turn_focus.as_mut() else {
+            return;
+        };
+        let elapsed = focus.started_at.elapsed();
+        let Some(stage) = focus.next_checkpoint(elapsed) else {
+            return;
+        };
+        let budget = short_duration(focus.budget);
+        let elapsed = short_duration(elapsed);
+        let content = match stage {
+            FocusStage::HalfwayReported => format!(
+                "[Focus: {elapsed} of {budget} used. Reply with a concise progress summary and \
+                 whether continuing is worthwhile. If it is, keep working in the same reply; \
+                 replying without tool calls ends the turn.]"
+            ),
+            _ => format!(
+                "[Focus: the {budget} budget is up. Update the todo tool with what is done and \
+                 what is left, then reply with a concise progress summary and whether \
+                 continuing is worthwhile. No other tools will run; the turn ends after this \
+                 reply.]"
+            ),
+        };
+        logging::info(&format!(
+            "FOCUS_CHECKPOINT session={} stage={:?} elapsed={} budget={}",
+            self.session.id, stage, elapsed, budget
+        ));
+        self.queue_soft_interrupt(content, false, SoftInterruptSource::System);
+    }
+
+    /// After the wrap-up reply, save progress: leave an open todo for the
+    /// task if none remain, checkpoint the mission when there is one, record
+    /// the checkpoint in the session and tell clients. No-op otherwise.
+    pub(super) fn finish_focus(&mut self, event_tx: &mpsc::UnboundedSender<ServerEvent>) {
+        let Some(focus) = self
+            .turn_focus
+            .take_if(|focus| focus.stage == FocusStage::WrappingUp)
+        else {
+            return;
+        };
+        let elapsed = focus.started_at.elapsed();
+        let summary = self
+            .last_assistant_text()
+            .map(|text| text.trim().to_string())
+            .filter(|text| !text.is_empty())
+            .unwrap_or_else(|| "No summary was given.".to_string());
+        let open_todos = self.capture_focus_todos(&focus.task);
+        let checkpoint = format!(
+            "Focus checkpoint after {} ({} budget), {} open todo(s): {}",
+            short_duration(elapsed),
+            short_duration(focus.budget),
+            open_todos,
+            summary
+        );
+        if let Err(err) = crate::mission::checkpoint(&self.session.id, &checkpoint) {
+            logging::warn(&format!(
+                "Failed to checkpoint mission for session {}: {}",
+                self.session.id, err
+            ));
+        }
+        self.add_message_with_display_role(
+            Role::User,
+            vec![ContentBlock::Text {
+                text: format!("[{checkpoint}]"),
+                cache_control: None,
+            }],
+            Some(StoredDisplayRole::System),
+        );
+        self.persist_session_best_effort("focus checkpoint");
+        let _ = event_tx.send(ServerEvent::FocusCheckpoint {
+            budget_secs: focus.budget.as_secs(),
+            elapsed_secs: elapsed.as_secs(),
+            open_todos,
+            summary,
+        });
+    }
+
+    /// Open todos after a focus turn. When the agent left none, add one to
+    /// continue the task so the work is not lost.
+    fn capture_focus_todos(&self, task: &str) -> usize {
+        let mut todos = crate::todo::load_todos(&self.session.id).unwrap_or_default();
+        let open = todos.iter().filter(|todo| is_open_todo(todo)).count();
+        if open > 0 || task.is_empty() {
+            return open;
+        }
+        todos.push(TodoItem {
+            content: format!(
+                "Continue focus task: {}",
+                crate::util::truncate_str(task, 200)
+            ),
+            status: "pending".to_string(),
+            priority: "high".to_string(),
+            id: format!("focus-{}", todos.len() + 1),
+            group: None,
+            confidence: None,
+            completion_confidence: None,
+            blocked_by: Vec::new(),
+            assigned_to: None,
+        });
+        let saved = match self.working_dir() {
+            Some(project_dir) => crate::todo::save_session_todos_in_project(
+                std::path::Path::new(project_dir),
+                &self.session.id,
+                &todos,
+            ),
+            None => crate::todo::save_todos(&self.session.id, &todos),
+        };
+        if let Err(err) = saved {
+            logging::warn(&format!(
+                "Failed to save focus todo for session {}: {}",
+                self.session.id, err
+            ));
+            return 0;
+        }
+        Bus::global().publish(BusEvent::TodoUpdated(crate::bus::TodoEvent {
+            session_id: self.session.id.clone(),
+            todos,
+        }));
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checkpoints_fire_once_at_halfway_and_expiry() {
+        let mut timer = FocusTimer::new(Duration::from_secs(20 * 60), "  migrate loader ");
+        assert_eq!(timer.task, "migrate loader");
+        assert_eq!(timer.next_checkpoint(Duration::from_secs(9 * 60)), None);
+        assert_eq!(
+            timer.next_checkpoint(Duration::from_secs(10 * 60)),
+            Some(FocusStage::HalfwayReported)
+        );
+        assert_eq!(timer.next_checkpoint(Duration::from_secs(15 * 60)), None);
+        assert_eq!(
+            timer.next_checkpoint(Duration::from_secs(20 * 60)),
+            Some(FocusStage::WrappingUp)
+        );
+        assert_eq!(timer.next_checkpoint(Duration::from_secs(30 * 60)), None);
+    }
+
+    #[test]
+    fn expiry_skips_an_unreported_halfway_mark() {
+        let mut timer = FocusTimer::new(Duration::from_secs(60), "task");
+        assert_eq!(
+            timer.next_checkpoint(Duration::from_secs(61)),
+            Some(FocusStage::WrappingUp)
+        );
+        assert_eq!(timer.next_checkpoint(Duration::from_secs(62)), None);
+    }
+
+    #[test]
+    fn short_duration_picks_unit() {
+        assert_eq!(short_duration(Duration::from_secs(45)), "45s");
+        assert_eq!(short_duration(Duration::from_secs(20 * 60 + 59)), "20m");
+        assert_eq!(short_duration(Duration::from_secs(3900)), "1h05m");
+    }
+}
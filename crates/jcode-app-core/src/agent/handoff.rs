@@ -0,0 +1,294 @@
+//! `/handoff`: continue in a fresh session that starts from a summary of this
+//! one.
+//!
+//! The background model drafts a structured summary (goals, decisions, open
+//! todos, files touched, gotchas) and the user edits it before anything is
+//! written. The child session keeps the parent's working dir, model and todos,
+//! records the parent in `parent_id`, and starts with the edited summary as its
+//! only conversation context. The parent gets a closing note pointing at the
+//! child.
+
+use super::*;
+
+/// Most recent transcript characters sent to the summarizer.
+const TRANSCRIPT_MAX_CHARS: usize = 48_000;
+
+/// Files listed for the summarizer, most recently touched last.
+const FILES_TOUCHED_MAX: usize = 40;
+
+/// Tools whose calls count as touching a file.
+const EDIT_TOOLS: &[&str] = &["edit", "write", "multiedit", "apply_patch", "patch"];
+
+const SUMMARY_SYSTEM_PROMPT: &str = "You write the handoff note that starts a fresh session \
+continuing an unwieldy coding session. The new session sees nothing but this note. Use exactly \
+these Markdown sections, each a short bullet list (write \"- none\" when empty): \
+## Goal, ## Decisions, ## Open todos, ## Files touched, ## Gotchas. Keep facts the next session \
+needs (paths, commands, names, constraints) and drop dead ends. No preamble.";
+
+/// A handoff summary request, built under the agent lock and run without it.
+pub struct HandoffSummaryRequest {
+    provider: Arc<dyn Provider>,
+    session_id: String,
+    prompt: String,
+}
+
+impl HandoffSummaryRequest {
+    /// Fails when the session has no conversation to hand off yet.
+    pub fn new(provider: Arc<dyn Provider>, session: &Session) -> Result<Self> {
+        let transcript = handoff_transcript(&session.messages);
+        if transcript.trim().is_empty() {
+            anyhow::bail!("nothing to hand off yet");
+        }
+        let mut prompt = format!(
+            "Write the handoff note for this session.\n\nTranscript (oldest first, may be truncated):\n\n{}",
+            transcript
+        );
+        let files = files_touched(&session.messages);
+        if !files.is_empty() {
+            prompt.push_str("\nFiles changed by tool calls:\n");
+            for file in files {
+                prompt.push_str(&format!("- {}\n", file));
+            }
+        }
+        let todos: Vec<_> = crate::todo::load_todos(&session.id)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|todo| todo.status != "completed" && todo.status != "cancelled")
+            .collect();
+        if !todos.is_empty() {
+            prompt.push_str("\nOpen todos:\n");
+            for todo in todos {
+                prompt.push_str(&format!("- [{}] {}\n", todo.status, todo.content));
+            }
+        }
+        Ok(Self {
+            provider,
+            session_id: session.id.clone(),
+            prompt,
+        })
+    }
+
+    pub async fn run(self) -> Result<String> {
+        let summary = crate::background_model::complete_simple(
+            self.provider.as_ref(),
+            &crate::usage::UsageOrigin::Handoff,
+            Some(&self.session_id),
+            &self.prompt,
+            SUMMARY_SYSTEM_PROMPT,
+        )
+        .await?;
+        let summary = summary.trim();
+        if summary.is_empty() {
+            anyhow::bail!("the model returned an empty summary");
+        }
+        Ok(summary.to_string())
+    }
+}
+
+/// Create and save the child session for an edited handoff `summary`. The
+/// parent is not modified.
+pub fn create_handoff_session(parent: &Session, summary: &str) -> Result<Session> {
+    let summary = summary.trim();
+    if summary.is_empty() {
+        anyhow::bail!("the handoff summary is empty");
+    }
+    let mut child = Session::create(Some(parent.id.clone()), None);
+    child.messages.clear();
+    child.ensure_initial_session_context_message();
+    child.working_dir = parent.working_dir.clone();
+    child.workspace_roots = parent.workspace_roots.clone();
+    child.tool_scope = parent.tool_scope.clone();
+    child.instructions = parent.instructions.clone();
+    child.refresh_initial_session_context_message();
+    child.model = parent.model.clone();
+    child.provider_key = parent.provider_key.clone();
+    child.route_api_method = parent.route_api_method.clone();
+    child.model_route = parent.model_route.clone();
+    child.subagent_model = parent.subagent_model.clone();
+    child.autoreview_enabled = parent.autoreview_enabled;
+    child.autojudge_enabled = parent.autojudge_enabled;
+    child.status = crate::session::SessionStatus::Closed;
+    child.append_handoff_context(&parent.id, parent.display_name(), summary);
+    child.save()?;
+    let todos = crate::todo::load_todos(&parent.id).unwrap_or_default();
+    crate::todo::save_todos(&child.id, &todos)?;
+    Ok(child)
+}
+
+impl Agent {
+    pub fn prepare_handoff_summary(&self) -> Result<HandoffSummaryRequest> {
+        HandoffSummaryRequest::new(self.provider.fork(), &self.session)
+    }
+
+    /// Create the child session and leave a closing note in this one.
+    /// Returns the child's id and display name.
+    pub fn create_handoff(&mut self, summary: &str) -> Result<(String, String)> {
+        let child = create_handoff_session(&self.session, summary)?;
+        let text = crate::session::handoff_notice_text(&child.id, child.display_name());
+        self.add_message_with_display_role(
+            Role::User,
+            vec![ContentBlock::Text {
+                text,
+                cache_control: None,
+            }],
+            Some(StoredDisplayRole::System),
+        );
+        self.session.save()?;
+        Ok((child.id.clone(), child.display_name().to_string()))
+    }
+}
+
+/// Plain-text transcript for the summarizer, newest turns kept when it is too
+/// long. Tool calls are listed by name with the file they touched, if any.
+fn handoff_transcript(messages: &[StoredMessage]) -> String {
+    let mut transcript = String::new();
+    for message in messages {
+        if message.display_role.is_some() {
+            continue;
+        }
+        let label = match message.role {
+            Role::User => "User",
+            Role::Assistant => "Assistant",
+        };
+        for block in &message.content {
+            match block {
+                ContentBlock::Text { text, .. } if !text.trim().is_empty() => {
+                    transcript.push_str(&format!("{}: {}\n\n", label, text.trim()));
+                }
+                ContentBlock::ToolUse { name, input, .. } => {
+                    match input.get("file_path").and_then(|path| path.as_str()) {
+                        Some(path) => {
+                            transcript.push_str(&format!("{}: [{} {}]\n\n", label, name, path))
+                        }
+                        None => transcript.push_str(&format!("{}: [used {}]\n\n", label, name)),
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    if transcript.len() > TRANSCRIPT_MAX_CHARS {
+        let cut = transcript.len() - TRANSCRIPT_MAX_CHARS;
+        let start = transcript
+            .char_indices()
+            .map(|(index, _)| index)
+            .find(|index| *index >= cut)
+            .unwrap_or(transcript.len());
+        transcript.drain(..start);
+    }
+    transcript
+}
+
+/// Files changed by edit tools, in the order they were last touched.
+fn files_touched(messages: &[StoredMessage]) -> Vec<String> {
+    let mut files: Vec<String> = Vec::new();
+    let mut touch = |path: &str| {
+        let path = path.trim();
+        if path.is_empty() || path == "/dev/null" {
+            return;
+        }
+        files.retain(|file| file != path);
+        files.push(path.to_string());
+    };
+    for message in messages {
+        for block in &message.content {
+            let ContentBlock::ToolUse { name, input, .. } = block else {
+                continue;
+            };
+            if !EDIT_TOOLS.contains(&name.as_str()) {
+                continue;
+            }
+            if let Some(path) = input.get("file_path").and_then(|path| path.as_str()) {
+                touch(path);
+            }
+            let patch = input.get("patch_text").and_then(|patch| patch.as_str());
+            for line in patch.into_iter().flat_map(str::lines) {
+                let path = [
+                    "*** Add File: ",
+                    "*** Update File: ",
+                    "*** Delete File: ",
+                    "*** Move to: ",
+                    "+++ b/",
+                ]
+                .iter()
+                .find_map(|prefix| line.strip_prefix(prefix));
+                if let Some(path) = path {
+                    touch(path);
+                }
+            }
+        }
+    }
+    let skip = files.len().saturating_sub(FILES_TOUCHED_MAX);
+    files.split_off(skip)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool_use(name: &str, input: serde_json::Value) -> StoredMessage {
+        let mut session = Session::create(None, None);
+        session.messages.clear();
+        session.add_message(
+            Role::Assistant,
+            vec![ContentBlock::ToolUse {
+                id: "call_1".to_string(),
+                name: name.to_string(),
+                input,
+            }],
+        );
+        session.messages.remove(0)
+    }
+
+    #[test]
+    fn files_touched_reads_paths_and_patches_in_last_touched_order() {
+        let messages = vec![
+            tool_use("edit", serde_json::json!({ "file_path": "src/a.rs" })),
+            tool_use("read", serde_json::json!({ "file_path": "src/ignored.rs" })),
+            tool_use(
+                "apply_patch",
+                serde_json::json!({
+                    "patch_text": "*** Begin Patch\n*** Update File: src/b.rs\n@@\n-x\n+y\n*** Add File: src/c.rs\n+z\n*** End Patch"
+                }),
+            ),
+            tool_use(
+                "patch",
+                serde_json::json!({ "patch_text": "--- a/src/d.rs\n+++ b/src/d.rs\n@@ -1 +1 @@\n-x\n+y\n" }),
+            ),
+            tool_use("write", serde_json::json!({ "file_path": "src/a.rs" })),
+        ];
+        assert_eq!(
+            files_touched(&messages),
+            vec!["src/b.rs", "src/c.rs", "src/d.rs", "src/a.rs"]
+        );
+    }
+
+    #[test]
+    fn handoff_session_starts_from_the_summary_and_links_the_parent() {
+        let _guard = crate::storage::lock_test_env();
+        let prev_home = std::env::var_os("JCODE_HOME");
+        let temp_home = tempfile::TempDir::new().expect("temp home");
+        crate::env::set_var("JCODE_HOME", temp_home.path());
+
+        let mut parent = Session::create(None, Some("Parent".to_string()));
+        parent.working_dir = Some("/tmp/project".to_string());
+        parent.model = Some("claude-opus-4-6".to_string());
+        let child = create_handoff_session(&parent, "  ## Goal\n- ship it  ");
+
+        if let Some(previous) = prev_home {
+            crate::env::set_var("JCODE_HOME", previous);
+        } else {
+            crate::env::remove_var("JCODE_HOME");
+        }
+        let child = child.expect("child session");
+        assert_eq!(child.parent_id.as_deref(), Some(parent.id.as_str()));
+        assert_eq!(child.working_dir.as_deref(), Some("/tmp/project"));
+        assert_eq!(child.model.as_deref(), Some("claude-opus-4-6"));
+        let context = child.messages.last().expect("handoff context");
+        assert_eq!(context.display_role, Some(StoredDisplayRole::System));
+        let text = context.content_preview();
+        assert!(text.contains(&parent.id));
+        assert!(text.ends_with("## Goal\n- ship it"));
+        assert!(create_handoff_session(&parent, "   ").is_err());
+    }
+}
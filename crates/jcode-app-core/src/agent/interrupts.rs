@@ -1,6 +1,7 @@
 use super::Agent;
+use crate::bus::{Bus, BusEvent, TurnQueueChanged};
 use crate::logging;
-use crate::message::{ContentBlock, Role};
+use crate::message::{ContentBlock, Role, ToolCall};
 use crate::protocol::ServerEvent;
 use crate::session::StoredDisplayRole;
 use anyhow::Result;
@@ -124,11 +125,7 @@ impl Agent {
         let content_chars = content.chars().count();
         if let Ok(mut queue) = self.soft_interrupt_queue.lock() {
             let pending_before = queue.len();
-            queue.push(SoftInterruptMessage {
-                content,
-                urgent,
-                source,
-            });
+            queue.push(SoftInterruptMessage::new(content, urgent, source));
             logging::info(&format!(
                 "AGENT_SOFT_INTERRUPT_QUEUE_PUSH session={} source={:?} urgent={} content_bytes={} content_chars={} pending_before={} pending_after={}",
                 self.session_id(),
@@ -139,6 +136,8 @@ impl Agent {
                 pending_before,
                 queue.len()
             ));
+            drop(queue);
+            self.publish_turn_queue_changed();
         } else {
             logging::warn(&format!(
                 "AGENT_SOFT_INTERRUPT_QUEUE_PUSH_FAILED session={} source={:?} urgent={} content_bytes={} content_chars={} reason=queue_lock_poisoned",
@@ -151,6 +150,13 @@ impl Agent {
         }
     }
 
+    /// Tell subscribed clients that the session's pending turns changed.
+    fn publish_turn_queue_changed(&self) {
+        Bus::global().publish(BusEvent::TurnQueueChanged(TurnQueueChanged {
+            session_id: self.session_id().to_string(),
+        }));
+    }
+
     /// Get a handle to the soft interrupt queue.
     /// The server can use this to queue interrupts without holding the agent lock.
     pub fn soft_interrupt_queue(&self) -> SoftInterruptQueue {
@@ -228,6 +234,7 @@ impl Agent {
             "last_upstream_provider": self.last_upstream_provider,
             "last_connection_type": self.last_connection_type,
             "active_skill": self.active_skill,
+            "auto_skills": self.auto_skill_names(),
             "allowed_tools": self.allowed_tools,
             "disabled_tools": self.disabled_tools,
             "session": {
@@ -344,6 +351,7 @@ impl Agent {
             ));
             queue.drain(..).collect()
         };
+        self.publish_turn_queue_changed();
 
         let mut injected = Vec::new();
         let mut current_source: Option<SoftInterruptSource> = None;
@@ -385,6 +393,7 @@ impl Agent {
             flush_group(self, &mut injected, source, &mut current_parts);
         }
 
+        self.record_finished_auto_debug();
         self.persist_session_best_effort("soft interrupt injection");
         logging::info(&format!(
             "AGENT_SOFT_INTERRUPT_INJECT_COMMIT session={} groups={} total_content_bytes={}",
@@ -429,6 +438,47 @@ impl Agent {
         }
     }
 
+    /// Injection point C, checked before every tool after the first: an
+    /// urgent interrupt skips the rest of the batch once the in-flight tool
+    /// has finished. Each skipped tool still gets a result so the history
+    /// stays valid, and the user's text goes in last so it is what the model
+    /// answers next. Returns `None` when nothing urgent is queued.
+    pub(super) fn take_urgent_soft_interrupt(
+        &mut self,
+        skipped: &[ToolCall],
+    ) -> Option<Vec<ServerEvent>> {
+        if skipped.is_empty() || !self.has_urgent_interrupt() {
+            return None;
+        }
+        crate::telemetry::record_user_cancelled();
+        for tool_call in skipped {
+            self.add_message(
+                Role::User,
+                vec![ContentBlock::ToolResult {
+                    tool_use_id: tool_call.id.clone(),
+                    content: "[Skipped: user interrupted]".to_string(),
+                    is_error: Some(true),
+                }],
+            );
+        }
+        self.add_message(
+            Role::User,
+            vec![ContentBlock::Text {
+                text: format!(
+                    "[User interrupted: {} remaining tool(s) skipped]",
+                    skipped.len()
+                ),
+                cache_control: None,
+            }],
+        );
+        let injected = self.inject_soft_interrupts();
+        Some(Self::build_soft_interrupt_events(
+            injected,
+            "C",
+            Some(skipped.len()),
+        ))
+    }
+
     pub(super) fn build_soft_interrupt_events(
         injected: Vec<InjectedSoftInterrupt>,
         point: &'static str,
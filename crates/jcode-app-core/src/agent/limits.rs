@@ -0,0 +1,356 @@
+//! Agent limits from `[agent]` config, which the active profile may override.
+//! `max_turns` and `max_wall_seconds` cover the whole request;
+//! `max_tool_calls_per_turn` covers the tool calls of a single model reply.
+//!
+//! On the first breach the agent is soft-interrupted with a wrap-up message and
+//! gets one more model call; tool calls in that reply are not executed. The
+//! request then stops with an error that is also recorded in the session.
+
+use super::*;
+use crate::config::AgentLimitsConfig;
+use crate::protocol::AgentLimitStatus;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(super) struct AgentLimits {
+    max_turns: Option<u32>,
+    max_tool_calls: Option<u32>,
+    max_wall: Option<Duration>,
+}
+
+impl AgentLimits {
+    fn from_config(config: &AgentLimitsConfig, max_turns_override: Option<u32>) -> Self {
+        Self {
+            max_turns: max_turns_override.or(config.max_turns).filter(|n| *n > 0),
+            max_tool_calls: config.max_tool_calls_per_turn.filter(|n| *n > 0),
+            max_wall: config
+                .max_wall_seconds
+                .filter(|secs| *secs > 0)
+                .map(Duration::from_secs),
+        }
+    }
+
+    fn is_unlimited(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LimitBreach {
+    Turns,
+    ToolCalls,
+    WallClock,
+}
+
+impl LimitBreach {
+    fn config_key(self) -> &'static str {
+        match self {
+            Self::Turns => "max_turns",
+            Self::ToolCalls => "max_tool_calls_per_turn",
+            Self::WallClock => "max_wall_seconds",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct TurnLimitTracker {
+    limits: AgentLimits,
+    started_at: Instant,
+    turns: u32,
+    /// Tool calls executed in the whole request, for reporting.
+    tool_calls: u32,
+    /// Tool calls executed from the current model reply.
+    turn_tool_calls: u32,
+    /// Set once the wrap-up message has been queued.
+    wrap_up: Option<LimitBreach>,
+}
+
+impl Default for TurnLimitTracker {
+    fn default() -> Self {
+        Self::new(AgentLimits::default())
+    }
+}
+
+impl TurnLimitTracker {
+    fn new(limits: AgentLimits) -> Self {
+        Self {
+            limits,
+            started_at: Instant::now(),
+            turns: 0,
+            tool_calls: 0,
+            turn_tool_calls: 0,
+            wrap_up: None,
+        }
+    }
+
+    fn breach(&self) -> Option<LimitBreach> {
+        let limits = &self.limits;
+        if limits.max_turns.is_some_and(|max| self.turns >= max) {
+            Some(LimitBreach::Turns)
+        } else if limits
+            .max_tool_calls
+            .is_some_and(|max| self.turn_tool_calls >= max)
+        {
+            Some(LimitBreach::ToolCalls)
+        } else if limits
+            .max_wall
+            .is_some_and(|max| self.started_at.elapsed() >= max)
+        {
+            Some(LimitBreach::WallClock)
+        } else {
+            None
+        }
+    }
+
+    fn start_turn(&mut self) {
+        self.turns += 1;
+        self.turn_tool_calls = 0;
+    }
+
+    fn record_tool_call(&mut self) {
+        self.tool_calls += 1;
+        self.turn_tool_calls += 1;
+    }
+
+    fn describe(&self, breach: LimitBreach) -> String {
+        match breach {
+            LimitBreach::Turns => format!(
+                "max_turns ({}) reached",
+                self.limits.max_turns.unwrap_or_default()
+            ),
+            LimitBreach::ToolCalls => format!(
+                "max_tool_calls_per_turn ({}) reached",
+                self.limits.max_tool_calls.unwrap_or_default()
+            ),
+            LimitBreach::WallClock => format!(
+                "max_wall_seconds ({}) reached",
+                self.limits.max_wall.unwrap_or_default().as_secs()
+            ),
+        }
+    }
+
+    fn status(&self) -> AgentLimitStatus {
+        AgentLimitStatus {
+            turns: self.turns,
+            tool_calls: self.tool_calls,
+            elapsed_secs: self.started_at.elapsed().as_secs(),
+            max_turns: self.limits.max_turns,
+            max_tool_calls_per_turn: self.limits.max_tool_calls,
+            max_wall_seconds: self.limits.max_wall.map(|max| max.as_secs()),
+            breached: self.wrap_up.map(|breach| breach.config_key().to_string()),
+        }
+    }
+}
+
+impl Agent {
+    /// Override `[agent] max_turns` for this agent (`jcode run --max-turns`).
+    pub fn set_max_turns(&mut self, max_turns: Option<u32>) {
+        self.max_turns_override = max_turns;
+    }
+
+    /// Limit usage of the current (or most recent) request, or `None` when no
+    /// limit is configured.
+    pub fn agent_limit_status(&self) -> Option<AgentLimitStatus> {
+        (!self.turn_limits.limits.is_unlimited()).then(|| self.turn_limits.status())
+    }
+
+    /// Reset the counters at the start of a request.
+    pub(super) fn begin_agent_limits(&mut self) {
+        self.turn_limits = TurnLimitTracker::new(AgentLimits::from_config(
+            &self.agent_limits_config(),
+            self.max_turns_override,
+        ));
+    }
+
+    /// Count a model call; the per-turn tool call budget starts over.
+    pub(super) fn note_agent_limit_turn(&mut self) {
+        self.turn_limits.start_turn();
+    }
+
+    pub(super) fn note_agent_limit_tool_call(&mut self) {
+        self.turn_limits.record_tool_call();
+    }
+
+    /// True when no more tool calls may run from the current model reply.
+    pub(super) fn agent_tool_budget_exhausted(&self) -> bool {
+        self.turn_limits.wrap_up.is_some()
+            || self
+                .turn_limits
+                .limits
+                .max_tool_calls
+                .is_some_and(|max| self.turn_limits.turn_tool_calls >= max)
+    }
+
+    /// Record a tool call that was not executed because of a limit.
+    pub(super) fn push_agent_limit_skipped_result(&mut self, tool_use_id: String) {
+        self.add_message(
+            Role::User,
+            vec![ContentBlock::ToolResult {
+                tool_use_id,
+                content: "[Skipped: agent limit reached]".to_string(),
+                is_error: Some(true),
+            }],
+        );
+    }
+
+    /// Queue the wrap-up soft interrupt the first time a limit is breached.
+    /// Call at a safe injection point, after all tool results are recorded.
+    pub(super) fn queue_agent_limit_wrap_up(&mut self) {
+        if self.turn_limits.wrap_up.is_some() {
+            return;
+        }
+        let Some(breach) = self.turn_limits.breach() else {
+            return;
+        };
+        self.turn_limits.wrap_up = Some(breach);
+        let reason = self.turn_limits.describe(breach);
+        logging::warn(&format!(
+            "AGENT_LIMIT_WRAP_UP session={} {} turns={} tool_calls={} elapsed_secs={}",
+            self.session.id,
+            reason,
+            self.turn_limits.turns,
+            self.turn_limits.tool_calls,
+            self.turn_limits.started_at.elapsed().as_secs()
+        ));
+        self.queue_soft_interrupt(
+            format!(
+                "[Agent limit: {reason}. Do not call any more tools. Reply once with a short \
+                 summary of what you finished, what is left, and how to continue.]"
+            ),
+            false,
+            SoftInterruptSource::System,
+        );
+    }
+
+    /// Once the wrap-up reply is in, stop the request. Tool calls from that
+    /// reply get skipped results so the history stays valid.
+    pub(super) fn agent_limit_stop(&mut self, pending: &[ToolCall]) -> Option<anyhow::Error> {
+        let breach = self.turn_limits.wrap_up?;
+        for tc in pending {
+            self.push_agent_limit_skipped_result(tc.id.clone());
+        }
+        let status = self.turn_limits.status();
+        let message = format!(
+            "Agent stopped: {} after {} turn(s), {} tool call(s), {}s.",
+            self.turn_limits.describe(breach),
+            status.turns,
+            status.tool_calls,
+            status.elapsed_secs
+        );
+        logging::warn(&format!(
+            "AGENT_LIMIT_STOP session={} {}",
+            self.session.id, message
+        ));
+        self.add_message_with_display_role(
+            Role::User,
+            vec![ContentBlock::Text {
+                text: format!("[{message}]"),
+                cache_control: None,
+            }],
+            Some(StoredDisplayRole::System),
+        );
+        self.persist_session_best_effort("agent limit stop");
+        Some(anyhow::anyhow!(message))
+    }
+
+    /// Send the limit status to clients ahead of `Done`, when limits are set.
+    pub(super) fn send_agent_limit_status(&self, event_tx: &mpsc::UnboundedSender<ServerEvent>) {
+        if let Some(status) = self.agent_limit_status() {
+            let _ = event_tx.send(ServerEvent::AgentLimits { status });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracker(max_turns: Option<u32>, max_tool_calls: Option<u32>) -> TurnLimitTracker {
+        TurnLimitTracker::new(AgentLimits::from_config(
+            &AgentLimitsConfig {
+                max_turns,
+                max_tool_calls_per_turn: max_tool_calls,
+                max_wall_seconds: None,
+                ..AgentLimitsConfig::default()
+            },
+            None,
+        ))
+    }
+
+    #[test]
+    fn zero_limits_are_treated_as_unset() {
+        let limits = AgentLimits::from_config(
+            &AgentLimitsConfig {
+                max_turns: Some(0),
+                max_tool_calls_per_turn: Some(0),
+                max_wall_seconds: Some(0),
+                max_parallel_subagents: None,
+            },
+            None,
+        );
+        assert!(limits.is_unlimited());
+    }
+
+    #[test]
+    fn cli_override_replaces_configured_max_turns() {
+        let config = AgentLimitsConfig {
+            max_turns: Some(50),
+            ..AgentLimitsConfig::default()
+        };
+        assert_eq!(
+            AgentLimits::from_config(&config, Some(5)).max_turns,
+            Some(5)
+        );
+        assert_eq!(AgentLimits::from_config(&config, None).max_turns, Some(50));
+    }
+
+    #[test]
+    fn breach_reports_first_limit_reached() {
+        let mut tracker = tracker(Some(3), Some(10));
+        tracker.turns = 2;
+        tracker.turn_tool_calls = 9;
+        assert_eq!(tracker.breach(), None);
+
+        tracker.turn_tool_calls = 10;
+        assert_eq!(tracker.breach(), Some(LimitBreach::ToolCalls));
+
+        tracker.turns = 3;
+        assert_eq!(tracker.breach(), Some(LimitBreach::Turns));
+        assert_eq!(
+            tracker.describe(LimitBreach::Turns),
+            "max_turns (3) reached"
+        );
+    }
+
+    #[test]
+    fn tool_call_limit_applies_to_each_turn() {
+        let mut tracker = tracker(None, Some(3));
+        for _ in 0..3 {
+            tracker.start_turn();
+            tracker.record_tool_call();
+            tracker.record_tool_call();
+            assert_eq!(tracker.breach(), None);
+        }
+        assert_eq!(tracker.tool_calls, 6);
+
+        tracker.start_turn();
+        for _ in 0..3 {
+            tracker.record_tool_call();
+        }
+        assert_eq!(tracker.breach(), Some(LimitBreach::ToolCalls));
+        assert_eq!(tracker.status().tool_calls, 9);
+    }
+
+    #[test]
+    fn status_reports_usage_and_breach() {
+        let mut tracker = tracker(Some(3), None);
+        tracker.turns = 3;
+        tracker.tool_calls = 7;
+        tracker.wrap_up = tracker.breach();
+        let status = tracker.status();
+        assert_eq!(status.turns, 3);
+        assert_eq!(status.tool_calls, 7);
+        assert_eq!(status.max_turns, Some(3));
+        assert_eq!(status.max_tool_calls_per_turn, None);
+        assert_eq!(status.breached.as_deref(), Some("max_turns"));
+    }
+}
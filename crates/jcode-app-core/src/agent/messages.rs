@@ -1,7 +1,8 @@
 use super::*;
 
 impl Agent {
-    pub(crate) fn add_message(&mut self, role: Role, content: Vec<ContentBlock>) -> String {
+    pub(crate) fn add_message(&mut self, role: Role, mut content: Vec<ContentBlock>) -> String {
+        self.redact_outbound(&role, &mut content);
         let id = self.session.add_message(role, content);
         let compaction = self.registry.compaction();
         if let Ok(mut manager) = compaction.try_write() {
@@ -17,9 +18,10 @@ impl Agent {
     pub(crate) fn add_message_with_display_role(
         &mut self,
         role: Role,
-        content: Vec<ContentBlock>,
+        mut content: Vec<ContentBlock>,
         display_role: Option<StoredDisplayRole>,
     ) -> String {
+        self.redact_outbound(&role, &mut content);
         let id = self
             .session
             .add_message_with_display_role(role, content, display_role);
@@ -37,9 +39,10 @@ impl Agent {
     pub(crate) fn add_message_with_duration(
         &mut self,
         role: Role,
-        content: Vec<ContentBlock>,
+        mut content: Vec<ContentBlock>,
         duration_ms: Option<u64>,
     ) -> String {
+        self.redact_outbound(&role, &mut content);
         let id = self
             .session
             .add_message_with_duration(role, content, duration_ms);
@@ -57,10 +60,11 @@ impl Agent {
     pub(crate) fn add_message_ext(
         &mut self,
         role: Role,
-        content: Vec<ContentBlock>,
+        mut content: Vec<ContentBlock>,
         duration_ms: Option<u64>,
         token_usage: Option<crate::session::StoredTokenUsage>,
     ) -> String {
+        self.redact_outbound(&role, &mut content);
         let id = self
             .session
             .add_message_ext(role, content, duration_ms, token_usage);
@@ -0,0 +1,269 @@
+//! Failure post-mortems (`[autodebug] post_mortems`).
+//!
+//! When a turn ends in an error (provider failure, tool crash), a background
+//! sidecar call reads the request that started the turn, what the agent did
+//! and the error, and writes three lines: what was being attempted, what
+//! failed, and a suggested next step. The result is added to the session as a
+//! system note, so the resume history shows it and the next turn starts with
+//! that context instead of a bare error string. User cancellations end the
+//! turn without an error and never get one.
+
+use super::*;
+use crate::sidecar::Sidecar;
+
+const REQUEST_MAX_BYTES: usize = 2_000;
+const STEP_MAX_BYTES: usize = 400;
+/// Most recent steps of the failed turn sent to the analysis.
+const MAX_STEPS: usize = 12;
+const ERROR_MAX_BYTES: usize = 1_500;
+const LINE_MAX_BYTES: usize = 300;
+
+/// Prefix of the session note, also how the model recognizes it.
+pub const POST_MORTEM_NOTE_PREFIX: &str = "[Post-mortem of the failed turn]";
+
+const POST_MORTEM_SYSTEM_PROMPT: &str = "You write the post-mortem of a coding agent turn that \
+ended in an error. Reply with exactly three lines, one sentence each: \"Attempted: <what the \
+agent was trying to do>\", \"Failed: <the concrete cause>\", \"Next: <the step most likely to \
+get past it>\". No preamble.";
+
+/// A post-mortem analysis, built under the agent lock and run without it.
+pub struct PostMortemRequest {
+    session_id: String,
+    /// Session length when the turn failed; the note is dropped if the
+    /// conversation moved on before the analysis finished.
+    messages_len: usize,
+    prompt: String,
+}
+
+impl PostMortemRequest {
+    /// `None` when the session has no user request to explain.
+    fn new(session: &Session, error: &str) -> Option<Self> {
+        let start = session.messages.iter().rposition(is_user_request)?;
+        let request = message_text(&session.messages[start]);
+        let mut prompt = format!(
+            "The user asked:\n{}\n",
+            crate::util::truncate_str(request.trim(), REQUEST_MAX_BYTES)
+        );
+        let steps = turn_steps(&session.messages[start + 1..]);
+        if !steps.is_empty() {
+            prompt.push_str("\nWhat the agent did (oldest first, may be truncated):\n");
+            let skip = steps.len().saturating_sub(MAX_STEPS);
+            for step in &steps[skip..] {
+                prompt.push_str(&format!("- {}\n", step));
+            }
+        }
+        prompt.push_str(&format!(
+            "\nThe turn ended with this error:\n{}\n",
+            crate::util::truncate_str(error.trim(), ERROR_MAX_BYTES)
+        ));
+        Some(Self {
+            session_id: session.id.clone(),
+            messages_len: session.messages.len(),
+            prompt,
+        })
+    }
+
+    /// Run the analysis; `None` when the sidecar call fails or says nothing.
+    pub async fn run(self) -> Option<(Self, String)> {
+        let result = Sidecar::new()
+            .with_usage_origin(crate::usage::UsageOrigin::AutoDebug)
+            .for_session(self.session_id.clone())
+            .complete(POST_MORTEM_SYSTEM_PROMPT, &self.prompt)
+            .await;
+        match result {
+            Ok(text) => clean_post_mortem(&text).map(|text| (self, text)),
+            Err(err) => {
+                logging::warn(&format!(
+                    "POST_MORTEM_FAILED session={} error={}",
+                    self.session_id, err
+                ));
+                None
+            }
+        }
+    }
+}
+
+impl Agent {
+    /// Prepare a post-mortem of the turn that just failed with `error`, unless
+    /// `[autodebug] post_mortems` is off.
+    pub fn prepare_post_mortem(&self, error: &str) -> Option<PostMortemRequest> {
+        if !crate::config::config().autodebug.post_mortems {
+            return None;
+        }
+        PostMortemRequest::new(&self.session, error)
+    }
+
+    /// Attach a finished post-mortem to the session as a system note. Returns
+    /// the note, or `None` when the session changed since the failure.
+    pub fn record_post_mortem(
+        &mut self,
+        request: &PostMortemRequest,
+        post_mortem: &str,
+    ) -> Result<Option<String>> {
+        if self.session.id != request.session_id
+            || self.session.messages.len() != request.messages_len
+        {
+            logging::info(&format!(
+                "POST_MORTEM_SKIPPED session={} reason=session_moved_on",
+                request.session_id
+            ));
+            return Ok(None);
+        }
+        let note = format!("{}\n{}", POST_MORTEM_NOTE_PREFIX, post_mortem);
+        self.add_message_with_display_role(
+            Role::User,
+            vec![ContentBlock::Text {
+                text: note.clone(),
+                cache_control: None,
+            }],
+            Some(StoredDisplayRole::System),
+        );
+        self.session.save()?;
+        Ok(Some(note))
+    }
+}
+
+/// A message the user typed, as opposed to tool results and system notes.
+fn is_user_request(message: &StoredMessage) -> bool {
+    message.role == Role::User
+        && message.display_role.is_none()
+        && message.content.iter().any(
+            |block| matches!(block, ContentBlock::Text { text, .. } if !text.trim().is_empty()),
+        )
+}
+
+fn message_text(message: &StoredMessage) -> String {
+    message
+        .content
+        .iter()
+        .filter_map(|block| match block {
+            ContentBlock::Text { text, .. } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// One line per assistant message text, tool call and failed tool result.
+fn turn_steps(messages: &[StoredMessage]) -> Vec<String> {
+    let mut steps = Vec::new();
+    let mut tool_names: HashMap<&str, &str> = HashMap::new();
+    for message in messages {
+        for block in &message.content {
+            let step = match block {
+                ContentBlock::Text { text, .. }
+                    if message.role == Role::Assistant && !text.trim().is_empty() =>
+                {
+                    format!("Said: {}", one_line(text))
+                }
+                ContentBlock::ToolUse {
+                    id, name, input, ..
+                } => {
+                    tool_names.insert(id.as_str(), name.as_str());
+                    let input = serde_json::to_string(input).unwrap_or_default();
+                    format!("Called {} {}", name, one_line(&input))
+                }
+                ContentBlock::ToolResult {
+                    tool_use_id,
+                    content,
+                    is_error: Some(true),
+                } => format!(
+                    "{} failed: {}",
+                    tool_names.get(tool_use_id.as_str()).unwrap_or(&"tool"),
+                    one_line(content)
+                ),
+                _ => continue,
+            };
+            steps.push(step);
+        }
+    }
+    steps
+}
+
+fn one_line(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    crate::util::truncate_str(&text, STEP_MAX_BYTES).to_string()
+}
+
+/// First three non-empty lines, each kept short.
+fn clean_post_mortem(text: &str) -> Option<String> {
+    let lines: Vec<String> = text
+        .lines()
+        .map(|line| line.trim().trim_start_matches(['-', '*']).trim())
+        .filter(|line| !line.is_empty())
+        .take(3)
+        .map(|line| crate::util::truncate_str(line, LINE_MAX_BYTES).to_string())
+        .collect();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn prompt_covers_request_steps_and_error() {
+        let mut session = Session::create(None, None);
+        session.add_message(
+            Role::User,
+            vec![ContentBlock::Text {
+                text: "Bump the parser to v2".to_string(),
+                cache_control: None,
+            }],
+        );
+        session.add_message(
+            Role::Assistant,
+            vec![ContentBlock::ToolUse {
+                id: "call_1".to_string(),
+                name: "bash".to_string(),
+                input: json!({ "command": "cargo update -p parser" }),
+                thought_signature: None,
+            }],
+        );
+        session.add_message(
+            Role::User,
+            vec![ContentBlock::ToolResult {
+                tool_use_id: "call_1".to_string(),
+                content: "error: no matching package\n  named `parser`".to_string(),
+                is_error: Some(true),
+            }],
+        );
+
+        let request =
+            PostMortemRequest::new(&session, "stream error: 529 overloaded").expect("request");
+        assert!(
+            request
+                .prompt
+                .starts_with("The user asked:\nBump the parser to v2\n")
+        );
+        assert!(
+            request
+                .prompt
+                .contains("- Called bash {\"command\":\"cargo update -p parser\"}")
+        );
+        assert!(
+            request
+                .prompt
+                .contains("- bash failed: error: no matching package named `parser`")
+        );
+        assert!(request.prompt.ends_with("stream error: 529 overloaded\n"));
+        assert_eq!(request.messages_len, session.messages.len());
+    }
+
+    #[test]
+    fn no_request_without_a_user_message() {
+        let mut session = Session::create(None, None);
+        session.messages.clear();
+        assert!(PostMortemRequest::new(&session, "boom").is_none());
+    }
+
+    #[test]
+    fn post_mortem_keeps_three_lines() {
+        assert_eq!(
+            clean_post_mortem("\n- Attempted: a\n\nFailed: b\nNext: c\nExtra: d\n").as_deref(),
+            Some("Attempted: a\nFailed: b\nNext: c")
+        );
+        assert_eq!(clean_post_mortem("  \n"), None);
+    }
+}
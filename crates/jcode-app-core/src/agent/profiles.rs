@@ -0,0 +1,177 @@
+//! Named agent profiles from `[profiles.<name>]` config.
+//!
+//! Selecting a profile switches the model, layers its tool settings over the
+//! agent's own tool policy, appends its instruction file to the system prompt,
+//! sets plan mode from `approval`, and overrides `[agent]` limits. The name is
+//! stored in the session; resuming the session re-applies everything except
+//! the model and plan mode, which the session already records.
+
+use super::*;
+use crate::config::{AgentLimitsConfig, AgentProfileConfig};
+use anyhow::Context;
+use std::path::Path;
+
+/// Largest `system_prompt_file` accepted for a profile.
+const MAX_PROFILE_PROMPT_BYTES: u64 = 64 * 1024;
+
+#[derive(Debug, Clone)]
+pub(super) struct ActiveProfile {
+    name: String,
+    config: AgentProfileConfig,
+    system_prompt: Option<String>,
+    /// Tool policy from before the profile was applied, restored on switch.
+    base_allowed_tools: Option<HashSet<String>>,
+    base_disabled_tools: HashSet<String>,
+}
+
+impl Agent {
+    /// Name of the active `[profiles.<name>]` preset, if any.
+    pub fn active_profile(&self) -> Option<&str> {
+        self.profile.as_ref().map(|profile| profile.name.as_str())
+    }
+
+    /// Switch to a named profile, or clear it with `None`. Takes effect from
+    /// the next model call; callers hold the agent between turns.
+    pub fn set_profile(&mut self, name: Option<&str>) -> Result<()> {
+        self.apply_profile(name, true)?;
+        self.log_env_snapshot("set_profile");
+        self.session.save()?;
+        Ok(())
+    }
+
+    /// Re-apply the profile recorded in a resumed session. The session already
+    /// carries the model and plan mode the profile chose, so those are kept.
+    pub(super) fn restore_profile_from_session(&mut self) {
+        let name = self.session.profile.clone();
+        if name.is_none() && self.profile.is_none() {
+            return;
+        }
+        if let Err(error) = self.apply_profile(name.as_deref(), false) {
+            logging::warn(&format!(
+                "Failed to restore profile '{}' for session {}: {}",
+                name.unwrap_or_default(),
+                self.session.id,
+                error
+            ));
+        }
+    }
+
+    fn apply_profile(&mut self, name: Option<&str>, select: bool) -> Result<()> {
+        // Resolve everything that can fail before touching the agent.
+        let next = match name {
+            Some(name) => {
+                let config = crate::config::config().profile(name)?.clone();
+                if let Some(error) = config.validate(name).into_iter().next() {
+                    anyhow::bail!(error);
+                }
+                let system_prompt = config
+                    .system_prompt_file
+                    .as_deref()
+                    .map(|path| self.read_profile_prompt(name, path))
+                    .transpose()?;
+                Some((name.to_string(), config, system_prompt))
+            }
+            None => None,
+        };
+        if select
+            && let Some(model) = next
+                .as_ref()
+                .and_then(|(_, config, _)| config.model_request())
+        {
+            self.set_model(&model)?;
+        }
+
+        let (base_allowed_tools, base_disabled_tools) = match self.profile.take() {
+            Some(previous) => (previous.base_allowed_tools, previous.base_disabled_tools),
+            None => (self.allowed_tools.clone(), self.disabled_tools.clone()),
+        };
+        self.allowed_tools = base_allowed_tools.clone();
+        self.disabled_tools = base_disabled_tools.clone();
+        self.session.profile = None;
+
+        if let Some((name, config, system_prompt)) = next {
+            if select && let Some(plan_mode) = config.plan_mode() {
+                self.session
+                    .plan_mode
+                    .get_or_insert_with(Default::default)
+                    .active = plan_mode;
+            }
+            let selection = crate::config::config()
+                .tools
+                .with_profile(&config)
+                .selection();
+            self.allowed_tools = match (base_allowed_tools.as_ref(), selection.allowed_tools) {
+                (Some(base), Some(narrowed)) => {
+                    Some(base.intersection(&narrowed).cloned().collect())
+                }
+                (Some(base), None) => Some(base.clone()),
+                (None, narrowed) => narrowed,
+            };
+            self.disabled_tools.extend(selection.disabled_tools);
+            self.session.profile = Some(name.clone());
+            self.profile = Some(ActiveProfile {
+                name,
+                config,
+                system_prompt,
+                base_allowed_tools,
+                base_disabled_tools,
+            });
+        }
+
+        self.sync_session_tool_policy();
+        self.unlock_tools();
+        Ok(())
+    }
+
+    fn read_profile_prompt(&self, name: &str, path: &str) -> Result<String> {
+        let path = if let Some(rest) = path.strip_prefix("~/")
+            && let Some(home) = dirs::home_dir()
+        {
+            home.join(rest)
+        } else {
+            match self.working_dir() {
+                Some(dir) => Path::new(dir).join(path),
+                None => PathBuf::from(path),
+            }
+        };
+        let len = std::fs::metadata(&path)
+            .with_context(|| {
+                format!(
+                    "[profiles.{name}] cannot read system_prompt_file {}",
+                    path.display()
+                )
+            })?
+            .len();
+        if len > MAX_PROFILE_PROMPT_BYTES {
+            anyhow::bail!(
+                "[profiles.{name}] system_prompt_file {} is {} KiB, the limit is {} KiB",
+                path.display(),
+                len.div_ceil(1024),
+                MAX_PROFILE_PROMPT_BYTES / 1024
+            );
+        }
+        std::fs::read_to_string(&path).with_context(|| {
+            format!(
+                "[profiles.{name}] cannot read system_prompt_file {}",
+                path.display()
+            )
+        })
+    }
+
+    /// Instructions from the active profile's `system_prompt_file`.
+    pub(super) fn profile_system_prompt(&self) -> Option<&str> {
+        self.profile.as_ref()?.system_prompt.as_deref()
+    }
+
+    /// `[agent]` limits with the active profile's overrides and the safe mode
+    /// turn cap applied.
+    pub(super) fn agent_limits_config(&self) -> AgentLimitsConfig {
+        let base = &crate::config::config().agent;
+        let mut limits = match self.profile.as_ref() {
+            Some(profile) => profile.config.apply_limits(base),
+            None => base.clone(),
+        };
+        self.apply_safe_mode_limits(&mut limits);
+        limits
+    }
+}
@@ -1,6 +1,10 @@
 use super::Agent;
+use crate::config::{PromptPlacement, PromptSection};
 use crate::logging;
-use crate::message::{Message, ToolDefinition};
+use crate::message::{ContentBlock, Message, ToolDefinition};
+
+/// Character budget (~500 tokens) for the open project todos summary.
+const PROJECT_TODOS_PROMPT_MAX_CHARS: usize = 2_000;
 
 impl Agent {
     pub(super) fn log_prompt_prefix_accounting(
@@ -64,6 +68,151 @@ impl Agent {
         split.dynamic_part.push_str(reminder);
     }
 
+    fn append_plan_mode_addendum(&self, split: &mut crate::prompt::SplitSystemPrompt) {
+        if !self.plan_mode_active() {
+            return;
+        }
+        if !split.dynamic_part.is_empty() {
+            split.dynamic_part.push_str("\n\n");
+        }
+        split
+            .dynamic_part
+            .push_str(crate::plan::proposal::plan_mode_system_prompt());
+    }
+
+    fn append_project_todos_summary(
+        &self,
+        split: &mut crate::prompt::SplitSystemPrompt,
+        working_dir: Option<&std::path::Path>,
+    ) {
+        if !crate::config::config()
+            .prompt
+            .section_enabled(PromptSection::Todos)
+        {
+            return;
+        }
+        let Some(summary) = working_dir.and_then(|dir| {
+            crate::todo::project_todos_prompt_summary(
+                dir,
+                &self.session.id,
+                PROJECT_TODOS_PROMPT_MAX_CHARS,
+            )
+        }) else {
+            return;
+        };
+        crate::prompt::append_section(split, PromptSection::Todos, &summary);
+    }
+
+    /// Take the `[prompt] git_context` snapshot for the turn that is starting.
+    pub(super) fn refresh_turn_git_context(&mut self) {
+        self.turn_git_context = if crate::config::config()
+            .prompt
+            .section_enabled(crate::config::PromptSection::Git)
+        {
+            self.session
+                .working_dir
+                .as_deref()
+                .and_then(|dir| crate::prompt::build_git_context(std::path::Path::new(dir)))
+        } else {
+            None
+        };
+    }
+
+    fn append_turn_git_context(&self, split: &mut crate::prompt::SplitSystemPrompt) {
+        let Some(context) = &self.turn_git_context else {
+            return;
+        };
+        crate::prompt::append_section(split, PromptSection::Git, context);
+    }
+
+    /// The `usage` section: tokens the session has used so far.
+    fn append_session_usage(&self, split: &mut crate::prompt::SplitSystemPrompt) {
+        if !crate::config::config()
+            .prompt
+            .section_enabled(PromptSection::Usage)
+        {
+            return;
+        }
+        let totals = self.session.token_usage_totals();
+        if totals.messages_with_token_usage == 0 {
+            return;
+        }
+        crate::prompt::append_section(
+            split,
+            PromptSection::Usage,
+            &crate::prompt::usage_section(totals.input_tokens, totals.output_tokens),
+        );
+    }
+
+    /// Apply `[prompt.sections] memory` to this turn's recalled memories:
+    /// trim them to the budget, drop them when off, or move them into the
+    /// static half of `split`. The flag says whether the memory still goes out
+    /// as a message after the history, which is where dynamic memory lives.
+    pub(super) fn place_memory_section(
+        &self,
+        memory: Option<crate::memory::PendingMemory>,
+        split: &mut crate::prompt::SplitSystemPrompt,
+    ) -> Option<(crate::memory::PendingMemory, bool)> {
+        let mut memory = memory?;
+        let placed = crate::prompt::place_section(
+            &crate::config::config().prompt,
+            PromptSection::Memory,
+            &memory.prompt,
+        )?;
+        memory.prompt = placed.text;
+        let as_message = placed.usage.placement != PromptPlacement::Static;
+        if !as_message {
+            if !split.static_part.is_empty() {
+                split.static_part.push_str("\n\n");
+            }
+            split.static_part.push_str(&memory.prompt);
+        }
+        split.sections.push(placed.usage);
+        Some((memory, as_message))
+    }
+
+    /// Keep the prompt a request sent for `/prompt sections`.
+    pub(super) fn remember_system_prompt(&mut self, split: &crate::prompt::SplitSystemPrompt) {
+        self.last_system_prompt = Some(split.clone());
+    }
+
+    /// Feed the last response's cache usage to the cache tracker under the
+    /// current `[prompt.sections]` layout, logging the measured hit-rate
+    /// change once a new layout has enough requests behind it.
+    pub(super) fn record_prompt_cache_usage(&mut self) {
+        let usage = &self.last_usage;
+        if usage.cache_read_input_tokens.is_none() && usage.cache_creation_input_tokens.is_none() {
+            return;
+        }
+        let prompt_tokens = self.effective_context_tokens_from_usage(
+            usage.input_tokens,
+            usage.cache_read_input_tokens,
+            usage.cache_creation_input_tokens,
+        );
+        let cached_tokens = usage.cache_read_input_tokens.unwrap_or(0);
+        let layout = crate::prompt::layout_fingerprint(&crate::config::config().prompt);
+        if let Some(impact) =
+            self.cache_tracker
+                .record_cache_usage(layout, cached_tokens, prompt_tokens)
+        {
+            logging::info(&format!("PROMPT_LAYOUT_CACHE_IMPACT: {}", impact));
+        }
+    }
+
+    /// `/prompt sections`: the sections of the last request's system prompt,
+    /// or of a fresh build before the first request.
+    pub fn prompt_sections_report(&self) -> String {
+        let split = match &self.last_system_prompt {
+            Some(split) => split.clone(),
+            None => self.build_system_prompt_split(None),
+        };
+        crate::prompt::sections_report(
+            &crate::config::config().prompt,
+            &split,
+            self.cache_tracker.layout_impact().as_deref(),
+        )
+    }
+
     /// Build split system prompt for better caching
     /// Returns static (cacheable) and dynamic (not cached) parts separately
     pub(super) fn build_system_prompt_split(
@@ -99,15 +248,43 @@ impl Agent {
             .as_ref()
             .map(std::path::PathBuf::from);
 
-        let (mut split, _context_info) = crate::prompt::build_system_prompt_split(
+        let mut scoped_dirs =
+            self.prompt_scoped_dirs(self.session.tool_working_dir().map(std::path::Path::new));
+        if let Some(scope) = &self.session.tool_scope {
+            scoped_dirs.push(std::path::PathBuf::from(scope));
+        }
+        let (mut split, _context_info) = crate::prompt::build_system_prompt_split_scoped(
             skill_prompt.as_deref(),
             &available_skills,
             self.session.is_canary,
             memory_prompt,
             working_dir.as_deref(),
+            &scoped_dirs,
         );
 
+        if let Some(instructions) = self.profile_system_prompt() {
+            split.static_part.push_str("\n\n# Profile Instructions\n\n");
+            split.static_part.push_str(instructions.trim_end());
+        }
+        if let Some(instructions) = &self.appended_system_prompt {
+            // Fixed for the whole run, so it can share the cached prefix.
+            split
+                .static_part
+                .push_str("\n\n# Additional Instructions\n\n");
+            split.static_part.push_str(instructions.trim_end());
+        }
+        self.append_project_todos_summary(&mut split, working_dir.as_deref());
+        self.append_turn_git_context(&mut split);
+        self.append_session_usage(&mut split);
+        crate::prompt::append_tool_scope(&mut split, self.session.tool_scope.as_deref());
+        crate::prompt::append_session_instructions(
+            &mut split,
+            self.session.instructions.as_deref(),
+        );
+        self.append_auto_skills(&mut split, &skills);
         self.append_current_turn_system_reminder(&mut split);
+        self.append_plan_mode_addendum(&mut split);
+        self.append_safe_mode_addendum(&mut split);
         crate::prompt::append_swarm_effort_directive(
             &mut split,
             self.provider.reasoning_effort().as_deref(),
@@ -116,6 +293,21 @@ impl Agent {
         split
     }
 
+    /// Directories this session's tool calls have worked in, so instruction
+    /// files scoped to them join the prompt as `nested` layers.
+    fn prompt_scoped_dirs(&self, working_dir: Option<&std::path::Path>) -> Vec<std::path::PathBuf> {
+        let inputs = self
+            .session
+            .messages
+            .iter()
+            .flat_map(|message| message.content.iter())
+            .filter_map(|block| match block {
+                ContentBlock::ToolUse { input, .. } => Some(input),
+                _ => None,
+            });
+        crate::prompt::scoped_dirs_from_tool_inputs(inputs, working_dir)
+    }
+
     /// Non-blocking memory prompt - takes pending result and spawns check for next turn
     #[cfg(test)]
     pub(super) fn build_memory_prompt_nonblocking(
@@ -77,6 +77,7 @@ impl Agent {
         self.session.provider_key = Some(selection.runtime_key.stable_id());
         self.session.route_api_method = Some(selection.api_method.clone());
         self.session.model = Some(resolved_model.clone());
+        self.record_session_model_route();
         let event = crate::provider::ProviderStateEvent::selected_model(
             crate::provider::ProviderModelSelectionSource::User,
             resolved_model,
@@ -94,7 +95,7 @@ impl Agent {
         )
     }
 
-    fn set_model_from_provider_state_event(
+    pub(super) fn set_model_from_provider_state_event(
         &mut self,
         model: &str,
         source: crate::provider::ProviderModelSelectionSource,
@@ -108,6 +109,7 @@ impl Agent {
                 self.session.provider_key.as_deref(),
             );
         self.session.model = Some(resolved_model.clone());
+        self.record_session_model_route();
         let event = crate::provider::ProviderStateEvent::selected_model(source, resolved_model);
         self.provider_runtime_state.apply(event);
         self.persist_session_best_effort("model selection");
@@ -115,6 +117,46 @@ impl Agent {
         Ok(())
     }
 
+    /// The route the provider is running on right now.
+    pub fn current_model_route(&self) -> crate::session::SessionModelRoute {
+        crate::provider::current_model_route(
+            self.provider.as_ref(),
+            self.session.route_api_method.as_deref(),
+        )
+    }
+
+    pub(super) fn record_session_model_route(&mut self) {
+        self.session.model_route = Some(self.current_model_route());
+    }
+
+    /// Re-apply the session's stored model route to the provider. When the
+    /// stored route cannot be used as-is, the fallback is kept for
+    /// [`Agent::take_model_route_fallback`] instead of switching silently.
+    pub(super) fn restore_model_route_from_session(&mut self) {
+        self.model_route_fallback = None;
+        if self.session.model.is_none() {
+            self.session.model = Some(self.provider.model());
+            self.record_session_model_route();
+            return;
+        }
+        match crate::provider::restore_session_model_route(self.provider.as_ref(), &self.session) {
+            None => self.record_session_model_route(),
+            Some(fallback) => {
+                logging::warn(&format!(
+                    "Session {} model route fell back: {}",
+                    self.session.id,
+                    fallback.summary()
+                ));
+                self.model_route_fallback = Some(fallback);
+            }
+        }
+    }
+
+    /// Fallback recorded by the last session restore, if any.
+    pub fn take_model_route_fallback(&mut self) -> Option<crate::session::ModelRouteFallback> {
+        self.model_route_fallback.take()
+    }
+
     pub(crate) fn provider_model_selection_generation(&self) -> u64 {
         self.provider_runtime_state.selection_generation()
     }
@@ -218,12 +260,96 @@ impl Agent {
         Ok(())
     }
 
+    /// Whether plan mode currently restricts this session to read-only tools.
+    pub fn plan_mode_active(&self) -> bool {
+        self.session
+            .plan_mode
+            .as_ref()
+            .is_some_and(|mode| mode.active)
+    }
+
+    /// The latest plan submitted through `plan_propose`, if any.
+    pub fn plan_proposal(&self) -> Option<&crate::plan::proposal::PlanProposal> {
+        self.session
+            .plan_mode
+            .as_ref()
+            .and_then(|mode| mode.proposal.as_ref())
+    }
+
+    /// When the latest plan was submitted, to tell a fresh proposal from one
+    /// left over from an earlier turn.
+    pub fn plan_proposed_at_unix_ms(&self) -> Option<u64> {
+        self.session
+            .plan_mode
+            .as_ref()
+            .and_then(|mode| mode.proposed_at_unix_ms)
+    }
+
+    /// Enter or leave plan mode. Tool definitions are rebuilt on the next turn
+    /// so the model only sees the tools plan mode allows.
+    pub fn set_plan_mode(&mut self, enabled: bool) -> Result<()> {
+        let mode = self.session.plan_mode.get_or_insert_with(Default::default);
+        if mode.active == enabled {
+            return Ok(());
+        }
+        mode.active = enabled;
+        self.apply_plan_mode_change("set_plan_mode")
+    }
+
+    /// Record the user's decision on the latest proposed plan.
+    pub fn decide_plan(
+        &mut self,
+        decision: crate::plan::proposal::PlanDecision,
+        feedback: Option<String>,
+    ) -> Result<()> {
+        let now_unix_ms = chrono::Utc::now().timestamp_millis().max(0) as u64;
+        self.session
+            .plan_mode
+            .get_or_insert_with(Default::default)
+            .decide(decision, feedback, now_unix_ms);
+        self.apply_plan_mode_change("decide_plan")
+    }
+
+    /// Store a plan submitted through `plan_propose` on the session.
+    pub(super) fn record_plan_proposal(&mut self, metadata: Option<&serde_json::Value>) {
+        let Some(proposal) = metadata
+            .and_then(|metadata| metadata.get("plan_proposal"))
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+        else {
+            return;
+        };
+        let now_unix_ms = chrono::Utc::now().timestamp_millis().max(0) as u64;
+        self.session
+            .plan_mode
+            .get_or_insert_with(Default::default)
+            .record_proposal(proposal, now_unix_ms);
+        self.persist_session_best_effort("plan proposal");
+    }
+
+    fn apply_plan_mode_change(&mut self, reason: &str) -> Result<()> {
+        self.sync_session_tool_policy();
+        self.unlock_tools();
+        self.log_env_snapshot(reason);
+        self.session.save()?;
+        Ok(())
+    }
+
     /// Set the working directory for this session
     pub fn set_working_dir(&mut self, dir: &str) {
         if self.session.working_dir.as_deref() == Some(dir) {
             return;
         }
         self.session.working_dir = Some(dir.to_string());
+        // A `/cd` scope only makes sense inside the directory it was set under.
+        let canonical_dir = std::fs::canonicalize(dir).unwrap_or_else(|_| PathBuf::from(dir));
+        if self
+            .session
+            .tool_scope
+            .as_deref()
+            .is_some_and(|scope| !std::path::Path::new(scope).starts_with(&canonical_dir))
+        {
+            self.session.clear_tool_scope();
+        }
         self.session.refresh_initial_session_context_message();
         self.log_env_snapshot("working_dir");
     }
@@ -233,6 +359,72 @@ impl Agent {
         self.session.working_dir.as_deref()
     }
 
+    /// Directory tools run in: the `/cd` scope, else the working directory
+    pub fn tool_working_dir(&self) -> Option<&str> {
+        self.session.tool_working_dir()
+    }
+
+    /// The `/cd` scope, when tools are scoped to a subdirectory
+    pub fn tool_scope(&self) -> Option<&str> {
+        self.session.tool_scope.as_deref()
+    }
+
+    /// Scope tools to a subdirectory (`/cd`), or back to the working directory
+    /// when `path` is `None`.
+    pub fn set_tool_scope(&mut self, path: Option<&str>) -> Result<()> {
+        match path {
+            Some(path) => {
+                self.session.scope_tools(path)?;
+            }
+            None => self.session.clear_tool_scope(),
+        }
+        self.log_env_snapshot("tool_scope");
+        self.session.save()?;
+        Ok(())
+    }
+
+    /// Replace the standing `/instructions` sent with every turn, or clear
+    /// them when `text` is `None` or blank.
+    pub fn set_instructions(&mut self, text: Option<String>) -> Result<()> {
+        self.session.instructions = text.filter(|text| !text.trim().is_empty());
+        self.session.save()?;
+        Ok(())
+    }
+
+    /// Remember the `/mirror` file so resume re-establishes it.
+    pub fn set_mirror_path(&mut self, path: Option<String>) -> Result<()> {
+        self.session.mirror_path = path;
+        self.session.save()?;
+        Ok(())
+    }
+
+    /// Attach an extra workspace root to this session
+    pub fn add_workspace_root(&mut self, path: &str) -> Result<String> {
+        let root = self.session.add_workspace_root(path)?;
+        self.log_env_snapshot("workspace_roots");
+        self.session.save()?;
+        Ok(root)
+    }
+
+    /// Detach an extra workspace root by name or path
+    pub fn remove_workspace_root(&mut self, name_or_path: &str) -> Result<Option<String>> {
+        let removed = self.session.remove_workspace_root(name_or_path);
+        if removed.is_some() {
+            self.log_env_snapshot("workspace_roots");
+            self.session.save()?;
+        }
+        Ok(removed)
+    }
+
+    /// Extra workspace roots beyond the working directory
+    pub fn workspace_roots(&self) -> Vec<PathBuf> {
+        self.session
+            .workspace_roots
+            .iter()
+            .map(PathBuf::from)
+            .collect()
+    }
+
     /// Get the stored messages (for transcript export)
     pub fn messages(&self) -> &[StoredMessage] {
         &self.session.messages
@@ -0,0 +1,84 @@
+//! In-turn waits for provider rate limits.
+//!
+//! When a provider rejects a request with HTTP 429 and advertises how long to
+//! back off (`StreamEvent::Error { retry_after_secs }`), the agent sleeps the
+//! wait out and resends the same request instead of failing the turn, as long
+//! as the wait fits under `[provider] rate_limit_max_wait_secs`. Longer waits
+//! surface as a `StreamError` so the client can schedule its own retry. Every
+//! incident is recorded against the active login for `/usage`.
+
+use super::*;
+
+impl Agent {
+    /// The wait to sleep through before resending a rate-limited request, or
+    /// `None` when the error should surface instead (no advertised wait, wait
+    /// above the configured ceiling, or retries exhausted).
+    pub(super) fn rate_limit_wait(
+        &self,
+        retry_after_secs: Option<u64>,
+        retries: u32,
+    ) -> Option<Duration> {
+        let wait = crate::provider::in_turn_rate_limit_wait(retry_after_secs, retries)?;
+        self.record_rate_limit_incident();
+        Some(wait)
+    }
+
+    /// Build the event that drives the client-side countdown for a wait.
+    pub(super) fn rate_limited_event(&self, wait: Duration, attempt: u32) -> ServerEvent {
+        ServerEvent::RateLimited {
+            provider: self.provider.display_name(),
+            retry_after_secs: wait.as_secs(),
+            attempt,
+            max_attempts: crate::provider::MAX_RATE_LIMIT_WAITS,
+        }
+    }
+
+    /// Sleep out a rate-limit wait. Returns `false` when the turn was
+    /// cancelled mid-wait.
+    pub(super) async fn sleep_rate_limit_wait(
+        &self,
+        wait: Duration,
+        event_tx: Option<&mpsc::UnboundedSender<ServerEvent>>,
+    ) -> bool {
+        let sleep = tokio::time::sleep(wait);
+        tokio::pin!(sleep);
+        let mut keepalive = stream_keepalive_ticker();
+        loop {
+            tokio::select! {
+                _ = &mut sleep => return true,
+                _ = keepalive.tick() => {
+                    if let Some(event_tx) = event_tx {
+                        send_stream_keepalive_mpsc(event_tx);
+                    }
+                }
+                _ = self.graceful_shutdown.notified() => {
+                    logging::info("Graceful shutdown/cancel during rate-limit wait - stopping turn");
+                    return false;
+                }
+            }
+        }
+    }
+
+    /// Record a terminal rate-limit failure (one the agent did not wait out).
+    pub(super) fn note_rate_limit_failure(&self, error: &anyhow::Error) {
+        let advertised_wait = error
+            .downcast_ref::<StreamError>()
+            .and_then(|stream_error| stream_error.retry_after_secs)
+            .is_some();
+        if advertised_wait || is_rate_limit_message(&error.to_string()) {
+            self.record_rate_limit_incident();
+        }
+    }
+
+    fn record_rate_limit_incident(&self) {
+        let source_key = crate::provider::rate_limit_source_key(self.provider.as_ref());
+        tokio::task::spawn_blocking(move || crate::usage::record_rate_limit(&source_key));
+    }
+}
+
+fn is_rate_limit_message(message: &str) -> bool {
+    let lower = message.to_ascii_lowercase();
+    lower.contains("too many requests")
+        || lower.contains("rate limit")
+        || lower.contains("rate_limit")
+}
@@ -0,0 +1,68 @@
+//! `[privacy]` redaction of what the agent sends to the provider.
+//!
+//! User messages and tool results are scrubbed as they enter the
+//! conversation, so the provider, compaction and the saved session only see
+//! placeholders. Tool calls get the originals back just before they run.
+
+use super::*;
+use crate::redaction::RedactionMap;
+
+impl Agent {
+    /// Replace sensitive values in user text and tool results with
+    /// placeholders when `[privacy] redact` is on, and save any new mapping
+    /// entries.
+    pub(super) fn redact_outbound(&mut self, role: &Role, content: &mut [ContentBlock]) {
+        if *role != Role::User || !crate::redaction::enabled() {
+            return;
+        }
+        let redactor = crate::redaction::configured_redactor();
+        let session_id = &self.session.id;
+        let map = self
+            .redaction_map
+            .get_or_insert_with(|| RedactionMap::load(session_id));
+        let mut count = 0;
+        for block in content.iter_mut() {
+            let text = match block {
+                ContentBlock::Text { text, .. } => text,
+                ContentBlock::ToolResult { content, .. } => content,
+                _ => continue,
+            };
+            let (redacted, replaced) = redactor.redact(text, map);
+            if replaced > 0 {
+                *text = redacted;
+                count += replaced;
+            }
+        }
+        if count == 0 {
+            return;
+        }
+        logging::info(&format!(
+            "REDACTION session={} replaced={}",
+            session_id, count
+        ));
+        if let Err(err) = map.save(session_id) {
+            logging::warn(&format!(
+                "Failed to save redaction map for session {}: {}",
+                session_id, err
+            ));
+        }
+        self.pending_redactions += count as u64;
+    }
+
+    /// Redactions since the previous request, for the next response's token
+    /// usage. Resets the count.
+    pub(super) fn take_pending_redactions(&mut self) -> Option<u64> {
+        Some(std::mem::take(&mut self.pending_redactions)).filter(|count| *count > 0)
+    }
+
+    /// Put originals back in place of placeholders in a tool call's input.
+    pub(super) fn reveal_redacted_input(&mut self, input: &mut serde_json::Value) {
+        let session_id = &self.session.id;
+        let map = self
+            .redaction_map
+            .get_or_insert_with(|| RedactionMap::load(session_id));
+        if !map.is_empty() {
+            map.reveal_json(input);
+        }
+    }
+}
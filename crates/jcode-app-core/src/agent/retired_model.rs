@@ -0,0 +1,95 @@
+//! Moving a session off a model its provider has retired.
+//!
+//! When a request is rejected because the model id was retired, deprecated or
+//! renamed, the agent switches the session to the model's successor (see
+//! `jcode_provider_core::MODEL_SUCCESSORS`, checked against the account's
+//! model list), marks the old id unavailable and resends. Clients get a
+//! `ModelChanged` whose `route_fallback` says why. Without a known successor
+//! the turn fails with the closest models the account can still use instead
+//! of the raw provider error.
+
+use super::*;
+
+impl Agent {
+    /// Successor switches allowed per turn, so a stale table entry can't loop.
+    pub(super) const MAX_RETIRED_MODEL_SWITCHES: u32 = 2;
+
+    /// Switch the session to the successor of its model when `error` says the
+    /// model was retired. Returns the old and new routes, or `None` when the
+    /// error is something else or no successor is available.
+    pub(super) fn switch_from_retired_model(
+        &mut self,
+        error: &str,
+    ) -> Option<crate::session::ModelRouteFallback> {
+        if !jcode_provider_core::is_model_retired_error(error) {
+            return None;
+        }
+        let retired = self.provider.model();
+        let successor =
+            jcode_provider_core::model_successor(&retired, &self.models_other_than(&retired))?;
+        let requested = self.current_model_route();
+        let provider_name = self.provider.display_name();
+        if let Err(e) = self.set_model_from_provider_state_event(
+            &successor,
+            crate::provider::ProviderModelSelectionSource::Startup,
+        ) {
+            logging::warn(&format!(
+                "Model '{}' was retired but switching to '{}' failed: {}",
+                retired, successor, e
+            ));
+            return None;
+        }
+        let reason = format!("'{}' was retired by {}", retired, provider_name);
+        crate::provider::record_model_unavailable_for_account(&retired, &reason);
+        let fallback = crate::session::ModelRouteFallback {
+            requested,
+            effective: self.current_model_route(),
+            reason: Some(reason),
+        };
+        logging::warn(&format!(
+            "Session {} moved off a retired model: {}",
+            self.session.id,
+            fallback.summary()
+        ));
+        Some(fallback)
+    }
+
+    /// The `ModelChanged` telling clients why the session left its model.
+    pub(super) fn retired_model_event(
+        &self,
+        fallback: crate::session::ModelRouteFallback,
+    ) -> ServerEvent {
+        ServerEvent::ModelChanged {
+            id: 0,
+            model: self.provider.model(),
+            provider_name: Some(self.provider.display_name()),
+            error: None,
+            route_fallback: Some(fallback),
+        }
+    }
+
+    /// The error for a retired model with no known successor, naming the
+    /// closest models instead of passing the provider's rejection through.
+    pub(super) fn retired_model_failure(&self, message: &str) -> Option<anyhow::Error> {
+        if !jcode_provider_core::is_model_retired_error(message) {
+            return None;
+        }
+        let retired = self.provider.model();
+        let nearest =
+            jcode_provider_core::nearest_models(&retired, &self.models_other_than(&retired), 3);
+        Some(anyhow::anyhow!(jcode_provider_core::retired_model_message(
+            &retired,
+            &self.provider.display_name(),
+            &nearest
+        )))
+    }
+
+    fn models_other_than(&self, model: &str) -> Vec<String> {
+        let key = jcode_provider_core::model_id::canonical(model);
+        self.provider
+            .available_models_for_switching()
+            .into_iter()
+            .filter(|candidate| jcode_provider_core::model_id::canonical(candidate) != key)
+            .collect()
+    }
+}
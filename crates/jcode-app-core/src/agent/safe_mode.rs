@@ -0,0 +1,86 @@
+//! `jcode --safe`: conservative defaults for trying jcode on a real repository.
+//!
+//! Safe mode is a session flag. While it is set, `Registry::execute` asks the
+//! client to approve every tool call outside the auto-allowed tier, bash runs
+//! in a read-only sandbox where one is available, memory writes are refused,
+//! ambient scheduling tools are hidden, and each request is capped at
+//! [`SAFE_MODE_MAX_TURNS`] model calls. Resuming the session keeps it on.
+
+use super::*;
+use crate::config::AgentLimitsConfig;
+
+/// Model calls allowed per request in safe mode, unless a lower limit is set.
+pub(super) const SAFE_MODE_MAX_TURNS: u32 = 25;
+
+/// Tools withheld from the model in safe mode.
+pub(super) const SAFE_MODE_HIDDEN_TOOLS: &[&str] = &["schedule", "schedule_ambient"];
+
+const SAFE_MODE_PROMPT: &str = "# Safe mode\n\nThis session runs in safe mode. Every tool call that can change files, run commands or reach the network waits for the user to approve it, so prefer read-only investigation and batch related changes together. Bash runs in a sandbox where files outside the working directory are read-only. Saving memories and scheduling ambient work are disabled.";
+
+impl Agent {
+    /// Whether this session runs with `jcode --safe` defaults.
+    pub fn safe_mode(&self) -> bool {
+        self.session.safe_mode
+    }
+
+    /// Turn safe mode on or off. Takes effect from the next tool call.
+    pub fn set_safe_mode(&mut self, enabled: bool) -> Result<()> {
+        self.session.safe_mode = enabled;
+        self.apply_safe_mode();
+        self.unlock_tools();
+        self.log_env_snapshot("set_safe_mode");
+        self.session.save()?;
+        Ok(())
+    }
+
+    /// Whether tool calls wait for the client's approval without safe mode.
+    pub fn approval_gate(&self) -> bool {
+        self.approval_gate
+    }
+
+    /// Turn the approval gate on or off. Tool calls outside the auto-allowed
+    /// tier then wait for the client's approval, as in safe mode, but nothing
+    /// else changes and the session is not saved with it.
+    pub fn set_approval_gate(&mut self, enabled: bool) {
+        self.approval_gate = enabled;
+        self.sync_session_tool_policy();
+    }
+
+    /// Bring memory and the published tool policy in line with the session's
+    /// safe mode flag. Also runs when a session is resumed.
+    pub(super) fn apply_safe_mode(&mut self) {
+        if self.session.safe_mode {
+            self.set_memory_enabled(false);
+        } else if !self.memory_enabled() {
+            self.set_memory_enabled(crate::config::config().features.memory);
+        }
+        self.sync_session_tool_policy();
+    }
+
+    pub(super) fn hide_safe_mode_tools(&self, tools: &mut Vec<ToolDefinition>) {
+        if self.session.safe_mode {
+            tools.retain(|tool| !SAFE_MODE_HIDDEN_TOOLS.contains(&tool.name.as_str()));
+        }
+    }
+
+    /// Cap `max_turns` in safe mode, keeping any lower configured limit.
+    pub(super) fn apply_safe_mode_limits(&self, limits: &mut AgentLimitsConfig) {
+        if self.session.safe_mode {
+            limits.max_turns = Some(
+                limits
+                    .max_turns
+                    .map_or(SAFE_MODE_MAX_TURNS, |max| max.min(SAFE_MODE_MAX_TURNS)),
+            );
+        }
+    }
+
+    pub(super) fn append_safe_mode_addendum(&self, split: &mut crate::prompt::SplitSystemPrompt) {
+        if !self.session.safe_mode {
+            return;
+        }
+        if !split.dynamic_part.is_empty() {
+            split.dynamic_part.push_str("\n\n");
+        }
+        split.dynamic_part.push_str(SAFE_MODE_PROMPT);
+    }
+}
@@ -0,0 +1,436 @@
+//! Context-triggered skill loading (`[skills]` config).
+//!
+//! Skills may declare `triggers` (file globs, keywords, tool names) in their
+//! SKILL.md frontmatter. Before the first model call the working directory is
+//! scanned for matching files; before every later call the messages added
+//! since the previous check are scanned for touched files, used tools, and
+//! keywords in user text. Matching skills load until `max_auto_loaded` or
+//! `auto_load_token_budget` is reached and stay active for the session; their
+//! instructions and what triggered each go into the dynamic prompt section.
+
+use super::*;
+use crate::config::SkillsConfig;
+use crate::protocol::ActiveSkillStatus;
+use crate::skill::{Skill, SkillTriggers};
+use serde_json::Value;
+use std::path::Path;
+
+/// Cap on files collected from the working directory at session start.
+const WORKSPACE_SCAN_MAX_FILES: usize = 5_000;
+const WORKSPACE_SCAN_MAX_DEPTH: usize = 8;
+/// Directories the start-of-session scan does not descend into.
+const SKIPPED_DIRS: &[&str] = &[
+    ".git",
+    "target",
+    "node_modules",
+    ".venv",
+    "venv",
+    "__pycache__",
+    "dist",
+    "build",
+];
+/// Input keys naming the file or directory a tool call works on.
+const PATH_KEYS: &[&str] = &["file_path", "path"];
+
+#[derive(Debug, Default)]
+pub(super) struct AutoSkillState {
+    active: Vec<ActiveSkillStatus>,
+    /// Session messages already checked for triggers.
+    scanned_messages: usize,
+    workspace_scanned: bool,
+}
+
+/// What the session touched since the last check.
+#[derive(Debug, Default)]
+struct WorkingSet {
+    /// Paths relative to the working directory, `/`-separated.
+    files: Vec<String>,
+    /// Lowercased user text.
+    text: String,
+    tools: HashSet<String>,
+}
+
+impl WorkingSet {
+    fn is_empty(&self) -> bool {
+        self.files.is_empty() && self.text.is_empty() && self.tools.is_empty()
+    }
+}
+
+/// Describe the first trigger in `triggers` that `set` satisfies.
+fn trigger_reason(triggers: &SkillTriggers, set: &WorkingSet) -> Option<String> {
+    for tool in triggers.tools.iter().map(|tool| tool.trim()) {
+        if set.tools.contains(tool) {
+            return Some(format!("tool `{}` used", tool));
+        }
+    }
+    for glob in triggers.files.iter().map(|glob| glob.trim()) {
+        let Ok(pattern) = glob::Pattern::new(glob) else {
+            continue;
+        };
+        // `*.tf` matches by file name anywhere; globs with a `/` match the
+        // whole relative path, where only `**` crosses directories.
+        let matched = if glob.contains('/') {
+            let options = glob::MatchOptions {
+                require_literal_separator: true,
+                ..Default::default()
+            };
+            set.files
+                .iter()
+                .find(|file| pattern.matches_with(file, options))
+        } else {
+            set.files.iter().find(|file| {
+                file.rsplit('/')
+                    .next()
+                    .is_some_and(|name| pattern.matches(name))
+            })
+        };
+        if let Some(file) = matched {
+            return Some(format!("file `{}` matches `{}`", file, glob));
+        }
+    }
+    for keyword in &triggers.keywords {
+        let keyword = keyword.trim().to_lowercase();
+        if !keyword.is_empty() && contains_word(&set.text, &keyword) {
+            return Some(format!("keyword `{}` mentioned", keyword));
+        }
+    }
+    None
+}
+
+fn contains_word(text: &str, word: &str) -> bool {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    text.match_indices(word).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + word.len()..].chars().next();
+        !before.is_some_and(is_word_char) && !after.is_some_and(is_word_char)
+    })
+}
+
+fn relative_path(path: &Path, working_dir: Option<&Path>) -> String {
+    let path = working_dir
+        .and_then(|dir| path.strip_prefix(dir).ok())
+        .unwrap_or(path);
+    path.to_string_lossy()
+        .replace('\\', "/")
+        .trim_start_matches("./")
+        .to_string()
+}
+
+/// Files under `root`, skipping VCS and build output, up to the scan cap.
+fn workspace_files(root: &Path) -> Vec<String> {
+    let mut files = Vec::new();
+    let mut pending = vec![(root.to_path_buf(), 0usize)];
+    while let Some((dir, depth)) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let path = entry.path();
+            if file_type.is_dir() {
+                let name = entry.file_name();
+                let name = name.to_string_lossy();
+                if depth < WORKSPACE_SCAN_MAX_DEPTH
+                    && !SKIPPED_DIRS.iter().any(|skipped| name == *skipped)
+                {
+                    pending.push((path, depth + 1));
+                }
+            } else if file_type.is_file() {
+                files.push(relative_path(&path, Some(root)));
+                if files.len() >= WORKSPACE_SCAN_MAX_FILES {
+                    return files;
+                }
+            }
+        }
+    }
+    files
+}
+
+fn collect_from_messages(
+    messages: &[StoredMessage],
+    working_dir: Option<&Path>,
+    set: &mut WorkingSet,
+) {
+    for message in messages {
+        for block in &message.content {
+            match block {
+                ContentBlock::ToolUse { name, input, .. } => {
+                    set.tools.insert(name.clone());
+                    for key in PATH_KEYS {
+                        if let Some(raw) = input.get(*key).and_then(Value::as_str)
+                            && !raw.trim().is_empty()
+                        {
+                            set.files
+                                .push(relative_path(Path::new(raw.trim()), working_dir));
+                        }
+                    }
+                }
+                ContentBlock::Text { text, .. }
+                    if message.role == Role::User && message.display_role.is_none() =>
+                {
+                    set.text.push_str(&text.to_lowercase());
+                    set.text.push('\n');
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Add skills from `candidates` whose triggers match `set` to `active`,
+/// within the count and token limits. Returns true if any were added.
+fn select_skills(
+    candidates: &[&Skill],
+    set: &WorkingSet,
+    active: &mut Vec<ActiveSkillStatus>,
+    mut tokens_used: usize,
+    config: &SkillsConfig,
+) -> bool {
+    let mut added = false;
+    for skill in candidates {
+        if active.len() >= config.max_auto_loaded {
+            break;
+        }
+        let Some(trigger) = trigger_reason(&skill.triggers, set) else {
+            continue;
+        };
+        let tokens = crate::util::estimate_tokens(&skill.get_prompt());
+        if tokens_used + tokens > config.auto_load_token_budget {
+            logging::info(&format!(
+                "Skills: not auto-loading /{} ({}): ~{} tokens would exceed the {} token budget",
+                skill.name, trigger, tokens, config.auto_load_token_budget
+            ));
+            continue;
+        }
+        logging::info(&format!(
+            "Skills: auto-loaded /{} ({})",
+            skill.name, trigger
+        ));
+        tokens_used += tokens;
+        active.push(ActiveSkillStatus {
+            name: skill.name.clone(),
+            trigger,
+        });
+        added = true;
+    }
+    added
+}
+
+impl Agent {
+    /// Check skill triggers against what the session touched since the last
+    /// call. Returns true when new skills were loaded.
+    pub(super) fn refresh_auto_skills(&mut self) -> bool {
+        let config = crate::config::config().skills.clone();
+        if !config.auto_load || self.auto_skills.active.len() >= config.max_auto_loaded {
+            return false;
+        }
+
+        let skills = self.current_skills_snapshot();
+        let candidates: Vec<&Skill> = skills
+            .list()
+            .into_iter()
+            .filter(|skill| {
+                !skill.triggers.is_empty()
+                    && self.active_skill.as_deref() != Some(skill.name.as_str())
+                    && !self
+                        .auto_skills
+                        .active
+                        .iter()
+                        .any(|active| active.name == skill.name)
+            })
+            .collect();
+        let working_dir = self.session.working_dir.as_ref().map(PathBuf::from);
+        let start = self
+            .auto_skills
+            .scanned_messages
+            .min(self.session.messages.len());
+        self.auto_skills.scanned_messages = self.session.messages.len();
+        if candidates.is_empty() {
+            return false;
+        }
+
+        let mut set = WorkingSet::default();
+        if !self.auto_skills.workspace_scanned {
+            self.auto_skills.workspace_scanned = true;
+            if let Some(dir) = working_dir.as_deref() {
+                set.files = workspace_files(dir);
+            }
+        }
+        collect_from_messages(
+            &self.session.messages[start..],
+            working_dir.as_deref(),
+            &mut set,
+        );
+        if set.is_empty() {
+            return false;
+        }
+
+        let tokens_used: usize = self
+            .auto_skills
+            .active
+            .iter()
+            .filter_map(|active| skills.get(&active.name))
+            .map(|skill| crate::util::estimate_tokens(&skill.get_prompt()))
+            .sum();
+        select_skills(
+            &candidates,
+            &set,
+            &mut self.auto_skills.active,
+            tokens_used,
+            &config,
+        )
+    }
+
+    /// Note which skills are auto-loaded and why, then their instructions.
+    pub(super) fn append_auto_skills(
+        &self,
+        split: &mut crate::prompt::SplitSystemPrompt,
+        skills: &SkillRegistry,
+    ) {
+        let mut notes = Vec::new();
+        let mut prompts = Vec::new();
+        for active in &self.auto_skills.active {
+            if let Some(skill) = skills.get(&active.name) {
+                notes.push(format!("/{} ({})", active.name, active.trigger));
+                prompts.push(skill.get_prompt());
+            }
+        }
+        if notes.is_empty() {
+            return;
+        }
+        if !split.dynamic_part.is_empty() {
+            split.dynamic_part.push_str("\n\n");
+        }
+        split.dynamic_part.push_str("# Auto-loaded Skills\n\n");
+        split.dynamic_part.push_str(&format!(
+            "Loaded because the session matched their triggers: {}.",
+            notes.join("; ")
+        ));
+        for prompt in prompts {
+            split.dynamic_part.push_str("\n\n");
+            split.dynamic_part.push_str(&prompt);
+        }
+    }
+
+    pub(super) fn auto_skill_names(&self) -> Vec<String> {
+        self.auto_skills
+            .active
+            .iter()
+            .map(|active| format!("/{}", active.name))
+            .collect()
+    }
+
+    pub(super) fn send_active_skills(&self, event_tx: &mpsc::UnboundedSender<ServerEvent>) {
+        let _ = event_tx.send(ServerEvent::ActiveSkills {
+            skills: self.auto_skills.active.clone(),
+        });
+    }
+
+    pub(super) fn reset_auto_skills(&mut self) {
+        self.auto_skills = AutoSkillState::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triggers(files: &[&str], keywords: &[&str], tools: &[&str]) -> SkillTriggers {
+        let owned = |items: &[&str]| items.iter().map(|item| item.to_string()).collect();
+        SkillTriggers {
+            files: owned(files),
+            keywords: owned(keywords),
+            tools: owned(tools),
+        }
+    }
+
+    #[test]
+    fn file_globs_match_by_name_or_relative_path() {
+        let set = WorkingSet {
+            files: vec!["README.md".to_string(), "infra/prod/main.tf".to_string()],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            trigger_reason(&triggers(&["*.tf"], &[], &[]), &set).as_deref(),
+            Some("file `infra/prod/main.tf` matches `*.tf`")
+        );
+        assert!(trigger_reason(&triggers(&["**/*.tf"], &[], &[]), &set).is_some());
+        assert!(trigger_reason(&triggers(&["infra/*.tf"], &[], &[]), &set).is_none());
+        assert!(trigger_reason(&triggers(&["docs/**"], &[], &[]), &set).is_none());
+    }
+
+    #[test]
+    fn keywords_match_whole_words_and_tools_match_by_name() {
+        let set = WorkingSet {
+            text: "please fix the terraform plan\n".to_string(),
+            tools: HashSet::from(["bash".to_string()]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            trigger_reason(&triggers(&[], &["Terraform"], &[]), &set).as_deref(),
+            Some("keyword `terraform` mentioned")
+        );
+        assert!(trigger_reason(&triggers(&[], &["form"], &[]), &set).is_none());
+        assert_eq!(
+            trigger_reason(&triggers(&[], &[], &["bash"]), &set).as_deref(),
+            Some("tool `bash` used")
+        );
+    }
+
+    fn stored(role: Role, block: ContentBlock) -> StoredMessage {
+        StoredMessage {
+            id: "m".to_string(),
+            role,
+            content: vec![block],
+            display_role: None,
+            timestamp: None,
+            tool_duration_ms: None,
+            token_usage: None,
+        }
+    }
+
+    #[test]
+    fn tool_inputs_and_user_text_feed_the_working_set() {
+        let dir = Path::new("/repo");
+        let messages = vec![
+            stored(
+                Role::User,
+                ContentBlock::Text {
+                    text: "Deploy with Terraform".to_string(),
+                    cache_control: None,
+                },
+            ),
+            stored(
+                Role::Assistant,
+                ContentBlock::ToolUse {
+                    id: "t1".to_string(),
+                    name: "read".to_string(),
+                    input: serde_json::json!({"file_path": "/repo/infra/main.tf"}),
+                    thought_signature: None,
+                },
+            ),
+        ];
+        let mut set = WorkingSet::default();
+        collect_from_messages(&messages, Some(dir), &mut set);
+
+        assert_eq!(set.files, vec!["infra/main.tf"]);
+        assert!(set.tools.contains("read"));
+        assert!(set.text.contains("deploy with terraform"));
+    }
+
+    #[test]
+    fn workspace_scan_skips_build_output() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        std::fs::create_dir_all(temp.path().join("infra")).expect("infra dir");
+        std::fs::create_dir_all(temp.path().join("target/debug")).expect("target dir");
+        std::fs::write(temp.path().join("infra/main.tf"), "").expect("tf file");
+        std::fs::write(temp.path().join("target/debug/out.tf"), "").expect("target file");
+
+        let files = workspace_files(temp.path());
+        assert_eq!(files, vec!["infra/main.tf"]);
+    }
+}
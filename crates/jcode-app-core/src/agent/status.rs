@@ -83,6 +83,13 @@ impl Agent {
                     ContentBlock::OpenAICompaction { .. } => {
                         transcript.push_str("[OpenAI native compaction]\n");
                     }
+                    ContentBlock::ServerToolUse { name, .. } => {
+                        transcript.push_str(&format!("[Used tool: {}]\n", name));
+                    }
+                    ContentBlock::WebSearchResult { results, .. } => {
+                        transcript.push_str(&format!("[Web search: {} results]\n", results.len()));
+                    }
+                    ContentBlock::Citations { .. } => {}
                 }
             }
             transcript.push('\n');
@@ -137,6 +144,29 @@ impl Agent {
             })
     }
 
+    /// What `[provider] deterministic` pinned for the response just
+    /// streamed, for its token usage. Consumes the reported fingerprint.
+    pub(super) fn take_response_determinism(
+        &mut self,
+    ) -> Option<crate::message::DeterminismParams> {
+        let fingerprint = self.last_system_fingerprint.take();
+        let seed = crate::provider::determinism::seed()?;
+        let mut params = self.provider.deterministic_params(seed);
+        params.system_fingerprint = fingerprint;
+        Some(params)
+    }
+
+    /// Determinism settings recorded with the most recent response, if the
+    /// mode was on for it.
+    pub fn last_determinism(&self) -> Option<crate::message::DeterminismParams> {
+        self.session.messages.iter().rev().find_map(|message| {
+            message
+                .token_usage
+                .as_ref()
+                .and_then(|usage| usage.determinism.clone())
+        })
+    }
+
     pub fn last_upstream_provider(&self) -> Option<String> {
         self.last_upstream_provider
             .clone()
@@ -0,0 +1,35 @@
+//! Recovery from provider streams that stall.
+//!
+//! Every provider stream is wrapped in the base crate's watchdog, which ends a
+//! stalled stream with a "stream idle timeout" error. A stall before any output
+//! resends the request; a stall mid-response keeps what was streamed and asks
+//! the model to continue, the same way a `max_tokens` cut-off is handled.
+
+use super::*;
+use crate::provider::EventStream;
+
+impl Agent {
+    /// Stop reason recorded for a response cut off by the watchdog; read as
+    /// truncation by `should_continue_after_stop_reason`.
+    pub(super) const STREAM_STALL_STOP_REASON: &str = "incomplete (stream idle timeout)";
+
+    /// Resends of a request whose stream stalled before producing output.
+    pub(super) const MAX_STREAM_STALL_RETRIES: u32 = 2;
+
+    /// Wrap a freshly opened provider stream with the stall watchdog. CLI
+    /// providers run tools inside the stream and can stay silent for as long
+    /// as a tool runs, so their streams are left alone.
+    pub(super) fn watch_provider_stream(&self, stream: EventStream) -> EventStream {
+        if self.provider.handles_tools_internally() {
+            return stream;
+        }
+        let source_key = crate::provider::rate_limit_source_key(self.provider.as_ref());
+        crate::provider::watch_stream(
+            stream,
+            crate::provider::StreamWatchdog::from_config(),
+            move || {
+                tokio::task::spawn_blocking(move || crate::usage::record_stream_stall(&source_key));
+            },
+        )
+    }
+}
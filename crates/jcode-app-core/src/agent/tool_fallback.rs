@@ -0,0 +1,299 @@
+//! Tool use for models without native tool calling.
+//!
+//! When the active model's capabilities say it has no tools, the turn loops
+//! follow `[provider] tool_fallback`: either end the turn with guidance, or
+//! switch to a text protocol. In text mode the tools are described in the
+//! system prompt instead of being sent natively, earlier tool calls and results
+//! are flattened to text, and fenced `tool` blocks in the reply are parsed back
+//! into tool calls.
+
+use super::*;
+use crate::config::ToolFallbackMode;
+
+const TOOL_BLOCK_OPEN: &str = "```tool";
+const TOOL_BLOCK_CLOSE: &str = "```";
+
+/// How this turn's request exposes tools to the model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ToolProtocol {
+    Native,
+    Textual,
+    Refuse,
+}
+
+impl Agent {
+    pub(super) fn tool_protocol(&self, tools: &[ToolDefinition]) -> ToolProtocol {
+        if tools.is_empty() {
+            return ToolProtocol::Native;
+        }
+        let capabilities = crate::provider::resolve_model_capabilities(
+            &self.provider.model(),
+            Some(self.provider.name()),
+        );
+        if capabilities.tools {
+            return ToolProtocol::Native;
+        }
+        match crate::config::config().provider.tool_fallback {
+            ToolFallbackMode::Textual => ToolProtocol::Textual,
+            ToolFallbackMode::Refuse => ToolProtocol::Refuse,
+        }
+    }
+
+    pub(super) fn tool_less_model_error(&self) -> anyhow::Error {
+        anyhow::anyhow!(
+            "{} does not support tool calling, so jcode cannot read or edit files with it. \
+Switch to a tool-capable model with /model, or set `tool_fallback = \"textual\"` under \
+[provider] in ~/.jcode/config.toml (or JCODE_TOOL_FALLBACK=textual) to drive tools through \
+a text protocol.",
+            self.provider.model()
+        )
+    }
+
+    /// Parse fenced `tool` blocks from a text-protocol reply into tool calls,
+    /// removing them from the visible text. Returns how many were parsed.
+    pub(super) fn recover_textual_tool_calls(
+        &self,
+        text_content: &mut String,
+        tool_calls: &mut Vec<ToolCall>,
+    ) -> usize {
+        let (remaining, calls) = parse_tool_blocks(text_content);
+        if calls.is_empty() {
+            return 0;
+        }
+        *text_content = remaining;
+        let count = calls.len();
+        for (name, input) in calls {
+            let intent = ToolCall::intent_from_input(&input);
+            tool_calls.push(ToolCall {
+                id: format!("fallback_text_call_{}", id::new_id("call")),
+                name,
+                input,
+                intent,
+                thought_signature: None,
+            });
+        }
+        logging::info(&format!(
+            "[agent] Parsed {} text-protocol tool call(s) for {}",
+            count,
+            self.provider.model()
+        ));
+        count
+    }
+}
+
+/// Switch a request to the text protocol: describe `tools` in the static
+/// system prompt and send none natively.
+pub(super) fn apply_textual_tool_protocol(
+    split_prompt: &mut crate::prompt::SplitSystemPrompt,
+    tools: &mut Vec<ToolDefinition>,
+) {
+    let tools = std::mem::take(tools);
+    if !split_prompt.static_part.is_empty() {
+        split_prompt.static_part.push_str("\n\n");
+    }
+    split_prompt
+        .static_part
+        .push_str(&textual_tool_instructions(&tools));
+}
+
+fn textual_tool_instructions(tools: &[ToolDefinition]) -> String {
+    let mut out = String::from(
+        "# Tools (text protocol)\n\
+This model has no native tool calling, so tools are called through text. To call a tool, write \
+a fenced block tagged `tool` holding one JSON object with `name` and `input`:\n\n\
+```tool\n{\"name\": \"read\", \"input\": {\"file_path\": \"src/main.rs\"}}\n```\n\n\
+You may write several blocks in one reply. Stop after your tool blocks: the results arrive in \
+the next user message. Reply without a tool block when you are done.\n\nAvailable tools:\n",
+    );
+    for tool in tools {
+        let summary = tool.description.lines().next().unwrap_or_default().trim();
+        out.push_str(&format!(
+            "- `{}`: {}\n  input schema: {}\n",
+            tool.name, summary, tool.input_schema
+        ));
+    }
+    out
+}
+
+/// Flatten native tool blocks into text for a model that cannot accept them.
+pub(super) fn textual_protocol_messages(messages: &[Message]) -> Vec<Message> {
+    let mut tool_names: HashMap<&str, &str> = HashMap::new();
+    messages
+        .iter()
+        .map(|message| {
+            let content = message
+                .content
+                .iter()
+                .map(|block| match block {
+                    ContentBlock::ToolUse {
+                        id, name, input, ..
+                    } => {
+                        tool_names.insert(id.as_str(), name.as_str());
+                        ContentBlock::Text {
+                            text: format!(
+                                "{}\n{}\n{}",
+                                TOOL_BLOCK_OPEN,
+                                serde_json::json!({ "name": name, "input": input }),
+                                TOOL_BLOCK_CLOSE
+                            ),
+                            cache_control: None,
+                        }
+                    }
+                    ContentBlock::ToolResult {
+                        tool_use_id,
+                        content,
+                        is_error,
+                    } => {
+                        let name = tool_names.get(tool_use_id.as_str()).copied();
+                        let outcome = if is_error.unwrap_or(false) {
+                            "failed"
+                        } else {
+                            "result"
+                        };
+                        ContentBlock::Text {
+                            text: format!(
+                                "[tool {} `{}`]\n{}",
+                                outcome,
+                                name.unwrap_or("unknown"),
+                                content
+                            ),
+                            cache_control: None,
+                        }
+                    }
+                    other => other.clone(),
+                })
+                .collect();
+            Message {
+                content,
+                ..message.clone()
+            }
+        })
+        .collect()
+}
+
+/// Split `text` into the prose outside fenced `tool` blocks and the
+/// `(name, input)` pairs inside them. Blocks that are not valid tool JSON are
+/// left in the text.
+fn parse_tool_blocks(text: &str) -> (String, Vec<(String, serde_json::Value)>) {
+    let mut remaining = String::new();
+    let mut calls = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(TOOL_BLOCK_OPEN) {
+        let after_open = &rest[start + TOOL_BLOCK_OPEN.len()..];
+        let Some(body_start) = after_open.find('\n') else {
+            break;
+        };
+        if !after_open[..body_start].trim().is_empty() {
+            // A longer tag such as ```toolkit; not ours.
+            remaining.push_str(&rest[..start + TOOL_BLOCK_OPEN.len()]);
+            rest = after_open;
+            continue;
+        }
+        let body = &after_open[body_start + 1..];
+        let Some(body_end) = body.find(TOOL_BLOCK_CLOSE) else {
+            break;
+        };
+        match parse_tool_call_json(&body[..body_end]) {
+            Some(call) => {
+                remaining.push_str(&rest[..start]);
+                calls.push(call);
+            }
+            None => {
+                remaining.push_str(&rest[..start + TOOL_BLOCK_OPEN.len() + body_start + 1]);
+                remaining.push_str(&body[..body_end + TOOL_BLOCK_CLOSE.len()]);
+            }
+        }
+        rest = &body[body_end + TOOL_BLOCK_CLOSE.len()..];
+    }
+    remaining.push_str(rest);
+    (remaining.trim().to_string(), calls)
+}
+
+fn parse_tool_call_json(body: &str) -> Option<(String, serde_json::Value)> {
+    let value: serde_json::Value = serde_json::from_str(body.trim()).ok()?;
+    let name = value.get("name")?.as_str()?.trim();
+    if name.is_empty() {
+        return None;
+    }
+    let input = match value.get("input") {
+        Some(input) if input.is_object() => input.clone(),
+        None | Some(serde_json::Value::Null) => serde_json::json!({}),
+        Some(_) => return None,
+    };
+    Some((name.to_string(), input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_tool_blocks_extracts_calls_and_keeps_prose() {
+        let text = "Let me look.\n```tool\n{\"name\": \"read\", \"input\": {\"file_path\": \"a.rs\"}}\n```\n```tool\n{\"name\": \"ls\"}\n```";
+        let (remaining, calls) = parse_tool_blocks(text);
+        assert_eq!(remaining, "Let me look.");
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].0, "read");
+        assert_eq!(calls[0].1["file_path"], "a.rs");
+        assert_eq!(calls[1], ("ls".to_string(), serde_json::json!({})));
+    }
+
+    #[test]
+    fn parse_tool_blocks_leaves_invalid_blocks_in_text() {
+        let text = "```tool\nnot json\n```\n```toolkit\n{\"name\": \"read\"}\n```";
+        let (remaining, calls) = parse_tool_blocks(text);
+        assert!(calls.is_empty());
+        assert_eq!(remaining, text);
+    }
+
+    #[test]
+    fn textual_protocol_messages_flatten_tool_blocks() {
+        let messages = vec![
+            Message {
+                role: Role::Assistant,
+                content: vec![ContentBlock::ToolUse {
+                    id: "call_1".to_string(),
+                    name: "read".to_string(),
+                    input: serde_json::json!({"file_path": "a.rs"}),
+                    thought_signature: None,
+                }],
+                timestamp: None,
+                tool_duration_ms: None,
+            },
+            Message::tool_result("call_1", "fn main() {}", false),
+        ];
+        let flattened = textual_protocol_messages(&messages);
+        let ContentBlock::Text { text, .. } = &flattened[0].content[0] else {
+            panic!("tool use should become text");
+        };
+        let (_, calls) = parse_tool_blocks(text);
+        assert_eq!(calls[0].0, "read");
+        let ContentBlock::Text { text, .. } = &flattened[1].content[0] else {
+            panic!("tool result should become text");
+        };
+        assert_eq!(text, "[tool result `read`]\nfn main() {}");
+    }
+
+    #[test]
+    fn textual_protocol_moves_tools_into_the_prompt() {
+        let mut prompt = crate::prompt::SplitSystemPrompt {
+            static_part: "Base prompt".to_string(),
+            dynamic_part: String::new(),
+            ..Default::default()
+        };
+        let mut tools = vec![ToolDefinition {
+            name: "read".to_string(),
+            description: "Read a file.\nMore detail.".to_string(),
+            input_schema: serde_json::json!({"type": "object"}),
+        }];
+        apply_textual_tool_protocol(&mut prompt, &mut tools);
+        assert!(tools.is_empty());
+        assert!(
+            prompt
+                .static_part
+                .starts_with("Base prompt\n\n# Tools (text protocol)")
+        );
+        assert!(prompt.static_part.contains("- `read`: Read a file.\n"));
+        assert!(!prompt.static_part.contains("More detail."));
+    }
+}
@@ -122,7 +122,7 @@ pub(super) fn print_tool_summary(tool: &ToolCall) {
                 println!("'{}'", pattern);
             }
         }
-        "ls" => {
+        "ls" | "tree" => {
             let path = tool
                 .input
                 .get("path")
@@ -1,4 +1,5 @@
 use super::*;
+use crate::plan::proposal::{PLAN_MODE_TOOLS, PLAN_PROPOSE_TOOL};
 
 impl Agent {
     /// Run a single turn with the given user message
@@ -14,7 +15,14 @@ impl Agent {
         if trace_enabled() {
             eprintln!("[trace] session_id {}", self.session.id);
         }
-        let _ = self.run_turn(true).await?;
+        let start_message_index = self.message_count();
+        let file_tracker = self.track_turn_file_changes();
+        let result = self.run_turn(true).await;
+        if let Some(tracker) = file_tracker {
+            self.auto_commit_turn(tracker, user_message, start_message_index, None)
+                .await;
+        }
+        result?;
         Ok(())
     }
 
@@ -30,7 +38,14 @@ impl Agent {
         if trace_enabled() {
             eprintln!("[trace] session_id {}", self.session.id);
         }
-        self.run_turn(false).await
+        let start_message_index = self.message_count();
+        let file_tracker = self.track_turn_file_changes();
+        let result = self.run_turn(false).await;
+        if let Some(tracker) = file_tracker {
+            self.auto_commit_turn(tracker, user_message, start_message_index, None)
+                .await;
+        }
+        result
     }
 
     /// Run one conversation turn with streaming events via mpsc channel (per-client)
@@ -41,6 +56,11 @@ impl Agent {
         system_reminder: Option<String>,
         event_tx: mpsc::UnboundedSender<ServerEvent>,
     ) -> Result<()> {
+        if self.aside_active() {
+            self.turn_focus = None;
+            return self.run_aside_turn(user_message, images, event_tx).await;
+        }
+
         // Inject any pending notifications before the user message
         let alerts = self.take_alerts();
         if !alerts.is_empty() {
@@ -83,8 +103,21 @@ impl Agent {
         let turn_started_at = Instant::now();
         let start_message_index = self.message_count();
         self.fire_turn_start_hook("chat");
+        let file_tracker = self.track_turn_file_changes();
+        let auto_commit_tx = event_tx.clone();
         let result = self.run_turn_streaming_mpsc(event_tx).await;
         self.current_turn_system_reminder = None;
+        self.turn_focus = None;
+        if let Some(tracker) = file_tracker {
+            self.auto_commit_turn(
+                tracker,
+                user_message,
+                start_message_index,
+                Some(&auto_commit_tx),
+            )
+            .await;
+        }
+        crate::metrics::record_turn_completed(result.is_ok());
         self.fire_turn_end_hook(&result, turn_started_at, start_message_index);
         result
     }
@@ -117,10 +150,19 @@ impl Agent {
         started_at: Instant,
         start_message_index: usize,
     ) {
+        let status = if result.is_ok() { "ok" } else { "error" };
+        crate::hooks::spawn_turn_end_rules(
+            self.session.id.clone(),
+            self.working_dir().map(std::path::PathBuf::from),
+            serde_json::json!({
+                "status": status,
+                "duration_ms": started_at.elapsed().as_millis() as u64,
+                "model": self.provider_model(),
+            }),
+        );
         if !crate::hooks::hook_configured("turn_end") {
             return;
         }
-        let status = if result.is_ok() { "ok" } else { "error" };
         let mut event = crate::hooks::HookEvent::new("turn_end")
             .session_id(self.session.id.clone())
             .field("STATUS", status)
@@ -148,6 +190,9 @@ impl Agent {
         let preserve_testing_build = self.session.testing_build.clone();
         let preserve_debug = self.session.is_debug;
         let preserve_working_dir = self.session.working_dir.clone();
+        let preserve_workspace_roots = self.session.workspace_roots.clone();
+        let preserve_tool_scope = self.session.tool_scope.clone();
+        let preserve_instructions = self.session.instructions.clone();
 
         self.session.mark_closed();
         self.persist_session_best_effort("pre-clear session close state");
@@ -161,6 +206,9 @@ impl Agent {
         new_session.testing_build = preserve_testing_build;
         new_session.is_debug = preserve_debug;
         new_session.working_dir = preserve_working_dir;
+        new_session.workspace_roots = preserve_workspace_roots;
+        new_session.tool_scope = preserve_tool_scope;
+        new_session.instructions = preserve_instructions;
         new_session.ensure_initial_session_context_message();
 
         self.session = new_session;
@@ -196,16 +244,11 @@ impl Agent {
         let removed = message_count - message_index;
         self.rewind_undo_snapshot = Some(RewindUndoSnapshot {
             messages: self.session.messages.clone(),
-            provider_session_id: self.provider_session_id.clone(),
-            session_provider_session_id: self.session.provider_session_id.clone(),
             visible_message_count: message_count,
         });
         self.session.truncate_messages(stored_len);
         self.session.updated_at = chrono::Utc::now();
-        self.provider_session_id = None;
-        self.session.provider_session_id = None;
-        self.cache_tracker.reset();
-        self.locked_tools = None;
+        self.note_history_rewritten();
         self.reset_tool_output_tracking();
         self.persist_session_best_effort("conversation rewind");
         Ok(removed)
@@ -219,11 +262,10 @@ impl Agent {
         let current_count = self.session.visible_conversation_message_count();
         let restored = snapshot.visible_message_count.saturating_sub(current_count);
         self.session.replace_messages(snapshot.messages);
-        self.provider_session_id = snapshot.provider_session_id;
-        self.session.provider_session_id = snapshot.session_provider_session_id;
         self.session.updated_at = chrono::Utc::now();
-        self.cache_tracker.reset();
-        self.locked_tools = None;
+        // The provider-side conversation may have moved on since the rewind, so
+        // the pre-rewind resume id is not restored with the messages.
+        self.note_history_rewritten();
         self.reset_tool_output_tracking();
         self.persist_session_best_effort("conversation rewind undo");
         Ok(restored)
@@ -272,6 +314,12 @@ impl Agent {
         self.system_prompt_override = Some(prompt.to_string());
     }
 
+    /// Append project-specific instructions to the normal system prompt.
+    /// Ignored while a full override from [`Agent::set_system_prompt`] is set.
+    pub fn set_appended_system_prompt(&mut self, instructions: Option<String>) {
+        self.appended_system_prompt = instructions.filter(|text| !text.trim().is_empty());
+    }
+
     pub fn set_debug(&mut self, is_debug: bool) {
         self.session.set_debug(is_debug);
         if let Err(err) = self.session.save() {
@@ -380,10 +428,61 @@ impl Agent {
         if !self.disabled_tools.is_empty() {
             tools.retain(|tool| !self.disabled_tools.contains(&tool.name));
         }
+        if self.plan_mode_active() {
+            tools.retain(|tool| PLAN_MODE_TOOLS.contains(&tool.name.as_str()));
+        } else {
+            tools.retain(|tool| tool.name != PLAN_PROPOSE_TOOL);
+        }
+        self.hide_safe_mode_tools(&mut tools);
         Self::apply_selfdev_tool_surface(&mut tools, self.session.is_canary);
         tools
     }
 
+    /// Publish this session's tool policy so `Registry::execute` enforces the
+    /// same restrictions as the advertised tool list, including plan mode and
+    /// safe mode.
+    pub(super) fn sync_session_tool_policy(&self) {
+        let allowed_tools = if self.plan_mode_active() {
+            Some(
+                PLAN_MODE_TOOLS
+                    .iter()
+                    .map(|name| name.to_string())
+                    .filter(|name| {
+                        self.allowed_tools
+                            .as_ref()
+                            .is_none_or(|allowed| allowed.contains(name))
+                    })
+                    .collect(),
+            )
+        } else {
+            self.allowed_tools.clone()
+        };
+        let mut disabled_tools = self.disabled_tools.clone();
+        if self.session.safe_mode {
+            disabled_tools.extend(
+                super::safe_mode::SAFE_MODE_HIDDEN_TOOLS
+                    .iter()
+                    .map(|name| name.to_string()),
+            );
+        }
+        crate::tool::set_session_tool_policy(
+            &self.session.id,
+            allowed_tools,
+            disabled_tools,
+            self.session.safe_mode,
+            self.approval_gate,
+        );
+    }
+
+    /// Reconcile the project todos with the configured `[todo] sync_file`, so
+    /// edits made by hand to that file are picked up at session start and at
+    /// each turn boundary.
+    pub(super) fn sync_project_todo_file(&self) {
+        if let Some(project_dir) = self.working_dir() {
+            crate::todo_sync::sync_configured(std::path::Path::new(project_dir));
+        }
+    }
+
     /// Tailor the `selfdev` tool definition to the session mode.
     ///
     /// The registry stores a single shared `selfdev` tool with a default
@@ -410,6 +509,7 @@ impl Agent {
         registry_names.iter().any(|name| {
             name.starts_with("mcp__")
                 && allowed.map(|set| set.contains(name)).unwrap_or(true)
+                && !self.plan_mode_active()
                 && !self.disabled_tools.contains(name)
                 && !locked.iter().any(|t| &t.name == name)
         })
@@ -432,6 +532,12 @@ impl Agent {
         if !self.disabled_tools.is_empty() {
             tools.retain(|tool| !self.disabled_tools.contains(&tool.name));
         }
+        if self.plan_mode_active() {
+            tools.retain(|tool| PLAN_MODE_TOOLS.contains(&tool.name.as_str()));
+        } else {
+            tools.retain(|tool| tool.name != PLAN_PROPOSE_TOOL);
+        }
+        self.hide_safe_mode_tools(&mut tools);
         Self::apply_selfdev_tool_surface(&mut tools, self.session.is_canary);
         tools
     }
@@ -451,7 +557,8 @@ impl Agent {
             session_id: self.session.id.clone(),
             message_id: self.session.id.clone(),
             tool_call_id: call_id,
-            working_dir: self.working_dir().map(PathBuf::from),
+            working_dir: self.tool_working_dir().map(PathBuf::from),
+            workspace_roots: self.workspace_roots(),
             stdin_request_tx: self.stdin_request_tx.clone(),
             graceful_shutdown_signal: Some(self.graceful_shutdown.clone()),
             execution_mode: ToolExecutionMode::Direct,
@@ -542,11 +649,8 @@ impl Agent {
         self.provider_session_id = session.provider_session_id.clone();
         self.session = session;
         crate::tool::clear_session_tool_policy(&previous_session_id);
-        crate::tool::set_session_tool_policy(
-            &self.session.id,
-            self.allowed_tools.clone(),
-            self.disabled_tools.clone(),
-        );
+        self.sync_session_tool_policy();
+        self.sync_project_todo_file();
         let assign_ms = assign_start.elapsed().as_millis();
 
         let reset_start = Instant::now();
@@ -555,25 +659,10 @@ impl Agent {
         let reset_ms = reset_start.elapsed().as_millis();
 
         let model_start = Instant::now();
-        if let Some(model) = self.session.model.clone() {
-            let model_request =
-                crate::provider::MultiProvider::model_switch_request_for_session_route(
-                    &model,
-                    self.session.provider_key.as_deref(),
-                    self.session.route_api_method.as_deref(),
-                );
-            if let Err(e) =
-                crate::provider::set_model_with_auth_refresh(self.provider.as_ref(), &model_request)
-            {
-                logging::error(&format!(
-                    "Failed to restore session model '{}' via '{}': {}",
-                    model, model_request, e
-                ));
-            }
-        } else {
-            self.session.model = Some(self.provider.model());
-        }
+        self.restore_model_route_from_session();
         self.restore_reasoning_effort_from_session();
+        self.restore_profile_from_session();
+        self.apply_safe_mode();
         let model_ms = model_start.elapsed().as_millis();
 
         let mark_active_start = Instant::now();
@@ -639,6 +728,7 @@ impl Agent {
                     Some(msg.tool_calls)
                 },
                 tool_data: msg.tool_data,
+                usage: msg.usage,
             })
             .collect()
     }
@@ -658,6 +748,7 @@ impl Agent {
                     Some(msg.tool_calls)
                 },
                 tool_data: msg.tool_data,
+                usage: msg.usage,
             })
             .collect();
         (history, images)
@@ -687,6 +778,7 @@ impl Agent {
                     Some(msg.tool_calls)
                 },
                 tool_data: msg.tool_data,
+                usage: msg.usage,
             })
             .collect();
         (history, images, compacted_info)
@@ -825,6 +917,13 @@ impl Agent {
                     ContentBlock::OpenAICompaction { .. } => {
                         transcript.push_str("[OpenAI native compaction]\n");
                     }
+                    ContentBlock::ServerToolUse { name, .. } => {
+                        transcript.push_str(&format!("[Used tool: {}]\n", name));
+                    }
+                    ContentBlock::WebSearchResult { results, .. } => {
+                        transcript.push_str(&format!("[Web search: {} results]\n", results.len()));
+                    }
+                    ContentBlock::Citations { .. } => {}
                 }
             }
             transcript.push('\n');
@@ -836,7 +935,7 @@ impl Agent {
         }
 
         // Extract using sidecar
-        let sidecar = crate::sidecar::Sidecar::new();
+        let sidecar = crate::sidecar::Sidecar::new().for_session(self.session.id.clone());
         match sidecar.extract_memories(&transcript).await {
             Ok(extracted) if !extracted.is_empty() => {
                 let manager = self
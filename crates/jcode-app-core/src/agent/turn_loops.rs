@@ -2,20 +2,27 @@ use super::*;
 
 impl Agent {
     /// Run turns until no more tool calls
-    /// Maximum number of context-limit compaction retries before giving up.
-    pub(super) const MAX_CONTEXT_LIMIT_RETRIES: u32 = 5;
+    /// Maximum number of context-limit recoveries (compaction or tool-result
+    /// trimming followed by a retry) per API call before giving up.
+    pub(super) const MAX_CONTEXT_LIMIT_RETRIES: u32 = 1;
     pub(super) const MAX_INCOMPLETE_CONTINUATION_ATTEMPTS: u32 = 3;
     pub(super) const MAX_EMPTY_POST_TOOL_CONTINUATION_ATTEMPTS: u32 = 1;
 
     pub(super) async fn run_turn(&mut self, print_output: bool) -> Result<String> {
         self.set_log_context();
         crate::session_metrics::record_turn(&self.session.id);
+        self.sync_project_todo_file();
+        self.refresh_turn_git_context();
+        self.begin_agent_limits();
         // Mark this session as actively streaming for presence UIs (e.g. the
         // macOS menu bar indicator). Cleared automatically on every exit path.
         let _streaming_guard = crate::session::StreamingGuard::new(self.session.id.clone());
         let mut final_text = String::new();
         let trace = trace_enabled();
         let mut context_limit_retries = 0u32;
+        let mut rate_limit_retries = 0u32;
+        let mut stream_stall_retries = 0u32;
+        let mut retired_model_switches = 0u32;
         let mut incomplete_continuations = 0u32;
         let mut empty_post_tool_continuations = 0u32;
 
@@ -27,7 +34,8 @@ impl Agent {
                     repaired
                 ));
             }
-            let (messages, compaction_event) = self.messages_for_provider();
+            let (mut messages, compaction_event) = self.messages_for_provider();
+            let pruned_tokens = self.prune_outbound_context(&mut messages);
             if let Some(event) = compaction_event {
                 // Reset cache tracker and tool lock on compaction since the message history changes
                 self.cache_tracker.reset();
@@ -41,14 +49,26 @@ impl Agent {
                 }
             }
 
-            let tools = self.tool_definitions().await;
+            let mut tools = self.tool_definitions().await;
+            let tool_protocol = self.tool_protocol(&tools);
+            if tool_protocol == ToolProtocol::Refuse {
+                return Err(self.tool_less_model_error());
+            }
             let messages: std::sync::Arc<[Message]> = messages.into();
             // Non-blocking memory: uses pending result from last turn, spawns check for next turn
             let memory_pending =
                 self.build_memory_prompt_nonblocking_shared(std::sync::Arc::clone(&messages), None);
+            if self.refresh_auto_skills() && print_output {
+                println!("Skills auto-loaded: {}", self.auto_skill_names().join(", "));
+            }
             // Use split prompt for better caching - static content cached, dynamic not
-            let split_prompt = self.build_system_prompt_split(None);
+            let mut split_prompt = self.build_system_prompt_split(None);
+            let memory_pending = self.place_memory_section(memory_pending, &mut split_prompt);
+            if tool_protocol == ToolProtocol::Textual {
+                apply_textual_tool_protocol(&mut split_prompt, &mut tools);
+            }
             self.log_prompt_prefix_accounting(&split_prompt, &tools);
+            self.remember_system_prompt(&split_prompt);
 
             // Check for client-side cache violations before memory injection.
             // Memory is an ephemeral suffix that changes each turn; tracking it would cause
@@ -57,17 +77,19 @@ impl Agent {
 
             // Inject memory as a user message at the end (preserves cache prefix)
             let mut messages_with_memory: Vec<Message> = messages.iter().cloned().collect();
-            if let Some(memory) = memory_pending.as_ref() {
+            if let Some((memory, as_message)) = memory_pending.as_ref() {
                 let memory_count = memory.count.max(1);
                 let age_ms = memory.computed_at.elapsed().as_millis() as u64;
                 crate::memory::record_injected_prompt(&memory.prompt, memory_count, age_ms);
                 self.record_memory_injection_in_session(memory);
-                logging::info(&format!(
-                    "Memory injected as message ({} chars)",
-                    memory.prompt.len()
-                ));
-                let (memory_msg, _persisted) = self.prepare_memory_injection_message(memory);
-                messages_with_memory.push(memory_msg);
+                if *as_message {
+                    logging::info(&format!(
+                        "Memory injected as message ({} chars)",
+                        memory.prompt.len()
+                    ));
+                    let (memory_msg, _persisted) = self.prepare_memory_injection_message(memory);
+                    messages_with_memory.push(memory_msg);
+                }
             }
 
             logging::info(&format!(
@@ -75,6 +97,7 @@ impl Agent {
                 messages_with_memory.len(),
                 tools.len()
             ));
+            self.note_agent_limit_turn();
             let api_start = Instant::now();
 
             // Publish status for TUI to show during Task execution
@@ -91,7 +114,34 @@ impl Agent {
             } else {
                 &messages_with_memory
             };
+            let textual_messages;
+            let send_messages: &[Message] = if tool_protocol == ToolProtocol::Textual {
+                textual_messages = textual_protocol_messages(send_messages);
+                &textual_messages
+            } else {
+                send_messages
+            };
+            if context_limit_retries < Self::MAX_CONTEXT_LIMIT_RETRIES
+                && let Some(recovery) =
+                    self.preflight_context_recovery(send_messages, &split_prompt, &tools)
+            {
+                context_limit_retries += 1;
+                if print_output {
+                    println!("📦 {}", recovery.status);
+                }
+                continue;
+            }
             let prompt_has_recent_tool_result = Self::messages_end_with_tool_result(send_messages);
+            // A response still generating server-side from before a restart is
+            // re-attached to only when this request is the one it answers.
+            if let Some(response_id) = self.session.take_resumable_background_response() {
+                self.provider.restore_background_response(&response_id);
+            }
+            let background_anchor = self
+                .session
+                .messages
+                .last()
+                .map(|message| message.id.clone());
             self.last_status_detail = None;
             let mut stream = match self
                 .provider
@@ -104,22 +154,28 @@ impl Agent {
                 )
                 .await
             {
-                Ok(stream) => stream,
+                Ok(stream) => self.watch_provider_stream(stream),
                 Err(e) => {
-                    if self.try_auto_compact_after_context_limit(&e.to_string()) {
+                    if context_limit_retries < Self::MAX_CONTEXT_LIMIT_RETRIES
+                        && let Some(recovery) =
+                            self.try_auto_compact_after_context_limit(&e.to_string())
+                    {
                         context_limit_retries += 1;
-                        if context_limit_retries > Self::MAX_CONTEXT_LIMIT_RETRIES {
-                            logging::warn(
-                                "Context-limit compaction retry limit reached; giving up",
-                            );
-                            return Err(anyhow::anyhow!(
-                                "Context limit exceeded after {} compaction retries",
-                                Self::MAX_CONTEXT_LIMIT_RETRIES
-                            ));
+                        if print_output {
+                            println!("📦 {}", recovery.status);
+                        }
+                        continue;
+                    }
+                    if retired_model_switches < Self::MAX_RETIRED_MODEL_SWITCHES
+                        && let Some(fallback) = self.switch_from_retired_model(&e.to_string())
+                    {
+                        retired_model_switches += 1;
+                        if print_output {
+                            println!("⚠ Model retired: {}", fallback.summary());
                         }
                         continue;
                     }
-                    return Err(e);
+                    return Err(self.context_limit_failure(e));
                 }
             };
 
@@ -145,6 +201,7 @@ impl Agent {
             }));
 
             let mut text_content = String::new();
+            let mut text_cursor = crate::message::StreamTextCursor::default();
             let mut tool_calls: Vec<ToolCall> = Vec::new();
             let mut current_tool: Option<ToolCall> = None;
             let mut current_tool_input = String::new();
@@ -162,18 +219,28 @@ impl Agent {
             let mut reasoning_content = String::new();
             let mut reasoning_signature = String::new();
             let mut openai_reasoning_items: Vec<ContentBlock> = Vec::new();
+            // Provider-executed (server-side) tool calls/results and citations
+            let mut server_tool_blocks: Vec<ContentBlock> = Vec::new();
+            let mut citations: Vec<crate::message::Citation> = Vec::new();
+            let mut usage_web_search: Option<u64> = None;
             // Track tool results from provider (already executed by Claude Code CLI)
             let mut sdk_tool_results: std::collections::HashMap<String, (String, bool)> =
                 std::collections::HashMap::new();
             let mut openai_native_compaction: Option<(String, usize)> = None;
 
             let mut retry_after_compaction = false;
+            let mut pending_rate_limit_wait: Option<Duration> = None;
+            let mut retry_after_stall = false;
+            let mut retry_after_model_switch = false;
             while let Some(event) = stream.next().await {
                 let event = match event {
                     Ok(event) => event,
                     Err(e) => {
                         let err_str = e.to_string();
-                        if self.try_auto_compact_after_context_limit(&err_str) {
+                        if context_limit_retries < Self::MAX_CONTEXT_LIMIT_RETRIES
+                            && let Some(recovery) =
+                                self.try_auto_compact_after_context_limit(&err_str)
+                        {
                             log_agent_provider_stream_lifecycle(
                                 logging::LogLevel::Warn,
                                 self,
@@ -189,18 +256,24 @@ impl Agent {
                                 ],
                             );
                             context_limit_retries += 1;
-                            if context_limit_retries > Self::MAX_CONTEXT_LIMIT_RETRIES {
-                                logging::warn(
-                                    "Context-limit compaction retry limit reached; giving up",
-                                );
-                                return Err(anyhow::anyhow!(
-                                    "Context limit exceeded after {} compaction retries",
-                                    Self::MAX_CONTEXT_LIMIT_RETRIES
-                                ));
+                            if print_output {
+                                println!("📦 {}", recovery.status);
                             }
                             retry_after_compaction = true;
                             break;
                         }
+                        if text_content.is_empty()
+                            && tool_calls.is_empty()
+                            && retired_model_switches < Self::MAX_RETIRED_MODEL_SWITCHES
+                            && let Some(fallback) = self.switch_from_retired_model(&err_str)
+                        {
+                            retired_model_switches += 1;
+                            if print_output {
+                                println!("⚠ Model retired: {}", fallback.summary());
+                            }
+                            retry_after_model_switch = true;
+                            break;
+                        }
                         log_agent_provider_stream_lifecycle(
                             logging::LogLevel::Error,
                             self,
@@ -208,10 +281,14 @@ impl Agent {
                             api_start,
                             vec![("mode", "blocking".to_string()), ("error", err_str)],
                         );
-                        return Err(e);
+                        return Err(self.context_limit_failure(e));
                     }
                 };
 
+                // Drop text a reconnected provider stream sent again.
+                let Some(event) = text_cursor.normalize(event) else {
+                    continue;
+                };
                 match event {
                     StreamEvent::ThinkingStart => {
                         // Track start but don't print - wait for ThinkingDone
@@ -451,6 +528,8 @@ impl Agent {
                         reasoning_content.clear();
                         reasoning_signature.clear();
                         openai_reasoning_items.clear();
+                        server_tool_blocks.clear();
+                        citations.clear();
                         openai_native_compaction = None;
                         saw_message_end = false;
                         stop_reason = None;
@@ -459,9 +538,14 @@ impl Agent {
                         stop_reason: reason,
                     } => {
                         saw_message_end = true;
+                        self.session.clear_background_response();
                         if reason.is_some() {
                             stop_reason = reason;
                         }
+                        if print_output && !citations.is_empty() {
+                            println!("{}", crate::message::citation_sources_markdown(&citations));
+                            io::stdout().flush()?;
+                        }
                         // Don't break yet - wait for SessionId which comes after MessageEnd
                         // (but stream close will also end the loop for providers without SessionId)
                     }
@@ -476,6 +560,11 @@ impl Agent {
                             break;
                         }
                     }
+                    StreamEvent::BackgroundResponse { response_id } => {
+                        self.session
+                            .set_background_response(response_id, background_anchor.clone());
+                        self.persist_session_best_effort("background response id");
+                    }
                     StreamEvent::UpstreamProvider { provider } => {
                         // Log upstream provider for local trace output
                         if trace {
@@ -483,6 +572,15 @@ impl Agent {
                         }
                         self.last_upstream_provider = Some(provider);
                     }
+                    StreamEvent::SystemFingerprint(fingerprint) => {
+                        if trace {
+                            eprintln!("[trace] system_fingerprint={}", fingerprint);
+                        }
+                        self.last_system_fingerprint = Some(fingerprint);
+                    }
+                    StreamEvent::TextDeltaAt { .. } => {
+                        // Rewritten to TextDelta by text_cursor above
+                    }
                     StreamEvent::OpenAIReasoning {
                         id,
                         summary,
@@ -530,16 +628,16 @@ impl Agent {
                             session_id: self.session.id.clone(),
                             message_id: self.session.id.clone(),
                             tool_call_id: request_id.clone(),
-                            working_dir: self.working_dir().map(PathBuf::from),
+                            working_dir: self.tool_working_dir().map(PathBuf::from),
+                            workspace_roots: self.workspace_roots(),
                             stdin_request_tx: self.stdin_request_tx.clone(),
                             graceful_shutdown_signal: Some(self.graceful_shutdown.clone()),
                             execution_mode: ToolExecutionMode::AgentTurn,
                         };
                         crate::telemetry::record_tool_call();
-                        let tool_result = self
-                            .registry
-                            .execute(&tool_name, ToolCall::normalize_input_to_object(input), ctx)
-                            .await;
+                        let mut input = ToolCall::normalize_input_to_object(input);
+                        self.reveal_redacted_input(&mut input);
+                        let tool_result = self.registry.execute(&tool_name, input, ctx).await;
                         if tool_result.is_err() {
                             crate::telemetry::record_tool_failure();
                         }
@@ -552,6 +650,46 @@ impl Agent {
                             let _ = sender.send(native_result).await;
                         }
                     }
+                    StreamEvent::ServerToolUse { id, name, input } => {
+                        if trace {
+                            eprintln!("\n[trace] server_tool_use name={} id={}", name, id);
+                        }
+                        if print_output {
+                            println!("\n[{}] {}", name, input);
+                            io::stdout().flush()?;
+                        }
+                        if !text_content.is_empty() && !text_content.ends_with('\n') {
+                            text_content.push_str("\n\n");
+                        }
+                        server_tool_blocks.push(ContentBlock::ServerToolUse { id, name, input });
+                    }
+                    StreamEvent::WebSearchResult {
+                        tool_use_id,
+                        results,
+                        error_code,
+                    } => {
+                        if trace {
+                            eprintln!(
+                                "[trace] web_search_result id={} results={} error={:?}",
+                                tool_use_id,
+                                results.len(),
+                                error_code
+                            );
+                        }
+                        server_tool_blocks.push(ContentBlock::WebSearchResult {
+                            tool_use_id,
+                            results,
+                            error_code,
+                        });
+                    }
+                    StreamEvent::Citation(citation) => {
+                        citations.push(citation);
+                    }
+                    StreamEvent::ServerToolUsage {
+                        web_search_requests,
+                    } => {
+                        usage_web_search = Some(web_search_requests);
+                    }
                     StreamEvent::Error {
                         message,
                         retry_after_secs,
@@ -559,7 +697,10 @@ impl Agent {
                         if trace {
                             eprintln!("[trace] stream_error {}", message);
                         }
-                        if self.try_auto_compact_after_context_limit(&message) {
+                        if context_limit_retries < Self::MAX_CONTEXT_LIMIT_RETRIES
+                            && let Some(recovery) =
+                                self.try_auto_compact_after_context_limit(&message)
+                        {
                             log_agent_provider_stream_lifecycle(
                                 logging::LogLevel::Warn,
                                 self,
@@ -575,18 +716,86 @@ impl Agent {
                                 ],
                             );
                             context_limit_retries += 1;
-                            if context_limit_retries > Self::MAX_CONTEXT_LIMIT_RETRIES {
-                                logging::warn(
-                                    "Context-limit compaction retry limit reached; giving up",
-                                );
-                                return Err(anyhow::anyhow!(
-                                    "Context limit exceeded after {} compaction retries",
-                                    Self::MAX_CONTEXT_LIMIT_RETRIES
-                                ));
+                            if print_output {
+                                println!("📦 {}", recovery.status);
                             }
                             retry_after_compaction = true;
                             break;
                         }
+                        if crate::provider::is_stream_idle_timeout(&message) {
+                            if !text_content.is_empty() || !tool_calls.is_empty() {
+                                log_agent_provider_stream_lifecycle(
+                                    logging::LogLevel::Warn,
+                                    self,
+                                    "stream_stalled_mid_response",
+                                    api_start,
+                                    vec![
+                                        ("mode", "blocking".to_string()),
+                                        ("error", message.clone()),
+                                    ],
+                                );
+                                stop_reason = Some(Self::STREAM_STALL_STOP_REASON.to_string());
+                                break;
+                            }
+                            if stream_stall_retries < Self::MAX_STREAM_STALL_RETRIES {
+                                stream_stall_retries += 1;
+                                log_agent_provider_stream_lifecycle(
+                                    logging::LogLevel::Warn,
+                                    self,
+                                    "stream_stalled_retry",
+                                    api_start,
+                                    vec![
+                                        ("mode", "blocking".to_string()),
+                                        ("error", message.clone()),
+                                        ("stream_stall_retries", stream_stall_retries.to_string()),
+                                    ],
+                                );
+                                retry_after_stall = true;
+                                break;
+                            }
+                        }
+                        if text_content.is_empty()
+                            && tool_calls.is_empty()
+                            && let Some(wait) =
+                                self.rate_limit_wait(retry_after_secs, rate_limit_retries)
+                        {
+                            rate_limit_retries += 1;
+                            log_agent_provider_stream_lifecycle(
+                                logging::LogLevel::Warn,
+                                self,
+                                "stream_event_rate_limited",
+                                api_start,
+                                vec![
+                                    ("mode", "blocking".to_string()),
+                                    ("error", message.clone()),
+                                    ("retry_after_secs", wait.as_secs().to_string()),
+                                    ("rate_limit_retries", rate_limit_retries.to_string()),
+                                ],
+                            );
+                            if print_output {
+                                println!(
+                                    "⏳ {} rate limited; retrying in {}s (attempt {}/{})",
+                                    self.provider.display_name(),
+                                    wait.as_secs(),
+                                    rate_limit_retries,
+                                    crate::provider::MAX_RATE_LIMIT_WAITS
+                                );
+                            }
+                            pending_rate_limit_wait = Some(wait);
+                            break;
+                        }
+                        if text_content.is_empty()
+                            && tool_calls.is_empty()
+                            && retired_model_switches < Self::MAX_RETIRED_MODEL_SWITCHES
+                            && let Some(fallback) = self.switch_from_retired_model(&message)
+                        {
+                            retired_model_switches += 1;
+                            if print_output {
+                                println!("⚠ Model retired: {}", fallback.summary());
+                            }
+                            retry_after_model_switch = true;
+                            break;
+                        }
                         log_agent_provider_stream_lifecycle(
                             logging::LogLevel::Error,
                             self,
@@ -603,11 +812,24 @@ impl Agent {
                                 ),
                             ],
                         );
-                        return Err(StreamError::new(message, retry_after_secs).into());
+                        return Err(self.context_limit_failure(
+                            StreamError::new(message, retry_after_secs).into(),
+                        ));
                     }
                 }
             }
 
+            if let Some(wait) = pending_rate_limit_wait {
+                if !self.sleep_rate_limit_wait(wait, None).await {
+                    return Ok(final_text);
+                }
+                continue;
+            }
+
+            if retry_after_stall || retry_after_model_switch {
+                continue;
+            }
+
             if retry_after_compaction {
                 log_agent_provider_stream_lifecycle(
                     logging::LogLevel::Info,
@@ -618,6 +840,8 @@ impl Agent {
                 );
                 continue;
             }
+            rate_limit_retries = 0;
+            stream_stall_retries = 0;
 
             let api_elapsed = api_start.elapsed();
             logging::info(&format!(
@@ -654,6 +878,21 @@ impl Agent {
                     usage_cache_read,
                     usage_cache_creation,
                 );
+                crate::metrics::record_tokens(
+                    self.provider.name(),
+                    &self.provider.model(),
+                    usage_input.unwrap_or(0),
+                    usage_output.unwrap_or(0),
+                );
+
+                crate::usage::record_origin_usage(
+                    &self.usage_origin,
+                    crate::usage::OriginUsage::new(
+                        1,
+                        usage_input.unwrap_or(0),
+                        usage_output.unwrap_or(0),
+                    ),
+                );
             }
 
             if print_output
@@ -687,8 +926,13 @@ impl Agent {
                 output_tokens: usage_output.unwrap_or(0),
                 cache_read_input_tokens: usage_cache_read,
                 cache_creation_input_tokens: usage_cache_creation,
+                web_search_requests: usage_web_search,
             };
+            self.record_prompt_cache_usage();
 
+            if tool_protocol == ToolProtocol::Textual {
+                self.recover_textual_tool_calls(&mut text_content, &mut tool_calls);
+            }
             self.recover_text_wrapped_tool_call(&mut text_content, &mut tool_calls);
 
             let visible_text_is_empty = text_content.trim().is_empty();
@@ -722,6 +966,11 @@ impl Agent {
                     thought_signature: tc.thought_signature.clone(),
                 });
             }
+            crate::message::attach_server_tool_blocks(
+                &mut content_blocks,
+                &server_tool_blocks,
+                &citations,
+            );
 
             let assistant_message_id = if !content_blocks.is_empty() {
                 crate::telemetry::record_assistant_response();
@@ -730,6 +979,13 @@ impl Agent {
                     output_tokens: self.last_usage.output_tokens,
                     cache_read_input_tokens: self.last_usage.cache_read_input_tokens,
                     cache_creation_input_tokens: self.last_usage.cache_creation_input_tokens,
+                    web_search_requests: self.last_usage.web_search_requests,
+                    pruned_context_tokens: (pruned_tokens > 0).then_some(pruned_tokens),
+                    redactions: self.take_pending_redactions(),
+                    model: Some(self.provider.model()),
+                    provider: Some(self.provider.name().to_string()),
+                    duration_ms: Some(api_elapsed.as_millis() as u64),
+                    determinism: self.take_response_determinism(),
                 });
                 let message_id =
                     self.add_message_ext(Role::Assistant, content_blocks, None, token_usage);
@@ -844,9 +1100,19 @@ impl Agent {
                 logging::info("Provider handles tools internally - executing native tools locally");
             }
 
+            // The model already had its wrap-up reply after a limit breach.
+            if let Some(error) = self.agent_limit_stop(&tool_calls) {
+                return Err(error);
+            }
+
             // Execute tools and add results
             let mut tool_results_dirty = false;
             for tc in tool_calls {
+                if self.agent_tool_budget_exhausted() {
+                    self.push_agent_limit_skipped_result(tc.id);
+                    tool_results_dirty = true;
+                    continue;
+                }
                 let message_id = assistant_message_id
                     .clone()
                     .unwrap_or_else(|| self.session.id.clone());
@@ -944,7 +1210,8 @@ impl Agent {
                     session_id: self.session.id.clone(),
                     message_id: message_id.clone(),
                     tool_call_id: tc.id.clone(),
-                    working_dir: self.working_dir().map(PathBuf::from),
+                    working_dir: self.tool_working_dir().map(PathBuf::from),
+                    workspace_roots: self.workspace_roots(),
                     stdin_request_tx: self.stdin_request_tx.clone(),
                     graceful_shutdown_signal: Some(self.graceful_shutdown.clone()),
                     execution_mode: ToolExecutionMode::AgentTurn,
@@ -972,8 +1239,11 @@ impl Agent {
                     model: Some(self.provider.model()),
                 }));
 
-                let result = self.registry.execute(&tc.name, tc.input.clone(), ctx).await;
+                let mut input = tc.input.clone();
+                self.reveal_redacted_input(&mut input);
+                let result = self.registry.execute(&tc.name, input, ctx).await;
                 crate::telemetry::record_tool_call();
+                self.note_agent_limit_tool_call();
                 self.unlock_tools_if_needed(&tc.name);
                 let tool_elapsed = tool_start.elapsed();
                 logging::info(&format!(
@@ -985,6 +1255,9 @@ impl Agent {
                 match result {
                     Ok(output) => {
                         let output = cap_tool_output_for_history(&tc.name, output);
+                        if tc.name == crate::plan::proposal::PLAN_PROPOSE_TOOL {
+                            self.record_plan_proposal(output.metadata.as_ref());
+                        }
                         Bus::global().publish(BusEvent::ToolUpdated(ToolEvent {
                             session_id: self.session.id.clone(),
                             message_id: message_id.clone(),
@@ -1028,7 +1301,9 @@ impl Agent {
                             title: None,
                         }));
 
-                        let error_msg = format!("Error: {}", e);
+                        let error_msg = self
+                            .session
+                            .explain_missing_dir_error(format!("Error: {}", e));
                         if trace {
                             eprintln!(
                                 "[trace] tool_exec_error name={} id={} {}",
@@ -1038,6 +1313,7 @@ impl Agent {
                         if print_output {
                             println!("{}", error_msg);
                         }
+                        self.note_tool_failure(&tc, &error_msg);
                         self.add_message_with_duration(
                             Role::User,
                             vec![ContentBlock::ToolResult {
@@ -1067,6 +1343,8 @@ impl Agent {
                 println!();
             }
 
+            self.queue_agent_limit_wrap_up();
+
             // Check for soft interrupts (e.g. Telegram messages) and inject them for the next turn
             let injected = self.inject_soft_interrupts();
             if !injected.is_empty() {
@@ -1079,6 +1357,11 @@ impl Agent {
             }
         }
 
+        if !self.is_graceful_shutdown()
+            && let Some(error) = self.agent_limit_stop(&[])
+        {
+            return Err(error);
+        }
         Ok(final_text)
     }
 
@@ -104,14 +104,20 @@ impl Agent {
         event_tx: mpsc::UnboundedSender<ServerEvent>,
     ) -> Result<()> {
         self.set_log_context();
+        self.sync_project_todo_file();
+        self.refresh_turn_git_context();
+        self.begin_agent_limits();
         // Mark this session as actively streaming for presence UIs (e.g. the
         // macOS menu bar indicator). Cleared automatically on every exit path.
         let _streaming_guard = crate::session::StreamingGuard::new(self.session.id.clone());
         let trace = trace_enabled();
         let mut context_limit_retries = 0u32;
+        let mut rate_limit_retries = 0u32;
+        let mut stream_stall_retries = 0u32;
+        let mut retired_model_switches = 0u32;
         let mut incomplete_continuations = 0u32;
 
-        loop {
+        'turn: loop {
             let repaired = self.repair_missing_tool_outputs();
             if repaired > 0 {
                 logging::warn(&format!(
@@ -119,7 +125,8 @@ impl Agent {
                     repaired
                 ));
             }
-            let (messages, compaction_event) = self.messages_for_provider();
+            let (mut messages, compaction_event) = self.messages_for_provider();
+            let pruned_tokens = self.prune_outbound_context(&mut messages);
             if let Some(event) = compaction_event {
                 // Reset cache tracker and tool lock on compaction since the message history changes
                 self.cache_tracker.reset();
@@ -142,10 +149,15 @@ impl Agent {
                     messages_compacted: event.messages_compacted,
                     summary_chars: event.summary_chars,
                     active_messages: event.active_messages,
+                    tool_results_trimmed: event.tool_results_trimmed,
                 });
             }
 
-            let tools = self.tool_definitions().await;
+            let mut tools = self.tool_definitions().await;
+            let tool_protocol = self.tool_protocol(&tools);
+            if tool_protocol == ToolProtocol::Refuse {
+                return Err(self.tool_less_model_error());
+            }
             let messages: std::sync::Arc<[Message]> = messages.into();
             // Non-blocking memory: uses pending result from last turn, spawns check for next turn
             let memory_pending = self.build_memory_prompt_nonblocking_shared(
@@ -157,9 +169,17 @@ impl Agent {
                     }
                 })),
             );
+            if self.refresh_auto_skills() {
+                self.send_active_skills(&event_tx);
+            }
             // Use split prompt for better caching - static content cached, dynamic not
-            let split_prompt = self.build_system_prompt_split(None);
+            let mut split_prompt = self.build_system_prompt_split(None);
+            let memory_pending = self.place_memory_section(memory_pending, &mut split_prompt);
+            if tool_protocol == ToolProtocol::Textual {
+                apply_textual_tool_protocol(&mut split_prompt, &mut tools);
+            }
             self.log_prompt_prefix_accounting(&split_prompt, &tools);
+            self.remember_system_prompt(&split_prompt);
 
             // Check for client-side cache violations before memory injection.
             // Memory is an ephemeral suffix that changes each turn; tracking it would cause
@@ -176,7 +196,7 @@ impl Agent {
 
             // Inject memory as a user message at the end (preserves cache prefix)
             let mut messages_with_memory: Vec<Message> = messages.iter().cloned().collect();
-            if let Some(memory) = memory_pending.as_ref() {
+            if let Some((memory, as_message)) = memory_pending.as_ref() {
                 let memory_count = memory.count.max(1);
                 let computed_age_ms = memory.computed_at.elapsed().as_millis() as u64;
                 crate::memory::record_injected_prompt(
@@ -191,14 +211,17 @@ impl Agent {
                     display_prompt: memory.display_prompt.clone(),
                     prompt_chars: memory.prompt.chars().count(),
                     computed_age_ms,
+                    report: memory.report.clone(),
                 });
-                let (memory_msg, persisted) = self.prepare_memory_injection_message(memory);
-                if !persisted {
-                    ephemeral_signature_messages.push(memory_msg.clone());
-                } else {
-                    cache_signature_messages.push(memory_msg.clone());
+                if *as_message {
+                    let (memory_msg, persisted) = self.prepare_memory_injection_message(memory);
+                    if !persisted {
+                        ephemeral_signature_messages.push(memory_msg.clone());
+                    } else {
+                        cache_signature_messages.push(memory_msg.clone());
+                    }
+                    messages_with_memory.push(memory_msg);
                 }
-                messages_with_memory.push(memory_msg);
             }
 
             logging::info(&format!(
@@ -206,6 +229,7 @@ impl Agent {
                 messages_with_memory.len(),
                 tools.len()
             ));
+            self.note_agent_limit_turn();
             let api_start = Instant::now();
 
             let stamped;
@@ -215,6 +239,21 @@ impl Agent {
             } else {
                 &messages_with_memory
             };
+            let textual_messages;
+            let send_messages: &[Message] = if tool_protocol == ToolProtocol::Textual {
+                textual_messages = textual_protocol_messages(send_messages);
+                &textual_messages
+            } else {
+                send_messages
+            };
+            if context_limit_retries < Self::MAX_CONTEXT_LIMIT_RETRIES
+                && let Some(recovery) =
+                    self.preflight_context_recovery(send_messages, &split_prompt, &tools)
+            {
+                context_limit_retries += 1;
+                let _ = event_tx.send(recovery.server_event());
+                continue;
+            }
             let provider = Arc::clone(&self.provider);
             // Capture the model id the request was issued with. A provider may
             // transparently switch models mid-request (e.g. Anthropic's retired
@@ -225,6 +264,16 @@ impl Agent {
             // `ModelChanged` and resync the UI/context-limit.
             let model_at_request_start = provider.model().to_string();
             let resume_session_id = self.provider_session_id.clone();
+            // A response still generating server-side from before a restart is
+            // re-attached to only when this request is the one it answers.
+            if let Some(response_id) = self.session.take_resumable_background_response() {
+                self.provider.restore_background_response(&response_id);
+            }
+            let background_anchor = self
+                .session
+                .messages
+                .last()
+                .map(|message| message.id.clone());
             self.last_status_detail = None;
             let _ = event_tx.send(kv_cache_request_event(
                 &cache_signature_messages,
@@ -254,33 +303,25 @@ impl Agent {
                         }
                         result = &mut complete_future => {
                             match result {
-                                Ok(stream) => break stream,
+                                Ok(stream) => break self.watch_provider_stream(stream),
                                 Err(e) => {
-                                    if self.try_auto_compact_after_context_limit(&e.to_string()) {
+                                    if context_limit_retries < Self::MAX_CONTEXT_LIMIT_RETRIES
+                                        && let Some(recovery) =
+                                            self.try_auto_compact_after_context_limit(&e.to_string())
+                                    {
                                         context_limit_retries += 1;
-                                        if context_limit_retries > Self::MAX_CONTEXT_LIMIT_RETRIES {
-                                            logging::warn(
-                                                "Context-limit compaction retry limit reached; giving up",
-                                            );
-                                            return Err(anyhow::anyhow!(
-                                                "Context limit exceeded after {} compaction retries",
-                                                Self::MAX_CONTEXT_LIMIT_RETRIES
-                                            ));
-                                        }
-                                        let _ = event_tx.send(ServerEvent::Compaction {
-                                            trigger: "auto_recovery".to_string(),
-                                            pre_tokens: None,
-                                            post_tokens: None,
-                                            tokens_saved: None,
-                                            duration_ms: None,
-                                            messages_dropped: None,
-                                            messages_compacted: None,
-                                            summary_chars: None,
-                                            active_messages: None,
-                                        });
-                                        continue;
+                                        let _ = event_tx.send(recovery.server_event());
+                                        continue 'turn;
                                     }
-                                    return Err(e);
+                                    if retired_model_switches < Self::MAX_RETIRED_MODEL_SWITCHES
+                                        && let Some(fallback) =
+                                            self.switch_from_retired_model(&e.to_string())
+                                    {
+                                        retired_model_switches += 1;
+                                        let _ = event_tx.send(self.retired_model_event(fallback));
+                                        continue 'turn;
+                                    }
+                                    return Err(self.context_limit_failure(e));
                                 }
                             }
                         }
@@ -304,6 +345,7 @@ impl Agent {
             );
 
             let mut text_content = String::new();
+            let mut text_cursor = crate::message::StreamTextCursor::default();
             let mut text_wrapped_detected = false;
             // Inline swarm worker output tap: publish a throttled tail of the
             // in-progress assistant text to the bus so a coordinator can render
@@ -336,11 +378,19 @@ impl Agent {
             // (via `ReasoningDone`) before real output or a tool call begins.
             let mut reasoning_open = false;
             let mut openai_reasoning_items: Vec<ContentBlock> = Vec::new();
+            // Provider-executed (server-side) tool calls/results and citations.
+            // They are shown to clients as regular tool calls but never run locally.
+            let mut server_tool_blocks: Vec<ContentBlock> = Vec::new();
+            let mut citations: Vec<crate::message::Citation> = Vec::new();
+            let mut usage_web_search: Option<u64> = None;
             let mut openai_native_compaction: Option<(String, usize)> = None;
             let mut tool_id_to_name: std::collections::HashMap<String, String> =
                 std::collections::HashMap::new();
 
             let mut retry_after_compaction = false;
+            let mut pending_rate_limit_wait: Option<Duration> = None;
+            let mut retry_after_stall = false;
+            let mut retry_after_model_switch = false;
             let mut keepalive = stream_keepalive_ticker();
             loop {
                 let next_event = std::pin::pin!(stream.next());
@@ -388,7 +438,10 @@ impl Agent {
                     Ok(event) => event,
                     Err(e) => {
                         let err_str = e.to_string();
-                        if self.try_auto_compact_after_context_limit(&err_str) {
+                        if context_limit_retries < Self::MAX_CONTEXT_LIMIT_RETRIES
+                            && let Some(recovery) =
+                                self.try_auto_compact_after_context_limit(&err_str)
+                        {
                             log_agent_provider_stream_lifecycle(
                                 logging::LogLevel::Warn,
                                 self,
@@ -404,27 +457,18 @@ impl Agent {
                                 ],
                             );
                             context_limit_retries += 1;
-                            if context_limit_retries > Self::MAX_CONTEXT_LIMIT_RETRIES {
-                                logging::warn(
-                                    "Context-limit compaction retry limit reached; giving up",
-                                );
-                                return Err(anyhow::anyhow!(
-                                    "Context limit exceeded after {} compaction retries",
-                                    Self::MAX_CONTEXT_LIMIT_RETRIES
-                                ));
-                            }
                             retry_after_compaction = true;
-                            let _ = event_tx.send(ServerEvent::Compaction {
-                                trigger: "auto_recovery".to_string(),
-                                pre_tokens: None,
-                                post_tokens: None,
-                                tokens_saved: None,
-                                duration_ms: None,
-                                messages_dropped: None,
-                                messages_compacted: None,
-                                summary_chars: None,
-                                active_messages: None,
-                            });
+                            let _ = event_tx.send(recovery.server_event());
+                            break;
+                        }
+                        if text_content.is_empty()
+                            && tool_calls.is_empty()
+                            && retired_model_switches < Self::MAX_RETIRED_MODEL_SWITCHES
+                            && let Some(fallback) = self.switch_from_retired_model(&err_str)
+                        {
+                            retired_model_switches += 1;
+                            let _ = event_tx.send(self.retired_model_event(fallback));
+                            retry_after_model_switch = true;
                             break;
                         }
                         log_agent_provider_stream_lifecycle(
@@ -434,10 +478,14 @@ impl Agent {
                             api_start,
                             vec![("mode", "mpsc".to_string()), ("error", err_str)],
                         );
-                        return Err(e);
+                        return Err(self.context_limit_failure(e));
                     }
                 };
 
+                // Drop text a reconnected provider stream sent again.
+                let Some(event) = text_cursor.normalize(event) else {
+                    continue;
+                };
                 match event {
                     StreamEvent::ThinkingStart => {
                         // Reasoning tokens are counted in provider output usage even when
@@ -718,6 +766,8 @@ impl Agent {
                         reasoning_signature.clear();
                         reasoning_open = false;
                         openai_reasoning_items.clear();
+                        server_tool_blocks.clear();
+                        citations.clear();
                         openai_native_compaction = None;
                         saw_message_end = false;
                         stop_reason = None;
@@ -742,6 +792,13 @@ impl Agent {
                         if reason.is_some() {
                             stop_reason = reason;
                         }
+                        // Citations are stored as their own block, so the sources
+                        // footer is streamed for display without joining `text_content`.
+                        let sources = crate::message::citation_sources_markdown(&citations);
+                        if !sources.is_empty() {
+                            let _ = event_tx.send(ServerEvent::TextDelta { text: sources });
+                        }
+                        self.session.clear_background_response();
                         let _ = event_tx.send(ServerEvent::MessageEnd);
                     }
                     StreamEvent::SessionId(sid) => {
@@ -749,6 +806,11 @@ impl Agent {
                         self.session.provider_session_id = Some(sid.clone());
                         let _ = event_tx.send(ServerEvent::SessionId { session_id: sid });
                     }
+                    StreamEvent::BackgroundResponse { response_id } => {
+                        self.session
+                            .set_background_response(response_id, background_anchor.clone());
+                        self.persist_session_best_effort("background response id");
+                    }
                     StreamEvent::OpenAIReasoning {
                         id,
                         summary,
@@ -783,16 +845,16 @@ impl Agent {
                             session_id: self.session.id.clone(),
                             message_id: self.session.id.clone(),
                             tool_call_id: request_id.clone(),
-                            working_dir: self.working_dir().map(PathBuf::from),
+                            working_dir: self.tool_working_dir().map(PathBuf::from),
+                            workspace_roots: self.workspace_roots(),
                             stdin_request_tx: self.stdin_request_tx.clone(),
                             graceful_shutdown_signal: Some(self.graceful_shutdown.clone()),
                             execution_mode: ToolExecutionMode::AgentTurn,
                         };
                         crate::telemetry::record_tool_call();
-                        let tool_result = self
-                            .registry
-                            .execute(&tool_name, ToolCall::normalize_input_to_object(input), ctx)
-                            .await;
+                        let mut input = ToolCall::normalize_input_to_object(input);
+                        self.reveal_redacted_input(&mut input);
+                        let tool_result = self.registry.execute(&tool_name, input, ctx).await;
                         if tool_result.is_err() {
                             crate::telemetry::record_tool_failure();
                         }
@@ -804,15 +866,82 @@ impl Agent {
                             let _ = sender.send(native_result).await;
                         }
                     }
+                    StreamEvent::ServerToolUse { id, name, input } => {
+                        if reasoning_open {
+                            reasoning_open = false;
+                            let _ = event_tx.send(ServerEvent::ReasoningDone {
+                                duration_secs: None,
+                            });
+                        }
+                        let _ = event_tx.send(ServerEvent::ToolStart {
+                            id: id.clone(),
+                            name: name.clone(),
+                        });
+                        let _ = event_tx.send(ServerEvent::ToolInput {
+                            delta: input.to_string(),
+                        });
+                        let _ = event_tx.send(ServerEvent::ToolExec {
+                            id: id.clone(),
+                            name: name.clone(),
+                        });
+                        tool_id_to_name.insert(id.clone(), name.clone());
+                        // Keep the text written before and after the search as
+                        // separate paragraphs in the stored turn.
+                        if !text_content.is_empty() && !text_content.ends_with('\n') {
+                            text_content.push_str("\n\n");
+                        }
+                        server_tool_blocks.push(ContentBlock::ServerToolUse { id, name, input });
+                    }
+                    StreamEvent::WebSearchResult {
+                        tool_use_id,
+                        results,
+                        error_code,
+                    } => {
+                        let tool_name = tool_id_to_name
+                            .get(&tool_use_id)
+                            .cloned()
+                            .unwrap_or_else(|| "web_search".to_string());
+                        let _ = event_tx.send(ServerEvent::ToolDone {
+                            id: tool_use_id.clone(),
+                            name: tool_name,
+                            output: crate::message::web_search_result_text(
+                                &results,
+                                error_code.as_deref(),
+                            ),
+                            error: error_code.clone(),
+                        });
+                        server_tool_blocks.push(ContentBlock::WebSearchResult {
+                            tool_use_id,
+                            results,
+                            error_code,
+                        });
+                    }
+                    StreamEvent::Citation(citation) => {
+                        citations.push(citation);
+                    }
+                    StreamEvent::ServerToolUsage {
+                        web_search_requests,
+                    } => {
+                        usage_web_search = Some(web_search_requests);
+                    }
                     StreamEvent::UpstreamProvider { provider } => {
                         self.last_upstream_provider = Some(provider.clone());
                         let _ = event_tx.send(ServerEvent::UpstreamProvider { provider });
                     }
+                    StreamEvent::SystemFingerprint(fingerprint) => {
+                        self.last_system_fingerprint = Some(fingerprint);
+                    }
+                    StreamEvent::TextDeltaAt { .. } => {
+                        // Rewritten to TextDelta by text_cursor above
+                    }
                     StreamEvent::Error {
                         message,
                         retry_after_secs,
                     } => {
-                        if self.try_auto_compact_after_context_limit(&message) {
+                        if context_limit_retries < Self::MAX_CONTEXT_LIMIT_RETRIES
+                            && let Some(recovery) =
+                                self.try_auto_compact_after_context_limit(&message)
+                        {
                             log_agent_provider_stream_lifecycle(
                                 logging::LogLevel::Warn,
                                 self,
@@ -828,27 +957,70 @@ impl Agent {
                                 ],
                             );
                             context_limit_retries += 1;
-                            if context_limit_retries > Self::MAX_CONTEXT_LIMIT_RETRIES {
-                                logging::warn(
-                                    "Context-limit compaction retry limit reached; giving up",
+                            retry_after_compaction = true;
+                            let _ = event_tx.send(recovery.server_event());
+                            break;
+                        }
+                        if crate::provider::is_stream_idle_timeout(&message) {
+                            if !text_content.is_empty() || !tool_calls.is_empty() {
+                                log_agent_provider_stream_lifecycle(
+                                    logging::LogLevel::Warn,
+                                    self,
+                                    "stream_stalled_mid_response",
+                                    api_start,
+                                    vec![("mode", "mpsc".to_string()), ("error", message.clone())],
                                 );
-                                return Err(anyhow::anyhow!(
-                                    "Context limit exceeded after {} compaction retries",
-                                    Self::MAX_CONTEXT_LIMIT_RETRIES
-                                ));
+                                stop_reason = Some(Self::STREAM_STALL_STOP_REASON.to_string());
+                                break;
                             }
-                            retry_after_compaction = true;
-                            let _ = event_tx.send(ServerEvent::Compaction {
-                                trigger: "auto_recovery".to_string(),
-                                pre_tokens: None,
-                                post_tokens: None,
-                                tokens_saved: None,
-                                duration_ms: None,
-                                messages_dropped: None,
-                                messages_compacted: None,
-                                summary_chars: None,
-                                active_messages: None,
-                            });
+                            if stream_stall_retries < Self::MAX_STREAM_STALL_RETRIES {
+                                stream_stall_retries += 1;
+                                log_agent_provider_stream_lifecycle(
+                                    logging::LogLevel::Warn,
+                                    self,
+                                    "stream_stalled_retry",
+                                    api_start,
+                                    vec![
+                                        ("mode", "mpsc".to_string()),
+                                        ("error", message.clone()),
+                                        ("stream_stall_retries", stream_stall_retries.to_string()),
+                                    ],
+                                );
+                                retry_after_stall = true;
+                                break;
+                            }
+                        }
+                        if text_content.is_empty()
+                            && tool_calls.is_empty()
+                            && let Some(wait) =
+                                self.rate_limit_wait(retry_after_secs, rate_limit_retries)
+                        {
+                            rate_limit_retries += 1;
+                            log_agent_provider_stream_lifecycle(
+                                logging::LogLevel::Warn,
+                                self,
+                                "stream_event_rate_limited",
+                                api_start,
+                                vec![
+                                    ("mode", "mpsc".to_string()),
+                                    ("error", message.clone()),
+                                    ("retry_after_secs", wait.as_secs().to_string()),
+                                    ("rate_limit_retries", rate_limit_retries.to_string()),
+                                ],
+                            );
+                            let _ =
+                                event_tx.send(self.rate_limited_event(wait, rate_limit_retries));
+                            pending_rate_limit_wait = Some(wait);
+                            break;
+                        }
+                        if text_content.is_empty()
+                            && tool_calls.is_empty()
+                            && retired_model_switches < Self::MAX_RETIRED_MODEL_SWITCHES
+                            && let Some(fallback) = self.switch_from_retired_model(&message)
+                        {
+                            retired_model_switches += 1;
+                            let _ = event_tx.send(self.retired_model_event(fallback));
+                            retry_after_model_switch = true;
                             break;
                         }
                         log_agent_provider_stream_lifecycle(
@@ -867,11 +1039,24 @@ impl Agent {
                                 ),
                             ],
                         );
-                        return Err(StreamError::new(message, retry_after_secs).into());
+                        return Err(self.context_limit_failure(
+                            StreamError::new(message, retry_after_secs).into(),
+                        ));
                     }
                 }
             }
 
+            if let Some(wait) = pending_rate_limit_wait {
+                if !self.sleep_rate_limit_wait(wait, Some(&event_tx)).await {
+                    return Ok(());
+                }
+                continue;
+            }
+
+            if retry_after_stall || retry_after_model_switch {
+                continue;
+            }
+
             if retry_after_compaction {
                 log_agent_provider_stream_lifecycle(
                     logging::LogLevel::Info,
@@ -882,6 +1067,8 @@ impl Agent {
                 );
                 continue;
             }
+            rate_limit_retries = 0;
+            stream_stall_retries = 0;
 
             let api_elapsed = api_start.elapsed();
             logging::info(&format!(
@@ -930,6 +1117,17 @@ impl Agent {
                     .saturating_add(usage_cache_read.unwrap_or(0))
                     .saturating_add(usage_cache_creation.unwrap_or(0));
                 crate::session_metrics::record_token_usage(&self.session.id, total, output);
+                crate::metrics::record_tokens(
+                    self.provider.name(),
+                    &self.provider.model(),
+                    input,
+                    output,
+                );
+
+                crate::usage::record_origin_usage(
+                    &self.usage_origin,
+                    crate::usage::OriginUsage::new(1, input, output),
+                );
             }
 
             if usage_input.is_some()
@@ -951,7 +1149,9 @@ impl Agent {
                 output_tokens: usage_output.unwrap_or(0),
                 cache_read_input_tokens: usage_cache_read,
                 cache_creation_input_tokens: usage_cache_creation,
+                web_search_requests: usage_web_search,
             };
+            self.record_prompt_cache_usage();
 
             // Detect a transparent mid-request model switch (e.g. Anthropic's
             // retired `claude-fable-5` falling back to `claude-opus-4-8`). The
@@ -967,32 +1167,54 @@ impl Agent {
                     "Provider switched model mid-request: '{}' -> '{}' (resyncing session/UI)",
                     model_at_request_start, model_after_stream
                 ));
+                let requested_route = self.session.model_route.clone();
                 self.session.model = Some(model_after_stream.clone());
+                self.record_session_model_route();
                 self.provider_runtime_state.apply(
                     crate::provider::ProviderStateEvent::RuntimeModelObserved {
                         model: model_after_stream.clone(),
                     },
                 );
                 self.persist_session_best_effort("model fallback");
+                let reason = format!(
+                    "'{}' is no longer served by {}",
+                    model_at_request_start, provider_name
+                );
+                crate::provider::record_model_unavailable_for_account(
+                    &model_at_request_start,
+                    &reason,
+                );
+                let route_fallback =
+                    requested_route.map(|requested| crate::session::ModelRouteFallback {
+                        requested,
+                        effective: self.current_model_route(),
+                        reason: Some(reason),
+                    });
                 let _ = event_tx.send(ServerEvent::ModelChanged {
                     id: 0,
                     model: model_after_stream,
                     provider_name: Some(provider_name),
                     error: None,
+                    route_fallback,
                 });
             }
 
-            let had_tool_calls_before = !tool_calls.is_empty();
+            let had_tool_calls_before = tool_calls.len();
+            if tool_protocol == ToolProtocol::Textual {
+                self.recover_textual_tool_calls(&mut text_content, &mut tool_calls);
+            }
             self.recover_text_wrapped_tool_call(&mut text_content, &mut tool_calls);
 
-            if !had_tool_calls_before
-                && !tool_calls.is_empty()
-                && let Some(tc) = tool_calls.last()
-                && tc.id.starts_with("fallback_text_call_")
-            {
+            if tool_calls.len() > had_tool_calls_before {
                 let _ = event_tx.send(ServerEvent::TextReplace {
                     text: text_content.clone(),
                 });
+            }
+            for tc in tool_calls
+                .iter()
+                .skip(had_tool_calls_before)
+                .filter(|tc| tc.id.starts_with("fallback_text_call_"))
+            {
                 let _ = event_tx.send(ServerEvent::ToolStart {
                     id: tc.id.clone(),
                     name: tc.name.clone(),
@@ -1033,6 +1255,11 @@ impl Agent {
                     thought_signature: None,
                 });
             }
+            crate::message::attach_server_tool_blocks(
+                &mut content_blocks,
+                &server_tool_blocks,
+                &citations,
+            );
 
             let assistant_message_id = if !content_blocks.is_empty() {
                 crate::telemetry::record_assistant_response();
@@ -1041,6 +1268,13 @@ impl Agent {
                     output_tokens: self.last_usage.output_tokens,
                     cache_read_input_tokens: self.last_usage.cache_read_input_tokens,
                     cache_creation_input_tokens: self.last_usage.cache_creation_input_tokens,
+                    web_search_requests: self.last_usage.web_search_requests,
+                    pruned_context_tokens: (pruned_tokens > 0).then_some(pruned_tokens),
+                    redactions: self.take_pending_redactions(),
+                    model: Some(self.provider.model()),
+                    provider: Some(self.provider.name().to_string()),
+                    duration_ms: Some(api_elapsed.as_millis() as u64),
+                    determinism: self.take_response_determinism(),
                 });
                 let message_id =
                     self.add_message_ext(Role::Assistant, content_blocks, None, token_usage);
@@ -1162,49 +1396,39 @@ impl Agent {
                 }
             }
 
+            // The model already had its wrap-up reply after a limit breach.
+            if let Some(error) = self.agent_limit_stop(&tool_calls) {
+                self.send_agent_limit_status(&event_tx);
+                return Err(error);
+            }
+
             // Execute tools and add results
             let tool_count = tool_calls.len();
             let mut tool_results_dirty = false;
             for tool_index in 0..tool_count {
                 // === INJECTION POINT C (before): Check for urgent abort before each tool (except first) ===
-                if tool_index > 0 && self.has_urgent_interrupt() {
-                    crate::telemetry::record_user_cancelled();
-                    // Add tool_results for all remaining skipped tools to maintain valid history
-                    for skipped_tc in &tool_calls[tool_index..] {
-                        self.add_message(
-                            Role::User,
-                            vec![ContentBlock::ToolResult {
-                                tool_use_id: skipped_tc.id.clone(),
-                                content: "[Skipped: user interrupted]".to_string(),
-                                is_error: Some(true),
-                            }],
-                        );
-                    }
-                    let tools_remaining = tool_count - tool_index;
-                    let injected = self.inject_soft_interrupts();
-                    if !injected.is_empty() {
-                        for event in
-                            Self::build_soft_interrupt_events(injected, "C", Some(tools_remaining))
-                        {
-                            let _ = event_tx.send(event);
-                        }
-                        // Add note about skipped tools for the AI
-                        self.add_message(
-                            Role::User,
-                            vec![ContentBlock::Text {
-                                text: format!(
-                                    "[User interrupted: {} remaining tool(s) skipped]",
-                                    tools_remaining
-                                ),
-                                cache_control: None,
-                            }],
-                        );
+                if tool_index > 0
+                    && let Some(events) = self.take_urgent_soft_interrupt(&tool_calls[tool_index..])
+                {
+                    for event in events {
+                        let _ = event_tx.send(event);
                     }
                     self.persist_session_best_effort("streamed tool output");
                     break; // Skip remaining tools
                 }
                 let tc = &tool_calls[tool_index];
 
+                if self.agent_tool_budget_exhausted() {
+                    self.push_agent_limit_skipped_result(tc.id.clone());
+                    tool_results_dirty = true;
+                    continue;
+                }
+                if self.focus_skips_tool(&tc.name) {
+                    self.push_focus_skipped_result(tc.id.clone());
+                    tool_results_dirty = true;
+                    continue;
+                }
+
                 let message_id = assistant_message_id
                     .clone()
                     .unwrap_or_else(|| self.session.id.clone());
@@ -1258,7 +1482,8 @@ impl Agent {
                     session_id: self.session.id.clone(),
                     message_id: message_id.clone(),
                     tool_call_id: tc.id.clone(),
-                    working_dir: self.working_dir().map(PathBuf::from),
+                    working_dir: self.tool_working_dir().map(PathBuf::from),
+                    workspace_roots: self.workspace_roots(),
                     stdin_request_tx: self.stdin_request_tx.clone(),
                     graceful_shutdown_signal: Some(self.graceful_shutdown.clone()),
                     execution_mode: ToolExecutionMode::AgentTurn,
@@ -1270,11 +1495,13 @@ impl Agent {
 
                 logging::info(&format!("Tool starting: {}", tc.name));
                 let tool_start = Instant::now();
+                self.note_agent_limit_tool_call();
 
                 // Spawn tool in its own task so we can detach it to background on Alt+B
                 let registry_clone = self.registry.clone();
                 let tool_name_for_spawn = tc.name.clone();
-                let tool_input_for_spawn = tc.input.clone();
+                let mut tool_input_for_spawn = tc.input.clone();
+                self.reveal_redacted_input(&mut tool_input_for_spawn);
                 let tool_handle = tokio::spawn(async move {
                     registry_clone
                         .execute(&tool_name_for_spawn, tool_input_for_spawn, ctx)
@@ -1338,6 +1565,9 @@ impl Agent {
                     match result {
                         Ok(output) => {
                             let output = cap_tool_output_for_history(&tc.name, output);
+                            if tc.name == crate::plan::proposal::PLAN_PROPOSE_TOOL {
+                                self.record_plan_proposal(output.metadata.as_ref());
+                            }
                             let _ = event_tx.send(ServerEvent::ToolDone {
                                 id: tc.id.clone(),
                                 name: tc.name.clone(),
@@ -1369,13 +1599,16 @@ impl Agent {
                             tool_results_dirty = true;
                         }
                         Err(e) => {
-                            let error_msg = format!("Error: {}", e);
+                            let error_msg = self
+                                .session
+                                .explain_missing_dir_error(format!("Error: {}", e));
                             let _ = event_tx.send(ServerEvent::ToolDone {
                                 id: tc.id.clone(),
                                 name: tc.name.clone(),
                                 output: error_msg.clone(),
                                 error: Some(error_msg.clone()),
                             });
+                            self.note_tool_failure(&tc, &error_msg);
 
                             self.add_message_with_duration(
                                 Role::User,
@@ -1498,6 +1731,12 @@ impl Agent {
             // === INJECTION POINT D: All tools done, before next API call ===
             // This is the safest point for non-urgent injection since all tool_results
             // have been added and the conversation is in a valid state.
+            if self.focus_wrapping_up() {
+                // The focus wrap-up reply and its todo updates are in.
+                break;
+            }
+            self.queue_agent_limit_wrap_up();
+            self.queue_focus_checkpoint();
             if let PostToolInterruptOutcome::SoftInterrupt { injected, point } =
                 self.take_post_tool_soft_interrupt()
             {
@@ -1507,6 +1746,14 @@ impl Agent {
             }
         }
 
+        self.finish_focus(&event_tx);
+        if !self.is_graceful_shutdown()
+            && let Some(error) = self.agent_limit_stop(&[])
+        {
+            self.send_agent_limit_status(&event_tx);
+            return Err(error);
+        }
+        self.send_agent_limit_status(&event_tx);
         Ok(())
     }
 }
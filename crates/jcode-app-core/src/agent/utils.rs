@@ -19,12 +19,14 @@ pub(super) fn git_state_for_dir(dir: &Path) -> Option<GitState> {
     let head = git_output(dir, &["rev-parse", "HEAD"]);
     let branch = git_output(dir, &["rev-parse", "--abbrev-ref", "HEAD"]);
     let dirty = git_output(dir, &["status", "--porcelain"]).map(|out| !out.is_empty());
+    let remote = git_output(dir, &["remote", "get-url", "origin"]);
 
     Some(GitState {
         root,
         head,
         branch,
         dirty,
+        remote,
     })
 }
 
@@ -288,8 +288,20 @@ async fn run_turn_streaming_mpsc_emits_model_changed_on_midstream_switch() {
     let deadline = Instant::now() + Duration::from_secs(20);
     while Instant::now() < deadline {
         match tokio::time::timeout(Duration::from_secs(1), rx.recv()).await {
-            Ok(Some(ServerEvent::ModelChanged { model, error, .. })) => {
+            Ok(Some(ServerEvent::ModelChanged {
+                model,
+                error,
+                route_fallback,
+                ..
+            })) => {
                 assert!(error.is_none(), "unexpected model-change error: {error:?}");
+                let reason = route_fallback.and_then(|fallback| fallback.reason);
+                assert!(
+                    reason
+                        .as_deref()
+                        .is_some_and(|reason| reason.contains("claude-fable-5")),
+                    "expected the switch to explain itself, got {reason:?}"
+                );
                 switched_model = Some(model);
                 break;
             }
@@ -311,6 +323,122 @@ async fn run_turn_streaming_mpsc_emits_model_changed_on_midstream_switch() {
     );
 }
 
+/// Provider that rejects a retired model id up front, like Anthropic's 404
+/// for `claude-3-opus`, and serves any other model.
+struct RetiredModelProvider {
+    model: std::sync::Mutex<String>,
+}
+
+#[async_trait]
+impl Provider for RetiredModelProvider {
+    async fn complete(
+        &self,
+        _messages: &[Message],
+        _tools: &[ToolDefinition],
+        _system: &str,
+        _resume_session_id: Option<&str>,
+    ) -> Result<EventStream> {
+        let model = self.model();
+        if model == "claude-3-opus-20240229" {
+            anyhow::bail!(
+                "anthropic api error (404 not found): {{\"type\":\"error\",\"error\":{{\"type\":\"not_found_error\",\"message\":\"model: {}\"}}}}",
+                model
+            );
+        }
+        let (tx, rx) = tokio_mpsc::channel::<Result<StreamEvent>>(8);
+        tokio::spawn(async move {
+            let _ = tx
+                .send(Ok(StreamEvent::TextDelta("hello".to_string())))
+                .await;
+            let _ = tx
+                .send(Ok(StreamEvent::MessageEnd {
+                    stop_reason: Some("end_turn".to_string()),
+                }))
+                .await;
+        });
+        Ok(Box::pin(ReceiverStream::new(rx)))
+    }
+
+    fn name(&self) -> &str {
+        "claude"
+    }
+
+    fn model(&self) -> String {
+        self.model.lock().unwrap().clone()
+    }
+
+    fn set_model(&self, model: &str) -> Result<()> {
+        *self.model.lock().unwrap() = model.to_string();
+        Ok(())
+    }
+
+    fn available_models_for_switching(&self) -> Vec<String> {
+        vec![
+            "claude-opus-4-6".to_string(),
+            "claude-sonnet-4-6".to_string(),
+        ]
+    }
+
+    fn fork(&self) -> Arc<dyn Provider> {
+        Arc::new(Self {
+            model: std::sync::Mutex::new(self.model()),
+        })
+    }
+}
+
+#[tokio::test]
+async fn run_turn_streaming_mpsc_moves_retired_model_to_successor() {
+    let _guard = crate::storage::lock_test_env();
+    let provider: Arc<dyn Provider> = Arc::new(RetiredModelProvider {
+        model: std::sync::Mutex::new("claude-3-opus-20240229".to_string()),
+    });
+    let registry = Registry::new(provider.clone()).await;
+    let mut agent = Agent::new(provider, registry);
+    agent.add_message(
+        Role::User,
+        vec![ContentBlock::Text {
+            text: "test".to_string(),
+            cache_control: None,
+        }],
+    );
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let task = tokio::spawn(async move {
+        let result = agent.run_turn_streaming_mpsc(tx).await;
+        (result, agent.session.model.clone())
+    });
+
+    let mut fallback = None;
+    let mut saw_text = false;
+    while let Ok(Some(event)) = tokio::time::timeout(Duration::from_secs(20), rx.recv()).await {
+        match event {
+            ServerEvent::ModelChanged {
+                model,
+                route_fallback,
+                ..
+            } => {
+                assert_eq!(model, "claude-opus-4-6");
+                fallback = route_fallback;
+            }
+            ServerEvent::TextDelta { text } => saw_text = text == "hello",
+            _ => {}
+        }
+    }
+
+    let (result, session_model) = task.await.unwrap();
+    result.expect("turn should succeed on the successor");
+    assert!(saw_text, "expected the resent request to stream");
+    assert_eq!(session_model.as_deref(), Some("claude-opus-4-6"));
+    let fallback = fallback.expect("expected a ModelChanged explaining the switch");
+    assert_eq!(fallback.requested.model, "claude-3-opus-20240229");
+    assert!(
+        fallback
+            .reason
+            .as_deref()
+            .is_some_and(|reason| reason.contains("was retired"))
+    );
+}
+
 #[tokio::test]
 async fn messages_for_provider_replays_persisted_native_compaction_in_auto_mode() {
     let provider: Arc<dyn Provider> = Arc::new(NativeAutoCompactionProvider);
@@ -636,6 +764,237 @@ async fn default_disabled_tools_are_not_exposed_or_executable() {
     crate::config::Config::invalidate_cache();
 }
 
+#[tokio::test]
+async fn plan_mode_restricts_tool_definitions_and_policy() {
+    let _guard = crate::storage::lock_test_env();
+    let prev_home = std::env::var_os("JCODE_HOME");
+    let temp_home = tempfile::TempDir::new().expect("temp home");
+    crate::env::set_var("JCODE_HOME", temp_home.path());
+
+    let provider: Arc<dyn Provider> = Arc::new(NativeAutoCompactionProvider);
+    let registry = Registry::new(provider.clone()).await;
+    let mut agent = Agent::new(provider, registry);
+    let plan_tools = crate::plan::proposal::PLAN_MODE_TOOLS;
+
+    let names = agent.tool_names().await;
+    assert!(!names.iter().any(|name| name == "plan_propose"));
+
+    agent.set_plan_mode(true).expect("enter plan mode");
+    let names = agent.tool_names().await;
+    assert!(names.iter().any(|name| name == "plan_propose"));
+    assert!(names.iter().all(|name| plan_tools.contains(&name.as_str())));
+    let ctx = crate::tool::ToolContext {
+        session_id: agent.session_id().to_string(),
+        message_id: "msg".to_string(),
+        tool_call_id: "call".to_string(),
+        working_dir: None,
+        workspace_roots: Vec::new(),
+        stdin_request_tx: None,
+        graceful_shutdown_signal: None,
+        execution_mode: crate::tool::ToolExecutionMode::Direct,
+    };
+    let err = agent
+        .registry
+        .execute("bash", serde_json::json!({"command": "true"}), ctx)
+        .await
+        .expect_err("bash must be blocked in plan mode");
+    assert!(err.to_string().contains("not allowed"));
+
+    agent.set_plan_mode(false).expect("leave plan mode");
+    let names = agent.tool_names().await;
+    assert!(names.iter().any(|name| name == "bash"));
+
+    if let Some(previous) = prev_home {
+        crate::env::set_var("JCODE_HOME", previous);
+    } else {
+        crate::env::remove_var("JCODE_HOME");
+    }
+}
+
+#[tokio::test]
+async fn aside_uses_read_only_child_and_hands_back_only_the_summary() {
+    let _guard = crate::storage::lock_test_env();
+    let prev_home = std::env::var_os("JCODE_HOME");
+    let temp_home = tempfile::TempDir::new().expect("temp home");
+    crate::env::set_var("JCODE_HOME", temp_home.path());
+
+    let provider: Arc<dyn Provider> = Arc::new(NativeAutoCompactionProvider);
+    let registry = Registry::new(provider.clone()).await;
+    let mut agent = Agent::new(provider, registry);
+    let main_messages = agent.session.messages.len();
+
+    agent.start_aside().expect("open aside");
+    assert!(agent.start_aside().is_err());
+    let child = agent.build_aside_agent().await;
+    let names = child.tool_names().await;
+    assert!(!names.is_empty());
+    assert!(
+        names
+            .iter()
+            .all(|name| super::aside::ASIDE_TOOLS.contains(&name.as_str()))
+    );
+    assert_ne!(child.session_id(), agent.session_id());
+    assert_eq!(child.session.messages.len(), main_messages);
+
+    agent
+        .close_aside(Some("  use the v2 parser  ".to_string()))
+        .expect("close aside");
+    assert!(!agent.aside_active());
+    assert_eq!(agent.session.messages.len(), main_messages + 1);
+    let last = agent.session.messages.last().expect("summary message");
+    assert_eq!(last.content_preview(), "[Aside summary] use the v2 parser");
+    assert_eq!(
+        agent.session.asides[0].summary.as_deref(),
+        Some("use the v2 parser")
+    );
+    assert!(agent.close_aside(None).is_err());
+
+    if let Some(previous) = prev_home {
+        crate::env::set_var("JCODE_HOME", previous);
+    } else {
+        crate::env::remove_var("JCODE_HOME");
+    }
+}
+
+/// Answers with its model name and records the tool count it was offered.
+struct CompareProvider {
+    model: StdMutex<String>,
+    offered_tools: Arc<StdMutex<Vec<usize>>>,
+}
+
+#[async_trait]
+impl Provider for CompareProvider {
+    async fn complete(
+        &self,
+        _messages: &[Message],
+        tools: &[ToolDefinition],
+        _system: &str,
+        _resume_session_id: Option<&str>,
+    ) -> Result<EventStream> {
+        self.offered_tools.lock().unwrap().push(tools.len());
+        let model = self.model();
+        if model == "broken" {
+            anyhow::bail!("model unavailable");
+        }
+        let events = vec![
+            Ok(StreamEvent::TextDelta(format!("answer from {}", model))),
+            Ok(StreamEvent::TokenUsage {
+                input_tokens: Some(100),
+                output_tokens: Some(7),
+                cache_read_input_tokens: None,
+                cache_creation_input_tokens: None,
+            }),
+        ];
+        Ok(Box::pin(futures::stream::iter(events)))
+    }
+
+    fn name(&self) -> &str {
+        "compare"
+    }
+
+    fn model(&self) -> String {
+        self.model.lock().unwrap().clone()
+    }
+
+    fn set_model(&self, model: &str) -> Result<()> {
+        *self.model.lock().unwrap() = model.to_string();
+        Ok(())
+    }
+
+    fn fork(&self) -> Arc<dyn Provider> {
+        Arc::new(Self {
+            model: StdMutex::new(self.model()),
+            offered_tools: self.offered_tools.clone(),
+        })
+    }
+}
+
+#[tokio::test]
+async fn compare_runs_each_model_without_tools_and_keeps_the_losers_as_alternatives() {
+    let _guard = crate::storage::lock_test_env();
+    let prev_home = std::env::var_os("JCODE_HOME");
+    let temp_home = tempfile::TempDir::new().expect("temp home");
+    crate::env::set_var("JCODE_HOME", temp_home.path());
+
+    let offered_tools = Arc::new(StdMutex::new(Vec::new()));
+    let provider: Arc<dyn Provider> = Arc::new(CompareProvider {
+        model: StdMutex::new("main".to_string()),
+        offered_tools: offered_tools.clone(),
+    });
+    let registry = Registry::new(provider.clone()).await;
+    let mut agent = Agent::new(provider.clone(), registry);
+    let main_messages = agent.session.messages.len();
+
+    assert!(agent.prepare_compare("hi", vec!["a".to_string()]).is_err());
+    let runs = agent
+        .prepare_compare(
+            "which is faster?",
+            vec!["a".to_string(), "b".to_string(), "broken".to_string()],
+        )
+        .expect("prepare compare")
+        .run()
+        .await;
+    assert_eq!(runs.len(), 3);
+    assert_eq!(runs[0].model, "a");
+    assert_eq!(runs[0].text, "answer from a");
+    assert_eq!(runs[1].text, "answer from b");
+    assert_eq!(
+        (runs[1].input_tokens, runs[1].output_tokens),
+        (Some(100), Some(7))
+    );
+    assert!(
+        runs[2]
+            .error
+            .as_deref()
+            .is_some_and(|e| e.contains("unavailable"))
+    );
+    assert!(
+        offered_tools
+            .lock()
+            .unwrap()
+            .iter()
+            .all(|count| *count == 0)
+    );
+    assert_eq!(
+        provider.model(),
+        "main",
+        "the session's provider is untouched"
+    );
+    assert_eq!(agent.session.messages.len(), main_messages);
+
+    assert!(
+        agent
+            .commit_compare("which is faster?", 2, runs.clone())
+            .is_err()
+    );
+    agent
+        .commit_compare("which is faster?", 1, runs)
+        .expect("commit compare");
+    assert_eq!(agent.session.messages.len(), main_messages + 2);
+    let kept = agent.session.messages.last().expect("kept answer");
+    assert_eq!(kept.content_preview(), "answer from b");
+    let models: Vec<&str> = agent
+        .session
+        .alternatives
+        .iter()
+        .map(|alternative| alternative.run.model.as_str())
+        .collect();
+    assert_eq!(models, ["a", "broken"]);
+    assert!(
+        agent
+            .session
+            .alternatives
+            .iter()
+            .all(|alternative| alternative.message_id == kept.id)
+    );
+
+    if let Some(previous) = prev_home {
+        crate::env::set_var("JCODE_HOME", previous);
+    } else {
+        crate::env::remove_var("JCODE_HOME");
+    }
+}
+
 fn seed_transient_session_state(agent: &mut Agent) {
     agent.push_alert("pending alert".to_string());
     agent.queue_soft_interrupt(
@@ -656,6 +1015,7 @@ fn seed_transient_session_state(agent: &mut Agent) {
         output_tokens: 17,
         cache_read_input_tokens: Some(3),
         cache_creation_input_tokens: Some(5),
+        web_search_requests: None,
     };
     agent.locked_tools = Some(vec![ToolDefinition {
         name: "test_tool".to_string(),
@@ -733,6 +1093,265 @@ async fn restore_session_resets_runtime_interrupt_and_queue_state() {
     assert!(agent.locked_tools.is_none());
 }
 
+/// Provider that understands OpenRouter-style `model@provider` pins and only
+/// knows the models in `known`.
+struct PinnedRouteProvider {
+    known: &'static [&'static str],
+    route: std::sync::Mutex<(String, Option<String>)>,
+}
+
+impl PinnedRouteProvider {
+    fn new(known: &'static [&'static str]) -> Self {
+        Self {
+            known,
+            route: std::sync::Mutex::new((known[0].to_string(), None)),
+        }
+    }
+}
+
+#[async_trait]
+impl Provider for PinnedRouteProvider {
+    async fn complete(
+        &self,
+        _messages: &[Message],
+        _tools: &[ToolDefinition],
+        _system: &str,
+        _resume_session_id: Option<&str>,
+    ) -> Result<EventStream> {
+        unimplemented!("route tests never send requests")
+    }
+
+    fn name(&self) -> &str {
+        "openrouter"
+    }
+
+    fn model(&self) -> String {
+        self.route.lock().unwrap().0.clone()
+    }
+
+    fn pinned_provider(&self) -> Option<String> {
+        self.route.lock().unwrap().1.clone()
+    }
+
+    fn set_model(&self, model: &str) -> Result<()> {
+        let (model, pin) = match model.rsplit_once('@') {
+            Some((model, pin)) => (model, Some(pin.to_string())),
+            None => (model, None),
+        };
+        if !self.known.contains(&model) {
+            anyhow::bail!("model '{}' is not available", model);
+        }
+        *self.route.lock().unwrap() = (model.to_string(), pin);
+        Ok(())
+    }
+
+    fn fork(&self) -> Arc<dyn Provider> {
+        Arc::new(Self {
+            known: self.known,
+            route: std::sync::Mutex::new(self.route.lock().unwrap().clone()),
+        })
+    }
+}
+
+fn save_session_with_route(id: &str, route: crate::session::SessionModelRoute) {
+    let mut session = crate::session::Session::create_with_id(id.to_string(), None, None);
+    session.model = Some(route.model.clone());
+    session.model_route = Some(route);
+    session.save().expect("save session with model route");
+}
+
+#[tokio::test]
+async fn restore_session_reapplies_pinned_model_route() {
+    let _guard = crate::storage::lock_test_env();
+    let provider: Arc<dyn Provider> =
+        Arc::new(PinnedRouteProvider::new(&["default-model", "kimi-k2"]));
+    let registry = Registry::new(provider.clone()).await;
+    let mut agent = Agent::new(provider, registry);
+    let route = crate::session::SessionModelRoute {
+        model: "kimi-k2".to_string(),
+        provider: Some("fireworks".to_string()),
+        api_method: None,
+    };
+    save_session_with_route("session_restore_pinned_route", route.clone());
+
+    agent
+        .restore_session("session_restore_pinned_route")
+        .expect("restore session should succeed");
+
+    assert_eq!(agent.current_model_route(), route);
+    assert_eq!(agent.take_model_route_fallback(), None);
+}
+
+#[tokio::test]
+async fn restore_session_reports_unavailable_route_instead_of_switching_silently() {
+    let _guard = crate::storage::lock_test_env();
+    let provider: Arc<dyn Provider> = Arc::new(PinnedRouteProvider::new(&["default-model"]));
+    let registry = Registry::new(provider.clone()).await;
+    let mut agent = Agent::new(provider, registry);
+    let route = crate::session::SessionModelRoute {
+        model: "retired-model".to_string(),
+        provider: Some("fireworks".to_string()),
+        api_method: None,
+    };
+    save_session_with_route("session_restore_unavailable_route", route.clone());
+
+    agent
+        .restore_session("session_restore_unavailable_route")
+        .expect("restore session should succeed");
+
+    let fallback = agent
+        .take_model_route_fallback()
+        .expect("unavailable route should be reported");
+    assert_eq!(fallback.requested, route);
+    assert_eq!(fallback.effective.model, "default-model");
+    assert!(
+        fallback
+            .summary()
+            .starts_with("requested retired-model@fireworks → using default-model ("),
+        "{}",
+        fallback.summary()
+    );
+    // The stored route is kept so it is retried once it is available again.
+    assert_eq!(agent.session.model_route, Some(route));
+}
+
+fn tool_call(id: &str) -> ToolCall {
+    ToolCall {
+        id: id.to_string(),
+        name: "bash".to_string(),
+        ..ToolCall::default()
+    }
+}
+
+#[tokio::test]
+async fn urgent_soft_interrupt_skips_remaining_tools_and_lands_last() {
+    let _guard = crate::storage::lock_test_env();
+    let prev_home = std::env::var_os("JCODE_HOME");
+    let temp_home = tempfile::TempDir::new().expect("temp home");
+    crate::env::set_var("JCODE_HOME", temp_home.path());
+    let provider: Arc<dyn Provider> = Arc::new(NativeAutoCompactionProvider);
+    let registry = Registry::new(provider.clone()).await;
+    let mut agent = Agent::new(provider, registry);
+    let calls = [
+        tool_call("call_a"),
+        tool_call("call_b"),
+        tool_call("call_c"),
+    ];
+
+    // A normal interjection never cuts a tool batch short.
+    agent.queue_soft_interrupt(
+        "after the batch".to_string(),
+        false,
+        SoftInterruptSource::User,
+    );
+    assert!(agent.take_urgent_soft_interrupt(&calls[1..]).is_none());
+    assert_eq!(agent.soft_interrupt_count(), 1);
+
+    agent.queue_soft_interrupt(
+        "stop, wrong file".to_string(),
+        true,
+        SoftInterruptSource::User,
+    );
+    let before = agent.session.messages.len();
+    let events = agent
+        .take_urgent_soft_interrupt(&calls[1..])
+        .expect("urgent interrupt should skip the remaining tools");
+    assert_eq!(agent.soft_interrupt_count(), 0);
+
+    let added = &agent.session.messages[before..];
+    let skipped_ids: Vec<&str> = added
+        .iter()
+        .filter_map(|message| match message.content.first() {
+            Some(ContentBlock::ToolResult { tool_use_id, .. }) => Some(tool_use_id.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(skipped_ids, ["call_b", "call_c"]);
+    assert!(
+        added[..2].iter().all(|message| matches!(
+            message.content.first(),
+            Some(ContentBlock::ToolResult { .. })
+        )),
+        "skipped tool results must directly follow the executed ones"
+    );
+    assert!(content_text(&added[2].content).contains("2 remaining tool(s) skipped"));
+    assert_eq!(
+        content_text(&added.last().unwrap().content),
+        "after the batch\n\nstop, wrong file"
+    );
+
+    match events.as_slice() {
+        [
+            ServerEvent::SoftInterruptInjected {
+                content,
+                point,
+                tools_skipped,
+                display_role,
+            },
+        ] => {
+            assert_eq!(content, "after the batch\n\nstop, wrong file");
+            assert_eq!(point, "C");
+            assert_eq!(*tools_skipped, Some(2));
+            assert!(display_role.is_none());
+        }
+        other => panic!("unexpected events: {other:?}"),
+    }
+
+    if let Some(previous) = prev_home {
+        crate::env::set_var("JCODE_HOME", previous);
+    } else {
+        crate::env::remove_var("JCODE_HOME");
+    }
+}
+
+#[tokio::test]
+async fn normal_soft_interrupt_waits_for_all_tool_results() {
+    let _guard = crate::storage::lock_test_env();
+    let prev_home = std::env::var_os("JCODE_HOME");
+    let temp_home = tempfile::TempDir::new().expect("temp home");
+    crate::env::set_var("JCODE_HOME", temp_home.path());
+    let provider: Arc<dyn Provider> = Arc::new(NativeAutoCompactionProvider);
+    let registry = Registry::new(provider.clone()).await;
+    let mut agent = Agent::new(provider, registry);
+
+    agent.queue_soft_interrupt(
+        "also check the tests".to_string(),
+        false,
+        SoftInterruptSource::User,
+    );
+    // Nothing to skip after the last tool, even for an urgent interrupt.
+    assert!(agent.take_urgent_soft_interrupt(&[]).is_none());
+
+    agent.add_message(
+        Role::User,
+        vec![ContentBlock::ToolResult {
+            tool_use_id: "call_a".to_string(),
+            content: "ok".to_string(),
+            is_error: None,
+        }],
+    );
+    let PostToolInterruptOutcome::SoftInterrupt { injected, point } =
+        agent.take_post_tool_soft_interrupt()
+    else {
+        panic!("queued interjection should be injected after the tool batch");
+    };
+    assert_eq!(point, "D");
+    assert_eq!(injected.len(), 1);
+    let last = agent.session.messages.last().unwrap();
+    assert_eq!(content_text(&last.content), "also check the tests");
+    let previous = &agent.session.messages[agent.session.messages.len() - 2];
+    assert!(matches!(
+        previous.content.first(),
+        Some(ContentBlock::ToolResult { .. })
+    ));
+
+    if let Some(previous) = prev_home {
+        crate::env::set_var("JCODE_HOME", previous);
+    } else {
+        crate::env::remove_var("JCODE_HOME");
+    }
+}
+
 #[tokio::test]
 async fn restore_session_rehydrates_injected_memory_ids() {
     let _guard = crate::storage::lock_test_env();
@@ -839,6 +1458,7 @@ async fn memory_injection_message_defaults_to_ephemeral_history() {
         computed_at: Instant::now(),
         count: 1,
         memory_ids: vec!["mem-ephemeral".to_string()],
+        report: None,
     };
 
     let (message, persisted) = agent.prepare_memory_injection_message(&memory);
@@ -872,6 +1492,7 @@ async fn memory_injection_message_can_persist_to_history() {
         computed_at: Instant::now(),
         count: 1,
         memory_ids: vec!["mem-persisted".to_string()],
+        report: None,
     };
 
     let (message, persisted) = agent.prepare_memory_injection_message(&memory);
@@ -1212,3 +1833,288 @@ fn guardrail_notice_absent_for_normal_turns() {
     assert!(Agent::provider_guardrail_notice(Some("end_turn"), false, false).is_none());
     assert!(Agent::provider_guardrail_notice(None, false, true).is_none());
 }
+
+/// Records the resume id every turn is sent with and hands out a fresh
+/// provider-side session id per turn, like the Claude CLI resume path.
+struct ResumeCapturingProvider {
+    captured_resume_session_ids: Arc<std::sync::Mutex<Vec<Option<String>>>>,
+}
+
+#[async_trait]
+impl Provider for ResumeCapturingProvider {
+    async fn complete(
+        &self,
+        _messages: &[Message],
+        _tools: &[ToolDefinition],
+        _system: &str,
+        resume_session_id: Option<&str>,
+    ) -> Result<EventStream> {
+        let turn = {
+            let mut captured = self.captured_resume_session_ids.lock().unwrap();
+            captured.push(resume_session_id.map(str::to_string));
+            captured.len()
+        };
+        let (tx, rx) = tokio_mpsc::channel::<Result<StreamEvent>>(8);
+        tokio::spawn(async move {
+            let _ = tx.send(Ok(StreamEvent::TextDelta("ok".to_string()))).await;
+            let _ = tx
+                .send(Ok(StreamEvent::MessageEnd {
+                    stop_reason: Some("end_turn".to_string()),
+                }))
+                .await;
+            let _ = tx
+                .send(Ok(StreamEvent::SessionId(format!(
+                    "provider-session-{turn}"
+                ))))
+                .await;
+        });
+        Ok(Box::pin(ReceiverStream::new(rx)))
+    }
+
+    fn name(&self) -> &str {
+        "openai"
+    }
+
+    fn supports_compaction(&self) -> bool {
+        true
+    }
+
+    fn uses_jcode_compaction(&self) -> bool {
+        false
+    }
+
+    fn context_window(&self) -> usize {
+        1_000
+    }
+
+    fn fork(&self) -> Arc<dyn Provider> {
+        Arc::new(Self {
+            captured_resume_session_ids: self.captured_resume_session_ids.clone(),
+        })
+    }
+
+    async fn complete_simple(&self, _prompt: &str, _system: &str) -> Result<String> {
+        Ok("manual summary from resume-capturing provider".to_string())
+    }
+}
+
+#[tokio::test]
+async fn history_rewrites_drop_provider_resume_id_for_next_turn() {
+    let _guard = crate::storage::lock_test_env();
+    let temp = tempfile::TempDir::new().expect("tempdir");
+    let prev_home = std::env::var_os("JCODE_HOME");
+    crate::env::set_var("JCODE_HOME", temp.path());
+
+    let captured = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let provider: Arc<dyn Provider> = Arc::new(ResumeCapturingProvider {
+        captured_resume_session_ids: captured.clone(),
+    });
+    let registry = Registry::new(provider.clone()).await;
+    let mut agent = Agent::new(provider, registry);
+    let last_resume_id = || captured.lock().unwrap().last().cloned().flatten();
+
+    agent.run_once_capture("first").await.expect("first turn");
+    agent.run_once_capture("second").await.expect("second turn");
+    assert_eq!(
+        last_resume_id().as_deref(),
+        Some("provider-session-1"),
+        "an untouched history should resume the provider conversation"
+    );
+
+    // Rewind.
+    agent.rewind_to_message(1).expect("rewind");
+    agent.run_once_capture("after rewind").await.expect("turn");
+    assert_eq!(last_resume_id(), None, "turn after rewind resumed");
+
+    // Undo the rewind after the provider conversation has moved on.
+    assert!(agent.provider_session_id.is_some());
+    agent.undo_rewind().expect("undo rewind");
+    agent.run_once_capture("after undo").await.expect("turn");
+    assert_eq!(last_resume_id(), None, "turn after rewind undo resumed");
+
+    // Restore a session whose persisted history was rewritten on disk.
+    let session_id = agent.session.id.clone();
+    let mut stored = crate::session::Session::load(&session_id).expect("load session");
+    stored.provider_session_id = Some("stale-provider-session".to_string());
+    stored.truncate_messages(1);
+    stored.save().expect("save rewritten session");
+    agent.restore_session(&session_id).expect("restore session");
+    agent.run_once_capture("after restore").await.expect("turn");
+    assert_eq!(last_resume_id(), None, "turn after restore resumed");
+
+    // Compaction.
+    for i in 0..30 {
+        agent.add_message(
+            Role::User,
+            vec![ContentBlock::Text {
+                text: format!("turn {i} {}", "x".repeat(120)),
+                cache_control: None,
+            }],
+        );
+    }
+    assert!(agent.provider_session_id.is_some());
+    let (message, success) = agent.request_manual_compaction();
+    assert!(success, "manual compaction should start: {message}");
+    let deadline = Instant::now() + Duration::from_secs(2);
+    let mut compacted = false;
+    while Instant::now() < deadline {
+        if agent.messages_for_provider().1.is_some() {
+            compacted = true;
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert!(compacted, "manual compaction event should be applied");
+    agent
+        .run_once_capture("after compaction")
+        .await
+        .expect("turn");
+    assert_eq!(last_resume_id(), None, "turn after compaction resumed");
+
+    if let Some(prev_home) = prev_home {
+        crate::env::set_var("JCODE_HOME", prev_home);
+    } else {
+        crate::env::remove_var("JCODE_HOME");
+    }
+}
+
+/// Captures every request as sent (dynamic prompt included) and asks for one
+/// `write` call before answering.
+struct PromptCapturingProvider {
+    requests: Arc<std::sync::Mutex<Vec<Vec<Message>>>>,
+}
+
+#[async_trait]
+impl Provider for PromptCapturingProvider {
+    async fn complete(
+        &self,
+        messages: &[Message],
+        _tools: &[ToolDefinition],
+        _system: &str,
+        _resume_session_id: Option<&str>,
+    ) -> Result<EventStream> {
+        let call = {
+            let mut requests = self.requests.lock().unwrap();
+            requests.push(messages.to_vec());
+            requests.len()
+        };
+        let events = if call == 1 {
+            vec![
+                StreamEvent::ToolUseStart {
+                    id: "call_write".to_string(),
+                    name: "write".to_string(),
+                },
+                StreamEvent::ToolInputDelta(
+                    serde_json::json!({ "file_path": "new.txt", "content": "dirty" }).to_string(),
+                ),
+                StreamEvent::ToolUseEnd,
+                StreamEvent::MessageEnd {
+                    stop_reason: Some("tool_use".to_string()),
+                },
+            ]
+        } else {
+            vec![
+                StreamEvent::TextDelta("done".to_string()),
+                StreamEvent::MessageEnd {
+                    stop_reason: Some("end_turn".to_string()),
+                },
+            ]
+        };
+        Ok(Box::pin(futures::stream::iter(events.into_iter().map(Ok))))
+    }
+
+    fn name(&self) -> &str {
+        "openai"
+    }
+
+    fn fork(&self) -> Arc<dyn Provider> {
+        Arc::new(Self {
+            requests: self.requests.clone(),
+        })
+    }
+}
+
+#[tokio::test]
+async fn git_context_is_stable_within_a_turn_for_prompt_caching() {
+    let _guard = crate::storage::lock_test_env();
+    let home = tempfile::TempDir::new().expect("tempdir");
+    let repo = tempfile::TempDir::new().expect("tempdir");
+    let prev_home = std::env::var_os("JCODE_HOME");
+    let prev_git_context = std::env::var_os("JCODE_PROMPT_GIT_CONTEXT");
+    crate::env::set_var("JCODE_HOME", home.path());
+    crate::env::set_var("JCODE_PROMPT_GIT_CONTEXT", "true");
+    crate::config::invalidate_config_cache();
+
+    let git = |args: &[&str]| {
+        let status = std::process::Command::new("git")
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(repo.path())
+            .status()
+            .expect("run git");
+        assert!(status.success(), "git {:?} failed", args);
+    };
+    git(&["init", "-q"]);
+    std::fs::write(repo.path().join("lib.rs"), "fn a() {}").unwrap();
+    git(&["add", "lib.rs"]);
+    git(&["commit", "-q", "-m", "Add lib"]);
+
+    let requests = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let provider: Arc<dyn Provider> = Arc::new(PromptCapturingProvider {
+        requests: requests.clone(),
+    });
+    let registry = Registry::new(provider.clone()).await;
+    let mut agent = Agent::new(provider, registry);
+    agent.memory_enabled = false;
+    agent.set_working_dir(&repo.path().display().to_string());
+
+    agent
+        .run_once_capture("edit something")
+        .await
+        .expect("turn 1");
+    assert!(repo.path().join("new.txt").exists());
+    agent.run_once_capture("thanks").await.expect("turn 2");
+
+    let requests = requests.lock().unwrap().clone();
+    assert_eq!(requests.len(), 3, "tool call, answer, next turn answer");
+    let is_git_context = |m: &Message| message_text(m).contains("# Git Context");
+    assert!(
+        requests
+            .iter()
+            .all(|request| request.iter().any(is_git_context)),
+        "every request should carry the git context"
+    );
+
+    // The write dirtied the tree mid-turn, but the turn's snapshot is reused,
+    // so the follow-up request only appends to the first one.
+    let mut tracker = crate::cache_tracker::CacheTracker::new();
+    tracker.record_request(&requests[0]);
+    tracker.record_request(&requests[1]);
+    assert_eq!(tracker.turn_count(), 2);
+    assert!(!tracker.had_violation(), "git context changed mid-turn");
+
+    // Across turns the context block moves after the newest prompt; the
+    // stored history before it stays append-only.
+    let history = |request: &[Message]| -> Vec<Message> {
+        request
+            .iter()
+            .filter(|m| !is_git_context(m))
+            .cloned()
+            .collect()
+    };
+    let mut tracker = crate::cache_tracker::CacheTracker::new();
+    for request in &requests {
+        tracker.record_request(&history(request));
+        assert!(!tracker.had_violation(), "history prefix changed");
+    }
+
+    match prev_git_context {
+        Some(value) => crate::env::set_var("JCODE_PROMPT_GIT_CONTEXT", value),
+        None => crate::env::remove_var("JCODE_PROMPT_GIT_CONTEXT"),
+    }
+    match prev_home {
+        Some(value) => crate::env::set_var("JCODE_HOME", value),
+        None => crate::env::remove_var("JCODE_HOME"),
+    }
+    crate::config::invalidate_config_cache();
+}
@@ -39,6 +39,56 @@ pub struct ResourceBudget {
     pub cycle_budget_desc: String,
 }
 
+impl ResourceBudget {
+    /// Describe `provider`'s budget from the recorded usage forecasts. Falls
+    /// back to adaptive placeholders when no window history matches.
+    pub fn from_usage_forecasts(provider: &str, forecasts: &[crate::usage::UsageForecast]) -> Self {
+        let prefix = super::scheduler::usage_provider_prefix(provider);
+        let windows: Vec<&crate::usage::UsageForecast> = forecasts
+            .iter()
+            .filter(|f| prefix.is_some_and(|prefix| f.provider_name.starts_with(prefix)))
+            .collect();
+        if windows.is_empty() {
+            return Self {
+                provider: provider.to_string(),
+                tokens_remaining_desc: "unknown (adaptive)".to_string(),
+                window_resets_desc: "unknown".to_string(),
+                user_usage_rate_desc: "estimated from history".to_string(),
+                cycle_budget_desc: "stay under 50k tokens".to_string(),
+            };
+        }
+
+        let per_window = |value: &dyn Fn(&crate::usage::UsageForecast) -> String| {
+            windows
+                .iter()
+                .map(|f| format!("{} {}", f.limit_name, value(f)))
+                .collect::<Vec<_>>()
+                .join("; ")
+        };
+        let min_remaining = windows
+            .iter()
+            .map(|f| (100.0 - f.usage_percent).max(0.0))
+            .fold(100.0_f32, f32::min);
+        let hits_limit_before_reset = windows
+            .iter()
+            .any(|f| f.hits_limit_in_secs.is_some() && !f.resets_first);
+
+        Self {
+            provider: provider.to_string(),
+            tokens_remaining_desc: per_window(&|f| format!("{:.0}% left", 100.0 - f.usage_percent)),
+            window_resets_desc: per_window(&|f| {
+                f.resets_at_local().unwrap_or_else(|| "unknown".to_string())
+            }),
+            user_usage_rate_desc: per_window(&|f| f.summary()),
+            cycle_budget_desc: if min_remaining < 20.0 || hits_limit_before_reset {
+                "stay under 15k tokens; the user is on track to exhaust a window".to_string()
+            } else {
+                "stay under 50k tokens".to_string()
+            },
+        }
+    }
+}
+
 /// Gather memory graph health stats from the MemoryManager.
 pub fn gather_memory_graph_health(
     memory_manager: &crate::memory::MemoryManager,
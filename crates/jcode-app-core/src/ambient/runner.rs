@@ -11,7 +11,10 @@ use crate::ambient::{
     self, AmbientCycleResult, AmbientLock, AmbientManager, AmbientState, AmbientStatus,
     CycleStatus, ScheduleTarget, ScheduledItem,
 };
-use crate::ambient_scheduler::{AdaptiveScheduler, AmbientSchedulerConfig};
+use crate::ambient_scheduler::{
+    AdaptiveScheduler, AmbientSchedulerConfig, CycleAction, CycleDecision,
+    usage_windows_for_provider,
+};
 use crate::config::config;
 use crate::logging;
 use crate::memory::MemoryManager;
@@ -54,6 +57,10 @@ struct AmbientRunnerInner {
     /// Soft interrupt queue for the currently-running ambient agent (if any).
     /// Telegram replies push messages here so they arrive mid-cycle.
     active_cycle_queue: RwLock<Option<SoftInterruptQueue>>,
+    /// Latest usage-aware decision about the next cycle (for status)
+    cycle_decision: RwLock<Option<CycleDecision>>,
+    /// A manual trigger runs the next cycle even if usage would hold it back
+    trigger_pending: RwLock<bool>,
 }
 
 impl AmbientRunnerHandle {
@@ -70,6 +77,8 @@ impl AmbientRunnerHandle {
                 notifier: NotificationDispatcher::new(),
                 active_user_sessions: RwLock::new(0),
                 active_cycle_queue: RwLock::new(None),
+                cycle_decision: RwLock::new(None),
+                trigger_pending: RwLock::new(false),
             }),
         }
     }
@@ -104,11 +113,11 @@ impl AmbientRunnerHandle {
         if let Some(ref q) = *queue
             && let Ok(mut q) = q.lock()
         {
-            q.push(SoftInterruptMessage {
-                content: format!("[{} message from user]\n{}", source, text),
-                urgent: false,
-                source: SoftInterruptSource::User,
-            });
+            q.push(SoftInterruptMessage::new(
+                format!("[{} message from user]\n{}", source, text),
+                false,
+                SoftInterruptSource::User,
+            ));
             logging::info(&format!(
                 "{} message injected into active ambient cycle: {}",
                 source,
@@ -138,6 +147,7 @@ impl AmbientRunnerHandle {
             state.status = AmbientStatus::Idle;
         }
         drop(state);
+        *self.inner.trigger_pending.write().await = true;
         self.inner.wake_notify.notify_one();
     }
 
@@ -174,6 +184,7 @@ impl AmbientRunnerHandle {
         let state = self.state().await;
         let running = self.is_running().await;
         let active_sessions = *self.inner.active_user_sessions.read().await;
+        let scheduling = self.inner.cycle_decision.read().await.clone();
 
         let (
             queue_count,
@@ -268,6 +279,7 @@ impl AmbientRunnerHandle {
             "next_scheduled_task_preview": next_reminder_preview,
             "next_scheduled_task_due": next_reminder_due,
             "overdue_scheduled_task_count": overdue_reminder_count,
+            "scheduling": scheduling,
             "active_user_sessions": active_sessions,
         })
         .to_string()
@@ -392,6 +404,7 @@ impl AmbientRunnerHandle {
 
         let mut agent = Agent::new(cycle_provider, registry);
         agent.set_debug(session.is_debug);
+        agent.set_usage_origin(crate::usage::UsageOrigin::Ambient);
         agent.restore_session(session_id)?;
 
         let reminder = ambient::format_scheduled_session_message(item);
@@ -421,6 +434,7 @@ impl AmbientRunnerHandle {
                 child.provider_key = parent.provider_key.clone();
                 child.route_api_method = parent.route_api_method.clone();
                 child.model = parent.model.clone();
+                child.model_route = parent.model_route.clone();
                 child.subagent_model = parent.subagent_model.clone();
                 child.improve_mode = parent.improve_mode;
                 child.autoreview_enabled = parent.autoreview_enabled;
@@ -464,6 +478,7 @@ impl AmbientRunnerHandle {
 
         let mut agent = Agent::new_with_session(cycle_provider, registry, child, None);
         agent.set_debug(child_is_debug);
+        agent.set_usage_origin(crate::usage::UsageOrigin::Ambient);
         if item.working_dir.is_some() {
             agent.set_working_dir_for_pending_context(item.working_dir.clone());
         }
@@ -566,6 +581,9 @@ impl AmbientRunnerHandle {
             min_interval_minutes: amb_config.min_interval_minutes,
             max_interval_minutes: amb_config.max_interval_minutes,
             pause_on_active_session: amb_config.pause_on_active_session,
+            interactive_usage_threshold_percent: amb_config.interactive_usage_threshold_percent,
+            usage_reserve_percent: amb_config.usage_reserve_percent,
+            prefer_quiet_hours: amb_config.prefer_quiet_hours,
             ..Default::default()
         };
         let mut scheduler = AdaptiveScheduler::new(scheduler_config);
@@ -692,6 +710,40 @@ impl AmbientRunnerHandle {
                 continue;
             }
 
+            // Check subscription usage so the cycle never starves interactive work
+            crate::usage::fetch_all_provider_usage().await;
+            let windows =
+                usage_windows_for_provider(provider.name(), crate::usage::stored_usage_windows());
+            let decision = scheduler.decide(&windows, Utc::now());
+            *self.inner.cycle_decision.write().await = Some(decision.clone());
+            let forced = std::mem::take(&mut *self.inner.trigger_pending.write().await);
+            if decision.waits() && !forced {
+                let retry_secs = decision.retry_in_secs.unwrap_or(MAX_IDLE_POLL_SECS).max(1);
+                let sleep_secs = next_direct_due
+                    .map(|next| (next - Utc::now()).num_seconds().max(1) as u64)
+                    .map_or(retry_secs, |secs| secs.min(retry_secs));
+                logging::info(&format!(
+                    "Ambient runner: cycle held back ({:?}: {}), rechecking in {}s",
+                    decision.action,
+                    decision.reasons.join("; "),
+                    sleep_secs
+                ));
+                {
+                    let mut s = self.inner.state.write().await;
+                    s.status = AmbientStatus::Scheduled {
+                        next_wake: Utc::now() + chrono::Duration::seconds(retry_secs as i64),
+                    };
+                    let _ = s.save();
+                }
+                tokio::select! {
+                    _ = self.inner.wake_notify.notified() => {
+                        logging::info("Ambient runner: nudged awake");
+                    },
+                    _ = tokio::time::sleep(std::time::Duration::from_secs(sleep_secs)) => {},
+                }
+                continue;
+            }
+
             // Try to acquire lock
             let lock = match AmbientLock::try_acquire() {
                 Ok(Some(lock)) => lock,
@@ -711,7 +763,15 @@ impl AmbientRunnerHandle {
             logging::info("Ambient runner: starting ambient cycle");
             self.set_running_detail("starting cycle").await;
 
+            let cycle_started_at = Utc::now();
+            scheduler.on_cycle_started(&windows);
             let cycle_result = self.run_cycle(&provider).await;
+            crate::usage::fetch_all_provider_usage().await;
+            scheduler.on_cycle_finished(
+                &usage_windows_for_provider(provider.name(), crate::usage::stored_usage_windows()),
+                cycle_started_at,
+                Utc::now(),
+            );
 
             // Clear the soft interrupt queue — cycle is done
             {
@@ -719,6 +779,7 @@ impl AmbientRunnerHandle {
                 *aq = None;
             }
 
+            crate::metrics::record_ambient_cycle(cycle_result.is_ok());
             match cycle_result {
                 Ok(result) => {
                     logging::info(&format!(
@@ -849,13 +910,22 @@ impl AmbientRunnerHandle {
         let recent_sessions = ambient::gather_recent_sessions(state.last_run);
         let feedback_memories = ambient::gather_feedback_memories(&memory_manager);
 
-        let budget = ambient::ResourceBudget {
-            provider: provider.name().to_string(),
-            tokens_remaining_desc: "unknown (adaptive)".to_string(),
-            window_resets_desc: "unknown".to_string(),
-            user_usage_rate_desc: "estimated from history".to_string(),
-            cycle_budget_desc: "stay under 50k tokens".to_string(),
-        };
+        let mut budget = ambient::ResourceBudget::from_usage_forecasts(
+            provider.name(),
+            &crate::usage::stored_usage_forecasts(),
+        );
+        let shrink = self
+            .inner
+            .cycle_decision
+            .read()
+            .await
+            .as_ref()
+            .is_some_and(|decision| decision.action == CycleAction::Shrink);
+        if shrink {
+            budget.cycle_budget_desc =
+                "stay under 15k tokens; usage is being held back for the user's interactive work"
+                    .to_string();
+        }
 
         let active_sessions = *self.inner.active_user_sessions.read().await;
 
@@ -898,6 +968,7 @@ impl AmbientRunnerHandle {
 
         let mut agent = Agent::new(cycle_provider.clone(), registry);
         agent.set_debug(true);
+        agent.set_usage_origin(crate::usage::UsageOrigin::Ambient);
         agent.set_system_prompt(&system_prompt);
         let ambient_session_id = agent.session_id().to_string();
         ambient_tools::register_ambient_session(ambient_session_id.clone());
@@ -2,8 +2,14 @@
 //!
 //! Tracks per-call token usage (user vs ambient), maintains a rolling usage log,
 //! and computes adaptive intervals for ambient cycles based on rate limit headroom.
+//! Before each cycle it also reads the shared subscription usage history (see
+//! `usage::forecast`) to decide whether the cycle should run, shrink, or wait
+//! so it never starves interactive work.
 use crate::storage;
-use chrono::{Duration as ChronoDuration, Utc};
+use crate::usage::UsageWindowHistory;
+use chrono::{DateTime, Duration as ChronoDuration, Local, TimeZone, Timelike, Utc};
+use serde::Serialize;
+use std::collections::VecDeque;
 use std::path::PathBuf;
 use std::time::Duration;
 
@@ -148,6 +154,13 @@ pub struct AmbientSchedulerConfig {
     /// Fraction of remaining budget reserved for user. 0.8 means ambient gets
     /// at most 20% of headroom.
     pub user_budget_reserve: f32,
+    /// Interactive consumption (percentage points of a window in the past
+    /// hour) at which cycles are skipped; cycles shrink above half of it.
+    pub interactive_usage_threshold_percent: f32,
+    /// Window usage percentage ambient cycles must never push past.
+    pub usage_reserve_percent: f32,
+    /// Defer cycles into historically quiet hours.
+    pub prefer_quiet_hours: bool,
 }
 
 impl Default for AmbientSchedulerConfig {
@@ -157,10 +170,187 @@ impl Default for AmbientSchedulerConfig {
             max_interval_minutes: 120,
             pause_on_active_session: true,
             user_budget_reserve: 0.8,
+            interactive_usage_threshold_percent: 10.0,
+            usage_reserve_percent: 70.0,
+            prefer_quiet_hours: true,
         }
     }
 }
 
+// ---------------------------------------------------------------------------
+// Usage-aware cycle decisions
+// ---------------------------------------------------------------------------
+
+/// Percentage points one cycle is assumed to cost until cycles are measured.
+const DEFAULT_CYCLE_COST_PERCENT: f32 = 2.0;
+
+/// Measured cycle costs averaged for the estimate.
+const MEASURED_CYCLE_COSTS: usize = 5;
+
+/// How long a skipped cycle waits before the usage is checked again.
+const SKIP_RECHECK: Duration = Duration::from_secs(30 * 60);
+
+/// Cycles shrink once a window is this close to the reserve.
+const SHRINK_MARGIN_PERCENT: f32 = 10.0;
+
+/// Quiet hours are only learned from at least this much history.
+const MIN_QUIET_HOURS_HISTORY_SECS: u64 = 2 * 86_400;
+
+/// An hour is quiet when it saw at most this share of the average hourly use.
+const QUIET_HOUR_FRACTION: f32 = 0.25;
+
+/// Ambient cycle spans are kept this long to tell their usage apart from
+/// interactive usage.
+const CYCLE_SPAN_RETENTION_SECS: u64 = 2 * 3_600;
+
+/// The usage-history provider prefix for an ambient provider name, matching
+/// the names `/usage` reports under.
+pub fn usage_provider_prefix(provider: &str) -> Option<&'static str> {
+    let lower = provider.to_ascii_lowercase();
+    if lower.contains("openai") || lower.contains("codex") {
+        Some("OpenAI")
+    } else if lower.contains("claude") || lower.contains("anthropic") {
+        Some("Anthropic")
+    } else {
+        None
+    }
+}
+
+/// The subscription windows of `provider` among `windows`.
+pub fn usage_windows_for_provider(
+    provider: &str,
+    windows: Vec<UsageWindowHistory>,
+) -> Vec<UsageWindowHistory> {
+    let Some(prefix) = usage_provider_prefix(provider) else {
+        return Vec::new();
+    };
+    windows
+        .into_iter()
+        .filter(|window| window.provider_name.starts_with(prefix))
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CycleAction {
+    Run,
+    /// Run with a smaller token budget
+    Shrink,
+    /// Wait because of current usage
+    Skip,
+    /// Wait for a historically quiet hour
+    Defer,
+}
+
+/// One subscription window as the scheduler saw it.
+#[derive(Debug, Clone, Serialize)]
+pub struct WindowUsage {
+    pub provider_name: String,
+    pub limit_name: String,
+    pub usage_percent: f32,
+    /// Percentage points consumed in the past hour outside ambient cycles.
+    pub interactive_last_hour_percent: f32,
+}
+
+/// Whether the next ambient cycle should run, and the data behind it.
+#[derive(Debug, Clone, Serialize)]
+pub struct CycleDecision {
+    pub action: CycleAction,
+    pub reasons: Vec<String>,
+    /// When a skipped or deferred cycle is reconsidered.
+    pub retry_in_secs: Option<u64>,
+    pub windows: Vec<WindowUsage>,
+    /// Local hours (0-23) learned as historically quiet.
+    pub quiet_hours: Vec<u32>,
+    pub cycle_cost_percent: f32,
+    pub interactive_threshold_percent: f32,
+    pub reserve_percent: f32,
+    pub decided_at: DateTime<Utc>,
+}
+
+impl CycleDecision {
+    /// Whether the cycle should wait instead of running now.
+    pub fn waits(&self) -> bool {
+        matches!(self.action, CycleAction::Skip | CycleAction::Defer)
+    }
+}
+
+fn local_hour(unix_secs: u64) -> Option<u32> {
+    Local
+        .timestamp_opt(unix_secs as i64, 0)
+        .single()
+        .map(|at| at.hour())
+}
+
+/// Percentage points `window` gained in the hour before `now`, skipping
+/// sample intervals that overlap an ambient cycle.
+fn interactive_last_hour(window: &UsageWindowHistory, cycle_spans: &[(u64, u64)], now: u64) -> f32 {
+    let start = now.saturating_sub(3_600);
+    window
+        .samples
+        .windows(2)
+        .filter(|pair| pair[1].at_unix_secs > start && pair[1].at_unix_secs <= now)
+        .filter(|pair| {
+            !cycle_spans.iter().any(|(cycle_start, cycle_end)| {
+                pair[0].at_unix_secs < *cycle_end && pair[1].at_unix_secs > *cycle_start
+            })
+        })
+        .map(|pair| (pair[1].usage_percent - pair[0].usage_percent).max(0.0))
+        .sum()
+}
+
+/// Local hours whose historical consumption stayed well below the average,
+/// learned from the busiest window. Empty until there is enough history.
+fn learn_quiet_hours(windows: &[UsageWindowHistory]) -> Vec<u32> {
+    let mut best: Option<([f32; 24], f32)> = None;
+    for window in windows {
+        let (Some(first), Some(last)) = (window.samples.first(), window.samples.last()) else {
+            continue;
+        };
+        if last.at_unix_secs.saturating_sub(first.at_unix_secs) < MIN_QUIET_HOURS_HISTORY_SECS {
+            continue;
+        }
+        let mut hours = [0.0_f32; 24];
+        for pair in window.samples.windows(2) {
+            let delta = pair[1].usage_percent - pair[0].usage_percent;
+            if delta > 0.0
+                && let Some(hour) = local_hour(pair[1].at_unix_secs)
+            {
+                hours[hour as usize] += delta;
+            }
+        }
+        let total: f32 = hours.iter().sum();
+        if best.is_none_or(|(_, best_total)| total > best_total) {
+            best = Some((hours, total));
+        }
+    }
+    let Some((hours, total)) = best.filter(|(_, total)| *total > 0.0) else {
+        return Vec::new();
+    };
+    let quiet_limit = total / 24.0 * QUIET_HOUR_FRACTION;
+    (0..24u32)
+        .filter(|hour| hours[*hour as usize] <= quiet_limit)
+        .collect()
+}
+
+/// Seconds from `now` until the next quiet hour starts, if one starts within
+/// `horizon`.
+fn secs_until_quiet_hour(
+    quiet_hours: &[u32],
+    now: DateTime<Utc>,
+    horizon: Duration,
+) -> Option<u64> {
+    let local = now.with_timezone(&Local);
+    let into_hour = u64::from(local.minute()) * 60 + u64::from(local.second());
+    (1..=24u64)
+        .map(|ahead| (ahead * 3_600).saturating_sub(into_hour))
+        .take_while(|secs| *secs <= horizon.as_secs())
+        .find(|secs| {
+            local_hour(now.timestamp().max(0) as u64 + secs)
+                .is_some_and(|hour| quiet_hours.contains(&hour))
+        })
+}
+
 // ---------------------------------------------------------------------------
 // Adaptive scheduler
 // ---------------------------------------------------------------------------
@@ -172,6 +362,13 @@ pub struct AdaptiveScheduler {
     backoff_multiplier: u32,
     /// Whether a user session is currently active.
     user_active: bool,
+    /// Recent ambient cycles as (start, end) unix seconds.
+    cycle_spans: Vec<(u64, u64)>,
+    /// Window usage when the running cycle started, keyed by window.
+    cycle_start_usage: Vec<(String, f32)>,
+    /// Percentage points recent cycles cost, newest last.
+    cycle_costs: VecDeque<f32>,
+    last_decision: Option<CycleDecision>,
 }
 
 impl AdaptiveScheduler {
@@ -181,9 +378,195 @@ impl AdaptiveScheduler {
             config,
             backoff_multiplier: 1,
             user_active: false,
+            cycle_spans: Vec::new(),
+            cycle_start_usage: Vec::new(),
+            cycle_costs: VecDeque::new(),
+            last_decision: None,
         }
     }
 
+    /// Decide whether the next cycle runs, shrinks, or waits, given the
+    /// ambient provider's subscription windows.
+    pub fn decide(&mut self, windows: &[UsageWindowHistory], now: DateTime<Utc>) -> CycleDecision {
+        let now_secs = now.timestamp().max(0) as u64;
+        let cost = self.cycle_cost_percent();
+        let reserve = self.config.usage_reserve_percent;
+        let threshold = self.config.interactive_usage_threshold_percent;
+        let min = Duration::from_secs(self.config.min_interval_minutes as u64 * 60);
+        let max = Duration::from_secs(self.config.max_interval_minutes as u64 * 60);
+        self.cycle_spans
+            .retain(|(_, end)| now_secs.saturating_sub(*end) <= CYCLE_SPAN_RETENTION_SECS);
+
+        let usage: Vec<WindowUsage> = windows
+            .iter()
+            .map(|window| WindowUsage {
+                provider_name: window.provider_name.clone(),
+                limit_name: window.limit_name.clone(),
+                usage_percent: window.samples.last().map_or(0.0, |s| s.usage_percent),
+                interactive_last_hour_percent: interactive_last_hour(
+                    window,
+                    &self.cycle_spans,
+                    now_secs,
+                ),
+            })
+            .collect();
+        let quiet_hours = if self.config.prefer_quiet_hours {
+            learn_quiet_hours(windows)
+        } else {
+            Vec::new()
+        };
+
+        let mut action = CycleAction::Run;
+        let mut reasons = Vec::new();
+        let mut retry = None;
+        let recheck = SKIP_RECHECK.clamp(min, max);
+        if usage.is_empty() {
+            reasons.push("no subscription usage history for this provider".to_string());
+        }
+        for (window, history) in usage.iter().zip(windows) {
+            if window.usage_percent + cost > reserve {
+                action = CycleAction::Skip;
+                reasons.push(format!(
+                    "{} at {:.0}% + ~{:.1}% per cycle would pass the {:.0}% reserve",
+                    window.limit_name, window.usage_percent, cost, reserve
+                ));
+                let until_reset = history
+                    .resets_at
+                    .as_deref()
+                    .and_then(|reset| DateTime::parse_from_rfc3339(reset).ok())
+                    .map(|reset| (reset.with_timezone(&Utc) - now).num_seconds().max(0) as u64)
+                    .map(|secs| Duration::from_secs(secs).clamp(min, max));
+                retry = Some(until_reset.unwrap_or(recheck));
+            }
+        }
+        let busiest = usage
+            .iter()
+            .max_by(|a, b| {
+                a.interactive_last_hour_percent
+                    .total_cmp(&b.interactive_last_hour_percent)
+            })
+            .filter(|window| window.interactive_last_hour_percent > 0.0);
+        if let Some(window) = busiest
+            && window.interactive_last_hour_percent >= threshold
+        {
+            action = CycleAction::Skip;
+            reasons.push(format!(
+                "interactive use took {:.1}% of the {} in the past hour (limit {:.0}%)",
+                window.interactive_last_hour_percent, window.limit_name, threshold
+            ));
+            retry = retry.max(Some(recheck));
+        }
+
+        let current_hour = local_hour(now_secs);
+        if action == CycleAction::Run
+            && !quiet_hours.is_empty()
+            && current_hour.is_some_and(|hour| !quiet_hours.contains(&hour))
+            && let Some(secs) = secs_until_quiet_hour(&quiet_hours, now, max)
+        {
+            action = CycleAction::Defer;
+            reasons.push(format!(
+                "{:02}:00 is usually busy; waiting {}m for a quiet hour",
+                current_hour.unwrap_or_default(),
+                secs.div_ceil(60)
+            ));
+            retry = Some(Duration::from_secs(secs).max(min));
+        }
+
+        if action == CycleAction::Run {
+            if let Some(window) = busiest
+                && window.interactive_last_hour_percent >= threshold / 2.0
+            {
+                action = CycleAction::Shrink;
+                reasons.push(format!(
+                    "interactive use took {:.1}% of the {} in the past hour",
+                    window.interactive_last_hour_percent, window.limit_name
+                ));
+            }
+            if let Some(window) = usage
+                .iter()
+                .find(|window| window.usage_percent + cost > reserve - SHRINK_MARGIN_PERCENT)
+            {
+                action = CycleAction::Shrink;
+                reasons.push(format!(
+                    "{} at {:.0}% is near the {:.0}% reserve",
+                    window.limit_name, window.usage_percent, reserve
+                ));
+            }
+        }
+        if action == CycleAction::Run && !usage.is_empty() {
+            reasons.push("usage leaves room for a full cycle".to_string());
+        }
+
+        let decision = CycleDecision {
+            action,
+            reasons,
+            retry_in_secs: retry.map(|retry| retry.as_secs()),
+            windows: usage,
+            quiet_hours,
+            cycle_cost_percent: cost,
+            interactive_threshold_percent: threshold,
+            reserve_percent: reserve,
+            decided_at: now,
+        };
+        self.last_decision = Some(decision.clone());
+        decision
+    }
+
+    /// The most recent [`Self::decide`] result.
+    pub fn last_decision(&self) -> Option<&CycleDecision> {
+        self.last_decision.as_ref()
+    }
+
+    /// Note the window usage as a cycle starts, to measure what it costs.
+    pub fn on_cycle_started(&mut self, windows: &[UsageWindowHistory]) {
+        self.cycle_start_usage = windows
+            .iter()
+            .filter_map(|window| {
+                let last = window.samples.last()?;
+                Some((window_key(window), last.usage_percent))
+            })
+            .collect();
+    }
+
+    /// Record a finished cycle so its usage is not mistaken for interactive
+    /// use, and learn its cost from the window usage now.
+    pub fn on_cycle_finished(
+        &mut self,
+        windows: &[UsageWindowHistory],
+        started_at: DateTime<Utc>,
+        ended_at: DateTime<Utc>,
+    ) {
+        self.cycle_spans.push((
+            started_at.timestamp().max(0) as u64,
+            ended_at.timestamp().max(0) as u64,
+        ));
+        let start_usage = std::mem::take(&mut self.cycle_start_usage);
+        let cost = windows
+            .iter()
+            .filter_map(|window| {
+                let key = window_key(window);
+                let (_, before) = start_usage.iter().find(|(k, _)| *k == key)?;
+                let after = window.samples.last()?.usage_percent;
+                // A drop means the window reset mid-cycle; nothing to learn.
+                (after >= *before).then_some(after - before)
+            })
+            .reduce(f32::max);
+        if let Some(cost) = cost {
+            self.cycle_costs.push_back(cost);
+            while self.cycle_costs.len() > MEASURED_CYCLE_COSTS {
+                self.cycle_costs.pop_front();
+            }
+        }
+    }
+
+    /// Percentage points a cycle is expected to cost.
+    pub fn cycle_cost_percent(&self) -> f32 {
+        if self.cycle_costs.is_empty() {
+            return DEFAULT_CYCLE_COST_PERCENT;
+        }
+        self.cycle_costs.iter().sum::<f32>() / self.cycle_costs.len() as f32
+    }
+
     /// Core interval calculation following the algorithm in AMBIENT_MODE.md.
     pub fn calculate_interval(&self, rate_limit_info: Option<&RateLimitInfo>) -> Duration {
         let max = Duration::from_secs(self.config.max_interval_minutes as u64 * 60);
@@ -276,6 +659,10 @@ impl AdaptiveScheduler {
     }
 }
 
+fn window_key(window: &UsageWindowHistory) -> String {
+    format!("{}::{}", window.provider_name, window.limit_name)
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -494,4 +881,117 @@ mod tests {
         assert_eq!(log.records.len(), 1);
         assert_eq!(log.records[0].total_tokens(), 200);
     }
+
+    fn usage_window(resets_at: Option<String>, samples: &[(u64, f32)]) -> UsageWindowHistory {
+        UsageWindowHistory {
+            provider_name: "Anthropic (Claude)".to_string(),
+            limit_name: "5-hour window".to_string(),
+            resets_at,
+            samples: samples
+                .iter()
+                .map(|(at_unix_secs, usage_percent)| crate::usage::UsageSample {
+                    at_unix_secs: *at_unix_secs,
+                    usage_percent: *usage_percent,
+                })
+                .collect(),
+        }
+    }
+
+    fn usage_scheduler(prefer_quiet_hours: bool) -> AdaptiveScheduler {
+        AdaptiveScheduler::new(AmbientSchedulerConfig {
+            prefer_quiet_hours,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_decide_keeps_the_usage_reserve() {
+        let now = Utc::now();
+        let secs = now.timestamp() as u64;
+        let resets_at = (now + ChronoDuration::hours(1)).to_rfc3339();
+        let mut scheduler = usage_scheduler(false);
+
+        let decision = scheduler.decide(
+            &[usage_window(
+                Some(resets_at),
+                &[(secs - 7_200, 69.0), (secs - 60, 69.0)],
+            )],
+            now,
+        );
+        assert_eq!(decision.action, CycleAction::Skip);
+        assert!(decision.waits());
+        assert_eq!(decision.retry_in_secs, Some(3_600));
+        assert!(
+            decision.reasons[0].contains("70% reserve"),
+            "{:?}",
+            decision.reasons
+        );
+
+        let near = scheduler.decide(&[usage_window(None, &[(secs - 60, 62.0)])], now);
+        assert_eq!(near.action, CycleAction::Shrink);
+
+        let empty = scheduler.decide(&[], now);
+        assert_eq!(empty.action, CycleAction::Run);
+        assert_eq!(
+            scheduler.last_decision().map(|decision| decision.action),
+            Some(CycleAction::Run)
+        );
+    }
+
+    #[test]
+    fn test_decide_backs_off_for_interactive_usage_but_not_ambient_cycles() {
+        let now = Utc::now();
+        let secs = now.timestamp() as u64;
+        let window = usage_window(None, &[(secs - 3_000, 10.0), (secs - 600, 22.0)]);
+        let mut scheduler = usage_scheduler(false);
+
+        let decision = scheduler.decide(std::slice::from_ref(&window), now);
+        assert_eq!(decision.action, CycleAction::Skip);
+        assert_eq!(decision.windows[0].interactive_last_hour_percent, 12.0);
+        assert_eq!(decision.retry_in_secs, Some(SKIP_RECHECK.as_secs()));
+
+        let moderate = usage_window(None, &[(secs - 3_000, 10.0), (secs - 600, 16.0)]);
+        assert_eq!(
+            scheduler.decide(&[moderate], now).action,
+            CycleAction::Shrink
+        );
+
+        // The same climb during an ambient cycle is not interactive use.
+        let started = now - ChronoDuration::minutes(45);
+        scheduler.on_cycle_started(&[usage_window(None, &[(secs - 3_000, 10.0)])]);
+        scheduler.on_cycle_finished(std::slice::from_ref(&window), started, now);
+        assert_eq!(scheduler.cycle_cost_percent(), 12.0);
+        let decision = scheduler.decide(&[window], now);
+        assert_eq!(decision.windows[0].interactive_last_hour_percent, 0.0);
+        assert_eq!(decision.action, CycleAction::Run);
+    }
+
+    #[test]
+    fn test_decide_defers_into_learned_quiet_hour() {
+        let now = Utc::now();
+        let secs = now.timestamp() as u64;
+        let quiet_hour = local_hour(secs + 3_600).unwrap();
+        // Three days of hourly use, except in the hour after this one.
+        let mut samples = Vec::new();
+        for hours_ago in (2..74u64).rev() {
+            let start = secs - hours_ago * 3_600;
+            let used = if local_hour(start + 1_800) == Some(quiet_hour) {
+                0.0
+            } else {
+                1.0
+            };
+            samples.push((start, 0.0));
+            samples.push((start + 1_800, used));
+        }
+        let window = usage_window(None, &samples);
+
+        let decision = usage_scheduler(true).decide(std::slice::from_ref(&window), now);
+        assert_eq!(decision.quiet_hours, vec![quiet_hour]);
+        assert_eq!(decision.action, CycleAction::Defer);
+        assert!(decision.retry_in_secs.is_some_and(|secs| secs <= 3_600));
+
+        let decision = usage_scheduler(false).decide(&[window], now);
+        assert!(decision.quiet_hours.is_empty());
+        assert_eq!(decision.action, CycleAction::Run);
+    }
 }
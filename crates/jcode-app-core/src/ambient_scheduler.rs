@@ -1,3 +1,4 @@
 pub use crate::ambient::scheduler::{
-    AdaptiveScheduler, AmbientSchedulerConfig, RateLimitInfo, UsageLog, UsageRecord, UsageSource,
+    AdaptiveScheduler, AmbientSchedulerConfig, CycleAction, CycleDecision, RateLimitInfo, UsageLog,
+    UsageRecord, UsageSource, WindowUsage, usage_windows_for_provider,
 };
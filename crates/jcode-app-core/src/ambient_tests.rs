@@ -455,3 +455,35 @@ fn test_scheduled_queue_items_accessor() {
     assert_eq!(items.len(), 1);
     assert_eq!(items[0].id, "s1");
 }
+
+#[test]
+fn test_resource_budget_from_usage_forecasts() {
+    let forecasts = vec![
+        crate::usage::UsageForecast {
+            provider_name: "Anthropic (Claude)".into(),
+            limit_name: "5-hour window".into(),
+            usage_percent: 85.0,
+            burn_percent_per_hour: Some(10.0),
+            hits_limit_in_secs: Some(5_400),
+            ..Default::default()
+        },
+        crate::usage::UsageForecast {
+            provider_name: "OpenAI (ChatGPT)".into(),
+            limit_name: "5-hour window".into(),
+            usage_percent: 10.0,
+            ..Default::default()
+        },
+    ];
+
+    let budget = ResourceBudget::from_usage_forecasts("claude", &forecasts);
+    assert_eq!(budget.tokens_remaining_desc, "5-hour window 15% left");
+    assert_eq!(
+        budget.user_usage_rate_desc,
+        "5-hour window at current burn rate you will hit 100% in ~1h 30m"
+    );
+    assert!(budget.cycle_budget_desc.contains("15k"));
+
+    let fallback = ResourceBudget::from_usage_forecasts("gemini", &forecasts);
+    assert_eq!(fallback.tokens_remaining_desc, "unknown (adaptive)");
+    assert_eq!(fallback.cycle_budget_desc, "stay under 50k tokens");
+}
@@ -0,0 +1,781 @@
+//! Backup and restore of a user's jcode state (`jcode backup`).
+//!
+//! A backup is a zstd-compressed tar holding an allowlist of `~/.jcode`:
+//! sessions, memories, skills, config, todos, and usage history. Caches, logs,
+//! sockets, locks, and builds are never packed; credentials only with
+//! `--include-auth`. A `jcode-backup.json` manifest comes first in the archive
+//! and lists every file with its category.
+//!
+//! Restores are planned before anything is written, so `--dry-run` shows the
+//! same decisions a real restore makes: missing files are created, newer local
+//! sessions and differing config are kept (reported as conflicts), and memory
+//! graphs are merged with [`MemoryGraph::merge_missing_from`], the same
+//! keep-existing rule as `jcode memory import`.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+
+use crate::memory_graph::MemoryGraph;
+use crate::session::session_journal_path_from_snapshot;
+use crate::storage;
+
+/// Manifest entry, always the first member of the archive.
+pub const MANIFEST_PATH: &str = "jcode-backup.json";
+
+/// Archive layout version written by this build.
+const FORMAT_VERSION: u32 = 1;
+
+/// Archive prefix for credential files kept in the app config directory
+/// (`~/.config/jcode`) rather than `~/.jcode`.
+const APP_CONFIG_PREFIX: &str = "app-config";
+
+const ZSTD_LEVEL: i32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupCategory {
+    Sessions,
+    Memories,
+    Skills,
+    Config,
+    Todos,
+    Usage,
+    Credentials,
+}
+
+impl BackupCategory {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Sessions => "sessions",
+            Self::Memories => "memories",
+            Self::Skills => "skills",
+            Self::Config => "config",
+            Self::Todos => "todos",
+            Self::Usage => "usage",
+            Self::Credentials => "credentials",
+        }
+    }
+}
+
+/// What a backup holds, relative to `~/.jcode`. Anything not listed (caches,
+/// logs, sockets, locks, builds, runtime state) is deliberately left out.
+const STATE_PATHS: &[(&str, BackupCategory)] = &[
+    ("sessions", BackupCategory::Sessions),
+    (storage::SQLITE_DB_FILE_NAME, BackupCategory::Sessions),
+    ("memory/global.json", BackupCategory::Memories),
+    ("memory/projects", BackupCategory::Memories),
+    ("skills", BackupCategory::Skills),
+    ("config.toml", BackupCategory::Config),
+    ("profiles", BackupCategory::Config),
+    ("JCODE.md", BackupCategory::Config),
+    ("prompt-overlay.md", BackupCategory::Config),
+    ("preferred-tools.md", BackupCategory::Config),
+    ("todos", BackupCategory::Todos),
+    ("copilot_usage.json", BackupCategory::Usage),
+    ("ambient/usage.json", BackupCategory::Usage),
+];
+
+/// Credential files under `~/.jcode`, packed only with `--include-auth`.
+const CREDENTIAL_PATHS: &[&str] = &[
+    "auth.json",
+    "openai-auth.json",
+    "gemini_oauth.json",
+    "google_oauth.json",
+    "google_credentials.json",
+    "antigravity_oauth.json",
+    "composio_gmail.json",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupFile {
+    /// Archive path: relative to `~/.jcode`, or under [`APP_CONFIG_PREFIX`].
+    pub path: String,
+    pub category: BackupCategory,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub format_version: u32,
+    pub created_at: DateTime<Utc>,
+    pub jcode_version: String,
+    pub include_auth: bool,
+    pub files: Vec<BackupFile>,
+}
+
+impl BackupManifest {
+    /// File count and total bytes per category.
+    pub fn totals(&self) -> BTreeMap<BackupCategory, (usize, u64)> {
+        let mut totals = BTreeMap::new();
+        for file in &self.files {
+            let entry = totals.entry(file.category).or_insert((0, 0));
+            entry.0 += 1;
+            entry.1 += file.bytes;
+        }
+        totals
+    }
+}
+
+/// Pack the current state into `output` (written atomically).
+pub fn create_backup(output: &Path, include_auth: bool) -> Result<BackupManifest> {
+    let home = storage::jcode_dir()?;
+    let db_path = home.join(storage::SQLITE_DB_FILE_NAME);
+    if db_path.exists()
+        && let Err(err) = storage::SqliteStore::open(&db_path).and_then(|db| db.checkpoint())
+    {
+        crate::logging::warn(&format!(
+            "Could not checkpoint {} before backup: {}",
+            db_path.display(),
+            err
+        ));
+    }
+
+    let mut sources = Vec::new();
+    for (relative, category) in STATE_PATHS {
+        collect_files(&home, Path::new(relative), *category, &mut sources)?;
+    }
+    if include_auth {
+        for relative in CREDENTIAL_PATHS {
+            collect_files(
+                &home,
+                Path::new(relative),
+                BackupCategory::Credentials,
+                &mut sources,
+            )?;
+        }
+        if let Ok(config_dir) = storage::app_config_dir() {
+            for path in env_files(&config_dir) {
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                sources.push(Source {
+                    archive_path: format!("{}/{}", APP_CONFIG_PREFIX, name),
+                    bytes: std::fs::metadata(&path)?.len(),
+                    path,
+                    category: BackupCategory::Credentials,
+                });
+            }
+        }
+    }
+
+    let manifest = BackupManifest {
+        format_version: FORMAT_VERSION,
+        created_at: Utc::now(),
+        jcode_version: jcode_build_meta::VERSION.to_string(),
+        include_auth,
+        files: sources
+            .iter()
+            .map(|source| BackupFile {
+                path: source.archive_path.clone(),
+                category: source.category,
+                bytes: source.bytes,
+            })
+            .collect(),
+    };
+
+    let mut tmp_name = output.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".partial");
+    let tmp_path = output.with_file_name(tmp_name);
+    let result = (|| -> Result<()> {
+        let file = std::fs::File::create(&tmp_path)
+            .with_context(|| format!("failed to create {}", tmp_path.display()))?;
+        let _ = jcode_core::fs::set_permissions_owner_only(&tmp_path);
+        let mut archive = tar::Builder::new(zstd::Encoder::new(file, ZSTD_LEVEL)?);
+        let manifest_json = serde_json::to_vec_pretty(&manifest)?;
+        let mut header = tar::Header::new_gnu();
+        header.set_size(manifest_json.len() as u64);
+        header.set_mode(0o600);
+        header.set_mtime(manifest.created_at.timestamp().max(0) as u64);
+        header.set_cksum();
+        archive.append_data(&mut header, MANIFEST_PATH, manifest_json.as_slice())?;
+        for source in &sources {
+            archive
+                .append_path_with_name(&source.path, &source.archive_path)
+                .with_context(|| format!("failed to add {}", source.path.display()))?;
+        }
+        archive.into_inner()?.finish()?.sync_all()?;
+        std::fs::rename(&tmp_path, output)?;
+        Ok(())
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp_path);
+    }
+    result?;
+    Ok(manifest)
+}
+
+struct Source {
+    archive_path: String,
+    path: PathBuf,
+    category: BackupCategory,
+    bytes: u64,
+}
+
+fn collect_files(
+    home: &Path,
+    relative: &Path,
+    category: BackupCategory,
+    out: &mut Vec<Source>,
+) -> Result<()> {
+    let path = home.join(relative);
+    let Ok(metadata) = std::fs::symlink_metadata(&path) else {
+        return Ok(());
+    };
+    if metadata.is_dir() {
+        let mut entries: Vec<_> = std::fs::read_dir(&path)?
+            .flatten()
+            .map(|entry| entry.file_name())
+            .collect();
+        entries.sort();
+        for name in entries {
+            collect_files(home, &relative.join(name), category, out)?;
+        }
+    } else if metadata.is_file() && !is_transient_file(&path) {
+        out.push(Source {
+            archive_path: relative.to_string_lossy().replace('\\', "/"),
+            path,
+            category,
+            bytes: metadata.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Temp files from interrupted atomic writes and SQLite side files.
+fn is_transient_file(path: &Path) -> bool {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    name.starts_with('.')
+        || name.ends_with(".tmp")
+        || name.ends_with(".partial")
+        || name.ends_with("-wal")
+        || name.ends_with("-shm")
+}
+
+fn env_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "env"))
+        .collect();
+    files.sort();
+    files
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestoreAction {
+    /// Not present locally
+    Create,
+    /// Identical to the local copy
+    Unchanged,
+    /// Replaces an older local copy (or any differing copy with `--force`)
+    Overwrite,
+    /// Memories missing locally are added to the local graph
+    Merge,
+    /// Local copy kept; see the item's note
+    KeepLocal,
+}
+
+impl RestoreAction {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Create => "create",
+            Self::Unchanged => "unchanged",
+            Self::Overwrite => "overwrite",
+            Self::Merge => "merge",
+            Self::KeepLocal => "conflict",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RestoreItem {
+    pub path: String,
+    pub category: BackupCategory,
+    pub action: RestoreAction,
+    pub note: Option<String>,
+    dest: PathBuf,
+    merged: Option<MemoryGraph>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RestoreReport {
+    pub manifest: BackupManifest,
+    pub items: Vec<RestoreItem>,
+    pub applied: bool,
+}
+
+impl RestoreReport {
+    pub fn count(&self, action: RestoreAction) -> usize {
+        self.items
+            .iter()
+            .filter(|item| item.action == action)
+            .count()
+    }
+}
+
+/// Sessions whose owning process is still running, other than this one.
+pub fn live_session_ids() -> Vec<String> {
+    let Some(dir) = storage::active_pids_dir() else {
+        return Vec::new();
+    };
+    let mut live: Vec<String> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let pid: u32 = std::fs::read_to_string(entry.path())
+                .ok()?
+                .trim()
+                .parse()
+                .ok()?;
+            (pid != std::process::id() && crate::platform::is_process_running(pid))
+                .then(|| entry.file_name().to_string_lossy().to_string())
+        })
+        .collect();
+    live.sort();
+    live
+}
+
+/// Plan a restore of `archive`, and apply it unless `dry_run`. With `force`,
+/// differing local files are replaced instead of kept; memory graphs are
+/// always merged.
+pub fn restore_backup(archive: &Path, dry_run: bool, force: bool) -> Result<RestoreReport> {
+    if !dry_run {
+        let live = live_session_ids();
+        if !live.is_empty() {
+            anyhow::bail!(
+                "{} jcode session(s) are running ({}). Close them before restoring, or use --dry-run to preview.",
+                live.len(),
+                live.join(", ")
+            );
+        }
+    }
+
+    let staging = tempfile::TempDir::new()?;
+    let manifest = unpack(archive, staging.path())?;
+    let home = storage::jcode_dir()?;
+    let mut items = Vec::with_capacity(manifest.files.len());
+    for file in &manifest.files {
+        let staged = staging.path().join(&file.path);
+        anyhow::ensure!(
+            staged.is_file(),
+            "backup is missing {} listed in its manifest",
+            file.path
+        );
+        let dest = restore_destination(&home, &file.path)?;
+        items.push(plan_item(file, &staged, dest, staging.path(), force)?);
+    }
+
+    if !dry_run {
+        for item in &items {
+            apply_item(item, &staging.path().join(&item.path), &manifest)?;
+        }
+    }
+    Ok(RestoreReport {
+        manifest,
+        items,
+        applied: !dry_run,
+    })
+}
+
+fn unpack(archive: &Path, staging: &Path) -> Result<BackupManifest> {
+    let file = std::fs::File::open(archive)
+        .with_context(|| format!("failed to open {}", archive.display()))?;
+    let mut tar = tar::Archive::new(zstd::Decoder::new(file)?);
+    for entry in tar.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.into_owned();
+        anyhow::ensure!(
+            is_safe_relative(&path),
+            "backup contains an unsafe path: {}",
+            path.display()
+        );
+        entry.unpack_in(staging)?;
+    }
+    let manifest: BackupManifest = serde_json::from_slice(
+        &std::fs::read(staging.join(MANIFEST_PATH)).context("not a jcode backup (no manifest)")?,
+    )?;
+    anyhow::ensure!(
+        manifest.format_version <= FORMAT_VERSION,
+        "backup format v{} is newer than this jcode supports (v{}); update jcode first",
+        manifest.format_version,
+        FORMAT_VERSION
+    );
+    Ok(manifest)
+}
+
+fn is_safe_relative(path: &Path) -> bool {
+    path.components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+fn restore_destination(home: &Path, archive_path: &str) -> Result<PathBuf> {
+    anyhow::ensure!(
+        is_safe_relative(Path::new(archive_path)),
+        "backup manifest lists an unsafe path: {}",
+        archive_path
+    );
+    match archive_path.strip_prefix(APP_CONFIG_PREFIX) {
+        Some(rest) if rest.starts_with('/') => {
+            Ok(storage::app_config_dir()?.join(rest.trim_start_matches('/')))
+        }
+        _ => Ok(home.join(archive_path)),
+    }
+}
+
+fn plan_item(
+    file: &BackupFile,
+    staged: &Path,
+    dest: PathBuf,
+    staging: &Path,
+    force: bool,
+) -> Result<RestoreItem> {
+    let mut item = RestoreItem {
+        path: file.path.clone(),
+        category: file.category,
+        action: RestoreAction::Create,
+        note: None,
+        dest,
+        merged: None,
+    };
+    if !item.dest.exists() {
+        return Ok(item);
+    }
+    if std::fs::read(staged)? == std::fs::read(&item.dest)? {
+        item.action = RestoreAction::Unchanged;
+        return Ok(item);
+    }
+
+    if file.category == BackupCategory::Memories
+        && let (Ok(mut local), Ok(backup)) = (
+            storage::read_json::<MemoryGraph>(&item.dest),
+            storage::read_json::<MemoryGraph>(staged),
+        )
+    {
+        let added = local.merge_missing_from(&backup);
+        if added == 0 {
+            item.action = RestoreAction::Unchanged;
+            item.note = Some("every backed-up memory is already present".to_string());
+        } else {
+            item.action = RestoreAction::Merge;
+            item.note = Some(format!("{} memories added", added));
+            item.merged = Some(local);
+        }
+        return Ok(item);
+    }
+
+    let conflict = if let Some(snapshot) = session_snapshot_for(&file.path) {
+        let local_snapshot = item.dest.with_file_name(snapshot_file_name(&snapshot));
+        let staged_snapshot = staging.join(&snapshot);
+        match (
+            session_updated_at(&local_snapshot),
+            session_updated_at(&staged_snapshot),
+        ) {
+            (Some(local), Some(backup)) if local > backup => Some(format!(
+                "local session is newer ({} vs {} in backup)",
+                local.format("%Y-%m-%d %H:%M"),
+                backup.format("%Y-%m-%d %H:%M")
+            )),
+            _ => None,
+        }
+    } else if file.path == "config.toml" {
+        let keys = differing_config_keys(&item.dest, staged);
+        Some(if keys.is_empty() {
+            "differs from local config".to_string()
+        } else {
+            format!("differing keys: {}", keys.join(", "))
+        })
+    } else {
+        Some("differs from local copy".to_string())
+    };
+
+    match conflict {
+        Some(reason) if !force => {
+            item.action = RestoreAction::KeepLocal;
+            item.note = Some(reason);
+        }
+        reason => {
+            item.action = RestoreAction::Overwrite;
+            item.note = reason;
+        }
+    }
+    Ok(item)
+}
+
+fn apply_item(item: &RestoreItem, staged: &Path, manifest: &BackupManifest) -> Result<()> {
+    match item.action {
+        RestoreAction::Unchanged | RestoreAction::KeepLocal => return Ok(()),
+        RestoreAction::Merge => {
+            if let Some(graph) = &item.merged {
+                storage::write_json(&item.dest, graph)?;
+            }
+            return Ok(());
+        }
+        RestoreAction::Create | RestoreAction::Overwrite => {}
+    }
+    // The backup holds a checkpointed database; a local WAL or shared-memory
+    // index left beside it belongs to the old file and would be replayed
+    // over the restored one on the next open.
+    if item.path == storage::SQLITE_DB_FILE_NAME {
+        for suffix in ["-wal", "-shm"] {
+            let mut side = item.dest.clone().into_os_string();
+            side.push(suffix);
+            match std::fs::remove_file(&side) {
+                Ok(()) => {}
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+    }
+    storage::write_bytes(&item.dest, &std::fs::read(staged)?)?;
+    if item.category == BackupCategory::Credentials {
+        storage::harden_secret_file_permissions(&item.dest);
+    }
+    // A restored snapshot must not pick up journal entries written after it
+    // locally; drop the local journal when the backup has none.
+    if item.action == RestoreAction::Overwrite
+        && item.path.starts_with("sessions/")
+        && item.path.ends_with(".json")
+    {
+        let journal = session_journal_path_from_snapshot(&item.dest);
+        let journal_name = format!(
+            "sessions/{}",
+            journal.file_name().unwrap_or_default().to_string_lossy()
+        );
+        if journal.exists() && !manifest.files.iter().any(|file| file.path == journal_name) {
+            std::fs::remove_file(&journal)?;
+        }
+    }
+    Ok(())
+}
+
+/// Archive path of the snapshot a session file belongs to
+/// (`sessions/<id>.json` for both the snapshot and its journal).
+fn session_snapshot_for(path: &str) -> Option<String> {
+    let name = path.strip_prefix("sessions/")?;
+    if name.contains('/') {
+        return None;
+    }
+    if let Some(stem) = name.strip_suffix(".journal.jsonl") {
+        return Some(format!("sessions/{}.json", stem));
+    }
+    name.ends_with(".json").then(|| path.to_string())
+}
+
+fn snapshot_file_name(snapshot: &str) -> &str {
+    snapshot.rsplit('/').next().unwrap_or(snapshot)
+}
+
+/// Last save time of a session file, including its journal's metadata.
+fn session_updated_at(snapshot: &Path) -> Option<DateTime<Utc>> {
+    #[derive(Deserialize)]
+    struct Stamp {
+        updated_at: DateTime<Utc>,
+    }
+    #[derive(Deserialize)]
+    struct JournalStamp {
+        meta: Stamp,
+    }
+
+    let mut latest = serde_json::from_slice::<Stamp>(&std::fs::read(snapshot).ok()?)
+        .ok()?
+        .updated_at;
+    if let Ok(journal) = std::fs::read_to_string(session_journal_path_from_snapshot(snapshot))
+        && let Some(line) = journal.lines().rev().find(|line| !line.trim().is_empty())
+        && let Ok(stamp) = serde_json::from_str::<JournalStamp>(line)
+    {
+        latest = latest.max(stamp.meta.updated_at);
+    }
+    Some(latest)
+}
+
+/// Dotted keys whose values differ between two config files.
+fn differing_config_keys(local: &Path, backup: &Path) -> Vec<String> {
+    let parse = |path: &Path| -> Option<BTreeMap<String, String>> {
+        let value: toml::Value = toml::from_str(&std::fs::read_to_string(path).ok()?).ok()?;
+        let mut flat = BTreeMap::new();
+        flatten_toml("", &value, &mut flat);
+        Some(flat)
+    };
+    let (Some(local), Some(backup)) = (parse(local), parse(backup)) else {
+        return Vec::new();
+    };
+    let mut keys: Vec<String> = local
+        .keys()
+        .chain(backup.keys())
+        .filter(|key| local.get(*key) != backup.get(*key))
+        .cloned()
+        .collect();
+    keys.sort();
+    keys.dedup();
+    keys
+}
+
+fn flatten_toml(prefix: &str, value: &toml::Value, out: &mut BTreeMap<String, String>) {
+    match value {
+        toml::Value::Table(table) => {
+            for (key, value) in table {
+                let key = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten_toml(&key, value, out);
+            }
+        }
+        other => {
+            out.insert(prefix.to_string(), other.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, contents: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    fn session_json(id: &str, updated_at: &str) -> String {
+        format!(
+            r#"{{"id":"{id}","parent_id":null,"title":null,"created_at":"2026-01-01T00:00:00Z","updated_at":"{updated_at}","messages":[]}}"#
+        )
+    }
+
+    #[test]
+    fn backup_round_trip_plans_conflicts_merges_memories_and_skips_caches() {
+        let _guard = storage::lock_test_env();
+        let prev_home = std::env::var_os("JCODE_HOME");
+        let home = tempfile::TempDir::new().unwrap();
+        let out = tempfile::TempDir::new().unwrap();
+        crate::env::set_var("JCODE_HOME", home.path());
+        let h = home.path();
+
+        write(
+            &h.join("sessions/session_a.json"),
+            &session_json("session_a", "2026-01-02T00:00:00Z"),
+        );
+        write(
+            &h.join("sessions/session_b.json"),
+            &session_json("session_b", "2026-01-02T00:00:00Z"),
+        );
+        write(&h.join("config.toml"), "[display]\ntheme = \"dark\"\n");
+        write(&h.join("auth.json"), "{}");
+        write(&h.join("cache/session-picker-list-v1.json"), "[]");
+        write(&h.join("logs/jcode.log"), "log");
+        let mut graph = MemoryGraph::new();
+        graph.add_memory(crate::memory::MemoryEntry::new(
+            crate::memory::MemoryCategory::Fact,
+            "from the old laptop",
+        ));
+        storage::write_json(&h.join("memory/global.json"), &graph).unwrap();
+
+        let archive = out.path().join("state.tar.zst");
+        let manifest = create_backup(&archive, false).unwrap();
+        let paths: Vec<&str> = manifest
+            .files
+            .iter()
+            .map(|file| file.path.as_str())
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                "sessions/session_a.json",
+                "sessions/session_b.json",
+                "memory/global.json",
+                "config.toml"
+            ]
+        );
+
+        // Diverge locally: a newer session, an edited config, a new memory,
+        // and a deleted session.
+        write(
+            &h.join("sessions/session_a.json"),
+            &session_json("session_a", "2026-03-01T00:00:00Z"),
+        );
+        std::fs::remove_file(h.join("sessions/session_b.json")).unwrap();
+        write(&h.join("config.toml"), "[display]\ntheme = \"light\"\n");
+        let mut local_graph = MemoryGraph::new();
+        local_graph.add_memory(crate::memory::MemoryEntry::new(
+            crate::memory::MemoryCategory::Fact,
+            "from the new laptop",
+        ));
+        storage::write_json(&h.join("memory/global.json"), &local_graph).unwrap();
+
+        let report = restore_backup(&archive, true, false).unwrap();
+        let action = |path: &str| {
+            report
+                .items
+                .iter()
+                .find(|item| item.path == path)
+                .map(|item| (item.action, item.note.clone()))
+                .unwrap()
+        };
+        assert_eq!(
+            action("sessions/session_a.json").0,
+            RestoreAction::KeepLocal
+        );
+        assert_eq!(action("sessions/session_b.json").0, RestoreAction::Create);
+        assert_eq!(
+            action("config.toml"),
+            (
+                RestoreAction::KeepLocal,
+                Some("differing keys: display.theme".to_string())
+            )
+        );
+        assert_eq!(action("memory/global.json").0, RestoreAction::Merge);
+        assert!(!report.applied);
+        assert!(!h.join("sessions/session_b.json").exists());
+
+        let report = restore_backup(&archive, false, false).unwrap();
+        assert!(report.applied);
+        assert!(h.join("sessions/session_b.json").exists());
+        assert!(
+            std::fs::read_to_string(h.join("config.toml"))
+                .unwrap()
+                .contains("light")
+        );
+        let merged: MemoryGraph = storage::read_json(&h.join("memory/global.json")).unwrap();
+        assert_eq!(merged.memory_count(), 2);
+
+        if let Some(prev_home) = prev_home {
+            crate::env::set_var("JCODE_HOME", prev_home);
+        } else {
+            crate::env::remove_var("JCODE_HOME");
+        }
+    }
+
+    #[test]
+    fn restoring_the_database_drops_stale_wal_and_shm_files() {
+        let _guard = storage::lock_test_env();
+        let prev_home = std::env::var_os("JCODE_HOME");
+        let home = tempfile::TempDir::new().unwrap();
+        let out = tempfile::TempDir::new().unwrap();
+        crate::env::set_var("JCODE_HOME", home.path());
+        let db = home.path().join(storage::SQLITE_DB_FILE_NAME);
+
+        write(&db, "backed up");
+        let archive = out.path().join("state.tar.zst");
+        create_backup(&archive, false).unwrap();
+
+        write(&db, "local");
+        write(&home.path().join("jcode.db-wal"), "stale wal");
+        write(&home.path().join("jcode.db-shm"), "stale shm");
+        let report = restore_backup(&archive, false, true).unwrap();
+        assert!(report.applied);
+        assert_eq!(std::fs::read_to_string(&db).unwrap(), "backed up");
+        assert!(!home.path().join("jcode.db-wal").exists());
+        assert!(!home.path().join("jcode.db-shm").exists());
+
+        if let Some(prev_home) = prev_home {
+            crate::env::set_var("JCODE_HOME", prev_home);
+        } else {
+            crate::env::remove_var("JCODE_HOME");
+        }
+    }
+}
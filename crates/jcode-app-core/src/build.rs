@@ -1,26 +1,32 @@
 pub use jcode_build_support::{
-    BinaryChoice, BinaryVersionReport, BuildInfo, BuildManifest, CanaryStatus, CrashInfo,
-    DevBinarySourceMetadata, MigrationContext, PendingActivation, PublishedBuild,
+    BinaryChoice, BinaryVersionReport, BuildInfo, BuildManifest, CanaryEvent, CanaryEventKind,
+    CanaryPromotion, CanaryStatus, CanaryTrackRecord, CrashInfo, DevBinarySourceMetadata,
+    InstalledVersion, MigrationContext, PendingActivation, PublishedBuild, RollbackOutcome,
     SELFDEV_CARGO_PROFILE, SelfDevBuildCommand, SelfDevBuildTarget, SharedServerRepair,
-    SourceState, advance_shared_server_if_tracking_stable, binary_name, binary_stem,
-    build_log_path, build_progress_path, builds_dir, canary_binary_path, clear_build_progress,
-    clear_migration_context, client_update_candidate, complete_pending_activation_for_session,
-    current_binary_build_time_string, current_binary_built_at, current_binary_path,
-    current_build_info, current_git_diff, current_git_hash, current_git_hash_full,
-    current_source_state, current_version_file, ensure_source_state_matches, find_dev_binary,
-    find_repo_in_ancestors, get_commit_message, get_repo_dir, install_binary_at_version,
-    install_local_release, install_version, is_jcode_repo, is_working_tree_dirty,
-    launcher_binary_path, launcher_dir, load_migration_context, manifest_path,
-    migration_context_path, preferred_reload_candidate, promote_version_to_shared_server,
-    publish_local_current_build, publish_local_current_build_for_source, read_build_progress,
-    read_current_version, read_shared_server_version, read_stable_version, release_binary_path,
+    SourceState, StableRecord, TestSelection, TestSelectionRules,
+    advance_shared_server_if_tracking_stable, append_canary_event, binary_name, binary_stem,
+    build_diff_stat, build_log_path, build_progress_path, builds_dir, canary_binary_path,
+    canary_events_path, clear_build_progress, clear_migration_context, client_update_candidate,
+    complete_pending_activation_for_session, current_binary_build_time_string,
+    current_binary_built_at, current_binary_path, current_build_info, current_git_diff,
+    current_git_hash, current_git_hash_full, current_source_state, current_version_file,
+    ensure_source_state_matches, find_dev_binary, find_repo_in_ancestors, format_uptime,
+    get_commit_message, get_repo_dir, install_binary_at_version, install_local_release,
+    install_version, installed_versions, is_jcode_repo, is_working_tree_dirty,
+    launcher_binary_path, launcher_dir, load_canary_events, load_migration_context, manifest_path,
+    migration_context_path, preferred_reload_candidate, promote_canary,
+    promote_version_to_shared_server, publish_local_current_build,
+    publish_local_current_build_for_source, read_build_progress, read_current_version,
+    read_shared_server_version, read_stable_version, record_canary_event_for_running_build,
+    record_canary_event_for_version, record_session_on_running_build, release_binary_path,
     repair_stale_shared_server_channel, repo_build_version, repo_scope_key, resolve_binary_payload,
-    rollback_pending_activation_for_session, run_selfdev_build, save_migration_context,
-    selfdev_binary_path, selfdev_build_command, selfdev_build_command_for_target,
-    shared_server_binary_path, shared_server_tracks_stable, shared_server_update_candidate,
-    shared_server_version_file, smoke_test_binary, smoke_test_server_binary, stable_binary_path,
-    stable_version_file, update_canary_symlink, update_current_symlink,
-    update_launcher_symlink_to_current, update_launcher_symlink_to_stable,
+    resolve_rollback_target, rollback_pending_activation_for_session, rollback_stable,
+    run_selfdev_build, running_canary_build, running_installed_version, save_migration_context,
+    select_rebuild_tests, selfdev_binary_path, selfdev_build_command,
+    selfdev_build_command_for_target, shared_server_binary_path, shared_server_tracks_stable,
+    shared_server_update_candidate, shared_server_version_file, smoke_test_binary,
+    smoke_test_server_binary, stable_binary_path, stable_version_file, update_canary_symlink,
+    update_current_symlink, update_launcher_symlink_to_current, update_launcher_symlink_to_stable,
     update_shared_server_symlink, update_stable_symlink, version_binary_path,
     version_matches_installed_channel, worktree_scope_key, write_build_progress,
     write_current_dev_binary_source_metadata, write_dev_binary_source_metadata,
@@ -199,7 +199,9 @@ impl MessageChannel for TelegramChannel {
         let mut offset: Option<i64> = None;
 
         loop {
-            match crate::telegram::get_updates(&self.client, &self.token, offset, 30).await {
+            match crate::telegram::get_updates(&self.client, &self.token, offset, 30, &["message"])
+                .await
+            {
                 Ok(updates) => {
                     if !updates.is_empty() {
                         logging::debug(&format!(
@@ -232,7 +234,7 @@ impl MessageChannel for TelegramChannel {
                         if let Some(req_id) = crate::notifications::extract_permission_id(trimmed) {
                             let (approved, message) =
                                 crate::notifications::parse_permission_reply(trimmed);
-                            if let Err(e) = crate::safety::record_permission_via_file(
+                            if let Err(e) = crate::safety::record_decision(
                                 &req_id,
                                 approved,
                                 "telegram_reply",
@@ -441,7 +443,7 @@ impl MessageChannel for DiscordChannel {
                         if let Some(req_id) = crate::notifications::extract_permission_id(trimmed) {
                             let (approved, message) =
                                 crate::notifications::parse_permission_reply(trimmed);
-                            if let Err(e) = crate::safety::record_permission_via_file(
+                            if let Err(e) = crate::safety::record_decision(
                                 &req_id,
                                 approved,
                                 "discord_reply",
@@ -702,7 +704,7 @@ impl MessageChannel for JadeRelayChannel {
                         if let Some(req_id) = crate::notifications::extract_permission_id(trimmed) {
                             let (approved, message) =
                                 crate::notifications::parse_permission_reply(trimmed);
-                            if let Err(e) = crate::safety::record_permission_via_file(
+                            if let Err(e) = crate::safety::record_decision(
                                 &req_id,
                                 approved,
                                 "jade_relay",
@@ -0,0 +1,386 @@
+//! Crash report bundles for bug reports.
+//!
+//! A bundle is a directory under `~/.jcode/crash-reports/<timestamp>/` holding
+//! everything a crash issue needs: build info, the panic and backtrace, the
+//! tail of today's log, the session's last messages with file contents and
+//! secrets redacted, the config with secrets stripped, and an environment
+//! summary. Bundles are written automatically on panic and when a session is
+//! marked crashed, and on demand by `jcode debug crash-report`, which also packs
+//! one into a `.tar.gz` next to it.
+
+use anyhow::{Context, Result};
+use chrono::Local;
+use serde::Serialize;
+use serde_json::Value;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use crate::message::{ContentBlock, redact_secrets};
+use crate::session::{Session, StoredMessage};
+
+const LOG_TAIL_LINES: usize = 500;
+const SESSION_TAIL_MESSAGES: usize = 20;
+
+/// Bytes read from the end of the log file when collecting the tail; plenty
+/// for [`LOG_TAIL_LINES`] without loading a full rotated segment.
+const LOG_TAIL_MAX_BYTES: u64 = 1024 * 1024;
+
+/// Tools whose results are file contents.
+const FILE_READ_TOOLS: &[&str] = &["read"];
+
+/// Tools whose inputs carry file contents (everything but the path is dropped).
+const FILE_WRITE_TOOLS: &[&str] = &["write", "edit", "multiedit", "patch", "apply_patch"];
+
+/// Input keys kept for [`FILE_WRITE_TOOLS`].
+const FILE_PATH_KEYS: &[&str] = &["file_path", "path"];
+
+/// Config key segments whose values are replaced in the bundle.
+const SECRET_KEY_SEGMENTS: &[&str] = &[
+    "key",
+    "token",
+    "secret",
+    "password",
+    "credential",
+    "credentials",
+    "authorization",
+    "cookie",
+];
+
+/// What triggered a bundle.
+#[derive(Debug, Clone, Default)]
+pub struct CrashContext {
+    /// One-line cause, e.g. `Panic` or `Terminated (SIGTERM)`
+    pub reason: String,
+    /// Panic message and backtrace, when there is one
+    pub panic: Option<String>,
+    pub session_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct CrashSummary {
+    created_at: String,
+    reason: String,
+    version: String,
+    git_hash: String,
+    session_id: Option<String>,
+    environment: EnvironmentSummary,
+}
+
+#[derive(Debug, Serialize)]
+struct EnvironmentSummary {
+    os: String,
+    arch: String,
+    terminal: Option<String>,
+    term_program: Option<String>,
+    provider: Option<String>,
+    model: Option<String>,
+}
+
+pub fn crash_reports_dir() -> Result<PathBuf> {
+    Ok(crate::storage::jcode_dir()?.join("crash-reports"))
+}
+
+/// Write a bundle for `context` and return its directory.
+pub fn write_bundle(context: &CrashContext) -> Result<PathBuf> {
+    let now = Local::now();
+    let dir = crash_reports_dir()?.join(now.format("%Y%m%d-%H%M%S-%3f").to_string());
+    crate::storage::ensure_dir(&dir)?;
+
+    let session = context
+        .session_id
+        .as_deref()
+        .and_then(|session_id| Session::load(session_id).ok());
+    let (provider, model) = crate::telemetry::current_provider_model()
+        .map(|(provider, model)| (Some(provider), Some(model)))
+        .unwrap_or_else(|| {
+            session
+                .as_ref()
+                .map(|session| (session.provider_key.clone(), session.model.clone()))
+                .unwrap_or_default()
+        });
+    let summary = CrashSummary {
+        created_at: now.to_rfc3339(),
+        reason: context.reason.clone(),
+        version: jcode_build_meta::VERSION.to_string(),
+        git_hash: jcode_build_meta::GIT_HASH.to_string(),
+        session_id: context.session_id.clone(),
+        environment: EnvironmentSummary {
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            terminal: std::env::var("TERM").ok(),
+            term_program: std::env::var("TERM_PROGRAM").ok(),
+            provider,
+            model,
+        },
+    };
+    crate::storage::write_json(&dir.join("summary.json"), &summary)?;
+
+    if let Some(panic) = &context.panic {
+        std::fs::write(dir.join("panic.txt"), panic)?;
+    }
+    if let Some(log_path) = crate::logging::log_path()
+        && let Ok(tail) = tail_lines(&log_path, LOG_TAIL_LINES)
+    {
+        std::fs::write(dir.join("log-tail.txt"), redact_secrets(&tail))?;
+    }
+    if let Some(session) = &session {
+        crate::storage::write_json(
+            &dir.join("session-tail.json"),
+            &redacted_session_tail(session, SESSION_TAIL_MESSAGES),
+        )?;
+    }
+    let mut config = serde_json::to_value(crate::config::config())?;
+    strip_config_secrets(&mut config);
+    crate::storage::write_json(&dir.join("config.json"), &config)?;
+
+    Ok(dir)
+}
+
+/// Write a bundle and log (rather than return) failures, for crash paths.
+pub fn write_bundle_best_effort(context: &CrashContext) -> Option<PathBuf> {
+    match write_bundle(context) {
+        Ok(dir) => Some(dir),
+        Err(err) => {
+            crate::logging::warn(&format!("Failed to write crash report: {}", err));
+            None
+        }
+    }
+}
+
+/// Most recent bundle directory, if any.
+pub fn latest_bundle() -> Option<PathBuf> {
+    std::fs::read_dir(crash_reports_dir().ok()?)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .max_by(|a, b| a.file_name().cmp(&b.file_name()))
+}
+
+/// Pack a bundle directory into `<dir>.tar.gz` and return the archive path.
+pub fn archive_bundle(dir: &Path) -> Result<PathBuf> {
+    let name = dir
+        .file_name()
+        .context("crash report directory has no name")?
+        .to_string_lossy()
+        .to_string();
+    let archive_path = dir.with_file_name(format!("{}.tar.gz", name));
+    let file = std::fs::File::create(&archive_path)
+        .with_context(|| format!("failed to create {}", archive_path.display()))?;
+    let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+    let mut archive = tar::Builder::new(encoder);
+    archive.append_dir_all(&name, dir)?;
+    archive.into_inner()?.finish()?;
+    Ok(archive_path)
+}
+
+/// Last `count` lines of the file at `path`.
+fn tail_lines(path: &Path, count: usize) -> Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let len = file.metadata()?.len();
+    let start = len.saturating_sub(LOG_TAIL_MAX_BYTES);
+    file.seek(SeekFrom::Start(start))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    let text = String::from_utf8_lossy(&bytes);
+    let mut lines: Vec<&str> = text.lines().collect();
+    if start > 0 && !lines.is_empty() {
+        // The first line was cut by the seek.
+        lines.remove(0);
+    }
+    let skip = lines.len().saturating_sub(count);
+    let mut tail = lines[skip..].join("\n");
+    tail.push('\n');
+    Ok(tail)
+}
+
+/// The last `count` messages, with secrets redacted, file contents read or
+/// written by tools dropped, and images replaced by a placeholder.
+fn redacted_session_tail(session: &Session, count: usize) -> Vec<StoredMessage> {
+    let redacted = session.redacted_for_export();
+    let skip = redacted.messages.len().saturating_sub(count);
+    let file_reads: Vec<String> = redacted
+        .messages
+        .iter()
+        .flat_map(|message| &message.content)
+        .filter_map(|block| match block {
+            ContentBlock::ToolUse { id, name, .. } if FILE_READ_TOOLS.contains(&name.as_str()) => {
+                Some(id.clone())
+            }
+            _ => None,
+        })
+        .collect();
+
+    let mut messages: Vec<StoredMessage> = redacted.messages.into_iter().skip(skip).collect();
+    for message in &mut messages {
+        for block in &mut message.content {
+            match block {
+                ContentBlock::ToolUse { name, input, .. }
+                    if FILE_WRITE_TOOLS.contains(&name.as_str()) =>
+                {
+                    redact_file_tool_input(input);
+                }
+                ContentBlock::ToolResult {
+                    tool_use_id,
+                    content,
+                    ..
+                } if file_reads.contains(tool_use_id) => {
+                    *content = format!("[file contents redacted: {} bytes]", content.len());
+                }
+                ContentBlock::Image { data, .. } => {
+                    *data = format!("[image redacted: {} bytes]", data.len());
+                }
+                _ => {}
+            }
+        }
+    }
+    messages
+}
+
+fn redact_file_tool_input(input: &mut Value) {
+    let Some(object) = input.as_object_mut() else {
+        return;
+    };
+    for (key, value) in object.iter_mut() {
+        if !FILE_PATH_KEYS.contains(&key.as_str()) {
+            *value = Value::String("[redacted]".to_string());
+        }
+    }
+}
+
+fn is_secret_key(key: &str) -> bool {
+    key.to_ascii_lowercase()
+        .split(['_', '-'])
+        .any(|segment| SECRET_KEY_SEGMENTS.contains(&segment))
+}
+
+/// Replace secret-looking config values and redact secrets embedded elsewhere.
+fn strip_config_secrets(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, entry) in map.iter_mut() {
+                if is_secret_key(key) && !matches!(entry, Value::Null) {
+                    *entry = Value::String("<redacted>".to_string());
+                } else {
+                    strip_config_secrets(entry);
+                }
+            }
+        }
+        Value::Array(entries) => entries.iter_mut().for_each(strip_config_secrets),
+        Value::String(text) => *text = redact_secrets(text),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::Role;
+    use serde_json::json;
+
+    fn stored(id: &str, content: Vec<ContentBlock>) -> StoredMessage {
+        StoredMessage {
+            id: id.to_string(),
+            role: Role::Assistant,
+            content,
+            display_role: None,
+            timestamp: None,
+            tool_duration_ms: None,
+            token_usage: None,
+        }
+    }
+
+    #[test]
+    fn config_secrets_are_stripped_but_keybindings_survive() {
+        let mut config = json!({
+            "keybindings": { "submit": "enter" },
+            "providers": [{ "name": "local", "api_key": "abc123", "api_key_env": null }],
+            "gmail": { "oauth_token": "t0k3n" }
+        });
+        strip_config_secrets(&mut config);
+        assert_eq!(config["keybindings"]["submit"], "enter");
+        assert_eq!(config["providers"][0]["name"], "local");
+        assert_eq!(config["providers"][0]["api_key"], "<redacted>");
+        assert_eq!(config["providers"][0]["api_key_env"], Value::Null);
+        assert_eq!(config["gmail"]["oauth_token"], "<redacted>");
+    }
+
+    #[test]
+    fn session_tail_drops_file_contents() {
+        let mut session = Session::create_with_id(
+            "session_crash_tail".to_string(),
+            None,
+            Some("crash".to_string()),
+        );
+        session.messages = vec![
+            stored("m0", vec![]),
+            stored(
+                "m1",
+                vec![
+                    ContentBlock::ToolUse {
+                        id: "call_read".to_string(),
+                        name: "read".to_string(),
+                        input: json!({ "file_path": "src/main.rs" }),
+                        thought_signature: None,
+                    },
+                    ContentBlock::ToolUse {
+                        id: "call_write".to_string(),
+                        name: "write".to_string(),
+                        input: json!({ "file_path": "notes.md", "content": "private" }),
+                        thought_signature: None,
+                    },
+                ],
+            ),
+            stored(
+                "m2",
+                vec![ContentBlock::ToolResult {
+                    tool_use_id: "call_read".to_string(),
+                    content: "fn main() {}".to_string(),
+                    is_error: None,
+                }],
+            ),
+        ];
+
+        let tail = redacted_session_tail(&session, 2);
+        assert_eq!(tail.len(), 2);
+        assert_eq!(tail[0].id, "m1");
+        let ContentBlock::ToolUse { input, .. } = &tail[0].content[1] else {
+            panic!("expected write tool use");
+        };
+        assert_eq!(input["file_path"], "notes.md");
+        assert_eq!(input["content"], "[redacted]");
+        let ContentBlock::ToolResult { content, .. } = &tail[1].content[0] else {
+            panic!("expected read tool result");
+        };
+        assert_eq!(content, "[file contents redacted: 12 bytes]");
+    }
+
+    #[test]
+    fn log_tail_keeps_the_last_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("jcode.log");
+        let log: String = (0..10).map(|i| format!("line {}\n", i)).collect();
+        std::fs::write(&path, log).unwrap();
+        assert_eq!(tail_lines(&path, 3).unwrap(), "line 7\nline 8\nline 9\n");
+    }
+
+    #[test]
+    fn bundles_pack_into_tar_gz() {
+        let dir = tempfile::tempdir().unwrap();
+        let bundle = dir.path().join("20261017-093000-125");
+        std::fs::create_dir_all(&bundle).unwrap();
+        std::fs::write(bundle.join("summary.json"), "{}").unwrap();
+
+        let archive = archive_bundle(&bundle).unwrap();
+        assert_eq!(archive, dir.path().join("20261017-093000-125.tar.gz"));
+        let file = std::fs::File::open(&archive).unwrap();
+        let mut entries = tar::Archive::new(flate2::read::GzDecoder::new(file));
+        let names: Vec<String> = entries
+            .entries()
+            .unwrap()
+            .flatten()
+            .map(|entry| entry.path().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert!(names.contains(&"20261017-093000-125/summary.json".to_string()));
+    }
+}
@@ -0,0 +1,392 @@
+//! Cached, ignore-aware index of the files and directories under a root.
+//!
+//! Walking a large checkout is the expensive part of listing it, so each root
+//! is walked once and the result shared: the `tree` tool reads from here, as
+//! should anything else that needs workspace paths (mention completion,
+//! pickers). `.gitignore` and `.jcodeignore` files at any level, plus
+//! `.git/info/exclude`, are applied during the walk; `.git` itself is always
+//! skipped.
+//!
+//! There is no filesystem watcher. A cached index is revalidated when read
+//! more than [`REVALIDATE_AFTER`] after its last check by comparing the
+//! modification time of every indexed directory, which changes whenever an
+//! entry is added, removed or renamed. After [`MAX_INDEX_AGE`] it is rebuilt
+//! outright so sizes and times of files edited in place stay current.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Walks stop after this many entries.
+pub const MAX_INDEX_ENTRIES: usize = 100_000;
+/// Reads within this long of the last check skip revalidation.
+pub const REVALIDATE_AFTER: Duration = Duration::from_secs(2);
+/// Indexes older than this are rebuilt.
+pub const MAX_INDEX_AGE: Duration = Duration::from_secs(60);
+/// Roots kept in the cache at once.
+const MAX_CACHED_ROOTS: usize = 8;
+/// Ignore files read in every directory.
+const IGNORE_FILES: &[&str] = &[".gitignore", ".jcodeignore"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexEntry {
+    /// Path relative to the index root, `/`-separated.
+    pub path: String,
+    pub is_dir: bool,
+    /// Size in bytes; 0 for directories.
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+}
+
+impl IndexEntry {
+    pub fn name(&self) -> &str {
+        self.path.rsplit('/').next().unwrap_or(&self.path)
+    }
+}
+
+#[derive(Debug)]
+pub struct FileIndex {
+    pub root: PathBuf,
+    /// Entries depth first; within a directory, subdirectories come before
+    /// files and each group is sorted by name.
+    pub entries: Vec<IndexEntry>,
+    /// Whether ignore files were applied.
+    pub respects_ignore: bool,
+    /// Whether the walk stopped at [`MAX_INDEX_ENTRIES`].
+    pub truncated: bool,
+    dir_mtimes: Vec<(PathBuf, Option<SystemTime>)>,
+    built_at: Instant,
+}
+
+impl FileIndex {
+    /// Walk `root` now, bypassing the cache.
+    pub fn build(root: &Path, respect_ignore: bool) -> Self {
+        let mut walker = Walker {
+            respect_ignore,
+            entries: Vec::new(),
+            dir_mtimes: Vec::new(),
+            truncated: false,
+        };
+        let mut rules = Vec::new();
+        if respect_ignore {
+            rules.extend(load_ignore_file(&root.join(".git/info/exclude"), ""));
+        }
+        walker.walk(root, "", &mut rules);
+        Self {
+            root: root.to_path_buf(),
+            entries: walker.entries,
+            respects_ignore: respect_ignore,
+            truncated: walker.truncated,
+            dir_mtimes: walker.dir_mtimes,
+            built_at: Instant::now(),
+        }
+    }
+
+    /// Whether any indexed directory changed since the walk.
+    fn is_stale(&self) -> bool {
+        self.built_at.elapsed() > MAX_INDEX_AGE
+            || self
+                .dir_mtimes
+                .iter()
+                .any(|(dir, mtime)| modified_time(dir) != *mtime)
+    }
+}
+
+struct CachedIndex {
+    index: Arc<FileIndex>,
+    checked_at: Instant,
+}
+
+static CACHE: LazyLock<Mutex<HashMap<(PathBuf, bool), CachedIndex>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// The index for `root`, from the cache when it is still current. Blocking:
+/// call from a blocking context.
+pub fn get(root: &Path, respect_ignore: bool) -> Arc<FileIndex> {
+    let key = (root.to_path_buf(), respect_ignore);
+    let cached = {
+        let mut cache = CACHE
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        cache.get_mut(&key).map(|cached| {
+            let fresh = cached.checked_at.elapsed() < REVALIDATE_AFTER;
+            cached.checked_at = Instant::now();
+            (cached.index.clone(), fresh)
+        })
+    };
+    if let Some((index, fresh)) = cached
+        && (fresh || !index.is_stale())
+    {
+        return index;
+    }
+
+    let index = Arc::new(FileIndex::build(root, respect_ignore));
+    let mut cache = CACHE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if cache.len() >= MAX_CACHED_ROOTS && !cache.contains_key(&key) {
+        let oldest = cache
+            .iter()
+            .min_by_key(|(_, cached)| cached.checked_at)
+            .map(|(key, _)| key.clone());
+        if let Some(oldest) = oldest {
+            cache.remove(&oldest);
+        }
+    }
+    cache.insert(
+        key,
+        CachedIndex {
+            index: index.clone(),
+            checked_at: Instant::now(),
+        },
+    );
+    index
+}
+
+/// Drop cached indexes for `root`, e.g. after a tool rewrote many files.
+pub fn invalidate(root: &Path) {
+    CACHE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .retain(|(cached_root, _), _| cached_root != root);
+}
+
+struct Walker {
+    respect_ignore: bool,
+    entries: Vec<IndexEntry>,
+    dir_mtimes: Vec<(PathBuf, Option<SystemTime>)>,
+    truncated: bool,
+}
+
+impl Walker {
+    fn walk(&mut self, dir: &Path, rel: &str, rules: &mut Vec<IgnoreRule>) {
+        self.dir_mtimes
+            .push((dir.to_path_buf(), modified_time(dir)));
+        let inherited_rules = rules.len();
+        if self.respect_ignore {
+            for name in IGNORE_FILES {
+                rules.extend(load_ignore_file(&dir.join(name), rel));
+            }
+        }
+
+        let mut children: Vec<(String, bool, std::fs::DirEntry)> = std::fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.ok())
+            .map(|entry| {
+                // `file_type` does not follow symlinks, so linked directories
+                // are listed but not walked.
+                let is_dir = entry.file_type().is_ok_and(|kind| kind.is_dir());
+                (
+                    entry.file_name().to_string_lossy().into_owned(),
+                    is_dir,
+                    entry,
+                )
+            })
+            .filter(|(name, _, _)| name != ".git")
+            .collect();
+        children.sort_by(|(a_name, a_dir, _), (b_name, b_dir, _)| {
+            b_dir.cmp(a_dir).then_with(|| a_name.cmp(b_name))
+        });
+
+        for (name, is_dir, entry) in children {
+            let path = if rel.is_empty() {
+                name
+            } else {
+                format!("{rel}/{name}")
+            };
+            if self.respect_ignore && is_ignored(rules, &path, is_dir) {
+                continue;
+            }
+            if self.entries.len() >= MAX_INDEX_ENTRIES {
+                self.truncated = true;
+                break;
+            }
+            let metadata = entry.metadata().ok();
+            self.entries.push(IndexEntry {
+                path: path.clone(),
+                is_dir,
+                size: metadata
+                    .as_ref()
+                    .filter(|_| !is_dir)
+                    .map_or(0, |metadata| metadata.len()),
+                modified: metadata.and_then(|metadata| metadata.modified().ok()),
+            });
+            if is_dir {
+                self.walk(&entry.path(), &path, rules);
+            }
+        }
+        rules.truncate(inherited_rules);
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// One line of a gitignore-style file.
+#[derive(Debug)]
+struct IgnoreRule {
+    pattern: glob::Pattern,
+    negated: bool,
+    dir_only: bool,
+    /// Patterns containing a `/` match the path from the ignore file's
+    /// directory; others match the name at any depth.
+    anchored: bool,
+    /// Directory of the ignore file, relative to the index root.
+    base: String,
+}
+
+const IGNORE_MATCH_OPTIONS: glob::MatchOptions = glob::MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+impl IgnoreRule {
+    fn parse(line: &str, base: &str) -> Option<Self> {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let (negated, line) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line.strip_prefix('\\').unwrap_or(line)),
+        };
+        let (dir_only, line) = match line.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let anchored = line.contains('/');
+        let line = line.trim_start_matches('/');
+        if line.is_empty() {
+            return None;
+        }
+        Some(Self {
+            pattern: glob::Pattern::new(line).ok()?,
+            negated,
+            dir_only,
+            anchored,
+            base: base.to_string(),
+        })
+    }
+
+    fn matches(&self, path: &str, is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+        let local = if self.base.is_empty() {
+            path
+        } else {
+            match path
+                .strip_prefix(self.base.as_str())
+                .and_then(|rest| rest.strip_prefix('/'))
+            {
+                Some(local) => local,
+                None => return false,
+            }
+        };
+        if self.anchored {
+            self.pattern.matches_with(local, IGNORE_MATCH_OPTIONS)
+        } else {
+            let name = local.rsplit('/').next().unwrap_or(local);
+            self.pattern.matches_with(name, IGNORE_MATCH_OPTIONS)
+        }
+    }
+}
+
+fn load_ignore_file(path: &Path, base: &str) -> Vec<IgnoreRule> {
+    std::fs::read_to_string(path)
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| IgnoreRule::parse(line, base))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The last matching rule decides, so later `!pattern` lines re-include.
+fn is_ignored(rules: &[IgnoreRule], path: &str, is_dir: bool) -> bool {
+    rules
+        .iter()
+        .rev()
+        .find(|rule| rule.matches(path, is_dir))
+        .is_some_and(|rule| !rule.negated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(index: &FileIndex) -> Vec<&str> {
+        index
+            .entries
+            .iter()
+            .map(|entry| entry.path.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn build_applies_nested_gitignore_and_jcodeignore_rules() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let root = temp.path();
+        for dir in ["src/generated", "node_modules/pkg", ".git", "docs"] {
+            std::fs::create_dir_all(root.join(dir)).expect("mkdir");
+        }
+        std::fs::write(root.join(".gitignore"), "node_modules/\n*.log\n!keep.log\n")
+            .expect("write");
+        std::fs::write(root.join("src/.jcodeignore"), "generated\n").expect("write");
+        for file in [
+            "src/main.rs",
+            "src/generated/out.rs",
+            "node_modules/pkg/index.js",
+            "debug.log",
+            "keep.log",
+            "docs/guide.md",
+            ".git/HEAD",
+        ] {
+            std::fs::write(root.join(file), "x").expect("write");
+        }
+
+        let index = FileIndex::build(root, true);
+        assert_eq!(
+            paths(&index),
+            vec![
+                "docs",
+                "docs/guide.md",
+                "src",
+                "src/.jcodeignore",
+                "src/main.rs",
+                ".gitignore",
+                "keep.log",
+            ]
+        );
+
+        let unfiltered = FileIndex::build(root, false);
+        assert!(paths(&unfiltered).contains(&"node_modules/pkg/index.js"));
+        assert!(
+            !paths(&unfiltered)
+                .iter()
+                .any(|path| path.starts_with(".git/"))
+        );
+    }
+
+    #[test]
+    fn cached_index_is_shared_until_invalidated() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let root = temp.path();
+        std::fs::write(root.join("a.txt"), "a").expect("write");
+
+        let first = get(root, true);
+        assert!(Arc::ptr_eq(&first, &get(root, true)));
+
+        std::fs::write(root.join("b.txt"), "b").expect("write");
+        invalidate(root);
+        let second = get(root, true);
+        assert_eq!(paths(&second), vec!["a.txt", "b.txt"]);
+    }
+}
@@ -28,19 +28,24 @@ pub mod agent;
 pub mod ambient;
 pub mod ambient_runner;
 pub mod ambient_scheduler;
+pub mod backup;
 pub mod build;
 pub mod catchup;
 pub mod channel;
+pub mod crash_report;
 pub mod external_auth;
+pub mod file_index;
 pub mod mission;
 pub mod network_retry;
 pub mod notifications;
 pub mod overnight;
 pub mod perf;
+pub mod provenance;
 pub mod replay;
 pub mod restart_snapshot;
 pub mod server;
 pub mod server_spawn;
+pub mod session_changes;
 pub mod session_effort;
 pub mod session_launch;
 pub mod session_rebuild;
@@ -1,21 +1,33 @@
-//! Notification dispatcher for ambient mode.
+//! Notification dispatcher for ambient mode and interactive sessions.
 //!
-//! Sends notifications via:
+//! Every backend implements [`Notifier`]:
 //! - ntfy.sh (push notifications to phone)
-//! - Desktop notifications (notify-send)
+//! - Desktop notifications (notify-send / Notification Center)
 //! - Email (SMTP via lettre)
+//! - Message channels (Telegram, Discord, Jade relay)
+//! - Generic webhooks (templated JSON POST)
 //!
-//! All sends are fire-and-forget: errors are logged, never block.
+//! `[notifications.routes]` picks the backends for each [`NotifyEvent`].
+//! All sends are fire-and-forget: each backend runs in its own task, errors
+//! are logged, and a failing backend never blocks the others.
 
-use crate::config::{SafetyConfig, config};
+mod backends;
+
+use crate::config::{NotificationsConfig, SafetyConfig, config};
 use crate::logging;
 use crate::safety::AmbientTranscript;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
 
-use jcode_notify_email::{
-    ReplyAction, SendEmailRequest, build_permission_email_html, poll_imap_once, send_email,
-};
+use backends::{ChannelNotifier, DesktopNotifier, EmailNotifier, NtfyNotifier, WebhookNotifier};
+pub use backends::{Notification, Notifier, NotifyEvent};
+use jcode_notify_email::{ReplyAction, build_permission_email_html, poll_imap_once};
 pub use jcode_notify_email::{extract_permission_id, parse_permission_reply};
 
+/// How long `jcode debug notify-test` waits for each backend.
+const TEST_SEND_TIMEOUT: Duration = Duration::from_secs(20);
+
 /// Notification priority levels (maps to ntfy priority header).
 #[derive(Debug, Clone, Copy)]
 pub enum Priority {
@@ -43,14 +55,24 @@ impl Priority {
             Priority::Urgent => "rotating_light",
         }
     }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Priority::Default => "default",
+            Priority::High => "high",
+            Priority::Urgent => "urgent",
+        }
+    }
 }
 
-/// Dispatcher that sends notifications through all configured channels.
+/// Dispatcher that routes notifications to the configured backends.
 #[derive(Clone)]
 pub struct NotificationDispatcher {
-    client: reqwest::Client,
-    config: SafetyConfig,
-    channels: crate::channel::ChannelRegistry,
+    backends: Vec<Arc<dyn Notifier>>,
+    routes: BTreeMap<String, Vec<String>>,
+    /// `safety.desktop_notifications`: include desktop in unrouted ambient events
+    desktop_by_default: bool,
+    email_from: Option<String>,
 }
 
 impl Default for NotificationDispatcher {
@@ -61,216 +83,221 @@ impl Default for NotificationDispatcher {
 
 impl NotificationDispatcher {
     pub fn new() -> Self {
-        let cfg = config().safety.clone();
-        Self {
-            client: crate::provider::shared_http_client(),
-            channels: crate::channel::ChannelRegistry::from_config(&cfg),
-            config: cfg,
-        }
+        let cfg = config();
+        Self::build(&cfg.safety, &cfg.notifications)
     }
 
     #[cfg(test)]
     pub fn from_config(config: SafetyConfig) -> Self {
+        Self::build(&config, &NotificationsConfig::default())
+    }
+
+    fn build(safety: &SafetyConfig, notifications: &NotificationsConfig) -> Self {
+        let client = crate::provider::shared_http_client();
+        let mut backends: Vec<Arc<dyn Notifier>> = vec![Arc::new(DesktopNotifier)];
+        if let Some(ref topic) = safety.ntfy_topic {
+            backends.push(Arc::new(NtfyNotifier::new(
+                client.clone(),
+                &safety.ntfy_server,
+                topic,
+                safety.ntfy_token.clone(),
+            )));
+        }
+        if let Some(email) = EmailNotifier::from_config(safety) {
+            backends.push(Arc::new(email));
+        }
+        for channel in crate::channel::ChannelRegistry::from_config(safety).send_enabled() {
+            backends.push(Arc::new(ChannelNotifier(channel)));
+        }
+        for webhook in &notifications.webhooks {
+            if webhook.url.trim().is_empty() {
+                logging::warn(&format!(
+                    "Notification webhook '{}' has no url; skipping",
+                    webhook.name
+                ));
+                continue;
+            }
+            backends.push(Arc::new(WebhookNotifier::new(
+                client.clone(),
+                webhook.clone(),
+            )));
+        }
+
         Self {
-            client: crate::provider::shared_http_client(),
-            channels: crate::channel::ChannelRegistry::from_config(&config),
-            config,
+            backends,
+            routes: notifications.routes.clone(),
+            desktop_by_default: safety.desktop_notifications,
+            email_from: safety.email_from.clone(),
+        }
+    }
+
+    /// Backends a notification for `event` goes to.
+    ///
+    /// An explicit route wins. Without one, turn completions go to the
+    /// desktop only and everything else goes to every configured backend
+    /// (desktop only when `safety.desktop_notifications` is on).
+    pub fn backends_for(&self, event: NotifyEvent) -> Vec<Arc<dyn Notifier>> {
+        if let Some(names) = self.routes.get(event.as_str()) {
+            let mut selected: Vec<Arc<dyn Notifier>> = Vec::new();
+            for name in names {
+                let before = selected.len();
+                selected.extend(
+                    self.backends
+                        .iter()
+                        .filter(|backend| backend.name() == name.as_str())
+                        .cloned(),
+                );
+                if selected.len() == before {
+                    logging::warn(&format!(
+                        "Notification route {} names unknown or unconfigured backend '{}'",
+                        event.as_str(),
+                        name
+                    ));
+                }
+            }
+            return selected;
+        }
+
+        self.backends
+            .iter()
+            .filter(|backend| match event {
+                NotifyEvent::TurnComplete => backend.name() == "desktop",
+                _ => backend.name() != "desktop" || self.desktop_by_default,
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Send a notification to its routed backends (fire-and-forget).
+    pub fn dispatch(&self, notification: Notification) {
+        // Guard: only dispatch if inside a tokio runtime
+        if tokio::runtime::Handle::try_current().is_err() {
+            logging::info("Notification skipped: no tokio runtime");
+            return;
+        }
+
+        let notification = Arc::new(notification);
+        for backend in self.backends_for(notification.event) {
+            let notification = Arc::clone(&notification);
+            tokio::spawn(async move {
+                if let Err(e) = backend.notify(&notification).await {
+                    logging::error(&format!("{} notification failed: {}", backend.name(), e));
+                }
+            });
         }
     }
 
+    /// Send a notification to its routed backends and wait for every result.
+    pub async fn dispatch_and_wait(
+        &self,
+        notification: Notification,
+    ) -> Vec<(String, anyhow::Result<()>)> {
+        let backends = self.backends_for(notification.event);
+        let results = futures::future::join_all(backends.iter().map(|backend| {
+            let notification = &notification;
+            async move {
+                match tokio::time::timeout(TEST_SEND_TIMEOUT, backend.notify(notification)).await {
+                    Ok(result) => result,
+                    Err(_) => Err(anyhow::anyhow!(
+                        "timed out after {}s",
+                        TEST_SEND_TIMEOUT.as_secs()
+                    )),
+                }
+            }
+        }))
+        .await;
+        backends
+            .iter()
+            .map(|backend| backend.name().to_string())
+            .zip(results)
+            .collect()
+    }
+
     /// Send a cycle summary notification (after ambient cycle completes).
     pub fn dispatch_cycle_summary(&self, transcript: &AmbientTranscript) {
         let title = format!(
             "Ambient cycle: {} memories, {} compactions",
             transcript.memories_modified, transcript.compactions
         );
-        let safe_body = format_cycle_body_safe(transcript);
-        let detailed_body = format_cycle_body_detailed(transcript);
-
-        let priority = if transcript.pending_permissions > 0 {
+        let mut notification = Notification::new(
+            NotifyEvent::CycleSummary,
+            title,
+            format_cycle_body_detailed(transcript),
+        );
+        notification.safe_body = format_cycle_body_safe(transcript);
+        notification.priority = if transcript.pending_permissions > 0 {
             Priority::High
         } else {
             Priority::Default
         };
+        notification.reference_id = Some(transcript.session_id.clone());
 
-        self.send_all(
-            &title,
-            &safe_body,
-            &detailed_body,
-            priority,
-            Some(&transcript.session_id),
-        );
+        self.dispatch(notification);
     }
 
     /// Send a permission request notification (high priority).
     pub fn dispatch_permission_request(&self, action: &str, description: &str, request_id: &str) {
+        self.dispatch(self.permission_request_notification(action, description, request_id));
+    }
+
+    fn permission_request_notification(
+        &self,
+        action: &str,
+        description: &str,
+        request_id: &str,
+    ) -> Notification {
         let title = format!("jcode: permission needed ({})", action);
-        let safe_body = "An ambient action needs your approval. Open jcode to review.".to_string();
         let detailed_body = format!(
             "Action: {}\n{}\n\nRequest ID: {}\nReview in jcode to approve or deny.",
             action, description, request_id
         );
+        let mut notification = Notification::new(NotifyEvent::ApprovalNeeded, title, detailed_body);
+        notification.safe_body =
+            "An ambient action needs your approval. Open jcode to review.".to_string();
+        notification.priority = Priority::High;
+        notification.reference_id = Some(request_id.to_string());
 
         // Build rich HTML email with approve/deny buttons
-        let reply_to = self
-            .config
-            .email_from
-            .as_deref()
-            .unwrap_or("jcode@localhost");
-        let email_html = build_permission_email_html(action, description, request_id, reply_to);
-
-        self.send_all_with_email_override(
-            &title,
-            &safe_body,
-            &detailed_body,
-            Priority::High,
-            Some(request_id),
-            Some(&email_html),
-        );
-    }
-
-    /// Send through all configured channels (fire-and-forget).
-    ///
-    /// `safe_body` is sanitized (no secrets) — used for ntfy (potentially public).
-    /// `detailed_body` includes full info — used for email and desktop (private channels).
-    /// `cycle_id` is embedded as Message-ID in emails for reply tracking.
-    fn send_all(
-        &self,
-        title: &str,
-        safe_body: &str,
-        detailed_body: &str,
-        priority: Priority,
-        cycle_id: Option<&str>,
-    ) {
-        self.send_all_with_email_override(
-            title,
-            safe_body,
-            detailed_body,
-            priority,
-            cycle_id,
-            None,
-        );
+        let reply_to = self.email_from.as_deref().unwrap_or("jcode@localhost");
+        notification.email_html = Some(build_permission_email_html(
+            action,
+            description,
+            request_id,
+            reply_to,
+        ));
+        notification
     }
 
-    /// Like `send_all`, but with an optional pre-built HTML body for the email channel.
-    /// When `email_html_override` is Some, it's used directly as the email body instead
-    /// of converting `detailed_body` through `markdown_to_html_email`.
-    fn send_all_with_email_override(
-        &self,
-        title: &str,
-        safe_body: &str,
-        detailed_body: &str,
-        priority: Priority,
-        cycle_id: Option<&str>,
-        email_html_override: Option<&str>,
-    ) {
-        // Guard: only dispatch if inside a tokio runtime
-        if tokio::runtime::Handle::try_current().is_err() {
-            logging::info("Notification skipped: no tokio runtime");
-            return;
-        }
-
-        // ntfy.sh — uses SAFE body (may be publicly readable)
-        if let Some(ref topic) = self.config.ntfy_topic {
-            let client = self.client.clone();
-            let url = format!("{}/{}", self.config.ntfy_server, topic);
-            let title = title.to_string();
-            let body = safe_body.to_string();
-            tokio::spawn(async move {
-                if let Err(e) = send_ntfy(&client, &url, &title, &body, priority).await {
-                    logging::error(&format!("ntfy notification failed: {}", e));
-                }
-            });
-        }
-
-        // Desktop notification — uses DETAILED body (local machine, private)
-        if self.config.desktop_notifications {
-            let title = title.to_string();
-            let body = detailed_body.to_string();
-            let urgency = match priority {
-                Priority::Default => "normal",
-                Priority::High | Priority::Urgent => "critical",
-            };
-            tokio::spawn(async move {
-                send_desktop(&title, &body, urgency);
-            });
-        }
-
-        // Email — uses DETAILED body (sent to your own address, private)
-        // If email_html_override is provided, send it directly as HTML.
-        if self.config.email_enabled
-            && let (Some(to), Some(host), Some(from)) = (
-                &self.config.email_to,
-                &self.config.email_smtp_host,
-                &self.config.email_from,
-            )
-        {
-            let to = to.clone();
-            let host = host.clone();
-            let from = from.clone();
-            let port = self.config.email_smtp_port;
-            let password = self.config.email_password.clone();
-            let title = title.to_string();
-            let body = detailed_body.to_string();
-            let cycle_id = cycle_id.map(|s| s.to_string());
-            let html_override = email_html_override.map(|s| s.to_string());
-            tokio::spawn(async move {
-                if let Err(e) = send_email(SendEmailRequest {
-                    smtp_host: &host,
-                    smtp_port: port,
-                    from: &from,
-                    to: &to,
-                    password: password.as_deref(),
-                    subject: &title,
-                    body: &body,
-                    cycle_id: cycle_id.as_deref(),
-                    html_override: html_override.as_deref(),
-                })
-                .await
-                {
-                    logging::error(&format!("Email notification failed: {}", e));
-                } else {
-                    logging::info(&format!("Email notification sent to {}: {}", to, title));
-                }
-            });
+    /// Build a sample notification for `jcode debug notify-test`.
+    pub fn test_notification(&self, event: NotifyEvent) -> Notification {
+        match event {
+            NotifyEvent::TurnComplete => {
+                let mut notification = Notification::new(
+                    event,
+                    "jcode: turn complete",
+                    "This is a test notification from jcode debug notify-test.",
+                );
+                notification.safe_body = "Test notification from jcode.".to_string();
+                notification
+            }
+            NotifyEvent::ApprovalNeeded => self.permission_request_notification(
+                "notify-test",
+                "This is a test notification; there is nothing to approve.",
+                "notify-test",
+            ),
+            NotifyEvent::CycleSummary => {
+                let mut notification = Notification::new(
+                    event,
+                    "Ambient cycle: test",
+                    "This is a test notification from jcode debug notify-test.",
+                );
+                notification.safe_body = "Test notification from jcode.".to_string();
+                notification
+            }
         }
-
-        // Message channels (Telegram, Discord, etc.) — uses DETAILED body
-        let channel_text = format!("*{}*\n\n{}", title, detailed_body);
-        self.channels.send_all(&channel_text);
     }
 }
 
-// ---------------------------------------------------------------------------
-// ntfy.sh
-// ---------------------------------------------------------------------------
-
-async fn send_ntfy(
-    client: &reqwest::Client,
-    url: &str,
-    title: &str,
-    body: &str,
-    priority: Priority,
-) -> anyhow::Result<()> {
-    let resp = client
-        .post(url)
-        .header("Title", title)
-        .header("Priority", priority.ntfy_value())
-        .header("Tags", priority.ntfy_tags())
-        .body(body.to_string())
-        .send()
-        .await?;
-
-    if !resp.status().is_success() {
-        let status = resp.status();
-        let text = resp.text().await.unwrap_or_default();
-        anyhow::bail!("ntfy returned {}: {}", status, text);
-    }
-
-    logging::info(&format!("ntfy notification sent: {}", title));
-    Ok(())
-}
-
 // ---------------------------------------------------------------------------
 // Desktop (cross-platform, fire-and-forget)
 // ---------------------------------------------------------------------------
@@ -351,16 +378,17 @@ pub fn send_desktop_notification_rich(
 // Desktop (notify-send)
 // ---------------------------------------------------------------------------
 
-fn send_desktop(title: &str, body: &str, urgency: &str) {
+fn send_desktop(title: &str, body: &str, urgency: &str) -> anyhow::Result<()> {
     // On macOS notify-send does not exist; route through Notification Center.
     #[cfg(target_os = "macos")]
     {
         let _ = urgency;
         send_desktop_notification(title, body);
+        Ok(())
     }
     #[cfg(not(target_os = "macos"))]
     {
-        let result = std::process::Command::new("notify-send")
+        let status = std::process::Command::new("notify-send")
             .arg("--app-name=jcode")
             .arg(format!("--urgency={}", urgency))
             .arg("--icon=dialog-information")
@@ -368,20 +396,14 @@ fn send_desktop(title: &str, body: &str, urgency: &str) {
             .arg(body)
             .stdout(std::process::Stdio::null())
             .stderr(std::process::Stdio::null())
-            .status();
+            .status()
+            .map_err(|e| anyhow::anyhow!("notify-send unavailable: {}", e))?;
 
-        match result {
-            Ok(status) if status.success() => {
-                logging::info(&format!("Desktop notification sent: {}", title));
-            }
-            Ok(status) => {
-                logging::warn(&format!("notify-send exited with {}", status));
-            }
-            Err(e) => {
-                // notify-send not available - not an error, just skip
-                logging::info(&format!("notify-send unavailable: {}", e));
-            }
+        if !status.success() {
+            anyhow::bail!("notify-send exited with {}", status);
         }
+        logging::info(&format!("Desktop notification sent: {}", title));
+        Ok(())
     }
 }
 
@@ -437,7 +459,7 @@ pub async fn imap_reply_loop(config: SafetyConfig) {
                             approved,
                             message,
                         } => {
-                            if let Err(e) = crate::safety::record_permission_via_file(
+                            if let Err(e) = crate::safety::record_decision(
                                 request_id,
                                 *approved,
                                 "email_reply",
@@ -650,4 +672,76 @@ mod tests {
         let cfg = SafetyConfig::default();
         let _dispatcher = NotificationDispatcher::from_config(cfg);
     }
+
+    fn backend_names(dispatcher: &NotificationDispatcher, event: NotifyEvent) -> Vec<String> {
+        dispatcher
+            .backends_for(event)
+            .iter()
+            .map(|backend| backend.name().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_default_routes() {
+        let safety = SafetyConfig {
+            ntfy_topic: Some("jcode-test".to_string()),
+            desktop_notifications: false,
+            ..SafetyConfig::default()
+        };
+        let dispatcher = NotificationDispatcher::build(&safety, &NotificationsConfig::default());
+
+        assert_eq!(
+            backend_names(&dispatcher, NotifyEvent::TurnComplete),
+            vec!["desktop"]
+        );
+        assert_eq!(
+            backend_names(&dispatcher, NotifyEvent::ApprovalNeeded),
+            vec!["ntfy"]
+        );
+    }
+
+    #[test]
+    fn test_explicit_routes_select_named_backends() {
+        let safety = SafetyConfig {
+            ntfy_topic: Some("jcode-test".to_string()),
+            ..SafetyConfig::default()
+        };
+        let notifications = NotificationsConfig {
+            routes: [
+                ("turn_complete".to_string(), vec!["slack".to_string()]),
+                (
+                    "approval_needed".to_string(),
+                    vec![
+                        "ntfy".to_string(),
+                        "telegram".to_string(),
+                        "desktop".to_string(),
+                    ],
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            webhooks: vec![crate::config::WebhookConfig {
+                name: "slack".to_string(),
+                url: "https://hooks.example.com/T000".to_string(),
+                ..Default::default()
+            }],
+            ..NotificationsConfig::default()
+        };
+        let dispatcher = NotificationDispatcher::build(&safety, &notifications);
+
+        assert_eq!(
+            backend_names(&dispatcher, NotifyEvent::TurnComplete),
+            vec!["slack"]
+        );
+        // Telegram is not configured, so it is skipped.
+        assert_eq!(
+            backend_names(&dispatcher, NotifyEvent::ApprovalNeeded),
+            vec!["ntfy", "desktop"]
+        );
+        // Unrouted events still reach every configured backend.
+        assert_eq!(
+            backend_names(&dispatcher, NotifyEvent::CycleSummary),
+            vec!["desktop", "ntfy", "slack"]
+        );
+    }
 }
@@ -0,0 +1,387 @@
+//! Notification backends behind the [`Notifier`] trait.
+//!
+//! Each backend sends one [`Notification`] and reports its own failure; the
+//! dispatcher runs them independently so one slow or broken backend never
+//! holds up the rest.
+
+use super::{Priority, send_desktop, send_desktop_notification_rich};
+use crate::channel::MessageChannel;
+use crate::config::{SafetyConfig, WebhookConfig};
+use crate::logging;
+use async_trait::async_trait;
+use jcode_notify_email::{SendEmailRequest, send_email};
+use std::sync::Arc;
+
+/// What happened, used to route a notification to backends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotifyEvent {
+    /// An interactive agent turn finished
+    TurnComplete,
+    /// An action is waiting for the user's approval
+    ApprovalNeeded,
+    /// An ambient cycle finished
+    CycleSummary,
+}
+
+impl NotifyEvent {
+    pub const ALL: [NotifyEvent; 3] = [
+        NotifyEvent::TurnComplete,
+        NotifyEvent::ApprovalNeeded,
+        NotifyEvent::CycleSummary,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NotifyEvent::TurnComplete => "turn_complete",
+            NotifyEvent::ApprovalNeeded => "approval_needed",
+            NotifyEvent::CycleSummary => "cycle_summary",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim().replace('-', "_");
+        Self::ALL
+            .into_iter()
+            .find(|event| event.as_str().eq_ignore_ascii_case(&value))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub event: NotifyEvent,
+    pub title: String,
+    /// Full text, for private backends (desktop, email, chat channels).
+    pub body: String,
+    /// Sanitized text for backends that may be publicly readable (ntfy,
+    /// webhooks): no model-generated content.
+    pub safe_body: String,
+    pub priority: Priority,
+    /// Cycle or permission request ID; emails embed it for reply tracking.
+    pub reference_id: Option<String>,
+    /// Second line on macOS desktop notifications
+    pub subtitle: Option<String>,
+    /// macOS Notification Center sound name
+    pub sound: Option<String>,
+    /// Pre-built HTML used by the email backend instead of `body`
+    pub email_html: Option<String>,
+}
+
+impl Notification {
+    pub fn new(event: NotifyEvent, title: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            event,
+            title: title.into(),
+            body: body.into(),
+            safe_body: "Open jcode for details.".to_string(),
+            priority: Priority::Default,
+            reference_id: None,
+            subtitle: None,
+            sound: None,
+            email_html: None,
+        }
+    }
+}
+
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// Name used in `[notifications.routes]`.
+    fn name(&self) -> &str;
+
+    async fn notify(&self, notification: &Notification) -> anyhow::Result<()>;
+}
+
+// ---------------------------------------------------------------------------
+// Desktop
+// ---------------------------------------------------------------------------
+
+pub struct DesktopNotifier;
+
+#[async_trait]
+impl Notifier for DesktopNotifier {
+    fn name(&self) -> &str {
+        "desktop"
+    }
+
+    async fn notify(&self, notification: &Notification) -> anyhow::Result<()> {
+        if notification.subtitle.is_some() || notification.sound.is_some() {
+            send_desktop_notification_rich(
+                &notification.title,
+                notification.subtitle.as_deref(),
+                &notification.body,
+                notification.sound.as_deref(),
+            );
+            return Ok(());
+        }
+        let urgency = match notification.priority {
+            Priority::Default => "normal",
+            Priority::High | Priority::Urgent => "critical",
+        };
+        let title = notification.title.clone();
+        let body = notification.body.clone();
+        tokio::task::spawn_blocking(move || send_desktop(&title, &body, urgency)).await?
+    }
+}
+
+// ---------------------------------------------------------------------------
+// ntfy
+// ---------------------------------------------------------------------------
+
+pub struct NtfyNotifier {
+    client: reqwest::Client,
+    url: String,
+    token: Option<String>,
+}
+
+impl NtfyNotifier {
+    pub fn new(client: reqwest::Client, server: &str, topic: &str, token: Option<String>) -> Self {
+        Self {
+            client,
+            url: format!("{}/{}", server.trim_end_matches('/'), topic),
+            token: token.filter(|token| !token.trim().is_empty()),
+        }
+    }
+}
+
+/// `Authorization` value for an ntfy token: access tokens get `Bearer`, a
+/// value that already names its scheme (e.g. `Basic ...`) is used as is.
+fn ntfy_authorization(token: &str) -> String {
+    let token = token.trim();
+    if token.contains(' ') {
+        token.to_string()
+    } else {
+        format!("Bearer {}", token)
+    }
+}
+
+#[async_trait]
+impl Notifier for NtfyNotifier {
+    fn name(&self) -> &str {
+        "ntfy"
+    }
+
+    async fn notify(&self, notification: &Notification) -> anyhow::Result<()> {
+        let mut request = self
+            .client
+            .post(&self.url)
+            .header("Title", &notification.title)
+            .header("Priority", notification.priority.ntfy_value())
+            .header("Tags", notification.priority.ntfy_tags())
+            .body(notification.safe_body.clone());
+        if let Some(token) = &self.token {
+            request = request.header("Authorization", ntfy_authorization(token));
+        }
+        let resp = request.send().await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let text = resp.text().await.unwrap_or_default();
+            anyhow::bail!("ntfy returned {}: {}", status, text);
+        }
+
+        logging::info(&format!("ntfy notification sent: {}", notification.title));
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Email
+// ---------------------------------------------------------------------------
+
+pub struct EmailNotifier {
+    to: String,
+    host: String,
+    port: u16,
+    from: String,
+    password: Option<String>,
+}
+
+impl EmailNotifier {
+    pub fn from_config(config: &SafetyConfig) -> Option<Self> {
+        if !config.email_enabled {
+            return None;
+        }
+        Some(Self {
+            to: config.email_to.clone()?,
+            host: config.email_smtp_host.clone()?,
+            port: config.email_smtp_port,
+            from: config.email_from.clone()?,
+            password: config.email_password.clone(),
+        })
+    }
+}
+
+#[async_trait]
+impl Notifier for EmailNotifier {
+    fn name(&self) -> &str {
+        "email"
+    }
+
+    async fn notify(&self, notification: &Notification) -> anyhow::Result<()> {
+        send_email(SendEmailRequest {
+            smtp_host: &self.host,
+            smtp_port: self.port,
+            from: &self.from,
+            to: &self.to,
+            password: self.password.as_deref(),
+            subject: &notification.title,
+            body: &notification.body,
+            cycle_id: notification.reference_id.as_deref(),
+            html_override: notification.email_html.as_deref(),
+        })
+        .await?;
+        logging::info(&format!(
+            "Email notification sent to {}: {}",
+            self.to, notification.title
+        ));
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Message channels (Telegram, Discord, Jade relay)
+// ---------------------------------------------------------------------------
+
+pub struct ChannelNotifier(pub Arc<dyn MessageChannel>);
+
+#[async_trait]
+impl Notifier for ChannelNotifier {
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    async fn notify(&self, notification: &Notification) -> anyhow::Result<()> {
+        self.0
+            .send(&format!(
+                "*{}*\n\n{}",
+                notification.title, notification.body
+            ))
+            .await
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Webhooks
+// ---------------------------------------------------------------------------
+
+const DEFAULT_WEBHOOK_TEMPLATE: &str = r#"{"event": "{{event}}", "title": "{{title}}", "body": "{{body}}", "details": "{{details}}", "priority": "{{priority}}"}"#;
+
+pub struct WebhookNotifier {
+    client: reqwest::Client,
+    config: WebhookConfig,
+}
+
+impl WebhookNotifier {
+    pub fn new(client: reqwest::Client, config: WebhookConfig) -> Self {
+        Self { client, config }
+    }
+}
+
+/// Fill the payload template with JSON-escaped values and check the result
+/// is valid JSON.
+pub(super) fn render_webhook_payload(
+    template: &str,
+    notification: &Notification,
+) -> anyhow::Result<serde_json::Value> {
+    fn escaped(value: &str) -> String {
+        let quoted = serde_json::Value::String(value.to_string()).to_string();
+        quoted[1..quoted.len() - 1].to_string()
+    }
+
+    let template = if template.trim().is_empty() {
+        DEFAULT_WEBHOOK_TEMPLATE
+    } else {
+        template
+    };
+    let rendered = template
+        .replace("{{event}}", &escaped(notification.event.as_str()))
+        .replace("{{title}}", &escaped(&notification.title))
+        .replace("{{body}}", &escaped(&notification.safe_body))
+        .replace("{{details}}", &escaped(&notification.body))
+        .replace("{{priority}}", notification.priority.as_str());
+    serde_json::from_str(&rendered)
+        .map_err(|err| anyhow::anyhow!("webhook template is not valid JSON: {}", err))
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    async fn notify(&self, notification: &Notification) -> anyhow::Result<()> {
+        let payload = render_webhook_payload(&self.config.template, notification)?;
+        let mut request = self.client.post(&self.config.url).json(&payload);
+        for (name, value) in &self.config.headers {
+            request = request.header(name, value);
+        }
+        let resp = request.send().await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let text = resp.text().await.unwrap_or_default();
+            anyhow::bail!("webhook returned {}: {}", status, text);
+        }
+        logging::info(&format!(
+            "Webhook {} notification sent: {}",
+            self.config.name, notification.title
+        ));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn webhook_payload_escapes_values_into_template() {
+        let mut notification = Notification::new(
+            NotifyEvent::ApprovalNeeded,
+            "jcode: permission needed (\"bash\")",
+            "rm -rf target\nline two",
+        );
+        notification.safe_body = "An action needs approval.".to_string();
+        notification.priority = Priority::High;
+
+        let payload = render_webhook_payload(
+            r#"{"text": "{{title}}: {{body}}", "level": "{{priority}}"}"#,
+            &notification,
+        )
+        .unwrap();
+        assert_eq!(
+            payload,
+            serde_json::json!({
+                "text": "jcode: permission needed (\"bash\"): An action needs approval.",
+                "level": "high",
+            })
+        );
+
+        let payload = render_webhook_payload("", &notification).unwrap();
+        assert_eq!(payload["event"], "approval_needed");
+        assert_eq!(payload["details"], "rm -rf target\nline two");
+
+        assert!(render_webhook_payload("{{title}}", &notification).is_err());
+    }
+
+    #[test]
+    fn ntfy_token_becomes_bearer_unless_scheme_given() {
+        assert_eq!(ntfy_authorization("tk_abc"), "Bearer tk_abc");
+        assert_eq!(
+            ntfy_authorization("Basic dXNlcjpwYXNz"),
+            "Basic dXNlcjpwYXNz"
+        );
+    }
+
+    #[test]
+    fn notify_event_parses_names() {
+        assert_eq!(
+            NotifyEvent::parse("approval-needed"),
+            Some(NotifyEvent::ApprovalNeeded)
+        );
+        assert_eq!(
+            NotifyEvent::parse("turn_complete"),
+            Some(NotifyEvent::TurnComplete)
+        );
+        assert_eq!(NotifyEvent::parse("other"), None);
+    }
+}
@@ -5,6 +5,7 @@ fn test_request_roundtrip() -> Result<()> {
         content: "hello".to_string(),
         images: vec![],
         system_reminder: None,
+        focus_secs: None,
     };
     let json = serde_json::to_string(&req)?;
     let decoded = parse_request_json(&json)?;
@@ -194,6 +195,7 @@ fn test_history_event_roundtrip_preserves_side_panel_snapshot() -> Result<()> {
             content: "hello".to_string(),
             tool_calls: None,
             tool_data: None,
+            usage: None,
         }],
         images: Vec::new(),
         provider_name: Some("openai".to_string()),
@@ -210,6 +212,11 @@ fn test_history_event_roundtrip_preserves_side_panel_snapshot() -> Result<()> {
             cache_reported_input_tokens: 100,
             cache_read_input_tokens: 80,
             cache_creation_input_tokens: 10,
+            web_search_requests: 0,
+            pruned_context_tokens: 0,
+            redactions: 0,
+            overhead_input_tokens: 0,
+            overhead_output_tokens: 0,
         }),
         all_sessions: Vec::new(),
         client_count: None,
@@ -224,6 +231,7 @@ fn test_history_event_roundtrip_preserves_side_panel_snapshot() -> Result<()> {
         status_detail: None,
         upstream_provider: None,
         resolved_credential: None,
+        model_route: None,
         reasoning_effort: None,
         service_tier: None,
         subagent_model: None,
@@ -285,6 +293,7 @@ fn test_compacted_history_event_roundtrip() -> Result<()> {
             content: "older response".to_string(),
             tool_calls: None,
             tool_data: None,
+            usage: None,
         }],
         images: Vec::new(),
         compacted_total: 128,
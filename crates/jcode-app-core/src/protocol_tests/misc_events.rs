@@ -160,6 +160,7 @@ fn test_protocol_enum_roundtrips_cover_wire_names() -> Result<()> {
         (FeatureToggle::Swarm, "swarm"),
         (FeatureToggle::Autoreview, "autoreview"),
         (FeatureToggle::Autojudge, "autojudge"),
+        (FeatureToggle::Plan, "plan"),
     ];
     for (feature, wire) in feature_toggles {
         let json = serde_json::to_string(&feature)?;
@@ -297,6 +298,7 @@ fn test_message_request_roundtrip_preserves_images_and_system_reminder() -> Resu
             ("image/jpeg".to_string(), "BBB".to_string()),
         ],
         system_reminder: Some("be concise".to_string()),
+        focus_secs: None,
     };
     let json = serde_json::to_string(&req)?;
     let decoded = parse_request_json(&json)?;
@@ -305,6 +307,7 @@ fn test_message_request_roundtrip_preserves_images_and_system_reminder() -> Resu
         content,
         images,
         system_reminder,
+        ..
     } = decoded
     else {
         return Err(anyhow!("expected Message"));
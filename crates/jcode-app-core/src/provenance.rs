@@ -0,0 +1,427 @@
+//! `[git] provenance`: which tool call wrote which lines.
+//!
+//! After every successful edit or write, the file tools hand the file's old
+//! and new contents to [`record_tool_change`]. The line diff between them
+//! moves the ranges already recorded for that file and adds one range per
+//! changed hunk, tagged with the session and tool call. The mapping lives in
+//! `.jcode/provenance.jsonl` at the repository root, one range per line.
+//!
+//! Ranges follow the agent's own edits exactly. Edits made outside jcode are
+//! not seen, so after those the ranges are approximate. `jcode blame` joins
+//! the mapping with `git blame` and looks the tool call up in its session to
+//! show the turn and the instruction behind it.
+
+use crate::message::{ContentBlock, Role};
+use crate::session::Session;
+use crate::tool::ToolContext;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use similar::{DiffTag, TextDiff};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+
+/// Mapping file, relative to the repository root.
+pub const PROVENANCE_FILE: &str = ".jcode/provenance.jsonl";
+
+/// Serializes read-modify-write cycles of the mapping file in this process.
+static PROVENANCE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Lines of one file written by one tool call.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProvenanceEntry {
+    /// Relative to the repository root, with `/` separators
+    pub path: String,
+    /// First line, 1-based, in the file as it is now
+    pub start_line: usize,
+    /// Last line, inclusive
+    pub end_line: usize,
+    pub session_id: String,
+    pub tool_call_id: String,
+    pub tool: String,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl ProvenanceEntry {
+    fn contains(&self, line: usize) -> bool {
+        (self.start_line..=self.end_line).contains(&line)
+    }
+}
+
+/// Where a tool call came from, looked up in its session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolCallOrigin {
+    /// 1-based count of user prompts up to the tool call
+    pub turn: usize,
+    /// The user prompt the tool call answered
+    pub instruction: String,
+}
+
+/// One line of `jcode blame` output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlamedLine {
+    pub line: usize,
+    /// Abbreviated commit hash; zeros for uncommitted lines
+    pub commit: String,
+    pub author: String,
+    pub summary: String,
+    pub text: String,
+    pub provenance: Option<ProvenanceEntry>,
+}
+
+/// Record the change `tool` made to `path`: the session-start snapshot for
+/// `/changes`, and the line ranges when `[git] provenance` is on. Failures
+/// are logged; they never fail the tool call.
+pub fn record_tool_change(ctx: &ToolContext, tool: &str, path: &Path, before: &str, after: &str) {
+    if before == after {
+        return;
+    }
+    crate::session_changes::record_original(&ctx.session_id, path, before);
+    if !crate::config::config().git.provenance {
+        return;
+    }
+    let Some((root, relative)) = repo_relative(path) else {
+        return;
+    };
+    let template = ProvenanceEntry {
+        path: relative,
+        start_line: 0,
+        end_line: 0,
+        session_id: ctx.session_id.clone(),
+        tool_call_id: ctx.tool_call_id.clone(),
+        tool: tool.to_string(),
+        recorded_at: Utc::now(),
+    };
+    let _guard = PROVENANCE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let file = root.join(PROVENANCE_FILE);
+    let result = load_entries(&file).and_then(|mut entries| {
+        apply_change(&mut entries, &template, before, after);
+        save_entries(&file, &entries)
+    });
+    if let Err(error) = result {
+        crate::logging::warn(&format!(
+            "[provenance] failed to update {}: {:#}",
+            file.display(),
+            error
+        ));
+    }
+}
+
+/// Move the recorded ranges of `template.path` through the diff from
+/// `before` to `after`, then add a range for each hunk of new lines.
+pub fn apply_change(
+    entries: &mut Vec<ProvenanceEntry>,
+    template: &ProvenanceEntry,
+    before: &str,
+    after: &str,
+) {
+    let diff = TextDiff::from_lines(before, after);
+    let ops: Vec<_> = diff.ops().iter().map(|op| op.as_tag_tuple()).collect();
+
+    entries.retain_mut(|entry| {
+        if entry.path != template.path {
+            return true;
+        }
+        // Only unchanged lines carry over; a range whose lines were all
+        // replaced or deleted is gone.
+        let old = entry.start_line - 1..entry.end_line;
+        let mut kept = ops
+            .iter()
+            .filter(|(tag, ..)| *tag == DiffTag::Equal)
+            .filter_map(|(_, old_range, new_range)| {
+                let start = old.start.max(old_range.start);
+                let end = old.end.min(old_range.end);
+                (start < end).then(|| {
+                    let shift = |line: usize| new_range.start + line - old_range.start;
+                    (shift(start), shift(end))
+                })
+            });
+        let Some((start, mut end)) = kept.next() else {
+            return false;
+        };
+        if let Some((_, last)) = kept.last() {
+            end = last;
+        }
+        entry.start_line = start + 1;
+        entry.end_line = end;
+        true
+    });
+
+    entries.extend(
+        ops.iter()
+            .filter(|(tag, _, new_range)| *tag != DiffTag::Equal && !new_range.is_empty())
+            .map(|(_, _, new_range)| ProvenanceEntry {
+                start_line: new_range.start + 1,
+                end_line: new_range.end,
+                ..template.clone()
+            }),
+    );
+}
+
+/// The newest recorded range of `path` that covers `line`.
+pub fn attribution<'a>(
+    entries: &'a [ProvenanceEntry],
+    path: &str,
+    line: usize,
+) -> Option<&'a ProvenanceEntry> {
+    entries
+        .iter()
+        .rev()
+        .find(|entry| entry.path == path && entry.contains(line))
+}
+
+/// `git blame` for `file` (or just `line`), each line joined with the tool
+/// call that wrote it.
+pub fn blame(file: &Path, line: Option<usize>) -> Result<Vec<BlamedLine>> {
+    let file = file
+        .canonicalize()
+        .with_context(|| format!("cannot read {}", file.display()))?;
+    let (root, relative) = repo_relative(&file)
+        .with_context(|| format!("{} is not in a git repository", file.display()))?;
+    let mut args = vec!["blame".to_string(), "--porcelain".to_string()];
+    if let Some(line) = line {
+        args.push(format!("-L{},{}", line, line));
+    }
+    args.push("--".to_string());
+    args.push(relative.clone());
+    let output = Command::new("git")
+        .args(&args)
+        .current_dir(&root)
+        .output()
+        .context("failed to run git blame")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git blame failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let entries = load_entries(&root.join(PROVENANCE_FILE))?;
+    let mut lines = parse_porcelain(&String::from_utf8_lossy(&output.stdout));
+    for blamed in &mut lines {
+        blamed.provenance = attribution(&entries, &relative, blamed.line).cloned();
+    }
+    Ok(lines)
+}
+
+/// Find the turn and prompt behind `tool_call_id` in the saved session.
+pub fn tool_call_origin(session_id: &str, tool_call_id: &str) -> Option<ToolCallOrigin> {
+    let session = Session::load(session_id).ok()?;
+    let mut turn = 0;
+    let mut instruction = None;
+    for message in &session.messages {
+        if message.role == Role::User && message.display_role.is_none() {
+            let text: Vec<&str> = message
+                .content
+                .iter()
+                .filter_map(|block| match block {
+                    ContentBlock::Text { text, .. } => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect();
+            if !text.is_empty() {
+                turn += 1;
+                instruction = Some(text.join("\n"));
+            }
+        }
+        let called = message
+            .content
+            .iter()
+            .any(|block| matches!(block, ContentBlock::ToolUse { id, .. } if id == tool_call_id));
+        if called {
+            return Some(ToolCallOrigin {
+                turn,
+                instruction: instruction.unwrap_or_default(),
+            });
+        }
+    }
+    None
+}
+
+fn parse_porcelain(output: &str) -> Vec<BlamedLine> {
+    let mut lines = Vec::new();
+    let mut commits: std::collections::HashMap<String, (String, String)> = Default::default();
+    let mut current: Option<(String, usize)> = None;
+    for raw in output.lines() {
+        if let Some(text) = raw.strip_prefix('\t') {
+            let Some((commit, line)) = current.take() else {
+                continue;
+            };
+            let (author, summary) = commits.get(&commit).cloned().unwrap_or_default();
+            lines.push(BlamedLine {
+                line,
+                commit: commit.chars().take(8).collect(),
+                author,
+                summary,
+                text: text.to_string(),
+                provenance: None,
+            });
+        } else if let Some((key, value)) = raw.split_once(' ') {
+            match key {
+                "author" | "summary" => {
+                    if let Some((commit, _)) = &current {
+                        let known = commits.entry(commit.clone()).or_default();
+                        if key == "author" {
+                            known.0 = value.to_string();
+                        } else {
+                            known.1 = value.to_string();
+                        }
+                    }
+                }
+                _ if key.len() == 40 && key.bytes().all(|b| b.is_ascii_hexdigit()) => {
+                    let line = value
+                        .split(' ')
+                        .nth(1)
+                        .and_then(|line| line.parse().ok())
+                        .unwrap_or(0);
+                    current = Some((key.to_string(), line));
+                }
+                _ => {}
+            }
+        }
+    }
+    lines
+}
+
+/// The mapping file of the repository containing `dir`, if any.
+pub fn mapping_file(dir: &Path) -> Option<PathBuf> {
+    let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    repo_root(&dir).map(|root| root.join(PROVENANCE_FILE))
+}
+
+fn repo_root(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|dir| dir.join(".git").exists())
+        .map(Path::to_path_buf)
+}
+
+/// Repository root and `path` relative to it, when `path` is inside a git
+/// work tree.
+fn repo_relative(path: &Path) -> Option<(PathBuf, String)> {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let root = repo_root(path.parent()?)?;
+    let relative = path.strip_prefix(&root).ok()?;
+    let relative = relative
+        .components()
+        .map(|part| part.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    Some((root, relative))
+}
+
+fn load_entries(file: &Path) -> Result<Vec<ProvenanceEntry>> {
+    let content = match std::fs::read_to_string(file) {
+        Ok(content) => content,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(error.into()),
+    };
+    Ok(content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+fn save_entries(file: &Path, entries: &[ProvenanceEntry]) -> Result<()> {
+    if let Some(parent) = file.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut content = String::new();
+    for entry in entries {
+        content.push_str(&serde_json::to_string(entry)?);
+        content.push('\n');
+    }
+    let temp = file.with_extension("jsonl.tmp");
+    std::fs::write(&temp, content)?;
+    std::fs::rename(&temp, file)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(tool_call_id: &str) -> ProvenanceEntry {
+        ProvenanceEntry {
+            path: "src/lib.rs".to_string(),
+            start_line: 0,
+            end_line: 0,
+            session_id: "session_test".to_string(),
+            tool_call_id: tool_call_id.to_string(),
+            tool: "edit".to_string(),
+            recorded_at: Utc::now(),
+        }
+    }
+
+    fn ranges(entries: &[ProvenanceEntry]) -> Vec<(&str, usize, usize)> {
+        entries
+            .iter()
+            .map(|entry| {
+                (
+                    entry.tool_call_id.as_str(),
+                    entry.start_line,
+                    entry.end_line,
+                )
+            })
+            .collect()
+    }
+
+    fn owner(entries: &[ProvenanceEntry], line: usize) -> &str {
+        attribution(entries, "src/lib.rs", line)
+            .map(|entry| entry.tool_call_id.as_str())
+            .unwrap_or("")
+    }
+
+    #[test]
+    fn later_edits_shift_and_shrink_recorded_ranges() {
+        let mut entries = Vec::new();
+        apply_change(&mut entries, &template("write"), "", "a\nb\nc\n");
+        assert_eq!(ranges(&entries), vec![("write", 1, 3)]);
+
+        // Two lines inserted above move the range down.
+        apply_change(
+            &mut entries,
+            &template("insert"),
+            "a\nb\nc\n",
+            "x\ny\na\nb\nc\n",
+        );
+        assert_eq!(ranges(&entries), vec![("write", 3, 5), ("insert", 1, 2)]);
+
+        // Replacing `b` keeps the write's surviving lines around it and
+        // attributes the new line to the replacing call.
+        apply_change(
+            &mut entries,
+            &template("replace"),
+            "x\ny\na\nb\nc\n",
+            "x\ny\na\nB\nc\n",
+        );
+        assert_eq!(owner(&entries, 4), "replace");
+        assert_eq!(owner(&entries, 5), "write");
+
+        // Deleting every line of a range drops it.
+        apply_change(
+            &mut entries,
+            &template("delete"),
+            "x\ny\na\nB\nc\n",
+            "a\nB\nc\n",
+        );
+        assert!(entries.iter().all(|entry| entry.tool_call_id != "insert"));
+        assert_eq!(owner(&entries, 1), "write");
+    }
+
+    #[test]
+    fn porcelain_lines_share_commit_details() {
+        let sha = "a".repeat(40);
+        let output = format!(
+            "{sha} 1 1 2\nauthor Ada\nsummary Add parser\nfilename src/lib.rs\n\tfn main() {{\n{sha} 2 2\n\t}}\n"
+        );
+        let lines = parse_porcelain(&output);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1].line, 2);
+        assert_eq!(lines[1].commit, "aaaaaaaa");
+        assert_eq!(lines[1].author, "Ada");
+        assert_eq!(lines[1].summary, "Add parser");
+        assert_eq!(lines[1].text, "}");
+    }
+}
@@ -69,6 +69,13 @@ pub enum TimelineEventKind {
         cache_read: Option<u64>,
         #[serde(default)]
         cache_creation: Option<u64>,
+        /// Model and provider that produced the response, and how long it took.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        model: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        provider: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        duration_ms: Option<u64>,
     },
 
     /// Turn complete (commits streaming text, resets to idle)
@@ -275,6 +282,9 @@ pub fn export_timeline(session: &Session) -> Vec<TimelineEvent> {
                             output: usage.output_tokens,
                             cache_read: usage.cache_read_input_tokens,
                             cache_creation: usage.cache_creation_input_tokens,
+                            model: usage.model.clone(),
+                            provider: usage.provider.clone(),
+                            duration_ms: usage.duration_ms,
                         },
                     });
                 }
@@ -349,6 +359,15 @@ pub fn export_timeline(session: &Session) -> Vec<TimelineEvent> {
                 participants: participants.clone(),
                 reason: reason.clone(),
             },
+            StoredReplayEventKind::AutoDebug {
+                tool_name,
+                diagnosis,
+                ..
+            } => TimelineEventKind::DisplayMessage {
+                role: "system".to_string(),
+                title: Some(format!("auto-debug: {}", tool_name)),
+                content: diagnosis.clone(),
+            },
         };
         events.push(TimelineEvent { t: offset, kind });
     }
@@ -498,6 +517,7 @@ pub fn timeline_to_replay_events(timeline: &[TimelineEvent]) -> Vec<(u64, Replay
                 output,
                 cache_read,
                 cache_creation,
+                ..
             } => {
                 out.push((
                     delay,
@@ -35,6 +35,9 @@ mod headless;
 mod jade_relay;
 mod lifecycle;
 mod live_turn;
+mod metrics;
+mod migration;
+mod permission_inbox;
 mod provider_control;
 mod reload;
 mod reload_recovery;
@@ -46,6 +49,8 @@ mod swarm;
 mod swarm_channels;
 mod swarm_mutation_state;
 mod swarm_persistence;
+mod turn_queue;
+mod turn_relay;
 mod util;
 
 pub(super) use self::await_members_state::AwaitMembersRuntime;
@@ -580,6 +585,20 @@ impl Server {
         });
     }
 
+    /// Probe the prompt's environment section off the accept path so the
+    /// first session doesn't wait on `--version` probes.
+    fn spawn_environment_prewarm(&self) {
+        tokio::task::spawn_blocking(|| {
+            let start = Instant::now();
+            if crate::prompt::environment_descriptor().is_some() {
+                crate::logging::info(&format!(
+                    "Environment probe completed in {}ms",
+                    start.elapsed().as_millis()
+                ));
+            }
+        });
+    }
+
     async fn recover_headless_sessions_on_startup(&self) {
         let sessions_to_restore = {
             let members = self.swarm_state.members.read().await;
@@ -921,6 +940,7 @@ impl Server {
         server_start_time: Instant,
     ) -> (tokio::task::JoinHandle<()>, tokio::task::JoinHandle<()>) {
         self.spawn_registry_prewarm();
+        self.spawn_environment_prewarm();
         let registry_info = self.build_registry_info();
 
         let runtime = self.runtime();
@@ -939,6 +959,7 @@ impl Server {
 
         // Spawn WebSocket gateway for iOS/web clients (if enabled)
         let _gateway_handle = self.spawn_gateway(runtime);
+        self.spawn_metrics_listener();
 
         // Startup recovery can be expensive in multi-session reloads. Run it
         // only after the replacement daemon is already accepting reconnects.
@@ -1113,6 +1134,12 @@ impl Server {
             });
         }
 
+        // Push permission inbox changes to clients, whichever surface made them.
+        permission_inbox::spawn_watcher();
+
+        // Announce newer server binaries ahead of migrating sessions onto them.
+        migration::spawn_watcher();
+
         // Spawn the background ambient/schedule loop.
         if let Some(ref runner) = self.ambient_runner {
             let ambient_handle = runner.clone();
@@ -2000,6 +2027,20 @@ impl Server {
 
         Some(runtime.spawn_gateway_accept_loop(client_rx))
     }
+
+    /// Spawn the Prometheus-style metrics listener if `[server]
+    /// metrics_listen` is set.
+    fn spawn_metrics_listener(&self) {
+        let Some(addr) = crate::config::config().server.metrics_listen.clone() else {
+            return;
+        };
+        let sessions = Arc::clone(&self.sessions);
+        tokio::spawn(async move {
+            if let Err(e) = metrics::run_metrics_listener(addr, sessions).await {
+                crate::logging::error(&format!("Metrics listener error: {}", e));
+            }
+        });
+    }
 }
 
 pub use self::client_api::Client;
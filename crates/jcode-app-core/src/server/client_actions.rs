@@ -8,7 +8,9 @@ use super::{
     remove_session_from_swarm, swarm_id_for_dir, truncate_detail, update_member_status,
 };
 use crate::agent::Agent;
-use crate::protocol::{FeatureToggle, NotificationType, ServerEvent};
+use crate::protocol::{
+    AsideAction, CompareAction, FeatureToggle, HandoffAction, NotificationType, ServerEvent,
+};
 use crate::session::Session;
 use crate::util::truncate_str;
 use jcode_agent_runtime::{SoftInterruptSource, StreamError};
@@ -249,6 +251,109 @@ pub(super) async fn handle_set_subagent_model(
     }
 }
 
+/// Select or clear the session profile. Waits for the agent, so a request
+/// sent mid-turn takes effect at the next turn boundary.
+pub(super) fn handle_set_profile(
+    id: u64,
+    profile: Option<String>,
+    agent: &Arc<Mutex<Agent>>,
+    client_event_tx: &mpsc::UnboundedSender<ServerEvent>,
+) {
+    let agent = Arc::clone(agent);
+    let tx = client_event_tx.clone();
+    tokio::spawn(async move {
+        let mut agent_guard = agent.lock().await;
+        match agent_guard.set_profile(profile.as_deref()) {
+            Ok(()) => {
+                let _ = tx.send(ServerEvent::Done { id });
+            }
+            Err(error) => {
+                let _ = tx.send(ServerEvent::Error {
+                    id,
+                    message: crate::util::format_error_chain(&error),
+                    retry_after_secs: None,
+                });
+            }
+        }
+    });
+}
+
+/// Turn safe mode on or off for the session. Like profile switches, this
+/// waits for the agent, so a request sent mid-turn lands at the turn boundary.
+pub(super) fn handle_set_safe_mode(
+    id: u64,
+    enabled: bool,
+    agent: &Arc<Mutex<Agent>>,
+    client_event_tx: &mpsc::UnboundedSender<ServerEvent>,
+) {
+    let agent = Arc::clone(agent);
+    let tx = client_event_tx.clone();
+    tokio::spawn(async move {
+        let mut agent_guard = agent.lock().await;
+        match agent_guard.set_safe_mode(enabled) {
+            Ok(()) => {
+                let _ = tx.send(ServerEvent::Done { id });
+            }
+            Err(error) => {
+                let _ = tx.send(ServerEvent::Error {
+                    id,
+                    message: crate::util::format_error_chain(&error),
+                    retry_after_secs: None,
+                });
+            }
+        }
+    });
+}
+
+/// Turn the approval gate on or off. Waits for the agent like safe mode does.
+pub(super) fn handle_set_approval_gate(
+    id: u64,
+    enabled: bool,
+    agent: &Arc<Mutex<Agent>>,
+    client_event_tx: &mpsc::UnboundedSender<ServerEvent>,
+) {
+    let agent = Arc::clone(agent);
+    let tx = client_event_tx.clone();
+    tokio::spawn(async move {
+        agent.lock().await.set_approval_gate(enabled);
+        let _ = tx.send(ServerEvent::Done { id });
+    });
+}
+
+/// Start a post-mortem of a turn that just failed. The request is built now,
+/// before a queued message can start the next turn; the model call runs in a
+/// task and its note is only recorded if the session has not moved on.
+pub(super) async fn spawn_turn_post_mortem(
+    error: String,
+    agent: &Arc<Mutex<Agent>>,
+    client_event_tx: &mpsc::UnboundedSender<ServerEvent>,
+) {
+    let Some(request) = agent.lock().await.prepare_post_mortem(&error) else {
+        return;
+    };
+    let agent = Arc::clone(agent);
+    let tx = client_event_tx.clone();
+    tokio::spawn(async move {
+        let Some((request, post_mortem)) = request.run().await else {
+            return;
+        };
+        let result = agent
+            .lock()
+            .await
+            .record_post_mortem(&request, &post_mortem);
+        match result {
+            Ok(Some(summary)) => {
+                let _ = tx.send(ServerEvent::PostMortem { summary });
+            }
+            Ok(None) => {}
+            Err(error) => crate::logging::warn(&format!(
+                "POST_MORTEM_SAVE_FAILED error={}",
+                crate::util::format_error_chain(&error)
+            )),
+        }
+    });
+}
+
 pub(super) fn handle_run_subagent(
     id: u64,
     prompt: String,
@@ -305,12 +410,13 @@ pub(super) fn handle_run_subagent(
             name: tool_name.clone(),
         });
 
-        let (registry, session_id, working_dir) = {
+        let (registry, session_id, working_dir, workspace_roots) = {
             let agent_guard = agent.lock().await;
             (
                 agent_guard.registry(),
                 agent_guard.session_id().to_string(),
-                agent_guard.working_dir().map(std::path::PathBuf::from),
+                agent_guard.tool_working_dir().map(std::path::PathBuf::from),
+                agent_guard.workspace_roots(),
             )
         };
 
@@ -319,6 +425,7 @@ pub(super) fn handle_run_subagent(
             message_id,
             tool_call_id: tool_call_id.clone(),
             working_dir,
+            workspace_roots,
             stdin_request_tx: None,
             graceful_shutdown_signal: None,
             execution_mode: crate::tool::ToolExecutionMode::Direct,
@@ -385,6 +492,215 @@ pub(super) fn handle_run_subagent(
     });
 }
 
+pub(super) async fn handle_plan_decision(
+    id: u64,
+    decision: crate::plan::proposal::PlanDecision,
+    feedback: Option<String>,
+    agent: &Arc<Mutex<Agent>>,
+    client_event_tx: &mpsc::UnboundedSender<ServerEvent>,
+) {
+    let mut agent_guard = agent.lock().await;
+    match agent_guard.decide_plan(decision, feedback) {
+        Ok(()) => {
+            let _ = client_event_tx.send(ServerEvent::Done { id });
+        }
+        Err(error) => {
+            let _ = client_event_tx.send(ServerEvent::Error {
+                id,
+                message: crate::util::format_error_chain(&error),
+                retry_after_secs: None,
+            });
+        }
+    }
+}
+
+pub(super) async fn handle_aside(
+    id: u64,
+    action: AsideAction,
+    agent: &Arc<Mutex<Agent>>,
+    client_event_tx: &mpsc::UnboundedSender<ServerEvent>,
+) {
+    let mut agent_guard = agent.lock().await;
+    let result = match action {
+        AsideAction::Start => agent_guard.start_aside(),
+        AsideAction::Close { summary } => agent_guard.close_aside(summary),
+        AsideAction::Summarize => {
+            let request = agent_guard.prepare_aside_summary();
+            drop(agent_guard);
+            // The summary is a model call; answer from a task so the client
+            // loop keeps serving requests meanwhile.
+            let client_event_tx = client_event_tx.clone();
+            tokio::spawn(async move {
+                let (summary, error) = match request {
+                    Ok(request) => match request.run().await {
+                        Ok(summary) => (Some(summary), None),
+                        Err(error) => (None, Some(crate::util::format_error_chain(&error))),
+                    },
+                    Err(error) => (None, Some(crate::util::format_error_chain(&error))),
+                };
+                let _ = client_event_tx.send(ServerEvent::AsideChanged {
+                    id,
+                    active: true,
+                    summary,
+                    error,
+                });
+            });
+            return;
+        }
+    };
+    let active = agent_guard.aside_active();
+    drop(agent_guard);
+    let _ = client_event_tx.send(ServerEvent::AsideChanged {
+        id,
+        active,
+        summary: None,
+        error: result
+            .err()
+            .map(|error| crate::util::format_error_chain(&error)),
+    });
+}
+
+pub(super) async fn handle_compare(
+    id: u64,
+    action: CompareAction,
+    agent: &Arc<Mutex<Agent>>,
+    client_event_tx: &mpsc::UnboundedSender<ServerEvent>,
+) {
+    let mut agent_guard = agent.lock().await;
+    match action {
+        CompareAction::Run { models, prompt } => {
+            let request = agent_guard.prepare_compare(&prompt, models);
+            drop(agent_guard);
+            // Every model answers in full before the client hears back, so
+            // run them from a task and keep serving requests meanwhile.
+            let client_event_tx = client_event_tx.clone();
+            tokio::spawn(async move {
+                let (runs, error) = match request {
+                    Ok(request) => (request.run().await, None),
+                    Err(error) => (Vec::new(), Some(crate::util::format_error_chain(&error))),
+                };
+                let _ = client_event_tx.send(ServerEvent::CompareResult {
+                    id,
+                    prompt,
+                    runs,
+                    error,
+                });
+            });
+        }
+        CompareAction::Commit {
+            prompt,
+            chosen,
+            runs,
+        } => {
+            let result = agent_guard.commit_compare(&prompt, chosen, runs);
+            drop(agent_guard);
+            let _ = client_event_tx.send(match result {
+                Ok(()) => ServerEvent::Done { id },
+                Err(error) => ServerEvent::Error {
+                    id,
+                    message: crate::util::format_error_chain(&error),
+                    retry_after_secs: None,
+                },
+            });
+        }
+    }
+}
+
+pub(super) async fn handle_handoff(
+    id: u64,
+    action: HandoffAction,
+    agent: &Arc<Mutex<Agent>>,
+    client_event_tx: &mpsc::UnboundedSender<ServerEvent>,
+) {
+    let mut agent_guard = agent.lock().await;
+    match action {
+        HandoffAction::Summarize => {
+            let request = agent_guard.prepare_handoff_summary();
+            drop(agent_guard);
+            // Same as the aside summary: a model call, so answer from a task.
+            let client_event_tx = client_event_tx.clone();
+            tokio::spawn(async move {
+                let (summary, error) = match request {
+                    Ok(request) => match request.run().await {
+                        Ok(summary) => (Some(summary), None),
+                        Err(error) => (None, Some(crate::util::format_error_chain(&error))),
+                    },
+                    Err(error) => (None, Some(crate::util::format_error_chain(&error))),
+                };
+                let _ = client_event_tx.send(ServerEvent::HandoffChanged {
+                    id,
+                    summary,
+                    new_session_id: None,
+                    new_session_name: None,
+                    error,
+                });
+            });
+        }
+        HandoffAction::Create { summary } => {
+            let result = agent_guard.create_handoff(&summary);
+            drop(agent_guard);
+            let event = match result {
+                Ok((new_session_id, new_session_name)) => ServerEvent::HandoffChanged {
+                    id,
+                    summary: None,
+                    new_session_id: Some(new_session_id),
+                    new_session_name: Some(new_session_name),
+                    error: None,
+                },
+                Err(error) => ServerEvent::HandoffChanged {
+                    id,
+                    summary: None,
+                    new_session_id: None,
+                    new_session_name: None,
+                    error: Some(crate::util::format_error_chain(&error)),
+                },
+            };
+            let _ = client_event_tx.send(event);
+        }
+    }
+}
+
+/// Answer `/env` from a blocking task: a refresh runs the `--version` probes,
+/// and so does the first lookup if the startup probe hasn't finished yet.
+pub(super) fn handle_environment(
+    id: u64,
+    refresh: bool,
+    client_event_tx: &mpsc::UnboundedSender<ServerEvent>,
+) {
+    let client_event_tx = client_event_tx.clone();
+    tokio::task::spawn_blocking(move || {
+        let refreshed = refresh
+            && crate::config::config()
+                .prompt
+                .section_enabled(crate::config::PromptSection::Environment);
+        let descriptor = if refreshed {
+            Some(crate::prompt::refresh_environment_descriptor())
+        } else {
+            crate::prompt::environment_descriptor()
+        };
+        let _ = client_event_tx.send(ServerEvent::EnvironmentChanged {
+            id,
+            descriptor,
+            refreshed,
+        });
+    });
+}
+
+/// Answer `/prompt sections`. Waits for the agent in a task of its own, so a
+/// running turn delays the report without blocking the connection.
+pub(super) fn handle_prompt_sections(
+    id: u64,
+    agent: &Arc<Mutex<Agent>>,
+    client_event_tx: &mpsc::UnboundedSender<ServerEvent>,
+) {
+    let agent = Arc::clone(agent);
+    let client_event_tx = client_event_tx.clone();
+    tokio::spawn(async move {
+        let report = agent.lock().await.prompt_sections_report();
+        let _ = client_event_tx.send(ServerEvent::PromptSections { id, report });
+    });
+}
+
 #[expect(
     clippy::too_many_arguments,
     reason = "set feature mutates agent state, persistence, swarm/session metadata, and client notifications together"
@@ -427,6 +743,12 @@ pub(super) async fn handle_set_feature(
             );
             let _ = client_event_tx.send(ServerEvent::Done { id });
         }
+        FeatureToggle::Autocommit => {
+            let mut agent_guard = agent.lock().await;
+            agent_guard.set_auto_commit_enabled(enabled);
+            drop(agent_guard);
+            let _ = client_event_tx.send(ServerEvent::Done { id });
+        }
         FeatureToggle::Autoreview => {
             let mut agent_guard = agent.lock().await;
             match agent_guard.set_autoreview_enabled(enabled) {
@@ -457,6 +779,21 @@ pub(super) async fn handle_set_feature(
                 }
             }
         }
+        FeatureToggle::Plan => {
+            let mut agent_guard = agent.lock().await;
+            match agent_guard.set_plan_mode(enabled) {
+                Ok(()) => {
+                    let _ = client_event_tx.send(ServerEvent::Done { id });
+                }
+                Err(error) => {
+                    let _ = client_event_tx.send(ServerEvent::Error {
+                        id,
+                        message: crate::util::format_error_chain(&error),
+                        retry_after_secs: None,
+                    });
+                }
+            }
+        }
         FeatureToggle::Swarm => {
             if *swarm_enabled == enabled {
                 let _ = client_event_tx.send(ServerEvent::Done { id });
@@ -624,6 +961,54 @@ pub(super) async fn handle_rename_session(
     );
 }
 
+pub(super) async fn handle_update_workspace_roots(
+    id: u64,
+    add: Vec<String>,
+    remove: Vec<String>,
+    agent: &Arc<Mutex<Agent>>,
+    client_event_tx: &mpsc::UnboundedSender<ServerEvent>,
+) {
+    let mut agent_guard = agent.lock().await;
+    let mut error = None;
+    for name in &remove {
+        match agent_guard.remove_workspace_root(name) {
+            Ok(Some(_)) => {}
+            Ok(None) => error = Some(format!("no workspace root named '{}'", name)),
+            Err(err) => error = Some(crate::util::format_error_chain(&err)),
+        }
+    }
+    for path in &add {
+        if let Err(err) = agent_guard.add_workspace_root(path) {
+            error = Some(crate::util::format_error_chain(&err));
+        }
+    }
+    let roots = agent_guard
+        .workspace_roots()
+        .iter()
+        .map(|root| root.display().to_string())
+        .collect();
+    drop(agent_guard);
+
+    let _ = client_event_tx.send(ServerEvent::WorkspaceRootsChanged { id, roots, error });
+}
+
+pub(super) async fn handle_set_tool_scope(
+    id: u64,
+    path: Option<String>,
+    agent: &Arc<Mutex<Agent>>,
+    client_event_tx: &mpsc::UnboundedSender<ServerEvent>,
+) {
+    let mut agent_guard = agent.lock().await;
+    let error = agent_guard
+        .set_tool_scope(path.as_deref())
+        .err()
+        .map(|err| crate::util::format_error_chain(&err));
+    let scope = agent_guard.tool_scope().map(str::to_string);
+    drop(agent_guard);
+
+    let _ = client_event_tx.send(ServerEvent::ToolScopeChanged { id, scope, error });
+}
+
 pub(super) async fn handle_trigger_memory_extraction(
     id: u64,
     agent: &Arc<Mutex<Agent>>,
@@ -661,6 +1046,9 @@ fn clone_split_session(parent_session_id: &str) -> anyhow::Result<(String, Strin
     child.replace_messages(parent.messages.clone());
     child.compaction = parent.compaction.clone();
     child.working_dir = parent.working_dir.clone();
+    child.workspace_roots = parent.workspace_roots.clone();
+    child.tool_scope = parent.tool_scope.clone();
+    child.instructions = parent.instructions.clone();
     child.model = parent.model.clone();
     child.status = crate::session::SessionStatus::Closed;
     // The parent agent keeps ownership of any in-flight request; tell the
@@ -695,9 +1083,13 @@ fn create_transfer_child_session(
     child.messages.clear();
     child.compaction = compaction;
     child.working_dir = parent.working_dir.clone();
+    child.workspace_roots = parent.workspace_roots.clone();
+    child.tool_scope = parent.tool_scope.clone();
+    child.instructions = parent.instructions.clone();
     child.model = parent.model.clone();
     child.provider_key = parent.provider_key.clone();
     child.route_api_method = parent.route_api_method.clone();
+    child.model_route = parent.model_route.clone();
     child.subagent_model = parent.subagent_model.clone();
     child.improve_mode = parent.improve_mode;
     child.autoreview_enabled = parent.autoreview_enabled;
@@ -1079,6 +1471,28 @@ pub(super) async fn handle_stdin_response(
     let _ = client_event_tx.send(ServerEvent::Done { id });
 }
 
+/// Answer an entry in the shared permission inbox on behalf of the client.
+pub(super) fn handle_permission_decision(
+    id: u64,
+    request_id: &str,
+    approved: bool,
+    message: Option<String>,
+    client_event_tx: &mpsc::UnboundedSender<ServerEvent>,
+) {
+    match crate::safety::record_decision(request_id, approved, "client", message) {
+        Ok(()) => {
+            let _ = client_event_tx.send(ServerEvent::Done { id });
+        }
+        Err(error) => {
+            let _ = client_event_tx.send(ServerEvent::Error {
+                id,
+                message: crate::util::format_error_chain(&error),
+                retry_after_secs: None,
+            });
+        }
+    }
+}
+
 pub(super) struct AgentTaskContext<'a> {
     pub(super) client_event_tx: &'a mpsc::UnboundedSender<ServerEvent>,
     pub(super) swarm_members: &'a Arc<RwLock<HashMap<String, SwarmMember>>>,
@@ -118,6 +118,7 @@ fn clone_split_session_uses_persisted_session_state() {
     );
     parent.working_dir = Some("/tmp/jcode-split-test".to_string());
     parent.model = Some("gpt-test".to_string());
+    parent.provider_session_id = Some("parent-provider-session".to_string());
     parent.add_message(
         Role::User,
         vec![ContentBlock::Text {
@@ -138,6 +139,10 @@ fn clone_split_session_uses_persisted_session_state() {
     let child = crate::session::Session::load(&child_id).expect("load child");
 
     assert_eq!(child.parent_id.as_deref(), Some(parent.id.as_str()));
+    assert!(
+        child.provider_session_id.is_none(),
+        "fork must not resume the parent's provider conversation"
+    );
     assert_eq!(
         child.messages.len(),
         parent.messages.len() + 1,
@@ -1,19 +1,22 @@
 use super::{
     ClientConnectionInfo, ClientDebugState, FileTouchService, SessionInterruptQueues, SwarmEvent,
-    SwarmEventType, SwarmMember, VersionedPlan, record_swarm_event, remove_background_tool_signal,
-    remove_session_channel_subscriptions, remove_session_from_swarm,
-    remove_session_interrupt_queue, unregister_session_event_sender, update_member_status,
+    SwarmEventType, SwarmMember, VersionedPlan, fanout_session_event, record_swarm_event,
+    remove_background_tool_signal, remove_session_channel_subscriptions, remove_session_from_swarm,
+    remove_session_interrupt_queue, truncate_detail, unregister_session_event_sender,
+    update_member_status, update_member_status_with_report,
 };
 use crate::agent::Agent;
+use crate::protocol::ServerEvent;
 use anyhow::Result;
 use jcode_agent_runtime::InterruptSignal;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::{Mutex, RwLock, broadcast};
+use tokio::sync::{Mutex, RwLock, broadcast, mpsc};
 
 type SessionAgents = Arc<RwLock<HashMap<String, Arc<Mutex<Agent>>>>>;
 type ChannelSubscriptions = Arc<RwLock<HashMap<String, HashMap<String, HashSet<String>>>>>;
+type ProcessingDone = (u64, Result<()>, Option<String>);
 
 const RELOAD_DISCONNECT_MARKER_MAX_AGE: Duration = Duration::from_secs(30);
 
@@ -47,6 +50,55 @@ async fn session_has_live_successor(
         .any(|info| info.session_id == session_id)
 }
 
+/// Report the end of a turn whose client is gone, the way the connection
+/// loop would have, to the clients now attached to the session.
+async fn finish_detached_turn(
+    session_id: &str,
+    (_, result, completion_report): ProcessingDone,
+    swarm_members: &Arc<RwLock<HashMap<String, SwarmMember>>>,
+    swarms_by_id: &Arc<RwLock<HashMap<String, HashSet<String>>>>,
+    event_history: &Arc<RwLock<std::collections::VecDeque<SwarmEvent>>>,
+    event_counter: &Arc<std::sync::atomic::AtomicU64>,
+    swarm_event_tx: &broadcast::Sender<SwarmEvent>,
+) {
+    let event = match result {
+        Ok(()) => {
+            update_member_status_with_report(
+                session_id,
+                "ready",
+                None,
+                completion_report,
+                swarm_members,
+                swarms_by_id,
+                Some(event_history),
+                Some(event_counter),
+                Some(swarm_event_tx),
+            )
+            .await;
+            ServerEvent::Done { id: 0 }
+        }
+        Err(error) => {
+            update_member_status(
+                session_id,
+                "failed",
+                Some(truncate_detail(&error.to_string(), 120)),
+                swarm_members,
+                swarms_by_id,
+                Some(event_history),
+                Some(event_counter),
+                Some(swarm_event_tx),
+            )
+            .await;
+            ServerEvent::Error {
+                id: 0,
+                message: crate::util::format_error_chain(&error),
+                retry_after_secs: None,
+            }
+        }
+    };
+    fanout_session_event(swarm_members, session_id, event).await;
+}
+
 #[expect(
     clippy::too_many_arguments,
     reason = "disconnect cleanup updates sessions, swarms, files, channels, debug state, and shutdown signals together"
@@ -56,7 +108,8 @@ pub(super) async fn cleanup_client_connection(
     client_session_id: &str,
     client_is_processing: bool,
     processing_task: &mut Option<tokio::task::JoinHandle<()>>,
-    event_handle: tokio::task::JoinHandle<()>,
+    processing_done_rx: &mut mpsc::UnboundedReceiver<ProcessingDone>,
+    mut event_handle: tokio::task::JoinHandle<()>,
     swarm_members: &Arc<RwLock<HashMap<String, SwarmMember>>>,
     swarms_by_id: &Arc<RwLock<HashMap<String, HashSet<String>>>>,
     swarm_coordinators: &Arc<RwLock<HashMap<String, String>>>,
@@ -79,7 +132,19 @@ pub(super) async fn cleanup_client_connection(
             .as_ref()
             .map(|handle| !handle.is_finished())
             .unwrap_or(false);
-    let disposition = disconnect_disposition(disconnected_while_processing);
+    let mut disposition = disconnect_disposition(disconnected_while_processing);
+    // A crashed client does not end its turn: it keeps running and a client
+    // that resumes the session picks up its stream.
+    let detach_turn = disposition == DisconnectDisposition::Crashed
+        && processing_task
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished());
+    if detach_turn {
+        // Close this client's event channel before it stops counting as
+        // attached, so a resuming client finds the turn without an owner.
+        event_handle.abort();
+        let _ = (&mut event_handle).await;
+    }
 
     {
         let mut debug_state = client_debug_state.write().await;
@@ -95,6 +160,27 @@ pub(super) async fn cleanup_client_connection(
     // reclaim the same session without tripping duplicate-attach guards.
     tokio::task::yield_now().await;
 
+    if detach_turn {
+        crate::logging::info(&format!(
+            "Client of session {} disconnected mid-turn; letting the turn finish",
+            client_session_id
+        ));
+        if let Some(done) = processing_done_rx.recv().await {
+            finish_detached_turn(
+                client_session_id,
+                done,
+                swarm_members,
+                swarms_by_id,
+                event_history,
+                event_counter,
+                swarm_event_tx,
+            )
+            .await;
+        }
+        processing_task.take();
+        disposition = DisconnectDisposition::Closed;
+    }
+
     let successor_connected =
         session_has_live_successor(client_connections, client_session_id).await;
     if successor_connected {
@@ -213,6 +299,7 @@ pub(super) async fn cleanup_client_connection(
         };
         crate::session_metrics::forget(client_session_id);
         crate::session_effort::forget_session_effort(client_session_id);
+        crate::tool::discard_pending_rename(client_session_id);
 
         if let Some(ref swarm_id) = swarm_id {
             record_swarm_event(
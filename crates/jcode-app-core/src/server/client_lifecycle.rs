@@ -1,8 +1,11 @@
 use super::client_actions::{
-    AgentTaskContext, NotifySessionContext, handle_agent_task, handle_compact, handle_input_shell,
-    handle_notify_session, handle_rename_session, handle_run_subagent, handle_set_feature,
-    handle_set_subagent_model, handle_split, handle_stdin_response, handle_transfer,
-    handle_trigger_memory_extraction,
+    AgentTaskContext, NotifySessionContext, handle_agent_task, handle_aside, handle_compact,
+    handle_compare, handle_environment, handle_handoff, handle_input_shell, handle_notify_session,
+    handle_permission_decision, handle_plan_decision, handle_prompt_sections,
+    handle_rename_session, handle_run_subagent, handle_set_approval_gate, handle_set_feature,
+    handle_set_profile, handle_set_safe_mode, handle_set_subagent_model, handle_set_tool_scope,
+    handle_split, handle_stdin_response, handle_transfer, handle_trigger_memory_extraction,
+    handle_update_workspace_roots, spawn_turn_post_mortem,
 };
 use super::client_comm::{
     handle_comm_channel_members, handle_comm_list, handle_comm_list_channels, handle_comm_message,
@@ -44,6 +47,7 @@ use super::provider_control::{
     handle_switch_anthropic_account, handle_switch_openai_account,
     try_available_models_updated_event,
 };
+use super::turn_relay::turn_relay;
 use super::{
     AwaitMembersRuntime, ClientConnectionInfo, ClientDebugState, FileTouchService,
     SessionControlHandle, SessionInterruptQueues, SharedContext, SwarmEvent, SwarmMember,
@@ -60,7 +64,7 @@ use crate::tool::Registry;
 use crate::transport::Stream;
 use anyhow::Result;
 use futures::FutureExt;
-use jcode_agent_runtime::{InterruptSignal, SoftInterruptSource, StreamError};
+use jcode_agent_runtime::{InterruptSignal, SoftInterruptQueue, SoftInterruptSource, StreamError};
 use std::collections::{HashMap, HashSet};
 use std::sync::{
     Arc,
@@ -80,6 +84,7 @@ struct ProcessingMessage {
     content: String,
     images: Vec<(String, String)>,
     system_reminder: Option<String>,
+    focus_secs: Option<u64>,
 }
 
 struct ProcessingState<'a> {
@@ -239,6 +244,7 @@ fn compaction_server_event(event: crate::compaction::CompactionEvent) -> ServerE
         messages_compacted: event.messages_compacted,
         summary_chars: event.summary_chars,
         active_messages: event.active_messages,
+        tool_results_trimmed: event.tool_results_trimmed,
     }
 }
 
@@ -412,6 +418,9 @@ pub(super) async fn handle_client(
     let mut current_client_instance_id: Option<String> = None;
     // Client selfdev status is determined by Subscribe request, not server's env
     let mut client_selfdev = false;
+    // Agent this connection turned the approval gate on for. The gate is
+    // cleared when the connection ends so it does not outlive its client.
+    let mut approval_gate_agent: Option<Arc<Mutex<Agent>>> = None;
 
     let client_start = std::time::Instant::now();
 
@@ -707,13 +716,39 @@ pub(super) async fn handle_client(
                                     crate::telemetry::record_error(crate::telemetry::ErrorCategory::AuthFailed);
                                 }
                             }
+                            let message = crate::util::format_error_chain(&e);
                             let _ = client_event_tx.send(ServerEvent::Error {
                                 id: done_id,
-                                message: crate::util::format_error_chain(&e),
+                                message: message.clone(),
                                 retry_after_secs,
                             });
+                            // Cancelled turns end through `Interrupted`, not
+                            // here, so they never get a post-mortem.
+                            spawn_turn_post_mortem(message, &agent, &client_event_tx).await;
                         }
                     }
+                    start_next_queued_message(
+                        &client_session_id,
+                        &mut ProcessingState {
+                            client_is_processing: &mut client_is_processing,
+                            message_id: &mut processing_message_id,
+                            session_id: &mut processing_session_id,
+                            task: &mut processing_task,
+                        },
+                        &client_connections,
+                        &client_connection_id,
+                        &agent,
+                        &client_event_tx,
+                        &processing_done_tx,
+                        &SwarmStatusRefs {
+                            members: &swarm_members,
+                            swarms_by_id: &swarms_by_id,
+                            event_history: &event_history,
+                            event_counter: &event_counter,
+                            event_tx: &swarm_event_tx,
+                        },
+                    )
+                    .await;
                 } else {
                     break;
                 }
@@ -791,6 +826,30 @@ pub(super) async fn handle_client(
                             });
                         }
                     }
+                    Ok(BusEvent::ClipboardWriteRequested(request)) => {
+                        if request.session_id == client_session_id {
+                            let _ = client_event_tx
+                                .send(ServerEvent::ClipboardWrite { text: request.text });
+                        }
+                    }
+                    Ok(BusEvent::PermissionInboxChanged(change)) => {
+                        let _ = client_event_tx.send(ServerEvent::PermissionInbox {
+                            entries: change.entries,
+                        });
+                    }
+                    Ok(BusEvent::MigrationPending(migration)) => {
+                        let _ = client_event_tx.send(super::migration::event_for(migration));
+                    }
+                    Ok(BusEvent::TurnQueueChanged(change)) => {
+                        if change.session_id == client_session_id {
+                            send_turn_queue_snapshot(
+                                change.session_id,
+                                session_control.soft_interrupt_queue(),
+                                client_event_tx.clone(),
+                                false,
+                            );
+                        }
+                    }
                     Ok(BusEvent::CompactionFinished) => {
                         let agent = Arc::clone(&agent);
                         let tx = client_event_tx.clone();
@@ -1015,6 +1074,7 @@ pub(super) async fn handle_client(
                 content,
                 images,
                 system_reminder,
+                focus_secs,
             } => {
                 if !client_is_processing {
                     let mut connections = client_connections.write().await;
@@ -1029,6 +1089,7 @@ pub(super) async fn handle_client(
                         content,
                         images,
                         system_reminder,
+                        focus_secs,
                     },
                     &client_session_id,
                     &mut ProcessingState {
@@ -1100,10 +1161,97 @@ pub(super) async fn handle_client(
                 clear_soft_interrupts(id, &client_session_id, &session_control, &client_event_tx);
             }
 
+            Request::QueueMessage {
+                id,
+                content,
+                images,
+                system_reminder,
+            } => {
+                if let Err(err) = super::turn_queue::push_message(
+                    &client_session_id,
+                    id,
+                    content,
+                    images,
+                    system_reminder,
+                ) {
+                    let _ = client_event_tx.send(ServerEvent::Error {
+                        id,
+                        message: format!("Failed to queue message: {:#}", err),
+                        retry_after_secs: None,
+                    });
+                    continue;
+                }
+                let _ = client_event_tx.send(ServerEvent::Ack { id });
+                start_next_queued_message(
+                    &client_session_id,
+                    &mut ProcessingState {
+                        client_is_processing: &mut client_is_processing,
+                        message_id: &mut processing_message_id,
+                        session_id: &mut processing_session_id,
+                        task: &mut processing_task,
+                    },
+                    &client_connections,
+                    &client_connection_id,
+                    &agent,
+                    &client_event_tx,
+                    &processing_done_tx,
+                    &SwarmStatusRefs {
+                        members: &swarm_members,
+                        swarms_by_id: &swarms_by_id,
+                        event_history: &event_history,
+                        event_counter: &event_counter,
+                        event_tx: &swarm_event_tx,
+                    },
+                )
+                .await;
+            }
+
+            Request::TurnQueue { id } => {
+                let _ = client_event_tx.send(ServerEvent::Ack { id });
+                send_turn_queue_snapshot(
+                    client_session_id.clone(),
+                    session_control.soft_interrupt_queue(),
+                    client_event_tx.clone(),
+                    false,
+                );
+            }
+
+            Request::TurnQueueRemove { id, item_id } => {
+                let result = super::turn_queue::remove(
+                    &client_session_id,
+                    &session_control.soft_interrupt_queue(),
+                    &item_id,
+                );
+                finish_turn_queue_edit(id, result, &session_control, &client_event_tx);
+            }
+
+            Request::TurnQueueMove {
+                id,
+                item_id,
+                position,
+            } => {
+                let result = super::turn_queue::move_item(
+                    &client_session_id,
+                    &session_control.soft_interrupt_queue(),
+                    &item_id,
+                    position,
+                );
+                finish_turn_queue_edit(id, result, &session_control, &client_event_tx);
+            }
+
             Request::BackgroundTool { id } => {
                 move_tool_to_background(id, &session_control, &client_event_tx);
             }
 
+            Request::CancelMcpCall { id } => {
+                let cancelled = crate::mcp::cancel_session_calls(&session_control.session_id);
+                crate::logging::info(&format!(
+                    "SERVER_CANCEL_MCP_CALL id={} session={} cancelled={}",
+                    id, session_control.session_id, cancelled
+                ));
+                let _ = client_event_tx.send(ServerEvent::Ack { id });
+            }
+
             Request::Clear { id } => {
                 if reject_if_agent_busy_for_request(
                     id,
@@ -1435,6 +1583,21 @@ pub(super) async fn handle_client(
                         last_available_models_snapshot = Some(snapshot);
                     }
                 }
+                // A client that attaches while decisions are pending sees them
+                // right away rather than on the next inbox change.
+                let inbox = super::permission_inbox::snapshot();
+                if !inbox.is_empty() {
+                    let _ = client_event_tx.send(ServerEvent::PermissionInbox { entries: inbox });
+                }
+                if let Some(event) = super::migration::pending_event() {
+                    let _ = client_event_tx.send(event);
+                }
+                send_turn_queue_snapshot(
+                    client_session_id.clone(),
+                    session_control.soft_interrupt_queue(),
+                    client_event_tx.clone(),
+                    true,
+                );
                 client_subscribed = true;
             }
 
@@ -1572,6 +1735,33 @@ pub(super) async fn handle_client(
                 if let Some(snapshot) = try_available_models_snapshot(&agent) {
                     last_available_models_snapshot = Some(snapshot);
                 }
+                // Messages queued before a reload run once the session is
+                // idle; a turn still live on another connection keeps the
+                // agent locked and drains the queue itself.
+                if agent.try_lock().is_ok() {
+                    start_next_queued_message(
+                        &client_session_id,
+                        &mut ProcessingState {
+                            client_is_processing: &mut client_is_processing,
+                            message_id: &mut processing_message_id,
+                            session_id: &mut processing_session_id,
+                            task: &mut processing_task,
+                        },
+                        &client_connections,
+                        &client_connection_id,
+                        &agent,
+                        &client_event_tx,
+                        &processing_done_tx,
+                        &SwarmStatusRefs {
+                            members: &swarm_members,
+                            swarms_by_id: &swarms_by_id,
+                            event_history: &event_history,
+                            event_counter: &event_counter,
+                            event_tx: &swarm_event_tx,
+                        },
+                    )
+                    .await;
+                }
             }
 
             Request::ResumeAllSessions { id } => {
@@ -1622,6 +1812,19 @@ pub(super) async fn handle_client(
                 handle_set_subagent_model(id, model, &agent, &client_event_tx).await;
             }
 
+            Request::SetProfile { id, profile } => {
+                handle_set_profile(id, profile, &agent, &client_event_tx);
+            }
+
+            Request::SetSafeMode { id, enabled } => {
+                handle_set_safe_mode(id, enabled, &agent, &client_event_tx);
+            }
+
+            Request::SetApprovalGate { id, enabled } => {
+                approval_gate_agent = enabled.then(|| Arc::clone(&agent));
+                handle_set_approval_gate(id, enabled, &agent, &client_event_tx);
+            }
+
             Request::RunSubagent {
                 id,
                 prompt,
@@ -1674,6 +1877,80 @@ pub(super) async fn handle_client(
                 handle_set_compaction_mode(id, mode, &agent, &client_event_tx).await;
             }
 
+            Request::UpdateWorkspaceRoots { id, add, remove } => {
+                if (!add.is_empty() || !remove.is_empty())
+                    && reject_if_agent_busy_for_request(
+                        id,
+                        "update_workspace_roots",
+                        &client_session_id,
+                        client_is_processing,
+                        &agent,
+                        &client_event_tx,
+                    )
+                {
+                    continue;
+                }
+                handle_update_workspace_roots(id, add, remove, &agent, &client_event_tx).await;
+            }
+
+            Request::SetToolScope { id, path } => {
+                if reject_if_agent_busy_for_request(
+                    id,
+                    "set_tool_scope",
+                    &client_session_id,
+                    client_is_processing,
+                    &agent,
+                    &client_event_tx,
+                ) {
+                    continue;
+                }
+                handle_set_tool_scope(id, path, &agent, &client_event_tx).await;
+            }
+
+            Request::SetInstructions { id, text } => {
+                if reject_if_agent_busy_for_request(
+                    id,
+                    "set_instructions",
+                    &client_session_id,
+                    client_is_processing,
+                    &agent,
+                    &client_event_tx,
+                ) {
+                    continue;
+                }
+                let saved = agent.lock().await.set_instructions(text);
+                let _ = client_event_tx.send(match saved {
+                    Ok(()) => ServerEvent::Ack { id },
+                    Err(err) => ServerEvent::Error {
+                        id,
+                        message: crate::util::format_error_chain(&err),
+                        retry_after_secs: None,
+                    },
+                });
+            }
+
+            Request::SetMirror { id, path } => {
+                if reject_if_agent_busy_for_request(
+                    id,
+                    "set_mirror",
+                    &client_session_id,
+                    client_is_processing,
+                    &agent,
+                    &client_event_tx,
+                ) {
+                    continue;
+                }
+                let saved = agent.lock().await.set_mirror_path(path);
+                let _ = client_event_tx.send(match saved {
+                    Ok(()) => ServerEvent::Ack { id },
+                    Err(err) => ServerEvent::Error {
+                        id,
+                        message: crate::util::format_error_chain(&err),
+                        retry_after_secs: None,
+                    },
+                });
+            }
+
             Request::RenameSession { id, title } => {
                 if reject_if_agent_busy_for_request(
                     id,
@@ -1757,6 +2034,74 @@ pub(super) async fn handle_client(
                 .await;
             }
 
+            Request::PlanDecision {
+                id,
+                decision,
+                feedback,
+            } => {
+                if reject_if_agent_busy_for_request(
+                    id,
+                    "plan_decision",
+                    &client_session_id,
+                    client_is_processing,
+                    &agent,
+                    &client_event_tx,
+                ) {
+                    continue;
+                }
+                handle_plan_decision(id, decision, feedback, &agent, &client_event_tx).await;
+            }
+
+            Request::Aside { id, action } => {
+                if reject_if_agent_busy_for_request(
+                    id,
+                    "aside",
+                    &client_session_id,
+                    client_is_processing,
+                    &agent,
+                    &client_event_tx,
+                ) {
+                    continue;
+                }
+                handle_aside(id, action, &agent, &client_event_tx).await;
+            }
+
+            Request::Compare { id, action } => {
+                if reject_if_agent_busy_for_request(
+                    id,
+                    "compare",
+                    &client_session_id,
+                    client_is_processing,
+                    &agent,
+                    &client_event_tx,
+                ) {
+                    continue;
+                }
+                handle_compare(id, action, &agent, &client_event_tx).await;
+            }
+
+            Request::Handoff { id, action } => {
+                if reject_if_agent_busy_for_request(
+                    id,
+                    "handoff",
+                    &client_session_id,
+                    client_is_processing,
+                    &agent,
+                    &client_event_tx,
+                ) {
+                    continue;
+                }
+                handle_handoff(id, action, &agent, &client_event_tx).await;
+            }
+
+            Request::Environment { id, refresh } => {
+                handle_environment(id, refresh, &client_event_tx);
+            }
+
+            Request::PromptSections { id } => {
+                handle_prompt_sections(id, &agent, &client_event_tx);
+            }
+
             Request::Split { id } => {
                 handle_split(id, &client_session_id, &client_event_tx).await;
             }
@@ -1807,6 +2152,15 @@ pub(super) async fn handle_client(
                     .await;
             }
 
+            Request::PermissionDecision {
+                id,
+                request_id,
+                approved,
+                message,
+            } => {
+                handle_permission_decision(id, &request_id, approved, message, &client_event_tx);
+            }
+
             Request::AgentTask { id, task, .. } => {
                 handle_agent_task(
                     id,
@@ -2567,6 +2921,19 @@ pub(super) async fn handle_client(
             Request::ClientDebugResponse { id, output } => {
                 handle_client_debug_response(id, output, &client_debug_response_tx);
             }
+            // `Request` is non-exhaustive so embedders can depend on the protocol crate
+            // without breaking on additions; anything not routed above is unsupported.
+            // Test builds make it exhaustive and drop this arm, so an unrouted
+            // variant fails to compile there.
+            #[cfg(not(test))]
+            #[allow(unreachable_patterns)]
+            _ => {
+                let _ = client_event_tx.send(ServerEvent::Error {
+                    id: request_id,
+                    message: format!("Unsupported request: {request_kind}"),
+                    retry_after_secs: None,
+                });
+            }
         }
         if request_lifecycle_logged {
             log_request_lifecycle_handled(
@@ -2588,11 +2955,18 @@ pub(super) async fn handle_client(
         }
     }
 
+    if let Some(agent) = approval_gate_agent.take() {
+        tokio::spawn(async move {
+            agent.lock().await.set_approval_gate(false);
+        });
+    }
+
     cleanup_client_connection(
         &sessions,
         &client_session_id,
         client_is_processing,
         &mut processing_task,
+        &mut processing_done_rx,
         event_handle,
         &swarm_members,
         &swarms_by_id,
@@ -2629,6 +3003,7 @@ async fn start_processing_message(
         content,
         images,
         system_reminder,
+        focus_secs,
     } = message;
     if server_reload_starting() {
         crate::logging::info(&format!(
@@ -2683,16 +3058,18 @@ async fn start_processing_message(
     };
     let agent = Arc::clone(agent);
     let report_agent = Arc::clone(&agent);
-    let tx = client_event_tx.clone();
+    // Relay the turn's events so they reach a resumed client if this one
+    // goes away before the turn finishes.
+    let (event_tx, relay) = turn_relay(client_session_id.to_string(), client_event_tx.clone());
     let done_tx = processing_done_tx.clone();
     crate::logging::info(&format!("Processing message id={} spawning task", id));
     *state.task = Some(tokio::spawn(async move {
-        let event_tx = tx.clone();
-        let result = match std::panic::AssertUnwindSafe(process_message_streaming_mpsc(
+        let result = match std::panic::AssertUnwindSafe(process_focus_message_streaming_mpsc(
             agent,
             &content,
             images,
             system_reminder,
+            focus_secs.filter(|secs| *secs > 0).map(Duration::from_secs),
             event_tx,
         ))
         .catch_unwind()
@@ -2724,6 +3101,7 @@ async fn start_processing_message(
                 id, error
             )),
         }
+        relay.finish().await;
         let completion_report = if result.is_ok() {
             let agent = report_agent.lock().await;
             agent.latest_assistant_text_after(start_message_index)
@@ -2734,6 +3112,104 @@ async fn start_processing_message(
     }));
 }
 
+/// Start the session's next `queue_message` turn if this connection is idle.
+/// The client is told first so it shows the message and adopts the turn.
+/// Messages queued before a server reload wait on disk until a client
+/// resumes the session.
+#[allow(clippy::too_many_arguments)]
+async fn start_next_queued_message(
+    client_session_id: &str,
+    state: &mut ProcessingState<'_>,
+    client_connections: &Arc<RwLock<HashMap<String, ClientConnectionInfo>>>,
+    client_connection_id: &str,
+    agent: &Arc<Mutex<Agent>>,
+    client_event_tx: &mpsc::UnboundedSender<ServerEvent>,
+    processing_done_tx: &mpsc::UnboundedSender<(u64, Result<()>, Option<String>)>,
+    swarm: &SwarmStatusRefs<'_>,
+) {
+    if *state.client_is_processing || server_reload_starting() {
+        return;
+    }
+    let Some(message) = super::turn_queue::pop_message(client_session_id) else {
+        return;
+    };
+    {
+        let mut connections = client_connections.write().await;
+        if let Some(info) = connections.get_mut(client_connection_id) {
+            info.is_processing = true;
+            info.current_tool_name = None;
+        }
+    }
+    let _ = client_event_tx.send(ServerEvent::QueuedTurnStarted {
+        id: message.id,
+        content: message.content.clone(),
+        images: message.images.clone(),
+    });
+    start_processing_message(
+        ProcessingMessage {
+            id: message.id,
+            content: message.content,
+            images: message.images,
+            system_reminder: message.system_reminder,
+            focus_secs: None,
+        },
+        client_session_id,
+        state,
+        agent,
+        client_event_tx,
+        processing_done_tx,
+        swarm,
+    )
+    .await;
+}
+
+/// Send the session's turn queue to the client. Scheduled follow-ups are read
+/// from disk, so the snapshot is built off the connection task.
+fn send_turn_queue_snapshot(
+    session_id: String,
+    soft_interrupts: SoftInterruptQueue,
+    client_event_tx: mpsc::UnboundedSender<ServerEvent>,
+    skip_empty: bool,
+) {
+    tokio::spawn(async move {
+        let Ok(items) = tokio::task::spawn_blocking(move || {
+            super::turn_queue::snapshot(&session_id, &soft_interrupts)
+        })
+        .await
+        else {
+            return;
+        };
+        if !(skip_empty && items.is_empty()) {
+            let _ = client_event_tx.send(ServerEvent::TurnQueue { items });
+        }
+    });
+}
+
+/// Acknowledge a turn queue edit. A successful edit publishes the new queue
+/// on the bus; a failed one (the item already ran or was removed elsewhere)
+/// resends the current queue so the client can correct its view. Failures are
+/// not sent as errors, which clients treat as a failed turn.
+fn finish_turn_queue_edit(
+    id: u64,
+    result: Result<(), String>,
+    session_control: &SessionControlHandle,
+    client_event_tx: &mpsc::UnboundedSender<ServerEvent>,
+) {
+    let _ = client_event_tx.send(ServerEvent::Ack { id });
+    if let Err(error) = result {
+        crate::logging::info(&format!(
+            "TURN_QUEUE_EDIT_FAILED id={} session={} error={}",
+            id, session_control.session_id, error
+        ));
+        send_turn_queue_snapshot(
+            session_control.session_id.clone(),
+            session_control.soft_interrupt_queue(),
+            client_event_tx.clone(),
+            false,
+        );
+    }
+}
+
 async fn cancel_processing_message(
     state: &mut ProcessingState<'_>,
     session_control: &SessionControlHandle,
@@ -2989,9 +3465,26 @@ pub(super) async fn process_message_streaming_mpsc(
     images: Vec<(String, String)>,
     system_reminder: Option<String>,
     event_tx: tokio::sync::mpsc::UnboundedSender<ServerEvent>,
+) -> Result<()> {
+    process_focus_message_streaming_mpsc(agent, content, images, system_reminder, None, event_tx)
+        .await
+}
+
+/// Like [`process_message_streaming_mpsc`], with an optional `/focus` budget
+/// for the turn.
+pub(super) async fn process_focus_message_streaming_mpsc(
+    agent: Arc<Mutex<Agent>>,
+    content: &str,
+    images: Vec<(String, String)>,
+    system_reminder: Option<String>,
+    focus: Option<Duration>,
+    event_tx: tokio::sync::mpsc::UnboundedSender<ServerEvent>,
 ) -> Result<()> {
     let mut agent = agent.lock().await;
     let session_id = agent.session_id().to_string();
+    if let Some(budget) = focus {
+        agent.set_turn_focus(budget, content);
+    }
     let result = agent
         .run_once_streaming_mpsc(content, images, system_reminder, event_tx)
         .await;
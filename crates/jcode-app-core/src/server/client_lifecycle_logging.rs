@@ -30,6 +30,7 @@ pub(super) fn interrupt_request_log_fields(
         )),
         Request::CancelSoftInterrupts { id } => Some(base("cancel_soft_interrupts", *id)),
         Request::BackgroundTool { id } => Some(base("background_tool", *id)),
+        Request::CancelMcpCall { id } => Some(base("cancel_mcp_call", *id)),
         _ => None,
     }
 }
@@ -65,6 +66,7 @@ pub(super) fn request_type_is_read_only(kind: &str) -> bool {
             | "comm_plan_status"
             | "comm_read_context"
             | "comm_await_members"
+            | "turn_queue"
     )
 }
 
@@ -2,7 +2,7 @@ use super::*;
 use crate::message::{Message, StreamEvent, ToolDefinition};
 use crate::provider::{EventStream, Provider};
 use async_trait::async_trait;
-use futures::stream;
+use futures::{StreamExt, stream};
 use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 
@@ -791,6 +791,397 @@ async fn lightweight_comm_request_skips_full_session_initialization() {
     );
 }
 
+/// Streams "Hello " and holds the rest of its first answer until released.
+#[derive(Clone)]
+struct GatedStreamProvider {
+    release: Arc<tokio::sync::Notify>,
+    calls: Arc<std::sync::atomic::AtomicUsize>,
+}
+
+#[async_trait]
+impl Provider for GatedStreamProvider {
+    async fn complete(
+        &self,
+        _messages: &[Message],
+        _tools: &[ToolDefinition],
+        _system: &str,
+        _resume_session_id: Option<&str>,
+    ) -> Result<EventStream> {
+        if self.calls.fetch_add(1, Ordering::SeqCst) > 0 {
+            return Ok(Box::pin(stream::iter(vec![Ok(StreamEvent::MessageEnd {
+                stop_reason: None,
+            })])));
+        }
+        let release = Arc::clone(&self.release);
+        let rest = stream::once(async move {
+            release.notified().await;
+            Ok(StreamEvent::TextDelta("world".to_string()))
+        });
+        Ok(Box::pin(
+            stream::iter(vec![Ok(StreamEvent::TextDelta("Hello ".to_string()))])
+                .chain(rest)
+                .chain(stream::iter(vec![Ok(StreamEvent::MessageEnd {
+                    stop_reason: None,
+                })])),
+        ))
+    }
+
+    fn name(&self) -> &str {
+        "gated-stream"
+    }
+
+    fn fork(&self) -> Arc<dyn Provider> {
+        Arc::new(self.clone())
+    }
+}
+
+async fn write_test_request(writer: &mut (impl AsyncWriteExt + Unpin), request: &Request) {
+    let payload = serde_json::to_string(request).expect("serialize request") + "\n";
+    writer
+        .write_all(payload.as_bytes())
+        .await
+        .expect("write request");
+}
+
+async fn read_test_event(reader: &mut (impl AsyncBufReadExt + Unpin)) -> ServerEvent {
+    let mut line = String::new();
+    let read = tokio::time::timeout(
+        std::time::Duration::from_secs(10),
+        reader.read_line(&mut line),
+    )
+    .await
+    .expect("server event in time")
+    .expect("read server event");
+    assert!(read > 0, "server closed the connection");
+    decode_request_or_event(&line)
+}
+
+#[test]
+fn client_killed_mid_stream_loses_nothing_and_resume_reattaches() {
+    let _lock = crate::storage::lock_test_env();
+    let _env = IsolatedReloadRecoveryEnv::new();
+    let rt = tokio::runtime::Runtime::new().expect("runtime");
+    rt.block_on(async {
+        let release = Arc::new(tokio::sync::Notify::new());
+        let provider_template: Arc<dyn Provider> = Arc::new(GatedStreamProvider {
+            release: Arc::clone(&release),
+            calls: Arc::new(std::sync::atomic::AtomicUsize::new(0)),
+        });
+
+        let sessions: SessionAgents = Arc::new(RwLock::new(HashMap::new()));
+        let global_session_id = Arc::new(RwLock::new(String::new()));
+        let client_count = Arc::new(RwLock::new(0usize));
+        let client_connections = Arc::new(RwLock::new(HashMap::new()));
+        let swarm_members = Arc::new(RwLock::new(HashMap::new()));
+        let swarms_by_id = Arc::new(RwLock::new(HashMap::new()));
+        let shared_context = Arc::new(RwLock::new(HashMap::new()));
+        let swarm_plans = Arc::new(RwLock::new(HashMap::new()));
+        let swarm_coordinators = Arc::new(RwLock::new(HashMap::new()));
+        let file_touch = FileTouchService::new();
+        let channel_subscriptions = Arc::new(RwLock::new(HashMap::new()));
+        let channel_subscriptions_by_session = Arc::new(RwLock::new(HashMap::new()));
+        let client_debug_state = Arc::new(RwLock::new(ClientDebugState::default()));
+        let (debug_response_tx, _) = broadcast::channel(8);
+        let event_history = Arc::new(RwLock::new(std::collections::VecDeque::new()));
+        let event_counter = Arc::new(std::sync::atomic::AtomicU64::new(0));
+        let (swarm_event_tx, _) = broadcast::channel(8);
+        let (global_event_tx, _) = broadcast::channel(8);
+        let global_is_processing = Arc::new(RwLock::new(false));
+        let shutdown_signals = Arc::new(RwLock::new(HashMap::new()));
+        let soft_interrupt_queues: SessionInterruptQueues = Arc::new(RwLock::new(HashMap::new()));
+        let mcp_pool = Arc::new(crate::mcp::SharedMcpPool::from_default_config());
+
+        let spawn_client = |stream: crate::transport::Stream| {
+            tokio::spawn(handle_client(
+                stream,
+                Arc::clone(&sessions),
+                global_event_tx.clone(),
+                Arc::clone(&provider_template),
+                Arc::clone(&global_is_processing),
+                Arc::clone(&global_session_id),
+                Arc::clone(&client_count),
+                Arc::clone(&client_connections),
+                Arc::clone(&swarm_members),
+                Arc::clone(&swarms_by_id),
+                Arc::clone(&shared_context),
+                Arc::clone(&swarm_plans),
+                Arc::clone(&swarm_coordinators),
+                file_touch.clone(),
+                Arc::clone(&channel_subscriptions),
+                Arc::clone(&channel_subscriptions_by_session),
+                Arc::clone(&client_debug_state),
+                debug_response_tx.clone(),
+                Arc::clone(&event_history),
+                Arc::clone(&event_counter),
+                swarm_event_tx.clone(),
+                "jcode-test".to_string(),
+                "🧪".to_string(),
+                Arc::clone(&mcp_pool),
+                Arc::clone(&shutdown_signals),
+                Arc::clone(&soft_interrupt_queues),
+                AwaitMembersRuntime::default(),
+                SwarmMutationRuntime::default(),
+            ))
+        };
+
+        // First client subscribes, starts a turn and dies mid-stream.
+        let (server_stream, client_stream) = crate::transport::Stream::pair().expect("socket pair");
+        let first_task = spawn_client(server_stream);
+        let (reader, mut writer) = client_stream.into_split();
+        let mut reader = BufReader::new(reader);
+        write_test_request(
+            &mut writer,
+            &Request::Subscribe {
+                id: 1,
+                working_dir: None,
+                selfdev: None,
+                target_session_id: None,
+                client_instance_id: None,
+                client_has_local_history: false,
+                allow_session_takeover: false,
+                terminal_env: Vec::new(),
+            },
+        )
+        .await;
+        while !matches!(
+            read_test_event(&mut reader).await,
+            ServerEvent::Done { id: 1 }
+        ) {}
+        write_test_request(
+            &mut writer,
+            &Request::Message {
+                id: 2,
+                content: "say hello".to_string(),
+                images: Vec::new(),
+                system_reminder: None,
+                focus_secs: None,
+            },
+        )
+        .await;
+        loop {
+            if let ServerEvent::TextDelta { text } = read_test_event(&mut reader).await {
+                assert_eq!(text, "Hello ");
+                break;
+            }
+        }
+        let session_id = client_connections
+            .read()
+            .await
+            .values()
+            .next()
+            .expect("first client connection")
+            .session_id
+            .clone();
+        drop(reader);
+        drop(writer);
+        for _ in 0..200 {
+            if client_connections.read().await.is_empty() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert!(
+            client_connections.read().await.is_empty(),
+            "dead client should be unregistered while its turn keeps running"
+        );
+        assert!(
+            sessions.read().await.contains_key(&session_id),
+            "the running turn's session should stay live"
+        );
+
+        // A new client resumes the session mid-turn and gets the rest.
+        let (server_stream, client_stream) = crate::transport::Stream::pair().expect("socket pair");
+        let second_task = spawn_client(server_stream);
+        let (reader, mut writer) = client_stream.into_split();
+        let mut reader = BufReader::new(reader);
+        write_test_request(
+            &mut writer,
+            &Request::ResumeSession {
+                id: 3,
+                session_id: session_id.clone(),
+                client_instance_id: None,
+                client_has_local_history: false,
+                allow_session_takeover: false,
+            },
+        )
+        .await;
+        let mut seen = loop {
+            match read_test_event(&mut reader).await {
+                ServerEvent::ResumeStreaming {
+                    session_id: resumed,
+                    text,
+                    current_tool_name,
+                } => {
+                    assert_eq!(resumed, session_id);
+                    assert_eq!(current_tool_name, None);
+                    break text;
+                }
+                ServerEvent::Error { message, .. } => panic!("resume failed: {message}"),
+                _ => {}
+            }
+        };
+        assert_eq!(seen, "Hello ");
+
+        release.notify_one();
+        loop {
+            match read_test_event(&mut reader).await {
+                ServerEvent::TextDelta { text } => seen.push_str(&text),
+                ServerEvent::Done { id: 0 } => break,
+                ServerEvent::Error { message, .. } => panic!("turn failed: {message}"),
+                _ => {}
+            }
+        }
+        assert_eq!(seen, "Hello world");
+
+        let agent = Arc::clone(
+            sessions
+                .read()
+                .await
+                .get(&session_id)
+                .expect("resumed session stays live"),
+        );
+        assert_eq!(
+            agent.lock().await.latest_assistant_text_after(0).as_deref(),
+            Some("Hello world")
+        );
+        let saved = crate::session::Session::load(&session_id).expect("session saved");
+        let saved_answer = saved
+            .messages
+            .iter()
+            .filter(|message| matches!(message.role, crate::message::Role::Assistant))
+            .flat_map(|message| &message.content)
+            .any(|block| {
+                matches!(
+                    block,
+                    crate::message::ContentBlock::Text { text, .. } if text == "Hello world"
+                )
+            });
+        assert!(
+            saved_answer,
+            "the finished answer should be persisted to the session"
+        );
+
+        first_task
+            .await
+            .expect("first client task join")
+            .expect("first client task result");
+        drop(reader);
+        drop(writer);
+        second_task
+            .await
+            .expect("second client task join")
+            .expect("second client task result");
+    });
+}
+
+#[test]
+fn approval_gate_is_cleared_when_its_connection_drops() {
+    let _lock = crate::storage::lock_test_env();
+    let _env = IsolatedReloadRecoveryEnv::new();
+    let rt = tokio::runtime::Runtime::new().expect("runtime");
+    rt.block_on(async {
+        let provider_template: Arc<dyn Provider> = Arc::new(GatedStreamProvider {
+            release: Arc::new(tokio::sync::Notify::new()),
+            calls: Arc::new(std::sync::atomic::AtomicUsize::new(0)),
+        });
+        let sessions: SessionAgents = Arc::new(RwLock::new(HashMap::new()));
+        let client_connections = Arc::new(RwLock::new(HashMap::new()));
+        let (debug_response_tx, _) = broadcast::channel(8);
+        let (swarm_event_tx, _) = broadcast::channel(8);
+        let (global_event_tx, _) = broadcast::channel(8);
+
+        let (server_stream, client_stream) = crate::transport::Stream::pair().expect("socket pair");
+        let server_task = tokio::spawn(handle_client(
+            server_stream,
+            Arc::clone(&sessions),
+            global_event_tx,
+            provider_template,
+            Arc::new(RwLock::new(false)),
+            Arc::new(RwLock::new(String::new())),
+            Arc::new(RwLock::new(0usize)),
+            Arc::clone(&client_connections),
+            Arc::new(RwLock::new(HashMap::new())),
+            Arc::new(RwLock::new(HashMap::new())),
+            Arc::new(RwLock::new(HashMap::new())),
+            Arc::new(RwLock::new(HashMap::new())),
+            Arc::new(RwLock::new(HashMap::new())),
+            FileTouchService::new(),
+            Arc::new(RwLock::new(HashMap::new())),
+            Arc::new(RwLock::new(HashMap::new())),
+            Arc::new(RwLock::new(ClientDebugState::default())),
+            debug_response_tx,
+            Arc::new(RwLock::new(std::collections::VecDeque::new())),
+            Arc::new(std::sync::atomic::AtomicU64::new(0)),
+            swarm_event_tx,
+            "jcode-test".to_string(),
+            "🧪".to_string(),
+            Arc::new(crate::mcp::SharedMcpPool::from_default_config()),
+            Arc::new(RwLock::new(HashMap::new())),
+            Arc::new(RwLock::new(HashMap::new())),
+            AwaitMembersRuntime::default(),
+            SwarmMutationRuntime::default(),
+        ));
+        let (reader, mut writer) = client_stream.into_split();
+        let mut reader = BufReader::new(reader);
+        write_test_request(
+            &mut writer,
+            &Request::Subscribe {
+                id: 1,
+                working_dir: None,
+                selfdev: None,
+                target_session_id: None,
+                client_instance_id: None,
+                client_has_local_history: false,
+                allow_session_takeover: false,
+                terminal_env: Vec::new(),
+            },
+        )
+        .await;
+        while !matches!(
+            read_test_event(&mut reader).await,
+            ServerEvent::Done { id: 1 }
+        ) {}
+        write_test_request(
+            &mut writer,
+            &Request::SetApprovalGate {
+                id: 2,
+                enabled: true,
+            },
+        )
+        .await;
+        while !matches!(
+            read_test_event(&mut reader).await,
+            ServerEvent::Done { id: 2 }
+        ) {}
+        let agent = Arc::clone(
+            sessions
+                .read()
+                .await
+                .values()
+                .next()
+                .expect("subscribed session"),
+        );
+        assert!(agent.lock().await.approval_gate());
+
+        drop(reader);
+        drop(writer);
+        server_task
+            .await
+            .expect("server task join")
+            .expect("server task result");
+        for _ in 0..200 {
+            if !agent.lock().await.approval_gate() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert!(
+            !agent.lock().await.approval_gate(),
+            "the gate should not outlive the connection that set it"
+        );
+    });
+}
+
 fn decode_request_or_event(line: &str) -> ServerEvent {
     serde_json::from_str(line.trim()).expect("decode server event")
 }
@@ -1,6 +1,7 @@
 #![cfg_attr(test, allow(clippy::await_holding_lock))]
 
 use super::client_state::{handle_get_history, spawn_model_prefetch_update};
+use super::turn_relay::attach_to_live_turn;
 use super::{
     ClientConnectionInfo, ClientDebugState, FileTouchService, SessionInterruptQueues, SwarmEvent,
     SwarmMember, SwarmState, VersionedPlan, broadcast_swarm_status, fanout_live_client_event,
@@ -1065,6 +1066,8 @@ pub(super) async fn handle_resume_session(
         )
         .await?;
         let _ = client_event_tx.send(ServerEvent::Done { id });
+        // Pick up a turn whose client crashed mid-stream.
+        let resumed_live_turn = attach_to_live_turn(&session_id, client_event_tx.clone());
         registry
             .register_mcp_tools(
                 Some(client_event_tx.clone()),
@@ -1082,6 +1085,7 @@ pub(super) async fn handle_resume_session(
                 ("target_session_id", session_id.clone()),
                 ("client_connection_id", client_connection_id.to_string()),
                 ("live_target_busy", live_target_busy.to_string()),
+                ("resumed_live_turn", resumed_live_turn.to_string()),
                 ("elapsed_ms", resume_start.elapsed().as_millis().to_string()),
             ],
         );
@@ -1218,14 +1222,17 @@ pub(super) async fn handle_resume_session(
         agent_guard.mark_closed();
     }
 
-    let (result, is_canary) = {
+    let (result, is_canary, route_fallback) = {
         let mut agent_guard = agent.lock().await;
         let result = agent_guard.restore_session(&session_id);
         if *client_selfdev {
             agent_guard.set_canary("self-dev");
         }
         let is_canary = agent_guard.is_canary();
-        (result, is_canary)
+        let route_fallback = agent_guard
+            .take_model_route_fallback()
+            .map(|fallback| (fallback, agent_guard.provider_name()));
+        (result, is_canary, route_fallback)
     };
 
     let was_interrupted = match &result {
@@ -1352,6 +1359,20 @@ pub(super) async fn handle_resume_session(
                 Some(was_interrupted),
             )
             .await?;
+            if let Some((fallback, provider_name)) = route_fallback {
+                crate::logging::warn(&format!(
+                    "Resumed session {} on a different model route: {}",
+                    session_id,
+                    fallback.summary()
+                ));
+                let _ = client_event_tx.send(ServerEvent::ModelChanged {
+                    id,
+                    model: fallback.effective.model.clone(),
+                    provider_name: Some(provider_name),
+                    error: None,
+                    route_fallback: Some(fallback),
+                });
+            }
             let _ = client_event_tx.send(ServerEvent::Done { id });
             registry
                 .register_mcp_tools(
@@ -105,11 +105,11 @@ async fn handle_clear_session_replaces_runtime_handles_and_updates_shutdown_regi
     old_queue
         .lock()
         .map_err(|_| anyhow!("old queue lock"))?
-        .push(jcode_agent_runtime::SoftInterruptMessage {
-            content: "stale queued message".to_string(),
-            urgent: false,
-            source: jcode_agent_runtime::SoftInterruptSource::User,
-        });
+        .push(jcode_agent_runtime::SoftInterruptMessage::new(
+            "stale queued message".to_string(),
+            false,
+            jcode_agent_runtime::SoftInterruptSource::User,
+        ));
     old_background_signal.fire();
     old_cancel_signal.fire();
 
@@ -251,6 +251,7 @@ pub(super) async fn handle_get_model_catalog(
         status_detail: None,
         upstream_provider: None,
         resolved_credential,
+        model_route: None,
         reasoning_effort: None,
         service_tier: None,
         subagent_model: None,
@@ -361,7 +362,9 @@ fn rendered_to_history_message(msg: crate::session::RenderedMessage) -> HistoryM
             Some(msg.tool_calls)
         },
         tool_data: msg.tool_data,
+        usage: msg.usage,
     }
+    usage: msg.usage,
 }
 
 fn history_reload_recovery_snapshot(
@@ -494,6 +497,7 @@ async fn send_history_from_persisted_session(
                 Some(msg.tool_calls)
             },
             tool_data: msg.tool_data,
+            usage: msg.usage,
         })
         .collect();
     let side_panel = crate::side_panel::snapshot_for_session(session_id).unwrap_or_default();
@@ -536,6 +540,7 @@ async fn send_history_from_persisted_session(
         status_detail: None,
         upstream_provider: None,
         resolved_credential: provider.active_resolved_credential(),
+        model_route: session.model_route.clone(),
         reasoning_effort: session
             .reasoning_effort
             .clone()
@@ -584,6 +589,7 @@ pub(super) async fn send_history(
         tool_names,
         upstream_provider,
         resolved_credential,
+        model_route,
         connection_type,
         status_detail,
         reasoning_effort,
@@ -659,6 +665,7 @@ pub(super) async fn send_history(
             tool_names,
             agent_guard.last_upstream_provider(),
             agent_guard.active_resolved_credential(),
+            agent_guard.current_model_route(),
             agent_guard.last_connection_type(),
             agent_guard.last_status_detail(),
             reasoning_effort,
@@ -752,6 +759,7 @@ pub(super) async fn send_history(
         status_detail,
         upstream_provider,
         resolved_credential,
+        model_route: Some(model_route),
         reasoning_effort,
         service_tier,
         compaction_mode,
@@ -8,7 +8,8 @@ use super::debug_command_exec::{
     DebugInterruptContext, execute_debug_command, resolve_debug_session,
 };
 use super::debug_events::{
-    maybe_handle_event_query_command, maybe_handle_event_subscription_command,
+    maybe_handle_bus_tail_command, maybe_handle_event_query_command,
+    maybe_handle_event_subscription_command,
 };
 use super::debug_help::{debug_help_text, parse_namespaced_command, swarm_debug_help_text};
 use super::debug_jobs::{DebugJob, maybe_handle_job_command};
@@ -510,6 +511,16 @@ pub(super) async fn handle_debug_client(
                             &mut writer,
                         )
                         .await?
+                        {
+                            return Ok(());
+                        } else if maybe_handle_bus_tail_command(
+                            id,
+                            cmd,
+                            requested_session.as_deref(),
+                            &mut reader,
+                            &mut writer,
+                        )
+                        .await?
                         {
                             return Ok(());
                         } else if let Some(output) =
@@ -6,7 +6,7 @@ use crate::agent::Agent;
 use crate::build;
 use crate::mcp::McpConfig;
 use anyhow::Result;
-use jcode_agent_runtime::{InterruptSignal, SoftInterruptSource};
+use jcode_agent_runtime::{InterruptSignal, SoftInterruptQueue, SoftInterruptSource};
 use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 use std::time::Duration;
@@ -43,6 +43,26 @@ impl DebugInterruptContext {
     }
 }
 
+/// Session and soft interrupt queue that `queue:*` commands act on. Prefers
+/// the lock-free queue handle so a running turn does not block the command.
+async fn debug_turn_queue_target(
+    agent: &Arc<Mutex<Agent>>,
+    interrupt_context: Option<&DebugInterruptContext>,
+) -> (String, SoftInterruptQueue) {
+    if let Some(ctx) = interrupt_context
+        && let Some(queue) = ctx
+            .soft_interrupt_queues
+            .read()
+            .await
+            .get(&ctx.session_id)
+            .cloned()
+    {
+        return (ctx.session_id.clone(), queue);
+    }
+    let agent = agent.lock().await;
+    (agent.session_id().to_string(), agent.soft_interrupt_queue())
+}
+
 pub(super) async fn resolve_debug_session(
     sessions: &SessionAgents,
     session_id: &Arc<RwLock<String>>,
@@ -175,6 +195,36 @@ pub(super) async fn execute_debug_command(
         return Ok("queued (urgent)".to_string());
     }
 
+    if trimmed == "queue:list" || trimmed == "queue" {
+        let (session_id, queue) = debug_turn_queue_target(&agent, interrupt_context.as_ref()).await;
+        let items =
+            tokio::task::spawn_blocking(move || super::turn_queue::snapshot(&session_id, &queue))
+                .await?;
+        return Ok(serde_json::to_string_pretty(&items).unwrap_or_else(|_| "[]".to_string()));
+    }
+
+    if let Some(item_id) = trimmed.strip_prefix("queue:remove ") {
+        let (session_id, queue) = debug_turn_queue_target(&agent, interrupt_context.as_ref()).await;
+        super::turn_queue::remove(&session_id, &queue, item_id.trim())
+            .map_err(|err| anyhow::anyhow!(err))?;
+        return Ok(format!("removed {}", item_id.trim()));
+    }
+
+    if let Some(rest) = trimmed.strip_prefix("queue:move ") {
+        let mut parts = rest.split_whitespace();
+        let (Some(item_id), Some(position), None) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(anyhow::anyhow!("usage: queue:move <item_id> <position>"));
+        };
+        let position: usize = position
+            .parse()
+            .map_err(|_| anyhow::anyhow!("queue:move position must be a number"))?;
+        let (session_id, queue) = debug_turn_queue_target(&agent, interrupt_context.as_ref()).await;
+        super::turn_queue::move_item(&session_id, &queue, item_id, position)
+            .map_err(|err| anyhow::anyhow!(err))?;
+        return Ok(format!("moved {} to position {}", item_id, position));
+    }
+
     if trimmed.starts_with("tool:") {
         let raw = trimmed.strip_prefix("tool:").unwrap_or("").trim();
         if raw.is_empty() {
@@ -596,10 +646,11 @@ pub(super) async fn execute_debug_command(
         crate::build::update_shared_server_symlink(&hash)?;
         crate::build::update_canary_symlink(&hash)?;
 
-        let mut manifest = crate::build::BuildManifest::load()?;
-        manifest.canary = Some(hash.clone());
-        manifest.canary_status = Some(crate::build::CanaryStatus::Testing);
-        manifest.save()?;
+        crate::build::BuildManifest::update(|manifest| {
+            manifest.canary = Some(hash.clone());
+            manifest.canary_status = Some(crate::build::CanaryStatus::Testing);
+            Ok(())
+        })?;
 
         let jcode_dir = crate::storage::jcode_dir()?;
         let info_path = jcode_dir.join("reload-info");
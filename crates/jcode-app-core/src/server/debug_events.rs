@@ -1,8 +1,10 @@
 use super::state::MAX_EVENT_HISTORY;
 use super::{SwarmEvent, SwarmEventType};
+use crate::bus::Bus;
+use crate::bus_tap::TappedEvent;
 use anyhow::Result;
 use std::sync::Arc;
-use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
 use tokio::sync::{RwLock, broadcast};
 
 pub(super) async fn maybe_handle_event_query_command(
@@ -58,6 +60,13 @@ pub(super) async fn maybe_handle_event_query_command(
         );
     }
 
+    if cmd == "events:stats" {
+        return Some(
+            serde_json::to_string_pretty(&Bus::global().stats())
+                .unwrap_or_else(|_| "{}".to_string()),
+        );
+    }
+
     if cmd == "events:count" {
         let history = event_history.read().await;
         let latest_id = history.back().map(|event| event.id).unwrap_or(0);
@@ -156,6 +165,150 @@ pub(super) async fn maybe_handle_event_subscription_command<W: AsyncWrite + Unpi
     Ok(true)
 }
 
+/// Filter for `events:tail [--session <id>] [--kinds a,b]`. A kind matches a
+/// bus event kind exactly or as its leading word, so `tool` covers
+/// `tool_updated`.
+#[derive(Debug, Default, PartialEq)]
+struct BusTailFilter {
+    session_id: Option<String>,
+    kinds: Vec<String>,
+}
+
+impl BusTailFilter {
+    fn parse(args: &str) -> Result<Self> {
+        let mut filter = Self::default();
+        let mut words = args.split_whitespace();
+        while let Some(word) = words.next() {
+            let mut value = || {
+                words
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("{} needs a value", word))
+            };
+            match word {
+                "--session" => filter.session_id = Some(value()?.to_string()),
+                "--kinds" => {
+                    filter.kinds = value()?
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|kind| !kind.is_empty())
+                        .map(str::to_string)
+                        .collect();
+                }
+                other => anyhow::bail!(
+                    "unknown events:tail option {:?}; expected --session <id> or --kinds <a,b>",
+                    other
+                ),
+            }
+        }
+        Ok(filter)
+    }
+
+    fn matches(&self, event: &TappedEvent) -> bool {
+        let session_ok = self
+            .session_id
+            .as_deref()
+            .is_none_or(|session_id| event.session_id.as_deref() == Some(session_id));
+        let kind_ok = self.kinds.is_empty()
+            || self.kinds.iter().any(|kind| {
+                event.kind == kind
+                    || event
+                        .kind
+                        .strip_prefix(kind.as_str())
+                        .is_some_and(|rest| rest.starts_with('_'))
+            });
+        session_ok && kind_ok
+    }
+}
+
+fn format_tapped_event(event: &TappedEvent) -> String {
+    format!(
+        "#{} {} session={} {}\n",
+        event.seq,
+        event.kind,
+        event.session_id.as_deref().unwrap_or("-"),
+        event.summary
+    )
+}
+
+/// `events:tail`: stream bus events as text lines until the client hangs up.
+/// Reads from `reader` only to notice the disconnect.
+pub(super) async fn maybe_handle_bus_tail_command<R, W>(
+    id: u64,
+    cmd: &str,
+    requested_session: Option<&str>,
+    reader: &mut R,
+    writer: &mut W,
+) -> Result<bool>
+where
+    R: AsyncBufRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let Some(args) = cmd.strip_prefix("events:tail") else {
+        return Ok(false);
+    };
+    if !(args.is_empty() || args.starts_with(':') || args.starts_with(' ')) {
+        return Ok(false);
+    }
+
+    let filter = BusTailFilter::parse(args.trim_start_matches(':')).map(|mut filter| {
+        if filter.session_id.is_none() {
+            filter.session_id = requested_session.map(str::to_string);
+        }
+        filter
+    });
+    let (ok, output) = match &filter {
+        Ok(filter) => (
+            true,
+            format!(
+                "Tailing bus events (session: {}, kinds: {}). Disconnect to stop.",
+                filter.session_id.as_deref().unwrap_or("any"),
+                if filter.kinds.is_empty() {
+                    "any".to_string()
+                } else {
+                    filter.kinds.join(",")
+                }
+            ),
+        ),
+        Err(err) => (false, err.to_string()),
+    };
+    let ack = crate::protocol::ServerEvent::DebugResponse { id, ok, output };
+    writer
+        .write_all(crate::protocol::encode_event(&ack).as_bytes())
+        .await?;
+    let Ok(filter) = filter else {
+        return Ok(true);
+    };
+
+    let mut tap = Bus::global().tap();
+    let mut discard = String::new();
+    loop {
+        tokio::select! {
+            read = reader.read_line(&mut discard) => match read {
+                Ok(0) | Err(_) => break,
+                Ok(_) => discard.clear(),
+            },
+            event = tap.recv() => {
+                let Some(event) = event else {
+                    break;
+                };
+                let mut out = String::new();
+                let dropped = tap.take_dropped();
+                if dropped > 0 {
+                    out.push_str(&format!("... {} events dropped while this tail was behind\n", dropped));
+                }
+                if filter.matches(&event) {
+                    out.push_str(&format_tapped_event(&event));
+                }
+                if !out.is_empty() && writer.write_all(out.as_bytes()).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+
+    Ok(true)
+}
+
 fn event_payload(event: &SwarmEvent) -> serde_json::Value {
     let timestamp_unix = event
         .absolute_time
@@ -172,3 +325,41 @@ fn event_payload(event: &SwarmEvent) -> serde_json::Value {
         "timestamp_unix": timestamp_unix,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tapped(kind: &'static str, session_id: Option<&str>) -> TappedEvent {
+        TappedEvent {
+            seq: 7,
+            kind,
+            session_id: session_id.map(str::to_string),
+            summary: "CompactionFinished".to_string(),
+        }
+    }
+
+    #[test]
+    fn bus_tail_filter_matches_session_and_kind_prefix() {
+        let filter = BusTailFilter::parse(" --session s1 --kinds tool,compaction_finished")
+            .expect("parse filter");
+        assert_eq!(filter.session_id.as_deref(), Some("s1"));
+        assert!(filter.matches(&tapped("tool_updated", Some("s1"))));
+        assert!(filter.matches(&tapped("compaction_finished", Some("s1"))));
+        assert!(!filter.matches(&tapped("todo_updated", Some("s1"))));
+        assert!(!filter.matches(&tapped("tool_updated", Some("s2"))));
+        assert!(!filter.matches(&tapped("tools_changed", Some("s1"))));
+
+        assert!(
+            BusTailFilter::parse("")
+                .unwrap()
+                .matches(&tapped("ui_activity", None))
+        );
+        assert!(BusTailFilter::parse("--kinds").is_err());
+        assert!(BusTailFilter::parse("--follow").is_err());
+        assert_eq!(
+            format_tapped_event(&tapped("models_updated", None)),
+            "#7 models_updated session=- CompactionFinished\n"
+        );
+    }
+}
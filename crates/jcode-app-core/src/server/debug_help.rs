@@ -28,6 +28,9 @@ SERVER COMMANDS (server: prefix or no prefix):
   swarm_message_async:<text> - Async swarm message (returns job id)
   tool:<name> <json>       - Execute tool directly
   cancel                   - Cancel in-flight generation (urgent interrupt)
+  queue:list               - List pending turns (queued messages, soft interrupts, scheduled follow-ups)
+  queue:remove <item_id>   - Drop a pending turn queue item before it runs
+  queue:move <item_id> <n> - Move a queued message or interrupt to position n of its kind
   clear                    - Clear conversation history
   agent:info               - Get comprehensive agent internal state
   agent:memory             - Get process + session memory breakdown
@@ -51,6 +54,9 @@ SERVER COMMANDS (server: prefix or no prefix):
   embeddings:stats         - Get embedding model/cache runtime stats
   embeddings:load          - Force-load the shared embedding model
   embeddings:unload        - Force-unload the shared embedding model and cache
+  network                  - Egress policy and outbound hosts seen (with counts)
+  env                      - Environment section of the system prompt
+  env:refresh              - Re-probe the environment (changes the prompt prefix once)
   sessions                 - List all sessions (with full metadata)
   clients                  - List connected TUI clients
   clients:map              - Map connected clients to sessions
@@ -104,6 +110,8 @@ EVENTS COMMANDS (events: prefix):
   events:types             - List available event types
   events:subscribe         - Subscribe to all events (streaming)
   events:subscribe:<types> - Subscribe filtered (e.g. status_change,member_change)
+  events:tail [--session <id>] [--kinds a,b] - Stream internal bus events as text
+  events:stats             - Bus event counts by kind and queue depths
 
 CLIENT COMMANDS (client: prefix):
   client:state             - Get TUI state
@@ -241,6 +249,9 @@ REAL-TIME EVENTS:
   events:types             - List available event types
   events:subscribe         - Subscribe to all events (streaming, keeps connection open)
   events:subscribe:<types> - Subscribe filtered (e.g. events:subscribe:status_change,member_change)
+  events:tail              - Stream internal bus events (kind, session, sequence, payload) until disconnect
+  events:tail --session <id> --kinds tool,todo - Tail only matching bus events (kinds match by prefix)
+  events:stats             - Bus event counts by kind, broadcast queue depth, open tails
 
 Examples:
   {"type":"debug_command","id":1,"command":"swarm:list"}
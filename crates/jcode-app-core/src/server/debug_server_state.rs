@@ -189,6 +189,29 @@ pub(super) async fn maybe_handle_server_state_command(
         ));
     }
 
+    if cmd == "network" || cmd == "network:stats" {
+        return Ok(Some(crate::network_policy::format_network_report()));
+    }
+
+    if cmd == "env" || cmd == "env:refresh" {
+        let refresh = cmd == "env:refresh";
+        let descriptor = tokio::task::spawn_blocking(move || {
+            if refresh
+                && crate::config::config()
+                    .prompt
+                    .section_enabled(crate::config::PromptSection::Environment)
+            {
+                Some(crate::prompt::refresh_environment_descriptor())
+            } else {
+                crate::prompt::environment_descriptor()
+            }
+        })
+        .await?;
+        return Ok(Some(descriptor.unwrap_or_else(|| {
+            "Environment section disabled ([prompt] environment = false)".to_string()
+        })));
+    }
+
     if cmd == "info" || cmd == "server:info" {
         let uptime_secs = server_start_time.elapsed().as_secs();
         let session_count = sessions.read().await.len();
@@ -0,0 +1,135 @@
+//! HTTP listener for `[server] metrics_listen`.
+//!
+//! Answers `GET /metrics` with `crate::metrics::render` plus the number of
+//! sessions loaded in this server, and 404 for anything else. One request per
+//! connection; there is no auth, so keep the address on loopback or a private
+//! network.
+
+use super::SessionAgents;
+use anyhow::Result;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Largest request head read before the connection is dropped.
+const MAX_REQUEST_HEAD: usize = 8 * 1024;
+
+pub(super) async fn run_metrics_listener(addr: String, sessions: SessionAgents) -> Result<()> {
+    let listener = TcpListener::bind(&addr).await?;
+    crate::logging::info(&format!("Metrics listening on http://{}/metrics", addr));
+    serve(listener, sessions).await
+}
+
+async fn serve(listener: TcpListener, sessions: SessionAgents) -> Result<()> {
+    loop {
+        let (stream, peer_addr) = listener.accept().await?;
+        let sessions = sessions.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, sessions).await {
+                crate::logging::warn(&format!("Metrics request from {} failed: {}", peer_addr, e));
+            }
+        });
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream, sessions: SessionAgents) -> Result<()> {
+    let mut head = Vec::new();
+    let mut chunk = [0u8; 1024];
+    while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            break;
+        }
+        head.extend_from_slice(&chunk[..n]);
+        if head.len() > MAX_REQUEST_HEAD {
+            anyhow::bail!("request head too large");
+        }
+    }
+    let head = String::from_utf8_lossy(&head);
+    let mut request_line = head.lines().next().unwrap_or("").split_whitespace();
+    let (method, path) = (request_line.next(), request_line.next());
+    let path = path.map(|path| path.split('?').next().unwrap_or(path));
+
+    let (status, body) = if method == Some("GET") && path == Some("/metrics") {
+        let active_sessions = sessions.read().await.len() as u64;
+        let body = crate::metrics::render(&[crate::metrics::Gauge {
+            name: "jcode_active_sessions",
+            help: "Sessions loaded in this server.",
+            value: active_sessions,
+        }]);
+        ("200 OK", body)
+    } else {
+        ("404 Not Found", "not found\n".to_string())
+    };
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/plain; version=0.0.4; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    async fn scrape(addr: std::net::SocketAddr, path: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.expect("connect");
+        stream
+            .write_all(format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).as_bytes())
+            .await
+            .expect("write request");
+        let mut response = String::new();
+        stream
+            .read_to_string(&mut response)
+            .await
+            .expect("read response");
+        response
+    }
+
+    fn sample(response: &str, series: &str) -> u64 {
+        response
+            .lines()
+            .find_map(|line| line.strip_prefix(series)?.trim().parse().ok())
+            .unwrap_or(0)
+    }
+
+    #[tokio::test]
+    async fn scrape_serves_counters_that_only_grow() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("local addr");
+        let sessions: SessionAgents = Arc::new(RwLock::new(HashMap::new()));
+        tokio::spawn(serve(listener, sessions));
+
+        let series = "jcode_tool_executions_total{tool=\"metrics_scrape_test\"}";
+        crate::metrics::record_tool_execution("metrics_scrape_test", true);
+        let first = scrape(addr, "/metrics").await;
+        assert!(first.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(first.contains("# TYPE jcode_turns_completed_total counter"));
+        assert!(first.contains("jcode_active_sessions 0\n"));
+        let before = sample(&first, series);
+        assert!(before >= 1);
+
+        crate::metrics::record_tool_execution("metrics_scrape_test", false);
+        crate::metrics::record_tool_execution("metrics_scrape_test", true);
+        let second = scrape(addr, "/metrics?format=text").await;
+        assert!(sample(&second, series) >= before + 2);
+        assert!(
+            sample(
+                &second,
+                "jcode_tool_errors_total{tool=\"metrics_scrape_test\"}"
+            ) >= 1
+        );
+
+        assert!(
+            scrape(addr, "/")
+                .await
+                .starts_with("HTTP/1.1 404 Not Found\r\n")
+        );
+    }
+}
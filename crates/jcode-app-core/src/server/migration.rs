@@ -0,0 +1,154 @@
+//! Warns sessions before the server moves them onto a newer binary.
+//!
+//! The server polls for a strictly-newer reload candidate (see
+//! `server_has_newer_binary`) and publishes [`BusEvent::MigrationPending`]
+//! once per target build, a grace period ahead of the migration. Clients show
+//! the pending migration and run the reload when it is due unless the user
+//! defers it. When the reload happens, every live session records the version
+//! boundary in its transcript.
+
+use super::{SessionAgents, reload_exec_target, server_has_newer_binary};
+use crate::bus::{Bus, BusEvent, MigrationPending};
+use crate::protocol::ServerEvent;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Often enough that an installed update is announced within a minute.
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+const SERVER_UPDATE_REASON: &str = "a newer server binary was installed";
+
+/// The migration announced for the current update, if any.
+static PENDING: Mutex<Option<MigrationPending>> = Mutex::new(None);
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// The version directory a reload candidate resolves to, e.g. `0.15.0` for
+/// `~/.jcode/builds/stable/jcode -> versions/0.15.0/jcode`.
+fn binary_version(path: &Path) -> String {
+    let canonical = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    canonical
+        .parent()
+        .and_then(Path::file_name)
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| canonical.display().to_string())
+}
+
+/// The migration to announce when `to_hash` is available, or `None` when it
+/// was already announced.
+fn next_migration(
+    pending: &mut Option<MigrationPending>,
+    to_hash: String,
+    grace_secs: u64,
+    now_ms: u64,
+) -> Option<MigrationPending> {
+    if pending
+        .as_ref()
+        .is_some_and(|migration| migration.to_hash == to_hash)
+    {
+        return None;
+    }
+    let migration = MigrationPending {
+        from_hash: jcode_build_meta::GIT_HASH.to_string(),
+        to_hash,
+        reason: SERVER_UPDATE_REASON.to_string(),
+        at: now_ms + grace_secs.saturating_mul(1000),
+    };
+    *pending = Some(migration.clone());
+    Some(migration)
+}
+
+fn check_for_update() -> Option<MigrationPending> {
+    let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+    if !server_has_newer_binary() {
+        *pending = None;
+        return None;
+    }
+    let (binary, _label) = reload_exec_target(false)?;
+    let grace_secs = crate::config::config().display.migration_grace_secs;
+    next_migration(&mut pending, binary_version(&binary), grace_secs, now_ms())
+}
+
+pub(super) fn event_for(migration: MigrationPending) -> ServerEvent {
+    ServerEvent::MigrationPending {
+        from_hash: migration.from_hash,
+        to_hash: migration.to_hash,
+        reason: migration.reason,
+        at: migration.at,
+    }
+}
+
+/// The announced migration, for clients that attach during the grace period.
+pub(super) fn pending_event() -> Option<ServerEvent> {
+    PENDING
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .map(event_for)
+}
+
+pub(super) fn spawn_watcher() {
+    tokio::spawn(async {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            interval.tick().await;
+            let Ok(Some(migration)) = tokio::task::spawn_blocking(check_for_update).await else {
+                continue;
+            };
+            crate::logging::info(&format!(
+                "Migration pending: {} -> {} at {} ({})",
+                migration.from_hash, migration.to_hash, migration.at, migration.reason
+            ));
+            Bus::global().publish(BusEvent::MigrationPending(migration));
+        }
+    });
+}
+
+/// Record the move to `binary` in every session the reload carries over.
+pub(super) async fn record_version_boundary(sessions: &SessionAgents, binary: &Path) {
+    let from_hash = jcode_build_meta::GIT_HASH;
+    let to_hash = binary_version(binary);
+    if to_hash == from_hash {
+        return;
+    }
+    let reason = PENDING
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .map(|migration| migration.reason.clone())
+        .unwrap_or_else(|| "server reload".to_string());
+    let agents: Vec<_> = sessions.read().await.values().cloned().collect();
+    for agent in agents {
+        // Sessions still busy after the graceful shutdown are skipped rather
+        // than holding up the reload.
+        if let Ok(mut agent) = agent.try_lock() {
+            agent.record_migration(from_hash, &to_hash, &reason);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_target_build_is_announced_once() {
+        let mut pending = None;
+        let first = next_migration(&mut pending, "0.15.0".to_string(), 300, 1_000).unwrap();
+        assert_eq!(first.to_hash, "0.15.0");
+        assert_eq!(first.at, 301_000);
+        assert_eq!(first.from_hash, jcode_build_meta::GIT_HASH);
+
+        assert!(next_migration(&mut pending, "0.15.0".to_string(), 300, 9_000).is_none());
+
+        let newer = next_migration(&mut pending, "0.15.1".to_string(), 0, 9_000).unwrap();
+        assert_eq!((newer.to_hash.as_str(), newer.at), ("0.15.1", 9_000));
+    }
+}
@@ -0,0 +1,49 @@
+//! Keeps every client's view of the shared permission inbox current.
+//!
+//! Requests and decisions reach the safety queue from whichever process
+//! handles them (this server, `jcode permissions`, the Telegram bridge, email
+//! replies), so the server polls the queue and publishes
+//! [`BusEvent::PermissionInboxChanged`] whenever the pending set changes.
+
+use crate::bus::{Bus, BusEvent, PermissionInboxChanged};
+use crate::protocol::PermissionInboxEntry;
+use crate::safety::{self, PermissionRequest};
+use std::time::Duration;
+
+/// Keeps answers from one surface visible on the others within a second.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Pending entries as clients see them, oldest first.
+pub(super) fn snapshot() -> Vec<PermissionInboxEntry> {
+    safety::inbox()
+        .iter()
+        .map(PermissionRequest::to_inbox_entry)
+        .collect()
+}
+
+pub(super) fn spawn_watcher() {
+    tokio::spawn(async {
+        // Deny requests whose session died or that expired while no server
+        // was running, so they are not listed again.
+        let _ = tokio::task::spawn_blocking(|| {
+            safety::expire_stale_permissions_via_file("server_startup")
+        })
+        .await;
+
+        let mut last: Vec<PermissionInboxEntry> = Vec::new();
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            interval.tick().await;
+            let Ok(entries) = tokio::task::spawn_blocking(snapshot).await else {
+                continue;
+            };
+            if entries != last {
+                Bus::global().publish(BusEvent::PermissionInboxChanged(PermissionInboxChanged {
+                    entries: entries.clone(),
+                }));
+                last = entries;
+            }
+        }
+    });
+}
@@ -318,6 +318,7 @@ fn send_model_changed_result(
                 model: updated,
                 provider_name: Some(provider_name),
                 error: None,
+                route_fallback: None,
             });
         }
         Err(error) => {
@@ -334,6 +335,7 @@ fn send_model_changed_result(
                 model: fallback_model,
                 provider_name: None,
                 error: Some(error.to_string()),
+                route_fallback: None,
             });
         }
     }
@@ -352,6 +354,7 @@ fn apply_cycle_model(
             model: agent.provider_model(),
             provider_name: None,
             error: Some("Model switching is not available for this provider.".to_string()),
+            route_fallback: None,
         });
         return;
     }
@@ -489,6 +492,7 @@ fn apply_set_model(
             model: current,
             provider_name: None,
             error: Some("Model switching is not available for this provider.".to_string()),
+            route_fallback: None,
         });
         return;
     }
@@ -537,6 +541,7 @@ fn apply_set_route(
             model: current,
             provider_name: None,
             error: Some("Model switching is not available for this provider.".to_string()),
+            route_fallback: None,
         });
         return;
     }
@@ -1398,6 +1403,7 @@ mod tests {
                 model,
                 provider_name: Some(provider_name),
                 error: None,
+                route_fallback: None,
             }) if model == "test-model-b" && provider_name == "test-effort"
         ));
     }
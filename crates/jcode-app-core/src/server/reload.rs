@@ -154,6 +154,7 @@ pub(super) async fn await_reload_signal(
 
         if let Some((binary, label)) = super::reload_exec_target(prefers_selfdev) {
             if binary.exists() {
+                super::migration::record_version_boundary(&sessions, &binary).await;
                 let socket = super::socket_path();
                 crate::logging::info(&format!(
                     "Server: exec'ing into {} binary {:?} (socket: {:?}, prep={}ms, state={})",
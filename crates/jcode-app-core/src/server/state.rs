@@ -454,6 +454,7 @@ pub(super) fn session_event_fanout_sender(
 }
 
 pub(super) fn enqueue_soft_interrupt(
+    session_id: &str,
     queue: &SoftInterruptQueue,
     content: String,
     urgent: bool,
@@ -463,11 +464,7 @@ pub(super) fn enqueue_soft_interrupt(
     let content_chars = content.chars().count();
     if let Ok(mut pending) = queue.lock() {
         let pending_before = pending.len();
-        pending.push(SoftInterruptMessage {
-            content,
-            urgent,
-            source,
-        });
+        pending.push(SoftInterruptMessage::new(content, urgent, source));
         crate::logging::info(&format!(
             "SOFT_INTERRUPT_QUEUE_PUSH source={:?} urgent={} content_bytes={} content_chars={} pending_before={} pending_after={}",
             source,
@@ -477,6 +474,8 @@ pub(super) fn enqueue_soft_interrupt(
             pending_before,
             pending.len()
         ));
+        drop(pending);
+        super::turn_queue::notify_changed(session_id);
         true
     } else {
         crate::logging::warn(&format!(
@@ -541,17 +540,29 @@ impl SessionControlHandle {
         urgent: bool,
         source: SoftInterruptSource,
     ) -> bool {
-        enqueue_soft_interrupt(&self.soft_interrupt_queue, content, urgent, source)
+        enqueue_soft_interrupt(
+            &self.session_id,
+            &self.soft_interrupt_queue,
+            content,
+            urgent,
+            source,
+        )
+    }
+
+    pub fn soft_interrupt_queue(&self) -> SoftInterruptQueue {
+        Arc::clone(&self.soft_interrupt_queue)
     }
 
     pub fn clear_soft_interrupts(&self) {
         if let Ok(mut queue) = self.soft_interrupt_queue.lock() {
             let cleared = queue.len();
             queue.clear();
+            drop(queue);
             crate::logging::info(&format!(
                 "SOFT_INTERRUPT_QUEUE_CLEAR session={} cleared={}",
                 self.session_id, cleared
             ));
+            super::turn_queue::notify_changed(&self.session_id);
         } else {
             crate::logging::warn(&format!(
                 "SOFT_INTERRUPT_QUEUE_CLEAR_FAILED session={} reason=queue_lock_poisoned",
@@ -643,7 +654,7 @@ pub(super) async fn queue_soft_interrupt_for_session(
     sessions: &super::SessionAgents,
 ) -> bool {
     if let Some(queue) = queues.read().await.get(session_id).cloned() {
-        return enqueue_soft_interrupt(&queue, content, urgent, source);
+        return enqueue_soft_interrupt(session_id, &queue, content, urgent, source);
     }
 
     let queue = {
@@ -658,7 +669,7 @@ pub(super) async fn queue_soft_interrupt_for_session(
 
     if let Some(queue) = queue {
         register_session_interrupt_queue(queues, session_id, queue.clone()).await;
-        enqueue_soft_interrupt(&queue, content, urgent, source)
+        enqueue_soft_interrupt(session_id, &queue, content, urgent, source)
     } else {
         let session_exists = {
             let guard = sessions.read().await;
@@ -671,11 +682,7 @@ pub(super) async fn queue_soft_interrupt_for_session(
 
         crate::soft_interrupt_store::append(
             session_id,
-            SoftInterruptMessage {
-                content,
-                urgent,
-                source,
-            },
+            SoftInterruptMessage::new(content, urgent, source),
         )
         .map(|_| true)
         .unwrap_or_else(|err| {
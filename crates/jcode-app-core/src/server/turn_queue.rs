@@ -0,0 +1,426 @@
+//! Per-session turn queue.
+//!
+//! Three things can start or steer the next turn of a session: user messages
+//! queued behind the running turn (`queue_message`), soft interrupts waiting
+//! for a safe injection point, and ambient schedule entries that will resume
+//! the session. This module lists them as one queue, lets clients drop or
+//! reorder entries before they run, and publishes
+//! [`BusEvent::TurnQueueChanged`] whenever the composition changes. Queued
+//! messages live in [`crate::queued_message_store`], so they survive a server
+//! reload and run once a client attaches to the session again.
+
+use crate::ambient::{AmbientManager, ScheduleTarget};
+use crate::bus::{Bus, BusEvent, TurnQueueChanged};
+use crate::protocol::{TurnQueueItem, TurnQueueKind};
+pub(super) use crate::queued_message_store::QueuedMessage;
+use chrono::SecondsFormat;
+use jcode_agent_runtime::{SoftInterruptQueue, SoftInterruptSource};
+use std::sync::Mutex;
+
+const MESSAGE_PREFIX: &str = "msg-";
+const INTERRUPT_PREFIX: &str = "int-";
+const SCHEDULE_PREFIX: &str = "sched-";
+
+const PREVIEW_BYTES: usize = 80;
+
+/// Serializes read-modify-write cycles of the queued message files.
+static MESSAGES_LOCK: Mutex<()> = Mutex::new(());
+
+/// Run `edit` on the session's queued messages and store the result.
+fn edit_messages<T>(
+    session_id: &str,
+    edit: impl FnOnce(&mut Vec<QueuedMessage>) -> T,
+) -> anyhow::Result<T> {
+    let _guard = MESSAGES_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut messages = crate::queued_message_store::load(session_id)?;
+    let result = edit(&mut messages);
+    crate::queued_message_store::overwrite(session_id, &messages)?;
+    Ok(result)
+}
+
+fn queued_messages(session_id: &str) -> Vec<QueuedMessage> {
+    let _guard = MESSAGES_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    crate::queued_message_store::load(session_id).unwrap_or_else(|err| {
+        crate::logging::warn(&format!(
+            "[turn-queue] failed to load queued messages for {}: {:#}",
+            session_id, err
+        ));
+        Vec::new()
+    })
+}
+
+pub(super) fn notify_changed(session_id: &str) {
+    Bus::global().publish(BusEvent::TurnQueueChanged(TurnQueueChanged {
+        session_id: session_id.to_string(),
+    }));
+}
+
+/// Queue a message behind the running turn. Returns its item id.
+pub(super) fn push_message(
+    session_id: &str,
+    id: u64,
+    content: String,
+    images: Vec<(String, String)>,
+    system_reminder: Option<String>,
+) -> anyhow::Result<String> {
+    let seq = edit_messages(session_id, |messages| {
+        let seq = crate::queued_message_store::next_seq(messages);
+        messages.push(QueuedMessage {
+            seq,
+            id,
+            content,
+            images,
+            system_reminder,
+        });
+        seq
+    })?;
+    notify_changed(session_id);
+    Ok(format!("{}{}", MESSAGE_PREFIX, seq))
+}
+
+/// Take the next queued message of the session, if any.
+pub(super) fn pop_message(session_id: &str) -> Option<QueuedMessage> {
+    let message = edit_messages(session_id, |messages| {
+        (!messages.is_empty()).then(|| messages.remove(0))
+    })
+    .unwrap_or_else(|err| {
+        crate::logging::warn(&format!(
+            "[turn-queue] failed to take a queued message for {}: {:#}",
+            session_id, err
+        ));
+        None
+    })?;
+    notify_changed(session_id);
+    Some(message)
+}
+
+/// Queued messages and soft interrupts, in the order they will run.
+fn pending_items(session_id: &str, soft_interrupts: &SoftInterruptQueue) -> Vec<TurnQueueItem> {
+    let mut items: Vec<TurnQueueItem> = queued_messages(session_id)
+        .iter()
+        .map(|message| TurnQueueItem {
+            id: format!("{}{}", MESSAGE_PREFIX, message.seq),
+            kind: TurnQueueKind::Message,
+            origin: "user".to_string(),
+            preview: preview(&message.content),
+            urgent: false,
+            due_at: None,
+        })
+        .collect();
+    if let Ok(pending) = soft_interrupts.lock() {
+        items.extend(pending.iter().map(|message| TurnQueueItem {
+            id: format!("{}{}", INTERRUPT_PREFIX, message.id),
+            kind: TurnQueueKind::SoftInterrupt,
+            origin: source_label(message.source).to_string(),
+            preview: preview(&message.content),
+            urgent: message.urgent,
+            due_at: None,
+        }));
+    }
+    items
+}
+
+/// Ambient schedule entries that will resume the session, soonest first.
+fn scheduled_items(session_id: &str) -> Vec<TurnQueueItem> {
+    let Ok(manager) = AmbientManager::new() else {
+        return Vec::new();
+    };
+    let mut items: Vec<_> = manager
+        .queue()
+        .items()
+        .iter()
+        .filter(|item| match &item.target {
+            ScheduleTarget::Session { session_id: target } => target == session_id,
+            _ => false,
+        })
+        .collect();
+    items.sort_by_key(|item| item.scheduled_for);
+    items
+        .into_iter()
+        .map(|item| TurnQueueItem {
+            id: format!("{}{}", SCHEDULE_PREFIX, item.id),
+            kind: TurnQueueKind::ScheduledFollowUp,
+            origin: "schedule".to_string(),
+            preview: preview(item.task_description.as_deref().unwrap_or(&item.context)),
+            urgent: false,
+            due_at: Some(
+                item.scheduled_for
+                    .to_rfc3339_opts(SecondsFormat::Secs, true),
+            ),
+        })
+        .collect()
+}
+
+/// Everything pending for the session: queued messages, soft interrupts,
+/// then scheduled follow-ups.
+pub(super) fn snapshot(
+    session_id: &str,
+    soft_interrupts: &SoftInterruptQueue,
+) -> Vec<TurnQueueItem> {
+    let mut items = pending_items(session_id, soft_interrupts);
+    items.extend(scheduled_items(session_id));
+    items
+}
+
+/// Drop a pending item before it runs.
+pub(super) fn remove(
+    session_id: &str,
+    soft_interrupts: &SoftInterruptQueue,
+    item_id: &str,
+) -> Result<(), String> {
+    let removed = if let Some(seq) = parse_id(item_id, MESSAGE_PREFIX) {
+        edit_messages(session_id, |messages| {
+            let before = messages.len();
+            messages.retain(|message| message.seq != seq);
+            messages.len() != before
+        })
+        .map_err(|err| format!("Failed to remove `{}`: {:#}", item_id, err))?
+    } else if let Some(id) = parse_id(item_id, INTERRUPT_PREFIX) {
+        soft_interrupts.lock().ok().is_some_and(|mut pending| {
+            let before = pending.len();
+            pending.retain(|message| message.id != id);
+            pending.len() != before
+        })
+    } else if let Some(schedule_id) = item_id.strip_prefix(SCHEDULE_PREFIX) {
+        if !scheduled_items(session_id)
+            .iter()
+            .any(|item| item.id == item_id)
+        {
+            return Err(format!("No pending item `{}` in this session.", item_id));
+        }
+        let cancelled = AmbientManager::new()
+            .and_then(|mut manager| manager.cancel_schedule(schedule_id))
+            .map_err(|err| format!("Failed to cancel `{}`: {}", item_id, err))?;
+        if cancelled.is_some() {
+            crate::tool::ambient::nudge_schedule_runner();
+        }
+        cancelled.is_some()
+    } else {
+        false
+    };
+    if !removed {
+        return Err(format!("No pending item `{}` in this session.", item_id));
+    }
+    notify_changed(session_id);
+    Ok(())
+}
+
+/// Move a queued message or soft interrupt to a 1-based position among items
+/// of its kind. Scheduled follow-ups run at their due time and cannot move.
+pub(super) fn move_item(
+    session_id: &str,
+    soft_interrupts: &SoftInterruptQueue,
+    item_id: &str,
+    position: usize,
+) -> Result<(), String> {
+    let moved = if let Some(seq) = parse_id(item_id, MESSAGE_PREFIX) {
+        edit_messages(session_id, |messages| {
+            let index = messages.iter().position(|message| message.seq == seq)?;
+            Some(move_within(messages, index, position))
+        })
+        .map_err(|err| format!("Failed to move `{}`: {:#}", item_id, err))?
+    } else if let Some(id) = parse_id(item_id, INTERRUPT_PREFIX) {
+        soft_interrupts.lock().ok().and_then(|mut pending| {
+            let index = pending.iter().position(|message| message.id == id)?;
+            Some(move_within(&mut pending, index, position))
+        })
+    } else if item_id.starts_with(SCHEDULE_PREFIX) {
+        return Err("Scheduled follow-ups run at their due time; remove them instead.".to_string());
+    } else {
+        None
+    };
+    match moved {
+        Some(true) => {
+            notify_changed(session_id);
+            Ok(())
+        }
+        Some(false) => Ok(()),
+        None => Err(format!("No pending item `{}` in this session.", item_id)),
+    }
+}
+
+/// Move `items[index]` to 1-based `position`, clamped to the list. Returns
+/// whether the order changed.
+fn move_within<T>(items: &mut Vec<T>, index: usize, position: usize) -> bool {
+    let target = position.clamp(1, items.len()) - 1;
+    if target == index {
+        return false;
+    }
+    let item = items.remove(index);
+    items.insert(target, item);
+    true
+}
+
+fn parse_id(item_id: &str, prefix: &str) -> Option<u64> {
+    item_id.strip_prefix(prefix)?.parse().ok()
+}
+
+fn source_label(source: SoftInterruptSource) -> &'static str {
+    match source {
+        SoftInterruptSource::User => "user",
+        SoftInterruptSource::System => "system",
+        SoftInterruptSource::BackgroundTask => "background_task",
+    }
+}
+
+fn preview(content: &str) -> String {
+    let line = content.trim().lines().next().unwrap_or_default();
+    let truncated = crate::util::truncate_str(line, PREVIEW_BYTES);
+    if truncated.len() < line.len() || content.trim().lines().nth(1).is_some() {
+        format!("{}…", truncated)
+    } else {
+        truncated.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jcode_agent_runtime::SoftInterruptMessage;
+    use std::sync::Arc;
+
+    fn soft_queue(messages: &[(&str, bool, SoftInterruptSource)]) -> SoftInterruptQueue {
+        Arc::new(std::sync::Mutex::new(
+            messages
+                .iter()
+                .map(|(content, urgent, source)| {
+                    SoftInterruptMessage::new(content.to_string(), *urgent, *source)
+                })
+                .collect(),
+        ))
+    }
+
+    /// JCODE_HOME pointed at a temp dir for the queued message files, put
+    /// back on drop.
+    struct TempHome {
+        _guard: std::sync::MutexGuard<'static, ()>,
+        _dir: tempfile::TempDir,
+        previous: Option<std::ffi::OsString>,
+    }
+
+    impl Drop for TempHome {
+        fn drop(&mut self) {
+            match self.previous.take() {
+                Some(value) => crate::env::set_var("JCODE_HOME", value),
+                None => crate::env::remove_var("JCODE_HOME"),
+            }
+        }
+    }
+
+    fn temp_home() -> TempHome {
+        let guard = crate::storage::lock_test_env();
+        let dir = tempfile::TempDir::new().expect("temp home");
+        let previous = std::env::var_os("JCODE_HOME");
+        crate::env::set_var("JCODE_HOME", dir.path());
+        TempHome {
+            _guard: guard,
+            _dir: dir,
+            previous,
+        }
+    }
+
+    #[test]
+    fn lists_messages_then_interrupts_with_origin() {
+        let _home = temp_home();
+        let session = "turn-queue-test-list";
+        let soft = soft_queue(&[
+            ("check CI", true, SoftInterruptSource::User),
+            (
+                "task done\nmore",
+                false,
+                SoftInterruptSource::BackgroundTask,
+            ),
+        ]);
+        let first = push_message(session, 7, "write the docs".to_string(), Vec::new(), None)
+            .expect("queue message");
+
+        let items = pending_items(session, &soft);
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].id, first);
+        assert_eq!(items[0].kind, TurnQueueKind::Message);
+        assert_eq!(items[0].preview, "write the docs");
+        assert_eq!(items[1].kind, TurnQueueKind::SoftInterrupt);
+        assert!(items[1].urgent);
+        assert_eq!(items[2].origin, "background_task");
+        assert_eq!(items[2].preview, "task done…");
+
+        let message = pop_message(session).expect("queued message");
+        assert_eq!(
+            (message.id, message.content.as_str()),
+            (7, "write the docs")
+        );
+        assert!(pop_message(session).is_none());
+    }
+
+    #[test]
+    fn remove_and_move_reorder_pending_items() {
+        let _home = temp_home();
+        let session = "turn-queue-test-edit";
+        let soft = soft_queue(&[
+            ("a", false, SoftInterruptSource::User),
+            ("b", false, SoftInterruptSource::User),
+            ("c", false, SoftInterruptSource::System),
+        ]);
+        let first =
+            push_message(session, 1, "one".to_string(), Vec::new(), None).expect("queue one");
+        let second =
+            push_message(session, 2, "two".to_string(), Vec::new(), None).expect("queue two");
+        let interrupt_c = pending_items(session, &soft)[4].id.clone();
+
+        move_item(session, &soft, &second, 1).unwrap();
+        move_item(session, &soft, &interrupt_c, 0).unwrap();
+        remove(session, &soft, &first).unwrap();
+
+        let previews: Vec<_> = pending_items(session, &soft)
+            .into_iter()
+            .map(|item| item.preview)
+            .collect();
+        assert_eq!(previews, ["two", "c", "a", "b"]);
+        assert!(remove(session, &soft, &first).is_err());
+        assert!(move_item(session, &soft, "sched-x", 1).is_err());
+        assert!(remove(session, &soft, "bogus").is_err());
+        while pop_message(session).is_some() {}
+    }
+
+    #[test]
+    fn queued_messages_survive_a_restart_and_keep_their_ids() {
+        let _home = temp_home();
+        let session = "turn-queue-test-persist";
+        let soft = soft_queue(&[]);
+        let first =
+            push_message(session, 3, "first".to_string(), Vec::new(), None).expect("queue first");
+        let second =
+            push_message(session, 4, "second".to_string(), Vec::new(), None).expect("queue second");
+        assert_ne!(first, second);
+
+        // Nothing is cached in memory: a fresh server reads the same queue.
+        let stored = crate::queued_message_store::load(session).expect("load stored queue");
+        assert_eq!(stored.len(), 2);
+        let ids: Vec<_> = pending_items(session, &soft)
+            .into_iter()
+            .map(|item| item.id)
+            .collect();
+        assert_eq!(ids, [first, second.clone()]);
+
+        assert_eq!(pop_message(session).map(|message| message.id), Some(3));
+        let third =
+            push_message(session, 5, "third".to_string(), Vec::new(), None).expect("queue third");
+        assert_ne!(third, second);
+        while pop_message(session).is_some() {}
+        assert!(
+            crate::queued_message_store::load(session)
+                .expect("load drained queue")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn move_within_clamps_position() {
+        let mut items = vec![1, 2, 3];
+        assert!(move_within(&mut items, 0, 9));
+        assert_eq!(items, [2, 3, 1]);
+        assert!(!move_within(&mut items, 0, 1));
+        assert!(move_within(&mut items, 2, 0));
+        assert_eq!(items, [1, 2, 3]);
+    }
+}
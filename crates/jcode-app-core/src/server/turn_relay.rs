@@ -0,0 +1,287 @@
+//! Client turns that outlive their client.
+//!
+//! A client-initiated turn streams through a relay rather than straight into
+//! the client's event channel. The relay keeps a snapshot of the assistant
+//! text and tool in flight. If the owning client goes away mid-turn, a client
+//! that resumes the session can attach to the relay: it receives the snapshot
+//! as `ResumeStreaming` and then the rest of the stream, so a crashed or
+//! closed terminal loses nothing the server produced.
+
+use crate::protocol::ServerEvent;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex as StdMutex};
+use tokio::sync::{mpsc, oneshot};
+
+/// What a mid-turn client needs to pick up the stream where it is.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct LiveTurnSnapshot {
+    /// Assistant text since the last message boundary. Earlier messages are
+    /// already in the session history.
+    text: String,
+    current_tool_name: Option<String>,
+}
+
+impl LiveTurnSnapshot {
+    fn apply(&mut self, event: &ServerEvent) {
+        match event {
+            ServerEvent::TextDelta { text } => self.text.push_str(text),
+            ServerEvent::TextReplace { text } => self.text = text.clone(),
+            ServerEvent::MessageEnd => self.text.clear(),
+            ServerEvent::ToolStart { name, .. } => self.current_tool_name = Some(name.clone()),
+            ServerEvent::ToolDone { .. } => self.current_tool_name = None,
+            _ => {}
+        }
+    }
+}
+
+struct LiveTurn {
+    relay_id: u64,
+    owner: mpsc::UnboundedSender<ServerEvent>,
+    attach: mpsc::UnboundedSender<mpsc::UnboundedSender<ServerEvent>>,
+}
+
+/// Running relayed turns by session id.
+static LIVE_TURNS: LazyLock<StdMutex<HashMap<String, LiveTurn>>> =
+    LazyLock::new(|| StdMutex::new(HashMap::new()));
+
+static NEXT_RELAY_ID: AtomicU64 = AtomicU64::new(1);
+
+fn live_turns() -> std::sync::MutexGuard<'static, HashMap<String, LiveTurn>> {
+    LIVE_TURNS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Attach `client_tx` to the running turn of `session_id` if the client that
+/// started it is gone. The client then receives `ResumeStreaming` followed by
+/// the rest of the turn. Returns `false` when there is nothing to attach to.
+pub(super) fn attach_to_live_turn(
+    session_id: &str,
+    client_tx: mpsc::UnboundedSender<ServerEvent>,
+) -> bool {
+    live_turns()
+        .get(session_id)
+        .is_some_and(|turn| turn.owner.is_closed() && turn.attach.send(client_tx).is_ok())
+}
+
+/// Ends a relayed turn. See [`turn_relay`].
+pub(super) struct TurnRelayFinish(oneshot::Sender<oneshot::Sender<()>>);
+
+impl TurnRelayFinish {
+    /// Wait until every event sent before this call has been delivered, and
+    /// stop offering the turn for attach. Send the turn's `Done` after this
+    /// so it cannot overtake the last deltas.
+    pub(super) async fn finish(self) {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        if self.0.send(reply_tx).is_ok() {
+            let _ = reply_rx.await;
+        }
+    }
+}
+
+/// Event sender for a turn owned by one client connection. Events go to
+/// `owner` while it is open and to clients attached with
+/// [`attach_to_live_turn`].
+pub(super) fn turn_relay(
+    session_id: String,
+    owner: mpsc::UnboundedSender<ServerEvent>,
+) -> (mpsc::UnboundedSender<ServerEvent>, TurnRelayFinish) {
+    let relay_id = NEXT_RELAY_ID.fetch_add(1, Ordering::Relaxed);
+    let (tx, mut rx) = mpsc::unbounded_channel::<ServerEvent>();
+    let (attach_tx, mut attach_rx) = mpsc::unbounded_channel();
+    let (finish_tx, mut finish_rx) = oneshot::channel::<oneshot::Sender<()>>();
+    live_turns().insert(
+        session_id.clone(),
+        LiveTurn {
+            relay_id,
+            owner: owner.clone(),
+            attach: attach_tx,
+        },
+    );
+    tokio::spawn(async move {
+        let mut relay = Relay {
+            session_id,
+            relay_id,
+            owner: Some(owner),
+            attached: Vec::new(),
+            snapshot: LiveTurnSnapshot::default(),
+            live: true,
+        };
+        loop {
+            tokio::select! {
+                biased;
+                event = rx.recv() => match event {
+                    Some(event) => relay.forward(event),
+                    None => break,
+                },
+                Some(client_tx) = attach_rx.recv(), if relay.live => relay.attach(client_tx),
+                request = &mut finish_rx, if relay.live => {
+                    while let Ok(event) = rx.try_recv() {
+                        relay.forward(event);
+                    }
+                    relay.end_turn();
+                    // Attach requests that raced the end of the turn still get
+                    // the final snapshot; the turn's `Done` follows.
+                    while let Ok(client_tx) = attach_rx.try_recv() {
+                        relay.attach(client_tx);
+                    }
+                    if let Ok(reply) = request {
+                        let _ = reply.send(());
+                    }
+                }
+            }
+        }
+        relay.end_turn();
+    });
+    (tx, TurnRelayFinish(finish_tx))
+}
+
+struct Relay {
+    session_id: String,
+    relay_id: u64,
+    owner: Option<mpsc::UnboundedSender<ServerEvent>>,
+    attached: Vec<mpsc::UnboundedSender<ServerEvent>>,
+    snapshot: LiveTurnSnapshot,
+    /// Events after the turn (e.g. a late memory check) are still relayed
+    /// but no longer tracked as part of it.
+    live: bool,
+}
+
+impl Relay {
+    fn forward(&mut self, event: ServerEvent) {
+        if self.live {
+            self.snapshot.apply(&event);
+        }
+        if let Some(owner) = &self.owner
+            && owner.send(event.clone()).is_err()
+        {
+            crate::logging::info(&format!(
+                "Turn relay for session {} lost its client; waiting for a resume",
+                self.session_id
+            ));
+            self.owner = None;
+        }
+        self.attached
+            .retain(|client_tx| client_tx.send(event.clone()).is_ok());
+    }
+
+    fn attach(&mut self, client_tx: mpsc::UnboundedSender<ServerEvent>) {
+        let resume = ServerEvent::ResumeStreaming {
+            session_id: self.session_id.clone(),
+            text: self.snapshot.text.clone(),
+            current_tool_name: self.snapshot.current_tool_name.clone(),
+        };
+        if client_tx.send(resume).is_ok() {
+            self.attached.push(client_tx);
+        }
+    }
+
+    fn end_turn(&mut self) {
+        if !std::mem::take(&mut self.live) {
+            return;
+        }
+        let mut turns = live_turns();
+        if turns
+            .get(&self.session_id)
+            .is_some_and(|turn| turn.relay_id == self.relay_id)
+        {
+            turns.remove(&self.session_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_tracks_text_since_the_last_message_and_the_running_tool() {
+        let mut snapshot = LiveTurnSnapshot::default();
+        snapshot.apply(&ServerEvent::TextDelta {
+            text: "first".to_string(),
+        });
+        snapshot.apply(&ServerEvent::MessageEnd);
+        snapshot.apply(&ServerEvent::TextDelta {
+            text: "Hel".to_string(),
+        });
+        snapshot.apply(&ServerEvent::ToolStart {
+            id: "tool-1".to_string(),
+            name: "bash".to_string(),
+        });
+        snapshot.apply(&ServerEvent::TextDelta {
+            text: "lo".to_string(),
+        });
+        assert_eq!(
+            snapshot,
+            LiveTurnSnapshot {
+                text: "Hello".to_string(),
+                current_tool_name: Some("bash".to_string()),
+            }
+        );
+
+        snapshot.apply(&ServerEvent::TextReplace {
+            text: "Hi".to_string(),
+        });
+        snapshot.apply(&ServerEvent::ToolDone {
+            id: "tool-1".to_string(),
+            name: "bash".to_string(),
+            output: String::new(),
+            error: None,
+        });
+        assert_eq!(snapshot.text, "Hi");
+        assert_eq!(snapshot.current_tool_name, None);
+    }
+
+    #[tokio::test]
+    async fn client_attaches_only_after_the_owner_is_gone() {
+        let session_id = "turn-relay-attach-test";
+        let (owner_tx, mut owner_rx) = mpsc::unbounded_channel();
+        let (relay, finish) = turn_relay(session_id.to_string(), owner_tx);
+        relay
+            .send(ServerEvent::TextDelta {
+                text: "Hel".to_string(),
+            })
+            .expect("relay open");
+
+        let (client_tx, mut client_rx) = mpsc::unbounded_channel();
+        assert!(!attach_to_live_turn(session_id, client_tx.clone()));
+        assert!(matches!(
+            owner_rx.recv().await,
+            Some(ServerEvent::TextDelta { text }) if text == "Hel"
+        ));
+
+        drop(owner_rx);
+        assert!(attach_to_live_turn(session_id, client_tx));
+        relay
+            .send(ServerEvent::TextDelta {
+                text: "lo".to_string(),
+            })
+            .expect("relay open");
+        finish.finish().await;
+        drop(relay);
+
+        let mut seen = String::new();
+        match client_rx.recv().await {
+            Some(ServerEvent::ResumeStreaming {
+                session_id: resumed,
+                text,
+                current_tool_name,
+            }) => {
+                assert_eq!(resumed, session_id);
+                assert_eq!(current_tool_name, None);
+                seen.push_str(&text);
+            }
+            other => panic!("expected ResumeStreaming, got {other:?}"),
+        }
+        while let Some(event) = client_rx.recv().await {
+            if let ServerEvent::TextDelta { text } = event {
+                seen.push_str(&text);
+            }
+        }
+        assert_eq!(seen, "Hello");
+
+        let (late_tx, _late_rx) = mpsc::unbounded_channel();
+        assert!(!attach_to_live_turn(session_id, late_tx));
+    }
+}
@@ -0,0 +1,465 @@
+//! Cumulative file changes of one session, for `/changes` and `jcode sessions
+//! changes`.
+//!
+//! The first time a session's tools modify a file, its content from before
+//! that edit is kept under `~/.jcode/session-changes/<session>.json`. The
+//! session's changes are the diffs from those snapshots to what is on disk
+//! now. This does not depend on git, so it also works outside repositories.
+//! A file that was missing or empty before the session counts as created.
+
+use crate::git_auto_commit::{self, AutoCommit};
+use crate::message::{ContentBlock, Role};
+use crate::session::Session;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use similar::{ChangeTag, TextDiff};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+
+/// Serializes read-modify-write cycles of snapshot files in this process.
+static SNAPSHOT_LOCK: Mutex<()> = Mutex::new(());
+
+/// A file as it was before the session first changed it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FileSnapshot {
+    pub path: PathBuf,
+    /// `None` when the session created the file
+    pub original: Option<String>,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Session-start snapshots of every file a session changed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionSnapshots {
+    #[serde(default)]
+    pub files: Vec<FileSnapshot>,
+}
+
+impl SessionSnapshots {
+    fn path(session_id: &str) -> Result<PathBuf> {
+        Ok(crate::storage::jcode_dir()?
+            .join("session-changes")
+            .join(format!("{session_id}.json")))
+    }
+
+    /// The saved snapshots of a session, or none.
+    pub fn load(session_id: &str) -> Result<Self> {
+        let path = Self::path(session_id)?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        crate::storage::read_json(&path)
+    }
+
+    fn save(&self, session_id: &str) -> Result<()> {
+        crate::storage::write_json_secret(&Self::path(session_id)?, self)
+    }
+}
+
+/// Keep `before` as the session-start content of `path` unless the session
+/// already changed the file. Failures are logged; they never fail the tool
+/// call.
+pub fn record_original(session_id: &str, path: &Path, before: &str) {
+    let _guard = SNAPSHOT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let result = SessionSnapshots::load(session_id).and_then(|mut snapshots| {
+        if snapshots.files.iter().any(|file| file.path == path) {
+            return Ok(());
+        }
+        snapshots.files.push(FileSnapshot {
+            path: path.to_path_buf(),
+            original: (!before.is_empty()).then(|| before.to_string()),
+            recorded_at: Utc::now(),
+        });
+        snapshots.save(session_id)
+    });
+    if let Err(error) = result {
+        crate::logging::warn(&format!(
+            "[session-changes] failed to snapshot {} for session {}: {:#}",
+            path.display(),
+            session_id,
+            error
+        ));
+    }
+}
+
+/// The current content of `paths`, taken before a multi-file edit so the
+/// whole edit can be undone with [`restore`]. Missing files are captured as
+/// `None`; any other read error aborts before anything is written.
+pub fn capture(paths: &[&Path]) -> Result<Vec<FileSnapshot>> {
+    paths
+        .iter()
+        .map(|path| {
+            let original = match std::fs::read_to_string(path) {
+                Ok(text) => Some(text),
+                Err(error) if error.kind() == std::io::ErrorKind::NotFound => None,
+                Err(error) => {
+                    return Err(error)
+                        .with_context(|| format!("failed to snapshot {}", path.display()));
+                }
+            };
+            Ok(FileSnapshot {
+                path: path.to_path_buf(),
+                original,
+                recorded_at: Utc::now(),
+            })
+        })
+        .collect()
+}
+
+/// Put a captured file back, deleting it if it did not exist. The content is
+/// written through a temp file and a rename, so a failed restore never
+/// leaves the file truncated.
+pub fn restore(snapshot: &FileSnapshot) -> Result<()> {
+    write_back(&snapshot.path, snapshot.original.as_deref())
+}
+
+fn write_back(path: &Path, original: Option<&str>) -> Result<()> {
+    match original {
+        Some(original) => {
+            let permissions = std::fs::metadata(path).map(|meta| meta.permissions()).ok();
+            crate::storage::write_bytes_without_backup(path, original.as_bytes())
+                .with_context(|| format!("failed to restore {}", path.display()))?;
+            if let Some(permissions) = permissions {
+                std::fs::set_permissions(path, permissions)?;
+            }
+            Ok(())
+        }
+        None if path.exists() => std::fs::remove_file(path)
+            .with_context(|| format!("failed to remove {}", path.display())),
+        None => Ok(()),
+    }
+}
+
+/// One file's net change since the session first touched it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChange {
+    pub path: PathBuf,
+    /// `None` when the session created the file
+    pub original: Option<String>,
+    /// `None` when the file no longer exists
+    pub current: Option<String>,
+    pub added: usize,
+    pub removed: usize,
+}
+
+impl FileChange {
+    fn new(path: PathBuf, original: Option<String>, current: Option<String>) -> Self {
+        let (added, removed) = line_counts(
+            original.as_deref().unwrap_or_default(),
+            current.as_deref().unwrap_or_default(),
+        );
+        Self {
+            path,
+            original,
+            current,
+            added,
+            removed,
+        }
+    }
+
+    pub fn status_label(&self) -> &'static str {
+        match (&self.original, &self.current) {
+            (None, _) => "created",
+            (Some(_), None) => "deleted",
+            (Some(_), Some(_)) => "modified",
+        }
+    }
+
+    /// Unified diff from the session-start content to the current content.
+    pub fn unified_diff(&self) -> String {
+        let name = self.path.display().to_string();
+        let old_header = match self.original {
+            Some(_) => format!("a/{}", name.trim_start_matches('/')),
+            None => "/dev/null".to_string(),
+        };
+        let new_header = match self.current {
+            Some(_) => format!("b/{}", name.trim_start_matches('/')),
+            None => "/dev/null".to_string(),
+        };
+        TextDiff::from_lines(
+            self.original.as_deref().unwrap_or_default(),
+            self.current.as_deref().unwrap_or_default(),
+        )
+        .unified_diff()
+        .context_radius(3)
+        .header(&old_header, &new_header)
+        .to_string()
+    }
+}
+
+fn line_counts(before: &str, after: &str) -> (usize, usize) {
+    let diff = TextDiff::from_lines(before, after);
+    diff.iter_all_changes()
+        .fold((0, 0), |(added, removed), change| match change.tag() {
+            ChangeTag::Insert => (added + 1, removed),
+            ChangeTag::Delete => (added, removed + 1),
+            ChangeTag::Equal => (added, removed),
+        })
+}
+
+/// Files whose content now differs from their session-start snapshot, in the
+/// order the session first changed them.
+pub fn changes(session_id: &str) -> Result<Vec<FileChange>> {
+    let snapshots = SessionSnapshots::load(session_id)?;
+    Ok(snapshots
+        .files
+        .into_iter()
+        .filter_map(|file| {
+            let current = std::fs::read_to_string(&file.path).ok();
+            let unchanged = match (&file.original, &current) {
+                (None, None) => true,
+                (None, Some(current)) => current.is_empty(),
+                (Some(original), current) => current.as_ref() == Some(original),
+            };
+            (!unchanged).then(|| FileChange::new(file.path, file.original, current))
+        })
+        .collect())
+}
+
+/// `path` relative to `base` when it lies below it.
+pub fn display_path(path: &Path, base: Option<&Path>) -> String {
+    base.and_then(|base| path.strip_prefix(base).ok())
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+/// Numbered file list with added/removed line counts.
+pub fn format_summary(changes: &[FileChange], base: Option<&Path>) -> String {
+    if changes.is_empty() {
+        return "No files changed this session.".to_string();
+    }
+    let added: usize = changes.iter().map(|change| change.added).sum();
+    let removed: usize = changes.iter().map(|change| change.removed).sum();
+    let mut out = format!(
+        "{} file{} changed, +{} -{}",
+        changes.len(),
+        if changes.len() == 1 { "" } else { "s" },
+        added,
+        removed
+    );
+    for (index, change) in changes.iter().enumerate() {
+        out.push_str(&format!(
+            "\n{:>3}. {} (+{} -{}, {})",
+            index + 1,
+            display_path(&change.path, base),
+            change.added,
+            change.removed,
+            change.status_label()
+        ));
+    }
+    out
+}
+
+/// Put `change.path` back to its session-start content, deleting files the
+/// session created.
+pub fn revert(change: &FileChange) -> Result<()> {
+    write_back(&change.path, change.original.as_deref())
+}
+
+/// Paths grouped by the git repository that contains them. Files outside a
+/// repository are returned separately.
+fn group_by_repo(changes: &[FileChange]) -> (BTreeMap<PathBuf, Vec<PathBuf>>, Vec<PathBuf>) {
+    let mut repos: BTreeMap<PathBuf, Vec<PathBuf>> = BTreeMap::new();
+    let mut outside = Vec::new();
+    for change in changes {
+        match change.path.parent().and_then(repo_root) {
+            Some(root) => repos.entry(root).or_default().push(change.path.clone()),
+            None => outside.push(change.path.clone()),
+        }
+    }
+    (repos, outside)
+}
+
+fn repo_root(dir: &Path) -> Option<PathBuf> {
+    // Deleted files may leave their directory behind or not; walk up to the
+    // nearest existing ancestor.
+    let dir = dir.ancestors().find(|dir| dir.is_dir())?;
+    let output = Command::new("git")
+        .args(["rev-parse", "--show-toplevel"])
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| PathBuf::from(String::from_utf8_lossy(&output.stdout).trim()))
+}
+
+/// `git add` the changed files in their repositories. Returns how many were
+/// staged and the files outside any repository.
+pub fn stage(changes: &[FileChange]) -> Result<(usize, Vec<PathBuf>)> {
+    let (repos, outside) = group_by_repo(changes);
+    let mut staged = 0;
+    for (root, paths) in &repos {
+        let output = Command::new("git")
+            .args(["add", "-A", "--"])
+            .args(paths)
+            .current_dir(root)
+            .output()
+            .context("failed to run git add")?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "git add failed in {}: {}",
+                root.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        staged += paths.len();
+    }
+    Ok((staged, outside))
+}
+
+/// Commit the changed files, one commit per repository, leaving whatever
+/// else is staged alone. Returns the commits and the files outside any
+/// repository.
+pub fn commit(session_id: &str, changes: &[FileChange]) -> Result<(Vec<AutoCommit>, Vec<PathBuf>)> {
+    let (repos, outside) = group_by_repo(changes);
+    let message = commit_message(session_id);
+    let mut commits = Vec::new();
+    for (root, paths) in &repos {
+        if let Some(commit) = git_auto_commit::commit_paths(root, paths, &message, None)? {
+            commits.push(commit);
+        }
+    }
+    Ok((commits, outside))
+}
+
+/// The session's title (or first prompt) as the subject, then the tool calls
+/// it made and its id.
+fn commit_message(session_id: &str) -> String {
+    let Ok(session) = Session::load(session_id) else {
+        return git_auto_commit::commit_message("", &[], session_id);
+    };
+    let first_prompt = session
+        .messages
+        .iter()
+        .filter(|message| message.role == Role::User && message.display_role.is_none())
+        .flat_map(|message| message.content.iter())
+        .find_map(|block| match block {
+            ContentBlock::Text { text, .. } if !text.trim().is_empty() => Some(text.as_str()),
+            _ => None,
+        });
+    let subject = session
+        .title
+        .as_deref()
+        .or(first_prompt)
+        .unwrap_or_default();
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    let names = session
+        .messages
+        .iter()
+        .flat_map(|message| message.content.iter())
+        .filter_map(|block| match block {
+            ContentBlock::ToolUse { name, .. } => Some(name),
+            _ => None,
+        });
+    for name in names {
+        match counts.iter_mut().find(|(known, _)| known == name) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((name.clone(), 1)),
+        }
+    }
+    git_auto_commit::commit_message(subject, &counts, session_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(original: Option<&str>, current: Option<&str>) -> FileChange {
+        FileChange::new(
+            PathBuf::from("/work/src/lib.rs"),
+            original.map(str::to_string),
+            current.map(str::to_string),
+        )
+    }
+
+    #[test]
+    fn counts_and_labels_follow_the_snapshot() {
+        let modified = change(Some("a\nb\nc\n"), Some("a\nB\nc\nd\n"));
+        assert_eq!((modified.added, modified.removed), (2, 1));
+        assert_eq!(modified.status_label(), "modified");
+        let created = change(None, Some("x\ny\n"));
+        assert_eq!(
+            (created.added, created.removed, created.status_label()),
+            (2, 0, "created")
+        );
+        let deleted = change(Some("x\n"), None);
+        assert_eq!(
+            (deleted.added, deleted.removed, deleted.status_label()),
+            (0, 1, "deleted")
+        );
+    }
+
+    #[test]
+    fn unified_diff_has_headers_and_hunks() {
+        let diff = change(Some("a\nb\n"), Some("a\nc\n")).unified_diff();
+        assert!(diff.starts_with("--- a/work/src/lib.rs\n+++ b/work/src/lib.rs\n@@"));
+        assert!(diff.contains("\n-b\n+c\n"));
+        assert!(
+            change(None, Some("x\n"))
+                .unified_diff()
+                .starts_with("--- /dev/null\n")
+        );
+    }
+
+    #[test]
+    fn captured_files_restore_content_and_remove_created_ones() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let kept = dir.path().join("kept.rs");
+        let created = dir.path().join("created.rs");
+        std::fs::write(&kept, "before\n").expect("write kept");
+
+        let snapshot = capture(&[kept.as_path(), created.as_path()]).expect("capture");
+        assert_eq!(snapshot[0].original.as_deref(), Some("before\n"));
+        assert_eq!(snapshot[1].original, None);
+
+        std::fs::write(&kept, "").expect("truncate kept");
+        std::fs::write(&created, "new\n").expect("write created");
+        for file in &snapshot {
+            restore(file).expect("restore");
+        }
+        assert_eq!(std::fs::read_to_string(&kept).expect("read"), "before\n");
+        assert!(!created.exists());
+    }
+
+    #[test]
+    fn summary_numbers_files_relative_to_base() {
+        let out = format_summary(
+            &[change(Some("a\n"), Some("b\n")), change(None, Some("x\n"))],
+            Some(Path::new("/work")),
+        );
+        assert_eq!(
+            out,
+            "2 files changed, +2 -1\n  1. src/lib.rs (+1 -1, modified)\n  2. src/lib.rs (+1 -0, created)"
+        );
+        assert_eq!(format_summary(&[], None), "No files changed this session.");
+    }
+
+    #[test]
+    fn revert_restores_or_removes() {
+        let dir = tempfile::tempdir().unwrap();
+        let edited = dir.path().join("edited.txt");
+        let created = dir.path().join("created.txt");
+        std::fs::write(&edited, "new\n").unwrap();
+        std::fs::write(&created, "hello\n").unwrap();
+        revert(&FileChange::new(
+            edited.clone(),
+            Some("old\n".to_string()),
+            Some("new\n".to_string()),
+        ))
+        .unwrap();
+        revert(&FileChange::new(
+            created.clone(),
+            None,
+            Some("hello\n".to_string()),
+        ))
+        .unwrap();
+        assert_eq!(std::fs::read_to_string(&edited).unwrap(), "old\n");
+        assert!(!created.exists());
+    }
+}
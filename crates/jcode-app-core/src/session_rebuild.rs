@@ -1,11 +1,12 @@
 use anyhow::Result;
+use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
 use std::process::{Command as ProcessCommand, ExitStatus};
 
 use crate::bus::{Bus, BusEvent, ClientMaintenanceAction, SessionUpdateStatus};
 use crate::{build, update};
 
-pub fn hot_rebuild(session_id: &str) -> Result<()> {
+pub fn hot_rebuild(session_id: &str, full_tests: bool) -> Result<()> {
     let cwd = std::env::current_dir()?;
     let repo_dir =
         build::get_repo_dir().ok_or_else(|| anyhow::anyhow!("Could not find jcode repository"))?;
@@ -13,7 +14,7 @@ pub fn hot_rebuild(session_id: &str) -> Result<()> {
     eprintln!("Rebuilding jcode with session {}...", session_id);
     pull_latest_changes_for_rebuild(&repo_dir);
     run_release_build(&repo_dir)?;
-    run_release_tests(&repo_dir)?;
+    run_release_tests(&repo_dir, full_tests)?;
     install_local_release_with_warning(&repo_dir);
 
     let is_selfdev = jcode_selfdev_types::client_selfdev_requested();
@@ -26,8 +27,8 @@ pub fn hot_rebuild(session_id: &str) -> Result<()> {
     exec_rebuilt_session(&exe, session_id, &cwd, is_selfdev)
 }
 
-pub fn spawn_background_session_rebuild(session_id: String) {
-    std::thread::spawn(move || run_background_session_rebuild(session_id));
+pub fn spawn_background_session_rebuild(session_id: String, full_tests: bool) {
+    std::thread::spawn(move || run_background_session_rebuild(session_id, full_tests));
 }
 
 fn pull_latest_changes_for_rebuild(repo_dir: &Path) {
@@ -46,20 +47,53 @@ fn run_release_build(repo_dir: &Path) -> Result<()> {
     Ok(())
 }
 
-fn run_release_tests(repo_dir: &Path) -> Result<()> {
-    eprintln!("Running tests...");
-    let status =
-        run_cargo_release_step(repo_dir, &["test", "--release", "--", "--test-threads=1"])?;
+fn run_release_tests(repo_dir: &Path, full_tests: bool) -> Result<()> {
+    let selection = rebuild_test_selection(repo_dir, full_tests);
+    eprintln!("Running tests ({})...", selection.summary());
+    let status = run_cargo_release_step(repo_dir, &selection.cargo_test_args())?;
     if !status.success() {
         eprintln!("\n⚠️  Tests failed! Aborting reload to protect your session.");
         eprintln!("Fix the failing tests and try /rebuild again.");
         anyhow::bail!("Tests failed - staying on current version");
     }
-    eprintln!("✓ All tests passed");
+    if selection.is_full() {
+        eprintln!("✓ All tests passed");
+    } else {
+        eprintln!("✓ Selected tests passed (use /rebuild --full-tests to run everything)");
+    }
     Ok(())
 }
 
-fn run_cargo_release_step(repo_dir: &Path, args: &[&str]) -> Result<ExitStatus> {
+/// Which tests gate this rebuild: the full suite when forced or disabled in
+/// `[rebuild]`, otherwise the subset affected since the last promoted build.
+fn rebuild_test_selection(repo_dir: &Path, full_tests: bool) -> build::TestSelection {
+    if full_tests {
+        return build::TestSelection::full("--full-tests requested");
+    }
+    let config = &crate::config::config().rebuild;
+    if !config.selective_tests {
+        return build::TestSelection::full("selective tests disabled in [rebuild]");
+    }
+    let mut rules = build::TestSelectionRules::default();
+    if let Some(smoke_packages) = &config.smoke_packages {
+        rules.smoke_packages = smoke_packages.clone();
+    }
+    rules
+        .full_suite_paths
+        .extend(config.full_suite_paths.iter().cloned());
+    rules
+        .ignored_paths
+        .extend(config.ignored_paths.iter().cloned());
+    rules.path_packages.extend(
+        config
+            .test_map
+            .iter()
+            .map(|(path, packages)| (path.clone(), packages.clone())),
+    );
+    build::select_rebuild_tests(repo_dir, &rules)
+}
+
+fn run_cargo_release_step(repo_dir: &Path, args: &[impl AsRef<OsStr>]) -> Result<ExitStatus> {
     Ok(ProcessCommand::new("cargo")
         .args(args)
         .current_dir(repo_dir)
@@ -91,7 +125,7 @@ fn exec_rebuilt_session(exe: &Path, session_id: &str, cwd: &Path, is_selfdev: bo
     Err(anyhow::anyhow!("Failed to exec {:?}: {}", exe, err))
 }
 
-fn run_background_session_rebuild(session_id: String) {
+fn run_background_session_rebuild(session_id: String, full_tests: bool) {
     let publisher = BackgroundRebuildPublisher::new(session_id);
     let Some(repo_dir) = build::get_repo_dir() else {
         publisher.error("Rebuild failed: could not find the jcode repository.");
@@ -102,7 +136,7 @@ fn run_background_session_rebuild(session_id: String) {
     if !background_release_build(&publisher, &repo_dir) {
         return;
     }
-    if !background_release_tests(&publisher, &repo_dir) {
+    if !background_release_tests(&publisher, &repo_dir, full_tests) {
         return;
     }
     background_install_local_release(&publisher, &repo_dir);
@@ -184,16 +218,23 @@ fn background_release_build(publisher: &BackgroundRebuildPublisher, repo_dir: &P
     true
 }
 
-fn background_release_tests(publisher: &BackgroundRebuildPublisher, repo_dir: &Path) -> bool {
-    publisher.status("Running release tests in the background...");
-    let status =
-        match run_cargo_release_step(repo_dir, &["test", "--release", "--", "--test-threads=1"]) {
-            Ok(status) => status,
-            Err(error) => {
-                publisher.error(format!("Rebuild failed while starting tests: {}", error));
-                return false;
-            }
-        };
+fn background_release_tests(
+    publisher: &BackgroundRebuildPublisher,
+    repo_dir: &Path,
+    full_tests: bool,
+) -> bool {
+    let selection = rebuild_test_selection(repo_dir, full_tests);
+    publisher.status(format!(
+        "Running release tests in the background ({})...",
+        selection.summary()
+    ));
+    let status = match run_cargo_release_step(repo_dir, &selection.cargo_test_args()) {
+        Ok(status) => status,
+        Err(error) => {
+            publisher.error(format!("Rebuild failed while starting tests: {}", error));
+            return false;
+        }
+    };
 
     if !status.success() {
         publisher.error(
@@ -1,5 +1,5 @@
 use std::sync::Mutex;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 static PROFILE: Mutex<Option<StartupProfile>> = Mutex::new(None);
 
@@ -26,6 +26,13 @@ pub fn init() {
     *guard = Some(StartupProfile::new());
 }
 
+/// Time since [`init`] ran at process start. Uses `try_lock` so a panic hook
+/// can call it safely.
+pub fn uptime() -> Option<Duration> {
+    let guard = PROFILE.try_lock().ok()?;
+    guard.as_ref().map(|profile| profile.start.elapsed())
+}
+
 pub fn mark(name: &str) {
     if let Ok(mut guard) = PROFILE.lock()
         && let Some(ref mut profile) = *guard
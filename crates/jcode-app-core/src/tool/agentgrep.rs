@@ -20,6 +20,7 @@ use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
 mod args;
+mod ast;
 mod context;
 mod render;
 
@@ -29,13 +30,15 @@ use self::args::{
     build_find_args, build_grep_args, build_outline_args, build_smart_args_and_query,
     resolve_search_root, summarize_agentgrep_request,
 };
+use self::ast::run_ast_search;
 use self::context::maybe_write_context_json;
 #[cfg(test)]
 use self::context::{
     collect_bash_exposure, collect_trace_exposure, tune_known_file, tune_known_region,
 };
 use self::render::{
-    render_find_output, render_grep_output, render_outline_output, render_smart_output,
+    SearchRenderOptions, render_ast_output, render_find_output, render_grep_output,
+    render_outline_output, render_smart_output,
 };
 
 #[derive(Debug, Deserialize)]
@@ -74,6 +77,14 @@ struct AgentGrepInput {
     debug_score: Option<bool>,
     #[serde(default)]
     paths_only: Option<bool>,
+    #[serde(default)]
+    exclude: Option<Vec<String>>,
+    #[serde(default)]
+    context_lines: Option<usize>,
+    #[serde(default)]
+    group_by_file: Option<bool>,
+    #[serde(default)]
+    max_results: Option<usize>,
 }
 
 fn default_agentgrep_mode() -> String {
@@ -177,7 +188,7 @@ impl Tool for AgentGrepTool {
     }
 
     fn description(&self) -> &str {
-        "Search code and file names. Defaults to grep mode when mode is omitted."
+        "Search code and file names. Defaults to grep mode when mode is omitted; ast mode matches code structure."
     }
 
     fn parameters_schema(&self) -> Value {
@@ -187,12 +198,12 @@ impl Tool for AgentGrepTool {
                 "intent": super::intent_schema_property(),
                 "mode": {
                     "type": "string",
-                    "enum": ["grep", "find", "outline", "trace"],
-                    "description": "Optional search mode. Defaults to grep. Use grep for normal code/text search, find for file-name/path search, outline to summarize one file, and trace for DSL-based relationship search."
+                    "enum": ["grep", "find", "outline", "trace", "ast"],
+                    "description": "Optional search mode. Defaults to grep. Use grep for normal code/text search, find for file-name/path search, outline to summarize one file, trace for DSL-based relationship search, and ast for structural code patterns in Rust, TypeScript/JavaScript and Python."
                 },
                 "query": {
                     "type": "string",
-                    "description": "Search query. Required for grep. For find, provide query terms to rank matching file paths, or omit query when path, glob, or type already narrows the file list. Grep treats query as literal text unless regex=true."
+                    "description": "Search query. Required for grep and ast. For find, provide query terms to rank matching file paths, or omit query when path, glob, or type already narrows the file list. Grep treats query as literal text unless regex=true. For ast, query is a code pattern where $NAME matches one token and $$$ matches any balanced run, e.g. fn $NAME($$$) { $$$ }."
                 },
                 "file": {
                     "type": "string",
@@ -215,6 +226,11 @@ impl Tool for AgentGrepTool {
                     "type": "string",
                     "description": "Optional file glob filter such as **/*.rs. Do not set glob to **/* just to search everything; omit it instead."
                 },
+                "exclude": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Globs for files to leave out of grep/ast results, such as **/tests/** or *.min.js. A glob without / also matches any single path component."
+                },
                 "type": {
                     "type": "string",
                     "description": "Optional ripgrep file type filter, such as rs, py, js, ts, or md."
@@ -227,6 +243,18 @@ impl Tool for AgentGrepTool {
                     "type": "integer",
                     "description": "Maximum number of matching regions to return."
                 },
+                "max_results": {
+                    "type": "integer",
+                    "description": "Maximum number of grep/ast matches to show, taken in path order. Large result sets without it come back as per-file counts."
+                },
+                "context_lines": {
+                    "type": "integer",
+                    "description": "Lines of surrounding code to show around each grep/ast match. Defaults to 0."
+                },
+                "group_by_file": {
+                    "type": "boolean",
+                    "description": "Group grep/ast matches under each file with per-file counts (default true). False gives one path:line: text entry per match."
+                },
                 "paths_only": {
                     "type": "boolean",
                     "description": "Return only matching paths instead of match excerpts where supported."
@@ -295,6 +323,12 @@ fn execute_linked_agentgrep(
     ctx: &ToolContext,
     context_json_path: Option<&Path>,
 ) -> Result<ToolOutput> {
+    if params.path.is_none()
+        && !ctx.workspace_roots.is_empty()
+        && matches!(params.mode.as_str(), "grep" | "find" | "ast")
+    {
+        return execute_across_workspace_roots(params, ctx, context_json_path);
+    }
     let exact_file = exact_search_file_path(ctx, params.path.as_deref());
     match params.mode.as_str() {
         "grep" => {
@@ -304,10 +338,19 @@ fn execute_linked_agentgrep(
                 run_grep(&root, &args).map_err(anyhow::Error::msg)?,
                 exact_file.as_deref(),
             );
-            Ok(
-                ToolOutput::new(render_grep_output(&result, &args, params.max_regions))
-                    .with_title("agentgrep grep"),
-            )
+            let result = filter_grep_result_excluding(result, &exclude_patterns(params)?);
+            let rendered =
+                render_grep_output(&result, &args, &search_render_options(params, &root));
+            Ok(ToolOutput::new(rendered.text)
+                .with_title("agentgrep grep")
+                .with_metadata(rendered.metadata))
+        }
+        "ast" => {
+            let (result, root) = run_ast_search(params, ctx, exact_file.as_deref())?;
+            let rendered = render_ast_output(&result, &search_render_options(params, &root));
+            Ok(ToolOutput::new(rendered.text)
+                .with_title("agentgrep ast")
+                .with_metadata(rendered.metadata))
         }
         "find" => {
             let args = build_find_args(params, ctx)?;
@@ -333,12 +376,36 @@ fn execute_linked_agentgrep(
                 .with_title(format!("agentgrep {}", params.mode)))
         }
         _ => Err(anyhow::anyhow!(
-            "Unsupported agentgrep mode: {}. Use grep, find, outline, or trace.",
+            "Unsupported agentgrep mode: {}. Use grep, find, outline, trace, or ast.",
             params.mode
         )),
     }
 }
 
+/// Multi-root sessions: an unscoped grep/find searches every workspace root and
+/// reports results under a per-root heading.
+fn execute_across_workspace_roots(
+    params: &AgentGrepInput,
+    ctx: &ToolContext,
+    context_json_path: Option<&Path>,
+) -> Result<ToolOutput> {
+    let mut sections = Vec::new();
+    for root in ctx.search_roots() {
+        let mut root_ctx = ctx.clone();
+        root_ctx.working_dir = Some(root.clone());
+        root_ctx.workspace_roots.clear();
+        let output = execute_linked_agentgrep(params, &root_ctx, context_json_path)?;
+        let name = super::workspace_root_name(&root).unwrap_or_else(|| root.display().to_string());
+        sections.push(format!(
+            "## {} ({})\n{}",
+            name,
+            root.display(),
+            output.output.trim_end()
+        ));
+    }
+    Ok(ToolOutput::new(sections.join("\n\n")).with_title(format!("agentgrep {}", params.mode)))
+}
+
 fn resolve_path_arg(ctx: &ToolContext, path: &str) -> PathBuf {
     ctx.resolve_path(Path::new(path))
 }
@@ -368,6 +435,58 @@ fn filter_grep_result_to_exact_file(
     result
 }
 
+/// Drop files matching any `exclude` glob and order the rest by path, so
+/// `max_results` always cuts the same matches.
+fn filter_grep_result_excluding(mut result: GrepResult, exclude: &[glob::Pattern]) -> GrepResult {
+    result
+        .files
+        .retain(|file| !is_excluded(&file.path, exclude));
+    result.files.sort_by(|a, b| a.path.cmp(&b.path));
+    result.total_files = result.files.len();
+    result.total_matches = result.files.iter().map(|file| file.matches.len()).sum();
+    result
+}
+
+fn exclude_patterns(params: &AgentGrepInput) -> Result<Vec<glob::Pattern>> {
+    params
+        .exclude
+        .iter()
+        .flatten()
+        .map(|pattern| pattern.trim())
+        .filter(|pattern| !pattern.is_empty())
+        .map(|pattern| {
+            glob::Pattern::new(pattern)
+                .map_err(|err| anyhow::anyhow!("invalid exclude glob '{pattern}': {err}"))
+        })
+        .collect()
+}
+
+/// Whether `path` matches an exclude glob, either as a whole or, for globs
+/// without a `/`, in any single component (`tests`, `*.snap`).
+fn is_excluded(path: &str, exclude: &[glob::Pattern]) -> bool {
+    exclude.iter().any(|pattern| {
+        pattern.matches(path)
+            || (!pattern.as_str().contains('/')
+                && Path::new(path)
+                    .components()
+                    .any(|component| pattern.matches(&component.as_os_str().to_string_lossy())))
+    })
+}
+
+fn search_render_options<'a>(params: &AgentGrepInput, root: &'a Path) -> SearchRenderOptions<'a> {
+    SearchRenderOptions {
+        root,
+        max_results: params.max_results.or(params.max_regions),
+        limit_param: if params.max_results.is_some() {
+            "max_results"
+        } else {
+            "max_regions"
+        },
+        context_lines: params.context_lines.unwrap_or(0),
+        group_by_file: params.group_by_file.unwrap_or(true),
+    }
+}
+
 fn filter_find_result_to_exact_file(
     mut result: FindResult,
     exact_file: Option<&str>,
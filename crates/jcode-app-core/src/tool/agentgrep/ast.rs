@@ -0,0 +1,562 @@
+//! `mode: "ast"`: structural search with ast-grep-style patterns.
+//!
+//! Patterns are written in the target language with metavariables:
+//! `$NAME` matches one token and `$$$` (or `$$$ARGS`) matches any
+//! bracket-balanced run of tokens, so `fn $NAME($$$) { $$$ }` matches every
+//! Rust function with a body. Matching works on tokens rather than a full
+//! syntax tree: comments and whitespace are ignored, strings are single
+//! tokens, and brackets must nest. A metavariable used twice must bind the
+//! same text both times.
+//!
+//! Candidate files come from a literal grep for the pattern's most specific
+//! token, so `path`, `glob`, `type` and ignore rules behave as in grep mode.
+
+use super::*;
+use std::collections::hash_map::Entry;
+
+/// Upper bound on matcher steps per start token, so a pathological pattern
+/// cannot stall the search.
+const MAX_MATCH_STEPS: usize = 20_000;
+/// Longest token run one `$$$` may cover.
+const MAX_MULTI_TOKENS: usize = 20_000;
+
+/// Structural matches in one file.
+#[derive(Debug)]
+pub(super) struct AstFileMatches {
+    pub(super) path: String,
+    pub(super) language: AstLanguage,
+    pub(super) matches: Vec<AstMatch>,
+}
+
+#[derive(Debug)]
+pub(super) struct AstSearchResult {
+    pub(super) pattern: String,
+    pub(super) files: Vec<AstFileMatches>,
+    pub(super) total_matches: usize,
+    /// Candidate files skipped because ast mode does not support their language.
+    pub(super) unsupported_files: usize,
+}
+
+/// Run an ast-mode search and return it with the root its paths are
+/// relative to.
+pub(super) fn run_ast_search(
+    params: &AgentGrepInput,
+    ctx: &ToolContext,
+    exact_file: Option<&str>,
+) -> Result<(AstSearchResult, PathBuf)> {
+    let query = params
+        .query
+        .as_deref()
+        .filter(|query| !query.trim().is_empty())
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "agentgrep ast requires 'query' with a code pattern, e.g. fn $NAME($$$) {{ $$$ }}"
+            )
+        })?;
+    let type_language = match params.file_type.as_deref() {
+        Some(file_type) => Some(AstLanguage::from_file_type(file_type).ok_or_else(|| {
+            anyhow::anyhow!(
+                "agentgrep ast supports rust, typescript/javascript and python; got type {file_type}"
+            )
+        })?),
+        None => None,
+    };
+    let mut patterns = HashMap::new();
+    let anchor_language = type_language.unwrap_or(AstLanguage::Rust);
+    let anchor = AstPattern::parse(query, anchor_language)?
+        .anchor()
+        .to_string();
+
+    let mut args = build_grep_args(params, ctx)?;
+    args.query = anchor;
+    args.regex = false;
+    args.paths_only = true;
+    let root = resolve_search_root(ctx, args.path.as_deref());
+    let candidates = filter_grep_result_excluding(
+        filter_grep_result_to_exact_file(
+            run_grep(&root, &args).map_err(anyhow::Error::msg)?,
+            exact_file,
+        ),
+        &exclude_patterns(params)?,
+    );
+
+    let mut result = AstSearchResult {
+        pattern: query.to_string(),
+        files: Vec::new(),
+        total_matches: 0,
+        unsupported_files: 0,
+    };
+    for candidate in &candidates.files {
+        let Some(language) = AstLanguage::from_path(&candidate.path) else {
+            result.unsupported_files += 1;
+            continue;
+        };
+        let pattern = match patterns.entry(language) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(AstPattern::parse(query, language)?),
+        };
+        let Ok(source) = std::fs::read_to_string(root.join(&candidate.path)) else {
+            continue;
+        };
+        let matches = find_matches(pattern, &source, language);
+        if matches.is_empty() {
+            continue;
+        }
+        result.total_matches += matches.len();
+        result.files.push(AstFileMatches {
+            path: candidate.path.clone(),
+            language,
+            matches,
+        });
+    }
+    Ok((result, root))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) enum AstLanguage {
+    Rust,
+    TypeScript,
+    Python,
+}
+
+impl AstLanguage {
+    pub(super) fn from_path(path: &str) -> Option<Self> {
+        let ext = Path::new(path).extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "rs" => Some(Self::Rust),
+            "ts" | "tsx" | "mts" | "cts" | "js" | "jsx" | "mjs" | "cjs" => Some(Self::TypeScript),
+            "py" | "pyi" => Some(Self::Python),
+            _ => None,
+        }
+    }
+
+    /// The language named by a ripgrep-style `type` filter.
+    fn from_file_type(file_type: &str) -> Option<Self> {
+        match file_type.trim().to_ascii_lowercase().as_str() {
+            "rs" | "rust" => Some(Self::Rust),
+            "ts" | "typescript" | "js" | "javascript" | "tsx" | "jsx" => Some(Self::TypeScript),
+            "py" | "python" => Some(Self::Python),
+            _ => None,
+        }
+    }
+
+    pub(super) fn as_str(self) -> &'static str {
+        match self {
+            Self::Rust => "rust",
+            Self::TypeScript => "typescript",
+            Self::Python => "python",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Token<'a> {
+    text: &'a str,
+    start: usize,
+    line: usize,
+}
+
+/// Operators kept as one token, longest first.
+const MULTI_CHAR_PUNCT: &[&str] = &[
+    "...", "===", "!==", "**=", "::", "->", "=>", "==", "!=", "<=", ">=", "&&", "||", "+=", "-=",
+    "*=", "/=", "..", "**", "?.", "??",
+];
+
+/// Split `source` into tokens, skipping whitespace and comments. `$` counts
+/// as an identifier character, which keeps pattern metavariables whole.
+fn tokenize(source: &str, language: AstLanguage) -> Vec<Token<'_>> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    let mut line = 1;
+    while pos < bytes.len() {
+        let byte = bytes[pos];
+        if byte == b'\n' {
+            line += 1;
+            pos += 1;
+            continue;
+        }
+        if byte.is_ascii_whitespace() {
+            pos += 1;
+            continue;
+        }
+        let rest = &source[pos..];
+        let start = pos;
+        let start_line = line;
+
+        if language == AstLanguage::Python && byte == b'#' {
+            pos += rest.find('\n').unwrap_or(rest.len());
+            continue;
+        }
+        if language != AstLanguage::Python && rest.starts_with("//") {
+            pos += rest.find('\n').unwrap_or(rest.len());
+            continue;
+        }
+        if language != AstLanguage::Python && rest.starts_with("/*") {
+            let len = rest[2..].find("*/").map_or(rest.len(), |end| end + 4);
+            line += rest[..len].matches('\n').count();
+            pos += len;
+            continue;
+        }
+
+        let len = if is_ident_start(byte) {
+            let ident_len = rest
+                .bytes()
+                .position(|b| !is_ident_continue(b))
+                .unwrap_or(rest.len());
+            match raw_string_len(&rest[ident_len..], &rest[..ident_len], language) {
+                Some(raw_len) => ident_len + raw_len,
+                None => ident_len,
+            }
+        } else if byte.is_ascii_digit() {
+            number_len(rest)
+        } else if byte == b'"' || byte == b'`' {
+            quoted_len(rest, language)
+        } else if byte == b'\'' {
+            if language == AstLanguage::Rust {
+                rust_quote_len(rest)
+            } else {
+                quoted_len(rest, language)
+            }
+        } else {
+            MULTI_CHAR_PUNCT
+                .iter()
+                .find(|punct| rest.starts_with(**punct))
+                .map_or_else(
+                    || rest.chars().next().map_or(1, char::len_utf8),
+                    |p| p.len(),
+                )
+        };
+
+        let text = &source[start..start + len];
+        line += text.matches('\n').count();
+        tokens.push(Token {
+            text,
+            start,
+            line: start_line,
+        });
+        pos += len;
+    }
+    tokens
+}
+
+fn is_ident_start(byte: u8) -> bool {
+    byte.is_ascii_alphabetic() || byte == b'_' || byte == b'$' || byte >= 0x80
+}
+
+fn is_ident_continue(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'$' || byte >= 0x80
+}
+
+/// Rust raw strings (`r"…"`, `r#"…"#`, `br"…"`) following the identifier
+/// `prefix`; returns the length after the prefix.
+fn raw_string_len(rest: &str, prefix: &str, language: AstLanguage) -> Option<usize> {
+    if language != AstLanguage::Rust || !matches!(prefix, "r" | "br" | "cr") {
+        return None;
+    }
+    let hashes = rest.bytes().take_while(|b| *b == b'#').count();
+    if rest.as_bytes().get(hashes) != Some(&b'"') {
+        return None;
+    }
+    let closing = format!("\"{}", "#".repeat(hashes));
+    let body = &rest[hashes + 1..];
+    Some(
+        body.find(&closing)
+            .map_or(rest.len(), |end| hashes + 1 + end + closing.len()),
+    )
+}
+
+fn number_len(rest: &str) -> usize {
+    let bytes = rest.as_bytes();
+    let mut len = 0;
+    while len < bytes.len() {
+        let byte = bytes[len];
+        let decimal_point =
+            byte == b'.' && bytes.get(len + 1).is_some_and(|next| next.is_ascii_digit());
+        if byte.is_ascii_alphanumeric() || byte == b'_' || decimal_point {
+            len += 1;
+        } else {
+            break;
+        }
+    }
+    len
+}
+
+/// A quoted string starting at `rest`, including Python triple quotes.
+fn quoted_len(rest: &str, language: AstLanguage) -> usize {
+    let quote = rest.as_bytes()[0];
+    if language == AstLanguage::Python {
+        let triple = if quote == b'"' { "\"\"\"" } else { "'''" };
+        if rest.starts_with(triple) {
+            return rest[3..].find(triple).map_or(rest.len(), |end| end + 6);
+        }
+    }
+    let bytes = rest.as_bytes();
+    let mut len = 1;
+    while len < bytes.len() {
+        match bytes[len] {
+            b'\\' => len += 2,
+            b'\n' if quote != b'`' && language == AstLanguage::Python => return len,
+            byte if byte == quote => return len + 1,
+            _ => len += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// A Rust char literal (`'a'`, `'\n'`) or lifetime (`'a`).
+fn rust_quote_len(rest: &str) -> usize {
+    let mut chars = rest.char_indices().skip(1);
+    match chars.next() {
+        Some((_, '\\')) => quoted_len(rest, AstLanguage::Rust),
+        Some((index, c)) => {
+            let after = index + c.len_utf8();
+            if rest[after..].starts_with('\'') {
+                return after + 1;
+            }
+            let ident_len = rest[1..]
+                .bytes()
+                .position(|b| !is_ident_continue(b))
+                .unwrap_or(rest.len() - 1);
+            1 + ident_len.max(1)
+        }
+        None => 1,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PatternToken {
+    Literal(String),
+    /// `$NAME`: exactly one token. `$_` binds nothing.
+    Var(String),
+    /// `$$$` or `$$$NAME`: any balanced run of tokens, possibly empty.
+    Multi(Option<String>),
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct AstPattern {
+    tokens: Vec<PatternToken>,
+}
+
+impl AstPattern {
+    pub(super) fn parse(pattern: &str, language: AstLanguage) -> Result<Self> {
+        let tokens: Vec<PatternToken> = tokenize(pattern, language)
+            .into_iter()
+            .map(|token| pattern_token(token.text))
+            .collect();
+        match tokens.first() {
+            None => anyhow::bail!("agentgrep ast requires a non-empty pattern in 'query'"),
+            Some(PatternToken::Multi(_)) => anyhow::bail!(
+                "agentgrep ast patterns cannot start with $$$; start with code or a $NAME metavariable"
+            ),
+            Some(_) => {}
+        }
+        if !tokens
+            .iter()
+            .any(|token| matches!(token, PatternToken::Literal(_)))
+        {
+            anyhow::bail!(
+                "agentgrep ast patterns need at least one literal token besides metavariables"
+            );
+        }
+        Ok(Self { tokens })
+    }
+
+    /// The most specific literal token, used to find candidate files.
+    pub(super) fn anchor(&self) -> &str {
+        self.tokens
+            .iter()
+            .filter_map(|token| match token {
+                PatternToken::Literal(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .max_by_key(|text| {
+                let is_word = text.bytes().next().is_some_and(is_ident_start);
+                (is_word, text.len())
+            })
+            .unwrap_or_default()
+    }
+}
+
+fn pattern_token(text: &str) -> PatternToken {
+    if let Some(name) = text.strip_prefix("$$$") {
+        if name.is_empty() || is_metavar_name(name) {
+            return PatternToken::Multi((!name.is_empty()).then(|| name.to_string()));
+        }
+    } else if let Some(name) = text.strip_prefix('$')
+        && is_metavar_name(name)
+    {
+        return PatternToken::Var(name.to_string());
+    }
+    PatternToken::Literal(text.to_string())
+}
+
+/// Metavariable names are upper case, as in ast-grep, so `$scope` in
+/// JavaScript stays a literal.
+fn is_metavar_name(name: &str) -> bool {
+    name == "_"
+        || (name.bytes().next().is_some_and(|b| b.is_ascii_uppercase())
+            && name
+                .bytes()
+                .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || b == b'_'))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct AstMatch {
+    pub(super) start_line: usize,
+    pub(super) end_line: usize,
+    /// First line of the match as written in the source.
+    pub(super) line_text: String,
+    pub(super) captures: Vec<(String, String)>,
+}
+
+/// All matches of `pattern` in `source`, in source order. Matches may nest
+/// (a function inside a function) but never start at the same token.
+pub(super) fn find_matches(
+    pattern: &AstPattern,
+    source: &str,
+    language: AstLanguage,
+) -> Vec<AstMatch> {
+    let tokens = tokenize(source, language);
+    let mut matches = Vec::new();
+    for start in 0..tokens.len() {
+        let mut matcher = Matcher {
+            pattern: &pattern.tokens,
+            tokens: &tokens,
+            bindings: Vec::new(),
+            steps: 0,
+        };
+        let Some(end) = matcher.match_from(0, start) else {
+            continue;
+        };
+        let first = tokens[start];
+        let last = tokens[end - 1];
+        let line_start = source[..first.start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[first.start..]
+            .find('\n')
+            .map_or(source.len(), |i| first.start + i);
+        let captures = matcher
+            .bindings
+            .iter()
+            .map(|(name, from, to)| (name.clone(), span_text(source, &tokens, *from, *to)))
+            .collect();
+        matches.push(AstMatch {
+            start_line: first.line,
+            end_line: last.line + last.text.matches('\n').count(),
+            line_text: source[line_start..line_end].trim_end().to_string(),
+            captures,
+        });
+    }
+    matches
+}
+
+/// Source text covered by tokens `from..to`.
+fn span_text(source: &str, tokens: &[Token<'_>], from: usize, to: usize) -> String {
+    if from >= to {
+        return String::new();
+    }
+    let last = tokens[to - 1];
+    source[tokens[from].start..last.start + last.text.len()].to_string()
+}
+
+struct Matcher<'p, 't, 's> {
+    pattern: &'p [PatternToken],
+    tokens: &'t [Token<'s>],
+    /// Bound metavariables as token ranges.
+    bindings: Vec<(String, usize, usize)>,
+    steps: usize,
+}
+
+impl Matcher<'_, '_, '_> {
+    /// Match `pattern[pi..]` starting at token `ti`; returns the end token.
+    fn match_from(&mut self, pi: usize, ti: usize) -> Option<usize> {
+        self.steps += 1;
+        if self.steps > MAX_MATCH_STEPS {
+            return None;
+        }
+        let Some(expected) = self.pattern.get(pi) else {
+            return Some(ti);
+        };
+        match expected {
+            PatternToken::Literal(text) => {
+                let token = self.tokens.get(ti)?;
+                if token.text != text {
+                    return None;
+                }
+                self.match_from(pi + 1, ti + 1)
+            }
+            PatternToken::Var(name) => {
+                let token = self.tokens.get(ti)?;
+                if is_open(token.text) || is_close(token.text) || matches!(token.text, "," | ";") {
+                    return None;
+                }
+                self.with_binding(name, ti, ti + 1, |this| this.match_from(pi + 1, ti + 1))
+            }
+            PatternToken::Multi(name) => {
+                let mut depth = 0usize;
+                let mut end = ti;
+                loop {
+                    if depth == 0 {
+                        let result = match name {
+                            Some(name) => self
+                                .with_binding(name, ti, end, |this| this.match_from(pi + 1, end)),
+                            None => self.match_from(pi + 1, end),
+                        };
+                        if result.is_some() {
+                            return result;
+                        }
+                    }
+                    let token = self.tokens.get(end)?;
+                    if is_open(token.text) {
+                        depth += 1;
+                    } else if is_close(token.text) {
+                        depth = depth.checked_sub(1)?;
+                    }
+                    end += 1;
+                    if end - ti > MAX_MULTI_TOKENS || self.steps > MAX_MATCH_STEPS {
+                        return None;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Bind `name` to tokens `from..to` for the rest of the match, or check
+    /// it against an earlier binding.
+    fn with_binding(
+        &mut self,
+        name: &str,
+        from: usize,
+        to: usize,
+        rest: impl FnOnce(&mut Self) -> Option<usize>,
+    ) -> Option<usize> {
+        if name == "_" {
+            return rest(self);
+        }
+        if let Some((bound_from, bound_to)) = self
+            .bindings
+            .iter()
+            .find(|(bound, _, _)| bound == name)
+            .map(|(_, from, to)| (*from, *to))
+        {
+            let same = bound_to - bound_from == to - from
+                && (0..to - from)
+                    .all(|i| self.tokens[bound_from + i].text == self.tokens[from + i].text);
+            return if same { rest(self) } else { None };
+        }
+        self.bindings.push((name.to_string(), from, to));
+        let result = rest(self);
+        if result.is_none() {
+            self.bindings.pop();
+        }
+        result
+    }
+}
+
+fn is_open(text: &str) -> bool {
+    matches!(text, "(" | "[" | "{")
+}
+
+fn is_close(text: &str) -> bool {
+    matches!(text, ")" | "]" | "}")
+}
@@ -1,21 +1,64 @@
+use super::ast::{AstMatch, AstSearchResult};
 use super::*;
 
 const MAX_RENDERED_MATCH_LINE_CHARS: usize = 240;
 const RENDERED_MATCH_PREFIX_CONTEXT_CHARS: usize = 80;
 const MAX_NON_CODE_MATCH_LINES_PER_FILE: usize = 3;
 
+/// Result sets with more matches than this come back as per-file counts
+/// unless `max_results` asks for a window of them.
+pub(super) const SUMMARY_MIN_MATCHES: usize = 300;
+/// Files listed in a per-file count summary.
+const MAX_SUMMARY_FILES: usize = 50;
+/// Characters of a metavariable binding shown in ast output.
+const MAX_RENDERED_CAPTURE_CHARS: usize = 80;
+
+/// How grep and ast results are laid out.
+pub(super) struct SearchRenderOptions<'a> {
+    /// Root the result paths are relative to, for reading context lines.
+    pub(super) root: &'a Path,
+    pub(super) max_results: Option<usize>,
+    /// Parameter named in the truncation note.
+    pub(super) limit_param: &'static str,
+    pub(super) context_lines: usize,
+    pub(super) group_by_file: bool,
+}
+
+/// Human-readable output plus the same result as JSON metadata.
+pub(super) struct RenderedSearch {
+    pub(super) text: String,
+    pub(super) metadata: Value,
+}
+
 pub(super) fn render_grep_output(
     result: &GrepResult,
     args: &GrepArgs,
-    max_matches: Option<usize>,
-) -> String {
+    options: &SearchRenderOptions<'_>,
+) -> RenderedSearch {
+    let counts = result
+        .files
+        .iter()
+        .map(|file| (file.path.as_str(), file.matches.len()))
+        .collect::<Vec<_>>();
     if args.paths_only {
-        return result
-            .files
-            .iter()
-            .map(|file| file.path.clone())
-            .collect::<Vec<_>>()
-            .join("\n");
+        return RenderedSearch {
+            text: result
+                .files
+                .iter()
+                .map(|file| file.path.clone())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            metadata: search_metadata("grep", &result.query, result.total_matches, &counts, None),
+        };
+    }
+    if options.max_results.is_none() && result.total_matches > SUMMARY_MIN_MATCHES {
+        return render_match_summary(
+            "grep",
+            "query",
+            &result.query,
+            result.total_matches,
+            &counts,
+        );
     }
 
     let mut lines = vec![
@@ -25,39 +68,59 @@ pub(super) fn render_grep_output(
             result.total_matches, result.total_files
         ),
     ];
-    let mut state = GrepRenderState::new(max_matches);
+    let mut state = GrepRenderState::new(options.max_results, result.files.len());
+    if !options.group_by_file {
+        lines.push(String::new());
+    }
 
-    for file in &result.files {
+    for (index, file) in result.files.iter().enumerate() {
         if state.limit_reached() {
             break;
         }
-        render_grep_file(file, args, &mut lines, &mut state);
+        if options.group_by_file {
+            render_grep_file(index, file, args, options, &mut lines, &mut state);
+        } else {
+            render_grep_file_flat(index, file, args, options, &mut lines, &mut state);
+        }
     }
 
-    if let Some(max) = max_matches
+    if let Some(max) = options.max_results
         && result.total_matches > state.displayed_matches
     {
         lines.push(String::new());
         lines.push(format!(
-            "... {} more matches omitted (max_regions={})",
+            "... {} more matches omitted ({}={})",
             result.total_matches.saturating_sub(state.displayed_matches),
+            options.limit_param,
             max
         ));
     }
 
-    lines.join("\n")
+    RenderedSearch {
+        text: lines.join("\n"),
+        metadata: search_metadata(
+            "grep",
+            &result.query,
+            result.total_matches,
+            &counts,
+            Some(&state.shown_lines),
+        ),
+    }
 }
 
 struct GrepRenderState {
     displayed_matches: usize,
     max_matches: Option<usize>,
+    /// Line numbers shown for each file, by file index.
+    shown_lines: Vec<Vec<usize>>,
 }
 
 impl GrepRenderState {
-    fn new(max_matches: Option<usize>) -> Self {
+    fn new(max_matches: Option<usize>, file_count: usize) -> Self {
         Self {
             displayed_matches: 0,
             max_matches,
+            shown_lines: vec![Vec::new(); file_count],
         }
     }
 
@@ -72,19 +135,34 @@ impl GrepRenderState {
             .unwrap_or(usize::MAX)
     }
 
-    fn record_match(&mut self) {
+    fn record_match(&mut self, file_index: usize, line_number: usize) {
         self.displayed_matches += 1;
+        self.shown_lines[file_index].push(line_number);
+    }
+}
+
+fn match_count_label(count: usize) -> String {
+    if count == 1 {
+        "1 match".to_string()
+    } else {
+        format!("{count} matches")
     }
 }
 
 fn render_grep_file(
+    index: usize,
     file: &FileMatches,
     args: &GrepArgs,
+    options: &SearchRenderOptions<'_>,
     lines: &mut Vec<String>,
     state: &mut GrepRenderState,
 ) {
     lines.push(String::new());
-    lines.push(file.path.clone());
+    lines.push(format!(
+        "{} ({})",
+        file.path,
+        match_count_label(file.matches.len())
+    ));
     if file.total_symbols > 0 {
         lines.push(format!(
             "  symbols: {} total, {} matched, {} other",
@@ -97,6 +175,11 @@ fn render_grep_file(
     }
     let non_code_cap = non_code_match_cap(file);
     let mut file_displayed_matches = 0usize;
+    let mut context = MatchContext::load(
+        options,
+        &file.path,
+        file.matches.iter().map(|line_match| line_match.line_number),
+    );
 
     for group in &file.groups {
         if state.limit_reached() {
@@ -126,12 +209,18 @@ fn render_grep_file(
         }
         for line_match in visible_matches {
             let line_text = compact_rendered_match_line(&line_match.line_text, args);
+            if let Some(context) = context.as_mut() {
+                context.push_before(line_match.line_number, "          ", lines);
+            }
             lines.push(format!(
                 "      - @ {} {}",
                 line_match.line_number, line_text
             ));
+            if let Some(context) = context.as_mut() {
+                context.push_after(line_match.line_number, "          ", lines);
+            }
             file_displayed_matches += 1;
-            state.record_match();
+            state.record_match(index, line_match.line_number);
         }
     }
     if non_code_cap.is_some()
@@ -165,6 +254,355 @@ fn render_grep_file(
     }
 }
 
+/// `group_by_file: false`: one `path:line: text` entry per match.
+fn render_grep_file_flat(
+    index: usize,
+    file: &FileMatches,
+    args: &GrepArgs,
+    options: &SearchRenderOptions<'_>,
+    lines: &mut Vec<String>,
+    state: &mut GrepRenderState,
+) {
+    let mut context = MatchContext::load(
+        options,
+        &file.path,
+        file.matches.iter().map(|line_match| line_match.line_number),
+    );
+    let context_prefix = format!("{}-", file.path);
+    for line_match in &file.matches {
+        if state.limit_reached() {
+            break;
+        }
+        if let Some(context) = context.as_mut() {
+            context.push_before(line_match.line_number, &context_prefix, lines);
+        }
+        lines.push(format!(
+            "{}:{}: {}",
+            file.path,
+            line_match.line_number,
+            compact_rendered_match_line(&line_match.line_text, args)
+        ));
+        if let Some(context) = context.as_mut() {
+            context.push_after(line_match.line_number, &context_prefix, lines);
+        }
+        state.record_match(index, line_match.line_number);
+    }
+}
+
+/// Source lines around each match of one file, for `context_lines`. Lines
+/// already shown, and other match lines, are not repeated.
+struct MatchContext {
+    lines: Vec<String>,
+    match_lines: HashSet<usize>,
+    context_lines: usize,
+    last_shown: usize,
+}
+
+impl MatchContext {
+    fn load(
+        options: &SearchRenderOptions<'_>,
+        path: &str,
+        match_lines: impl Iterator<Item = usize>,
+    ) -> Option<Self> {
+        if options.context_lines == 0 {
+            return None;
+        }
+        let content = std::fs::read_to_string(options.root.join(path)).ok()?;
+        Some(Self {
+            lines: content.lines().map(ToOwned::to_owned).collect(),
+            match_lines: match_lines.collect(),
+            context_lines: options.context_lines,
+            last_shown: 0,
+        })
+    }
+
+    fn push_before(&mut self, line: usize, prefix: &str, out: &mut Vec<String>) {
+        let mut first = line.saturating_sub(self.context_lines).max(1);
+        if self.last_shown < line {
+            first = first.max(self.last_shown + 1);
+        }
+        for number in first..line {
+            if !self.match_lines.contains(&number) {
+                self.push_line(number, prefix, out);
+            }
+        }
+    }
+
+    fn push_after(&mut self, line: usize, prefix: &str, out: &mut Vec<String>) {
+        let mut last = line;
+        for number in line + 1..=line + self.context_lines {
+            if number > self.lines.len() || self.match_lines.contains(&number) {
+                break;
+            }
+            self.push_line(number, prefix, out);
+            last = number;
+        }
+        self.last_shown = self.last_shown.max(last);
+    }
+
+    fn push_line(&self, number: usize, prefix: &str, out: &mut Vec<String>) {
+        if let Some(text) = self.lines.get(number - 1) {
+            out.push(format!(
+                "{prefix}{number}- {}",
+                util::truncate_str(text, MAX_RENDERED_MATCH_LINE_CHARS)
+            ));
+        }
+    }
+}
+
+/// Per-file counts in place of a result set too large to list.
+fn render_match_summary(
+    mode: &str,
+    query_label: &str,
+    query: &str,
+    total_matches: usize,
+    counts: &[(&str, usize)],
+) -> RenderedSearch {
+    let mut ranked = counts.to_vec();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    let mut lines = vec![
+        format!("{query_label}: {query}"),
+        format!(
+            "matches: {} in {} files (too many to list; showing per-file counts)",
+            total_matches,
+            counts.len()
+        ),
+        String::new(),
+    ];
+    for (path, count) in ranked.iter().take(MAX_SUMMARY_FILES) {
+        lines.push(format!("  {count:>6}  {path}"));
+    }
+    if ranked.len() > MAX_SUMMARY_FILES {
+        lines.push(format!(
+            "  ... {} more files",
+            ranked.len() - MAX_SUMMARY_FILES
+        ));
+    }
+    lines.push(String::new());
+    lines.push(
+        "Narrow the search with path, glob, exclude or type, or set max_results to list the first matches."
+            .to_string(),
+    );
+    RenderedSearch {
+        text: lines.join("\n"),
+        metadata: search_metadata(mode, query, total_matches, counts, None),
+    }
+}
+
+/// JSON form of a grep result. `shown_lines` is `None` when no matches were
+/// listed (summaries and `paths_only`).
+fn search_metadata(
+    mode: &str,
+    query: &str,
+    total_matches: usize,
+    counts: &[(&str, usize)],
+    shown_lines: Option<&[Vec<usize>]>,
+) -> Value {
+    let files = counts
+        .iter()
+        .enumerate()
+        .map(|(index, (path, count))| {
+            json!({
+                "path": path,
+                "match_count": count,
+                "shown_lines": shown_lines.map(|shown| shown[index].clone()).unwrap_or_default(),
+            })
+        })
+        .collect::<Vec<_>>();
+    let shown_matches: usize = shown_lines
+        .map(|shown| shown.iter().map(Vec::len).sum())
+        .unwrap_or(0);
+    json!({
+        "mode": mode,
+        "query": query,
+        "total_matches": total_matches,
+        "total_files": counts.len(),
+        "shown_matches": shown_matches,
+        "truncated": shown_matches < total_matches,
+        "files": files,
+    })
+}
+
+pub(super) fn render_ast_output(
+    result: &AstSearchResult,
+    options: &SearchRenderOptions<'_>,
+) -> RenderedSearch {
+    let counts = result
+        .files
+        .iter()
+        .map(|file| (file.path.as_str(), file.matches.len()))
+        .collect::<Vec<_>>();
+    if options.max_results.is_none() && result.total_matches > SUMMARY_MIN_MATCHES {
+        return render_match_summary(
+            "ast",
+            "pattern",
+            &result.pattern,
+            result.total_matches,
+            &counts,
+        );
+    }
+
+    let mut lines = vec![
+        format!("pattern: {}", result.pattern),
+        format!(
+            "matches: {} in {} files",
+            result.total_matches,
+            result.files.len()
+        ),
+    ];
+    if result.unsupported_files > 0 {
+        lines.push(format!(
+            "skipped: {} candidate files in languages ast mode does not support (rust, typescript/javascript, python)",
+            result.unsupported_files
+        ));
+    }
+    if result.files.is_empty() {
+        lines.push(
+            "no structural matches; check that the pattern is valid code in the target language, or use grep mode"
+                .to_string(),
+        );
+    }
+
+    let mut remaining = options.max_results.unwrap_or(usize::MAX);
+    let mut metadata_files = Vec::new();
+    if !options.group_by_file {
+        lines.push(String::new());
+    }
+    for file in &result.files {
+        if remaining == 0 {
+            break;
+        }
+        let shown = &file.matches[..file.matches.len().min(remaining)];
+        remaining -= shown.len();
+        let mut context = MatchContext::load(
+            options,
+            &file.path,
+            shown.iter().map(|ast_match| ast_match.start_line),
+        );
+        let context_prefix = if options.group_by_file {
+            "        ".to_string()
+        } else {
+            format!("{}-", file.path)
+        };
+        if options.group_by_file {
+            lines.push(String::new());
+            lines.push(format!(
+                "{} ({}, {})",
+                file.path,
+                match_count_label(file.matches.len()),
+                file.language.as_str()
+            ));
+        }
+        for ast_match in shown {
+            if let Some(context) = context.as_mut() {
+                context.push_before(ast_match.start_line, &context_prefix, &mut lines);
+            }
+            let line_text = util::truncate_str(&ast_match.line_text, MAX_RENDERED_MATCH_LINE_CHARS);
+            let captures = ast_match
+                .captures
+                .iter()
+                .map(|(name, text)| format!("${name} = {}", compact_capture(text)))
+                .collect::<Vec<_>>();
+            if options.group_by_file {
+                lines.push(format!(
+                    "  - @ {} {}",
+                    line_span(ast_match.start_line, ast_match.end_line),
+                    line_text
+                ));
+                if !captures.is_empty() {
+                    lines.push(format!("      {}", captures.join(", ")));
+                }
+            } else if captures.is_empty() {
+                lines.push(format!(
+                    "{}:{}: {}",
+                    file.path,
+                    line_span(ast_match.start_line, ast_match.end_line),
+                    line_text
+                ));
+            } else {
+                lines.push(format!(
+                    "{}:{}: {}  [{}]",
+                    file.path,
+                    line_span(ast_match.start_line, ast_match.end_line),
+                    line_text,
+                    captures.join(", ")
+                ));
+            }
+            if let Some(context) = context.as_mut() {
+                context.push_after(ast_match.start_line, &context_prefix, &mut lines);
+            }
+        }
+        metadata_files.push(json!({
+            "path": file.path,
+            "language": file.language.as_str(),
+            "match_count": file.matches.len(),
+            "matches": shown.iter().map(ast_match_metadata).collect::<Vec<_>>(),
+        }));
+    }
+
+    let shown_matches = result
+        .total_matches
+        .min(options.max_results.unwrap_or(usize::MAX));
+    if let Some(max) = options.max_results
+        && result.total_matches > max
+    {
+        lines.push(String::new());
+        lines.push(format!(
+            "... {} more matches omitted ({}={})",
+            result.total_matches - max,
+            options.limit_param,
+            max
+        ));
+    }
+
+    RenderedSearch {
+        text: lines.join("\n"),
+        metadata: json!({
+            "mode": "ast",
+            "query": result.pattern,
+            "total_matches": result.total_matches,
+            "total_files": result.files.len(),
+            "shown_matches": shown_matches,
+            "truncated": shown_matches < result.total_matches,
+            "files": metadata_files,
+        }),
+    }
+}
+
+fn ast_match_metadata(ast_match: &AstMatch) -> Value {
+    let captures = ast_match
+        .captures
+        .iter()
+        .map(|(name, text)| (name.clone(), Value::String(text.clone())))
+        .collect::<serde_json::Map<_, _>>();
+    json!({
+        "start_line": ast_match.start_line,
+        "end_line": ast_match.end_line,
+        "captures": captures,
+    })
+}
+
+fn line_span(start_line: usize, end_line: usize) -> String {
+    if start_line == end_line {
+        start_line.to_string()
+    } else {
+        format!("{start_line}-{end_line}")
+    }
+}
+
+/// A metavariable binding on one line, cut to a readable length.
+fn compact_capture(text: &str) -> String {
+    let single_line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if single_line.len() > MAX_RENDERED_CAPTURE_CHARS {
+        format!(
+            "{}…",
+            util::truncate_str(&single_line, MAX_RENDERED_CAPTURE_CHARS)
+        )
+    } else {
+        single_line
+    }
+}
+
 fn non_code_match_cap(file: &FileMatches) -> Option<usize> {
     match file.language.as_str() {
         "json" | "yaml" | "markdown" | "text" | "" => Some(MAX_NON_CODE_MATCH_LINES_PER_FILE),
@@ -8,6 +8,7 @@ fn test_ctx(root: &Path) -> ToolContext {
         message_id: "test".to_string(),
         tool_call_id: "test".to_string(),
         working_dir: Some(root.to_path_buf()),
+        workspace_roots: Vec::new(),
         stdin_request_tx: None,
         graceful_shutdown_signal: None,
         execution_mode: super::super::ToolExecutionMode::Direct,
@@ -41,6 +42,10 @@ fn grep_input(query: &str, max_regions: Option<usize>) -> AgentGrepInput {
         debug_plan: None,
         debug_score: None,
         paths_only: None,
+        exclude: None,
+        context_lines: None,
+        group_by_file: None,
+        max_results: None,
     }
 }
 
@@ -98,6 +103,108 @@ fn grep_max_regions_limits_rendered_match_excerpts() {
     );
 }
 
+#[test]
+fn grep_shows_context_lines_and_per_file_counts() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    fs::write(
+        temp.path().join("a.rs"),
+        "fn one() {\n    let before = 1;\n    status_notice();\n    let after = 2;\n}\n",
+    )
+    .expect("write file");
+
+    let mut input = grep_input("status_notice", None);
+    input.context_lines = Some(1);
+    let output =
+        execute_linked_agentgrep(&input, &test_ctx(temp.path()), None).expect("agentgrep execute");
+
+    assert!(
+        output.output.contains("a.rs (1 match)"),
+        "{}",
+        output.output
+    );
+    assert!(
+        output.output.contains("2-     let before = 1;"),
+        "{}",
+        output.output
+    );
+    assert!(
+        output.output.contains("4-     let after = 2;"),
+        "{}",
+        output.output
+    );
+    let metadata = output.metadata.expect("grep metadata");
+    assert_eq!(metadata["total_matches"], 1);
+    assert_eq!(metadata["files"][0]["path"], "a.rs");
+    assert_eq!(metadata["files"][0]["shown_lines"], json!([3]));
+}
+
+#[test]
+fn grep_flat_output_excludes_globs_and_truncates_in_path_order() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    fs::create_dir_all(temp.path().join("tests")).expect("mkdir");
+    for name in ["b.rs", "a.rs", "tests/c.rs"] {
+        fs::write(
+            temp.path().join(name),
+            "fn one() { status_notice(); }\nfn two() { status_notice(); }\n",
+        )
+        .expect("write file");
+    }
+
+    let mut input = grep_input("status_notice", None);
+    input.group_by_file = Some(false);
+    input.exclude = Some(vec!["tests".to_string()]);
+    input.max_results = Some(3);
+    let output = execute_linked_agentgrep(&input, &test_ctx(temp.path()), None)
+        .expect("agentgrep execute")
+        .output;
+
+    assert!(output.contains("matches: 4 in 2 files"), "{output}");
+    assert!(
+        output.contains("a.rs:1: fn one() { status_notice(); }"),
+        "{output}"
+    );
+    assert!(
+        output.contains("a.rs:2: fn two() { status_notice(); }"),
+        "{output}"
+    );
+    assert!(
+        output.contains("b.rs:1: fn one() { status_notice(); }"),
+        "{output}"
+    );
+    assert!(!output.contains("b.rs:2:"), "{output}");
+    assert!(!output.contains("c.rs"), "{output}");
+    assert!(
+        output.contains("1 more matches omitted (max_results=3)"),
+        "{output}"
+    );
+}
+
+#[test]
+fn grep_summarizes_very_large_result_sets() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let many = "status_notice();\n".repeat(render::SUMMARY_MIN_MATCHES);
+    fs::write(temp.path().join("big.rs"), &many).expect("write file");
+    fs::write(temp.path().join("small.rs"), "status_notice();\n").expect("write file");
+
+    let output = execute_linked_agentgrep(
+        &grep_input("status_notice", None),
+        &test_ctx(temp.path()),
+        None,
+    )
+    .expect("agentgrep execute");
+
+    let text = &output.output;
+    assert!(text.contains("too many to list"), "{text}");
+    assert!(
+        text.contains(&format!("{:>6}  big.rs", render::SUMMARY_MIN_MATCHES)),
+        "{text}"
+    );
+    assert!(text.contains("     1  small.rs"), "{text}");
+    assert!(text.contains("set max_results"), "{text}");
+    assert!(!text.contains("      - @ "), "{text}");
+    assert_eq!(output.metadata.expect("metadata")["truncated"], true);
+}
+
 #[test]
 fn grep_caps_non_code_file_match_excerpts_by_default() {
     let temp = tempfile::tempdir().expect("tempdir");
@@ -412,13 +519,18 @@ fn schema_only_advertises_common_public_fields() {
     assert!(props.contains_key("max_files"));
     assert!(props.contains_key("max_regions"));
     assert!(props.contains_key("paths_only"));
+    assert!(props.contains_key("exclude"));
+    assert!(props.contains_key("context_lines"));
+    assert!(props.contains_key("group_by_file"));
+    assert!(props.contains_key("max_results"));
     assert_eq!(
         mode_enum,
         &vec![
             json!("grep"),
             json!("find"),
             json!("outline"),
-            json!("trace")
+            json!("trace"),
+            json!("ast")
         ]
     );
     assert!(!props.contains_key("hidden"));
@@ -768,3 +880,120 @@ fn input_accepts_legacy_grep_param_aliases() {
     assert_eq!(input.path.as_deref(), Some("src"));
     assert_eq!(input.mode, "grep");
 }
+
+#[test]
+fn ast_patterns_match_balanced_token_runs_and_bind_metavariables() {
+    use ast::{AstLanguage, AstPattern, find_matches};
+
+    let source = r#"// fn commented() {}
+pub fn alpha(a: u32, b: &str) -> u32 {
+    let s = "fn fake() { }";
+    fn inner() { beta(1, (2, 3)) }
+    a + 1
+}
+"#;
+    let pattern =
+        AstPattern::parse("fn $NAME($$$ARGS) -> $RET { $$$ }", AstLanguage::Rust).expect("pattern");
+    let matches = find_matches(&pattern, source, AstLanguage::Rust);
+    assert_eq!(matches.len(), 1, "{matches:?}");
+    assert_eq!((matches[0].start_line, matches[0].end_line), (2, 6));
+    assert_eq!(
+        matches[0].captures,
+        vec![
+            ("NAME".to_string(), "alpha".to_string()),
+            ("ARGS".to_string(), "a: u32, b: &str".to_string()),
+            ("RET".to_string(), "u32".to_string()),
+        ]
+    );
+
+    let pattern = AstPattern::parse("beta($A, $$$REST)", AstLanguage::Rust).expect("pattern");
+    let matches = find_matches(&pattern, source, AstLanguage::Rust);
+    assert_eq!(matches.len(), 1, "{matches:?}");
+    assert_eq!(matches[0].captures[1].1, "(2, 3)");
+
+    let pattern = AstPattern::parse("$A == $A", AstLanguage::Python).expect("pattern");
+    let matches = find_matches(
+        &pattern,
+        "x = a == a\ny = a == b  # a == a\n",
+        AstLanguage::Python,
+    );
+    assert_eq!(matches.len(), 1, "{matches:?}");
+    assert_eq!(matches[0].start_line, 1);
+
+    assert!(AstPattern::parse("$$$ foo", AstLanguage::Rust).is_err());
+    assert!(AstPattern::parse("$A", AstLanguage::Rust).is_err());
+}
+
+#[tokio::test]
+async fn execute_runs_ast_search_across_languages() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    fs::create_dir_all(temp.path().join("src")).expect("mkdir");
+    fs::write(
+        temp.path().join("src/app.rs"),
+        "fn auth_status() -> bool {\n    true\n}\n\nfn render() {\n    auth_status();\n}\n",
+    )
+    .expect("write rust file");
+    fs::write(
+        temp.path().join("src/app.py"),
+        "def auth_status():\n    return True\n",
+    )
+    .expect("write python file");
+    fs::write(temp.path().join("src/notes.md"), "fn auth_status() {}\n").expect("write notes");
+
+    let output = AgentGrepTool::new()
+        .execute(
+            json!({"mode": "ast", "query": "fn $NAME() { $$$ }", "path": "src"}),
+            test_ctx(temp.path()),
+        )
+        .await
+        .expect("ast output");
+
+    assert!(
+        output.output.contains("matches: 1 in 1 files"),
+        "{}",
+        output.output
+    );
+    assert!(
+        output.output.contains("app.rs (1 match, rust)"),
+        "{}",
+        output.output
+    );
+    assert!(
+        output.output.contains("  - @ 5-7 fn render() {"),
+        "{}",
+        output.output
+    );
+    assert!(
+        output.output.contains("$NAME = render"),
+        "{}",
+        output.output
+    );
+    assert!(
+        output.output.contains("skipped: 1 candidate files"),
+        "{}",
+        output.output
+    );
+    let metadata = output.metadata.expect("ast metadata");
+    assert_eq!(
+        metadata["files"][0]["matches"][0]["captures"]["NAME"],
+        "render"
+    );
+
+    let output = AgentGrepTool::new()
+        .execute(
+            json!({"mode": "ast", "query": "def $NAME(): $$$", "path": "src", "type": "py"}),
+            test_ctx(temp.path()),
+        )
+        .await
+        .expect("python ast output");
+    assert!(
+        output.output.contains("app.py (1 match, python)"),
+        "{}",
+        output.output
+    );
+    assert!(
+        output.output.contains("$NAME = auth_status"),
+        "{}",
+        output.output
+    );
+}
@@ -58,7 +58,7 @@ pub fn init_schedule_runner(handle: AmbientRunnerHandle) {
     }
 }
 
-fn get_safety_system() -> Arc<SafetySystem> {
+pub(super) fn get_safety_system() -> Arc<SafetySystem> {
     SAFETY_SYSTEM
         .get()
         .cloned()
@@ -83,7 +83,7 @@ pub fn unregister_ambient_session(session_id: &str) {
     }
 }
 
-fn is_ambient_session_registered(session_id: &str) -> bool {
+pub(super) fn is_ambient_session_registered(session_id: &str) -> bool {
     ambient_session_ids()
         .lock()
         .map(|ids| ids.contains(session_id))
@@ -677,6 +677,7 @@ impl Tool for RequestPermissionTool {
             wait: params.wait,
             created_at: now,
             context: Some(request_context),
+            expires_at: None,
         };
 
         let system = get_safety_system();
@@ -1011,7 +1012,7 @@ fn format_scheduled_item(item: &ScheduledItem) -> String {
     )
 }
 
-fn nudge_schedule_runner() {
+pub(crate) fn nudge_schedule_runner() {
     let runner = SCHEDULE_RUNNER
         .get_or_init(|| Mutex::new(None))
         .lock()
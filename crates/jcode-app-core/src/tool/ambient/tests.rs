@@ -271,6 +271,7 @@ async fn test_request_permission_rejects_non_ambient_session() {
         message_id: "msg_1".to_string(),
         tool_call_id: "call_1".to_string(),
         working_dir: None,
+        workspace_roots: Vec::new(),
         stdin_request_tx: None,
         graceful_shutdown_signal: None,
         execution_mode: crate::tool::ToolExecutionMode::Direct,
@@ -428,6 +429,7 @@ async fn test_schedule_tool_defaults_to_resuming_originating_session() {
         message_id: "msg_1".to_string(),
         tool_call_id: "call_1".to_string(),
         working_dir: None,
+        workspace_roots: Vec::new(),
         stdin_request_tx: None,
         graceful_shutdown_signal: None,
         execution_mode: crate::tool::ToolExecutionMode::Direct,
@@ -485,6 +487,7 @@ async fn test_schedule_tool_requires_time() {
         message_id: "msg_1".to_string(),
         tool_call_id: "call_1".to_string(),
         working_dir: None,
+        workspace_roots: Vec::new(),
         stdin_request_tx: None,
         graceful_shutdown_signal: None,
         execution_mode: crate::tool::ToolExecutionMode::Direct,
@@ -92,6 +92,13 @@ impl Tool for ApplyPatchTool {
                         tokio::fs::create_dir_all(parent).await?;
                     }
                     tokio::fs::write(&resolved, contents).await?;
+                    crate::provenance::record_tool_change(
+                        &ctx,
+                        "apply_patch",
+                        &resolved,
+                        "",
+                        contents,
+                    );
                     let diff = generate_diff_summary("", contents);
                     publish_file_touch(
                         &ctx,
@@ -114,6 +121,13 @@ impl Tool for ApplyPatchTool {
                         .await
                         .unwrap_or_default();
                     if tokio::fs::remove_file(&resolved).await.is_ok() {
+                        crate::provenance::record_tool_change(
+                            &ctx,
+                            "apply_patch",
+                            &resolved,
+                            &old_contents,
+                            "",
+                        );
                         let diff = generate_diff_summary(&old_contents, "");
                         publish_file_touch(
                             &ctx,
@@ -149,6 +163,20 @@ impl Tool for ApplyPatchTool {
                                 }
                                 tokio::fs::write(&dest_resolved, &new_contents).await?;
                                 let _ = tokio::fs::remove_file(&resolved).await;
+                                crate::provenance::record_tool_change(
+                                    &ctx,
+                                    "apply_patch",
+                                    &resolved,
+                                    &old_contents,
+                                    "",
+                                );
+                                crate::provenance::record_tool_change(
+                                    &ctx,
+                                    "apply_patch",
+                                    &dest_resolved,
+                                    "",
+                                    &new_contents,
+                                );
                                 publish_file_touch(
                                     &ctx,
                                     &resolved,
@@ -185,6 +213,13 @@ impl Tool for ApplyPatchTool {
                                 }
                             } else {
                                 tokio::fs::write(&resolved, &new_contents).await?;
+                                crate::provenance::record_tool_change(
+                                    &ctx,
+                                    "apply_patch",
+                                    &resolved,
+                                    &old_contents,
+                                    &new_contents,
+                                );
                                 publish_file_touch(
                                     &ctx,
                                     &resolved,
@@ -0,0 +1,439 @@
+//! `ask_user` tool: pause the turn for a decision only the user can make.
+//!
+//! With a client attached, the question goes out over the stdin forwarding
+//! channel tagged with [`ASK_USER_PREFIX`] and is listed in the permission
+//! inbox, and the reply comes back as the tool result within the same turn.
+//! Without a client (`jcode run`, local mode) the question is listed and
+//! notified, and after `[safety] ask_user_timeout_secs` the agent is told to
+//! proceed on its own judgment. Ambient cycles never wait: the question is
+//! queued like a `request_permission` call.
+
+use super::{Tool, ToolContext, ToolOutput, ambient};
+use crate::safety::{self, PermissionRequest, Urgency};
+use anyhow::{Result, bail};
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+/// Prefix of stdin request ids that carry an `ask_user` question.
+pub const ASK_USER_PREFIX: &str = "ask-user-";
+
+/// Tool result when nobody answered in time.
+const NO_RESPONSE: &str =
+    "The user did not respond. Proceed with your best judgment and state the assumption you made.";
+
+/// Most options a question may offer.
+const MAX_OPTIONS: usize = 9;
+
+/// How often a pending question checks whether another surface answered it.
+const INBOX_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+static QUESTION_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+pub struct AskUserTool;
+
+impl AskUserTool {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[derive(Deserialize)]
+struct AskUserInput {
+    question: String,
+    #[serde(default)]
+    options: Vec<String>,
+    #[serde(
+        default = "default_true",
+        deserialize_with = "super::serde_coerce::bool_from_string_or_bool"
+    )]
+    allow_free_text: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Where an answer came from.
+#[derive(Debug, PartialEq)]
+enum Answer {
+    Client(String),
+    Inbox {
+        approved: bool,
+        message: Option<String>,
+    },
+    NoResponse,
+}
+
+#[async_trait]
+impl Tool for AskUserTool {
+    fn name(&self) -> &str {
+        "ask_user"
+    }
+
+    fn description(&self) -> &str {
+        "Ask the user a question and wait for the answer within this turn. Use it for decisions you cannot make yourself, offering options when the choices are known."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "required": ["question"],
+            "properties": {
+                "intent": super::intent_schema_property(),
+                "question": {
+                    "type": "string",
+                    "description": "The question, with enough context to answer it without scrolling back."
+                },
+                "options": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "maxItems": MAX_OPTIONS,
+                    "description": "Possible answers, shown numbered. The user may reply with the number."
+                },
+                "allow_free_text": {
+                    "type": "boolean",
+                    "description": "Whether the user may answer with their own text instead of an option (default: true)."
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, input: Value, ctx: ToolContext) -> Result<ToolOutput> {
+        let params: AskUserInput = serde_json::from_value(input)?;
+        let question = params.question.trim();
+        if question.is_empty() {
+            bail!("ask_user needs a non-empty `question`");
+        }
+        let options: Vec<String> = params
+            .options
+            .iter()
+            .map(|option| option.trim().to_string())
+            .filter(|option| !option.is_empty())
+            .collect();
+        if options.len() > MAX_OPTIONS {
+            bail!("ask_user accepts at most {MAX_OPTIONS} options");
+        }
+        let prompt = question_prompt(question, &options, params.allow_free_text);
+        let request_id = format!(
+            "{}{}-{}",
+            ASK_USER_PREFIX,
+            ctx.tool_call_id,
+            QUESTION_COUNTER.fetch_add(1, Ordering::Relaxed)
+        );
+
+        if ambient::is_ambient_session_registered(&ctx.session_id) {
+            return Ok(queue_for_ambient(&ctx, request_id, question, &prompt));
+        }
+
+        let answer = match ctx.stdin_request_tx.as_ref() {
+            Some(stdin_tx) => {
+                let (response_tx, response_rx) = oneshot::channel();
+                let sent = stdin_tx
+                    .send(super::StdinInputRequest {
+                        request_id: request_id.clone(),
+                        prompt: prompt.clone(),
+                        is_password: false,
+                        response_tx,
+                    })
+                    .is_ok();
+                if let Err(error) = safety::enqueue_session_request(
+                    &request_id,
+                    &ctx.session_id,
+                    "ask_user",
+                    &prompt,
+                ) {
+                    crate::logging::warn(&format!(
+                        "Could not add {} to the permission inbox: {}",
+                        request_id, error
+                    ));
+                }
+                let ttl = safety::interactive_approval_ttl()
+                    .to_std()
+                    .unwrap_or(Duration::ZERO);
+                wait_for_answer(&request_id, sent.then_some(response_rx), ttl).await
+            }
+            None => {
+                let timeout =
+                    Duration::from_secs(crate::config::config().safety.ask_user_timeout_secs);
+                if timeout.is_zero() {
+                    Answer::NoResponse
+                } else {
+                    let request = question_request(&ctx, &request_id, question, &prompt);
+                    let expires_at = request.created_at + chrono::Duration::from_std(timeout)?;
+                    ambient::get_safety_system().request_permission(PermissionRequest {
+                        wait: true,
+                        expires_at: Some(expires_at),
+                        ..request
+                    });
+                    wait_for_answer(&request_id, None, timeout).await
+                }
+            }
+        };
+
+        let (output, answered_via) = match answer {
+            Answer::Client(text) => (answer_text(&text, &options), "session_client"),
+            Answer::Inbox {
+                message: Some(text),
+                ..
+            } if !text.trim().is_empty() => (answer_text(&text, &options), "permission_inbox"),
+            Answer::Inbox { approved, .. } => (
+                if approved {
+                    "The user approved from the permission inbox without further detail."
+                        .to_string()
+                } else {
+                    "The user declined from the permission inbox.".to_string()
+                },
+                "permission_inbox",
+            ),
+            Answer::NoResponse => (NO_RESPONSE.to_string(), "timeout"),
+        };
+        Ok(ToolOutput::new(output)
+            .with_title(format!(
+                "ask_user: {}",
+                crate::util::truncate_str(question, 60)
+            ))
+            .with_metadata(json!({
+                "question": question,
+                "options": options,
+                "answered_via": answered_via,
+            })))
+    }
+}
+
+/// The question as the user sees it, options numbered from 1.
+fn question_prompt(question: &str, options: &[String], allow_free_text: bool) -> String {
+    let mut prompt = question.to_string();
+    if options.is_empty() {
+        prompt.push_str("\nReply with your answer.");
+        return prompt;
+    }
+    prompt.push('\n');
+    for (index, option) in options.iter().enumerate() {
+        prompt.push_str(&format!("\n  {}. {}", index + 1, option));
+    }
+    prompt.push_str(if allow_free_text {
+        "\n\nReply with a number or your own answer."
+    } else {
+        "\n\nReply with a number."
+    });
+    prompt
+}
+
+/// The tool result for a reply, resolving an option number or an exact
+/// option to the option text.
+fn answer_text(reply: &str, options: &[String]) -> String {
+    let reply = reply.trim();
+    if reply.is_empty() {
+        return "The user dismissed the question without answering. Proceed with your best judgment."
+            .to_string();
+    }
+    let chosen = reply
+        .trim_end_matches('.')
+        .parse::<usize>()
+        .ok()
+        .and_then(|number| number.checked_sub(1))
+        .and_then(|index| options.get(index).map(|option| (index, option)))
+        .or_else(|| {
+            options
+                .iter()
+                .enumerate()
+                .find(|(_, option)| option.eq_ignore_ascii_case(reply))
+        });
+    match chosen {
+        Some((index, option)) => format!("The user chose option {}: {}", index + 1, option),
+        None => format!("The user answered: {}", reply),
+    }
+}
+
+fn question_request(
+    ctx: &ToolContext,
+    request_id: &str,
+    question: &str,
+    prompt: &str,
+) -> PermissionRequest {
+    PermissionRequest {
+        id: request_id.to_string(),
+        action: "ask_user".to_string(),
+        description: prompt.to_string(),
+        rationale: question.to_string(),
+        urgency: Urgency::High,
+        wait: false,
+        created_at: Utc::now(),
+        context: Some(json!({
+            "origin": "session",
+            "session_id": ctx.session_id,
+            "tool_call_id": ctx.tool_call_id,
+        })),
+        expires_at: None,
+    }
+}
+
+/// Ambient cycles run unattended, so the question is queued and notified like
+/// a `request_permission` call and the cycle carries on.
+fn queue_for_ambient(
+    ctx: &ToolContext,
+    request_id: String,
+    question: &str,
+    prompt: &str,
+) -> ToolOutput {
+    let mut request = question_request(ctx, &request_id, question, prompt);
+    if let Some(context) = request.context.as_mut().and_then(Value::as_object_mut) {
+        context.remove("origin");
+    }
+    ambient::get_safety_system().request_permission(request);
+    ToolOutput::new(format!(
+        "Question queued for the user (id: {}). Ambient cycles do not wait for answers: \
+         proceed with your best judgment, or leave the work that depends on it for a later cycle.",
+        request_id
+    ))
+    .with_title("ask_user: queued")
+}
+
+/// Wait for the client's reply or an inbox decision, whichever comes first.
+/// An unanswered question is dropped from the inbox after `timeout`.
+async fn wait_for_answer(
+    request_id: &str,
+    response_rx: Option<oneshot::Receiver<String>>,
+    timeout: Duration,
+) -> Answer {
+    // Once the client leaves, other surfaces can still answer.
+    let client_reply = async move {
+        match response_rx {
+            Some(rx) => match rx.await {
+                Ok(reply) => reply,
+                Err(_) => std::future::pending().await,
+            },
+            None => std::future::pending().await,
+        }
+    };
+    tokio::pin!(client_reply);
+    let deadline = tokio::time::Instant::now() + timeout;
+    let mut poll = tokio::time::interval(INBOX_POLL_INTERVAL);
+    loop {
+        tokio::select! {
+            reply = &mut client_reply => {
+                // Fails harmlessly when another surface answered first.
+                let _ = safety::record_decision(request_id, true, "session_client", Some(reply.clone()));
+                return Answer::Client(reply);
+            }
+            _ = poll.tick() => {
+                if let Some(decision) = safety::decision_for(request_id) {
+                    return Answer::Inbox {
+                        approved: decision.approved,
+                        message: decision.message,
+                    };
+                }
+                if tokio::time::Instant::now() >= deadline {
+                    let _ = safety::record_decision(
+                        request_id,
+                        false,
+                        "expired",
+                        Some("Nobody answered in time".to_string()),
+                    );
+                    return Answer::NoResponse;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options() -> Vec<String> {
+        vec![
+            "Migrate the DB schema".to_string(),
+            "Add a compat shim".to_string(),
+        ]
+    }
+
+    #[test]
+    fn prompt_numbers_options_and_says_how_to_reply() {
+        assert_eq!(
+            question_prompt("Which approach?", &options(), true),
+            "Which approach?\n\n  1. Migrate the DB schema\n  2. Add a compat shim\n\nReply with a number or your own answer."
+        );
+        assert_eq!(
+            question_prompt("Which approach?", &options(), false),
+            "Which approach?\n\n  1. Migrate the DB schema\n  2. Add a compat shim\n\nReply with a number."
+        );
+        assert_eq!(
+            question_prompt("Which branch?", &[], true),
+            "Which branch?\nReply with your answer."
+        );
+    }
+
+    #[test]
+    fn replies_resolve_to_options_or_free_text() {
+        assert_eq!(
+            answer_text(" 2 ", &options()),
+            "The user chose option 2: Add a compat shim"
+        );
+        assert_eq!(
+            answer_text("add a compat shim", &options()),
+            "The user chose option 2: Add a compat shim"
+        );
+        assert_eq!(answer_text("3", &options()), "The user answered: 3");
+        assert_eq!(
+            answer_text("neither, ask Sam", &options()),
+            "The user answered: neither, ask Sam"
+        );
+        assert!(answer_text("", &options()).starts_with("The user dismissed"));
+    }
+
+    #[tokio::test]
+    #[allow(
+        clippy::await_holding_lock,
+        reason = "the question is listed in the permission inbox under JCODE_HOME"
+    )]
+    async fn unanswered_questions_time_out_and_leave_the_inbox() {
+        let _storage_guard = crate::storage::lock_test_env();
+        let temp_home = tempfile::TempDir::new().expect("temp home");
+        let previous_home = std::env::var_os("JCODE_HOME");
+        crate::env::set_var("JCODE_HOME", temp_home.path());
+
+        let request_id = format!("{}timeout-test", ASK_USER_PREFIX);
+        safety::enqueue_session_request(&request_id, "ask-user-test", "ask_user", "Which?")
+            .expect("enqueue");
+        let answer = wait_for_answer(&request_id, None, Duration::from_millis(10)).await;
+        assert_eq!(answer, Answer::NoResponse);
+        assert!(safety::inbox().is_empty());
+
+        match previous_home {
+            Some(value) => crate::env::set_var("JCODE_HOME", value),
+            None => crate::env::remove_var("JCODE_HOME"),
+        }
+    }
+
+    #[tokio::test]
+    #[allow(
+        clippy::await_holding_lock,
+        reason = "the question is listed in the permission inbox under JCODE_HOME"
+    )]
+    async fn client_reply_answers_and_clears_the_inbox() {
+        let _storage_guard = crate::storage::lock_test_env();
+        let temp_home = tempfile::TempDir::new().expect("temp home");
+        let previous_home = std::env::var_os("JCODE_HOME");
+        crate::env::set_var("JCODE_HOME", temp_home.path());
+
+        let request_id = format!("{}reply-test", ASK_USER_PREFIX);
+        safety::enqueue_session_request(&request_id, "ask-user-test", "ask_user", "Which?")
+            .expect("enqueue");
+        let (response_tx, response_rx) = oneshot::channel();
+        response_tx.send("2".to_string()).expect("send reply");
+        let answer = wait_for_answer(&request_id, Some(response_rx), Duration::from_secs(5)).await;
+        assert_eq!(answer, Answer::Client("2".to_string()));
+        assert!(safety::inbox().is_empty());
+
+        match previous_home {
+            Some(value) => crate::env::set_var("JCODE_HOME", value),
+            None => crate::env::remove_var("JCODE_HOME"),
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use super::bash_env::ProjectEnv;
 use super::{StdinInputRequest, Tool, ToolContext, ToolOutput};
 use crate::background::TaskResult;
 use crate::bus::{
@@ -11,15 +12,18 @@ use chrono::Utc;
 use serde::Deserialize;
 use serde_json::{Value, json};
 use std::fs::OpenOptions;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::{Command as StdCommand, Stdio};
 use std::sync::LazyLock;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
 use tokio::process::Command as TokioCommand;
 
-const MAX_OUTPUT_LEN: usize = 30000;
 const DEFAULT_TIMEOUT_MS: u64 = 120000;
+/// How long a killed command gets to flush its pipes before its output is
+/// given up on.
+const TIMEOUT_KILL_GRACE_MS: u64 = 2000;
 const STDIN_POLL_INTERVAL_MS: u64 = 500;
 const STDIN_INITIAL_DELAY_MS: u64 = 300;
 const PROGRESS_MARKER_PREFIX: &str = "JCODE_PROGRESS ";
@@ -45,6 +49,87 @@ fn timeout_message(timeout_ms: u64) -> String {
     msg
 }
 
+/// `timeout_secs` as actually applied: at least one second and at most the
+/// `[tools.bash] max_timeout_secs` ceiling.
+fn capped_timeout_secs(requested: u64, max_timeout_secs: u64) -> u64 {
+    requested.clamp(1, max_timeout_secs.max(1))
+}
+
+fn hard_timeout_secs(params: &BashInput) -> Option<u64> {
+    let max_timeout_secs = crate::config::config().tools.bash.max_timeout_secs;
+    params
+        .timeout_secs
+        .map(|requested| capped_timeout_secs(requested, max_timeout_secs))
+}
+
+/// Output of a command killed at its `timeout_secs` limit, with a hint for
+/// commands that are meant to run long.
+fn format_timed_out_output(
+    output: String,
+    limit_secs: u64,
+    requested_secs: u64,
+    max_bytes: usize,
+) -> String {
+    let mut text = truncate_head_tail(output, max_bytes);
+    if !text.is_empty() {
+        text.push_str("\n\n");
+    }
+    text.push_str(&format!(
+        "--- Command timed out after {}s; its process group was killed ---",
+        limit_secs
+    ));
+    if requested_secs > limit_secs {
+        text.push_str(&format!(
+            "\n(timeout_secs={} was capped by [tools.bash] max_timeout_secs)",
+            requested_secs
+        ));
+    }
+    text.push_str(
+        "\nIf this command is meant to run for a long time, rerun it with run_in_background=true \
+         and use `bg` with action=\"wait\" to follow it instead of raising timeout_secs.",
+    );
+    text
+}
+
+fn timed_out_metadata(limit_secs: u64) -> Value {
+    json!({
+        "timed_out": true,
+        "timeout_secs": limit_secs,
+    })
+}
+
+/// Resolve an explicit `cwd` against the session workspace. It must be an
+/// existing directory inside `working_dir` or one of the extra workspace
+/// roots.
+fn resolve_cwd(cwd: &Path, ctx: &ToolContext) -> Result<PathBuf> {
+    let resolved = ctx.resolve_path(cwd);
+    let dir = resolved
+        .canonicalize()
+        .map_err(|e| anyhow::anyhow!("cwd {}: {}", resolved.display(), e))?;
+    if !dir.is_dir() {
+        anyhow::bail!("cwd {} is not a directory", dir.display());
+    }
+    let roots = ctx.search_roots();
+    let inside_workspace = roots
+        .iter()
+        .filter_map(|root| root.canonicalize().ok())
+        .any(|root| dir.starts_with(root));
+    if !roots.is_empty() && !inside_workspace {
+        anyhow::bail!(
+            "cwd {} is outside the session workspace; omit cwd to run in {}",
+            dir.display(),
+            roots[0].display()
+        );
+    }
+    Ok(dir)
+}
+
+/// Where the command runs: the checked `cwd` if the call gave one, else the
+/// session working directory.
+fn command_dir<'a>(params: &'a BashInput, ctx: &'a ToolContext) -> Option<&'a Path> {
+    params.cwd.as_deref().or(ctx.working_dir.as_deref())
+}
+
 fn progress_ratio_regex() -> Result<&'static regex::Regex> {
     static REGEX: LazyLock<Result<regex::Regex, regex::Error>> = LazyLock::new(|| {
         regex::Regex::new(
@@ -459,22 +544,63 @@ fn build_shell_command(cmd_str: &str) -> TokioCommand {
     }
 }
 
+/// Shell filter for detached commands: replaces each `NAME=value` line of
+/// `$JCODE_RELOAD_DETACH_REDACT` with `[redacted:NAME]` in its input. It runs
+/// in the wrapper, so the output file is redacted even after jcode reloads.
+#[cfg(unix)]
+const DETACHED_REDACT_FILTER: &str = r#"jcode_redact() {
+  local line nl entry
+  local -a entries
+  mapfile -t entries <<<"$JCODE_RELOAD_DETACH_REDACT"
+  while nl=$'\n'; IFS= read -r line || { nl=; [[ -n $line ]]; }; do
+    for entry in "${entries[@]}"; do
+      line=${line//"${entry#*=}"/"[redacted:${entry%%=*}]"}
+    done
+    printf '%s%s' "$line" "$nl"
+  done
+}"#;
+
+/// Wrapper for a command that must outlive jcode. With `redact` set (the
+/// project env's [`ProjectEnv::redaction_lines`]), output passes through
+/// [`DETACHED_REDACT_FILTER`] before it reaches the output file.
 #[cfg(unix)]
-fn build_detached_shell_wrapper(command: &str) -> StdCommand {
+fn build_detached_shell_wrapper(command: &str, redact: Option<String>) -> StdCommand {
+    const RUN: &str = r#"eval "$JCODE_RELOAD_DETACH_COMMAND"; status=$?; printf '\n--- Command finished with exit code: %s ---\n' "$status"; exit "$status""#;
     let mut cmd = StdCommand::new("bash");
-    cmd.arg("-lc")
-        .arg(
-            r#"eval "$JCODE_RELOAD_DETACH_COMMAND"; status=$?; printf '\n--- Command finished with exit code: %s ---\n' "$status"; exit "$status""#,
-        )
-        .env("JCODE_RELOAD_DETACH_COMMAND", command);
+    match redact {
+        Some(redact) => {
+            cmd.arg("-lc")
+                .arg(format!(
+                    "{DETACHED_REDACT_FILTER}\n{{ unset JCODE_RELOAD_DETACH_REDACT; {RUN}; }} 2>&1 | jcode_redact\nexit \"${{PIPESTATUS[0]}}\""
+                ))
+                .env("JCODE_RELOAD_DETACH_REDACT", redact);
+        }
+        None => {
+            cmd.arg("-lc").arg(RUN);
+        }
+    }
+    cmd.env("JCODE_RELOAD_DETACH_COMMAND", command);
     cmd
 }
 
-fn format_command_output(mut output: String, exit_code: Option<i32>) -> String {
-    if output.len() > MAX_OUTPUT_LEN {
-        output = truncate_str(&output, MAX_OUTPUT_LEN).to_string();
-        output.push_str("\n... (output truncated)");
+/// Keep the first and last halves of `max_bytes` of `output`, cut on UTF-8
+/// boundaries, around a marker giving the dropped byte count.
+fn truncate_head_tail(output: String, max_bytes: usize) -> String {
+    if output.len() <= max_bytes {
+        return output;
     }
+    let head_end = output.floor_char_boundary(max_bytes / 2);
+    let tail_start = output.ceil_char_boundary(output.len() - (max_bytes - max_bytes / 2));
+    format!(
+        "{}\n\n... ({} bytes of output truncated) ...\n\n{}",
+        &output[..head_end],
+        tail_start - head_end,
+        &output[tail_start..]
+    )
+}
+
+fn format_command_output(output: String, exit_code: Option<i32>, max_bytes: usize) -> String {
+    let mut output = truncate_head_tail(output, max_bytes);
 
     if let Some(code) = exit_code.filter(|code| *code != 0) {
         output.push_str(&format!("\n\nExit code: {}", code));
@@ -491,14 +617,46 @@ fn format_command_output(mut output: String, exit_code: Option<i32>) -> String {
 mod utf8_truncation_tests {
     #[cfg(windows)]
     use super::build_shell_command;
-    use super::format_command_output;
+    use super::{capped_timeout_secs, format_command_output, format_timed_out_output};
 
     #[test]
     fn format_command_output_truncates_on_utf8_boundary() {
-        let input = format!("{}é", "a".repeat(29_999));
-        let output = format_command_output(input, None);
-        assert!(output.ends_with("\n... (output truncated)"));
-        assert!(output.starts_with(&"a".repeat(29_999)));
+        let input = format!(
+            "{}é{}é{}",
+            "a".repeat(14_999),
+            "b".repeat(100),
+            "c".repeat(14_999)
+        );
+        let output = format_command_output(input, None, 30_000);
+        assert_eq!(
+            output,
+            format!(
+                "{}\n\n... (104 bytes of output truncated) ...\n\n{}",
+                "a".repeat(14_999),
+                "c".repeat(14_999)
+            )
+        );
+    }
+
+    #[test]
+    fn format_command_output_keeps_head_and_tail() {
+        let input = format!("first line\n{}\nlast line", "x".repeat(1000));
+        let output = format_command_output(input, Some(2), 100);
+        assert!(output.starts_with("first line\n"));
+        assert!(output.ends_with("\nlast line\n\nExit code: 2"));
+        assert!(output.contains("bytes of output truncated"));
+    }
+
+    #[test]
+    fn timeout_secs_is_capped_by_config() {
+        assert_eq!(capped_timeout_secs(30, 120), 30);
+        assert_eq!(capped_timeout_secs(900, 120), 120);
+        assert_eq!(capped_timeout_secs(0, 120), 1);
+
+        let output = format_timed_out_output("partial\n".to_string(), 120, 900, 30_000);
+        assert!(output.starts_with("partial\n\n\n--- Command timed out after 120s"));
+        assert!(output.contains("timeout_secs=900 was capped"));
+        assert!(output.contains("run_in_background=true"));
     }
 
     #[cfg(windows)]
@@ -534,11 +692,26 @@ struct BashInput {
     #[serde(default)]
     timeout: Option<u64>,
     #[serde(default)]
+    timeout_secs: Option<u64>,
+    #[serde(default)]
+    cwd: Option<PathBuf>,
+    #[serde(default)]
     run_in_background: Option<bool>,
     #[serde(default = "default_true")]
     notify: bool,
     #[serde(default)]
     wake: bool,
+    #[serde(default)]
+    clean_env: bool,
+}
+
+/// The `[tools.bash]` project environment for this command.
+fn project_env(params: &BashInput, ctx: &ToolContext) -> Result<ProjectEnv> {
+    ProjectEnv::load(
+        &crate::config::config().tools.bash,
+        ctx.working_dir.as_deref(),
+        params.clean_env,
+    )
 }
 
 fn default_true() -> bool {
@@ -578,6 +751,14 @@ impl Tool for BashTool {
                     "type": "integer",
                     "description": "Timeout in MILLISECONDS (not seconds). Kills the command when exceeded and reports exit 124. e.g. 1000 = 1s, 600000 = 10min. Omit to run with no timeout; do NOT pass small values like 1000 for long jobs such as builds or test suites."
                 },
+                "timeout_secs": {
+                    "type": "integer",
+                    "description": "Hard limit in SECONDS for a foreground command, capped by the user's config (120s unless changed). When exceeded the command's process group is killed and the output so far is returned. For builds, servers and other long jobs use run_in_background instead."
+                },
+                "cwd": {
+                    "type": "string",
+                    "description": "Directory to run in, relative to the working directory. Must be inside the session workspace. Defaults to the working directory."
+                },
                 "run_in_background": {
                     "type": "boolean",
                     "description": format!("Run in background. {}", BACKGROUND_PROGRESS_GUIDANCE)
@@ -589,6 +770,10 @@ impl Tool for BashTool {
                 "wake": {
                     "type": "boolean",
                     "description": "Wake on completion."
+                },
+                "clean_env": {
+                    "type": "boolean",
+                    "description": "Run without the project env file variables."
                 }
             }
         })
@@ -598,6 +783,27 @@ impl Tool for BashTool {
         let mut params: BashInput = serde_json::from_value(input)?;
         let run_in_background = params.run_in_background.unwrap_or(false);
 
+        if let Some(cwd) = params.cwd.take() {
+            params.cwd = Some(resolve_cwd(&cwd, &ctx)?);
+        }
+
+        if super::session_safe_mode(&ctx.session_id) {
+            // The sandbox starts in the working directory it protects.
+            let command = match params.cwd.as_deref() {
+                Some(cwd) => format!(
+                    "cd {} && {}",
+                    super::bash_sandbox::shell_quote(&cwd.to_string_lossy()),
+                    params.command
+                ),
+                None => params.command.clone(),
+            };
+            if let Some(sandboxed) =
+                super::bash_sandbox::sandbox_command(&command, ctx.working_dir.as_deref())
+            {
+                params.command = sandboxed;
+            }
+        }
+
         if run_in_background {
             return self.execute_background(params, ctx).await;
         }
@@ -625,6 +831,45 @@ impl Tool for BashTool {
     }
 }
 
+/// Kill the process group of a foreground command that ran past its
+/// `timeout_secs` and return what it printed.
+async fn kill_timed_out_foreground(
+    child_pid: u32,
+    mut work_handle: tokio::task::JoinHandle<Result<ToolOutput>>,
+    title: String,
+    limit_secs: u64,
+    requested_secs: u64,
+    max_output_bytes: usize,
+) -> Result<ToolOutput> {
+    if child_pid != 0 {
+        #[cfg(unix)]
+        let _ = crate::platform::signal_detached_process_group(child_pid, libc::SIGKILL);
+        #[cfg(not(unix))]
+        let _ = crate::platform::signal_detached_process_group(child_pid, 0);
+    }
+    match tokio::time::timeout(
+        Duration::from_millis(TIMEOUT_KILL_GRACE_MS),
+        &mut work_handle,
+    )
+    .await
+    {
+        Ok(Ok(result)) => result,
+        _ => {
+            // Something outside the process group still holds the pipes.
+            work_handle.abort();
+            let output = format_timed_out_output(
+                String::new(),
+                limit_secs,
+                requested_secs,
+                max_output_bytes,
+            );
+            Ok(ToolOutput::new(output)
+                .with_title(title)
+                .with_metadata(timed_out_metadata(limit_secs)))
+        }
+    }
+}
+
 impl BashTool {
     async fn execute_foreground(
         &self,
@@ -639,24 +884,46 @@ impl BashTool {
         }
 
         let timeout_ms = params.timeout.unwrap_or(DEFAULT_TIMEOUT_MS).min(600000);
-        let timeout_duration = Duration::from_millis(timeout_ms);
+        let hard_timeout = hard_timeout_secs(params);
+        let timeout_duration = match hard_timeout {
+            Some(secs) => Duration::from_secs(secs),
+            None => Duration::from_millis(timeout_ms),
+        };
+        let max_output_bytes = crate::config::config().tools.bash.max_output_bytes;
 
         let has_stdin_channel = ctx.stdin_request_tx.is_some();
+        let env = project_env(params, ctx)?;
 
         let mut command = build_shell_command(&params.command);
         command
+            .envs(env.vars())
             .kill_on_drop(true)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped());
 
+        // A hard timeout kills the whole process group, so give the command
+        // one of its own.
+        #[cfg(unix)]
+        if hard_timeout.is_some() {
+            unsafe {
+                command.pre_exec(|| {
+                    if libc::setpgid(0, 0) == -1 {
+                        return Err(std::io::Error::last_os_error());
+                    }
+                    Ok(())
+                });
+            }
+        }
+
         if has_stdin_channel {
             command.stdin(Stdio::piped());
         }
 
-        if let Some(ref dir) = ctx.working_dir {
+        if let Some(dir) = command_dir(params, ctx) {
             command.current_dir(dir);
         }
         let mut child = command.spawn()?;
+        let killed = std::sync::Arc::new(AtomicBool::new(false));
 
         let child_pid = child.id().unwrap_or(0);
         let stdin_handle = child.stdin.take();
@@ -672,6 +939,8 @@ impl BashTool {
         let stdin_tx = ctx.stdin_request_tx.clone();
         let tool_call_id = ctx.tool_call_id.clone();
         let title_for_work = title.clone();
+        let killed_for_work = killed.clone();
+        let requested_timeout_secs = params.timeout_secs.unwrap_or_default();
 
         // Run the command (read stdout/stderr, service stdin, wait for exit) in a
         // dedicated task so that, if it exceeds the foreground timeout, we can hand
@@ -776,7 +1045,21 @@ impl BashTool {
                     }
                     output.push_str(&stderr);
                 }
-                let output = format_command_output(output, status.code());
+                let output = env.redact(&output);
+                if let Some(limit_secs) =
+                    hard_timeout.filter(|_| killed_for_work.load(Ordering::SeqCst))
+                {
+                    let output = format_timed_out_output(
+                        output,
+                        limit_secs,
+                        requested_timeout_secs,
+                        max_output_bytes,
+                    );
+                    return Ok(ToolOutput::new(output)
+                        .with_title(title_for_work)
+                        .with_metadata(timed_out_metadata(limit_secs)));
+                }
+                let output = format_command_output(output, status.code(), max_output_bytes);
                 Ok(ToolOutput::new(output).with_title(title_for_work))
             });
 
@@ -787,6 +1070,19 @@ impl BashTool {
                 Err(join_err) => Err(anyhow::anyhow!("Command task panicked: {}", join_err)),
             },
             Err(_) => {
+                if let Some(limit_secs) = hard_timeout {
+                    killed.store(true, Ordering::SeqCst);
+                    return kill_timed_out_foreground(
+                        child_pid,
+                        work_handle,
+                        title,
+                        limit_secs,
+                        requested_timeout_secs,
+                        max_output_bytes,
+                    )
+                    .await;
+                }
+
                 // Timed out, but the command is still running. Instead of killing
                 // it, promote it to a background task so it keeps running, renders
                 // as a background-task card, and the agent is told where to find it.
@@ -853,21 +1149,28 @@ impl BashTool {
         ctx: &ToolContext,
     ) -> Result<ToolOutput> {
         let timeout_ms = params.timeout.unwrap_or(DEFAULT_TIMEOUT_MS).min(600000);
-        let timeout_duration = Duration::from_millis(timeout_ms);
+        let hard_timeout = hard_timeout_secs(params);
+        let timeout_duration = match hard_timeout {
+            Some(secs) => Duration::from_secs(secs),
+            None => Duration::from_millis(timeout_ms),
+        };
+        let max_output_bytes = crate::config::config().tools.bash.max_output_bytes;
         let started_at = Utc::now().to_rfc3339();
         let started = Instant::now();
         let manager = crate::background::global();
         let info = manager.reserve_task_info();
         let display_name = summarize_background_command(params.intent.as_deref(), &params.command);
 
-        let mut cmd = build_detached_shell_wrapper(&params.command);
+        let env = project_env(params, ctx)?;
+        let mut cmd = build_detached_shell_wrapper(&params.command, env.redaction_lines());
+        cmd.envs(env.vars());
         let stdout = OpenOptions::new()
             .create(true)
             .append(true)
             .open(&info.output_file)?;
         let stderr = stdout.try_clone()?;
         cmd.stdin(Stdio::null()).stdout(stdout).stderr(stderr);
-        if let Some(ref dir) = ctx.working_dir {
+        if let Some(dir) = command_dir(params, ctx) {
             cmd.current_dir(dir);
         }
 
@@ -882,14 +1185,45 @@ impl BashTool {
                     .unwrap_or_default();
                 let _ = tokio::fs::remove_file(&info.output_file).await;
                 let _ = tokio::fs::remove_file(&info.status_file).await;
-                return Ok(
-                    ToolOutput::new(format_command_output(output, status.code())).with_title(
+                return Ok(ToolOutput::new(format_command_output(
+                    env.redact(&output),
+                    status.code(),
+                    max_output_bytes,
+                ))
+                .with_title(
+                    params
+                        .intent
+                        .clone()
+                        .unwrap_or_else(|| params.command.clone()),
+                ));
+            }
+
+            if let Some(limit_secs) = hard_timeout
+                && started.elapsed() >= timeout_duration
+            {
+                // The detached wrapper leads its own session, so its pid is
+                // also the process group to kill.
+                let _ = crate::platform::signal_detached_process_group(pid, libc::SIGKILL);
+                let _ = child.wait();
+                let output = tokio::fs::read_to_string(&info.output_file)
+                    .await
+                    .unwrap_or_default();
+                let _ = tokio::fs::remove_file(&info.output_file).await;
+                let _ = tokio::fs::remove_file(&info.status_file).await;
+                let output = format_timed_out_output(
+                    env.redact(&output),
+                    limit_secs,
+                    params.timeout_secs.unwrap_or_default(),
+                    max_output_bytes,
+                );
+                return Ok(ToolOutput::new(output)
+                    .with_title(
                         params
                             .intent
                             .clone()
                             .unwrap_or_else(|| params.command.clone()),
-                    ),
-                );
+                    )
+                    .with_metadata(timed_out_metadata(limit_secs)));
             }
 
             if started.elapsed() >= timeout_duration {
@@ -993,11 +1327,24 @@ impl BashTool {
 
     /// Execute a command in the background
     async fn execute_background(&self, params: BashInput, ctx: ToolContext) -> Result<ToolOutput> {
+        let env = project_env(&params, &ctx)?;
+        self.spawn_background(params, ctx, env).await
+    }
+
+    async fn spawn_background(
+        &self,
+        params: BashInput,
+        ctx: ToolContext,
+        env: ProjectEnv,
+    ) -> Result<ToolOutput> {
         let command = params.command.clone();
         let description = params.intent.clone();
         let display_name = summarize_background_command(description.as_deref(), &command);
-        let working_dir = ctx.working_dir.clone();
-        let timeout_ms = params.timeout.map(|timeout| timeout.min(600000));
+        let working_dir = command_dir(&params, &ctx).map(Path::to_path_buf);
+        let timeout_ms = params
+            .timeout
+            .or_else(|| params.timeout_secs.map(|secs| secs.saturating_mul(1000)))
+            .map(|timeout| timeout.min(600000));
         let timeout_duration = timeout_ms.map(Duration::from_millis);
 
         let wake = params.wake;
@@ -1011,6 +1358,7 @@ impl BashTool {
                 wake,
 				move |output_path| async move {
 					let mut cmd = build_shell_command(&command);
+					cmd.envs(env.vars());
 					#[cfg(unix)]
 					unsafe {
 						cmd.pre_exec(|| {
@@ -1079,6 +1427,7 @@ impl BashTool {
                             }, if !stdout_done => {
                                 match line {
                                     Ok(Some(line)) => {
+                                        let line = env.redact_line(&line);
                                         handle_background_output_line(&output_path, &mut file, &line, false).await;
                                     }
                                     _ => { stdout_done = true; }
@@ -1092,6 +1441,7 @@ impl BashTool {
                             }, if !stderr_done => {
                                 match line {
                                     Ok(Some(line)) => {
+                                        let line = env.redact_line(&line);
                                         handle_background_output_line(&output_path, &mut file, &line, true).await;
                                     }
                                     _ => { stderr_done = true; }
@@ -0,0 +1,299 @@
+//! Project environment for the bash tool (`[tools.bash]`).
+//!
+//! Env files are read fresh for every command and their variables are set on
+//! the child process, so `$VAR` references resolve in the shell while the
+//! values stay out of the transcript. Every value read from the files, passed
+//! or not, is scrubbed from command output before it reaches the session.
+//! Commands also get the effective `[network]` allowlist in
+//! `JCODE_NETWORK_ALLOWED_HOSTS` and, while it is enforced, proxy variables
+//! pointing at the egress proxy that applies it.
+
+use crate::config::BashToolConfig;
+use crate::logging;
+use std::path::{Path, PathBuf};
+
+/// Values shorter than this are not redacted; scrubbing `1` or `true` from
+/// output would mangle it without protecting anything.
+const MIN_REDACTED_VALUE_LEN: usize = 6;
+
+#[derive(Debug, Default, Clone)]
+pub(super) struct ProjectEnv {
+    /// Variables set on the child process.
+    vars: Vec<(String, String)>,
+    /// `(value, name)` pairs scrubbed from output, longest value first.
+    secrets: Vec<(String, String)>,
+}
+
+impl ProjectEnv {
+    /// Load the configured env files for a command. With `clean` set no file
+    /// variables are passed to the child, but output is still redacted. Fails
+    /// when `[network]` enforces and its egress proxy cannot start.
+    pub(super) fn load(
+        config: &BashToolConfig,
+        working_dir: Option<&Path>,
+        clean: bool,
+    ) -> anyhow::Result<Self> {
+        let mut loaded: Vec<(String, String)> = Vec::new();
+        if config.dotenv {
+            loaded.extend(read_env_file(&resolve_env_path(".env", working_dir), false));
+        }
+        if let Some(env_file) = config
+            .env_file
+            .as_deref()
+            .map(str::trim)
+            .filter(|path| !path.is_empty())
+        {
+            loaded.extend(read_env_file(
+                &resolve_env_path(env_file, working_dir),
+                true,
+            ));
+        }
+        let mut env = Self::from_loaded(config, loaded, clean);
+        // The `[network]` allowlist, so sandbox wrappers and scripts in the
+        // command can restrict egress to the same hosts as jcode itself.
+        if let Some(hosts) = crate::network_policy::allowed_hosts_env_value() {
+            env.vars
+                .push((crate::network_policy::ALLOWED_HOSTS_ENV.to_string(), hosts));
+        }
+        env.vars.extend(super::bash_proxy::proxy_env()?);
+        Ok(env)
+    }
+
+    pub(super) fn from_loaded(
+        config: &BashToolConfig,
+        loaded: Vec<(String, String)>,
+        clean: bool,
+    ) -> Self {
+        let mut secrets: Vec<(String, String)> = if config.redact_output {
+            loaded
+                .iter()
+                .filter(|(_, value)| value.chars().count() >= MIN_REDACTED_VALUE_LEN)
+                .map(|(name, value)| (value.clone(), name.clone()))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        secrets.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
+        secrets.dedup_by(|a, b| a.0 == b.0);
+
+        let vars = if clean {
+            Vec::new()
+        } else {
+            loaded
+                .into_iter()
+                .filter(|(name, _)| env_var_allowed(&config.env_allowlist, name))
+                .collect()
+        };
+        Self { vars, secrets }
+    }
+
+    pub(super) fn vars(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.vars
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// Replace env-file values in `text` with `[redacted:NAME]`.
+    pub(super) fn redact(&self, text: &str) -> String {
+        let mut out = text.to_string();
+        for (value, name) in &self.secrets {
+            if out.contains(value.as_str()) {
+                out = out.replace(value.as_str(), &format!("[redacted:{}]", name));
+            }
+        }
+        out
+    }
+
+    /// Redact one line of output read line by line. A multi-line value (a
+    /// quoted `\n` in the env file) never fits on one line, so each of its
+    /// lines is scrubbed on its own.
+    pub(super) fn redact_line(&self, line: &str) -> String {
+        let mut out = line.to_string();
+        for (value, name) in self.secret_lines() {
+            if out.contains(value) {
+                out = out.replace(value, &format!("[redacted:{}]", name));
+            }
+        }
+        out
+    }
+
+    /// The redacted values as `NAME=value` lines, longest value first, for
+    /// the shell filter of detached commands; `None` when nothing is redacted.
+    /// Like [`Self::redact_line`], the filter works line by line.
+    pub(super) fn redaction_lines(&self) -> Option<String> {
+        let lines = self.secret_lines();
+        (!lines.is_empty()).then(|| {
+            lines
+                .iter()
+                .map(|(line, name)| format!("{}={}", name, line))
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+    }
+
+    /// `(line, name)` for every line of every redacted value, longest first.
+    fn secret_lines(&self) -> Vec<(&str, &str)> {
+        let mut lines: Vec<(&str, &str)> = self
+            .secrets
+            .iter()
+            .flat_map(|(value, name)| {
+                value
+                    .lines()
+                    .filter(|line| line.chars().count() >= MIN_REDACTED_VALUE_LEN)
+                    .map(move |line| (line, name.as_str()))
+            })
+            .collect();
+        lines.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
+        lines.dedup_by(|a, b| a.0 == b.0);
+        lines
+    }
+}
+
+fn env_var_allowed(allowlist: &[String], name: &str) -> bool {
+    allowlist.is_empty()
+        || allowlist.iter().any(|pattern| {
+            let pattern = pattern.trim();
+            match pattern.strip_suffix('*') {
+                Some(prefix) => name.starts_with(prefix),
+                None => pattern == name,
+            }
+        })
+}
+
+fn resolve_env_path(path: &str, working_dir: Option<&Path>) -> PathBuf {
+    if let Some(rest) = path.strip_prefix("~/")
+        && let Some(home) = dirs::home_dir()
+    {
+        return home.join(rest);
+    }
+    let path = Path::new(path);
+    match working_dir {
+        Some(dir) if path.is_relative() => dir.join(path),
+        _ => path.to_path_buf(),
+    }
+}
+
+fn read_env_file(path: &Path, warn_if_missing: bool) -> Vec<(String, String)> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => parse_env_file(&contents),
+        Err(err) => {
+            if warn_if_missing || err.kind() != std::io::ErrorKind::NotFound {
+                logging::warn(&format!(
+                    "[bash] Could not read env file {}: {}",
+                    path.display(),
+                    err
+                ));
+            }
+            Vec::new()
+        }
+    }
+}
+
+/// Parse `KEY=value` lines in the usual `.env` dialect: `#` comments,
+/// optional `export`, and single- or double-quoted values.
+fn parse_env_file(contents: &str) -> Vec<(String, String)> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (name, value) = line.split_once('=')?;
+            let name = name.trim();
+            if name.is_empty()
+                || !name
+                    .chars()
+                    .all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+            {
+                return None;
+            }
+            Some((name.to_string(), parse_env_value(value.trim())))
+        })
+        .collect()
+}
+
+fn parse_env_value(value: &str) -> String {
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|rest| rest.rfind(quote).map(|end| &rest[..end]))
+        {
+            return if quote == '"' {
+                inner.replace("\\n", "\n").replace("\\\"", "\"")
+            } else {
+                inner.to_string()
+            };
+        }
+    }
+    match value.find(" #") {
+        Some(comment) => value[..comment].trim_end().to_string(),
+        None => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_env_file_handles_comments_exports_and_quotes() {
+        let parsed = parse_env_file(
+            "# db\nexport DATABASE_URL=\"postgres://u:p@localhost/db\"\nAPI_KEY='sk-abc 123'\nPLAIN=value # note\nnot a line\n",
+        );
+        assert_eq!(
+            parsed,
+            vec![
+                (
+                    "DATABASE_URL".to_string(),
+                    "postgres://u:p@localhost/db".to_string()
+                ),
+                ("API_KEY".to_string(), "sk-abc 123".to_string()),
+                ("PLAIN".to_string(), "value".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn allowlist_filters_passed_vars_but_redacts_every_value() {
+        let config = BashToolConfig {
+            env_allowlist: vec!["AWS_*".to_string()],
+            ..BashToolConfig::default()
+        };
+        let loaded = vec![
+            ("AWS_SECRET".to_string(), "aws-secret-value".to_string()),
+            ("STRIPE_KEY".to_string(), "sk_live_123456".to_string()),
+            ("DEBUG".to_string(), "1".to_string()),
+        ];
+        let env = ProjectEnv::from_loaded(&config, loaded, false);
+        assert_eq!(
+            env.vars().collect::<Vec<_>>(),
+            vec![("AWS_SECRET", "aws-secret-value")]
+        );
+        assert_eq!(
+            env.redact("key=sk_live_123456 aws=aws-secret-value debug=1"),
+            "key=[redacted:STRIPE_KEY] aws=[redacted:AWS_SECRET] debug=1"
+        );
+        assert_eq!(
+            env.redaction_lines().as_deref(),
+            Some("AWS_SECRET=aws-secret-value\nSTRIPE_KEY=sk_live_123456")
+        );
+    }
+
+    #[test]
+    fn clean_env_passes_nothing_and_redaction_can_be_disabled() {
+        let loaded = vec![("TOKEN".to_string(), "token-value".to_string())];
+        let clean = ProjectEnv::from_loaded(&BashToolConfig::default(), loaded.clone(), true);
+        assert_eq!(clean.vars().count(), 0);
+        assert_eq!(clean.redact("token-value"), "[redacted:TOKEN]");
+
+        let config = BashToolConfig {
+            redact_output: false,
+            ..BashToolConfig::default()
+        };
+        let raw = ProjectEnv::from_loaded(&config, loaded, false);
+        assert_eq!(raw.redact("token-value"), "token-value");
+        assert_eq!(raw.redaction_lines(), None);
+    }
+}
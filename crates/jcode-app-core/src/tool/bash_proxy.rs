@@ -0,0 +1,246 @@
+//! Local egress proxy for bash commands (`[network] mode = "enforce"`).
+//!
+//! While the policy enforces, bash commands get `HTTP_PROXY`, `HTTPS_PROXY`
+//! and `ALL_PROXY` pointing at a loopback proxy started on first use. It only
+//! tunnels to hosts the installed `[network]` policy allows (HTTPS via
+//! `CONNECT`, plain HTTP via absolute-form requests) and answers anything else
+//! with `403`. Destinations are counted with jcode's own, so
+//! `jcode debug network` shows them too. If the proxy cannot start, bash
+//! commands fail rather than run unproxied; the next command tries again.
+//! Programs that ignore the proxy variables and open raw sockets are not
+//! covered.
+
+use anyhow::{Context, Result};
+use jcode_provider_core::egress;
+use std::sync::Mutex;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Largest request head read before the proxy gives up on a connection.
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+
+/// Address of the running proxy. Only a successful start is kept, so a
+/// failed one is retried by the next command.
+static PROXY_ADDR: Mutex<Option<String>> = Mutex::new(None);
+
+/// Variables pointing a command at the proxy, or nothing when the policy is
+/// not enforcing. Errors when enforcing and the proxy could not start.
+pub(super) fn proxy_env() -> Result<Vec<(String, String)>> {
+    if !egress::policy().is_some_and(|policy| policy.enforce) {
+        return Ok(Vec::new());
+    }
+    let addr = {
+        let mut running = PROXY_ADDR
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        match running.as_ref() {
+            Some(addr) => addr.clone(),
+            None => {
+                let addr = start().context(
+                    "[network] mode is \"enforce\" but the bash egress proxy could not start",
+                )?;
+                *running = Some(addr.clone());
+                addr
+            }
+        }
+    };
+    let url = format!("http://{addr}");
+    let mut vars = Vec::new();
+    for name in ["HTTP_PROXY", "HTTPS_PROXY", "ALL_PROXY"] {
+        vars.push((name.to_string(), url.clone()));
+        vars.push((name.to_ascii_lowercase(), url.clone()));
+    }
+    for name in ["NO_PROXY", "no_proxy"] {
+        vars.push((name.to_string(), "localhost,127.0.0.1,::1".to_string()));
+    }
+    Ok(vars)
+}
+
+fn start() -> Result<String> {
+    let handle = tokio::runtime::Handle::try_current()?;
+    let bound = std::net::TcpListener::bind("127.0.0.1:0").and_then(|listener| {
+        listener.set_nonblocking(true)?;
+        Ok(listener)
+    });
+    let listener = match bound {
+        Ok(listener) => listener,
+        Err(error) => {
+            crate::logging::warn(&format!(
+                "[network] Could not start the bash egress proxy: {}",
+                error
+            ));
+            return Err(error.into());
+        }
+    };
+    let addr = listener.local_addr()?.to_string();
+    handle.spawn(async move {
+        let listener = match TcpListener::from_std(listener) {
+            Ok(listener) => listener,
+            Err(error) => {
+                crate::logging::warn(&format!("[network] Bash egress proxy failed: {}", error));
+                return;
+            }
+        };
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let _ = serve(stream).await;
+            });
+        }
+    });
+    crate::logging::info(&format!(
+        "[network] Bash egress proxy listening on {}",
+        addr
+    ));
+    Ok(addr)
+}
+
+async fn serve(mut client: TcpStream) -> std::io::Result<()> {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 4096];
+    let head_len = loop {
+        if let Some(pos) = head.windows(4).position(|window| window == b"\r\n\r\n") {
+            break pos + 4;
+        }
+        if head.len() > MAX_HEAD_BYTES {
+            return respond(&mut client, "431 Request Header Fields Too Large", "").await;
+        }
+        let read = client.read(&mut buf).await?;
+        if read == 0 {
+            return Ok(());
+        }
+        head.extend_from_slice(&buf[..read]);
+    };
+    let line_end = head
+        .windows(2)
+        .position(|window| window == b"\r\n")
+        .unwrap_or(head_len);
+    let request_line = String::from_utf8_lossy(&head[..line_end]).to_string();
+    let Some(target) = parse_request_line(&request_line) else {
+        return respond(&mut client, "400 Bad Request", "").await;
+    };
+    if let Err(denied) = egress::check_host(&target.host) {
+        return respond(&mut client, "403 Forbidden", &denied.to_string()).await;
+    }
+    let mut upstream = match TcpStream::connect((target.host.as_str(), target.port)).await {
+        Ok(stream) => stream,
+        Err(error) => return respond(&mut client, "502 Bad Gateway", &error.to_string()).await,
+    };
+    match target.origin_line {
+        None => {
+            client
+                .write_all(b"HTTP/1.1 200 Connection Established\r\n\r\n")
+                .await?;
+            upstream.write_all(&head[head_len..]).await?;
+        }
+        Some(line) => {
+            upstream.write_all(line.as_bytes()).await?;
+            upstream.write_all(&head[line_end..]).await?;
+        }
+    }
+    tokio::io::copy_bidirectional(&mut client, &mut upstream).await?;
+    Ok(())
+}
+
+async fn respond(client: &mut TcpStream, status: &str, body: &str) -> std::io::Result<()> {
+    let body = if body.is_empty() {
+        String::new()
+    } else {
+        format!("{body}\n")
+    };
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    client.write_all(response.as_bytes()).await
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct ProxyTarget {
+    host: String,
+    port: u16,
+    /// The request line rewritten to origin form for plain HTTP; `None` for a
+    /// `CONNECT` tunnel.
+    origin_line: Option<String>,
+}
+
+fn parse_request_line(line: &str) -> Option<ProxyTarget> {
+    let mut parts = line.split_whitespace();
+    let method = parts.next()?;
+    let target = parts.next()?;
+    let version = parts.next()?;
+    if method.eq_ignore_ascii_case("CONNECT") {
+        let (host, port) = target.rsplit_once(':')?;
+        return Some(ProxyTarget {
+            host: host
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .to_string(),
+            port: port.parse().ok()?,
+            origin_line: None,
+        });
+    }
+    let url = reqwest::Url::parse(target).ok()?;
+    if url.scheme() != "http" {
+        return None;
+    }
+    let mut path = url.path().to_string();
+    if let Some(query) = url.query() {
+        path.push('?');
+        path.push_str(query);
+    }
+    Some(ProxyTarget {
+        host: url.host_str()?.to_string(),
+        port: url.port_or_known_default()?,
+        origin_line: Some(format!("{method} {path} {version}")),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_connect_and_absolute_form_requests() {
+        assert_eq!(
+            parse_request_line("CONNECT crates.io:443 HTTP/1.1"),
+            Some(ProxyTarget {
+                host: "crates.io".to_string(),
+                port: 443,
+                origin_line: None,
+            })
+        );
+        assert_eq!(
+            parse_request_line("GET http://example.com/a/b?c=1 HTTP/1.1"),
+            Some(ProxyTarget {
+                host: "example.com".to_string(),
+                port: 80,
+                origin_line: Some("GET /a/b?c=1 HTTP/1.1".to_string()),
+            })
+        );
+        assert_eq!(parse_request_line("GET /relative HTTP/1.1"), None);
+    }
+
+    #[test]
+    fn failed_start_errors_while_enforcing_and_is_retried() {
+        use jcode_provider_core::egress::EgressPolicy;
+
+        let previous = egress::policy();
+        egress::set_policy(EgressPolicy {
+            enforce: true,
+            allowed_hosts: vec!["crates.io".to_string()],
+        });
+        // Outside a runtime the proxy has nowhere to run, so it cannot start.
+        let without_runtime = proxy_env();
+        let runtime = tokio::runtime::Runtime::new().expect("runtime");
+        let with_runtime = runtime.block_on(async { proxy_env() });
+        egress::set_policy(previous.unwrap_or_default());
+
+        if PROXY_ADDR.lock().unwrap().is_some() && without_runtime.is_ok() {
+            // Another test already started the proxy in this process.
+            return;
+        }
+        assert!(without_runtime.is_err());
+        let vars = with_runtime.expect("proxy starts on retry");
+        assert!(vars.iter().any(|(name, _)| name == "HTTPS_PROXY"));
+    }
+}
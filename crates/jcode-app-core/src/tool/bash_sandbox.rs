@@ -0,0 +1,81 @@
+//! Read-only sandbox for bash commands in `jcode --safe` sessions.
+//!
+//! On Linux the command runs under `bwrap` with the whole filesystem mounted
+//! read-only except the working directory and a private `/tmp`. On macOS
+//! `sandbox-exec` denies writes outside the working directory and the temp
+//! directories. Without either tool the command runs unsandboxed, still
+//! behind the safe mode approval prompt.
+
+use std::path::Path;
+use std::sync::LazyLock;
+
+static BWRAP_AVAILABLE: LazyLock<bool> = LazyLock::new(|| on_path("bwrap"));
+static SANDBOX_EXEC_AVAILABLE: LazyLock<bool> = LazyLock::new(|| on_path("sandbox-exec"));
+
+fn on_path(binary: &str) -> bool {
+    std::env::var_os("PATH")
+        .is_some_and(|path| std::env::split_paths(&path).any(|dir| dir.join(binary).is_file()))
+}
+
+pub(super) fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Wrap `command` so it can only write inside `working_dir`. Returns `None`
+/// when no sandbox tool is available on this platform.
+pub(super) fn sandbox_command(command: &str, working_dir: Option<&Path>) -> Option<String> {
+    let working_dir = working_dir
+        .map(Path::to_path_buf)
+        .or_else(|| std::env::current_dir().ok())?;
+    if cfg!(target_os = "linux") && *BWRAP_AVAILABLE {
+        Some(bwrap_command(command, &working_dir))
+    } else if cfg!(target_os = "macos") && *SANDBOX_EXEC_AVAILABLE {
+        Some(sandbox_exec_command(command, &working_dir))
+    } else {
+        None
+    }
+}
+
+fn bwrap_command(command: &str, working_dir: &Path) -> String {
+    let dir = shell_quote(&working_dir.to_string_lossy());
+    format!(
+        "bwrap --ro-bind / / --dev /dev --proc /proc --tmpfs /tmp --bind {dir} {dir} --chdir {dir} --die-with-parent bash -c {}",
+        shell_quote(command)
+    )
+}
+
+fn sandbox_exec_command(command: &str, working_dir: &Path) -> String {
+    let dir = working_dir.to_string_lossy().replace('"', "\\\"");
+    let profile = format!(
+        "(version 1)(allow default)(deny file-write*)(allow file-write* (subpath \"{dir}\") (subpath \"/private/tmp\") (subpath \"/private/var/folders\") (literal \"/dev/null\"))"
+    );
+    format!(
+        "sandbox-exec -p {} bash -c {}",
+        shell_quote(&profile),
+        shell_quote(command)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bwrap_command_binds_only_the_working_dir_writable() {
+        let wrapped = bwrap_command("echo 'hi' > out.txt", Path::new("/work/repo"));
+        assert_eq!(
+            wrapped,
+            "bwrap --ro-bind / / --dev /dev --proc /proc --tmpfs /tmp --bind '/work/repo' '/work/repo' --chdir '/work/repo' --die-with-parent bash -c 'echo '\\''hi'\\'' > out.txt'"
+        );
+    }
+
+    #[test]
+    fn sandbox_exec_profile_allows_writes_under_the_working_dir() {
+        let wrapped = sandbox_exec_command("ls", Path::new("/Users/me/repo"));
+        assert!(
+            wrapped.starts_with("sandbox-exec -p '(version 1)(allow default)(deny file-write*)")
+        );
+        assert!(wrapped.contains("(subpath \"/Users/me/repo\")"));
+        assert!(wrapped.ends_with("bash -c 'ls'"));
+    }
+}
@@ -0,0 +1,359 @@
+//! `clipboard` tool: read the user's clipboard or put text on it.
+//!
+//! Writes are forwarded to the session's client, which owns the terminal and
+//! can use OSC 52 when the user is on SSH. Reads run on this machine through
+//! the platform's clipboard command and need a one-time approval per session,
+//! since clipboards often hold passwords and tokens. The approval goes through
+//! the stdin forwarding channel and the permission inbox like safe mode does,
+//! tagged with [`CLIPBOARD_READ_APPROVAL_PREFIX`].
+
+use super::{Tool, ToolContext, ToolOutput};
+use crate::bus::{Bus, BusEvent, ClipboardWriteRequested};
+use anyhow::{Result, anyhow, bail};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+
+/// Prefix of stdin request ids that ask the client to allow clipboard reads.
+pub const CLIPBOARD_READ_APPROVAL_PREFIX: &str = "clipboard-approval-";
+
+/// Longest clipboard text returned by a read.
+const MAX_READ_BYTES: usize = 32 * 1024;
+/// Longest text accepted by a write. Many terminals drop larger OSC 52
+/// payloads.
+const MAX_WRITE_BYTES: usize = 100 * 1024;
+
+/// Sessions whose user allowed clipboard reads.
+static READ_GRANTS: LazyLock<Mutex<HashSet<String>>> = LazyLock::new(|| Mutex::new(HashSet::new()));
+static APPROVAL_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+pub struct ClipboardTool;
+
+impl ClipboardTool {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[derive(Deserialize)]
+struct ClipboardInput {
+    action: String,
+    #[serde(default)]
+    text: Option<String>,
+}
+
+#[async_trait]
+impl Tool for ClipboardTool {
+    fn name(&self) -> &str {
+        "clipboard"
+    }
+
+    fn description(&self) -> &str {
+        "Read text from or write text to the user's clipboard. Reads need the user's approval once per session."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "required": ["action"],
+            "properties": {
+                "intent": super::intent_schema_property(),
+                "action": {
+                    "type": "string",
+                    "enum": ["read", "write"],
+                    "description": "read returns the clipboard text; write replaces it."
+                },
+                "text": {
+                    "type": "string",
+                    "description": "Text to copy (write only)."
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, input: Value, ctx: ToolContext) -> Result<ToolOutput> {
+        let params: ClipboardInput = serde_json::from_value(input)?;
+        match params.action.as_str() {
+            "read" => read(&ctx).await,
+            "write" => {
+                let text = params
+                    .text
+                    .ok_or_else(|| anyhow!("clipboard write needs `text`"))?;
+                write(text, &ctx)
+            }
+            other => bail!("Unknown clipboard action: {other}. Valid actions: read, write"),
+        }
+    }
+}
+
+async fn read(ctx: &ToolContext) -> Result<ToolOutput> {
+    ensure_read_allowed(ctx).await?;
+    let text = tokio::task::spawn_blocking(read_clipboard_text).await??;
+
+    let digest = format!("{:x}", Sha256::digest(text.as_bytes()));
+    crate::logging::event_info(
+        "CLIPBOARD",
+        vec![
+            ("action", "read".to_string()),
+            ("session_id", ctx.session_id.clone()),
+            ("bytes", text.len().to_string()),
+            ("sha256", digest.clone()),
+        ],
+    );
+
+    let mut output = crate::util::truncate_str(&text, MAX_READ_BYTES).to_string();
+    if output.len() < text.len() {
+        output.push_str(&format!(
+            "\n\n[clipboard truncated: showing {} of {} bytes]",
+            output.len(),
+            text.len()
+        ));
+    }
+    if text.is_empty() {
+        output = "Clipboard is empty.".to_string();
+    }
+    Ok(ToolOutput::new(output)
+        .with_title("clipboard read")
+        .with_metadata(json!({
+            "action": "read",
+            "bytes": text.len(),
+            "sha256": digest,
+        })))
+}
+
+fn write(text: String, ctx: &ToolContext) -> Result<ToolOutput> {
+    if text.len() > MAX_WRITE_BYTES {
+        bail!(
+            "Clipboard text is {} bytes; the limit is {} bytes",
+            text.len(),
+            MAX_WRITE_BYTES
+        );
+    }
+    if ctx.stdin_request_tx.is_none() {
+        bail!("No interactive client is attached to receive the clipboard text");
+    }
+    let chars = text.chars().count();
+    crate::logging::event_info(
+        "CLIPBOARD",
+        vec![
+            ("action", "write".to_string()),
+            ("session_id", ctx.session_id.clone()),
+            ("bytes", text.len().to_string()),
+        ],
+    );
+    Bus::global().publish(BusEvent::ClipboardWriteRequested(ClipboardWriteRequested {
+        session_id: ctx.session_id.clone(),
+        text,
+    }));
+    Ok(
+        ToolOutput::new(format!("Copied {} characters to the clipboard.", chars))
+            .with_title("clipboard write")
+            .with_metadata(json!({ "action": "write", "chars": chars })),
+    )
+}
+
+/// Ask the user once per session before the first read.
+async fn ensure_read_allowed(ctx: &ToolContext) -> Result<()> {
+    if read_granted(&ctx.session_id) {
+        return Ok(());
+    }
+    let Some(stdin_tx) = ctx.stdin_request_tx.as_ref() else {
+        bail!("Clipboard reads need the user's approval, but no interactive client is attached");
+    };
+    let request_id = format!(
+        "{}{}-{}",
+        CLIPBOARD_READ_APPROVAL_PREFIX,
+        ctx.tool_call_id,
+        APPROVAL_COUNTER.fetch_add(1, Ordering::Relaxed)
+    );
+    let approved = super::safe_mode::request_approval(
+        ctx,
+        stdin_tx,
+        request_id,
+        "clipboard",
+        "clipboard read".to_string(),
+    )
+    .await
+    .ok_or_else(|| anyhow!("Clipboard read needs approval, but the client left"))?;
+    if !approved {
+        bail!("The user declined clipboard access");
+    }
+    READ_GRANTS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(ctx.session_id.clone());
+    Ok(())
+}
+
+fn read_granted(session_id: &str) -> bool {
+    READ_GRANTS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .contains(session_id)
+}
+
+/// What post-tool hooks see of a clipboard read: its size and hash, never
+/// the text. Other tool outputs pass through unchanged.
+pub(super) fn audit_view<'a>(tool_name: &str, output: &'a ToolOutput) -> Cow<'a, str> {
+    let metadata = output.metadata.as_ref();
+    let is_read = tool_name == "clipboard"
+        && metadata
+            .and_then(|m| m.get("action"))
+            .and_then(Value::as_str)
+            == Some("read");
+    if !is_read {
+        return Cow::Borrowed(&output.output);
+    }
+    let field = |key: &str| {
+        metadata
+            .and_then(|m| m.get(key))
+            .map(|value| value.to_string().trim_matches('"').to_string())
+            .unwrap_or_default()
+    };
+    Cow::Owned(format!(
+        "[clipboard contents redacted: {} bytes, sha256 {}]",
+        field("bytes"),
+        field("sha256")
+    ))
+}
+
+/// Clipboard readers to try, most specific first.
+fn read_commands() -> Vec<(&'static str, &'static [&'static str])> {
+    if cfg!(target_os = "macos") {
+        vec![("pbpaste", &[])]
+    } else if cfg!(windows) {
+        vec![(
+            "powershell",
+            &["-NoProfile", "-Command", "Get-Clipboard -Raw"],
+        )]
+    } else {
+        let mut commands: Vec<(&'static str, &'static [&'static str])> = Vec::new();
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            commands.push(("wl-paste", &["--no-newline", "--type", "text"]));
+        }
+        commands.push(("xclip", &["-selection", "clipboard", "-o"]));
+        commands.push(("xsel", &["--clipboard", "--output"]));
+        commands
+    }
+}
+
+fn read_clipboard_text() -> Result<String> {
+    let commands = read_commands();
+    for (program, args) in &commands {
+        let Ok(output) = Command::new(program)
+            .args(*args)
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+        else {
+            continue;
+        };
+        if output.status.success() {
+            return Ok(String::from_utf8_lossy(&output.stdout).into_owned());
+        }
+    }
+    let tried: Vec<&str> = commands.iter().map(|(program, _)| *program).collect();
+    bail!(
+        "Could not read the clipboard (tried {}). Over SSH the remote host usually has no clipboard.",
+        tried.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(session_id: &str) -> ToolContext {
+        ToolContext {
+            session_id: session_id.to_string(),
+            message_id: "m".to_string(),
+            tool_call_id: "t".to_string(),
+            working_dir: None,
+            workspace_roots: Vec::new(),
+            stdin_request_tx: None,
+            graceful_shutdown_signal: None,
+            execution_mode: super::super::ToolExecutionMode::Direct,
+        }
+    }
+
+    #[tokio::test]
+    #[allow(
+        clippy::await_holding_lock,
+        reason = "the approval is listed in the permission inbox under JCODE_HOME"
+    )]
+    async fn read_asks_once_per_session_and_refuses_without_a_client() {
+        let _storage_guard = crate::storage::lock_test_env();
+        let temp_home = tempfile::TempDir::new().expect("temp home");
+        let previous_home = std::env::var_os("JCODE_HOME");
+        crate::env::set_var("JCODE_HOME", temp_home.path());
+
+        let error = read(&ctx("clipboard-unattended")).await.unwrap_err();
+        assert!(error.to_string().contains("approval"), "{error}");
+
+        let (stdin_tx, mut stdin_rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut attended = ctx("clipboard-attended");
+        attended.stdin_request_tx = Some(stdin_tx);
+        let answer = tokio::spawn(async move {
+            let request = stdin_rx.recv().await.expect("approval request");
+            assert!(
+                request
+                    .request_id
+                    .starts_with(CLIPBOARD_READ_APPROVAL_PREFIX)
+            );
+            let _ = request.response_tx.send("y".to_string());
+            stdin_rx
+        });
+        ensure_read_allowed(&attended).await.expect("approved");
+        let mut stdin_rx = answer.await.expect("responder");
+
+        ensure_read_allowed(&attended)
+            .await
+            .expect("still approved");
+        assert!(
+            stdin_rx.try_recv().is_err(),
+            "second read must not ask again"
+        );
+        assert!(!read_granted("clipboard-unattended"));
+        assert!(
+            crate::safety::inbox().is_empty(),
+            "the answered approval leaves the inbox"
+        );
+
+        match previous_home {
+            Some(value) => crate::env::set_var("JCODE_HOME", value),
+            None => crate::env::remove_var("JCODE_HOME"),
+        }
+    }
+
+    #[test]
+    fn audit_view_hides_read_contents() {
+        let read = ToolOutput::new("hunter2").with_metadata(json!({
+            "action": "read",
+            "bytes": 7,
+            "sha256": "abc123",
+        }));
+        assert_eq!(
+            audit_view("clipboard", &read),
+            "[clipboard contents redacted: 7 bytes, sha256 abc123]"
+        );
+
+        let write = ToolOutput::new("Copied 3 characters to the clipboard.")
+            .with_metadata(json!({ "action": "write", "chars": 3 }));
+        assert_eq!(audit_view("clipboard", &write), write.output);
+        assert_eq!(audit_view("read", &read), "hunter2");
+    }
+
+    #[test]
+    fn write_requires_a_client_and_caps_size() {
+        let error = write("x".repeat(MAX_WRITE_BYTES + 1), &ctx("clipboard-size")).unwrap_err();
+        assert!(error.to_string().contains("limit"), "{error}");
+        let error = write("hello".to_string(), &ctx("clipboard-size")).unwrap_err();
+        assert!(error.to_string().contains("client"), "{error}");
+    }
+}
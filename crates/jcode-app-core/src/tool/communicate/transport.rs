@@ -105,6 +105,7 @@ pub(super) async fn send_request_with_timeout(
             | "connection_type"
             | "connection_phase"
             | "status_detail"
+            | "rate_limited"
             | "upstream_provider"
             | "reloading"
             | "reload_progress"
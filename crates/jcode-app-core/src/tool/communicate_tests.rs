@@ -680,18 +680,21 @@ fn latest_assistant_report_uses_last_non_empty_assistant_message() {
             content: " earlier ".to_string(),
             tool_calls: None,
             tool_data: None,
+            usage: None,
         },
         HistoryMessage {
             role: "user".to_string(),
             content: "ignored".to_string(),
             tool_calls: None,
             tool_data: None,
+            usage: None,
         },
         HistoryMessage {
             role: "assistant".to_string(),
             content: " final report ".to_string(),
             tool_calls: None,
             tool_data: None,
+            usage: None,
         },
     ];
 
@@ -1028,6 +1031,7 @@ impl RawClient {
             content: content.to_string(),
             images: vec![],
             system_reminder: None,
+            focus_secs: None,
         })
         .await
     }
@@ -1142,6 +1146,7 @@ fn test_ctx(session_id: &str, working_dir: &Path) -> ToolContext {
         message_id: "msg-1".to_string(),
         tool_call_id: "call-1".to_string(),
         working_dir: Some(working_dir.to_path_buf()),
+        workspace_roots: Vec::new(),
         stdin_request_tx: None,
         graceful_shutdown_signal: None,
         execution_mode: ToolExecutionMode::Direct,
@@ -182,6 +182,7 @@ fn format_members_includes_status_and_detail() {
         message_id: "msg-1".to_string(),
         tool_call_id: "call-1".to_string(),
         working_dir: None,
+        workspace_roots: Vec::new(),
         stdin_request_tx: None,
         graceful_shutdown_signal: None,
         execution_mode: ToolExecutionMode::Direct,
@@ -15,6 +15,7 @@ fn ctx() -> ToolContext {
         message_id: "cov".into(),
         tool_call_id: "cov".into(),
         working_dir: None,
+        workspace_roots: Vec::new(),
         stdin_request_tx: None,
         graceful_shutdown_signal: None,
         execution_mode: ToolExecutionMode::Direct,
@@ -10,6 +10,7 @@ fn ctx() -> ToolContext {
         message_id: "test".into(),
         tool_call_id: "test".into(),
         working_dir: None,
+        workspace_roots: Vec::new(),
         stdin_request_tx: None,
         graceful_shutdown_signal: None,
         execution_mode: ToolExecutionMode::Direct,
@@ -215,6 +215,24 @@ impl Tool for ConversationSearchTool {
                             crate::message::ContentBlock::OpenAICompaction { .. } => {
                                 output.push_str("[OpenAI native compaction]\n");
                             }
+                            crate::message::ContentBlock::ServerToolUse { name, .. } => {
+                                output.push_str(&format!("[Tool call: {}]\n", name));
+                            }
+                            crate::message::ContentBlock::WebSearchResult {
+                                results,
+                                error_code,
+                                ..
+                            } => {
+                                let text = crate::message::web_search_result_text(
+                                    results,
+                                    error_code.as_deref(),
+                                );
+                                output.push_str(&format!(
+                                    "[Tool result: {}]\n",
+                                    crate::util::truncate_str(&text, 200)
+                                ));
+                            }
+                            crate::message::ContentBlock::Citations { .. } => {}
                         }
                     }
                     output.push('\n');
@@ -278,6 +296,14 @@ fn message_to_text(msg: &Message) -> String {
             crate::message::ContentBlock::OpenAICompaction { .. } => {
                 Some("[OpenAI native compaction]".to_string())
             }
+            crate::message::ContentBlock::WebSearchResult {
+                results,
+                error_code,
+                ..
+            } => Some(crate::message::web_search_result_text(
+                results,
+                error_code.as_deref(),
+            )),
             _ => None,
         })
         .collect::<Vec<_>>()
@@ -340,6 +366,7 @@ mod tests {
             message_id: "test-message".to_string(),
             tool_call_id: "test-tool-call".to_string(),
             working_dir: None,
+            workspace_roots: Vec::new(),
             stdin_request_tx: None,
             graceful_shutdown_signal: None,
             execution_mode: crate::tool::ToolExecutionMode::Direct,
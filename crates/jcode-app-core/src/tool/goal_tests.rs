@@ -16,6 +16,7 @@ async fn initiative_tool_create_and_resume_round_trip() {
         message_id: "msg1".to_string(),
         tool_call_id: "tool1".to_string(),
         working_dir: Some(project.clone()),
+        workspace_roots: Vec::new(),
         stdin_request_tx: None,
         graceful_shutdown_signal: None,
         execution_mode: crate::tool::ToolExecutionMode::AgentTurn,
@@ -96,6 +97,7 @@ async fn initiative_tool_list_opens_goals_overview_by_default() {
         message_id: "msg1".to_string(),
         tool_call_id: "tool1".to_string(),
         working_dir: Some(project.clone()),
+        workspace_roots: Vec::new(),
         stdin_request_tx: None,
         graceful_shutdown_signal: None,
         execution_mode: crate::tool::ToolExecutionMode::AgentTurn,
@@ -144,6 +146,7 @@ async fn initiative_tool_update_refreshes_open_overview_without_stealing_focus()
         message_id: "msg1".to_string(),
         tool_call_id: "tool1".to_string(),
         working_dir: Some(project.clone()),
+        workspace_roots: Vec::new(),
         stdin_request_tx: None,
         graceful_shutdown_signal: None,
         execution_mode: crate::tool::ToolExecutionMode::AgentTurn,
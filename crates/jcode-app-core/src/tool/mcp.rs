@@ -156,6 +156,7 @@ impl McpManagementTool {
         let manager = self.manager.read().await;
         let servers = manager.connected_servers().await;
         let all_tools = manager.all_tools().await;
+        let degraded = manager.degraded_servers().await;
 
         if servers.is_empty() {
             return Ok(ToolOutput::new(
@@ -171,7 +172,14 @@ impl McpManagementTool {
         output.push_str(&format!("Connected MCP servers: {}\n\n", servers.len()));
 
         for server in &servers {
-            output.push_str(&format!("## {}\n", server));
+            if degraded.contains(server) {
+                output.push_str(&format!(
+                    "## {} (degraded: tool calls keep timing out; reload to reconnect)\n",
+                    server
+                ));
+            } else {
+                output.push_str(&format!("## {}\n", server));
+            }
             let server_tools: Vec<_> = all_tools.iter().filter(|(s, _)| s == server).collect();
 
             if server_tools.is_empty() {
@@ -207,6 +215,7 @@ impl McpManagementTool {
             shared: true,
             transport: None,
             url: None,
+            timeout_secs: None,
         };
 
         let manager = self.manager.read().await;
@@ -422,6 +431,7 @@ mod tests {
             message_id: "test-message".to_string(),
             tool_call_id: "test-tool-call".to_string(),
             working_dir: None,
+            workspace_roots: Vec::new(),
             stdin_request_tx: None,
             graceful_shutdown_signal: None,
             execution_mode: crate::tool::ToolExecutionMode::Direct,
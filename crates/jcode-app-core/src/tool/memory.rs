@@ -11,6 +11,12 @@ pub struct MemoryTool {
     manager: MemoryManager,
 }
 
+impl Default for MemoryTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MemoryTool {
     pub fn new() -> Self {
         Self {
@@ -1,10 +1,15 @@
 mod agentgrep;
 pub mod ambient;
 mod apply_patch;
+pub mod ask_user;
 mod bash;
+mod bash_env;
+mod bash_proxy;
+mod bash_sandbox;
 mod batch;
 mod bg;
 mod browser;
+pub mod clipboard;
 mod communicate;
 #[cfg(target_os = "macos")]
 mod computer;
@@ -16,11 +21,14 @@ mod goal;
 mod invalid;
 mod ls;
 pub mod mcp;
-mod memory;
+pub mod memory;
 mod multiedit;
 mod open;
 mod patch;
+mod plan_propose;
 mod read;
+mod refactor_rename;
+pub mod safe_mode;
 pub mod selfdev;
 pub(crate) mod serde_coerce;
 mod session_search;
@@ -29,6 +37,7 @@ mod side_panel;
 mod skill;
 mod task;
 mod todo;
+mod tree;
 mod webfetch;
 mod websearch;
 mod write;
@@ -45,14 +54,19 @@ use std::sync::{LazyLock, RwLock as StdRwLock};
 use tokio::sync::RwLock;
 
 pub(crate) use jcode_tool_core::intent_schema_property;
-pub use jcode_tool_core::{StdinInputRequest, Tool, ToolContext, ToolExecutionMode};
+pub use jcode_tool_core::{
+    StdinInputRequest, Tool, ToolContext, ToolExecutionMode, workspace_root_name,
+};
 pub use jcode_tool_types::{ToolImage, ToolOutput};
+pub(crate) use refactor_rename::discard_pending_rename;
 pub(crate) use session_search::spawn_recent_index_warmup;
 
 #[derive(Clone, Debug, Default)]
 struct SessionToolPolicy {
     allowed_tools: Option<HashSet<String>>,
     disabled_tools: HashSet<String>,
+    safe_mode: bool,
+    approval_gate: bool,
 }
 
 static SESSION_TOOL_POLICIES: LazyLock<StdRwLock<HashMap<String, SessionToolPolicy>>> =
@@ -62,6 +76,8 @@ pub(crate) fn set_session_tool_policy(
     session_id: &str,
     allowed_tools: Option<HashSet<String>>,
     disabled_tools: HashSet<String>,
+    safe_mode: bool,
+    approval_gate: bool,
 ) {
     let mut policies = SESSION_TOOL_POLICIES
         .write()
@@ -71,6 +87,8 @@ pub(crate) fn set_session_tool_policy(
         SessionToolPolicy {
             allowed_tools,
             disabled_tools,
+            safe_mode,
+            approval_gate,
         },
     );
 }
@@ -90,6 +108,15 @@ fn session_tool_policy(session_id: &str) -> Option<SessionToolPolicy> {
         .cloned()
 }
 
+/// Whether the session runs in `jcode --safe` mode.
+pub(crate) fn session_safe_mode(session_id: &str) -> bool {
+    SESSION_TOOL_POLICIES
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(session_id)
+        .is_some_and(|policy| policy.safe_mode)
+}
+
 /// Registry of available tools (Arc-wrapped for sharing)
 ///
 /// Clone creates a fresh CompactionManager so each subagent gets independent
@@ -178,6 +205,12 @@ impl Registry {
                 multiedit::MultiEditTool::new,
             );
             Self::insert_tool_timed(&mut m, &mut timings, "patch", patch::PatchTool::new);
+            Self::insert_tool_timed(
+                &mut m,
+                &mut timings,
+                "refactor_rename",
+                refactor_rename::RefactorRenameTool::new,
+            );
             Self::insert_tool_timed(
                 &mut m,
                 &mut timings,
@@ -185,9 +218,16 @@ impl Registry {
                 apply_patch::ApplyPatchTool::new,
             );
             Self::insert_tool_timed(&mut m, &mut timings, "ls", ls::LsTool::new);
+            Self::insert_tool_timed(&mut m, &mut timings, "tree", tree::TreeTool::new);
             Self::insert_tool_timed(&mut m, &mut timings, "bash", bash::BashTool::new);
             Self::insert_tool_timed(&mut m, &mut timings, "browser", browser::BrowserTool::new);
             Self::insert_tool_timed(&mut m, &mut timings, "open", open::OpenTool::new);
+            Self::insert_tool_timed(
+                &mut m,
+                &mut timings,
+                "clipboard",
+                clipboard::ClipboardTool::new,
+            );
             #[cfg(target_os = "macos")]
             Self::insert_tool_timed(
                 &mut m,
@@ -209,6 +249,13 @@ impl Registry {
             );
             Self::insert_tool_timed(&mut m, &mut timings, "invalid", invalid::InvalidTool::new);
             Self::insert_tool_timed(&mut m, &mut timings, "todo", todo::TodoTool::new);
+            Self::insert_tool_timed(&mut m, &mut timings, "ask_user", ask_user::AskUserTool::new);
+            Self::insert_tool_timed(
+                &mut m,
+                &mut timings,
+                "plan_propose",
+                plan_propose::PlanProposeTool::new,
+            );
             Self::insert_tool_timed(&mut m, &mut timings, "bg", bg::BgTool::new);
             Self::insert_tool_timed(
                 &mut m,
@@ -539,7 +586,8 @@ impl Registry {
     pub async fn execute(&self, name: &str, input: Value, ctx: ToolContext) -> Result<ToolOutput> {
         let tools = self.tools.read().await;
         let resolved_name = Self::resolve_tool_name(name);
-        if let Some(policy) = session_tool_policy(&ctx.session_id) {
+        let policy = session_tool_policy(&ctx.session_id);
+        if let Some(policy) = policy.as_ref() {
             if let Some(allowed) = policy.allowed_tools.as_ref()
                 && !allowed.contains(resolved_name)
             {
@@ -595,6 +643,33 @@ impl Registry {
             }
         }
 
+        // Matcher-scoped rules from config and `.jcode/hooks.toml`. These
+        // fail closed: any non-zero exit or timeout blocks the call.
+        let hook_call = crate::hooks::ToolHookCall {
+            session_id: &ctx.session_id,
+            working_dir: ctx.working_dir.as_deref(),
+            tool_name: resolved_name,
+            input: &input,
+        };
+        if let crate::hooks::GateDecision::Block { reason } =
+            crate::hooks::run_pre_tool_rules(&hook_call).await
+        {
+            let mut fields =
+                Self::tool_lifecycle_fields("blocked", name, resolved_name, &input, &ctx);
+            fields.push(("block_reason".to_string(), reason.clone()));
+            crate::logging::event_warn("TOOL_LIFECYCLE", fields);
+            return Err(anyhow::anyhow!("Tool call blocked by hook: {reason}"));
+        }
+
+        // `jcode --safe`: ask the user before anything outside the
+        // auto-allowed tier runs. The approval gate asks the same way without
+        // the other safe mode restrictions.
+        if policy.is_some_and(|policy| policy.safe_mode) {
+            safe_mode::check(resolved_name, &input, &ctx).await?;
+        } else if policy.is_some_and(|policy| policy.approval_gate) {
+            safe_mode::check_approval("Approval gate", resolved_name, &input, &ctx).await?;
+        }
+
         crate::logging::event_info(
             "TOOL_LIFECYCLE",
             Self::tool_lifecycle_fields("start", name, resolved_name, &input, &ctx),
@@ -605,7 +680,18 @@ impl Registry {
         let latency_ms = started_at.elapsed().as_millis().min(u128::from(u64::MAX)) as u64;
 
         crate::telemetry::record_tool_execution(resolved_name, &input, result.is_ok(), latency_ms);
+        crate::metrics::record_tool_execution(resolved_name, result.is_ok());
         Self::fire_post_tool_hook(resolved_name, &ctx, &result, latency_ms);
+        match &result {
+            Ok(output) => {
+                let audited = clipboard::audit_view(resolved_name, output);
+                crate::hooks::run_post_tool_rules(&hook_call, true, &audited).await
+            }
+            Err(error) => {
+                let message = crate::util::format_error_chain(error);
+                crate::hooks::run_post_tool_rules(&hook_call, false, &message).await
+            }
+        }
 
         let mut output = match result {
             Ok(output) => output,
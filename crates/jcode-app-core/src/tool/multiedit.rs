@@ -1,4 +1,5 @@
 use super::{Tool, ToolContext, ToolOutput};
+use crate::bus::{Bus, BusEvent, FileOp, FileTouch};
 use anyhow::Result;
 use async_trait::async_trait;
 use serde::Deserialize;
@@ -127,6 +128,28 @@ impl Tool for MultiEditTool {
 
         // Write the result
         tokio::fs::write(&path, &content).await?;
+        crate::provenance::record_tool_change(
+            &ctx,
+            "multiedit",
+            &path,
+            &original_content,
+            &content,
+        );
+
+        if !applied.is_empty() {
+            Bus::global().publish(BusEvent::FileTouch(FileTouch {
+                session_id: ctx.session_id.clone(),
+                path: path.to_path_buf(),
+                op: FileOp::Edit,
+                intent: None,
+                summary: Some(format!(
+                    "applied {} edit{}",
+                    applied.len(),
+                    if applied.len() == 1 { "" } else { "s" }
+                )),
+                detail: None,
+            }));
+        }
 
         // Format output
         let mut output = format!("Edited {}\n\n", params.file_path);
@@ -1,4 +1,5 @@
 use super::{Tool, ToolContext, ToolOutput};
+use crate::bus::{Bus, BusEvent, FileOp, FileTouch};
 use anyhow::Result;
 use async_trait::async_trait;
 use serde::Deserialize;
@@ -71,9 +72,30 @@ impl Tool for PatchTool {
 
         for patch in patches {
             let resolved_path = ctx.resolve_path(Path::new(&patch.path));
+            let before = tokio::fs::read_to_string(&resolved_path)
+                .await
+                .unwrap_or_default();
             let result = apply_patch_with_diff(&patch, &resolved_path).await;
             match result {
                 Ok((msg, diff)) => {
+                    let after = tokio::fs::read_to_string(&resolved_path)
+                        .await
+                        .unwrap_or_default();
+                    crate::provenance::record_tool_change(
+                        &ctx,
+                        "patch",
+                        &resolved_path,
+                        &before,
+                        &after,
+                    );
+                    Bus::global().publish(BusEvent::FileTouch(FileTouch {
+                        session_id: ctx.session_id.clone(),
+                        path: resolved_path.clone(),
+                        op: FileOp::Edit,
+                        intent: None,
+                        summary: Some(format!("{} via patch", msg)),
+                        detail: None,
+                    }));
                     if diff.is_empty() {
                         results.push(format!("✓ {}: {}", patch.path, msg));
                     } else {
@@ -0,0 +1,80 @@
+use super::{Tool, ToolContext, ToolOutput};
+use crate::plan::proposal::{PLAN_PROPOSE_TOOL, PlanProposal};
+use anyhow::{Result, bail};
+use async_trait::async_trait;
+use serde_json::{Value, json};
+
+pub struct PlanProposeTool;
+
+impl PlanProposeTool {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl Tool for PlanProposeTool {
+    fn name(&self) -> &str {
+        PLAN_PROPOSE_TOOL
+    }
+
+    fn description(&self) -> &str {
+        "Submit a plan for user approval while plan mode is active. Stop after calling it and wait for the user's decision."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "required": ["title", "steps"],
+            "properties": {
+                "intent": super::intent_schema_property(),
+                "title": {
+                    "type": "string",
+                    "description": "Short plan title."
+                },
+                "steps": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Ordered steps."
+                },
+                "files": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Files expected to be created or modified."
+                },
+                "commands": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Commands expected to be run."
+                },
+                "risks": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Risks or open questions."
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, input: Value, _ctx: ToolContext) -> Result<ToolOutput> {
+        let proposal: PlanProposal = serde_json::from_value(input)?;
+        if proposal.steps.iter().all(|step| step.trim().is_empty()) {
+            bail!("plan_propose requires at least one non-empty step");
+        }
+        Ok(ToolOutput::new(proposal.to_markdown())
+            .with_title(format!("plan: {} steps", proposal.steps.len()))
+            .with_metadata(json!({ "plan_proposal": proposal })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schema_requires_title_and_steps() {
+        let schema = PlanProposeTool::new().parameters_schema();
+        assert_eq!(schema["required"], json!(["title", "steps"]));
+        assert!(schema["properties"].get("risks").is_some());
+    }
+}
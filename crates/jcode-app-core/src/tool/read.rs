@@ -31,6 +31,8 @@ struct ReadInput {
     offset: Option<usize>,
     #[serde(default)]
     limit: Option<usize>,
+    #[serde(default)]
+    pages: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -123,7 +125,7 @@ impl Tool for ReadTool {
     }
 
     fn description(&self) -> &str {
-        "Read a file. Supports text files, image files, and PDFs."
+        "Read a file. Supports text files, image files, PDFs, Jupyter notebooks, and Word/Excel documents."
     }
 
     fn parameters_schema(&self) -> Value {
@@ -143,6 +145,10 @@ impl Tool for ReadTool {
                 "limit": {
                     "type": "integer",
                     "description": "Max text lines to read. Default 5000."
+                },
+                "pages": {
+                    "type": "string",
+                    "description": "PDF page or range to read, e.g. \"3\", \"2-5\" or \"4-\". Default all pages."
                 }
             }
         })
@@ -174,9 +180,9 @@ impl Tool for ReadTool {
             return handle_image_file(&path, &params.file_path);
         }
 
-        // Check for PDF files and extract text
-        if is_pdf_file(&path) {
-            return handle_pdf_file(&path, &params.file_path);
+        // Notebooks, PDFs and Office files render as text instead of raw bytes
+        if let Some(kind) = documents::detect(&path) {
+            return documents::read_document(kind, &path, &params);
         }
 
         // Check for binary files
@@ -272,6 +278,8 @@ impl Tool for ReadTool {
     }
 }
 
+mod documents;
+mod office;
 #[cfg(test)]
 mod tests;
 
@@ -463,87 +471,3 @@ fn get_image_dimensions_from_data(data: &[u8]) -> Option<(u32, u32)> {
 
     None
 }
-
-/// Check if a file is a PDF based on extension
-fn is_pdf_file(path: &Path) -> bool {
-    if let Some(ext) = path.extension() {
-        ext.to_string_lossy().to_lowercase() == "pdf"
-    } else {
-        false
-    }
-}
-
-/// Handle reading a PDF file - extract text content
-#[cfg(feature = "pdf")]
-fn handle_pdf_file(path: &Path, file_path: &str) -> Result<ToolOutput> {
-    // Get file metadata
-    let metadata = std::fs::metadata(path)?;
-    let file_size = metadata.len();
-
-    let size_str = if file_size < 1024 {
-        format!("{} bytes", file_size)
-    } else if file_size < 1024 * 1024 {
-        format!("{:.1} KB", file_size as f64 / 1024.0)
-    } else {
-        format!("{:.1} MB", file_size as f64 / 1024.0 / 1024.0)
-    };
-
-    // Extract text from PDF
-    match jcode_pdf::extract_text(path) {
-        Ok(text) => {
-            let mut output = String::new();
-            output.push_str(&format!("PDF: {} ({})\n", file_path, size_str));
-            output.push_str(&format!("{}\n", "=".repeat(60)));
-
-            // Split into pages (pdf_extract uses form feed \x0c as page separator)
-            let pages: Vec<&str> = text.split('\x0c').collect();
-            let page_count = pages.len();
-
-            output.push_str(&format!("Pages: {}\n\n", page_count));
-
-            for (i, page) in pages.iter().enumerate() {
-                let page_text = page.trim();
-                if !page_text.is_empty() {
-                    output.push_str(&format!("--- Page {} ---\n", i + 1));
-                    // Limit each page to reasonable length
-                    if page_text.len() > 10000 {
-                        output.push_str(crate::util::truncate_str(page_text, 10000));
-                        output.push_str("\n... (page truncated)\n");
-                    } else {
-                        output.push_str(page_text);
-                    }
-                    output.push_str("\n\n");
-                }
-            }
-
-            Ok(ToolOutput::new(output))
-        }
-        Err(e) => {
-            // Fall back to metadata only if text extraction fails
-            Ok(ToolOutput::new(format!(
-                "PDF: {} ({})\nCould not extract text: {}\nThis may be a scanned/image-based PDF.",
-                file_path, size_str, e
-            )))
-        }
-    }
-}
-
-/// Handle reading a PDF file when PDF support is not compiled in.
-#[cfg(not(feature = "pdf"))]
-fn handle_pdf_file(path: &Path, file_path: &str) -> Result<ToolOutput> {
-    let metadata = std::fs::metadata(path)?;
-    let file_size = metadata.len();
-
-    let size_str = if file_size < 1024 {
-        format!("{} bytes", file_size)
-    } else if file_size < 1024 * 1024 {
-        format!("{:.1} KB", file_size as f64 / 1024.0)
-    } else {
-        format!("{:.1} MB", file_size as f64 / 1024.0 / 1024.0)
-    };
-
-    Ok(ToolOutput::new(format!(
-        "PDF: {} ({})\nPDF text extraction is not available in this build. Rebuild with the `pdf` feature enabled to extract text.",
-        file_path, size_str
-    )))
-}
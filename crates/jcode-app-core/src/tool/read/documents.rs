@@ -0,0 +1,484 @@
+//! Content-type aware reading for files whose raw bytes are useless to the
+//! model: Jupyter notebooks, PDFs, and Word/Excel documents.
+//!
+//! Formats are detected from the extension together with the leading bytes,
+//! so a renamed PDF or Office file is still recognised and a `.ipynb` that is
+//! not JSON falls back to plain text. Each renderer caps its output and ends
+//! with a note saying what it left out.
+
+use super::office;
+use super::{ReadInput, ToolOutput};
+use anyhow::Result;
+use serde_json::Value;
+use std::io::Read;
+use std::path::Path;
+
+/// Largest rendered document returned in one read.
+pub(super) const MAX_DOCUMENT_CHARS: usize = 100_000;
+/// Largest notebook or Office file that is parsed at all.
+const MAX_DOCUMENT_FILE_BYTES: u64 = 50 * 1024 * 1024;
+/// Per-output caps for notebook cell outputs.
+const MAX_NOTEBOOK_OUTPUT_CHARS: usize = 2_000;
+const MAX_NOTEBOOK_OUTPUT_LINES: usize = 40;
+/// Per-page cap for PDF text.
+const MAX_PDF_PAGE_CHARS: usize = 10_000;
+/// How far into a file a PDF header may start.
+const PDF_HEADER_SEARCH_BYTES: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum DocumentKind {
+    Notebook,
+    Pdf,
+    Docx,
+    Xlsx,
+}
+
+impl DocumentKind {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Notebook => "Notebook",
+            Self::Pdf => "PDF",
+            Self::Docx => "Word document",
+            Self::Xlsx => "Excel workbook",
+        }
+    }
+}
+
+/// The document format of `path`, or `None` for text and other binaries.
+pub(super) fn detect(path: &Path) -> Option<DocumentKind> {
+    let ext = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let head = read_head(path, PDF_HEADER_SEARCH_BYTES)?;
+
+    if head.windows(5).any(|window| window == b"%PDF-") {
+        return Some(DocumentKind::Pdf);
+    }
+    if head.starts_with(b"PK\x03\x04") {
+        return match ext.as_str() {
+            "docx" | "docm" | "dotx" => Some(DocumentKind::Docx),
+            "xlsx" | "xlsm" | "xltx" => Some(DocumentKind::Xlsx),
+            _ => office::detect_zip(path, MAX_DOCUMENT_FILE_BYTES),
+        };
+    }
+    let first_non_space = head.iter().find(|byte| !byte.is_ascii_whitespace());
+    if ext == "ipynb" && first_non_space == Some(&b'{') {
+        return Some(DocumentKind::Notebook);
+    }
+    None
+}
+
+fn read_head(path: &Path, len: usize) -> Option<Vec<u8>> {
+    let mut file = std::fs::File::open(path).ok()?;
+    let mut head = vec![0u8; len];
+    let n = file.read(&mut head).ok()?;
+    head.truncate(n);
+    Some(head)
+}
+
+pub(super) fn read_document(
+    kind: DocumentKind,
+    path: &Path,
+    params: &ReadInput,
+) -> Result<ToolOutput> {
+    let file_path = params.file_path.as_str();
+    let file_size = std::fs::metadata(path)?.len();
+    let header = format!(
+        "{}: {} ({})\n{}\n",
+        kind.label(),
+        file_path,
+        format_size(file_size),
+        "=".repeat(60)
+    );
+
+    if kind == DocumentKind::Pdf {
+        return read_pdf(path, &header, params.pages.as_deref());
+    }
+    if params.pages.is_some() {
+        anyhow::bail!("`pages` only applies to PDFs.");
+    }
+    if file_size > MAX_DOCUMENT_FILE_BYTES {
+        return Ok(ToolOutput::new(format!(
+            "{}Too large to render (limit {}). Use bash to extract the parts you need.",
+            header,
+            format_size(MAX_DOCUMENT_FILE_BYTES)
+        )));
+    }
+
+    let rendered = match kind {
+        DocumentKind::Notebook => match serde_json::from_slice::<Value>(&std::fs::read(path)?) {
+            Ok(notebook) => render_notebook(&notebook),
+            Err(err) => {
+                return Ok(ToolOutput::new(format!(
+                    "{}Not a valid notebook (invalid JSON: {}).",
+                    header, err
+                )));
+            }
+        },
+        DocumentKind::Docx => office::render_docx(&std::fs::read(path)?)?,
+        DocumentKind::Xlsx => office::render_xlsx(&std::fs::read(path)?)?,
+        DocumentKind::Pdf => unreachable!("handled above"),
+    };
+    Ok(ToolOutput::new(format!("{}{}", header, rendered.finish())))
+}
+
+/// Rendered document text plus notes on what was left out.
+#[derive(Debug, Default)]
+pub(super) struct Rendered {
+    text: String,
+    omitted: Vec<String>,
+    /// Set once [`MAX_DOCUMENT_CHARS`] is reached; later pushes are dropped.
+    full: bool,
+}
+
+impl Rendered {
+    /// Append `text` if it fits. Returns `false` once the document cap is hit.
+    pub(super) fn push(&mut self, text: &str) -> bool {
+        if self.full {
+            return false;
+        }
+        let room = MAX_DOCUMENT_CHARS.saturating_sub(self.text.len());
+        if text.len() <= room {
+            self.text.push_str(text);
+            return true;
+        }
+        self.text.push_str(crate::util::truncate_str(text, room));
+        self.full = true;
+        false
+    }
+
+    pub(super) fn is_full(&self) -> bool {
+        self.full
+    }
+
+    pub(super) fn omit(&mut self, note: impl Into<String>) {
+        self.omitted.push(note.into());
+    }
+
+    pub(super) fn finish(mut self) -> String {
+        if self.full {
+            self.omitted.insert(
+                0,
+                format!(
+                    "everything after the first {} characters",
+                    MAX_DOCUMENT_CHARS
+                ),
+            );
+        }
+        let mut out = self.text.trim_end().to_string();
+        if out.is_empty() {
+            out.push_str("(no text content)");
+        }
+        if !self.omitted.is_empty() {
+            out.push_str(&format!("\n\n[Omitted: {}]", self.omitted.join("; ")));
+        }
+        out.push('\n');
+        out
+    }
+}
+
+/// Counted omissions, reported as e.g. "3 image outputs".
+fn count_note(count: usize, what: &str) -> Option<String> {
+    (count > 0).then(|| format!("{} {}", count, what))
+}
+
+fn render_notebook(notebook: &Value) -> Rendered {
+    let mut rendered = Rendered::default();
+    let cells = notebook["cells"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or(&[]);
+    let language = notebook["metadata"]["kernelspec"]["language"]
+        .as_str()
+        .or_else(|| notebook["metadata"]["language_info"]["name"].as_str())
+        .unwrap_or("unknown");
+    rendered.push(&format!(
+        "Cells: {} · language: {}\n\n",
+        cells.len(),
+        language
+    ));
+
+    let mut truncated_outputs = 0;
+    let mut rich_outputs = 0;
+    let mut shown_cells = 0;
+    for (index, cell) in cells.iter().enumerate() {
+        let cell_type = cell["cell_type"].as_str().unwrap_or("unknown");
+        let execution = cell["execution_count"]
+            .as_u64()
+            .map(|count| format!(" [{}]", count))
+            .unwrap_or_default();
+        let mut block = format!("--- Cell {} · {}{} ---\n", index + 1, cell_type, execution);
+        block.push_str(joined_text(&cell["source"]).trim_end());
+        block.push('\n');
+
+        let outputs = cell["outputs"].as_array().map(Vec::as_slice).unwrap_or(&[]);
+        let mut output_text = String::new();
+        for output in outputs {
+            match notebook_output_text(output) {
+                Some(text) => {
+                    let (text, truncated) = cap_output(&text);
+                    truncated_outputs += usize::from(truncated);
+                    output_text.push_str(&text);
+                    if !text.ends_with('\n') {
+                        output_text.push('\n');
+                    }
+                }
+                None => rich_outputs += 1,
+            }
+        }
+        if !output_text.is_empty() {
+            block.push_str("--- Output ---\n");
+            block.push_str(&output_text);
+        }
+        block.push('\n');
+
+        if !rendered.push(&block) {
+            break;
+        }
+        shown_cells += 1;
+    }
+
+    if shown_cells < cells.len() {
+        rendered.omit(format!("cells {}-{}", shown_cells + 1, cells.len()));
+    }
+    if let Some(note) = count_note(
+        truncated_outputs,
+        &format!(
+            "outputs cut to {} lines / {} characters",
+            MAX_NOTEBOOK_OUTPUT_LINES, MAX_NOTEBOOK_OUTPUT_CHARS
+        ),
+    ) {
+        rendered.omit(note);
+    }
+    if let Some(note) = count_note(rich_outputs, "image/HTML outputs without text") {
+        rendered.omit(note);
+    }
+    rendered
+}
+
+/// Text of one cell output, or `None` when it only has rich (image/HTML) data.
+fn notebook_output_text(output: &Value) -> Option<String> {
+    match output["output_type"].as_str() {
+        Some("stream") => Some(joined_text(&output["text"])),
+        Some("error") => {
+            let mut text = format!(
+                "{}: {}\n",
+                output["ename"].as_str().unwrap_or("Error"),
+                output["evalue"].as_str().unwrap_or_default()
+            );
+            for line in output["traceback"].as_array().into_iter().flatten() {
+                text.push_str(&strip_ansi(line.as_str().unwrap_or_default()));
+                text.push('\n');
+            }
+            Some(text)
+        }
+        _ => {
+            let data = &output["data"];
+            ["text/plain", "text/markdown"]
+                .iter()
+                .find_map(|mime| data.get(*mime))
+                .map(joined_text)
+        }
+    }
+}
+
+/// Notebook text fields are either a string or a list of lines.
+fn joined_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Array(lines) => lines.iter().filter_map(Value::as_str).collect(),
+        _ => String::new(),
+    }
+}
+
+fn cap_output(text: &str) -> (String, bool) {
+    let mut truncated = false;
+    let mut capped: String = if text.lines().count() > MAX_NOTEBOOK_OUTPUT_LINES {
+        truncated = true;
+        text.lines()
+            .take(MAX_NOTEBOOK_OUTPUT_LINES)
+            .collect::<Vec<_>>()
+            .join("\n")
+    } else {
+        text.to_string()
+    };
+    if capped.len() > MAX_NOTEBOOK_OUTPUT_CHARS {
+        truncated = true;
+        capped = crate::util::truncate_str(&capped, MAX_NOTEBOOK_OUTPUT_CHARS).to_string();
+    }
+    if truncated {
+        capped.push_str("\n... (output truncated)\n");
+    }
+    (capped, truncated)
+}
+
+/// Drop ANSI escape sequences (notebook tracebacks are colourised).
+fn strip_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            out.push(c);
+            continue;
+        }
+        if chars.next() == Some('[') {
+            for c in chars.by_ref() {
+                if ('@'..='~').contains(&c) {
+                    break;
+                }
+            }
+        }
+    }
+    out
+}
+
+/// A 1-based inclusive page range from `"3"`, `"2-5"` or `"4-"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct PageRange {
+    pub(super) first: usize,
+    pub(super) last: Option<usize>,
+}
+
+impl PageRange {
+    pub(super) fn parse(pages: &str) -> Result<Self> {
+        let invalid = || {
+            anyhow::anyhow!(
+                "Invalid pages '{}': use a 1-based page or range like \"3\", \"2-5\" or \"4-\".",
+                pages
+            )
+        };
+        let parse_page = |page: &str| match page.trim().parse::<usize>() {
+            Ok(page) if page > 0 => Ok(page),
+            _ => Err(invalid()),
+        };
+        let range = match pages.split_once('-') {
+            None => {
+                let page = parse_page(pages)?;
+                Self {
+                    first: page,
+                    last: Some(page),
+                }
+            }
+            Some((first, last)) if last.trim().is_empty() => Self {
+                first: parse_page(first)?,
+                last: None,
+            },
+            Some((first, last)) => Self {
+                first: parse_page(first)?,
+                last: Some(parse_page(last)?),
+            },
+        };
+        if range.last.is_some_and(|last| last < range.first) {
+            return Err(invalid());
+        }
+        Ok(range)
+    }
+
+    fn contains(self, page: usize) -> bool {
+        page >= self.first && self.last.is_none_or(|last| page <= last)
+    }
+}
+
+/// Render the extracted text of each PDF page.
+pub(super) fn render_pdf_text(pages: &[String], range: Option<PageRange>) -> Rendered {
+    let mut rendered = Rendered::default();
+    let page_count = pages.len();
+    let range = range.unwrap_or(PageRange {
+        first: 1,
+        last: None,
+    });
+    let last = range.last.unwrap_or(page_count).min(page_count);
+    if range.first > page_count {
+        rendered.push(&format!(
+            "Pages: {} (none in the requested range {}-{})\n",
+            page_count,
+            range.first,
+            range.last.map(|last| last.to_string()).unwrap_or_default()
+        ));
+        return rendered;
+    }
+    if range.first == 1 && last == page_count {
+        rendered.push(&format!("Pages: {}\n\n", page_count));
+    } else {
+        rendered.push(&format!(
+            "Pages: {} (showing {}-{})\n\n",
+            page_count, range.first, last
+        ));
+    }
+
+    let mut truncated_pages = 0;
+    let mut next_page = range.first;
+    for (index, page) in pages.iter().enumerate() {
+        let number = index + 1;
+        if !range.contains(number) {
+            continue;
+        }
+        let page_text = page.trim();
+        if !page_text.is_empty() {
+            let mut block = format!("--- Page {} ---\n", number);
+            if page_text.len() > MAX_PDF_PAGE_CHARS {
+                truncated_pages += 1;
+                block.push_str(crate::util::truncate_str(page_text, MAX_PDF_PAGE_CHARS));
+                block.push_str("\n... (page truncated)");
+            } else {
+                block.push_str(page_text);
+            }
+            block.push_str("\n\n");
+            if !rendered.push(&block) {
+                break;
+            }
+        }
+        next_page = number + 1;
+    }
+
+    if next_page <= last {
+        rendered.omit(format!(
+            "pages {}-{} (read them with pages=\"{}-\")",
+            next_page, last, next_page
+        ));
+    }
+    if let Some(note) = count_note(
+        truncated_pages,
+        &format!("pages cut to {} characters", MAX_PDF_PAGE_CHARS),
+    ) {
+        rendered.omit(note);
+    }
+    rendered.omit("images and layout");
+    rendered
+}
+
+#[cfg(feature = "pdf")]
+fn read_pdf(path: &Path, header: &str, pages: Option<&str>) -> Result<ToolOutput> {
+    let range = pages.map(PageRange::parse).transpose()?;
+    match jcode_pdf::extract_pages(path) {
+        Ok(pages) => Ok(ToolOutput::new(format!(
+            "{}{}",
+            header,
+            render_pdf_text(&pages, range).finish()
+        ))),
+        Err(e) => Ok(ToolOutput::new(format!(
+            "{}Could not extract text: {}\nThis may be a scanned/image-based PDF.",
+            header, e
+        ))),
+    }
+}
+
+#[cfg(not(feature = "pdf"))]
+fn read_pdf(_path: &Path, header: &str, pages: Option<&str>) -> Result<ToolOutput> {
+    pages.map(PageRange::parse).transpose()?;
+    Ok(ToolOutput::new(format!(
+        "{}PDF text extraction is not available in this build. Rebuild with the `pdf` feature enabled to extract text.",
+        header
+    )))
+}
+
+fn format_size(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{} bytes", bytes)
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MB", bytes as f64 / 1024.0 / 1024.0)
+    }
+}
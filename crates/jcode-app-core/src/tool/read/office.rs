@@ -0,0 +1,564 @@
+//! Basic text extraction from Word (`.docx`) and Excel (`.xlsx`) files.
+//!
+//! Both are zip archives of XML parts. This reads the central directory
+//! directly and inflates only the parts it needs, then walks the XML with a
+//! small tag scanner: paragraphs and table cells for Word, shared strings and
+//! cell values for Excel. Formatting, formulas, charts and images are
+//! dropped and reported as omitted.
+
+use super::documents::{DocumentKind, Rendered};
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+
+/// Largest inflated XML part that is read.
+const MAX_PART_BYTES: u64 = 64 * 1024 * 1024;
+/// Rows shown per worksheet.
+const MAX_SHEET_ROWS: usize = 500;
+/// Characters shown per worksheet cell.
+const MAX_CELL_CHARS: usize = 200;
+
+/// Tell a Word or Excel file from its archive contents, for zips whose
+/// extension does not say.
+pub(super) fn detect_zip(path: &Path, max_file_bytes: u64) -> Option<DocumentKind> {
+    if std::fs::metadata(path).ok()?.len() > max_file_bytes {
+        return None;
+    }
+    let data = std::fs::read(path).ok()?;
+    let archive = ZipArchive::parse(&data).ok()?;
+    if archive.contains("word/document.xml") {
+        Some(DocumentKind::Docx)
+    } else if archive.contains("xl/workbook.xml") {
+        Some(DocumentKind::Xlsx)
+    } else {
+        None
+    }
+}
+
+pub(super) fn render_docx(data: &[u8]) -> Result<Rendered> {
+    let archive = ZipArchive::parse(data)?;
+    let xml = archive
+        .read_string("word/document.xml")?
+        .context("not a Word document (no word/document.xml)")?;
+
+    let mut rendered = Rendered::default();
+    let mut text = String::new();
+    let mut in_text = false;
+    let mut cell_depth = 0usize;
+    let mut images = 0usize;
+    for event in XmlEvents::new(&xml) {
+        match event {
+            XmlEvent::Start { name, self_closing } => match name {
+                "w:t" => in_text = !self_closing,
+                "w:tab" => text.push('\t'),
+                "w:br" | "w:cr" => text.push('\n'),
+                "w:tc" => cell_depth += 1,
+                "w:drawing" | "w:pict" => images += 1,
+                _ => {}
+            },
+            XmlEvent::End(name) => match name {
+                "w:t" => in_text = false,
+                "w:p" if cell_depth > 0 => text.push(' '),
+                "w:p" => {
+                    text.push('\n');
+                    if !rendered.push(&text) {
+                        break;
+                    }
+                    text.clear();
+                }
+                "w:tc" => {
+                    cell_depth = cell_depth.saturating_sub(1);
+                    let trimmed = text.trim_end_matches(' ').len();
+                    text.truncate(trimmed);
+                    text.push_str(" | ");
+                }
+                "w:tr" => {
+                    let trimmed = text.trim_end_matches(" | ").len();
+                    text.truncate(trimmed);
+                    text.push('\n');
+                    if !rendered.push(&text) {
+                        break;
+                    }
+                    text.clear();
+                }
+                _ => {}
+            },
+            XmlEvent::Text(raw) if in_text => text.push_str(&unescape(raw)),
+            XmlEvent::Text(_) => {}
+        }
+    }
+    rendered.push(&text);
+
+    if images > 0 {
+        rendered.omit(format!("{} embedded images", images));
+    }
+    rendered.omit("formatting, headers/footers, comments and footnotes");
+    Ok(rendered)
+}
+
+pub(super) fn render_xlsx(data: &[u8]) -> Result<Rendered> {
+    let archive = ZipArchive::parse(data)?;
+    let workbook = archive
+        .read_string("xl/workbook.xml")?
+        .context("not an Excel workbook (no xl/workbook.xml)")?;
+    let shared_strings = match archive.read_string("xl/sharedStrings.xml")? {
+        Some(xml) => parse_shared_strings(&xml),
+        None => Vec::new(),
+    };
+    let targets = match archive.read_string("xl/_rels/workbook.xml.rels")? {
+        Some(xml) => parse_relationships(&xml),
+        None => HashMap::new(),
+    };
+
+    let sheets = parse_sheets(&workbook);
+    let mut rendered = Rendered::default();
+    rendered.push(&format!("Sheets: {}\n\n", sheets.len()));
+    let mut omitted_rows = Vec::new();
+    for (index, (name, rel_id)) in sheets.iter().enumerate() {
+        let part = targets
+            .get(rel_id)
+            .map(|target| resolve_part(target))
+            .unwrap_or_else(|| format!("xl/worksheets/sheet{}.xml", index + 1));
+        let Some(xml) = archive.read_string(&part)? else {
+            rendered.omit(format!("sheet '{}' (missing {})", name, part));
+            continue;
+        };
+        let sheet = render_sheet(&xml, &shared_strings);
+        let mut block = format!(
+            "--- Sheet {}: {} ({} rows) ---\n",
+            index + 1,
+            name,
+            sheet.rows
+        );
+        block.push_str(&sheet.text);
+        block.push('\n');
+        if sheet.rows > MAX_SHEET_ROWS {
+            omitted_rows.push(format!("'{}' rows after {}", name, MAX_SHEET_ROWS));
+        }
+        if !rendered.push(&block) {
+            break;
+        }
+    }
+
+    if !omitted_rows.is_empty() {
+        rendered.omit(omitted_rows.join(", "));
+    }
+    rendered.omit(format!(
+        "formulas, formatting and charts; cells cut to {} characters",
+        MAX_CELL_CHARS
+    ));
+    Ok(rendered)
+}
+
+struct RenderedSheet {
+    text: String,
+    rows: usize,
+}
+
+fn render_sheet(xml: &str, shared_strings: &[String]) -> RenderedSheet {
+    let mut text = String::new();
+    let mut rows = 0usize;
+    let mut row_number = String::new();
+    let mut cells: Vec<String> = Vec::new();
+    let mut cell_column = 0usize;
+    let mut cell_type = String::new();
+    let mut value = String::new();
+    let mut in_value = false;
+
+    let mut events = XmlEvents::new(xml);
+    while let Some(event) = events.next() {
+        match event {
+            XmlEvent::Start { name: "row", .. } => {
+                rows += 1;
+                row_number = events.attr("r").unwrap_or_else(|| rows.to_string());
+                cells.clear();
+            }
+            XmlEvent::Start { name: "c", .. } => {
+                cell_column = events
+                    .attr("r")
+                    .and_then(|reference| column_index(&reference))
+                    .unwrap_or(cells.len());
+                cell_type = events.attr("t").unwrap_or_default();
+                value.clear();
+            }
+            XmlEvent::Start {
+                name: "v" | "t",
+                self_closing: false,
+            } => in_value = true,
+            XmlEvent::End("v" | "t") => in_value = false,
+            XmlEvent::Text(raw) if in_value => value.push_str(&unescape(raw)),
+            XmlEvent::End("c") => {
+                let shown = match cell_type.as_str() {
+                    "s" => value
+                        .trim()
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|index| shared_strings.get(index))
+                        .cloned()
+                        .unwrap_or_default(),
+                    "b" if value.trim() == "1" => "TRUE".to_string(),
+                    "b" => "FALSE".to_string(),
+                    _ => value.clone(),
+                };
+                if !shown.is_empty() {
+                    if cells.len() <= cell_column {
+                        cells.resize(cell_column + 1, String::new());
+                    }
+                    cells[cell_column] = cap_cell(&shown);
+                }
+            }
+            XmlEvent::End("row") if rows <= MAX_SHEET_ROWS && !cells.is_empty() => {
+                text.push_str(&row_number);
+                for cell in &cells {
+                    text.push('\t');
+                    text.push_str(cell);
+                }
+                text.push('\n');
+            }
+            _ => {}
+        }
+    }
+    RenderedSheet { text, rows }
+}
+
+fn cap_cell(value: &str) -> String {
+    let single_line = value.replace(['\n', '\t'], " ");
+    if single_line.len() > MAX_CELL_CHARS {
+        format!(
+            "{}…",
+            crate::util::truncate_str(&single_line, MAX_CELL_CHARS)
+        )
+    } else {
+        single_line
+    }
+}
+
+/// 0-based column of a cell reference like `"AB12"`.
+fn column_index(reference: &str) -> Option<usize> {
+    let letters: String = reference
+        .chars()
+        .take_while(|c| c.is_ascii_alphabetic())
+        .collect();
+    if letters.is_empty() {
+        return None;
+    }
+    let column = letters.chars().fold(0usize, |acc, c| {
+        acc * 26 + (c.to_ascii_uppercase() as usize - 'A' as usize + 1)
+    });
+    Some(column - 1)
+}
+
+fn parse_shared_strings(xml: &str) -> Vec<String> {
+    let mut strings = Vec::new();
+    let mut current = String::new();
+    let mut in_text = false;
+    // Phonetic runs (`rPh`) repeat the text as a reading guide.
+    let mut in_phonetic = false;
+    for event in XmlEvents::new(xml) {
+        match event {
+            XmlEvent::Start { name: "si", .. } => current.clear(),
+            XmlEvent::End("si") => strings.push(std::mem::take(&mut current)),
+            XmlEvent::Start {
+                name: "rPh",
+                self_closing: false,
+            } => in_phonetic = true,
+            XmlEvent::End("rPh") => in_phonetic = false,
+            XmlEvent::Start {
+                name: "t",
+                self_closing: false,
+            } => in_text = !in_phonetic,
+            XmlEvent::End("t") => in_text = false,
+            XmlEvent::Text(raw) if in_text => current.push_str(&unescape(raw)),
+            _ => {}
+        }
+    }
+    strings
+}
+
+/// `(name, relationship id)` of each sheet, in workbook order.
+fn parse_sheets(xml: &str) -> Vec<(String, String)> {
+    let mut sheets = Vec::new();
+    let mut events = XmlEvents::new(xml);
+    while let Some(event) = events.next() {
+        if let XmlEvent::Start { name: "sheet", .. } = event {
+            let name = events.attr("name").unwrap_or_default();
+            let rel_id = events.attr("r:id").unwrap_or_default();
+            sheets.push((name, rel_id));
+        }
+    }
+    sheets
+}
+
+fn parse_relationships(xml: &str) -> HashMap<String, String> {
+    let mut targets = HashMap::new();
+    let mut events = XmlEvents::new(xml);
+    while let Some(event) = events.next() {
+        if let XmlEvent::Start {
+            name: "Relationship",
+            ..
+        } = event
+            && let (Some(id), Some(target)) = (events.attr("Id"), events.attr("Target"))
+        {
+            targets.insert(id, target);
+        }
+    }
+    targets
+}
+
+/// Archive path of a workbook relationship target.
+fn resolve_part(target: &str) -> String {
+    match target.strip_prefix('/') {
+        Some(absolute) => absolute.to_string(),
+        None => format!("xl/{}", target),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum XmlEvent<'a> {
+    Start { name: &'a str, self_closing: bool },
+    End(&'a str),
+    Text(&'a str),
+}
+
+/// Minimal XML tokenizer: tags and raw text, with comments, processing
+/// instructions and doctypes skipped. CDATA is returned as text. Attributes
+/// of the last start tag are available through [`XmlEvents::attr`].
+struct XmlEvents<'a> {
+    rest: &'a str,
+    attrs: &'a str,
+}
+
+impl<'a> XmlEvents<'a> {
+    fn new(xml: &'a str) -> Self {
+        Self {
+            rest: xml,
+            attrs: "",
+        }
+    }
+
+    /// Unescaped value of `key` on the most recent start tag.
+    fn attr(&self, key: &str) -> Option<String> {
+        let mut rest = self.attrs;
+        while let Some(eq) = rest.find('=') {
+            let name = rest[..eq].trim();
+            let after = rest[eq + 1..].trim_start();
+            let quote = after.chars().next()?;
+            if quote != '"' && quote != '\'' {
+                return None;
+            }
+            let value_end = after[1..].find(quote)?;
+            if name == key {
+                return Some(unescape(&after[1..1 + value_end]));
+            }
+            rest = &after[value_end + 2..];
+        }
+        None
+    }
+}
+
+impl<'a> Iterator for XmlEvents<'a> {
+    type Item = XmlEvent<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.rest.is_empty() {
+                return None;
+            }
+            let Some(open) = self.rest.find('<') else {
+                let text = self.rest;
+                self.rest = "";
+                return Some(XmlEvent::Text(text));
+            };
+            if open > 0 {
+                let text = &self.rest[..open];
+                self.rest = &self.rest[open..];
+                return Some(XmlEvent::Text(text));
+            }
+
+            if let Some(cdata) = self.rest.strip_prefix("<![CDATA[") {
+                let end = cdata.find("]]>").unwrap_or(cdata.len());
+                self.rest = cdata.get(end + 3..).unwrap_or("");
+                return Some(XmlEvent::Text(&cdata[..end]));
+            }
+            let skip_to = if self.rest.starts_with("<!--") {
+                Some("-->")
+            } else if self.rest.starts_with("<?") {
+                Some("?>")
+            } else if self.rest.starts_with("<!") {
+                Some(">")
+            } else {
+                None
+            };
+            if let Some(terminator) = skip_to {
+                self.rest = match self.rest.find(terminator) {
+                    Some(end) => &self.rest[end + terminator.len()..],
+                    None => "",
+                };
+                continue;
+            }
+
+            let close = self.rest.find('>')?;
+            let tag = &self.rest[1..close];
+            self.rest = &self.rest[close + 1..];
+            if let Some(name) = tag.strip_prefix('/') {
+                return Some(XmlEvent::End(name.trim()));
+            }
+            let (tag, self_closing) = match tag.strip_suffix('/') {
+                Some(tag) => (tag, true),
+                None => (tag, false),
+            };
+            let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+            self.attrs = &tag[name_end..];
+            return Some(XmlEvent::Start {
+                name: &tag[..name_end],
+                self_closing,
+            });
+        }
+    }
+}
+
+fn unescape(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let Some(semi) = rest.find(';').filter(|&semi| semi <= 10) else {
+            out.push('&');
+            rest = &rest[1..];
+            continue;
+        };
+        let entity = &rest[1..semi];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| entity.strip_prefix('#').map(str::parse::<u32>))
+                .and_then(Result::ok)
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[semi + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+struct ZipEntry {
+    name: String,
+    method: u16,
+    compressed_size: usize,
+    local_header_offset: usize,
+}
+
+/// Read-only view of a zip archive held in memory. Zip64 archives are not
+/// supported; Office files rarely need them.
+struct ZipArchive<'a> {
+    data: &'a [u8],
+    entries: Vec<ZipEntry>,
+}
+
+impl<'a> ZipArchive<'a> {
+    fn parse(data: &'a [u8]) -> Result<Self> {
+        const END_OF_CENTRAL_DIRECTORY: u32 = 0x0605_4b50;
+        const CENTRAL_DIRECTORY_ENTRY: u32 = 0x0201_4b50;
+
+        let search_start = data.len().saturating_sub(22 + u16::MAX as usize);
+        let eocd = (search_start..data.len().saturating_sub(21))
+            .rev()
+            .find(|&offset| read_u32(data, offset) == Some(END_OF_CENTRAL_DIRECTORY))
+            .context("not a zip archive (no end of central directory)")?;
+        let entry_count = read_u16(data, eocd + 10).context("truncated zip")? as usize;
+        let mut offset = read_u32(data, eocd + 16).context("truncated zip")? as usize;
+
+        let mut entries = Vec::with_capacity(entry_count);
+        for _ in 0..entry_count {
+            if read_u32(data, offset) != Some(CENTRAL_DIRECTORY_ENTRY) {
+                anyhow::bail!("corrupt zip central directory");
+            }
+            let field = |at: usize| read_u16(data, offset + at).context("truncated zip");
+            let method = field(10)?;
+            let name_len = field(28)? as usize;
+            let extra_len = field(30)? as usize;
+            let comment_len = field(32)? as usize;
+            let compressed_size = read_u32(data, offset + 20).context("truncated zip")? as usize;
+            let local_header_offset =
+                read_u32(data, offset + 42).context("truncated zip")? as usize;
+            let name = data
+                .get(offset + 46..offset + 46 + name_len)
+                .context("truncated zip")?;
+            entries.push(ZipEntry {
+                name: String::from_utf8_lossy(name).into_owned(),
+                method,
+                compressed_size,
+                local_header_offset,
+            });
+            offset += 46 + name_len + extra_len + comment_len;
+        }
+        Ok(Self { data, entries })
+    }
+
+    fn contains(&self, name: &str) -> bool {
+        self.entries.iter().any(|entry| entry.name == name)
+    }
+
+    /// The inflated part `name` as UTF-8, or `None` if the archive lacks it.
+    fn read_string(&self, name: &str) -> Result<Option<String>> {
+        const LOCAL_FILE_HEADER: u32 = 0x0403_4b50;
+
+        let Some(entry) = self.entries.iter().find(|entry| entry.name == name) else {
+            return Ok(None);
+        };
+        let header = entry.local_header_offset;
+        if read_u32(self.data, header) != Some(LOCAL_FILE_HEADER) {
+            anyhow::bail!("corrupt zip entry {}", name);
+        }
+        let name_len = read_u16(self.data, header + 26).context("truncated zip")? as usize;
+        let extra_len = read_u16(self.data, header + 28).context("truncated zip")? as usize;
+        let start = header + 30 + name_len + extra_len;
+        let compressed = self
+            .data
+            .get(start..start + entry.compressed_size)
+            .context("truncated zip")?;
+
+        let mut bytes = Vec::new();
+        match entry.method {
+            0 => bytes.extend_from_slice(compressed),
+            8 => {
+                flate2::read::DeflateDecoder::new(compressed)
+                    .take(MAX_PART_BYTES)
+                    .read_to_end(&mut bytes)
+                    .with_context(|| format!("failed to inflate {}", name))?;
+            }
+            method => anyhow::bail!("unsupported zip compression method {} in {}", method, name),
+        }
+        Ok(Some(String::from_utf8_lossy(&bytes).into_owned()))
+    }
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset + 2)?;
+    Some(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
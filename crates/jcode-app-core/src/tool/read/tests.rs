@@ -8,6 +8,7 @@ fn make_ctx(working_dir: std::path::PathBuf) -> ToolContext {
         message_id: "test-message".to_string(),
         tool_call_id: "test-call".to_string(),
         working_dir: Some(working_dir),
+        workspace_roots: Vec::new(),
         stdin_request_tx: None,
         graceful_shutdown_signal: None,
         execution_mode: ToolExecutionMode::Direct,
@@ -342,3 +343,191 @@ async fn read_tool_prefers_end_line_over_limit() {
         output.output
     );
 }
+
+/// A stored (uncompressed) zip holding `files`, enough for the Office reader.
+fn stored_zip(files: &[(&str, &str)]) -> Vec<u8> {
+    let mut data = Vec::new();
+    let mut central = Vec::new();
+    for (name, contents) in files {
+        let offset = data.len() as u32;
+        let size = contents.len() as u32;
+        let mut header = Vec::new();
+        header.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        header.extend_from_slice(&[20, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        header.extend_from_slice(&0u32.to_le_bytes());
+        header.extend_from_slice(&size.to_le_bytes());
+        header.extend_from_slice(&size.to_le_bytes());
+        header.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        header.extend_from_slice(&0u16.to_le_bytes());
+        data.extend_from_slice(&header);
+        data.extend_from_slice(name.as_bytes());
+        data.extend_from_slice(contents.as_bytes());
+
+        central.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+        central.extend_from_slice(&[20, 0]);
+        central.extend_from_slice(&header[4..]);
+        central.extend_from_slice(&[0; 12]);
+        central.extend_from_slice(&offset.to_le_bytes());
+        central.extend_from_slice(name.as_bytes());
+    }
+    let central_offset = data.len() as u32;
+    data.extend_from_slice(&central);
+    data.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+    data.extend_from_slice(&[0, 0, 0, 0]);
+    data.extend_from_slice(&(files.len() as u16).to_le_bytes());
+    data.extend_from_slice(&(files.len() as u16).to_le_bytes());
+    data.extend_from_slice(&(central.len() as u32).to_le_bytes());
+    data.extend_from_slice(&central_offset.to_le_bytes());
+    data.extend_from_slice(&0u16.to_le_bytes());
+    data
+}
+
+async fn read_file(dir: &std::path::Path, params: Value) -> Result<ToolOutput> {
+    ReadTool::new()
+        .execute(params, make_ctx(dir.to_path_buf()))
+        .await
+}
+
+#[tokio::test]
+async fn read_tool_renders_notebook_cells_with_truncated_outputs() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let long_output: String = (1..=100).map(|n| format!("row {}\n", n)).collect();
+    let notebook = json!({
+        "metadata": {"kernelspec": {"language": "python"}},
+        "cells": [
+            {"cell_type": "markdown", "source": ["# Title\n", "Intro"]},
+            {
+                "cell_type": "code",
+                "execution_count": 1,
+                "source": "print(rows)",
+                "outputs": [
+                    {"output_type": "stream", "name": "stdout", "text": long_output},
+                    {"output_type": "display_data", "data": {"image/png": "iVBORw0KGgo="}}
+                ]
+            }
+        ]
+    });
+    std::fs::write(temp.path().join("analysis.ipynb"), notebook.to_string()).expect("write");
+
+    let output = read_file(temp.path(), json!({"file_path": "analysis.ipynb"}))
+        .await
+        .expect("read notebook")
+        .output;
+
+    assert!(output.contains("Cells: 2 · language: python"), "{output}");
+    assert!(
+        output.contains("--- Cell 1 · markdown ---\n# Title\nIntro"),
+        "{output}"
+    );
+    assert!(
+        output.contains("--- Cell 2 · code [1] ---\nprint(rows)"),
+        "{output}"
+    );
+    assert!(output.contains("row 1\n"), "{output}");
+    assert!(!output.contains("row 100"), "{output}");
+    assert!(output.contains("[Omitted:"), "{output}");
+}
+
+#[tokio::test]
+async fn read_tool_extracts_docx_and_xlsx_text() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let docx = stored_zip(&[(
+        "word/document.xml",
+        r#"<w:document><w:body><w:p><w:r><w:t>Quarterly &amp; annual</w:t></w:r></w:p><w:tbl><w:tr><w:tc><w:p><w:r><w:t>Q1</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>42</w:t></w:r></w:p></w:tc></w:tr></w:tbl></w:body></w:document>"#,
+    )]);
+    // No extension: detected from the archive contents.
+    std::fs::write(temp.path().join("report"), docx).expect("write docx");
+    let xlsx = stored_zip(&[
+        (
+            "xl/workbook.xml",
+            r#"<workbook><sheets><sheet name="Sales" sheetId="1" r:id="rId1"/></sheets></workbook>"#,
+        ),
+        (
+            "xl/_rels/workbook.xml.rels",
+            r#"<Relationships><Relationship Id="rId1" Target="worksheets/sheet1.xml"/></Relationships>"#,
+        ),
+        (
+            "xl/sharedStrings.xml",
+            r#"<sst><si><t>Region</t></si><si><t>North</t></si></sst>"#,
+        ),
+        (
+            "xl/worksheets/sheet1.xml",
+            r#"<worksheet><sheetData><row r="1"><c r="A1" t="s"><v>0</v></c><c r="B1"><v>Total</v></c></row><row r="2"><c r="A2" t="s"><v>1</v></c><c r="C2"><v>12.5</v></c></row></sheetData></worksheet>"#,
+        ),
+    ]);
+    std::fs::write(temp.path().join("sales.xlsx"), xlsx).expect("write xlsx");
+
+    let output = read_file(temp.path(), json!({"file_path": "report"}))
+        .await
+        .expect("read docx")
+        .output;
+    assert!(output.starts_with("Word document: report"), "{output}");
+    assert!(output.contains("Quarterly & annual"), "{output}");
+    assert!(output.contains("Q1 | 42"), "{output}");
+
+    let output = read_file(temp.path(), json!({"file_path": "sales.xlsx"}))
+        .await
+        .expect("read xlsx")
+        .output;
+    assert!(output.contains("Sales"), "{output}");
+    assert!(output.contains("Region\tTotal"), "{output}");
+    assert!(output.contains("North\t\t12.5"), "{output}");
+}
+
+#[tokio::test]
+async fn read_tool_keeps_binary_error_for_unknown_archives() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let zip = stored_zip(&[("notes.txt", "hello")]);
+    std::fs::write(temp.path().join("bundle.zip"), zip).expect("write zip");
+
+    let output = read_file(temp.path(), json!({"file_path": "bundle.zip"}))
+        .await
+        .expect("read zip")
+        .output;
+    assert!(output.contains("Binary file detected"), "{output}");
+}
+
+#[test]
+fn page_range_parses_single_pages_and_ranges() {
+    use documents::PageRange;
+    assert_eq!(
+        PageRange::parse("3").unwrap(),
+        PageRange {
+            first: 3,
+            last: Some(3)
+        }
+    );
+    assert_eq!(
+        PageRange::parse("2-5").unwrap(),
+        PageRange {
+            first: 2,
+            last: Some(5)
+        }
+    );
+    assert_eq!(
+        PageRange::parse("4-").unwrap(),
+        PageRange {
+            first: 4,
+            last: None
+        }
+    );
+    assert!(PageRange::parse("0").is_err());
+    assert!(PageRange::parse("5-2").is_err());
+    assert!(PageRange::parse("abc").is_err());
+}
+
+#[test]
+fn pdf_text_renders_requested_pages_only() {
+    use documents::{PageRange, render_pdf_text};
+    let pages = ["first page", "second page", "third page"].map(String::from);
+
+    let all = render_pdf_text(&pages, None).finish();
+    assert!(all.starts_with("Pages: 3\n"), "{all}");
+    assert!(all.contains("--- Page 3 ---\nthird page"), "{all}");
+
+    let middle = render_pdf_text(&pages, Some(PageRange::parse("2").unwrap())).finish();
+    assert!(middle.contains("Pages: 3 (showing 2-2)"), "{middle}");
+    assert!(middle.contains("second page"), "{middle}");
+    assert!(!middle.contains("first page"), "{middle}");
+    assert!(!middle.contains("third page"), "{middle}");
+}
@@ -0,0 +1,951 @@
+//! `refactor_rename` tool: rename an identifier across the workspace in one
+//! reviewed step.
+//!
+//! The first call only plans: occurrences are found on word boundaries, and
+//! those inside strings and comments are left alone unless asked for, using a
+//! per-language lexing heuristic (jcode has no language server client to ask
+//! for a real rename). The plan is returned as the tool result and kept for the
+//! session for [`PLAN_TTL`]. A second call with `confirm: true` applies it,
+//! provided no planned file changed in between. Every planned file is
+//! snapshotted with [`session_changes::capture`] before the first write, and
+//! each is replaced through a temp file and a rename; if any write fails, all
+//! of them are restored from the snapshot. The verification command from the
+//! call or `[tools.refactor] verify_command` runs last; when it fails the
+//! rename is rolled back the same way unless the call asked to keep it.
+
+use super::{Tool, ToolContext, ToolOutput};
+use crate::bus::{Bus, BusEvent, FileOp, FileTouch};
+use crate::session_changes::{self, FileSnapshot};
+use anyhow::{Context, Result, bail};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Files larger than this are not scanned.
+const MAX_FILE_BYTES: u64 = 1024 * 1024;
+/// Changed lines listed in a plan.
+const MAX_PREVIEW_LINES: usize = 200;
+/// Verification output kept for the model, from the end.
+const MAX_VERIFY_OUTPUT_BYTES: usize = 4000;
+
+/// How long a plan waits for `confirm: true` before it is dropped.
+const PLAN_TTL: Duration = Duration::from_secs(30 * 60);
+
+/// The last plan of each session, waiting for `confirm: true`.
+static PENDING: LazyLock<Mutex<HashMap<String, PendingPlan>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+struct PendingPlan {
+    plan: RenamePlan,
+    planned_at: Instant,
+}
+
+/// The pending plans, with expired ones already dropped.
+fn pending() -> MutexGuard<'static, HashMap<String, PendingPlan>> {
+    let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+    pending.retain(|_, entry| entry.planned_at.elapsed() < PLAN_TTL);
+    pending
+}
+
+fn store_pending(session_id: &str, plan: RenamePlan) {
+    pending().insert(
+        session_id.to_string(),
+        PendingPlan {
+            plan,
+            planned_at: Instant::now(),
+        },
+    );
+}
+
+fn take_pending(session_id: &str) -> Option<RenamePlan> {
+    pending().remove(session_id).map(|entry| entry.plan)
+}
+
+/// Drop a session's unconfirmed plan, e.g. when its client goes away.
+pub(crate) fn discard_pending_rename(session_id: &str) {
+    take_pending(session_id);
+}
+
+pub struct RefactorRenameTool;
+
+impl RefactorRenameTool {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[derive(Deserialize)]
+struct RefactorRenameInput {
+    old_name: String,
+    new_name: String,
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "super::serde_coerce::bool_from_string_or_bool"
+    )]
+    include_strings_and_comments: bool,
+    #[serde(
+        default,
+        deserialize_with = "super::serde_coerce::bool_from_string_or_bool"
+    )]
+    confirm: bool,
+    #[serde(default)]
+    verify_command: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "super::serde_coerce::bool_from_string_or_bool"
+    )]
+    keep_on_verify_failure: bool,
+}
+
+/// Comment and string syntax assumed for a file, from its extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Syntax {
+    /// `//` and `/* */` comments, `"` strings
+    CLike,
+    /// `#` comments, `"` and `'` strings
+    Hash,
+    /// No comments or strings are recognised
+    Plain,
+}
+
+fn syntax_for(path: &Path) -> Syntax {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    match extension.as_str() {
+        "rs" | "c" | "h" | "cc" | "cpp" | "hpp" | "cs" | "go" | "java" | "kt" | "kts" | "swift"
+        | "scala" | "dart" | "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "zig" | "proto" => {
+            Syntax::CLike
+        }
+        "py" | "rb" | "sh" | "bash" | "zsh" | "pl" | "toml" | "yaml" | "yml" | "r" | "nix" => {
+            Syntax::Hash
+        }
+        _ => Syntax::Plain,
+    }
+}
+
+/// Word-boundary occurrences of a name in one file.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Occurrences {
+    /// Byte offsets to rename
+    renamed: Vec<usize>,
+    /// Occurrences in strings or comments that were left alone
+    skipped: usize,
+}
+
+fn is_ident_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte >= 0x80
+}
+
+/// Find `name` in `text` where it is not part of a longer identifier. With
+/// `include_all` off, occurrences inside strings and comments are counted
+/// but not returned for renaming.
+fn find_occurrences(text: &str, name: &str, syntax: Syntax, include_all: bool) -> Occurrences {
+    #[derive(Clone, Copy, PartialEq)]
+    enum State {
+        Code,
+        LineComment,
+        BlockComment,
+        Str(u8),
+    }
+
+    let bytes = text.as_bytes();
+    let needle = name.as_bytes();
+    let mut found = Occurrences::default();
+    let mut state = State::Code;
+    let mut i = 0;
+    while i < bytes.len() {
+        let rest = &bytes[i..];
+        if rest.starts_with(needle)
+            && (i == 0 || !is_ident_byte(bytes[i - 1]))
+            && bytes
+                .get(i + needle.len())
+                .is_none_or(|&b| !is_ident_byte(b))
+        {
+            if state == State::Code || include_all {
+                found.renamed.push(i);
+            } else {
+                found.skipped += 1;
+            }
+            i += needle.len();
+            continue;
+        }
+        let byte = bytes[i];
+        match state {
+            State::Code => match (syntax, byte) {
+                (Syntax::CLike, b'/') if rest.starts_with(b"//") => state = State::LineComment,
+                (Syntax::CLike, b'/') if rest.starts_with(b"/*") => {
+                    state = State::BlockComment;
+                    i += 1;
+                }
+                // Step over char literals so `'"'` does not open a string.
+                (Syntax::CLike, b'\'')
+                    if rest.get(1) == Some(&b'\\') && rest.get(3) == Some(&b'\'') =>
+                {
+                    i += 3;
+                }
+                (Syntax::CLike, b'\'') if rest.get(2) == Some(&b'\'') => i += 2,
+                (Syntax::Hash, b'#') => state = State::LineComment,
+                (Syntax::CLike | Syntax::Hash, b'"') => state = State::Str(b'"'),
+                (Syntax::Hash, b'\'') => state = State::Str(b'\''),
+                _ => {}
+            },
+            State::LineComment if byte == b'\n' => state = State::Code,
+            State::BlockComment if rest.starts_with(b"*/") => {
+                state = State::Code;
+                i += 1;
+            }
+            State::Str(_) if byte == b'\\' => i += 1,
+            // Apostrophes in prose (`it's`) would otherwise swallow the file.
+            State::Str(b'\'') if byte == b'\n' => state = State::Code,
+            State::Str(quote) if byte == quote => state = State::Code,
+            _ => {}
+        }
+        i += 1;
+    }
+    found
+}
+
+/// `text` with the occurrences at `offsets` replaced.
+fn replace_at(text: &str, offsets: &[usize], old_len: usize, new_name: &str) -> String {
+    let mut updated = String::with_capacity(text.len());
+    let mut last = 0;
+    for &offset in offsets {
+        updated.push_str(&text[last..offset]);
+        updated.push_str(new_name);
+        last = offset + old_len;
+    }
+    updated.push_str(&text[last..]);
+    updated
+}
+
+#[derive(Debug, Clone)]
+struct PlannedFile {
+    path: PathBuf,
+    original: String,
+    updated: String,
+    occurrences: usize,
+}
+
+#[derive(Debug, Clone)]
+struct RenamePlan {
+    old_name: String,
+    new_name: String,
+    scope: PathBuf,
+    include_all: bool,
+    files: Vec<PlannedFile>,
+    skipped: usize,
+    /// Occurrences of the new name already in scope
+    collisions: usize,
+}
+
+impl RenamePlan {
+    fn occurrences(&self) -> usize {
+        self.files.iter().map(|file| file.occurrences).sum()
+    }
+
+    fn same_request(&self, other: &Self) -> bool {
+        self.old_name == other.old_name
+            && self.new_name == other.new_name
+            && self.scope == other.scope
+            && self.include_all == other.include_all
+    }
+}
+
+/// Text files under `scope`, honouring ignore files, or `scope` itself.
+fn candidate_files(scope: &Path) -> Vec<PathBuf> {
+    if scope.is_file() {
+        return vec![scope.to_path_buf()];
+    }
+    crate::file_index::get(scope, true)
+        .entries
+        .iter()
+        .filter(|entry| !entry.is_dir && entry.size <= MAX_FILE_BYTES)
+        .map(|entry| scope.join(&entry.path))
+        .collect()
+}
+
+fn build_plan(
+    scope: &Path,
+    old_name: &str,
+    new_name: &str,
+    include_all: bool,
+) -> Result<RenamePlan> {
+    if !scope.exists() {
+        bail!("Path not found: {}", scope.display());
+    }
+    let mut plan = RenamePlan {
+        old_name: old_name.to_string(),
+        new_name: new_name.to_string(),
+        scope: scope.to_path_buf(),
+        include_all,
+        files: Vec::new(),
+        skipped: 0,
+        collisions: 0,
+    };
+    for path in candidate_files(scope) {
+        let Ok(bytes) = std::fs::read(&path) else {
+            continue;
+        };
+        if bytes.contains(&0) {
+            continue;
+        }
+        let Ok(text) = String::from_utf8(bytes) else {
+            continue;
+        };
+        let syntax = syntax_for(&path);
+        plan.collisions += find_occurrences(&text, new_name, syntax, false)
+            .renamed
+            .len();
+        let found = find_occurrences(&text, old_name, syntax, include_all);
+        plan.skipped += found.skipped;
+        if found.renamed.is_empty() {
+            continue;
+        }
+        let updated = replace_at(&text, &found.renamed, old_name.len(), new_name);
+        plan.files.push(PlannedFile {
+            path,
+            occurrences: found.renamed.len(),
+            original: text,
+            updated,
+        });
+    }
+    Ok(plan)
+}
+
+fn display_path(path: &Path, base: &Path) -> String {
+    path.strip_prefix(base)
+        .ok()
+        .filter(|relative| !relative.as_os_str().is_empty())
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+fn plural(count: usize, word: &str) -> String {
+    format!("{} {}{}", count, word, if count == 1 { "" } else { "s" })
+}
+
+/// The plan as shown to the model and the user.
+fn format_plan(plan: &RenamePlan, base: &Path) -> String {
+    let mut out = format!(
+        "Planned rename `{}` → `{}` in {}: {} in {}.\n",
+        plan.old_name,
+        plan.new_name,
+        display_path(&plan.scope, base),
+        plural(plan.occurrences(), "occurrence"),
+        plural(plan.files.len(), "file"),
+    );
+    if plan.skipped > 0 {
+        out.push_str(&format!(
+            "{} in strings or comments left alone (include_strings_and_comments renames them too).\n",
+            plural(plan.skipped, "more occurrence"),
+        ));
+    }
+    if plan.collisions > 0 {
+        out.push_str(&format!(
+            "Warning: `{}` already appears {} in scope, so the rename may collide.\n",
+            plan.new_name,
+            plural(plan.collisions, "time"),
+        ));
+    }
+    let mut shown = 0;
+    for file in &plan.files {
+        out.push_str(&format!("\n{}\n", display_path(&file.path, base)));
+        let changed = file
+            .original
+            .lines()
+            .zip(file.updated.lines())
+            .enumerate()
+            .filter(|(_, (before, after))| before != after);
+        for (index, (_, after)) in changed {
+            if shown == MAX_PREVIEW_LINES {
+                break;
+            }
+            out.push_str(&format!("  {}: {}\n", index + 1, after.trim()));
+            shown += 1;
+        }
+    }
+    if shown == MAX_PREVIEW_LINES {
+        out.push_str(&format!(
+            "\n(preview stops at {} changed lines)\n",
+            MAX_PREVIEW_LINES
+        ));
+    }
+    out.push_str(
+        "\nNothing has been changed yet. Call refactor_rename again with the same arguments and confirm: true to apply.",
+    );
+    out
+}
+
+/// Replace `path` through a sibling temp file and a rename, so a failed write
+/// never leaves it truncated. The file keeps its permissions.
+fn write_replacing(path: &Path, contents: &str) -> Result<()> {
+    let permissions = std::fs::metadata(path).map(|meta| meta.permissions()).ok();
+    crate::storage::write_bytes_without_backup(path, contents.as_bytes())?;
+    if let Some(permissions) = permissions {
+        std::fs::set_permissions(path, permissions)?;
+    }
+    Ok(())
+}
+
+/// Snapshot every planned file, then write them. If a write fails, every
+/// planned file, including the one that failed, is restored from the
+/// snapshot. The snapshot is returned so a failed verification can undo the
+/// rename too.
+fn apply_plan(plan: &RenamePlan) -> Result<Vec<FileSnapshot>> {
+    apply_plan_with(plan, write_replacing)
+}
+
+fn apply_plan_with(
+    plan: &RenamePlan,
+    write: impl Fn(&Path, &str) -> Result<()>,
+) -> Result<Vec<FileSnapshot>> {
+    let paths: Vec<&Path> = plan.files.iter().map(|file| file.path.as_path()).collect();
+    let snapshot = session_changes::capture(&paths)?;
+    for file in &plan.files {
+        if let Err(error) = write(&file.path, &file.updated) {
+            bail!(
+                "Rename failed writing {}: {:#}. {}",
+                file.path.display(),
+                error,
+                roll_back(&snapshot)
+            );
+        }
+    }
+    Ok(snapshot)
+}
+
+/// Restore every snapshotted file and describe the outcome.
+fn roll_back(snapshot: &[FileSnapshot]) -> String {
+    let failures: Vec<String> = snapshot
+        .iter()
+        .filter_map(|file| {
+            session_changes::restore(file)
+                .err()
+                .map(|error| format!("{} ({:#})", file.path.display(), error))
+        })
+        .collect();
+    if failures.is_empty() {
+        format!(
+            "Rolled back {}; nothing was changed.",
+            plural(snapshot.len(), "file")
+        )
+    } else {
+        format!("Could not roll back: {}", failures.join(", "))
+    }
+}
+
+fn shell_command(command: &str) -> tokio::process::Command {
+    #[cfg(windows)]
+    {
+        let mut cmd = tokio::process::Command::new("cmd");
+        cmd.arg("/C").arg(command);
+        cmd
+    }
+
+    #[cfg(not(windows))]
+    {
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.arg("-c").arg(command);
+        cmd
+    }
+}
+
+/// Run the verification command. Returns whether it passed and a
+/// description of how it went.
+async fn verify(command: &str, working_dir: Option<&Path>, timeout: Duration) -> (bool, String) {
+    let mut cmd = shell_command(command);
+    cmd.kill_on_drop(true);
+    if let Some(dir) = working_dir {
+        cmd.current_dir(dir);
+    }
+    let output = match tokio::time::timeout(timeout, cmd.output()).await {
+        Err(_) => {
+            return (
+                false,
+                format!(
+                    "Verification `{}` timed out after {}s.",
+                    command,
+                    timeout.as_secs()
+                ),
+            );
+        }
+        Ok(Err(error)) => {
+            return (
+                false,
+                format!("Verification `{}` could not start: {}", command, error),
+            );
+        }
+        Ok(Ok(output)) => output,
+    };
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    let text = text.trim();
+    let mut start = text.len().saturating_sub(MAX_VERIFY_OUTPUT_BYTES);
+    while !text.is_char_boundary(start) {
+        start += 1;
+    }
+    let status = if output.status.success() {
+        "passed".to_string()
+    } else {
+        match output.status.code() {
+            Some(code) => format!("failed (exit {})", code),
+            None => "failed".to_string(),
+        }
+    };
+    let description = if text.is_empty() {
+        format!("Verification `{}` {}.", command, status)
+    } else {
+        format!(
+            "Verification `{}` {}:\n{}{}",
+            command,
+            status,
+            if start > 0 { "…" } else { "" },
+            &text[start..]
+        )
+    };
+    (output.status.success(), description)
+}
+
+#[async_trait]
+impl Tool for RefactorRenameTool {
+    fn name(&self) -> &str {
+        "refactor_rename"
+    }
+
+    fn description(&self) -> &str {
+        "Rename an identifier across files. The first call returns the planned edits; call again with confirm: true to apply them and run the verification command."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "required": ["old_name", "new_name"],
+            "properties": {
+                "intent": super::intent_schema_property(),
+                "old_name": {
+                    "type": "string",
+                    "description": "Identifier to rename. Matched on word boundaries."
+                },
+                "new_name": {
+                    "type": "string",
+                    "description": "Replacement identifier."
+                },
+                "path": {
+                    "type": "string",
+                    "description": "File or directory to limit the rename to (default: the working directory)."
+                },
+                "include_strings_and_comments": {
+                    "type": "boolean",
+                    "description": "Also rename occurrences inside strings and comments (default: false)."
+                },
+                "confirm": {
+                    "type": "boolean",
+                    "description": "Apply the plan returned by the previous call with the same arguments."
+                },
+                "verify_command": {
+                    "type": "string",
+                    "description": "Command to run after applying, e.g. `cargo check`. Defaults to [tools.refactor] verify_command."
+                },
+                "keep_on_verify_failure": {
+                    "type": "boolean",
+                    "description": "Keep the applied rename when verification fails instead of rolling it back (default: false)."
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, input: Value, ctx: ToolContext) -> Result<ToolOutput> {
+        let params: RefactorRenameInput = serde_json::from_value(input)?;
+        let old_name = params.old_name.trim();
+        let new_name = params.new_name.trim();
+        if old_name.is_empty() || old_name.contains(char::is_whitespace) {
+            bail!("old_name must be a single identifier");
+        }
+        if new_name.is_empty() || new_name.contains(char::is_whitespace) {
+            bail!("new_name must be a single identifier");
+        }
+        if old_name == new_name {
+            bail!("old_name and new_name are the same");
+        }
+        let scope = ctx.resolve_path(Path::new(params.path.as_deref().unwrap_or(".")));
+        let base = ctx.working_dir.clone().unwrap_or_else(|| scope.clone());
+        let include_all = params.include_strings_and_comments;
+
+        let plan = {
+            let (scope, old_name, new_name) =
+                (scope.clone(), old_name.to_string(), new_name.to_string());
+            tokio::task::spawn_blocking(move || {
+                build_plan(&scope, &old_name, &new_name, include_all)
+            })
+            .await
+            .context("rename planning panicked")??
+        };
+        let title = format!("rename {} → {}", old_name, new_name);
+        if plan.files.is_empty() {
+            discard_pending_rename(&ctx.session_id);
+            return Ok(ToolOutput::new(format!(
+                "No occurrences of `{}` to rename in {}{}.",
+                old_name,
+                display_path(&scope, &base),
+                if plan.skipped > 0 {
+                    format!(
+                        " ({} in strings or comments)",
+                        plural(plan.skipped, "occurrence")
+                    )
+                } else {
+                    String::new()
+                }
+            ))
+            .with_title(title));
+        }
+
+        if !params.confirm {
+            let output = format_plan(&plan, &base);
+            store_pending(&ctx.session_id, plan);
+            return Ok(ToolOutput::new(output).with_title(title));
+        }
+
+        // Apply only what was previewed: the same request over unchanged files.
+        let previewed = take_pending(&ctx.session_id);
+        let matches_preview = previewed.as_ref().is_some_and(|previewed| {
+            previewed.same_request(&plan)
+                && previewed.files.len() == plan.files.len()
+                && previewed
+                    .files
+                    .iter()
+                    .zip(&plan.files)
+                    .all(|(before, now)| before.path == now.path && before.original == now.original)
+        });
+        if !matches_preview {
+            let output = format!(
+                "The files changed since the last preview, or this rename was not previewed. Review the current plan:\n\n{}",
+                format_plan(&plan, &base)
+            );
+            store_pending(&ctx.session_id, plan);
+            return Ok(ToolOutput::new(output).with_title(title));
+        }
+
+        let snapshot = tokio::task::spawn_blocking({
+            let plan = plan.clone();
+            move || apply_plan(&plan)
+        })
+        .await
+        .context("rename apply panicked")??;
+
+        let mut output = format!(
+            "Renamed `{}` → `{}`: {} in {}.",
+            old_name,
+            new_name,
+            plural(plan.occurrences(), "occurrence"),
+            plural(plan.files.len(), "file"),
+        );
+        let refactor_config = crate::config::config().tools.refactor.clone();
+        let verify_command = params
+            .verify_command
+            .filter(|command| !command.trim().is_empty())
+            .or(refactor_config.verify_command);
+        if let Some(command) = verify_command {
+            let (passed, outcome) = verify(
+                &command,
+                ctx.working_dir.as_deref(),
+                Duration::from_secs(refactor_config.verify_timeout_secs),
+            )
+            .await;
+            output.push_str("\n\n");
+            output.push_str(&outcome);
+            if !passed && !params.keep_on_verify_failure {
+                output.push_str("\n\n");
+                output.push_str(&roll_back(&snapshot));
+                output.push_str(
+                    " Call refactor_rename again with confirm: true and keep_on_verify_failure: true to apply it anyway.",
+                );
+                // The files are back to the previewed content, so the same
+                // plan can still be confirmed.
+                store_pending(&ctx.session_id, plan);
+                return Ok(ToolOutput::new(output).with_title(title));
+            }
+        }
+
+        for file in &plan.files {
+            crate::provenance::record_tool_change(
+                &ctx,
+                "refactor_rename",
+                &file.path,
+                &file.original,
+                &file.updated,
+            );
+            Bus::global().publish(BusEvent::FileTouch(FileTouch {
+                session_id: ctx.session_id.clone(),
+                path: file.path.clone(),
+                op: FileOp::Edit,
+                intent: None,
+                summary: Some(format!("renamed {} → {}", old_name, new_name)),
+                detail: None,
+            }));
+        }
+
+        Ok(ToolOutput::new(output)
+            .with_title(title)
+            .with_metadata(json!({
+                "files": plan.files.iter().map(|file| file.path.display().to_string()).collect::<Vec<_>>(),
+                "occurrences": plan.occurrences(),
+            })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn occurrences_respect_word_boundaries_strings_and_comments() {
+        let source = "let count = 1;\nlet counter = count + 1; // count\nprintln!(\"{count}\");\n/* count */ count";
+        let found = find_occurrences(source, "count", Syntax::CLike, false);
+        assert_eq!(found.renamed.len(), 3);
+        assert_eq!(found.skipped, 3);
+
+        let all = find_occurrences(source, "count", Syntax::CLike, true);
+        assert_eq!(all.renamed.len(), 6);
+
+        let python = "count = 1  # count\nprint('count', count)";
+        let found = find_occurrences(python, "count", Syntax::Hash, false);
+        assert_eq!((found.renamed.len(), found.skipped), (2, 2));
+    }
+
+    #[test]
+    fn escaped_quotes_do_not_end_a_string() {
+        let source = "let s = \"a \\\" name\"; let q = '\"'; name";
+        let found = find_occurrences(source, "name", Syntax::CLike, false);
+        assert_eq!((found.renamed.len(), found.skipped), (1, 1));
+    }
+
+    #[test]
+    fn replace_at_rewrites_only_the_given_offsets() {
+        let text = "foo(foo_bar, foo)";
+        let found = find_occurrences(text, "foo", Syntax::Plain, false);
+        assert_eq!(
+            replace_at(text, &found.renamed, 3, "baz"),
+            "baz(foo_bar, baz)"
+        );
+    }
+
+    #[test]
+    fn plan_covers_matching_files_and_flags_collisions() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::write(
+            dir.path().join("a.rs"),
+            "fn old_name() {}\nfn new_name() {}\n",
+        )
+        .expect("write a");
+        std::fs::write(dir.path().join("b.rs"), "old_name();\n").expect("write b");
+        std::fs::write(dir.path().join("c.rs"), "unrelated();\n").expect("write c");
+
+        let plan = build_plan(dir.path(), "old_name", "new_name", false).expect("plan");
+        assert_eq!(plan.files.len(), 2);
+        assert_eq!(plan.occurrences(), 2);
+        assert_eq!(plan.collisions, 1);
+        let text = format_plan(&plan, dir.path());
+        assert!(text.contains("2 occurrences in 2 files"), "{text}");
+        assert!(text.contains("b.rs\n  1: new_name();"), "{text}");
+        assert!(text.contains("may collide"), "{text}");
+    }
+
+    #[test]
+    fn failed_write_restores_every_planned_file_from_the_snapshot() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let first = dir.path().join("a.rs");
+        let second = dir.path().join("b.rs");
+        std::fs::write(&first, "old\n").expect("write a");
+        std::fs::write(&second, "old();\n").expect("write b");
+        let planned = |path: &Path, original: &str| PlannedFile {
+            path: path.to_path_buf(),
+            original: original.to_string(),
+            updated: original.replace("old", "new"),
+            occurrences: 1,
+        };
+        let plan = RenamePlan {
+            old_name: "old".to_string(),
+            new_name: "new".to_string(),
+            scope: dir.path().to_path_buf(),
+            include_all: false,
+            files: vec![planned(&first, "old\n"), planned(&second, "old();\n")],
+            skipped: 0,
+            collisions: 0,
+        };
+
+        // The second write truncates its file before failing, like a plain
+        // in-place write interrupted halfway.
+        let error = apply_plan_with(&plan, |path, contents| {
+            if path == second {
+                std::fs::write(path, "")?;
+                bail!("disk full");
+            }
+            write_replacing(path, contents)
+        })
+        .expect_err("second write fails");
+        assert!(error.to_string().contains("disk full"), "{error}");
+        assert!(error.to_string().contains("Rolled back 2 files"), "{error}");
+        assert_eq!(std::fs::read_to_string(&first).expect("read a"), "old\n");
+        assert_eq!(
+            std::fs::read_to_string(&second).expect("read b"),
+            "old();\n"
+        );
+    }
+
+    #[test]
+    fn expired_and_discarded_plans_are_evicted() {
+        let plan = RenamePlan {
+            old_name: "old".to_string(),
+            new_name: "new".to_string(),
+            scope: PathBuf::from("."),
+            include_all: false,
+            files: Vec::new(),
+            skipped: 0,
+            collisions: 0,
+        };
+        store_pending("rename-evict-a", plan.clone());
+        store_pending("rename-evict-b", plan);
+        discard_pending_rename("rename-evict-a");
+        assert!(!pending().contains_key("rename-evict-a"));
+
+        if let Some(expired) = Instant::now().checked_sub(PLAN_TTL + Duration::from_secs(1)) {
+            if let Some(entry) = pending().get_mut("rename-evict-b") {
+                entry.planned_at = expired;
+            }
+            assert!(take_pending("rename-evict-b").is_none());
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    #[allow(
+        clippy::await_holding_lock,
+        reason = "applied renames are snapshotted for /changes under JCODE_HOME"
+    )]
+    async fn confirm_applies_the_previewed_plan_and_verifies() {
+        let _storage_guard = crate::storage::lock_test_env();
+        let temp_home = tempfile::TempDir::new().expect("temp home");
+        let previous_home = std::env::var_os("JCODE_HOME");
+        crate::env::set_var("JCODE_HOME", temp_home.path());
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let file = dir.path().join("lib.rs");
+        std::fs::write(&file, "fn old_name() {}\nfn main() { old_name() }\n").expect("write");
+        let ctx = ToolContext {
+            session_id: "refactor-rename-test".to_string(),
+            message_id: "m".to_string(),
+            tool_call_id: "t".to_string(),
+            working_dir: Some(dir.path().to_path_buf()),
+            workspace_roots: Vec::new(),
+            stdin_request_tx: None,
+            graceful_shutdown_signal: None,
+            execution_mode: super::super::ToolExecutionMode::Direct,
+        };
+        let tool = RefactorRenameTool::new();
+        let input = json!({"old_name": "old_name", "new_name": "new_name"});
+
+        let preview = tool
+            .execute(input.clone(), ctx.clone())
+            .await
+            .expect("preview");
+        assert!(preview.output.contains("Nothing has been changed yet"));
+        assert!(
+            std::fs::read_to_string(&file)
+                .expect("read")
+                .contains("old_name")
+        );
+
+        let mut confirm = input;
+        confirm["confirm"] = json!(true);
+        confirm["verify_command"] = json!("grep -q new_name lib.rs");
+        let applied = tool.execute(confirm, ctx).await.expect("apply");
+        assert!(
+            applied
+                .output
+                .contains("Renamed `old_name` → `new_name`: 2 occurrences in 1 file."),
+            "{}",
+            applied.output
+        );
+        assert!(applied.output.contains("passed"), "{}", applied.output);
+        assert_eq!(
+            std::fs::read_to_string(&file).expect("read"),
+            "fn new_name() {}\nfn main() { new_name() }\n"
+        );
+
+        match previous_home {
+            Some(value) => crate::env::set_var("JCODE_HOME", value),
+            None => crate::env::remove_var("JCODE_HOME"),
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    #[allow(
+        clippy::await_holding_lock,
+        reason = "applied renames are snapshotted for /changes under JCODE_HOME"
+    )]
+    async fn failed_verification_rolls_the_rename_back() {
+        let _storage_guard = crate::storage::lock_test_env();
+        let temp_home = tempfile::TempDir::new().expect("temp home");
+        let previous_home = std::env::var_os("JCODE_HOME");
+        crate::env::set_var("JCODE_HOME", temp_home.path());
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let file = dir.path().join("lib.rs");
+        let source = "fn old_name() {}\n";
+        std::fs::write(&file, source).expect("write");
+        let ctx = ToolContext {
+            session_id: "refactor-rename-verify-test".to_string(),
+            message_id: "m".to_string(),
+            tool_call_id: "t".to_string(),
+            working_dir: Some(dir.path().to_path_buf()),
+            workspace_roots: Vec::new(),
+            stdin_request_tx: None,
+            graceful_shutdown_signal: None,
+            execution_mode: super::super::ToolExecutionMode::Direct,
+        };
+        let tool = RefactorRenameTool::new();
+        let input = json!({"old_name": "old_name", "new_name": "new_name"});
+        tool.execute(input.clone(), ctx.clone())
+            .await
+            .expect("preview");
+
+        let mut confirm = input;
+        confirm["confirm"] = json!(true);
+        confirm["verify_command"] = json!("false");
+        let output = tool
+            .execute(confirm.clone(), ctx.clone())
+            .await
+            .expect("apply");
+        assert!(output.output.contains("failed"), "{}", output.output);
+        assert!(
+            output.output.contains("Rolled back 1 file"),
+            "{}",
+            output.output
+        );
+        assert_eq!(std::fs::read_to_string(&file).expect("read"), source);
+
+        confirm["keep_on_verify_failure"] = json!(true);
+        let kept = tool.execute(confirm, ctx).await.expect("apply again");
+        assert!(!kept.output.contains("Rolled back"), "{}", kept.output);
+        assert_eq!(
+            std::fs::read_to_string(&file).expect("read"),
+            "fn new_name() {}\n"
+        );
+
+        match previous_home {
+            Some(value) => crate::env::set_var("JCODE_HOME", value),
+            None => crate::env::remove_var("JCODE_HOME"),
+        }
+    }
+}
@@ -0,0 +1,285 @@
+//! Per-call gate for sessions started with `jcode --safe`, also used on its
+//! own by the approval gate that ACP clients turn on.
+//!
+//! Tools outside the safety system's auto-allowed tier need an interactive
+//! approval. The request reuses the stdin forwarding channel, tagged with
+//! [`SAFE_MODE_APPROVAL_PREFIX`] so the client can tell it apart from a bash
+//! command waiting on input, and is listed in the shared permission inbox so
+//! other surfaces can answer it too. Memory writes are refused outright.
+
+use super::{StdinInputRequest, ToolContext};
+use crate::safety::{self, ActionTier, classify_action};
+use anyhow::{Result, anyhow, bail};
+use serde_json::Value;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// Prefix of stdin request ids that ask the client to approve a tool call.
+pub const SAFE_MODE_APPROVAL_PREFIX: &str = "safe-approval-";
+
+/// `memory` tool actions that change stored memories.
+const MEMORY_WRITE_ACTIONS: &[&str] = &["remember", "forget", "tag", "link"];
+
+/// Tools that only dispatch other tool calls, each of which is gated itself.
+const DISPATCH_ONLY_TOOLS: &[&str] = &["batch"];
+
+/// Longest input summary shown in an approval prompt.
+const MAX_PROMPT_DETAIL_CHARS: usize = 200;
+
+/// How often a pending approval checks whether another surface answered it.
+const INBOX_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+static APPROVAL_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Whether a client reply approves the pending call.
+pub fn is_approval(answer: &str) -> bool {
+    matches!(
+        answer.trim().to_ascii_lowercase().as_str(),
+        "y" | "yes" | "allow"
+    )
+}
+
+/// Refuse memory writes and wait for the user to approve any call outside the
+/// auto-allowed tier.
+pub(super) async fn check(tool_name: &str, input: &Value, ctx: &ToolContext) -> Result<()> {
+    if tool_name == "memory"
+        && input
+            .get("action")
+            .and_then(Value::as_str)
+            .is_some_and(|action| MEMORY_WRITE_ACTIONS.contains(&action))
+    {
+        bail!("Memory writes are disabled in safe mode");
+    }
+    check_approval("Safe mode", tool_name, input, ctx).await
+}
+
+/// Wait for the user to approve a call outside the auto-allowed tier. `label`
+/// names the policy asking, in errors the model sees.
+pub(super) async fn check_approval(
+    label: &str,
+    tool_name: &str,
+    input: &Value,
+    ctx: &ToolContext,
+) -> Result<()> {
+    if DISPATCH_ONLY_TOOLS.contains(&tool_name)
+        || classify_action(tool_name) == ActionTier::AutoAllowed
+    {
+        return Ok(());
+    }
+
+    let Some(stdin_tx) = ctx.stdin_request_tx.as_ref() else {
+        bail!("{label}: '{tool_name}' needs approval, but no interactive client is attached");
+    };
+    let request_id = format!(
+        "{}{}-{}",
+        SAFE_MODE_APPROVAL_PREFIX,
+        ctx.tool_call_id,
+        APPROVAL_COUNTER.fetch_add(1, Ordering::Relaxed)
+    );
+    let approved = request_approval(
+        ctx,
+        stdin_tx,
+        request_id,
+        tool_name,
+        approval_prompt(tool_name, input),
+    )
+    .await
+    .ok_or_else(|| anyhow!("{label}: '{tool_name}' needs approval, but the client left"))?;
+    if approved {
+        Ok(())
+    } else {
+        bail!("{label}: the user declined '{tool_name}'")
+    }
+}
+
+/// Ask the session's client to approve `prompt` and list the request in the
+/// permission inbox, so the desktop app, `jcode permissions` or Telegram can
+/// answer it instead. Whichever answer comes first wins; an unanswered request
+/// is denied when its inbox entry expires. `None` means the client is gone.
+pub(super) async fn request_approval(
+    ctx: &ToolContext,
+    stdin_tx: &UnboundedSender<StdinInputRequest>,
+    request_id: String,
+    action: &str,
+    prompt: String,
+) -> Option<bool> {
+    let (response_tx, mut response_rx) = tokio::sync::oneshot::channel();
+    stdin_tx
+        .send(StdinInputRequest {
+            request_id: request_id.clone(),
+            prompt: prompt.clone(),
+            is_password: false,
+            response_tx,
+        })
+        .ok()?;
+    if let Err(error) =
+        safety::enqueue_session_request(&request_id, &ctx.session_id, action, &prompt)
+    {
+        crate::logging::warn(&format!(
+            "Could not add {} to the permission inbox: {}",
+            request_id, error
+        ));
+    }
+
+    let deadline = tokio::time::Instant::now()
+        + safety::interactive_approval_ttl()
+            .to_std()
+            .unwrap_or(Duration::ZERO);
+    let mut poll = tokio::time::interval(INBOX_POLL_INTERVAL);
+    loop {
+        tokio::select! {
+            answer = &mut response_rx => {
+                let approved = is_approval(&answer.unwrap_or_default());
+                // Fails harmlessly when another surface answered first.
+                let _ = safety::record_decision(&request_id, approved, "session_client", None);
+                return Some(approved);
+            }
+            _ = poll.tick() => {
+                if let Some(decision) = safety::decision_for(&request_id) {
+                    return Some(decision.approved);
+                }
+                if tokio::time::Instant::now() >= deadline {
+                    let _ = safety::record_decision(
+                        &request_id,
+                        false,
+                        "expired",
+                        Some("Nobody answered in time".to_string()),
+                    );
+                    return Some(false);
+                }
+            }
+        }
+    }
+}
+
+/// One-line description of the call, e.g. "bash: cargo test".
+fn approval_prompt(tool_name: &str, input: &Value) -> String {
+    let detail = ["command", "file_path", "path", "url", "prompt"]
+        .iter()
+        .find_map(|key| input.get(*key).and_then(Value::as_str))
+        .map(str::to_string)
+        .unwrap_or_else(|| input.to_string());
+    let detail = detail.lines().next().unwrap_or_default();
+    format!(
+        "{}: {}",
+        tool_name,
+        crate::util::truncate_str(detail, MAX_PROMPT_DETAIL_CHARS)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn approval_prompt_prefers_the_command_or_path() {
+        let bash = serde_json::json!({"command": "cargo test\necho done", "intent": "x"});
+        assert_eq!(approval_prompt("bash", &bash), "bash: cargo test");
+        let write = serde_json::json!({"file_path": "src/main.rs", "content": "fn main() {}"});
+        assert_eq!(approval_prompt("write", &write), "write: src/main.rs");
+    }
+
+    #[tokio::test]
+    async fn memory_writes_and_unattended_calls_are_refused() {
+        let ctx = ToolContext {
+            session_id: "safe-mode-test".to_string(),
+            message_id: "m".to_string(),
+            tool_call_id: "t".to_string(),
+            working_dir: None,
+            workspace_roots: Vec::new(),
+            stdin_request_tx: None,
+            graceful_shutdown_signal: None,
+            execution_mode: super::super::ToolExecutionMode::Direct,
+        };
+        let remember = serde_json::json!({"action": "remember", "content": "x"});
+        assert!(check("memory", &remember, &ctx).await.is_err());
+        let recall = serde_json::json!({"action": "recall"});
+        assert!(check("memory", &recall, &ctx).await.is_ok());
+        assert!(check("read", &serde_json::json!({}), &ctx).await.is_ok());
+        let error = check("bash", &serde_json::json!({"command": "ls"}), &ctx)
+            .await
+            .expect_err("bash needs approval");
+        assert!(error.to_string().contains("needs approval"));
+        let error = check_approval(
+            "Approval gate",
+            "bash",
+            &serde_json::json!({"command": "ls"}),
+            &ctx,
+        )
+        .await
+        .expect_err("the approval gate asks too");
+        assert!(
+            error
+                .to_string()
+                .starts_with("Approval gate: 'bash' needs approval")
+        );
+    }
+
+    #[tokio::test]
+    #[allow(
+        clippy::await_holding_lock,
+        reason = "the approval is listed in the permission inbox under JCODE_HOME"
+    )]
+    async fn approval_answered_from_the_inbox_resolves_the_waiting_call() {
+        let _storage_guard = crate::storage::lock_test_env();
+        let temp_home = tempfile::TempDir::new().expect("temp home");
+        let previous_home = std::env::var_os("JCODE_HOME");
+        crate::env::set_var("JCODE_HOME", temp_home.path());
+
+        let (stdin_tx, mut stdin_rx) = tokio::sync::mpsc::unbounded_channel();
+        let ctx = ToolContext {
+            session_id: "safe-mode-inbox".to_string(),
+            message_id: "m".to_string(),
+            tool_call_id: "t".to_string(),
+            working_dir: None,
+            workspace_roots: Vec::new(),
+            stdin_request_tx: None,
+            graceful_shutdown_signal: None,
+            execution_mode: super::super::ToolExecutionMode::Direct,
+        };
+        let answer_elsewhere = tokio::spawn(async move {
+            // Keep the client's request pending while another surface answers.
+            let request = stdin_rx.recv().await.expect("approval request");
+            loop {
+                let inbox = crate::safety::inbox();
+                if let Some(entry) = inbox.iter().find(|entry| entry.id == request.request_id) {
+                    assert_eq!(
+                        entry.to_inbox_entry().origin_id.as_deref(),
+                        Some("safe-mode-inbox")
+                    );
+                    crate::safety::record_decision(&entry.id, true, "test", None)
+                        .expect("record decision");
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+            request
+        });
+
+        let approved = request_approval(
+            &ctx,
+            &stdin_tx,
+            format!("{}inbox-test", SAFE_MODE_APPROVAL_PREFIX),
+            "bash",
+            "bash: ls".to_string(),
+        )
+        .await;
+        let _request = answer_elsewhere.await.expect("answering task");
+        assert_eq!(approved, Some(true));
+        assert!(crate::safety::inbox().is_empty());
+
+        match previous_home {
+            Some(value) => crate::env::set_var("JCODE_HOME", value),
+            None => crate::env::remove_var("JCODE_HOME"),
+        }
+    }
+
+    #[test]
+    fn only_yes_answers_approve() {
+        assert!(is_approval(" Y "));
+        assert!(is_approval("yes"));
+        assert!(!is_approval(""));
+        assert!(!is_approval("n"));
+    }
+}
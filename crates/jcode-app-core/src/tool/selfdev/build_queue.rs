@@ -368,8 +368,8 @@ impl SelfDevTool {
                                 &repo_dir,
                                 &source_after_build,
                             )?;
-                            let mut manifest = build::BuildManifest::load()?;
-                            manifest.add_to_history(build::current_build_info(&repo_dir)?)?;
+                            let info = build::current_build_info(&repo_dir)?;
+                            build::BuildManifest::update(|manifest| manifest.add_to_history(info))?;
                             Some(published)
                         };
                         let mut request = BuildRequest::load(&request_id)?.ok_or_else(|| {
@@ -21,6 +21,7 @@ pub fn enter_selfdev_session(
                 child.model = parent.model.clone();
                 child.provider_key = parent.provider_key.clone();
                 child.route_api_method = parent.route_api_method.clone();
+                child.model_route = parent.model_route.clone();
                 child.subagent_model = parent.subagent_model.clone();
                 child.improve_mode = parent.improve_mode;
                 child.autoreview_enabled = parent.autoreview_enabled;
@@ -303,20 +303,21 @@ impl SelfDevTool {
         };
 
         // Update manifest - track what we're testing
-        let mut manifest = build::BuildManifest::load()?;
-        manifest.canary = Some(hash.clone());
-        manifest.canary_status = Some(build::CanaryStatus::Testing);
-        manifest.set_pending_activation(build::PendingActivation {
-            session_id: session_id.to_string(),
-            new_version: hash.clone(),
-            previous_current_version: published
-                .as_ref()
-                .and_then(|published| published.previous_current_version.clone()),
-            previous_shared_server_version,
-            source_fingerprint: Some(source.fingerprint.clone()),
-            requested_at: chrono::Utc::now(),
+        build::BuildManifest::update(|manifest| {
+            manifest.canary = Some(hash.clone());
+            manifest.canary_status = Some(build::CanaryStatus::Testing);
+            manifest.pending_activation = Some(build::PendingActivation {
+                session_id: session_id.to_string(),
+                new_version: hash.clone(),
+                previous_current_version: published
+                    .as_ref()
+                    .and_then(|published| published.previous_current_version.clone()),
+                previous_shared_server_version,
+                source_fingerprint: Some(source.fingerprint.clone()),
+                requested_at: chrono::Utc::now(),
+            });
+            Ok(())
         })?;
-        manifest.save()?;
 
         if !SelfDevTool::is_test_session()
             && let Err(error) = build::update_shared_server_symlink(&hash)
@@ -45,6 +45,7 @@ fn create_test_context(session_id: &str, working_dir: Option<std::path::PathBuf>
         message_id: "test-message".to_string(),
         tool_call_id: "test-tool-call".to_string(),
         working_dir,
+        workspace_roots: Vec::new(),
         stdin_request_tx: None,
         graceful_shutdown_signal: None,
         execution_mode: crate::tool::ToolExecutionMode::Direct,
@@ -616,7 +616,9 @@ fn search_sessions_blocking(
         return Ok(report);
     }
 
-    if source_matches_filter("jcode", options) {
+    if source_matches_filter("jcode", options)
+        && !search_indexed_jcode_sessions(query, options, &mut report)
+    {
         let collection = collect_session_files(sessions_dir, options.max_scan_sessions)?;
         report.truncated |= collection.truncated;
         let mut files = collection.files;
@@ -930,6 +932,78 @@ fn read_candidate_raw(
     Some(raw)
 }
 
+/// Pick candidates with the storage backend's full-text index
+/// (`[storage] backend = "sqlite"`) instead of scanning session files.
+/// Returns `false` when there is no index, or for exhaustive searches.
+fn search_indexed_jcode_sessions(
+    query: &QueryProfile,
+    options: &SearchOptions,
+    report: &mut SearchReport,
+) -> bool {
+    if options.exhaustive {
+        return false;
+    }
+    let store = storage::store();
+    let budget = indexed_candidate_budget(options).min(MAX_DESERIALIZE);
+    let mut ids = match store.search_sessions(&query.terms, budget.saturating_add(1)) {
+        Ok(Some(ids)) => ids,
+        Ok(None) => return false,
+        Err(err) => {
+            crate::logging::warn(&format!(
+                "session_search storage index unavailable; falling back to file scan: {err}"
+            ));
+            return false;
+        }
+    };
+    if !options.include_current {
+        ids.retain(|id| *id != options.current_session_id);
+    }
+    if ids.len() > budget {
+        ids.truncate(budget);
+        report.truncated = true;
+    }
+    report.scanned_jcode_sessions = ids.len();
+    report.candidate_jcode_sessions = ids.len();
+    if ids.is_empty() {
+        return true;
+    }
+
+    let thread_count = SCAN_THREADS.min(ids.len());
+    let chunk_size = ids.len().div_ceil(thread_count);
+    let store = &store;
+    let outcomes: Vec<SearchWorkerOutcome> = std::thread::scope(|scope| {
+        let handles: Vec<_> = ids
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    let mut outcome = SearchWorkerOutcome::default();
+                    for id in chunk {
+                        match store.load_session(id) {
+                            Ok(session) => append_session_results(
+                                &mut outcome.results,
+                                &session,
+                                query,
+                                options,
+                            ),
+                            Err(_) => outcome.parse_errors += 1,
+                        }
+                    }
+                    outcome
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap_or_default())
+            .collect()
+    });
+    for outcome in outcomes {
+        report.parse_errors += outcome.parse_errors;
+        report.results.extend(outcome.results);
+    }
+    true
+}
+
 fn score_candidates_parallel(
     candidates: &[SessionFileCandidate],
     query: &QueryProfile,
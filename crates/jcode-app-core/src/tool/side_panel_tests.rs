@@ -43,6 +43,7 @@ async fn side_panel_tool_writes_page() {
                 message_id: "msg1".to_string(),
                 tool_call_id: "tool1".to_string(),
                 working_dir: None,
+                workspace_roots: Vec::new(),
                 stdin_request_tx: None,
                 graceful_shutdown_signal: None,
                 execution_mode: crate::tool::ToolExecutionMode::AgentTurn,
@@ -74,6 +75,7 @@ async fn side_panel_tool_loads_file_with_derived_page_id() {
                 message_id: "msg1".to_string(),
                 tool_call_id: "tool1".to_string(),
                 working_dir: Some(temp.path().to_path_buf()),
+                workspace_roots: Vec::new(),
                 stdin_request_tx: None,
                 graceful_shutdown_signal: None,
                 execution_mode: crate::tool::ToolExecutionMode::AgentTurn,
@@ -99,9 +99,12 @@ impl SkillTool {
         let name = normalize_skill_name(name, "load")?;
 
         let registry = self.registry.read().await;
-        let skill = registry
-            .get(&name)
-            .ok_or_else(|| anyhow::anyhow!("Skill '{}' not found", name))?;
+        let Some(skill) = registry.get(&name) else {
+            if let Some(dir) = registry.invalid_skill_dir(&name) {
+                anyhow::bail!("{}", load_failure_report(&name, dir));
+            }
+            anyhow::bail!("Skill '{}' not found", name);
+        };
 
         let base_dir = skill
             .path
@@ -154,6 +157,7 @@ impl SkillTool {
             output
         };
 
+        append_invalid_skills(&mut output, &registry);
         append_endorsed_skills(&mut output, &installed);
 
         Ok(ToolOutput::new(output).with_title("Skills: List"))
@@ -163,6 +167,11 @@ impl SkillTool {
         let name = normalize_skill_name(name, "reload")?;
 
         let mut registry = self.registry.write().await;
+        let skill_dir = registry
+            .get(&name)
+            .and_then(|skill| skill.path.parent())
+            .or_else(|| registry.invalid_skill_dir(&name))
+            .map(std::path::Path::to_path_buf);
 
         match registry.reload(&name) {
             Ok(true) => {
@@ -180,20 +189,25 @@ impl SkillTool {
                         .with_title(format!("Skills: Reloaded {}", name)))
                 }
             }
-            Ok(false) => Ok(ToolOutput::new(format!(
-                "Skill '{}' not found or was deleted.\n\nUse 'list' to see available skills.",
-                name
-            ))
-            .with_title("Skills: Not found")),
+            Ok(false) => match skill_dir.filter(|dir| dir.exists()) {
+                Some(dir) => Ok(ToolOutput::new(load_failure_report(&name, &dir))
+                    .with_title("Skills: Reload failed")),
+                None => Ok(ToolOutput::new(format!(
+                    "Skill '{}' not found or was deleted.\n\nUse 'list' to see available skills.",
+                    name
+                ))
+                .with_title("Skills: Not found")),
+            },
             Err(e) => {
                 crate::logging::warn(&format!(
                     "[tool:skill_manage] reload failed skill={} error={}",
                     name, e
                 ));
-                Ok(
-                    ToolOutput::new(format!("Failed to reload skill '{}': {}", name, e))
-                        .with_title("Skills: Reload failed"),
-                )
+                let output = match skill_dir {
+                    Some(dir) => load_failure_report(&name, &dir),
+                    None => format!("Failed to reload skill '{}': {}", name, e),
+                };
+                Ok(ToolOutput::new(output).with_title("Skills: Reload failed"))
             }
         }
     }
@@ -209,6 +223,7 @@ impl SkillTool {
                 for skill in skills {
                     output.push_str(&format!("- /{}: {}\n", skill.name, skill.description));
                 }
+                append_invalid_skills(&mut output, &registry);
 
                 Ok(ToolOutput::new(output).with_title(format!("Skills: Reloaded {}", count)))
             }
@@ -249,6 +264,33 @@ impl SkillTool {
     }
 }
 
+/// Explain why the skill in `dir` did not load, using the same validator as
+/// `jcode skill check`.
+fn load_failure_report(name: &str, dir: &std::path::Path) -> String {
+    let check = crate::skill::validate_skill_dir(dir);
+    let mut output = format!("Skill '{}' failed to load ({}).\n", name, dir.display());
+    if check.issues.is_empty() {
+        output.push_str("  error: SKILL.md could not be parsed\n");
+    } else {
+        output.push_str(&check.render_issues());
+    }
+    output.push_str("\nFix SKILL.md, then reload with skill_manage (action=reload_all).");
+    output
+}
+
+/// Note skill directories that exist on disk but failed to load.
+fn append_invalid_skills(output: &mut String, registry: &SkillRegistry) {
+    let invalid = registry.invalid_skill_names();
+    if invalid.is_empty() {
+        return;
+    }
+    output.push_str(&format!(
+        "\nFailed to load {} skill(s): {}\nLoad one by name to see what is wrong with it.\n",
+        invalid.len(),
+        invalid.join(", ")
+    ));
+}
+
 /// Append the curated jcode-endorsed skill catalog to `output`, grouped by
 /// category and marked with installed/not-installed status. `installed` is the
 /// set of skill names currently loaded in the registry.
@@ -345,6 +387,7 @@ mod tests {
             message_id: "test-message".to_string(),
             tool_call_id: "test-tool-call".to_string(),
             working_dir: None,
+            workspace_roots: Vec::new(),
             stdin_request_tx: None,
             graceful_shutdown_signal: None,
             execution_mode: crate::tool::ToolExecutionMode::Direct,
@@ -498,4 +541,32 @@ mod tests {
             result.output
         );
     }
+
+    #[tokio::test]
+    async fn test_load_reports_validation_errors_for_broken_skill() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let skill_dir = temp_dir.path().join(".jcode").join("skills").join("broken");
+        std::fs::create_dir_all(&skill_dir).unwrap();
+        std::fs::write(skill_dir.join("SKILL.md"), "---\nname: broken\n---\n\nBody").unwrap();
+        let registry = SkillRegistry::load_for_working_dir(Some(temp_dir.path())).unwrap();
+        let tool = SkillTool::new(Arc::new(RwLock::new(registry)));
+
+        let err = tool
+            .execute(
+                json!({"action": "load", "name": "broken"}),
+                create_test_context(),
+            )
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("failed to load"), "{err}");
+        assert!(err.contains("required field `description`"), "{err}");
+
+        let list = tool
+            .execute(json!({"action": "list"}), create_test_context())
+            .await
+            .unwrap();
+        assert!(list.output.contains("Failed to load"));
+        assert!(list.output.contains("broken"));
+    }
 }
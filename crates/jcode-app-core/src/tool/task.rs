@@ -1,7 +1,11 @@
 use super::{Registry, Tool, ToolContext, ToolOutput};
 use crate::agent::Agent;
-use crate::bus::{Bus, BusEvent, ToolSummary, ToolSummaryState};
+use crate::bus::{
+    BatchProgress, BatchSubcallProgress, BatchSubcallState, Bus, BusEvent, ToolSummary,
+    ToolSummaryState,
+};
 use crate::logging;
+use crate::message::ToolCall;
 use crate::protocol::HistoryMessage;
 use crate::provider::Provider;
 use crate::session::Session;
@@ -46,19 +50,83 @@ impl SubagentTool {
 
 #[derive(Deserialize)]
 struct SubagentInput {
+    #[serde(default)]
     description: String,
+    #[serde(default)]
     prompt: String,
+    #[serde(default)]
     subagent_type: String,
     #[serde(default)]
     model: Option<String>,
     #[serde(default)]
+    profile: Option<String>,
+    #[serde(default)]
+    working_dir: Option<String>,
+    #[serde(default)]
     session_id: Option<String>,
     #[serde(default)]
     output_mode: SubagentOutputMode,
+    #[serde(default = "default_inherit_instructions")]
+    inherit_instructions: bool,
+    /// Several independent tasks to run concurrently instead of one.
+    #[serde(default)]
+    tasks: Vec<SubagentTask>,
     #[serde(rename = "command", default)]
     _command: Option<String>,
 }
 
+#[derive(Clone, Debug, Deserialize)]
+struct SubagentTask {
+    description: String,
+    prompt: String,
+    #[serde(default = "default_subagent_type")]
+    subagent_type: String,
+    #[serde(default)]
+    model: Option<String>,
+    /// `[profiles.<name>]` preset for the child agent.
+    #[serde(default)]
+    profile: Option<String>,
+    /// Directory the child's tools run in instead of the parent's.
+    #[serde(default)]
+    working_dir: Option<String>,
+    /// Whether the child gets the parent's `/instructions`.
+    #[serde(default = "default_inherit_instructions")]
+    inherit_instructions: bool,
+}
+
+fn default_subagent_type() -> String {
+    "general".to_string()
+}
+
+fn default_inherit_instructions() -> bool {
+    true
+}
+
+impl SubagentInput {
+    fn single_task(&self) -> SubagentTask {
+        SubagentTask {
+            description: self.description.clone(),
+            prompt: self.prompt.clone(),
+            subagent_type: self.subagent_type.clone(),
+            model: self.model.clone(),
+            profile: self.profile.clone(),
+            working_dir: self.working_dir.clone(),
+            inherit_instructions: self.inherit_instructions,
+        }
+    }
+}
+
+/// Outcome of one child agent run.
+struct SubagentRun {
+    final_text: String,
+    session_id: String,
+    model: String,
+    summary: Vec<ToolSummary>,
+    usage: crate::protocol::TokenUsageTotals,
+    history: Option<Vec<HistoryMessage>>,
+    full_transcript: Option<String>,
+}
+
 #[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 enum SubagentOutputMode {
@@ -87,7 +155,6 @@ impl Tool for SubagentTool {
     fn parameters_schema(&self) -> Value {
         json!({
             "type": "object",
-            "required": ["description", "prompt", "subagent_type"],
             "properties": {
                 "intent": super::intent_schema_property(),
                 "description": {
@@ -106,6 +173,18 @@ impl Tool for SubagentTool {
                     "type": "string",
                     "description": "Model override."
                 },
+                "profile": {
+                    "type": "string",
+                    "description": "Named config profile for the subagent (model, tools, prompt, limits)."
+                },
+                "working_dir": {
+                    "type": "string",
+                    "description": "Directory the subagent's tools run in, inside the workspace. Relative paths resolve against the current tool directory, which is the default."
+                },
+                "inherit_instructions": {
+                    "type": "boolean",
+                    "description": "Pass the session's standing /instructions on to the subagent. Defaults to true; set false for work they do not apply to."
+                },
                 "session_id": {
                     "type": "string",
                     "description": "Existing session ID."
@@ -115,6 +194,23 @@ impl Tool for SubagentTool {
                     "enum": ["answer", "compact", "full_transcript"],
                     "description": "Return mode. 'answer' returns the final answer only, 'compact' adds a user-visible transcript, and 'full_transcript' adds raw persisted messages. Defaults to 'answer'."
                 },
+                "tasks": {
+                    "type": "array",
+                    "description": "Independent tasks to run in parallel, each in its own session. Use instead of description/prompt/subagent_type.",
+                    "items": {
+                        "type": "object",
+                        "required": ["description", "prompt"],
+                        "properties": {
+                            "description": { "type": "string" },
+                            "prompt": { "type": "string" },
+                            "subagent_type": { "type": "string" },
+                            "model": { "type": "string" },
+                            "profile": { "type": "string" },
+                            "working_dir": { "type": "string" },
+                            "inherit_instructions": { "type": "boolean" }
+                        }
+                    }
+                },
                 "command": {
                     "type": "string",
                     "description": "Source command."
@@ -125,30 +221,111 @@ impl Tool for SubagentTool {
 
     async fn execute(&self, input: Value, ctx: ToolContext) -> Result<ToolOutput> {
         let params: SubagentInput = serde_json::from_value(input)?;
+        if !params.tasks.is_empty() {
+            return self.execute_parallel(params.tasks, &ctx).await;
+        }
+        if params.prompt.trim().is_empty() {
+            anyhow::bail!("subagent needs a prompt (or a tasks list)");
+        }
+
+        let task = params.single_task();
+        let run = self
+            .run_subagent(
+                &task,
+                params.session_id.as_deref(),
+                params.output_mode,
+                &ctx,
+            )
+            .await?;
+
+        let output = format_subagent_output(
+            &run.final_text,
+            &run.session_id,
+            params.output_mode,
+            run.history.as_deref(),
+            run.full_transcript.as_deref(),
+        );
+
+        Ok(ToolOutput::new(output)
+            .with_title(subagent_display_title(&task, &run.model))
+            .with_metadata(json!({
+                "summary": run.summary,
+                "sessionId": run.session_id,
+                "model": run.model,
+                "outputMode": params.output_mode.as_str(),
+            })))
+    }
+}
 
-        let mut session = if let Some(session_id) = &params.session_id {
+impl SubagentTool {
+    /// Run one child agent to completion in its own session.
+    async fn run_subagent(
+        &self,
+        task: &SubagentTask,
+        existing_session_id: Option<&str>,
+        output_mode: SubagentOutputMode,
+        ctx: &ToolContext,
+    ) -> Result<SubagentRun> {
+        let mut session = if let Some(session_id) = existing_session_id {
             Session::load(session_id).unwrap_or_else(|err| {
                 logging::warn(&format!(
                     "[tool:subagent] failed to load existing session {}; creating a new subagent session instead: {}",
                     session_id, err
                 ));
-                Session::create(Some(ctx.session_id.clone()), Some(subagent_title(&params)))
+                Session::create(Some(ctx.session_id.clone()), Some(subagent_title(task)))
             })
         } else {
-            Session::create(Some(ctx.session_id.clone()), Some(subagent_title(&params)))
+            Session::create(Some(ctx.session_id.clone()), Some(subagent_title(task)))
+        };
+        let profile_model = match task.profile.as_deref() {
+            Some(name) => {
+                let profile = crate::config::config().profile(name)?;
+                if let Some(error) = profile.validate(name).into_iter().next() {
+                    anyhow::bail!(error);
+                }
+                profile.model_request()
+            }
+            None => None,
         };
         let parent_subagent_model = Self::preferred_parent_subagent_model(&ctx.session_id);
         let provider_model = self.provider.model();
         let resolved_model = Self::resolve_model(
-            params.model.as_deref(),
+            task.model.as_deref().or(profile_model.as_deref()),
             session.model.as_deref(),
             parent_subagent_model.as_deref(),
             &provider_model,
         );
         session.model = Some(resolved_model.clone());
+        if task.profile.is_some() {
+            session.profile = task.profile.clone();
+        }
 
-        if let Some(ref working_dir) = ctx.working_dir {
-            session.working_dir = Some(working_dir.display().to_string());
+        // `ctx.working_dir` is the parent's tool directory, so the child keeps
+        // the parent's working directory and inherits its `/cd` scope.
+        let tool_dir = ctx
+            .working_dir
+            .as_ref()
+            .map(|dir| dir.display().to_string());
+        let parent = Session::load(&ctx.session_id).ok();
+        let parent_working_dir = parent
+            .as_ref()
+            .and_then(|parent| parent.working_dir.clone());
+        if let Some(dir) = parent_working_dir.or_else(|| tool_dir.clone()) {
+            session.working_dir = Some(dir);
+        }
+        // Standing `/instructions` follow the work into the child unless the
+        // caller opts out for a task they do not apply to.
+        session.instructions = parent
+            .and_then(|parent| parent.instructions)
+            .filter(|_| task.inherit_instructions);
+        session.workspace_roots = ctx
+            .workspace_roots
+            .iter()
+            .map(|root| root.display().to_string())
+            .collect();
+        session.tool_scope = tool_dir.filter(|dir| session.working_dir.as_ref() != Some(dir));
+        if let Some(dir) = task.working_dir.as_deref() {
+            session.scope_tools(dir)?;
         }
 
         session.save()?;
@@ -202,7 +379,7 @@ impl Tool for SubagentTool {
 
         logging::info(&format!(
             "Subagent starting: {} (type: {})",
-            params.description, params.subagent_type
+            task.description, task.subagent_type
         ));
 
         // Run subagent on an isolated provider fork so model/session changes do not
@@ -213,13 +390,16 @@ impl Tool for SubagentTool {
             session,
             Some(allowed),
         );
+        let usage_origin = crate::usage::UsageOrigin::Subagent(task.subagent_type.clone());
+        agent.set_usage_origin(usage_origin.clone());
+        let usage_before = agent.token_usage_totals();
 
         let start = std::time::Instant::now();
         // Bound the wait so a stuck/hung child turn (e.g. a model that never
         // emits a final answer) cannot block the caller indefinitely. `0`
         // disables the bound. See issue #365.
         let timeout_secs = crate::config::config().agents.subagent_timeout_secs;
-        let run_fut = agent.run_once_capture(&params.prompt);
+        let run_fut = agent.run_once_capture(&task.prompt);
         let run_result = if timeout_secs == 0 {
             run_fut.await
         } else {
@@ -230,15 +410,15 @@ impl Tool for SubagentTool {
                     logging::warn(&format!(
                         "[tool:subagent] subagent timed out after {}s description={} type={} session_id={} model={}",
                         timeout_secs,
-                        params.description,
-                        params.subagent_type,
+                        task.description,
+                        task.subagent_type,
                         agent.session_id(),
                         resolved_model
                     ));
                     listener.abort();
                     return Err(anyhow::anyhow!(
                         "subagent '{}' timed out after {}s without producing a final answer",
-                        params.description,
+                        task.description,
                         timeout_secs
                     ));
                 }
@@ -247,21 +427,22 @@ impl Tool for SubagentTool {
         let final_text = run_result.map_err(|err| {
             logging::warn(&format!(
                 "[tool:subagent] subagent failed description={} type={} session_id={} model={} error={}",
-                params.description,
-                params.subagent_type,
+                task.description,
+                task.subagent_type,
                 agent.session_id(),
                 resolved_model,
                 err
             ));
+            listener.abort();
             err
         })?;
         let sub_session_id = agent.session_id().to_string();
-        let history = if params.output_mode == SubagentOutputMode::Compact {
+        let history = if output_mode == SubagentOutputMode::Compact {
             Some(agent.get_history())
         } else {
             None
         };
-        let full_transcript = if params.output_mode == SubagentOutputMode::FullTranscript {
+        let full_transcript = if output_mode == SubagentOutputMode::FullTranscript {
             let session = Session::load(&sub_session_id)?;
             Some(serde_json::to_string_pretty(&session.messages)?)
         } else {
@@ -270,7 +451,7 @@ impl Tool for SubagentTool {
 
         logging::info(&format!(
             "Subagent completed: {} in {:.1}s",
-            params.description,
+            task.description,
             start.elapsed().as_secs_f64()
         ));
 
@@ -284,37 +465,286 @@ impl Tool for SubagentTool {
             .collect();
         summary.sort_by(|a, b| a.id.cmp(&b.id));
 
-        let output = format_subagent_output(
-            &final_text,
-            &sub_session_id,
-            params.output_mode,
-            history.as_deref(),
-            full_transcript.as_deref(),
+        let usage = agent.token_usage_totals();
+        crate::usage::attribute_to_session(
+            &ctx.session_id,
+            &usage_origin,
+            crate::usage::OriginUsage::new(
+                usage
+                    .messages_with_token_usage
+                    .saturating_sub(usage_before.messages_with_token_usage) as u64,
+                usage.input_tokens.saturating_sub(usage_before.input_tokens),
+                usage
+                    .output_tokens
+                    .saturating_sub(usage_before.output_tokens),
+            ),
         );
 
-        Ok(ToolOutput::new(output)
-            .with_title(subagent_display_title(&params, &resolved_model))
+        Ok(SubagentRun {
+            final_text,
+            session_id: sub_session_id,
+            model: resolved_model,
+            summary,
+            usage,
+            history,
+            full_transcript,
+        })
+    }
+
+    /// Run `tasks` concurrently, at most `[agent] max_parallel_subagents` at a
+    /// time. A failed task is reported in its slot and never cancels the rest.
+    async fn execute_parallel(
+        &self,
+        tasks: Vec<SubagentTask>,
+        ctx: &ToolContext,
+    ) -> Result<ToolOutput> {
+        if let Some(task) = tasks.iter().find(|task| task.prompt.trim().is_empty()) {
+            anyhow::bail!("subagent task '{}' has an empty prompt", task.description);
+        }
+        let max_parallel = crate::config::config().agent.max_parallel_subagents();
+        let progress = Mutex::new(ParallelProgress::new(ctx, &tasks));
+        let slots = tokio::sync::Semaphore::new(max_parallel);
+        let update_progress = |update: &dyn Fn(&mut ParallelProgress)| {
+            let mut progress = progress
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            update(&mut progress);
+            progress.publish();
+        };
+        update_progress(&|_| {});
+
+        // `join_all` keeps input order; the semaphore bounds how many run.
+        let runs = tasks.iter().enumerate().map(|(index, task)| {
+            let slots = &slots;
+            let update_progress = &update_progress;
+            async move {
+                let _slot = slots.acquire().await;
+                update_progress(&|progress| progress.start(index));
+                let started = std::time::Instant::now();
+                let result = self
+                    .run_subagent(task, None, SubagentOutputMode::Answer, ctx)
+                    .await;
+                let ok = result.is_ok();
+                update_progress(&|progress| progress.finish(index, ok));
+                (result, started.elapsed())
+            }
+        });
+        let results = futures::future::join_all(runs).await;
+
+        let digest = format_parallel_digest(&tasks, &results, max_parallel);
+        let failed = results.iter().filter(|(result, _)| result.is_err()).count();
+        if failed > 0 {
+            logging::warn(&format!(
+                "[tool:subagent] {} of {} parallel subagent tasks failed for {} in session {}",
+                failed,
+                tasks.len(),
+                ctx.tool_call_id,
+                ctx.session_id
+            ));
+        }
+
+        let metadata: Vec<Value> = tasks
+            .iter()
+            .zip(&results)
+            .enumerate()
+            .map(|(index, (task, (result, elapsed)))| match result {
+                Ok(run) => json!({
+                    "index": index + 1,
+                    "description": task.description,
+                    "subagentType": task.subagent_type,
+                    "status": "ok",
+                    "sessionId": run.session_id,
+                    "model": run.model,
+                    "durationMs": elapsed.as_millis() as u64,
+                    "usage": {
+                        "inputTokens": run.usage.input_tokens,
+                        "outputTokens": run.usage.output_tokens,
+                        "cacheReadInputTokens": run.usage.cache_read_input_tokens,
+                    },
+                    "summary": run.summary,
+                }),
+                Err(err) => json!({
+                    "index": index + 1,
+                    "description": task.description,
+                    "subagentType": task.subagent_type,
+                    "status": "error",
+                    "error": format!("{err:#}"),
+                    "durationMs": elapsed.as_millis() as u64,
+                }),
+            })
+            .collect();
+
+        Ok(ToolOutput::new(digest)
+            .with_title(format!(
+                "{} subagents ({} ok, {} failed)",
+                tasks.len(),
+                tasks.len() - failed,
+                failed
+            ))
             .with_metadata(json!({
-                "summary": summary,
-                "sessionId": sub_session_id,
-                "model": resolved_model,
-                "outputMode": params.output_mode.as_str(),
+                "results": metadata,
+                "maxParallel": max_parallel,
             })))
     }
 }
 
-fn subagent_title(params: &SubagentInput) -> String {
-    format!(
-        "{} (@{} subagent)",
-        params.description, params.subagent_type
-    )
+/// Live per-task state, published as [`BatchProgress`] so the TUI shows every
+/// running child the same way it shows a running `batch` call.
+struct ParallelProgress {
+    session_id: String,
+    tool_call_id: String,
+    calls: Vec<ToolCall>,
+    states: Vec<Option<BatchSubcallState>>,
+    last_completed: Option<String>,
 }
 
-fn subagent_display_title(params: &SubagentInput, model: &str) -> String {
-    format!(
-        "{} ({} · {})",
-        params.description, params.subagent_type, model
-    )
+impl ParallelProgress {
+    fn new(ctx: &ToolContext, tasks: &[SubagentTask]) -> Self {
+        let calls = tasks
+            .iter()
+            .enumerate()
+            .map(|(index, task)| {
+                let input = json!({
+                    "description": task.description,
+                    "subagent_type": task.subagent_type,
+                });
+                ToolCall {
+                    id: format!("batch-{}-subagent", index + 1),
+                    name: "subagent".to_string(),
+                    intent: ToolCall::intent_from_input(&input),
+                    input,
+                    thought_signature: None,
+                }
+            })
+            .collect();
+        Self {
+            session_id: ctx.session_id.clone(),
+            tool_call_id: ctx.tool_call_id.clone(),
+            calls,
+            states: vec![None; tasks.len()],
+            last_completed: None,
+        }
+    }
+
+    fn start(&mut self, index: usize) {
+        self.states[index] = Some(BatchSubcallState::Running);
+    }
+
+    fn finish(&mut self, index: usize, ok: bool) {
+        self.states[index] = Some(if ok {
+            BatchSubcallState::Succeeded
+        } else {
+            BatchSubcallState::Failed
+        });
+        self.last_completed = self.calls[index]
+            .input
+            .get("description")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+    }
+
+    fn publish(&self) {
+        let running = self
+            .states
+            .iter()
+            .zip(&self.calls)
+            .filter(|(state, _)| matches!(state, Some(BatchSubcallState::Running)))
+            .map(|(_, call)| call.clone())
+            .collect();
+        // Queued tasks are left out until they get a slot.
+        let subcalls = self
+            .states
+            .iter()
+            .zip(&self.calls)
+            .enumerate()
+            .filter_map(|(index, (state, call))| {
+                Some(BatchSubcallProgress {
+                    index: index + 1,
+                    tool_call: call.clone(),
+                    state: state.clone()?,
+                })
+            })
+            .collect();
+        let completed = self
+            .states
+            .iter()
+            .filter(|state| {
+                matches!(
+                    state,
+                    Some(BatchSubcallState::Succeeded | BatchSubcallState::Failed)
+                )
+            })
+            .count();
+        Bus::global().publish(BusEvent::BatchProgress(BatchProgress {
+            session_id: self.session_id.clone(),
+            tool_call_id: self.tool_call_id.clone(),
+            total: self.calls.len(),
+            completed,
+            last_completed: self.last_completed.clone(),
+            running,
+            subcalls,
+        }));
+    }
+}
+
+/// Combined result for the parent: a status line, then each task's answer (or
+/// error) in input order, trimmed so all of them fit one tool result.
+fn format_parallel_digest(
+    tasks: &[SubagentTask],
+    results: &[(Result<SubagentRun>, std::time::Duration)],
+    max_parallel: usize,
+) -> String {
+    let failed = results.iter().filter(|(result, _)| result.is_err()).count();
+    let mut output = format!(
+        "Ran {} subagent tasks ({} at a time): {} succeeded, {} failed.\n\n",
+        tasks.len(),
+        max_parallel.min(tasks.len()),
+        tasks.len() - failed,
+        failed
+    );
+    let max_per_task = 50_000 / tasks.len().max(1);
+    for (index, (task, (result, elapsed))) in tasks.iter().zip(results).enumerate() {
+        match result {
+            Ok(run) => {
+                output.push_str(&format!(
+                    "--- [{}] {} ({} · {}) · ok · {:.1}s ---\n",
+                    index + 1,
+                    task.description,
+                    task.subagent_type,
+                    run.model,
+                    elapsed.as_secs_f64()
+                ));
+                let answer = run.final_text.trim_end();
+                if answer.len() > max_per_task {
+                    output.push_str(crate::util::truncate_str(answer, max_per_task));
+                    output.push_str("...\n(truncated)");
+                } else {
+                    output.push_str(answer);
+                }
+                output.push_str(&format!("\n(session_id: {})\n\n", run.session_id));
+            }
+            Err(err) => {
+                output.push_str(&format!(
+                    "--- [{}] {} ({}) · failed · {:.1}s ---\nError: {:#}\n\n",
+                    index + 1,
+                    task.description,
+                    task.subagent_type,
+                    elapsed.as_secs_f64(),
+                    err
+                ));
+            }
+        }
+    }
+    output.truncate(output.trim_end().len());
+    output
+}
+
+fn subagent_title(task: &SubagentTask) -> String {
+    format!("{} (@{} subagent)", task.description, task.subagent_type)
+}
+
+fn subagent_display_title(task: &SubagentTask, model: &str) -> String {
+    format!("{} ({} · {})", task.description, task.subagent_type, model)
 }
 
 impl SubagentOutputMode {
@@ -397,8 +827,8 @@ fn format_compact_subagent_history(messages: &[HistoryMessage]) -> String {
 #[cfg(test)]
 mod tests {
     use super::{
-        SubagentInput, SubagentOutputMode, format_compact_subagent_history, format_subagent_output,
-        subagent_display_title,
+        SubagentInput, SubagentOutputMode, SubagentRun, format_compact_subagent_history,
+        format_parallel_digest, format_subagent_output, subagent_display_title,
     };
     use crate::protocol::HistoryMessage;
 
@@ -409,17 +839,79 @@ mod tests {
             prompt: "prompt".to_string(),
             subagent_type: "general".to_string(),
             model: None,
+            profile: None,
+            working_dir: None,
             session_id: None,
             output_mode: SubagentOutputMode::Answer,
+            inherit_instructions: true,
+            tasks: Vec::new(),
             _command: None,
         };
 
         assert_eq!(
-            subagent_display_title(&params, "gpt-5.4"),
+            subagent_display_title(&params.single_task(), "gpt-5.4"),
             "Verify subagent model (general · gpt-5.4)"
         );
     }
 
+    #[test]
+    fn tasks_list_parses_with_default_type_and_model_override() {
+        let params: SubagentInput = serde_json::from_value(serde_json::json!({
+            "tasks": [
+                {"description": "crate a", "prompt": "inspect a"},
+                {"description": "crate b", "prompt": "inspect b", "subagent_type": "explore", "model": "fast", "inherit_instructions": false}
+            ]
+        }))
+        .expect("parse tasks");
+        assert_eq!(params.tasks.len(), 2);
+        assert_eq!(params.tasks[0].subagent_type, "general");
+        assert_eq!(params.tasks[1].model.as_deref(), Some("fast"));
+        assert!(params.tasks[0].inherit_instructions);
+        assert!(!params.tasks[1].inherit_instructions);
+        assert!(params.prompt.is_empty());
+    }
+
+    #[test]
+    fn parallel_digest_keeps_input_order_and_reports_failures() {
+        let params: SubagentInput = serde_json::from_value(serde_json::json!({
+            "tasks": [
+                {"description": "crate a", "prompt": "a"},
+                {"description": "crate b", "prompt": "b"}
+            ]
+        }))
+        .expect("parse tasks");
+        let results = vec![
+            (
+                Err(anyhow::anyhow!("provider overloaded")),
+                std::time::Duration::from_millis(1500),
+            ),
+            (
+                Ok(SubagentRun {
+                    final_text: "crate b looks fine\n".to_string(),
+                    session_id: "session_b".to_string(),
+                    model: "gpt-5.4".to_string(),
+                    summary: Vec::new(),
+                    usage: Default::default(),
+                    history: None,
+                    full_transcript: None,
+                }),
+                std::time::Duration::from_millis(2000),
+            ),
+        ];
+
+        let digest = format_parallel_digest(&params.tasks, &results, 4);
+        assert!(digest.starts_with("Ran 2 subagent tasks (2 at a time): 1 succeeded, 1 failed."));
+        let first = digest
+            .find("--- [1] crate a (general) · failed · 1.5s ---")
+            .unwrap();
+        let second = digest
+            .find("--- [2] crate b (general · gpt-5.4) · ok · 2.0s ---")
+            .unwrap();
+        assert!(first < second);
+        assert!(digest.contains("Error: provider overloaded"));
+        assert!(digest.ends_with("crate b looks fine\n(session_id: session_b)"));
+    }
+
     #[test]
     fn resolve_model_prefers_explicit_then_existing_then_parent_then_provider() {
         assert_eq!(
@@ -473,6 +965,7 @@ mod tests {
             content: "I will inspect it.".to_string(),
             tool_calls: Some(vec!["read".to_string()]),
             tool_data: None,
+            usage: None,
         }];
         let output = format_subagent_output(
             "final answer",
@@ -169,6 +169,7 @@ async fn test_batch_resolves_oauth_names() {
         message_id: "test".to_string(),
         tool_call_id: "test".to_string(),
         working_dir: Some(temp_dir),
+        workspace_roots: Vec::new(),
         stdin_request_tx: None,
         graceful_shutdown_signal: None,
         execution_mode: ToolExecutionMode::Direct,
@@ -186,13 +187,20 @@ async fn registry_execute_enforces_session_tool_policy_after_alias_resolution()
     let registry = Registry::new(provider).await;
     let temp_dir = std::env::temp_dir();
     let session_id = "test-policy-deny";
-    set_session_tool_policy(session_id, None, HashSet::from(["bash".to_string()]));
+    set_session_tool_policy(
+        session_id,
+        None,
+        HashSet::from(["bash".to_string()]),
+        false,
+        false,
+    );
 
     let ctx = ToolContext {
         session_id: session_id.to_string(),
         message_id: "test".to_string(),
         tool_call_id: "test".to_string(),
         working_dir: Some(temp_dir.clone()),
+        workspace_roots: Vec::new(),
         stdin_request_tx: None,
         graceful_shutdown_signal: None,
         execution_mode: ToolExecutionMode::Direct,
@@ -243,6 +251,7 @@ async fn registry_execute_pre_tool_hook_blocks_and_allows() {
         message_id: "test".to_string(),
         tool_call_id: "test".to_string(),
         working_dir: Some(std::env::temp_dir()),
+        workspace_roots: Vec::new(),
         stdin_request_tx: None,
         graceful_shutdown_signal: None,
         execution_mode: ToolExecutionMode::Direct,
@@ -554,6 +563,7 @@ async fn unknown_tool_error_lists_available_tools_and_suggestions() {
         message_id: "test".to_string(),
         tool_call_id: "test".to_string(),
         working_dir: None,
+        workspace_roots: Vec::new(),
         stdin_request_tx: None,
         graceful_shutdown_signal: None,
         execution_mode: ToolExecutionMode::Direct,
@@ -603,3 +613,47 @@ async fn gemini_build_tools_from_registry_definitions_omits_const_keywords() {
         "const"
     ));
 }
+
+#[test]
+fn tool_context_resolves_paths_across_workspace_roots() {
+    let api = tempfile::tempdir().expect("api root");
+    let web_parent = tempfile::tempdir().expect("web parent");
+    let web = web_parent.path().join("web");
+    std::fs::create_dir_all(web.join("src")).expect("mkdir web/src");
+    std::fs::write(web.join("src/App.tsx"), "export {}\n").expect("write App.tsx");
+    std::fs::write(api.path().join("Cargo.toml"), "[package]\n").expect("write Cargo.toml");
+
+    let ctx = ToolContext {
+        session_id: "test".to_string(),
+        message_id: "test".to_string(),
+        tool_call_id: "test".to_string(),
+        working_dir: Some(api.path().to_path_buf()),
+        workspace_roots: vec![web.clone()],
+        stdin_request_tx: None,
+        graceful_shutdown_signal: None,
+        execution_mode: ToolExecutionMode::Direct,
+    };
+
+    assert_eq!(
+        ctx.resolve_path(std::path::Path::new("web:src/App.tsx")),
+        web.join("src/App.tsx")
+    );
+    assert_eq!(
+        ctx.resolve_path(std::path::Path::new("src/App.tsx")),
+        web.join("src/App.tsx"),
+        "a path only present under one extra root should resolve there"
+    );
+    assert_eq!(
+        ctx.resolve_path(std::path::Path::new("Cargo.toml")),
+        api.path().join("Cargo.toml")
+    );
+    assert_eq!(
+        ctx.resolve_path(std::path::Path::new("new_file.rs")),
+        api.path().join("new_file.rs"),
+        "new files default to the primary working directory"
+    );
+    assert_eq!(
+        ctx.search_roots(),
+        vec![api.path().to_path_buf(), web.clone()]
+    );
+}
@@ -1,6 +1,6 @@
 use super::{Tool, ToolContext, ToolOutput};
 use crate::bus::{Bus, BusEvent, TodoEvent};
-use crate::todo::{TodoItem, load_todos, save_todos};
+use crate::todo::{TodoItem, load_todos, save_session_todos_in_project, save_todos};
 use anyhow::Result;
 use async_trait::async_trait;
 use serde::Deserialize;
@@ -164,7 +164,13 @@ impl Tool for TodoTool {
         };
         match params.todos {
             Some(todos) => {
-                save_todos(&ctx.session_id, &todos)?;
+                match ctx.working_dir.as_deref() {
+                    Some(project_dir) => {
+                        save_session_todos_in_project(project_dir, &ctx.session_id, &todos)?;
+                        crate::todo_sync::sync_configured(project_dir);
+                    }
+                    None => save_todos(&ctx.session_id, &todos)?,
+                }
 
                 Bus::global().publish(BusEvent::TodoUpdated(TodoEvent {
                     session_id: ctx.session_id.clone(),
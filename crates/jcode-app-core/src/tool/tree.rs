@@ -0,0 +1,380 @@
+use super::{Tool, ToolContext, ToolOutput};
+use crate::file_index::{self, FileIndex};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+const DEFAULT_MAX_DEPTH: usize = 3;
+const DEFAULT_MAX_ENTRIES: usize = 200;
+const MAX_ENTRIES_LIMIT: usize = 2000;
+/// Rough output budget (~4k tokens) regardless of `max_entries`.
+const MAX_OUTPUT_CHARS: usize = 16_000;
+/// Entries shown per directory before the rest are summarised.
+const MAX_ENTRIES_PER_DIR: usize = 50;
+/// Files modified within this window are marked.
+const RECENT_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+
+pub struct TreeTool;
+
+impl TreeTool {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[derive(Deserialize)]
+struct TreeInput {
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default)]
+    max_depth: Option<usize>,
+    #[serde(default)]
+    max_entries: Option<usize>,
+    #[serde(default)]
+    no_ignore: Option<bool>,
+}
+
+#[async_trait]
+impl Tool for TreeTool {
+    fn name(&self) -> &str {
+        "tree"
+    }
+
+    fn description(&self) -> &str {
+        "Show a directory tree with file counts, sizes and recently modified files. Respects .gitignore and .jcodeignore."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "intent": super::intent_schema_property(),
+                "path": {
+                    "type": "string",
+                    "description": "Directory path."
+                },
+                "max_depth": {
+                    "type": "integer",
+                    "description": "Levels to expand. Default 3."
+                },
+                "max_entries": {
+                    "type": "integer",
+                    "description": "Entries to show. Default 200."
+                },
+                "no_ignore": {
+                    "type": "boolean",
+                    "description": "Include gitignored/jcodeignored files."
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, input: Value, ctx: ToolContext) -> Result<ToolOutput> {
+        let params: TreeInput = serde_json::from_value(input)?;
+
+        let base_path = params.path.clone().unwrap_or_else(|| ".".to_string());
+        let base = ctx.resolve_path(Path::new(&base_path));
+
+        if !base.exists() {
+            return Err(anyhow::anyhow!("Directory not found: {}", base_path));
+        }
+
+        if !base.is_dir() {
+            return Err(anyhow::anyhow!("Not a directory: {}", base_path));
+        }
+
+        let options = TreeOptions {
+            max_depth: params.max_depth.unwrap_or(DEFAULT_MAX_DEPTH).max(1),
+            max_entries: params
+                .max_entries
+                .unwrap_or(DEFAULT_MAX_ENTRIES)
+                .clamp(1, MAX_ENTRIES_LIMIT),
+            max_chars: MAX_OUTPUT_CHARS,
+            now: SystemTime::now(),
+        };
+        let respect_ignore = !params.no_ignore.unwrap_or(false);
+
+        // Index the working directory when the target lies inside it, so
+        // repeated calls on different subdirectories share one walk.
+        let index_root = ctx
+            .working_dir
+            .clone()
+            .filter(|dir| base.starts_with(dir))
+            .unwrap_or_else(|| base.clone());
+        let prefix = base
+            .strip_prefix(&index_root)
+            .map(|rel| rel.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_default();
+
+        let tree = tokio::task::spawn_blocking(move || {
+            let index = file_index::get(&index_root, respect_ignore);
+            let tree = Tree::from_index(&index, &prefix);
+            // An explicitly requested directory is listed even when the
+            // working directory's ignore rules hide it.
+            if tree.nodes.len() == 1 && !prefix.is_empty() {
+                return Tree::from_index(&file_index::get(&base, respect_ignore), "");
+            }
+            tree
+        })
+        .await?;
+
+        let rendered = tree.render(&base_path, &options);
+        let mut output = rendered.text;
+        output.push_str(&format!(
+            "\n{} of {} entries shown ({} files, {} directories)",
+            rendered.shown,
+            tree.nodes.len() - 1,
+            tree.nodes[0].file_count,
+            tree.nodes.len() - 1 - tree.nodes[0].file_count,
+        ));
+        if tree.index_truncated {
+            output.push_str(&format!(
+                "\nIndex stopped at {} entries; counts are incomplete.",
+                file_index::MAX_INDEX_ENTRIES
+            ));
+        }
+        if respect_ignore {
+            output.push_str(
+                "\nIgnored paths (.gitignore, .jcodeignore) are hidden; pass no_ignore: true to include them.",
+            );
+        }
+
+        Ok(ToolOutput::new(output)
+            .with_title(format!("tree {}", base_path))
+            .with_metadata(json!({
+                "shown": rendered.shown,
+                "total": tree.nodes.len() - 1,
+                "files": tree.nodes[0].file_count,
+                "size": tree.nodes[0].total_size,
+                "omitted_dirs": rendered.omitted_dirs,
+                "index_truncated": tree.index_truncated,
+                "respects_ignore": respect_ignore,
+            })))
+    }
+}
+
+struct TreeOptions {
+    max_depth: usize,
+    max_entries: usize,
+    max_chars: usize,
+    now: SystemTime,
+}
+
+struct Node {
+    name: String,
+    is_dir: bool,
+    modified: Option<SystemTime>,
+    children: Vec<usize>,
+    /// Files beneath this node, recursively (1 for a file).
+    file_count: usize,
+    /// Bytes beneath this node, recursively.
+    total_size: u64,
+}
+
+/// The part of a [`FileIndex`] under one directory; node 0 is that directory.
+struct Tree {
+    nodes: Vec<Node>,
+    index_truncated: bool,
+}
+
+struct RenderedTree {
+    text: String,
+    shown: usize,
+    omitted_dirs: usize,
+}
+
+impl Tree {
+    fn from_index(index: &FileIndex, prefix: &str) -> Self {
+        let mut nodes = vec![Node {
+            name: String::new(),
+            is_dir: true,
+            modified: None,
+            children: Vec::new(),
+            file_count: 0,
+            total_size: 0,
+        }];
+        let mut parents = vec![0];
+        let mut by_path: HashMap<&str, usize> = HashMap::new();
+
+        for entry in &index.entries {
+            let rel = if prefix.is_empty() {
+                entry.path.as_str()
+            } else {
+                match entry
+                    .path
+                    .strip_prefix(prefix)
+                    .and_then(|rest| rest.strip_prefix('/'))
+                {
+                    Some(rel) => rel,
+                    None => continue,
+                }
+            };
+            // Entries are depth first, so a parent is always indexed first.
+            let parent = match rel.rsplit_once('/') {
+                Some((dir, _)) => match by_path.get(dir) {
+                    Some(&parent) => parent,
+                    None => continue,
+                },
+                None => 0,
+            };
+            let id = nodes.len();
+            nodes.push(Node {
+                name: entry.name().to_string(),
+                is_dir: entry.is_dir,
+                modified: entry.modified,
+                children: Vec::new(),
+                file_count: usize::from(!entry.is_dir),
+                total_size: entry.size,
+            });
+            parents.push(parent);
+            nodes[parent].children.push(id);
+            if entry.is_dir {
+                by_path.insert(rel, id);
+            }
+        }
+
+        // Children always follow their parent, so one reverse pass rolls the
+        // totals up.
+        for id in (1..nodes.len()).rev() {
+            let (file_count, total_size) = (nodes[id].file_count, nodes[id].total_size);
+            let parent = &mut nodes[parents[id]];
+            parent.file_count += file_count;
+            parent.total_size += total_size;
+        }
+
+        Self {
+            nodes,
+            index_truncated: index.truncated,
+        }
+    }
+
+    /// Pick entries breadth first, so every level near the top gets space
+    /// before any deep directory, then render the picks depth first.
+    fn render(&self, base_path: &str, options: &TreeOptions) -> RenderedTree {
+        let mut shown = vec![false; self.nodes.len()];
+        let mut shown_count = 0;
+        let mut chars = 0;
+        let mut queue = VecDeque::from([(0usize, 0usize)]);
+        'select: while let Some((dir, depth)) = queue.pop_front() {
+            for &child in self.nodes[dir].children.iter().take(MAX_ENTRIES_PER_DIR) {
+                let line_chars = (depth + 1) * 2 + self.line(child, options).len() + 1;
+                if shown_count >= options.max_entries || chars + line_chars > options.max_chars {
+                    break 'select;
+                }
+                shown[child] = true;
+                shown_count += 1;
+                chars += line_chars;
+                if self.nodes[child].is_dir && depth + 1 < options.max_depth {
+                    queue.push_back((child, depth + 1));
+                }
+            }
+        }
+
+        let root = &self.nodes[0];
+        let mut text = format!(
+            "{}/  ({})\n",
+            base_path.trim_end_matches('/'),
+            dir_summary(root)
+        );
+        let mut omitted_dirs = 0;
+        self.render_children(0, 1, options, &shown, &mut text, &mut omitted_dirs);
+        RenderedTree {
+            text,
+            shown: shown_count,
+            omitted_dirs,
+        }
+    }
+
+    fn render_children(
+        &self,
+        dir: usize,
+        depth: usize,
+        options: &TreeOptions,
+        shown: &[bool],
+        out: &mut String,
+        omitted_dirs: &mut usize,
+    ) {
+        let indent = "  ".repeat(depth);
+        let children = &self.nodes[dir].children;
+        for &child in children.iter().filter(|&&child| shown[child]) {
+            out.push_str(&indent);
+            out.push_str(&self.line(child, options));
+            out.push('\n');
+            if self.nodes[child].is_dir && depth < options.max_depth {
+                self.render_children(child, depth + 1, options, shown, out, omitted_dirs);
+            }
+        }
+
+        // Directories at max_depth are never expanded and are summarised by
+        // their annotation, so a marker here always means the budget ran out.
+        let omitted = children.iter().filter(|&&child| !shown[child]).count();
+        if omitted > 0 {
+            *omitted_dirs += 1;
+            out.push_str(&format!(
+                "{}… {} {} omitted\n",
+                indent,
+                omitted,
+                if omitted == 1 { "entry" } else { "entries" }
+            ));
+        }
+    }
+
+    fn line(&self, id: usize, options: &TreeOptions) -> String {
+        let node = &self.nodes[id];
+        if node.is_dir {
+            return format!("{}/  ({})", node.name, dir_summary(node));
+        }
+        match node
+            .modified
+            .and_then(|modified| options.now.duration_since(modified).ok())
+            .filter(|age| *age < RECENT_WINDOW)
+        {
+            Some(age) => format!("{}  [modified {} ago]", node.name, format_age(age)),
+            None => node.name.clone(),
+        }
+    }
+}
+
+fn dir_summary(node: &Node) -> String {
+    format!(
+        "{} {}, {}",
+        node.file_count,
+        if node.file_count == 1 {
+            "file"
+        } else {
+            "files"
+        },
+        format_size(node.total_size)
+    )
+}
+
+fn format_size(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{} bytes", bytes)
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else if bytes < 1024 * 1024 * 1024 {
+        format!("{:.1} MB", bytes as f64 / 1024.0 / 1024.0)
+    } else {
+        format!("{:.1} GB", bytes as f64 / 1024.0 / 1024.0 / 1024.0)
+    }
+}
+
+fn format_age(age: Duration) -> String {
+    let secs = age.as_secs();
+    if secs < 60 {
+        "<1m".to_string()
+    } else if secs < 60 * 60 {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{}h", secs / 3600)
+    }
+}
+
+#[cfg(test)]
+#[path = "tree_tests.rs"]
+mod tests;
@@ -0,0 +1,124 @@
+use super::*;
+use std::fs;
+
+fn test_ctx(root: &Path) -> ToolContext {
+    ToolContext {
+        session_id: "test".to_string(),
+        message_id: "test".to_string(),
+        tool_call_id: "test".to_string(),
+        working_dir: Some(root.to_path_buf()),
+        workspace_roots: Vec::new(),
+        stdin_request_tx: None,
+        graceful_shutdown_signal: None,
+        execution_mode: super::super::ToolExecutionMode::Direct,
+    }
+}
+
+fn options(max_depth: usize, max_entries: usize) -> TreeOptions {
+    TreeOptions {
+        max_depth,
+        max_entries,
+        max_chars: MAX_OUTPUT_CHARS,
+        now: SystemTime::now(),
+    }
+}
+
+#[tokio::test]
+async fn tree_respects_ignore_files_and_annotates_directories() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path();
+    fs::create_dir_all(root.join("src/nested")).expect("mkdir");
+    fs::create_dir_all(root.join("node_modules/pkg")).expect("mkdir");
+    fs::create_dir_all(root.join("scratch")).expect("mkdir");
+    fs::write(root.join(".gitignore"), "node_modules/\n").expect("write");
+    fs::write(root.join(".jcodeignore"), "scratch\n").expect("write");
+    fs::write(root.join("src/lib.rs"), "a".repeat(2048)).expect("write");
+    fs::write(root.join("src/nested/mod.rs"), "b".repeat(1024)).expect("write");
+    fs::write(root.join("node_modules/pkg/index.js"), "x").expect("write");
+    fs::write(root.join("scratch/notes.md"), "x").expect("write");
+
+    let output = TreeTool::new()
+        .execute(json!({}), test_ctx(root))
+        .await
+        .expect("tree")
+        .output;
+
+    assert!(output.contains("src/  (2 files, 3.0 KB)"), "{output}");
+    assert!(
+        output.contains("    mod.rs  [modified <1m ago]"),
+        "{output}"
+    );
+    assert!(!output.contains("node_modules"), "{output}");
+    assert!(!output.contains("scratch"), "{output}");
+    assert!(output.contains("pass no_ignore: true"), "{output}");
+
+    let unfiltered = TreeTool::new()
+        .execute(json!({"no_ignore": true}), test_ctx(root))
+        .await
+        .expect("tree")
+        .output;
+    assert!(
+        unfiltered.contains("node_modules/  (1 file, 1 bytes)"),
+        "{unfiltered}"
+    );
+
+    let ignored_dir = TreeTool::new()
+        .execute(json!({"path": "node_modules"}), test_ctx(root))
+        .await
+        .expect("tree")
+        .output;
+    assert!(ignored_dir.contains("index.js"), "{ignored_dir}");
+}
+
+#[test]
+fn render_marks_omitted_entries_per_directory() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path();
+    fs::create_dir_all(root.join("a")).expect("mkdir");
+    fs::create_dir_all(root.join("b/deep")).expect("mkdir");
+    for i in 0..5 {
+        fs::write(root.join(format!("a/file{i}.txt")), "x").expect("write");
+    }
+    fs::write(root.join("b/deep/leaf.txt"), "x").expect("write");
+
+    let tree = Tree::from_index(&FileIndex::build(root, true), "");
+    assert_eq!(tree.nodes[0].file_count, 6);
+
+    let rendered = tree.render(".", &options(3, 5));
+    assert!(
+        rendered.text.contains("a/  (5 files, 5 bytes)"),
+        "{}",
+        rendered.text
+    );
+    assert!(rendered.text.contains("  file2.txt"), "{}", rendered.text);
+    assert!(
+        rendered.text.contains("    … 2 entries omitted"),
+        "{}",
+        rendered.text
+    );
+    assert_eq!(rendered.shown, 5);
+
+    // Directories at max_depth keep their annotation but are not expanded.
+    let shallow = tree.render(".", &options(1, 100));
+    assert!(
+        shallow.text.contains("b/  (1 file, 1 bytes)"),
+        "{}",
+        shallow.text
+    );
+    assert!(!shallow.text.contains("deep"), "{}", shallow.text);
+    assert!(!shallow.text.contains("omitted"), "{}", shallow.text);
+}
+
+#[test]
+fn tree_from_index_scopes_to_subdirectory() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path();
+    fs::create_dir_all(root.join("crates/core/src")).expect("mkdir");
+    fs::write(root.join("crates/core/src/lib.rs"), "x").expect("write");
+    fs::write(root.join("README.md"), "x").expect("write");
+
+    let tree = Tree::from_index(&FileIndex::build(root, true), "crates/core");
+    let names: Vec<&str> = tree.nodes.iter().map(|node| node.name.as_str()).collect();
+    assert_eq!(names, vec!["", "src", "lib.rs"]);
+    assert_eq!(tree.nodes[0].file_count, 1);
+}
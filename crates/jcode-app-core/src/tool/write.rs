@@ -76,6 +76,13 @@ impl Tool for WriteTool {
 
         // Write the file
         tokio::fs::write(&path, &params.content).await?;
+        crate::provenance::record_tool_change(
+            &ctx,
+            "write",
+            &path,
+            old_content.as_deref().unwrap_or_default(),
+            &params.content,
+        );
 
         let _new_len = params.content.len();
         let line_count = params.content.lines().count();
@@ -8,7 +8,8 @@ use jcode_update_core::{
 };
 pub use jcode_update_core::{
     DownloadProgress, GIT_PULL_DIVERGED_SUMMARY, GitHubAsset, GitHubRelease, PreparedUpdate,
-    UpdateCheckResult, UpdateEstimate, format_download_progress_bar, summary_is_divergence,
+    UpdateCheckResult, UpdateEstimate, format_bytes, format_download_progress_bar,
+    summary_is_divergence,
 };
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -160,12 +161,20 @@ fn record_source_update_duration(duration: Duration) {
     }
 }
 
+/// Whether this install updates from GitHub release assets rather than git.
+/// Release builds always do; so do source builds with no jcode checkout to pull
+/// from (Homebrew, `cargo install`). Repo and self-dev installs keep the git
+/// flow.
+pub fn uses_release_updates() -> bool {
+    is_release_build() || build::get_repo_dir().is_none()
+}
+
 pub fn should_auto_update() -> bool {
     if std::env::var("JCODE_NO_AUTO_UPDATE").is_ok() {
         return false;
     }
 
-    if !is_release_build() {
+    if !uses_release_updates() {
         return false;
     }
 
@@ -219,6 +228,7 @@ pub fn fetch_latest_release_blocking() -> Result<GitHubRelease> {
     );
 
     let client = reqwest::blocking::Client::builder()
+        .dns_resolver(jcode_provider_core::egress::resolver())
         .timeout(UPDATE_CHECK_TIMEOUT)
         .user_agent("jcode-updater")
         .build()?;
@@ -241,9 +251,70 @@ pub fn fetch_latest_release_blocking() -> Result<GitHubRelease> {
     Ok(release)
 }
 
+/// The newest nightly (prerelease) that ships an asset for this platform.
+fn fetch_latest_nightly_release_blocking() -> Result<Option<GitHubRelease>> {
+    let url = format!(
+        "https://api.github.com/repos/{}/releases?per_page=20",
+        GITHUB_REPO
+    );
+
+    let client = reqwest::blocking::Client::builder()
+        .dns_resolver(jcode_provider_core::egress::resolver())
+        .timeout(UPDATE_CHECK_TIMEOUT)
+        .user_agent("jcode-updater")
+        .build()?;
+
+    let response = client
+        .get(&url)
+        .send()
+        .context("Failed to fetch nightly releases")?;
+    if !response.status().is_success() {
+        anyhow::bail!("GitHub API error: {}", response.status());
+    }
+
+    let releases: Vec<GitHubRelease> = response.json().context("Failed to parse release list")?;
+    Ok(releases
+        .into_iter()
+        .find(|release| release.prerelease && platform_asset(release).is_ok()))
+}
+
+/// Whether `release` is a nightly this binary is not already running. Nightly
+/// tags carry no semver, so compare against the build's commit and the last
+/// installed release instead.
+fn nightly_release_is_new(release: &GitHubRelease, current_short: &str) -> bool {
+    if release.tag_name.contains(current_short)
+        || release.target_commitish.starts_with(current_short)
+    {
+        return false;
+    }
+    let metadata = UpdateMetadata::load().unwrap_or_default();
+    metadata.installed_version.as_deref() != Some(release.tag_name.as_str())
+}
+
+fn latest_new_nightly_release(current_short: &str) -> Option<GitHubRelease> {
+    match fetch_latest_nightly_release_blocking() {
+        Ok(Some(release)) if nightly_release_is_new(&release, current_short) => {
+            crate::logging::info(&format!(
+                "Main channel: nightly release {} available",
+                release.tag_name
+            ));
+            Some(release)
+        }
+        Ok(_) => None,
+        Err(error) => {
+            crate::logging::warn(&format!(
+                "Main channel: nightly release lookup failed: {}",
+                error
+            ));
+            None
+        }
+    }
+}
+
 fn latest_main_sha_blocking() -> Result<String> {
     let url = format!("https://api.github.com/repos/{}/commits/main", GITHUB_REPO);
     let client = reqwest::blocking::Client::builder()
+        .dns_resolver(jcode_provider_core::egress::resolver())
         .timeout(UPDATE_CHECK_TIMEOUT)
         .user_agent("jcode-updater")
         .build()?;
@@ -278,18 +349,19 @@ fn checksum_asset(release: &GitHubRelease) -> Option<&GitHubAsset> {
     release.assets.iter().find(|a| a.name == "SHA256SUMS")
 }
 
-fn verify_asset_checksum_if_available(
+/// Check the downloaded asset against the release's published `SHA256SUMS`.
+/// A release without checksums is refused rather than installed unverified.
+fn verify_asset_checksum(
     client: &reqwest::blocking::Client,
     release: &GitHubRelease,
     asset: &GitHubAsset,
     bytes: &[u8],
 ) -> Result<()> {
     let Some(checksum_asset) = checksum_asset(release) else {
-        crate::logging::info(&format!(
-            "Release {} does not include SHA256SUMS; skipping checksum verification",
+        anyhow::bail!(
+            "Release {} does not publish SHA256SUMS; refusing to install an unverified binary",
             release.tag_name
-        ));
-        return Ok(());
+        );
     };
 
     let response = client
@@ -311,8 +383,9 @@ fn synthetic_main_release(latest_sha: &str) -> GitHubRelease {
         _name: Some(format!("Built from main ({})", latest_sha)),
         _html_url: format!("https://github.com/{}/commit/{}", GITHUB_REPO, latest_sha),
         _published_at: None,
+        prerelease: false,
         assets: vec![],
-        _target_commitish: latest_sha.to_string(),
+        target_commitish: latest_sha.to_string(),
     }
 }
 
@@ -365,10 +438,14 @@ fn prepare_stable_update_blocking() -> Result<PreparedUpdate> {
         });
     }
 
+    Ok(prepare_release_update(current_version, release))
+}
+
+fn prepare_release_update(current_version: &str, release: GitHubRelease) -> PreparedUpdate {
     let Ok(asset) = platform_asset(&release) else {
-        return Ok(PreparedUpdate::None {
+        return PreparedUpdate::None {
             current: current_version.to_string(),
-        });
+        };
     };
     let metadata = UpdateMetadata::load().unwrap_or_default();
     let duration = estimate_release_update_duration(asset._size, metadata.last_release_update_secs);
@@ -386,10 +463,10 @@ fn prepare_stable_update_blocking() -> Result<PreparedUpdate> {
         }
     );
 
-    Ok(PreparedUpdate::Stable {
+    PreparedUpdate::Stable {
         release,
         estimate: update_estimate(summary, duration),
-    })
+    }
 }
 
 fn prepare_main_update_blocking() -> Result<PreparedUpdate> {
@@ -426,6 +503,10 @@ fn prepare_main_update_blocking() -> Result<PreparedUpdate> {
         current_short, latest_sha
     ));
 
+    if let Some(release) = latest_new_nightly_release(current_short) {
+        return Ok(prepare_release_update(jcode_build_meta::VERSION, release));
+    }
+
     if has_cargo() {
         let repo_dir = source_build_repo_dir()?;
         let repo_exists = repo_dir.join(".git").exists();
@@ -463,7 +544,7 @@ fn prepare_main_update_blocking() -> Result<PreparedUpdate> {
 }
 
 pub fn prepare_update_blocking() -> Result<PreparedUpdate> {
-    let channel = crate::config::config().features.update_channel;
+    let channel = crate::config::config().update_channel();
     match channel {
         crate::config::UpdateChannel::Main => prepare_main_update_blocking(),
         crate::config::UpdateChannel::Stable => prepare_stable_update_blocking(),
@@ -566,7 +647,7 @@ pub fn spawn_background_session_update(session_id: String) {
 }
 
 pub fn check_for_update_blocking() -> Result<Option<GitHubRelease>> {
-    let channel = crate::config::config().features.update_channel;
+    let channel = crate::config::config().update_channel();
     match channel {
         crate::config::UpdateChannel::Main => check_for_main_update_blocking(),
         crate::config::UpdateChannel::Stable => check_for_stable_update_blocking(),
@@ -600,7 +681,8 @@ fn check_for_stable_update_blocking() -> Result<Option<GitHubRelease>> {
 /// Check for updates on the main branch (cutting edge channel).
 /// Compares the current binary's git hash against the latest commit on main.
 /// If a new commit is found:
-///   - Tries to build from source if cargo is available
+///   - Uses the latest nightly prerelease when it ships this platform's asset
+///   - Otherwise tries to build from source if cargo is available
 ///   - Falls back to latest GitHub Release if not
 fn check_for_main_update_blocking() -> Result<Option<GitHubRelease>> {
     let current_hash = jcode_build_meta::GIT_HASH;
@@ -632,6 +714,11 @@ fn check_for_main_update_blocking() -> Result<Option<GitHubRelease>> {
         current_short, latest_sha
     ));
 
+    // Prefer a prebuilt, checksummed nightly over building locally
+    if let Some(release) = latest_new_nightly_release(current_short) {
+        return Ok(Some(release));
+    }
+
     // Try to build from source
     if has_cargo() {
         crate::logging::info("Main channel: cargo found, attempting build from source");
@@ -973,9 +1060,6 @@ pub fn download_and_install_blocking_with_progress(
 
     let download_url = asset.browser_download_url.clone();
 
-    let temp_dir = std::env::temp_dir();
-    let temp_path = temp_dir.join(format!("jcode-update-{}", std::process::id()));
-
     // The `timeout` here applies per request. Since each retry below is a
     // separate request, this acts as a *per-attempt* budget rather than a cap
     // on the whole asset: a slow-but-progressing download resumes via HTTP
@@ -983,6 +1067,7 @@ pub fn download_and_install_blocking_with_progress(
     // even when a single attempt would not finish in time. A genuinely hung
     // connection is still bounded by the per-attempt timeout.
     let client = reqwest::blocking::Client::builder()
+        .dns_resolver(jcode_provider_core::egress::resolver())
         .connect_timeout(DOWNLOAD_CONNECT_TIMEOUT)
         .timeout(DOWNLOAD_ATTEMPT_TIMEOUT)
         .user_agent("jcode-updater")
@@ -996,23 +1081,53 @@ pub fn download_and_install_blocking_with_progress(
     let (bytes, _total) =
         download_asset_with_resume(&client, &download_url, total_hint, &mut on_progress)?;
 
-    verify_asset_checksum_if_available(&client, release, asset, &bytes)?;
+    verify_asset_checksum(&client, release, asset, &bytes)?;
+
+    let version = release.tag_name.trim_start_matches('v');
+    let staging_dir = stage_release_asset(asset, version, &bytes)?;
+    if let Err(error) = self_check_binary(&staging_dir.join(build::binary_name())) {
+        let _ = fs::remove_dir_all(&staging_dir);
+        return Err(error.context(format!(
+            "{} failed its self-check; nothing was installed",
+            release.tag_name
+        )));
+    }
+    let versioned_path = install_staged_version(&staging_dir, version)?;
+    promote_release_version(version)?;
+
+    let mut metadata = UpdateMetadata::load().unwrap_or_default();
+    metadata.installed_version = Some(release.tag_name.clone());
+    metadata.installed_from = Some(asset.browser_download_url.clone());
+    metadata.last_check = SystemTime::now();
+    metadata.save()?;
+    record_release_update_duration(started.elapsed());
+
+    Ok(versioned_path)
+}
 
-    let mut installed_version_dir: Option<PathBuf> = None;
+/// Unpack a verified release asset into `builds/staging/`, laid out exactly as
+/// it will live under `builds/versions/<version>/`. Staging next to the
+/// versions directory keeps the final install a same-filesystem rename.
+fn stage_release_asset(asset: &GitHubAsset, version: &str, bytes: &[u8]) -> Result<PathBuf> {
+    let staging_dir =
+        build::builds_dir()?
+            .join("staging")
+            .join(format!("{}-{}", version, std::process::id()));
+    if staging_dir.exists() {
+        let _ = fs::remove_dir_all(&staging_dir);
+    }
+    fs::create_dir_all(&staging_dir).context("Failed to create update staging dir")?;
+
+    let binary = staging_dir.join(build::binary_name());
+    let mut staged_files = Vec::new();
     if asset.name.ends_with(".tar.gz") {
-        let cursor = std::io::Cursor::new(&bytes);
+        let cursor = std::io::Cursor::new(bytes);
         let gz = flate2::read::GzDecoder::new(cursor);
         let mut archive = tar::Archive::new(gz);
-        let extract_dir = temp_path.with_extension("extract");
-        if extract_dir.exists() {
-            let _ = fs::remove_dir_all(&extract_dir);
-        }
-        fs::create_dir_all(&extract_dir).context("Failed to create archive extraction dir")?;
-        let mut extracted_binary: Option<PathBuf> = None;
         for entry in archive.entries()? {
             let mut entry = entry?;
             let entry_path = entry.path()?.into_owned();
-            if entry_path.components().count() != 1 {
+            if entry_path.components().count() != 1 || !entry.header().entry_type().is_file() {
                 continue;
             }
             let file_name = entry_path
@@ -1022,77 +1137,81 @@ pub fn download_and_install_blocking_with_progress(
             if file_name.is_empty() || file_name.ends_with(".tar.gz") {
                 continue;
             }
-            let dest = extract_dir.join(&file_name);
-            entry.unpack(&dest)?;
-            if file_name.starts_with("jcode") && !file_name.ends_with(".bin") {
-                extracted_binary = Some(dest);
-            }
-        }
-        let Some(extracted_binary) = extracted_binary else {
-            anyhow::bail!("Could not find jcode binary inside tar.gz archive");
-        };
-        crate::platform::set_permissions_executable(&extracted_binary)?;
-
-        let version = release.tag_name.trim_start_matches('v');
-        let dest_dir = build::builds_dir()?.join("versions").join(version);
-        fs::create_dir_all(&dest_dir).context("Failed to create version install dir")?;
-        let mut installed_files = Vec::new();
-        for entry in fs::read_dir(&extract_dir).context("Failed to read extracted archive")? {
-            let entry = entry?;
-            if !entry.file_type()?.is_file() {
-                continue;
-            }
-            let name = entry.file_name();
-            let name_string = name.to_string_lossy();
-            let dest_name = if name_string == get_asset_name()
-                || name_string == format!("{}.exe", get_asset_name())
+            let dest_name = if file_name == get_asset_name()
+                || file_name == format!("{}.exe", get_asset_name())
             {
                 build::binary_name().to_string()
             } else {
-                name_string.to_string()
+                file_name
             };
-            let dest = dest_dir.join(dest_name);
-            if dest.exists() {
-                fs::remove_file(&dest)?;
-            }
-            fs::copy(entry.path(), &dest)
-                .with_context(|| format!("Failed to install {}", dest.display()))?;
-            if dest
-                .file_name()
-                .is_some_and(|name| name == build::binary_name())
-                || dest.extension().is_some_and(|ext| ext == "bin")
-            {
+            let dest = staging_dir.join(&dest_name);
+            entry
+                .unpack(&dest)
+                .with_context(|| format!("Failed to unpack {}", dest.display()))?;
+            if dest_name == build::binary_name() || dest_name.ends_with(".bin") {
                 crate::platform::set_permissions_executable(&dest)?;
             }
-            installed_files.push(dest);
+            staged_files.push(dest);
         }
-        // Give every installed file the same mtime. The wrapper script and the
-        // `.bin` payload otherwise land with whatever sub-second skew the copy
-        // loop produced, and any code comparing binary freshness by mtime then
-        // sees two "different age" files for one logical install.
-        let install_stamp = SystemTime::now();
-        for path in &installed_files {
-            if let Ok(file) = fs::File::options().write(true).open(path) {
-                let _ = file.set_modified(install_stamp);
-            }
+        if !binary.exists() {
+            let _ = fs::remove_dir_all(&staging_dir);
+            anyhow::bail!("Could not find jcode binary inside tar.gz archive");
         }
-        let _ = fs::remove_dir_all(&extract_dir);
-        installed_version_dir = Some(dest_dir.join(build::binary_name()));
     } else {
-        fs::write(&temp_path, &bytes).context("Failed to write temp file")?;
+        fs::write(&binary, bytes).context("Failed to write staged binary")?;
+        crate::platform::set_permissions_executable(&binary)?;
+        staged_files.push(binary);
+    }
+
+    // Give every installed file the same mtime. The wrapper script and the
+    // `.bin` payload otherwise land with whatever sub-second skew the unpack
+    // loop produced, and any code comparing binary freshness by mtime then
+    // sees two "different age" files for one logical install.
+    let install_stamp = SystemTime::now();
+    for path in &staged_files {
+        if let Ok(file) = fs::File::options().write(true).open(path) {
+            let _ = file.set_modified(install_stamp);
+        }
     }
+    Ok(staging_dir)
+}
 
-    let version = release.tag_name.trim_start_matches('v');
-    let mut metadata = UpdateMetadata::load().unwrap_or_default();
+/// Move a self-checked staging directory into `builds/versions/<version>/`.
+/// Any existing install of the same version is set aside first and put back
+/// if the rename fails, so the version directory is never left half-written.
+fn install_staged_version(staging_dir: &Path, version: &str) -> Result<PathBuf> {
+    let versions_dir = build::builds_dir()?.join("versions");
+    fs::create_dir_all(&versions_dir).context("Failed to create versions dir")?;
+    let dest_dir = versions_dir.join(version);
+    let replaced = versions_dir.join(format!(".{}-replaced-{}", version, std::process::id()));
+
+    let had_previous = dest_dir.exists();
+    if had_previous {
+        if replaced.exists() {
+            let _ = fs::remove_dir_all(&replaced);
+        }
+        fs::rename(&dest_dir, &replaced)
+            .with_context(|| format!("Failed to set aside {}", dest_dir.display()))?;
+    }
+    if let Err(error) = fs::rename(staging_dir, &dest_dir) {
+        if had_previous {
+            let _ = fs::rename(&replaced, &dest_dir);
+        }
+        let _ = fs::remove_dir_all(staging_dir);
+        return Err(error).with_context(|| format!("Failed to install {}", dest_dir.display()));
+    }
+    if had_previous {
+        let _ = fs::remove_dir_all(&replaced);
+    }
+    Ok(dest_dir.join(build::binary_name()))
+}
+
+/// Point stable, current and the launcher at an installed release, then run
+/// the launcher once more. If the promoted build cannot start, the previous
+/// stable build is restored so new launches keep working.
+fn promote_release_version(version: &str) -> Result<()> {
+    let previous = build::read_stable_version()?;
 
-    let versioned_path = if let Some(versioned_path) = installed_version_dir {
-        versioned_path
-    } else {
-        crate::platform::set_permissions_executable(&temp_path)?;
-        let versioned_path = build::install_binary_at_version(&temp_path, version)?;
-        let _ = fs::remove_file(&temp_path);
-        versioned_path
-    };
     if let Err(error) = build::advance_shared_server_if_tracking_stable(version) {
         crate::logging::warn(&format!(
             "update: failed to advance shared-server channel to {}: {}",
@@ -1101,15 +1220,49 @@ pub fn download_and_install_blocking_with_progress(
     }
     build::update_stable_symlink(version)?;
     build::update_current_symlink(version)?;
-    build::update_launcher_symlink_to_current()?;
+    let launcher = build::update_launcher_symlink_to_current()?;
 
-    metadata.installed_version = Some(release.tag_name.clone());
-    metadata.installed_from = Some(asset.browser_download_url.clone());
-    metadata.last_check = SystemTime::now();
-    metadata.save()?;
-    record_release_update_duration(started.elapsed());
+    let Err(error) = self_check_binary(&launcher) else {
+        return Ok(());
+    };
+    match previous.filter(|previous| previous != version) {
+        Some(previous) => {
+            build::rollback_stable(&previous).with_context(|| {
+                format!(
+                    "{} failed its self-check and restoring {} also failed",
+                    version, previous
+                )
+            })?;
+            Err(error.context(format!(
+                "{} failed its self-check after install; restored {}",
+                version, previous
+            )))
+        }
+        None => Err(error.context(format!(
+            "{} failed its self-check after install and there is no previous stable build to restore",
+            version
+        ))),
+    }
+}
 
-    Ok(versioned_path)
+/// Run `<binary> --version`. A build that cannot even report its version is
+/// never left as the one new launches start.
+fn self_check_binary(binary: &Path) -> Result<String> {
+    let output = std::process::Command::new(binary)
+        .arg("--version")
+        .env("JCODE_NO_AUTO_UPDATE", "1")
+        .output()
+        .with_context(|| format!("Failed to run {} --version", binary.display()))?;
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !output.status.success() || stdout.is_empty() {
+        anyhow::bail!(
+            "{} --version exited with {}: {}",
+            binary.display(),
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(stdout)
 }
 
 pub fn check_and_maybe_update(auto_install: bool) -> UpdateCheckResult {
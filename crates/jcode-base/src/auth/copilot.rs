@@ -671,7 +671,9 @@ pub async fn verify_copilot_credentials_live(client: &reqwest::Client) -> Result
 /// short-lived HTTP client. Useful for callers (e.g. the TUI crate) that do not
 /// depend on `reqwest` directly.
 pub async fn verify_copilot_credentials_live_default() -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = reqwest::Client::builder()
+        .dns_resolver(jcode_provider_core::egress::resolver())
+        .build()?;
     verify_copilot_credentials_live(&client).await
 }
 
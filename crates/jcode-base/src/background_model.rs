@@ -0,0 +1,141 @@
+//! Cheap model for background work.
+//!
+//! Compaction summaries, aside summaries, `/remember` condensing and sidecar
+//! calls that go through the live provider don't need the session's model.
+//! They run on `agents.background_model`, or the provider's cheap default, on
+//! a fork of the session provider. If that model can't be used the call is
+//! retried on the session model with a log note instead of failing. Usage is
+//! counted in the `/usage` "Background tasks" entry, and under the call's
+//! origin for the session it was made for.
+
+use crate::provider::Provider;
+use crate::usage::{OriginUsage, UsageOrigin};
+use anyhow::Result;
+
+/// `agents.background_model` value that keeps background work on the session
+/// model.
+pub const INHERIT: &str = "inherit";
+
+/// Built-in background model for a provider, keyed by `Provider::name()`.
+/// Providers without one run background work on the session model.
+pub fn default_for_provider(provider_name: &str) -> Option<&'static str> {
+    match provider_name.to_ascii_lowercase().as_str() {
+        "claude" | "anthropic" => Some("claude-haiku-4-5"),
+        "openai" | "copilot" => Some("gpt-5-mini"),
+        _ => None,
+    }
+}
+
+/// The model background work should switch to, or `None` to stay on the
+/// session model.
+pub fn resolve(provider: &dyn Provider) -> Option<String> {
+    let configured = crate::config::config().agents.background_model.clone();
+    resolve_for(configured.as_deref(), provider.name(), &provider.model())
+}
+
+fn resolve_for(
+    configured: Option<&str>,
+    provider_name: &str,
+    session_model: &str,
+) -> Option<String> {
+    let model = match configured.map(str::trim).filter(|model| !model.is_empty()) {
+        Some(model) if model.eq_ignore_ascii_case(INHERIT) => return None,
+        Some(model) => model.to_string(),
+        None => default_for_provider(provider_name)?.to_string(),
+    };
+    (model != session_model).then_some(model)
+}
+
+/// `Provider::complete_simple` for background work. `origin` names the work
+/// in log notes and usage counters; `session_id` is the session the work was
+/// done for, if any. Calls that stay on the session model by configuration are
+/// not counted as background usage; they bill like any other session request,
+/// but still count as one call of their origin.
+pub async fn complete_simple(
+    provider: &dyn Provider,
+    origin: &UsageOrigin,
+    session_id: Option<&str>,
+    prompt: &str,
+    system: &str,
+) -> Result<String> {
+    let fell_back = match resolve(provider) {
+        None => false,
+        Some(model) => match provider
+            .complete_simple_with_model(&model, prompt, system)
+            .await
+        {
+            Ok(completion) => {
+                crate::usage::record_background_usage(
+                    &completion.model,
+                    completion.input_tokens,
+                    completion.output_tokens,
+                    false,
+                );
+                record_origin(
+                    origin,
+                    session_id,
+                    OriginUsage::new(
+                        1,
+                        completion.input_tokens.unwrap_or(0),
+                        completion.output_tokens.unwrap_or(0),
+                    ),
+                );
+                return Ok(completion.text);
+            }
+            Err(err) => {
+                crate::logging::info(&format!(
+                    "[background] {}: {} unavailable ({}); using session model {}",
+                    origin.label(),
+                    model,
+                    err,
+                    provider.model()
+                ));
+                true
+            }
+        },
+    };
+    let text = provider.complete_simple(prompt, system).await?;
+    if fell_back {
+        crate::usage::record_background_usage(&provider.model(), None, None, true);
+    }
+    record_origin(origin, session_id, OriginUsage::new(1, 0, 0));
+    Ok(text)
+}
+
+fn record_origin(origin: &UsageOrigin, session_id: Option<&str>, usage: OriginUsage) {
+    crate::usage::record_origin_usage(origin, usage);
+    if let Some(session_id) = session_id {
+        crate::usage::attribute_to_session(session_id, origin, usage);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_configured_then_provider_default() {
+        assert_eq!(
+            resolve_for(None, "Claude", "claude-opus-4-6"),
+            Some("claude-haiku-4-5".to_string())
+        );
+        assert_eq!(
+            resolve_for(None, "copilot", "gpt-5.4"),
+            Some("gpt-5-mini".to_string())
+        );
+        assert_eq!(resolve_for(None, "openrouter", "some/model"), None);
+        assert_eq!(
+            resolve_for(Some(" gpt-5.4-mini "), "openai", "gpt-5.4"),
+            Some("gpt-5.4-mini".to_string())
+        );
+        assert_eq!(
+            resolve_for(Some("Inherit"), "claude", "claude-opus-4-6"),
+            None
+        );
+        assert_eq!(
+            resolve_for(None, "claude", "claude-haiku-4-5"),
+            None,
+            "already on the background model"
+        );
+    }
+}
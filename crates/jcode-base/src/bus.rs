@@ -1,3 +1,4 @@
+use crate::bus_tap::{BusStats, BusTap, TapRegistry};
 use crate::message::ToolCall;
 use crate::side_panel::SidePanelSnapshot;
 use crate::todo::TodoItem;
@@ -291,6 +292,42 @@ pub struct SwarmAwaitCompleted {
     pub wake: bool,
 }
 
+/// Draft for a `/remember` memory pin, condensed off the UI thread.
+#[derive(Clone, Debug)]
+pub struct MemoryPinDrafted {
+    pub session_id: String,
+    /// Transcript index of the pinned assistant message.
+    pub message_index: usize,
+    /// Condensed memory text, or why condensing failed.
+    pub result: std::result::Result<String, String>,
+}
+
+/// Summary drafted for a local-mode `/handoff`.
+#[derive(Clone, Debug)]
+pub struct HandoffDrafted {
+    pub session_id: String,
+    /// The drafted summary, or why drafting failed.
+    pub result: std::result::Result<String, String>,
+}
+
+/// Answers of a local-mode `/compare` run.
+#[derive(Clone, Debug)]
+pub struct CompareFinished {
+    pub session_id: String,
+    pub prompt: String,
+    pub runs: Vec<crate::protocol::CompareRun>,
+}
+
+/// Outcome of storing a `/remember` memory pin.
+#[derive(Clone, Debug)]
+pub struct MemoryPinSaved {
+    pub session_id: String,
+    /// `project` or `global`.
+    pub scope: String,
+    /// Id of the stored memory, or the storage error.
+    pub result: std::result::Result<String, String>,
+}
+
 /// Result of a `/productivity` report generation run.
 ///
 /// Carries already-rendered outputs (markdown + PNG bytes) so the TUI layer can
@@ -318,6 +355,37 @@ pub struct SidePanelUpdated {
     pub snapshot: SidePanelSnapshot,
 }
 
+/// Text the `clipboard` tool asked the session's client to copy. The client
+/// owns the terminal, so it can use OSC 52 when the user is on SSH.
+#[derive(Clone, Debug)]
+pub struct ClipboardWriteRequested {
+    pub session_id: String,
+    pub text: String,
+}
+
+/// The shared permission inbox changed: a request was queued, answered from
+/// any surface, or expired. Carries every entry still pending.
+#[derive(Clone, Debug)]
+pub struct PermissionInboxChanged {
+    pub entries: Vec<crate::protocol::PermissionInboxEntry>,
+}
+
+/// The server will move its sessions onto a newer binary at `at` (Unix ms).
+#[derive(Clone, Debug)]
+pub struct MigrationPending {
+    pub from_hash: String,
+    pub to_hash: String,
+    pub reason: String,
+    pub at: u64,
+}
+
+/// A session's pending turns changed: a message or soft interrupt was queued,
+/// injected, removed or reordered. Subscribers re-read the queue.
+#[derive(Clone, Debug)]
+pub struct TurnQueueChanged {
+    pub session_id: String,
+}
+
 #[derive(Clone, Debug)]
 pub enum UpdateStatus {
     Checking,
@@ -445,6 +513,22 @@ pub enum BusEvent {
     MermaidRenderCompleted,
     /// Productivity report finished generating off the UI thread
     ProductivityReportReady(ProductivityReportReady),
+    /// `/remember` condensed an assistant message into a memory draft
+    MemoryPinDrafted(MemoryPinDrafted),
+    /// `/remember` finished storing a memory
+    MemoryPinSaved(MemoryPinSaved),
+    /// A local-mode `/handoff` summary was drafted
+    HandoffDrafted(HandoffDrafted),
+    /// Every model of a local-mode `/compare` run answered
+    CompareFinished(CompareFinished),
+    /// The `clipboard` tool wants text placed on the user's clipboard
+    ClipboardWriteRequested(ClipboardWriteRequested),
+    /// Pending permission decisions changed
+    PermissionInboxChanged(PermissionInboxChanged),
+    /// Pending turns of a session changed
+    TurnQueueChanged(TurnQueueChanged),
+    /// A newer server binary was found; sessions migrate after a grace period
+    MigrationPending(MigrationPending),
 }
 
 pub struct Bus {
@@ -453,6 +537,8 @@ pub struct Bus {
     /// global static) so tests can exercise coalescing on a private bus
     /// without racing other tests that publish to the global bus.
     models_updated_state: std::sync::Arc<Mutex<ModelsUpdatedPublishState>>,
+    /// Per-kind counts and debug taps; see [`crate::bus_tap`].
+    taps: TapRegistry,
 }
 
 const MODELS_UPDATED_DEBOUNCE: Duration = Duration::from_millis(750);
@@ -497,6 +583,7 @@ impl Bus {
             models_updated_state: std::sync::Arc::new(Mutex::new(
                 ModelsUpdatedPublishState::default(),
             )),
+            taps: TapRegistry::default(),
         }
     }
 
@@ -511,9 +598,22 @@ impl Bus {
                 .unwrap_or_else(|poisoned| poisoned.into_inner());
             *latest = Some(status.clone());
         }
+        self.taps.record(&event);
         let _ = self.sender.send(event);
     }
 
+    /// A bounded live copy of everything published from now on, for debug
+    /// clients. A tap that falls behind drops events rather than slowing
+    /// publishers down.
+    pub fn tap(&self) -> BusTap {
+        self.taps.open()
+    }
+
+    pub fn stats(&self) -> BusStats {
+        self.taps
+            .stats(self.sender.len(), self.sender.receiver_count())
+    }
+
     pub fn latest_update_status(&self) -> Option<UpdateStatus> {
         latest_update_status()
             .lock()
@@ -566,6 +666,7 @@ impl Bus {
             // global bus and clear the wrong pending flag.
             let state = std::sync::Arc::clone(&self.models_updated_state);
             let sender = self.sender.clone();
+            let taps = self.taps.clone();
             handle.spawn(async move {
                 tokio::time::sleep(delay).await;
                 let mut state = state
@@ -574,6 +675,7 @@ impl Bus {
                 state.publish_pending = false;
                 state.last_published_at = Some(Instant::now());
                 drop(state);
+                taps.record(&BusEvent::ModelsUpdated);
                 let _ = sender.send(BusEvent::ModelsUpdated);
             });
             return;
@@ -0,0 +1,287 @@
+//! Debug taps on the [`Bus`](crate::bus::Bus): per-kind publish counts and
+//! live feeds behind `events:tail` / `events:stats` on the debug socket.
+//!
+//! Each tap has its own small queue filled with `try_send`. When a slow reader
+//! lets it fill up, further events are dropped and counted for that tap
+//! instead of holding up the publisher.
+
+use crate::bus::BusEvent;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc::{self, error::TrySendError};
+
+/// Events a tap holds before it starts dropping.
+pub const TAP_CAPACITY: usize = 512;
+/// Longest payload summary carried by a [`TappedEvent`].
+const SUMMARY_MAX_BYTES: usize = 240;
+
+/// One published event as seen by a tap.
+#[derive(Clone, Debug)]
+pub struct TappedEvent {
+    /// Position in the bus's publish order, starting at 1.
+    pub seq: u64,
+    pub kind: &'static str,
+    pub session_id: Option<String>,
+    /// The event's `Debug` form, truncated.
+    pub summary: String,
+}
+
+/// A live feed of bus events; see [`Bus::tap`](crate::bus::Bus::tap).
+pub struct BusTap {
+    rx: mpsc::Receiver<TappedEvent>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl BusTap {
+    pub async fn recv(&mut self) -> Option<TappedEvent> {
+        self.rx.recv().await
+    }
+
+    /// Events dropped because this tap was full since the last call.
+    pub fn take_dropped(&self) -> u64 {
+        self.dropped.swap(0, Ordering::Relaxed)
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct BusStats {
+    /// Events published since startup.
+    pub published: u64,
+    pub by_kind: BTreeMap<&'static str, u64>,
+    /// Events still queued in the broadcast channel for its slowest receiver.
+    pub queued: usize,
+    pub subscribers: usize,
+    pub taps: Vec<TapStats>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TapStats {
+    pub queued: usize,
+    pub capacity: usize,
+    pub dropped: u64,
+}
+
+struct TapSlot {
+    tx: mpsc::Sender<TappedEvent>,
+    dropped: Arc<AtomicU64>,
+    dropped_total: u64,
+}
+
+#[derive(Default)]
+struct TapState {
+    seq: u64,
+    by_kind: BTreeMap<&'static str, u64>,
+    taps: Vec<TapSlot>,
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct TapRegistry {
+    state: Arc<Mutex<TapState>>,
+}
+
+impl TapRegistry {
+    fn lock(&self) -> std::sync::MutexGuard<'_, TapState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Count `event` and hand it to every open tap.
+    pub(crate) fn record(&self, event: &BusEvent) {
+        let mut state = self.lock();
+        state.seq += 1;
+        let kind = event.kind();
+        *state.by_kind.entry(kind).or_insert(0) += 1;
+        if state.taps.is_empty() {
+            return;
+        }
+        let tapped = TappedEvent {
+            seq: state.seq,
+            kind,
+            session_id: event.session_id().map(str::to_string),
+            summary: summarize(event),
+        };
+        state
+            .taps
+            .retain_mut(|tap| match tap.tx.try_send(tapped.clone()) {
+                Ok(()) => true,
+                Err(TrySendError::Full(_)) => {
+                    tap.dropped.fetch_add(1, Ordering::Relaxed);
+                    tap.dropped_total += 1;
+                    true
+                }
+                Err(TrySendError::Closed(_)) => false,
+            });
+    }
+
+    pub(crate) fn open(&self) -> BusTap {
+        let (tx, rx) = mpsc::channel(TAP_CAPACITY);
+        let dropped = Arc::new(AtomicU64::new(0));
+        self.lock().taps.push(TapSlot {
+            tx,
+            dropped: Arc::clone(&dropped),
+            dropped_total: 0,
+        });
+        BusTap { rx, dropped }
+    }
+
+    pub(crate) fn stats(&self, queued: usize, subscribers: usize) -> BusStats {
+        let mut state = self.lock();
+        state.taps.retain(|tap| !tap.tx.is_closed());
+        BusStats {
+            published: state.seq,
+            by_kind: state.by_kind.clone(),
+            queued,
+            subscribers,
+            taps: state
+                .taps
+                .iter()
+                .map(|tap| TapStats {
+                    queued: tap.tx.max_capacity() - tap.tx.capacity(),
+                    capacity: tap.tx.max_capacity(),
+                    dropped: tap.dropped_total,
+                })
+                .collect(),
+        }
+    }
+}
+
+fn summarize(event: &BusEvent) -> String {
+    let summary = format!("{:?}", event);
+    if summary.len() <= SUMMARY_MAX_BYTES {
+        return summary;
+    }
+    format!(
+        "{}…",
+        crate::util::truncate_str(&summary, SUMMARY_MAX_BYTES)
+    )
+}
+
+impl BusEvent {
+    /// Stable snake_case name of the variant, used by debug taps and stats.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            BusEvent::ToolUpdated(_) => "tool_updated",
+            BusEvent::TodoUpdated(_) => "todo_updated",
+            BusEvent::SubagentStatus(_) => "subagent_status",
+            BusEvent::ManualToolCompleted(_) => "manual_tool_completed",
+            BusEvent::BatchProgress(_) => "batch_progress",
+            BusEvent::FileTouch(_) => "file_touch",
+            BusEvent::SwarmOutputTail(_) => "swarm_output_tail",
+            BusEvent::BackgroundTaskCompleted(_) => "background_task_completed",
+            BusEvent::BackgroundTaskProgress(_) => "background_task_progress",
+            BusEvent::SwarmAwaitCompleted(_) => "swarm_await_completed",
+            BusEvent::UsageReport(_) => "usage_report",
+            BusEvent::UsageReportProgress(_) => "usage_report_progress",
+            BusEvent::LoginCompleted(_) => "login_completed",
+            BusEvent::OnboardingModelValidated(_) => "onboarding_model_validated",
+            BusEvent::InputShellCompleted(_) => "input_shell_completed",
+            BusEvent::ClipboardPasteCompleted(_) => "clipboard_paste_completed",
+            BusEvent::ModelRefreshCompleted(_) => "model_refresh_completed",
+            BusEvent::UiActivity(_) => "ui_activity",
+            BusEvent::GitStatusCompleted(_) => "git_status_completed",
+            BusEvent::UpdateStatus(_) => "update_status",
+            BusEvent::SessionUpdateStatus(_) => "session_update_status",
+            BusEvent::DictationCompleted { .. } => "dictation_completed",
+            BusEvent::DictationFailed { .. } => "dictation_failed",
+            BusEvent::CompactionFinished => "compaction_finished",
+            BusEvent::ModelsUpdated => "models_updated",
+            BusEvent::ProviderModelActivated { .. } => "provider_model_activated",
+            BusEvent::SidePanelUpdated(_) => "side_panel_updated",
+            BusEvent::MermaidRenderCompleted => "mermaid_render_completed",
+            BusEvent::ProductivityReportReady(_) => "productivity_report_ready",
+            BusEvent::MemoryPinDrafted(_) => "memory_pin_drafted",
+            BusEvent::MemoryPinSaved(_) => "memory_pin_saved",
+            BusEvent::HandoffDrafted(_) => "handoff_drafted",
+            BusEvent::CompareFinished(_) => "compare_finished",
+            BusEvent::ClipboardWriteRequested(_) => "clipboard_write_requested",
+            BusEvent::PermissionInboxChanged(_) => "permission_inbox_changed",
+            BusEvent::TurnQueueChanged(_) => "turn_queue_changed",
+            BusEvent::MigrationPending(_) => "migration_pending",
+        }
+    }
+
+    /// The session the event concerns, when it names one.
+    pub fn session_id(&self) -> Option<&str> {
+        use crate::bus::SessionUpdateStatus;
+        match self {
+            BusEvent::ToolUpdated(event) => Some(&event.session_id),
+            BusEvent::TodoUpdated(event) => Some(&event.session_id),
+            BusEvent::SubagentStatus(event) => Some(&event.session_id),
+            BusEvent::ManualToolCompleted(event) => Some(&event.session_id),
+            BusEvent::BatchProgress(event) => Some(&event.session_id),
+            BusEvent::FileTouch(event) => Some(&event.session_id),
+            BusEvent::SwarmOutputTail(event) => Some(&event.session_id),
+            BusEvent::BackgroundTaskCompleted(event) => Some(&event.session_id),
+            BusEvent::BackgroundTaskProgress(event) => Some(&event.session_id),
+            BusEvent::SwarmAwaitCompleted(event) => Some(&event.session_id),
+            BusEvent::OnboardingModelValidated(event) => Some(&event.session_id),
+            BusEvent::InputShellCompleted(event) => Some(&event.session_id),
+            BusEvent::ClipboardPasteCompleted(event) => Some(&event.session_id),
+            BusEvent::ModelRefreshCompleted(event) => Some(&event.session_id),
+            BusEvent::UiActivity(event) => event.session_id.as_deref(),
+            BusEvent::GitStatusCompleted(event) => Some(&event.session_id),
+            BusEvent::SessionUpdateStatus(
+                SessionUpdateStatus::Status { session_id, .. }
+                | SessionUpdateStatus::NoUpdate { session_id, .. }
+                | SessionUpdateStatus::ReadyToReload { session_id, .. }
+                | SessionUpdateStatus::Error { session_id, .. },
+            ) => Some(session_id),
+            BusEvent::DictationCompleted { session_id, .. }
+            | BusEvent::DictationFailed { session_id, .. } => session_id.as_deref(),
+            BusEvent::ProviderModelActivated { session_id, .. } => Some(session_id),
+            BusEvent::SidePanelUpdated(event) => Some(&event.session_id),
+            BusEvent::ProductivityReportReady(event) => Some(&event.session_id),
+            BusEvent::MemoryPinDrafted(event) => Some(&event.session_id),
+            BusEvent::MemoryPinSaved(event) => Some(&event.session_id),
+            BusEvent::HandoffDrafted(event) => Some(&event.session_id),
+            BusEvent::CompareFinished(event) => Some(&event.session_id),
+            BusEvent::ClipboardWriteRequested(event) => Some(&event.session_id),
+            BusEvent::TurnQueueChanged(event) => Some(&event.session_id),
+            BusEvent::UsageReport(_)
+            | BusEvent::UsageReportProgress(_)
+            | BusEvent::LoginCompleted(_)
+            | BusEvent::UpdateStatus(_)
+            | BusEvent::CompactionFinished
+            | BusEvent::ModelsUpdated
+            | BusEvent::MermaidRenderCompleted
+            | BusEvent::PermissionInboxChanged(_)
+            | BusEvent::MigrationPending(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bus::{Bus, BusEvent};
+
+    #[tokio::test]
+    async fn full_tap_drops_instead_of_blocking_the_publisher() {
+        let bus = Bus::new_isolated_for_tests();
+        let mut tap = bus.tap();
+        for _ in 0..super::TAP_CAPACITY + 5 {
+            bus.publish(BusEvent::CompactionFinished);
+        }
+        bus.publish(BusEvent::ModelsUpdated);
+
+        let stats = bus.stats();
+        assert_eq!(stats.published, super::TAP_CAPACITY as u64 + 6);
+        assert_eq!(
+            stats.by_kind["compaction_finished"],
+            super::TAP_CAPACITY as u64 + 5
+        );
+        assert_eq!(stats.taps.len(), 1);
+        assert_eq!(stats.taps[0].queued, super::TAP_CAPACITY);
+        assert_eq!(stats.taps[0].dropped, 6);
+
+        let first = tap.recv().await.expect("first tapped event");
+        assert_eq!((first.seq, first.kind), (1, "compaction_finished"));
+        assert_eq!(tap.take_dropped(), 6);
+        assert_eq!(tap.take_dropped(), 0);
+
+        drop(tap);
+        assert!(bus.stats().taps.is_empty());
+    }
+}
@@ -13,6 +13,9 @@ use std::collections::VecDeque;
 /// Maximum number of prefix hashes to remember (for detecting intermittent violations)
 const MAX_HISTORY: usize = 10;
 
+/// Requests measured under a changed prompt layout before its hit rate is reported
+const LAYOUT_IMPACT_REQUESTS: u32 = 5;
+
 /// Tracks message prefixes to detect cache violations
 #[derive(Debug, Clone, Default)]
 pub struct CacheTracker {
@@ -26,6 +29,35 @@ pub struct CacheTracker {
     hash_history: VecDeque<u64>,
     /// Whether append-only was violated on the last request
     last_violation: Option<CacheViolation>,
+    /// Provider-reported cache hit rates around `[prompt.sections]` layout changes
+    layout_hit_rates: LayoutHitRates,
+}
+
+/// Prompt-cache reads over a run of requests
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheHitRate {
+    pub requests: u32,
+    pub cached_tokens: u64,
+    pub prompt_tokens: u64,
+}
+
+impl CacheHitRate {
+    /// Share of prompt tokens read from the cache, in percent
+    pub fn percent(&self) -> f64 {
+        if self.prompt_tokens == 0 {
+            return 0.0;
+        }
+        self.cached_tokens as f64 * 100.0 / self.prompt_tokens as f64
+    }
+}
+
+/// Hit rate under the current prompt layout and the one before it
+#[derive(Debug, Clone, Default)]
+struct LayoutHitRates {
+    layout: Option<u64>,
+    before: Option<CacheHitRate>,
+    current: CacheHitRate,
+    reported: bool,
 }
 
 /// Information about a cache violation
@@ -207,6 +239,72 @@ impl CacheTracker {
     pub fn had_violation(&self) -> bool {
         self.last_violation.is_some()
     }
+
+    /// Record the provider-reported cache usage of one request sent under
+    /// prompt `layout` (see `prompt::layout_fingerprint`). Survives
+    /// [`Self::reset`], since a layout's hit rate spans compactions.
+    ///
+    /// Once a changed layout has [`LAYOUT_IMPACT_REQUESTS`] requests behind
+    /// it, returns a one-time line comparing its hit rate with the layout's
+    /// before it.
+    pub fn record_cache_usage(
+        &mut self,
+        layout: u64,
+        cached_tokens: u64,
+        prompt_tokens: u64,
+    ) -> Option<String> {
+        let rates = &mut self.layout_hit_rates;
+        if rates.layout != Some(layout) {
+            if rates.current.requests > 0 {
+                rates.before = Some(rates.current);
+            }
+            rates.layout = Some(layout);
+            rates.current = CacheHitRate::default();
+            rates.reported = false;
+        }
+        if prompt_tokens == 0 {
+            return None;
+        }
+        rates.current.requests += 1;
+        rates.current.cached_tokens += cached_tokens.min(prompt_tokens);
+        rates.current.prompt_tokens += prompt_tokens;
+        if rates.reported
+            || rates.before.is_none()
+            || rates.current.requests < LAYOUT_IMPACT_REQUESTS
+        {
+            return None;
+        }
+        rates.reported = true;
+        self.layout_impact()
+    }
+
+    /// The measured hit rate under the current prompt layout, next to the
+    /// previous layout's when there was a change.
+    pub fn layout_impact(&self) -> Option<String> {
+        let rates = &self.layout_hit_rates;
+        let current = rates.current;
+        match rates.before {
+            Some(before) if current.requests == 0 => Some(format!(
+                "Prompt layout changed; no requests measured yet (was {:.0}% cache hits over {} requests)",
+                before.percent(),
+                before.requests
+            )),
+            Some(before) => Some(format!(
+                "Cache hit rate {:.0}% over {} requests since the last [prompt.sections] change, {:.0}% over {} before ({:+.0} points)",
+                current.percent(),
+                current.requests,
+                before.percent(),
+                before.requests,
+                current.percent() - before.percent()
+            )),
+            None if current.requests > 0 => Some(format!(
+                "Cache hit rate {:.0}% over {} requests with this layout",
+                current.percent(),
+                current.requests
+            )),
+            None => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -304,6 +402,27 @@ mod tests {
         assert!(tracker.record_request(&msgs2).is_none());
     }
 
+    #[test]
+    fn layout_change_reports_hit_rate_impact_once() {
+        let mut tracker = CacheTracker::new();
+        for _ in 0..3 {
+            assert!(tracker.record_cache_usage(1, 200, 1000).is_none());
+        }
+        tracker.reset();
+        assert!(tracker.record_cache_usage(2, 0, 1000).is_none());
+        for _ in 0..3 {
+            assert!(tracker.record_cache_usage(2, 900, 1000).is_none());
+        }
+        let impact = tracker
+            .record_cache_usage(2, 900, 1000)
+            .expect("impact after five requests");
+        assert_eq!(
+            impact,
+            "Cache hit rate 72% over 5 requests since the last [prompt.sections] change, 20% over 3 before (+52 points)"
+        );
+        assert!(tracker.record_cache_usage(2, 900, 1000).is_none());
+    }
+
     /// Verify normal multi-turn conversation growth never triggers a false positive.
     /// This is the pattern that happens every real session: each turn appends a new
     /// assistant response and user message onto the unchanged prior history.
@@ -30,14 +30,15 @@ pub use jcode_compaction_core::{
     CHARS_PER_TOKEN, COMPACTION_THRESHOLD, CRITICAL_THRESHOLD, CompactionAction, CompactionEvent,
     CompactionStats, DEFAULT_TOKEN_BUDGET, EMBED_MAX_CHARS_PER_MSG, EMBEDDING_HISTORY_WINDOW,
     EMERGENCY_IMAGE_MAX_CHARS, EMERGENCY_TOOL_RESULT_MAX_CHARS, MANUAL_COMPACT_MIN_THRESHOLD,
-    MIN_TURNS_TO_KEEP, PAYLOAD_IMAGE_CHAR_BUDGET, RECENT_TURNS_TO_KEEP,
+    MIN_TURNS_TO_KEEP, MessageContribution, PAYLOAD_IMAGE_CHAR_BUDGET, RECENT_TURNS_TO_KEEP,
     SEMANTIC_EMBED_CACHE_CAPACITY, SUMMARY_PROMPT, SYSTEM_OVERHEAD_TOKENS, Summary,
     TOKEN_HISTORY_WINDOW, build_compaction_prompt, build_emergency_summary_text,
-    compacted_summary_text_block, content_char_count, emergency_strip_large_images,
-    emergency_truncate_large_payloads, estimate_compaction_tokens,
-    is_request_payload_too_large_error, mean_embedding, message_char_count, safe_compaction_cutoff,
-    semantic_cache_key, semantic_goal_text, semantic_message_text, strip_large_images_in_contents,
-    summary_payload_char_count,
+    compacted_summary_text_block, content_char_count, context_overflow_token_counts,
+    emergency_strip_large_images, emergency_truncate_large_payloads, estimate_compaction_tokens,
+    is_context_overflow_error, is_request_payload_too_large_error, largest_messages,
+    mean_embedding, message_char_count, safe_compaction_cutoff, semantic_cache_key,
+    semantic_goal_text, semantic_message_text, strip_large_images_in_contents,
+    summary_payload_char_count, trim_oldest_tool_results_in_contents,
 };
 
 const HARD_THRESHOLD_PENDING_WAIT_MS: u64 = 15_000;
@@ -202,6 +203,9 @@ pub struct CompactionManager {
 
     /// Monotonic recency counter for the semantic embedding cache LRU.
     semantic_embed_cache_counter: u64,
+
+    /// Session that summary calls are counted against as compaction overhead.
+    usage_session_id: Option<String>,
 }
 
 impl CompactionManager {
@@ -227,6 +231,7 @@ impl CompactionManager {
             embedding_history: VecDeque::with_capacity(EMBEDDING_HISTORY_WINDOW + 1),
             semantic_embed_cache: HashMap::with_capacity(SEMANTIC_EMBED_CACHE_CAPACITY),
             semantic_embed_cache_counter: 0,
+            usage_session_id: None,
         }
     }
 
@@ -235,6 +240,11 @@ impl CompactionManager {
         *self = Self::new();
     }
 
+    /// Count summary calls against `session_id` in its per-origin usage.
+    pub fn set_usage_session(&mut self, session_id: &str) {
+        self.usage_session_id = Some(session_id.to_string());
+    }
+
     pub fn with_budget(mut self, budget: usize) -> Self {
         self.token_budget = budget;
         self
@@ -903,11 +913,16 @@ impl CompactionManager {
         self.pending_trigger = Some(mode_label.clone());
 
         // Spawn background task that notifies via Bus when done
+        let usage_session_id = self.usage_session_id.clone();
         self.pending_task = Some(tokio::spawn(async move {
             let start = std::time::Instant::now();
-            let result =
-                generate_compaction_artifact(provider, messages_to_summarize, existing_summary)
-                    .await;
+            let result = generate_compaction_artifact(
+                provider,
+                usage_session_id.as_deref(),
+                messages_to_summarize,
+                existing_summary,
+            )
+            .await;
             let duration_ms = start.elapsed().as_millis() as u64;
             crate::logging::info(&format!(
                 "Compaction ({}) finished in {:.2}s ({} messages summarized)",
@@ -1116,11 +1131,16 @@ impl CompactionManager {
         self.pending_cutoff = cutoff;
         self.pending_trigger = Some("manual".to_string());
 
+        let usage_session_id = self.usage_session_id.clone();
         self.pending_task = Some(tokio::spawn(async move {
             let start = std::time::Instant::now();
-            let result =
-                generate_compaction_artifact(provider, messages_to_summarize, existing_summary)
-                    .await;
+            let result = generate_compaction_artifact(
+                provider,
+                usage_session_id.as_deref(),
+                messages_to_summarize,
+                existing_summary,
+            )
+            .await;
             let duration_ms = start.elapsed().as_millis() as u64;
             crate::logging::info(&format!(
                 "Compaction finished in {:.2}s ({} messages summarized)",
@@ -1229,6 +1249,7 @@ impl CompactionManager {
                         .as_ref()
                         .map(|summary| summary.text.len()),
                     active_messages: Some(self.active_messages_count()),
+                    tool_results_trimmed: None,
                 });
                 crate::logging::info(&format!(
                     "[TIMING] compaction_complete: trigger={}, duration={}ms, pre_tokens={}, post_tokens={}, tokens_saved={}, messages_compacted={}, summary_chars={}, active_messages={}",
@@ -1547,6 +1568,7 @@ impl CompactionManager {
                 .as_ref()
                 .map(|summary| summary.text.len()),
             active_messages: Some(self.active_messages_count()),
+            tool_results_trimmed: None,
         });
         self.log_compaction_outcome(CompactionOutcomeLog {
             trigger: "hard_compact",
@@ -1674,6 +1696,7 @@ impl Default for CompactionManager {
 /// Generate summary using the provider
 async fn generate_compaction_artifact(
     provider: Arc<dyn Provider>,
+    usage_session_id: Option<&str>,
     messages: Vec<Message>,
     mut existing_summary: Option<Summary>,
 ) -> Result<CompactionResult> {
@@ -1734,12 +1757,14 @@ async fn generate_compaction_artifact(
     let prompt = build_compaction_prompt(&messages, existing_summary.as_ref(), max_prompt_chars);
 
     // Generate summary using simple completion
-    let summary = provider
-        .complete_simple(
-            &prompt,
-            "You are a helpful assistant that summarizes conversations.",
-        )
-        .await?;
+    let summary = crate::background_model::complete_simple(
+        provider.as_ref(),
+        &crate::usage::UsageOrigin::Compaction,
+        usage_session_id,
+        &prompt,
+        "You are a helpful assistant that summarizes conversations.",
+    )
+    .await?;
 
     Ok(CompactionResult {
         summary_text: summary,
@@ -1773,7 +1798,8 @@ pub async fn build_transfer_compaction_state(
         .as_ref()
         .map(|state| state.original_turn_count.max(state.covers_up_to_turn))
         .unwrap_or(0);
-    let result = generate_compaction_artifact(provider, messages.clone(), existing_summary).await?;
+    let result =
+        generate_compaction_artifact(provider, None, messages.clone(), existing_summary).await?;
     let total_turns = prior_turns + messages.len();
 
     Ok(Some(crate::session::StoredCompactionState {
@@ -4,14 +4,19 @@
 //! Environment variables override config file settings.
 
 pub use jcode_config_types::{
-    AgentsConfig, AmbientConfig, AuthConfig, AutoJudgeConfig, AutoReviewConfig, CompactionConfig,
-    CompactionMode, CrossProviderFailoverMode, DiagramDisplayMode, DiagramPanePosition,
-    DiffDisplayMode, DisplayConfig, FeatureConfig, GatewayConfig, HooksConfig, KeybindingsConfig,
-    LaunchHotkeyEntry, LaunchHotkeysConfig, MarkdownSpacingMode, NamedProviderAuth,
-    NamedProviderConfig, NamedProviderModelConfig, NamedProviderType, NativeScrollbarConfig,
-    NotificationsConfig, PowerConfig, ProviderConfig, ReasoningDisplayMode, SafetyConfig,
-    SessionPickerResumeAction, SwarmSpawnMode, TerminalConfig, UpdateChannel, WebSearchConfig,
-    WebSearchEngine,
+    AgentLimitsConfig, AgentProfileConfig, AgentsConfig, AmbientConfig, AnthropicProviderConfig,
+    AnthropicServerTool, AuthConfig, AutoDebugConfig, AutoJudgeConfig, AutoReviewConfig,
+    CompactionConfig, CompactionMode, CopilotProviderConfig, CrossProviderFailoverMode,
+    DiagramDisplayMode, DiagramPanePosition, DiffDisplayMode, DisplayConfig, FeatureConfig,
+    GatewayConfig, GitConfig, HookRule, HooksConfig, KeybindingsConfig, LaunchHotkeyEntry,
+    LaunchHotkeysConfig, MarkdownSpacingMode, MemoryConfig, NamedProviderAuth, NamedProviderConfig,
+    NamedProviderModelConfig, NamedProviderType, NativeScrollbarConfig, NetworkConfig, NetworkMode,
+    NotificationsConfig, OutputConfig, PROMPT_SOURCES, PowerConfig, PrivacyConfig, PromptConfig,
+    PromptPlacement, PromptSection, PromptSectionConfig, PromptSectionsConfig, ProviderConfig,
+    ReasoningDisplayMode, RebuildConfig, SafetyConfig, ServerConfig, SessionPickerResumeAction,
+    SkillsConfig, StorageBackend, StorageConfig, SwarmSpawnMode, TelegramConfig, TerminalConfig,
+    TodoConfig, ToolFallbackMode, UpdateChannel, UpdateConfig, WebSearchConfig, WebSearchEngine,
+    WebhookConfig,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, BTreeSet, HashSet};
@@ -30,6 +35,7 @@ const CONFIG_ENV_KEYS: &[&str] = &[
     "HOME",
     "JCODE_ACP_PROFILE",
     "JCODE_ACP_TOOL_PROFILE",
+    "JCODE_ACP_APPROVALS",
     "JCODE_AMBIENT_ENABLED",
     "JCODE_AMBIENT_MAX_INTERVAL",
     "JCODE_AMBIENT_MIN_INTERVAL",
@@ -38,6 +44,9 @@ const CONFIG_ENV_KEYS: &[&str] = &[
     "JCODE_AMBIENT_PROVIDER",
     "JCODE_AMBIENT_VISIBLE",
     "JCODE_ANIMATION_FPS",
+    "JCODE_ASK_USER_TIMEOUT_SECS",
+    "JCODE_AUTODEBUG_ENABLED",
+    "JCODE_AUTODEBUG_POST_MORTEMS",
     "JCODE_AUTOJUDGE_ENABLED",
     "JCODE_AUTOJUDGE_MODEL",
     "JCODE_AUTOREVIEW_ENABLED",
@@ -50,10 +59,14 @@ const CONFIG_ENV_KEYS: &[&str] = &[
     "JCODE_CHAT_NATIVE_SCROLLBAR",
     "JCODE_COMPACT_NOTIFICATIONS",
     "JCODE_COPY_BADGE_ALT_LABEL",
+    "JCODE_COPY_MESSAGE_KEY",
     "JCODE_COPY_SELECTION_TOGGLE_KEY",
+    "JCODE_COPILOT_MONTHLY_PREMIUM_BUDGET",
     "JCODE_COPILOT_PREMIUM",
     "JCODE_CROSS_PROVIDER_FAILOVER",
     "JCODE_DEBUG_SOCKET",
+    "JCODE_DETERMINISTIC",
+    "JCODE_DETERMINISTIC_SEED",
     "JCODE_DICTATION_COMMAND",
     "JCODE_DICTATION_KEY",
     "JCODE_DICTATION_MODE",
@@ -74,10 +87,13 @@ const CONFIG_ENV_KEYS: &[&str] = &[
     "JCODE_EFFORT_INCREASE_KEY",
     "JCODE_EMAIL_REPLY_ENABLED",
     "JCODE_EMAIL_TO",
+    "JCODE_FILE_PICKER_KEY",
     "JCODE_FOCUS_HOOK",
     "JCODE_GATEWAY_BIND_ADDR",
     "JCODE_GATEWAY_ENABLED",
     "JCODE_GATEWAY_PORT",
+    "JCODE_GIT_AUTO_COMMIT",
+    "JCODE_GIT_AUTO_COMMIT_BRANCH",
     "JCODE_HOME",
     "JCODE_HOOK_PRE_TOOL",
     "JCODE_HOOK_PRE_TOOL_TIMEOUT_MS",
@@ -87,8 +103,10 @@ const CONFIG_ENV_KEYS: &[&str] = &[
     "JCODE_HOOK_TURN_END",
     "JCODE_HOOK_TURN_START",
     "JCODE_IDLE_ANIMATION",
+    "JCODE_IMAGE_OPEN_KEY",
     "JCODE_IMAP_HOST",
     "JCODE_INFO_WIDGET_TOGGLE_KEY",
+    "JCODE_INTERJECT_KEY",
     "JCODE_JADE_RELAY_API_BASE",
     "JCODE_JADE_RELAY_ENABLED",
     "JCODE_JADE_RELAY_LAUNCH_ENABLED",
@@ -105,6 +123,8 @@ const CONFIG_ENV_KEYS: &[&str] = &[
     "JCODE_MEMORY_EMBEDDING_DIM",
     "JCODE_MEMORY_EMBEDDING_MODEL",
     "JCODE_MEMORY_ENABLED",
+    "JCODE_MEMORY_INJECTION_MAX_ENTRIES",
+    "JCODE_MEMORY_INJECTION_TOKEN_BUDGET",
     "JCODE_MEMORY_MODEL",
     "JCODE_MEMORY_SIDECAR_ENABLED",
     "JCODE_PERSIST_MEMORY_INJECTIONS",
@@ -114,23 +134,31 @@ const CONFIG_ENV_KEYS: &[&str] = &[
     "JCODE_MODEL_SWITCH_PREV_KEY",
     "JCODE_MOUSE_CAPTURE",
     "JCODE_NEW_TERMINAL_KEY",
+    "JCODE_NETWORK_MODE",
     "JCODE_NTFY_SERVER",
     "JCODE_NTFY_TOPIC",
+    "JCODE_OPENAI_BACKGROUND_AFTER_RECONNECT",
     "JCODE_OPENAI_NATIVE_COMPACTION_MODE",
     "JCODE_OPENAI_NATIVE_COMPACTION_THRESHOLD_TOKENS",
     "JCODE_OPENAI_REASONING_EFFORT",
+    "JCODE_OPENAI_RESUMABLE_STREAMS",
     "JCODE_OPENAI_SERVICE_TIER",
     "JCODE_OPENAI_TRANSPORT",
+    "JCODE_OUTPUT_TEE_FILE",
     "JCODE_ANTHROPIC_REASONING_EFFORT",
+    "JCODE_ANTHROPIC_SERVER_TOOLS",
     "JCODE_PRESERVE_REASONING_CONTEXT",
     "JCODE_PERFORMANCE",
     "JCODE_PIN_IMAGES",
     "JCODE_PREVENT_SLEEP_WHILE_STREAMING",
     "JCODE_PROVIDER",
     "JCODE_PROMPT_ENTRY_ANIMATION",
+    "JCODE_PROMPT_GIT_CONTEXT",
+    "JCODE_PRUNE_SUPERSEDED_READS",
     "JCODE_QUEUE_MODE",
     "JCODE_REASONING_DISPLAY",
     "JCODE_REDRAW_FPS",
+    "JCODE_REMEMBER_MESSAGE_KEY",
     "JCODE_SAME_PROVIDER_ACCOUNT_FAILOVER",
     "JCODE_SCROLL_BOOKMARK_KEY",
     "JCODE_SCROLL_DOWN_FALLBACK_KEY",
@@ -143,13 +171,16 @@ const CONFIG_ENV_KEYS: &[&str] = &[
     "JCODE_SCROLL_UP_KEY",
     "JCODE_SEARXNG_URL",
     "JCODE_SHOW_AGENTGREP_OUTPUT",
+    "JCODE_MESSAGE_FOOTERS",
     "JCODE_SHOW_DIFFS",
     "JCODE_SHOW_THINKING",
+    "JCODE_STORAGE_BACKEND",
     "JCODE_SIDE_PANEL_TOGGLE_KEY",
     "JCODE_SIDE_PANEL_NATIVE_SCROLLBAR",
     "JCODE_SMTP_PASSWORD",
     "JCODE_SPAWN_HOOK",
     "JCODE_STREAM_IDLE_TIMEOUT_SECS",
+    "JCODE_PROVIDER_IDLE_TIMEOUT_SECS",
     "JCODE_SWARM_ENABLED",
     "JCODE_SWARM_MODEL",
     "JCODE_SWARM_MAX_CONCURRENT_AGENTS",
@@ -159,6 +190,7 @@ const CONFIG_ENV_KEYS: &[&str] = &[
     "JCODE_TELEGRAM_REPLY_ENABLED",
     "JCODE_TOOL_PROFILE",
     "JCODE_TOOLS",
+    "JCODE_TOOL_FALLBACK",
     "JCODE_TRUSTED_EXTERNAL_AUTH_SOURCES",
     "JCODE_TYPING_SCROLL_LOCK_TOGGLE_KEY",
     "JCODE_UPDATE_CHANNEL",
@@ -440,7 +472,7 @@ pub fn on_config_reloaded(listener: fn()) {
 
 /// Main configuration struct
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct Config {
     /// Keybinding configuration
     pub keybindings: KeybindingsConfig,
@@ -481,10 +513,23 @@ pub struct Config {
     /// Agent-specific model defaults
     pub agents: AgentsConfig,
 
+    /// Per-request agent loop limits (turns, tool calls, wall clock)
+    pub agent: AgentLimitsConfig,
+
+    /// Named agent presets, keyed by profile name.
+    ///
+    /// Example:
+    /// [profiles.review]
+    /// model = "claude-opus-4-6"
+    /// tools = ["read", "agentgrep", "ls"]
+    /// system_prompt_file = "~/.jcode/prompts/review.md"
+    pub profiles: BTreeMap<String, AgentProfileConfig>,
+
     /// Terminal window/pane spawning configuration
     pub terminal: TerminalConfig,
 
     /// Lifecycle hooks (external commands at turn/session/tool boundaries)
+    #[serde(deserialize_with = "jcode_config_types::deserialize_hooks_config")]
     pub hooks: HooksConfig,
 
     /// Ambient mode configuration
@@ -499,30 +544,75 @@ pub struct Config {
     /// WebSocket gateway configuration (for iOS/web clients)
     pub gateway: GatewayConfig,
 
+    /// Server daemon configuration (metrics listener)
+    pub server: ServerConfig,
+
     /// Compaction configuration
     pub compaction: CompactionConfig,
 
     /// Power-management configuration (prevent sleep while streaming)
     pub power: PowerConfig,
 
+    /// Project todo configuration (optional markdown sync)
+    pub todo: TodoConfig,
+
+    /// System prompt layering (instruction files and size cap)
+    pub prompt: PromptConfig,
+
+    /// Copies of assistant output (tee file)
+    pub output: OutputConfig,
+
+    /// Redaction of outbound messages
+    pub privacy: PrivacyConfig,
+
+    /// Per-turn git auto-commit
+    pub git: GitConfig,
+
+    /// Memory injection budget and ranking
+    pub memory: MemoryConfig,
+
+    /// Self-dev rebuild test selection
+    pub rebuild: RebuildConfig,
+
+    /// Release-based update channel
+    pub update: UpdateConfig,
+
+    /// Outbound network allowlist
+    pub network: NetworkConfig,
+
     /// Auto-review configuration
     pub autoreview: AutoReviewConfig,
 
     /// Auto-judge configuration
     pub autojudge: AutoJudgeConfig,
 
+    /// Diagnosis of repeated tool failures
+    pub autodebug: AutoDebugConfig,
+
+    /// Automatic skill loading
+    pub skills: SkillsConfig,
+
+    /// Session and memory storage backend
+    pub storage: StorageConfig,
+
+    /// Telegram bot bridge (`jcode telegram`)
+    pub telegram: TelegramConfig,
+
     /// Global "launch a new jcode" hotkeys (macOS). Baked once by auto-import.
     pub launch_hotkeys: LaunchHotkeysConfig,
 }
 
 /// Agent Client Protocol adapter configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct AcpConfig {
     /// Client compatibility profile: "standard" (default), "extended", or "full".
     pub profile: String,
     /// Tool profile to request when `jcode acp` starts a daemon itself.
     pub tool_profile: String,
+    /// Send tool calls outside the auto-allowed tier to the editor as
+    /// `session/request_permission` before they run.
+    pub approvals: bool,
 }
 
 impl Default for AcpConfig {
@@ -530,13 +620,14 @@ impl Default for AcpConfig {
         Self {
             profile: "standard".to_string(),
             tool_profile: "acp".to_string(),
+            approvals: true,
         }
     }
 }
 
 /// Controls which tools are sent to the model.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct ToolConfig {
     /// Tool profile: "full" (default), "acp", "minimal"/"lite", or "none".
     pub profile: String,
@@ -547,6 +638,67 @@ pub struct ToolConfig {
     pub disabled: Vec<String>,
     /// Disable all built-in tools unless `enabled` is provided.
     pub disable_base_tools: bool,
+    /// Limits and environment for the bash tool (`[tools.bash]`).
+    pub bash: BashToolConfig,
+    /// Verification for the refactor_rename tool (`[tools.refactor]`).
+    pub refactor: RefactorToolConfig,
+}
+
+/// Limits and project environment for the bash tool (`[tools.bash]`).
+///
+/// Variables from the env files are set on the child process only; their
+/// values never enter the prompt, and output containing them is redacted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BashToolConfig {
+    /// Env file to load, relative to the session working directory.
+    pub env_file: Option<String>,
+    /// Also load `.env` from the session working directory.
+    pub dotenv: bool,
+    /// Variables from the env files to pass. Empty passes all of them; a
+    /// trailing `*` matches a prefix (`AWS_*`).
+    pub env_allowlist: Vec<String>,
+    /// Replace env-file values found in command output with a placeholder.
+    pub redact_output: bool,
+    /// Upper bound for the `timeout_secs` a tool call may ask for.
+    pub max_timeout_secs: u64,
+    /// Command output kept for the model; longer output keeps its head and
+    /// tail around a truncation marker.
+    pub max_output_bytes: usize,
+}
+
+impl Default for BashToolConfig {
+    fn default() -> Self {
+        Self {
+            env_file: None,
+            dotenv: false,
+            env_allowlist: Vec::new(),
+            redact_output: true,
+            max_timeout_secs: 120,
+            max_output_bytes: 30_000,
+        }
+    }
+}
+
+/// Verification run after `refactor_rename` applies a rename
+/// (`[tools.refactor]`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RefactorToolConfig {
+    /// Command run in the working directory after a rename, e.g.
+    /// `cargo check`. A tool call may pass its own instead.
+    pub verify_command: Option<String>,
+    /// Seconds the verification command may run before it is killed.
+    pub verify_timeout_secs: u64,
+}
+
+impl Default for RefactorToolConfig {
+    fn default() -> Self {
+        Self {
+            verify_command: None,
+            verify_timeout_secs: 300,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
@@ -672,6 +824,56 @@ impl ToolConfig {
     }
 }
 
+impl Config {
+    /// Look up a `[profiles.<name>]` preset.
+    pub fn profile(&self, name: &str) -> anyhow::Result<&AgentProfileConfig> {
+        self.profiles.get(name).ok_or_else(|| {
+            let known: Vec<&str> = self.profiles.keys().map(String::as_str).collect();
+            if known.is_empty() {
+                anyhow::anyhow!("Unknown profile `{name}`: no [profiles.*] sections in config.toml")
+            } else {
+                anyhow::anyhow!("Unknown profile `{name}` (known: {})", known.join(", "))
+            }
+        })
+    }
+
+    /// Validation errors across all `[profiles.*]` sections.
+    pub fn profile_errors(&self) -> Vec<String> {
+        self.profiles
+            .iter()
+            .flat_map(|(name, profile)| profile.validate(name))
+            .collect()
+    }
+
+    /// The effective update channel: `[update] channel`, falling back to the
+    /// older `[features] update_channel`.
+    pub fn update_channel(&self) -> UpdateChannel {
+        self.update.channel.unwrap_or(self.features.update_channel)
+    }
+}
+
+impl ToolConfig {
+    /// The `[tools]` config with a profile's tool settings layered on top.
+    pub fn with_profile(&self, profile: &AgentProfileConfig) -> ToolConfig {
+        let mut tools = if profile.tools.is_empty() {
+            self.clone()
+        } else {
+            ToolConfig {
+                profile: String::new(),
+                enabled: profile.tools.clone(),
+                disabled: self.disabled.clone(),
+                disable_base_tools: false,
+                bash: self.bash.clone(),
+                refactor: self.refactor.clone(),
+            }
+        };
+        tools
+            .disabled
+            .extend(profile.disabled_tools.iter().cloned());
+        tools
+    }
+}
+
 fn normalize_tool_name(name: &str) -> String {
     let trimmed = name.trim().trim_matches('"');
     jcode_tool_types::resolve_tool_name(trimmed).to_string()
@@ -679,7 +881,7 @@ fn normalize_tool_name(name: &str) -> String {
 
 /// External dictation / speech-to-text integration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct DictationConfig {
     /// Shell command to run. Must print the transcript to stdout.
     pub command: String,
@@ -706,6 +908,10 @@ mod config_file;
 mod default_file;
 mod display_summary;
 mod env_overrides;
+pub mod file_edit;
+mod validation;
+
+pub use validation::{ConfigIssue, ConfigIssueKind};
 
 #[cfg(test)]
 #[path = "config_tests.rs"]
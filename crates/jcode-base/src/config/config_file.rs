@@ -47,13 +47,38 @@ impl Config {
 
         let content = std::fs::read_to_string(&path)
             .map_err(|e| anyhow::anyhow!("Failed to read config file {}: {}", path.display(), e))?;
-        let mut config = toml::from_str::<Self>(&content).map_err(|e| {
+        let (mut config, issues) = Self::parse_lenient(&content, Some(&path)).map_err(|e| {
             anyhow::anyhow!("Failed to parse config file {}: {}", path.display(), e)
         })?;
+        for issue in &issues {
+            crate::logging::warn(&format!("Ignoring config key: {}", issue));
+        }
+        for warning in config.prompt.section_warnings() {
+            crate::logging::warn(&warning);
+        }
         config.display.apply_legacy_compat();
         Ok(Some(config))
     }
 
+    /// Keys in the config file that loading ignores as unknown or invalid.
+    ///
+    /// Errors when the file cannot be read or is not valid TOML at all, in
+    /// which case loading falls back to defaults.
+    pub fn file_issues() -> anyhow::Result<Vec<ConfigIssue>> {
+        let Some(path) = Self::path() else {
+            return Ok(Vec::new());
+        };
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| anyhow::anyhow!("Failed to read config file {}: {}", path.display(), e))?;
+        let (_, issues) = Self::parse_lenient(&content, Some(&path)).map_err(|e| {
+            anyhow::anyhow!("Failed to parse config file {}: {}", path.display(), e)
+        })?;
+        Ok(issues)
+    }
+
     /// Save config to file
     pub fn save(&self) -> anyhow::Result<()> {
         let path = Self::path().ok_or_else(|| anyhow::anyhow!("No config path"))?;
@@ -159,6 +184,32 @@ impl Config {
         Ok(())
     }
 
+    /// Update the persisted diff mode and right-hand pane width (`/view save`).
+    pub fn set_display_layout(
+        diff_mode: DiffDisplayMode,
+        side_pane_ratio: u8,
+    ) -> anyhow::Result<()> {
+        let mut cfg = Self::load();
+        cfg.display.diff_mode = diff_mode;
+        cfg.display.side_pane_ratio = side_pane_ratio;
+        cfg.save()?;
+        crate::logging::info(&format!(
+            "Saved display layout to config: diff_mode={} side_pane_ratio={}",
+            diff_mode.label(),
+            side_pane_ratio
+        ));
+        Ok(())
+    }
+
+    /// Update the persisted `[update] channel`.
+    pub fn set_update_channel(channel: UpdateChannel) -> anyhow::Result<()> {
+        let mut cfg = Self::load();
+        cfg.update.channel = Some(channel);
+        cfg.save()?;
+        crate::logging::info(&format!("Saved update.channel to config: {}", channel));
+        Ok(())
+    }
+
     /// Update the persisted reasoning display mode preference.
     pub fn set_reasoning_display(mode: ReasoningDisplayMode) -> anyhow::Result<()> {
         let mut cfg = Self::load();
@@ -195,6 +246,18 @@ impl Config {
         Ok(())
     }
 
+    /// Update the persisted message-footers preference.
+    pub fn set_message_footers(show: bool) -> anyhow::Result<()> {
+        let mut cfg = Self::load();
+        cfg.display.message_footers = show;
+        cfg.save()?;
+        crate::logging::info(&format!(
+            "Saved display.message_footers to config: {}",
+            show
+        ));
+        Ok(())
+    }
+
     /// Persist the baked global launch-hotkey mapping.
     ///
     /// Auto-import calls this once with the per-repo chord -> directory layout it
@@ -10,11 +10,20 @@ impl Config {
             std::fs::create_dir_all(parent)?;
         }
 
+        std::fs::write(&path, Self::default_config_file_content())?;
+        Ok(path)
+    }
+
+    /// The commented default config.toml template.
+    pub fn default_config_file_content() -> String {
         let default_content = r#"# jcode configuration file
 # Location: ~/.jcode/config.toml
 #
 # Environment variables override these settings.
 # Run `/config` in jcode to see current settings.
+# Unknown or mistyped keys are ignored with a startup warning (an error with
+# --strict-config). `jcode config doctor` checks the whole file,
+# `jcode config get/set <key>` reads or edits single settings.
 
 [keybindings]
 # Scroll keys (vim-style by default)
@@ -66,6 +75,9 @@ diagram_pane_toggle = "alt+t"
 typing_scroll_lock_toggle = "alt+s"
 diff_mode_cycle = "alt+g"
 info_widget_toggle = "alt+i"
+# Collapse the live todo checklist above the input to a single line. Click an
+# item in the checklist to copy its text.
+todo_checklist_toggle = "alt+x"
 # Focus the inline swarm panel (list of agents this session manages). While
 # focused: j/k select, o pops the selected agent out to a new terminal, esc
 # exits. Active only with agents.swarm_spawn_mode = "inline".
@@ -81,10 +93,42 @@ swarm_panel_focus = "alt+n"
 # Note: some macOS terminals intercept Cmd combos; if so, pick another binding.
 # new_terminal = "cmd+shift+;"
 
+# While a turn is streaming, open an inline input to interject: Enter sends it
+# into the running turn, Tab toggles urgent, Esc closes it. Normal interjections
+# land after the current tool batch; urgent ones skip the remaining tools once
+# the in-flight tool finishes. When idle the key keeps inserting a newline.
+# Default: Alt+Enter. Set "" to disable.
+# interject = "alt+enter"
+
 # Open the /resume session picker.
 # Default: Cmd+B on macOS, Alt+R on Windows/Linux. Set "" to disable.
 # open_resume = "cmd+b"
 
+# Open the inline image or diagram filling most of the chat in the system viewer.
+# image_open = "alt+o"
+
+# Save the focused assistant answer as a memory (same as /remember).
+# remember_message = "alt+p"
+
+# Copy the focused answer or tool output via OSC 52 (works over SSH and tmux).
+# copy_message = "alt+q"
+
+# Browse the workspace and attach files (or insert @mentions) from a picker.
+# Alt+F and Ctrl+Right still move forward a word; set "" to give Ctrl+F back.
+# file_picker = "ctrl+f"
+
+# Readline-style editing in the input box. Killed text goes to a kill ring;
+# yank pastes the latest kill and yank-pop cycles older ones. Set "" to disable.
+# composer_word_back = "alt+b,ctrl+left"
+# composer_word_forward = "alt+f,ctrl+right"
+# composer_kill_line_end = "ctrl+k"
+# composer_kill_line_start = "ctrl+u"
+# composer_kill_word_back = "ctrl+w"
+# composer_yank = "ctrl+y"
+# composer_yank_pop = ""      # unbound by default (Alt+Y toggles copy mode)
+# composer_undo = "ctrl+_,ctrl+shift+-"
+# composer_transpose = ""     # unbound by default (Ctrl+T toggles queue mode)
+
 # /resume picker Enter behavior. Options: "current-terminal" or "new-terminal".
 # By default Enter resumes in this terminal; Ctrl+Enter performs the alternate action.
 session_picker_enter = "current-terminal"
@@ -110,8 +154,14 @@ timeout_secs = 90
 
 [display]
 # Diff display mode: "off", "inline" (default), "full-inline", "pinned" (dedicated pane), or "file"
+# "file" splits the screen: the right pane follows the most recently edited file
+# (or a file pinned with /view <path>) with the latest diff highlighted.
 diff_mode = "inline"
 
+# Width of the right-hand pane as a percentage of the chat area, 25-100 (default: 40)
+# /view save writes the current split here.
+side_pane_ratio = 40
+
 # Center all content by default (default: false)
 centered = false
 
@@ -128,6 +178,21 @@ queue_mode = false
 # Automatically reload the remote server when a newer server binary is detected (default: true)
 auto_server_reload = true
 
+# Seconds of warning before a session migrates to a newer binary (default: 300)
+# The banner offers `/migrate now` or `/migrate defer` (30 minutes) meanwhile
+migration_grace_secs = 300
+
+# Terminal title layout. Placeholders:
+#   {state}       ● streaming, ◐ tool running, ✋ approval pending, ✓ idle
+#   {state_label} streaming / tool / approval / idle
+#   {icon} {server} {session} {suffix}
+title_format = "{state} {icon} {server} {session}{suffix}"
+
+# Also write the title to ~/.jcode/status/<session> (default: false), e.g. for
+#   set -g status-right '#(cat ~/.jcode/status/* 2>/dev/null | paste -sd " ")'
+# tmux also shows the title itself as #{pane_title}.
+tmux_status = false
+
 # Capture mouse events (enables scroll wheel; disables terminal text selection)
 mouse_capture = true
 
@@ -163,6 +228,10 @@ prompt_entry_animation = true
 # results directly in the chat.
 # show_agentgrep_output = false
 
+# Show a dim footer with the model, provider, duration and token usage under
+# each assistant message restored from session history (default: true)
+# message_footers = true
+
 # Occasionally surface a "learn this keybinding" nudge (in a distinct color)
 # when you keep doing something the slow way (e.g. /resume) instead of using
 # its configured shortcut. Set false to disable all such hints (default: true).
@@ -206,7 +275,8 @@ persist_memory_injections = false
 # should essentially never happen and indicate a prefix-cache bug.
 kv_cache_miss_notices = true
 # Update channel: "stable" (releases only) or "main" (latest commits on push)
-# Set to "main" for bleeding edge updates every time code is pushed
+# Set to "main" for bleeding edge updates every time code is pushed.
+# `[update] channel` takes precedence when set.
 update_channel = "stable"
 
 [websearch]
@@ -244,6 +314,27 @@ profile = "full"
 # Disable all built-in tools unless enabled is set.
 disable_base_tools = false
 
+[tools.bash]
+# Project env vars for commands the agent runs. Values are set on the command's
+# process only: the model refers to them as $VAR and never sees them.
+# env_file = ".jcode/env"   # relative to the session working directory
+# dotenv = true             # also load .env
+# Only pass these variables from the env files (trailing * matches a prefix).
+# env_allowlist = ["DATABASE_URL", "AWS_*"]
+# Replace env-file values that show up in command output with [redacted:NAME].
+redact_output = true
+# Ceiling for the timeout_secs a bash call may set. A command that runs past
+# its timeout_secs has its process group killed.
+max_timeout_secs = 120
+# Output bytes returned to the model; the middle of longer output is dropped.
+max_output_bytes = 30000
+
+[tools.refactor]
+# Command refactor_rename runs after applying a rename, from the session
+# working directory. Its exit status and output tail go back to the model.
+# verify_command = "cargo check"
+verify_timeout_secs = 300
+
 [acp]
 # Agent Client Protocol adapter compatibility profile: standard, extended, or full.
 # standard emits only spec-compatible ACP messages.
@@ -252,6 +343,9 @@ profile = "standard"
 # Tool profile requested when `jcode acp` starts the daemon itself.
 # Existing daemons keep their current server-wide tool config.
 tool_profile = "acp"
+# Ask the editor before tool calls outside the auto-allowed tier (session/request_permission).
+# Only the approvals apply; the other `--safe` restrictions stay off.
+approvals = true
 
 [provider]
 # Default model (optional, uses provider default if not set)
@@ -277,6 +371,17 @@ openai_reasoning_effort = "low"
 # Defaults to `priority` to match Codex /fast behavior for OpenAI OAuth
 # (higher speed, higher usage). Set to "off" to disable.
 openai_service_tier = "priority"
+# Run OpenAI API-key requests in Responses background mode, so a stream cut off
+# by a flaky connection resumes after its last event instead of regenerating
+# the response. Responses are stored server-side. Not available with ChatGPT
+# sign-in. Also overridable via JCODE_OPENAI_RESUMABLE_STREAMS.
+# openai_resumable_streams = false
+# Once a turn has to reconnect, resend it in background mode and start later
+# turns that way too, so long turns survive further drops. A jcode restart
+# re-attaches to a response still generating. Falls back to plain streaming
+# where background mode is unavailable. Also overridable via
+# JCODE_OPENAI_BACKGROUND_AFTER_RECONNECT.
+# openai_background_after_reconnect = true
 # Preserve provider-native reasoning/thinking for future-turn context when supported.
 # Applies to OpenRouter, Anthropic, and OpenAI native reasoning replay. Display is separate.
 preserve_reasoning_context = true
@@ -287,6 +392,11 @@ preserve_reasoning_context = true
 # Try another account on the same provider before switching providers (default: true)
 # same_provider_account_failover = false
 cross_provider_failover = "countdown"
+# When the active model has no native tool calling (e.g. o1-mini, gpt-5-chat):
+# textual = describe the tools in the prompt and parse fenced ```tool blocks (default)
+# refuse = stop the turn and suggest switching to a tool-capable model
+# Also overridable via JCODE_TOOL_FALLBACK.
+# tool_fallback = "refuse"
 # Copilot premium mode: "normal" (default), "one" (first msg only), "zero" (all free)
 # Set to "zero" if you have premium Copilot and want free requests
 # copilot_premium = "zero"
@@ -295,6 +405,82 @@ cross_provider_failover = "countdown"
 # silently for minutes before emitting tokens. Default: 180.
 # Also overridable per-launch via JCODE_STREAM_IDLE_TIMEOUT_SECS.
 # stream_idle_timeout_secs = 600
+# Max seconds between streamed chunks once a response has started. A stream
+# that goes quiet for longer is cut off and retried, keeping any partial text.
+# 0 disables the check. Default: 90.
+# Also overridable per-launch via JCODE_PROVIDER_IDLE_TIMEOUT_SECS.
+# idle_timeout_secs = 90
+# When a provider rate-limits a request (HTTP 429 with retry-after / reset
+# headers), wait up to this many seconds and retry the same request in-turn,
+# with a countdown in the status bar. Longer waits end the turn with an error
+# and a client-side auto-retry. 0 = never wait in-turn. Default: 90.
+# rate_limit_max_wait_secs = 90
+# Reproducible runs (e.g. evals): temperature 0 / top_p 1 where the API accepts
+# them, a fixed seed where supported (OpenAI-compatible chat APIs), and the
+# dated model snapshot instead of an alias. Effective settings are recorded
+# with each response; parts a provider cannot honor are logged as warnings.
+# Also overridable via JCODE_DETERMINISTIC / JCODE_DETERMINISTIC_SEED, or per
+# run with `jcode run --deterministic`.
+# deterministic = false
+# deterministic_seed = 0
+
+# Anthropic server-side tools, run by Anthropic and billed per use. Requested
+# only when the active Claude model supports them; results and citations are
+# kept in the session. Also overridable via JCODE_ANTHROPIC_SERVER_TOOLS.
+# [provider.anthropic]
+# server_tools = ["web_search"]
+
+# Copilot monthly premium-request budget. From 80% jcode switches to
+# one-premium-per-session; at 100% premium models are refused until next month
+# (included models like gpt-4.1 keep working). Override per session with
+# /premium normal|conserve|off. Also overridable via JCODE_COPILOT_MONTHLY_PREMIUM_BUDGET.
+# [provider.copilot]
+# monthly_premium_budget = 300
+
+[compaction]
+# Replace the bodies of earlier `read` results with a one-line stub once a later
+# full read, write or edit of the same file supersedes them. Only the request
+# sent to the model changes; the session keeps every result. Tokens saved are
+# recorded on the assistant message's usage. Also JCODE_PRUNE_SUPERSEDED_READS.
+# prune_superseded_reads = false
+
+[agent]
+# Limits on the agent loop, mainly for unattended `jcode run`.
+# When a limit is hit the agent is asked to wrap up and gets one final reply
+# (further tool calls are not executed); the request then stops with an error,
+# and `jcode run` exits non-zero. `jcode run --max-turns N` overrides max_turns.
+# Unset or 0 = no limit (default). /turninfo shows the last request's usage.
+#
+# Model calls (agent loop iterations) per request.
+# max_turns = 50
+#
+# Tool calls executed from a single model reply (agent loop iteration).
+# max_tool_calls_per_turn = 200
+#
+# Wall-clock seconds per request.
+# max_wall_seconds = 1800
+#
+# Subagent tasks run at once when the subagent tool is given a list of tasks
+# (default 4; the rest wait for a free slot).
+# max_parallel_subagents = 4
+
+# Named agent profiles. Select one with `jcode --profile review`, switch
+# mid-session with `/profile review` (takes effect at the next turn), or pass
+# `profile` in a subagent task. The active profile is stored in the session and
+# shown in the status bar. Unknown keys are startup errors.
+#
+# [profiles.review]
+# provider = "claude-api"              # optional route prefix for `model`
+# model = "claude-opus-4-6"
+# tools = ["read", "agentgrep", "ls"]  # allow-list, same rules as [tools] enabled
+# disabled_tools = []
+# system_prompt_file = "~/.jcode/prompts/review.md"
+# approval = "plan"                    # "plan" = approve a plan first, "auto" = run tools directly
+# max_turns = 30                       # overrides [agent] limits
+#
+# [profiles.quickfix]
+# model = "claude-haiku-4-5"
+# tools = ["*"]
 
 [agents]
 # Defaults for spawned helper agents (swarm workers, subagents, sidecars).
@@ -332,6 +518,13 @@ swarm_max_concurrent_agents = 32
 # Env override: JCODE_MEMORY_MODEL
 # memory_model = "claude-haiku-4"
 #
+# Model for background work: compaction summaries, aside summaries, /remember
+# condensing. Unset = the provider's cheap model (claude-haiku-4-5 on Claude,
+# gpt-5-mini on OpenAI/Copilot, the session model elsewhere). "inherit" = always
+# the session model. Falls back to the session model if the cheap one fails.
+# Env override: JCODE_BACKGROUND_MODEL
+# background_model = "claude-haiku-4-5"
+#
 # Whether the memory sidecar (LLM precision judge) handles relevance/extraction.
 # Default true: the LLM precision-judge path is the only reliably productive
 # memory mode. Set false only to opt into the lower-precision no-LLM hybrid path.
@@ -364,6 +557,31 @@ swarm_max_concurrent_agents = 32
 # memory_embedding_base_url = "https://api.openai.com/v1"
 # memory_embedding_dim = 1536
 
+[memory]
+# Limits for the memories injected into a request. Candidates are ranked by a
+# weighted mean of embedding similarity to the current user message, recency,
+# reinforcement strength, and a per-category score, then added in score order
+# while they fit both limits. Each injected memory carries a one-line
+# provenance. `/context memories` shows the last injection with its score
+# breakdown and the entries that just missed the cut.
+# Env overrides: JCODE_MEMORY_INJECTION_TOKEN_BUDGET, JCODE_MEMORY_INJECTION_MAX_ENTRIES
+injection_token_budget = 600
+injection_max_entries = 5
+# Age (days) at which the recency score halves
+recency_half_life_days = 30.0
+
+[memory.weights]
+similarity = 0.6
+recency = 0.15
+strength = 0.1
+category = 0.15
+
+[memory.category_weights]
+correction = 1.0
+preference = 0.8
+fact = 0.6
+entity = 0.5
+
 [terminal]
 # External command that takes over headed session spawns (swarm agents,
 # resume-in-new-terminal, self-dev windows, restart restores).
@@ -424,6 +642,24 @@ swarm_max_concurrent_agents = 32
 # macOS Notification Center sound played on completion (e.g. "Glass", "Ping",
 # "Hero"). Empty string disables the sound. Ignored on non-macOS. (default: "Glass")
 # turn_complete_sound = "Glass"
+#
+# Where each event goes. Events: turn_complete, approval_needed, cycle_summary.
+# Backends: desktop, ntfy, email, telegram, discord, jade_relay, or a webhook
+# name. Without an entry, turn_complete goes to desktop and the ambient events
+# go to every configured [safety] backend. A failing backend never blocks the
+# others; try them with `jcode debug notify-test <event>`.
+# [notifications.routes]
+# turn_complete = ["desktop"]
+# approval_needed = ["ntfy", "telegram"]
+#
+# Webhooks receive a JSON POST. In `template`, {{event}}, {{title}}, {{body}}
+# (sanitized), {{details}} (full text), and {{priority}} are replaced with
+# JSON-escaped text; leave it out for a flat object with those fields.
+# [[notifications.webhooks]]
+# name = "slack"
+# url = "https://hooks.slack.com/services/..."
+# template = '{"text": "{{title}}\n{{body}}"}'
+# headers = { Authorization = "Bearer ..." }
 
 [hooks]
 # Lifecycle hooks: external commands jcode runs at well-defined points so other
@@ -473,6 +709,31 @@ swarm_max_concurrent_agents = 32
 # JCODE_HOOK_STATUS, JCODE_HOOK_DURATION_MS, JCODE_HOOK_OUTPUT_BYTES,
 # JCODE_HOOK_ERROR.
 # post_tool = ""
+#
+# Matcher-scoped rules. Each names an event ("pre_tool", "post_tool",
+# "turn_end"), optional tool-name globs (`tools`, e.g. "edit", "mcp__*") and
+# path globs (`paths`; `*` stays within a directory, `**` crosses them, a
+# trailing `/` covers a whole directory), and a command that gets the event as
+# JSON on stdin (tool_name, tool_input, paths, and tool_result for post_tool).
+# A matching pre_tool rule fails closed: any non-zero exit or timeout blocks
+# the call, with stderr shown to the model. post_tool rules are awaited, so a
+# formatter finishes before the next step. Default timeout_ms is 10000.
+# Projects can add rules as [[hooks]] entries in .jcode/hooks.toml. They only
+# run after you review the file and run `jcode hooks trust` in the project;
+# any later change to the file needs a new `jcode hooks trust`. Every rule
+# run is recorded in ~/.jcode/logs/hooks.jsonl.
+# [[hooks.rules]]
+# event = "post_tool"
+# tools = ["edit", "write", "multiedit", "apply_patch"]
+# paths = ["*.rs"]
+# command = "cargo fmt"
+# timeout_ms = 30000
+#
+# [[hooks.rules]]
+# event = "pre_tool"
+# tools = ["edit", "write", "multiedit", "apply_patch"]
+# paths = ["generated/"]
+# command = "sh -c 'echo generated/ is produced by codegen, edit the schema instead >&2; exit 1'"
 
 [ambient]
 # Ambient mode: background agent that maintains your codebase
@@ -492,6 +753,13 @@ min_interval_minutes = 5
 max_interval_minutes = 120
 # Pause ambient when user has active session
 pause_on_active_session = true
+# Skip cycles when interactive use burned more than this many percentage points
+# of a subscription window in the past hour; shrink them above half of it
+interactive_usage_threshold_percent = 10.0
+# Never let ambient push a subscription window (e.g. the 5h window) past this %
+usage_reserve_percent = 70.0
+# Defer cycles into hours that were historically quiet, learned from usage history
+prefer_quiet_hours = true
 # Enable proactive work (new features, refactoring) vs garden-only (lint, format, deps)
 proactive_work = true
 # Branch prefix for proactive work
@@ -507,6 +775,12 @@ port = 7643
 # Bind address (0.0.0.0 for LAN/Tailscale reachability)
 bind_addr = "0.0.0.0"
 
+[server]
+# Serve Prometheus text-format metrics (turns, tokens, tool calls, sessions,
+# ambient cycles, rate limits, provider fallbacks) at http://<addr>/metrics.
+# Unset by default; keep it on loopback unless you put auth in front of it.
+# metrics_listen = "127.0.0.1:9187"
+
 [power]
 # Prevent the machine from suspending (idle/lid sleep) while any jcode session
 # is actively streaming/processing. The display can still sleep; only system
@@ -514,16 +788,197 @@ bind_addr = "0.0.0.0"
 # Set JCODE_DISABLE_POWER_INHIBIT=1 to force-disable regardless of this setting.
 prevent_sleep_while_streaming = true
 
+[todo]
+# Mirror the project todos (.jcode/todos.json) into a markdown file so people
+# who don't run jcode can see them. jcode only rewrites the section between the
+# <!-- jcode:todos:start --> and <!-- jcode:todos:end --> markers; checking a
+# box or editing an item there is read back at session start and turn
+# boundaries. Conflicts resolve last-writer-wins. (default: unset, no sync)
+# sync_file = "TODO.md"
+
+[prompt]
+# Layers of the static system prompt, in order. "base" is the built-in prompt,
+# "global" is ~/.jcode/JCODE.md (plus legacy ~/AGENTS.md), "project" is the
+# first instruction file found walking up from the working directory to the
+# repo root, and "nested" adds instruction files from subdirectories the agent
+# has worked in. Drop a name to skip that layer. `/prompt show` prints the
+# assembled result with per-layer token counts.
+sources = ["base", "global", "project", "nested"]
+# File names checked in each directory, first match wins
+project_files = ["JCODE.md", "AGENTS.md", "CLAUDE.md"]
+# Combined size cap for instruction files (chars); extra layers are truncated
+# or skipped with a warning
+max_instruction_chars = 100000
+# Add a per-turn git snapshot (branch, ahead/behind, dirty files, last five
+# commits) to the dynamic part of the system prompt. Skipped outside git repos.
+# git_context = false
+# Describe this machine (OS, shell, CPUs, memory, tool versions) once in the
+# static system prompt, so the model doesn't probe it with bash every session.
+# Probed at server start; `/env refresh` re-probes, `/env` shows the result.
+environment = true
+# Tools listed with their versions. Bare names run `<name> --version`; add
+# arguments for tools that differ, e.g. "go version" or "java -version".
+environment_tools = ["git", "rg", "fd", "node", "npm", "python3", "cargo", "rustc", "make", "docker"]
+
+[prompt.sections]
+# Which half of the system prompt each optional section goes in: "static" (the
+# cached prefix), "dynamic" (rebuilt every request) or "off", plus an optional
+# max_tokens budget. Sections are date, git, memory, todos, environment and
+# usage (tokens used so far). Sections that change every turn (git, memory,
+# todos, usage) belong in dynamic; placing them in static busts the cache on
+# each request and is warned about. `/prompt sections` shows each section's
+# size and half, and the hit-rate change measured after a layout change.
+# date = { place = "dynamic" }
+# todos = { place = "dynamic", max_tokens = 500 }
+# environment = { place = "static", max_tokens = 400 }
+
+[output]
+# Append the final assistant message of every turn to this file, each under a
+# timestamp and session header. Writes are append-only and flushed to disk.
+# `/tee <path|off>` changes it for the current session. (default: unset)
+# tee_file = "~/jcode-responses.log"
+
+[privacy]
+# Replace emails, IPv4/IPv6 addresses, AWS keys, JWTs and known secret formats
+# in your messages and in tool results with placeholders like <EMAIL_1> before
+# they reach the provider. The originals stay in a local per-session mapping;
+# `/redaction` shows it and `/redaction test <text>` previews a pass.
+# (default: false)
+# redact = false
+# Extra regexes, redacted as <CUSTOM_n>. With a capture group, only the first
+# group is replaced. (default: [])
+# redact_patterns = ['corp-[a-z0-9]+\.internal', 'customer_id=(\d+)']
+
+[git]
+# Commit at the end of every turn that modified files, for review and rollback.
+# Only the files the agent's tools wrote are committed; anything else you have
+# staged stays staged and out of the commit. `/autocommit on|off` changes it
+# for the current session. (default: false)
+# auto_commit = false
+# Branch for the commits, created from HEAD if missing and advanced without
+# being checked out. Unset commits onto the current branch.
+# auto_commit_branch = "jcode/auto"
+# Record the session, turn and tool call behind every line the agent's file
+# tools write in .jcode/provenance.jsonl at the repository root. Later edits
+# shift the recorded ranges. `jcode blame <file> [line]` joins it with git
+# blame and shows the instruction that led to each change. (default: false)
+# provenance = false
+
+[rebuild]
+# /rebuild runs only the tests affected by changes since the last promoted
+# build: changed files map to their crate plus every crate depending on it,
+# joined with a small smoke set. Changes it cannot map (Cargo.toml, Cargo.lock,
+# build.rs, src/main.rs, unknown paths) run the full suite, and the rebuild
+# output says which subset ran and why. `/rebuild --full-tests` forces the
+# full suite once; set this to false to always run it.
+selective_tests = true
+# Packages tested on every selective run (default: jcode-protocol, jcode-config-types)
+# smoke_packages = ["jcode-protocol", "jcode-config-types"]
+# Extra paths that force the full suite / never need tests. "dir/" matches a
+# directory, "*.ext" an extension, a bare name that file anywhere.
+# full_suite_paths = ["migrations/"]
+# ignored_paths = ["notes/"]
+# Path -> packages overrides, checked before the built-in rules
+# `jcode promote` refuses (without --force) a canary that crashed, was rolled
+# back, or has logged fewer clean session-hours than this. Events are kept in
+# ~/.jcode/builds/canary-events.jsonl; `jcode debug canary` summarizes them.
+promote_min_clean_hours = 1.0
+# [rebuild.test_map]
+# "crates/jcode-tui-markdown/" = ["jcode-tui-markdown", "jcode-tui"]
+
+[update]
+# Channel for `jcode update` and background update checks: "stable" (tagged
+# GitHub releases) or "nightly" (the latest prerelease). Installs without a jcode
+# git checkout (Homebrew, `cargo install`) update from these release assets:
+# the download is staged, checked against the published SHA256SUMS, and only
+# promoted after the new binary passes `jcode --version`. Repo and self-dev
+# installs keep updating through git. `jcode update --channel nightly` sets this.
+# Unset falls back to [features] update_channel.
+# channel = "stable"
+
+[network]
+# Every HTTP request jcode makes (providers, web tools, update checks, the
+# embedding backend, notification webhooks) is checked against allowed_hosts.
+# "enforce" denies and logs anything else; "observe" only logs and counts it.
+# Unset, the mode is "enforce" once allowed_hosts is set and "observe" while
+# it is empty, so web tools keep working until you opt in.
+# `jcode debug network` lists the destinations seen by the running server.
+# Also overridable via JCODE_NETWORK_MODE.
+# mode = "enforce"
+# Exact hosts, "*.example.com" for a domain and its subdomains, or "*" for any.
+# Empty (the default) allows the configured providers, MCP endpoints,
+# notification targets, and jcode's own services. Loopback is always allowed.
+# Bash commands see the effective list in JCODE_NETWORK_ALLOWED_HOSTS. In
+# enforce mode they also get HTTP(S)_PROXY set to a local proxy that applies
+# the same list.
+# allowed_hosts = ["api.anthropic.com", "*.githubusercontent.com"]
+
+[autodebug]
+# When one tool keeps failing with a similar error, a cheap sidecar model looks
+# at the failed calls and the files they touched, and its diagnosis is injected
+# into the next turn as an "auto-debug:" note. Analyses are recorded in the
+# session. Env override: JCODE_AUTODEBUG_ENABLED.
+enabled = true
+# Failures of the same tool with a similar error before an analysis runs
+failure_threshold = 3
+# Analyses per session
+max_analyses_per_session = 3
+# When a turn ends in an error (not a cancellation), add a three-line
+# post-mortem (attempted, failed, next step) to the session so a resumed
+# session starts with that context. Env override: JCODE_AUTODEBUG_POST_MORTEMS.
+post_mortems = true
+
+[skills]
+# Load skills whose SKILL.md `triggers` (file globs, keywords, tool names) match
+# the session: files in the working directory at start, files and tools used
+# since, and the user's messages. Active skills are noted in the system prompt
+# and listed by /skills. Env override: JCODE_SKILLS_AUTO_LOAD.
+auto_load = true
+# Skills auto-loaded at once
+max_auto_loaded = 3
+# Estimated tokens all auto-loaded skill instructions may use
+auto_load_token_budget = 6000
+
+[storage]
+# Where sessions and memories live: "files" (one JSON snapshot + journal per
+# session) or "sqlite" (~/.jcode/jcode.db, WAL mode, indexed listing/search,
+# appends new messages instead of rewriting the session). Move existing data
+# with `jcode storage migrate --to sqlite` (or `--to files`) before switching.
+# Env override: JCODE_STORAGE_BACKEND.
+backend = "files"
+
+[telegram]
+# `jcode telegram` runs a bot that lets you drive sessions from Telegram:
+# messages go to a session on the server (one per chat unless `session` is set),
+# replies stream back as edited messages, and permission requests become
+# Approve/Deny buttons. Send /stop to detach a chat from its session.
+# bot_token = ""  # From @BotFather; defaults to [safety] telegram_bot_token
+# Only these Telegram user IDs are served; other senders are ignored and logged.
+# Env override: JCODE_TELEGRAM_ALLOWED_USER_IDS (comma-separated).
+allowed_user_ids = []
+# session = ""  # Route every chat to this existing session
+# Minimum milliseconds between edits of a streaming reply
+edit_interval_ms = 1500
+
 [safety]
 # Notification settings for ambient mode events
 
 # ntfy.sh push notifications (free, phone app: https://ntfy.sh)
 # ntfy_topic = "jcode-ambient-your-secret-topic"
 # ntfy_server = "https://ntfy.sh"
+# Access token (or a full "Basic ..." header) for protected self-hosted topics.
+# Priorities map to ntfy levels: routine 3, approvals/errors 4, critical 5.
+# ntfy_token = ""  # Prefer JCODE_NTFY_TOKEN env var
 
 # Desktop notifications via notify-send (default: true)
 desktop_notifications = true
 
+# How long an `ask_user` question waits when no client is attached (e.g.
+# `jcode run`) before the agent proceeds on its own best judgment. The
+# question is listed in the permission inbox and sent to the notification
+# backends meanwhile. Env override: JCODE_ASK_USER_TIMEOUT_SECS.
+# ask_user_timeout_secs = 300
+
 # Email notifications via SMTP
 # email_enabled = false
 # email_to = "you@example.com"
@@ -567,11 +1022,8 @@ desktop_notifications = true
             jcode_config_types::default_binding("effort_increase", p).unwrap_or("alt+right");
         let effort_decrease =
             jcode_config_types::default_binding("effort_decrease", p).unwrap_or("alt+left");
-        let default_content = default_content
+        default_content
             .replace("@EFFORT_INCREASE@", effort_increase)
-            .replace("@EFFORT_DECREASE@", effort_decrease);
-
-        std::fs::write(&path, default_content)?;
-        Ok(path)
+            .replace("@EFFORT_DECREASE@", effort_decrease)
     }
 }
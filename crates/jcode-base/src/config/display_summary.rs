@@ -44,6 +44,7 @@ impl Config {
 - Markdown spacing: {}
 - Pin images: {}
 - Diff line wrap: {}
+- Side pane width: {}%
 - Queue mode: {}
 - Auto server reload: {}
 - Mouse capture: {}
@@ -59,6 +60,7 @@ impl Config {
 - Redraw FPS: {}
 - Copy badge Alt label: {}
 - Show agentgrep output: {}
+- Message footers: {}
 
 **Features:**
 - Memory: {}
@@ -68,6 +70,10 @@ impl Config {
 - KV cache miss notices: {}
 - Update channel: {}
 
+**Network:**
+- Mode: {}
+- Allowed hosts: {}
+
 **Tools:**
 - Profile: {}
 - Enabled allow-list: {}
@@ -92,6 +98,7 @@ impl Config {
 - Review: {}
 - Judge: {}
 - Memory: {}
+- Background: {}
 - Memory sidecar: {}
 - Ambient: {}
 
@@ -99,12 +106,16 @@ impl Config {
 - Enabled: {}
 - Bind address: {}:{}
 
+**Server:**
+- Metrics: {}
+
 **Ambient:**
 - Enabled: {}
 - Provider: {}
 - Model: {}
 - Interval: {}-{} minutes
 - Pause on active session: {}
+- Usage-aware scheduling: skip above {}%/h interactive, {}% reserve, quiet hours {}
 - Proactive work: {}
 - Work branch prefix: `{}`
 - Visible mode: {}
@@ -158,6 +169,7 @@ impl Config {
             self.display.markdown_spacing.label(),
             self.display.pin_images,
             self.display.diff_line_wrap,
+            self.display.side_pane_ratio,
             self.display.queue_mode,
             self.display.auto_server_reload,
             self.display.mouse_capture,
@@ -185,12 +197,19 @@ impl Config {
                 self.display.copy_badge_alt_label.trim()
             },
             self.display.show_agentgrep_output,
+            self.display.message_footers,
             self.features.memory,
             self.features.swarm,
             self.features.message_timestamps,
             self.features.persist_memory_injections,
             self.features.kv_cache_miss_notices,
-            self.features.update_channel,
+            self.update_channel(),
+            self.network.effective_mode().as_str(),
+            if self.network.allowed_hosts.is_empty() {
+                "(defaults)".to_string()
+            } else {
+                self.network.allowed_hosts.join(", ")
+            },
             if self.tools.profile.trim().is_empty() {
                 "full"
             } else {
@@ -255,6 +274,10 @@ impl Config {
                 .memory_model
                 .as_deref()
                 .unwrap_or("(sidecar auto-select)"),
+            self.agents
+                .background_model
+                .as_deref()
+                .unwrap_or("(provider's cheap model)"),
             if self.agents.memory_sidecar_enabled {
                 "enabled"
             } else {
@@ -267,6 +290,11 @@ impl Config {
             self.gateway.enabled,
             self.gateway.bind_addr,
             self.gateway.port,
+            self.server
+                .metrics_listen
+                .as_deref()
+                .map(|addr| format!("http://{}/metrics", addr))
+                .unwrap_or_else(|| "off".to_string()),
             self.ambient.enabled,
             self.ambient.provider.as_deref().unwrap_or("(auto)"),
             self.ambient
@@ -276,6 +304,9 @@ impl Config {
             self.ambient.min_interval_minutes,
             self.ambient.max_interval_minutes,
             self.ambient.pause_on_active_session,
+            self.ambient.interactive_usage_threshold_percent,
+            self.ambient.usage_reserve_percent,
+            self.ambient.prefer_quiet_hours,
             self.ambient.proactive_work,
             self.ambient.work_branch_prefix,
             self.ambient.visible,
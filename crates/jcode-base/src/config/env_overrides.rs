@@ -80,9 +80,27 @@ impl Config {
         if let Ok(v) = std::env::var("JCODE_INFO_WIDGET_TOGGLE_KEY") {
             self.keybindings.info_widget_toggle = v;
         }
+        if let Ok(v) = std::env::var("JCODE_TODO_CHECKLIST_TOGGLE_KEY") {
+            self.keybindings.todo_checklist_toggle = v;
+        }
         if let Ok(v) = std::env::var("JCODE_NEW_TERMINAL_KEY") {
             self.keybindings.new_terminal = v;
         }
+        if let Ok(v) = std::env::var("JCODE_INTERJECT_KEY") {
+            self.keybindings.interject = v;
+        }
+        if let Ok(v) = std::env::var("JCODE_IMAGE_OPEN_KEY") {
+            self.keybindings.image_open = v;
+        }
+        if let Ok(v) = std::env::var("JCODE_REMEMBER_MESSAGE_KEY") {
+            self.keybindings.remember_message = v;
+        }
+        if let Ok(v) = std::env::var("JCODE_COPY_MESSAGE_KEY") {
+            self.keybindings.copy_message = v;
+        }
+        if let Ok(v) = std::env::var("JCODE_FILE_PICKER_KEY") {
+            self.keybindings.file_picker = v;
+        }
 
         // Dictation
         if let Ok(v) = std::env::var("JCODE_DICTATION_COMMAND") {
@@ -134,6 +152,11 @@ impl Config {
                 self.acp.tool_profile = trimmed.to_string();
             }
         }
+        if let Ok(v) = std::env::var("JCODE_ACP_APPROVALS")
+            && let Some(parsed) = parse_env_bool(&v)
+        {
+            self.acp.approvals = parsed;
+        }
 
         // Display
         if let Ok(v) = std::env::var("JCODE_DIFF_MODE") {
@@ -182,6 +205,16 @@ impl Config {
                 self.display.auto_server_reload = parsed;
             }
         }
+        if let Ok(v) = std::env::var("JCODE_MIGRATION_GRACE_SECS") {
+            if let Ok(parsed) = v.trim().parse::<u64>() {
+                self.display.migration_grace_secs = parsed;
+            }
+        }
+        if let Ok(v) = std::env::var("JCODE_TMUX_STATUS") {
+            if let Some(parsed) = parse_env_bool(&v) {
+                self.display.tmux_status = parsed;
+            }
+        }
         if let Ok(v) = std::env::var("JCODE_MOUSE_CAPTURE") {
             if let Some(parsed) = parse_env_bool(&v) {
                 self.display.mouse_capture = parsed;
@@ -253,6 +286,11 @@ impl Config {
                 self.display.show_agentgrep_output = parsed;
             }
         }
+        if let Ok(v) = std::env::var("JCODE_MESSAGE_FOOTERS") {
+            if let Some(parsed) = parse_env_bool(&v) {
+                self.display.message_footers = parsed;
+            }
+        }
         if let Ok(v) = std::env::var("JCODE_CHAT_NATIVE_SCROLLBAR") {
             if let Some(parsed) = parse_env_bool(&v) {
                 self.display.native_scrollbars.chat = parsed;
@@ -265,6 +303,54 @@ impl Config {
         }
 
         // Features
+        if let Ok(v) = std::env::var("JCODE_PRUNE_SUPERSEDED_READS")
+            && let Some(parsed) = parse_env_bool(&v)
+        {
+            self.compaction.prune_superseded_reads = parsed;
+        }
+        if let Ok(v) = std::env::var("JCODE_PROMPT_GIT_CONTEXT")
+            && let Some(parsed) = parse_env_bool(&v)
+        {
+            self.prompt.git_context = parsed;
+        }
+        if let Ok(v) = std::env::var("JCODE_PROMPT_ENVIRONMENT")
+            && let Some(parsed) = parse_env_bool(&v)
+        {
+            self.prompt.environment = parsed;
+        }
+        if let Ok(v) = std::env::var("JCODE_OUTPUT_TEE_FILE") {
+            let trimmed = v.trim();
+            self.output.tee_file = (!trimmed.is_empty()).then(|| trimmed.to_string());
+        }
+        if let Ok(v) = std::env::var("JCODE_PRIVACY_REDACT") {
+            if let Some(parsed) = parse_env_bool(&v) {
+                self.privacy.redact = parsed;
+            }
+        }
+        if let Ok(v) = std::env::var("JCODE_GIT_AUTO_COMMIT") {
+            if let Some(parsed) = parse_env_bool(&v) {
+                self.git.auto_commit = parsed;
+            }
+        }
+        if let Ok(v) = std::env::var("JCODE_GIT_AUTO_COMMIT_BRANCH") {
+            let trimmed = v.trim();
+            self.git.auto_commit_branch = (!trimmed.is_empty()).then(|| trimmed.to_string());
+        }
+        if let Ok(v) = std::env::var("JCODE_GIT_PROVENANCE") {
+            if let Some(parsed) = parse_env_bool(&v) {
+                self.git.provenance = parsed;
+            }
+        }
+        if let Ok(v) = std::env::var("JCODE_MEMORY_INJECTION_MAX_ENTRIES") {
+            if let Ok(parsed) = v.trim().parse::<usize>() {
+                self.memory.injection_max_entries = parsed;
+            }
+        }
+        if let Ok(v) = std::env::var("JCODE_MEMORY_INJECTION_TOKEN_BUDGET") {
+            if let Ok(parsed) = v.trim().parse::<usize>() {
+                self.memory.injection_token_budget = parsed;
+            }
+        }
         if let Ok(v) = std::env::var("JCODE_MEMORY_ENABLED") {
             if let Some(parsed) = parse_env_bool(&v) {
                 self.features.memory = parsed;
@@ -293,7 +379,12 @@ impl Config {
         if let Ok(v) = std::env::var("JCODE_UPDATE_CHANNEL")
             && let Some(channel) = UpdateChannel::parse(&v)
         {
-            self.features.update_channel = channel;
+            self.update.channel = Some(channel);
+        }
+        if let Ok(v) = std::env::var("JCODE_NETWORK_MODE")
+            && let Some(mode) = NetworkMode::parse(&v)
+        {
+            self.network.mode = Some(mode);
         }
 
         // Agents (spawned helper sessions)
@@ -323,6 +414,14 @@ impl Config {
                 Some(trimmed.to_string())
             };
         }
+        if let Ok(v) = std::env::var("JCODE_BACKGROUND_MODEL") {
+            let trimmed = v.trim();
+            self.agents.background_model = if trimmed.is_empty() {
+                None
+            } else {
+                Some(trimmed.to_string())
+            };
+        }
         if let Ok(v) = std::env::var("JCODE_MEMORY_SIDECAR_ENABLED") {
             if let Some(parsed) = parse_env_bool(&v) {
                 self.agents.memory_sidecar_enabled = parsed;
@@ -483,6 +582,32 @@ impl Config {
             };
         }
 
+        // Autodebug
+        if let Ok(v) = std::env::var("JCODE_AUTODEBUG_ENABLED") {
+            if let Some(parsed) = parse_env_bool(&v) {
+                self.autodebug.enabled = parsed;
+            }
+        }
+        if let Ok(v) = std::env::var("JCODE_AUTODEBUG_POST_MORTEMS") {
+            if let Some(parsed) = parse_env_bool(&v) {
+                self.autodebug.post_mortems = parsed;
+            }
+        }
+
+        // Skills
+        if let Ok(v) = std::env::var("JCODE_SKILLS_AUTO_LOAD") {
+            if let Some(parsed) = parse_env_bool(&v) {
+                self.skills.auto_load = parsed;
+            }
+        }
+
+        // Storage
+        if let Ok(v) = std::env::var("JCODE_STORAGE_BACKEND")
+            && let Some(backend) = StorageBackend::parse(&v)
+        {
+            self.storage.backend = backend;
+        }
+
         // Ambient
         if let Ok(v) = std::env::var("JCODE_AMBIENT_ENABLED") {
             if let Some(parsed) = parse_env_bool(&v) {
@@ -518,6 +643,14 @@ impl Config {
         if let Ok(v) = std::env::var("JCODE_NTFY_SERVER") {
             self.safety.ntfy_server = v;
         }
+        if let Ok(v) = std::env::var("JCODE_NTFY_TOKEN") {
+            self.safety.ntfy_token = Some(v);
+        }
+        if let Ok(v) = std::env::var("JCODE_ASK_USER_TIMEOUT_SECS") {
+            if let Ok(parsed) = v.trim().parse::<u64>() {
+                self.safety.ask_user_timeout_secs = parsed;
+            }
+        }
         if let Ok(v) = std::env::var("JCODE_SMTP_PASSWORD") {
             self.safety.email_password = Some(v);
         }
@@ -545,6 +678,12 @@ impl Config {
                 self.safety.telegram_reply_enabled = parsed;
             }
         }
+        if let Ok(v) = std::env::var("JCODE_TELEGRAM_ALLOWED_USER_IDS") {
+            self.telegram.allowed_user_ids = v
+                .split(',')
+                .filter_map(|id| id.trim().parse().ok())
+                .collect();
+        }
         if let Ok(v) = std::env::var("JCODE_DISCORD_BOT_TOKEN") {
             self.safety.discord_bot_token = Some(v);
             self.safety.discord_enabled = true;
@@ -622,6 +761,12 @@ impl Config {
             }
         }
 
+        // Server metrics
+        if let Ok(v) = std::env::var("JCODE_METRICS_LISTEN") {
+            let trimmed = v.trim();
+            self.server.metrics_listen = (!trimmed.is_empty()).then(|| trimmed.to_string());
+        }
+
         // Power management
         if let Ok(v) = std::env::var("JCODE_PREVENT_SLEEP_WHILE_STREAMING") {
             if let Some(parsed) = parse_env_bool(&v) {
@@ -663,6 +808,16 @@ impl Config {
                 self.provider.openai_service_tier = Some(trimmed);
             }
         }
+        if let Ok(v) = std::env::var("JCODE_OPENAI_RESUMABLE_STREAMS")
+            && let Some(parsed) = parse_env_bool(&v)
+        {
+            self.provider.openai_resumable_streams = parsed;
+        }
+        if let Ok(v) = std::env::var("JCODE_OPENAI_BACKGROUND_AFTER_RECONNECT")
+            && let Some(parsed) = parse_env_bool(&v)
+        {
+            self.provider.openai_background_after_reconnect = parsed;
+        }
         if let Ok(v) = std::env::var("JCODE_OPENAI_NATIVE_COMPACTION_MODE") {
             let trimmed = v.trim().to_ascii_lowercase();
             if !trimmed.is_empty() {
@@ -676,6 +831,12 @@ impl Config {
                 }
             }
         }
+        if let Ok(v) = std::env::var("JCODE_ANTHROPIC_SERVER_TOOLS") {
+            self.provider.anthropic.server_tools = parse_env_list(&v)
+                .into_iter()
+                .filter_map(|item| AnthropicServerTool::parse(&item))
+                .collect();
+        }
         if let Ok(v) = std::env::var("JCODE_PRESERVE_REASONING_CONTEXT") {
             if let Some(parsed) = parse_env_bool(&v) {
                 self.provider.preserve_reasoning_context = parsed;
@@ -686,6 +847,11 @@ impl Config {
                 self.provider.cross_provider_failover = mode;
             }
         }
+        if let Ok(v) = std::env::var("JCODE_TOOL_FALLBACK") {
+            if let Some(mode) = ToolFallbackMode::parse(&v) {
+                self.provider.tool_fallback = mode;
+            }
+        }
         if let Ok(v) = std::env::var("JCODE_SAME_PROVIDER_ACCOUNT_FAILOVER") {
             if let Some(enabled) = parse_env_bool(&v) {
                 self.provider.same_provider_account_failover = enabled;
@@ -698,6 +864,26 @@ impl Config {
                 }
             }
         }
+        if let Ok(v) = std::env::var("JCODE_PROVIDER_IDLE_TIMEOUT_SECS")
+            && let Ok(parsed) = v.trim().parse::<u64>()
+        {
+            self.provider.idle_timeout_secs = parsed;
+        }
+        if let Ok(v) = std::env::var("JCODE_DETERMINISTIC")
+            && let Some(parsed) = parse_env_bool(&v)
+        {
+            self.provider.deterministic = parsed;
+        }
+        if let Ok(v) = std::env::var("JCODE_DETERMINISTIC_SEED")
+            && let Ok(parsed) = v.trim().parse::<u64>()
+        {
+            self.provider.deterministic_seed = parsed;
+        }
+        if let Ok(v) = std::env::var("JCODE_COPILOT_MONTHLY_PREMIUM_BUDGET") {
+            if let Ok(parsed) = v.trim().parse::<u64>() {
+                self.provider.copilot.monthly_premium_budget = (parsed > 0).then_some(parsed);
+            }
+        }
 
         // Copilot premium mode: env var overrides config
         // If set in config but not in env, propagate config -> env
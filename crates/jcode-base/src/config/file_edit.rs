@@ -0,0 +1,119 @@
+//! Line-level edits to config.toml that keep comments and layout intact.
+//!
+//! `Config::save` re-serializes the whole file and drops every comment, so
+//! anything that writes into a hand-edited or template-generated config goes
+//! through these helpers instead.
+
+use super::*;
+use std::path::PathBuf;
+
+/// `raw` as written when it is a TOML value, otherwise as a quoted string.
+pub fn toml_value_literal(raw: &str) -> String {
+    let raw = raw.trim();
+    let parses = format!("value = {}", raw)
+        .parse::<toml::Table>()
+        .is_ok_and(|table| table.contains_key("value"));
+    if parses {
+        raw.to_string()
+    } else {
+        toml::Value::String(raw.to_string()).to_string()
+    }
+}
+
+/// Replace `leaf` under `[table]`, or add it (and the table) when missing.
+pub fn upsert_key(content: &str, table: &str, leaf: &str, value: &str) -> String {
+    let mut lines = split_lines_lossy(content);
+    let entry = format!("{} = {}", leaf, value);
+    let header = format!("[{}]", table);
+    let Some(start) = lines.iter().position(|line| line.trim() == header) else {
+        if lines.last().is_some_and(|line| !line.trim().is_empty()) {
+            lines.push(String::new());
+        }
+        lines.push(header);
+        lines.push(entry);
+        return join_lines(lines);
+    };
+
+    let end = lines
+        .iter()
+        .enumerate()
+        .skip(start + 1)
+        .find(|(_, line)| is_toml_header(line))
+        .map(|(idx, _)| idx)
+        .unwrap_or(lines.len());
+    if let Some(line) = lines[start + 1..end]
+        .iter_mut()
+        .find(|line| line_has_toml_key(line, leaf))
+    {
+        *line = entry;
+    } else {
+        // Keep the key next to its table's other keys, above trailing blanks.
+        let insert_at = lines[start + 1..end]
+            .iter()
+            .rposition(|line| !line.trim().is_empty())
+            .map(|idx| start + 2 + idx)
+            .unwrap_or(start + 1);
+        lines.insert(insert_at, entry);
+    }
+    join_lines(lines)
+}
+
+pub fn is_toml_header(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.starts_with('[') && trimmed.ends_with(']')
+}
+
+pub fn line_has_toml_key(line: &str, key: &str) -> bool {
+    let trimmed = line.trim_start();
+    let Some(rest) = trimmed.strip_prefix(key) else {
+        return false;
+    };
+    rest.trim_start().starts_with('=')
+}
+
+pub fn split_lines_lossy(content: &str) -> Vec<String> {
+    content.lines().map(ToString::to_string).collect()
+}
+
+pub fn join_lines(lines: Vec<String>) -> String {
+    let mut joined = lines.join("\n");
+    if !joined.is_empty() {
+        joined.push('\n');
+    }
+    joined
+}
+
+impl Config {
+    /// Set several `table.key` entries in config.toml in place. `values` are
+    /// TOML literals (see [`toml_value_literal`]). A missing file starts from
+    /// the commented default template, so the result stays self-documenting.
+    pub fn set_file_keys(values: &[(&str, String)]) -> anyhow::Result<PathBuf> {
+        let path = Self::path().ok_or_else(|| anyhow::anyhow!("No config path"))?;
+        let mut content = if path.exists() {
+            std::fs::read_to_string(&path)?
+        } else {
+            Self::default_config_file_content()
+        };
+        for (key, value) in values {
+            let (table, leaf) = key
+                .rsplit_once('.')
+                .ok_or_else(|| anyhow::anyhow!("`{}` is not a `table.key` name", key))?;
+            content = upsert_key(&content, table, leaf, value);
+        }
+
+        let (_, issues) = Self::parse_lenient(&content, Some(&path))?;
+        if let Some(issue) = issues
+            .iter()
+            .find(|issue| values.iter().any(|(key, _)| issue.key == *key))
+        {
+            anyhow::bail!("{}", issue);
+        }
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, content)?;
+        Self::invalidate_cache();
+        Ok(path)
+    }
+}
@@ -0,0 +1,262 @@
+//! Lenient config.toml parsing with precise diagnostics.
+//!
+//! Every config section denies unknown fields, so a typo like
+//! `pause_on_active_sesion` fails deserialization instead of being silently
+//! dropped. Loading must not fall back to an all-default config over one bad
+//! key, though: [`Config::parse_lenient`] blanks the offending line (keeping
+//! line numbers stable), records a [`ConfigIssue`], and retries until the rest
+//! of the file parses.
+
+use super::*;
+use std::path::Path;
+
+/// Upper bound on blanked keys before giving up on a file.
+const MAX_RECOVERED_ISSUES: usize = 64;
+
+/// Longest multi-line value blanked when dropping a key.
+const MAX_VALUE_LINES: usize = 200;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigIssueKind {
+    /// The key is not part of its section's schema
+    UnknownKey,
+    /// The key exists but its value has the wrong type or an unknown variant
+    InvalidValue(String),
+}
+
+/// One key that loading ignored, with enough context to fix it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigIssue {
+    pub file: Option<PathBuf>,
+    /// 1-based line of the key in `file`
+    pub line: Option<usize>,
+    /// Dotted path, e.g. `ambient.pause_on_active_sesion`
+    pub key: String,
+    pub kind: ConfigIssueKind,
+    /// Nearest valid key (for unknown keys) or value (for unknown variants)
+    pub suggestion: Option<String>,
+}
+
+impl std::fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.file, self.line) {
+            (Some(file), Some(line)) => write!(f, "{}:{}: ", file.display(), line)?,
+            (Some(file), None) => write!(f, "{}: ", file.display())?,
+            (None, Some(line)) => write!(f, "line {}: ", line)?,
+            (None, None) => {}
+        }
+        match &self.kind {
+            ConfigIssueKind::UnknownKey => write!(f, "unknown key `{}`", self.key)?,
+            ConfigIssueKind::InvalidValue(message) => {
+                write!(f, "invalid value for `{}`: {}", self.key, message)?
+            }
+        }
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, " (did you mean `{}`?)", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+impl Config {
+    /// Parse config.toml text, skipping unknown or invalid keys.
+    ///
+    /// Returns the config built from every valid key plus one issue per
+    /// skipped key. TOML syntax errors (and anything that cannot be pinned
+    /// to a line) are returned as errors, since there is no sensible partial
+    /// config to fall back to.
+    pub fn parse_lenient(
+        content: &str,
+        file: Option<&Path>,
+    ) -> Result<(Self, Vec<ConfigIssue>), toml::de::Error> {
+        let mut lines: Vec<String> = content.lines().map(ToString::to_string).collect();
+        let mut issues = Vec::new();
+        loop {
+            let text = lines.join("\n");
+            let err = match toml::from_str::<Self>(&text) {
+                Ok(config) => return Ok((config, issues)),
+                Err(err) => err,
+            };
+            let Some(span) = err.span() else {
+                return Err(err);
+            };
+            if issues.len() >= MAX_RECOVERED_ISSUES || text.parse::<toml::Table>().is_err() {
+                return Err(err);
+            }
+            let index = text[..span.start.min(text.len())].matches('\n').count();
+            if lines.get(index).is_none_or(|line| line.trim().is_empty()) {
+                return Err(err);
+            }
+            let mut issue = describe_issue(&lines, index, err.message());
+            issue.file = file.map(Path::to_path_buf);
+            if !blank_entry(&mut lines, index) {
+                return Err(err);
+            }
+            issues.push(issue);
+        }
+    }
+
+    /// Whether `key` (dotted) names a real config setting.
+    ///
+    /// Used by `jcode config get/set` to reject typos with a suggestion
+    /// before touching the file.
+    pub fn check_key(key: &str) -> Result<(), ConfigIssue> {
+        // Probe with a value of the wrong type: a known key reports an
+        // invalid value (or parses), an unknown one reports an unknown field.
+        let probe = format!("{} = {{}}\n", key);
+        let Ok((_, issues)) = Self::parse_lenient(&probe, None) else {
+            return Ok(());
+        };
+        match issues.into_iter().next() {
+            Some(issue) if issue.kind == ConfigIssueKind::UnknownKey => Err(ConfigIssue {
+                key: key.to_string(),
+                ..issue
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn describe_issue(lines: &[String], index: usize, message: &str) -> ConfigIssue {
+    let message = message.lines().next().unwrap_or(message).trim();
+    let line = &lines[index];
+    let section = header_name(line).map(str::to_string).unwrap_or_else(|| {
+        lines[..index]
+            .iter()
+            .rev()
+            .find_map(|line| header_name(line))
+            .unwrap_or_default()
+            .to_string()
+    });
+    let key = match (header_name(line), line_key(line)) {
+        (Some(_), _) => section.clone(),
+        (None, Some(key)) if section.is_empty() => key.to_string(),
+        (None, Some(key)) => format!("{}.{}", section, key),
+        (None, None) => section.clone(),
+    };
+
+    let (kind, suggestion) = if let Some(rest) = message.strip_prefix("unknown field ") {
+        let names = backticked(rest);
+        let suggestion = names
+            .first()
+            .and_then(|unknown| nearest(unknown, &names[1..]));
+        (ConfigIssueKind::UnknownKey, suggestion)
+    } else if let Some(rest) = message.strip_prefix("unknown variant ") {
+        let names = backticked(rest);
+        let suggestion = names
+            .first()
+            .and_then(|unknown| nearest(unknown, &names[1..]));
+        (
+            ConfigIssueKind::InvalidValue(message.to_string()),
+            suggestion,
+        )
+    } else {
+        (ConfigIssueKind::InvalidValue(message.to_string()), None)
+    };
+
+    ConfigIssue {
+        file: None,
+        line: Some(index + 1),
+        key,
+        kind,
+        suggestion,
+    }
+}
+
+/// Blank the key/value (or whole table) starting at `index` so the rest of
+/// the file can be retried. Returns false when that cannot be done cleanly.
+fn blank_entry(lines: &mut [String], index: usize) -> bool {
+    if header_name(&lines[index]).is_some() {
+        // An unknown or invalid table: drop it with everything up to the
+        // next header, or its keys would be attributed to the table above.
+        lines[index].clear();
+        for line in lines.iter_mut().skip(index + 1) {
+            if header_name(line).is_some() {
+                break;
+            }
+            line.clear();
+        }
+        return true;
+    }
+
+    let original: Vec<String> = lines.to_vec();
+    for end in index..lines.len().min(index + MAX_VALUE_LINES) {
+        if end > index && header_name(&lines[end]).is_some() {
+            break;
+        }
+        lines[end].clear();
+        if lines.join("\n").parse::<toml::Table>().is_ok() {
+            return true;
+        }
+    }
+    lines.clone_from_slice(&original);
+    false
+}
+
+/// Table name of a `[table]` / `[[array]]` header line.
+fn header_name(line: &str) -> Option<&str> {
+    let trimmed = line.trim();
+    if !trimmed.starts_with('[') {
+        return None;
+    }
+    let end = trimmed.rfind(']')?;
+    let rest = trimmed[end + 1..].trim_start();
+    if !(rest.is_empty() || rest.starts_with('#')) {
+        return None;
+    }
+    let name = trimmed[..=end]
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .trim();
+    // `["a", "b"]` inside a multi-line array is not a header.
+    (!name.is_empty() && !name.contains(',')).then_some(name)
+}
+
+/// Key of a `key = value` line.
+fn line_key(line: &str) -> Option<&str> {
+    let (key, _) = line.split_once('=')?;
+    let key = key.trim().trim_matches('"').trim_matches('\'');
+    (!key.is_empty() && !key.contains('[') && !key.contains('{')).then_some(key)
+}
+
+/// Names quoted in backticks by serde messages, e.g. the unknown field
+/// followed by the expected ones.
+fn backticked(message: &str) -> Vec<String> {
+    message
+        .split('`')
+        .skip(1)
+        .step_by(2)
+        .map(str::to_string)
+        .collect()
+}
+
+/// Closest candidate to `input` when it is plausibly a typo of it.
+fn nearest(input: &str, candidates: &[String]) -> Option<String> {
+    let input_lower = input.to_ascii_lowercase();
+    let max_distance = (input.chars().count() / 3).max(2);
+    candidates
+        .iter()
+        .map(|candidate| {
+            let distance = levenshtein(&input_lower, &candidate.to_ascii_lowercase());
+            (distance, candidate)
+        })
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.clone())
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+    for (i, &ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            curr[j + 1] = (prev[j + 1] + 1).min(curr[j] + 1).min(prev[j] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
@@ -1,5 +1,5 @@
 use super::{
-    AmbientConfig, Config, DiffDisplayMode, DisplayConfig, ProviderConfig,
+    AmbientConfig, Config, ConfigIssueKind, DiffDisplayMode, DisplayConfig, ProviderConfig,
     SessionPickerResumeAction, SwarmSpawnMode, ToolConfig, config_env_fingerprint,
     populate_context_limits_from_config_ref,
 };
@@ -128,6 +128,66 @@ fn test_env_override_swarm_model() {
     restore_env_var("JCODE_SWARM_MODEL", prev);
 }
 
+#[test]
+fn profiles_parse_and_report_unknown_keys() {
+    let cfg: Config = toml::from_str(
+        r#"
+[agent]
+max_turns = 50
+
+[profiles.review]
+provider = "claude-api"
+model = "claude-opus-4-6"
+tools = ["read", "agentgrep"]
+approval = "plan"
+max_turns = 10
+modle = "typo"
+
+[profiles.quickfix]
+model = "claude-haiku-4-5"
+approval = "ask"
+"#,
+    )
+    .expect("profiles should parse");
+
+    let review = cfg.profile("review").expect("review profile");
+    assert_eq!(
+        review.model_request().as_deref(),
+        Some("claude-api:claude-opus-4-6")
+    );
+    assert_eq!(review.plan_mode(), Some(true));
+    let limits = review.apply_limits(&cfg.agent);
+    assert_eq!(limits.max_turns, Some(10));
+
+    assert_eq!(
+        cfg.profile_errors(),
+        vec![
+            "[profiles.quickfix] `approval` must be one of auto, plan, got `ask`".to_string(),
+            "[profiles.review] unknown key `modle`".to_string(),
+        ]
+    );
+    let err = cfg.profile("missing").unwrap_err().to_string();
+    assert!(err.contains("known: quickfix, review"), "{err}");
+}
+
+#[test]
+fn profile_tools_replace_allow_list_and_extend_disabled() {
+    let base = ToolConfig {
+        disabled: vec!["browser".to_string()],
+        ..ToolConfig::default()
+    };
+    let profile = super::AgentProfileConfig {
+        tools: vec!["read".to_string(), "bash".to_string()],
+        disabled_tools: vec!["bash".to_string()],
+        ..Default::default()
+    };
+    let selection = base.with_profile(&profile).selection();
+    let allowed = selection.allowed_tools.expect("allow-list");
+    assert!(allowed.contains("read"));
+    assert!(!allowed.contains("bash"));
+    assert!(selection.disabled_tools.contains("browser"));
+}
+
 #[test]
 fn spawn_hook_defaults_to_none_and_parses_from_toml() {
     assert_eq!(Config::default().terminal.spawn_hook, None);
@@ -377,6 +437,28 @@ fn tool_config_explicit_enabled_and_disabled_lists_compose() {
     assert!(selection.disabled_tools.contains("browser"));
 }
 
+#[test]
+fn tool_config_parses_bash_env_section() {
+    let cfg: Config = toml::from_str(
+        "[tools.bash]\nenv_file = \".jcode/env\"\ndotenv = true\nenv_allowlist = [\"DATABASE_URL\"]\n",
+    )
+    .expect("parse [tools.bash]");
+    assert_eq!(cfg.tools.bash.env_file.as_deref(), Some(".jcode/env"));
+    assert!(cfg.tools.bash.dotenv);
+    assert_eq!(cfg.tools.bash.env_allowlist, vec!["DATABASE_URL"]);
+    assert!(cfg.tools.bash.redact_output);
+    assert!(!ToolConfig::default().bash.dotenv);
+}
+
+#[test]
+fn tool_config_parses_bash_limits() {
+    let cfg: Config = toml::from_str("[tools.bash]\nmax_timeout_secs = 600\n")
+        .expect("parse [tools.bash] limits");
+    assert_eq!(cfg.tools.bash.max_timeout_secs, 600);
+    assert_eq!(cfg.tools.bash.max_output_bytes, 30_000);
+    assert_eq!(ToolConfig::default().bash.max_timeout_secs, 120);
+}
+
 #[test]
 fn tool_config_none_profile_disables_all_tools() {
     let cfg = ToolConfig {
@@ -617,6 +699,23 @@ fn test_provider_failover_defaults_match_new_behavior() {
     assert!(provider.same_provider_account_failover);
 }
 
+#[test]
+fn test_tool_fallback_defaults_to_textual_and_parses_aliases() {
+    assert_eq!(
+        Config::default().provider.tool_fallback,
+        super::ToolFallbackMode::Textual
+    );
+    assert_eq!(
+        super::ToolFallbackMode::parse(" Refuse "),
+        Some(super::ToolFallbackMode::Refuse)
+    );
+    assert_eq!(
+        super::ToolFallbackMode::parse("text"),
+        Some(super::ToolFallbackMode::Textual)
+    );
+    assert_eq!(super::ToolFallbackMode::parse("native"), None);
+}
+
 #[test]
 fn test_native_scrollbars_default_to_enabled() {
     let display = DisplayConfig::default();
@@ -819,6 +918,20 @@ fn known_update_channel_values_still_parse() {
     assert_eq!(cfg.features.update_channel, super::UpdateChannel::Stable);
 }
 
+#[test]
+fn update_section_channel_overrides_features_update_channel() {
+    let cfg: Config = toml::from_str("[features]\nupdate_channel = \"stable\"\n")
+        .expect("features-only channel should parse");
+    assert_eq!(cfg.update_channel(), super::UpdateChannel::Stable);
+
+    let cfg: Config = toml::from_str(
+        "[features]\nupdate_channel = \"stable\"\n\n[update]\nchannel = \"nightly\"\n",
+    )
+    .expect("[update] channel should parse");
+    assert_eq!(cfg.update.channel, Some(super::UpdateChannel::Main));
+    assert_eq!(cfg.update_channel(), super::UpdateChannel::Main);
+}
+
 #[test]
 fn update_channel_parse_accepts_known_aliases_and_rejects_unknown() {
     use super::UpdateChannel;
@@ -874,3 +987,77 @@ fn populate_context_limits_from_config_ref_seeds_global_cache() {
         "global context-limit resolution should respect named provider context_window"
     );
 }
+
+#[test]
+fn lenient_parse_reports_typos_with_line_and_suggestion_and_keeps_the_rest() {
+    let content = r#"
+[ambient]
+enabled = true
+pause_on_active_sesion = false
+
+[agents]
+swarm_spawn_mode = "headles"
+swarm_max_concurrent_agents = 8
+
+[ambiant]
+enabled = true
+
+[display]
+centered = true
+"#;
+    let (cfg, issues) =
+        Config::parse_lenient(content, Some(Path::new("/tmp/config.toml"))).expect("parses");
+
+    assert!(cfg.ambient.enabled);
+    assert!(
+        cfg.ambient.pause_on_active_session,
+        "typo falls back to default"
+    );
+    assert_eq!(cfg.agents.swarm_max_concurrent_agents, 8);
+    assert!(cfg.display.centered, "keys after the unknown table survive");
+
+    let keys: Vec<&str> = issues.iter().map(|issue| issue.key.as_str()).collect();
+    assert_eq!(
+        keys,
+        vec![
+            "ambient.pause_on_active_sesion",
+            "agents.swarm_spawn_mode",
+            "ambiant"
+        ]
+    );
+
+    assert_eq!(issues[0].kind, ConfigIssueKind::UnknownKey);
+    assert_eq!(issues[0].line, Some(4));
+    assert_eq!(
+        issues[0].suggestion.as_deref(),
+        Some("pause_on_active_session")
+    );
+    assert_eq!(
+        issues[0].to_string(),
+        "/tmp/config.toml:4: unknown key `ambient.pause_on_active_sesion` (did you mean `pause_on_active_session`?)"
+    );
+
+    assert!(matches!(issues[1].kind, ConfigIssueKind::InvalidValue(_)));
+    assert_eq!(issues[1].line, Some(7));
+    assert_eq!(issues[1].suggestion.as_deref(), Some("headless"));
+
+    assert_eq!(issues[2].line, Some(10));
+    assert_eq!(issues[2].suggestion.as_deref(), Some("ambient"));
+}
+
+#[test]
+fn lenient_parse_still_rejects_toml_syntax_errors() {
+    assert!(Config::parse_lenient("[display\ncentered = true\n", None).is_err());
+}
+
+#[test]
+fn check_key_accepts_real_settings_and_suggests_for_typos() {
+    assert!(Config::check_key("ambient.pause_on_active_session").is_ok());
+    assert!(Config::check_key("notifications.routes").is_ok());
+    assert!(Config::check_key("providers.my-gateway.base_url").is_ok());
+
+    let issue = Config::check_key("ambient.pause_on_active_sesion").unwrap_err();
+    assert_eq!(issue.kind, ConfigIssueKind::UnknownKey);
+    assert_eq!(issue.suggestion.as_deref(), Some("pause_on_active_session"));
+    assert!(Config::check_key("displya.centered").is_err());
+}
@@ -6,21 +6,43 @@
 use chrono::{Datelike, Utc};
 use std::path::PathBuf;
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 static TRACKER: Mutex<Option<CopilotUsageTracker>> = Mutex::new(None);
 
+/// Disk snapshot used for display, so clients see usage recorded by the server.
+static DISPLAY_CACHE: Mutex<Option<(Instant, CopilotUsageTracker)>> = Mutex::new(None);
+
+const DISPLAY_CACHE_TTL: Duration = Duration::from_secs(10);
+
+/// Response headers carrying the premium-request quota, most specific first.
+const PREMIUM_QUOTA_HEADERS: &[&str] = &[
+    "x-quota-snapshot-premium_interactions",
+    "x-quota-snapshot-premium_models",
+];
+
+/// Share of the monthly budget after which premium requests are conserved.
+const CONSERVE_AT_PERCENT: u64 = 80;
+
 fn usage_path() -> PathBuf {
     crate::storage::jcode_dir()
         .unwrap_or_else(|_| PathBuf::from(".").join(".jcode"))
         .join("copilot_usage.json")
 }
 
-pub use jcode_usage_types::{AllTimeUsage, CopilotUsageTracker, DayUsage, MonthUsage};
+pub use jcode_usage_types::{
+    AllTimeUsage, CopilotPremiumQuota, CopilotUsageTracker, DayUsage, MonthUsage,
+};
+
+fn current_month() -> String {
+    let now = Utc::now();
+    format!("{}-{:02}", now.year(), now.month())
+}
 
 fn roll_if_needed(tracker: &mut CopilotUsageTracker) {
     let now = Utc::now();
     let today = now.format("%Y-%m-%d").to_string();
-    let month = format!("{}-{:02}", now.year(), now.month());
+    let month = current_month();
 
     if tracker.today.date != today {
         tracker.today = DayUsage {
@@ -98,11 +120,168 @@ pub fn get_usage() -> CopilotUsageTracker {
     tracker.clone()
 }
 
+/// Parse a quota snapshot header value such as
+/// `ent=300&ov=0.0&ovPerm=false&rem=72.5&rst=2026-11-01T00:00:00Z`.
+/// `ent=-1` marks an unlimited entitlement.
+pub fn parse_premium_quota_header(value: &str) -> Option<CopilotPremiumQuota> {
+    let mut entitlement: Option<i64> = None;
+    let mut percent_remaining: Option<f64> = None;
+    let mut resets_at = None;
+    for pair in value.split('&') {
+        let Some((key, raw)) = pair.split_once('=') else {
+            continue;
+        };
+        let raw = raw.trim();
+        match key.trim() {
+            "ent" => entitlement = raw.parse().ok(),
+            "rem" => percent_remaining = raw.parse().ok(),
+            "rst" if !raw.is_empty() => resets_at = Some(raw.replace("%3A", ":")),
+            _ => {}
+        }
+    }
+    let entitlement = entitlement?;
+    Some(CopilotPremiumQuota {
+        month: current_month(),
+        entitlement: u64::try_from(entitlement).ok(),
+        percent_remaining: percent_remaining.unwrap_or(100.0).clamp(0.0, 100.0),
+        resets_at,
+    })
+}
+
+/// Record the premium quota GitHub reported on a chat response, if any.
+pub fn record_quota_headers(headers: &reqwest::header::HeaderMap) {
+    let Some(quota) = PREMIUM_QUOTA_HEADERS
+        .iter()
+        .filter_map(|name| headers.get(*name)?.to_str().ok())
+        .find_map(parse_premium_quota_header)
+    else {
+        return;
+    };
+    let mut guard = match TRACKER.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    let tracker = guard.get_or_insert_with(load_tracker);
+    if tracker.premium_quota.as_ref() != Some(&quota) {
+        tracker.premium_quota = Some(quota);
+        save_tracker(tracker);
+    }
+}
+
+/// Premium requests spent this month: the larger of jcode's own count and
+/// GitHub's quota snapshot, which also covers other Copilot clients.
+pub fn premium_used_this_month(tracker: &CopilotUsageTracker) -> u64 {
+    let month = current_month();
+    let local = if tracker.month.month == month {
+        tracker.month.premium_requests
+    } else {
+        0
+    };
+    let remote = tracker
+        .premium_quota
+        .as_ref()
+        .filter(|quota| quota.month == month)
+        .and_then(CopilotPremiumQuota::used)
+        .unwrap_or(0);
+    local.max(remote)
+}
+
+/// Where this month's premium usage stands against the configured budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PremiumBudgetLevel {
+    Ok,
+    /// At least 80% spent: only the first request of a session is premium.
+    Conserve,
+    /// Budget spent: premium models are refused.
+    Exhausted,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PremiumBudgetStatus {
+    pub used: u64,
+    pub budget: u64,
+}
+
+impl PremiumBudgetStatus {
+    pub fn level(&self) -> PremiumBudgetLevel {
+        if self.used >= self.budget {
+            PremiumBudgetLevel::Exhausted
+        } else if self.used.saturating_mul(100) >= self.budget.saturating_mul(CONSERVE_AT_PERCENT) {
+            PremiumBudgetLevel::Conserve
+        } else {
+            PremiumBudgetLevel::Ok
+        }
+    }
+
+    pub fn remaining(&self) -> u64 {
+        self.budget.saturating_sub(self.used)
+    }
+
+    pub fn usage_percent(&self) -> f32 {
+        (self.used as f32 / self.budget as f32 * 100.0).min(100.0)
+    }
+}
+
+fn budget_status_for(tracker: &CopilotUsageTracker) -> Option<PremiumBudgetStatus> {
+    let budget = crate::config::config()
+        .provider
+        .copilot
+        .monthly_premium_budget
+        .filter(|budget| *budget > 0)?;
+    Some(PremiumBudgetStatus {
+        used: premium_used_this_month(tracker),
+        budget,
+    })
+}
+
+/// This month's premium usage against `[provider.copilot] monthly_premium_budget`,
+/// or `None` when no budget is configured.
+pub fn budget_status() -> Option<PremiumBudgetStatus> {
+    budget_status_for(&get_usage())
+}
+
+/// Usage as last written to disk, refreshed at most every few seconds.
+fn display_usage() -> CopilotUsageTracker {
+    let mut guard = match DISPLAY_CACHE.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    if let Some((loaded_at, tracker)) = guard.as_ref()
+        && loaded_at.elapsed() < DISPLAY_CACHE_TTL
+    {
+        return tracker.clone();
+    }
+    let mut tracker = load_tracker();
+    roll_if_needed(&mut tracker);
+    *guard = Some((Instant::now(), tracker.clone()));
+    tracker
+}
+
+/// Short remaining-quota label for the status bar, e.g. `premium 42/300 left`.
+pub fn premium_status_label() -> Option<String> {
+    let usage = display_usage();
+    if let Some(status) = budget_status_for(&usage) {
+        return Some(format!(
+            "premium {}/{} left",
+            status.remaining(),
+            status.budget
+        ));
+    }
+    let quota = usage
+        .premium_quota
+        .as_ref()
+        .filter(|quota| quota.month == current_month())?;
+    quota
+        .entitlement
+        .map(|_| format!("premium {:.0}% left", quota.percent_remaining))
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
-        AllTimeUsage, CopilotUsageTracker, DayUsage, MonthUsage, TRACKER, load_tracker,
-        save_tracker, usage_path,
+        AllTimeUsage, CopilotPremiumQuota, CopilotUsageTracker, DayUsage, MonthUsage,
+        PremiumBudgetLevel, PremiumBudgetStatus, TRACKER, current_month, load_tracker,
+        parse_premium_quota_header, premium_used_this_month, save_tracker, usage_path,
     };
     use std::ffi::OsString;
 
@@ -177,6 +356,7 @@ mod tests {
                 input_tokens: 100,
                 output_tokens: 50,
             },
+            premium_quota: None,
         };
 
         save_tracker(&tracker);
@@ -186,4 +366,56 @@ mod tests {
         assert_eq!(loaded.today.requests, 2);
         assert_eq!(loaded.all_time.output_tokens, 50);
     }
+
+    #[test]
+    fn parses_premium_quota_header() {
+        let quota = parse_premium_quota_header(
+            "ent=300&ov=0.0&ovPerm=false&rem=72.5&rst=2026-11-01T00:00:00Z",
+        )
+        .expect("quota");
+        assert_eq!(quota.entitlement, Some(300));
+        assert_eq!(quota.percent_remaining, 72.5);
+        assert_eq!(quota.resets_at.as_deref(), Some("2026-11-01T00:00:00Z"));
+        assert_eq!(quota.used(), Some(83));
+
+        let unlimited = parse_premium_quota_header("ent=-1&rem=100").expect("quota");
+        assert_eq!(unlimited.entitlement, None);
+        assert_eq!(unlimited.used(), None);
+
+        assert!(parse_premium_quota_header("rem=50").is_none());
+    }
+
+    #[test]
+    fn premium_used_prefers_larger_of_local_and_remote_counts() {
+        let mut tracker = CopilotUsageTracker {
+            month: MonthUsage {
+                month: current_month(),
+                premium_requests: 40,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(premium_used_this_month(&tracker), 40);
+
+        tracker.premium_quota = Some(CopilotPremiumQuota {
+            month: current_month(),
+            entitlement: Some(300),
+            percent_remaining: 50.0,
+            resets_at: None,
+        });
+        assert_eq!(premium_used_this_month(&tracker), 150);
+
+        tracker.premium_quota.as_mut().unwrap().month = "1999-01".to_string();
+        assert_eq!(premium_used_this_month(&tracker), 40);
+    }
+
+    #[test]
+    fn budget_levels_conserve_at_80_percent_and_block_at_100() {
+        let level = |used| PremiumBudgetStatus { used, budget: 100 }.level();
+        assert_eq!(level(79), PremiumBudgetLevel::Ok);
+        assert_eq!(level(80), PremiumBudgetLevel::Conserve);
+        assert_eq!(level(99), PremiumBudgetLevel::Conserve);
+        assert_eq!(level(100), PremiumBudgetLevel::Exhausted);
+        assert_eq!(level(140), PremiumBudgetLevel::Exhausted);
+    }
 }
@@ -222,6 +222,46 @@ pub fn embed(text: &str) -> Result<EmbeddingVec> {
     result
 }
 
+/// Generate embeddings for several texts in one model call, using the LRU for
+/// texts embedded recently. Results are in input order.
+pub fn embed_batch(texts: &[&str]) -> Result<Vec<EmbeddingVec>> {
+    let hashes: Vec<u64> = texts.iter().map(|text| hash_text(text)).collect();
+    let mut results: Vec<Option<EmbeddingVec>> = vec![None; texts.len()];
+    if let Ok(mut cache) = embedder_cache().lock() {
+        for (slot, hash) in results.iter_mut().zip(&hashes) {
+            if let Some((emb, _)) = cache.embedding_lru.get(hash) {
+                *slot = Some(emb.clone());
+                cache.cache_hits = cache.cache_hits.saturating_add(1);
+            }
+        }
+    }
+
+    let missing: Vec<usize> = (0..texts.len()).filter(|&i| results[i].is_none()).collect();
+    if !missing.is_empty() {
+        let embedder = get_embedder()?;
+        let started = Instant::now();
+        let inputs: Vec<&str> = missing.iter().map(|&i| texts[i]).collect();
+        let embedded = embedder.embed_batch(&inputs);
+        let elapsed_ms = saturating_u64_from_u128(started.elapsed().as_millis());
+
+        if let Ok(mut cache) = embedder_cache().lock() {
+            cache.embed_calls = cache.embed_calls.saturating_add(1);
+            cache.total_embed_ms = cache.total_embed_ms.saturating_add(elapsed_ms);
+            if embedded.is_err() {
+                cache.embed_failures = cache.embed_failures.saturating_add(1);
+            }
+        }
+        for (i, emb) in missing.into_iter().zip(embedded?) {
+            results[i] = Some(emb);
+        }
+    }
+
+    if let Ok(mut cache) = embedder_cache().lock() {
+        cache.last_used_at = Some(Instant::now());
+    }
+    Ok(results.into_iter().flatten().collect())
+}
+
 /// Unload the embedding model if it has been idle for at least `idle_for`.
 ///
 /// Returns `true` when an unload occurred.
@@ -43,6 +43,13 @@ pub trait EmbeddingBackend: Send + Sync {
     /// [`Self::embed_query`] / [`Self::embed_passage`] which apply formatting.
     fn embed_raw(&self, text: &str) -> Result<Vec<f32>>;
 
+    /// Embed many already-formatted texts, in order. Backends with a batch
+    /// API override this to amortize one call over many inputs; the default
+    /// loops over [`Self::embed_raw`].
+    fn embed_raw_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        texts.iter().map(|t| self.embed_raw(t)).collect()
+    }
+
     /// Apply this model's query-side formatting (e.g. an `"query: "` prefix).
     /// Default: identity (no prefix), correct for MiniLM and OpenAI.
     fn format_query(&self, text: &str) -> String {
@@ -65,11 +72,11 @@ pub trait EmbeddingBackend: Send + Sync {
         self.embed_raw(&self.format_passage(text))
     }
 
-    /// Batch-embed many passages. Backends with a remote API override this to
-    /// amortize one HTTP round-trip over many inputs; the default loops over
-    /// [`Self::embed_passage`] so local backends need no special-casing.
+    /// Batch-embed many passages (applies passage formatting).
     fn embed_passages(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
-        texts.iter().map(|t| self.embed_passage(t)).collect()
+        let inputs: Vec<String> = texts.iter().map(|t| self.format_passage(t)).collect();
+        let inputs: Vec<&str> = inputs.iter().map(String::as_str).collect();
+        self.embed_raw_batch(&inputs)
     }
 }
 
@@ -95,6 +102,10 @@ impl EmbeddingBackend for LocalOnnxBackend {
         crate::embedding::embed(text)
     }
 
+    fn embed_raw_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        crate::embedding::embed_batch(texts)
+    }
+
     // MiniLM is symmetric and prefix-free: default identity formatting is correct.
 }
 
@@ -179,6 +190,7 @@ impl OpenAiEmbeddingBackend {
             scope
                 .spawn(move || -> Result<Vec<Vec<f32>>> {
                     let client = reqwest::blocking::Client::builder()
+                        .dns_resolver(jcode_provider_core::egress::resolver())
                         .timeout(std::time::Duration::from_secs(60))
                         .build()?;
                     let body = serde_json::json!({
@@ -259,9 +271,8 @@ impl EmbeddingBackend for OpenAiEmbeddingBackend {
             .ok_or_else(|| anyhow::anyhow!("OpenAI embeddings returned no vector"))
     }
 
-    fn embed_passages(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
-        let inputs: Vec<String> = texts.iter().map(|t| self.format_passage(t)).collect();
-        self.embed_inputs(inputs)
+    fn embed_raw_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        self.embed_inputs(texts.iter().map(|t| t.to_string()).collect())
     }
 
     // OpenAI embeddings are symmetric and prefix-free: identity formatting.
@@ -318,21 +329,51 @@ pub fn model_matches_active(entry_model: &str) -> bool {
     entry_model == active_model_id()
 }
 
+/// Embed formatted `inputs` with `backend` through the persistent
+/// [`crate::embedding_cache`], batching whatever is not cached yet.
+fn embed_cached(backend: &dyn EmbeddingBackend, inputs: &[String]) -> Result<Vec<Vec<f32>>> {
+    let inputs: Vec<&str> = inputs.iter().map(String::as_str).collect();
+    crate::embedding_cache::embed_cached(backend.model_id(), &inputs, |missing| {
+        backend.embed_raw_batch(missing)
+    })
+}
+
 /// Embed a retrieval QUERY with the active backend, returning the vector and the
-/// backend's model id. The local backend round-trips through the cached
-/// `crate::embedding` facade; remote backends call their API directly.
+/// backend's model id. Vectors come from the persistent embedding cache when
+/// the same query was embedded before.
 pub fn embed_query_active(text: &str) -> anyhow::Result<(Vec<f32>, String)> {
+    let (mut vecs, model_id) = embed_queries_active(&[text])?;
+    let vec = vecs
+        .pop()
+        .ok_or_else(|| anyhow::anyhow!("embedding backend returned no vector"))?;
+    Ok((vec, model_id))
+}
+
+/// Embed several retrieval QUERIES in one batch with the active backend.
+pub fn embed_queries_active(texts: &[&str]) -> anyhow::Result<(Vec<Vec<f32>>, String)> {
     let backend = active_backend();
-    let vec = backend.embed_query(text)?;
-    Ok((vec, backend.model_id().to_string()))
+    let inputs: Vec<String> = texts.iter().map(|t| backend.format_query(t)).collect();
+    let vecs = embed_cached(backend.as_ref(), &inputs)?;
+    Ok((vecs, backend.model_id().to_string()))
 }
 
 /// Embed a stored PASSAGE/memory with the active backend, returning the vector
 /// and the backend's model id (to persist on the entry for space-gating).
 pub fn embed_passage_active(text: &str) -> anyhow::Result<(Vec<f32>, String)> {
+    let (mut vecs, model_id) = embed_passages_active(&[text])?;
+    let vec = vecs
+        .pop()
+        .ok_or_else(|| anyhow::anyhow!("embedding backend returned no vector"))?;
+    Ok((vec, model_id))
+}
+
+/// Embed many PASSAGES in one batch with the active backend. Cached vectors
+/// are reused; only the rest reach the backend.
+pub fn embed_passages_active(texts: &[&str]) -> anyhow::Result<(Vec<Vec<f32>>, String)> {
     let backend = active_backend();
-    let vec = backend.embed_passage(text)?;
-    Ok((vec, backend.model_id().to_string()))
+    let inputs: Vec<String> = texts.iter().map(|t| backend.format_passage(t)).collect();
+    let vecs = embed_cached(backend.as_ref(), &inputs)?;
+    Ok((vecs, backend.model_id().to_string()))
 }
 
 #[cfg(test)]
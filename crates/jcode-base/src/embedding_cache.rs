@@ -0,0 +1,241 @@
+//! Persistent embedding cache under `~/.jcode/embeddings/`.
+//!
+//! Vectors are keyed by the backend's model id (which names both backend and
+//! model, e.g. `openai:text-embedding-3-small`) and a SHA-256 of the exact
+//! text that was embedded, so query and passage formatting never collide.
+//! Each model has one append-only JSONL file, read into memory on first use.
+//! Lookups that miss are embedded in one batch and appended.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, VecDeque};
+use std::io::BufRead;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+/// Vectors kept per model before the oldest quarter is dropped.
+const MAX_ENTRIES_PER_MODEL: usize = 50_000;
+
+static CACHE: OnceLock<Mutex<HashMap<PathBuf, ModelCache>>> = OnceLock::new();
+static HITS: AtomicU64 = AtomicU64::new(0);
+static MISSES: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Serialize, Deserialize)]
+struct CachedVector {
+    hash: String,
+    vector: Vec<f32>,
+}
+
+/// One model's vectors, with insertion order for trimming.
+#[derive(Default)]
+struct ModelCache {
+    vectors: HashMap<String, Vec<f32>>,
+    order: VecDeque<String>,
+}
+
+impl ModelCache {
+    fn load(path: &PathBuf) -> Self {
+        let mut cache = Self::default();
+        let Ok(file) = std::fs::File::open(path) else {
+            return cache;
+        };
+        for line in std::io::BufReader::new(file).lines() {
+            // A torn last line from a crash is skipped.
+            let Ok(entry) = line.map_err(anyhow::Error::from).and_then(|line| {
+                serde_json::from_str::<CachedVector>(&line).map_err(anyhow::Error::from)
+            }) else {
+                continue;
+            };
+            cache.insert(entry.hash, entry.vector);
+        }
+        cache
+    }
+
+    fn insert(&mut self, hash: String, vector: Vec<f32>) {
+        if self.vectors.insert(hash.clone(), vector).is_none() {
+            self.order.push_back(hash);
+        }
+    }
+
+    /// Drop the oldest quarter and rewrite the file when over capacity.
+    fn trim(&mut self, path: &PathBuf) -> Result<()> {
+        if self.vectors.len() <= MAX_ENTRIES_PER_MODEL {
+            return Ok(());
+        }
+        for hash in self.order.drain(..MAX_ENTRIES_PER_MODEL / 4) {
+            self.vectors.remove(&hash);
+        }
+        let mut out = Vec::new();
+        for hash in &self.order {
+            let entry = CachedVector {
+                hash: hash.clone(),
+                vector: self.vectors[hash].clone(),
+            };
+            serde_json::to_writer(&mut out, &entry)?;
+            out.push(b'\n');
+        }
+        crate::storage::write_bytes(path, &out)
+    }
+}
+
+/// Cache hit and miss counts since the process started.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EmbeddingCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl EmbeddingCacheStats {
+    /// Counts accumulated since `earlier`.
+    pub fn since(self, earlier: Self) -> Self {
+        Self {
+            hits: self.hits.saturating_sub(earlier.hits),
+            misses: self.misses.saturating_sub(earlier.misses),
+        }
+    }
+}
+
+pub fn stats() -> EmbeddingCacheStats {
+    EmbeddingCacheStats {
+        hits: HITS.load(Ordering::Relaxed),
+        misses: MISSES.load(Ordering::Relaxed),
+    }
+}
+
+fn content_hash(text: &str) -> String {
+    hex::encode(Sha256::digest(text.as_bytes()))
+}
+
+fn cache_file(model_id: &str) -> Result<PathBuf> {
+    let name: String = model_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    Ok(crate::storage::jcode_dir()?
+        .join("embeddings")
+        .join(format!("{name}.jsonl")))
+}
+
+/// Vectors for `texts` from the cache, calling `embed` once with every text
+/// that is not cached yet. Results are in input order.
+pub fn embed_cached<F>(model_id: &str, texts: &[&str], embed: F) -> Result<Vec<Vec<f32>>>
+where
+    F: FnOnce(&[&str]) -> Result<Vec<Vec<f32>>>,
+{
+    let path = cache_file(model_id)?;
+    let hashes: Vec<String> = texts.iter().map(|text| content_hash(text)).collect();
+    let mut results: Vec<Option<Vec<f32>>> = {
+        let mut caches = CACHE
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let cache = caches
+            .entry(path.clone())
+            .or_insert_with(|| ModelCache::load(&path));
+        hashes
+            .iter()
+            .map(|hash| cache.vectors.get(hash).cloned())
+            .collect()
+    };
+
+    let missing: Vec<usize> = (0..texts.len()).filter(|&i| results[i].is_none()).collect();
+    HITS.fetch_add((texts.len() - missing.len()) as u64, Ordering::Relaxed);
+    MISSES.fetch_add(missing.len() as u64, Ordering::Relaxed);
+    if missing.is_empty() {
+        return Ok(results.into_iter().flatten().collect());
+    }
+
+    let inputs: Vec<&str> = missing.iter().map(|&i| texts[i]).collect();
+    let embedded = embed(&inputs)?;
+    if embedded.len() != inputs.len() {
+        anyhow::bail!(
+            "embedding backend returned {} vectors for {} inputs",
+            embedded.len(),
+            inputs.len()
+        );
+    }
+
+    let mut caches = CACHE
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let cache = caches.entry(path.clone()).or_default();
+    for (i, vector) in missing.into_iter().zip(embedded) {
+        let entry = CachedVector {
+            hash: hashes[i].clone(),
+            vector,
+        };
+        if let Err(error) = crate::storage::append_json_line_fast(&path, &entry) {
+            crate::logging::warn(&format!(
+                "Failed to write embedding cache {}: {error}",
+                path.display()
+            ));
+        }
+        results[i] = Some(entry.vector.clone());
+        cache.insert(entry.hash, entry.vector);
+    }
+    if let Err(error) = cache.trim(&path) {
+        crate::logging::warn(&format!(
+            "Failed to trim embedding cache {}: {error}",
+            path.display()
+        ));
+    }
+    Ok(results.into_iter().flatten().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fake_embed(texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        Ok(texts.iter().map(|text| vec![text.len() as f32]).collect())
+    }
+
+    #[test]
+    fn misses_are_embedded_once_and_persisted() {
+        let _guard = crate::storage::lock_test_env();
+        let home = tempfile::tempdir().unwrap();
+        let old = std::env::var("JCODE_HOME").ok();
+        crate::env::set_var("JCODE_HOME", home.path());
+
+        let first = embed_cached("test:model", &["a", "bbb"], fake_embed).unwrap();
+        assert_eq!(first, vec![vec![1.0], vec![3.0]]);
+
+        let mut asked = Vec::new();
+        let second = embed_cached("test:model", &["bbb", "cc"], |texts| {
+            asked.extend(texts.iter().map(|text| text.to_string()));
+            fake_embed(texts)
+        })
+        .unwrap();
+        assert_eq!(second, vec![vec![3.0], vec![2.0]]);
+        assert_eq!(asked, ["cc"]);
+
+        // A fresh process reads the vectors back from disk.
+        let path = cache_file("test:model").unwrap();
+        assert!(path.ends_with("embeddings/test_model.jsonl"));
+        assert_eq!(ModelCache::load(&path).vectors.len(), 3);
+
+        match old {
+            Some(value) => crate::env::set_var("JCODE_HOME", value),
+            None => crate::env::remove_var("JCODE_HOME"),
+        }
+    }
+
+    #[test]
+    fn stats_since_subtracts() {
+        let later = EmbeddingCacheStats { hits: 5, misses: 2 };
+        let earlier = EmbeddingCacheStats { hits: 3, misses: 2 };
+        assert_eq!(
+            later.since(earlier),
+            EmbeddingCacheStats { hits: 2, misses: 0 }
+        );
+    }
+}
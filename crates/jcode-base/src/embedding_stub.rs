@@ -126,6 +126,14 @@ pub fn embed(text: &str) -> Result<EmbeddingVec> {
     anyhow::bail!("Embeddings feature not compiled in this build")
 }
 
+pub fn embed_batch(texts: &[&str]) -> Result<Vec<EmbeddingVec>> {
+    logging::warn(&format!(
+        "embedding batch rejected because embeddings feature is disabled count={}",
+        texts.len()
+    ));
+    anyhow::bail!("Embeddings feature not compiled in this build")
+}
+
 pub fn maybe_unload_if_idle(idle_for: Duration) -> bool {
     logging::debug(&format!(
         "embedding idle unload skipped in stub idle_secs={}",
@@ -0,0 +1,421 @@
+//! Per-turn commits for `[git] auto_commit`.
+//!
+//! Only the files the agent modified are committed. The commit is built in a
+//! throwaway index seeded from the target branch, so whatever the user has
+//! staged stays staged and out of the commit. With `auto_commit_branch` set,
+//! the commit goes onto that branch without checking it out. Otherwise it
+//! lands on the current branch, and the real index is refreshed for the
+//! committed paths only.
+
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+
+const SUBJECT_MAX_CHARS: usize = 72;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutoCommit {
+    /// Abbreviated commit hash
+    pub sha: String,
+    /// Branch the commit landed on, or `HEAD` when detached
+    pub branch: String,
+    pub subject: String,
+    /// Committed paths, relative to the repository root
+    pub files: Vec<String>,
+}
+
+/// Commit message for one turn: the first line of the user's message as the
+/// subject, then the tool calls that made the changes and the session id.
+pub fn commit_message(
+    user_message: &str,
+    tool_counts: &[(String, usize)],
+    session_id: &str,
+) -> String {
+    let subject = user_message
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(truncate_subject)
+        .unwrap_or_else(|| "jcode turn".to_string());
+    let mut message = subject;
+    if !tool_counts.is_empty() {
+        let tools = tool_counts
+            .iter()
+            .map(|(name, count)| format!("{} ×{}", name, count))
+            .collect::<Vec<_>>()
+            .join(", ");
+        message.push_str(&format!("\n\nTools: {}", tools));
+    }
+    message.push_str(&format!("\n\nSession: {}", session_id));
+    message
+}
+
+fn truncate_subject(line: &str) -> String {
+    if line.chars().count() <= SUBJECT_MAX_CHARS {
+        return line.to_string();
+    }
+    let mut subject: String = line.chars().take(SUBJECT_MAX_CHARS - 1).collect();
+    subject.push('…');
+    subject
+}
+
+/// Commit `paths` (absolute, or relative to `working_dir`) with `message`.
+/// Paths outside the repository or ignored by git are skipped. Returns `None`
+/// when nothing is left to commit or the tree did not change.
+pub fn commit_paths(
+    working_dir: &Path,
+    paths: &[PathBuf],
+    message: &str,
+    branch: Option<&str>,
+) -> Result<Option<AutoCommit>> {
+    let root = PathBuf::from(git(working_dir, None, &["rev-parse", "--show-toplevel"])?);
+    let root = root.canonicalize().unwrap_or(root);
+    let relative = repo_relative_paths(&root, working_dir, paths);
+    if relative.is_empty() {
+        return Ok(None);
+    }
+
+    let current_branch = git(&root, None, &["symbolic-ref", "-q", "--short", "HEAD"]).ok();
+    let scratch_branch = branch
+        .map(str::trim)
+        .filter(|name| !name.is_empty() && Some(*name) != current_branch.as_deref());
+    if let Some(name) = scratch_branch {
+        git(&root, None, &["check-ref-format", "--branch", name])
+            .with_context(|| format!("invalid auto-commit branch `{}`", name))?;
+    }
+    let target_ref = match scratch_branch {
+        Some(name) => format!("refs/heads/{}", name),
+        None => "HEAD".to_string(),
+    };
+    let old_tip = resolve_commit(&root, &target_ref);
+    let parent = match (&old_tip, scratch_branch) {
+        (None, Some(_)) => resolve_commit(&root, "HEAD"),
+        _ => old_tip.clone(),
+    };
+
+    let git_dir = PathBuf::from(git(&root, None, &["rev-parse", "--absolute-git-dir"])?);
+    let index = TempIndex(git_dir.join(format!("jcode-autocommit-index-{}", std::process::id())));
+    let _ = std::fs::remove_file(&index.0);
+    let index_path = Some(index.0.as_path());
+
+    match &parent {
+        Some(parent) => git(&root, index_path, &["read-tree", parent])?,
+        None => git(&root, index_path, &["read-tree", "--empty"])?,
+    };
+    let stageable = stageable_paths(&root, index_path, &relative)?;
+    if stageable.is_empty() {
+        return Ok(None);
+    }
+    let mut add_args = vec!["add", "-A", "--"];
+    add_args.extend(stageable.iter().map(String::as_str));
+    git(&root, index_path, &add_args)?;
+    let tree = git(&root, index_path, &["write-tree"])?;
+    if let Some(parent) = &parent
+        && git(&root, None, &["rev-parse", &format!("{}^{{tree}}", parent)])? == tree
+    {
+        return Ok(None);
+    }
+
+    let mut commit_args = vec!["commit-tree", tree.as_str()];
+    if let Some(parent) = &parent {
+        commit_args.extend(["-p", parent.as_str()]);
+    }
+    commit_args.extend(["-m", message]);
+    let sha = git(&root, None, &commit_args)?;
+    git(
+        &root,
+        None,
+        &[
+            "update-ref",
+            "-m",
+            "jcode: auto-commit",
+            &target_ref,
+            &sha,
+            old_tip.as_deref().unwrap_or(""),
+        ],
+    )?;
+    if scratch_branch.is_none() {
+        // Refresh the real index for the committed paths only, so they do not
+        // show as staged reversals of the new commit.
+        let mut reset_args = vec!["reset", "-q", "--"];
+        reset_args.extend(stageable.iter().map(String::as_str));
+        git(&root, None, &reset_args)?;
+    }
+
+    Ok(Some(AutoCommit {
+        sha: git(&root, None, &["rev-parse", "--short", &sha])?,
+        branch: scratch_branch
+            .map(str::to_string)
+            .or(current_branch)
+            .unwrap_or_else(|| "HEAD".to_string()),
+        subject: message.lines().next().unwrap_or_default().to_string(),
+        files: stageable,
+    }))
+}
+
+/// The paths git reports as modified, deleted or untracked, with their
+/// modification times. Comparing two snapshots tells which files changed in
+/// between, including ones written by shell commands rather than file tools.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorktreeSnapshot(BTreeMap<PathBuf, Option<SystemTime>>);
+
+impl WorktreeSnapshot {
+    pub fn capture(working_dir: &Path) -> Result<Self> {
+        let root = PathBuf::from(git(working_dir, None, &["rev-parse", "--show-toplevel"])?);
+        let status = git(
+            &root,
+            None,
+            &["status", "--porcelain=v1", "-z", "--untracked-files=all"],
+        )?;
+        let mut paths = BTreeMap::new();
+        let mut records = status.split('\0').filter(|record| !record.is_empty());
+        while let Some(record) = records.next() {
+            let (Some(code), Some(path)) = (record.get(..2), record.get(3..)) else {
+                continue;
+            };
+            let mut entries = vec![path];
+            // Renames and copies are followed by their source path.
+            if code.starts_with(['R', 'C'])
+                && let Some(source) = records.next()
+            {
+                entries.push(source);
+            }
+            for path in entries {
+                let path = root.join(path);
+                let modified = std::fs::metadata(&path)
+                    .and_then(|meta| meta.modified())
+                    .ok();
+                paths.insert(path, modified);
+            }
+        }
+        Ok(Self(paths))
+    }
+
+    /// Paths that became dirty, changed again, or went back to clean since
+    /// `earlier`.
+    pub fn changed_since(&self, earlier: &Self) -> Vec<PathBuf> {
+        let mut changed: BTreeSet<&PathBuf> = self
+            .0
+            .iter()
+            .filter(|(path, modified)| earlier.0.get(*path) != Some(*modified))
+            .map(|(path, _)| path)
+            .collect();
+        changed.extend(earlier.0.keys().filter(|path| !self.0.contains_key(*path)));
+        changed.into_iter().cloned().collect()
+    }
+}
+
+/// Removes the temporary index on every exit path.
+struct TempIndex(PathBuf);
+
+impl Drop for TempIndex {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+fn repo_relative_paths(root: &Path, working_dir: &Path, paths: &[PathBuf]) -> Vec<String> {
+    let mut relative = BTreeSet::new();
+    for path in paths {
+        let path = if path.is_absolute() {
+            path.clone()
+        } else {
+            working_dir.join(path)
+        };
+        // Deleted files cannot be canonicalized, so resolve the parent.
+        let (Some(parent), Some(name)) = (path.parent(), path.file_name()) else {
+            continue;
+        };
+        let parent = parent
+            .canonicalize()
+            .unwrap_or_else(|_| parent.to_path_buf());
+        if let Ok(rel) = parent.join(name).strip_prefix(root) {
+            let rel = rel.to_string_lossy().replace('\\', "/");
+            if !rel.is_empty() && !rel.starts_with(".git/") {
+                relative.insert(rel);
+            }
+        }
+    }
+    relative.into_iter().collect()
+}
+
+/// The subset of `paths` git will stage: tracked in the parent tree, or
+/// untracked and not ignored.
+fn stageable_paths(root: &Path, index: Option<&Path>, paths: &[String]) -> Result<Vec<String>> {
+    let mut args = vec![
+        "ls-files",
+        "-z",
+        "--cached",
+        "--others",
+        "--exclude-standard",
+        "--",
+    ];
+    args.extend(paths.iter().map(String::as_str));
+    let listed = git(root, index, &args)?;
+    let listed: BTreeSet<&str> = listed.split('\0').filter(|path| !path.is_empty()).collect();
+    Ok(listed.into_iter().map(str::to_string).collect())
+}
+
+fn resolve_commit(root: &Path, rev: &str) -> Option<String> {
+    git(
+        root,
+        None,
+        &[
+            "rev-parse",
+            "--verify",
+            "-q",
+            &format!("{}^{{commit}}", rev),
+        ],
+    )
+    .ok()
+}
+
+fn git(dir: &Path, index: Option<&Path>, args: &[&str]) -> Result<String> {
+    let mut command = Command::new("git");
+    command
+        .current_dir(dir)
+        .env("GIT_LITERAL_PATHSPECS", "1")
+        .args(args);
+    if let Some(index) = index {
+        command.env("GIT_INDEX_FILE", index);
+    }
+    let output = command
+        .output()
+        .with_context(|| format!("failed to run git {}", args.join(" ")))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.first().copied().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .trim_end()
+        .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().expect("tempdir");
+        for args in [
+            &["init", "-q"][..],
+            &["config", "user.name", "Test"],
+            &["config", "user.email", "test@example.com"],
+        ] {
+            git(dir.path(), None, args).expect("git setup");
+        }
+        std::fs::write(dir.path().join("README.md"), "hello\n").expect("write");
+        std::fs::write(dir.path().join(".gitignore"), "target/\n").expect("write");
+        git(dir.path(), None, &["add", "."]).expect("add");
+        git(dir.path(), None, &["commit", "-q", "-m", "init"]).expect("commit");
+        dir
+    }
+
+    fn changed_files(dir: &Path, rev: &str) -> Vec<String> {
+        git(dir, None, &["show", "--name-only", "--format=", rev])
+            .expect("show")
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn commits_only_agent_paths_and_keeps_user_staging() {
+        let repo = repo();
+        let dir = repo.path();
+        std::fs::write(dir.join("user.txt"), "staged by the user\n").expect("write");
+        git(dir, None, &["add", "user.txt"]).expect("add");
+        std::fs::write(dir.join("README.md"), "edited by the agent\n").expect("write");
+        std::fs::create_dir_all(dir.join("target")).expect("mkdir");
+        std::fs::write(dir.join("target/out.bin"), "ignored\n").expect("write");
+
+        let commit = commit_paths(
+            dir,
+            &[dir.join("README.md"), PathBuf::from("target/out.bin")],
+            "Update readme\n\nTools: edit ×1",
+            None,
+        )
+        .expect("commit")
+        .expect("a commit");
+
+        assert_eq!(commit.files, vec!["README.md".to_string()]);
+        assert_eq!(commit.subject, "Update readme");
+        assert_eq!(changed_files(dir, "HEAD"), vec!["README.md".to_string()]);
+        let status = git(dir, None, &["status", "--porcelain"]).expect("status");
+        assert!(status.contains("A  user.txt"), "{status}");
+        assert!(!status.contains("README.md"), "{status}");
+
+        // Nothing changed since: no empty commit.
+        let again = commit_paths(dir, &[dir.join("README.md")], "Again", None).expect("commit");
+        assert!(again.is_none());
+    }
+
+    #[test]
+    fn scratch_branch_advances_without_checkout() {
+        let repo = repo();
+        let dir = repo.path();
+        let head = git(dir, None, &["rev-parse", "HEAD"]).expect("head");
+
+        std::fs::write(dir.join("a.txt"), "one\n").expect("write");
+        let first = commit_paths(dir, &[dir.join("a.txt")], "First", Some("jcode/auto"))
+            .expect("commit")
+            .expect("a commit");
+        assert_eq!(first.branch, "jcode/auto");
+        std::fs::write(dir.join("a.txt"), "two\n").expect("write");
+        commit_paths(dir, &[dir.join("a.txt")], "Second", Some("jcode/auto"))
+            .expect("commit")
+            .expect("a commit");
+
+        assert_eq!(git(dir, None, &["rev-parse", "HEAD"]).expect("head"), head);
+        let log = git(dir, None, &["log", "--format=%s", "jcode/auto"]).expect("log");
+        assert_eq!(log.lines().collect::<Vec<_>>(), ["Second", "First", "init"]);
+        let status = git(dir, None, &["status", "--porcelain"]).expect("status");
+        assert_eq!(status, "?? a.txt");
+    }
+
+    #[test]
+    fn worktree_snapshot_reports_files_changed_in_between() {
+        let repo = repo();
+        let dir = repo.path();
+        std::fs::write(dir.join("user.txt"), "already dirty\n").expect("write");
+        let before = WorktreeSnapshot::capture(dir).expect("snapshot");
+
+        std::fs::write(dir.join("README.md"), "written by a shell command\n").expect("write");
+        std::fs::create_dir_all(dir.join("src")).expect("mkdir");
+        std::fs::write(dir.join("src/new.rs"), "fn main() {}\n").expect("write");
+        std::fs::create_dir_all(dir.join("target")).expect("mkdir");
+        std::fs::write(dir.join("target/out.bin"), "ignored\n").expect("write");
+        let after = WorktreeSnapshot::capture(dir).expect("snapshot");
+
+        let root = dir.canonicalize().expect("canonicalize");
+        let changed: Vec<PathBuf> = after
+            .changed_since(&before)
+            .into_iter()
+            .map(|path| path.canonicalize().expect("canonicalize"))
+            .collect();
+        assert_eq!(
+            changed,
+            vec![root.join("README.md"), root.join("src/new.rs")]
+        );
+    }
+
+    #[test]
+    fn message_uses_first_line_and_tool_summary() {
+        let message = commit_message(
+            "\n  Rename the config loader  \nand update callers",
+            &[("edit".to_string(), 3), ("write".to_string(), 1)],
+            "session_fox_1",
+        );
+        assert_eq!(
+            message,
+            "Rename the config loader\n\nTools: edit ×3, write ×1\n\nSession: session_fox_1"
+        );
+        let long = commit_message(&"x".repeat(100), &[], "s");
+        assert_eq!(long.lines().next().expect("subject").chars().count(), 72);
+    }
+}
@@ -20,9 +20,25 @@
 //!
 //! Hook processes get `JCODE_HOOKS_DISABLED=1` in their environment so a
 //! hook that itself invokes jcode does not recursively trigger hooks.
+//!
+//! Matcher-scoped `[[hooks]]` rules (see [`rules`]) add per-tool and per-path
+//! `pre_tool`/`post_tool`/`turn_end` commands on top of these, including
+//! project-local ones from `.jcode/hooks.toml`, which only load once the user
+//! trusts that file (see [`trust`]).
 
 use std::path::PathBuf;
 
+mod rules;
+mod trust;
+
+pub use rules::{
+    HookRuleSource, PROJECT_HOOKS_FILE, ToolHookCall, run_post_tool_rules, run_pre_tool_rules,
+    spawn_turn_end_rules,
+};
+pub use trust::{
+    ProjectHooksTrust, project_hooks_trust, trust_project_hooks, untrust_project_hooks,
+};
+
 /// Maximum bytes of JSON payload exported via `JCODE_HOOK_PAYLOAD`.
 const PAYLOAD_ENV_LIMIT: usize = 16 * 1024;
 /// Maximum bytes of tool input JSON exported to the pre_tool gate.
@@ -0,0 +1,512 @@
+//! Matcher-scoped hook rules.
+//!
+//! Rules come from `[[hooks.rules]]` in config.toml and `[[hooks]]` in the
+//! project's `.jcode/hooks.toml`, the latter only after `jcode hooks trust`
+//! (see `trust.rs`). Each names an event (`pre_tool`,
+//! `post_tool`, `turn_end`), optional tool-name and path globs, and a command
+//! that receives the event as JSON on stdin.
+//!
+//! Unlike the legacy `pre_tool` gate, a matching `pre_tool` rule fails
+//! closed: any non-zero exit, timeout, or spawn failure blocks the tool call.
+//! `post_tool` rules are awaited so a formatter finishes before the model's
+//! next step, but their outcome never changes the tool result. Every firing
+//! is appended to `~/.jcode/logs/hooks.jsonl`.
+
+use super::{BLOCK_REASON_LIMIT, GateDecision, HookEvent, build_hook_process, truncate_bytes};
+use crate::config::HookRule;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Project-local hook rules, relative to the session working directory.
+pub const PROJECT_HOOKS_FILE: &str = ".jcode/hooks.toml";
+
+const DEFAULT_RULE_TIMEOUT_MS: u64 = 10_000;
+/// Maximum bytes of tool output forwarded to `post_tool` rules.
+const RESULT_OUTPUT_LIMIT: usize = 64 * 1024;
+const AUDIT_LOG_FILE: &str = "hooks.jsonl";
+
+/// Where a rule was configured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookRuleSource {
+    Config,
+    Project,
+}
+
+/// A tool call as seen by hook rules.
+pub struct ToolHookCall<'a> {
+    pub session_id: &'a str,
+    pub working_dir: Option<&'a Path>,
+    pub tool_name: &'a str,
+    pub input: &'a Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum RuleOutcome {
+    Ok,
+    Failed,
+    TimedOut,
+    Error(String),
+}
+
+impl RuleOutcome {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::Failed => "failed",
+            Self::TimedOut => "timeout",
+            Self::Error(_) => "error",
+        }
+    }
+}
+
+struct RuleRun {
+    outcome: RuleOutcome,
+    exit_code: Option<i32>,
+    stderr: String,
+    duration_ms: u64,
+}
+
+#[derive(Serialize)]
+struct HookAuditRecord<'a> {
+    timestamp: String,
+    event: &'a str,
+    source: HookRuleSource,
+    command: &'a str,
+    session_id: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_name: Option<&'a str>,
+    paths: &'a [String],
+    outcome: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exit_code: Option<i32>,
+    duration_ms: u64,
+    stderr: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    blocked: Option<bool>,
+}
+
+/// Run every matching `pre_tool` rule in order. The first rule that does
+/// not exit 0 blocks the call.
+pub async fn run_pre_tool_rules(call: &ToolHookCall<'_>) -> GateDecision {
+    let paths = touched_paths(call.input, call.working_dir);
+    for (source, rule) in matching_tool_rules("pre_tool", call, &paths) {
+        let payload = json!({
+            "event": "pre_tool",
+            "session_id": call.session_id,
+            "cwd": call.working_dir,
+            "tool_name": call.tool_name,
+            "tool_input": call.input,
+            "paths": paths,
+        });
+        let run = run_rule(&rule, tool_event("pre_tool", call), &payload).await;
+        let blocked = run.outcome != RuleOutcome::Ok;
+        audit(
+            "pre_tool",
+            source,
+            &rule,
+            call.session_id,
+            Some(call.tool_name),
+            &paths,
+            &run,
+            Some(blocked),
+        );
+        if blocked {
+            let reason = block_reason(&rule, &run);
+            crate::logging::info(&format!(
+                "Hook rule '{}' blocked tool '{}' for session {}: {}",
+                rule.command, call.tool_name, call.session_id, reason
+            ));
+            return GateDecision::Block { reason };
+        }
+    }
+    GateDecision::Allow
+}
+
+/// Run every matching `post_tool` rule with the tool's input and result.
+pub async fn run_post_tool_rules(call: &ToolHookCall<'_>, ok: bool, output: &str) {
+    let paths = touched_paths(call.input, call.working_dir);
+    for (source, rule) in matching_tool_rules("post_tool", call, &paths) {
+        let payload = json!({
+            "event": "post_tool",
+            "session_id": call.session_id,
+            "cwd": call.working_dir,
+            "tool_name": call.tool_name,
+            "tool_input": call.input,
+            "paths": paths,
+            "tool_result": {
+                "status": if ok { "ok" } else { "error" },
+                "output": truncate_bytes(output, RESULT_OUTPUT_LIMIT),
+            },
+        });
+        let run = run_rule(&rule, tool_event("post_tool", call), &payload).await;
+        if run.outcome != RuleOutcome::Ok {
+            crate::logging::warn(&format!(
+                "Hook rule '{}' after tool '{}' {}: {}",
+                rule.command,
+                call.tool_name,
+                run.outcome.label(),
+                block_reason(&rule, &run)
+            ));
+        }
+        audit(
+            "post_tool",
+            source,
+            &rule,
+            call.session_id,
+            Some(call.tool_name),
+            &paths,
+            &run,
+            None,
+        );
+    }
+}
+
+/// Run matching `turn_end` rules in the background. `fields` is merged into
+/// the stdin payload.
+pub fn spawn_turn_end_rules(session_id: String, working_dir: Option<PathBuf>, fields: Value) {
+    let rules: Vec<(HookRuleSource, HookRule)> = load_rules(working_dir.as_deref())
+        .into_iter()
+        .filter(|(_, rule)| rule.event == "turn_end")
+        .collect();
+    if rules.is_empty() {
+        return;
+    }
+    tokio::spawn(async move {
+        let mut payload = json!({
+            "event": "turn_end",
+            "session_id": session_id,
+            "cwd": working_dir,
+        });
+        if let (Some(payload), Value::Object(fields)) = (payload.as_object_mut(), fields) {
+            payload.extend(fields);
+        }
+        for (source, rule) in rules {
+            let mut event = HookEvent::new("turn_end").session_id(session_id.clone());
+            if let Some(dir) = &working_dir {
+                event = event.cwd(dir.display().to_string());
+            }
+            let run = run_rule(&rule, event, &payload).await;
+            audit(
+                "turn_end",
+                source,
+                &rule,
+                &session_id,
+                None,
+                &[],
+                &run,
+                None,
+            );
+        }
+    });
+}
+
+fn tool_event(name: &'static str, call: &ToolHookCall<'_>) -> HookEvent {
+    let mut event = HookEvent::new(name)
+        .session_id(call.session_id)
+        .field("TOOL_NAME", call.tool_name);
+    if let Some(dir) = call.working_dir {
+        event = event.cwd(dir.display().to_string());
+    }
+    event
+}
+
+fn load_rules(working_dir: Option<&Path>) -> Vec<(HookRuleSource, HookRule)> {
+    if super::hooks_suppressed() {
+        return Vec::new();
+    }
+    let mut rules: Vec<(HookRuleSource, HookRule)> = crate::config::config()
+        .hooks
+        .rules
+        .iter()
+        .cloned()
+        .map(|rule| (HookRuleSource::Config, rule))
+        .collect();
+    if let Some(dir) = working_dir {
+        rules.extend(
+            load_project_rules(dir)
+                .into_iter()
+                .map(|rule| (HookRuleSource::Project, rule)),
+        );
+    }
+    rules.retain(|(_, rule)| !rule.command.trim().is_empty());
+    rules
+}
+
+fn load_project_rules(project_dir: &Path) -> Vec<HookRule> {
+    #[derive(Deserialize)]
+    struct ProjectHooksFile {
+        #[serde(default)]
+        hooks: Vec<HookRule>,
+    }
+
+    let path = project_dir.join(PROJECT_HOOKS_FILE);
+    let Ok(text) = std::fs::read_to_string(&path) else {
+        return Vec::new();
+    };
+    if !super::trust::is_trusted(project_dir, &text) {
+        return Vec::new();
+    }
+    match toml::from_str::<ProjectHooksFile>(&text) {
+        Ok(file) => file.hooks,
+        Err(error) => {
+            crate::logging::warn(&format!(
+                "Ignoring invalid hook rules in {}: {}",
+                path.display(),
+                error
+            ));
+            Vec::new()
+        }
+    }
+}
+
+fn matching_tool_rules(
+    event: &str,
+    call: &ToolHookCall<'_>,
+    paths: &[String],
+) -> Vec<(HookRuleSource, HookRule)> {
+    load_rules(call.working_dir)
+        .into_iter()
+        .filter(|(_, rule)| rule_matches(rule, event, call.tool_name, paths))
+        .collect()
+}
+
+fn rule_matches(rule: &HookRule, event: &str, tool_name: &str, paths: &[String]) -> bool {
+    rule.event == event
+        && (rule.tools.is_empty()
+            || rule
+                .tools
+                .iter()
+                .any(|pattern| glob_match(pattern.trim(), tool_name)))
+        && (rule.paths.is_empty()
+            || paths.iter().any(|path| {
+                rule.paths
+                    .iter()
+                    .any(|pattern| path_matches(pattern.trim(), path))
+            }))
+}
+
+/// Files a tool call touches, relative to `working_dir` when inside it.
+fn touched_paths(input: &Value, working_dir: Option<&Path>) -> Vec<String> {
+    const PATH_KEYS: [&str; 6] = [
+        "file_path",
+        "path",
+        "target",
+        "target_path",
+        "old_path",
+        "new_path",
+    ];
+    const PATCH_HEADERS: [&str; 5] = [
+        "*** Add File: ",
+        "*** Update File: ",
+        "*** Delete File: ",
+        "*** Move to: ",
+        "+++ ",
+    ];
+
+    let Some(object) = input.as_object() else {
+        return Vec::new();
+    };
+    let mut raw: Vec<&str> = PATH_KEYS
+        .iter()
+        .filter_map(|key| object.get(*key).and_then(Value::as_str))
+        .collect();
+    if let Some(paths) = object.get("paths").and_then(Value::as_array) {
+        raw.extend(paths.iter().filter_map(Value::as_str));
+    }
+    if let Some(patch) = object.get("patch_text").and_then(Value::as_str) {
+        for line in patch.lines() {
+            if let Some(path) = PATCH_HEADERS
+                .iter()
+                .find_map(|header| line.strip_prefix(header))
+            {
+                let path = path.split('\t').next().unwrap_or(path).trim();
+                if path != "/dev/null" {
+                    raw.push(path.strip_prefix("b/").unwrap_or(path));
+                }
+            }
+        }
+    }
+
+    let mut paths: Vec<String> = raw
+        .into_iter()
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .map(|path| {
+            let path = Path::new(path);
+            let relative = working_dir
+                .and_then(|dir| path.strip_prefix(dir).ok())
+                .unwrap_or(path);
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            relative
+                .strip_prefix("./")
+                .map(str::to_string)
+                .unwrap_or(relative)
+        })
+        .collect();
+    paths.dedup();
+    paths
+}
+
+/// Match a path glob. Patterns without a `/` match the file name; a trailing
+/// `/` matches everything under that directory.
+fn path_matches(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.strip_prefix("./").unwrap_or(pattern);
+    if let Some(dir) = pattern.strip_suffix('/') {
+        return glob_match(&format!("{dir}/**"), path);
+    }
+    if pattern.contains('/') {
+        glob_match(pattern, path)
+    } else {
+        glob_match(pattern, path.rsplit('/').next().unwrap_or(path))
+    }
+}
+
+/// `*` and `?` stop at `/`; `**` crosses directories.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    glob_match_chars(&pattern, &text)
+}
+
+fn glob_match_chars(pattern: &[char], text: &[char]) -> bool {
+    match pattern {
+        [] => text.is_empty(),
+        ['*', '*', rest @ ..] => {
+            if let ['/', after @ ..] = rest
+                && glob_match_chars(after, text)
+            {
+                return true;
+            }
+            (0..=text.len()).any(|index| glob_match_chars(rest, &text[index..]))
+        }
+        ['*', rest @ ..] => {
+            for index in 0..=text.len() {
+                if glob_match_chars(rest, &text[index..]) {
+                    return true;
+                }
+                if text.get(index) == Some(&'/') {
+                    break;
+                }
+            }
+            false
+        }
+        ['?', rest @ ..] => {
+            matches!(text.first(), Some(ch) if *ch != '/') && glob_match_chars(rest, &text[1..])
+        }
+        [ch, rest @ ..] => text.first() == Some(ch) && glob_match_chars(rest, &text[1..]),
+    }
+}
+
+async fn run_rule(rule: &HookRule, event: HookEvent, payload: &Value) -> RuleRun {
+    let started = Instant::now();
+    let finish = |outcome: RuleOutcome, exit_code: Option<i32>, stderr: String| RuleRun {
+        outcome,
+        exit_code,
+        stderr,
+        duration_ms: started.elapsed().as_millis().min(u128::from(u64::MAX)) as u64,
+    };
+
+    let std_cmd = match build_hook_process(&rule.command, &event) {
+        Ok(cmd) => cmd,
+        Err(error) => return finish(RuleOutcome::Error(error.to_string()), None, String::new()),
+    };
+    let mut cmd = tokio::process::Command::from(std_cmd);
+    cmd.stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true);
+    let mut child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(error) => return finish(RuleOutcome::Error(error.to_string()), None, String::new()),
+    };
+
+    let stdin = child.stdin.take();
+    let input = payload.to_string();
+    let timeout = Duration::from_millis(rule.timeout_ms.unwrap_or(DEFAULT_RULE_TIMEOUT_MS).max(1));
+    // The stdin write is inside the timeout too: a hook that never reads a
+    // large payload must not stall the agent.
+    let waited = tokio::time::timeout(timeout, async move {
+        if let Some(mut stdin) = stdin {
+            use tokio::io::AsyncWriteExt;
+            let _ = stdin.write_all(input.as_bytes()).await;
+        }
+        child.wait_with_output().await
+    })
+    .await;
+
+    match waited {
+        Ok(Ok(output)) => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let stderr = truncate_bytes(stderr.trim(), BLOCK_REASON_LIMIT).to_string();
+            let outcome = if output.status.success() {
+                RuleOutcome::Ok
+            } else {
+                RuleOutcome::Failed
+            };
+            finish(outcome, output.status.code(), stderr)
+        }
+        Ok(Err(error)) => finish(RuleOutcome::Error(error.to_string()), None, String::new()),
+        Err(_elapsed) => finish(RuleOutcome::TimedOut, None, String::new()),
+    }
+}
+
+fn block_reason(rule: &HookRule, run: &RuleRun) -> String {
+    if !run.stderr.is_empty() {
+        return run.stderr.clone();
+    }
+    match &run.outcome {
+        RuleOutcome::Ok => String::new(),
+        RuleOutcome::Failed => format!(
+            "hook `{}` exited with {}",
+            rule.command,
+            run.exit_code
+                .map(|code| code.to_string())
+                .unwrap_or_else(|| "a signal".to_string())
+        ),
+        RuleOutcome::TimedOut => format!(
+            "hook `{}` timed out after {}ms",
+            rule.command,
+            rule.timeout_ms.unwrap_or(DEFAULT_RULE_TIMEOUT_MS)
+        ),
+        RuleOutcome::Error(error) => format!("hook `{}` failed to run: {}", rule.command, error),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn audit(
+    event: &str,
+    source: HookRuleSource,
+    rule: &HookRule,
+    session_id: &str,
+    tool_name: Option<&str>,
+    paths: &[String],
+    run: &RuleRun,
+    blocked: Option<bool>,
+) {
+    let record = HookAuditRecord {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        event,
+        source,
+        command: &rule.command,
+        session_id,
+        tool_name,
+        paths,
+        outcome: run.outcome.label(),
+        exit_code: run.exit_code,
+        duration_ms: run.duration_ms,
+        stderr: &run.stderr,
+        blocked,
+    };
+    let result = crate::storage::logs_dir()
+        .and_then(|dir| crate::storage::append_json_line_fast(&dir.join(AUDIT_LOG_FILE), &record));
+    if let Err(error) = result {
+        crate::logging::debug(&format!("Failed to write hook audit record: {error}"));
+    }
+}
+
+#[cfg(test)]
+#[path = "rules_tests.rs"]
+mod rules_tests;
@@ -0,0 +1,227 @@
+use super::*;
+
+fn rule(event: &str, tools: &[&str], paths: &[&str]) -> HookRule {
+    HookRule {
+        event: event.to_string(),
+        command: "true".to_string(),
+        tools: tools.iter().map(|tool| tool.to_string()).collect(),
+        paths: paths.iter().map(|path| path.to_string()).collect(),
+        timeout_ms: None,
+    }
+}
+
+#[test]
+fn glob_star_stays_in_segment_and_double_star_crosses() {
+    assert!(glob_match("*.rs", "main.rs"));
+    assert!(!glob_match("*.rs", "src/main.rs"));
+    assert!(glob_match("src/**/*.rs", "src/main.rs"));
+    assert!(glob_match("src/**/*.rs", "src/a/b/main.rs"));
+    assert!(glob_match("mcp__*", "mcp__github__create_issue"));
+    assert!(glob_match("ed?t", "edit"));
+    assert!(!glob_match("edit", "multiedit"));
+}
+
+#[test]
+fn path_patterns_match_file_names_and_directories() {
+    assert!(path_matches("*.rs", "crates/core/src/lib.rs"));
+    assert!(path_matches("generated/", "generated/api/client.rs"));
+    assert!(path_matches("generated/**", "generated/schema.json"));
+    assert!(!path_matches("generated/**", "src/generated.rs"));
+    assert!(path_matches("./docs/*.md", "docs/intro.md"));
+}
+
+#[test]
+fn touched_paths_cover_path_fields_and_patches() {
+    let dir = Path::new("/work/repo");
+    let input = json!({"file_path": "/work/repo/src/lib.rs"});
+    assert_eq!(touched_paths(&input, Some(dir)), vec!["src/lib.rs"]);
+
+    let input = json!({
+        "patch_text": "*** Begin Patch\n*** Update File: generated/a.rs\n@@\n-x\n+y\n*** Add File: ./b.rs\n+z\n*** End Patch"
+    });
+    assert_eq!(
+        touched_paths(&input, Some(dir)),
+        vec!["generated/a.rs", "b.rs"]
+    );
+
+    let input = json!({"patch_text": "--- a/src/x.rs\n+++ b/src/x.rs\n@@ -1 +1 @@\n-a\n+b\n"});
+    assert_eq!(touched_paths(&input, Some(dir)), vec!["src/x.rs"]);
+}
+
+#[test]
+fn rules_match_on_event_tool_and_path() {
+    let fmt = rule("post_tool", &["edit", "write"], &["*.rs"]);
+    let rust = vec!["src/lib.rs".to_string()];
+    let markdown = vec!["README.md".to_string()];
+    assert!(rule_matches(&fmt, "post_tool", "edit", &rust));
+    assert!(!rule_matches(&fmt, "pre_tool", "edit", &rust));
+    assert!(!rule_matches(&fmt, "post_tool", "bash", &rust));
+    assert!(!rule_matches(&fmt, "post_tool", "edit", &markdown));
+    assert!(!rule_matches(&fmt, "post_tool", "edit", &[]));
+
+    let any = rule("pre_tool", &[], &[]);
+    assert!(rule_matches(&any, "pre_tool", "mcp__db__query", &[]));
+}
+
+#[test]
+fn hooks_config_accepts_rule_array_and_table_forms() {
+    let config: crate::config::Config = toml::from_str(
+        r#"
+[[hooks]]
+event = "pre_tool"
+command = "deny-generated"
+paths = ["generated/**"]
+"#,
+    )
+    .expect("array form");
+    assert_eq!(config.hooks.rules.len(), 1);
+    assert_eq!(config.hooks.pre_tool_timeout_ms, 5000);
+
+    let config: crate::config::Config = toml::from_str(
+        r#"
+[hooks]
+turn_end = "notify"
+
+[[hooks.rules]]
+event = "post_tool"
+command = "cargo fmt"
+tools = ["edit"]
+timeout_ms = 30000
+"#,
+    )
+    .expect("table form");
+    assert_eq!(config.hooks.turn_end.as_deref(), Some("notify"));
+    assert_eq!(config.hooks.rules[0].timeout_ms, Some(30000));
+}
+
+#[test]
+fn hooks_config_errors_name_the_bad_field() {
+    let err = toml::from_str::<crate::config::Config>(
+        "[[hooks]]\nevent = \"pre_tool\"\ncomand = \"deny-generated\"\n",
+    )
+    .expect_err("missing command");
+    let message = err.to_string();
+    assert!(message.contains("missing field `command`"), "{message}");
+    assert!(!message.contains("untagged"), "{message}");
+
+    let err = toml::from_str::<crate::config::Config>("[hooks]\npre_tool_timeout_ms = \"soon\"\n")
+        .expect_err("bad timeout");
+    let message = err.to_string();
+    assert!(message.contains("invalid type"), "{message}");
+}
+
+#[cfg(unix)]
+#[tokio::test]
+#[allow(clippy::await_holding_lock)]
+async fn project_pre_tool_rule_blocks_with_stderr_and_is_audited() {
+    let _guard = crate::storage::lock_test_env();
+    let temp = tempfile::TempDir::new().expect("temp dir");
+    let prev_home = std::env::var_os("JCODE_HOME");
+    crate::env::set_var("JCODE_HOME", temp.path().join("home"));
+
+    let project = temp.path().join("project");
+    std::fs::create_dir_all(project.join(".jcode")).expect("project dir");
+    std::fs::write(
+        project.join(PROJECT_HOOKS_FILE),
+        r#"
+[[hooks]]
+event = "pre_tool"
+tools = ["edit", "write"]
+paths = ["generated/"]
+command = "sh -c 'cat > /dev/null; echo generated files are read-only >&2; exit 1'"
+"#,
+    )
+    .expect("write hooks file");
+
+    // Nothing runs until the project's hooks file is trusted.
+    let blocked_input = json!({"file_path": "generated/api.rs"});
+    let call = ToolHookCall {
+        session_id: "ses_rules",
+        working_dir: Some(&project),
+        tool_name: "edit",
+        input: &blocked_input,
+    };
+    assert_eq!(run_pre_tool_rules(&call).await, GateDecision::Allow);
+    crate::hooks::trust_project_hooks(&project).expect("trust hooks");
+
+    let decision = run_pre_tool_rules(&call).await;
+    assert_eq!(
+        decision,
+        GateDecision::Block {
+            reason: "generated files are read-only".to_string()
+        }
+    );
+
+    let allowed_input = json!({"file_path": "src/api.rs"});
+    let decision = run_pre_tool_rules(&ToolHookCall {
+        session_id: "ses_rules",
+        working_dir: Some(&project),
+        tool_name: "edit",
+        input: &allowed_input,
+    })
+    .await;
+    assert_eq!(decision, GateDecision::Allow);
+
+    let audit_log = crate::storage::logs_dir()
+        .expect("logs dir")
+        .join(AUDIT_LOG_FILE);
+    let records = std::fs::read_to_string(audit_log).expect("audit log");
+    assert_eq!(records.lines().count(), 1);
+    let record: Value = serde_json::from_str(records.lines().next().unwrap()).unwrap();
+    assert_eq!(record["source"], "project");
+    assert_eq!(record["blocked"], true);
+    assert_eq!(record["exit_code"], 1);
+
+    if let Some(prev_home) = prev_home {
+        crate::env::set_var("JCODE_HOME", prev_home);
+    } else {
+        crate::env::remove_var("JCODE_HOME");
+    }
+}
+
+#[test]
+fn project_rules_need_trust_for_their_current_content() {
+    let _guard = crate::storage::lock_test_env();
+    let temp = tempfile::TempDir::new().expect("temp dir");
+    let prev_home = std::env::var_os("JCODE_HOME");
+    crate::env::set_var("JCODE_HOME", temp.path().join("home"));
+
+    let project = temp.path().join("project");
+    std::fs::create_dir_all(project.join(".jcode")).expect("project dir");
+    let hooks_file = project.join(PROJECT_HOOKS_FILE);
+    let rules = "[[hooks]]\nevent = \"post_tool\"\ncommand = \"cargo fmt\"\n";
+    std::fs::write(&hooks_file, rules).expect("write hooks file");
+
+    assert_eq!(
+        crate::hooks::project_hooks_trust(&project),
+        crate::hooks::ProjectHooksTrust::Untrusted { changed: false }
+    );
+    assert!(load_project_rules(&project).is_empty());
+
+    crate::hooks::trust_project_hooks(&project).expect("trust hooks");
+    assert_eq!(
+        crate::hooks::project_hooks_trust(&project),
+        crate::hooks::ProjectHooksTrust::Trusted
+    );
+    assert_eq!(load_project_rules(&project).len(), 1);
+
+    std::fs::write(
+        &hooks_file,
+        format!("{rules}\n[[hooks]]\nevent = \"turn_end\"\ncommand = \"curl evil\"\n"),
+    )
+    .expect("change hooks file");
+    assert_eq!(
+        crate::hooks::project_hooks_trust(&project),
+        crate::hooks::ProjectHooksTrust::Untrusted { changed: true }
+    );
+    assert!(load_project_rules(&project).is_empty());
+
+    assert!(crate::hooks::untrust_project_hooks(&project).expect("untrust"));
+    assert!(!crate::hooks::untrust_project_hooks(&project).expect("untrust again"));
+
+    if let Some(prev_home) = prev_home {
+        crate::env::set_var("JCODE_HOME", prev_home);
+    } else {
+        crate::env::remove_var("JCODE_HOME");
+    }
+}
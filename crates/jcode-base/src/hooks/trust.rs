@@ -0,0 +1,121 @@
+//! Trust decisions for project hook rules (`.jcode/hooks.toml`).
+//!
+//! A checkout's hook file runs shell commands on every matching tool call, so
+//! it only loads after the user trusts that exact file with
+//! `jcode hooks trust`. Decisions live in `~/.jcode/trusted_project_hooks.json`,
+//! keyed by the canonical project path and the file's SHA-256; editing the
+//! file (or pulling a change to it) needs a fresh decision.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+
+use super::rules::PROJECT_HOOKS_FILE;
+
+const TRUST_FILE: &str = "trusted_project_hooks.json";
+
+/// Untrusted files already reported, so the warning is logged once per
+/// content rather than on every tool call.
+static REPORTED_UNTRUSTED: LazyLock<Mutex<HashSet<(PathBuf, String)>>> =
+    LazyLock::new(|| Mutex::new(HashSet::new()));
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TrustStore {
+    /// Canonical project directory -> SHA-256 of the trusted hooks file.
+    #[serde(default)]
+    projects: BTreeMap<String, String>,
+}
+
+/// Trust state of a project's hooks file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProjectHooksTrust {
+    /// The project has no `.jcode/hooks.toml`.
+    NoFile,
+    Trusted,
+    /// Never trusted, or changed since it was.
+    Untrusted {
+        changed: bool,
+    },
+}
+
+fn store_path() -> Result<PathBuf> {
+    Ok(crate::storage::jcode_dir()?.join(TRUST_FILE))
+}
+
+fn load_store() -> TrustStore {
+    store_path()
+        .ok()
+        .filter(|path| path.exists())
+        .and_then(|path| crate::storage::read_json(&path).ok())
+        .unwrap_or_default()
+}
+
+fn project_key(project_dir: &Path) -> String {
+    std::fs::canonicalize(project_dir)
+        .unwrap_or_else(|_| project_dir.to_path_buf())
+        .display()
+        .to_string()
+}
+
+fn content_hash(text: &str) -> String {
+    hex::encode(Sha256::digest(text.as_bytes()))
+}
+
+/// Whether the hooks file with contents `text` in `project_dir` is trusted.
+/// Logs a warning the first time an untrusted version is seen.
+pub(super) fn is_trusted(project_dir: &Path, text: &str) -> bool {
+    let key = project_key(project_dir);
+    let hash = content_hash(text);
+    if load_store().projects.get(&key) == Some(&hash) {
+        return true;
+    }
+    let first = REPORTED_UNTRUSTED
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert((PathBuf::from(&key), hash));
+    if first {
+        crate::logging::warn(&format!(
+            "Ignoring hook rules in {}: not trusted. Review the file, then run `jcode hooks trust` in that directory.",
+            project_dir.join(PROJECT_HOOKS_FILE).display()
+        ));
+    }
+    false
+}
+
+pub fn project_hooks_trust(project_dir: &Path) -> ProjectHooksTrust {
+    let Ok(text) = std::fs::read_to_string(project_dir.join(PROJECT_HOOKS_FILE)) else {
+        return ProjectHooksTrust::NoFile;
+    };
+    match load_store().projects.get(&project_key(project_dir)) {
+        Some(hash) if *hash == content_hash(&text) => ProjectHooksTrust::Trusted,
+        Some(_) => ProjectHooksTrust::Untrusted { changed: true },
+        None => ProjectHooksTrust::Untrusted { changed: false },
+    }
+}
+
+/// Trust the current contents of `project_dir`'s hooks file. Returns the
+/// file's path.
+pub fn trust_project_hooks(project_dir: &Path) -> Result<PathBuf> {
+    let path = project_dir.join(PROJECT_HOOKS_FILE);
+    let text = std::fs::read_to_string(&path)
+        .with_context(|| format!("No project hook rules at {}", path.display()))?;
+    let mut store = load_store();
+    store
+        .projects
+        .insert(project_key(project_dir), content_hash(&text));
+    crate::storage::write_json(&store_path()?, &store)?;
+    Ok(path)
+}
+
+/// Forget the trust decision for `project_dir`. Returns whether there was one.
+pub fn untrust_project_hooks(project_dir: &Path) -> Result<bool> {
+    let mut store = load_store();
+    let removed = store.projects.remove(&project_key(project_dir)).is_some();
+    if removed {
+        crate::storage::write_json(&store_path()?, &store)?;
+    }
+    Ok(removed)
+}
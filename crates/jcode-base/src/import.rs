@@ -4,7 +4,7 @@
 //! so they can be resumed within jcode.
 
 use crate::message::{ContentBlock, Role};
-use crate::session::{Session, SessionStatus, StoredMessage};
+use crate::session::{SESSION_ORIGIN_IMPORTED, Session, SessionStatus, StoredMessage};
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use jcode_import_core::{
@@ -368,7 +368,8 @@ fn convert_content_blocks(content: &ClaudeCodeContent) -> Vec<ContentBlock> {
                     content: content.clone(),
                     is_error: *is_error,
                 }),
-                ClaudeCodeContentBlock::Unknown => None,
+                ClaudeCodeContentBlock::Image {} => Some(opaque_note("image")),
+                ClaudeCodeContentBlock::Unknown => Some(opaque_note("Claude Code content block")),
             })
             .collect(),
     }
@@ -575,11 +576,11 @@ pub fn import_session_from_file(path: &Path, session_id: &str) -> Result<Session
     // Create jcode session
     let jcode_session_id = imported_claude_code_session_id(session_id);
 
-    // Don't clobber a continuation. The resume picker hides the imported jcode
-    // session and only shows the external `claude:<id>` entry, so re-selecting it
-    // calls back into this function. If the user already resumed and continued
-    // the imported session inside jcode, a plain re-import would overwrite their
-    // snapshot and silently drop those messages. When the existing imported
+    // Don't clobber a continuation. The resume picker prefers the external
+    // `claude:<id>` entry once the transcript is newer than the imported jcode
+    // session, so re-selecting it calls back into this function. If the user
+    // already resumed and continued the imported session inside jcode, a plain
+    // re-import would overwrite their snapshot and silently drop those messages. When the existing imported
     // snapshot already has more messages than the external transcript (i.e. it
     // diverged with jcode-side work), keep it as-is and resume that instead.
     if crate::session::session_exists(&jcode_session_id)
@@ -596,6 +597,7 @@ pub fn import_session_from_file(path: &Path, session_id: &str) -> Result<Session
     session.model = model;
     session.created_at = created_at;
     session.status = SessionStatus::Closed;
+    session.origin = Some(SESSION_ORIGIN_IMPORTED.to_string());
 
     for message in imported_messages {
         session.append_stored_message(message);
@@ -631,6 +633,51 @@ fn append_text_message(
     });
 }
 
+/// Placeholder for source content jcode has no block for, so the transcript
+/// shows that something was there instead of silently dropping it.
+fn opaque_note(kind: &str) -> ContentBlock {
+    ContentBlock::Text {
+        text: format!("[{} not imported]", kind),
+        cache_control: None,
+    }
+}
+
+/// Append `block` as its own message, except that a tool call joins the
+/// assistant message before it and consecutive tool results share one user
+/// message, matching how jcode records tool turns.
+fn push_imported_block(
+    messages: &mut Vec<StoredMessage>,
+    role: Role,
+    block: ContentBlock,
+    timestamp: Option<DateTime<Utc>>,
+) {
+    let joins_previous = match &block {
+        ContentBlock::ToolUse { .. } => true,
+        ContentBlock::ToolResult { .. } => messages.last().is_some_and(|last| {
+            last.content
+                .iter()
+                .all(|block| matches!(block, ContentBlock::ToolResult { .. }))
+        }),
+        _ => false,
+    };
+    if joins_previous
+        && let Some(last) = messages.last_mut()
+        && last.role == role
+    {
+        last.content.push(block);
+        return;
+    }
+    messages.push(StoredMessage {
+        id: crate::id::new_id("msg"),
+        role,
+        content: vec![block],
+        display_role: None,
+        timestamp,
+        tool_duration_ms: None,
+        token_usage: None,
+    });
+}
+
 fn finalize_imported_session(
     mut session: Session,
     created_at: DateTime<Utc>,
@@ -640,6 +687,7 @@ fn finalize_imported_session(
     session.updated_at = updated_at.unwrap_or(created_at);
     session.last_active_at = updated_at.or(Some(created_at));
     session.status = SessionStatus::Closed;
+    session.origin = Some(SESSION_ORIGIN_IMPORTED.to_string());
     session.save()?;
     Ok(session)
 }
@@ -708,6 +756,7 @@ pub fn import_codex_session_from_path(
         .and_then(|v| v.as_str())
         .map(|s| s.to_string());
     let mut model: Option<String> = None;
+    let mut messages: Vec<StoredMessage> = Vec::new();
     let mut session = Session::create_with_id(imported_codex_session_id(session_id), None, None);
     session.provider_session_id = Some(session_id.to_string());
     session.provider_key = Some("openai-codex".to_string());
@@ -735,11 +784,35 @@ pub fn import_codex_session_from_path(
                 value.get("timestamp"),
                 value.get("model"),
             )
+        } else if line_type == "turn_context" {
+            let Some(payload) = value.get("payload") else {
+                continue;
+            };
+            if working_dir.is_none() {
+                working_dir = payload
+                    .get("cwd")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string());
+            }
+            if model.is_none() {
+                model = payload
+                    .get("model")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string());
+            }
+            continue;
         } else if line_type == "response_item" {
             let Some(payload) = value.get("payload") else {
                 continue;
             };
             if payload.get("type").and_then(|v| v.as_str()) != Some("message") {
+                let timestamp = parse_rfc3339_json(value.get("timestamp"));
+                if let Some((role, block)) = codex_item_block(payload) {
+                    if timestamp.is_some() {
+                        updated_at = timestamp;
+                    }
+                    push_imported_block(&mut messages, role, block, timestamp);
+                }
                 continue;
             }
             let Some(role) = payload.get("role").and_then(|v| v.as_str()) else {
@@ -784,15 +857,99 @@ pub fn import_codex_session_from_path(
         if timestamp.is_some() {
             updated_at = timestamp;
         }
-        append_text_message(&mut session, role, text, timestamp);
+        let text = text.trim();
+        if !text.is_empty() {
+            let block = ContentBlock::Text {
+                text: text.to_string(),
+                cache_control: None,
+            };
+            push_imported_block(&mut messages, role, block, timestamp);
+        }
     }
 
+    for message in messages {
+        session.append_stored_message(message);
+    }
     session.title = title.or_else(|| Some(format!("Codex session {}", session_id)));
     session.working_dir = working_dir;
     session.model = model;
     finalize_imported_session(session, created_at, updated_at)
 }
 
+/// Convert a non-message Codex `response_item` payload. Tool calls and their
+/// outputs map onto jcode tool blocks and reasoning summaries onto reasoning;
+/// anything else becomes an opaque note. Returns `None` for items with
+/// nothing worth keeping.
+fn codex_item_block(payload: &serde_json::Value) -> Option<(Role, ContentBlock)> {
+    let item_type = payload.get("type").and_then(|v| v.as_str())?;
+    let call_id = || {
+        payload
+            .get("call_id")
+            .or_else(|| payload.get("id"))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| crate::id::new_id("call"))
+    };
+    match item_type {
+        "function_call" | "custom_tool_call" => {
+            let name = payload
+                .get("name")
+                .and_then(|v| v.as_str())
+                .unwrap_or("tool")
+                .to_string();
+            // `function_call` carries JSON-encoded arguments, `custom_tool_call`
+            // free-form input (e.g. an apply_patch body).
+            let input = match payload.get("arguments").or_else(|| payload.get("input")) {
+                Some(serde_json::Value::String(raw)) => serde_json::from_str(raw)
+                    .ok()
+                    .filter(serde_json::Value::is_object)
+                    .unwrap_or_else(|| serde_json::json!({ "input": raw })),
+                Some(value) => value.clone(),
+                None => serde_json::json!({}),
+            };
+            Some((
+                Role::Assistant,
+                ContentBlock::ToolUse {
+                    id: call_id(),
+                    name,
+                    input,
+                    thought_signature: None,
+                },
+            ))
+        }
+        "function_call_output" | "custom_tool_call_output" => {
+            let content = match payload.get("output") {
+                Some(serde_json::Value::String(output)) => output.clone(),
+                Some(output) => extract_text_from_json_value(output),
+                None => String::new(),
+            };
+            Some((
+                Role::User,
+                ContentBlock::ToolResult {
+                    tool_use_id: call_id(),
+                    content,
+                    is_error: None,
+                },
+            ))
+        }
+        "reasoning" => {
+            let text = extract_text_from_json_value(
+                payload.get("summary").unwrap_or(&serde_json::Value::Null),
+            );
+            let text = text.trim();
+            (!text.is_empty()).then(|| {
+                (
+                    Role::Assistant,
+                    ContentBlock::Reasoning {
+                        text: text.to_string(),
+                    },
+                )
+            })
+        }
+        other => Some((Role::Assistant, opaque_note(&format!("Codex {}", other)))),
+    }
+}
+
 pub fn import_pi_session(session_path: &str) -> Result<Session> {
     let path = PathBuf::from(session_path);
     let file = File::open(&path)?;
@@ -1099,6 +1256,67 @@ pub fn import_cursor_session_from_path(
     finalize_imported_session(session, created_at, Some(created_at))
 }
 
+/// Another CLI whose transcripts `jcode sessions import` converts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionImportSource {
+    ClaudeCode,
+    Codex,
+}
+
+impl SessionImportSource {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ClaudeCode => "Claude Code",
+            Self::Codex => "Codex",
+        }
+    }
+
+    /// Where the CLI keeps its transcripts when no path is given.
+    pub fn default_root(self) -> Result<PathBuf> {
+        match self {
+            Self::ClaudeCode => crate::storage::user_home_path(".claude/projects"),
+            Self::Codex => crate::storage::user_home_path(".codex/sessions"),
+        }
+    }
+}
+
+/// Transcript files to import: `path` itself when it is a file, every
+/// `.jsonl` below it when it is a directory, and the source's default
+/// directory when no path is given.
+pub fn session_import_files(
+    source: SessionImportSource,
+    path: Option<&Path>,
+) -> Result<Vec<PathBuf>> {
+    let root = match path {
+        Some(path) => path.to_path_buf(),
+        None => source.default_root()?,
+    };
+    if root.is_file() {
+        return Ok(vec![root]);
+    }
+    if !root.is_dir() {
+        anyhow::bail!("No {} transcripts at {}", source.label(), root.display());
+    }
+    let mut files = collect_files_recursive(&root, "jsonl");
+    files.sort();
+    Ok(files)
+}
+
+/// Convert one transcript file into a saved jcode session. Returns `None` for
+/// a Claude Code file without any messages (e.g. a summary-only stub).
+pub fn import_session_file(source: SessionImportSource, path: &Path) -> Result<Option<Session>> {
+    match source {
+        SessionImportSource::ClaudeCode => {
+            let info = claude_code_session_info_from_file(path, None)?;
+            if info.message_count == 0 {
+                return Ok(None);
+            }
+            import_session_from_file(path, &info.session_id).map(Some)
+        }
+        SessionImportSource::Codex => import_codex_session_from_path(path, None).map(Some),
+    }
+}
+
 #[cfg(test)]
 #[path = "import_tests.rs"]
 mod tests;
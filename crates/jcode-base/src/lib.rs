@@ -19,8 +19,10 @@
 
 pub mod auth;
 pub mod background;
+pub mod background_model;
 pub mod browser;
 pub mod bus;
+pub mod bus_tap;
 pub mod cache_tracker;
 pub mod client_input;
 pub mod compaction;
@@ -30,11 +32,13 @@ pub mod dictation;
 #[cfg(feature = "embeddings")]
 pub mod embedding;
 pub mod embedding_backend;
+pub mod embedding_cache;
 #[cfg(not(feature = "embeddings"))]
 pub mod embedding_stub;
 pub mod env;
 pub mod gateway;
 pub mod generated_image;
+pub mod git_auto_commit;
 pub mod gmail;
 pub mod goal;
 pub mod hooks;
@@ -47,12 +51,16 @@ pub mod mcp;
 pub mod memory;
 pub mod memory_agent;
 pub mod memory_graph;
+pub mod memory_injection;
 pub mod memory_judge_metrics;
 pub mod memory_log;
 pub mod memory_rerank;
 pub mod memory_types;
 pub mod message;
+pub mod metrics;
 pub mod model_pricing;
+pub mod network_policy;
+pub mod output_tee;
 pub mod plan;
 pub mod platform;
 pub mod power_inhibit;
@@ -63,9 +71,12 @@ pub mod protocol;
 pub mod provider;
 pub mod provider_activity;
 pub mod provider_catalog;
+pub mod queued_message_store;
+pub mod redaction;
 pub mod registry;
 pub mod runtime_memory_log;
 pub mod safety;
+pub mod saved_prompt;
 pub mod secret_input;
 pub mod session;
 pub mod session_list_cache;
@@ -83,6 +94,7 @@ pub mod telemetry {
 }
 pub mod terminal_launch;
 pub mod todo;
+pub mod todo_sync;
 pub mod transport;
 pub mod usage;
 pub mod util;
@@ -0,0 +1,100 @@
+//! MCP tool calls in flight, by session, so the user can cancel a stuck call
+//! without interrupting the whole turn.
+
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex};
+use tokio::sync::Notify;
+
+struct InFlightCall {
+    session_id: String,
+    cancel: Arc<Notify>,
+}
+
+/// Calls in flight keyed by tool call id.
+static IN_FLIGHT: LazyLock<Mutex<HashMap<String, InFlightCall>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Registration of one running call. The call stops waiting once
+/// [`CallRegistration::cancelled`] resolves; dropping it unregisters the call.
+pub struct CallRegistration {
+    tool_call_id: String,
+    cancel: Arc<Notify>,
+}
+
+impl CallRegistration {
+    /// Resolves when the user cancels this call.
+    pub async fn cancelled(&self) {
+        self.cancel.notified().await;
+    }
+}
+
+impl Drop for CallRegistration {
+    fn drop(&mut self) {
+        IN_FLIGHT
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .remove(&self.tool_call_id);
+    }
+}
+
+/// Track a running MCP call so [`cancel_session_calls`] can reach it.
+pub fn track_call(session_id: &str, tool_call_id: &str) -> CallRegistration {
+    let cancel = Arc::new(Notify::new());
+    IN_FLIGHT
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(
+            tool_call_id.to_string(),
+            InFlightCall {
+                session_id: session_id.to_string(),
+                cancel: Arc::clone(&cancel),
+            },
+        );
+    CallRegistration {
+        tool_call_id: tool_call_id.to_string(),
+        cancel,
+    }
+}
+
+/// Cancel every MCP call running for `session_id`. Returns how many were
+/// cancelled.
+pub fn cancel_session_calls(session_id: &str) -> usize {
+    let in_flight = IN_FLIGHT
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let mut cancelled = 0;
+    for call in in_flight.values() {
+        if call.session_id == session_id {
+            // `notify_one` keeps a permit, so a cancel that lands before the
+            // call starts waiting is not lost.
+            call.cancel.notify_one();
+            cancelled += 1;
+        }
+    }
+    cancelled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn cancel_reaches_only_the_sessions_calls() {
+        let mine = track_call("session_cancel_a", "call_cancel_1");
+        let other = track_call("session_cancel_b", "call_cancel_2");
+
+        assert_eq!(cancel_session_calls("session_cancel_a"), 1);
+        tokio::time::timeout(Duration::from_secs(1), mine.cancelled())
+            .await
+            .expect("the cancelled call must wake");
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), other.cancelled())
+                .await
+                .is_err()
+        );
+
+        drop(mine);
+        assert_eq!(cancel_session_calls("session_cancel_a"), 0);
+    }
+}
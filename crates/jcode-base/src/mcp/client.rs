@@ -6,7 +6,8 @@ use serde_json::Value;
 use std::collections::HashMap;
 use std::process::Stdio;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::time::Duration;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::process::{Child, Command};
 use tokio::sync::{Mutex, mpsc, oneshot};
@@ -23,11 +24,135 @@ pub struct McpHandle {
     server_info: Arc<std::sync::RwLock<Option<ServerInfo>>>,
     capabilities: Arc<std::sync::RwLock<ServerCapabilities>>,
     tools: Arc<std::sync::RwLock<Vec<McpToolDef>>>,
+    /// How long a `tools/call` may run before it is cancelled
+    call_timeout: Duration,
+    /// Tool calls in a row that timed out; the server is degraded at
+    /// [`DEGRADED_AFTER_TIMEOUTS`].
+    consecutive_timeouts: Arc<AtomicU32>,
+}
+
+/// Consecutive tool call timeouts after which a server is marked degraded.
+pub const DEGRADED_AFTER_TIMEOUTS: u32 = 3;
+
+/// A tool call that did not complete. Surfaced to the model as a structured
+/// tool result rather than a transport error.
+#[derive(Debug, Clone)]
+pub enum McpCallError {
+    TimedOut {
+        server: String,
+        tool: String,
+        after: Duration,
+        /// Consecutive timeouts on this server, including this one
+        consecutive: u32,
+    },
+    Cancelled {
+        server: String,
+        tool: String,
+    },
+}
+
+impl McpCallError {
+    /// Machine-readable form for the tool result metadata.
+    pub fn metadata(&self) -> Value {
+        match self {
+            Self::TimedOut {
+                server,
+                tool,
+                after,
+                consecutive,
+            } => serde_json::json!({
+                "mcp_error": "timeout",
+                "server": server,
+                "tool": tool,
+                "timeout_secs": after.as_secs(),
+                "consecutive_timeouts": consecutive,
+                "server_degraded": *consecutive >= DEGRADED_AFTER_TIMEOUTS,
+            }),
+            Self::Cancelled { server, tool } => serde_json::json!({
+                "mcp_error": "cancelled",
+                "server": server,
+                "tool": tool,
+            }),
+        }
+    }
+}
+
+impl std::fmt::Display for McpCallError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TimedOut {
+                server,
+                tool,
+                after,
+                consecutive,
+            } => {
+                write!(
+                    f,
+                    "MCP tool '{}' on server '{}' timed out after {}s and was cancelled",
+                    tool,
+                    server,
+                    after.as_secs()
+                )?;
+                if *consecutive >= DEGRADED_AFTER_TIMEOUTS {
+                    write!(
+                        f,
+                        "; '{}' is degraded after {} timeouts in a row \
+                         (the mcp tool's reload action reconnects it)",
+                        server, consecutive
+                    )?;
+                }
+                Ok(())
+            }
+            Self::Cancelled { server, tool } => write!(
+                f,
+                "MCP tool '{}' on server '{}' was cancelled by the user",
+                tool, server
+            ),
+        }
+    }
+}
+
+impl std::error::Error for McpCallError {}
+
+/// A request awaiting its response. Dropping it before the response arrives
+/// (timeout, user cancel, interrupted turn) forgets the request and tells the
+/// server to stop working on it.
+struct InFlightRequest<'a> {
+    handle: &'a McpHandle,
+    id: u64,
+    answered: bool,
+}
+
+impl Drop for InFlightRequest<'_> {
+    fn drop(&mut self) {
+        if self.answered {
+            return;
+        }
+        let notification = JsonRpcRequest::new(
+            0,
+            "notifications/cancelled",
+            Some(serde_json::json!({ "requestId": self.id, "reason": "client cancelled" })),
+        );
+        if let Ok(msg) = serde_json::to_string(&notification) {
+            let _ = self.handle.writer_tx.try_send(msg + "\n");
+        }
+        let pending = Arc::clone(&self.handle.pending);
+        let id = self.id;
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            runtime.spawn(async move {
+                pending.lock().await.remove(&id);
+            });
+        }
+    }
 }
 
 impl McpHandle {
-    /// Send a request and wait for response
-    pub async fn request(&self, method: &str, params: Option<Value>) -> Result<JsonRpcResponse> {
+    /// Write a request and return its id and the receiver for the response.
+    async fn send_request(
+        &self,
+        method: &str,
+        params: Option<Value>,
+    ) -> Result<(u64, oneshot::Receiver<JsonRpcResponse>)> {
         let id = self.request_id.fetch_add(1, Ordering::SeqCst);
         let request = JsonRpcRequest::new(id, method, params);
 
@@ -42,6 +167,12 @@ impl McpHandle {
             .send(msg)
             .await
             .context("Failed to send request")?;
+        Ok((id, rx))
+    }
+
+    /// Send a request and wait for response
+    pub async fn request(&self, method: &str, params: Option<Value>) -> Result<JsonRpcResponse> {
+        let (_, rx) = self.send_request(method, params).await?;
 
         let response = tokio::time::timeout(std::time::Duration::from_secs(30), rx)
             .await
@@ -55,7 +186,8 @@ impl McpHandle {
         Ok(response)
     }
 
-    /// Call a tool
+    /// Call a tool, cancelling it once the server's call timeout passes.
+    /// Dropping the returned future cancels the call as well.
     pub async fn call_tool(&self, name: &str, arguments: Value) -> Result<ToolCallResult> {
         let arguments = if arguments.is_null() {
             Value::Object(serde_json::Map::new())
@@ -67,10 +199,41 @@ impl McpHandle {
             arguments,
         };
 
-        let response = self
-            .request("tools/call", Some(serde_json::to_value(params)?))
+        let (id, rx) = self
+            .send_request("tools/call", Some(serde_json::to_value(params)?))
             .await?;
+        let mut in_flight = InFlightRequest {
+            handle: self,
+            id,
+            answered: false,
+        };
+        let response = match tokio::time::timeout(self.call_timeout, rx).await {
+            Ok(response) => {
+                in_flight.answered = true;
+                self.consecutive_timeouts.store(0, Ordering::Relaxed);
+                response.context("Channel closed")?
+            }
+            Err(_) => {
+                let consecutive = self.consecutive_timeouts.fetch_add(1, Ordering::Relaxed) + 1;
+                if consecutive == DEGRADED_AFTER_TIMEOUTS {
+                    crate::logging::warn(&format!(
+                        "MCP [{}]: marked degraded after {} tool call timeouts in a row",
+                        self.name, consecutive
+                    ));
+                }
+                return Err(McpCallError::TimedOut {
+                    server: self.name.clone(),
+                    tool: name.to_string(),
+                    after: self.call_timeout,
+                    consecutive,
+                }
+                .into());
+            }
+        };
 
+        if let Some(err) = &response.error {
+            anyhow::bail!("MCP error {}: {}", err.code, err.message);
+        }
         let result = response.result.context("No result from tool call")?;
         let tool_result: ToolCallResult = serde_json::from_value(result)?;
 
@@ -82,6 +245,11 @@ impl McpHandle {
         &self.name
     }
 
+    /// Whether recent tool calls kept timing out.
+    pub fn is_degraded(&self) -> bool {
+        self.consecutive_timeouts.load(Ordering::Relaxed) >= DEGRADED_AFTER_TIMEOUTS
+    }
+
     /// Get server info
     pub fn server_info(&self) -> Option<ServerInfo> {
         self.server_info
@@ -234,6 +402,8 @@ impl McpClient {
             server_info: Arc::new(std::sync::RwLock::new(None)),
             capabilities: Arc::new(std::sync::RwLock::new(ServerCapabilities::default())),
             tools: Arc::new(std::sync::RwLock::new(Vec::new())),
+            call_timeout: config.call_timeout(),
+            consecutive_timeouts: Arc::new(AtomicU32::new(0)),
         };
 
         let mut client = Self { handle, child };
@@ -337,6 +507,10 @@ impl McpClient {
         self.handle.tools()
     }
 
+    pub fn is_degraded(&self) -> bool {
+        self.handle.is_degraded()
+    }
+
     pub async fn call_tool(&self, name: &str, arguments: Value) -> Result<ToolCallResult> {
         self.handle.call_tool(name, arguments).await
     }
@@ -245,6 +245,27 @@ impl McpManager {
         names
     }
 
+    /// Connected servers whose tool calls keep timing out.
+    pub async fn degraded_servers(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .pool_handles
+            .read()
+            .await
+            .iter()
+            .filter(|(_, handle)| handle.is_degraded())
+            .map(|(name, _)| name.clone())
+            .collect();
+        names.extend(
+            self.owned_clients
+                .read()
+                .await
+                .iter()
+                .filter(|(_, client)| client.is_degraded())
+                .map(|(name, _)| name.clone()),
+        );
+        names
+    }
+
     /// Get all available tools from all connected servers
     pub async fn all_tools(&self) -> Vec<(String, McpToolDef)> {
         let mut tools = Vec::new();
@@ -494,6 +515,7 @@ mod tests {
                 shared: false,
                 transport: None,
                 url: None,
+                timeout_secs: None,
             },
         );
         let manager = McpManager::with_config(config);
@@ -516,4 +538,68 @@ mod tests {
             "connect-on-first-call must be bounded"
         );
     }
+
+    /// An MCP server that completes the handshake, then never answers a
+    /// `tools/call`.
+    #[cfg(unix)]
+    fn hanging_server_config() -> McpServerConfig {
+        let script = r#"
+while IFS= read -r line; do
+  id=$(printf '%s' "$line" | sed -n 's/.*"id":\([0-9]*\).*/\1/p')
+  case "$line" in
+    *'"initialize"'*)
+      printf '{"jsonrpc":"2.0","id":%s,"result":{"protocolVersion":"2024-11-05","capabilities":{},"serverInfo":{"name":"stub","version":"0"}}}\n' "$id" ;;
+    *'"tools/list"'*)
+      printf '{"jsonrpc":"2.0","id":%s,"result":{"tools":[{"name":"hang","inputSchema":{"type":"object"}}]}}\n' "$id" ;;
+    *'"tools/call"'*)
+      exec sleep 1000000 ;;
+  esac
+done
+"#;
+        McpServerConfig {
+            command: "sh".to_string(),
+            args: vec!["-c".to_string(), script.to_string()],
+            env: HashMap::new(),
+            shared: false,
+            transport: None,
+            url: None,
+            timeout_secs: Some(1),
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn tool_call_on_hanging_server_times_out_and_degrades_server() {
+        let mut config = McpConfig::default();
+        config
+            .servers
+            .insert("stub".to_string(), hanging_server_config());
+        let manager = McpManager::with_config(config);
+        manager
+            .ensure_server_connected("stub", Duration::from_secs(10))
+            .await
+            .expect("stub server should complete the handshake");
+
+        for attempt in 1..=crate::mcp::DEGRADED_AFTER_TIMEOUTS {
+            let started = std::time::Instant::now();
+            let err = tokio::time::timeout(
+                Duration::from_secs(10),
+                manager.call_tool("stub", "hang", serde_json::json!({})),
+            )
+            .await
+            .expect("call_tool must return, not hang")
+            .expect_err("a call with no response must time out");
+            assert!(started.elapsed() < Duration::from_secs(5));
+            let Some(crate::mcp::McpCallError::TimedOut {
+                after, consecutive, ..
+            }) = err.downcast_ref::<crate::mcp::McpCallError>()
+            else {
+                panic!("expected a structured timeout, got: {err:#}");
+            };
+            assert_eq!((*after, *consecutive), (Duration::from_secs(1), attempt));
+        }
+
+        assert_eq!(manager.degraded_servers().await, vec!["stub".to_string()]);
+        manager.disconnect_all().await;
+    }
 }
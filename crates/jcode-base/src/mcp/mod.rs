@@ -4,6 +4,7 @@
 //! Supports shared server pools so multiple sessions reuse the same
 //! MCP server processes instead of spawning duplicates.
 
+mod cancel;
 mod client;
 mod manager;
 pub mod pool;
@@ -11,7 +12,8 @@ mod protocol;
 pub mod schema_cache;
 mod tool;
 
-pub use client::{McpClient, McpHandle};
+pub use cancel::{CallRegistration, cancel_session_calls, track_call};
+pub use client::{DEGRADED_AFTER_TIMEOUTS, McpCallError, McpClient, McpHandle};
 pub use manager::McpManager;
 pub use pool::{SharedMcpPool, get_shared_pool, init_shared_pool};
 pub use protocol::*;
@@ -189,6 +189,10 @@ pub struct McpServerConfig {
     /// URL for HTTP/SSE servers (Claude Code compat). Unused by jcode today.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
+    /// Seconds a `tools/call` may run before it is cancelled
+    /// (default [`DEFAULT_TOOL_CALL_TIMEOUT_SECS`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
 }
 
 impl McpServerConfig {
@@ -204,8 +208,20 @@ impl McpServerConfig {
         }
         !self.command.trim().is_empty()
     }
+
+    /// How long a tool call on this server may run before it is cancelled.
+    pub fn call_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(
+            self.timeout_secs
+                .unwrap_or(DEFAULT_TOOL_CALL_TIMEOUT_SECS)
+                .max(1),
+        )
+    }
 }
 
+/// Tool call timeout for servers that do not set `timeout_secs`.
+pub const DEFAULT_TOOL_CALL_TIMEOUT_SECS: u64 = 60;
+
 fn default_shared() -> bool {
     true
 }
@@ -349,6 +365,10 @@ impl McpConfig {
                         .get("shared")
                         .and_then(|v| v.as_bool())
                         .unwrap_or(true);
+                    let timeout_secs = server
+                        .get("tool_timeout_sec")
+                        .and_then(|v| v.as_float().or_else(|| v.as_integer().map(|i| i as f64)))
+                        .map(|secs| secs.ceil() as u64);
                     config.servers.insert(
                         name.clone(),
                         McpServerConfig {
@@ -358,6 +378,7 @@ impl McpConfig {
                             shared,
                             transport: None,
                             url: None,
+                            timeout_secs,
                         },
                     );
                 }
@@ -409,14 +430,46 @@ impl McpConfig {
     }
 
     /// Load from default locations (merges jcode global + local, local overrides)
-    #[expect(
-        clippy::collapsible_if,
-        reason = "Import logic keeps source-specific MCP config merge order explicit"
-    )]
     pub fn load() -> Self {
         // First-run import from Claude Code / Codex CLI
         Self::import_from_external();
 
+        let mut merged = Self::load_sources();
+
+        // jcode only supports stdio servers today. Drop HTTP/SSE entries (common
+        // in Claude Code configs) so they don't fail to spawn, but log them so
+        // the omission is visible.
+        merged.servers.retain(|name, cfg| {
+            let keep = cfg.is_stdio();
+            if !keep {
+                crate::logging::info(&format!(
+                    "MCP: Skipping non-stdio server '{}' ({}); HTTP/SSE transports are not yet supported",
+                    name,
+                    cfg.transport.as_deref().unwrap_or("http")
+                ));
+            }
+            keep
+        });
+
+        merged
+    }
+
+    /// URLs of HTTP/SSE servers across all config sources. jcode does not
+    /// connect to them yet, but the default `[network]` allowlist includes them.
+    pub fn endpoint_urls() -> Vec<String> {
+        Self::load_sources()
+            .servers
+            .into_values()
+            .filter_map(|cfg| cfg.url)
+            .collect()
+    }
+
+    /// Merge every config source, including servers jcode cannot run.
+    #[expect(
+        clippy::collapsible_if,
+        reason = "Import logic keeps source-specific MCP config merge order explicit"
+    )]
+    fn load_sources() -> Self {
         let mut merged = Self::default();
 
         // Load jcode's own global config (~/.jcode/mcp.json)
@@ -463,21 +516,6 @@ impl McpConfig {
             }
         }
 
-        // jcode only supports stdio servers today. Drop HTTP/SSE entries (common
-        // in Claude Code configs) so they don't fail to spawn, but log them so
-        // the omission is visible.
-        merged.servers.retain(|name, cfg| {
-            let keep = cfg.is_stdio();
-            if !keep {
-                crate::logging::info(&format!(
-                    "MCP: Skipping non-stdio server '{}' ({}); HTTP/SSE transports are not yet supported",
-                    name,
-                    cfg.transport.as_deref().unwrap_or("http")
-                ));
-            }
-            keep
-        });
-
         merged
     }
 }
@@ -1,5 +1,6 @@
 //! MCP Tool - wraps MCP server tools for jcode's tool system
 
+use super::client::McpCallError;
 use super::manager::McpManager;
 use super::protocol::{ContentBlock, McpToolDef};
 use anyhow::Result;
@@ -46,16 +47,36 @@ impl Tool for McpTool {
         self.tool_def.input_schema.clone()
     }
 
-    async fn execute(&self, input: Value, _ctx: ToolContext) -> Result<ToolOutput> {
+    async fn execute(&self, input: Value, ctx: ToolContext) -> Result<ToolOutput> {
         let input = if input.is_null() {
             Value::Object(serde_json::Map::new())
         } else {
             input
         };
+        let title = format!("mcp:{}:{}", self.server_name, self.tool_def.name);
+        let registration = super::cancel::track_call(&ctx.session_id, &ctx.tool_call_id);
         let manager = self.manager.read().await;
-        let result = manager
-            .call_tool(&self.server_name, &self.tool_def.name, input)
-            .await?;
+        let call = manager.call_tool(&self.server_name, &self.tool_def.name, input);
+        // Dropping the call on cancel tells the server to stop working on it.
+        let result = tokio::select! {
+            result = call => result,
+            _ = registration.cancelled() => Err(McpCallError::Cancelled {
+                server: self.server_name.clone(),
+                tool: self.tool_def.name.clone(),
+            }
+            .into()),
+        };
+        let result = match result {
+            Ok(result) => result,
+            Err(err) => {
+                let Some(call_error) = err.downcast_ref::<McpCallError>() else {
+                    return Err(err);
+                };
+                return Ok(ToolOutput::new(format!("Error: {}", call_error))
+                    .with_title(title)
+                    .with_metadata(call_error.metadata()));
+            }
+        };
 
         // Convert MCP content blocks to output string
         let mut output_parts = Vec::new();
@@ -84,7 +105,6 @@ impl Tool for McpTool {
         }
 
         let output = output_parts.join("\n");
-        let title = format!("mcp:{}:{}", self.server_name, self.tool_def.name);
 
         if result.is_error {
             Ok(ToolOutput::new(format!("Error: {}", output)).with_title(title))
@@ -31,7 +31,8 @@ mod prompt_support;
 
 pub use crate::memory_types::{
     MemoryCategory, MemoryEntry, MemoryScope, MemoryStore, Reinforcement, TrustLevel,
-    format_relevant_display_prompt, format_relevant_prompt,
+    format_injection_display_prompt, format_injection_prompt, format_relevant_display_prompt,
+    format_relevant_prompt,
 };
 use crate::memory_types::{
     collect_skill_query_terms, format_entries_for_prompt, memory_matches_search, memory_score,
@@ -48,8 +49,8 @@ pub use pending::{
     PendingMemory, clear_all_injected_memories, clear_all_pending_memory, clear_injected_memories,
     clear_pending_memory, has_any_pending_memory, has_pending_memory, is_memory_injected,
     is_memory_injected_any, mark_memories_injected, set_pending_memory,
-    set_pending_memory_with_ids, set_pending_memory_with_ids_and_display, sync_injected_memories,
-    take_pending_memory,
+    set_pending_memory_with_ids, set_pending_memory_with_ids_and_display,
+    set_pending_memory_with_report, sync_injected_memories, take_pending_memory,
 };
 use pending::{begin_memory_check, finish_memory_check};
 pub(crate) use prompt_support::format_context_for_extraction;
@@ -185,6 +186,40 @@ impl MemoryEntryEmbeddingExt for MemoryEntry {
     }
 }
 
+/// Embed every entry that has no embedding in one batch. Returns how many were
+/// embedded and how many failed.
+fn ensure_embeddings<'a>(entries: impl IntoIterator<Item = &'a mut MemoryEntry>) -> (usize, usize) {
+    let mut missing: Vec<&mut MemoryEntry> = entries
+        .into_iter()
+        .filter(|entry| entry.embedding.is_none())
+        .collect();
+    if missing.is_empty() {
+        return (0, 0);
+    }
+    let texts: Vec<&str> = missing.iter().map(|entry| entry.content.as_str()).collect();
+    match crate::embedding_backend::embed_passages_active(&texts) {
+        Ok((embeddings, model_id)) => {
+            for (entry, embedding) in missing.iter_mut().zip(embeddings) {
+                entry.set_embedding(Some(embedding), Some(model_id.clone()));
+            }
+            (missing.len(), 0)
+        }
+        Err(err) => {
+            crate::logging::info(&format!("Failed to generate embeddings: {err}"));
+            (0, missing.len())
+        }
+    }
+}
+
+/// Log the embedding cache hits and misses of one search.
+fn log_embedding_cache_use(search: &str, before: crate::embedding_cache::EmbeddingCacheStats) {
+    let used = crate::embedding_cache::stats().since(before);
+    crate::logging::info(&format!(
+        "[memory] {search}: embedding cache {} hit(s), {} miss(es)",
+        used.hits, used.misses
+    ));
+}
+
 #[derive(Debug, Clone)]
 pub struct MemoryManager {
     project_dir: Option<PathBuf>,
@@ -575,6 +610,7 @@ impl MemoryManager {
         limit: usize,
     ) -> Result<Vec<(MemoryEntry, f32)>> {
         // Generate embedding for query text
+        let cache_before = crate::embedding_cache::stats();
         let query_embedding = match crate::embedding_backend::embed_query_active(text) {
             Ok((emb, _model)) => emb,
             Err(e) => {
@@ -585,6 +621,7 @@ impl MemoryManager {
                 return Ok(Vec::new());
             }
         };
+        log_embedding_cache_use("find_similar", cache_before);
 
         self.find_similar_with_embedding(&query_embedding, threshold, limit)
     }
@@ -798,11 +835,9 @@ impl MemoryManager {
     ) -> Result<Vec<MemoryEntry>> {
         let mut entries = self.collect_memories_with_embeddings_scoped(scope)?;
         if scope.includes_global() {
-            entries.extend(
-                self.synthetic_skill_entries()
-                    .into_iter()
-                    .filter_map(|mut entry| entry.ensure_embedding().then_some(entry)),
-            );
+            let mut skills = self.synthetic_skill_entries();
+            ensure_embeddings(skills.iter_mut());
+            entries.extend(skills.into_iter().filter(|entry| entry.embedding.is_some()));
         }
         Ok(entries)
     }
@@ -814,6 +849,7 @@ impl MemoryManager {
         limit: usize,
         scope: MemoryScope,
     ) -> Result<Vec<(MemoryEntry, f32)>> {
+        let cache_before = crate::embedding_cache::stats();
         let query_embedding = match crate::embedding_backend::embed_query_active(text) {
             Ok((emb, _model)) => emb,
             Err(e) => {
@@ -826,6 +862,7 @@ impl MemoryManager {
         };
 
         let entries = self.collect_retrieval_candidates_with_embeddings_scoped(scope)?;
+        log_embedding_cache_use("retrieval candidates", cache_before);
         Self::score_and_filter(entries, &query_embedding, text, threshold, limit)
     }
 
@@ -935,36 +972,20 @@ impl MemoryManager {
 
         // Process project memories
         if let Ok(mut graph) = self.load_project_graph() {
-            let mut changed = false;
-            for entry in graph.memories.values_mut() {
-                if entry.embedding.is_none() {
-                    if entry.ensure_embedding() {
-                        generated += 1;
-                        changed = true;
-                    } else {
-                        failed += 1;
-                    }
-                }
-            }
-            if changed {
+            let (embedded, missed) = ensure_embeddings(graph.memories.values_mut());
+            generated += embedded;
+            failed += missed;
+            if embedded > 0 {
                 self.save_project_graph(&graph)?;
             }
         }
 
         // Process global memories
         if let Ok(mut graph) = self.load_global_graph() {
-            let mut changed = false;
-            for entry in graph.memories.values_mut() {
-                if entry.embedding.is_none() {
-                    if entry.ensure_embedding() {
-                        generated += 1;
-                        changed = true;
-                    } else {
-                        failed += 1;
-                    }
-                }
-            }
-            if changed {
+            let (embedded, missed) = ensure_embeddings(graph.memories.values_mut());
+            generated += embedded;
+            failed += missed;
+            if embedded > 0 {
                 self.save_global_graph(&graph)?;
             }
         }
@@ -1680,6 +1701,14 @@ impl MemoryManager {
             return Ok(graph);
         }
 
+        if let Some(mut graph) = stored_graph(&path)? {
+            Self::normalize_graph_search_text(&mut graph);
+            if !self.test_mode {
+                cache_graph(path, &graph);
+            }
+            return Ok(graph);
+        }
+
         if path.exists() {
             // Try loading as MemoryGraph first
             if let Ok(graph) = storage::read_json::<MemoryGraph>(&path)
@@ -1742,6 +1771,14 @@ impl MemoryManager {
             return Ok(graph);
         }
 
+        if let Some(mut graph) = stored_graph(&path)? {
+            Self::normalize_graph_search_text(&mut graph);
+            if !self.test_mode {
+                cache_graph(path, &graph);
+            }
+            return Ok(graph);
+        }
+
         if path.exists() {
             // Try loading as MemoryGraph first
             if let Ok(graph) = storage::read_json::<MemoryGraph>(&path)
@@ -1788,7 +1825,7 @@ impl MemoryManager {
     /// Save project memories as a MemoryGraph
     pub fn save_project_graph(&self, graph: &MemoryGraph) -> Result<()> {
         if let Some(path) = self.project_memory_path()? {
-            storage::write_json(&path, graph)?;
+            write_graph(&path, graph)?;
             if !self.test_mode {
                 cache_graph(path, graph);
             }
@@ -1799,7 +1836,7 @@ impl MemoryManager {
     /// Save global memories as a MemoryGraph
     pub fn save_global_graph(&self, graph: &MemoryGraph) -> Result<()> {
         let path = self.global_memory_path()?;
-        storage::write_json(&path, graph)?;
+        write_graph(&path, graph)?;
         if !self.test_mode {
             cache_graph(path, graph);
         }
@@ -1995,6 +2032,19 @@ pub const EMBEDDING_MAX_HITS: usize = 10;
 /// Minimum per-retriever candidate pool size for hybrid fusion.
 const HYBRID_POOL_MIN: usize = 50;
 
+/// A graph held by a database-backed store (`[storage] backend = "sqlite"`).
+/// `None` for the file store, which goes through the backup-aware file load.
+fn stored_graph(path: &std::path::Path) -> Result<Option<MemoryGraph>> {
+    match storage::store().load_document(path)? {
+        Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+        None => Ok(None),
+    }
+}
+
+fn write_graph(path: &std::path::Path, graph: &MemoryGraph) -> Result<()> {
+    storage::store().save_document(path, &serde_json::to_vec(graph)?)
+}
+
 /// Rank memories by BM25 over their normalized search text.
 ///
 /// Returns `(entry_index, score)` pairs sorted by score desc, truncated to
@@ -48,6 +48,8 @@ pub struct PendingMemory {
     pub count: usize,
     /// IDs of memories included in this prompt (for dedup tracking).
     pub memory_ids: Vec<String>,
+    /// Ranking and budget details behind this selection, for `/context memories`.
+    pub report: Option<crate::protocol::MemoryInjectionReport>,
 }
 
 impl PendingMemory {
@@ -163,6 +165,18 @@ pub fn set_pending_memory_with_ids_and_display(
     count: usize,
     memory_ids: Vec<String>,
     display_prompt: Option<String>,
+) {
+    set_pending_memory_with_report(session_id, prompt, count, memory_ids, display_prompt, None);
+}
+
+/// Store a pending memory result along with the ranking report that produced it.
+pub fn set_pending_memory_with_report(
+    session_id: &str,
+    prompt: String,
+    count: usize,
+    memory_ids: Vec<String>,
+    display_prompt: Option<String>,
+    report: Option<crate::protocol::MemoryInjectionReport>,
 ) {
     crate::memory_log::log_pending_prepared(session_id, &prompt, count, &memory_ids);
 
@@ -193,6 +207,7 @@ pub fn set_pending_memory_with_ids_and_display(
                 computed_at: Instant::now(),
                 count,
                 memory_ids,
+                report,
             },
         );
     }
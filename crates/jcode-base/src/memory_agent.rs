@@ -41,9 +41,6 @@ const CONTEXT_CHANNEL_CAPACITY: usize = 16;
 /// Similarity threshold for topic change detection (lower = more different)
 const TOPIC_CHANGE_THRESHOLD: f32 = 0.3;
 
-/// Maximum memories to surface per turn
-const MAX_MEMORIES_PER_TURN: usize = 5;
-
 /// Dynamic no-sidecar gate tunables (variable-k surfacing without an LLM).
 ///
 /// When the memory sidecar is disabled (no LLM to judge relevance), we used to
@@ -52,7 +49,7 @@ const MAX_MEMORIES_PER_TURN: usize = 5;
 /// the top candidate, then keep each following candidate only while its hybrid
 /// score stays within `GATE_REL_FLOOR` of the top AND within `GATE_DROP_RATIO`
 /// of the previous kept score. The first big gap cuts the tail. This injects a
-/// VARIABLE count (1..=MAX_MEMORIES_PER_TURN) instead of a fixed 5.
+/// VARIABLE count (1..=`[memory] injection_max_entries`) instead of a fixed 5.
 ///
 /// Bench (self-dev corpus, 150 query windows): precision@5 0.23 -> 0.36 (+56%),
 /// avg injected 5.0 -> ~2.25/turn, at zero added cost. Note this cannot drop to
@@ -151,6 +148,13 @@ pub fn build_transcript_for_extraction(messages: &[crate::message::Message]) ->
                 crate::message::ContentBlock::OpenAICompaction { .. } => {
                     transcript.push_str("[OpenAI native compaction]\n");
                 }
+                crate::message::ContentBlock::ServerToolUse { name, .. } => {
+                    transcript.push_str(&format!("[Used tool: {}]\n", name));
+                }
+                crate::message::ContentBlock::WebSearchResult { results, .. } => {
+                    transcript.push_str(&format!("[Web search: {} results]\n", results.len()));
+                }
+                crate::message::ContentBlock::Citations { .. } => {}
             }
         }
         transcript.push('\n');
@@ -305,6 +309,24 @@ fn relevance_context_signature(context: &str) -> String {
         .join("\n")
 }
 
+/// Embed the relevance context and, when non-empty, the focused query in one
+/// batch. Returns both vectors and the active backend's model id.
+fn embed_turn_queries(
+    context: &str,
+    focused_query: &str,
+) -> Result<(Vec<f32>, Option<Vec<f32>>, String)> {
+    let mut texts = vec![context];
+    if !focused_query.trim().is_empty() {
+        texts.push(focused_query);
+    }
+    let (mut vecs, model) = crate::embedding_backend::embed_queries_active(&texts)?;
+    let focused = if vecs.len() > 1 { vecs.pop() } else { None };
+    let context = vecs
+        .pop()
+        .ok_or_else(|| anyhow::anyhow!("embedding backend returned no vector"))?;
+    Ok((context, focused, model))
+}
+
 /// Decide whether to run the expensive Mode-2 listwise rerank this turn.
 ///
 /// - First rerank of a session (`last_rerank_turn == None`) always fires.
@@ -543,14 +565,17 @@ impl MemoryAgent {
 
         // Step 1: Embed current context (via the active embedding backend:
         // local MiniLM by default, or the remote OpenAI backend when configured).
+        // The focused query used to rank injections in step 4 goes in the same
+        // batch, so each turn embeds at most once.
         let start = Instant::now();
         let context_for_embedding = context.clone();
-        let context_embedding = match tokio::task::spawn_blocking(move || {
-            crate::embedding_backend::embed_query_active(&context_for_embedding)
+        let query_for_embedding = focused_query.clone();
+        let embedded = tokio::task::spawn_blocking(move || {
+            embed_turn_queries(&context_for_embedding, &query_for_embedding)
         })
-        .await
-        {
-            Ok(Ok((emb, _model))) => emb,
+        .await;
+        let (context_embedding, focused_embedding, context_model) = match embedded {
+            Ok(Ok(embedded)) => embedded,
             Ok(Err(e)) => {
                 crate::logging::event_rate_limited(
                     crate::logging::LogLevel::Info,
@@ -693,7 +718,7 @@ impl MemoryAgent {
         // Step 3: Decide which candidates to surface.
         // Mode-2 (sidecar enabled): a single listwise LLM rerank reorders the
         // hybrid candidates by relevance to the focused query and omits
-        // irrelevant ones; we surface the top MAX_MEMORIES_PER_TURN. This matches
+        // irrelevant ones; `[memory]` ranking then trims them to budget. This matches
         // the validated benchmark pipeline (recall@5 0.53 -> 0.75) and uses ONE
         // LLM call instead of the old per-candidate binary checks.
         // Mode-1 (no sidecar): take the top hybrid-ranked candidates by score.
@@ -703,6 +728,7 @@ impl MemoryAgent {
         memory::add_event(MemoryEventKind::SidecarStarted);
 
         let candidate_ids: Vec<String> = new_candidates.iter().map(|(e, _)| e.id.clone()).collect();
+        let retrieved = new_candidates.clone();
 
         // Cadence gate for the EXPENSIVE Mode-2 rerank: run the listwise LLM
         // rerank at most once per `memory_rerank_cadence` turns. Skipped turns
@@ -716,7 +742,7 @@ impl MemoryAgent {
             should_run_rerank(ss.turn_count, ss.last_rerank_turn, cadence, topic_changed)
         };
 
-        let relevant = if let Some(sidecar) = self.live_sidecar() {
+        let (relevant, filtered_reason) = if let Some(sidecar) = self.live_sidecar() {
             if should_rerank {
                 let agents = &crate::config::config().agents;
                 let votes = agents.memory_rerank_votes.max(1);
@@ -743,13 +769,12 @@ impl MemoryAgent {
                     // Real judge verdict: surface it and remember it as the new
                     // verified set for future cadence/failure carries.
                     let turn = self.session_state(session_id).turn_count;
-                    let result: Vec<_> = reranked.into_iter().take(MAX_MEMORIES_PER_TURN).collect();
                     {
                         let ss = self.session_state(session_id);
                         ss.last_rerank_turn = Some(turn);
-                        ss.last_verified_ids = result.iter().map(|e| e.id.clone()).collect();
+                        ss.last_verified_ids = reranked.iter().map(|e| e.id.clone()).collect();
                     }
-                    result
+                    (reranked, "not judged relevant")
                 } else {
                     // Judge FAILED this turn (rerank returned empty). Do NOT inject
                     // unvetted hybrid order; carry the last judge-verified set so
@@ -762,7 +787,7 @@ impl MemoryAgent {
                         outcome,
                         carried.len()
                     ));
-                    carried
+                    (carried, "not previously verified")
                 }
             } else {
                 // Cadence-gated turn: re-surface ONLY the memories the last
@@ -781,7 +806,7 @@ impl MemoryAgent {
                     session_id,
                     carried.len()
                 ));
-                carried
+                (carried, "not previously verified")
             }
         } else {
             // No LLM judge. This is reached only when the user explicitly opted
@@ -794,7 +819,10 @@ impl MemoryAgent {
                 session_id,
                 candidate_ids.len(),
             );
-            self.select_top_candidates_no_sidecar(session_id, new_candidates)
+            (
+                self.select_top_candidates_no_sidecar(session_id, new_candidates),
+                "below score gate",
+            )
         };
 
         let verified_ids: Vec<String> = relevant.iter().map(|e| e.id.clone()).collect();
@@ -810,38 +838,45 @@ impl MemoryAgent {
             context_snippet: jcode_core::util::truncate_str(&context, 200).to_string(),
         };
 
-        // Step 4: Format and store for main agent
+        // Step 4: Rank within the `[memory]` budget, format, and store for the
+        // main agent
         if !relevant.is_empty() {
-            let ids: Vec<String> = relevant.iter().map(|e| e.id.clone()).collect();
+            let query_embedding = focused_embedding.unwrap_or(context_embedding);
+            let query_model = context_model;
+            let (relevant, filtered): (Vec<_>, Vec<_>) = retrieved
+                .into_iter()
+                .partition(|(entry, _)| verified_ids.contains(&entry.id));
+            let selection = crate::memory_injection::select_for_injection(
+                relevant,
+                filtered,
+                filtered_reason,
+                Some(crate::memory_injection::InjectionQuery {
+                    embedding: &query_embedding,
+                    model: &query_model,
+                }),
+                &crate::config::config().memory,
+                Utc::now(),
+            );
+            let selected = selection.entries;
+            let ids: Vec<String> = selected.iter().map(|e| e.id.clone()).collect();
             {
                 let ss = self.session_state(session_id);
-                for entry in &relevant {
+                for entry in &selected {
                     ss.surfaced_memories.insert(entry.id.clone());
                 }
             }
 
-            if let Some(prompt) = memory::format_relevant_prompt(&relevant, MAX_MEMORIES_PER_TURN) {
+            if let Some(prompt) = memory::format_injection_prompt(&selected, selected.len()) {
                 let display_prompt =
-                    memory::format_relevant_display_prompt(&relevant, MAX_MEMORIES_PER_TURN);
-                let count = prompt
-                    .lines()
-                    .map(str::trim_start)
-                    .filter(|line| {
-                        line.split_once(". ")
-                            .map(|(prefix, _)| {
-                                !prefix.is_empty() && prefix.chars().all(|c| c.is_ascii_digit())
-                            })
-                            .unwrap_or(false)
-                    })
-                    .count()
-                    .max(1);
-
-                memory::set_pending_memory_with_ids_and_display(
+                    memory::format_injection_display_prompt(&selected, selected.len());
+                let count = selected.len();
+                memory::set_pending_memory_with_report(
                     session_id,
                     prompt,
                     count,
                     ids,
                     display_prompt,
+                    Some(selection.report),
                 );
                 memory::set_state(MemoryState::FoundRelevant { count });
             } else {
@@ -860,7 +895,7 @@ impl MemoryAgent {
 
     /// Mode-1 (no sidecar) candidate selection: a score-relative dynamic gate
     /// over the hybrid-ranked candidates. Returns a VARIABLE number of memories
-    /// (1..=`MAX_MEMORIES_PER_TURN`) instead of always padding to a fixed top-k,
+    /// (1..=`[memory] injection_max_entries`) instead of always padding to a fixed top-k,
     /// cutting the tail at the first large score gap. See `GATE_REL_FLOOR` /
     /// `GATE_DROP_RATIO` for the rationale and benchmark numbers.
     ///
@@ -873,7 +908,8 @@ impl MemoryAgent {
         session_id: &str,
         candidates: Vec<(MemoryEntry, f32)>,
     ) -> Vec<MemoryEntry> {
-        let selected = dynamic_gate_select(candidates, MAX_MEMORIES_PER_TURN);
+        let max_entries = crate::config::config().memory.injection_max_entries;
+        let selected = dynamic_gate_select(candidates, max_entries);
         for (entry, sim) in &selected {
             crate::logging::info(&format!(
                 "[{}] Memory relevant (semantic sim={:.2}): {}",
@@ -0,0 +1,324 @@
+//! Ranking and budget for memories injected into a request (`[memory]`).
+//!
+//! The memory agent decides which memories are relevant. This module orders
+//! them by a weighted score (similarity to the current user message, recency,
+//! reinforcement strength, category), adds them in score order while they fit
+//! `injection_max_entries` and `injection_token_budget`, and reports what was
+//! injected and what just missed, for `/context memories`.
+
+use crate::config::MemoryConfig;
+use crate::memory_types::{MemoryEntry, memory_provenance};
+use crate::protocol::{MemoryInjectionReport, MemoryScoreBreakdown};
+use chrono::{DateTime, Utc};
+
+/// Near misses kept in the report.
+const MISSED_LIMIT: usize = 5;
+const PREVIEW_CHARS: usize = 80;
+const DEFAULT_CATEGORY_WEIGHT: f32 = 0.5;
+
+/// Embedding of the current user message and the model that produced it.
+/// Only memories embedded by the same model are compared against it.
+#[derive(Debug, Clone, Copy)]
+pub struct InjectionQuery<'a> {
+    pub embedding: &'a [f32],
+    pub model: &'a str,
+}
+
+#[derive(Debug, Clone)]
+pub struct InjectionSelection {
+    /// Memories to inject, highest score first.
+    pub entries: Vec<MemoryEntry>,
+    pub report: MemoryInjectionReport,
+}
+
+/// Rank `relevant` memories and keep those that fit the `[memory]` limits.
+///
+/// Both lists pair each memory with its retrieval score, which stands in for
+/// the similarity when a memory has no embedding comparable to `query`.
+/// `filtered` holds candidates the relevance step dropped; the best of them
+/// are reported as near misses with `filtered_reason`.
+pub fn select_for_injection(
+    relevant: Vec<(MemoryEntry, f32)>,
+    filtered: Vec<(MemoryEntry, f32)>,
+    filtered_reason: &str,
+    query: Option<InjectionQuery<'_>>,
+    config: &MemoryConfig,
+    now: DateTime<Utc>,
+) -> InjectionSelection {
+    let max_retrieval = relevant
+        .iter()
+        .chain(&filtered)
+        .map(|(_, score)| *score)
+        .fold(0.0_f32, f32::max);
+    let score = |(entry, retrieval): (MemoryEntry, f32)| {
+        let fallback = if max_retrieval > 0.0 {
+            retrieval / max_retrieval
+        } else {
+            0.0
+        };
+        let breakdown = score_memory(&entry, query, fallback, config, now);
+        (entry, breakdown)
+    };
+
+    let mut ranked: Vec<_> = relevant.into_iter().map(score).collect();
+    ranked.sort_by(|a, b| b.1.score.total_cmp(&a.1.score));
+
+    let mut report = MemoryInjectionReport {
+        token_budget: config.injection_token_budget,
+        max_entries: config.injection_max_entries,
+        ..MemoryInjectionReport::default()
+    };
+    let mut entries = Vec::new();
+    for (entry, mut breakdown) in ranked {
+        let cut_reason = if entries.len() >= config.injection_max_entries {
+            Some("entry limit")
+        } else if report.tokens_used + breakdown.tokens > config.injection_token_budget {
+            Some("token budget")
+        } else {
+            None
+        };
+        match cut_reason {
+            Some(reason) => {
+                breakdown.cut_reason = Some(reason.to_string());
+                report.missed.push(breakdown);
+            }
+            None => {
+                report.tokens_used += breakdown.tokens;
+                report.injected.push(breakdown);
+                entries.push(entry);
+            }
+        }
+    }
+
+    let mut dropped: Vec<_> = filtered
+        .into_iter()
+        .map(score)
+        .map(|(_, mut breakdown)| {
+            breakdown.cut_reason = Some(filtered_reason.to_string());
+            breakdown
+        })
+        .collect();
+    dropped.sort_by(|a, b| b.score.total_cmp(&a.score));
+    report.missed.extend(dropped);
+    report.missed.truncate(MISSED_LIMIT);
+
+    InjectionSelection { entries, report }
+}
+
+fn score_memory(
+    entry: &MemoryEntry,
+    query: Option<InjectionQuery<'_>>,
+    fallback_similarity: f32,
+    config: &MemoryConfig,
+    now: DateTime<Utc>,
+) -> MemoryScoreBreakdown {
+    let similarity = match (query, entry.embedding.as_deref()) {
+        (Some(query), Some(embedding)) if entry.embedding_matches_model(query.model) => {
+            crate::embedding::cosine_similarity(query.embedding, embedding)
+        }
+        _ => fallback_similarity,
+    }
+    .clamp(0.0, 1.0);
+
+    let last_touched = entry
+        .reinforcements
+        .iter()
+        .map(|reinforcement| reinforcement.timestamp)
+        .fold(entry.updated_at, DateTime::max);
+    let age_days = (now - last_touched).num_seconds().max(0) as f32 / 86_400.0;
+    let recency = if config.recency_half_life_days > 0.0 {
+        0.5_f32.powf(age_days / config.recency_half_life_days)
+    } else {
+        1.0
+    };
+
+    let strength = entry.strength as f32 / (entry.strength as f32 + 2.0);
+    let category = entry.category.to_string();
+    let category_weight = config
+        .category_weights
+        .get(&category)
+        .copied()
+        .unwrap_or(DEFAULT_CATEGORY_WEIGHT)
+        .clamp(0.0, 1.0);
+
+    let weights = config.weights;
+    let total = weights.similarity + weights.recency + weights.strength + weights.category;
+    let score = if total > 0.0 {
+        (weights.similarity * similarity
+            + weights.recency * recency
+            + weights.strength * strength
+            + weights.category * category_weight)
+            / total
+    } else {
+        similarity
+    };
+
+    let line = format!(
+        "1. {}\n   {}\n",
+        entry.content.trim(),
+        memory_provenance(entry)
+    );
+    MemoryScoreBreakdown {
+        id: entry.id.clone(),
+        category,
+        preview: jcode_core::util::truncate_str(entry.content.trim(), PREVIEW_CHARS).to_string(),
+        similarity,
+        recency,
+        strength,
+        category_weight,
+        score,
+        tokens: jcode_core::util::estimate_tokens(&line).max(1),
+        cut_reason: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory_types::MemoryCategory;
+
+    fn memory(
+        id: &str,
+        category: MemoryCategory,
+        content: &str,
+        embedding: Vec<f32>,
+    ) -> MemoryEntry {
+        let mut entry = MemoryEntry::new(category, content).with_id(id);
+        entry.embedding = Some(embedding);
+        entry.embedding_model = Some("test-model".to_string());
+        entry
+    }
+
+    fn query(embedding: &[f32]) -> Option<InjectionQuery<'_>> {
+        Some(InjectionQuery {
+            embedding,
+            model: "test-model",
+        })
+    }
+
+    #[test]
+    fn ranks_by_weighted_score_and_respects_entry_limit() {
+        let config = MemoryConfig {
+            injection_max_entries: 2,
+            ..MemoryConfig::default()
+        };
+        let relevant = vec![
+            (
+                memory(
+                    "far",
+                    MemoryCategory::Fact,
+                    "Unrelated fact",
+                    vec![0.0, 1.0],
+                ),
+                0.9,
+            ),
+            (
+                memory(
+                    "near",
+                    MemoryCategory::Fact,
+                    "Closely related fact",
+                    vec![1.0, 0.0],
+                ),
+                0.1,
+            ),
+            (
+                memory(
+                    "fix",
+                    MemoryCategory::Correction,
+                    "Use cargo nextest",
+                    vec![0.8, 0.6],
+                ),
+                0.5,
+            ),
+        ];
+
+        let selection = select_for_injection(
+            relevant,
+            vec![(
+                memory("judge", MemoryCategory::Entity, "Dropped", vec![0.9, 0.1]),
+                0.4,
+            )],
+            "not judged relevant",
+            query(&[1.0, 0.0]),
+            &config,
+            Utc::now(),
+        );
+
+        let ids: Vec<_> = selection
+            .entries
+            .iter()
+            .map(|entry| entry.id.as_str())
+            .collect();
+        assert_eq!(ids, ["near", "fix"]);
+        let injected = &selection.report.injected[0];
+        assert!((injected.similarity - 1.0).abs() < 1e-6);
+        assert!(injected.score > selection.report.injected[1].score);
+        let missed: Vec<_> = selection
+            .report
+            .missed
+            .iter()
+            .map(|m| (m.id.as_str(), m.cut_reason.as_deref()))
+            .collect();
+        assert_eq!(
+            missed,
+            [
+                ("far", Some("entry limit")),
+                ("judge", Some("not judged relevant"))
+            ]
+        );
+    }
+
+    #[test]
+    fn token_budget_skips_entries_that_do_not_fit() {
+        let long = "word ".repeat(200);
+        let config = MemoryConfig {
+            injection_token_budget: 60,
+            ..MemoryConfig::default()
+        };
+        let relevant = vec![
+            (
+                memory("long", MemoryCategory::Fact, &long, vec![1.0, 0.0]),
+                1.0,
+            ),
+            (
+                memory("short", MemoryCategory::Fact, "Short note", vec![0.5, 0.5]),
+                0.5,
+            ),
+        ];
+
+        let selection = select_for_injection(
+            relevant,
+            Vec::new(),
+            "filtered",
+            query(&[1.0, 0.0]),
+            &config,
+            Utc::now(),
+        );
+
+        assert_eq!(selection.entries.len(), 1);
+        assert_eq!(selection.entries[0].id, "short");
+        assert!(selection.report.tokens_used <= 60);
+        assert_eq!(selection.report.missed[0].id, "long");
+        assert_eq!(
+            selection.report.missed[0].cut_reason.as_deref(),
+            Some("token budget")
+        );
+    }
+
+    #[test]
+    fn recency_halves_per_half_life() {
+        let config = MemoryConfig::default();
+        let now = Utc::now();
+        let mut entry = memory("old", MemoryCategory::Fact, "Old fact", vec![1.0]);
+        entry.updated_at = now - chrono::Duration::days(30);
+
+        let breakdown = score_memory(&entry, None, 0.5, &config, now);
+        assert!(
+            (breakdown.recency - 0.5).abs() < 0.01,
+            "{}",
+            breakdown.recency
+        );
+        assert!((breakdown.similarity - 0.5).abs() < 1e-6);
+        assert!((breakdown.category_weight - 0.6).abs() < 1e-6);
+    }
+}
@@ -42,6 +42,11 @@ fn format_content_block_for_relevance(block: &crate::message::ContentBlock) -> O
         crate::message::ContentBlock::OpenAICompaction { .. } => {
             Some("[OpenAI native compaction]".to_string())
         }
+        crate::message::ContentBlock::ServerToolUse { name, .. } => {
+            Some(format!("[Tool: {}]", name))
+        }
+        crate::message::ContentBlock::WebSearchResult { .. }
+        | crate::message::ContentBlock::Citations { .. } => None,
     }
 }
 
@@ -80,6 +85,22 @@ fn format_content_block_for_extraction(block: &crate::message::ContentBlock) ->
         crate::message::ContentBlock::OpenAICompaction { .. } => {
             Some("[OpenAI native compaction]".to_string())
         }
+        crate::message::ContentBlock::ServerToolUse { name, input, .. } => {
+            let input_str =
+                serde_json::to_string(input).unwrap_or_else(|_| "<invalid json>".into());
+            let input_str = truncate_chars(&input_str, MEMORY_CONTEXT_MAX_BLOCK_CHARS / 2);
+            Some(format!("[Tool: {} input: {}]", name, input_str))
+        }
+        crate::message::ContentBlock::WebSearchResult {
+            results,
+            error_code,
+            ..
+        } => {
+            let text = crate::message::web_search_result_text(results, error_code.as_deref());
+            let content = truncate_chars(&text, MEMORY_CONTEXT_MAX_BLOCK_CHARS / 2);
+            Some(format!("[Tool result: {}]", content))
+        }
+        crate::message::ContentBlock::Citations { .. } => None,
     }
 }
 
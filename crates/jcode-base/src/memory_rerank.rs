@@ -132,9 +132,10 @@ pub enum RerankMode {
 ///
 /// Equivalent to [`rerank_candidates_with_mode`] with [`RerankMode::Precision`]:
 /// returns ONLY the memories the model judged relevant, in model order (no
-/// irrelevant padding). The caller still applies its own upper-bound cap (e.g.
-/// `MAX_MEMORIES_PER_TURN`), so the injected set is `min(relevant_count, cap)`,
-/// and empty when the model judges nothing relevant.
+/// irrelevant padding). The caller still applies its own upper-bound caps
+/// (`[memory] injection_max_entries` and the token budget), so the injected set
+/// is at most the relevant count, and empty when the model judges nothing
+/// relevant.
 pub async fn rerank_candidates(
     sidecar: &Sidecar,
     focused_query: &str,
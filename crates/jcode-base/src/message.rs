@@ -9,10 +9,11 @@ use std::path::Path;
 use std::sync::OnceLock;
 
 pub use jcode_message_types::{
-    CacheControl, ConnectionPhase, ContentBlock, InputShellResult, Message, Role, StreamEvent,
-    TOOL_OUTPUT_MISSING_TEXT, ToolCall, ToolDefinition, ends_with_fresh_user_turn,
+    CacheControl, Citation, ConnectionPhase, ContentBlock, DeterminismParams, InputShellResult,
+    Message, Role, StreamEvent, StreamTextCursor, TOOL_OUTPUT_MISSING_TEXT, ToolCall,
+    ToolDefinition, WebSearchHit, citation_sources_markdown, ends_with_fresh_user_turn,
     extend_stable_hash, messages_with_dynamic_system_context, sanitize_tool_id,
-    stable_message_hash,
+    stable_message_hash, web_search_result_text,
 };
 
 mod notifications;
@@ -266,6 +267,32 @@ pub fn push_reasoning_blocks(
     }
 }
 
+/// Attach provider-executed (server-side) tool blocks and citations to an
+/// assistant turn.
+///
+/// Server tool calls and their results lead the message so rendered history
+/// shows the search before the answer it informed. Citations sit right after
+/// the text they annotate and are dropped when the turn produced no text.
+pub fn attach_server_tool_blocks(
+    blocks: &mut Vec<ContentBlock>,
+    server_tool_blocks: &[ContentBlock],
+    citations: &[Citation],
+) {
+    if !citations.is_empty()
+        && let Some(text_idx) = blocks
+            .iter()
+            .position(|block| matches!(block, ContentBlock::Text { .. }))
+    {
+        blocks.insert(
+            text_idx + 1,
+            ContentBlock::Citations {
+                citations: citations.to_vec(),
+            },
+        );
+    }
+    blocks.splice(0..0, server_tool_blocks.iter().cloned());
+}
+
 pub fn generated_image_tool_input(
     path: &str,
     metadata_path: Option<&str>,
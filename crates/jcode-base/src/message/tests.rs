@@ -751,3 +751,91 @@ fn reasoning_trace_serde_round_trip() {
         other => panic!("expected ReasoningTrace, got {other:?}"),
     }
 }
+
+#[test]
+fn attach_server_tool_blocks_leads_with_search_and_cites_after_text() {
+    let mut blocks = vec![
+        ContentBlock::Text {
+            text: "answer".to_string(),
+            cache_control: None,
+        },
+        ContentBlock::ToolUse {
+            id: "toolu_1".to_string(),
+            name: "bash".to_string(),
+            input: serde_json::json!({}),
+            thought_signature: None,
+        },
+    ];
+    let server_blocks = vec![
+        ContentBlock::ServerToolUse {
+            id: "srvtoolu_1".to_string(),
+            name: "web_search".to_string(),
+            input: serde_json::json!({"query": "rust"}),
+        },
+        ContentBlock::WebSearchResult {
+            tool_use_id: "srvtoolu_1".to_string(),
+            results: Vec::new(),
+            error_code: None,
+        },
+    ];
+    let citations = vec![Citation {
+        url: "https://www.rust-lang.org".to_string(),
+        title: Some("Rust".to_string()),
+        cited_text: None,
+    }];
+
+    attach_server_tool_blocks(&mut blocks, &server_blocks, &citations);
+
+    let kinds: Vec<&str> = blocks
+        .iter()
+        .map(|block| match block {
+            ContentBlock::ServerToolUse { .. } => "server_tool_use",
+            ContentBlock::WebSearchResult { .. } => "web_search_result",
+            ContentBlock::Text { .. } => "text",
+            ContentBlock::Citations { .. } => "citations",
+            ContentBlock::ToolUse { .. } => "tool_use",
+            _ => "other",
+        })
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            "server_tool_use",
+            "web_search_result",
+            "text",
+            "citations",
+            "tool_use"
+        ]
+    );
+}
+
+#[test]
+fn attach_server_tool_blocks_drops_citations_without_text() {
+    let mut blocks = Vec::new();
+    let citations = vec![Citation {
+        url: "https://example.com".to_string(),
+        title: None,
+        cited_text: None,
+    }];
+    attach_server_tool_blocks(&mut blocks, &[], &citations);
+    assert!(blocks.is_empty());
+}
+
+#[test]
+fn citation_sources_markdown_dedupes_urls_in_cited_order() {
+    let cite = |url: &str, title: Option<&str>| Citation {
+        url: url.to_string(),
+        title: title.map(str::to_string),
+        cited_text: None,
+    };
+    let sources = citation_sources_markdown(&[
+        cite("https://b.example", Some("B")),
+        cite("https://a.example", None),
+        cite("https://b.example", Some("B again")),
+    ]);
+    assert_eq!(
+        sources,
+        "\n\n**Sources**\n1. [B](https://b.example)\n2. [https://a.example](https://a.example)"
+    );
+    assert!(citation_sources_markdown(&[]).is_empty());
+}
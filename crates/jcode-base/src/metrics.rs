@@ -0,0 +1,152 @@
+//! Process-wide counters behind the server's Prometheus-style `/metrics`
+//! endpoint (`[server] metrics_listen`).
+//!
+//! The agent loop, tool registry, provider failover, ambient runner and
+//! `usage::record_rate_limit` bump these as they go; the server renders them
+//! in the text exposition format on each scrape. Counters only ever grow for
+//! the life of the process, so scrapers can take rates over them.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::Mutex;
+
+const TURNS_COMPLETED: &str = "jcode_turns_completed_total";
+const INPUT_TOKENS: &str = "jcode_input_tokens_total";
+const OUTPUT_TOKENS: &str = "jcode_output_tokens_total";
+const TOOL_EXECUTIONS: &str = "jcode_tool_executions_total";
+const TOOL_ERRORS: &str = "jcode_tool_errors_total";
+const AMBIENT_CYCLES: &str = "jcode_ambient_cycles_total";
+const RATE_LIMITS: &str = "jcode_rate_limit_incidents_total";
+const PROVIDER_FALLBACKS: &str = "jcode_provider_fallbacks_total";
+
+/// Every counter with its help text, in exposition order. Listed up front so
+/// a scrape shows each family even before its first sample.
+const COUNTERS: &[(&str, &str)] = &[
+    (TURNS_COMPLETED, "Agent turns finished, by outcome."),
+    (INPUT_TOKENS, "Input tokens reported by providers."),
+    (OUTPUT_TOKENS, "Output tokens reported by providers."),
+    (TOOL_EXECUTIONS, "Tool calls executed."),
+    (TOOL_ERRORS, "Tool calls that returned an error."),
+    (AMBIENT_CYCLES, "Ambient cycles run, by outcome."),
+    (RATE_LIMITS, "Provider rate-limit (HTTP 429) incidents."),
+    (PROVIDER_FALLBACKS, "Requests moved to another provider."),
+];
+
+type Labels = Vec<(&'static str, String)>;
+
+static COUNTER_VALUES: Mutex<BTreeMap<(&'static str, Labels), u64>> = Mutex::new(BTreeMap::new());
+
+fn add(name: &'static str, labels: Labels, amount: u64) {
+    let mut values = COUNTER_VALUES.lock().unwrap_or_else(|e| e.into_inner());
+    let value = values.entry((name, labels)).or_insert(0);
+    *value = value.saturating_add(amount);
+}
+
+fn outcome(ok: bool) -> String {
+    if ok { "ok" } else { "error" }.to_string()
+}
+
+pub fn record_turn_completed(ok: bool) {
+    add(TURNS_COMPLETED, vec![("status", outcome(ok))], 1);
+}
+
+/// Tokens of one provider response.
+pub fn record_tokens(provider: &str, model: &str, input_tokens: u64, output_tokens: u64) {
+    let labels = vec![
+        ("provider", provider.to_string()),
+        ("model", model.to_string()),
+    ];
+    add(INPUT_TOKENS, labels.clone(), input_tokens);
+    add(OUTPUT_TOKENS, labels, output_tokens);
+}
+
+pub fn record_tool_execution(tool: &str, ok: bool) {
+    let labels = vec![("tool", tool.to_string())];
+    if !ok {
+        add(TOOL_ERRORS, labels.clone(), 1);
+    }
+    add(TOOL_EXECUTIONS, labels, 1);
+}
+
+pub fn record_ambient_cycle(ok: bool) {
+    add(AMBIENT_CYCLES, vec![("status", outcome(ok))], 1);
+}
+
+/// `source_key` is the activity-ledger key of the throttled login.
+pub fn record_rate_limit(source_key: &str) {
+    add(RATE_LIMITS, vec![("source", source_key.to_string())], 1);
+}
+
+pub fn record_provider_fallback(from: &str, to: &str) {
+    add(
+        PROVIDER_FALLBACKS,
+        vec![("from", from.to_string()), ("to", to.to_string())],
+        1,
+    );
+}
+
+/// A gauge sampled at scrape time by whoever serves the metrics.
+pub struct Gauge<'a> {
+    pub name: &'a str,
+    pub help: &'a str,
+    pub value: u64,
+}
+
+/// All counters plus `gauges` in the Prometheus text exposition format.
+pub fn render(gauges: &[Gauge<'_>]) -> String {
+    let values = COUNTER_VALUES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    let mut out = String::new();
+    for (name, help) in COUNTERS {
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+        let _ = writeln!(out, "# TYPE {} counter", name);
+        for ((_, labels), value) in values.iter().filter(|((family, _), _)| family == name) {
+            let _ = writeln!(out, "{}{} {}", name, format_labels(labels), value);
+        }
+    }
+    for gauge in gauges {
+        let _ = writeln!(out, "# HELP {} {}", gauge.name, gauge.help);
+        let _ = writeln!(out, "# TYPE {} gauge", gauge.name);
+        let _ = writeln!(out, "{} {}", gauge.name, gauge.value);
+    }
+    out
+}
+
+fn format_labels(labels: &[(&'static str, String)]) -> String {
+    if labels.is_empty() {
+        return String::new();
+    }
+    let pairs: Vec<String> = labels
+        .iter()
+        .map(|(key, value)| {
+            let value = value
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n");
+            format!("{}=\"{}\"", key, value)
+        })
+        .collect();
+    format!("{{{}}}", pairs.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_lists_every_family_and_escapes_labels() {
+        record_tool_execution("bash \"quoted\"", false);
+        let text = render(&[Gauge {
+            name: "jcode_active_sessions",
+            help: "Sessions loaded.",
+            value: 3,
+        }]);
+        for (name, _) in COUNTERS {
+            assert!(text.contains(&format!("# TYPE {} counter", name)));
+        }
+        assert!(text.contains("jcode_tool_errors_total{tool=\"bash \\\"quoted\\\"\"} "));
+        assert!(text.contains("# TYPE jcode_active_sessions gauge\njcode_active_sessions 3\n"));
+    }
+}
@@ -0,0 +1,201 @@
+//! `[network]` allowlist wiring.
+//!
+//! Builds the effective host list from config and installs it as the
+//! process-wide egress policy (`jcode_provider_core::egress`), which every
+//! HTTP client consults when resolving a hostname.
+
+use crate::config::{Config, NetworkMode};
+use jcode_provider_core::egress::{self, EgressPolicy};
+
+/// Env var carrying the effective allowlist (comma-separated) into bash
+/// commands, so sandbox wrappers and scripts can apply the same rules.
+pub const ALLOWED_HOSTS_ENV: &str = "JCODE_NETWORK_ALLOWED_HOSTS";
+
+/// Services jcode talks to whatever provider is active: OAuth logins and
+/// subscription providers, update checks and release downloads, the local
+/// embedding model download, web search, model metadata, telemetry, and the
+/// built-in Telegram/Discord channels.
+const BUILTIN_HOSTS: &[&str] = &[
+    "*.anthropic.com",
+    "*.claude.com",
+    "claude.ai",
+    "*.openai.com",
+    "chatgpt.com",
+    "*.googleapis.com",
+    "accounts.google.com",
+    "codeassist.google.com",
+    "*.github.com",
+    "*.githubusercontent.com",
+    "*.githubcopilot.com",
+    "*.openai.azure.com",
+    "*.cognitiveservices.azure.com",
+    "*.dashscope.aliyuncs.com",
+    "api.minimax.io",
+    "api.minimaxi.com",
+    "*.cursor.com",
+    "*.cursor.sh",
+    "api.cline.bot",
+    "*.huggingface.co",
+    "*.hf.co",
+    "models.dev",
+    "*.duckduckgo.com",
+    "*.bing.com",
+    "api.bing.microsoft.com",
+    "backend.composio.dev",
+    "api.telegram.org",
+    "discord.com",
+    "jcode-telemetry.jeremyhuang55555.workers.dev",
+];
+
+/// Install the policy for the current config. Called at startup and again
+/// whenever the config reloads.
+pub fn install() {
+    let config = crate::config::config();
+    let policy = EgressPolicy {
+        enforce: config.network.effective_mode() == NetworkMode::Enforce,
+        allowed_hosts: effective_allowed_hosts(config, &crate::mcp::McpConfig::endpoint_urls()),
+    };
+    crate::logging::info(&format!(
+        "[network] Egress policy: mode={} hosts={}",
+        config.network.effective_mode().as_str(),
+        policy.allowed_hosts.len()
+    ));
+    egress::set_policy(policy);
+}
+
+/// `[network] allowed_hosts` when set, otherwise the defaults derived from
+/// config plus `mcp_urls`.
+pub fn effective_allowed_hosts(config: &Config, mcp_urls: &[String]) -> Vec<String> {
+    let configured: Vec<String> = config
+        .network
+        .allowed_hosts
+        .iter()
+        .map(|host| host.trim().to_ascii_lowercase())
+        .filter(|host| !host.is_empty())
+        .collect();
+    if !configured.is_empty() {
+        return configured;
+    }
+
+    let mut urls: Vec<String> = jcode_provider_metadata::openai_compatible_profiles()
+        .iter()
+        .map(|profile| profile.api_base.to_string())
+        .collect();
+    urls.extend(
+        config
+            .providers
+            .values()
+            .map(|provider| provider.base_url.clone()),
+    );
+    urls.extend(config.agents.memory_embedding_base_url.clone());
+    urls.extend(config.websearch.searxng_url.clone());
+    urls.extend(
+        config
+            .notifications
+            .webhooks
+            .iter()
+            .map(|webhook| webhook.url.clone()),
+    );
+    urls.push(config.safety.ntfy_server.clone());
+    urls.extend(config.safety.jade_relay_api_base.clone());
+    urls.extend(mcp_urls.iter().cloned());
+
+    let mut hosts: Vec<String> = BUILTIN_HOSTS.iter().map(|host| host.to_string()).collect();
+    hosts.extend(urls.iter().filter_map(|url| egress::host_from_url(url)));
+    hosts.sort();
+    hosts.dedup();
+    hosts
+}
+
+/// Value for [`ALLOWED_HOSTS_ENV`], or `None` before a policy is installed.
+pub fn allowed_hosts_env_value() -> Option<String> {
+    egress::policy().map(|policy| policy.allowed_hosts.join(","))
+}
+
+/// Plain-text report for `jcode debug network`.
+pub fn format_network_report() -> String {
+    let mut out = match egress::policy() {
+        Some(policy) => format!(
+            "Mode: {}\nAllowed hosts: {}\n",
+            if policy.enforce { "enforce" } else { "observe" },
+            policy.allowed_hosts.join(", ")
+        ),
+        None => "Mode: (no policy installed, all hosts allowed)\n".to_string(),
+    };
+    let destinations = egress::observed_destinations();
+    if destinations.is_empty() {
+        out.push_str("\nNo outbound connections since the server started.");
+        return out;
+    }
+    out.push_str("\nDestinations since the server started:\n");
+    for destination in destinations {
+        let mut line = format!("  {:>6}  {}", destination.total(), destination.host);
+        if destination.denied > 0 {
+            line.push_str(&format!("  (denied {})", destination.denied));
+        }
+        if destination.unlisted > 0 {
+            line.push_str(&format!("  (unlisted {})", destination.unlisted));
+        }
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_cover_providers_notifications_and_mcp() {
+        let mut config = Config::default();
+        config.providers.insert(
+            "gateway".to_string(),
+            crate::config::NamedProviderConfig {
+                base_url: "https://llm.internal.example/v1".to_string(),
+                ..Default::default()
+            },
+        );
+        config
+            .notifications
+            .webhooks
+            .push(crate::config::WebhookConfig {
+                url: "https://hooks.slack.com/services/T000".to_string(),
+                ..Default::default()
+            });
+        let hosts = effective_allowed_hosts(&config, &["https://mcp.example.dev/sse".to_string()]);
+        for expected in [
+            "api.deepseek.com",
+            "llm.internal.example",
+            "hooks.slack.com",
+            "ntfy.sh",
+            "mcp.example.dev",
+            "*.github.com",
+        ] {
+            assert!(
+                hosts.iter().any(|host| host == expected),
+                "missing {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn mode_only_enforces_once_hosts_are_listed() {
+        let mut config = Config::default();
+        assert_eq!(config.network.effective_mode(), NetworkMode::Observe);
+        config.network.allowed_hosts = vec!["api.example.com".to_string()];
+        assert_eq!(config.network.effective_mode(), NetworkMode::Enforce);
+        config.network.mode = Some(NetworkMode::Observe);
+        assert_eq!(config.network.effective_mode(), NetworkMode::Observe);
+    }
+
+    #[test]
+    fn configured_hosts_replace_the_defaults() {
+        let mut config = Config::default();
+        config.network.allowed_hosts = vec![" API.Example.com ".to_string(), String::new()];
+        assert_eq!(
+            effective_allowed_hosts(&config, &["https://mcp.example.dev".to_string()]),
+            vec!["api.example.com".to_string()]
+        );
+    }
+}
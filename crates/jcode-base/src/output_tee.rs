@@ -0,0 +1,191 @@
+//! Copies of finished assistant responses.
+//!
+//! `[output] tee_file` (or `/tee <path>` for one session) appends the final
+//! assistant message of each turn to a running log, and `jcode run --tee-cmd`
+//! pipes the final response into a command's stdin. Both are side channels:
+//! failures are logged and reported to the caller, never propagated into the
+//! turn.
+
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+
+/// The `[output] tee_file` path, with `~/` expanded. `None` when unset.
+pub fn configured_tee_file() -> Option<PathBuf> {
+    crate::config::config()
+        .output
+        .tee_file
+        .as_deref()
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .map(expand_home)
+}
+
+pub fn expand_home(path: &str) -> PathBuf {
+    if let Some(rest) = path.strip_prefix("~/")
+        && let Some(home) = dirs::home_dir()
+    {
+        return home.join(rest);
+    }
+    PathBuf::from(path)
+}
+
+/// One tee record: a header line naming the time and session, then the text.
+pub fn format_tee_entry(
+    timestamp: chrono::DateTime<chrono::Local>,
+    session_id: &str,
+    session_name: Option<&str>,
+    text: &str,
+) -> String {
+    let session = match session_name {
+        Some(name) => format!("{} ({})", name, session_id),
+        None => session_id.to_string(),
+    };
+    format!(
+        "=== {} · {} ===\n{}\n\n",
+        timestamp.format("%Y-%m-%d %H:%M:%S %:z"),
+        session,
+        text.trim_end()
+    )
+}
+
+/// Append one response to `path`, creating the file and its parent directory
+/// if needed. The record goes out in a single append-mode write and is synced
+/// before returning, so a crash never truncates earlier records and at worst
+/// loses the one being written.
+pub fn append_response(
+    path: &Path,
+    session_id: &str,
+    session_name: Option<&str>,
+    text: &str,
+) -> Result<()> {
+    if text.trim().is_empty() {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    let entry = format_tee_entry(chrono::Local::now(), session_id, session_name, text);
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("failed to open {}", path.display()))?;
+    file.write_all(entry.as_bytes())
+        .with_context(|| format!("failed to append to {}", path.display()))?;
+    file.sync_data()
+        .with_context(|| format!("failed to sync {}", path.display()))?;
+    Ok(())
+}
+
+/// Run `command` through the shell with `text` on stdin and wait for it. The
+/// command's stdout goes to our stderr so machine-readable output on stdout
+/// stays clean. A non-zero exit is an error carrying the command's stderr.
+pub async fn pipe_to_command(command: &str, text: &str) -> Result<()> {
+    let mut child = shell_command(command)
+        .stdin(Stdio::piped())
+        .stdout(std::io::stderr())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("failed to start `{}`", command))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A command that exits without reading stdin is not a failure here;
+        // its exit status decides.
+        let _ = stdin.write_all(text.as_bytes()).await;
+        drop(stdin);
+    }
+    let output = child
+        .wait_with_output()
+        .await
+        .with_context(|| format!("failed to wait for `{}`", command))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!(
+            "`{}` exited with {}{}",
+            command,
+            output.status,
+            if stderr.trim().is_empty() {
+                String::new()
+            } else {
+                format!(": {}", stderr.trim())
+            }
+        );
+    }
+    Ok(())
+}
+
+fn shell_command(command: &str) -> tokio::process::Command {
+    #[cfg(windows)]
+    {
+        let mut cmd = tokio::process::Command::new("cmd");
+        cmd.arg("/C").arg(command);
+        cmd
+    }
+
+    #[cfg(not(windows))]
+    {
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.arg("-c").arg(command);
+        cmd
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn entries_append_without_touching_earlier_records() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("logs/responses.log");
+
+        append_response(&path, "session_fox_1", Some("fox"), "First answer.\n").expect("first");
+        append_response(&path, "session_fox_1", Some("fox"), "   ").expect("blank");
+        append_response(&path, "session_owl_2", None, "Second answer.").expect("second");
+
+        let contents = std::fs::read_to_string(&path).expect("read");
+        let records: Vec<&str> = contents.split("=== ").filter(|r| !r.is_empty()).collect();
+        assert_eq!(records.len(), 2, "{contents}");
+        assert!(records[0].contains("· fox (session_fox_1) ===\nFirst answer.\n\n"));
+        assert!(records[1].contains("· session_owl_2 ===\nSecond answer.\n\n"));
+    }
+
+    #[test]
+    fn entry_header_has_timestamp_and_session() {
+        let timestamp = chrono::Local
+            .with_ymd_and_hms(2026, 3, 4, 5, 6, 7)
+            .single()
+            .expect("timestamp");
+        let entry = format_tee_entry(timestamp, "session_fox_1", Some("fox"), "Done.");
+        assert!(entry.starts_with("=== 2026-03-04 05:06:07 "), "{entry}");
+        assert!(
+            entry.ends_with(" · fox (session_fox_1) ===\nDone.\n\n"),
+            "{entry}"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn pipe_to_command_feeds_stdin_and_reports_failures() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let out = dir.path().join("out.txt");
+        pipe_to_command(&format!("cat > '{}'", out.display()), "final response")
+            .await
+            .expect("pipe");
+        assert_eq!(
+            std::fs::read_to_string(&out).expect("read"),
+            "final response"
+        );
+
+        let error = pipe_to_command("echo nope >&2; exit 3", "ignored")
+            .await
+            .expect_err("non-zero exit");
+        assert!(error.to_string().contains("nope"), "{error}");
+    }
+}
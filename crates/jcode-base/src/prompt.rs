@@ -1,8 +1,24 @@
 //! System prompt management
 
+use crate::config::{PromptPlacement, PromptSection};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+mod environment;
+mod layers;
+mod sections;
+
+pub use environment::{environment_descriptor, refresh_environment_descriptor};
+pub use layers::{
+    GLOBAL_INSTRUCTIONS_TEMPLATE, PromptLayer, PromptLayerKind, PromptLayers,
+    create_global_instructions_file, global_instructions_path, load_prompt_layers,
+    scoped_dirs_from_tool_inputs,
+};
+pub use sections::{
+    PlacedSection, PromptSectionUsage, append_section, date_section, layout_fingerprint,
+    place_section, sections_report, usage_section,
+};
+
 /// Default system prompt for jcode (embedded at compile time)
 pub const DEFAULT_SYSTEM_PROMPT: &str = include_str!("prompt/system_prompt.md");
 
@@ -100,6 +116,54 @@ pub fn append_swarm_effort_directive(split: &mut SplitSystemPrompt, effort: Opti
     }
     split.dynamic_part.push_str(directive);
 }
+
+/// Tell the model tools run in a `/cd` scope rather than the working directory
+/// its session context names. No-op without a scope.
+pub fn append_tool_scope(split: &mut SplitSystemPrompt, scope: Option<&str>) {
+    let Some(scope) = scope else {
+        return;
+    };
+    if !split.dynamic_part.is_empty() {
+        split.dynamic_part.push_str("\n\n");
+    }
+    split.dynamic_part.push_str(&format!(
+        "# Tool Scope\n\nThe user scoped tools to `{}` with `/cd`. Tool calls run there and \
+         relative paths resolve against it; focus on that directory unless asked otherwise.",
+        scope
+    ));
+}
+
+/// Token budget for `/instructions`. Longer notes are cut in the prompt and
+/// the user is warned when saving them.
+pub const SESSION_INSTRUCTIONS_MAX_TOKENS: usize = 2_000;
+
+/// Append the session's standing `/instructions` verbatim. They live in the
+/// system prompt rather than the transcript, so compaction never drops them.
+/// No-op without instructions.
+pub fn append_session_instructions(split: &mut SplitSystemPrompt, instructions: Option<&str>) {
+    let Some(instructions) = instructions.map(str::trim).filter(|text| !text.is_empty()) else {
+        return;
+    };
+    let max_chars = SESSION_INSTRUCTIONS_MAX_TOKENS * crate::util::APPROX_CHARS_PER_TOKEN;
+    let body = if instructions.len() > max_chars {
+        format!(
+            "{}\n\n[Truncated to the {}-token budget for session instructions]",
+            crate::util::truncate_str(instructions, max_chars).trim_end(),
+            SESSION_INSTRUCTIONS_MAX_TOKENS
+        )
+    } else {
+        instructions.to_string()
+    };
+    if !split.dynamic_part.is_empty() {
+        split.dynamic_part.push_str("\n\n");
+    }
+    split.dynamic_part.push_str(&format!(
+        "# Session Instructions\n\nThe user set these standing instructions for this session \
+         with `/instructions`. Follow them on every turn.\n\n{}",
+        body
+    ));
+}
+
 /// Mission-continuation template (embedded at compile time). Consumed by the
 /// `mission` module in the upper `jcode-app-core` layer; the asset lives here
 /// alongside the other prompt templates.
@@ -122,6 +186,8 @@ pub struct SplitSystemPrompt {
     pub static_part: String,
     /// Dynamic turn context that changes per request (memory, active skill, reminders)
     pub dynamic_part: String,
+    /// The `[prompt.sections]` sections in either part, for `/prompt sections`
+    pub sections: Vec<PromptSectionUsage>,
 }
 
 impl SplitSystemPrompt {
@@ -167,6 +233,8 @@ pub struct ContextInfo {
     pub has_global_agents_md: bool,
     /// Global AGENTS.md size (chars)
     pub global_agents_md_chars: usize,
+    /// Directory-scoped instruction files size (chars)
+    pub scoped_instructions_chars: usize,
     /// Skills section size (chars)
     pub skills_chars: usize,
     /// Self-dev section size (chars)
@@ -177,6 +245,10 @@ pub struct ContextInfo {
     pub prompt_overlay_chars: usize,
     /// Preferred tools section size (chars)
     pub preferred_tools_chars: usize,
+    /// Environment description size (chars)
+    pub environment_chars: usize,
+    /// Attributed `[prompt] sources` layers that made up the static prompt
+    pub prompt_layers: PromptLayers,
 
     // === Dynamic (Conversation) ===
     /// Tool definitions sent to API (chars)
@@ -215,11 +287,13 @@ impl ContextInfo {
             + self.session_context_chars
             + self.project_agents_md_chars
             + self.global_agents_md_chars
+            + self.scoped_instructions_chars
             + self.skills_chars
             + self.selfdev_chars
             + self.memory_chars
             + self.prompt_overlay_chars
             + self.preferred_tools_chars
+            + self.environment_chars
             + self.tool_defs_chars
     }
 
@@ -243,6 +317,9 @@ impl ContextInfo {
         if self.has_global_agents_md {
             parts.push(("~agents", self.global_agents_md_chars, "📋"));
         }
+        if self.scoped_instructions_chars > 0 {
+            parts.push(("dirs", self.scoped_instructions_chars, "📂"));
+        }
         if self.skills_chars > 0 {
             parts.push(("skills", self.skills_chars, "🔧"));
         }
@@ -258,6 +335,9 @@ impl ContextInfo {
         if self.preferred_tools_chars > 0 {
             parts.push(("tools", self.preferred_tools_chars, "🧰"));
         }
+        if self.environment_chars > 0 {
+            parts.push(("env", self.environment_chars, "🖥"));
+        }
         parts
     }
 }
@@ -310,32 +390,32 @@ pub fn build_system_prompt_full(
     memory_prompt: Option<&str>,
     working_dir: Option<&Path>,
 ) -> (String, ContextInfo) {
-    let mut parts = vec![DEFAULT_SYSTEM_PROMPT.to_string()];
-    let mut info = ContextInfo {
-        system_prompt_chars: DEFAULT_SYSTEM_PROMPT.len(),
-        ..Default::default()
-    };
+    let mut parts = Vec::new();
+    let mut info = ContextInfo::default();
 
     // Add self-dev guidance. Full workflow instructions are only included for
     // active self-dev sessions; other sessions get a lightweight hint.
-    if is_selfdev {
+    let selfdev_prompt = if is_selfdev {
         let selfdev_prompt = build_selfdev_prompt_for_working_dir(working_dir);
         info.selfdev_chars = selfdev_prompt.len();
-        parts.push(selfdev_prompt);
+        selfdev_prompt
     } else {
-        parts.push(build_selfdev_hint_prompt());
-    }
+        build_selfdev_hint_prompt()
+    };
 
-    // Add AGENTS.md instructions with tracking (from working_dir or cwd)
-    let (md_content, md_info) = load_agents_md_files_from_dir(working_dir);
-    if let Some(content) = md_content {
-        parts.push(content);
+    // Base prompt and instruction files, in `[prompt] sources` order
+    push_prompt_layers(&mut parts, &mut info, working_dir, &[], selfdev_prompt);
+
+    let prompt_config = &crate::config::config().prompt;
+    if let Some(placed) = environment_descriptor().and_then(|environment| {
+        place_section(prompt_config, PromptSection::Environment, &environment)
+    }) {
+        info.environment_chars = placed.text.len();
+        parts.push(placed.text);
+    }
+    if let Some(placed) = place_section(prompt_config, PromptSection::Date, &date_section()) {
+        parts.push(placed.text);
     }
-    // Merge file info
-    info.has_project_agents_md = md_info.has_project_agents_md;
-    info.project_agents_md_chars = md_info.project_agents_md_chars;
-    info.has_global_agents_md = md_info.has_global_agents_md;
-    info.global_agents_md_chars = md_info.global_agents_md_chars;
 
     // Add optional prompt overlays from ~/.jcode/ and ./.jcode/
     let (overlay_content, overlay_chars) = load_prompt_overlay_files_from_dir(working_dir);
@@ -352,9 +432,11 @@ pub fn build_system_prompt_full(
         parts.push(content);
     }
 
-    if let Some(memory) = memory_prompt {
-        info.memory_chars = memory.len();
-        parts.push(memory.to_string());
+    if let Some(placed) =
+        memory_prompt.and_then(|memory| place_section(prompt_config, PromptSection::Memory, memory))
+    {
+        info.memory_chars = placed.text.len();
+        parts.push(placed.text);
     }
 
     // Add available skills list
@@ -390,34 +472,64 @@ pub fn build_system_prompt_split(
     memory_prompt: Option<&str>,
     working_dir: Option<&Path>,
 ) -> (SplitSystemPrompt, ContextInfo) {
-    let mut static_parts = vec![DEFAULT_SYSTEM_PROMPT.to_string()];
+    build_system_prompt_split_scoped(
+        skill_prompt,
+        available_skills,
+        is_selfdev,
+        memory_prompt,
+        working_dir,
+        &[],
+    )
+}
+
+/// [`build_system_prompt_split`] plus `nested` instruction layers for the
+/// directories the agent has worked in (see [`scoped_dirs_from_tool_inputs`]).
+pub fn build_system_prompt_split_scoped(
+    skill_prompt: Option<&str>,
+    available_skills: &[SkillInfo],
+    is_selfdev: bool,
+    memory_prompt: Option<&str>,
+    working_dir: Option<&Path>,
+    scoped_dirs: &[PathBuf],
+) -> (SplitSystemPrompt, ContextInfo) {
+    let mut static_parts = Vec::new();
     let mut dynamic_parts = Vec::new();
-    let mut info = ContextInfo {
-        system_prompt_chars: DEFAULT_SYSTEM_PROMPT.len(),
-        ..Default::default()
-    };
+    let mut sections = Vec::new();
+    let mut info = ContextInfo::default();
+    let prompt_config = &crate::config::config().prompt;
 
     // === STATIC CONTENT (cacheable) ===
 
     // Add self-dev guidance. Full workflow instructions are only included for
     // active self-dev sessions; other sessions get a lightweight hint.
-    if is_selfdev {
+    let selfdev_prompt = if is_selfdev {
         let selfdev_prompt = build_selfdev_prompt_static_for_working_dir(working_dir);
         info.selfdev_chars = selfdev_prompt.len();
-        static_parts.push(selfdev_prompt);
+        selfdev_prompt
     } else {
-        static_parts.push(build_selfdev_hint_prompt());
-    }
+        build_selfdev_hint_prompt()
+    };
 
-    // Add AGENTS.md instructions (static per project)
-    let (md_content, md_info) = load_agents_md_files_from_dir(working_dir);
-    if let Some(content) = md_content {
-        static_parts.push(content);
+    // Base prompt and instruction files, in `[prompt] sources` order (static
+    // per project; a new nested layer changes the cached prefix once)
+    push_prompt_layers(
+        &mut static_parts,
+        &mut info,
+        working_dir,
+        scoped_dirs,
+        selfdev_prompt,
+    );
+
+    // Machine description, probed once per server start (static by default)
+    if let Some(placed) = environment_descriptor().and_then(|environment| {
+        place_section(prompt_config, PromptSection::Environment, &environment)
+    }) {
+        info.environment_chars =
+            push_placed_section(&mut static_parts, &mut dynamic_parts, &mut sections, placed);
+    }
+    if let Some(placed) = place_section(prompt_config, PromptSection::Date, &date_section()) {
+        push_placed_section(&mut static_parts, &mut dynamic_parts, &mut sections, placed);
     }
-    info.has_project_agents_md = md_info.has_project_agents_md;
-    info.project_agents_md_chars = md_info.project_agents_md_chars;
-    info.has_global_agents_md = md_info.has_global_agents_md;
-    info.global_agents_md_chars = md_info.global_agents_md_chars;
 
     // Add optional prompt overlays from ~/.jcode/ and ./.jcode/
     let (overlay_content, overlay_chars) = load_prompt_overlay_files_from_dir(working_dir);
@@ -449,10 +561,12 @@ pub fn build_system_prompt_split(
 
     // === TURN CONTEXT (not cached) ===
 
-    // Memory prompt (changes per conversation)
-    if let Some(memory) = memory_prompt {
-        info.memory_chars = memory.len();
-        dynamic_parts.push(memory.to_string());
+    // Memory prompt (changes per conversation, dynamic by default)
+    if let Some(placed) =
+        memory_prompt.and_then(|memory| place_section(prompt_config, PromptSection::Memory, memory))
+    {
+        info.memory_chars =
+            push_placed_section(&mut static_parts, &mut dynamic_parts, &mut sections, placed);
     }
 
     // Active skill prompt (changes per skill invocation)
@@ -468,11 +582,60 @@ pub fn build_system_prompt_split(
         SplitSystemPrompt {
             static_part,
             dynamic_part,
+            sections,
         },
         info,
     )
 }
 
+/// Push a `[prompt.sections]` section into the half it was placed in.
+/// Returns its size in chars.
+fn push_placed_section(
+    static_parts: &mut Vec<String>,
+    dynamic_parts: &mut Vec<String>,
+    sections: &mut Vec<PromptSectionUsage>,
+    placed: PlacedSection,
+) -> usize {
+    let chars = placed.text.len();
+    match placed.usage.placement {
+        PromptPlacement::Static => static_parts.push(placed.text),
+        _ => dynamic_parts.push(placed.text),
+    }
+    sections.push(placed.usage);
+    chars
+}
+
+/// Push the `[prompt] sources` layers, with the self-dev section right after
+/// the base prompt, and record their sizes in `info`.
+fn push_prompt_layers(
+    parts: &mut Vec<String>,
+    info: &mut ContextInfo,
+    working_dir: Option<&Path>,
+    scoped_dirs: &[PathBuf],
+    selfdev_prompt: String,
+) {
+    let layers = load_prompt_layers(working_dir, scoped_dirs, &crate::config::config().prompt);
+    let mut selfdev_prompt = Some(selfdev_prompt);
+    for layer in &layers.layers {
+        parts.push(layer.text.clone());
+        if layer.kind == PromptLayerKind::Base {
+            parts.extend(selfdev_prompt.take());
+        }
+    }
+    // `base` left out of `[prompt] sources`: keep the self-dev section first.
+    if let Some(selfdev_prompt) = selfdev_prompt {
+        parts.insert(0, selfdev_prompt);
+    }
+
+    info.system_prompt_chars = layers.chars_of(PromptLayerKind::Base);
+    info.project_agents_md_chars = layers.source_chars_of(PromptLayerKind::Project);
+    info.has_project_agents_md = info.project_agents_md_chars > 0;
+    info.global_agents_md_chars = layers.source_chars_of(PromptLayerKind::Global);
+    info.has_global_agents_md = info.global_agents_md_chars > 0;
+    info.scoped_instructions_chars = layers.source_chars_of(PromptLayerKind::Nested);
+    info.prompt_layers = layers;
+}
+
 /// Build self-dev tools prompt section (static version without dynamic socket path)
 fn build_selfdev_hint_prompt() -> String {
     SELFDEV_HINT_PROMPT.to_string()
@@ -536,6 +699,15 @@ fn build_selfdev_prompt_for_context(context: SelfDevProductContext) -> String {
 
 /// Build immutable session context captured once per session.
 pub fn build_session_context(working_dir: Option<&Path>) -> String {
+    build_session_context_with_roots(working_dir, &[])
+}
+
+/// Build the session context for a session that may span extra workspace roots
+/// beyond its working directory.
+pub fn build_session_context_with_roots(
+    working_dir: Option<&Path>,
+    extra_roots: &[PathBuf],
+) -> String {
     let mut lines = vec!["# Session Context".to_string()];
 
     let now_utc = chrono::Utc::now();
@@ -565,9 +737,78 @@ pub fn build_session_context(working_dir: Option<&Path>) -> String {
         lines.push(git_info);
     }
 
+    if let Some(roots) = build_workspace_roots_context(cwd.as_deref(), extra_roots) {
+        lines.push(roots);
+    }
+
     lines.join("\n")
 }
 
+/// Summarize every workspace root: its `name:` prefix, a brief top-level tree,
+/// and per-root git status for the extra roots. Returns `None` for ordinary
+/// single-root sessions.
+pub fn build_workspace_roots_context(
+    working_dir: Option<&Path>,
+    extra_roots: &[PathBuf],
+) -> Option<String> {
+    if extra_roots.is_empty() {
+        return None;
+    }
+
+    let mut lines = vec![
+        "Workspace roots (address files in another root as `name:relative/path`):".to_string(),
+    ];
+    let primary = working_dir.map(|dir| (dir, true));
+    let extras = extra_roots.iter().map(|root| (root.as_path(), false));
+    for (root, is_primary) in primary.into_iter().chain(extras) {
+        let name = jcode_tool_core::workspace_root_name(root)
+            .unwrap_or_else(|| root.display().to_string());
+        let suffix = if is_primary { " (primary)" } else { "" };
+        lines.push(format!("- {}: {}{}", name, root.display(), suffix));
+        if let Some(tree) = top_level_tree_summary(root) {
+            lines.push(format!("  Contents: {}", tree));
+        }
+        // The primary root's git status is already reported above.
+        if !is_primary && let Some(git_info) = get_git_info(Some(root)) {
+            for line in git_info.lines() {
+                lines.push(format!("  {}", line));
+            }
+        }
+    }
+
+    Some(lines.join("\n"))
+}
+
+/// One-line listing of a directory's visible top-level entries.
+fn top_level_tree_summary(root: &Path) -> Option<String> {
+    const MAX_ENTRIES: usize = 20;
+
+    let mut entries: Vec<String> = std::fs::read_dir(root)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with('.') {
+                return None;
+            }
+            let is_dir = entry.file_type().map(|ty| ty.is_dir()).unwrap_or(false);
+            Some(if is_dir { format!("{}/", name) } else { name })
+        })
+        .collect();
+    if entries.is_empty() {
+        return None;
+    }
+    entries.sort();
+
+    let total = entries.len();
+    entries.truncate(MAX_ENTRIES);
+    let mut summary = entries.join(", ");
+    if total > MAX_ENTRIES {
+        summary.push_str(&format!(", ... (+{} more)", total - MAX_ENTRIES));
+    }
+    Some(summary)
+}
+
 /// Get git branch and status summary
 fn get_git_info(working_dir: Option<&Path>) -> Option<String> {
     let mut command = Command::new("git");
@@ -630,6 +871,111 @@ fn get_git_info(working_dir: Option<&Path>) -> Option<String> {
     }
 }
 
+/// Character budget (~500 tokens) for the `# Git Context` section.
+const GIT_CONTEXT_MAX_CHARS: usize = 2_000;
+const GIT_CONTEXT_MAX_FILES: usize = 20;
+const GIT_CONTEXT_COMMITS: usize = 5;
+/// Back-to-back turns (and sessions sharing a repo) reuse one snapshot
+/// instead of forking git again.
+const GIT_CONTEXT_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(5);
+
+type GitContextCache = std::collections::HashMap<PathBuf, (std::time::Instant, Option<String>)>;
+
+static GIT_CONTEXT_CACHE: std::sync::LazyLock<std::sync::Mutex<GitContextCache>> =
+    std::sync::LazyLock::new(Default::default);
+
+/// `# Git Context` section for `[prompt] git_context`: branch, ahead/behind
+/// counts, dirty files and the last few commit subjects. `None` outside a git
+/// work tree.
+pub fn build_git_context(working_dir: &Path) -> Option<String> {
+    let now = std::time::Instant::now();
+    let mut cache = GIT_CONTEXT_CACHE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some((taken_at, context)) = cache.get(working_dir)
+        && now.duration_since(*taken_at) < GIT_CONTEXT_CACHE_TTL
+    {
+        return context.clone();
+    }
+    let context = build_git_context_uncached(working_dir);
+    cache.insert(working_dir.to_path_buf(), (now, context.clone()));
+    context
+}
+
+fn git_output(working_dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .current_dir(working_dir)
+        .args(args)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Turn a `git status --branch` header (`## main...origin/main [ahead 1]`)
+/// into `main (ahead 1 of origin/main)`.
+fn describe_git_branch(header: &str) -> String {
+    let header = header.trim_start_matches("## ");
+    let (head, tracking) = match header.split_once(" [") {
+        Some((head, tracking)) => (head, Some(tracking.trim_end_matches(']'))),
+        None => (header, None),
+    };
+    match (head.split_once("..."), tracking) {
+        (Some((branch, upstream)), Some(tracking)) => {
+            format!("{} ({} of {})", branch, tracking, upstream)
+        }
+        (Some((branch, upstream)), None) => format!("{} (up to date with {})", branch, upstream),
+        (None, _) => head.to_string(),
+    }
+}
+
+fn build_git_context_uncached(working_dir: &Path) -> Option<String> {
+    let status = git_output(working_dir, &["status", "--porcelain", "--branch"])?;
+    let mut lines = status.lines();
+    let mut section = vec!["# Git Context".to_string(), String::new()];
+    if let Some(header) = lines.next().filter(|line| line.starts_with("## ")) {
+        section.push(format!("Branch: {}", describe_git_branch(header)));
+    }
+
+    let dirty: Vec<&str> = lines.collect();
+    if dirty.is_empty() {
+        section.push("Working tree: clean".to_string());
+    } else {
+        section.push(format!("Dirty files ({}):", dirty.len()));
+        for file in dirty.iter().take(GIT_CONTEXT_MAX_FILES) {
+            section.push(format!("  {}", file));
+        }
+        if dirty.len() > GIT_CONTEXT_MAX_FILES {
+            section.push(format!(
+                "  ... (+{} more)",
+                dirty.len() - GIT_CONTEXT_MAX_FILES
+            ));
+        }
+    }
+
+    // Fails on a branch without commits; the section is still useful then.
+    let count = format!("-{}", GIT_CONTEXT_COMMITS);
+    if let Some(log) = git_output(working_dir, &["log", count.as_str(), "--format=%h %s"])
+        && !log.trim().is_empty()
+    {
+        section.push("Recent commits:".to_string());
+        section.extend(log.lines().map(|line| format!("  {}", line)));
+    }
+
+    let mut context = String::new();
+    for line in section {
+        if context.len() + line.len() + 1 > GIT_CONTEXT_MAX_CHARS {
+            context.push_str("  ...");
+            break;
+        }
+        context.push_str(&line);
+        context.push('\n');
+    }
+    Some(context.trim_end().to_string())
+}
+
 fn hardware_context() -> Option<String> {
     // Hardware never changes for the life of the process, but this used to be
     // rebuilt for every session create/attach, forking `lspci` each time. On a
@@ -735,48 +1081,31 @@ fn gpu_summary() -> Option<String> {
     }
 }
 
-/// Load AGENTS.md files from a specific working directory
+/// Load the global and project instruction files for a working directory
 pub fn load_agents_md_files_from_dir(working_dir: Option<&Path>) -> (Option<String>, ContextInfo) {
-    let mut contents = vec![];
-    let mut info = ContextInfo::default();
-
-    // Helper to load a file if it exists, returns (formatted_content, raw_size)
-    let load_file = |path: &Path, label: &str| -> Option<(String, usize)> {
-        if path.exists() {
-            std::fs::read_to_string(path).ok().map(|content| {
-                let raw_size = content.len();
-                let formatted = format!("# {}\n\n{}", label, content.trim());
-                (formatted, raw_size)
-            })
-        } else {
-            None
-        }
+    let config = crate::config::PromptConfig {
+        sources: vec!["global".to_string(), "project".to_string()],
+        ..crate::config::config().prompt.clone()
+    };
+    let layers = load_prompt_layers(working_dir, &[], &config);
+    let project_chars = layers.source_chars_of(PromptLayerKind::Project);
+    let global_chars = layers.source_chars_of(PromptLayerKind::Global);
+    let info = ContextInfo {
+        has_project_agents_md: project_chars > 0,
+        project_agents_md_chars: project_chars,
+        has_global_agents_md: global_chars > 0,
+        global_agents_md_chars: global_chars,
+        ..Default::default()
     };
 
-    // Project-level files (from specified working directory or current directory)
-    let project_dir = working_dir.unwrap_or(Path::new("."));
-    if let Some((content, size)) = load_file(
-        &project_dir.join("AGENTS.md"),
-        "Project Instructions (AGENTS.md)",
-    ) {
-        info.has_project_agents_md = true;
-        info.project_agents_md_chars = size;
-        contents.push(content);
-    }
-
-    // Home directory files
-    if let Ok(global_agents_md) = crate::storage::user_home_path("AGENTS.md")
-        && let Some((content, size)) =
-            load_file(&global_agents_md, "Global Instructions (~/.AGENTS.md)")
-    {
-        info.has_global_agents_md = true;
-        info.global_agents_md_chars = size;
-        contents.push(content);
-    }
-
-    if contents.is_empty() {
+    if layers.layers.is_empty() {
         (None, info)
     } else {
+        let contents: Vec<&str> = layers
+            .layers
+            .iter()
+            .map(|layer| layer.text.as_str())
+            .collect();
         (Some(contents.join("\n\n")), info)
     }
 }
@@ -0,0 +1,269 @@
+//! Environment section of the static system prompt.
+//!
+//! Describes the machine the agent runs on (OS, shell, CPUs, memory and the
+//! versions of `[prompt] environment_tools`) so the model doesn't spend tool
+//! calls rediscovering it every session. It is probed once, on first use or
+//! at server start, and reused until `/env refresh`, which keeps the static
+//! prompt prefix cache-stable.
+
+use std::process::{Command, Stdio};
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+/// How long one `--version` probe may run before it is killed.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+/// Longest version line kept per tool.
+const MAX_VERSION_CHARS: usize = 80;
+
+static DESCRIPTOR: RwLock<Option<String>> = RwLock::new(None);
+
+/// The environment section for the static prompt, or `None` when
+/// `[prompt] environment` is off. Probed on first call, then cached.
+pub fn environment_descriptor() -> Option<String> {
+    if !crate::config::config()
+        .prompt
+        .section_enabled(crate::config::PromptSection::Environment)
+    {
+        return None;
+    }
+    if let Some(descriptor) = DESCRIPTOR
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+    {
+        return Some(descriptor);
+    }
+    let mut cached = DESCRIPTOR
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    Some(cached.get_or_insert_with(probe_descriptor).clone())
+}
+
+/// Probe the machine again and replace the cached section. The next prompt
+/// build picks it up, so this invalidates the prompt cache once.
+pub fn refresh_environment_descriptor() -> String {
+    let descriptor = probe_descriptor();
+    *DESCRIPTOR
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(descriptor.clone());
+    descriptor
+}
+
+fn probe_descriptor() -> String {
+    let tools = crate::config::config().prompt.environment_tools.clone();
+    EnvironmentFacts {
+        os: os_description(),
+        shell: std::env::var("SHELL")
+            .ok()
+            .filter(|shell| !shell.is_empty()),
+        cpus: std::thread::available_parallelism()
+            .ok()
+            .map(|cpus| cpus.get()),
+        memory: std::fs::read_to_string("/proc/meminfo")
+            .ok()
+            .and_then(|meminfo| memory_description(&meminfo)),
+        tools: probe_tool_versions(&tools),
+    }
+    .render()
+}
+
+struct EnvironmentFacts {
+    os: String,
+    shell: Option<String>,
+    cpus: Option<usize>,
+    memory: Option<String>,
+    /// Tool label and its version line, `None` when it is not installed.
+    tools: Vec<(String, Option<String>)>,
+}
+
+impl EnvironmentFacts {
+    fn render(&self) -> String {
+        let mut lines = vec![
+            "# Environment".to_string(),
+            String::new(),
+            "Probed when jcode started. Rely on it instead of re-checking the platform or tool versions with bash.".to_string(),
+            format!("OS: {}", self.os),
+        ];
+        let runner = if cfg!(windows) { "cmd.exe" } else { "bash" };
+        match &self.shell {
+            Some(shell) => lines.push(format!(
+                "Shell: {} (the bash tool runs commands with {})",
+                shell, runner
+            )),
+            None => lines.push(format!("Shell: {}", runner)),
+        }
+        if let Some(cpus) = self.cpus {
+            lines.push(format!("CPUs: {}", cpus));
+        }
+        if let Some(memory) = &self.memory {
+            lines.push(format!("Memory: {}", memory));
+        }
+        if !self.tools.is_empty() {
+            lines.push("Tools:".to_string());
+            for (tool, version) in &self.tools {
+                lines.push(format!(
+                    "- {}: {}",
+                    tool,
+                    version.as_deref().unwrap_or("not found")
+                ));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+fn os_description() -> String {
+    let arch = std::env::consts::ARCH;
+    let name = if cfg!(target_os = "linux") {
+        let pretty = std::fs::read_to_string("/etc/os-release")
+            .ok()
+            .and_then(|release| {
+                release.lines().find_map(|line| {
+                    let value = line.strip_prefix("PRETTY_NAME=")?;
+                    Some(value.trim_matches('"').to_string())
+                })
+            });
+        let kernel = std::fs::read_to_string("/proc/sys/kernel/osrelease")
+            .ok()
+            .map(|kernel| kernel.trim().to_string());
+        match (pretty, kernel) {
+            (Some(pretty), Some(kernel)) => format!("{} (Linux {})", pretty, kernel),
+            (Some(pretty), None) => pretty,
+            (None, Some(kernel)) => format!("Linux {}", kernel),
+            (None, None) => "Linux".to_string(),
+        }
+    } else if cfg!(target_os = "macos") {
+        match probe_version("sw_vers", &["-productVersion"]) {
+            Some(version) => format!("macOS {}", version),
+            None => "macOS".to_string(),
+        }
+    } else {
+        std::env::consts::OS.to_string()
+    };
+    format!("{}, {}", name, arch)
+}
+
+fn memory_description(meminfo: &str) -> Option<String> {
+    let field = |name: &str| {
+        meminfo.lines().find_map(|line| {
+            let rest = line.strip_prefix(name)?.strip_prefix(':')?.trim();
+            rest.split_whitespace().next()?.parse::<u64>().ok()
+        })
+    };
+    let gib = |kb: u64| kb as f64 / 1024.0 / 1024.0;
+    let total = field("MemTotal")?;
+    Some(match field("MemAvailable") {
+        Some(available) => format!(
+            "{:.1} GiB total, {:.1} GiB available at startup",
+            gib(total),
+            gib(available)
+        ),
+        None => format!("{:.1} GiB total", gib(total)),
+    })
+}
+
+/// Split an `environment_tools` entry into its label, program and arguments.
+/// A bare name gets `--version`.
+fn probe_command(entry: &str) -> Option<(&str, Vec<&str>)> {
+    let mut words = entry.split_whitespace();
+    let program = words.next()?;
+    let args: Vec<&str> = words.collect();
+    if args.is_empty() {
+        Some((program, vec!["--version"]))
+    } else {
+        Some((program, args))
+    }
+}
+
+fn probe_tool_versions(entries: &[String]) -> Vec<(String, Option<String>)> {
+    std::thread::scope(|scope| {
+        let probes: Vec<_> = entries
+            .iter()
+            .filter_map(|entry| probe_command(entry))
+            .map(|(program, args)| {
+                let handle = scope.spawn(move || probe_version(program, &args));
+                (program.to_string(), handle)
+            })
+            .collect();
+        probes
+            .into_iter()
+            .map(|(program, handle)| (program, handle.join().ok().flatten()))
+            .collect()
+    })
+}
+
+/// First non-empty output line of `program args`, or `None` when it can't be
+/// run, fails or hangs.
+fn probe_version(program: &str, args: &[&str]) -> Option<String> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .ok()?;
+    let started = Instant::now();
+    loop {
+        match child.try_wait().ok()? {
+            Some(_) => break,
+            None if started.elapsed() >= PROBE_TIMEOUT => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+            None => std::thread::sleep(Duration::from_millis(10)),
+        }
+    }
+    let output = child.wait_with_output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    // `java -version` and a few others print to stderr.
+    [&output.stdout, &output.stderr]
+        .into_iter()
+        .flat_map(|bytes| {
+            String::from_utf8_lossy(bytes)
+                .lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty())
+                .map(str::to_string)
+        })
+        .next()
+        .map(|line| crate::util::truncate_str(&line, MAX_VERSION_CHARS).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn descriptor_lists_facts_and_missing_tools() {
+        let facts = EnvironmentFacts {
+            os: "Ubuntu 24.04 LTS (Linux 6.8.0), x86_64".to_string(),
+            shell: Some("/bin/zsh".to_string()),
+            cpus: Some(16),
+            memory: memory_description("MemTotal: 33554432 kB\nMemAvailable: 16777216 kB\n"),
+            tools: vec![
+                ("git".to_string(), Some("git version 2.43.0".to_string())),
+                ("fd".to_string(), None),
+            ],
+        };
+        let text = facts.render();
+        assert!(text.starts_with("# Environment\n\n"));
+        assert!(text.contains("\nOS: Ubuntu 24.04 LTS (Linux 6.8.0), x86_64\n"));
+        assert!(text.contains("\nCPUs: 16\n"));
+        assert!(text.contains("\nMemory: 32.0 GiB total, 16.0 GiB available at startup\n"));
+        assert!(text.ends_with("Tools:\n- git: git version 2.43.0\n- fd: not found"));
+    }
+
+    #[test]
+    fn tool_entries_default_to_version_flag() {
+        assert_eq!(probe_command("rg"), Some(("rg", vec!["--version"])));
+        assert_eq!(probe_command("go version"), Some(("go", vec!["version"])));
+        assert_eq!(probe_command("  "), None);
+        assert_eq!(
+            probe_version("jcode-env-probe-missing-tool", &["--version"]),
+            None
+        );
+    }
+}
@@ -0,0 +1,381 @@
+//! Attributed layers of the static system prompt.
+//!
+//! `[prompt] sources` orders the layers: the built-in base prompt, the user's
+//! global instruction file, the nearest project instruction file, and
+//! instruction files from subdirectories the agent has worked in. Every
+//! instruction layer is a headed section naming the file it came from, and
+//! their combined size is capped by `[prompt] max_instruction_chars`.
+
+use super::DEFAULT_SYSTEM_PROMPT;
+use crate::config::PromptConfig;
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptLayerKind {
+    Base,
+    Global,
+    Project,
+    Nested,
+}
+
+impl PromptLayerKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Base => "base",
+            Self::Global => "global",
+            Self::Project => "project",
+            Self::Nested => "nested",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "base" => Some(Self::Base),
+            "global" => Some(Self::Global),
+            "project" => Some(Self::Project),
+            "nested" => Some(Self::Nested),
+            _ => None,
+        }
+    }
+}
+
+/// One section of the static system prompt and where it came from.
+#[derive(Debug, Clone)]
+pub struct PromptLayer {
+    pub kind: PromptLayerKind,
+    /// Section heading, e.g. `Project Instructions (AGENTS.md)`.
+    pub label: String,
+    /// Instruction file the layer was read from (`None` for the base prompt).
+    pub path: Option<PathBuf>,
+    /// The section exactly as it appears in the prompt.
+    pub text: String,
+    /// Size of the source file before formatting and capping.
+    pub source_chars: usize,
+    /// Whether the size cap cut this layer short.
+    pub truncated: bool,
+}
+
+impl PromptLayer {
+    pub fn estimated_tokens(&self) -> usize {
+        crate::util::estimate_tokens(&self.text)
+    }
+}
+
+/// The ordered layers plus any problems found while assembling them.
+#[derive(Debug, Clone, Default)]
+pub struct PromptLayers {
+    pub layers: Vec<PromptLayer>,
+    pub warnings: Vec<String>,
+}
+
+impl PromptLayers {
+    /// Combined size of one kind of layer, as formatted in the prompt.
+    pub fn chars_of(&self, kind: PromptLayerKind) -> usize {
+        self.layers
+            .iter()
+            .filter(|layer| layer.kind == kind)
+            .map(|layer| layer.text.len())
+            .sum()
+    }
+
+    /// Raw size of the source files for one kind of layer.
+    pub fn source_chars_of(&self, kind: PromptLayerKind) -> usize {
+        self.layers
+            .iter()
+            .filter(|layer| layer.kind == kind)
+            .map(|layer| layer.source_chars)
+            .sum()
+    }
+}
+
+/// Assemble the layers named in `config.sources`.
+///
+/// `scoped_dirs` are directories the agent has worked in; any of them below
+/// the working directory that holds an instruction file adds a `nested` layer.
+pub fn load_prompt_layers(
+    working_dir: Option<&Path>,
+    scoped_dirs: &[PathBuf],
+    config: &PromptConfig,
+) -> PromptLayers {
+    let mut out = PromptLayers::default();
+    let project_dir = working_dir
+        .map(Path::to_path_buf)
+        .or_else(|| std::env::current_dir().ok())
+        .unwrap_or_else(|| PathBuf::from("."));
+    let mut seen_paths = HashSet::new();
+    let mut seen_kinds = HashSet::new();
+
+    for source in &config.sources {
+        let Some(kind) = PromptLayerKind::parse(source) else {
+            out.warnings.push(format!(
+                "[prompt] unknown source `{}` (expected one of {})",
+                source,
+                crate::config::PROMPT_SOURCES.join(", ")
+            ));
+            continue;
+        };
+        if !seen_kinds.insert(kind) {
+            continue;
+        }
+        let mut layers = match kind {
+            PromptLayerKind::Base => vec![PromptLayer {
+                kind,
+                label: "Base System Prompt".to_string(),
+                path: None,
+                text: DEFAULT_SYSTEM_PROMPT.to_string(),
+                source_chars: DEFAULT_SYSTEM_PROMPT.len(),
+                truncated: false,
+            }],
+            PromptLayerKind::Global => global_layers(),
+            PromptLayerKind::Project => project_layers(&project_dir, &config.project_files),
+            PromptLayerKind::Nested => {
+                nested_layers(&project_dir, scoped_dirs, &config.project_files)
+            }
+        };
+        layers.retain(|layer| match layer.path.as_ref() {
+            Some(path) => seen_paths.insert(path.clone()),
+            None => true,
+        });
+        out.layers.extend(layers);
+    }
+
+    apply_instruction_cap(&mut out, config.max_instruction_chars);
+    for warning in &out.warnings {
+        warn_once(warning);
+    }
+    out
+}
+
+/// Directories touched by `file_path`/`path` tool inputs, for `nested` layers.
+/// Relative paths resolve against `working_dir`.
+pub fn scoped_dirs_from_tool_inputs<'a>(
+    inputs: impl IntoIterator<Item = &'a serde_json::Value>,
+    working_dir: Option<&Path>,
+) -> Vec<PathBuf> {
+    let mut dirs = BTreeSet::new();
+    for input in inputs {
+        for key in ["file_path", "path"] {
+            let Some(raw) = input.get(key).and_then(|value| value.as_str()) else {
+                continue;
+            };
+            let raw = raw.trim();
+            if raw.is_empty() {
+                continue;
+            }
+            let path = match (Path::new(raw).is_absolute(), working_dir) {
+                (false, Some(dir)) => dir.join(raw),
+                _ => PathBuf::from(raw),
+            };
+            // `file_path` names a file; `path` (ls, grep, glob) usually a directory.
+            let dir = match key {
+                "file_path" => path.parent().map(Path::to_path_buf),
+                _ => Some(path),
+            };
+            if let Some(dir) = dir {
+                dirs.insert(dir);
+            }
+        }
+    }
+    dirs.into_iter().collect()
+}
+
+/// Starting point for `~/.jcode/JCODE.md`, offered by first-run onboarding.
+pub const GLOBAL_INSTRUCTIONS_TEMPLATE: &str = include_str!("global_instructions_template.md");
+
+/// Path of the user's global instruction file, `~/.jcode/JCODE.md`.
+pub fn global_instructions_path() -> anyhow::Result<PathBuf> {
+    Ok(crate::storage::jcode_dir()?.join("JCODE.md"))
+}
+
+/// Write [`GLOBAL_INSTRUCTIONS_TEMPLATE`] to `~/.jcode/JCODE.md` unless the
+/// file already exists. Returns the path when it was created.
+pub fn create_global_instructions_file() -> anyhow::Result<Option<PathBuf>> {
+    let path = global_instructions_path()?;
+    if path.exists() {
+        return Ok(None);
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, GLOBAL_INSTRUCTIONS_TEMPLATE)?;
+    Ok(Some(path))
+}
+
+fn global_layers() -> Vec<PromptLayer> {
+    let mut layers = Vec::new();
+    if let Ok(path) = global_instructions_path() {
+        layers.extend(read_layer(
+            PromptLayerKind::Global,
+            &path,
+            "Global Instructions (~/.jcode/JCODE.md)".to_string(),
+            None,
+        ));
+    }
+    if let Ok(path) = crate::storage::user_home_path("AGENTS.md") {
+        layers.extend(read_layer(
+            PromptLayerKind::Global,
+            &path,
+            "Global Instructions (~/.AGENTS.md)".to_string(),
+            None,
+        ));
+    }
+    layers
+}
+
+fn project_layers(project_dir: &Path, names: &[String]) -> Vec<PromptLayer> {
+    let Some(path) = find_instruction_file(project_dir, names) else {
+        return Vec::new();
+    };
+    let label = format!(
+        "Project Instructions ({})",
+        display_relative(&path, project_dir)
+    );
+    read_layer(PromptLayerKind::Project, &path, label, None)
+        .into_iter()
+        .collect()
+}
+
+/// The first of `names` found in `start` or its ancestors. The walk stops at
+/// the repository root and never treats the home directory as a project.
+fn find_instruction_file(start: &Path, names: &[String]) -> Option<PathBuf> {
+    let home = dirs::home_dir();
+    for dir in start.ancestors() {
+        if home.as_deref() == Some(dir) {
+            return None;
+        }
+        if let Some(path) = instruction_file_in(dir, names) {
+            return Some(path);
+        }
+        if dir.join(".git").exists() {
+            return None;
+        }
+    }
+    None
+}
+
+fn instruction_file_in(dir: &Path, names: &[String]) -> Option<PathBuf> {
+    names
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file())
+}
+
+fn nested_layers(
+    project_dir: &Path,
+    scoped_dirs: &[PathBuf],
+    names: &[String],
+) -> Vec<PromptLayer> {
+    // Every directory strictly between the project dir and a touched dir can
+    // scope instructions to the touched files.
+    let mut candidates = BTreeSet::new();
+    for dir in scoped_dirs {
+        let Ok(relative) = dir.strip_prefix(project_dir) else {
+            continue;
+        };
+        let mut current = project_dir.to_path_buf();
+        for component in relative.components() {
+            current.push(component);
+            candidates.insert(current.clone());
+        }
+    }
+    candidates
+        .into_iter()
+        .filter_map(|dir| {
+            let path = instruction_file_in(&dir, names)?;
+            let scope = display_relative(&dir, project_dir);
+            let label = format!(
+                "Directory Instructions ({})",
+                display_relative(&path, project_dir)
+            );
+            read_layer(
+                PromptLayerKind::Nested,
+                &path,
+                label,
+                Some(format!("Applies to files under `{}/`.", scope)),
+            )
+        })
+        .collect()
+}
+
+fn read_layer(
+    kind: PromptLayerKind,
+    path: &Path,
+    label: String,
+    preamble: Option<String>,
+) -> Option<PromptLayer> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let body = content.trim();
+    let text = match preamble {
+        Some(preamble) => format!("# {}\n\n{}\n\n{}", label, preamble, body),
+        None => format!("# {}\n\n{}", label, body),
+    };
+    Some(PromptLayer {
+        kind,
+        label,
+        path: Some(path.to_path_buf()),
+        text,
+        source_chars: content.len(),
+        truncated: false,
+    })
+}
+
+fn display_relative(path: &Path, base: &Path) -> String {
+    path.strip_prefix(base)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+/// Truncate the layer that crosses the cap and drop the ones after it. The
+/// base prompt does not count toward the cap.
+fn apply_instruction_cap(out: &mut PromptLayers, max_chars: usize) {
+    let mut used = 0usize;
+    let mut dropped = Vec::new();
+    out.layers.retain_mut(|layer| {
+        if layer.kind == PromptLayerKind::Base {
+            return true;
+        }
+        let remaining = max_chars.saturating_sub(used);
+        if layer.text.len() <= remaining {
+            used += layer.text.len();
+            return true;
+        }
+        if remaining == 0 {
+            dropped.push(layer.label.clone());
+            return false;
+        }
+        let kept = crate::util::truncate_str(&layer.text, remaining).to_string();
+        layer.text = format!(
+            "{}\n\n[Truncated: instruction files exceed [prompt] max_instruction_chars = {}]",
+            kept, max_chars
+        );
+        layer.truncated = true;
+        used = max_chars;
+        out.warnings.push(format!(
+            "{} truncated: instruction files exceed [prompt] max_instruction_chars = {}",
+            layer.label, max_chars
+        ));
+        true
+    });
+    if !dropped.is_empty() {
+        out.warnings.push(format!(
+            "Skipped over the instruction size cap ({} chars): {}",
+            max_chars,
+            dropped.join(", ")
+        ));
+    }
+}
+
+/// The prompt is rebuilt every turn, so log each distinct warning once.
+fn warn_once(message: &str) {
+    static SEEN: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+    let mut seen = SEEN
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if seen.insert(message.to_string()) {
+        crate::logging::warn(&format!("[prompt] {}", message));
+    }
+}
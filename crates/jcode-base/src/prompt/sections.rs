@@ -0,0 +1,182 @@
+//! `[prompt.sections]`: which half of the split system prompt each optional
+//! section (date, git, memory, todos, environment, usage) goes in, and how
+//! large it may get.
+//!
+//! The static half is sent as the cached prefix, so a section that changes
+//! there rewrites the cache on the next request. Every section that is placed
+//! is recorded in [`SplitSystemPrompt::sections`] for `/prompt sections`.
+
+use super::SplitSystemPrompt;
+use crate::config::{PromptConfig, PromptPlacement, PromptSection};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// One optional section as it went into a prompt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptSectionUsage {
+    pub section: PromptSection,
+    pub placement: PromptPlacement,
+    pub chars: usize,
+    /// Cut to the section's `max_tokens` budget.
+    pub truncated: bool,
+}
+
+impl PromptSectionUsage {
+    pub fn estimated_tokens(&self) -> usize {
+        self.chars / crate::util::APPROX_CHARS_PER_TOKEN
+    }
+}
+
+/// A section ready to go into one half of the prompt.
+#[derive(Debug, Clone)]
+pub struct PlacedSection {
+    pub text: String,
+    pub usage: PromptSectionUsage,
+}
+
+/// Place `text` as `section` per `config`: `None` when the section is off or
+/// empty, otherwise the text cut to the section's budget.
+pub fn place_section(
+    config: &PromptConfig,
+    section: PromptSection,
+    text: &str,
+) -> Option<PlacedSection> {
+    let placement = config.placement(section);
+    let text = text.trim_end();
+    if placement == PromptPlacement::Off || text.trim().is_empty() {
+        return None;
+    }
+    let (text, truncated) = match config.section(section).max_tokens {
+        Some(max_tokens)
+            if text.len() > max_tokens.saturating_mul(crate::util::APPROX_CHARS_PER_TOKEN) =>
+        {
+            let kept =
+                crate::util::truncate_str(text, max_tokens * crate::util::APPROX_CHARS_PER_TOKEN);
+            (
+                format!(
+                    "{}\n\n[Truncated to the {}-token budget in [prompt.sections] {}]",
+                    kept.trim_end(),
+                    max_tokens,
+                    section.as_str()
+                ),
+                true,
+            )
+        }
+        _ => (text.to_string(), false),
+    };
+    Some(PlacedSection {
+        usage: PromptSectionUsage {
+            section,
+            placement,
+            chars: text.len(),
+            truncated,
+        },
+        text,
+    })
+}
+
+/// Append `text` as `section` to the half `[prompt.sections]` puts it in.
+/// Returns where it went, [`PromptPlacement::Off`] when it was left out.
+pub fn append_section(
+    split: &mut SplitSystemPrompt,
+    section: PromptSection,
+    text: &str,
+) -> PromptPlacement {
+    let Some(placed) = place_section(&crate::config::config().prompt, section, text) else {
+        return PromptPlacement::Off;
+    };
+    let part = match placed.usage.placement {
+        PromptPlacement::Static => &mut split.static_part,
+        _ => &mut split.dynamic_part,
+    };
+    if !part.is_empty() {
+        part.push_str("\n\n");
+    }
+    part.push_str(&placed.text);
+    let placement = placed.usage.placement;
+    split.sections.push(placed.usage);
+    placement
+}
+
+/// The `# Current Date` section.
+pub fn date_section() -> String {
+    let today = chrono::Local::now();
+    format!(
+        "# Current Date\n\nToday is {} ({}).",
+        today.format("%Y-%m-%d"),
+        today.format("%A")
+    )
+}
+
+/// The `# Session Usage` section, from the session's token totals.
+pub fn usage_section(input_tokens: u64, output_tokens: u64) -> String {
+    format!(
+        "# Session Usage\n\nThis session has sent {} input tokens and received {} output tokens \
+         so far.",
+        crate::util::format_number(input_tokens as usize),
+        crate::util::format_number(output_tokens as usize)
+    )
+}
+
+/// Identifies the current placement and budgets, so the cache tracker can
+/// tell when a layout change took effect.
+pub fn layout_fingerprint(config: &PromptConfig) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for section in PromptSection::ALL {
+        config.placement(section).hash(&mut hasher);
+        config.section(section).max_tokens.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// The `/prompt sections` report for `split`: each section's size and half,
+/// totals per half, placement warnings and, when known, the measured cache
+/// impact of the last layout change.
+pub fn sections_report(
+    config: &PromptConfig,
+    split: &SplitSystemPrompt,
+    cache_impact: Option<&str>,
+) -> String {
+    let mut out = String::from("Sections ([prompt.sections])\n");
+    for section in PromptSection::ALL {
+        let placement = config.placement(section);
+        let used = split.sections.iter().find(|usage| usage.section == section);
+        let size = match (placement, used) {
+            (PromptPlacement::Off, _) => "-".to_string(),
+            (_, Some(usage)) => format!(
+                "~{} tokens{}",
+                usage.estimated_tokens(),
+                if usage.truncated { " (truncated)" } else { "" }
+            ),
+            (_, None) => "empty this turn".to_string(),
+        };
+        let budget = config
+            .section(section)
+            .max_tokens
+            .map(|max| format!(", budget {}", max))
+            .unwrap_or_default();
+        out.push_str(&format!(
+            "- {:<12} {:<8} {}{}\n",
+            section.as_str(),
+            placement.as_str(),
+            size,
+            budget
+        ));
+    }
+    out.push_str(&format!(
+        "\nStatic half: ~{} tokens (cached) · dynamic half: ~{} tokens\n",
+        crate::util::estimate_tokens(&split.static_part),
+        crate::util::estimate_tokens(&split.dynamic_part)
+    ));
+    let warnings = config.section_warnings();
+    if !warnings.is_empty() {
+        out.push_str("\nWarnings\n");
+        for warning in &warnings {
+            out.push_str(&format!("- {}\n", warning));
+        }
+    }
+    if let Some(impact) = cache_impact {
+        out.push_str(&format!("\nCache\n- {}\n", impact));
+    }
+    out
+}
@@ -67,6 +67,110 @@ fn test_load_agents_md_files_uses_sandboxed_global_files() {
     }
 }
 
+#[test]
+fn test_prompt_layers_walk_up_for_project_file_and_add_nested_scopes() {
+    let _guard = crate::storage::lock_test_env();
+    let prev_home = std::env::var_os("JCODE_HOME");
+    let temp = tempfile::TempDir::new().unwrap();
+    crate::env::set_var("JCODE_HOME", temp.path());
+    std::fs::write(temp.path().join("JCODE.md"), "global jcode instructions").unwrap();
+
+    let repo = tempfile::TempDir::new().unwrap();
+    std::fs::create_dir_all(repo.path().join(".git")).unwrap();
+    std::fs::write(repo.path().join("AGENTS.md"), "repo agents instructions").unwrap();
+    std::fs::write(repo.path().join("CLAUDE.md"), "repo claude instructions").unwrap();
+    let working_dir = repo.path().join("app");
+    std::fs::create_dir_all(working_dir.join("src/ui")).unwrap();
+    std::fs::write(working_dir.join("src/ui/JCODE.md"), "ui-only instructions").unwrap();
+
+    let config = crate::config::PromptConfig::default();
+    let layers = load_prompt_layers(Some(&working_dir), &[], &config);
+    let kinds: Vec<_> = layers.layers.iter().map(|layer| layer.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            PromptLayerKind::Base,
+            PromptLayerKind::Global,
+            PromptLayerKind::Project
+        ]
+    );
+    let project = &layers.layers[2];
+    assert_eq!(
+        project.path.as_deref(),
+        Some(repo.path().join("AGENTS.md").as_path())
+    );
+    assert!(project.text.contains("repo agents instructions"));
+    assert!(
+        !layers
+            .layers
+            .iter()
+            .any(|layer| layer.text.contains("repo claude instructions"))
+    );
+
+    let scoped = scoped_dirs_from_tool_inputs(
+        [&serde_json::json!({"file_path": "src/ui/view.rs"})],
+        Some(&working_dir),
+    );
+    let layers = load_prompt_layers(Some(&working_dir), &scoped, &config);
+    let nested = layers.layers.last().unwrap();
+    assert_eq!(nested.kind, PromptLayerKind::Nested);
+    assert!(
+        nested
+            .text
+            .starts_with("# Directory Instructions (src/ui/JCODE.md)")
+    );
+    assert!(nested.text.contains("Applies to files under `src/ui/`."));
+    assert!(nested.estimated_tokens() > 0);
+
+    if let Some(prev_home) = prev_home {
+        crate::env::set_var("JCODE_HOME", prev_home);
+    } else {
+        crate::env::remove_var("JCODE_HOME");
+    }
+}
+
+#[test]
+fn test_prompt_layers_respect_source_order_and_size_cap() {
+    let _guard = crate::storage::lock_test_env();
+    let prev_home = std::env::var_os("JCODE_HOME");
+    let temp = tempfile::TempDir::new().unwrap();
+    crate::env::set_var("JCODE_HOME", temp.path());
+    std::fs::write(temp.path().join("JCODE.md"), "g".repeat(300)).unwrap();
+
+    let repo = tempfile::TempDir::new().unwrap();
+    std::fs::create_dir_all(repo.path().join(".git")).unwrap();
+    std::fs::write(repo.path().join("JCODE.md"), "p".repeat(300)).unwrap();
+
+    let config = crate::config::PromptConfig {
+        sources: vec!["project".into(), "global".into(), "bogus".into()],
+        max_instruction_chars: 200,
+        ..Default::default()
+    };
+    let layers = load_prompt_layers(Some(repo.path()), &[], &config);
+    assert_eq!(layers.layers.len(), 1);
+    assert_eq!(layers.layers[0].kind, PromptLayerKind::Project);
+    assert!(layers.layers[0].truncated);
+    assert!(layers.layers[0].text.contains("[Truncated:"));
+    assert!(
+        layers
+            .warnings
+            .iter()
+            .any(|warning| warning.contains("unknown source `bogus`"))
+    );
+    assert!(
+        layers
+            .warnings
+            .iter()
+            .any(|warning| warning.contains("Global Instructions (~/.jcode/JCODE.md)"))
+    );
+
+    if let Some(prev_home) = prev_home {
+        crate::env::set_var("JCODE_HOME", prev_home);
+    } else {
+        crate::env::remove_var("JCODE_HOME");
+    }
+}
+
 #[test]
 fn test_session_context_includes_time_timezone_and_system_info() {
     let context = build_session_context(None);
@@ -78,6 +182,69 @@ fn test_session_context_includes_time_timezone_and_system_info() {
     assert!(context.contains("Jcode version: "));
 }
 
+#[test]
+fn test_session_context_summarizes_extra_workspace_roots() {
+    let api = tempfile::TempDir::new().unwrap();
+    let web_parent = tempfile::TempDir::new().unwrap();
+    let web = web_parent.path().join("web");
+    std::fs::create_dir_all(web.join("src")).unwrap();
+    std::fs::write(web.join("package.json"), "{}").unwrap();
+    std::fs::write(web.join(".env"), "SECRET=1").unwrap();
+
+    let single = build_session_context(Some(api.path()));
+    assert!(!single.contains("Workspace roots"));
+
+    let context = build_session_context_with_roots(Some(api.path()), &[web.clone()]);
+    assert!(context.contains("Workspace roots"));
+    assert!(context.contains(&format!("- web: {}", web.display())));
+    assert!(context.contains("(primary)"));
+    assert!(context.contains("Contents: package.json, src/"));
+    assert!(!context.contains(".env"));
+}
+
+#[test]
+fn test_git_context_summarizes_repo_and_skips_non_git_dirs() {
+    let plain = tempfile::TempDir::new().unwrap();
+    assert_eq!(build_git_context(plain.path()), None);
+
+    let repo = tempfile::TempDir::new().unwrap();
+    let git = |args: &[&str]| {
+        let status = Command::new("git")
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(repo.path())
+            .status()
+            .expect("run git");
+        assert!(status.success(), "git {:?} failed", args);
+    };
+    git(&["init", "-q"]);
+    std::fs::write(repo.path().join("lib.rs"), "fn a() {}").unwrap();
+    git(&["add", "lib.rs"]);
+    git(&["commit", "-q", "-m", "Add lib"]);
+    std::fs::write(repo.path().join("notes.md"), "todo").unwrap();
+
+    let context = build_git_context(repo.path()).expect("git context");
+    assert!(context.starts_with("# Git Context"));
+    assert!(context.contains("Branch: "));
+    assert!(context.contains("Dirty files (1):\n  ?? notes.md"));
+    assert!(context.contains("Recent commits:"));
+    assert!(context.contains(" Add lib"));
+    assert!(context.len() <= GIT_CONTEXT_MAX_CHARS);
+}
+
+#[test]
+fn test_git_branch_header_includes_ahead_behind_counts() {
+    assert_eq!(
+        describe_git_branch("## main...origin/main [ahead 2, behind 1]"),
+        "main (ahead 2, behind 1 of origin/main)"
+    );
+    assert_eq!(
+        describe_git_branch("## main...origin/main"),
+        "main (up to date with origin/main)"
+    );
+    assert_eq!(describe_git_branch("## feature"), "feature");
+}
+
 #[test]
 fn test_split_prompt_does_not_inject_session_context_per_turn() {
     let (split, _info) = build_system_prompt_split(None, &[], false, None, None);
@@ -200,6 +367,16 @@ fn test_preferred_tools_files_are_loaded_from_project_and_global_jcode_dirs() {
     }
 }
 
+#[test]
+fn test_environment_section_is_static_and_stable() {
+    let (first, info) = build_system_prompt_split(None, &[], false, None, None);
+    let (second, _) = build_system_prompt_split(None, &[], false, None, None);
+    assert!(first.static_part.contains("# Environment"));
+    assert!(!first.dynamic_part.contains("# Environment"));
+    assert!(info.environment_chars > 0);
+    assert_eq!(first.static_part, second.static_part);
+}
+
 #[test]
 fn test_non_selfdev_prompt_includes_lightweight_selfdev_hint() {
     let prompt = build_system_prompt(None, &[]);
@@ -278,6 +455,7 @@ fn swarm_effort_directive_is_appended_only_for_swarm_sentinel() {
     let mut split = SplitSystemPrompt {
         static_part: "base".to_string(),
         dynamic_part: String::new(),
+        ..Default::default()
     };
     append_swarm_effort_directive(&mut split, Some("xhigh"));
     assert!(!split.dynamic_part.contains("Swarm Effort"));
@@ -292,6 +470,41 @@ fn swarm_effort_directive_is_appended_only_for_swarm_sentinel() {
     assert!(other.dynamic_part.is_empty());
 }
 
+#[test]
+fn tool_scope_is_appended_only_when_set() {
+    let mut split = SplitSystemPrompt::default();
+    append_tool_scope(&mut split, None);
+    assert!(split.dynamic_part.is_empty());
+
+    append_tool_scope(&mut split, Some("/repo/packages/web"));
+    assert!(split.dynamic_part.starts_with("# Tool Scope"));
+    assert!(split.dynamic_part.contains("`/repo/packages/web`"));
+}
+
+#[test]
+fn session_instructions_are_verbatim_and_capped() {
+    let mut split = SplitSystemPrompt::default();
+    append_session_instructions(&mut split, Some("  \n "));
+    assert!(split.dynamic_part.is_empty());
+
+    append_session_instructions(
+        &mut split,
+        Some("Never touch `vendor/`.\nPrefer small diffs."),
+    );
+    assert!(split.dynamic_part.starts_with("# Session Instructions"));
+    assert!(
+        split
+            .dynamic_part
+            .ends_with("Never touch `vendor/`.\nPrefer small diffs.")
+    );
+
+    let mut long = SplitSystemPrompt::default();
+    let text = "x".repeat(SESSION_INSTRUCTIONS_MAX_TOKENS * 8);
+    append_session_instructions(&mut long, Some(&text));
+    assert!(long.dynamic_part.contains("[Truncated to the"));
+    assert!(long.dynamic_part.len() < text.len());
+}
+
 #[test]
 fn swarm_deep_effort_injects_task_graph_directive() {
     use crate::prompt::is_deep_swarm_effort;
@@ -334,3 +547,43 @@ fn classify_effort_distinguishes_reasoning_from_swarm_modes() {
     assert!(EffortKind::SwarmDeep.is_swarm_mode());
     assert!(!EffortKind::Reasoning.is_swarm_mode());
 }
+
+#[test]
+fn prompt_sections_place_budget_and_warn() {
+    use crate::config::{PromptPlacement, PromptSection, PromptSectionConfig};
+
+    let mut config = crate::config::PromptConfig::default();
+    assert_eq!(
+        config.placement(PromptSection::Environment),
+        PromptPlacement::Static
+    );
+    assert_eq!(config.placement(PromptSection::Git), PromptPlacement::Off);
+    assert!(place_section(&config, PromptSection::Date, &date_section()).is_none());
+
+    config.sections.todos = PromptSectionConfig {
+        place: Some(PromptPlacement::Static),
+        max_tokens: Some(10),
+    };
+    let placed = place_section(&config, PromptSection::Todos, &"t".repeat(100)).unwrap();
+    assert_eq!(placed.usage.placement, PromptPlacement::Static);
+    assert!(placed.usage.truncated);
+    assert!(
+        placed
+            .text
+            .starts_with(&format!("{}\n\n[Truncated", "t".repeat(40)))
+    );
+
+    let warnings = config.section_warnings();
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].starts_with("[prompt.sections] todos changes every turn"));
+
+    let split = SplitSystemPrompt {
+        static_part: placed.text.clone(),
+        dynamic_part: String::new(),
+        sections: vec![placed.usage],
+    };
+    let report = sections_report(&config, &split, None);
+    assert!(report.contains("- todos        static   ~"));
+    assert!(report.contains("(truncated), budget 10"));
+    assert!(report.contains("- date         off      -"));
+}
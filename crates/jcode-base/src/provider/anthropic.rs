@@ -7,7 +7,8 @@ use super::{EventStream, NativeToolResultSender, Provider};
 use crate::auth;
 use crate::auth::oauth;
 #[cfg(test)]
-use crate::message::{ContentBlock, Role};
+use crate::config::AnthropicServerTool;
+use crate::message::{Citation, ContentBlock, Role};
 use crate::message::{Message, StreamEvent, ToolDefinition};
 use anyhow::{Context, Result};
 use async_trait::async_trait;
@@ -15,7 +16,8 @@ use futures::StreamExt;
 #[cfg(test)]
 use jcode_provider_anthropic::{ApiContentBlock, ToolResultContent, ToolResultContentBlock};
 use jcode_provider_anthropic::{
-    ApiMessage, ApiMetadata, ApiOutputConfig, ApiRequest, ApiSystem, ApiThinking, ApiTool,
+    ApiMessage, ApiMetadata, ApiOutputConfig, ApiRequest, ApiServerTool, ApiSystem, ApiThinking,
+    ApiToolParam,
 };
 use jcode_provider_core::{
     ANTHROPIC_OAUTH_BETA_HEADERS, anthropic_effectively_1m, anthropic_is_1m_model as is_1m_model,
@@ -710,6 +712,34 @@ impl AnthropicProvider {
         )
     }
 
+    /// Models that accept Anthropic's server-side web search tool.
+    fn model_supports_web_search(model: &str) -> bool {
+        let model = Self::normalized_model_key(model);
+        model.contains("claude-fable-5")
+            || model.contains("claude-mythos")
+            || model.contains("claude-opus-4")
+            || model.contains("claude-sonnet-4")
+            || model.contains("claude-haiku-4")
+            || model.contains("claude-3-7-sonnet")
+            || model.contains("claude-sonnet-3-7")
+            || model.contains("claude-3-5-haiku")
+    }
+
+    /// Server tools from `[provider.anthropic] server_tools` that `model`
+    /// supports. Unsupported ones are left out so the request stays valid.
+    fn server_tools_for_model(model: &str) -> Vec<ApiServerTool> {
+        crate::config::config()
+            .provider
+            .anthropic
+            .server_tools
+            .iter()
+            .filter_map(|tool| match tool {
+                AnthropicServerTool::WebSearch => Self::model_supports_web_search(model)
+                    .then(jcode_provider_anthropic::web_search_server_tool),
+            })
+            .collect()
+    }
+
     fn model_supports_priority_service_tier(model: &str) -> bool {
         Self::normalized_model_key(model).contains("claude-opus-4-8")
     }
@@ -808,6 +838,25 @@ impl AnthropicProvider {
         (thinking, output_config, temperature)
     }
 
+    /// Deterministic-mode request settings for `model`. Extended thinking
+    /// rules out temperature, current models reject temperature and top_p
+    /// together, and the Messages API takes no seed.
+    fn deterministic_params_for(&self, model: &str) -> crate::message::DeterminismParams {
+        let thinking = self.build_reasoning_request_parts(model, false).0.is_some();
+        let mut params = super::determinism::params_for(strip_1m_suffix(model));
+        if thinking {
+            params
+                .unsupported
+                .push("temperature (extended thinking is on)".to_string());
+        } else {
+            params.temperature = Some(0.0);
+        }
+        params
+            .unsupported
+            .extend(["top_p".to_string(), "seed".to_string()]);
+        params
+    }
+
     /// Get the access token from credentials
     /// Supports both OAuth tokens and direct API keys
     /// Automatically refreshes OAuth tokens when expired
@@ -969,10 +1018,25 @@ impl AnthropicProvider {
         jcode_provider_anthropic::format_content_blocks(blocks, is_oauth)
     }
 
-    /// Convert tool definitions to Anthropic API format
+    /// Convert tool definitions to Anthropic API format, preceded by any
+    /// configured server tools the model supports.
     /// Adds cache_control to the last tool for prompt caching
-    fn format_tools(&self, tools: &[ToolDefinition], is_oauth: bool) -> Vec<ApiTool> {
-        jcode_provider_anthropic::format_tools(tools, is_oauth, is_cache_ttl_1h())
+    fn format_tools(
+        &self,
+        tools: &[ToolDefinition],
+        is_oauth: bool,
+        model: &str,
+    ) -> Vec<ApiToolParam> {
+        let mut api_tools: Vec<ApiToolParam> = Self::server_tools_for_model(model)
+            .into_iter()
+            .map(ApiToolParam::Server)
+            .collect();
+        api_tools.extend(
+            jcode_provider_anthropic::format_tools(tools, is_oauth, is_cache_ttl_1h())
+                .into_iter()
+                .map(ApiToolParam::Client),
+        );
+        api_tools
     }
 }
 
@@ -1050,13 +1114,19 @@ impl Provider for AnthropicProvider {
             .read()
             .unwrap_or_else(|poisoned| poisoned.into_inner())
             .clone();
-        let api_model = strip_1m_suffix(&model).to_string();
+        let mut api_model = strip_1m_suffix(&model).to_string();
 
         // Format request
         let api_messages = self.format_messages(messages, is_oauth);
-        let api_tools = self.format_tools(tools, is_oauth);
-        let (thinking, output_config, temperature) =
+        let api_tools = self.format_tools(tools, is_oauth, &model);
+        let (thinking, output_config, mut temperature) =
             self.build_reasoning_request_parts(&model, is_oauth);
+        if super::determinism::enabled() {
+            let params = self.deterministic_params_for(&model);
+            super::determinism::warn_unsupported(self.name(), &params);
+            api_model = params.model;
+            temperature = params.temperature;
+        }
 
         let request = ApiRequest {
             model: api_model,
@@ -1132,6 +1202,10 @@ impl Provider for AnthropicProvider {
             .clone()
     }
 
+    fn deterministic_params(&self, _seed: u64) -> crate::message::DeterminismParams {
+        self.deterministic_params_for(&self.model())
+    }
+
     fn set_model(&self, model: &str) -> Result<()> {
         // Native-1M models (Opus 4.8/4.7) no longer carry a redundant `[1m]`
         // alias. Gracefully migrate a stale `<model>[1m]` id (from old config or
@@ -1377,13 +1451,19 @@ impl Provider for AnthropicProvider {
             .read()
             .unwrap_or_else(|poisoned| poisoned.into_inner())
             .clone();
-        let api_model = strip_1m_suffix(&model).to_string();
+        let mut api_model = strip_1m_suffix(&model).to_string();
 
         // Format request
         let api_messages = self.format_messages(messages, is_oauth);
-        let api_tools = self.format_tools(tools, is_oauth);
-        let (thinking, output_config, temperature) =
+        let api_tools = self.format_tools(tools, is_oauth, &model);
+        let (thinking, output_config, mut temperature) =
             self.build_reasoning_request_parts(&model, is_oauth);
+        if super::determinism::enabled() {
+            let params = self.deterministic_params_for(&model);
+            super::determinism::warn_unsupported(self.name(), &params);
+            api_model = params.model;
+            temperature = params.temperature;
+        }
 
         let request = ApiRequest {
             model: api_model,
@@ -1624,13 +1704,24 @@ async fn run_stream_with_retries(
                     ));
                     request.thinking = None;
                     request.output_config = None;
-                    if is_oauth {
+                    if super::determinism::enabled() {
+                        request.temperature = Some(0.0);
+                    } else if is_oauth {
                         request.temperature = Some(1.0);
                     }
                     last_error = Some(e);
                     continue;
                 }
 
+                // Rate limited with a server-advertised wait: quick backoff
+                // retries would only burn attempts inside the window, so hand
+                // the wait to the agent, which sleeps it out and resends.
+                if !saw_output && let Some(event) = super::rate_limit::rate_limited_stream_event(&e)
+                {
+                    let _ = tx.send(Ok(event)).await;
+                    return;
+                }
+
                 // Check if this is a transient/retryable error
                 if is_retryable_error(&error_str) && attempt + 1 < MAX_RETRIES {
                     if saw_output {
@@ -1816,6 +1907,16 @@ async fn stream_response(
 
     if !response.status().is_success() {
         let status = response.status();
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            let retry_after_secs =
+                super::rate_limit::retry_after_secs_from_headers(response.headers());
+            let error_text = crate::util::http_error_body(response, "HTTP error").await;
+            return Err(super::rate_limit::RateLimited {
+                retry_after_secs,
+                message: format!("Anthropic API error ({}): {}", status, error_text),
+            }
+            .into());
+        }
         let error_text = crate::util::http_error_body(response, "HTTP error").await;
         anyhow::bail!("Anthropic API error ({}): {}", status, error_text);
     }
@@ -1864,6 +1965,14 @@ async fn stream_response(
         }
     }
 
+    if let Some(web_search_requests) = sse_state.web_search_requests.filter(|n| *n > 0) {
+        let _ = tx
+            .send(Ok(StreamEvent::ServerToolUsage {
+                web_search_requests,
+            }))
+            .await;
+    }
+
     // Send final token usage if we have it
     if sse_state.input_tokens.is_some() || sse_state.output_tokens.is_some() {
         // Log cache usage for debugging
@@ -2038,7 +2147,9 @@ fn anthropic_recommended_model_from_error(error_str: &str) -> Option<String> {
 ///
 /// Strategy (most authoritative first):
 ///   1. Honor any server "Please use X" recommendation parsed from the error.
-///   2. Otherwise pick the highest-quality untried model from the curated
+///   2. Otherwise follow the successor table for the model that was just
+///      rejected, so a retired Sonnet moves to the current Sonnet.
+///   3. Otherwise pick the highest-quality untried model from the curated
 ///      flagship-first catalog, skipping retired families so we never downgrade
 ///      to a cheaper tier (e.g. Haiku) while a stronger model is available.
 ///
@@ -2061,8 +2172,19 @@ fn anthropic_fallback_model(tried: &[String], error_str: &str) -> Option<String>
         return Some(recommended);
     }
 
-    // 2. Best available by curated quality order, skipping retired and tried.
-    crate::provider::known_anthropic_model_ids()
+    let known = crate::provider::known_anthropic_model_ids();
+
+    // 2. Known successor of the rejected model, when the catalog lists it.
+    if let Some(rejected) = tried.last()
+        && let Some(successor) = jcode_provider_core::model_successor(rejected, &known)
+        && !already_tried(&successor)
+        && !anthropic_model_is_retired(&successor)
+    {
+        return Some(successor);
+    }
+
+    // 3. Best available by curated quality order, skipping retired and tried.
+    known
         .into_iter()
         .filter(|candidate| !already_tried(candidate) && !anthropic_model_is_retired(candidate))
         .min_by_key(|candidate| anthropic_model_quality_rank(candidate))
@@ -2135,11 +2257,15 @@ struct SseEvent {
 #[derive(Default)]
 struct SseStreamState {
     current_tool_use: Option<ToolUseAccumulator>,
+    /// Server tool call (e.g. `web_search`) whose input is still streaming.
+    /// Kept apart from `current_tool_use`: the API runs it, not us.
+    current_server_tool_use: Option<ServerToolUseAccumulator>,
     current_thinking_block: bool,
     input_tokens: Option<u64>,
     output_tokens: Option<u64>,
     cache_read_input_tokens: Option<u64>,
     cache_creation_input_tokens: Option<u64>,
+    web_search_requests: Option<u64>,
     /// Lowercased base id of the model we asked for, so `message_start` can flag
     /// a silent server-side substitution (e.g. an unavailable id aliased to a
     /// different model). Empty when unknown (e.g. in unit tests).
@@ -2148,6 +2274,12 @@ struct SseStreamState {
     warned_model_substitution: bool,
 }
 
+struct ServerToolUseAccumulator {
+    id: String,
+    name: String,
+    input_json: String,
+}
+
 /// Process an SSE event and return StreamEvents if applicable
 fn process_sse_event(
     event: &SseEvent,
@@ -2196,6 +2328,9 @@ fn process_sse_event(
                     state.cache_read_input_tokens = usage.cache_read_input_tokens.map(|t| t as u64);
                     state.cache_creation_input_tokens =
                         usage.cache_creation_input_tokens.map(|t| t as u64);
+                    if let Some(server_tool_use) = usage.server_tool_use {
+                        state.web_search_requests = server_tool_use.web_search_requests;
+                    }
                     if let Some(tier) = usage.service_tier.as_deref() {
                         crate::logging::info(&format!("Anthropic granted service_tier={}", tier));
                         if std::env::var("JCODE_LOG_SERVICE_TIER").is_ok() {
@@ -2237,6 +2372,25 @@ fn process_sse_event(
                             name: mapped_name,
                         });
                     }
+                    ApiContentBlockStart::ServerToolUse { id, name } => {
+                        state.current_server_tool_use = Some(ServerToolUseAccumulator {
+                            id,
+                            name,
+                            input_json: String::new(),
+                        });
+                    }
+                    ApiContentBlockStart::WebSearchToolResult {
+                        tool_use_id,
+                        content,
+                    } => {
+                        let (results, error_code) =
+                            jcode_provider_anthropic::parse_web_search_result_content(&content);
+                        events.push(StreamEvent::WebSearchResult {
+                            tool_use_id,
+                            results,
+                            error_code,
+                        });
+                    }
                 }
             }
         }
@@ -2247,10 +2401,14 @@ fn process_sse_event(
                         events.push(StreamEvent::TextDelta(text));
                     }
                     ApiDelta::InputJson { partial_json } => {
-                        if let Some(tool) = state.current_tool_use.as_mut() {
+                        if let Some(tool) = state.current_server_tool_use.as_mut() {
                             tool.input_json.push_str(&partial_json);
+                        } else {
+                            if let Some(tool) = state.current_tool_use.as_mut() {
+                                tool.input_json.push_str(&partial_json);
+                            }
+                            events.push(StreamEvent::ToolInputDelta(partial_json));
                         }
-                        events.push(StreamEvent::ToolInputDelta(partial_json));
                     }
                     ApiDelta::Thinking { thinking } => {
                         events.push(StreamEvent::ThinkingDelta(thinking));
@@ -2258,12 +2416,34 @@ fn process_sse_event(
                     ApiDelta::Signature { signature } => {
                         events.push(StreamEvent::ThinkingSignatureDelta(signature));
                     }
+                    ApiDelta::Citations { citation } => {
+                        // Only web search citations carry a URL; document
+                        // citations have nothing to link to.
+                        if let Some(url) = citation.url {
+                            events.push(StreamEvent::Citation(Citation {
+                                url,
+                                title: citation.title,
+                                cited_text: citation.cited_text,
+                            }));
+                        }
+                    }
                 }
             }
         }
         "content_block_stop" => {
             // If we were accumulating a tool_use, it's complete now
-            if state.current_tool_use.take().is_some() {
+            if let Some(tool) = state.current_server_tool_use.take() {
+                let input = if tool.input_json.trim().is_empty() {
+                    json!({})
+                } else {
+                    serde_json::from_str(&tool.input_json).unwrap_or_else(|_| json!({}))
+                };
+                events.push(StreamEvent::ServerToolUse {
+                    id: tool.id,
+                    name: tool.name,
+                    input,
+                });
+            } else if state.current_tool_use.take().is_some() {
                 events.push(StreamEvent::ToolUseEnd);
             } else if state.current_thinking_block {
                 state.current_thinking_block = false;
@@ -2274,6 +2454,9 @@ fn process_sse_event(
             if let Ok(parsed) = serde_json::from_str::<MessageDeltaEvent>(&event.data) {
                 if let Some(usage) = parsed.usage {
                     state.output_tokens = usage.output_tokens.map(|t| t as u64);
+                    if let Some(server_tool_use) = usage.server_tool_use {
+                        state.web_search_requests = server_tool_use.web_search_requests;
+                    }
                 }
                 if let Some(stop_reason) = parsed.delta.stop_reason {
                     events.push(StreamEvent::MessageEnd {
@@ -2376,6 +2559,10 @@ enum ApiContentBlockStart {
     },
     #[serde(rename = "tool_use")]
     ToolUse { id: String, name: String },
+    #[serde(rename = "server_tool_use")]
+    ServerToolUse { id: String, name: String },
+    #[serde(rename = "web_search_tool_result")]
+    WebSearchToolResult { tool_use_id: String, content: Value },
 }
 
 #[derive(Deserialize)]
@@ -2399,6 +2586,18 @@ enum ApiDelta {
         #[serde(rename = "signature")]
         signature: String,
     },
+    #[serde(rename = "citations_delta")]
+    Citations { citation: ApiCitation },
+}
+
+#[derive(Deserialize)]
+struct ApiCitation {
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    cited_text: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -2419,6 +2618,14 @@ struct UsageInfo {
     cache_read_input_tokens: Option<u32>,
     cache_creation_input_tokens: Option<u32>,
     service_tier: Option<String>,
+    #[serde(default)]
+    server_tool_use: Option<ServerToolUseUsage>,
+}
+
+#[derive(Deserialize)]
+struct ServerToolUseUsage {
+    #[serde(default)]
+    web_search_requests: Option<u64>,
 }
 
 #[cfg(test)]
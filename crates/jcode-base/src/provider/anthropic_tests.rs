@@ -128,6 +128,27 @@ fn test_anthropic_reasoning_effort_request_parts() {
     );
 }
 
+#[test]
+fn test_anthropic_deterministic_params_report_what_thinking_rules_out() {
+    let provider = AnthropicProvider::new();
+    provider.set_model("claude-sonnet-4-6").unwrap();
+    provider.set_reasoning_effort("medium").unwrap();
+
+    let params = provider.deterministic_params_for("claude-sonnet-4-6[1m]");
+    assert_eq!(params.model, "claude-sonnet-4-6");
+    assert_eq!(params.temperature, None);
+    assert_eq!(params.seed, None);
+    assert_eq!(
+        params.unsupported,
+        vec![
+            "dated model snapshot",
+            "temperature (extended thinking is on)",
+            "top_p",
+            "seed"
+        ]
+    );
+}
+
 #[test]
 fn test_anthropic_preserves_swarm_sentinels_for_cycling() {
     // Regression: storing a swarm effort must preserve which swarm mode was
@@ -492,6 +513,208 @@ fn test_anthropic_signed_thinking_replayed_in_request_blocks() {
     );
 }
 
+#[test]
+fn test_anthropic_server_web_search_sse_events() {
+    let mut state = SseStreamState::default();
+    let event = |event_type: &str, data: serde_json::Value| SseEvent {
+        event_type: event_type.to_string(),
+        data: data.to_string(),
+    };
+
+    let start = event(
+        "content_block_start",
+        serde_json::json!({
+            "type": "content_block_start",
+            "index": 1,
+            "content_block": {"type": "server_tool_use", "id": "srvtoolu_1", "name": "web_search", "input": {}}
+        }),
+    );
+    assert!(process_sse_event(&start, &mut state, false).is_empty());
+
+    // Server tool input is accumulated, never surfaced as a local tool call.
+    let delta = event(
+        "content_block_delta",
+        serde_json::json!({
+            "type": "content_block_delta",
+            "index": 1,
+            "delta": {"type": "input_json_delta", "partial_json": "{\"query\": \"rust 2024\"}"}
+        }),
+    );
+    assert!(process_sse_event(&delta, &mut state, false).is_empty());
+
+    let stop = event(
+        "content_block_stop",
+        serde_json::json!({"type": "content_block_stop", "index": 1}),
+    );
+    let events = process_sse_event(&stop, &mut state, false);
+    assert!(matches!(
+        events.as_slice(),
+        [StreamEvent::ServerToolUse { id, name, input }]
+            if id == "srvtoolu_1" && name == "web_search" && input["query"] == "rust 2024"
+    ));
+
+    let result = event(
+        "content_block_start",
+        serde_json::json!({
+            "type": "content_block_start",
+            "index": 2,
+            "content_block": {
+                "type": "web_search_tool_result",
+                "tool_use_id": "srvtoolu_1",
+                "content": [{
+                    "type": "web_search_result",
+                    "url": "https://blog.rust-lang.org/",
+                    "title": "Rust Blog",
+                    "encrypted_content": "enc",
+                    "page_age": "2 days ago"
+                }]
+            }
+        }),
+    );
+    let events = process_sse_event(&result, &mut state, false);
+    let [
+        StreamEvent::WebSearchResult {
+            tool_use_id,
+            results,
+            error_code,
+        },
+    ] = events.as_slice()
+    else {
+        panic!("expected a web search result event");
+    };
+    assert_eq!(tool_use_id, "srvtoolu_1");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].url, "https://blog.rust-lang.org/");
+    assert_eq!(results[0].encrypted_content, "enc");
+    assert!(error_code.is_none());
+
+    let citation = event(
+        "content_block_delta",
+        serde_json::json!({
+            "type": "content_block_delta",
+            "index": 3,
+            "delta": {
+                "type": "citations_delta",
+                "citation": {
+                    "type": "web_search_result_location",
+                    "url": "https://blog.rust-lang.org/",
+                    "title": "Rust Blog",
+                    "cited_text": "Rust 2024 is stable",
+                    "encrypted_index": "idx"
+                }
+            }
+        }),
+    );
+    let events = process_sse_event(&citation, &mut state, false);
+    assert!(matches!(
+        events.as_slice(),
+        [StreamEvent::Citation(citation)] if citation.url == "https://blog.rust-lang.org/"
+    ));
+
+    let message_delta = event(
+        "message_delta",
+        serde_json::json!({
+            "type": "message_delta",
+            "delta": {"stop_reason": "end_turn"},
+            "usage": {"output_tokens": 42, "server_tool_use": {"web_search_requests": 1}}
+        }),
+    );
+    process_sse_event(&message_delta, &mut state, false);
+    assert_eq!(state.web_search_requests, Some(1));
+}
+
+#[test]
+fn test_anthropic_web_search_error_result_parsed() {
+    let mut state = SseStreamState::default();
+    let result = SseEvent {
+        event_type: "content_block_start".to_string(),
+        data: serde_json::json!({
+            "type": "content_block_start",
+            "index": 1,
+            "content_block": {
+                "type": "web_search_tool_result",
+                "tool_use_id": "srvtoolu_1",
+                "content": {"type": "web_search_tool_result_error", "error_code": "max_uses_exceeded"}
+            }
+        })
+        .to_string(),
+    };
+    let events = process_sse_event(&result, &mut state, false);
+    assert!(matches!(
+        events.as_slice(),
+        [StreamEvent::WebSearchResult { results, error_code: Some(code), .. }]
+            if results.is_empty() && code == "max_uses_exceeded"
+    ));
+}
+
+#[test]
+fn test_anthropic_server_tool_blocks_replayed_and_citations_skipped() {
+    let provider = AnthropicProvider::new();
+    let blocks = provider.format_content_blocks(
+        &[
+            ContentBlock::ServerToolUse {
+                id: "srvtoolu_1".to_string(),
+                name: "web_search".to_string(),
+                input: serde_json::json!({"query": "rust"}),
+            },
+            ContentBlock::WebSearchResult {
+                tool_use_id: "srvtoolu_1".to_string(),
+                results: vec![crate::message::WebSearchHit {
+                    url: "https://www.rust-lang.org/".to_string(),
+                    title: "Rust".to_string(),
+                    page_age: None,
+                    encrypted_content: "enc".to_string(),
+                }],
+                error_code: None,
+            },
+            ContentBlock::Text {
+                text: "Rust is a language.".to_string(),
+                cache_control: None,
+            },
+            ContentBlock::Citations {
+                citations: vec![crate::message::Citation {
+                    url: "https://www.rust-lang.org/".to_string(),
+                    title: Some("Rust".to_string()),
+                    cited_text: None,
+                }],
+            },
+        ],
+        false,
+    );
+
+    let value = serde_json::to_value(&blocks).expect("serialize content blocks");
+    assert_eq!(
+        value,
+        serde_json::json!([
+            {"type": "server_tool_use", "id": "srvtoolu_1", "name": "web_search", "input": {"query": "rust"}},
+            {
+                "type": "web_search_tool_result",
+                "tool_use_id": "srvtoolu_1",
+                "content": [{
+                    "type": "web_search_result",
+                    "url": "https://www.rust-lang.org/",
+                    "title": "Rust",
+                    "encrypted_content": "enc"
+                }]
+            },
+            {"type": "text", "text": "Rust is a language."}
+        ])
+    );
+}
+
+#[test]
+fn test_anthropic_web_search_limited_to_supported_models() {
+    assert!(AnthropicProvider::model_supports_web_search(
+        "claude-opus-4-8"
+    ));
+    assert!(AnthropicProvider::model_supports_web_search(
+        "claude-sonnet-4-6[1m]"
+    ));
+    assert!(!AnthropicProvider::model_supports_web_search(
+        "claude-3-haiku-20240307"
+    ));
+}
+
 #[tokio::test]
 #[ignore = "live smoke: requires ANTHROPIC_API_KEY, or set JCODE_LIVE_ANTHROPIC_ALLOW_OAUTH=1 to use Claude OAuth credentials"]
 async fn live_anthropic_reasoning_smoke() -> Result<()> {
@@ -1509,6 +1732,18 @@ fn anthropic_fallback_prefers_best_available_and_skips_tried_and_retired() {
     );
 }
 
+#[test]
+fn anthropic_fallback_follows_successor_of_rejected_model() {
+    // A retired Sonnet moves to the current Sonnet rather than the flagship
+    // Opus the quality ranking would pick.
+    let fallback = anthropic_fallback_model(&["claude-3-5-sonnet-20241022".to_string()], "")
+        .expect("a fallback should exist");
+    assert!(
+        AnthropicProvider::normalized_model_key(&fallback).starts_with("claude-sonnet-4-"),
+        "expected a current Sonnet, got {fallback}"
+    );
+}
+
 #[test]
 fn anthropic_fallback_honors_server_recommendation() {
     // The real 404 body recommends a specific replacement model. We must honor
@@ -7,19 +7,19 @@ use chrono::Utc;
 pub(crate) use jcode_provider_antigravity::is_known_model;
 use jcode_provider_antigravity::{
     AVAILABLE_MODELS, CatalogModel, CatalogSnapshot, DEFAULT_FALLBACK_MODEL, FETCH_MODELS_API_URL,
-    FetchAvailableModelsResponse, GENERATE_CONTENT_API_URL, PersistedCatalog, X_GOOG_API_CLIENT,
-    antigravity_compatible_schema, antigravity_user_agent, catalog_is_stale, catalog_model_detail,
-    client_metadata_header, is_retryable_empty_turn, merge_antigravity_model_ids,
-    parse_fetch_available_models_response, remap_unsupported_model,
+    FetchAvailableModelsResponse, PersistedCatalog, STREAM_GENERATE_CONTENT_API_URL,
+    X_GOOG_API_CLIENT, antigravity_compatible_schema, antigravity_user_agent, catalog_is_stale,
+    catalog_model_detail, client_metadata_header, is_abnormal_finish_reason,
+    merge_antigravity_model_ids, parse_fetch_available_models_response, remap_unsupported_model,
 };
 #[cfg(test)]
 use jcode_provider_antigravity::{
-    flatten_schema_combiners, metadata_platform, model_is_claude, model_is_gemini,
-    strip_numeric_schema_bounds,
+    flatten_schema_combiners, is_retryable_empty_turn, metadata_platform, model_is_claude,
+    model_is_gemini, strip_numeric_schema_bounds,
 };
 use jcode_provider_gemini::{
     CodeAssistGenerateRequest, CodeAssistGenerateResponse, GeminiFunctionCallingConfig,
-    GeminiToolConfig, VertexGenerateContentRequest,
+    GeminiToolConfig, GeminiUsageMetadata, VertexGenerateContentRequest,
 };
 use serde_json::{Value, json};
 use std::sync::{Arc, RwLock};
@@ -331,7 +331,9 @@ impl AntigravityProvider {
             .await
     }
 
-    async fn generate_content(
+    /// Send a `streamGenerateContent` request and return the SSE response once
+    /// the backend has accepted it.
+    async fn stream_generate_content(
         &self,
         model: &str,
         messages: &[Message],
@@ -339,7 +341,7 @@ impl AntigravityProvider {
         system: &str,
         resume_session_id: Option<&str>,
         force_function_call: bool,
-    ) -> Result<CodeAssistGenerateResponse> {
+    ) -> Result<reqwest::Response> {
         let mut tokens = antigravity_auth::load_or_refresh_tokens().await?;
         let project = match tokens
             .project_id
@@ -424,7 +426,7 @@ impl AntigravityProvider {
         super::fingerprint::log_provider_canonical_input(
             "antigravity",
             model,
-            "gemini_generate_content",
+            "gemini_stream_generate_content",
             &payload,
             &content_items,
             system_value.as_ref(),
@@ -441,7 +443,7 @@ impl AntigravityProvider {
 
         let response = self
             .client
-            .post(GENERATE_CONTENT_API_URL)
+            .post(STREAM_GENERATE_CONTENT_API_URL)
             .bearer_auth(&tokens.access_token)
             .header(reqwest::header::CONTENT_TYPE, "application/json")
             .header(reqwest::header::USER_AGENT, antigravity_user_agent())
@@ -454,25 +456,208 @@ impl AntigravityProvider {
             .json(&request)
             .send()
             .await
-            .context("Failed to send Antigravity generateContent request")?;
+            .context("Failed to send Antigravity streamGenerateContent request")?;
 
         if !response.status().is_success() {
             let status = response.status();
             let body = crate::util::http_error_body(response, "HTTP error").await;
             anyhow::bail!(
-                "Antigravity generateContent failed (HTTP {}): {}",
+                "Antigravity streamGenerateContent failed (HTTP {}): {}",
                 status,
                 body.trim()
             );
         }
 
-        response
-            .json()
-            .await
-            .context("Failed to decode Antigravity generateContent response")
+        Ok(response)
+    }
+}
+
+/// Accumulated state of one `streamGenerateContent` turn.
+///
+/// Text and function-call parts are forwarded as each SSE chunk arrives; the
+/// finish reason, usage and any unconsumed thought signature are held until the
+/// stream ends so [`StreamedTurn::finish`] can close the turn.
+#[derive(Default)]
+struct StreamedTurn {
+    sse_buffer: Vec<u8>,
+    saw_candidate: bool,
+    produced_output: bool,
+    pending_signature: Option<String>,
+    finish_reason: Option<String>,
+    finish_message: Option<String>,
+    usage: Option<GeminiUsageMetadata>,
+}
+
+impl StreamedTurn {
+    /// Feed raw SSE bytes and return the events for every complete `data:` line.
+    fn push_sse(&mut self, bytes: &[u8]) -> Result<Vec<StreamEvent>> {
+        self.sse_buffer.extend_from_slice(bytes);
+        let mut events = Vec::new();
+        while let Some(line_end) = self.sse_buffer.iter().position(|byte| *byte == b'\n') {
+            let line: Vec<u8> = self.sse_buffer.drain(..=line_end).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\r', '\n']);
+            if let Some(data) = crate::util::sse_data_line(line)
+                && !data.trim().is_empty()
+            {
+                let chunk: CodeAssistGenerateResponse = serde_json::from_str(data.trim())
+                    .context("Failed to decode Antigravity streamGenerateContent chunk")?;
+                events.extend(self.apply_chunk(chunk));
+            }
+        }
+        Ok(events)
+    }
+
+    fn apply_chunk(&mut self, chunk: CodeAssistGenerateResponse) -> Vec<StreamEvent> {
+        let mut events = Vec::new();
+        let Some(response) = chunk.response else {
+            return events;
+        };
+        if let Some(usage) = response.usage_metadata {
+            self.usage = Some(usage);
+        }
+        let Some(candidate) = response
+            .candidates
+            .and_then(|mut candidates| candidates.drain(..).next())
+        else {
+            return events;
+        };
+        self.saw_candidate = true;
+        if candidate.finish_reason.is_some() {
+            self.finish_reason = candidate.finish_reason;
+        }
+        if candidate.finish_message.is_some() {
+            self.finish_message = candidate.finish_message;
+        }
+        let Some(content) = candidate.content else {
+            return events;
+        };
+        // Gemini 3 attaches a `thoughtSignature` to function-call parts (and
+        // occasionally to a standalone preceding part, possibly in an earlier
+        // chunk). Emit tool calls through the standard ToolUseStart/End path so
+        // jcode drives the multi-turn loop, and replay the signature via a
+        // dedicated ToolUseSignature event so it can be persisted on the ToolUse
+        // block and resent on later turns (required by the Cloud Code backend,
+        // which rejects function calls missing it).
+        for part in content.parts {
+            let part_signature = part
+                .thought_signature
+                .as_ref()
+                .filter(|sig| !sig.is_empty())
+                .cloned();
+            if let Some(text) = part.text.filter(|text| !text.is_empty()) {
+                self.produced_output = true;
+                events.push(StreamEvent::TextDelta(text));
+            }
+            if let Some(function_call) = part.function_call {
+                self.produced_output = true;
+                let signature = part_signature.or_else(|| self.pending_signature.take());
+                let raw_call_id = function_call
+                    .id
+                    .clone()
+                    .unwrap_or_else(|| Uuid::new_v4().to_string());
+                events.push(StreamEvent::ToolUseStart {
+                    id: crate::message::sanitize_tool_id(&raw_call_id),
+                    name: function_call.name,
+                });
+                events.push(StreamEvent::ToolInputDelta(function_call.args.to_string()));
+                events.push(StreamEvent::ToolUseEnd);
+                if let Some(signature) = signature {
+                    events.push(StreamEvent::ToolUseSignature(signature));
+                }
+            } else if let Some(signature) = part_signature {
+                // Standalone signature part; remember it for the next function
+                // call in this turn.
+                self.pending_signature = Some(signature);
+            }
+        }
+        events
+    }
+
+    /// An abnormal finish with no text and no tool call, which is worth
+    /// re-requesting transparently.
+    fn is_retryable_empty(&self) -> bool {
+        !self.produced_output && is_abnormal_finish_reason(self.finish_reason.as_deref())
+    }
+
+    /// Events that close the turn once the stream has ended.
+    fn finish(mut self) -> Vec<Result<StreamEvent>> {
+        let mut events = Vec::new();
+        if let Some(usage) = self.usage.take() {
+            events.push(Ok(StreamEvent::TokenUsage {
+                input_tokens: usage.prompt_token_count,
+                output_tokens: usage.candidates_token_count,
+                cache_read_input_tokens: usage.cached_content_token_count,
+                cache_creation_input_tokens: None,
+            }));
+        }
+        if !self.saw_candidate {
+            events.push(Err(anyhow::anyhow!(
+                "Antigravity returned no candidates for streamGenerateContent"
+            )));
+            return events;
+        }
+        // A thought signature that was never consumed by a following function
+        // call (e.g. a pure-text reasoning turn) is still an opaque reasoning
+        // signal. Surface it as a ThinkingSignatureDelta rather than dropping it,
+        // so reasoning-aware consumers (and the provider-doctor reasoning probe)
+        // can see the model reasoned.
+        if let Some(signature) = self.pending_signature.take() {
+            events.push(Ok(StreamEvent::ThinkingSignatureDelta(signature)));
+        }
+        // An abnormal finish (typically Gemini-3's intermittent
+        // `MALFORMED_FUNCTION_CALL`, where the model writes pseudo-code rather
+        // than a valid functionCall) that yielded no text and no tool call is a
+        // dead turn: surface it as a retryable error instead of a silent empty
+        // `MessageEnd` that looks like the agent gave up. `STOP`/`MAX_TOKENS`
+        // are normal terminal reasons and are left to flow through as usual.
+        if self.is_retryable_empty() {
+            let reason = self.finish_reason.as_deref().unwrap_or("unknown");
+            let detail = self
+                .finish_message
+                .as_deref()
+                .filter(|msg| !msg.trim().is_empty())
+                .map(|msg| format!(": {}", crate::util::truncate_str(msg.trim(), 300)))
+                .unwrap_or_default();
+            events.push(Err(anyhow::anyhow!(
+                "Antigravity returned no usable output (finish_reason={reason}){detail}"
+            )));
+            return events;
+        }
+        events.push(Ok(StreamEvent::MessageEnd {
+            stop_reason: self.finish_reason,
+        }));
+        events
     }
 }
 
+/// Forward one streamed response to `tx` as it arrives and return the turn
+/// state for the caller to retry or finish.
+async fn stream_turn(
+    response: reqwest::Response,
+    tx: &mpsc::Sender<Result<StreamEvent>>,
+) -> Result<StreamedTurn> {
+    use futures::StreamExt;
+
+    let mut stream = response.bytes_stream();
+    let mut turn = StreamedTurn::default();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.context("Antigravity stream error")?;
+        for event in turn.push_sse(&chunk)? {
+            if tx.send(Ok(event)).await.is_err() {
+                return Ok(turn);
+            }
+        }
+    }
+    // Flush a final `data:` line the server did not newline-terminate.
+    for event in turn.push_sse(b"\n")? {
+        if tx.send(Ok(event)).await.is_err() {
+            break;
+        }
+    }
+    Ok(turn)
+}
+
 impl Default for AntigravityProvider {
     fn default() -> Self {
         Self::new()
@@ -516,190 +701,57 @@ impl Provider for AntigravityProvider {
                     phase: ConnectionPhase::WaitingForResponse,
                 }))
                 .await;
-            let response = match provider
-                .generate_content(
-                    &model,
-                    &messages,
-                    &tools,
-                    &system,
-                    resume_session_id.as_deref(),
-                    false,
-                )
-                .await
-            {
-                Ok(response) => response,
-                Err(err) => {
-                    let _ = tx.send(Err(err)).await;
-                    return;
-                }
-            };
             // Gemini-3 thinking models intermittently return an empty
             // `MALFORMED_FUNCTION_CALL` turn (pseudo-code instead of a clean
             // functionCall). It is transient, so transparently re-request a few
             // times before surfacing it; this turns a frequent hard failure into a
             // near-always-successful turn without the agent loop seeing the blip.
             // The retries force function-calling mode `ANY` so the model must emit
-            // a real functionCall rather than the pseudo-code that failed.
-            let mut response = response;
+            // a real functionCall rather than the pseudo-code that failed. A
+            // retryable turn emitted no text or tool events, so nothing has to be
+            // taken back from the consumer.
             let mut malformed_retries = 0u8;
             const MAX_MALFORMED_RETRIES: u8 = 2;
-            while is_retryable_empty_turn(&response) && malformed_retries < MAX_MALFORMED_RETRIES {
-                malformed_retries += 1;
-                match provider
-                    .generate_content(
+            let turn = loop {
+                let response = match provider
+                    .stream_generate_content(
                         &model,
                         &messages,
                         &tools,
                         &system,
                         resume_session_id.as_deref(),
-                        true,
+                        malformed_retries > 0,
                     )
                     .await
                 {
-                    Ok(retried) => response = retried,
+                    Ok(response) => response,
                     Err(err) => {
                         let _ = tx.send(Err(err)).await;
                         return;
                     }
-                }
-            }
-            let _ = tx
-                .send(Ok(StreamEvent::ConnectionPhase {
-                    phase: ConnectionPhase::Streaming,
-                }))
-                .await;
-            if let Some(usage) = response
-                .response
-                .as_ref()
-                .and_then(|r| r.usage_metadata.as_ref())
-            {
+                };
                 let _ = tx
-                    .send(Ok(StreamEvent::TokenUsage {
-                        input_tokens: usage.prompt_token_count,
-                        output_tokens: usage.candidates_token_count,
-                        cache_read_input_tokens: usage.cached_content_token_count,
-                        cache_creation_input_tokens: None,
+                    .send(Ok(StreamEvent::ConnectionPhase {
+                        phase: ConnectionPhase::Streaming,
                     }))
                     .await;
-            }
-            let Some(candidate) = response
-                .response
-                .and_then(|r| r.candidates)
-                .and_then(|mut c| c.drain(..).next())
-            else {
-                let _ = tx
-                    .send(Err(anyhow::anyhow!(
-                        "Antigravity returned no candidates for generateContent"
-                    )))
-                    .await;
-                return;
-            };
-            // Track whether this candidate produced any usable output (text or a
-            // tool call). Gemini-3 thinking models intermittently emit Python-style
-            // pseudo-code instead of a clean functionCall and finish with
-            // `MALFORMED_FUNCTION_CALL` (or a bare `OTHER`) and empty content. If we
-            // silently end the turn the agent loop looks like it stalled with no
-            // answer, so we surface an actionable error below instead.
-            let mut produced_output = false;
-            if let Some(content) = candidate.content {
-                // Gemini 3 attaches a `thoughtSignature` to function-call parts
-                // (and occasionally to a standalone preceding part). Emit tool
-                // calls through the standard ToolUseStart/End path so jcode
-                // drives the multi-turn loop, and replay the signature via a
-                // dedicated ToolUseSignature event so it can be persisted on the
-                // ToolUse block and resent on later turns (required by the
-                // Cloud Code backend, which rejects function calls missing it).
-                let mut pending_signature: Option<String> = None;
-                for part in content.parts {
-                    let part_signature = part
-                        .thought_signature
-                        .as_ref()
-                        .filter(|sig| !sig.is_empty())
-                        .cloned();
-                    if let Some(text) = part.text.filter(|text| !text.is_empty()) {
-                        produced_output = true;
-                        let _ = tx.send(Ok(StreamEvent::TextDelta(text))).await;
-                    }
-                    if let Some(function_call) = part.function_call {
-                        produced_output = true;
-                        let signature = part_signature.clone().or_else(|| pending_signature.take());
-                        let raw_call_id = function_call
-                            .id
-                            .clone()
-                            .unwrap_or_else(|| Uuid::new_v4().to_string());
-                        let call_id = crate::message::sanitize_tool_id(&raw_call_id);
-                        let _ = tx
-                            .send(Ok(StreamEvent::ToolUseStart {
-                                id: call_id,
-                                name: function_call.name,
-                            }))
-                            .await;
-                        let _ = tx
-                            .send(Ok(StreamEvent::ToolInputDelta(
-                                function_call.args.to_string(),
-                            )))
-                            .await;
-                        let _ = tx.send(Ok(StreamEvent::ToolUseEnd)).await;
-                        if let Some(signature) = signature {
-                            let _ = tx.send(Ok(StreamEvent::ToolUseSignature(signature))).await;
-                        }
-                    } else if let Some(signature) = part_signature {
-                        // Standalone signature part; remember it for the next
-                        // function call in this candidate.
-                        pending_signature = Some(signature);
+                let turn = match stream_turn(response, &tx).await {
+                    Ok(turn) => turn,
+                    Err(err) => {
+                        let _ = tx.send(Err(err)).await;
+                        return;
                     }
+                };
+                if turn.is_retryable_empty() && malformed_retries < MAX_MALFORMED_RETRIES {
+                    malformed_retries += 1;
+                    continue;
                 }
-                // A thought signature that was never consumed by a following
-                // function call (e.g. a pure-text reasoning turn) is still an
-                // opaque reasoning signal. Surface it as a ThinkingSignatureDelta
-                // rather than dropping it, so reasoning-aware consumers (and the
-                // provider-doctor reasoning probe) can see the model reasoned.
-                if let Some(signature) = pending_signature.take() {
-                    let _ = tx
-                        .send(Ok(StreamEvent::ThinkingSignatureDelta(signature)))
-                        .await;
-                }
-            }
+                break turn;
+            };
 
-            // An abnormal finish (typically Gemini-3's intermittent
-            // `MALFORMED_FUNCTION_CALL`, where the model writes pseudo-code rather
-            // than a valid functionCall) that yielded no text and no tool call is a
-            // dead turn: surface it as a retryable error instead of a silent empty
-            // `MessageEnd` that looks like the agent gave up. `STOP`/`MAX_TOKENS`
-            // are normal terminal reasons and are left to flow through as usual.
-            if !produced_output {
-                let abnormal = candidate
-                    .finish_reason
-                    .as_deref()
-                    .map(|reason| {
-                        !matches!(
-                            reason.to_ascii_uppercase().as_str(),
-                            "STOP" | "MAX_TOKENS" | "FINISH_REASON_UNSPECIFIED" | ""
-                        )
-                    })
-                    .unwrap_or(false);
-                if abnormal {
-                    let reason = candidate.finish_reason.as_deref().unwrap_or("unknown");
-                    let detail = candidate
-                        .finish_message
-                        .as_deref()
-                        .filter(|msg| !msg.trim().is_empty())
-                        .map(|msg| format!(": {}", crate::util::truncate_str(msg.trim(), 300)))
-                        .unwrap_or_default();
-                    let _ = tx
-                        .send(Err(anyhow::anyhow!(
-                            "Antigravity returned no usable output (finish_reason={reason}){detail}"
-                        )))
-                        .await;
-                    return;
-                }
+            for event in turn.finish() {
+                let _ = tx.send(event).await;
             }
-
-            let _ = tx
-                .send(Ok(StreamEvent::MessageEnd {
-                    stop_reason: candidate.finish_reason.clone(),
-                }))
-                .await;
         });
 
         Ok(Box::pin(ReceiverStream::new(rx)))
@@ -609,3 +609,92 @@ fn is_retryable_empty_turn_ignores_normal_and_productive_turns() {
     .expect("decode empty stop response");
     assert!(!is_retryable_empty_turn(&empty_stop));
 }
+
+#[test]
+fn streamed_turn_forwards_text_chunks_incrementally() {
+    let mut turn = StreamedTurn::default();
+    let first = turn
+        .push_sse(b"data: {\"response\":{\"candidates\":[{\"content\":{\"parts\":[{\"text\":\"Hel\"}]}}]}}\n\n")
+        .expect("first chunk");
+    assert!(matches!(first.as_slice(), [StreamEvent::TextDelta(text)] if text == "Hel"));
+
+    // A chunk split mid-line is held until its newline arrives.
+    let partial = turn
+        .push_sse(
+            b"data: {\"response\":{\"candidates\":[{\"content\":{\"parts\":[{\"text\":\"lo\"}]},",
+        )
+        .expect("partial chunk");
+    assert!(partial.is_empty());
+    let rest = turn
+        .push_sse(b"\"finishReason\":\"STOP\"}],\"usageMetadata\":{\"promptTokenCount\":7,\"candidatesTokenCount\":2}}}\n\n")
+        .expect("rest of chunk");
+    assert!(matches!(rest.as_slice(), [StreamEvent::TextDelta(text)] if text == "lo"));
+
+    let closing = turn.finish();
+    assert!(matches!(
+        closing[0],
+        Ok(StreamEvent::TokenUsage {
+            input_tokens: Some(7),
+            output_tokens: Some(2),
+            ..
+        })
+    ));
+    assert!(matches!(
+        &closing[1],
+        Ok(StreamEvent::MessageEnd { stop_reason: Some(reason) }) if reason == "STOP"
+    ));
+}
+
+#[test]
+fn streamed_turn_pairs_function_call_with_signature_from_earlier_chunk() {
+    let mut turn = StreamedTurn::default();
+    let body = concat!(
+        "data: {\"response\":{\"candidates\":[{\"content\":{\"parts\":[{\"thoughtSignature\":\"sig-1\"}]}}]}}\n\n",
+        "data: {\"response\":{\"candidates\":[{\"content\":{\"parts\":[{\"functionCall\":{\"id\":\"call-1\",\"name\":\"read\",\"args\":{\"path\":\"a.rs\"}}}]},\"finishReason\":\"STOP\"}]}}\n\n",
+    );
+    let events = turn.push_sse(body.as_bytes()).expect("stream body");
+
+    assert!(matches!(
+        &events[0],
+        StreamEvent::ToolUseStart { id, name } if id == "call-1" && name == "read"
+    ));
+    assert!(matches!(
+        &events[1],
+        StreamEvent::ToolInputDelta(input) if input == "{\"path\":\"a.rs\"}"
+    ));
+    assert!(matches!(events[2], StreamEvent::ToolUseEnd));
+    assert!(matches!(&events[3], StreamEvent::ToolUseSignature(sig) if sig == "sig-1"));
+    assert!(!turn.is_retryable_empty());
+    assert!(matches!(
+        turn.finish().as_slice(),
+        [Ok(StreamEvent::MessageEnd { .. })]
+    ));
+}
+
+#[test]
+fn streamed_turn_flags_malformed_empty_turn_for_retry() {
+    let mut turn = StreamedTurn::default();
+    let events = turn
+        .push_sse(b"data: {\"response\":{\"candidates\":[{\"content\":{},\"finishReason\":\"MALFORMED_FUNCTION_CALL\",\"finishMessage\":\"Malformed function call\"}]}}")
+        .expect("unterminated chunk");
+    assert!(events.is_empty());
+    // The final line is only parsed once the stream end flushes it.
+    assert!(turn.push_sse(b"\n").expect("flush").is_empty());
+
+    assert!(turn.is_retryable_empty());
+    let closing = turn.finish();
+    let Some(Err(err)) = closing.last() else {
+        panic!("expected dead-turn error, got {closing:?}");
+    };
+    assert!(err.to_string().contains("MALFORMED_FUNCTION_CALL"));
+}
+
+#[test]
+fn streamed_turn_without_candidates_is_an_error() {
+    let mut turn = StreamedTurn::default();
+    turn.push_sse(b"data: {\"response\":{\"usageMetadata\":{\"promptTokenCount\":3}}}\n\n")
+        .expect("usage-only chunk");
+    let closing = turn.finish();
+    assert!(matches!(closing[0], Ok(StreamEvent::TokenUsage { .. })));
+    assert!(matches!(closing.last(), Some(Err(_))));
+}
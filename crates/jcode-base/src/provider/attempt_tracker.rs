@@ -31,6 +31,7 @@ use tokio::task::JoinHandle;
 pub(crate) fn stream_event_is_replay_visible(event: &StreamEvent) -> bool {
     match event {
         StreamEvent::TextDelta(_)
+        | StreamEvent::TextDeltaAt { .. }
         | StreamEvent::ToolUseStart { .. }
         | StreamEvent::ToolInputDelta(_)
         | StreamEvent::ToolUseEnd
@@ -42,18 +43,24 @@ pub(crate) fn stream_event_is_replay_visible(event: &StreamEvent) -> bool {
         | StreamEvent::OpenAIReasoning { .. }
         | StreamEvent::MessageEnd { .. }
         | StreamEvent::Compaction { .. }
-        | StreamEvent::NativeToolCall { .. } => true,
+        | StreamEvent::NativeToolCall { .. }
+        | StreamEvent::ServerToolUse { .. }
+        | StreamEvent::WebSearchResult { .. }
+        | StreamEvent::Citation(_) => true,
         StreamEvent::ThinkingStart
         | StreamEvent::ThinkingEnd
         | StreamEvent::ThinkingDone { .. }
         | StreamEvent::RetryRollback { .. }
         | StreamEvent::TokenUsage { .. }
+        | StreamEvent::ServerToolUsage { .. }
         | StreamEvent::ConnectionType { .. }
         | StreamEvent::ConnectionPhase { .. }
         | StreamEvent::StatusDetail { .. }
         | StreamEvent::Error { .. }
         | StreamEvent::SessionId(_)
-        | StreamEvent::UpstreamProvider { .. } => false,
+        | StreamEvent::BackgroundResponse { .. }
+        | StreamEvent::UpstreamProvider { .. }
+        | StreamEvent::SystemFingerprint(_) => false,
     }
 }
 
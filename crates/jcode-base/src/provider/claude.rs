@@ -95,7 +95,10 @@ impl ClaudeProvider {
                     | ContentBlock::AnthropicThinking { .. }
                     | ContentBlock::OpenAIReasoning { .. } => {}
                     ContentBlock::Image { .. } => {}
-                    ContentBlock::OpenAICompaction { .. } => {}
+                    ContentBlock::OpenAICompaction { .. }
+                    | ContentBlock::ServerToolUse { .. }
+                    | ContentBlock::WebSearchResult { .. }
+                    | ContentBlock::Citations { .. } => {}
                 }
             }
             if !parts.is_empty() {
@@ -1085,9 +1088,11 @@ fn to_claude_tool_name(name: &str) -> String {
         "glob" => "Glob",
         "grep" => "Grep",
         "ls" => "Ls",
+        "tree" => "Tree",
         "webfetch" => "WebFetch",
         "websearch" => "WebSearch",
         "open" => "Open",
+        "clipboard" => "Clipboard",
         "codesearch" => "CodeSearch",
         "invalid" => "Invalid",
         "skill" => "Skill",
@@ -1114,9 +1119,11 @@ fn to_internal_tool_name(name: &str) -> String {
         "Glob" => "glob",
         "Grep" => "grep",
         "Ls" => "ls",
+        "Tree" => "tree",
         "WebFetch" => "webfetch",
         "WebSearch" => "websearch",
         "Open" => "open",
+        "Clipboard" => "clipboard",
         "Launch" => "open",
         "CodeSearch" => "codesearch",
         "Invalid" => "invalid",
@@ -1,5 +1,6 @@
 use super::{EventStream, Provider};
 use crate::auth::copilot as copilot_auth;
+use crate::copilot_usage::PremiumBudgetLevel;
 use crate::message::{ContentBlock, Message as ChatMessage, Role, StreamEvent, ToolDefinition};
 use anyhow::Result;
 use async_trait::async_trait;
@@ -9,7 +10,7 @@ use jcode_provider_copilot::max_token_parameter_for_model as copilot_max_token_p
 use jcode_provider_copilot::{
     COPILOT_API_VERSION, PersistedCatalog,
     add_max_token_parameter as add_copilot_max_token_parameter,
-    build_messages as build_copilot_messages, build_tools as build_copilot_tools,
+    build_messages as build_copilot_messages, build_tools as build_copilot_tools, is_premium_model,
 };
 pub(crate) use jcode_provider_copilot::{DEFAULT_MODEL, FALLBACK_MODELS, is_known_display_model};
 pub use jcode_provider_core::PremiumMode;
@@ -41,6 +42,8 @@ pub struct CopilotApiProvider {
     init_ready: Arc<tokio::sync::Notify>,
     init_done: Arc<std::sync::atomic::AtomicBool>,
     premium_mode: Arc<std::sync::atomic::AtomicU8>,
+    /// Set once the user picks a premium mode, so the budget never overrides it.
+    premium_mode_pinned: Arc<std::sync::atomic::AtomicBool>,
     user_turn_count: Arc<std::sync::atomic::AtomicU64>,
     created_at: std::time::Instant,
 }
@@ -127,6 +130,7 @@ impl CopilotApiProvider {
             init_ready: Arc::new(tokio::sync::Notify::new()),
             init_done: Arc::new(std::sync::atomic::AtomicBool::new(false)),
             premium_mode: Arc::new(std::sync::atomic::AtomicU8::new(Self::env_premium_mode())),
+            premium_mode_pinned: Arc::new(std::sync::atomic::AtomicBool::new(false)),
             user_turn_count: Arc::new(std::sync::atomic::AtomicU64::new(0)),
             created_at: std::time::Instant::now(),
         };
@@ -162,6 +166,7 @@ impl CopilotApiProvider {
             init_ready: Arc::new(tokio::sync::Notify::new()),
             init_done: Arc::new(std::sync::atomic::AtomicBool::new(false)),
             premium_mode: Arc::new(std::sync::atomic::AtomicU8::new(Self::env_premium_mode())),
+            premium_mode_pinned: Arc::new(std::sync::atomic::AtomicBool::new(false)),
             user_turn_count: Arc::new(std::sync::atomic::AtomicU64::new(0)),
             created_at: std::time::Instant::now(),
         };
@@ -248,11 +253,56 @@ impl CopilotApiProvider {
     pub fn set_premium_mode(&self, mode: PremiumMode) {
         self.premium_mode
             .store(mode as u8, std::sync::atomic::Ordering::Relaxed);
+        self.premium_mode_pinned
+            .store(true, std::sync::atomic::Ordering::Relaxed);
         if mode != PremiumMode::Normal {
             crate::logging::info(&format!("Copilot premium mode set to {:?}", mode));
         }
     }
 
+    /// Apply `[provider.copilot] monthly_premium_budget` before a request:
+    /// conserve premium requests from 80% of the budget and refuse premium
+    /// models once it is spent. `/premium off` sends everything as non-premium,
+    /// so it is never blocked.
+    fn enforce_premium_budget(&self, model: &str) -> Result<()> {
+        let Some(status) = crate::copilot_usage::budget_status() else {
+            return Ok(());
+        };
+        match status.level() {
+            PremiumBudgetLevel::Ok => Ok(()),
+            PremiumBudgetLevel::Conserve => {
+                if self.get_premium_mode() == PremiumMode::Normal
+                    && !self
+                        .premium_mode_pinned
+                        .load(std::sync::atomic::Ordering::Relaxed)
+                {
+                    self.premium_mode.store(
+                        PremiumMode::OnePerSession as u8,
+                        std::sync::atomic::Ordering::Relaxed,
+                    );
+                    crate::logging::info(&format!(
+                        "Copilot premium budget {}/{} used; conserving premium requests",
+                        status.used, status.budget
+                    ));
+                }
+                Ok(())
+            }
+            PremiumBudgetLevel::Exhausted => {
+                if self.get_premium_mode() == PremiumMode::Zero || !is_premium_model(model) {
+                    return Ok(());
+                }
+                anyhow::bail!(
+                    "Copilot monthly premium budget exhausted ({}/{} premium requests used). \
+                     Switch to an included model with /model ({}), raise \
+                     [provider.copilot] monthly_premium_budget, or run /premium off.",
+                    status.used,
+                    status.budget,
+                    jcode_provider_copilot::INCLUDED_MODELS.join(", ")
+                )
+            }
+        }
+    }
+
     pub fn get_premium_mode(&self) -> PremiumMode {
         match self.premium_mode.load(std::sync::atomic::Ordering::Relaxed) {
             1 => PremiumMode::OnePerSession,
@@ -578,7 +628,10 @@ impl CopilotApiProvider {
                 crate::provider::attempt_tracker::track_attempt_output(tx.clone());
 
             // Process SSE stream - returns Err on timeout/stream errors
-            match self.process_sse_stream(resp, attempt_tx).await {
+            crate::copilot_usage::record_quota_headers(resp.headers());
+            let is_premium = is_user_initiated && is_premium_model(&model);
+
+            match self.process_sse_stream(resp, attempt_tx, is_premium).await {
                 Ok(()) => {
                     let _ = attempt_guard.finish().await;
                     return;
@@ -639,6 +692,7 @@ impl CopilotApiProvider {
         &self,
         resp: reqwest::Response,
         tx: mpsc::Sender<Result<StreamEvent>>,
+        is_premium: bool,
     ) -> Result<()> {
         use futures::StreamExt;
 
@@ -698,7 +752,11 @@ impl CopilotApiProvider {
                                 }))
                                 .await;
                         }
-                        crate::copilot_usage::record_request(input_tokens, output_tokens, true);
+                        crate::copilot_usage::record_request(
+                            input_tokens,
+                            output_tokens,
+                            is_premium,
+                        );
                         let _ = tx
                             .send(Ok(StreamEvent::MessageEnd { stop_reason: None }))
                             .await;
@@ -852,6 +910,8 @@ impl Provider for CopilotApiProvider {
             e
         })?;
 
+        self.enforce_premium_budget(&self.model())?;
+
         let is_user_initiated = self.is_user_initiated(messages);
         if is_user_initiated {
             self.user_turn_count
@@ -901,6 +961,7 @@ impl Provider for CopilotApiProvider {
             init_ready: self.init_ready.clone(),
             init_done: self.init_done.clone(),
             premium_mode: self.premium_mode.clone(),
+            premium_mode_pinned: self.premium_mode_pinned.clone(),
             user_turn_count: self.user_turn_count.clone(),
             created_at: self.created_at,
         };
@@ -1008,6 +1069,7 @@ impl Provider for CopilotApiProvider {
             init_ready: self.init_ready.clone(),
             init_done: self.init_done.clone(),
             premium_mode: self.premium_mode.clone(),
+            premium_mode_pinned: self.premium_mode_pinned.clone(),
             user_turn_count: self.user_turn_count.clone(),
             created_at: self.created_at,
         })
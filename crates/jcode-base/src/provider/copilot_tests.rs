@@ -13,6 +13,7 @@ fn make_test_provider(fetched: Vec<String>) -> CopilotApiProvider {
         init_ready: Arc::new(tokio::sync::Notify::new()),
         init_done: Arc::new(std::sync::atomic::AtomicBool::new(true)),
         premium_mode: Arc::new(std::sync::atomic::AtomicU8::new(0)),
+        premium_mode_pinned: Arc::new(std::sync::atomic::AtomicBool::new(false)),
         user_turn_count: Arc::new(std::sync::atomic::AtomicU64::new(0)),
         created_at: std::time::Instant::now(),
     }
@@ -157,6 +158,30 @@ fn context_window_handles_dot_and_dash_names() {
     );
 }
 
+#[test]
+fn included_models_do_not_spend_premium_requests() {
+    assert!(!is_premium_model("gpt-4.1"));
+    assert!(!is_premium_model("GPT-5-mini"));
+    assert!(is_premium_model("claude-opus-4.6"));
+    assert!(is_premium_model("gpt-5.4"));
+}
+
+#[test]
+fn explicit_premium_mode_is_pinned() {
+    let provider = make_test_provider(Vec::new());
+    assert!(
+        !provider
+            .premium_mode_pinned
+            .load(std::sync::atomic::Ordering::Relaxed)
+    );
+    provider.set_premium_mode(PremiumMode::Normal);
+    assert!(
+        provider
+            .premium_mode_pinned
+            .load(std::sync::atomic::Ordering::Relaxed)
+    );
+}
+
 #[test]
 fn has_credentials_returns_bool() {
     let _ = CopilotApiProvider::has_credentials();
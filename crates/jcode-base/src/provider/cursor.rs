@@ -103,6 +103,26 @@ fn build_cli_prompt(system: &str, messages: &[Message]) -> String {
                 ContentBlock::OpenAICompaction { .. } => {
                     out.push_str("[openai native compaction]\n");
                 }
+                ContentBlock::ServerToolUse { name, input, .. } => {
+                    out.push_str("[server_tool_use ");
+                    out.push_str(name);
+                    out.push_str("] ");
+                    out.push_str(&input.to_string());
+                    out.push('\n');
+                }
+                ContentBlock::WebSearchResult {
+                    results,
+                    error_code,
+                    ..
+                } => {
+                    out.push_str("[web_search_result]\n");
+                    out.push_str(&crate::message::web_search_result_text(
+                        results,
+                        error_code.as_deref(),
+                    ));
+                    out.push('\n');
+                }
+                ContentBlock::Citations { .. } => {}
             }
         }
         out.push('\n');
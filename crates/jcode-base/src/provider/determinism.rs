@@ -0,0 +1,73 @@
+//! `[provider] deterministic`: pinned model and sampling for reproducible
+//! runs. Each provider applies the parts its API accepts and reports the rest
+//! through [`super::Provider::deterministic_params`].
+
+use crate::message::DeterminismParams;
+use jcode_provider_core::model_id::{
+    has_date_suffix, pin_dated_snapshot, strip_long_context_suffix,
+};
+use std::collections::HashSet;
+use std::sync::{LazyLock, Mutex};
+
+/// Warnings already logged, so a long run warns once per provider and model.
+static WARNED: LazyLock<Mutex<HashSet<String>>> = LazyLock::new(|| Mutex::new(HashSet::new()));
+
+/// Whether `[provider] deterministic` is on.
+pub fn enabled() -> bool {
+    crate::config::config().provider.deterministic
+}
+
+/// The seed to send, when deterministic mode is on.
+pub fn seed() -> Option<u64> {
+    let provider = &crate::config::config().provider;
+    provider
+        .deterministic
+        .then_some(provider.deterministic_seed)
+}
+
+/// Starting point for a provider's params: `model` pinned to its dated
+/// snapshot, with nothing else applied yet.
+pub(crate) fn params_for(model: &str) -> DeterminismParams {
+    let model = pin_dated_snapshot(model);
+    let unsupported = if has_date_suffix(strip_long_context_suffix(&model)) {
+        Vec::new()
+    } else {
+        vec!["dated model snapshot".to_string()]
+    };
+    DeterminismParams {
+        model,
+        unsupported,
+        ..DeterminismParams::default()
+    }
+}
+
+/// Log the parts of deterministic mode `provider` cannot honor, once per
+/// distinct warning.
+pub fn warn_unsupported(provider: &str, params: &DeterminismParams) {
+    let Some(warning) = params.warning(provider) else {
+        return;
+    };
+    let first = WARNED
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(warning.clone());
+    if first {
+        crate::logging::warn(&warning);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn params_for_pins_aliases_and_flags_undated_models() {
+        let pinned = params_for("claude-sonnet-4-5");
+        assert_eq!(pinned.model, "claude-sonnet-4-5-20250929");
+        assert!(pinned.unsupported.is_empty());
+
+        let undated = params_for("some-local-model");
+        assert_eq!(undated.model, "some-local-model");
+        assert_eq!(undated.unsupported, vec!["dated model snapshot"]);
+    }
+}
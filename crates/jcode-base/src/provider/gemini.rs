@@ -1028,6 +1028,7 @@ fn is_vpc_sc_error(err: &anyhow::Error) -> bool {
 fn gemini_http_client() -> reqwest::Client {
     reqwest::Client::builder()
         .user_agent("jcode/1.0 (gemini)")
+        .dns_resolver(jcode_provider_core::egress::resolver())
         .http1_only()
         .connect_timeout(Duration::from_secs(20))
         .timeout(Duration::from_secs(90))
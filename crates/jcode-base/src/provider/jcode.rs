@@ -130,6 +130,10 @@ impl Provider for JcodeProvider {
         self.inner.preferred_provider()
     }
 
+    fn pinned_provider(&self) -> Option<String> {
+        self.inner.pinned_provider()
+    }
+
     fn model_routes(&self) -> Vec<ModelRoute> {
         self.ensure_runtime_mode();
         filtered_model_routes(self.inner.model_routes())
@@ -187,6 +191,10 @@ impl Provider for JcodeProvider {
         self.inner.invalidate_credentials().await;
     }
 
+    fn restore_background_response(&self, response_id: &str) {
+        self.inner.restore_background_response(response_id);
+    }
+
     fn set_premium_mode(&self, mode: copilot::PremiumMode) {
         self.inner.set_premium_mode(mode);
     }
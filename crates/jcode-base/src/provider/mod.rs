@@ -9,6 +9,7 @@ mod catalog_routes;
 pub mod claude;
 pub mod copilot;
 pub mod cursor;
+pub mod determinism;
 mod dispatch;
 mod failover;
 mod fingerprint;
@@ -21,12 +22,14 @@ pub mod openai;
 pub mod openai_request;
 pub mod openrouter;
 pub mod pricing;
+mod rate_limit;
 mod registry;
 mod route_builders;
 mod routing;
 mod selection;
 mod startup;
 mod state;
+mod stream_watchdog;
 
 use crate::auth;
 use crate::message::{Message, ToolDefinition};
@@ -56,12 +59,13 @@ pub use jcode_provider_core::{
     ModelCapabilities, ModelCatalogRefreshSummary, ModelRoute, ModelRouteApiMethod,
     NativeCompactionResult, NativeToolResult, NativeToolResultSender, PremiumMode, Provider,
     RouteBillingKind, RouteCheapnessEstimate, RouteCostConfidence, RouteCostSource, RouteSelection,
-    RuntimeKey, dedupe_model_routes, explicit_model_provider_prefix, fresh_transport_client,
-    model_name_for_provider, normalize_copilot_model_name, provider_from_model_key,
-    shared_http_client, summarize_model_catalog_refresh,
+    RuntimeKey, SimpleCompletion, dedupe_model_routes, explicit_model_provider_prefix,
+    fresh_transport_client, model_name_for_provider, normalize_copilot_model_name,
+    provider_from_model_key, shared_http_client, summarize_model_catalog_refresh,
 };
 pub use jcode_provider_core::{ProviderFailoverPrompt, parse_failover_prompt_message};
 pub use jcode_provider_core::{model_route_provider_labels_match, pick_next_fallback_route};
+pub use rate_limit::{MAX_RATE_LIMIT_WAITS, in_turn_rate_limit_wait, rate_limit_source_key};
 pub use route_builders::{
     build_anthropic_oauth_route, build_copilot_route, build_openai_api_key_route,
     build_openai_oauth_route, build_openrouter_auto_route, build_openrouter_endpoint_route,
@@ -72,6 +76,9 @@ pub(crate) use routing::{
     anthropic_api_key_route_availability, anthropic_oauth_route_availability,
     is_transient_transport_error, should_eager_detect_copilot_tier,
 };
+pub use stream_watchdog::{
+    STREAM_IDLE_TIMEOUT, StreamWatchdog, is_stream_idle_timeout, watch_stream,
+};
 
 /// Process-wide handle to the live agent provider.
 ///
@@ -270,6 +277,73 @@ pub fn set_model_with_auth_refresh(provider: &dyn Provider, model: &str) -> Resu
     }
 }
 
+/// The route `provider` is running on right now.
+pub fn current_model_route(
+    provider: &dyn Provider,
+    api_method: Option<&str>,
+) -> crate::session::SessionModelRoute {
+    crate::session::SessionModelRoute {
+        model: provider.model(),
+        provider: provider.pinned_provider(),
+        api_method: api_method.map(str::to_string),
+    }
+}
+
+/// Re-apply a session's stored model route (model, provider pin, API method)
+/// to `provider`. Returns the fallback when the route could not be restored
+/// as-is, with the account-availability reason when one is known.
+pub fn restore_session_model_route(
+    provider: &dyn Provider,
+    session: &crate::session::Session,
+) -> Option<crate::session::ModelRouteFallback> {
+    let model = session.model.as_deref()?;
+    // Sessions saved before routes were recorded (or whose model was set
+    // without one) only know the model and API method.
+    let requested = session
+        .model_route
+        .clone()
+        .filter(|route| route.model == model)
+        .unwrap_or_else(|| crate::session::SessionModelRoute {
+            model: model.to_string(),
+            provider: None,
+            api_method: session.route_api_method.clone(),
+        });
+    let api_method = requested
+        .api_method
+        .as_deref()
+        .or(session.route_api_method.as_deref());
+    let model_request = MultiProvider::model_switch_request_for_session_route(
+        &requested.model_spec(),
+        session.provider_key.as_deref(),
+        api_method,
+    );
+    let error = set_model_with_auth_refresh(provider, &model_request).err();
+    if let Some(e) = &error {
+        crate::logging::error(&format!(
+            "Failed to restore session model '{}' via '{}': {}",
+            requested.model_spec(),
+            model_request,
+            e
+        ));
+    }
+
+    let effective = current_model_route(provider, api_method);
+    let pin_lost = requested.provider.is_some() && effective.provider != requested.provider;
+    if error.is_none() && effective.model == requested.model && !pin_lost {
+        return None;
+    }
+    let availability = model_availability_for_account(&requested.model);
+    let reason = (availability.state == AccountModelAvailabilityState::Unavailable)
+        .then_some(availability.reason)
+        .flatten()
+        .or_else(|| error.map(|e| e.to_string()));
+    Some(crate::session::ModelRouteFallback {
+        requested,
+        effective,
+        reason,
+    })
+}
+
 use self::dispatch::CompletionMode;
 pub use self::models::{
     AccountModelAvailability, AccountModelAvailabilityState, AnthropicModelCatalog,
@@ -671,6 +745,7 @@ impl MultiProvider {
                         self.set_active_provider(candidate);
                         let from_label = Self::provider_label(active);
                         let to_label = Self::provider_label(candidate);
+                        crate::metrics::record_provider_fallback(from_label, to_label);
                         crate::logging::info(&format!(
                             "{}: switched from {} to {}",
                             mode.switch_log_prefix(),
@@ -1516,6 +1591,10 @@ impl Provider for MultiProvider {
         }
     }
 
+    fn usage_source_key(&self) -> Option<String> {
+        Some(self.activity_source_key(self.active_provider()))
+    }
+
     fn active_resolved_credential(&self) -> Option<jcode_provider_core::ResolvedCredential> {
         use jcode_provider_core::ResolvedCredential;
         match self.active_provider() {
@@ -1616,6 +1695,27 @@ impl Provider for MultiProvider {
         }
     }
 
+    fn deterministic_params(&self, seed: u64) -> crate::message::DeterminismParams {
+        let params = match self.active_provider() {
+            ActiveProvider::Claude => self
+                .anthropic_provider()
+                .map(|provider| provider.deterministic_params(seed)),
+            ActiveProvider::OpenAI => self
+                .openai_provider()
+                .map(|provider| provider.deterministic_params(seed)),
+            ActiveProvider::OpenRouter => self
+                .active_openrouter_execution_provider()
+                .map(|provider| provider.deterministic_params(seed)),
+            // The remaining backends take no sampling overrides.
+            ActiveProvider::Copilot
+            | ActiveProvider::Antigravity
+            | ActiveProvider::Gemini
+            | ActiveProvider::Cursor
+            | ActiveProvider::Bedrock => None,
+        };
+        params.unwrap_or_else(|| crate::message::DeterminismParams::unsupported(self.model()))
+    }
+
     fn set_model(&self, model: &str) -> Result<()> {
         self.spawn_anthropic_catalog_refresh_if_needed();
         self.spawn_openai_catalog_refresh_if_needed();
@@ -1816,6 +1916,15 @@ impl Provider for MultiProvider {
         None
     }
 
+    fn pinned_provider(&self) -> Option<String> {
+        if matches!(self.active_provider(), ActiveProvider::OpenRouter) {
+            return self
+                .openrouter_provider()
+                .and_then(|openrouter| openrouter.explicit_provider_pin_for_current_model());
+        }
+        None
+    }
+
     fn model_routes(&self) -> Vec<ModelRoute> {
         self.fresh_routes_memo_entry().routes
     }
@@ -2340,6 +2449,14 @@ impl Provider for MultiProvider {
         }
     }
 
+    fn restore_background_response(&self, response_id: &str) {
+        if matches!(self.active_provider(), ActiveProvider::OpenAI)
+            && let Some(openai) = self.openai_provider()
+        {
+            openai.restore_background_response(response_id);
+        }
+    }
+
     fn set_premium_mode(&self, mode: PremiumMode) {
         if let Some(copilot) = self.copilot_provider() {
             copilot.set_premium_mode(mode);
@@ -1004,11 +1004,13 @@ pub fn context_limit_for_model_with_provider(
 
 pub fn resolve_model_capabilities(model: &str, provider_hint: Option<&str>) -> ModelCapabilities {
     let provider = provider_for_model_with_hint(model, provider_hint).map(str::to_string);
-    let context_window = context_limit_for_model_with_provider(model, provider_hint);
-    ModelCapabilities {
-        provider,
-        context_window,
+    if provider.as_deref() == Some("bedrock") {
+        return crate::provider::bedrock::BedrockProvider::model_capabilities(model);
     }
+    let context_window = context_limit_for_model_with_provider(model, provider_hint);
+    let mut capabilities = ModelCapabilities::assumed(provider, context_window);
+    capabilities.apply_known_model_features(model);
+    capabilities
 }
 
 /// Detect which provider a model belongs to
@@ -13,6 +13,7 @@ use reqwest::{Client, StatusCode};
 use serde_json::Value;
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, LazyLock, RwLock as StdRwLock};
 use std::time::{Duration, Instant};
 use tokio::net::TcpStream;
@@ -102,6 +103,10 @@ static WEBSOCKET_COOLDOWNS: LazyLock<Arc<RwLock<HashMap<String, Instant>>>> =
     LazyLock::new(|| Arc::new(RwLock::new(HashMap::new())));
 static WEBSOCKET_FAILURE_STREAKS: LazyLock<Arc<RwLock<HashMap<String, u32>>>> =
     LazyLock::new(|| Arc::new(RwLock::new(HashMap::new())));
+/// Models whose account rejected Responses background mode. Requests for them
+/// stay on plain streaming for the rest of the process.
+static BACKGROUND_UNAVAILABLE_MODELS: LazyLock<StdRwLock<HashSet<String>>> =
+    LazyLock::new(|| StdRwLock::new(HashSet::new()));
 
 #[expect(
     clippy::upper_case_acronyms,
@@ -145,6 +150,9 @@ impl OpenAITransportMode {
 #[derive(Debug)]
 enum OpenAIStreamFailure {
     FallbackToHttps(anyhow::Error),
+    /// The request asked for background mode and the model or account does
+    /// not offer it; resend it as a plain stream.
+    BackgroundUnavailable(anyhow::Error),
     Other(anyhow::Error),
 }
 
@@ -541,6 +549,12 @@ pub struct OpenAIProvider {
     websocket_failure_streaks: Arc<RwLock<HashMap<String, u32>>>,
     /// Persistent WebSocket connection for incremental continuation
     persistent_ws: Arc<Mutex<Option<PersistentWsState>>>,
+    /// Background response from a restored session, re-attached to by the
+    /// next request if it is still generating.
+    pending_background_response: Arc<std::sync::Mutex<Option<String>>>,
+    /// Set once a turn needed a reconnect, so later turns start in background
+    /// mode (and over HTTPS, which can resume it).
+    background_preferred: Arc<AtomicBool>,
 }
 
 impl OpenAIProvider {
@@ -646,6 +660,8 @@ impl OpenAIProvider {
             websocket_cooldowns: Arc::clone(&WEBSOCKET_COOLDOWNS),
             websocket_failure_streaks: Arc::clone(&WEBSOCKET_FAILURE_STREAKS),
             persistent_ws: Arc::new(Mutex::new(None)),
+            pending_background_response: Arc::new(std::sync::Mutex::new(None)),
+            background_preferred: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -1067,6 +1083,37 @@ impl OpenAIProvider {
         request
     }
 
+    /// Deterministic-mode settings for a Responses request. Reasoning models
+    /// and the ChatGPT backend reject sampling parameters, and the Responses
+    /// API takes no seed.
+    fn deterministic_params_for(
+        model_id: &str,
+        is_chatgpt_mode: bool,
+        reasoning_effort: Option<&str>,
+    ) -> crate::message::DeterminismParams {
+        let mut params = crate::provider::determinism::params_for(model_id);
+        if is_chatgpt_mode || reasoning_effort.is_some() {
+            params
+                .unsupported
+                .extend(["temperature".to_string(), "top_p".to_string()]);
+        } else {
+            params.temperature = Some(0.0);
+            params.top_p = Some(1.0);
+        }
+        params.unsupported.push("seed".to_string());
+        params
+    }
+
+    fn apply_deterministic_params(request: &mut Value, params: &crate::message::DeterminismParams) {
+        request["model"] = serde_json::json!(params.model);
+        if let Some(temperature) = params.temperature {
+            request["temperature"] = serde_json::json!(temperature);
+        }
+        if let Some(top_p) = params.top_p {
+            request["top_p"] = serde_json::json!(top_p);
+        }
+    }
+
     async fn model_id(&self) -> String {
         let current = self.model.read().await.clone();
         let availability = crate::provider::model_availability_for_account(&current);
@@ -1146,7 +1193,7 @@ mod stream;
 
 use self::openai_stream_runtime::{PersistentWsResult, is_retryable_error, openai_access_token};
 
-use self::stream::{OpenAIResponsesStream, parse_openai_response_event};
+use self::stream::{OpenAIResponsesStream, ResponseStreamCursor, parse_openai_response_event};
 #[cfg(test)]
 use self::stream::{handle_openai_output_item, parse_text_wrapped_tool_call};
 
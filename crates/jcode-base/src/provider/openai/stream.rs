@@ -1,5 +1,5 @@
 pub(super) use jcode_provider_openai::stream::{
-    OpenAIResponsesStream, parse_openai_response_event,
+    OpenAIResponsesStream, ResponseStreamCursor, parse_openai_response_event,
 };
 
 #[cfg(test)]
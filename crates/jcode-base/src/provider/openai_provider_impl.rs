@@ -1,5 +1,7 @@
 use super::openai_stream_runtime::{
-    stream_response, stream_response_websocket_persistent, try_persistent_ws_continuation,
+    background_mode_available, background_response_in_progress, record_background_unavailable,
+    request_uses_background_mode, stream_response, stream_response_websocket_persistent,
+    try_persistent_ws_continuation,
 };
 use super::*;
 
@@ -35,7 +37,7 @@ impl Provider for OpenAIProvider {
             .unwrap_or_else(|poisoned| poisoned.into_inner().clone());
         let native_compaction_threshold =
             self.native_compaction_threshold_for_context_window(self.context_window());
-        let request = Self::build_response_request(
+        let mut request = Self::build_response_request(
             &model_id,
             instructions,
             &input,
@@ -48,6 +50,55 @@ impl Provider for OpenAIProvider {
             self.prompt_cache_retention.as_deref(),
             native_compaction_threshold,
         );
+        if crate::provider::determinism::enabled() {
+            let params = Self::deterministic_params_for(
+                &model_id,
+                is_chatgpt_mode,
+                api_reasoning_effort.as_deref(),
+            );
+            crate::provider::determinism::warn_unsupported(self.name(), &params);
+            Self::apply_deterministic_params(&mut request, &params);
+        }
+        // Background mode lets a dropped HTTPS stream resume where it stopped
+        // instead of generating the response again. The ChatGPT backend does
+        // not offer it. Turns start in it when configured, or once a turn on
+        // this provider has needed a reconnect.
+        let background_available = !is_chatgpt_mode && background_mode_available(&model_id);
+        let background_after_reconnect = background_available
+            && crate::config::config()
+                .provider
+                .openai_background_after_reconnect;
+        let background_preferred = Arc::clone(&self.background_preferred);
+        if background_available
+            && (crate::config::config().provider.openai_resumable_streams
+                || background_preferred.load(Ordering::Relaxed))
+        {
+            request["background"] = serde_json::json!(true);
+            request["store"] = serde_json::json!(true);
+        }
+        // A restored session whose last turn was still generating server-side
+        // streams that response instead of generating the turn again.
+        let pending_background_response = self
+            .pending_background_response
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take();
+        let reattach_response_id = match pending_background_response {
+            Some(response_id)
+                if background_available
+                    && background_response_in_progress(
+                        &self.client,
+                        &self.credentials,
+                        &response_id,
+                    )
+                    .await =>
+            {
+                request["background"] = serde_json::json!(true);
+                request["store"] = serde_json::json!(true);
+                Some(response_id)
+            }
+            _ => None,
+        };
 
         // --- Persistent WebSocket continuation path ---
         // Try to reuse an existing WebSocket connection with previous_response_id
@@ -58,11 +109,16 @@ impl Provider for OpenAIProvider {
             .try_read()
             .map(|g| *g)
             .unwrap_or(OpenAITransportMode::HTTPS);
-        let use_websocket_transport = match transport_mode_snapshot {
-            OpenAITransportMode::HTTPS => false,
-            OpenAITransportMode::WebSocket => true,
-            OpenAITransportMode::Auto => Self::should_prefer_websocket(&model_id),
-        };
+        let use_websocket_transport = reattach_response_id.is_none()
+            && match transport_mode_snapshot {
+                OpenAITransportMode::HTTPS => false,
+                OpenAITransportMode::WebSocket => true,
+                // Only HTTPS can resume a background response.
+                OpenAITransportMode::Auto => {
+                    Self::should_prefer_websocket(&model_id)
+                        && !background_preferred.load(Ordering::Relaxed)
+                }
+            };
         let request_tools = request
             .get("tools")
             .cloned()
@@ -283,11 +339,19 @@ impl Provider for OpenAIProvider {
 
                 // Normal path: fresh connection with full input (with retry logic)
                 let mut last_error = None;
-                let mut force_https_for_request = false;
+                let mut force_https_for_request = reattach_response_id.is_some();
                 let mut skip_backoff_once = false;
+                let mut silent_retry_once = false;
+                // Progress through the current HTTPS response, shared across
+                // attempts so a background-mode retry resumes instead of
+                // restarting. A re-attach starts from the restored response.
+                let stream_cursor = Arc::new(std::sync::Mutex::new(ResponseStreamCursor {
+                    response_id: reattach_response_id.clone(),
+                    ..ResponseStreamCursor::default()
+                }));
 
                 for attempt in 0..MAX_RETRIES {
-                    if attempt > 0 {
+                    if attempt > 0 && !silent_retry_once {
                         emit_connection_phase(
                             &tx,
                             crate::message::ConnectionPhase::Retrying {
@@ -310,6 +374,7 @@ impl Provider for OpenAIProvider {
                         ));
                     }
                     skip_backoff_once = false;
+                    silent_retry_once = false;
 
                     let transport = if force_https_for_request {
                         OpenAITransport::HTTPS
@@ -404,7 +469,10 @@ impl Provider for OpenAIProvider {
                             attempt_client,
                             Arc::clone(&credentials),
                             request.clone(),
-                            if force_https_for_request {
+                            Arc::clone(&stream_cursor),
+                            if attempt == 0 && reattach_response_id.is_some() {
+                                "https re-attach".to_string()
+                            } else if force_https_for_request {
                                 let reason = last_error
                                     .as_ref()
                                     .map(|error: &anyhow::Error| {
@@ -522,6 +590,30 @@ impl Provider for OpenAIProvider {
                             last_error = Some(error);
                             continue;
                         }
+                        Err(OpenAIStreamFailure::BackgroundUnavailable(error)) => {
+                            log_openai_stream_lifecycle(
+                                crate::logging::LogLevel::Info,
+                                "background_unavailable",
+                                vec![
+                                    ("model", model_for_transport.clone()),
+                                    ("attempt", (attempt + 1).to_string()),
+                                    ("error", error.to_string()),
+                                ],
+                            );
+                            record_background_unavailable(&model_for_transport);
+                            if let Some(obj) = request.as_object_mut() {
+                                obj.remove("background");
+                                obj.insert("store".to_string(), serde_json::json!(false));
+                            }
+                            *stream_cursor
+                                .lock()
+                                .unwrap_or_else(|poisoned| poisoned.into_inner()) =
+                                ResponseStreamCursor::default();
+                            skip_backoff_once = true;
+                            silent_retry_once = true;
+                            last_error = Some(error);
+                            continue;
+                        }
                         Err(OpenAIStreamFailure::Other(error)) => {
                             let elapsed_ms = attempt_started.elapsed().as_millis();
                             // Full anyhow chain ({:#}) so a send-level transport
@@ -529,18 +621,68 @@ impl Provider for OpenAIProvider {
                             // request to OpenAI API")` (e.g. TLS BadRecordMac) is
                             // visible to the retry classifier.
                             let error_str = format!("{error:#}").to_lowercase();
+                            // Rate limited with a server-advertised wait: hand
+                            // it to the agent, which sleeps it out and resends,
+                            // instead of burning quick retries inside the window.
+                            if !saw_output
+                                && let Some(event) =
+                                    crate::provider::rate_limit::rate_limited_stream_event(&error)
+                            {
+                                log_openai_stream_lifecycle(
+                                    crate::logging::LogLevel::Warn,
+                                    "rate_limited",
+                                    vec![
+                                        ("model", model_for_transport.clone()),
+                                        ("attempt", (attempt + 1).to_string()),
+                                        ("error", error.to_string()),
+                                    ],
+                                );
+                                let _ = tx.send(Ok(event)).await;
+                                return;
+                            }
                             if is_retryable_error(&error_str) && attempt + 1 < MAX_RETRIES {
-                                if saw_output {
-                                    // Partial output already reached the
-                                    // consumer; roll it back so the retried
-                                    // response replays cleanly instead of
-                                    // duplicating.
-                                    let _ = tx
-                                        .send(Ok(StreamEvent::RetryRollback {
-                                            attempt: attempt + 2,
-                                            max: MAX_RETRIES,
-                                        }))
-                                        .await;
+                                let resume = !use_websocket
+                                    && request_uses_background_mode(&request)
+                                    && stream_cursor
+                                        .lock()
+                                        .unwrap_or_else(|poisoned| poisoned.into_inner())
+                                        .resume_point()
+                                        .is_some();
+                                if resume {
+                                    // The next attempt picks the same response
+                                    // up after its last event, so the partial
+                                    // output stays. Only HTTPS can resume it.
+                                    force_https_for_request = true;
+                                } else {
+                                    *stream_cursor
+                                        .lock()
+                                        .unwrap_or_else(|poisoned| poisoned.into_inner()) =
+                                        ResponseStreamCursor::default();
+                                    if saw_output {
+                                        // Partial output already reached the
+                                        // consumer; roll it back so the retried
+                                        // response replays cleanly instead of
+                                        // duplicating.
+                                        let _ = tx
+                                            .send(Ok(StreamEvent::RetryRollback {
+                                                attempt: attempt + 2,
+                                                max: MAX_RETRIES,
+                                            }))
+                                            .await;
+                                    }
+                                    if background_after_reconnect
+                                        && !request_uses_background_mode(&request)
+                                    {
+                                        // A turn that outlived its connection
+                                        // once may again: resend it in
+                                        // background mode so the next drop
+                                        // resumes, and start later turns that
+                                        // way too.
+                                        request["background"] = serde_json::json!(true);
+                                        request["store"] = serde_json::json!(true);
+                                        force_https_for_request = true;
+                                        background_preferred.store(true, Ordering::Relaxed);
+                                    }
                                 }
                                 log_openai_stream_lifecycle(
                                     crate::logging::LogLevel::Warn,
@@ -550,6 +692,7 @@ impl Provider for OpenAIProvider {
                                         ("attempt", (attempt + 1).to_string()),
                                         ("next_attempt", (attempt + 2).to_string()),
                                         ("transport", transport_label.to_string()),
+                                        ("resume", resume.to_string()),
                                         ("error", error.to_string()),
                                         ("elapsed_ms", elapsed_ms.to_string()),
                                     ],
@@ -643,6 +786,24 @@ impl Provider for OpenAIProvider {
         true
     }
 
+    fn deterministic_params(&self, _seed: u64) -> crate::message::DeterminismParams {
+        let is_chatgpt_mode = self
+            .credentials
+            .try_read()
+            .map(|credentials| Self::is_chatgpt_mode(&credentials))
+            .unwrap_or(false);
+        let reasoning_effort = self
+            .reasoning_effort
+            .read()
+            .map(|guard| guard.clone())
+            .unwrap_or_else(|poisoned| poisoned.into_inner().clone());
+        Self::deterministic_params_for(
+            &self.model(),
+            is_chatgpt_mode,
+            Self::api_reasoning_effort(reasoning_effort.as_deref()).as_deref(),
+        )
+    }
+
     fn set_model(&self, model: &str) -> Result<()> {
         if !crate::provider::known_openai_model_ids()
             .iter()
@@ -956,9 +1117,20 @@ impl Provider for OpenAIProvider {
             websocket_cooldowns: Arc::clone(&self.websocket_cooldowns),
             websocket_failure_streaks: Arc::clone(&self.websocket_failure_streaks),
             persistent_ws: Arc::new(Mutex::new(None)),
+            pending_background_response: Arc::new(std::sync::Mutex::new(None)),
+            background_preferred: Arc::new(AtomicBool::new(
+                self.background_preferred.load(Ordering::Relaxed),
+            )),
         })
     }
 
+    fn restore_background_response(&self, response_id: &str) {
+        *self
+            .pending_background_response
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(response_id.to_string());
+    }
+
     async fn invalidate_credentials(&self) {
         let mode = *self.credential_mode.read().await;
         if let Ok(credentials) = mode.load_credentials() {
@@ -62,11 +62,104 @@ pub(super) async fn force_refresh_openai_token(
     Ok(new_access_token)
 }
 
-/// Stream the response from OpenAI API
+/// Whether `request` runs in Responses background mode, whose streams can be
+/// resumed from a sequence number after a dropped connection.
+pub(super) fn request_uses_background_mode(request: &Value) -> bool {
+    request.get("background").and_then(Value::as_bool) == Some(true)
+}
+
+/// Whether requests for `model` may use background mode, i.e. it has not been
+/// rejected for this account.
+pub(super) fn background_mode_available(model: &str) -> bool {
+    !BACKGROUND_UNAVAILABLE_MODELS
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .contains(model)
+}
+
+pub(super) fn record_background_unavailable(model: &str) {
+    BACKGROUND_UNAVAILABLE_MODELS
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(model.to_string());
+}
+
+/// Detect an API rejection of `background`/`store` itself (unsupported model,
+/// or an account that cannot store responses), as opposed to any other 4xx.
+pub(super) fn is_background_unavailable_error(status: StatusCode, body: &str) -> bool {
+    if !matches!(
+        status,
+        StatusCode::BAD_REQUEST | StatusCode::FORBIDDEN | StatusCode::UNPROCESSABLE_ENTITY
+    ) {
+        return false;
+    }
+    let lower = body.to_ascii_lowercase();
+    let mentions_background = lower.contains("background")
+        || lower.contains("'store'")
+        || lower.contains("\"store\"")
+        || lower.contains("zero data retention");
+    let rejected = lower.contains("not supported")
+        || lower.contains("unsupported")
+        || lower.contains("not available")
+        || lower.contains("not enabled")
+        || lower.contains("not allowed")
+        || lower.contains("does not have access");
+    mentions_background && rejected
+}
+
+/// Whether the background response `response_id` is still queued or
+/// generating, so a request can stream it instead of creating a new one.
+pub(super) async fn background_response_in_progress(
+    client: &Client,
+    credentials: &Arc<RwLock<CodexCredentials>>,
+    response_id: &str,
+) -> bool {
+    let status = async {
+        let access_token = openai_access_token(credentials).await?;
+        let url = OpenAIProvider::responses_url(&*credentials.read().await);
+        let response = client
+            .get(format!("{}/{}", url, response_id))
+            .header("Authorization", format!("Bearer {}", access_token))
+            .timeout(Duration::from_secs(10))
+            .send()
+            .await?
+            .error_for_status()?;
+        let body: Value = response.json().await?;
+        anyhow::Ok(
+            body.get("status")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+        )
+    }
+    .await;
+    match status {
+        Ok(status) => {
+            crate::logging::info(&format!(
+                "OpenAI background response {} from the restored session is {}",
+                response_id, status
+            ));
+            matches!(status.as_str(), "queued" | "in_progress")
+        }
+        Err(err) => {
+            crate::logging::warn(&format!(
+                "Could not check OpenAI background response {}: {err:#}",
+                response_id
+            ));
+            false
+        }
+    }
+}
+
+/// Stream the response from OpenAI API. A background-mode `request` whose
+/// `cursor` already names the response resumes after the last event seen
+/// (or from the start, when re-attaching after a restart) instead of creating
+/// the response again.
 pub(super) async fn stream_response(
     client: Client,
     credentials: Arc<RwLock<CodexCredentials>>,
     request: Value,
+    cursor: Arc<std::sync::Mutex<ResponseStreamCursor>>,
     initial_status_detail: String,
     tx: mpsc::Sender<Result<StreamEvent>>,
 ) -> Result<(), OpenAIStreamFailure> {
@@ -95,8 +188,43 @@ pub(super) async fn stream_response(
     let account_id = creds.account_id.clone();
     drop(creds);
 
-    let mut builder = client
-        .post(&url)
+    let background = request_uses_background_mode(&request);
+    let resume_from = if background {
+        let cursor = cursor
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        cursor
+            .response_id
+            .clone()
+            .map(|response_id| (response_id, cursor.sequence_number))
+    } else {
+        None
+    };
+    let builder = match &resume_from {
+        Some((response_id, sequence_number)) => {
+            log_openai_stream_lifecycle(
+                crate::logging::LogLevel::Info,
+                "https_resume",
+                vec![
+                    ("model", request_model.clone()),
+                    ("response_id", response_id.clone()),
+                    (
+                        "starting_after",
+                        sequence_number
+                            .map(|sequence_number| sequence_number.to_string())
+                            .unwrap_or_else(|| "start".to_string()),
+                    ),
+                ],
+            );
+            let mut query = vec![("stream", "true".to_string())];
+            if let Some(sequence_number) = sequence_number {
+                query.push(("starting_after", sequence_number.to_string()));
+            }
+            client.get(format!("{}/{}", url, response_id)).query(&query)
+        }
+        None => client.post(&url).json(&request),
+    };
+    let mut builder = builder
         .header("Authorization", format!("Bearer {}", access_token))
         .header("Content-Type", "application/json");
 
@@ -111,7 +239,6 @@ pub(super) async fn stream_response(
     let connect_start = std::time::Instant::now();
 
     let response = builder
-        .json(&request)
         .send()
         .await
         .context("Failed to send request to OpenAI API")
@@ -141,11 +268,11 @@ pub(super) async fn stream_response(
 
     if !response.status().is_success() {
         let status = response.status();
-        let retry_after = response
-            .headers()
-            .get("retry-after")
-            .and_then(|v| v.to_str().ok())
-            .and_then(|s| s.parse::<u64>().ok());
+        let retry_after = if status == StatusCode::TOO_MANY_REQUESTS {
+            crate::provider::rate_limit::retry_after_secs_from_headers(response.headers())
+        } else {
+            None
+        };
 
         let body = crate::util::http_error_body(response, "HTTP error").await;
         log_openai_stream_lifecycle(
@@ -168,6 +295,14 @@ pub(super) async fn stream_response(
             ],
         );
 
+        if background && resume_from.is_none() && is_background_unavailable_error(status, &body) {
+            return Err(OpenAIStreamFailure::BackgroundUnavailable(anyhow::anyhow!(
+                "OpenAI background mode unavailable {}: {}",
+                status,
+                body
+            )));
+        }
+
         if let Some(reason) = classify_unavailable_model_error(status, &body)
             && let Some(model_name) = request.get("model").and_then(|m| m.as_str())
         {
@@ -216,15 +351,23 @@ pub(super) async fn stream_response(
         }
 
         // For rate limits, include retry info in the error
-        let msg = if status == StatusCode::TOO_MANY_REQUESTS {
+        if status == StatusCode::TOO_MANY_REQUESTS {
             let wait_info = retry_after
                 .map(|s| format!(" (retry after {}s)", s))
                 .unwrap_or_default();
-            format!("Rate limited{}: {}", wait_info, body)
-        } else {
-            format!("OpenAI API error {}: {}", status, body)
-        };
-        return Err(OpenAIStreamFailure::Other(anyhow::anyhow!("{}", msg)));
+            return Err(OpenAIStreamFailure::Other(
+                crate::provider::rate_limit::RateLimited {
+                    retry_after_secs: retry_after,
+                    message: format!("Rate limited{}: {}", wait_info, body),
+                }
+                .into(),
+            ));
+        }
+        return Err(OpenAIStreamFailure::Other(anyhow::anyhow!(
+            "OpenAI API error {}: {}",
+            status,
+            body
+        )));
     }
 
     emit_connection_phase(&tx, ConnectionPhase::WaitingForResponse).await;
@@ -236,8 +379,12 @@ pub(super) async fn stream_response(
         .await;
 
     // Stream the response
-    let mut stream = OpenAIResponsesStream::new(response.bytes_stream());
+    let mut stream =
+        OpenAIResponsesStream::new(response.bytes_stream()).with_cursor(Arc::clone(&cursor));
     let mut saw_message_end = false;
+    // Background responses are announced once their id is known, so the
+    // session can re-attach to them after a restart.
+    let mut announced_background = !background;
 
     use futures::StreamExt;
     while let Some(result) = stream.next().await {
@@ -246,6 +393,18 @@ pub(super) async fn stream_response(
                 if matches!(event, StreamEvent::MessageEnd { .. }) {
                     saw_message_end = true;
                 }
+                if !announced_background
+                    && let Some(response_id) = cursor
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner())
+                        .response_id
+                        .clone()
+                {
+                    announced_background = true;
+                    let _ = tx
+                        .send(Ok(StreamEvent::BackgroundResponse { response_id }))
+                        .await;
+                }
                 if let StreamEvent::Error { message, .. } = &event {
                     if let Some(model_name) = request.get("model").and_then(|m| m.as_str()) {
                         maybe_record_runtime_model_unavailable_from_stream_error(
@@ -587,6 +746,12 @@ pub(super) async fn try_persistent_ws_continuation(
     if let Some(reasoning) = request.get("reasoning") {
         continuation_request["reasoning"] = reasoning.clone();
     }
+    if let Some(temperature) = request.get("temperature") {
+        continuation_request["temperature"] = temperature.clone();
+    }
+    if let Some(top_p) = request.get("top_p") {
+        continuation_request["top_p"] = top_p.clone();
+    }
     if let Some(context_management) = request.get("context_management") {
         continuation_request["context_management"] = context_management.clone();
     }
@@ -929,6 +1094,18 @@ pub(super) async fn try_persistent_ws_continuation(
 
 /// Stream response via WebSocket, saving the connection for reuse.
 /// This replaces the old `stream_response_websocket` for the fresh-connection path.
+/// Apply the `[network]` egress policy to the websocket host. The websocket
+/// does not go through reqwest, so its resolver never sees this connection.
+/// A denial is not a transport failure: retrying over HTTPS would reach the
+/// same host.
+fn check_ws_egress(ws_url: &str) -> Result<(), OpenAIStreamFailure> {
+    let Some(host) = jcode_provider_core::egress::host_from_url(ws_url) else {
+        return Ok(());
+    };
+    jcode_provider_core::egress::check_host(&host)
+        .map_err(|denied| OpenAIStreamFailure::Other(anyhow::Error::new(denied)))
+}
+
 pub(super) async fn stream_response_websocket_persistent(
     credentials: Arc<RwLock<CodexCredentials>>,
     request: Value,
@@ -965,6 +1142,7 @@ pub(super) async fn stream_response_websocket_persistent(
     let creds = credentials.read().await;
     let is_chatgpt_mode = !creds.refresh_token.is_empty() || creds.id_token.is_some();
     let ws_url = OpenAIProvider::responses_ws_url(&creds);
+    check_ws_egress(&ws_url)?;
     let mut ws_request = ws_url.into_client_request().map_err(|err| {
         OpenAIStreamFailure::Other(anyhow::anyhow!(
             "Failed to build websocket request: {}",
@@ -1485,6 +1663,37 @@ pub(super) fn is_retryable_error(error_str: &str) -> bool {
 mod stream_runtime_tests {
     use super::*;
 
+    #[test]
+    fn websocket_host_is_checked_against_enforced_policy() {
+        use jcode_provider_core::egress::{self, EgressPolicy};
+
+        let previous = egress::policy();
+        egress::set_policy(EgressPolicy {
+            enforce: true,
+            allowed_hosts: vec!["*.openai.com".to_string()],
+        });
+        let denied = check_ws_egress("wss://ws-egress-test.invalid/v1/responses");
+        let allowed = check_ws_egress("wss://api.openai.com/v1/responses");
+        egress::set_policy(previous.unwrap_or_default());
+
+        match denied {
+            Err(OpenAIStreamFailure::Other(err)) => {
+                assert!(err.to_string().contains("ws-egress-test.invalid"), "{err}")
+            }
+            Err(OpenAIStreamFailure::FallbackToHttps(err)) => {
+                panic!("a denied host must not fall back to HTTPS: {err}")
+            }
+            _ => panic!("expected the websocket host to be denied"),
+        }
+        assert!(allowed.is_ok());
+        let observed = egress::observed_destinations();
+        let host = observed
+            .iter()
+            .find(|destination| destination.host == "ws-egress-test.invalid")
+            .expect("denied host is recorded");
+        assert_eq!(host.denied, 1);
+    }
+
     #[test]
     fn unauthorized_triggers_token_refresh() {
         assert!(should_refresh_token(StatusCode::UNAUTHORIZED, ""));
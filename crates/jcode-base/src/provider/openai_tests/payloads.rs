@@ -238,3 +238,36 @@ fn swarm_effort_maps_to_xhigh_for_api_and_is_accepted_by_normalize() {
     );
     assert_eq!(request["reasoning"]["effort"], serde_json::json!("xhigh"));
 }
+
+#[test]
+fn test_deterministic_params_pin_snapshot_and_sampling_when_accepted() {
+    let params = OpenAIProvider::deterministic_params_for("gpt-4.1", false, None);
+    assert_eq!(params.model, "gpt-4.1-2025-04-14");
+    assert_eq!(params.temperature, Some(0.0));
+    assert_eq!(params.top_p, Some(1.0));
+    assert_eq!(params.unsupported, vec!["seed"]);
+
+    let mut request = OpenAIProvider::build_response_request(
+        "gpt-4.1",
+        "system".to_string(),
+        &[],
+        &[],
+        false,
+        Some(DEFAULT_MAX_OUTPUT_TOKENS),
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+    OpenAIProvider::apply_deterministic_params(&mut request, &params);
+    assert_eq!(request["model"], serde_json::json!("gpt-4.1-2025-04-14"));
+    assert_eq!(request["temperature"], serde_json::json!(0.0));
+    assert_eq!(request["top_p"], serde_json::json!(1.0));
+
+    // Reasoning requests reject sampling parameters.
+    let reasoning = OpenAIProvider::deterministic_params_for("gpt-5", false, Some("low"));
+    assert_eq!(reasoning.model, "gpt-5-2025-08-07");
+    assert_eq!(reasoning.temperature, None);
+    assert_eq!(reasoning.unsupported, vec!["temperature", "top_p", "seed"]);
+}
@@ -465,3 +465,32 @@ async fn test_record_websocket_success_clears_normalized_keys() {
         "success should clear normalized failure streak entries"
     );
 }
+
+#[test]
+fn test_background_unavailable_error_detection() {
+    use openai_stream_runtime::{
+        background_mode_available, is_background_unavailable_error, record_background_unavailable,
+    };
+
+    assert!(is_background_unavailable_error(
+        StatusCode::BAD_REQUEST,
+        r#"{"error":{"message":"Background mode is not supported for this model.","param":"background"}}"#,
+    ));
+    assert!(is_background_unavailable_error(
+        StatusCode::FORBIDDEN,
+        r#"{"error":{"message":"'store' is not allowed for organizations with zero data retention."}}"#,
+    ));
+    assert!(!is_background_unavailable_error(
+        StatusCode::BAD_REQUEST,
+        r#"{"error":{"message":"Invalid value for 'input'."}}"#,
+    ));
+    assert!(!is_background_unavailable_error(
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "background worker unavailable",
+    ));
+
+    let model = "gpt-test-background-unavailable";
+    assert!(background_mode_available(model));
+    record_background_unavailable(model);
+    assert!(!background_mode_available(model));
+}
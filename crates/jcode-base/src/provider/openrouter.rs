@@ -1422,6 +1422,18 @@ impl OpenRouterProvider {
         }
     }
 
+    /// Deterministic-mode settings for a Chat Completions request, which
+    /// takes all three sampling parameters. Whether the upstream honored the
+    /// seed shows up in the `system_fingerprint` it reports back.
+    fn deterministic_params_for(model: &str, seed: u64) -> crate::message::DeterminismParams {
+        crate::message::DeterminismParams {
+            temperature: Some(0.0),
+            top_p: Some(1.0),
+            seed: Some(seed),
+            ..crate::provider::determinism::params_for(model)
+        }
+    }
+
     /// Detect providers that strictly enforce the OpenAI-compatible schema and
     /// reject the non-standard `reasoning_content` message field and top-level
     /// `thinking` request field. Mistral's API returns 422 "Extra inputs are
@@ -181,6 +181,15 @@ impl Provider for OpenRouterProvider {
             request["provider"] = obj;
         }
 
+        if let Some(seed) = crate::provider::determinism::seed() {
+            let params = Self::deterministic_params_for(&model, seed);
+            crate::provider::determinism::warn_unsupported(self.name(), &params);
+            request["model"] = serde_json::json!(params.model);
+            request["temperature"] = serde_json::json!(params.temperature);
+            request["top_p"] = serde_json::json!(params.top_p);
+            request["seed"] = serde_json::json!(params.seed);
+        }
+
         // Merge user-configured extra request-body fields last so they can
         // satisfy non-standard backend requirements (e.g. NVIDIA NIM
         // DeepSeek-V4 `chat_template_kwargs`) and intentionally override any
@@ -285,6 +294,10 @@ impl Provider for OpenRouterProvider {
             .unwrap_or_else(|_| DEFAULT_MODEL.to_string())
     }
 
+    fn deterministic_params(&self, seed: u64) -> crate::message::DeterminismParams {
+        Self::deterministic_params_for(&self.model(), seed)
+    }
+
     fn supports_image_input(&self) -> bool {
         if Self::profile_rejects_image_input(self.profile_id.as_deref()) {
             return false;
@@ -612,6 +625,10 @@ impl Provider for OpenRouterProvider {
         self.preferred_provider()
     }
 
+    fn pinned_provider(&self) -> Option<String> {
+        self.explicit_provider_pin_for_current_model()
+    }
+
     fn context_window(&self) -> usize {
         // Defensive: the runtime model may transiently carry a session-routing
         // `<profile>:<model>` prefix (e.g. right after session restore, before
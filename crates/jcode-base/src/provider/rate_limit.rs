@@ -0,0 +1,297 @@
+//! Provider rate-limit (HTTP 429) wait hints.
+//!
+//! Anthropic and OpenAI both say how long to back off, but in different
+//! headers:
+//!   - `retry-after-ms` / `retry-after` (delta seconds or an HTTP date)
+//!   - `anthropic-ratelimit-{requests,tokens,input-tokens,output-tokens}-reset`
+//!     (RFC 3339 timestamp)
+//!   - `x-ratelimit-reset-{requests,tokens}` (Go-style duration, e.g. `6m0s`)
+//!
+//! [`retry_after_secs_from_headers`] folds them into one wait, and
+//! [`RateLimited`] carries it through the provider retry loop so the agent can
+//! surface a consistent `StreamEvent::Error { retry_after_secs }` instead of an
+//! opaque error string.
+
+use super::Provider;
+use crate::message::StreamEvent;
+use chrono::{DateTime, Utc};
+use reqwest::header::HeaderMap;
+use std::fmt;
+use std::time::Duration;
+
+/// Maximum in-turn rate-limit waits per API call before the error surfaces.
+pub const MAX_RATE_LIMIT_WAITS: u32 = 3;
+
+/// Reset-header families, paired with the `remaining` header that tells us
+/// whether that bucket is the one actually exhausted.
+const RESET_HEADER_FAMILIES: &[(&str, &str)] = &[
+    (
+        "anthropic-ratelimit-requests-reset",
+        "anthropic-ratelimit-requests-remaining",
+    ),
+    (
+        "anthropic-ratelimit-tokens-reset",
+        "anthropic-ratelimit-tokens-remaining",
+    ),
+    (
+        "anthropic-ratelimit-input-tokens-reset",
+        "anthropic-ratelimit-input-tokens-remaining",
+    ),
+    (
+        "anthropic-ratelimit-output-tokens-reset",
+        "anthropic-ratelimit-output-tokens-remaining",
+    ),
+    (
+        "x-ratelimit-reset-requests",
+        "x-ratelimit-remaining-requests",
+    ),
+    ("x-ratelimit-reset-tokens", "x-ratelimit-remaining-tokens"),
+];
+
+/// A provider rejected the request with a rate limit. `retry_after_secs` is
+/// the server-advertised wait, when the response carried one.
+#[derive(Debug)]
+pub(crate) struct RateLimited {
+    pub(crate) retry_after_secs: Option<u64>,
+    pub(crate) message: String,
+}
+
+impl fmt::Display for RateLimited {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for RateLimited {}
+
+/// Convert a provider error into the terminal stream event the agent expects
+/// for a rate limit with a known wait. Returns `None` for anything else so the
+/// caller keeps its normal retry/error handling.
+pub(crate) fn rate_limited_stream_event(error: &anyhow::Error) -> Option<StreamEvent> {
+    let limited = error.downcast_ref::<RateLimited>()?;
+    let retry_after_secs = limited.retry_after_secs?;
+    Some(StreamEvent::Error {
+        message: limited.message.clone(),
+        retry_after_secs: Some(retry_after_secs),
+    })
+}
+
+/// The wait to sleep through before resending a rate-limited request within
+/// the same turn, or `None` when the error should surface instead: no
+/// advertised wait, a wait above `[provider] rate_limit_max_wait_secs`, or
+/// `waits_so_far` already at [`MAX_RATE_LIMIT_WAITS`].
+pub fn in_turn_rate_limit_wait(
+    retry_after_secs: Option<u64>,
+    waits_so_far: u32,
+) -> Option<Duration> {
+    let secs = retry_after_secs?;
+    let ceiling = crate::config::config().provider.rate_limit_max_wait_secs;
+    (waits_so_far < MAX_RATE_LIMIT_WAITS && secs <= ceiling).then(|| Duration::from_secs(secs))
+}
+
+/// Activity-ledger key a rate-limit incident on `provider` is recorded under.
+pub fn rate_limit_source_key(provider: &dyn Provider) -> String {
+    provider.usage_source_key().unwrap_or_else(|| {
+        crate::provider_activity::source_key_for_provider_label(&provider.display_name(), None)
+    })
+}
+
+/// Seconds to wait before retrying, derived from a 429 response's headers.
+///
+/// `retry-after-ms` / `retry-after` win when present. Otherwise the reset of
+/// whichever bucket reports zero remaining is used, falling back to the
+/// soonest advertised reset. Waits are rounded up to whole seconds and never
+/// reported as zero, so a retry never races the window it was told to wait out.
+pub(crate) fn retry_after_secs_from_headers(headers: &HeaderMap) -> Option<u64> {
+    retry_after_secs_at(headers, Utc::now())
+}
+
+fn retry_after_secs_at(headers: &HeaderMap, now: DateTime<Utc>) -> Option<u64> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    };
+
+    if let Some(ms) = header("retry-after-ms").and_then(|v| v.parse::<f64>().ok())
+        && ms.is_finite()
+        && ms >= 0.0
+    {
+        return Some(ceil_secs(ms / 1000.0));
+    }
+    if let Some(value) = header("retry-after") {
+        if let Ok(secs) = value.parse::<f64>()
+            && secs.is_finite()
+            && secs >= 0.0
+        {
+            return Some(ceil_secs(secs));
+        }
+        if let Ok(at) = DateTime::parse_from_rfc2822(value) {
+            return Some(secs_until(at.with_timezone(&Utc), now));
+        }
+    }
+
+    let mut exhausted: Option<u64> = None;
+    let mut soonest: Option<u64> = None;
+    for (reset_name, remaining_name) in RESET_HEADER_FAMILIES {
+        let Some(wait) = header(reset_name).and_then(|value| parse_reset(value, now)) else {
+            continue;
+        };
+        soonest = Some(soonest.map_or(wait, |current| current.min(wait)));
+        let is_exhausted = header(remaining_name)
+            .and_then(|value| value.parse::<u64>().ok())
+            .is_some_and(|remaining| remaining == 0);
+        if is_exhausted {
+            exhausted = Some(exhausted.map_or(wait, |current| current.max(wait)));
+        }
+    }
+    exhausted.or(soonest)
+}
+
+/// A reset header is either an RFC 3339 timestamp (Anthropic), a Go-style
+/// duration (OpenAI), or bare seconds.
+fn parse_reset(value: &str, now: DateTime<Utc>) -> Option<u64> {
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Some(secs_until(at.with_timezone(&Utc), now));
+    }
+    if let Ok(secs) = value.parse::<f64>()
+        && secs.is_finite()
+        && secs >= 0.0
+    {
+        return Some(ceil_secs(secs));
+    }
+    parse_go_duration_secs(value).map(ceil_secs)
+}
+
+/// Parse durations like `6m0s`, `1h2m3.5s`, `20ms`, or `1.5s` into seconds.
+fn parse_go_duration_secs(value: &str) -> Option<f64> {
+    let mut total = 0.0;
+    let mut rest = value;
+    let mut parsed_any = false;
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        if number_len == 0 {
+            return None;
+        }
+        let number: f64 = rest[..number_len].parse().ok()?;
+        rest = &rest[number_len..];
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(rest.len());
+        let scale = match &rest[..unit_len] {
+            "h" => 3600.0,
+            "m" => 60.0,
+            "s" => 1.0,
+            "ms" => 0.001,
+            _ => return None,
+        };
+        rest = &rest[unit_len..];
+        total += number * scale;
+        parsed_any = true;
+    }
+    parsed_any.then_some(total)
+}
+
+fn secs_until(at: DateTime<Utc>, now: DateTime<Utc>) -> u64 {
+    let millis = (at - now).num_milliseconds().max(0) as f64;
+    ceil_secs(millis / 1000.0)
+}
+
+fn ceil_secs(secs: f64) -> u64 {
+    (secs.ceil() as u64).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::{HeaderName, HeaderValue};
+
+    fn headers(pairs: &[(&str, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(
+                HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                HeaderValue::from_str(value).unwrap(),
+            );
+        }
+        map
+    }
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-03-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn retry_after_seconds_and_http_date() {
+        assert_eq!(
+            retry_after_secs_at(&headers(&[("retry-after", "17")]), now()),
+            Some(17)
+        );
+        assert_eq!(
+            retry_after_secs_at(
+                &headers(&[("retry-after", "Sun, 01 Mar 2026 12:00:42 GMT")]),
+                now()
+            ),
+            Some(42)
+        );
+        assert_eq!(
+            retry_after_secs_at(&headers(&[("retry-after-ms", "1500")]), now()),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn anthropic_reset_prefers_exhausted_bucket() {
+        let map = headers(&[
+            ("anthropic-ratelimit-requests-reset", "2026-03-01T12:00:05Z"),
+            ("anthropic-ratelimit-requests-remaining", "12"),
+            (
+                "anthropic-ratelimit-output-tokens-reset",
+                "2026-03-01T12:00:30Z",
+            ),
+            ("anthropic-ratelimit-output-tokens-remaining", "0"),
+        ]);
+        assert_eq!(retry_after_secs_at(&map, now()), Some(30));
+    }
+
+    #[test]
+    fn openai_duration_resets_fall_back_to_soonest() {
+        let map = headers(&[
+            ("x-ratelimit-reset-requests", "1m30s"),
+            ("x-ratelimit-reset-tokens", "250ms"),
+        ]);
+        assert_eq!(retry_after_secs_at(&map, now()), Some(1));
+        assert_eq!(parse_go_duration_secs("6m0s"), Some(360.0));
+        assert_eq!(parse_go_duration_secs("bogus"), None);
+    }
+
+    #[test]
+    fn only_rate_limits_with_a_wait_become_stream_events() {
+        let with_wait: anyhow::Error = RateLimited {
+            retry_after_secs: Some(9),
+            message: "Anthropic API error (429 Too Many Requests): slow down".to_string(),
+        }
+        .into();
+        assert!(matches!(
+            rate_limited_stream_event(&with_wait),
+            Some(StreamEvent::Error {
+                retry_after_secs: Some(9),
+                ..
+            })
+        ));
+
+        let without_wait: anyhow::Error = RateLimited {
+            retry_after_secs: None,
+            message: "rate limited".to_string(),
+        }
+        .into();
+        assert!(rate_limited_stream_event(&without_wait).is_none());
+        assert!(rate_limited_stream_event(&anyhow::anyhow!("boom")).is_none());
+    }
+}
@@ -0,0 +1,241 @@
+//! Stuck-stream watchdog.
+//!
+//! Wraps a provider [`EventStream`] with two timeouts: how long to wait for
+//! the response to start (`[provider] stream_idle_timeout_secs`) and how long
+//! the response may then go quiet between chunks (`[provider]
+//! idle_timeout_secs`). Connection status events do not count as data, so a
+//! provider that reports "waiting for response" and then hangs still trips the
+//! first timeout.
+//!
+//! A stall ends the stream with a `StreamEvent::Error` whose message starts
+//! with [`STREAM_IDLE_TIMEOUT`]; the agent keeps any partial output and
+//! retries from there.
+
+use super::EventStream;
+use crate::message::StreamEvent;
+use anyhow::Result;
+use futures::StreamExt;
+use std::time::{Duration, Instant};
+
+/// Prefix of the error message emitted for a stalled stream.
+pub const STREAM_IDLE_TIMEOUT: &str = "stream idle timeout";
+
+pub fn is_stream_idle_timeout(message: &str) -> bool {
+    message.starts_with(STREAM_IDLE_TIMEOUT)
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct StreamWatchdog {
+    /// Max wait for the first response event.
+    pub first_event: Duration,
+    /// Max gap between response events once the response started. `None`
+    /// disables the check.
+    pub idle: Option<Duration>,
+}
+
+impl StreamWatchdog {
+    pub fn from_config() -> Self {
+        let provider = &crate::config::config().provider;
+        Self {
+            first_event: Duration::from_secs(provider.stream_idle_timeout_secs.max(1)),
+            idle: (provider.idle_timeout_secs > 0)
+                .then(|| Duration::from_secs(provider.idle_timeout_secs)),
+        }
+    }
+}
+
+enum EventKind {
+    /// Connection bookkeeping; says nothing about whether the model responds.
+    Status,
+    /// The provider is replaying the request; wait for it to start again.
+    Restart,
+    Data,
+}
+
+fn classify(event: &StreamEvent) -> EventKind {
+    match event {
+        StreamEvent::ConnectionType { .. }
+        | StreamEvent::ConnectionPhase { .. }
+        | StreamEvent::StatusDetail { .. }
+        | StreamEvent::UpstreamProvider { .. }
+        | StreamEvent::BackgroundResponse { .. }
+        | StreamEvent::SystemFingerprint(_) => EventKind::Status,
+        StreamEvent::Retrying { .. } | StreamEvent::RetryRollback { .. } => EventKind::Restart,
+        _ => EventKind::Data,
+    }
+}
+
+struct WatchState {
+    inner: EventStream,
+    watchdog: StreamWatchdog,
+    on_stall: Option<Box<dyn FnOnce() + Send>>,
+    responding: bool,
+    /// Time spent waiting on `inner` since the last data event. Time the
+    /// consumer spends between polls is not counted.
+    quiet: Duration,
+    stalled: bool,
+}
+
+impl WatchState {
+    async fn next(&mut self) -> Option<Result<StreamEvent>> {
+        if self.stalled {
+            return None;
+        }
+        let budget = if self.responding {
+            self.watchdog.idle
+        } else {
+            Some(self.watchdog.first_event)
+        };
+        let Some(budget) = budget else {
+            return self.inner.next().await;
+        };
+
+        let waiting_since = Instant::now();
+        let next = tokio::time::timeout(budget.saturating_sub(self.quiet), self.inner.next()).await;
+        self.quiet += waiting_since.elapsed();
+        let Ok(next) = next else {
+            return Some(Ok(self.stall(budget)));
+        };
+        if let Some(Ok(event)) = &next {
+            match classify(event) {
+                EventKind::Status => {}
+                EventKind::Restart => {
+                    self.responding = false;
+                    self.quiet = Duration::ZERO;
+                }
+                EventKind::Data => {
+                    self.responding = true;
+                    self.quiet = Duration::ZERO;
+                }
+            }
+        }
+        next
+    }
+
+    fn stall(&mut self, budget: Duration) -> StreamEvent {
+        self.stalled = true;
+        let phase = if self.responding {
+            "mid-response"
+        } else {
+            "before the response started"
+        };
+        crate::logging::warn(&format!(
+            "Provider stream stalled {} (no data for {}s)",
+            phase,
+            budget.as_secs()
+        ));
+        if let Some(on_stall) = self.on_stall.take() {
+            on_stall();
+        }
+        StreamEvent::Error {
+            message: format!(
+                "{}: no data from the provider for {}s {}",
+                STREAM_IDLE_TIMEOUT,
+                budget.as_secs(),
+                phase
+            ),
+            retry_after_secs: None,
+        }
+    }
+}
+
+/// Wrap `stream` with `watchdog`. `on_stall` runs once if the stream stalls.
+pub fn watch_stream(
+    stream: EventStream,
+    watchdog: StreamWatchdog,
+    on_stall: impl FnOnce() + Send + 'static,
+) -> EventStream {
+    let state = WatchState {
+        inner: stream,
+        watchdog,
+        on_stall: Some(Box::new(on_stall)),
+        responding: false,
+        quiet: Duration::ZERO,
+        stalled: false,
+    };
+    Box::pin(futures::stream::unfold(state, |mut state| async move {
+        let next = state.next().await?;
+        Some((next, state))
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::ConnectionPhase;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const WATCHDOG: StreamWatchdog = StreamWatchdog {
+        first_event: Duration::from_millis(200),
+        idle: Some(Duration::from_millis(50)),
+    };
+
+    fn stalling(events: Vec<StreamEvent>) -> EventStream {
+        Box::pin(
+            futures::stream::iter(events.into_iter().map(Ok)).chain(futures::stream::pending()),
+        )
+    }
+
+    async fn collect(stream: EventStream) -> (Vec<StreamEvent>, usize) {
+        let stalls = Arc::new(AtomicUsize::new(0));
+        let counter = stalls.clone();
+        let stream = watch_stream(stream, WATCHDOG, move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        let events = stream.map(|event| event.expect("event")).collect().await;
+        (events, stalls.load(Ordering::SeqCst))
+    }
+
+    fn error_message(event: &StreamEvent) -> &str {
+        match event {
+            StreamEvent::Error { message, .. } => message,
+            other => panic!("expected error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn mid_response_stall_ends_with_idle_timeout_error() {
+        let (events, stalls) = collect(stalling(vec![StreamEvent::TextDelta(
+            "partial".to_string(),
+        )]))
+        .await;
+
+        assert_eq!(events.len(), 2);
+        assert!(matches!(&events[0], StreamEvent::TextDelta(text) if text == "partial"));
+        let message = error_message(&events[1]);
+        assert!(is_stream_idle_timeout(message), "{message}");
+        assert!(message.contains("mid-response"), "{message}");
+        assert_eq!(stalls, 1);
+    }
+
+    #[tokio::test]
+    async fn status_events_do_not_count_as_a_started_response() {
+        let (events, stalls) = collect(stalling(vec![StreamEvent::ConnectionPhase {
+            phase: ConnectionPhase::WaitingForResponse,
+        }]))
+        .await;
+
+        assert_eq!(events.len(), 2);
+        assert!(error_message(&events[1]).contains("before the response started"));
+        assert_eq!(stalls, 1);
+    }
+
+    #[tokio::test]
+    async fn completed_stream_passes_through() {
+        let stream: EventStream = Box::pin(futures::stream::iter(
+            [
+                StreamEvent::TextDelta("done".to_string()),
+                StreamEvent::MessageEnd {
+                    stop_reason: Some("end_turn".to_string()),
+                },
+            ]
+            .into_iter()
+            .map(Ok),
+        ));
+        let (events, stalls) = collect(stream).await;
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(stalls, 0);
+    }
+}
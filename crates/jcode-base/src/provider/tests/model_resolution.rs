@@ -1632,6 +1632,23 @@ fn test_resolve_model_capabilities_uses_provider_hint() {
     let gemini = resolve_model_capabilities("gemini-2.5-pro", Some("gemini"));
     assert_eq!(gemini.provider.as_deref(), Some("gemini"));
     assert_eq!(gemini.context_window, Some(1_000_000));
+    assert!(gemini.tools && gemini.vision);
+}
+
+#[test]
+fn test_resolve_model_capabilities_flags_tool_less_models() {
+    let o1_mini = resolve_model_capabilities("openai/o1-mini", Some("openrouter"));
+    assert_eq!(o1_mini.provider.as_deref(), Some("openrouter"));
+    assert!(!o1_mini.tools);
+    assert!(!o1_mini.system_prompt);
+
+    let deepseek = resolve_model_capabilities("us.deepseek.r1-v1:0", None);
+    assert_eq!(deepseek.provider.as_deref(), Some("bedrock"));
+    assert!(!deepseek.tools);
+    assert!(deepseek.max_output_tokens.is_some());
+
+    let claude = resolve_model_capabilities("claude-sonnet-4-6", None);
+    assert!(claude.tools && claude.vision && claude.streaming);
 }
 
 #[test]
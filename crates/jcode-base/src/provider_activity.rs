@@ -1,10 +1,14 @@
 //! Cross-provider activity ledger.
 //!
-//! Tracks two things per login/credential ("source key"):
+//! Tracks three things per login/credential ("source key"):
 //!   1. When jcode last successfully used it (for recency-sorted `/usage`).
 //!   2. Locally accumulated API-key spend in USD (day / month / all-time),
 //!      mirroring the dollar figures the TUI cost paths compute, since most
 //!      providers do not expose per-key spend through their public APIs.
+//!   3. Recent rate-limit (HTTP 429) incidents, so `/usage` can show how often
+//!      a login has been throttled lately.
+//!   4. Recent stalled streams (provider went quiet mid-response), so chronic
+//!      stallers stand out in `/usage`.
 //!
 //! Data persists to `~/.jcode/provider_activity.json` and is shared across
 //! processes (server records last-used, TUI records spend, `/usage` reads
@@ -32,6 +36,12 @@ const QUERY_RELOAD_TTL: Duration = Duration::from_secs(2);
 /// this many seconds, so busy sessions do not rewrite the file on every call.
 const LAST_USED_WRITE_THROTTLE_SECS: u64 = 30;
 
+/// Rate-limit incidents older than this are pruned on the next write.
+const RATE_LIMIT_RETENTION_SECS: u64 = 86_400;
+
+/// Stream stalls older than this are pruned on the next write.
+const STREAM_STALL_RETENTION_SECS: u64 = 86_400;
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ProviderSpend {
     /// `YYYY-MM-DD` the `day_usd` bucket belongs to.
@@ -54,6 +64,12 @@ pub struct ProviderActivityEntry {
     pub last_used_unix_secs: Option<u64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub spend: Option<ProviderSpend>,
+    /// Unix timestamps of recent rate-limit incidents (last 24h).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rate_limits_unix_secs: Vec<u64>,
+    /// Unix timestamps of recent stream idle timeouts (last 24h).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stream_stalls_unix_secs: Vec<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -183,6 +199,65 @@ pub fn record_spend(source_key: &str, usd: f64) {
     });
 }
 
+/// Append an incident timestamp to the list `incidents` selects, pruning
+/// entries older than `retention_secs`.
+fn record_incident(
+    source_key: &str,
+    retention_secs: u64,
+    incidents: fn(&mut ProviderActivityEntry) -> &mut Vec<u64>,
+) {
+    let source_key = source_key.trim();
+    if source_key.is_empty() {
+        return;
+    }
+    let now = now_unix_secs();
+    let source_key = source_key.to_string();
+    with_fresh_store(move |store| {
+        let list = incidents(store.entries.entry(source_key).or_default());
+        list.retain(|at| now.saturating_sub(*at) < retention_secs);
+        list.push(now);
+        true
+    });
+}
+
+fn count_within(incidents: &[u64], window_secs: u64) -> usize {
+    let now = now_unix_secs();
+    incidents
+        .iter()
+        .filter(|at| now.saturating_sub(**at) < window_secs)
+        .count()
+}
+
+/// Record that a login/credential was rate limited right now.
+pub fn record_rate_limit(source_key: &str) {
+    record_incident(source_key, RATE_LIMIT_RETENTION_SECS, |entry| {
+        &mut entry.rate_limits_unix_secs
+    });
+}
+
+/// Number of rate-limit incidents recorded for a credential within the last
+/// `window_secs` seconds.
+pub fn rate_limits_within(source_key: &str, window_secs: u64) -> usize {
+    snapshot_entry(source_key)
+        .map(|entry| count_within(&entry.rate_limits_unix_secs, window_secs))
+        .unwrap_or(0)
+}
+
+/// Record that a stream from a login/credential stalled right now.
+pub fn record_stream_stall(source_key: &str) {
+    record_incident(source_key, STREAM_STALL_RETENTION_SECS, |entry| {
+        &mut entry.stream_stalls_unix_secs
+    });
+}
+
+/// Number of stalled streams recorded for a credential within the last
+/// `window_secs` seconds.
+pub fn stream_stalls_within(source_key: &str, window_secs: u64) -> usize {
+    snapshot_entry(source_key)
+        .map(|entry| count_within(&entry.stream_stalls_unix_secs, window_secs))
+        .unwrap_or(0)
+}
+
 pub fn last_used_unix_secs(source_key: &str) -> Option<u64> {
     snapshot_entry(source_key)?.last_used_unix_secs
 }
@@ -429,6 +504,53 @@ mod tests {
         assert!((spend.all_time_usd - 0.75).abs() < 1e-9);
     }
 
+    #[test]
+    fn record_rate_limit_counts_recent_incidents() {
+        let _env_lock = lock_env();
+        clear_ledger_cache();
+        let temp = tempfile::tempdir().expect("tempdir");
+        let _home = EnvVarGuard::set("JCODE_HOME", temp.path().as_os_str());
+
+        assert_eq!(rate_limits_within("claude:api-key", 3_600), 0);
+        record_rate_limit("claude:api-key");
+        record_rate_limit("claude:api-key");
+        record_rate_limit("openai:api-key");
+        assert_eq!(rate_limits_within("claude:api-key", 3_600), 2);
+        assert_eq!(rate_limits_within("openai:api-key", 3_600), 1);
+
+        // Incidents outside the window are not counted, and stale ones are
+        // pruned on the next write.
+        with_fresh_store(|store| {
+            let entry = store.entries.get_mut("claude:api-key").expect("entry");
+            entry.rate_limits_unix_secs[0] -= 2 * RATE_LIMIT_RETENTION_SECS;
+            true
+        });
+        assert_eq!(rate_limits_within("claude:api-key", 3_600), 1);
+        record_rate_limit("claude:api-key");
+        clear_ledger_cache();
+        let entry = snapshot_entry("claude:api-key").expect("entry reloaded from disk");
+        assert_eq!(entry.rate_limits_unix_secs.len(), 2);
+    }
+
+    #[test]
+    fn record_stream_stall_counts_separately_from_rate_limits() {
+        let _env_lock = lock_env();
+        clear_ledger_cache();
+        let temp = tempfile::tempdir().expect("tempdir");
+        let _home = EnvVarGuard::set("JCODE_HOME", temp.path().as_os_str());
+
+        record_stream_stall("openrouter");
+        record_stream_stall("openrouter");
+        record_rate_limit("openrouter");
+        assert_eq!(stream_stalls_within("openrouter", 86_400), 2);
+        assert_eq!(rate_limits_within("openrouter", 86_400), 1);
+        assert_eq!(stream_stalls_within("claude:api-key", 86_400), 0);
+
+        clear_ledger_cache();
+        let entry = snapshot_entry("openrouter").expect("entry reloaded from disk");
+        assert_eq!(entry.stream_stalls_unix_secs.len(), 2);
+    }
+
     #[test]
     fn record_spend_ignores_invalid_amounts() {
         let _env_lock = lock_env();
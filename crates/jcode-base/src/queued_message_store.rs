@@ -0,0 +1,66 @@
+//! User messages queued behind a session's running turn. They are kept under
+//! `~/.jcode/queued-messages/<session>.json` next to the session, so a server
+//! reload does not drop them.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// A message waiting to run as its own turn once the session is idle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueuedMessage {
+    /// Stable per-session number, shown as the item id `msg-<seq>`.
+    pub seq: u64,
+    /// Request id of the `queue_message` request, reused for the turn.
+    pub id: u64,
+    pub content: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub images: Vec<(String, String)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_reminder: Option<String>,
+}
+
+fn dir_path() -> Result<PathBuf> {
+    Ok(crate::storage::jcode_dir()?.join("queued-messages"))
+}
+
+fn path_for_session(session_id: &str) -> Result<PathBuf> {
+    Ok(dir_path()?.join(format!("{}.json", session_id)))
+}
+
+pub fn load(session_id: &str) -> Result<Vec<QueuedMessage>> {
+    let path = path_for_session(session_id)?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    crate::storage::read_json(&path)
+}
+
+pub fn overwrite(session_id: &str, messages: &[QueuedMessage]) -> Result<()> {
+    let path = path_for_session(session_id)?;
+    if messages.is_empty() {
+        if path.exists() {
+            let _ = std::fs::remove_file(path);
+        }
+        return Ok(());
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    crate::storage::write_json_fast(&path, messages)
+}
+
+/// The `seq` for the next message of a session.
+pub fn next_seq(messages: &[QueuedMessage]) -> u64 {
+    messages
+        .iter()
+        .map(|message| message.seq)
+        .max()
+        .unwrap_or(0)
+        + 1
+}
+
+#[cfg(test)]
+#[path = "queued_message_store_tests.rs"]
+mod queued_message_store_tests;
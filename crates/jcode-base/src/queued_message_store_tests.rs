@@ -0,0 +1,41 @@
+use super::*;
+
+#[test]
+fn queued_messages_round_trip_and_clear() {
+    let _guard = crate::storage::lock_test_env();
+    let temp = tempfile::TempDir::new().expect("temp dir");
+    let prev_home = std::env::var_os("JCODE_HOME");
+    crate::env::set_var("JCODE_HOME", temp.path());
+
+    let session_id = "ses_queued_message_store";
+    assert!(load(session_id).expect("load empty").is_empty());
+    let messages = vec![
+        QueuedMessage {
+            seq: 1,
+            id: 7,
+            content: "write the docs".to_string(),
+            images: vec![("image/png".to_string(), "aGk=".to_string())],
+            system_reminder: None,
+        },
+        QueuedMessage {
+            seq: 4,
+            id: 9,
+            content: "then run the tests".to_string(),
+            images: Vec::new(),
+            system_reminder: Some("be brief".to_string()),
+        },
+    ];
+    overwrite(session_id, &messages).expect("write queue");
+    assert_eq!(load(session_id).expect("load queue"), messages);
+    assert_eq!(next_seq(&messages), 5);
+
+    overwrite(session_id, &[]).expect("clear queue");
+    assert!(load(session_id).expect("load after clear").is_empty());
+    assert_eq!(next_seq(&[]), 1);
+
+    if let Some(prev_home) = prev_home {
+        crate::env::set_var("JCODE_HOME", prev_home);
+    } else {
+        crate::env::remove_var("JCODE_HOME");
+    }
+}
@@ -3,6 +3,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::sync::{Mutex, OnceLock};
 
+use crate::protocol::{PermissionInboxEntry, PermissionOrigin};
 use crate::storage;
 
 /// Hook invoked to deliver a permission-request notification.
@@ -62,6 +63,40 @@ pub struct PermissionRequest {
     pub created_at: DateTime<Utc>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub context: Option<serde_json::Value>,
+    /// When an unanswered request is dropped as denied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl PermissionRequest {
+    /// Whether an ambient cycle or an interactive session raised this request.
+    pub fn origin(&self) -> PermissionOrigin {
+        let from_session = self
+            .context
+            .as_ref()
+            .and_then(|context| context.get("origin"))
+            .and_then(|origin| origin.as_str())
+            == Some("session");
+        if from_session {
+            PermissionOrigin::Session
+        } else {
+            PermissionOrigin::Ambient
+        }
+    }
+
+    /// The request as clients see it in the permission inbox.
+    pub fn to_inbox_entry(&self) -> PermissionInboxEntry {
+        let unix_ms = |at: DateTime<Utc>| u64::try_from(at.timestamp_millis()).unwrap_or(0);
+        PermissionInboxEntry {
+            request_id: self.id.clone(),
+            origin: self.origin(),
+            origin_id: request_session_id(self),
+            action: self.action.clone(),
+            description: self.description.clone(),
+            created_at_ms: unix_ms(self.created_at),
+            expires_at_ms: self.expires_at.map(unix_ms),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -130,10 +165,12 @@ pub struct AmbientTranscript {
 // ---------------------------------------------------------------------------
 
 const AUTO_ALLOWED: &[&str] = &[
+    "ask_user",
     "read",
     "glob",
     "grep",
     "ls",
+    "tree",
     "memory",
     "todo",
     "todowrite",
@@ -143,6 +180,16 @@ const AUTO_ALLOWED: &[&str] = &[
     "codesearch",
 ];
 
+/// Classify an action name into a tier without loading the review queue.
+pub fn classify_action(action: &str) -> ActionTier {
+    let lower = action.to_lowercase();
+    if AUTO_ALLOWED.iter().any(|&a| a == lower) {
+        ActionTier::AutoAllowed
+    } else {
+        ActionTier::RequiresPermission
+    }
+}
+
 // ---------------------------------------------------------------------------
 // SafetySystem
 // ---------------------------------------------------------------------------
@@ -175,12 +222,7 @@ impl SafetySystem {
 
     /// Classify an action name into a tier.
     pub fn classify(&self, action: &str) -> ActionTier {
-        let lower = action.to_lowercase();
-        if AUTO_ALLOWED.iter().any(|&a| a == lower) {
-            ActionTier::AutoAllowed
-        } else {
-            ActionTier::RequiresPermission
-        }
+        classify_action(action)
     }
 
     /// Submit a permission request. Returns `Queued` with the request id.
@@ -189,6 +231,7 @@ impl SafetySystem {
         let action = request.action.clone();
         let description = request.description.clone();
         if let Ok(mut q) = self.queue.lock() {
+            refresh_from_disk(&mut q, queue_path());
             q.push(request);
             let _ = persist_queue(&q);
         }
@@ -204,10 +247,11 @@ impl SafetySystem {
         let mut expired: Vec<(String, String)> = Vec::new();
 
         if let Ok(mut q) = self.queue.lock() {
+            refresh_from_disk(&mut q, queue_path());
             let mut retained: Vec<PermissionRequest> = Vec::with_capacity(q.len());
             for req in q.drain(..) {
-                if let Some(reason) = stale_request_reason(&req) {
-                    expired.push((req.id.clone(), reason));
+                if let Some(message) = stale_request_message(&req) {
+                    expired.push((req.id.clone(), message));
                 } else {
                     retained.push(req);
                 }
@@ -221,16 +265,14 @@ impl SafetySystem {
         }
 
         if let Ok(mut h) = self.history.lock() {
-            for (request_id, reason) in &expired {
+            refresh_from_disk(&mut h, history_path());
+            for (request_id, message) in &expired {
                 h.push(Decision {
                     request_id: request_id.clone(),
                     approved: false,
                     decided_at: Utc::now(),
                     decided_via: via.to_string(),
-                    message: Some(format!(
-                        "Expired automatically: {}. Original agent is no longer active.",
-                        reason
-                    )),
+                    message: Some(message.clone()),
                 });
             }
             let _ = persist_history(&h);
@@ -240,6 +282,9 @@ impl SafetySystem {
     }
 
     /// Record a user decision (approve / deny) for a pending request.
+    ///
+    /// Fails when the request is no longer pending, e.g. because another
+    /// surface already resolved it.
     pub fn record_decision(
         &self,
         request_id: &str,
@@ -249,7 +294,12 @@ impl SafetySystem {
     ) -> Result<()> {
         // Remove from queue
         if let Ok(mut q) = self.queue.lock() {
+            refresh_from_disk(&mut q, queue_path());
+            let before = q.len();
             q.retain(|r| r.id != request_id);
+            if q.len() == before {
+                anyhow::bail!("Permission request {} is no longer pending", request_id);
+            }
             let _ = persist_queue(&q);
         }
 
@@ -262,6 +312,7 @@ impl SafetySystem {
         };
 
         if let Ok(mut h) = self.history.lock() {
+            refresh_from_disk(&mut h, history_path());
             h.push(decision);
             let _ = persist_history(&h);
         }
@@ -271,7 +322,13 @@ impl SafetySystem {
 
     /// Return all pending permission requests.
     pub fn pending_requests(&self) -> Vec<PermissionRequest> {
-        self.queue.lock().map(|q| q.clone()).unwrap_or_default()
+        self.queue
+            .lock()
+            .map(|mut q| {
+                refresh_from_disk(&mut q, queue_path());
+                q.clone()
+            })
+            .unwrap_or_default()
     }
 
     /// Append an action to the in-memory log.
@@ -370,13 +427,39 @@ fn persist_history(history: &[Decision]) -> Result<()> {
     storage::write_json(&path, history)
 }
 
+/// Replace `items` with what is on disk, so a long-lived instance never
+/// writes a stale copy over requests or decisions from other processes.
+fn refresh_from_disk<T: serde::de::DeserializeOwned>(
+    items: &mut Vec<T>,
+    path: Result<std::path::PathBuf>,
+) {
+    if let Ok(path) = path
+        && path.exists()
+        && let Ok(on_disk) = storage::read_json(&path)
+    {
+        *items = on_disk;
+    }
+}
+
 // ---------------------------------------------------------------------------
 // File-based permission decision (for IMAP poller / external callers)
 // ---------------------------------------------------------------------------
 
-/// Record a permission decision by directly manipulating the queue/history JSON files.
-/// Used by the IMAP reply poller which doesn't have access to the live SafetySystem instance.
-pub fn record_permission_via_file(
+/// Pending permission requests read straight from the queue file.
+/// Used by processes that don't hold the live SafetySystem instance.
+pub fn pending_requests_via_file() -> Vec<PermissionRequest> {
+    queue_path()
+        .ok()
+        .filter(|path| path.exists())
+        .and_then(|path| storage::read_json(&path).ok())
+        .unwrap_or_default()
+}
+
+/// Record a decision for a pending request from any surface: the TUI, the
+/// desktop app, `jcode permissions`, Telegram or an email reply. The request
+/// leaves the shared inbox and the decision lands in the history, where a
+/// waiting interactive approval picks it up.
+pub fn record_decision(
     request_id: &str,
     approved: bool,
     via: &str,
@@ -386,33 +469,81 @@ pub fn record_permission_via_file(
     if let Some(parent) = qp.parent() {
         storage::ensure_dir(parent)?;
     }
-    let mut queue: Vec<PermissionRequest> = if qp.exists() {
-        storage::read_json(&qp).unwrap_or_default()
-    } else {
-        Vec::new()
-    };
-    queue.retain(|r| r.id != request_id);
-    persist_queue(&queue)?;
+    SafetySystem::new().record_decision(request_id, approved, via, message)
+}
 
-    let hp = history_path()?;
-    if let Some(parent) = hp.parent() {
+/// Record a permission decision by directly manipulating the queue/history JSON files.
+/// Same as [`record_decision`].
+pub fn record_permission_via_file(
+    request_id: &str,
+    approved: bool,
+    via: &str,
+    message: Option<String>,
+) -> Result<()> {
+    record_decision(request_id, approved, via, message)
+}
+
+/// Every pending decision, oldest first, whether an ambient cycle or an
+/// interactive session raised it. Requests past their expiry are left out;
+/// the next expiry sweep records them as denied.
+pub fn inbox() -> Vec<PermissionRequest> {
+    let now = Utc::now();
+    let mut requests: Vec<PermissionRequest> = pending_requests_via_file()
+        .into_iter()
+        .filter(|request| request.expires_at.is_none_or(|at| at > now))
+        .collect();
+    requests.sort_by_key(|request| request.created_at);
+    requests
+}
+
+/// How long an interactive approval waits in the inbox before it is denied.
+pub fn interactive_approval_ttl() -> chrono::Duration {
+    chrono::Duration::minutes(15)
+}
+
+/// Put an interactive approval (safe mode, clipboard reads) in the inbox so
+/// any surface can answer it. Unlike [`SafetySystem::request_permission`]
+/// this sends no notification; the session's own client already shows it.
+pub fn enqueue_session_request(
+    request_id: &str,
+    session_id: &str,
+    action: &str,
+    description: &str,
+) -> Result<()> {
+    let qp = queue_path()?;
+    if let Some(parent) = qp.parent() {
         storage::ensure_dir(parent)?;
     }
-    let mut history: Vec<Decision> = if hp.exists() {
-        storage::read_json(&hp).unwrap_or_default()
-    } else {
-        Vec::new()
-    };
-    history.push(Decision {
-        request_id: request_id.to_string(),
-        approved,
-        decided_at: Utc::now(),
-        decided_via: via.to_string(),
-        message,
+    let mut queue = pending_requests_via_file();
+    let now = Utc::now();
+    queue.push(PermissionRequest {
+        id: request_id.to_string(),
+        action: action.to_string(),
+        description: description.to_string(),
+        rationale: String::new(),
+        urgency: Urgency::High,
+        wait: true,
+        created_at: now,
+        context: Some(serde_json::json!({
+            "origin": "session",
+            "session_id": session_id,
+        })),
+        expires_at: Some(now + interactive_approval_ttl()),
     });
-    persist_history(&history)?;
+    persist_queue(&queue)
+}
 
-    Ok(())
+/// The decision recorded for `request_id`, if any surface has resolved it.
+pub fn decision_for(request_id: &str) -> Option<Decision> {
+    let history: Vec<Decision> = history_path()
+        .ok()
+        .filter(|path| path.exists())
+        .and_then(|path| storage::read_json(&path).ok())
+        .unwrap_or_default();
+    history
+        .into_iter()
+        .rev()
+        .find(|decision| decision.request_id == request_id)
 }
 
 /// Expire stale permission requests directly via queue/history files.
@@ -430,8 +561,8 @@ pub fn expire_stale_permissions_via_file(via: &str) -> Result<Vec<String>> {
 
     let mut expired: Vec<(String, String)> = Vec::new();
     queue.retain(|req| {
-        if let Some(reason) = stale_request_reason(req) {
-            expired.push((req.id.clone(), reason));
+        if let Some(message) = stale_request_message(req) {
+            expired.push((req.id.clone(), message));
             false
         } else {
             true
@@ -452,16 +583,13 @@ pub fn expire_stale_permissions_via_file(via: &str) -> Result<Vec<String>> {
     } else {
         Vec::new()
     };
-    for (request_id, reason) in &expired {
+    for (request_id, message) in &expired {
         history.push(Decision {
             request_id: request_id.clone(),
             approved: false,
             decided_at: Utc::now(),
             decided_via: via.to_string(),
-            message: Some(format!(
-                "Expired automatically: {}. Original agent is no longer active.",
-                reason
-            )),
+            message: Some(message.clone()),
         });
     }
     persist_history(&history)?;
@@ -469,6 +597,25 @@ pub fn expire_stale_permissions_via_file(via: &str) -> Result<Vec<String>> {
     Ok(expired.into_iter().map(|(id, _)| id).collect())
 }
 
+/// Why a pending request can no longer be serviced, as the message of the
+/// denial recorded for it.
+fn stale_request_message(request: &PermissionRequest) -> Option<String> {
+    if let Some(expires_at) = request.expires_at
+        && expires_at <= Utc::now()
+    {
+        return Some(format!(
+            "Expired automatically: nobody answered before {}.",
+            expires_at.to_rfc3339()
+        ));
+    }
+    stale_request_reason(request).map(|reason| {
+        format!(
+            "Expired automatically: {}. Original agent is no longer active.",
+            reason
+        )
+    })
+}
+
 fn stale_request_reason(request: &PermissionRequest) -> Option<String> {
     let session_id = request_session_id(request)?;
     let mut session = match crate::session::Session::load(&session_id) {
@@ -492,7 +639,8 @@ fn stale_request_reason(request: &PermissionRequest) -> Option<String> {
     }
 }
 
-fn request_session_id(request: &PermissionRequest) -> Option<String> {
+/// Owning session of a permission request, when its context records one.
+pub fn request_session_id(request: &PermissionRequest) -> Option<String> {
     let context = request.context.as_ref()?;
 
     context
@@ -605,6 +753,7 @@ mod tests {
                 wait: false,
                 created_at: Utc::now(),
                 context: None,
+                expires_at: None,
             };
 
             let result = sys.request_permission(req);
@@ -633,6 +782,7 @@ mod tests {
                 wait: false,
                 created_at: Utc::now(),
                 context: None,
+                expires_at: None,
             };
 
             sys.request_permission(req);
@@ -702,6 +852,7 @@ mod tests {
                 wait: false,
                 created_at: Utc::now(),
                 context: None,
+                expires_at: None,
             };
             sys.request_permission(req);
             assert_eq!(sys.pending_requests().len(), baseline + 1);
@@ -719,4 +870,68 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    fn test_inbox_lists_ambient_and_session_requests_until_resolved() {
+        with_temp_home(|| {
+            let sys = SafetySystem::new();
+            sys.request_permission(PermissionRequest {
+                id: "req_ambient".to_string(),
+                action: "push".to_string(),
+                description: "Push to origin".to_string(),
+                rationale: "Ready for review".to_string(),
+                urgency: Urgency::Normal,
+                wait: false,
+                created_at: Utc::now() - chrono::Duration::minutes(5),
+                context: Some(serde_json::json!({ "session_id": "session_cycle" })),
+                expires_at: None,
+            });
+            enqueue_session_request("safe-approval-1", "session_live", "bash", "bash: ls").unwrap();
+
+            let entries: Vec<PermissionInboxEntry> = inbox()
+                .iter()
+                .map(PermissionRequest::to_inbox_entry)
+                .collect();
+            assert_eq!(entries.len(), 2);
+            assert_eq!(entries[0].origin, PermissionOrigin::Ambient);
+            assert_eq!(entries[0].origin_id.as_deref(), Some("session_cycle"));
+            assert_eq!(entries[1].origin, PermissionOrigin::Session);
+            assert!(entries[1].expires_at_ms.is_some());
+
+            // The long-lived instance sees the session request another
+            // caller queued, and does not drop it when it records a decision.
+            sys.record_decision("req_ambient", false, "tui", None)
+                .unwrap();
+            assert!(
+                sys.record_decision("req_ambient", true, "telegram_bot", None)
+                    .is_err()
+            );
+            assert_eq!(decision_for("req_ambient").map(|d| d.approved), Some(false));
+
+            record_decision("safe-approval-1", true, "desktop", None).unwrap();
+            assert!(inbox().is_empty());
+            assert_eq!(
+                decision_for("safe-approval-1").map(|d| d.decided_via),
+                Some("desktop".to_string())
+            );
+        });
+    }
+
+    #[test]
+    fn test_expired_session_requests_leave_the_inbox_as_denied() {
+        with_temp_home(|| {
+            enqueue_session_request("clipboard-approval-1", "session_gone", "clipboard", "read")
+                .unwrap();
+            let mut queue = pending_requests_via_file();
+            queue[0].expires_at = Some(Utc::now() - chrono::Duration::seconds(1));
+            persist_queue(&queue).unwrap();
+
+            assert!(inbox().is_empty());
+            let expired = expire_stale_permissions_via_file("test").unwrap();
+            assert_eq!(expired, vec!["clipboard-approval-1".to_string()]);
+            let decision = decision_for("clipboard-approval-1").unwrap();
+            assert!(!decision.approved);
+            assert!(decision.message.unwrap().contains("nobody answered"));
+        });
+    }
 }
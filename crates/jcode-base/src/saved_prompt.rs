@@ -0,0 +1,371 @@
+//! Saved prompts: reusable messages kept as `<name>.md` files in
+//! `~/.jcode/prompts/` or a project's `.jcode/prompts/`.
+//!
+//! A prompt can contain `{{variable}}` placeholders. They are filled from
+//! `name=value` assignments first, then from the built-ins `{{branch}}`,
+//! `{{diff}}`, `{{clipboard}}` and `{{file:path}}`. Expansion happens before
+//! the message is sent, so the session stores the expanded text.
+
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const PROMPTS_DIR: &str = "prompts";
+const BUILTIN_VARIABLES: &[&str] = &["branch", "diff", "clipboard"];
+const FILE_PLACEHOLDER_PREFIX: &str = "file:";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SavedPrompt {
+    pub name: String,
+    pub path: PathBuf,
+    pub template: String,
+}
+
+/// Where the built-in placeholders read from.
+pub struct PromptContext<'a> {
+    /// Repository for `{{branch}}` and `{{diff}}`, and the base for relative
+    /// `{{file:path}}` paths. Defaults to the current directory.
+    pub working_dir: Option<&'a Path>,
+    /// Text for `{{clipboard}}`.
+    pub clipboard: &'a dyn Fn() -> Option<String>,
+}
+
+/// `~/.jcode/prompts`, where `/prompt save` writes.
+pub fn global_dir() -> Result<PathBuf> {
+    Ok(crate::storage::jcode_dir()?.join(PROMPTS_DIR))
+}
+
+fn project_dir(working_dir: Option<&Path>) -> PathBuf {
+    let path = Path::new(".jcode").join(PROMPTS_DIR);
+    working_dir.map(|dir| dir.join(&path)).unwrap_or(path)
+}
+
+/// Directories searched for prompts, in load order (later entries win).
+pub fn prompt_dirs(working_dir: Option<&Path>) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Ok(dir) = global_dir() {
+        dirs.push(dir);
+    }
+    dirs.push(project_dir(working_dir));
+    dirs
+}
+
+/// All saved prompts by name. Project prompts replace global ones with the
+/// same name.
+pub fn list(working_dir: Option<&Path>) -> Vec<SavedPrompt> {
+    let mut prompts = BTreeMap::new();
+    for dir in prompt_dirs(working_dir) {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("md") {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            match std::fs::read_to_string(&path) {
+                Ok(template) => {
+                    prompts.insert(
+                        name.to_string(),
+                        SavedPrompt {
+                            name: name.to_string(),
+                            path,
+                            template,
+                        },
+                    );
+                }
+                Err(err) => crate::logging::warn(&format!(
+                    "Saved prompts: failed to read {}: {}",
+                    path.display(),
+                    err
+                )),
+            }
+        }
+    }
+    prompts.into_values().collect()
+}
+
+pub fn find(name: &str, working_dir: Option<&Path>) -> Result<SavedPrompt> {
+    list(working_dir)
+        .into_iter()
+        .find(|prompt| prompt.name == name)
+        .with_context(|| {
+            format!(
+                "No saved prompt named '{}' in {}",
+                name,
+                prompt_dirs(working_dir)
+                    .iter()
+                    .map(|dir| dir.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(" or ")
+            )
+        })
+}
+
+/// Write `template` to `~/.jcode/prompts/<name>.md`, replacing any prompt
+/// saved there under that name.
+pub fn save(name: &str, template: &str) -> Result<PathBuf> {
+    validate_name(name)?;
+    let dir = global_dir()?;
+    std::fs::create_dir_all(&dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    let path = dir.join(format!("{}.md", name));
+    std::fs::write(&path, template)
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+pub fn validate_name(name: &str) -> Result<()> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        anyhow::bail!(
+            "Invalid prompt name '{}': use letters, digits, '-' and '_'",
+            name
+        );
+    }
+    Ok(())
+}
+
+/// Parse `name=value` arguments.
+pub fn parse_assignments<'a>(
+    args: impl IntoIterator<Item = &'a str>,
+) -> Result<BTreeMap<String, String>> {
+    let mut vars = BTreeMap::new();
+    for arg in args {
+        let Some((name, value)) = arg.split_once('=') else {
+            anyhow::bail!("Expected name=value, got '{}'", arg);
+        };
+        let name = name.trim();
+        if name.is_empty() {
+            anyhow::bail!("Expected name=value, got '{}'", arg);
+        }
+        vars.insert(name.to_string(), value.to_string());
+    }
+    Ok(vars)
+}
+
+enum Segment<'a> {
+    Text(&'a str),
+    Placeholder(&'a str),
+}
+
+/// Split a template into text and `{{placeholder}}` names. Braces that do
+/// not form a placeholder are kept as text.
+fn segments(template: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let after_open = &rest[start + 2..];
+        let Some(end) = after_open.find("}}") else {
+            break;
+        };
+        let name = after_open[..end].trim();
+        if name.is_empty() || name.contains(['{', '\n']) {
+            segments.push(Segment::Text(&rest[..start + 2]));
+            rest = after_open;
+            continue;
+        }
+        segments.push(Segment::Text(&rest[..start]));
+        segments.push(Segment::Placeholder(name));
+        rest = &after_open[end + 2..];
+    }
+    segments.push(Segment::Text(rest));
+    segments
+}
+
+fn is_builtin(name: &str) -> bool {
+    BUILTIN_VARIABLES.contains(&name) || name.starts_with(FILE_PLACEHOLDER_PREFIX)
+}
+
+/// Variables `template` needs that `vars` does not set, in order of first
+/// use. Built-ins are never missing.
+pub fn missing_variables(template: &str, vars: &BTreeMap<String, String>) -> Vec<String> {
+    let mut missing: Vec<String> = Vec::new();
+    for segment in segments(template) {
+        if let Segment::Placeholder(name) = segment
+            && !vars.contains_key(name)
+            && !is_builtin(name)
+            && !missing.iter().any(|seen| seen == name)
+        {
+            missing.push(name.to_string());
+        }
+    }
+    missing
+}
+
+/// Fill every placeholder in `template`. Fails on a variable that `vars`
+/// does not set or a built-in that cannot be read.
+pub fn expand(
+    template: &str,
+    vars: &BTreeMap<String, String>,
+    context: &PromptContext<'_>,
+) -> Result<String> {
+    let mut out = String::with_capacity(template.len());
+    for segment in segments(template) {
+        match segment {
+            Segment::Text(text) => out.push_str(text),
+            Segment::Placeholder(name) => match vars.get(name) {
+                Some(value) => out.push_str(value),
+                None => out.push_str(&builtin_value(name, context)?),
+            },
+        }
+    }
+    Ok(out)
+}
+
+fn builtin_value(name: &str, context: &PromptContext<'_>) -> Result<String> {
+    if let Some(path) = name.strip_prefix(FILE_PLACEHOLDER_PREFIX) {
+        let path = Path::new(path.trim());
+        let path = match context.working_dir {
+            Some(dir) if path.is_relative() => dir.join(path),
+            _ => path.to_path_buf(),
+        };
+        return std::fs::read_to_string(&path)
+            .with_context(|| format!("{{{{{}}}}}: failed to read {}", name, path.display()));
+    }
+    match name {
+        "branch" => git_output(context.working_dir, &["rev-parse", "--abbrev-ref", "HEAD"])
+            .map(|branch| branch.trim().to_string())
+            .context("{{branch}}: not in a git repository"),
+        "diff" => git_output(context.working_dir, &["diff", "HEAD"])
+            .context("{{diff}}: not in a git repository"),
+        "clipboard" => {
+            (context.clipboard)().context("{{clipboard}}: clipboard is empty or unavailable")
+        }
+        _ => anyhow::bail!("Missing value for {{{{{}}}}}", name),
+    }
+}
+
+fn git_output(working_dir: Option<&Path>, args: &[&str]) -> Option<String> {
+    let mut command = Command::new("git");
+    command.args(args);
+    if let Some(dir) = working_dir {
+        command.current_dir(dir);
+    }
+    let output = command.output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Clipboard text from the platform's command-line tools, for callers
+/// without a clipboard library (e.g. `jcode run`).
+pub fn command_clipboard_text() -> Option<String> {
+    let candidates: &[(&str, &[&str])] = if cfg!(target_os = "macos") {
+        &[("pbpaste", &[])]
+    } else if cfg!(windows) {
+        &[("powershell", &["-NoProfile", "-Command", "Get-Clipboard"])]
+    } else {
+        &[
+            ("wl-paste", &["--no-newline"]),
+            ("xclip", &["-selection", "clipboard", "-o"]),
+            ("xsel", &["--clipboard", "--output"]),
+        ]
+    };
+    candidates.iter().find_map(|(program, args)| {
+        let output = Command::new(program).args(*args).output().ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+            .filter(|text| !text.is_empty())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_clipboard() -> Option<String> {
+        None
+    }
+
+    fn vars(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn expand_fills_variables_files_and_clipboard() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        std::fs::write(temp.path().join("notes.txt"), "be strict").expect("write notes");
+        let clipboard = || Some("pasted".to_string());
+        let context = PromptContext {
+            working_dir: Some(temp.path()),
+            clipboard: &clipboard,
+        };
+
+        let expanded = expand(
+            "Review against {{ base }}. {{file:notes.txt}}; {{clipboard}} {{base}} {not} {{}}",
+            &vars(&[("base", "main")]),
+            &context,
+        )
+        .expect("expand");
+        assert_eq!(
+            expanded,
+            "Review against main. be strict; pasted main {not} {{}}"
+        );
+    }
+
+    #[test]
+    fn missing_variables_skips_builtins_and_assigned_values() {
+        let template = "{{branch}} {{diff}} {{file:a.md}} {{base}} {{focus}} {{base}}";
+        assert_eq!(
+            missing_variables(template, &vars(&[("focus", "tests")])),
+            vec!["base".to_string()]
+        );
+
+        let context = PromptContext {
+            working_dir: None,
+            clipboard: &no_clipboard,
+        };
+        assert!(expand("{{base}}", &BTreeMap::new(), &context).is_err());
+        assert!(expand("{{clipboard}}", &BTreeMap::new(), &context).is_err());
+    }
+
+    #[test]
+    fn parse_assignments_requires_name_equals_value() {
+        assert_eq!(
+            parse_assignments(["base=main", "note=a=b"]).expect("parse"),
+            vars(&[("base", "main"), ("note", "a=b")])
+        );
+        assert!(parse_assignments(["base"]).is_err());
+        assert!(parse_assignments(["=main"]).is_err());
+    }
+
+    #[test]
+    fn project_prompts_replace_global_prompts_with_the_same_name() -> Result<()> {
+        let _guard = crate::storage::lock_test_env();
+        let home = tempfile::tempdir()?;
+        let project = tempfile::tempdir()?;
+        crate::env::set_var("JCODE_HOME", home.path());
+
+        save("review", "global review")?;
+        save("standup", "global standup")?;
+        let local_dir = project.path().join(".jcode").join(PROMPTS_DIR);
+        std::fs::create_dir_all(&local_dir)?;
+        std::fs::write(local_dir.join("review.md"), "project review")?;
+
+        let prompts = list(Some(project.path()));
+        let names: Vec<_> = prompts.iter().map(|prompt| prompt.name.as_str()).collect();
+        assert_eq!(names, vec!["review", "standup"]);
+        assert_eq!(
+            find("review", Some(project.path()))?.template,
+            "project review"
+        );
+        assert!(find("missing", Some(project.path())).is_err());
+        assert!(save("../escape", "x").is_err());
+
+        crate::env::remove_var("JCODE_HOME");
+        Ok(())
+    }
+}
@@ -29,39 +29,52 @@ impl StreamingGuard {
     }
 }
 use chrono::{DateTime, Utc};
+use jcode_plan::proposal::SessionPlanMode;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
-use std::path::Path;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+mod alternatives;
+mod aside;
 mod crash;
 mod journal;
 mod maintenance;
+mod markdown;
 mod memory_profile;
 mod model;
 mod persistence;
+mod relocation;
 mod render;
+mod schema;
 mod storage_paths;
 pub use crash::{
-    CrashedSessionsInfo, detect_crashed_sessions, find_recent_crashed_sessions,
-    find_session_by_name_or_id, recover_crashed_sessions, recover_crashed_sessions_by_ids,
+    CrashedSessionsInfo, detect_crashed_sessions, find_latest_cli_run_session,
+    find_recent_crashed_sessions, find_session_by_name_or_id, recover_crashed_sessions,
+    recover_crashed_sessions_by_ids,
 };
 pub use jcode_session_types::{
-    EnvSnapshot, GitState, SessionImproveMode, SessionStatus, StoredCompactionState,
-    StoredDisplayRole, StoredMemoryInjection, StoredMessage, StoredTokenUsage,
+    EnvSnapshot, GitState, ModelRouteFallback, ModelUsageStats, SessionImproveMode,
+    SessionModelRoute, SessionStatus, StoredCompactionState, StoredDisplayRole,
+    StoredMemoryInjection, StoredMessage, StoredTokenUsage,
 };
 use journal::{PersistVectorMode, SessionJournalMeta, SessionPersistState};
 pub use maintenance::prune_old_session_backups;
+pub use markdown::{MAX_TOOL_RESULT_CHARS, MarkdownTranscript, conversation_markdown};
 pub use memory_profile::SessionMemoryProfileSnapshot;
 use memory_profile::{
     ContentBlockMemoryStats, SessionMemoryProfileCache, summarize_blocks, summarize_message_content,
 };
 use model::SESSION_CONTEXT_PREFIX;
-pub use model::{StoredReplayEvent, StoredReplayEventKind};
+pub use model::{SessionAlternative, SessionAside, StoredReplayEvent, StoredReplayEventKind};
+pub use relocation::relocation_candidates;
 pub use render::{
     RenderedCompactedHistoryInfo, RenderedImage, RenderedImageAnchor, RenderedImageSource,
     RenderedMessage, has_rendered_images, is_attached_image_label_text, render_images,
     render_messages, render_messages_and_images, render_messages_and_images_with_compacted_history,
     summarize_tool_calls,
 };
+pub use schema::{
+    SESSION_SCHEMA_VERSION, SessionSchemaStatus, migrate_session, pre_migration_backup_path,
+};
 pub use storage_paths::session_journal_path_from_snapshot;
 #[cfg(test)]
 pub(crate) use storage_paths::session_path_in_dir;
@@ -89,6 +102,9 @@ fn is_visible_conversation_message(message: &StoredMessage) -> bool {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Session {
+    /// On-disk layout version; see [`SESSION_SCHEMA_VERSION`].
+    #[serde(default = "schema::default_schema_version")]
+    pub schema_version: u32,
     pub id: String,
     pub parent_id: Option<String>,
     pub title: Option<String>,
@@ -104,6 +120,20 @@ pub struct Session {
     /// Provider-specific session ID (e.g., Claude Code CLI session for resume)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub provider_session_id: Option<String>,
+    /// Server-side background response still generating the current turn
+    /// (OpenAI Responses background mode), so a restarted jcode re-attaches
+    /// to it instead of generating the turn again.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background_response_id: Option<String>,
+    /// Id of the last message the background response was requested after.
+    /// It is only re-attached to while the history still ends there.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background_response_after: Option<String>,
+    /// Overhead usage made on behalf of this session (subagents, compaction,
+    /// summaries, memory capture), keyed by `UsageOrigin::label`. Interactive
+    /// usage is already on the messages.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub origin_usage: BTreeMap<String, crate::usage::OriginUsage>,
     /// Stable provider/profile key for session-source filtering (e.g. "openai",
     /// "opencode", "opencode-go").
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -115,6 +145,10 @@ pub struct Session {
     /// "openai-compatible:nvidia-nim", "openai-api").
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub route_api_method: Option<String>,
+    /// Full route for `model`, including any upstream provider pin, so resume
+    /// restores `model@provider` rather than just the bare model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_route: Option<SessionModelRoute>,
     /// Provider reasoning/thinking effort for this session (e.g., OpenAI low|medium|high|xhigh).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub reasoning_effort: Option<String>,
@@ -130,6 +164,20 @@ pub struct Session {
     /// Whether automatic end-of-turn judging is enabled for this session.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub autojudge_enabled: Option<bool>,
+    /// Plan-mode state and the latest proposed plan for this session.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plan_mode: Option<SessionPlanMode>,
+    /// Named `[profiles.<name>]` preset active for this session.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// `jcode --safe`: approvals for risky tools, sandboxed bash, no memory
+    /// writes or ambient scheduling, and a turn cap. Kept across resume.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub safe_mode: bool,
+    /// Where the session was started, when that matters for listing it
+    /// (e.g. [`SESSION_ORIGIN_CLI_RUN`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<String>,
     /// Whether this session is a canary session (testing new builds)
     #[serde(default)]
     pub is_canary: bool,
@@ -139,6 +187,25 @@ pub struct Session {
     /// Working directory (for self-dev detection)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub working_dir: Option<String>,
+    /// Additional workspace roots beyond `working_dir` (multi-repo sessions)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub workspace_roots: Vec<String>,
+    /// Directory tools run in when `/cd` scoped them to a subdirectory, e.g.
+    /// one package of a monorepo. `None` means `working_dir`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_scope: Option<String>,
+    /// Previous `working_dir` when the project was moved or renamed and the
+    /// session was pointed at its new location on resume.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relocated_from: Option<String>,
+    /// Standing instructions set with `/instructions`. Sent verbatim in the
+    /// system prompt every turn, so compaction never drops them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+    /// Markdown file `/mirror` keeps rewriting with the live transcript, so
+    /// resume picks the mirror back up.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mirror_path: Option<String>,
     /// Memorable short name (e.g., "fox", "oak")
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub short_name: Option<String>,
@@ -169,6 +236,13 @@ pub struct Session {
     /// Non-conversation UI/state events persisted for higher-fidelity replay.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub replay_events: Vec<StoredReplayEvent>,
+    /// `/aside` scratch threads, kept apart from `messages` so they never
+    /// reach the provider and exports can include or leave them out.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub asides: Vec<SessionAside>,
+    /// `/compare` answers that were not kept, next to the one that was.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alternatives: Vec<SessionAlternative>,
     #[serde(skip)]
     persist_state: SessionPersistState,
     #[serde(skip)]
@@ -183,10 +257,22 @@ pub struct Session {
     memory_profile_cache: SessionMemoryProfileCache,
     #[serde(skip)]
     memory_profile_dirty: bool,
+    /// Set when loaded from a newer schema; saves are skipped.
+    #[serde(skip)]
+    read_only_reason: Option<String>,
+    /// Set for in-memory sessions (such as an aside's child agent) that must
+    /// never be written to disk.
+    #[serde(skip)]
+    ephemeral: bool,
+    /// Tool directory before the last `/cd`, for `/cd -`.
+    #[serde(skip)]
+    previous_tool_dir: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 struct SessionStartupStub {
+    #[serde(default = "schema::default_schema_version")]
+    schema_version: u32,
     id: String,
     #[serde(default)]
     parent_id: Option<String>,
@@ -201,12 +287,20 @@ struct SessionStartupStub {
     #[serde(default)]
     provider_session_id: Option<String>,
     #[serde(default)]
+    background_response_id: Option<String>,
+    #[serde(default)]
+    background_response_after: Option<String>,
+    #[serde(default)]
+    origin_usage: BTreeMap<String, crate::usage::OriginUsage>,
+    #[serde(default)]
     provider_key: Option<String>,
     #[serde(default)]
     model: Option<String>,
     #[serde(default)]
     route_api_method: Option<String>,
     #[serde(default)]
+    model_route: Option<SessionModelRoute>,
+    #[serde(default)]
     reasoning_effort: Option<String>,
     #[serde(default)]
     subagent_model: Option<String>,
@@ -217,12 +311,30 @@ struct SessionStartupStub {
     #[serde(default)]
     autojudge_enabled: Option<bool>,
     #[serde(default)]
+    plan_mode: Option<SessionPlanMode>,
+    #[serde(default)]
+    profile: Option<String>,
+    #[serde(default)]
+    safe_mode: bool,
+    #[serde(default)]
+    origin: Option<String>,
+    #[serde(default)]
     is_canary: bool,
     #[serde(default)]
     testing_build: Option<String>,
     #[serde(default)]
     working_dir: Option<String>,
     #[serde(default)]
+    workspace_roots: Vec<String>,
+    #[serde(default)]
+    tool_scope: Option<String>,
+    #[serde(default)]
+    relocated_from: Option<String>,
+    #[serde(default)]
+    instructions: Option<String>,
+    #[serde(default)]
+    mirror_path: Option<String>,
+    #[serde(default)]
     short_name: Option<String>,
     #[serde(default)]
     status: SessionStatus,
@@ -243,12 +355,40 @@ const MAX_SESSION_JOURNAL_BYTES: u64 = 512 * 1024;
 /// Max number of environment snapshots to retain per session
 const MAX_ENV_SNAPSHOTS: usize = 8;
 
+/// Extra workspace roots passed on the command line (`--root`), as a
+/// platform path list.
+pub const WORKSPACE_ROOTS_ENV: &str = "JCODE_WORKSPACE_ROOTS";
+
+/// [`Session::origin`] of sessions created by `jcode run --continue`,
+/// `--session` or `--new`.
+pub const SESSION_ORIGIN_CLI_RUN: &str = "cli-run";
+
+/// [`Session::origin`] of sessions converted from another CLI's transcript
+/// (Claude Code, Codex, ...).
+pub const SESSION_ORIGIN_IMPORTED: &str = "imported";
+
+/// Extra workspace roots requested at startup via [`WORKSPACE_ROOTS_ENV`].
+pub fn startup_workspace_roots() -> Vec<String> {
+    std::env::var_os(WORKSPACE_ROOTS_ENV)
+        .map(|value| {
+            std::env::split_paths(&value)
+                .filter(|path| !path.as_os_str().is_empty())
+                .map(|path| path.to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 fn current_working_dir_string() -> Option<String> {
     std::env::current_dir()
         .ok()
         .map(|p| p.to_string_lossy().to_string())
 }
 
+fn workspace_root_name_of(root: &str) -> Option<String> {
+    jcode_tool_core::workspace_root_name(Path::new(root))
+}
+
 fn env_flag_enabled(name: &str) -> bool {
     std::env::var(name)
         .map(|v| {
@@ -262,6 +402,14 @@ fn default_is_test_session() -> bool {
     env_flag_enabled("JCODE_TEST_SESSION")
 }
 
+/// The closing note a `/handoff` leaves in the parent session.
+pub fn handoff_notice_text(child_session_id: &str, child_display_name: &str) -> String {
+    format!(
+        "[Handed off to session {} ({})]\nWork continues there from a summary of this session.",
+        child_display_name, child_session_id
+    )
+}
+
 pub fn derive_session_provider_key(provider_name: &str) -> Option<String> {
     let normalized_name = provider_name.trim().to_ascii_lowercase();
     if normalized_name == "jcode" {
@@ -312,17 +460,30 @@ impl Session {
         session.updated_at = stub.updated_at;
         session.compaction = stub.compaction;
         session.provider_session_id = stub.provider_session_id;
+        session.background_response_id = stub.background_response_id;
+        session.background_response_after = stub.background_response_after;
+        session.origin_usage = stub.origin_usage;
         session.provider_key = stub.provider_key;
         session.model = stub.model;
         session.route_api_method = stub.route_api_method;
+        session.model_route = stub.model_route;
         session.reasoning_effort = stub.reasoning_effort;
         session.subagent_model = stub.subagent_model;
         session.improve_mode = stub.improve_mode;
         session.autoreview_enabled = stub.autoreview_enabled;
         session.autojudge_enabled = stub.autojudge_enabled;
+        session.plan_mode = stub.plan_mode;
+        session.profile = stub.profile;
+        session.safe_mode = stub.safe_mode;
+        session.origin = stub.origin;
         session.is_canary = stub.is_canary;
         session.testing_build = stub.testing_build;
         session.working_dir = stub.working_dir;
+        session.workspace_roots = stub.workspace_roots;
+        session.tool_scope = stub.tool_scope;
+        session.relocated_from = stub.relocated_from;
+        session.instructions = stub.instructions;
+        session.mirror_path = stub.mirror_path;
         session.short_name = stub.short_name;
         session.status = stub.status;
         session.last_pid = stub.last_pid;
@@ -330,6 +491,9 @@ impl Session {
         session.is_debug = stub.is_debug;
         session.saved = stub.saved;
         session.save_label = stub.save_label;
+        if stub.schema_version > SESSION_SCHEMA_VERSION {
+            session.mark_newer_schema(stub.schema_version);
+        }
         session.messages.clear();
         session.env_snapshots.clear();
         session.memory_injections.clear();
@@ -347,17 +511,30 @@ impl Session {
         session.messages = snapshot.messages;
         session.compaction = snapshot.compaction;
         session.provider_session_id = snapshot.provider_session_id;
+        session.background_response_id = snapshot.background_response_id;
+        session.background_response_after = snapshot.background_response_after;
+        session.origin_usage = snapshot.origin_usage;
         session.provider_key = snapshot.provider_key;
         session.model = snapshot.model;
         session.route_api_method = snapshot.route_api_method;
+        session.model_route = snapshot.model_route;
         session.reasoning_effort = snapshot.reasoning_effort;
         session.subagent_model = snapshot.subagent_model;
         session.improve_mode = snapshot.improve_mode;
         session.autoreview_enabled = snapshot.autoreview_enabled;
         session.autojudge_enabled = snapshot.autojudge_enabled;
+        session.plan_mode = snapshot.plan_mode;
+        session.profile = snapshot.profile;
+        session.safe_mode = snapshot.safe_mode;
+        session.origin = snapshot.origin;
         session.is_canary = snapshot.is_canary;
         session.testing_build = snapshot.testing_build;
         session.working_dir = snapshot.working_dir;
+        session.workspace_roots = snapshot.workspace_roots;
+        session.tool_scope = snapshot.tool_scope;
+        session.relocated_from = snapshot.relocated_from;
+        session.instructions = snapshot.instructions;
+        session.mirror_path = snapshot.mirror_path;
         session.short_name = snapshot.short_name;
         session.status = snapshot.status;
         session.last_pid = snapshot.last_pid;
@@ -365,6 +542,9 @@ impl Session {
         session.is_debug = snapshot.is_debug;
         session.saved = snapshot.saved;
         session.save_label = snapshot.save_label;
+        if snapshot.schema_version > SESSION_SCHEMA_VERSION {
+            session.mark_newer_schema(snapshot.schema_version);
+        }
         session.replay_events.clear();
         session.env_snapshots.clear();
         session.memory_injections.clear();
@@ -485,6 +665,9 @@ impl Session {
             updated_at: self.updated_at,
             compaction: self.compaction.clone(),
             provider_session_id: self.provider_session_id.clone(),
+            background_response_id: self.background_response_id.clone(),
+            background_response_after: self.background_response_after.clone(),
+            origin_usage: self.origin_usage.clone(),
             provider_key: self.provider_key.clone(),
             model: self.model.clone(),
             reasoning_effort: self.reasoning_effort.clone(),
@@ -492,9 +675,18 @@ impl Session {
             improve_mode: self.improve_mode,
             autoreview_enabled: self.autoreview_enabled,
             autojudge_enabled: self.autojudge_enabled,
+            plan_mode: self.plan_mode.clone(),
+            profile: self.profile.clone(),
+            safe_mode: self.safe_mode,
+            origin: self.origin.clone(),
             is_canary: self.is_canary,
             testing_build: self.testing_build.clone(),
             working_dir: self.working_dir.clone(),
+            workspace_roots: self.workspace_roots.clone(),
+            tool_scope: self.tool_scope.clone(),
+            relocated_from: self.relocated_from.clone(),
+            instructions: self.instructions.clone(),
+            mirror_path: self.mirror_path.clone(),
             short_name: self.short_name.clone(),
             status: self.status.clone(),
             last_pid: self.last_pid,
@@ -516,6 +708,8 @@ impl Session {
             env_snapshots_mode: PersistVectorMode::Clean,
             memory_injections_mode: PersistVectorMode::Clean,
             replay_events_mode: PersistVectorMode::Clean,
+            asides_dirty: false,
+            alternatives_dirty: false,
             last_meta: Some(self.journal_meta()),
         };
     }
@@ -669,6 +863,9 @@ impl Session {
         self.updated_at = meta.updated_at;
         self.compaction = meta.compaction;
         self.provider_session_id = meta.provider_session_id;
+        self.background_response_id = meta.background_response_id;
+        self.background_response_after = meta.background_response_after;
+        self.origin_usage = meta.origin_usage;
         self.provider_key = meta.provider_key;
         self.model = meta.model;
         self.reasoning_effort = meta.reasoning_effort;
@@ -676,9 +873,18 @@ impl Session {
         self.improve_mode = meta.improve_mode;
         self.autoreview_enabled = meta.autoreview_enabled;
         self.autojudge_enabled = meta.autojudge_enabled;
+        self.plan_mode = meta.plan_mode;
+        self.profile = meta.profile;
+        self.safe_mode = meta.safe_mode;
+        self.origin = meta.origin;
         self.is_canary = meta.is_canary;
         self.testing_build = meta.testing_build;
         self.working_dir = meta.working_dir;
+        self.workspace_roots = meta.workspace_roots;
+        self.tool_scope = meta.tool_scope;
+        self.relocated_from = meta.relocated_from;
+        self.instructions = meta.instructions;
+        self.mirror_path = meta.mirror_path;
         self.short_name = meta.short_name;
         self.status = meta.status;
         self.last_pid = meta.last_pid;
@@ -699,6 +905,7 @@ impl Session {
         // Try to extract short name from ID if it's a memorable ID
         let short_name = extract_session_name(&session_id).map(|s| s.to_string());
         let mut session = Self {
+            schema_version: SESSION_SCHEMA_VERSION,
             id: session_id,
             parent_id,
             title,
@@ -708,17 +915,30 @@ impl Session {
             messages: Vec::new(),
             compaction: None,
             provider_session_id: None,
+            background_response_id: None,
+            background_response_after: None,
+            origin_usage: BTreeMap::new(),
             provider_key: None,
             model: None,
             route_api_method: None,
+            model_route: None,
             reasoning_effort: None,
             subagent_model: None,
             improve_mode: None,
             autoreview_enabled: None,
             autojudge_enabled: None,
+            plan_mode: None,
+            profile: None,
+            safe_mode: false,
+            origin: None,
             is_canary: false,
             testing_build: None,
             working_dir: current_working_dir_string(),
+            workspace_roots: Vec::new(),
+            tool_scope: None,
+            relocated_from: None,
+            instructions: None,
+            mirror_path: None,
             short_name,
             status: SessionStatus::Active,
             last_pid: Some(std::process::id()),
@@ -729,6 +949,8 @@ impl Session {
             env_snapshots: Vec::new(),
             memory_injections: Vec::new(),
             replay_events: Vec::new(),
+            asides: Vec::new(),
+            alternatives: Vec::new(),
             persist_state: SessionPersistState::default(),
             provider_messages_cache: Vec::new(),
             provider_message_prefix_hashes_cache: Vec::new(),
@@ -736,6 +958,9 @@ impl Session {
             provider_messages_cache_mode: PersistVectorMode::Full,
             memory_profile_cache: SessionMemoryProfileCache::default(),
             memory_profile_dirty: false,
+            read_only_reason: None,
+            ephemeral: false,
+            previous_tool_dir: None,
         };
         session.reset_persist_state(false);
         session
@@ -746,6 +971,7 @@ impl Session {
         let (id, short_name) = new_memorable_session_id();
         let is_debug = default_is_test_session();
         let mut session = Self {
+            schema_version: SESSION_SCHEMA_VERSION,
             id,
             parent_id,
             title,
@@ -755,17 +981,30 @@ impl Session {
             messages: Vec::new(),
             compaction: None,
             provider_session_id: None,
+            background_response_id: None,
+            background_response_after: None,
+            origin_usage: BTreeMap::new(),
             provider_key: None,
             model: None,
             route_api_method: None,
+            model_route: None,
             reasoning_effort: None,
             subagent_model: None,
             improve_mode: None,
             autoreview_enabled: None,
             autojudge_enabled: None,
+            plan_mode: None,
+            profile: None,
+            safe_mode: false,
+            origin: None,
             is_canary: false,
             testing_build: None,
             working_dir: current_working_dir_string(),
+            workspace_roots: Vec::new(),
+            tool_scope: None,
+            relocated_from: None,
+            instructions: None,
+            mirror_path: None,
             short_name: Some(short_name),
             status: SessionStatus::Active,
             last_pid: Some(std::process::id()),
@@ -776,6 +1015,8 @@ impl Session {
             env_snapshots: Vec::new(),
             memory_injections: Vec::new(),
             replay_events: Vec::new(),
+            asides: Vec::new(),
+            alternatives: Vec::new(),
             persist_state: SessionPersistState::default(),
             provider_messages_cache: Vec::new(),
             provider_message_prefix_hashes_cache: Vec::new(),
@@ -783,6 +1024,9 @@ impl Session {
             provider_messages_cache_mode: PersistVectorMode::Full,
             memory_profile_cache: SessionMemoryProfileCache::default(),
             memory_profile_dirty: false,
+            read_only_reason: None,
+            ephemeral: false,
+            previous_tool_dir: None,
         };
         session.reset_persist_state(false);
         session
@@ -819,6 +1063,160 @@ impl Session {
         self.updated_at = Utc::now();
     }
 
+    /// Add an extra workspace root so this session spans several repositories.
+    /// `~/` expands to the home directory and relative paths resolve against the
+    /// session working directory. Returns the canonical root path that was stored.
+    pub fn add_workspace_root(&mut self, path: &str) -> anyhow::Result<String> {
+        let path = path.trim();
+        let raw = match (path.strip_prefix("~/"), dirs::home_dir()) {
+            (Some(rest), Some(home)) => home.join(rest),
+            _ => PathBuf::from(path),
+        };
+        let joined = match self.working_dir.as_deref() {
+            Some(base) if raw.is_relative() => Path::new(base).join(&raw),
+            _ => raw,
+        };
+        let canonical = std::fs::canonicalize(&joined)
+            .map_err(|err| anyhow::anyhow!("{}: {}", joined.display(), err))?;
+        if !canonical.is_dir() {
+            anyhow::bail!("{} is not a directory", canonical.display());
+        }
+        let Some(name) = jcode_tool_core::workspace_root_name(&canonical) else {
+            anyhow::bail!("{} has no usable root name", canonical.display());
+        };
+        let root = canonical.to_string_lossy().to_string();
+        if self.working_dir.as_deref() == Some(root.as_str())
+            || self.workspace_roots.contains(&root)
+        {
+            return Ok(root);
+        }
+        if self
+            .workspace_roots
+            .iter()
+            .any(|existing| workspace_root_name_of(existing).as_deref() == Some(name.as_str()))
+        {
+            anyhow::bail!("a workspace root named '{}' is already attached", name);
+        }
+        self.workspace_roots.push(root.clone());
+        self.note_workspace_roots_changed();
+        Ok(root)
+    }
+
+    /// Attach the extra workspace roots requested at startup (`--root`).
+    pub fn apply_startup_workspace_roots(&mut self) {
+        for root in startup_workspace_roots() {
+            if let Err(err) = self.add_workspace_root(&root) {
+                crate::logging::warn(&format!("Ignoring workspace root {}: {}", root, err));
+            }
+        }
+    }
+
+    /// Detach an extra workspace root by root name or path. Returns the removed
+    /// root path, if any matched.
+    pub fn remove_workspace_root(&mut self, name_or_path: &str) -> Option<String> {
+        let needle = name_or_path.trim().trim_end_matches(['/', '\\']);
+        let index = self.workspace_roots.iter().position(|root| {
+            root == needle || workspace_root_name_of(root).as_deref() == Some(needle)
+        })?;
+        let removed = self.workspace_roots.remove(index);
+        self.note_workspace_roots_changed();
+        Some(removed)
+    }
+
+    /// Make a workspace root change visible to the model. Before the
+    /// conversation starts the initial context is rebuilt; afterwards a system
+    /// reminder is appended so earlier context is never rewritten.
+    fn note_workspace_roots_changed(&mut self) {
+        self.updated_at = Utc::now();
+        if !self.messages.iter().any(is_visible_conversation_message) {
+            self.refresh_initial_session_context_message();
+            return;
+        }
+
+        let roots: Vec<PathBuf> = self.workspace_roots.iter().map(PathBuf::from).collect();
+        let context = crate::prompt::build_workspace_roots_context(
+            self.working_dir.as_deref().map(Path::new),
+            &roots,
+        )
+        .unwrap_or_else(|| {
+            "Workspace roots: only the working directory remains attached.".to_string()
+        });
+        self.add_message_with_display_role(
+            Role::User,
+            vec![ContentBlock::Text {
+                text: format!("<system-reminder>\n{}\n</system-reminder>", context),
+                cache_control: None,
+            }],
+            Some(StoredDisplayRole::System),
+        );
+    }
+
+    /// Directory tools run in: the `/cd` scope, else the working directory.
+    pub fn tool_working_dir(&self) -> Option<&str> {
+        self.tool_scope.as_deref().or(self.working_dir.as_deref())
+    }
+
+    /// Scope tools to a directory inside the working directory or an attached
+    /// workspace root (`/cd`). `~/` expands to the home directory, relative
+    /// paths resolve against the current tool directory and `-` returns to the
+    /// previous one. Returns the canonical directory tools now run in.
+    pub fn scope_tools(&mut self, path: &str) -> anyhow::Result<String> {
+        let path = path.trim();
+        let path = if path == "-" {
+            self.previous_tool_dir
+                .clone()
+                .ok_or_else(|| anyhow::anyhow!("no previous tool directory"))?
+        } else {
+            path.to_string()
+        };
+        let raw = match (path.strip_prefix("~/"), dirs::home_dir()) {
+            (Some(rest), Some(home)) => home.join(rest),
+            _ => PathBuf::from(&path),
+        };
+        let joined = match self.tool_working_dir() {
+            Some(base) if raw.is_relative() => Path::new(base).join(&raw),
+            _ => raw,
+        };
+        let canonical = std::fs::canonicalize(&joined)
+            .map_err(|err| anyhow::anyhow!("{}: {}", joined.display(), err))?;
+        if !canonical.is_dir() {
+            anyhow::bail!("{} is not a directory", canonical.display());
+        }
+        let canonical_working_dir = self
+            .working_dir
+            .as_deref()
+            .and_then(|dir| std::fs::canonicalize(dir).ok());
+        let inside_workspace = canonical_working_dir
+            .iter()
+            .cloned()
+            .chain(self.workspace_roots.iter().map(PathBuf::from))
+            .any(|root| canonical.starts_with(root));
+        if !inside_workspace {
+            anyhow::bail!(
+                "{} is outside the working directory and workspace roots",
+                canonical.display()
+            );
+        }
+
+        let dir = canonical.to_string_lossy().to_string();
+        let previous = self.tool_working_dir().map(str::to_string);
+        self.tool_scope =
+            (canonical_working_dir.as_deref() != Some(canonical.as_path())).then(|| dir.clone());
+        if previous.as_deref() != Some(dir.as_str()) {
+            self.previous_tool_dir = previous;
+        }
+        self.updated_at = Utc::now();
+        Ok(dir)
+    }
+
+    /// Drop the `/cd` scope so tools run in the working directory again.
+    pub fn clear_tool_scope(&mut self) {
+        if let Some(scope) = self.tool_scope.take() {
+            self.previous_tool_dir = Some(scope);
+            self.updated_at = Utc::now();
+        }
+    }
+
     /// Get the title users should see for this session: custom rename first,
     /// then the generated/imported title, if one exists.
     pub fn display_title(&self) -> Option<&str> {
@@ -877,8 +1275,7 @@ impl Session {
             self.working_dir = Some(current_dir);
         }
 
-        let context =
-            crate::prompt::build_session_context(self.working_dir.as_deref().map(Path::new));
+        let context = self.build_session_context();
         let wrapped = format!("<system-reminder>\n{}\n</system-reminder>", context.trim());
         self.add_message_with_display_role(
             Role::User,
@@ -891,6 +1288,14 @@ impl Session {
         true
     }
 
+    fn build_session_context(&self) -> String {
+        let roots: Vec<PathBuf> = self.workspace_roots.iter().map(PathBuf::from).collect();
+        crate::prompt::build_session_context_with_roots(
+            self.working_dir.as_deref().map(Path::new),
+            &roots,
+        )
+    }
+
     /// Refresh the initial immutable session-context message if the session has
     /// not started a real conversation yet. This covers remote/client-server
     /// startup where the server creates an Agent before the subscribing client
@@ -909,8 +1314,7 @@ impl Session {
             return false;
         };
 
-        let context =
-            crate::prompt::build_session_context(self.working_dir.as_deref().map(Path::new));
+        let context = self.build_session_context();
         let wrapped = format!("<system-reminder>\n{}\n</system-reminder>", context.trim());
         for block in &mut message.content {
             if let ContentBlock::Text { text, .. } = block
@@ -966,6 +1370,43 @@ request in this new forked session, using the inherited conversation only as con
         );
     }
 
+    /// Seed a `/handoff` child with the edited summary of its parent session.
+    /// Shown as a system card and sent to the model as the starting context.
+    pub fn append_handoff_context(
+        &mut self,
+        parent_session_id: &str,
+        parent_display_name: &str,
+        summary: &str,
+    ) {
+        let text = format!(
+            "[Handoff from session {} ({})]\n{}",
+            parent_display_name,
+            parent_session_id,
+            summary.trim()
+        );
+        self.add_message_with_display_role(
+            Role::User,
+            vec![ContentBlock::Text {
+                text,
+                cache_control: None,
+            }],
+            Some(StoredDisplayRole::System),
+        );
+    }
+
+    /// Close a session that was handed off with a note pointing at the child.
+    pub fn append_handoff_notice(&mut self, child_session_id: &str, child_display_name: &str) {
+        let text = handoff_notice_text(child_session_id, child_display_name);
+        self.add_message_with_display_role(
+            Role::User,
+            vec![ContentBlock::Text {
+                text,
+                cache_control: None,
+            }],
+            Some(StoredDisplayRole::System),
+        );
+    }
+
     /// Mark this session as a canary tester
     pub fn set_canary(&mut self, build_hash: &str) {
         self.is_canary = true;
@@ -1073,30 +1514,19 @@ request in this new forked session, using the inherited conversation only as con
             compaction.summary_text = crate::message::redact_secrets(&compaction.summary_text);
         }
         for msg in &mut redacted.messages {
-            for block in &mut msg.content {
-                match block {
-                    ContentBlock::Text { text, .. }
-                    | ContentBlock::Reasoning { text }
-                    | ContentBlock::ReasoningTrace { text } => {
-                        *text = crate::message::redact_secrets(text);
-                    }
-                    ContentBlock::AnthropicThinking { thinking, .. } => {
-                        *thinking = crate::message::redact_secrets(thinking);
-                    }
-                    ContentBlock::OpenAIReasoning { summary, .. } => {
-                        for item in summary {
-                            *item = crate::message::redact_secrets(item);
-                        }
-                    }
-                    ContentBlock::ToolResult { content, .. } => {
-                        *content = crate::message::redact_secrets(content);
-                    }
-                    ContentBlock::ToolUse { input, .. } => redact_json_value(input),
-                    ContentBlock::Image { .. } => {}
-                    ContentBlock::OpenAICompaction { .. } => {}
-                }
+            redact_stored_message(msg);
+        }
+        for aside in &mut redacted.asides {
+            for msg in &mut aside.messages {
+                redact_stored_message(msg);
+            }
+            if let Some(summary) = aside.summary.as_mut() {
+                *summary = crate::message::redact_secrets(summary);
             }
         }
+        for alternative in &mut redacted.alternatives {
+            alternative.run.text = crate::message::redact_secrets(&alternative.run.text);
+        }
         for event in &mut redacted.replay_events {
             match &mut event.kind {
                 StoredReplayEventKind::DisplayMessage { title, content, .. } => {
@@ -1120,6 +1550,14 @@ request in this new forked session, using the inherited conversation only as con
                         item.content = crate::message::redact_secrets(&item.content);
                     }
                 }
+                StoredReplayEventKind::AutoDebug {
+                    error_signature,
+                    diagnosis,
+                    ..
+                } => {
+                    *error_signature = crate::message::redact_secrets(error_signature);
+                    *diagnosis = crate::message::redact_secrets(diagnosis);
+                }
             }
         }
         redacted
@@ -1147,10 +1585,97 @@ request in this new forked session, using the inherited conversation only as con
             totals.cache_creation_input_tokens = totals
                 .cache_creation_input_tokens
                 .saturating_add(usage.cache_creation_input_tokens.unwrap_or(0));
+            totals.web_search_requests = totals
+                .web_search_requests
+                .saturating_add(usage.web_search_requests.unwrap_or(0));
+            totals.pruned_context_tokens = totals
+                .pruned_context_tokens
+                .saturating_add(usage.pruned_context_tokens.unwrap_or(0));
+            totals.redactions = totals
+                .redactions
+                .saturating_add(usage.redactions.unwrap_or(0));
+        }
+        for usage in self.origin_usage.values() {
+            totals.overhead_input_tokens = totals
+                .overhead_input_tokens
+                .saturating_add(usage.input_tokens);
+            totals.overhead_output_tokens = totals
+                .overhead_output_tokens
+                .saturating_add(usage.output_tokens);
         }
         totals
     }
 
+    /// Fold overhead usage queued by `usage::attribute_to_session` into
+    /// `origin_usage`.
+    pub fn apply_pending_origin_usage(&mut self) {
+        for (label, usage) in crate::usage::take_pending_origin_usage(&self.id) {
+            self.origin_usage.entry(label).or_default().add(usage);
+        }
+    }
+
+    /// Per-origin usage for `/session stats`: the session's own turns as
+    /// "interactive", then every overhead origin.
+    pub fn origin_usage_stats(&self) -> BTreeMap<String, crate::usage::OriginUsage> {
+        let mut stats = self.origin_usage.clone();
+        let interactive = self
+            .messages
+            .iter()
+            .filter_map(|message| message.token_usage.as_ref())
+            .fold(crate::usage::OriginUsage::default(), |mut total, usage| {
+                total.add(crate::usage::OriginUsage::new(
+                    1,
+                    usage.input_tokens,
+                    usage.output_tokens,
+                ));
+                total
+            });
+        if interactive.calls > 0 {
+            stats.insert(
+                crate::usage::UsageOrigin::INTERACTIVE.to_string(),
+                interactive,
+            );
+        }
+        stats
+    }
+
+    /// Per-model usage for `/session stats`, in order of first use.
+    pub fn model_usage_stats(&self) -> Vec<ModelUsageStats> {
+        let mut stats: Vec<ModelUsageStats> = Vec::new();
+        for usage in self
+            .messages
+            .iter()
+            .filter_map(|message| message.token_usage.as_ref())
+        {
+            let index = match stats
+                .iter()
+                .position(|entry| entry.model == usage.model && entry.provider == usage.provider)
+            {
+                Some(index) => index,
+                None => {
+                    stats.push(ModelUsageStats {
+                        model: usage.model.clone(),
+                        provider: usage.provider.clone(),
+                        ..ModelUsageStats::default()
+                    });
+                    stats.len() - 1
+                }
+            };
+            let entry = &mut stats[index];
+            entry.responses += 1;
+            entry.input_tokens = entry.input_tokens.saturating_add(usage.input_tokens);
+            entry.output_tokens = entry.output_tokens.saturating_add(usage.output_tokens);
+            entry.cache_read_input_tokens = entry
+                .cache_read_input_tokens
+                .saturating_add(usage.cache_read_input_tokens.unwrap_or(0));
+            if let Some(duration_ms) = usage.duration_ms {
+                entry.duration_ms = entry.duration_ms.saturating_add(duration_ms);
+                entry.timed_responses += 1;
+            }
+        }
+        stats
+    }
+
     pub fn add_message(&mut self, role: Role, content: Vec<ContentBlock>) -> String {
         self.add_message_ext_with_display_role(role, content, None, None, None)
     }
@@ -1222,15 +1747,55 @@ request in this new forked session, using the inherited conversation only as con
 
     pub fn replace_messages(&mut self, messages: Vec<StoredMessage>) {
         self.messages = messages;
-        self.mark_memory_profile_dirty();
-        self.mark_messages_full_dirty();
+        self.note_history_rewritten();
     }
 
     pub fn truncate_messages(&mut self, len: usize) {
         if len < self.messages.len() {
             self.messages.truncate(len);
-            self.mark_memory_profile_dirty();
-            self.mark_messages_full_dirty();
+            self.note_history_rewritten();
+        }
+    }
+
+    /// Invariant: any rewrite of the stored transcript invalidates the
+    /// provider-side conversation, so the resume ids are dropped and the next
+    /// turn sends the full rebuilt context instead of resuming history jcode no
+    /// longer has.
+    fn note_history_rewritten(&mut self) {
+        self.provider_session_id = None;
+        self.clear_background_response();
+        self.mark_memory_profile_dirty();
+        self.mark_messages_full_dirty();
+    }
+
+    /// Record the background response generating the reply to the current
+    /// history, anchored to the message it follows.
+    pub fn set_background_response(&mut self, response_id: String, after: Option<String>) {
+        self.background_response_id = Some(response_id);
+        self.background_response_after = after;
+    }
+
+    pub fn clear_background_response(&mut self) {
+        self.background_response_id = None;
+        self.background_response_after = None;
+    }
+
+    /// Take the recorded background response if the history still ends at the
+    /// message it was requested after, so the outbound request is the one it
+    /// answers. A stale id (the user sent something else, or the history
+    /// changed) is discarded.
+    pub fn take_resumable_background_response(&mut self) -> Option<String> {
+        let response_id = self.background_response_id.take()?;
+        let after = self.background_response_after.take();
+        let last = self.messages.last().map(|message| &message.id);
+        if after.is_some() && after.as_ref() == last {
+            Some(response_id)
+        } else {
+            crate::logging::info(&format!(
+                "Discarding background response {} for session {}: the history moved on",
+                response_id, self.id
+            ));
+            None
         }
     }
 
@@ -1252,12 +1817,46 @@ request in this new forked session, using the inherited conversation only as con
             target_total_chars,
         );
         if stripped > 0 {
-            self.mark_memory_profile_dirty();
-            self.mark_messages_full_dirty();
+            self.note_history_rewritten();
         }
         stripped
     }
 
+    /// Shorten tool results oldest-first, among messages from `start` onward,
+    /// until those messages fit within `target_total_chars`. Used to recover
+    /// from a context overflow when compaction alone cannot.
+    ///
+    /// Tool results at or after the latest user prompt belong to the turn in
+    /// progress and are left intact. Returns the number of tool results that
+    /// were shortened.
+    pub fn trim_oldest_tool_results(&mut self, start: usize, target_total_chars: usize) -> usize {
+        let start = start.min(self.messages.len());
+        let active = &mut self.messages[start..];
+        let pinned_from = active
+            .iter()
+            .rposition(|message| {
+                message.role == Role::User
+                    && message.display_role.is_none()
+                    && message
+                        .content
+                        .iter()
+                        .any(|block| matches!(block, ContentBlock::Text { .. }))
+            })
+            .unwrap_or(active.len());
+        let mut contents: Vec<&mut Vec<ContentBlock>> =
+            active.iter_mut().map(|m| &mut m.content).collect();
+        let trimmed = jcode_compaction_core::trim_oldest_tool_results_in_contents(
+            &mut contents,
+            pinned_from,
+            target_total_chars,
+            jcode_compaction_core::EMERGENCY_TOOL_RESULT_MAX_CHARS,
+        );
+        if trimmed > 0 {
+            self.note_history_rewritten();
+        }
+        trimmed
+    }
+
     pub fn visible_conversation_message_count(&self) -> usize {
         self.messages
             .iter()
@@ -1344,6 +1943,47 @@ request in this new forked session, using the inherited conversation only as con
         self.mark_replay_events_append_dirty();
     }
 
+    /// Record that the session moved to another binary, so the transcript
+    /// shows where the version changed.
+    pub fn record_migration(&mut self, from_hash: &str, to_hash: &str, reason: &str) {
+        self.record_replay_display_message(
+            "system",
+            Some("Migration".to_string()),
+            format!("Migrated from {} to {}: {}", from_hash, to_hash, reason),
+        );
+    }
+
+    /// Record an auto-debug diagnosis of a repeatedly failing tool.
+    pub fn record_auto_debug_event(
+        &mut self,
+        tool_name: String,
+        failure_count: u32,
+        error_signature: String,
+        diagnosis: String,
+    ) {
+        let event = StoredReplayEvent {
+            timestamp: Utc::now(),
+            kind: StoredReplayEventKind::AutoDebug {
+                tool_name,
+                failure_count,
+                error_signature,
+                diagnosis,
+            },
+        };
+        self.memory_profile_cache.replay_events_count += 1;
+        self.memory_profile_cache.replay_events_json_bytes += estimate_json_bytes(&event);
+        self.replay_events.push(event);
+        self.mark_replay_events_append_dirty();
+    }
+
+    /// Auto-debug analyses recorded in this session.
+    pub fn auto_debug_event_count(&self) -> usize {
+        self.replay_events
+            .iter()
+            .filter(|event| matches!(event.kind, StoredReplayEventKind::AutoDebug { .. }))
+            .count()
+    }
+
     pub fn record_swarm_status_event(&mut self, members: Vec<crate::protocol::SwarmMemberStatus>) {
         let kind = StoredReplayEventKind::SwarmStatus { members };
         if self
@@ -1475,6 +2115,36 @@ request in this new forked session, using the inherited conversation only as con
     }
 }
 
+fn redact_stored_message(msg: &mut StoredMessage) {
+    for block in &mut msg.content {
+        match block {
+            ContentBlock::Text { text, .. }
+            | ContentBlock::Reasoning { text }
+            | ContentBlock::ReasoningTrace { text } => {
+                *text = crate::message::redact_secrets(text);
+            }
+            ContentBlock::AnthropicThinking { thinking, .. } => {
+                *thinking = crate::message::redact_secrets(thinking);
+            }
+            ContentBlock::OpenAIReasoning { summary, .. } => {
+                for item in summary {
+                    *item = crate::message::redact_secrets(item);
+                }
+            }
+            ContentBlock::ToolResult { content, .. } => {
+                *content = crate::message::redact_secrets(content);
+            }
+            ContentBlock::ToolUse { input, .. } | ContentBlock::ServerToolUse { input, .. } => {
+                redact_json_value(input)
+            }
+            ContentBlock::Image { .. } => {}
+            ContentBlock::OpenAICompaction { .. }
+            | ContentBlock::WebSearchResult { .. }
+            | ContentBlock::Citations { .. } => {}
+        }
+    }
+}
+
 fn redact_json_value(value: &mut serde_json::Value) {
     match value {
         serde_json::Value::String(s) => {
@@ -1496,6 +2166,8 @@ fn redact_json_value(value: &mut serde_json::Value) {
 
 #[derive(Debug, Deserialize)]
 struct RemoteStartupSessionSnapshot {
+    #[serde(default = "schema::default_schema_version")]
+    schema_version: u32,
     id: String,
     #[serde(default)]
     parent_id: Option<String>,
@@ -1512,12 +2184,20 @@ struct RemoteStartupSessionSnapshot {
     #[serde(default)]
     provider_session_id: Option<String>,
     #[serde(default)]
+    background_response_id: Option<String>,
+    #[serde(default)]
+    background_response_after: Option<String>,
+    #[serde(default)]
+    origin_usage: BTreeMap<String, crate::usage::OriginUsage>,
+    #[serde(default)]
     provider_key: Option<String>,
     #[serde(default)]
     model: Option<String>,
     #[serde(default)]
     route_api_method: Option<String>,
     #[serde(default)]
+    model_route: Option<SessionModelRoute>,
+    #[serde(default)]
     reasoning_effort: Option<String>,
     #[serde(default)]
     subagent_model: Option<String>,
@@ -1528,12 +2208,30 @@ struct RemoteStartupSessionSnapshot {
     #[serde(default)]
     autojudge_enabled: Option<bool>,
     #[serde(default)]
+    plan_mode: Option<SessionPlanMode>,
+    #[serde(default)]
+    profile: Option<String>,
+    #[serde(default)]
+    safe_mode: bool,
+    #[serde(default)]
+    origin: Option<String>,
+    #[serde(default)]
     is_canary: bool,
     #[serde(default)]
     testing_build: Option<String>,
     #[serde(default)]
     working_dir: Option<String>,
     #[serde(default)]
+    workspace_roots: Vec<String>,
+    #[serde(default)]
+    tool_scope: Option<String>,
+    #[serde(default)]
+    relocated_from: Option<String>,
+    #[serde(default)]
+    instructions: Option<String>,
+    #[serde(default)]
+    mirror_path: Option<String>,
+    #[serde(default)]
     short_name: Option<String>,
     #[serde(default)]
     status: SessionStatus,
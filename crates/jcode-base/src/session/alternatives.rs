@@ -0,0 +1,22 @@
+use chrono::Utc;
+
+use super::{Session, SessionAlternative};
+use crate::protocol::CompareRun;
+
+impl Session {
+    /// Keep the `/compare` answers that lost to the assistant message
+    /// `message_id`.
+    pub fn record_alternatives(&mut self, message_id: &str, runs: Vec<CompareRun>) {
+        if runs.is_empty() {
+            return;
+        }
+        let created_at = Utc::now();
+        self.alternatives
+            .extend(runs.into_iter().map(|run| SessionAlternative {
+                message_id: message_id.to_string(),
+                created_at,
+                run,
+            }));
+        self.persist_state.alternatives_dirty = true;
+    }
+}
@@ -0,0 +1,84 @@
+use chrono::Utc;
+
+use super::{ContentBlock, SESSION_CONTEXT_PREFIX, Session, SessionAside, StoredMessage};
+use crate::id::new_id;
+
+impl Session {
+    /// The aside currently open, if any. Only the last aside can be open.
+    pub fn active_aside(&self) -> Option<&SessionAside> {
+        self.asides.last().filter(|aside| aside.ended_at.is_none())
+    }
+
+    /// Open a new aside and return its id. Returns `None` when one is
+    /// already open.
+    pub fn begin_aside(&mut self) -> Option<String> {
+        if self.active_aside().is_some() {
+            return None;
+        }
+        let id = new_id("aside");
+        self.asides.push(SessionAside {
+            id: id.clone(),
+            started_at: Utc::now(),
+            ended_at: None,
+            messages: Vec::new(),
+            summary: None,
+        });
+        self.persist_state.asides_dirty = true;
+        Some(id)
+    }
+
+    /// Messages to start the open aside's conversation from: its stored
+    /// transcript when resuming, otherwise the main session-context message,
+    /// so the aside sees the same project context.
+    pub fn aside_seed_messages(&self) -> Vec<StoredMessage> {
+        if let Some(aside) = self.active_aside()
+            && !aside.messages.is_empty()
+        {
+            return aside.messages.clone();
+        }
+        self.messages
+            .iter()
+            .find(|message| {
+                message.content.iter().any(|block| {
+                    matches!(block, ContentBlock::Text { text, .. } if text.starts_with(SESSION_CONTEXT_PREFIX))
+                })
+            })
+            .cloned()
+            .into_iter()
+            .collect()
+    }
+
+    /// Replace the transcript of the open aside. No-op when none is open.
+    pub fn set_aside_messages(&mut self, messages: Vec<StoredMessage>) {
+        let Some(aside) = self
+            .asides
+            .last_mut()
+            .filter(|aside| aside.ended_at.is_none())
+        else {
+            return;
+        };
+        aside.messages = messages;
+        self.persist_state.asides_dirty = true;
+    }
+
+    /// Close the open aside, recording the summary handed back to the main
+    /// conversation. Returns false when no aside was open.
+    pub fn end_aside(&mut self, summary: Option<String>) -> bool {
+        let Some(aside) = self
+            .asides
+            .last_mut()
+            .filter(|aside| aside.ended_at.is_none())
+        else {
+            return false;
+        };
+        aside.ended_at = Some(Utc::now());
+        aside.summary = summary;
+        self.persist_state.asides_dirty = true;
+        true
+    }
+
+    /// Keep this session in memory only; `save` becomes a no-op.
+    pub fn set_ephemeral(&mut self) {
+        self.ephemeral = true;
+    }
+}
@@ -1,4 +1,4 @@
-use super::{Session, SessionStatus, active_pids_dir, session_exists};
+use super::{SESSION_ORIGIN_CLI_RUN, Session, SessionStatus, active_pids_dir, session_exists};
 use crate::id::extract_session_name;
 use crate::message::{ContentBlock, Role};
 use crate::storage;
@@ -127,9 +127,15 @@ fn recover_loaded_crashed_sessions(mut crashed: Vec<Session>) -> Result<Vec<Stri
             Session::create_with_id(new_id.clone(), Some(old.id.clone()), old.title.clone());
         new_session.custom_title = old.custom_title.clone();
         new_session.working_dir = old.working_dir.clone();
+        new_session.workspace_roots = old.workspace_roots.clone();
+        new_session.tool_scope = old.tool_scope.clone();
+        new_session.relocated_from = old.relocated_from.clone();
+        new_session.instructions = old.instructions.clone();
+        new_session.mirror_path = old.mirror_path.clone();
         new_session.provider_key = old.provider_key.clone();
         new_session.route_api_method = old.route_api_method.clone();
         new_session.model = old.model.clone();
+        new_session.model_route = old.model_route.clone();
         new_session.improve_mode = old.improve_mode;
         new_session.is_canary = old.is_canary;
         new_session.is_debug = old.is_debug;
@@ -639,6 +645,41 @@ pub fn find_session_by_name_or_id(name_or_id: &str) -> Result<String> {
     Ok(matches[0].0.clone())
 }
 
+/// Most recently updated `jcode run` session (see
+/// [`SESSION_ORIGIN_CLI_RUN`]) started in `working_dir`.
+pub fn find_latest_cli_run_session(working_dir: &str) -> Result<Option<String>> {
+    let sessions_dir = storage::jcode_dir()?.join("sessions");
+    if !sessions_dir.exists() {
+        return Ok(None);
+    }
+
+    let mut latest: Option<(String, DateTime<Utc>)> = None;
+    for entry in std::fs::read_dir(&sessions_dir)? {
+        let path = entry?.path();
+        if !path.extension().map(|e| e == "json").unwrap_or(false) {
+            continue;
+        }
+        let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let Ok(session) = Session::load_startup_stub(stem).or_else(|_| Session::load(stem)) else {
+            continue;
+        };
+        if session.origin.as_deref() != Some(SESSION_ORIGIN_CLI_RUN)
+            || session.working_dir.as_deref() != Some(working_dir)
+        {
+            continue;
+        }
+        if latest
+            .as_ref()
+            .is_none_or(|(_, updated_at)| session.updated_at > *updated_at)
+        {
+            latest = Some((stem.to_string(), session.updated_at));
+        }
+    }
+    Ok(latest.map(|(id, _)| id))
+}
+
 #[cfg(test)]
 mod batch_crash_tests {
     use super::*;
@@ -656,6 +697,46 @@ mod batch_crash_tests {
         assert_eq!(info.display_names[0], "fox");
     }
 
+    #[test]
+    fn find_latest_cli_run_session_matches_origin_and_working_dir() -> anyhow::Result<()> {
+        let _guard = crate::storage::lock_test_env();
+        let temp = tempfile::tempdir()?;
+        crate::env::set_var("JCODE_HOME", temp.path());
+
+        // Saved oldest first; `save` stamps `updated_at`.
+        let save = |id: &str, origin: Option<&str>, dir: &str| {
+            let mut session = Session::create_with_id(id.to_string(), None, None);
+            session.origin = origin.map(str::to_string);
+            session.working_dir = Some(dir.to_string());
+            session.save()
+        };
+        save(
+            "session_runold_1770000000000",
+            Some(SESSION_ORIGIN_CLI_RUN),
+            "/work/a",
+        )?;
+        save(
+            "session_runnew_1770000000001",
+            Some(SESSION_ORIGIN_CLI_RUN),
+            "/work/a",
+        )?;
+        save("session_tui_1770000000002", None, "/work/a")?;
+        save(
+            "session_runother_1770000000003",
+            Some(SESSION_ORIGIN_CLI_RUN),
+            "/work/b",
+        )?;
+
+        assert_eq!(
+            find_latest_cli_run_session("/work/a")?.as_deref(),
+            Some("session_runnew_1770000000001")
+        );
+        assert_eq!(find_latest_cli_run_session("/work/c")?, None);
+
+        crate::env::remove_var("JCODE_HOME");
+        Ok(())
+    }
+
     #[test]
     fn find_session_by_name_or_id_matches_custom_title() {
         let _guard = crate::storage::lock_test_env();
@@ -1,5 +1,7 @@
 use chrono::{DateTime, Utc};
+use jcode_plan::proposal::SessionPlanMode;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 use super::{
     EnvSnapshot, SessionImproveMode, SessionStatus, StoredCompactionState, StoredMemoryInjection,
@@ -15,6 +17,12 @@ pub(super) struct SessionJournalMeta {
     pub(super) updated_at: DateTime<Utc>,
     pub(super) compaction: Option<StoredCompactionState>,
     pub(super) provider_session_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) background_response_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) background_response_after: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub(super) origin_usage: BTreeMap<String, crate::usage::OriginUsage>,
     pub(super) provider_key: Option<String>,
     pub(super) model: Option<String>,
     #[serde(default)]
@@ -23,9 +31,27 @@ pub(super) struct SessionJournalMeta {
     pub(super) improve_mode: Option<SessionImproveMode>,
     pub(super) autoreview_enabled: Option<bool>,
     pub(super) autojudge_enabled: Option<bool>,
+    #[serde(default)]
+    pub(super) plan_mode: Option<SessionPlanMode>,
+    #[serde(default)]
+    pub(super) profile: Option<String>,
+    #[serde(default)]
+    pub(super) safe_mode: bool,
+    #[serde(default)]
+    pub(super) origin: Option<String>,
     pub(super) is_canary: bool,
     pub(super) testing_build: Option<String>,
     pub(super) working_dir: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(super) workspace_roots: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) tool_scope: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) relocated_from: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) instructions: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) mirror_path: Option<String>,
     pub(super) short_name: Option<String>,
     pub(super) status: SessionStatus,
     pub(super) last_pid: Option<u32>,
@@ -67,6 +93,10 @@ pub(super) struct SessionPersistState {
     pub(super) env_snapshots_mode: PersistVectorMode,
     pub(super) memory_injections_mode: PersistVectorMode,
     pub(super) replay_events_mode: PersistVectorMode,
+    /// Asides are not journaled, so any change to them forces a snapshot.
+    pub(super) asides_dirty: bool,
+    /// Same for `/compare` alternatives.
+    pub(super) alternatives_dirty: bool,
     pub(super) last_meta: Option<SessionJournalMeta>,
 }
 
@@ -83,6 +113,10 @@ pub(super) fn metadata_requires_snapshot(
         || prev.improve_mode != current.improve_mode
         || prev.autoreview_enabled != current.autoreview_enabled
         || prev.autojudge_enabled != current.autojudge_enabled
+        || prev.plan_mode != current.plan_mode
+        || prev.profile != current.profile
+        || prev.safe_mode != current.safe_mode
+        || prev.origin != current.origin
         || prev.is_canary != current.is_canary
         || prev.testing_build != current.testing_build
         || prev.working_dir != current.working_dir
@@ -0,0 +1,162 @@
+//! Markdown transcripts. [`conversation_markdown`] renders stored messages in
+//! full, for ambient cycle logs and exports; `/mirror` drives the same
+//! [`MarkdownTranscript`] writer from the TUI's display messages.
+
+use super::StoredMessage;
+use crate::message::{ContentBlock, Role};
+
+/// Tool results longer than this are cut in transcripts.
+pub const MAX_TOOL_RESULT_CHARS: usize = 2000;
+
+/// Builds a markdown transcript one section at a time.
+#[derive(Debug, Default)]
+pub struct MarkdownTranscript {
+    md: String,
+}
+
+impl MarkdownTranscript {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn heading(&mut self, label: &str) {
+        self.md.push_str(&format!("### {}\n\n", label));
+    }
+
+    pub fn paragraph(&mut self, text: &str) {
+        self.md.push_str(text);
+        self.md.push_str("\n\n");
+    }
+
+    pub fn thinking(&mut self, text: &str) {
+        self.md.push_str(&format!("*Thinking:* {}\n\n", text));
+    }
+
+    /// A line in italics, e.g. a model/token footer.
+    pub fn note(&mut self, text: &str) {
+        self.md.push_str(&format!("*{}*\n\n", text));
+    }
+
+    /// A tool call with its full input.
+    pub fn tool_input(&mut self, label: &str, name: &str, input: &serde_json::Value) {
+        let input_str = serde_json::to_string_pretty(input).unwrap_or_else(|_| input.to_string());
+        self.md.push_str(&format!(
+            "**{}: `{}`**\n```json\n{}\n```\n\n",
+            label, name, input_str
+        ));
+    }
+
+    pub fn tool_result(&mut self, is_error: bool, content: &str) {
+        let label = if is_error { "Error" } else { "Result" };
+        self.md.push_str(&format!(
+            "**{}:**\n```\n{}\n```\n\n",
+            label,
+            truncate_result(content)
+        ));
+    }
+
+    /// A tool call folded into a one-line `<details>` summary, with its
+    /// output inside.
+    pub fn tool_summary(&mut self, summary: &str, output: &str) {
+        self.md
+            .push_str(&format!("<details><summary>{}</summary>\n\n", summary));
+        if !output.trim().is_empty() {
+            self.md
+                .push_str(&format!("```\n{}\n```\n\n", truncate_result(output)));
+        }
+        self.md.push_str("</details>\n\n");
+    }
+
+    pub fn finish(self) -> String {
+        self.md
+    }
+}
+
+fn truncate_result(content: &str) -> String {
+    if content.len() > MAX_TOOL_RESULT_CHARS {
+        format!(
+            "{}... (truncated, {} chars total)",
+            crate::util::truncate_str(content, MAX_TOOL_RESULT_CHARS),
+            content.len()
+        )
+    } else {
+        content.to_string()
+    }
+}
+
+/// The full conversation in `messages` as a markdown transcript.
+pub fn conversation_markdown(messages: &[StoredMessage]) -> String {
+    let mut md = MarkdownTranscript::new();
+    for msg in messages {
+        md.heading(match msg.role {
+            Role::User => "User",
+            Role::Assistant => "Assistant",
+        });
+        for block in &msg.content {
+            push_block(&mut md, block);
+        }
+        if let Some(footer) = msg
+            .token_usage
+            .as_ref()
+            .and_then(|usage| usage.summary_line())
+        {
+            md.note(&footer);
+        }
+    }
+    md.finish()
+}
+
+fn push_block(md: &mut MarkdownTranscript, block: &ContentBlock) {
+    match block {
+        ContentBlock::Text { text, .. } => md.paragraph(text),
+        ContentBlock::Reasoning { text } | ContentBlock::ReasoningTrace { text } => {
+            md.thinking(text)
+        }
+        ContentBlock::AnthropicThinking { thinking, .. } => md.thinking(thinking),
+        ContentBlock::OpenAIReasoning { summary, .. } => {
+            if !summary.is_empty() {
+                md.thinking(&summary.join("\n"));
+            }
+        }
+        ContentBlock::ToolUse { name, input, .. } => md.tool_input("Tool", name, input),
+        ContentBlock::ToolResult {
+            content, is_error, ..
+        } => md.tool_result(is_error == &Some(true), content),
+        ContentBlock::Image { .. } => md.paragraph("[Image]"),
+        ContentBlock::OpenAICompaction { .. } => md.paragraph("[OpenAI native compaction]"),
+        ContentBlock::ServerToolUse { name, input, .. } => {
+            md.tool_input("Server tool", name, input)
+        }
+        ContentBlock::WebSearchResult {
+            results,
+            error_code,
+            ..
+        } => md.tool_result(
+            false,
+            &crate::message::web_search_result_text(results, error_code.as_deref()),
+        ),
+        ContentBlock::Citations { citations } => {
+            let sources = crate::message::citation_sources_markdown(citations);
+            if !sources.is_empty() {
+                md.paragraph(sources.trim_start());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tool_summary_folds_long_output() {
+        let mut md = MarkdownTranscript::new();
+        md.heading("Assistant");
+        md.tool_summary("bash · ls", &"x".repeat(MAX_TOOL_RESULT_CHARS + 5));
+        md.tool_summary("read · empty.txt", "");
+        let out = md.finish();
+        assert!(out.starts_with("### Assistant\n\n<details><summary>bash · ls</summary>\n\n```\n"));
+        assert!(out.contains("... (truncated, 2005 chars total)\n```\n\n</details>\n\n"));
+        assert!(out.ends_with("<details><summary>read · empty.txt</summary>\n\n</details>\n\n"));
+    }
+}
@@ -125,6 +125,25 @@ impl ContentBlockMemoryStats {
                 self.openai_compaction_bytes += encrypted_content.len();
                 self.record_bytes(encrypted_content.len());
             }
+            ContentBlock::ServerToolUse { input, .. } => {
+                self.tool_use_blocks += 1;
+                let input_bytes = estimate_json_bytes(input);
+                self.tool_use_input_json_bytes += input_bytes;
+                self.record_bytes(input_bytes);
+            }
+            ContentBlock::WebSearchResult { results, .. } => {
+                let bytes = results.iter().map(|hit| hit.payload_bytes()).sum::<usize>();
+                self.tool_result_blocks += 1;
+                self.tool_result_bytes += bytes;
+                self.max_tool_result_bytes = self.max_tool_result_bytes.max(bytes);
+                self.record_bytes(bytes);
+            }
+            ContentBlock::Citations { citations } => {
+                let bytes = citations.iter().map(|c| c.payload_bytes()).sum::<usize>();
+                self.text_blocks += 1;
+                self.text_bytes += bytes;
+                self.record_bytes(bytes);
+            }
         }
     }
 
@@ -36,6 +36,39 @@ pub enum StoredReplayEventKind {
         #[serde(default, skip_serializing_if = "Option::is_none")]
         reason: Option<String>,
     },
+    /// Diagnosis of a tool that kept failing, injected into the next turn.
+    #[serde(rename = "auto_debug")]
+    AutoDebug {
+        tool_name: String,
+        failure_count: u32,
+        /// Normalized error the failures shared
+        error_signature: String,
+        diagnosis: String,
+    },
 }
 
 pub(super) const SESSION_CONTEXT_PREFIX: &str = "<system-reminder>\n# Session Context";
+
+/// A `/aside` scratch thread. Its messages never join the main conversation;
+/// only the summary, when the user keeps one, is appended there on close.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionAside {
+    pub id: String,
+    pub started_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ended_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub messages: Vec<super::StoredMessage>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+}
+
+/// A `/compare` answer the user did not keep. `message_id` is the assistant
+/// message that was committed in its place.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionAlternative {
+    pub message_id: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub run: crate::protocol::CompareRun,
+}
@@ -6,8 +6,17 @@ use std::time::Instant;
 
 use super::journal::{PersistVectorMode, SessionJournalEntry, metadata_requires_snapshot};
 use super::storage_paths::{file_len_or_zero, session_journal_path_from_snapshot, session_path};
-use super::{MAX_SESSION_JOURNAL_BYTES, RemoteStartupSessionSnapshot, Session, SessionStartupStub};
-use crate::storage;
+use super::{
+    MAX_SESSION_JOURNAL_BYTES, RemoteStartupSessionSnapshot, Session, SessionStartupStub,
+    StoredMessage, is_visible_conversation_message,
+};
+use crate::config::StorageBackend;
+use crate::message::{ContentBlock, Role};
+use crate::storage::{self, SessionListing, SessionWrite, SqliteStore};
+
+/// Transcript text kept in a [`SessionListing`] for picker filtering; matches
+/// the excerpt budget the file-based picker reads from each snapshot.
+const LISTING_SEARCH_TEXT_BYTES: usize = 64 * 1024;
 
 impl Session {
     fn apply_journal_entry(&mut self, entry: SessionJournalEntry) {
@@ -33,7 +42,7 @@ impl Session {
         let load_start = Instant::now();
         let snapshot_bytes = file_len_or_zero(path);
         let snapshot_start = Instant::now();
-        let mut session: Session = storage::read_json(path)?;
+        let (mut session, _) = Self::read_versioned_snapshot(path)?;
         let snapshot_ms = snapshot_start.elapsed().as_millis();
         let journal_path = session_journal_path_from_snapshot(path);
         let journal_bytes = file_len_or_zero(&journal_path);
@@ -108,8 +117,7 @@ impl Session {
     }
 
     pub fn load(session_id: &str) -> Result<Self> {
-        let path = session_path(session_id)?;
-        Self::load_from_path(&path)
+        storage::store().load_session(session_id)
     }
 
     /// Load only the metadata needed for remote-client startup.
@@ -118,6 +126,10 @@ impl Session {
     /// client can paint quickly while the server performs the authoritative
     /// session restore + history bootstrap.
     pub fn load_startup_stub(session_id: &str) -> Result<Self> {
+        let store = storage::store();
+        if store.kind() == StorageBackend::Sqlite {
+            return store.load_session(session_id);
+        }
         let path = session_path(session_id)?;
         let reader = BufReader::new(std::fs::File::open(&path)?);
         let stub: SessionStartupStub = serde_json::from_reader(reader)?;
@@ -125,6 +137,10 @@ impl Session {
     }
 
     pub fn load_for_remote_startup(session_id: &str) -> Result<Self> {
+        let store = storage::store();
+        if store.kind() == StorageBackend::Sqlite {
+            return store.load_session(session_id);
+        }
         let path = session_path(session_id)?;
         let load_start = Instant::now();
         let snapshot_bytes = file_len_or_zero(&path);
@@ -204,7 +220,23 @@ impl Session {
     }
 
     pub fn save(&mut self) -> Result<()> {
+        if let Some(reason) = &self.read_only_reason {
+            crate::logging::warn(&format!(
+                "Not saving read-only session {}: {}",
+                self.id, reason
+            ));
+            return Ok(());
+        }
+        self.apply_pending_origin_usage();
+        if self.ephemeral {
+            return Ok(());
+        }
         self.updated_at = Utc::now();
+        storage::store().save_session(self)
+    }
+
+    /// Snapshot + journal save used by the file store.
+    pub(crate) fn save_to_files(&mut self) -> Result<()> {
         let path = session_path(&self.id)?;
         let journal_path = session_journal_path_from_snapshot(&path);
         let start = std::time::Instant::now();
@@ -221,6 +253,8 @@ impl Session {
             || self.persist_state.env_snapshots_mode == PersistVectorMode::Full
             || self.persist_state.memory_injections_mode == PersistVectorMode::Full
             || self.persist_state.replay_events_mode == PersistVectorMode::Full
+            || self.persist_state.asides_dirty
+            || self.persist_state.alternatives_dirty
             || self.messages.len() < self.persist_state.messages_len
             || self.env_snapshots.len() < self.persist_state.env_snapshots_len
             || self.memory_injections.len() < self.persist_state.memory_injections_len
@@ -393,4 +427,152 @@ impl Session {
         }
         result
     }
+
+    /// Write a full snapshot to the session file, dropping any journal.
+    pub(crate) fn write_file_snapshot(&mut self) -> Result<()> {
+        let path = session_path(&self.id)?;
+        let journal_path = session_journal_path_from_snapshot(&path);
+        self.checkpoint_snapshot(&path, &journal_path)
+    }
+
+    /// Save into the SQLite store, appending only messages added since the
+    /// last save unless history was rewritten in place.
+    pub(crate) fn save_to_sqlite(&mut self, store: &SqliteStore) -> Result<()> {
+        let start = Instant::now();
+        let append_from = (self.persist_state.snapshot_exists
+            && self.persist_state.messages_mode != PersistVectorMode::Full
+            && self.messages.len() >= self.persist_state.messages_len)
+            .then_some(self.persist_state.messages_len);
+        let messages = std::mem::take(&mut self.messages);
+        let meta_json = serde_json::to_string(self);
+        self.messages = messages;
+        let listing = self.listing();
+        let result = store.write_session(&SessionWrite {
+            listing: &listing,
+            meta_json: &meta_json?,
+            messages: &self.messages,
+            append_from,
+        });
+        if result.is_ok() {
+            self.reset_persist_state(true);
+        }
+        let result_label = if result.is_ok() { "ok" } else { "error" };
+        let save_mode = if append_from.is_some() {
+            "append"
+        } else {
+            "rewrite"
+        };
+        let mut fields = vec![
+            ("phase", "save_done".to_string()),
+            ("session_id", self.id.clone()),
+            ("backend", StorageBackend::Sqlite.as_str().to_string()),
+            ("result", result_label.to_string()),
+            ("save_mode", save_mode.to_string()),
+            ("messages", self.messages.len().to_string()),
+            ("elapsed_ms", start.elapsed().as_millis().to_string()),
+        ];
+        if let Err(error) = &result {
+            fields.push(("error", crate::util::format_error_chain(error)));
+            crate::logging::event_warn("SESSION_PERSISTENCE", fields);
+        } else {
+            crate::logging::event_info("SESSION_PERSISTENCE", fields);
+        }
+        result
+    }
+
+    /// Rebuild a session from the SQLite store's metadata row and messages.
+    pub(crate) fn from_stored_rows(meta_json: &str, messages: Vec<StoredMessage>) -> Result<Self> {
+        let mut value: serde_json::Value = serde_json::from_str(meta_json)?;
+        let version = value
+            .get("schema_version")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or_default();
+        if let Some(object) = value.as_object_mut() {
+            object.insert("messages".to_string(), serde_json::Value::Array(Vec::new()));
+        }
+        let mut session: Session = serde_json::from_value(value)?;
+        session.messages = messages;
+        if version > u64::from(super::SESSION_SCHEMA_VERSION) {
+            session.mark_newer_schema(version as u32);
+        }
+        session.reset_persist_state(true);
+        session.reset_provider_messages_cache();
+        session.mark_memory_profile_dirty();
+        Ok(session)
+    }
+
+    /// Picker summary of this session, as stored by indexed backends.
+    pub fn listing(&self) -> SessionListing {
+        let mut listing = SessionListing {
+            id: self.id.clone(),
+            parent_id: self.parent_id.clone(),
+            title: self.title.clone(),
+            custom_title: self.custom_title.clone(),
+            short_name: self.short_name.clone(),
+            working_dir: self.working_dir.clone(),
+            model: self.model.clone(),
+            provider_key: self.provider_key.clone(),
+            status: self.status.clone(),
+            is_canary: self.is_canary,
+            is_debug: self.is_debug,
+            saved: self.saved,
+            save_label: self.save_label.clone(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            last_active_at: self.last_active_at,
+            visible_message_count: 0,
+            user_message_count: 0,
+            assistant_message_count: 0,
+            estimated_tokens: 0,
+            first_user_prompt: None,
+            search_text: String::new(),
+        };
+        for message in self
+            .messages
+            .iter()
+            .filter(|message| is_visible_conversation_message(message))
+        {
+            listing.visible_message_count += 1;
+            let text = message
+                .content
+                .iter()
+                .filter_map(|block| match block {
+                    ContentBlock::Text { text, .. } => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            match message.role {
+                Role::User => {
+                    listing.user_message_count += 1;
+                    if listing.first_user_prompt.is_none() && !text.is_empty() {
+                        listing.first_user_prompt = Some(text.clone());
+                    }
+                }
+                Role::Assistant => listing.assistant_message_count += 1,
+            }
+            if let Some(usage) = &message.token_usage {
+                let total = usage.input_tokens
+                    + usage.output_tokens
+                    + usage.cache_read_input_tokens.unwrap_or(0)
+                    + usage.cache_creation_input_tokens.unwrap_or(0);
+                listing.estimated_tokens = listing.estimated_tokens.saturating_add(total as usize);
+            }
+            push_search_excerpt(&mut listing.search_text, &text);
+        }
+        listing
+    }
+}
+
+fn push_search_excerpt(search_text: &mut String, text: &str) {
+    let remaining = LISTING_SEARCH_TEXT_BYTES.saturating_sub(search_text.len());
+    if remaining <= 1 || text.is_empty() {
+        return;
+    }
+    let mut end = text.len().min(remaining - 1);
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    search_text.push(' ');
+    search_text.push_str(&text[..end].to_lowercase());
 }
@@ -0,0 +1,278 @@
+use chrono::Utc;
+use std::path::{Path, PathBuf};
+
+use super::{ContentBlock, Role, Session, StoredDisplayRole};
+
+/// Folders under the home directory where projects commonly live.
+const PROJECT_PARENT_DIRS: &[&str] = &[
+    "src",
+    "code",
+    "dev",
+    "work",
+    "repos",
+    "git",
+    "projects",
+    "Projects",
+    "Developer",
+    "workspace",
+];
+
+/// Directories scanned per parent when matching by git remote.
+const MAX_REMOTE_SCAN_ENTRIES: usize = 500;
+
+impl Session {
+    /// The recorded working directory when it no longer exists on disk, e.g.
+    /// because the project folder was moved or renamed since the last run.
+    pub fn missing_working_dir(&self) -> Option<&str> {
+        self.working_dir
+            .as_deref()
+            .filter(|dir| !Path::new(dir).is_dir())
+    }
+
+    /// `origin` remote of the working directory from the latest environment
+    /// snapshot that captured git state.
+    pub fn last_git_remote(&self) -> Option<&str> {
+        self.env_snapshots
+            .iter()
+            .rev()
+            .find_map(|snapshot| snapshot.working_git.as_ref()?.remote.as_deref())
+    }
+
+    /// Point the session at the new location of its moved working directory.
+    /// Workspace roots and the `/cd` scope under the old directory follow it,
+    /// and a transcript note tells the model where its files went. Returns the
+    /// canonical new working directory.
+    pub fn relocate_working_dir(&mut self, new_dir: &Path) -> anyhow::Result<String> {
+        let canonical = std::fs::canonicalize(new_dir)
+            .map_err(|err| anyhow::anyhow!("{}: {}", new_dir.display(), err))?;
+        if !canonical.is_dir() {
+            anyhow::bail!("{} is not a directory", canonical.display());
+        }
+        let new_root = canonical.to_string_lossy().to_string();
+        let Some(old_root) = self.working_dir.clone() else {
+            self.working_dir = Some(new_root.clone());
+            return Ok(new_root);
+        };
+        if old_root == new_root {
+            return Ok(new_root);
+        }
+
+        let rebase = |path: &mut String| {
+            if let Some(moved) = rebase_path(path, &old_root, &new_root) {
+                *path = moved;
+            }
+        };
+        self.workspace_roots.iter_mut().for_each(rebase);
+        self.tool_scope.iter_mut().for_each(rebase);
+        self.previous_tool_dir.iter_mut().for_each(rebase);
+        self.working_dir = Some(new_root.clone());
+        self.relocated_from = Some(old_root.clone());
+        self.updated_at = Utc::now();
+        self.add_message_with_display_role(
+            Role::User,
+            vec![ContentBlock::Text {
+                text: format!(
+                    "<system-reminder>\nThe working directory moved from {} to {} since this session was last active. Files that were under the old path now live under the new one; use the new path from here on.\n</system-reminder>",
+                    old_root, new_root
+                ),
+                cache_control: None,
+            }],
+            Some(StoredDisplayRole::System),
+        );
+        Ok(new_root)
+    }
+
+    /// Add a hint to a tool error that looks like a missing file or directory
+    /// under the session root: either the path the session was relocated from,
+    /// or a working directory that has disappeared since the session started.
+    pub fn explain_missing_dir_error(&self, error: String) -> String {
+        if !looks_like_not_found(&error) {
+            return error;
+        }
+        if let (Some(old_root), Some(new_root)) = (&self.relocated_from, &self.working_dir)
+            && error.contains(old_root.as_str())
+        {
+            return format!(
+                "{}\n\nHint: {} was moved to {} when this session was resumed. Use paths under {} instead.",
+                error, old_root, new_root, new_root
+            );
+        }
+        if let Some(missing) = self.missing_working_dir() {
+            return format!(
+                "{}\n\nHint: the session working directory {} no longer exists; it was probably moved or renamed. Resume the session (`jcode --resume {}`) to point it at the new location.",
+                error, missing, self.id
+            );
+        }
+        error
+    }
+}
+
+/// Likely new locations for a moved or renamed working directory, best match
+/// first. Looks for folders with the same name or the same git `origin`
+/// remote next to the old location, in `search_dirs` and under common
+/// project folders in the home directory.
+pub fn relocation_candidates(
+    old_dir: &Path,
+    git_remote: Option<&str>,
+    search_dirs: &[PathBuf],
+) -> Vec<PathBuf> {
+    let name = old_dir.file_name();
+    let remote = git_remote.map(normalize_git_remote);
+
+    let mut parents: Vec<PathBuf> = old_dir
+        .ancestors()
+        .skip(1)
+        .find(|dir| dir.is_dir())
+        .map(Path::to_path_buf)
+        .into_iter()
+        .collect();
+    parents.extend(search_dirs.iter().cloned());
+    if let Some(home) = dirs::home_dir() {
+        parents.extend(PROJECT_PARENT_DIRS.iter().map(|dir| home.join(dir)));
+        parents.push(home);
+    }
+
+    let mut scored: Vec<(u8, PathBuf)> = Vec::new();
+    let mut consider = |dir: PathBuf| {
+        if dir == old_dir || scored.iter().any(|(_, seen)| *seen == dir) {
+            return;
+        }
+        let same_name = name.is_some() && dir.file_name() == name;
+        let same_remote = remote.is_some()
+            && git_origin_remote(&dir).as_deref().map(normalize_git_remote) == remote;
+        let score = u8::from(same_remote) * 2 + u8::from(same_name);
+        if score > 0 {
+            scored.push((score, dir));
+        }
+    };
+
+    for dir in search_dirs {
+        if dir.is_dir() {
+            consider(dir.clone());
+        }
+    }
+    for parent in &parents {
+        if let Some(name) = name {
+            let dir = parent.join(name);
+            if dir.is_dir() {
+                consider(dir);
+            }
+        }
+        if remote.is_none() {
+            continue;
+        }
+        let Ok(entries) = std::fs::read_dir(parent) else {
+            continue;
+        };
+        for entry in entries.flatten().take(MAX_REMOTE_SCAN_ENTRIES) {
+            let dir = entry.path();
+            if dir.join(".git").exists() {
+                consider(dir);
+            }
+        }
+    }
+
+    scored.sort_by(|a, b| b.0.cmp(&a.0));
+    scored.into_iter().map(|(_, dir)| dir).collect()
+}
+
+/// `origin` URL read straight from `.git/config`, so scanning many folders
+/// does not spawn git for each one.
+fn git_origin_remote(dir: &Path) -> Option<String> {
+    let config = std::fs::read_to_string(dir.join(".git").join("config")).ok()?;
+    let mut in_origin = false;
+    for line in config.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_origin = line == "[remote \"origin\"]";
+        } else if in_origin
+            && let Some((key, value)) = line.split_once('=')
+            && key.trim() == "url"
+        {
+            return Some(value.trim().to_string());
+        }
+    }
+    None
+}
+
+/// Reduce a git remote to `host/owner/repo` so SSH and HTTPS forms compare equal.
+fn normalize_git_remote(remote: &str) -> String {
+    let remote = remote.trim().trim_end_matches('/');
+    let remote = remote.strip_suffix(".git").unwrap_or(remote);
+    let without_scheme = remote.split_once("://").map_or(remote, |(_, rest)| rest);
+    let without_user = without_scheme
+        .split_once('@')
+        .map_or(without_scheme, |(_, rest)| rest);
+    without_user.replacen(':', "/", 1).to_lowercase()
+}
+
+fn rebase_path(path: &str, old_root: &str, new_root: &str) -> Option<String> {
+    let rest = Path::new(path).strip_prefix(old_root).ok()?;
+    Some(Path::new(new_root).join(rest).to_string_lossy().to_string())
+}
+
+fn looks_like_not_found(error: &str) -> bool {
+    let lower = error.to_lowercase();
+    [
+        "no such file or directory",
+        "os error 2",
+        "not found",
+        "does not exist",
+    ]
+    .iter()
+    .any(|needle| lower.contains(needle))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn git_remotes_compare_across_ssh_and_https() {
+        assert_eq!(
+            normalize_git_remote("git@github.com:Owner/Repo.git"),
+            normalize_git_remote("https://github.com/owner/repo/")
+        );
+        assert_eq!(
+            normalize_git_remote("ssh://git@github.com/owner/repo.git"),
+            "github.com/owner/repo"
+        );
+    }
+
+    #[test]
+    fn candidates_find_renamed_dir_by_remote_and_moved_dir_by_name() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        let renamed = root.join("new-name");
+        std::fs::create_dir_all(renamed.join(".git")).unwrap();
+        std::fs::write(
+            renamed.join(".git").join("config"),
+            "[core]\n\tbare = false\n[remote \"origin\"]\n\turl = git@github.com:me/app.git\n",
+        )
+        .unwrap();
+        let elsewhere = root.join("elsewhere");
+        std::fs::create_dir_all(elsewhere.join("app")).unwrap();
+
+        let old = root.join("app");
+        let candidates = relocation_candidates(
+            &old,
+            Some("https://github.com/me/app"),
+            std::slice::from_ref(&elsewhere),
+        );
+        assert_eq!(candidates.first(), Some(&renamed));
+        assert!(candidates.contains(&elsewhere.join("app")));
+        assert!(!candidates.contains(&old));
+    }
+
+    #[test]
+    fn rebase_path_keeps_subdirectories() {
+        assert_eq!(
+            rebase_path("/old/app/web", "/old/app", "/new/app").as_deref(),
+            Some("/new/app/web")
+        );
+        assert_eq!(rebase_path("/other", "/old/app", "/new/app"), None);
+        assert_eq!(
+            rebase_path("/old/application", "/old/app", "/new/app"),
+            None
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use super::{Session, StoredDisplayRole};
+use super::{Session, StoredDisplayRole, StoredTokenUsage};
 use crate::message::{ContentBlock, Role, ToolCall};
 use jcode_config_types::ReasoningDisplayMode;
 pub use jcode_session_types::{
@@ -317,6 +317,10 @@ pub fn render_messages_and_images_with_compacted_history(
     // 0-based ordinal of the next rendered user prompt, used to anchor pasted
     // user images to their prompt in the transcript.
     let mut user_prompt_count = 0usize;
+    // Usage of assistant responses since the last rendered assistant text.
+    // Tool-only responses render no text, so their usage is folded into the
+    // footer of the answer that follows them.
+    let mut pending_usage: Option<StoredTokenUsage> = None;
     let compacted_count = session
         .compaction
         .as_ref()
@@ -329,6 +333,16 @@ pub fn render_messages_and_images_with_compacted_history(
     );
     let compacted_info = (compacted_count > 0).then_some(compacted_info);
 
+    if let Some(reason) = session.read_only_reason() {
+        rendered.push(RenderedMessage {
+            role: "system".to_string(),
+            content: format!("⚠ Read-only session: {}", reason),
+            tool_calls: Vec::new(),
+            tool_data: None,
+            usage: None,
+        });
+    }
+
     if compacted_count > 0 {
         let visible_compacted = compacted_info
             .as_ref()
@@ -363,6 +377,7 @@ pub fn render_messages_and_images_with_compacted_history(
             content,
             tool_calls: Vec::new(),
             tool_data: None,
+            usage: None,
         });
     }
 
@@ -379,6 +394,14 @@ pub fn render_messages_and_images_with_compacted_history(
                 Role::Assistant => "assistant",
             },
         };
+        if role == "assistant"
+            && let Some(usage) = &msg.token_usage
+        {
+            match pending_usage.as_mut() {
+                Some(pending) => pending.accumulate(usage),
+                None => pending_usage = Some(usage.clone()),
+            }
+        }
         let message_role = msg.role.clone();
         let mut text = String::new();
         // Reasoning is accumulated separately so it can be rendered *before* the
@@ -449,6 +472,7 @@ pub fn render_messages_and_images_with_compacted_history(
                             content: combined,
                             tool_calls: tool_calls.clone(),
                             tool_data: None,
+                            usage: None,
                         });
                     }
 
@@ -468,6 +492,7 @@ pub fn render_messages_and_images_with_compacted_history(
                         content: content.clone(),
                         tool_calls: Vec::new(),
                         tool_data,
+                        usage: None,
                     });
                 }
                 ContentBlock::Reasoning { text: t } | ContentBlock::ReasoningTrace { text: t } => {
@@ -496,6 +521,58 @@ pub fn render_messages_and_images_with_compacted_history(
                     }
                 }
                 ContentBlock::OpenAICompaction { .. } => {}
+                ContentBlock::ServerToolUse { id, name, input } => {
+                    let tool_call = ToolCall {
+                        id: id.clone(),
+                        name: name.clone(),
+                        input: input.clone(),
+                        intent: ToolCall::intent_from_input(input),
+                        thought_signature: None,
+                    };
+                    tool_map.insert(id.clone(), tool_call);
+                    tool_calls.push(name.clone());
+                }
+                ContentBlock::WebSearchResult {
+                    tool_use_id,
+                    results,
+                    error_code,
+                } => {
+                    let combined = format!("{}{}", reasoning, text);
+                    if !combined.is_empty() {
+                        text.clear();
+                        reasoning.clear();
+                        rendered.push(RenderedMessage {
+                            role: role.to_string(),
+                            content: combined,
+                            tool_calls: tool_calls.clone(),
+                            tool_data: None,
+                            usage: None,
+                        });
+                    }
+                    let tool_data = tool_map.get(tool_use_id).cloned().or_else(|| {
+                        Some(ToolCall {
+                            id: tool_use_id.clone(),
+                            name: "web_search".to_string(),
+                            input: serde_json::Value::Null,
+                            intent: None,
+                            thought_signature: None,
+                        })
+                    });
+                    current_tool = tool_data.clone();
+                    rendered.push(RenderedMessage {
+                        role: "tool".to_string(),
+                        content: crate::message::web_search_result_text(
+                            results,
+                            error_code.as_deref(),
+                        ),
+                        tool_calls: Vec::new(),
+                        tool_data,
+                        usage: None,
+                    });
+                }
+                ContentBlock::Citations { citations } => {
+                    text.push_str(&crate::message::citation_sources_markdown(citations));
+                }
             }
         }
 
@@ -504,11 +581,20 @@ pub fn render_messages_and_images_with_compacted_history(
             if role == "user" && !is_attached_image_label_text(&text) {
                 user_prompt_count += 1;
             }
+            let usage = if role == "assistant" {
+                pending_usage.take()
+            } else {
+                if role == "user" {
+                    pending_usage = None;
+                }
+                None
+            };
             rendered.push(RenderedMessage {
                 role: role.to_string(),
                 content: combined,
                 tool_calls,
                 tool_data: None,
+                usage,
             });
         } else if !pending_prompt_image_indices.is_empty() {
             // The message carried images but produced no rendered user prompt;
@@ -0,0 +1,296 @@
+//! Versioned on-disk layout for session snapshots.
+//!
+//! Every snapshot carries a `schema_version`. Older snapshots are upgraded on
+//! load by running the registered migrations one version at a time over the raw
+//! JSON, and the upgraded snapshot replaces the file so later loads skip the
+//! migration. When the upgrade changed more than the version stamp, the
+//! original is kept under `pre-migration/<id>.pre-migration.json` first.
+//! Snapshots from a newer jcode (common in self-dev, where an
+//! older binary reopens a session a rebuilt one wrote) are loaded best-effort
+//! and marked read-only so this build never overwrites fields it does not know.
+
+use anyhow::{Context, Result};
+use serde_json::{Map, Value, json};
+use std::path::{Path, PathBuf};
+
+use super::storage_paths::session_path;
+use super::{Session, StoredMessage};
+use crate::storage;
+
+/// Layout version written by this build.
+pub const SESSION_SCHEMA_VERSION: u32 = 2;
+
+/// Version assumed for snapshots written before `schema_version` existed.
+const UNVERSIONED_SCHEMA_VERSION: u32 = 1;
+
+/// Directory, next to the session files, holding originals of migrated
+/// snapshots. A subdirectory keeps them out of `sessions/*.json` scans.
+const PRE_MIGRATION_DIR: &str = "pre-migration";
+
+type Migration = fn(&mut Map<String, Value>);
+
+/// `(from, step)`: each step upgrades a snapshot from `from` to `from + 1`.
+const MIGRATIONS: &[(u32, Migration)] = &[(1, migrate_v1_to_v2)];
+
+/// Fields a snapshot cannot be loaded without, kept when probing newer ones.
+const REQUIRED_FIELDS: &[&str] = &["id", "created_at", "updated_at"];
+
+pub(super) fn default_schema_version() -> u32 {
+    UNVERSIONED_SCHEMA_VERSION
+}
+
+/// How a snapshot's layout compared with this build's when it was read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionSchemaStatus {
+    Current,
+    /// Upgraded from an older layout
+    Migrated {
+        from: u32,
+    },
+    /// Written by a newer jcode; loaded read-only
+    Newer {
+        version: u32,
+    },
+}
+
+/// Where the original of a migrated snapshot is preserved.
+pub fn pre_migration_backup_path(path: &Path) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    path.with_file_name(PRE_MIGRATION_DIR)
+        .join(format!("{}.pre-migration.json", stem))
+}
+
+/// Upgrade one session's snapshot on disk to [`SESSION_SCHEMA_VERSION`].
+pub fn migrate_session(session_id: &str) -> Result<SessionSchemaStatus> {
+    let path = session_path(session_id)?;
+    Session::read_versioned_snapshot(&path).map(|(_, status)| status)
+}
+
+fn schema_version_of(value: &Value) -> u32 {
+    value
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .and_then(|version| u32::try_from(version).ok())
+        .unwrap_or(UNVERSIONED_SCHEMA_VERSION)
+}
+
+/// Run the registered migrations over `value` up to the current version.
+fn migrate_session_value(value: &mut Value) -> Result<()> {
+    let mut version = schema_version_of(value);
+    let session = value
+        .as_object_mut()
+        .context("session snapshot is not a JSON object")?;
+    while version < SESSION_SCHEMA_VERSION {
+        let Some((_, step)) = MIGRATIONS.iter().find(|(from, _)| *from == version) else {
+            anyhow::bail!("no migration registered from session schema v{}", version);
+        };
+        step(session);
+        version += 1;
+        session.insert("schema_version".to_string(), version.into());
+    }
+    Ok(())
+}
+
+fn newer_schema_reason(version: u32) -> String {
+    format!(
+        "this session was written by a newer jcode (session schema v{}, this build reads up to v{}). \
+It was loaded best-effort and changes will not be saved; update jcode to continue it.",
+        version, SESSION_SCHEMA_VERSION
+    )
+}
+
+/// Deserialize a snapshot from a newer layout, dropping whatever this build
+/// cannot read instead of failing: unreadable messages, then any other field
+/// whose shape changed.
+fn best_effort_session(mut value: Value) -> Result<Session> {
+    if let Ok(session) = serde_json::from_value::<Session>(value.clone()) {
+        return Ok(session);
+    }
+    let session = value
+        .as_object_mut()
+        .context("session snapshot is not a JSON object")?;
+    if let Some(Value::Array(messages)) = session.get_mut("messages") {
+        let before = messages.len();
+        messages.retain(|message| serde_json::from_value::<StoredMessage>(message.clone()).is_ok());
+        if messages.len() < before {
+            crate::logging::warn(&format!(
+                "Dropped {} unreadable message(s) from newer-schema session",
+                before - messages.len()
+            ));
+        }
+    }
+    let mut probe: Map<String, Value> = REQUIRED_FIELDS
+        .iter()
+        .filter_map(|field| Some((field.to_string(), session.get(*field)?.clone())))
+        .collect();
+    probe.insert("messages".to_string(), Value::Array(Vec::new()));
+    let optional: Vec<String> = session
+        .keys()
+        .filter(|key| !REQUIRED_FIELDS.contains(&key.as_str()) && *key != "messages")
+        .cloned()
+        .collect();
+    for key in optional {
+        let mut candidate = probe.clone();
+        candidate.insert(key.clone(), session[&key].clone());
+        if serde_json::from_value::<Session>(Value::Object(candidate)).is_err() {
+            crate::logging::warn(&format!(
+                "Ignoring unreadable field '{}' in newer-schema session",
+                key
+            ));
+            session.remove(&key);
+        }
+    }
+    serde_json::from_value(value).context("session written by a newer jcode could not be read")
+}
+
+impl Session {
+    /// Read the snapshot at `path`, upgrading older layouts and degrading newer
+    /// ones to a read-only best-effort load.
+    ///
+    /// Migrated snapshots are written back, after the original has been
+    /// preserved when the upgrade changed more than the version stamp.
+    pub(super) fn read_versioned_snapshot(path: &Path) -> Result<(Session, SessionSchemaStatus)> {
+        let bytes = std::fs::read(path)?;
+        if let Ok(session) = serde_json::from_slice::<Session>(&bytes)
+            && session.schema_version == SESSION_SCHEMA_VERSION
+        {
+            return Ok((session, SessionSchemaStatus::Current));
+        }
+        // An unparseable primary goes through the usual `.bak` recovery.
+        let (value, original) = match serde_json::from_slice::<Value>(&bytes) {
+            Ok(value) => (value, bytes),
+            Err(_) => {
+                let value: Value = storage::read_json(path)?;
+                let original = serde_json::to_vec(&value)?;
+                (value, original)
+            }
+        };
+
+        let version = schema_version_of(&value);
+        if version > SESSION_SCHEMA_VERSION {
+            let mut session = best_effort_session(value)?;
+            session.mark_newer_schema(version);
+            crate::logging::warn(&format!(
+                "Session {} has schema v{} (newer than v{}); loaded read-only",
+                session.id, version, SESSION_SCHEMA_VERSION
+            ));
+            return Ok((session, SessionSchemaStatus::Newer { version }));
+        }
+        if version == SESSION_SCHEMA_VERSION {
+            // Current layout that still failed typed parsing: treat it as
+            // corrupt and let the `.bak` recovery have a go.
+            let session = storage::read_json(path)?;
+            return Ok((session, SessionSchemaStatus::Current));
+        }
+
+        let mut migrated = value.clone();
+        migrate_session_value(&mut migrated)?;
+        let session: Session = serde_json::from_value(migrated.clone()).with_context(|| {
+            format!(
+                "session schema v{} → v{} migration left {} unreadable",
+                version,
+                SESSION_SCHEMA_VERSION,
+                path.display()
+            )
+        })?;
+
+        let mut unstamped = migrated;
+        if let Some(object) = unstamped.as_object_mut() {
+            object.remove("schema_version");
+        }
+        let changed = unstamped != value;
+        if changed {
+            let backup = pre_migration_backup_path(path);
+            if !backup.exists() {
+                storage::write_bytes(&backup, &original)?;
+            }
+            storage::write_json_fast(path, &session)?;
+        } else if let Err(err) = storage::write_json_fast(path, &session) {
+            // Only the stamp is missing; the next load migrates again.
+            crate::logging::warn(&format!(
+                "Could not record schema v{} for session {}: {}",
+                SESSION_SCHEMA_VERSION, session.id, err
+            ));
+        }
+        crate::logging::info(&format!(
+            "Migrated session {} from schema v{} to v{}{}",
+            session.id,
+            version,
+            SESSION_SCHEMA_VERSION,
+            if changed { " (original preserved)" } else { "" }
+        ));
+        Ok((session, SessionSchemaStatus::Migrated { from: version }))
+    }
+
+    /// Flag a session loaded from a newer layout as read-only.
+    pub(super) fn mark_newer_schema(&mut self, version: u32) {
+        self.schema_version = version;
+        self.read_only_reason = Some(newer_schema_reason(version));
+    }
+
+    /// Why this session must not be saved, when it was loaded read-only.
+    pub fn read_only_reason(&self) -> Option<&str> {
+        self.read_only_reason.as_deref()
+    }
+}
+
+/// Snapshots from before `schema_version` existed. Besides today's field set,
+/// older builds wrote message `content` as a bare string, omitted message ids,
+/// used `input`/`output` token-usage keys, and stored `status` as a lowercase
+/// string or with a bare crash/error message.
+fn migrate_v1_to_v2(session: &mut Map<String, Value>) {
+    if let Some(Value::Array(messages)) = session.get_mut("messages") {
+        for (index, message) in messages.iter_mut().enumerate() {
+            let Some(message) = message.as_object_mut() else {
+                continue;
+            };
+            if !message.contains_key("id") {
+                message.insert("id".to_string(), format!("legacy_msg_{}", index).into());
+            }
+            if let Some(text) = message.get("content").and_then(Value::as_str) {
+                let content = json!([{ "type": "text", "text": text }]);
+                message.insert("content".to_string(), content);
+            }
+            if let Some(Value::Object(usage)) = message.get_mut("token_usage") {
+                rename_key(usage, "input", "input_tokens");
+                rename_key(usage, "output", "output_tokens");
+            }
+        }
+    }
+    if let Some(status) = session.get("status").and_then(legacy_status) {
+        session.insert("status".to_string(), status);
+    }
+}
+
+fn rename_key(object: &mut Map<String, Value>, from: &str, to: &str) {
+    if !object.contains_key(to)
+        && let Some(value) = object.remove(from)
+    {
+        object.insert(to.to_string(), value);
+    }
+}
+
+fn legacy_status(status: &Value) -> Option<Value> {
+    match status {
+        Value::String(name) => Some(match name.as_str() {
+            "active" => json!("Active"),
+            "closed" => json!("Closed"),
+            "reloaded" => json!("Reloaded"),
+            "compacted" => json!("Compacted"),
+            "rate_limited" => json!("RateLimited"),
+            "crashed" => json!({ "Crashed": { "message": null } }),
+            "error" => json!({ "Error": { "message": "" } }),
+            _ => return None,
+        }),
+        Value::Object(variant) if variant.len() == 1 => {
+            let (name, message) = variant.iter().next()?;
+            let message = message.as_str()?;
+            matches!(name.as_str(), "Crashed" | "Error")
+                .then(|| json!({ name.as_str(): { "message": message } }))
+        }
+        _ => None,
+    }
+}
@@ -48,7 +48,5 @@ pub fn session_journal_path(session_id: &str) -> Result<PathBuf> {
 }
 
 pub fn session_exists(session_id: &str) -> bool {
-    session_path(session_id)
-        .map(|path| path.exists())
-        .unwrap_or(false)
+    crate::storage::store().session_exists(session_id)
 }
@@ -1,5 +1,6 @@
 use super::*;
 use anyhow::{Result, anyhow};
+use std::path::PathBuf;
 
 #[test]
 fn test_session_exists_roundtrip() -> Result<()> {
@@ -188,6 +189,9 @@ fn token_usage_totals_counts_cache_reported_inputs_only_when_cache_fields_exist(
             output_tokens: 10,
             cache_read_input_tokens: None,
             cache_creation_input_tokens: None,
+            web_search_requests: None,
+            pruned_context_tokens: None,
+            ..StoredTokenUsage::default()
         }),
     );
     session.add_message_ext(
@@ -202,6 +206,9 @@ fn token_usage_totals_counts_cache_reported_inputs_only_when_cache_fields_exist(
             output_tokens: 20,
             cache_read_input_tokens: Some(150),
             cache_creation_input_tokens: Some(25),
+            web_search_requests: None,
+            pruned_context_tokens: None,
+            ..StoredTokenUsage::default()
         }),
     );
 
@@ -214,6 +221,89 @@ fn token_usage_totals_counts_cache_reported_inputs_only_when_cache_fields_exist(
     assert_eq!(totals.cache_creation_input_tokens, 25);
 }
 
+#[test]
+fn pending_origin_usage_is_kept_apart_from_interactive_totals() {
+    use crate::usage::{OriginUsage, UsageOrigin};
+
+    let mut session = Session::create_with_id(
+        "session_origin_usage_test".to_string(),
+        None,
+        Some("Origin usage".to_string()),
+    );
+    session.add_message_ext(
+        Role::Assistant,
+        vec![ContentBlock::Text {
+            text: "answer".to_string(),
+            cache_control: None,
+        }],
+        None,
+        Some(StoredTokenUsage {
+            input_tokens: 1_000,
+            output_tokens: 100,
+            ..StoredTokenUsage::default()
+        }),
+    );
+    crate::usage::attribute_to_session(
+        &session.id,
+        &UsageOrigin::Compaction,
+        OriginUsage::new(1, 400, 40),
+    );
+    crate::usage::attribute_to_session(
+        &session.id,
+        &UsageOrigin::Subagent("explore".to_string()),
+        OriginUsage::new(2, 600, 60),
+    );
+    // Interactive usage is already on the messages and is never queued.
+    crate::usage::attribute_to_session(
+        &session.id,
+        &UsageOrigin::Interactive,
+        OriginUsage::new(1, 9_999, 9_999),
+    );
+    session.apply_pending_origin_usage();
+    session.apply_pending_origin_usage();
+
+    let totals = session.token_usage_totals();
+    assert_eq!(totals.input_tokens, 1_000);
+    assert_eq!(totals.output_tokens, 100);
+    assert_eq!(totals.overhead_input_tokens, 1_000);
+    assert_eq!(totals.overhead_output_tokens, 100);
+
+    let stats = session.origin_usage_stats();
+    assert_eq!(
+        stats.keys().map(String::as_str).collect::<Vec<_>>(),
+        vec!["compaction", "interactive", "subagent:explore"]
+    );
+    assert_eq!(stats["interactive"], OriginUsage::new(1, 1_000, 100));
+    assert_eq!(stats["subagent:explore"], OriginUsage::new(2, 600, 60));
+}
+
+#[test]
+fn background_response_is_only_resumed_while_history_ends_at_its_request() {
+    let mut session =
+        Session::create_with_id("session_background_response_test".to_string(), None, None);
+    let user_text = |text: &str| {
+        vec![ContentBlock::Text {
+            text: text.to_string(),
+            cache_control: None,
+        }]
+    };
+    session.add_message(Role::User, user_text("long task"));
+    let anchor = session.messages.last().map(|message| message.id.clone());
+
+    session.set_background_response("resp_1".to_string(), anchor.clone());
+    assert_eq!(
+        session.take_resumable_background_response().as_deref(),
+        Some("resp_1")
+    );
+    assert_eq!(session.take_resumable_background_response(), None);
+
+    session.set_background_response("resp_2".to_string(), anchor);
+    session.add_message(Role::User, user_text("something else"));
+    assert_eq!(session.take_resumable_background_response(), None);
+    assert_eq!(session.background_response_id, None);
+    assert_eq!(session.background_response_after, None);
+}
+
 #[test]
 fn initial_session_context_is_persisted_once_and_not_overwritten() {
     let mut session = Session::create_with_id(
@@ -717,6 +807,160 @@ fn test_save_persists_full_session_content() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_workspace_roots_persist_and_resume() -> Result<()> {
+    let _env_lock = lock_env();
+    let temp_home = tempfile::Builder::new()
+        .prefix("jcode-session-roots-test-")
+        .tempdir()
+        .map_err(|e| anyhow!(e))?;
+    let _home = EnvVarGuard::set("JCODE_HOME", temp_home.path().as_os_str());
+    let primary = tempfile::tempdir().map_err(|e| anyhow!(e))?;
+    let extras = tempfile::tempdir().map_err(|e| anyhow!(e))?;
+    let web = extras.path().join("web");
+    std::fs::create_dir_all(&web).map_err(|e| anyhow!(e))?;
+    let web = std::fs::canonicalize(&web).map_err(|e| anyhow!(e))?;
+
+    let mut session = Session::create_with_id(
+        "session_workspace_roots_test".to_string(),
+        None,
+        Some("workspace roots".to_string()),
+    );
+    session.working_dir = Some(primary.path().display().to_string());
+    let added = session.add_workspace_root(&web.display().to_string())?;
+    assert_eq!(added, web.display().to_string());
+    assert!(
+        session
+            .add_workspace_root(&extras.path().join("missing").display().to_string())
+            .is_err()
+    );
+    session.save()?;
+
+    let loaded = Session::load("session_workspace_roots_test")?;
+    assert_eq!(loaded.workspace_roots, vec![web.display().to_string()]);
+    let stub = Session::load_startup_stub("session_workspace_roots_test")?;
+    assert_eq!(stub.workspace_roots, vec![web.display().to_string()]);
+
+    let mut loaded = loaded;
+    assert_eq!(
+        loaded.remove_workspace_root("web"),
+        Some(web.display().to_string())
+    );
+    assert!(loaded.workspace_roots.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_tool_scope_stays_inside_workspace_and_persists() -> Result<()> {
+    let _env_lock = lock_env();
+    let temp_home = tempfile::Builder::new()
+        .prefix("jcode-session-tool-scope-test-")
+        .tempdir()
+        .map_err(|e| anyhow!(e))?;
+    let _home = EnvVarGuard::set("JCODE_HOME", temp_home.path().as_os_str());
+    let repo = tempfile::tempdir().map_err(|e| anyhow!(e))?;
+    let repo_path = std::fs::canonicalize(repo.path()).map_err(|e| anyhow!(e))?;
+    let web = repo_path.join("packages/web");
+    let api = repo_path.join("packages/api");
+    std::fs::create_dir_all(&web).map_err(|e| anyhow!(e))?;
+    std::fs::create_dir_all(&api).map_err(|e| anyhow!(e))?;
+    let outside = tempfile::tempdir().map_err(|e| anyhow!(e))?;
+
+    let mut session = Session::create_with_id(
+        "session_tool_scope_test".to_string(),
+        None,
+        Some("tool scope".to_string()),
+    );
+    session.working_dir = Some(repo_path.display().to_string());
+    assert_eq!(
+        session.tool_working_dir(),
+        Some(repo_path.to_str().unwrap())
+    );
+
+    assert_eq!(
+        session.scope_tools("packages/web")?,
+        web.display().to_string()
+    );
+    assert_eq!(session.scope_tools("../api")?, api.display().to_string());
+    assert_eq!(session.tool_working_dir(), Some(api.to_str().unwrap()));
+    assert_eq!(session.scope_tools("-")?, web.display().to_string());
+    assert!(
+        session
+            .scope_tools(&outside.path().display().to_string())
+            .is_err()
+    );
+    assert_eq!(session.tool_scope, Some(web.display().to_string()));
+    session.save()?;
+
+    let loaded = Session::load("session_tool_scope_test")?;
+    assert_eq!(loaded.tool_scope, Some(web.display().to_string()));
+    let stub = Session::load_startup_stub("session_tool_scope_test")?;
+    assert_eq!(stub.tool_scope, Some(web.display().to_string()));
+
+    let mut loaded = loaded;
+    loaded.scope_tools(&repo_path.display().to_string())?;
+    assert_eq!(loaded.tool_scope, None);
+    Ok(())
+}
+
+#[test]
+fn test_relocate_moved_working_dir_persists_and_explains_old_paths() -> Result<()> {
+    let _env_lock = lock_env();
+    let temp_home = tempfile::Builder::new()
+        .prefix("jcode-session-relocate-test-")
+        .tempdir()
+        .map_err(|e| anyhow!(e))?;
+    let _home = EnvVarGuard::set("JCODE_HOME", temp_home.path().as_os_str());
+    let projects = tempfile::tempdir().map_err(|e| anyhow!(e))?;
+    let projects = std::fs::canonicalize(projects.path()).map_err(|e| anyhow!(e))?;
+    let old_root = projects.join("app");
+    let new_root = projects.join("app-renamed");
+    std::fs::create_dir_all(new_root.join("web")).map_err(|e| anyhow!(e))?;
+
+    let mut session = Session::create_with_id(
+        "session_relocate_test".to_string(),
+        None,
+        Some("relocate".to_string()),
+    );
+    session.working_dir = Some(old_root.display().to_string());
+    session.tool_scope = Some(old_root.join("web").display().to_string());
+    assert_eq!(
+        session.missing_working_dir(),
+        Some(old_root.to_str().unwrap())
+    );
+    let missing = session.explain_missing_dir_error("Error: No such file or directory".into());
+    assert!(missing.contains("no longer exists"));
+
+    session.relocate_working_dir(&new_root)?;
+    assert_eq!(session.working_dir, Some(new_root.display().to_string()));
+    assert_eq!(
+        session.tool_scope,
+        Some(new_root.join("web").display().to_string())
+    );
+    assert_eq!(session.missing_working_dir(), None);
+    assert!(session.messages.last().is_some_and(|message| {
+        message.content.iter().any(|block| {
+            matches!(block, ContentBlock::Text { text, .. } if text.contains("working directory moved"))
+        })
+    }));
+    session.save()?;
+
+    let loaded = Session::load("session_relocate_test")?;
+    assert_eq!(loaded.relocated_from, Some(old_root.display().to_string()));
+    let error = format!(
+        "Error: {}/src/main.rs: No such file or directory (os error 2)",
+        old_root.display()
+    );
+    let hinted = loaded.explain_missing_dir_error(error.clone());
+    assert!(hinted.starts_with(&error));
+    assert!(hinted.contains(&format!("was moved to {}", new_root.display())));
+    assert_eq!(
+        loaded.explain_missing_dir_error("Error: permission denied".into()),
+        "Error: permission denied"
+    );
+    Ok(())
+}
+
 #[test]
 fn test_save_persists_compaction_state() -> Result<()> {
     let _env_lock = lock_env();
@@ -891,6 +1135,115 @@ fn test_save_checkpoints_after_full_mutation_and_clears_journal() -> Result<()>
     Ok(())
 }
 
+#[test]
+fn test_asides_persist_apart_from_main_messages() -> Result<()> {
+    let _env_lock = lock_env();
+    let temp_home = tempfile::Builder::new()
+        .prefix("jcode-session-aside-test-")
+        .tempdir()
+        .map_err(|e| anyhow!(e))?;
+    let _home = EnvVarGuard::set("JCODE_HOME", temp_home.path().as_os_str());
+
+    let mut session = Session::create_with_id(
+        "session_aside_persist_test".to_string(),
+        None,
+        Some("aside test".to_string()),
+    );
+    session.add_message(
+        Role::User,
+        vec![ContentBlock::Text {
+            text: "main".to_string(),
+            cache_control: None,
+        }],
+    );
+    session.save()?;
+
+    assert!(session.begin_aside().is_some());
+    assert!(session.begin_aside().is_none());
+    session.set_aside_messages(vec![StoredMessage {
+        id: "aside_msg".to_string(),
+        role: Role::User,
+        content: vec![ContentBlock::Text {
+            text: "side question".to_string(),
+            cache_control: None,
+        }],
+        display_role: None,
+        timestamp: None,
+        tool_duration_ms: None,
+        token_usage: None,
+    }]);
+    session.save()?;
+
+    // Asides are not journaled, so the change must land in the snapshot.
+    let journal_path = session_journal_path("session_aside_persist_test")?;
+    assert!(!journal_path.exists());
+
+    let loaded = Session::load("session_aside_persist_test")?;
+    assert_eq!(loaded.messages.len(), 1);
+    let aside = loaded.active_aside().ok_or_else(|| anyhow!("aside open"))?;
+    assert_eq!(aside.messages[0].content_preview(), "side question");
+
+    assert!(session.end_aside(Some("use the v2 parser".to_string())));
+    assert!(!session.end_aside(None));
+    session.save()?;
+
+    let loaded = Session::load("session_aside_persist_test")?;
+    assert!(loaded.active_aside().is_none());
+    assert_eq!(loaded.asides.len(), 1);
+    assert_eq!(
+        loaded.asides[0].summary.as_deref(),
+        Some("use the v2 parser")
+    );
+
+    let mut scratch =
+        Session::create_with_id("session_aside_ephemeral_test".to_string(), None, None);
+    scratch.set_ephemeral();
+    scratch.save()?;
+    assert!(!session_path("session_aside_ephemeral_test")?.exists());
+    Ok(())
+}
+
+#[test]
+fn test_compare_alternatives_persist_with_the_kept_answer() -> Result<()> {
+    let _env_lock = lock_env();
+    let temp_home = tempfile::Builder::new()
+        .prefix("jcode-session-alternatives-test-")
+        .tempdir()
+        .map_err(|e| anyhow!(e))?;
+    let _home = EnvVarGuard::set("JCODE_HOME", temp_home.path().as_os_str());
+
+    let mut session =
+        Session::create_with_id("session_alternatives_persist_test".to_string(), None, None);
+    session.save()?;
+    let kept = session.add_message(
+        Role::Assistant,
+        vec![ContentBlock::Text {
+            text: "kept".to_string(),
+            cache_control: None,
+        }],
+    );
+    session.record_alternatives(
+        &kept,
+        vec![crate::protocol::CompareRun {
+            model: "model-b".to_string(),
+            text: "other answer".to_string(),
+            input_tokens: Some(10),
+            output_tokens: Some(2),
+            latency_ms: 900,
+            error: None,
+        }],
+    );
+    session.save()?;
+
+    let loaded = Session::load("session_alternatives_persist_test")?;
+    assert_eq!(loaded.messages.len(), 1);
+    assert_eq!(loaded.alternatives.len(), 1);
+    assert_eq!(loaded.alternatives[0].message_id, kept);
+    assert_eq!(loaded.alternatives[0].run.model, "model-b");
+    assert_eq!(loaded.alternatives[0].run.text, "other answer");
+    Ok(())
+}
+
 #[test]
 fn test_redacted_for_export_redacts_tool_result_and_tool_input() -> Result<()> {
     let mut session = Session::create_with_id(
@@ -1069,6 +1422,80 @@ fn test_render_messages_honors_system_display_role_override() {
     assert!(rendered[0].content.contains("Background Task Completed"));
 }
 
+#[test]
+fn test_render_messages_carries_tool_call_usage_into_the_answer() {
+    let mut session = Session::create_with_id(
+        "session_render_usage_test".to_string(),
+        None,
+        Some("render usage test".to_string()),
+    );
+    let usage = |input_tokens, duration_ms| StoredTokenUsage {
+        input_tokens,
+        output_tokens: 10,
+        model: Some("gpt-5".to_string()),
+        provider: Some("openai".to_string()),
+        duration_ms: Some(duration_ms),
+        ..StoredTokenUsage::default()
+    };
+
+    session.add_message(
+        Role::User,
+        vec![ContentBlock::Text {
+            text: "list files".to_string(),
+            cache_control: None,
+        }],
+    );
+    session.add_message_ext(
+        Role::Assistant,
+        vec![ContentBlock::ToolUse {
+            id: "call_1".to_string(),
+            name: "ls".to_string(),
+            input: serde_json::json!({}),
+            thought_signature: None,
+        }],
+        None,
+        Some(usage(100, 1_000)),
+    );
+    session.add_message(
+        Role::User,
+        vec![ContentBlock::ToolResult {
+            tool_use_id: "call_1".to_string(),
+            content: "a.rs".to_string(),
+            is_error: None,
+        }],
+    );
+    session.add_message_ext(
+        Role::Assistant,
+        vec![ContentBlock::Text {
+            text: "One file.".to_string(),
+            cache_control: None,
+        }],
+        None,
+        Some(usage(150, 2_000)),
+    );
+
+    let rendered = render_messages(&session);
+    let answer = rendered.last().expect("answer rendered");
+    assert_eq!(answer.content, "One file.");
+    let answer_usage = answer.usage.as_ref().expect("answer carries usage");
+    assert_eq!(answer_usage.input_tokens, 250);
+    assert_eq!(answer_usage.output_tokens, 20);
+    assert_eq!(answer_usage.duration_ms, Some(3_000));
+    assert_eq!(answer_usage.model.as_deref(), Some("gpt-5"));
+    assert!(
+        rendered[..rendered.len() - 1]
+            .iter()
+            .all(|message| message.usage.is_none())
+    );
+
+    let stats = session.model_usage_stats();
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats[0].model.as_deref(), Some("gpt-5"));
+    assert_eq!(stats[0].responses, 2);
+    assert_eq!(stats[0].input_tokens, 250);
+    assert_eq!(stats[0].timed_responses, 2);
+}
+
 #[test]
 fn test_render_messages_renders_reasoning_before_answer_in_stored_order() {
     // Regression: providers persist the assistant turn as `[Text, ReasoningTrace,
@@ -1970,3 +2397,183 @@ fn streaming_guard_creates_visible_macos_sleep_assertion() {
         "streaming assertion should be released after guard drop; output was:\n{stdout}"
     );
 }
+
+fn install_session_fixture(home: &Path, session_id: &str, contents: &str) -> Result<PathBuf> {
+    let path = session_path_in_dir(home, session_id);
+    std::fs::create_dir_all(path.parent().ok_or_else(|| anyhow!("no parent"))?)?;
+    std::fs::write(&path, contents)?;
+    Ok(path)
+}
+
+#[test]
+fn legacy_string_content_fixture_migrates_and_preserves_original() -> Result<()> {
+    let _env_lock = lock_env();
+    let temp_home = tempfile::Builder::new()
+        .prefix("jcode-session-migration-test-")
+        .tempdir()
+        .map_err(|e| anyhow!(e))?;
+    let _home = EnvVarGuard::set("JCODE_HOME", temp_home.path().as_os_str());
+
+    let fixture = include_str!("fixtures/legacy_string_content.json");
+    let session_id = "session_legacy_string_content";
+    let path = install_session_fixture(temp_home.path(), session_id, fixture)?;
+
+    let session = Session::load(session_id)?;
+    assert_eq!(session.schema_version, SESSION_SCHEMA_VERSION);
+    assert!(session.read_only_reason().is_none());
+    assert_eq!(session.messages.len(), 2);
+    assert_eq!(session.messages[0].id, "legacy_msg_0");
+    assert!(matches!(
+        &session.messages[1].content[..],
+        [ContentBlock::Text { text, .. }] if text == "The lockfile is out of date."
+    ));
+    let usage = session.messages[1]
+        .token_usage
+        .as_ref()
+        .ok_or_else(|| anyhow!("token usage should survive migration"))?;
+    assert_eq!((usage.input_tokens, usage.output_tokens), (812, 64));
+    assert_eq!(session.status, SessionStatus::Crashed { message: None });
+
+    let backup = pre_migration_backup_path(&path);
+    assert_eq!(std::fs::read_to_string(&backup)?, fixture);
+    let on_disk: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+    assert_eq!(on_disk["schema_version"], SESSION_SCHEMA_VERSION);
+    assert_eq!(
+        migrate_session(session_id)?,
+        SessionSchemaStatus::Current,
+        "the load should already have rewritten the snapshot"
+    );
+    Ok(())
+}
+
+#[test]
+fn legacy_unversioned_fixture_migrates_status_and_keeps_fields() -> Result<()> {
+    let _env_lock = lock_env();
+    let temp_home = tempfile::Builder::new()
+        .prefix("jcode-session-migration-test-")
+        .tempdir()
+        .map_err(|e| anyhow!(e))?;
+    let _home = EnvVarGuard::set("JCODE_HOME", temp_home.path().as_os_str());
+
+    let session_id = "session_legacy_unversioned";
+    let path = install_session_fixture(
+        temp_home.path(),
+        session_id,
+        include_str!("fixtures/legacy_unversioned.json"),
+    )?;
+
+    assert_eq!(
+        migrate_session(session_id)?,
+        SessionSchemaStatus::Migrated { from: 1 }
+    );
+    assert!(pre_migration_backup_path(&path).exists());
+
+    let session = Session::load(session_id)?;
+    assert_eq!(
+        session.status,
+        SessionStatus::Crashed {
+            message: Some("panicked at src/agent.rs:412".to_string())
+        }
+    );
+    assert_eq!(session.improve_mode, Some(SessionImproveMode::ImproveRun));
+    assert_eq!(session.testing_build.as_deref(), Some("4f2a9c1"));
+    assert_eq!(session.short_name.as_deref(), Some("otter"));
+    assert!(session.saved);
+    assert_eq!(session.messages[1].id, "msg_02");
+    Ok(())
+}
+
+#[test]
+fn stamp_only_migration_is_recorded_on_first_load_without_a_backup() -> Result<()> {
+    let _env_lock = lock_env();
+    let temp_home = tempfile::Builder::new()
+        .prefix("jcode-session-migration-test-")
+        .tempdir()
+        .map_err(|e| anyhow!(e))?;
+    let _home = EnvVarGuard::set("JCODE_HOME", temp_home.path().as_os_str());
+
+    let session_id = "session_stamp_only";
+    let mut session = Session::create_with_id(session_id.to_string(), None, None);
+    session.add_message(
+        Role::User,
+        vec![ContentBlock::Text {
+            text: "hello".to_string(),
+            cache_control: None,
+        }],
+    );
+    session.save()?;
+    let path = session_path(session_id)?;
+    let mut value: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+    value
+        .as_object_mut()
+        .ok_or_else(|| anyhow!("snapshot should be an object"))?
+        .remove("schema_version");
+    std::fs::write(&path, serde_json::to_vec(&value)?)?;
+
+    let loaded = Session::load(session_id)?;
+    assert_eq!(loaded.messages.len(), 1);
+    assert!(!pre_migration_backup_path(&path).exists());
+    let on_disk: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+    assert_eq!(on_disk["schema_version"], SESSION_SCHEMA_VERSION);
+    assert_eq!(migrate_session(session_id)?, SessionSchemaStatus::Current);
+    Ok(())
+}
+
+#[test]
+fn newer_schema_session_loads_read_only_and_is_never_saved() -> Result<()> {
+    let _env_lock = lock_env();
+    let temp_home = tempfile::Builder::new()
+        .prefix("jcode-session-migration-test-")
+        .tempdir()
+        .map_err(|e| anyhow!(e))?;
+    let _home = EnvVarGuard::set("JCODE_HOME", temp_home.path().as_os_str());
+
+    let session_id = "session_from_the_future";
+    let newer = serde_json::json!({
+        "schema_version": SESSION_SCHEMA_VERSION + 1,
+        "id": session_id,
+        "title": "Written by a rebuilt binary",
+        "created_at": "2026-03-01T10:00:00Z",
+        "updated_at": "2026-03-01T10:30:00Z",
+        "messages": [
+            {
+                "id": "msg_ok",
+                "role": "user",
+                "content": [{ "type": "text", "text": "still readable" }]
+            },
+            {
+                "id": "msg_new_block",
+                "role": "assistant",
+                "content": [{ "type": "hologram", "frames": 3 }]
+            }
+        ],
+        "model": "gpt-5.4",
+        "status": { "Paused": { "until": "2026-03-02T00:00:00Z" } },
+        "brand_new_field": { "nested": true }
+    });
+    let contents = serde_json::to_string_pretty(&newer)?;
+    let path = install_session_fixture(temp_home.path(), session_id, &contents)?;
+
+    let mut session = Session::load(session_id)?;
+    assert!(session.read_only_reason().is_some());
+    assert_eq!(session.schema_version, SESSION_SCHEMA_VERSION + 1);
+    assert_eq!(session.messages.len(), 1);
+    assert_eq!(session.model.as_deref(), Some("gpt-5.4"));
+    assert_eq!(session.status, SessionStatus::Active);
+
+    let rendered = render_messages(&session);
+    assert_eq!(rendered[0].role, "system");
+    assert!(rendered[0].content.contains("newer jcode"));
+
+    session.rename_title(Some("edited".to_string()));
+    session.save()?;
+    assert_eq!(std::fs::read_to_string(&path)?, contents);
+    assert!(!pre_migration_backup_path(&path).exists());
+    assert_eq!(
+        migrate_session(session_id)?,
+        SessionSchemaStatus::Newer {
+            version: SESSION_SCHEMA_VERSION + 1
+        }
+    );
+    Ok(())
+}
@@ -70,6 +70,10 @@ pub struct Sidecar {
     /// per-model behavior applies. Used by the memory benchmark to pin
     /// GPT-5.5 with no thinking.
     reasoning_override: Option<String>,
+    /// Origin that provider-path calls are counted under.
+    usage_origin: crate::usage::UsageOrigin,
+    /// Session that provider-path calls are counted against, if any.
+    usage_session_id: Option<String>,
 }
 
 impl Sidecar {
@@ -103,6 +107,8 @@ impl Sidecar {
             max_tokens: DEFAULT_MAX_TOKENS,
             backend,
             reasoning_override: None,
+            usage_origin: crate::usage::UsageOrigin::Memory,
+            usage_session_id: None,
         }
     }
 
@@ -123,10 +129,12 @@ impl Sidecar {
         } else if auth::claude::load_credentials().is_ok() {
             (SidecarBackend::Claude, SIDECAR_CLAUDE_MODEL.to_string())
         } else if let Some(provider) = crate::provider::active_provider_fork() {
-            // Dispatch through whatever provider the user is running on. The
-            // model string is informational here; the provider already has the
-            // user's selected model and routes accordingly.
-            (SidecarBackend::Provider, provider.model())
+            // Dispatch through whatever provider the user is running on, on
+            // its background model. The model string is informational here;
+            // `background_model` resolves it again per call.
+            let model = crate::background_model::resolve(provider.as_ref())
+                .unwrap_or_else(|| provider.model());
+            (SidecarBackend::Provider, model)
         } else {
             // No credentials and no live provider: default to Claude so the
             // eventual error message is actionable.
@@ -166,6 +174,8 @@ impl Sidecar {
             max_tokens: DEFAULT_MAX_TOKENS,
             backend: SidecarBackend::Claude,
             reasoning_override: None,
+            usage_origin: crate::usage::UsageOrigin::Memory,
+            usage_session_id: None,
         }
     }
 
@@ -179,9 +189,24 @@ impl Sidecar {
             max_tokens: DEFAULT_MAX_TOKENS,
             backend: SidecarBackend::OpenAI,
             reasoning_override: reasoning_effort,
+            usage_origin: crate::usage::UsageOrigin::Memory,
+            usage_session_id: None,
         }
     }
 
+    /// Count calls through the live provider under `origin` instead of
+    /// memory work.
+    pub fn with_usage_origin(mut self, origin: crate::usage::UsageOrigin) -> Self {
+        self.usage_origin = origin;
+        self
+    }
+
+    /// Count calls through the live provider against `session_id`.
+    pub fn for_session(mut self, session_id: impl Into<String>) -> Self {
+        self.usage_session_id = Some(session_id.into());
+        self
+    }
+
     /// Return the currently selected backend label.
     pub fn backend_name(&self) -> &'static str {
         match self.backend {
@@ -205,16 +230,22 @@ impl Sidecar {
     ///
     /// This is the universal path: it works for every provider jcode supports,
     /// because `complete_simple` is a default method on the `Provider` trait that
-    /// collects the streamed `TextDelta`s into a single string. The provider was
-    /// forked at construction time, so it carries the user's selected model.
+    /// collects the streamed `TextDelta`s into a single string. The call runs on
+    /// the provider's background model and falls back to the user's selected
+    /// model if that one is unavailable.
     async fn complete_via_provider(&self, system: &str, user_message: &str) -> Result<String> {
         let provider = crate::provider::active_provider_fork().context(
             "No active provider registered for sidecar; memory features require a logged-in provider",
         )?;
-        provider
-            .complete_simple(user_message, system)
-            .await
-            .context("Sidecar completion via active provider failed")
+        crate::background_model::complete_simple(
+            provider.as_ref(),
+            &self.usage_origin,
+            self.usage_session_id.as_deref(),
+            user_message,
+            system,
+        )
+        .await
+        .context("Sidecar completion via active provider failed")
     }
 
     /// Complete via OpenAI Responses API.
@@ -8,6 +8,18 @@ use std::sync::Arc;
 use std::sync::OnceLock;
 use tokio::sync::RwLock;
 
+mod import;
+mod validate;
+
+pub use import::{
+    ClaudeImportReport, IMPORT_ORIGIN_KEY, ImportAction, ImportKind, ImportedSkill, SkippedImport,
+    import_claude_dir, resolve_claude_dir,
+};
+pub use validate::{
+    MAX_DESCRIPTION_CHARS, MAX_SKILL_ASSET_BYTES, MAX_SKILL_FILE_BYTES, SkillCheck, SkillIssue,
+    SkillIssueLevel, check_skill_name, scaffold_skill, validate_skill_dir,
+};
+
 /// A skill definition from SKILL.md
 #[derive(Debug, Clone)]
 pub struct Skill {
@@ -16,21 +28,46 @@ pub struct Skill {
     pub allowed_tools: Option<Vec<String>>,
     pub content: String,
     pub path: PathBuf,
+    /// Context that loads the skill automatically (see [`SkillTriggers`]).
+    pub triggers: SkillTriggers,
     search_text: String,
 }
 
+/// Optional `triggers:` frontmatter. When the session's working set matches
+/// any entry, the agent loads the skill without being asked.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct SkillTriggers {
+    /// Globs matched against files in or touched by the session, e.g. `**/*.tf`.
+    pub files: Vec<String>,
+    /// Case-insensitive words matched against the user's messages.
+    pub keywords: Vec<String>,
+    /// Tool names whose use activates the skill.
+    pub tools: Vec<String>,
+}
+
+impl SkillTriggers {
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty() && self.keywords.is_empty() && self.tools.is_empty()
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct SkillFrontmatter {
     name: String,
     description: String,
     #[serde(rename = "allowed-tools")]
     allowed_tools: Option<String>,
+    #[serde(default)]
+    triggers: SkillTriggers,
 }
 
 /// Registry of available skills
 #[derive(Debug, Default, Clone)]
 pub struct SkillRegistry {
     skills: HashMap<String, Skill>,
+    /// Skill directories whose SKILL.md failed to parse, keyed by directory name.
+    invalid: HashMap<String, PathBuf>,
 }
 
 impl SkillRegistry {
@@ -236,6 +273,21 @@ impl SkillRegistry {
         working_dir.map(|dir| dir.join(&path)).unwrap_or(path)
     }
 
+    /// Directories scanned for skills, in load order (later entries win).
+    pub fn skill_roots(working_dir: Option<&Path>) -> Vec<PathBuf> {
+        let mut roots = Vec::new();
+        if let Ok(jcode_dir) = crate::storage::jcode_dir() {
+            roots.push(jcode_dir.join("skills"));
+        }
+        if let Ok(agents_skills) = crate::storage::user_home_path(".agents/skills") {
+            roots.push(agents_skills);
+        }
+        for name in [".jcode", ".agents", ".claude"] {
+            roots.push(Self::project_local_dir(working_dir, name));
+        }
+        roots
+    }
+
     fn load_project_local_dirs(&mut self, working_dir: Option<&Path>) -> Result<()> {
         // Load from ./.jcode/skills/ (project-local jcode skills)
         let local_jcode = Self::project_local_dir(working_dir, ".jcode");
@@ -255,9 +307,43 @@ impl SkillRegistry {
             self.load_from_dir(&local_claude)?;
         }
 
+        self.load_claude_commands(working_dir);
+
         Ok(())
     }
 
+    /// Translate ./.claude/commands/*.md in place so a project's Claude Code
+    /// slash commands keep working. Real skills with the same name win.
+    fn load_claude_commands(&mut self, working_dir: Option<&Path>) -> usize {
+        let commands_dir = Path::new(".claude").join("commands");
+        let commands_dir = working_dir
+            .map(|dir| dir.join(&commands_dir))
+            .unwrap_or(commands_dir);
+        if !commands_dir.is_dir() {
+            return 0;
+        }
+
+        let mut count = 0;
+        for (path, name) in import::command_files(&commands_dir) {
+            if self.skills.contains_key(&name) {
+                continue;
+            }
+            match import::translate_command(&path, name) {
+                Ok(command) => {
+                    let skill = command.into_skill(&path);
+                    self.skills.insert(skill.name.clone(), skill);
+                    count += 1;
+                }
+                Err(err) => crate::logging::warn(&format!(
+                    "Skills: failed to load command {}: {}",
+                    path.display(),
+                    err
+                )),
+            }
+        }
+        count
+    }
+
     /// Load skills from a directory
     fn load_from_dir(&mut self, dir: &Path) -> Result<()> {
         if !dir.is_dir() {
@@ -270,10 +356,8 @@ impl SkillRegistry {
 
             if path.is_dir() {
                 let skill_file = path.join("SKILL.md");
-                if skill_file.exists()
-                    && let Ok(skill) = Self::parse_skill(&skill_file)
-                {
-                    self.skills.insert(skill.name.clone(), skill);
+                if skill_file.exists() {
+                    self.insert_parsed(&path, &skill_file);
                 }
             }
         }
@@ -281,6 +365,31 @@ impl SkillRegistry {
         Ok(())
     }
 
+    /// Parse `skill_file` and register it, remembering `dir` as invalid when
+    /// parsing fails so `skill_manage` can explain why the skill is missing.
+    fn insert_parsed(&mut self, dir: &Path, skill_file: &Path) -> bool {
+        let dir_name = dir
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        match Self::parse_skill(skill_file) {
+            Ok(skill) => {
+                self.invalid.remove(&dir_name);
+                self.skills.insert(skill.name.clone(), skill);
+                true
+            }
+            Err(err) => {
+                crate::logging::warn(&format!(
+                    "Skills: failed to load {}: {}",
+                    skill_file.display(),
+                    err
+                ));
+                self.invalid.insert(dir_name, dir.to_path_buf());
+                false
+            }
+        }
+    }
+
     /// Parse a SKILL.md file
     fn parse_skill(path: &Path) -> Result<Skill> {
         let content = std::fs::read_to_string(path)?;
@@ -292,6 +401,7 @@ impl SkillRegistry {
             name,
             description,
             allowed_tools,
+            triggers,
         } = frontmatter;
 
         let allowed_tools =
@@ -304,29 +414,17 @@ impl SkillRegistry {
             allowed_tools,
             content: body,
             path: path.to_path_buf(),
+            triggers,
             search_text,
         })
     }
 
     /// Parse YAML frontmatter from markdown
     fn parse_frontmatter(content: &str) -> Result<(SkillFrontmatter, String)> {
-        let content = content.trim();
-
-        if !content.starts_with("---") {
-            anyhow::bail!("Missing YAML frontmatter");
-        }
-
-        let rest = &content[3..];
-        let end = rest
-            .find("---")
-            .ok_or_else(|| anyhow::anyhow!("Unclosed frontmatter"))?;
-
-        let yaml = &rest[..end];
-        let body = rest[end + 3..].trim().to_string();
-
+        let (yaml, body) = split_frontmatter(content)?;
         let frontmatter: SkillFrontmatter = serde_yaml::from_str(yaml)?;
 
-        Ok((frontmatter, body))
+        Ok((frontmatter, body.trim().to_string()))
     }
 
     /// Get a skill by name
@@ -356,7 +454,11 @@ impl SkillRegistry {
 
         if let Some(path) = path {
             if path.exists() {
-                let skill = Self::parse_skill(&path)?;
+                let skill = if path.file_name().is_some_and(|name| name == "SKILL.md") {
+                    Self::parse_skill(&path)?
+                } else {
+                    import::translate_command(&path, import::command_name(&path))?.into_skill(&path)
+                };
                 self.skills.insert(skill.name.clone(), skill);
                 Ok(true)
             } else {
@@ -378,6 +480,7 @@ impl SkillRegistry {
     /// active session working directory.
     pub fn reload_all_for_working_dir(&mut self, working_dir: Option<&Path>) -> Result<usize> {
         self.skills.clear();
+        self.invalid.clear();
 
         let mut count = 0;
 
@@ -414,6 +517,8 @@ impl SkillRegistry {
             count += self.load_from_dir_count(&local_claude)?;
         }
 
+        count += self.load_claude_commands(working_dir);
+
         Ok(count)
     }
 
@@ -430,10 +535,7 @@ impl SkillRegistry {
 
             if path.is_dir() {
                 let skill_file = path.join("SKILL.md");
-                if skill_file.exists()
-                    && let Ok(skill) = Self::parse_skill(&skill_file)
-                {
-                    self.skills.insert(skill.name.clone(), skill);
+                if skill_file.exists() && self.insert_parsed(&path, &skill_file) {
                     count += 1;
                 }
             }
@@ -452,6 +554,18 @@ impl SkillRegistry {
         }
     }
 
+    /// Directory of a skill that was found on disk but failed to load.
+    pub fn invalid_skill_dir(&self, name: &str) -> Option<&Path> {
+        self.invalid.get(name).map(PathBuf::as_path)
+    }
+
+    /// Names of skill directories that failed to load, sorted.
+    pub fn invalid_skill_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.invalid.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
     /// Return true if a skill with the given name is currently loaded.
     pub fn contains(&self, name: &str) -> bool {
         self.skills.contains_key(name)
@@ -696,6 +810,22 @@ impl Skill {
     }
 }
 
+/// Split SKILL.md into its YAML frontmatter and markdown body.
+fn split_frontmatter(content: &str) -> Result<(&str, &str)> {
+    let content = content.trim();
+
+    if !content.starts_with("---") {
+        anyhow::bail!("Missing YAML frontmatter");
+    }
+
+    let rest = &content[3..];
+    let end = rest
+        .find("---")
+        .ok_or_else(|| anyhow::anyhow!("Unclosed frontmatter"))?;
+
+    Ok((&rest[..end], &rest[end + 3..]))
+}
+
 fn build_skill_search_text(name: &str, description: &str, content: &str) -> String {
     normalize_skill_search_text(&format!("{}\n{}\n{}", name, description, content))
 }
@@ -727,6 +857,7 @@ mod tests {
             allowed_tools: None,
             content: content.to_string(),
             path: PathBuf::from(format!("/tmp/{name}/SKILL.md")),
+            triggers: SkillTriggers::default(),
             search_text: build_skill_search_text(name, description, content),
         }
     }
@@ -936,4 +1067,77 @@ mod tests {
         assert!(registry.contains("present-skill"));
         assert!(!registry.contains("missing-skill"));
     }
+
+    #[test]
+    fn registry_remembers_skill_dirs_that_fail_to_parse() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let dir = temp
+            .path()
+            .join(".jcode")
+            .join("skills")
+            .join("half-written");
+        std::fs::create_dir_all(&dir).expect("create skill dir");
+        std::fs::write(
+            dir.join("SKILL.md"),
+            "---\nname: half-written\n---\n\nBody\n",
+        )
+        .expect("write skill");
+
+        let registry = SkillRegistry::load_for_working_dir(Some(temp.path())).expect("load skills");
+        assert!(!registry.contains("half-written"));
+        assert_eq!(
+            registry.invalid_skill_dir("half-written"),
+            Some(dir.as_path())
+        );
+        assert_eq!(registry.invalid_skill_names(), vec!["half-written"]);
+    }
+
+    #[test]
+    fn project_claude_commands_load_as_skills_without_shadowing_real_ones() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let commands = temp.path().join(".claude").join("commands");
+        std::fs::create_dir_all(commands.join("git")).expect("create commands dir");
+        std::fs::write(
+            commands.join("git").join("commit-msg.md"),
+            "---\ndescription: Draft a commit message\n---\n\nSummarize the staged diff.\n",
+        )
+        .expect("write command");
+        std::fs::write(commands.join("shadowed.md"), "Command body\n").expect("write command");
+        write_test_skill(temp.path(), ".jcode", "shadowed");
+
+        let registry = SkillRegistry::load_for_working_dir(Some(temp.path())).expect("load skills");
+        let command = registry.get("commit-msg").expect("command should load");
+        assert_eq!(command.description, "Draft a commit message");
+        assert_eq!(command.content, "Summarize the staged diff.");
+        assert_eq!(
+            registry.get("shadowed").expect("skill").description,
+            "Test skill shadowed"
+        );
+    }
+
+    #[test]
+    fn parses_optional_trigger_metadata() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let dir = temp.path().join(".jcode").join("skills").join("terraform");
+        std::fs::create_dir_all(&dir).expect("create skill dir");
+        std::fs::write(
+            dir.join("SKILL.md"),
+            "---\nname: terraform\ndescription: Terraform help\ntriggers:\n  files: [\"**/*.tf\"]\n  keywords: [terraform]\n---\n\nPlan before apply.\n",
+        )
+        .expect("write skill");
+        write_test_skill(temp.path(), ".jcode", "plain");
+
+        let registry = SkillRegistry::load_for_working_dir(Some(temp.path())).expect("load skills");
+        let triggers = &registry.get("terraform").expect("terraform skill").triggers;
+        assert_eq!(triggers.files, vec!["**/*.tf"]);
+        assert_eq!(triggers.keywords, vec!["terraform"]);
+        assert!(triggers.tools.is_empty());
+        assert!(
+            registry
+                .get("plain")
+                .expect("plain skill")
+                .triggers
+                .is_empty()
+        );
+    }
 }
@@ -0,0 +1,767 @@
+//! Translate Claude Code `.claude/skills/` and `.claude/commands/` layouts into
+//! jcode skills. Used by `jcode skill import --from-claude` and by the registry
+//! to pick up a project's `.claude/commands/` without copying anything.
+
+use anyhow::Result;
+use serde_yaml::{Mapping, Value};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use super::{Skill, SkillTriggers, build_skill_search_text, check_skill_name, split_frontmatter};
+
+/// Frontmatter key recording where an imported skill came from. Re-importing
+/// only overwrites skills whose origin matches, so hand-written skills are safe.
+pub const IMPORT_ORIGIN_KEY: &str = "imported-from";
+
+const MAX_COMMAND_DEPTH: usize = 6;
+const MAX_FALLBACK_DESCRIPTION_CHARS: usize = 200;
+
+/// Claude Code skill frontmatter keys that carry over unchanged.
+const KNOWN_SKILL_KEYS: [&str; 7] = [
+    "name",
+    "description",
+    "allowed-tools",
+    "triggers",
+    "license",
+    "metadata",
+    IMPORT_ORIGIN_KEY,
+];
+
+/// Claude Code tool names and their closest jcode equivalent.
+const TOOL_NAME_MAP: [(&str, &str); 12] = [
+    ("Bash", "bash"),
+    ("Read", "read"),
+    ("Write", "write"),
+    ("Edit", "edit"),
+    ("MultiEdit", "multiedit"),
+    ("Grep", "agentgrep"),
+    ("Glob", "ls"),
+    ("LS", "ls"),
+    ("WebFetch", "webfetch"),
+    ("WebSearch", "websearch"),
+    ("TodoWrite", "todo"),
+    ("Task", "subagent"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportKind {
+    Skill,
+    Command,
+}
+
+impl ImportKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Skill => "skill",
+            Self::Command => "command",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportAction {
+    Created,
+    Updated,
+    Unchanged,
+}
+
+impl ImportAction {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Updated => "updated",
+            Self::Unchanged => "unchanged",
+        }
+    }
+}
+
+/// One skill written (or confirmed up to date) by an import.
+#[derive(Debug, Clone)]
+pub struct ImportedSkill {
+    pub name: String,
+    pub kind: ImportKind,
+    pub source: PathBuf,
+    pub dest: PathBuf,
+    pub action: ImportAction,
+    /// Parts of the source that had no jcode equivalent.
+    pub notes: Vec<String>,
+}
+
+/// A source entry that was not imported, with the reason.
+#[derive(Debug, Clone)]
+pub struct SkippedImport {
+    pub source: PathBuf,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ClaudeImportReport {
+    pub imported: Vec<ImportedSkill>,
+    pub skipped: Vec<SkippedImport>,
+}
+
+impl ClaudeImportReport {
+    pub fn count(&self, action: ImportAction) -> usize {
+        self.imported
+            .iter()
+            .filter(|item| item.action == action)
+            .count()
+    }
+
+    fn skip(&mut self, source: &Path, reason: impl Into<String>) {
+        self.skipped.push(SkippedImport {
+            source: source.to_path_buf(),
+            reason: reason.into(),
+        });
+    }
+}
+
+/// A `.claude/commands/*.md` file rendered as a jcode skill.
+#[derive(Debug, Clone)]
+pub(super) struct TranslatedCommand {
+    pub name: String,
+    pub description: String,
+    pub allowed_tools: Option<String>,
+    pub body: String,
+    pub notes: Vec<String>,
+}
+
+impl TranslatedCommand {
+    pub(super) fn into_skill(self, path: &Path) -> Skill {
+        let allowed_tools = self
+            .allowed_tools
+            .map(|tools| tools.split(',').map(|t| t.trim().to_string()).collect());
+        let search_text = build_skill_search_text(&self.name, &self.description, &self.body);
+        Skill {
+            name: self.name,
+            description: self.description,
+            allowed_tools,
+            content: self.body,
+            path: path.to_path_buf(),
+            triggers: SkillTriggers::default(),
+            search_text,
+        }
+    }
+
+    fn render(&self, origin: &str) -> Result<String> {
+        let mut frontmatter = Mapping::new();
+        frontmatter.insert("name".into(), self.name.clone().into());
+        frontmatter.insert("description".into(), self.description.clone().into());
+        if let Some(tools) = &self.allowed_tools {
+            frontmatter.insert("allowed-tools".into(), tools.clone().into());
+        }
+        frontmatter.insert(IMPORT_ORIGIN_KEY.into(), origin.into());
+        render_skill_file(&frontmatter, &self.body)
+    }
+}
+
+/// Accept either a `.claude` directory or a directory containing one.
+pub fn resolve_claude_dir(path: &Path) -> PathBuf {
+    let nested = path.join(".claude");
+    if nested.is_dir() {
+        nested
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// Import every skill in `claude_dir/skills/` and command in
+/// `claude_dir/commands/` into `dest_root`, one skill directory each.
+pub fn import_claude_dir(claude_dir: &Path, dest_root: &Path) -> Result<ClaudeImportReport> {
+    let skills_dir = claude_dir.join("skills");
+    let commands_dir = claude_dir.join("commands");
+    if !skills_dir.is_dir() && !commands_dir.is_dir() {
+        anyhow::bail!(
+            "{} has no skills/ or commands/ directory to import",
+            claude_dir.display()
+        );
+    }
+    std::fs::create_dir_all(dest_root)?;
+
+    let mut report = ClaudeImportReport::default();
+    let mut claimed = HashSet::new();
+
+    let mut skill_dirs: Vec<PathBuf> = std::fs::read_dir(&skills_dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir())
+                .filter(|path| !file_name(path).starts_with('.'))
+                .collect()
+        })
+        .unwrap_or_default();
+    skill_dirs.sort();
+    for dir in skill_dirs {
+        if let Err(err) = import_skill_dir(&dir, dest_root, &mut claimed, &mut report) {
+            report.skip(&dir, err.to_string());
+        }
+    }
+
+    for (path, name) in command_files(&commands_dir) {
+        if let Err(err) = import_command(&path, name, dest_root, &mut claimed, &mut report) {
+            report.skip(&path, err.to_string());
+        }
+    }
+
+    Ok(report)
+}
+
+fn import_skill_dir(
+    dir: &Path,
+    dest_root: &Path,
+    claimed: &mut HashSet<String>,
+    report: &mut ClaudeImportReport,
+) -> Result<()> {
+    let skill_file = dir.join("SKILL.md");
+    if !skill_file.is_file() {
+        report.skip(dir, "no SKILL.md");
+        return Ok(());
+    }
+    let content = std::fs::read_to_string(&skill_file)?;
+    let (yaml, body) = split_frontmatter(&content)?;
+    let mut frontmatter: Mapping = serde_yaml::from_str(yaml)?;
+
+    let name = match frontmatter.get("name").and_then(Value::as_str) {
+        Some(name) => name.to_string(),
+        None => file_name(dir),
+    };
+    check_skill_name(&name).map_err(|err| anyhow::anyhow!(err))?;
+    if frontmatter
+        .get("description")
+        .and_then(Value::as_str)
+        .is_none()
+    {
+        anyhow::bail!("SKILL.md has no `description`");
+    }
+    if !claimed.insert(name.clone()) {
+        report.skip(dir, format!("another entry already imported /{}", name));
+        return Ok(());
+    }
+
+    let mut notes = Vec::new();
+    frontmatter.insert("name".into(), name.clone().into());
+    if let Some(tools) = frontmatter.get("allowed-tools").cloned() {
+        match map_allowed_tools(&tools, &mut notes) {
+            Some(mapped) => frontmatter.insert("allowed-tools".into(), mapped.into()),
+            None => frontmatter.remove("allowed-tools"),
+        };
+    }
+    for key in frontmatter.keys().filter_map(Value::as_str) {
+        if !KNOWN_SKILL_KEYS.contains(&key) {
+            notes.push(format!(
+                "frontmatter `{}` is kept but ignored by jcode",
+                key
+            ));
+        }
+    }
+
+    let origin = origin_of(dir);
+    frontmatter.insert(IMPORT_ORIGIN_KEY.into(), origin.clone().into());
+    let rendered = render_skill_file(&frontmatter, body.trim())?;
+
+    let dest = dest_root.join(&name);
+    let Some(existed) = claim_dest(&dest, &origin, dir, report)? else {
+        return Ok(());
+    };
+    let mut changed = sync_dir(dir, &dest)?;
+    changed |= write_if_changed(&dest.join("SKILL.md"), rendered.as_bytes())?;
+
+    report.imported.push(ImportedSkill {
+        name,
+        kind: ImportKind::Skill,
+        source: dir.to_path_buf(),
+        dest,
+        action: action_for(existed, changed),
+        notes,
+    });
+    Ok(())
+}
+
+fn import_command(
+    path: &Path,
+    name: String,
+    dest_root: &Path,
+    claimed: &mut HashSet<String>,
+    report: &mut ClaudeImportReport,
+) -> Result<()> {
+    let command = translate_command(path, name)?;
+    if !claimed.insert(command.name.clone()) {
+        report.skip(
+            path,
+            format!("another entry already imported /{}", command.name),
+        );
+        return Ok(());
+    }
+
+    let origin = origin_of(path);
+    let rendered = command.render(&origin)?;
+    let dest = dest_root.join(&command.name);
+    let Some(existed) = claim_dest(&dest, &origin, path, report)? else {
+        return Ok(());
+    };
+    std::fs::create_dir_all(&dest)?;
+    let changed = write_if_changed(&dest.join("SKILL.md"), rendered.as_bytes())?;
+
+    report.imported.push(ImportedSkill {
+        name: command.name,
+        kind: ImportKind::Command,
+        source: path.to_path_buf(),
+        dest,
+        action: action_for(existed, changed),
+        notes: command.notes,
+    });
+    Ok(())
+}
+
+/// Returns whether `dest` already existed, or `None` (after recording a skip)
+/// when it holds a skill that this source did not create.
+fn claim_dest(
+    dest: &Path,
+    origin: &str,
+    source: &Path,
+    report: &mut ClaudeImportReport,
+) -> Result<Option<bool>> {
+    if !dest.exists() {
+        return Ok(Some(false));
+    }
+    match imported_origin(&dest.join("SKILL.md")) {
+        Some(existing) if existing == origin => Ok(Some(true)),
+        Some(existing) => {
+            report.skip(
+                source,
+                format!("{} was imported from {}", dest.display(), existing),
+            );
+            Ok(None)
+        }
+        None => {
+            report.skip(
+                source,
+                format!(
+                    "{} already exists and was not imported; rename or remove it to import",
+                    dest.display()
+                ),
+            );
+            Ok(None)
+        }
+    }
+}
+
+fn action_for(existed: bool, changed: bool) -> ImportAction {
+    match (existed, changed) {
+        (false, _) => ImportAction::Created,
+        (true, true) => ImportAction::Updated,
+        (true, false) => ImportAction::Unchanged,
+    }
+}
+
+/// The `imported-from` marker of an existing SKILL.md, if any.
+fn imported_origin(skill_file: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(skill_file).ok()?;
+    let (yaml, _) = split_frontmatter(&content).ok()?;
+    let frontmatter: Mapping = serde_yaml::from_str(yaml).ok()?;
+    frontmatter
+        .get(IMPORT_ORIGIN_KEY)
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+fn origin_of(path: &Path) -> String {
+    std::fs::canonicalize(path)
+        .unwrap_or_else(|_| path.to_path_buf())
+        .display()
+        .to_string()
+}
+
+/// `.claude/commands/**/*.md`, sorted, paired with the slash-command name.
+/// Subdirectories only namespace commands in Claude Code, so the name is the
+/// file stem.
+pub(super) fn command_files(commands_dir: &Path) -> Vec<(PathBuf, String)> {
+    let mut files = Vec::new();
+    collect_markdown(commands_dir, 0, &mut files);
+    files.sort();
+    files
+        .into_iter()
+        .map(|path| {
+            let name = command_name(&path);
+            (path, name)
+        })
+        .collect()
+}
+
+fn collect_markdown(dir: &Path, depth: usize, files: &mut Vec<PathBuf>) {
+    if depth > MAX_COMMAND_DEPTH {
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if file_name(&path).starts_with('.') {
+            continue;
+        }
+        if path.is_dir() {
+            collect_markdown(&path, depth + 1, files);
+        } else if path.extension().is_some_and(|ext| ext == "md") {
+            files.push(path);
+        }
+    }
+}
+
+/// Slash-command name for a command file: the file stem, lowercased, with
+/// characters jcode skill names can't hold replaced by `-`.
+pub(super) fn command_name(path: &Path) -> String {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let mut name = String::with_capacity(stem.len());
+    for c in stem.chars() {
+        let c = if c.is_ascii_lowercase() || c.is_ascii_digit() {
+            c
+        } else {
+            '-'
+        };
+        if !(c == '-' && name.ends_with('-')) {
+            name.push(c);
+        }
+    }
+    name.trim_matches('-').to_string()
+}
+
+/// Translate a Claude Code command file. Frontmatter is optional there; the
+/// description falls back to the first line of the prompt.
+pub(super) fn translate_command(path: &Path, name: String) -> Result<TranslatedCommand> {
+    check_skill_name(&name).map_err(|err| anyhow::anyhow!(err))?;
+    let content = std::fs::read_to_string(path)?;
+    let (frontmatter, body) = if content.trim_start().starts_with("---") {
+        let (yaml, body) = split_frontmatter(&content)?;
+        let frontmatter: Mapping = if yaml.trim().is_empty() {
+            Mapping::new()
+        } else {
+            serde_yaml::from_str(yaml)?
+        };
+        (frontmatter, body.trim().to_string())
+    } else {
+        (Mapping::new(), content.trim().to_string())
+    };
+    if body.is_empty() {
+        anyhow::bail!("command has no prompt text");
+    }
+
+    let mut notes = Vec::new();
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    if stem != name {
+        notes.push(format!("renamed /{} to /{}", stem, name));
+    }
+
+    let mut description = None;
+    let mut allowed_tools = None;
+    for (key, value) in &frontmatter {
+        let Some(key) = key.as_str() else {
+            continue;
+        };
+        match key {
+            "description" => description = value.as_str().map(str::to_string),
+            "allowed-tools" => allowed_tools = map_allowed_tools(value, &mut notes),
+            "argument-hint" => notes.push(format!(
+                "argument-hint `{}` dropped: jcode skills take no arguments",
+                yaml_inline(value)
+            )),
+            "model" => notes.push(format!(
+                "model `{}` ignored: skills run on the session's model",
+                yaml_inline(value)
+            )),
+            other => notes.push(format!("frontmatter `{}` has no jcode equivalent", other)),
+        }
+    }
+    let description = description
+        .map(|d| d.trim().to_string())
+        .filter(|d| !d.is_empty())
+        .unwrap_or_else(|| fallback_description(&body, &name));
+
+    if body.contains("$ARGUMENTS") || has_positional_argument(&body) {
+        notes.push("uses $ARGUMENTS placeholders, which jcode leaves as literal text".to_string());
+    }
+    if body.lines().any(|line| line.trim_start().starts_with("!`")) {
+        notes.push("!`command` lines are not pre-run; the agent sees them as text".to_string());
+    }
+
+    Ok(TranslatedCommand {
+        name,
+        description,
+        allowed_tools,
+        body,
+        notes,
+    })
+}
+
+fn fallback_description(body: &str, name: &str) -> String {
+    let first_line = body
+        .lines()
+        .map(|line| line.trim().trim_start_matches('#').trim())
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+    if first_line.is_empty() {
+        return format!("Imported Claude Code command /{}", name);
+    }
+    let mut description: String = first_line
+        .chars()
+        .take(MAX_FALLBACK_DESCRIPTION_CHARS)
+        .collect();
+    if description.len() < first_line.len() {
+        description.push('…');
+    }
+    description
+}
+
+fn has_positional_argument(body: &str) -> bool {
+    body.as_bytes()
+        .windows(2)
+        .any(|pair| pair[0] == b'$' && (b'1'..=b'9').contains(&pair[1]))
+}
+
+/// Map Claude Code tool names to jcode's, noting patterns and unknown tools.
+/// Returns `None` when nothing maps.
+fn map_allowed_tools(value: &Value, notes: &mut Vec<String>) -> Option<String> {
+    let entries: Vec<String> = match value {
+        Value::String(list) => split_tool_list(list),
+        Value::Sequence(items) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        _ => {
+            notes.push("allowed-tools is not a list; dropped".to_string());
+            return None;
+        }
+    };
+
+    let mut mapped: Vec<&str> = Vec::new();
+    for entry in entries {
+        let entry = entry.trim();
+        let (tool, pattern) = match entry.split_once('(') {
+            Some((tool, _)) => (tool.trim(), true),
+            None => (entry, false),
+        };
+        let Some(&(_, jcode_tool)) = TOOL_NAME_MAP
+            .iter()
+            .find(|(claude, jcode)| *claude == tool || *jcode == tool)
+        else {
+            notes.push(format!("tool `{}` has no jcode equivalent; dropped", entry));
+            continue;
+        };
+        if pattern {
+            notes.push(format!(
+                "`{}` widened to `{}`: jcode has no per-command tool patterns",
+                entry, jcode_tool
+            ));
+        }
+        if !mapped.contains(&jcode_tool) {
+            mapped.push(jcode_tool);
+        }
+    }
+    (!mapped.is_empty()).then(|| mapped.join(", "))
+}
+
+/// Split a comma-separated tool list, ignoring commas inside `Bash(...)`.
+fn split_tool_list(list: &str) -> Vec<String> {
+    let mut entries = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    for c in list.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                entries.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    entries.push(current);
+    entries
+        .into_iter()
+        .map(|entry| entry.trim().to_string())
+        .filter(|entry| !entry.is_empty())
+        .collect()
+}
+
+fn yaml_inline(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => serde_yaml::to_string(other)
+            .map(|text| text.trim().to_string())
+            .unwrap_or_default(),
+    }
+}
+
+fn render_skill_file(frontmatter: &Mapping, body: &str) -> Result<String> {
+    let yaml = serde_yaml::to_string(frontmatter)?;
+    Ok(format!("---\n{}---\n\n{}\n", yaml, body))
+}
+
+/// Mirror `src` into `dst`, leaving SKILL.md to the caller and removing files
+/// that no longer exist in `src`. Returns whether anything changed.
+fn sync_dir(src: &Path, dst: &Path) -> std::io::Result<bool> {
+    std::fs::create_dir_all(dst)?;
+    let mut changed = false;
+    let mut keep = HashSet::new();
+    for entry in std::fs::read_dir(src)?.flatten() {
+        let name = entry.file_name();
+        let src_path = entry.path();
+        let dst_path = dst.join(&name);
+        if name == "SKILL.md" {
+            keep.insert(name);
+            continue;
+        }
+        if src_path.is_dir() {
+            if entry.file_type()?.is_symlink() {
+                continue;
+            }
+            changed |= sync_dir(&src_path, &dst_path)?;
+        } else if src_path.is_file() {
+            changed |= write_if_changed(&dst_path, &std::fs::read(&src_path)?)?;
+        } else {
+            continue;
+        }
+        keep.insert(name);
+    }
+    for entry in std::fs::read_dir(dst)?.flatten() {
+        if keep.contains(&entry.file_name()) {
+            continue;
+        }
+        let path = entry.path();
+        if path.is_dir() {
+            std::fs::remove_dir_all(&path)?;
+        } else {
+            std::fs::remove_file(&path)?;
+        }
+        changed = true;
+    }
+    Ok(changed)
+}
+
+fn write_if_changed(path: &Path, contents: &[u8]) -> std::io::Result<bool> {
+    if std::fs::read(path).is_ok_and(|existing| existing == contents) {
+        return Ok(false);
+    }
+    std::fs::write(path, contents)?;
+    Ok(true)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, contents: &str) {
+        std::fs::create_dir_all(path.parent().expect("parent")).expect("create dirs");
+        std::fs::write(path, contents).expect("write file");
+    }
+
+    fn claude_fixture(root: &Path) -> PathBuf {
+        let claude = root.join(".claude");
+        write(
+            &claude.join("skills/pdf-tools/SKILL.md"),
+            "---\nname: pdf-tools\ndescription: Work with PDFs\nallowed-tools: Read, Bash(python:*)\nmodel: opus\n---\n\nSee [helper](scripts/fill.py).\n",
+        );
+        write(
+            &claude.join("skills/pdf-tools/scripts/fill.py"),
+            "print(1)\n",
+        );
+        write(
+            &claude.join("commands/review-pr.md"),
+            "---\ndescription: Review a pull request\nargument-hint: <pr-number>\nallowed-tools: Bash(gh pr view:*), Read\n---\n\nReview PR $ARGUMENTS carefully.\n",
+        );
+        write(
+            &claude.join("commands/frontend/Fix_Styles.md"),
+            "# Fix the stylesheet\n\nTidy up CSS.\n",
+        );
+        claude
+    }
+
+    #[test]
+    fn imports_skills_and_commands_with_origin_markers() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let claude = claude_fixture(temp.path());
+        let dest = temp.path().join("skills");
+
+        let report = import_claude_dir(&claude, &dest).expect("import");
+        assert!(report.skipped.is_empty(), "{:?}", report.skipped);
+        let names: Vec<&str> = report.imported.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, vec!["pdf-tools", "fix-styles", "review-pr"]);
+        assert!(
+            report
+                .imported
+                .iter()
+                .all(|item| item.action == ImportAction::Created)
+        );
+
+        let review = std::fs::read_to_string(dest.join("review-pr/SKILL.md")).expect("read");
+        assert!(review.contains("description: Review a pull request"));
+        assert!(review.contains("allowed-tools: bash, read"));
+        assert!(review.contains(IMPORT_ORIGIN_KEY));
+        let review_notes = &report.imported[2].notes;
+        assert!(review_notes.iter().any(|n| n.contains("argument-hint")));
+        assert!(review_notes.iter().any(|n| n.contains("$ARGUMENTS")));
+        assert!(review_notes.iter().any(|n| n.contains("gh pr view")));
+
+        let styles = std::fs::read_to_string(dest.join("fix-styles/SKILL.md")).expect("read");
+        assert!(styles.contains("description: Fix the stylesheet"));
+        assert!(report.imported[1].notes[0].contains("renamed /Fix_Styles"));
+
+        assert!(dest.join("pdf-tools/scripts/fill.py").is_file());
+        assert!(report.imported[0].notes.iter().any(|n| n.contains("model")));
+        let check = super::super::validate_skill_dir(&dest.join("pdf-tools"));
+        assert!(check.is_ok(), "{}", check.render_issues());
+    }
+
+    #[test]
+    fn reimport_updates_in_place_and_skips_foreign_skills() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let claude = claude_fixture(temp.path());
+        let dest = temp.path().join("skills");
+        import_claude_dir(&claude, &dest).expect("first import");
+
+        write(
+            &claude.join("commands/review-pr.md"),
+            "Review the PR and summarize risks.\n",
+        );
+        std::fs::remove_file(claude.join("skills/pdf-tools/scripts/fill.py")).expect("remove");
+        write(
+            &dest.join("fix-styles/SKILL.md"),
+            "---\nname: fix-styles\ndescription: Mine\n---\n\nHand-written.\n",
+        );
+
+        let report = import_claude_dir(&claude, &dest).expect("second import");
+        let action = |name: &str| {
+            report
+                .imported
+                .iter()
+                .find(|item| item.name == name)
+                .map(|item| item.action)
+        };
+        assert_eq!(action("review-pr"), Some(ImportAction::Updated));
+        assert_eq!(action("pdf-tools"), Some(ImportAction::Updated));
+        assert_eq!(action("fix-styles"), None);
+        assert_eq!(report.skipped.len(), 1);
+        assert!(!dest.join("pdf-tools/scripts/fill.py").exists());
+        let mine = std::fs::read_to_string(dest.join("fix-styles/SKILL.md")).expect("read");
+        assert!(mine.contains("Hand-written."));
+
+        let again = import_claude_dir(&claude, &dest).expect("third import");
+        assert_eq!(again.count(ImportAction::Unchanged), 2);
+    }
+}
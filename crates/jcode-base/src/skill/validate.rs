@@ -0,0 +1,562 @@
+//! Skill scaffolding and validation, shared by `jcode skill` and `skill_manage`
+//! so both report the same diagnostics for a malformed skill.
+
+use anyhow::Result;
+use serde_yaml::Value;
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+/// Largest SKILL.md the loader is expected to inject into a prompt.
+pub const MAX_SKILL_FILE_BYTES: u64 = 256 * 1024;
+/// Descriptions are listed in every system prompt, so keep them short.
+pub const MAX_DESCRIPTION_CHARS: usize = 1024;
+/// Helper files larger than this are flagged; skills should stay lightweight.
+pub const MAX_SKILL_ASSET_BYTES: u64 = 4 * 1024 * 1024;
+const MAX_NAME_CHARS: usize = 64;
+const DESCRIPTION_PLACEHOLDER: &str = "TODO";
+const TRIGGER_KINDS: [&str; 3] = ["files", "keywords", "tools"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkillIssueLevel {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkillIssue {
+    pub level: SkillIssueLevel,
+    pub message: String,
+}
+
+impl SkillIssue {
+    fn error(message: impl Into<String>) -> Self {
+        Self {
+            level: SkillIssueLevel::Error,
+            message: message.into(),
+        }
+    }
+
+    fn warning(message: impl Into<String>) -> Self {
+        Self {
+            level: SkillIssueLevel::Warning,
+            message: message.into(),
+        }
+    }
+}
+
+/// Result of validating one skill directory.
+#[derive(Debug, Clone)]
+pub struct SkillCheck {
+    pub dir: PathBuf,
+    /// Name from the frontmatter, falling back to the directory name.
+    pub name: String,
+    pub issues: Vec<SkillIssue>,
+}
+
+impl SkillCheck {
+    /// True when the skill has no errors (warnings are allowed).
+    pub fn is_ok(&self) -> bool {
+        !self
+            .issues
+            .iter()
+            .any(|issue| issue.level == SkillIssueLevel::Error)
+    }
+
+    /// Indented `error:`/`warning:` lines, one per issue.
+    pub fn render_issues(&self) -> String {
+        let mut out = String::new();
+        for issue in &self.issues {
+            let label = match issue.level {
+                SkillIssueLevel::Error => "error",
+                SkillIssueLevel::Warning => "warning",
+            };
+            let _ = writeln!(out, "  {}: {}", label, issue.message);
+        }
+        out
+    }
+}
+
+/// Check that `name` is usable as a skill directory and slash command.
+pub fn check_skill_name(name: &str) -> std::result::Result<(), String> {
+    if name.is_empty() {
+        return Err("skill name is empty".to_string());
+    }
+    if name.chars().count() > MAX_NAME_CHARS {
+        return Err(format!(
+            "skill name is longer than {} characters",
+            MAX_NAME_CHARS
+        ));
+    }
+    if name.starts_with('-') || name.ends_with('-') {
+        return Err(format!(
+            "skill name `{}` must not start or end with `-`",
+            name
+        ));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        return Err(format!(
+            "skill name `{}` may only contain lowercase letters, digits, and `-`",
+            name
+        ));
+    }
+    Ok(())
+}
+
+/// Validate the skill in `dir` (the directory containing SKILL.md).
+pub fn validate_skill_dir(dir: &Path) -> SkillCheck {
+    let dir_name = dir
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default()
+        .to_string();
+    let mut check = SkillCheck {
+        dir: dir.to_path_buf(),
+        name: dir_name.clone(),
+        issues: Vec::new(),
+    };
+
+    let skill_file = dir.join("SKILL.md");
+    let size = match std::fs::metadata(&skill_file) {
+        Ok(meta) => meta.len(),
+        Err(_) => {
+            check.issues.push(SkillIssue::error(
+                "missing SKILL.md (run `jcode skill new <name>` for a template)",
+            ));
+            return check;
+        }
+    };
+    if size > MAX_SKILL_FILE_BYTES {
+        check.issues.push(SkillIssue::error(format!(
+            "SKILL.md is {} KiB; the limit is {} KiB (move reference material into separate files)",
+            size / 1024,
+            MAX_SKILL_FILE_BYTES / 1024
+        )));
+    }
+    let content = match std::fs::read_to_string(&skill_file) {
+        Ok(content) => content,
+        Err(err) => {
+            check
+                .issues
+                .push(SkillIssue::error(format!("cannot read SKILL.md: {}", err)));
+            return check;
+        }
+    };
+
+    let (yaml, body) = match super::split_frontmatter(&content) {
+        Ok(parts) => parts,
+        Err(err) => {
+            check.issues.push(SkillIssue::error(format!(
+                "{} (SKILL.md must start with a `---` block holding `name` and `description`)",
+                err
+            )));
+            return check;
+        }
+    };
+    let manifest = match serde_yaml::from_str::<Value>(yaml) {
+        Ok(Value::Mapping(manifest)) => manifest,
+        Ok(Value::Null) => Default::default(),
+        Ok(_) => {
+            check.issues.push(SkillIssue::error(
+                "frontmatter must be a YAML mapping of `key: value` fields",
+            ));
+            return check;
+        }
+        Err(err) => {
+            check.issues.push(SkillIssue::error(format!(
+                "frontmatter is not valid YAML: {}",
+                err
+            )));
+            return check;
+        }
+    };
+
+    match manifest.get("name") {
+        None => check.issues.push(SkillIssue::error(
+            "frontmatter is missing required field `name`",
+        )),
+        Some(Value::String(name)) => {
+            if let Err(problem) = check_skill_name(name) {
+                check.issues.push(SkillIssue::warning(problem));
+            }
+            if !dir_name.is_empty() && *name != dir_name {
+                check.issues.push(SkillIssue::warning(format!(
+                    "`name: {}` does not match the directory name `{}`; the skill is invoked as /{}",
+                    name, dir_name, name
+                )));
+            }
+            check.name = name.clone();
+        }
+        Some(_) => check
+            .issues
+            .push(SkillIssue::error("`name` must be a string")),
+    }
+
+    match manifest.get("description") {
+        None => check.issues.push(SkillIssue::error(
+            "frontmatter is missing required field `description`",
+        )),
+        Some(Value::String(description)) => {
+            let trimmed = description.trim();
+            if trimmed.is_empty() {
+                check
+                    .issues
+                    .push(SkillIssue::error("`description` is empty"));
+            } else if trimmed.starts_with(DESCRIPTION_PLACEHOLDER) {
+                check.issues.push(SkillIssue::warning(
+                    "`description` still holds the template placeholder; say when the agent should use this skill",
+                ));
+            }
+            let chars = trimmed.chars().count();
+            if chars > MAX_DESCRIPTION_CHARS {
+                check.issues.push(SkillIssue::warning(format!(
+                    "`description` is {} characters; keep it under {} since it is listed in every prompt",
+                    chars, MAX_DESCRIPTION_CHARS
+                )));
+            }
+        }
+        Some(_) => check
+            .issues
+            .push(SkillIssue::error("`description` must be a string")),
+    }
+
+    match manifest.get("allowed-tools") {
+        None => {}
+        Some(Value::String(tools)) => {
+            if tools.split(',').any(|tool| tool.trim().is_empty()) {
+                check.issues.push(SkillIssue::warning(
+                    "`allowed-tools` has an empty entry (use `bash, read, write`)",
+                ));
+            }
+        }
+        Some(_) => check.issues.push(SkillIssue::error(
+            "`allowed-tools` must be a comma-separated string, e.g. `allowed-tools: bash, read`",
+        )),
+    }
+
+    if let Some(inputs) = manifest.get("inputs") {
+        check_inputs(inputs, &mut check.issues);
+    }
+    if let Some(triggers) = manifest.get("triggers") {
+        check_triggers(triggers, &mut check.issues);
+    }
+
+    let body = body.trim();
+    if body.is_empty() {
+        check.issues.push(SkillIssue::error(
+            "SKILL.md has no instructions after the frontmatter",
+        ));
+    }
+    for target in referenced_files(body) {
+        if !dir.join(&target).exists() {
+            check.issues.push(SkillIssue::error(format!(
+                "instructions link to `{}`, which does not exist in {}",
+                target,
+                dir.display()
+            )));
+        }
+    }
+
+    check_asset_sizes(dir, dir, &mut check.issues);
+    check
+}
+
+/// `inputs` is an optional list of `{ name, description, required }` entries
+/// describing the arguments a skill accepts.
+fn check_inputs(inputs: &Value, issues: &mut Vec<SkillIssue>) {
+    let Value::Sequence(inputs) = inputs else {
+        issues.push(SkillIssue::error(
+            "`inputs` must be a list of `- name: ...` entries",
+        ));
+        return;
+    };
+    let mut seen = HashSet::new();
+    for (index, input) in inputs.iter().enumerate() {
+        let position = index + 1;
+        let Value::Mapping(input) = input else {
+            issues.push(SkillIssue::error(format!(
+                "`inputs` entry {} must be a mapping with a `name`",
+                position
+            )));
+            continue;
+        };
+        match input.get("name") {
+            Some(Value::String(name)) if !name.trim().is_empty() => {
+                if !seen.insert(name.clone()) {
+                    issues.push(SkillIssue::error(format!(
+                        "`inputs` declares `{}` more than once",
+                        name
+                    )));
+                }
+            }
+            _ => issues.push(SkillIssue::error(format!(
+                "`inputs` entry {} is missing a string `name`",
+                position
+            ))),
+        }
+        if let Some(description) = input.get("description")
+            && !description.is_string()
+        {
+            issues.push(SkillIssue::error(format!(
+                "`inputs` entry {}: `description` must be a string",
+                position
+            )));
+        }
+        if let Some(required) = input.get("required")
+            && !required.is_bool()
+        {
+            issues.push(SkillIssue::error(format!(
+                "`inputs` entry {}: `required` must be true or false",
+                position
+            )));
+        }
+    }
+}
+
+/// `triggers` is an optional mapping of `files`, `keywords`, and `tools`
+/// lists; anything else would stop the skill from loading.
+fn check_triggers(triggers: &Value, issues: &mut Vec<SkillIssue>) {
+    let Value::Mapping(triggers) = triggers else {
+        issues.push(SkillIssue::error(
+            "`triggers` must be a mapping with `files`, `keywords`, or `tools` lists",
+        ));
+        return;
+    };
+    for (key, value) in triggers {
+        let Some(key) = key.as_str().filter(|key| TRIGGER_KINDS.contains(key)) else {
+            issues.push(SkillIssue::warning(format!(
+                "`triggers` has unknown key `{}` (expected `files`, `keywords`, or `tools`)",
+                serde_yaml::to_string(key).unwrap_or_default().trim()
+            )));
+            continue;
+        };
+        let entries = match value {
+            Value::Sequence(entries) => entries,
+            Value::Null => continue,
+            _ => {
+                issues.push(SkillIssue::error(format!(
+                    "`triggers.{}` must be a list, e.g. `{}: [\"...\"]`",
+                    key, key
+                )));
+                continue;
+            }
+        };
+        for entry in entries {
+            match entry.as_str().map(str::trim) {
+                Some(entry) if !entry.is_empty() => {}
+                _ => issues.push(SkillIssue::error(format!(
+                    "`triggers.{}` entries must be non-empty strings",
+                    key
+                ))),
+            }
+        }
+    }
+}
+
+/// Relative file targets of markdown links in `body`, ignoring URLs, anchors,
+/// and anything inside HTML comments.
+fn referenced_files(body: &str) -> Vec<String> {
+    let mut text = String::with_capacity(body.len());
+    let mut rest = body;
+    while let Some(start) = rest.find("<!--") {
+        text.push_str(&rest[..start]);
+        rest = match rest[start..].find("-->") {
+            Some(end) => &rest[start + end + 3..],
+            None => "",
+        };
+    }
+    text.push_str(rest);
+
+    let mut targets = Vec::new();
+    let mut rest = text.as_str();
+    while let Some(start) = rest.find("](") {
+        rest = &rest[start + 2..];
+        let Some(end) = rest.find(')') else {
+            break;
+        };
+        let target = rest[..end].split_whitespace().next().unwrap_or_default();
+        let target = target.split('#').next().unwrap_or_default();
+        rest = &rest[end + 1..];
+        if target.is_empty()
+            || target.contains("://")
+            || target.starts_with("mailto:")
+            || target.starts_with('/')
+            || target.starts_with('~')
+        {
+            continue;
+        }
+        if !targets.iter().any(|existing| existing == target) {
+            targets.push(target.to_string());
+        }
+    }
+    targets
+}
+
+fn check_asset_sizes(root: &Path, dir: &Path, issues: &mut Vec<SkillIssue>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        if meta.is_dir() {
+            check_asset_sizes(root, &path, issues);
+        } else if meta.len() > MAX_SKILL_ASSET_BYTES && path != root.join("SKILL.md") {
+            issues.push(SkillIssue::warning(format!(
+                "{} is {} MiB; skill files over {} MiB slow down loading and sharing",
+                path.strip_prefix(root).unwrap_or(&path).display(),
+                meta.len() / (1024 * 1024),
+                MAX_SKILL_ASSET_BYTES / (1024 * 1024)
+            )));
+        }
+    }
+}
+
+/// Create `<root>/<name>/` with a commented SKILL.md template and an empty
+/// `scripts/` directory. Returns the new skill directory.
+pub fn scaffold_skill(root: &Path, name: &str) -> Result<PathBuf> {
+    check_skill_name(name).map_err(|problem| anyhow::anyhow!(problem))?;
+    let dir = root.join(name);
+    if dir.exists() {
+        anyhow::bail!("{} already exists", dir.display());
+    }
+    std::fs::create_dir_all(dir.join("scripts"))?;
+    std::fs::write(dir.join("SKILL.md"), skill_template(name))?;
+    Ok(dir)
+}
+
+fn skill_template(name: &str) -> String {
+    format!(
+        "---\n\
+name: {name}\n\
+# One line the agent reads to decide when to load this skill.\n\
+description: {DESCRIPTION_PLACEHOLDER} describe when the agent should use {name}\n\
+# Optional: comma-separated tools the skill expects to use.\n\
+# allowed-tools: bash, read, write\n\
+# Optional: arguments the skill accepts when invoked as /{name} <args>.\n\
+# inputs:\n\
+#   - name: target\n\
+#     description: File or directory to work on\n\
+#     required: true\n\
+# Optional: load the skill automatically when the session matches any entry.\n\
+# triggers:\n\
+#   files: [\"**/*.tf\"]\n\
+#   keywords: [terraform]\n\
+#   tools: [bash]\n\
+---\n\
+\n\
+# {name}\n\
+\n\
+<!--\n\
+Instructions the agent follows after loading this skill. Keep them short and\n\
+concrete. Put helper scripts in scripts/ and link them with relative markdown\n\
+links; `jcode skill check {name}` verifies every link resolves.\n\
+-->\n\
+\n\
+1. Describe the first step.\n"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_skill(root: &Path, name: &str, content: &str) -> PathBuf {
+        let dir = root.join(name);
+        std::fs::create_dir_all(&dir).expect("create skill dir");
+        std::fs::write(dir.join("SKILL.md"), content).expect("write SKILL.md");
+        dir
+    }
+
+    fn messages(check: &SkillCheck, level: SkillIssueLevel) -> Vec<&str> {
+        check
+            .issues
+            .iter()
+            .filter(|issue| issue.level == level)
+            .map(|issue| issue.message.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn scaffolded_skill_passes_with_only_a_placeholder_warning() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let dir = scaffold_skill(temp.path(), "deploy-docs").expect("scaffold");
+        assert!(dir.join("scripts").is_dir());
+
+        let check = validate_skill_dir(&dir);
+        assert!(check.is_ok(), "{}", check.render_issues());
+        assert_eq!(check.name, "deploy-docs");
+        let warnings = messages(&check, SkillIssueLevel::Warning);
+        assert_eq!(warnings.len(), 1, "{:?}", warnings);
+        assert!(warnings[0].contains("placeholder"));
+
+        assert!(scaffold_skill(temp.path(), "deploy-docs").is_err());
+        assert!(scaffold_skill(temp.path(), "Bad Name").is_err());
+    }
+
+    #[test]
+    fn reports_missing_fields_and_broken_links() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let dir = write_skill(
+            temp.path(),
+            "broken",
+            "---\nallowed-tools:\n  - bash\ninputs:\n  - description: no name\ntriggers:\n  files: \"*.tf\"\n---\n\nRun [the script](scripts/run.sh#usage) or see [docs](https://example.com).\n",
+        );
+
+        let check = validate_skill_dir(&dir);
+        assert!(!check.is_ok());
+        let errors = messages(&check, SkillIssueLevel::Error);
+        assert!(errors.iter().any(|m| m.contains("required field `name`")));
+        assert!(
+            errors
+                .iter()
+                .any(|m| m.contains("required field `description`"))
+        );
+        assert!(errors.iter().any(|m| m.contains("`allowed-tools`")));
+        assert!(errors.iter().any(|m| m.contains("entry 1 is missing")));
+        assert!(
+            errors
+                .iter()
+                .any(|m| m.contains("`triggers.files` must be a list"))
+        );
+        assert!(errors.iter().any(|m| m.contains("`scripts/run.sh`")));
+        assert!(!errors.iter().any(|m| m.contains("example.com")));
+
+        std::fs::create_dir_all(dir.join("scripts")).expect("scripts dir");
+        std::fs::write(dir.join("scripts/run.sh"), "echo hi\n").expect("script");
+        let errors_after = validate_skill_dir(&dir).issues.len();
+        assert_eq!(errors_after, check.issues.len() - 1);
+    }
+
+    #[test]
+    fn reports_unparseable_frontmatter_and_name_mismatch() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let dir = write_skill(temp.path(), "no-frontmatter", "# Just markdown\n");
+        let errors = validate_skill_dir(&dir).issues;
+        assert!(errors[0].message.contains("Missing YAML frontmatter"));
+
+        let dir = write_skill(
+            temp.path(),
+            "renamed",
+            "---\nname: other\ndescription: Does things\n---\n\nDo the thing.\n",
+        );
+        let check = validate_skill_dir(&dir);
+        assert!(check.is_ok());
+        assert_eq!(check.name, "other");
+        assert!(
+            messages(&check, SkillIssueLevel::Warning)[0].contains("does not match the directory")
+        );
+
+        let empty = tempfile::tempdir().expect("tempdir");
+        let check = validate_skill_dir(empty.path());
+        assert!(check.issues[0].message.contains("missing SKILL.md"));
+    }
+}
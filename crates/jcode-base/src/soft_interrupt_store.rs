@@ -50,11 +50,7 @@ impl From<SoftInterruptMessage> for PersistedSoftInterrupt {
 
 impl From<PersistedSoftInterrupt> for SoftInterruptMessage {
     fn from(value: PersistedSoftInterrupt) -> Self {
-        Self {
-            content: value.content,
-            urgent: value.urgent,
-            source: value.source.into(),
-        }
+        Self::new(value.content, value.urgent, value.source.into())
     }
 }
 
@@ -10,20 +10,16 @@ fn append_take_and_clear_round_trip() {
     let session_id = "ses_soft_interrupt_store";
     append(
         session_id,
-        SoftInterruptMessage {
-            content: "hello".to_string(),
-            urgent: true,
-            source: SoftInterruptSource::System,
-        },
+        SoftInterruptMessage::new("hello".to_string(), true, SoftInterruptSource::System),
     )
     .expect("append first interrupt");
     append(
         session_id,
-        SoftInterruptMessage {
-            content: "world".to_string(),
-            urgent: false,
-            source: SoftInterruptSource::BackgroundTask,
-        },
+        SoftInterruptMessage::new(
+            "world".to_string(),
+            false,
+            SoftInterruptSource::BackgroundTask,
+        ),
     )
     .expect("append second interrupt");
 
@@ -39,11 +35,7 @@ fn append_take_and_clear_round_trip() {
 
     append(
         session_id,
-        SoftInterruptMessage {
-            content: "later".to_string(),
-            urgent: false,
-            source: SoftInterruptSource::User,
-        },
+        SoftInterruptMessage::new("later".to_string(), false, SoftInterruptSource::User),
     )
     .expect("append later interrupt");
     clear(session_id).expect("clear interrupts");
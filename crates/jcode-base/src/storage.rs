@@ -2,6 +2,14 @@
 
 pub use jcode_storage::*;
 
+mod backend;
+mod migrate;
+mod sqlite;
+
+pub use backend::{FileStore, SessionListing, Store, store};
+pub use migrate::{MigrationFailure, MigrationReport, migrate_storage};
+pub use sqlite::{SQLITE_DB_FILE_NAME, SessionWrite, SqliteStore};
+
 use anyhow::Result;
 use serde::de::DeserializeOwned;
 use std::path::Path;
@@ -0,0 +1,128 @@
+//! Pluggable persistence for sessions and memory graphs.
+//!
+//! The file store is the historical layout (one JSON snapshot plus append
+//! journal per session). `[storage] backend = "sqlite"` switches to a single
+//! WAL-mode database with indexed listing and search; see [`super::SqliteStore`].
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+
+use super::SqliteStore;
+use crate::config::StorageBackend;
+use crate::session::{Session, SessionStatus, session_path};
+
+/// Picker-ready summary of a session, kept alongside the session so listing
+/// never has to parse transcripts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionListing {
+    pub id: String,
+    pub parent_id: Option<String>,
+    pub title: Option<String>,
+    pub custom_title: Option<String>,
+    pub short_name: Option<String>,
+    pub working_dir: Option<String>,
+    pub model: Option<String>,
+    pub provider_key: Option<String>,
+    pub status: SessionStatus,
+    pub is_canary: bool,
+    pub is_debug: bool,
+    pub saved: bool,
+    pub save_label: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub last_active_at: Option<DateTime<Utc>>,
+    pub visible_message_count: usize,
+    pub user_message_count: usize,
+    pub assistant_message_count: usize,
+    pub estimated_tokens: usize,
+    pub first_user_prompt: Option<String>,
+    /// Lowercased excerpt of the visible transcript for in-memory filtering.
+    pub search_text: String,
+}
+
+pub trait Store: Send + Sync {
+    fn kind(&self) -> StorageBackend;
+
+    fn session_exists(&self, session_id: &str) -> bool;
+
+    fn load_session(&self, session_id: &str) -> Result<Session>;
+
+    /// Persist everything changed since the session was loaded or last saved.
+    fn save_session(&self, session: &mut Session) -> Result<()>;
+
+    /// Sessions with visible conversation, newest first: the `limit` most
+    /// recent plus, with `include_saved`, every saved one. `None` means the
+    /// backend keeps no index and callers should scan session files.
+    fn list_sessions(
+        &self,
+        _limit: usize,
+        _include_saved: bool,
+    ) -> Result<Option<Vec<SessionListing>>> {
+        Ok(None)
+    }
+
+    /// IDs of sessions whose messages or metadata match any of `terms`,
+    /// newest first. `None` when there is no index to query.
+    fn search_sessions(&self, _terms: &[String], _limit: usize) -> Result<Option<Vec<String>>> {
+        Ok(None)
+    }
+
+    /// A JSON document previously written with [`Store::save_document`].
+    /// The file store returns `None` so callers keep their own backup
+    /// recovery and legacy-format handling for on-disk files.
+    fn load_document(&self, _path: &Path) -> Result<Option<Vec<u8>>> {
+        Ok(None)
+    }
+
+    /// Store a JSON document (e.g. a memory graph) under its file path.
+    fn save_document(&self, path: &Path, json: &[u8]) -> Result<()>;
+}
+
+/// One JSON snapshot plus append journal per session under `sessions/`.
+pub struct FileStore;
+
+impl Store for FileStore {
+    fn kind(&self) -> StorageBackend {
+        StorageBackend::Files
+    }
+
+    fn session_exists(&self, session_id: &str) -> bool {
+        session_path(session_id)
+            .map(|path| path.exists())
+            .unwrap_or(false)
+    }
+
+    fn load_session(&self, session_id: &str) -> Result<Session> {
+        Session::load_from_path(&session_path(session_id)?)
+    }
+
+    fn save_session(&self, session: &mut Session) -> Result<()> {
+        session.save_to_files()
+    }
+
+    fn save_document(&self, path: &Path, json: &[u8]) -> Result<()> {
+        super::write_bytes(path, json)
+    }
+}
+
+/// The store selected by `[storage] backend`. Falls back to files (with a
+/// warning) if the database cannot be opened, so a broken database never
+/// stops sessions from being saved.
+pub fn store() -> Arc<dyn Store> {
+    match crate::config::config().storage.backend {
+        StorageBackend::Files => Arc::new(FileStore),
+        StorageBackend::Sqlite => match SqliteStore::shared() {
+            Ok(store) => store,
+            Err(err) => {
+                crate::logging::warn(&format!(
+                    "SQLite storage unavailable, using session files: {}",
+                    err
+                ));
+                Arc::new(FileStore)
+            }
+        },
+    }
+}
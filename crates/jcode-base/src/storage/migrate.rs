@@ -0,0 +1,276 @@
+//! `jcode storage migrate`: copy sessions and memory graphs between the file
+//! layout and the SQLite database, reading every item back to verify it.
+//!
+//! The source is never deleted, so switching `[storage] backend` back is
+//! always possible.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+use super::SqliteStore;
+use crate::config::StorageBackend;
+use crate::memory_graph::{GRAPH_VERSION, MemoryGraph};
+use crate::session::{Session, session_path};
+
+#[derive(Debug, Clone)]
+pub struct MigrationFailure {
+    /// Session ID or memory file path.
+    pub item: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct MigrationReport {
+    pub target: StorageBackend,
+    pub database: PathBuf,
+    pub sessions_migrated: usize,
+    /// Sessions the target already holds at the same or a newer save.
+    pub sessions_current: usize,
+    /// Read-only sessions (written by a newer jcode) left where they are.
+    pub sessions_skipped: usize,
+    pub memories_migrated: usize,
+    /// Memory graphs the target already holds at the same or a newer write.
+    pub memories_current: usize,
+    /// Legacy-format memory files; they are upgraded on first load instead.
+    pub memories_skipped: usize,
+    pub failures: Vec<MigrationFailure>,
+}
+
+impl MigrationReport {
+    fn record(&mut self, item: impl Into<String>, result: Result<()>) -> bool {
+        match result {
+            Ok(()) => true,
+            Err(err) => {
+                self.failures.push(MigrationFailure {
+                    item: item.into(),
+                    error: crate::util::format_error_chain(&err),
+                });
+                false
+            }
+        }
+    }
+}
+
+enum SessionOutcome {
+    Migrated,
+    Current,
+    Skipped,
+}
+
+/// Copy all sessions and memory graphs into `target`. Sessions the target
+/// already has at the same or a newer save are left alone, so re-running after
+/// switching backends never rolls work back.
+pub fn migrate_storage(target: StorageBackend) -> Result<MigrationReport> {
+    let store = SqliteStore::shared()?;
+    let mut report = MigrationReport {
+        target,
+        database: store.path().to_path_buf(),
+        sessions_migrated: 0,
+        sessions_current: 0,
+        sessions_skipped: 0,
+        memories_migrated: 0,
+        memories_current: 0,
+        memories_skipped: 0,
+        failures: Vec::new(),
+    };
+    match target {
+        StorageBackend::Sqlite => files_to_sqlite(&store, &mut report)?,
+        StorageBackend::Files => sqlite_to_files(&store, &mut report)?,
+    }
+    Ok(report)
+}
+
+fn files_to_sqlite(store: &SqliteStore, report: &mut MigrationReport) -> Result<()> {
+    for path in session_snapshot_files()? {
+        let item = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let mut session = match Session::load_from_path(&path) {
+            Ok(session) => session,
+            Err(err) => {
+                report.record(item, Err(err));
+                continue;
+            }
+        };
+        if session.read_only_reason().is_some() {
+            report.sessions_skipped += 1;
+            continue;
+        }
+        match store.session_updated_at(&session.id) {
+            Ok(Some(stored)) if stored >= session.updated_at => {
+                report.sessions_current += 1;
+                continue;
+            }
+            Ok(_) => {}
+            Err(err) => {
+                report.record(item, Err(err));
+                continue;
+            }
+        }
+        let result = session.save_to_sqlite(store).and_then(|()| {
+            let stored = store
+                .read_session(&session.id)?
+                .context("session missing after write")?;
+            let stored = Session::from_stored_rows(&stored.0, stored.1)?;
+            verify_same_session(&session, &stored)
+        });
+        if report.record(item, result) {
+            report.sessions_migrated += 1;
+        }
+    }
+
+    for path in memory_graph_files()? {
+        let item = path.display().to_string();
+        if let (Ok(Some(stored)), Some(modified)) =
+            (store.document_updated_at(&path), file_modified_at(&path))
+            && stored >= modified
+        {
+            report.memories_current += 1;
+            continue;
+        }
+        let bytes = match std::fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                report.record(item, Err(err.into()));
+                continue;
+            }
+        };
+        if !serde_json::from_slice::<MemoryGraph>(&bytes)
+            .is_ok_and(|graph| graph.graph_version == GRAPH_VERSION)
+        {
+            report.memories_skipped += 1;
+            continue;
+        }
+        let result = super::Store::save_document(store, &path, &bytes).and_then(|()| {
+            let stored = super::Store::load_document(store, &path)?;
+            anyhow::ensure!(
+                stored.as_deref() == Some(bytes.as_slice()),
+                "stored memory graph differs from the file"
+            );
+            Ok(())
+        });
+        if report.record(item, result) {
+            report.memories_migrated += 1;
+        }
+    }
+    Ok(())
+}
+
+fn sqlite_to_files(store: &SqliteStore, report: &mut MigrationReport) -> Result<()> {
+    for id in store.session_ids()? {
+        let result = (|| -> Result<SessionOutcome> {
+            let (meta, messages) = store
+                .read_session(&id)?
+                .context("session disappeared during migration")?;
+            let mut session = Session::from_stored_rows(&meta, messages)?;
+            if session.read_only_reason().is_some() {
+                return Ok(SessionOutcome::Skipped);
+            }
+            let path = session_path(&id)?;
+            if path.exists()
+                && Session::load_from_path(&path)
+                    .is_ok_and(|existing| existing.updated_at >= session.updated_at)
+            {
+                return Ok(SessionOutcome::Current);
+            }
+            session.write_file_snapshot()?;
+            let written = Session::load_from_path(&path)?;
+            verify_same_session(&session, &written)?;
+            Ok(SessionOutcome::Migrated)
+        })();
+        match result {
+            Ok(SessionOutcome::Migrated) => report.sessions_migrated += 1,
+            Ok(SessionOutcome::Current) => report.sessions_current += 1,
+            Ok(SessionOutcome::Skipped) => report.sessions_skipped += 1,
+            Err(err) => {
+                report.record(id, Err(err));
+            }
+        }
+    }
+
+    for path in store.document_paths()? {
+        let item = path.display().to_string();
+        if let (Ok(Some(stored)), Some(modified)) =
+            (store.document_updated_at(&path), file_modified_at(&path))
+            && modified > stored
+        {
+            report.memories_current += 1;
+            continue;
+        }
+        let result = (|| -> Result<()> {
+            let bytes = super::Store::load_document(store, &path)?
+                .context("memory graph disappeared during migration")?;
+            super::write_bytes(&path, &bytes)?;
+            anyhow::ensure!(
+                std::fs::read(&path)? == bytes,
+                "written memory file differs from the database"
+            );
+            Ok(())
+        })();
+        if report.record(item, result) {
+            report.memories_migrated += 1;
+        }
+    }
+    Ok(())
+}
+
+fn file_modified_at(path: &Path) -> Option<DateTime<Utc>> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    Some(modified.into())
+}
+
+fn verify_same_session(expected: &Session, actual: &Session) -> Result<()> {
+    let expected: Value = serde_json::to_value(expected)?;
+    let actual: Value = serde_json::to_value(actual)?;
+    anyhow::ensure!(
+        expected == actual,
+        "session read back from the target differs from the source"
+    );
+    Ok(())
+}
+
+/// `sessions/<id>.json` snapshots, skipping journals, backups, and temp files.
+fn session_snapshot_files() -> Result<Vec<PathBuf>> {
+    let dir = super::jcode_dir()?.join("sessions");
+    let mut files = json_files_in(&dir)?;
+    files.sort();
+    Ok(files)
+}
+
+/// `memory/global.json` and per-project graphs under `memory/projects/`.
+fn memory_graph_files() -> Result<Vec<PathBuf>> {
+    let memory_dir = super::jcode_dir()?.join("memory");
+    let mut files: Vec<PathBuf> = [memory_dir.join("global.json")]
+        .into_iter()
+        .filter(|path| path.is_file())
+        .collect();
+    files.extend(json_files_in(&memory_dir.join("projects"))?);
+    files.sort();
+    Ok(files)
+}
+
+fn json_files_in(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        // `<stem>.json` only: excludes `.journal.jsonl`, `.json.bak`, and
+        // dotted temp files left by interrupted atomic writes.
+        if path.is_file()
+            && !name.starts_with('.')
+            && name.ends_with(".json")
+            && !name.trim_end_matches(".json").contains('.')
+        {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
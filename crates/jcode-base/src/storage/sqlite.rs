@@ -0,0 +1,490 @@
+//! SQLite storage backend: one WAL-mode `jcode.db` holding sessions,
+//! messages, memory graphs, and per-message token usage.
+//!
+//! Saves append only the messages added since the last save; full rewrites
+//! happen only when history was edited in place (e.g. compaction) or the
+//! stored row count disagrees with the session. Message and metadata text is
+//! indexed with FTS5 so search can pick candidates without loading sessions.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::{Connection, OptionalExtension, params};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use super::{SessionListing, Store};
+use crate::config::StorageBackend;
+use crate::message::ContentBlock;
+use crate::session::{Session, StoredMessage, session_path};
+
+pub const SQLITE_DB_FILE_NAME: &str = "jcode.db";
+
+/// Bump when the schema below changes; older databases are upgraded in
+/// [`SqliteStore::migrate_schema`].
+const SCHEMA_VERSION: i32 = 1;
+
+const SCHEMA: &str = r#"
+CREATE TABLE IF NOT EXISTS sessions (
+    id TEXT PRIMARY KEY,
+    updated_at TEXT NOT NULL,
+    saved INTEGER NOT NULL DEFAULT 0,
+    visible_messages INTEGER NOT NULL DEFAULT 0,
+    message_count INTEGER NOT NULL DEFAULT 0,
+    listing TEXT NOT NULL,
+    meta TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS sessions_by_updated ON sessions (updated_at DESC);
+CREATE TABLE IF NOT EXISTS messages (
+    session_id TEXT NOT NULL REFERENCES sessions (id) ON DELETE CASCADE,
+    seq INTEGER NOT NULL,
+    id TEXT NOT NULL,
+    role TEXT NOT NULL,
+    timestamp TEXT,
+    body TEXT NOT NULL,
+    PRIMARY KEY (session_id, seq)
+) WITHOUT ROWID;
+CREATE VIRTUAL TABLE IF NOT EXISTS message_text USING fts5 (
+    text, session_id UNINDEXED, seq UNINDEXED
+);
+CREATE VIRTUAL TABLE IF NOT EXISTS session_text USING fts5 (text, session_id UNINDEXED);
+CREATE TABLE IF NOT EXISTS usage (
+    session_id TEXT NOT NULL REFERENCES sessions (id) ON DELETE CASCADE,
+    seq INTEGER NOT NULL,
+    message_id TEXT NOT NULL,
+    timestamp TEXT,
+    input_tokens INTEGER NOT NULL,
+    output_tokens INTEGER NOT NULL,
+    cache_read_input_tokens INTEGER,
+    cache_creation_input_tokens INTEGER,
+    PRIMARY KEY (session_id, seq)
+) WITHOUT ROWID;
+CREATE TABLE IF NOT EXISTS memories (
+    path TEXT PRIMARY KEY,
+    graph TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+"#;
+
+/// What a session save hands to [`SqliteStore::write_session`].
+pub struct SessionWrite<'a> {
+    pub listing: &'a SessionListing,
+    /// The session serialized without its messages.
+    pub meta_json: &'a str,
+    pub messages: &'a [StoredMessage],
+    /// Index of the first message not yet stored, or `None` when the stored
+    /// messages must be replaced (history edited in place).
+    pub append_from: Option<usize>,
+}
+
+pub struct SqliteStore {
+    path: PathBuf,
+    conn: Mutex<Connection>,
+}
+
+static SHARED: Mutex<Option<Arc<SqliteStore>>> = Mutex::new(None);
+
+impl SqliteStore {
+    pub fn default_path() -> Result<PathBuf> {
+        Ok(super::jcode_dir()?.join(SQLITE_DB_FILE_NAME))
+    }
+
+    /// Process-wide store for [`SqliteStore::default_path`], reopened when
+    /// `JCODE_HOME` points somewhere else.
+    pub fn shared() -> Result<Arc<Self>> {
+        let path = Self::default_path()?;
+        let mut shared = SHARED
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(store) = shared.as_ref()
+            && store.path == path
+        {
+            return Ok(store.clone());
+        }
+        let store = Arc::new(Self::open(&path)?);
+        *shared = Some(store.clone());
+        Ok(store)
+    }
+
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            super::ensure_dir(parent)?;
+        }
+        let conn =
+            Connection::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+        conn.busy_timeout(std::time::Duration::from_secs(5))?;
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        conn.pragma_update(None, "synchronous", "NORMAL")?;
+        conn.pragma_update(None, "foreign_keys", "ON")?;
+        Self::migrate_schema(&conn)?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn migrate_schema(conn: &Connection) -> Result<()> {
+        let version: i32 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+        if version > SCHEMA_VERSION {
+            anyhow::bail!(
+                "database schema v{} is newer than this jcode supports (v{})",
+                version,
+                SCHEMA_VERSION
+            );
+        }
+        conn.execute_batch(SCHEMA)?;
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+        Ok(())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Fold the write-ahead log into the main file so `jcode.db` alone is a
+    /// complete copy (used before backing it up).
+    pub fn checkpoint(&self) -> Result<()> {
+        self.conn()
+            .execute_batch("PRAGMA wal_checkpoint(TRUNCATE);")?;
+        Ok(())
+    }
+
+    fn conn(&self) -> MutexGuard<'_, Connection> {
+        self.conn
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn write_session(&self, write: &SessionWrite<'_>) -> Result<()> {
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        let id = write.listing.id.as_str();
+        let stored: Option<usize> = tx
+            .query_row(
+                "SELECT message_count FROM sessions WHERE id = ?1",
+                [id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let first_new = match (write.append_from, stored) {
+            (Some(from), Some(count)) if from == count && from <= write.messages.len() => from,
+            _ => {
+                tx.execute("DELETE FROM messages WHERE session_id = ?1", [id])?;
+                tx.execute("DELETE FROM usage WHERE session_id = ?1", [id])?;
+                tx.execute("DELETE FROM message_text WHERE session_id = ?1", [id])?;
+                0
+            }
+        };
+
+        tx.execute(
+            "INSERT INTO sessions (id, updated_at, saved, visible_messages, message_count, listing, meta)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT (id) DO UPDATE SET
+                updated_at = excluded.updated_at,
+                saved = excluded.saved,
+                visible_messages = excluded.visible_messages,
+                message_count = excluded.message_count,
+                listing = excluded.listing,
+                meta = excluded.meta",
+            params![
+                id,
+                timestamp(&write.listing.updated_at),
+                write.listing.saved,
+                write.listing.visible_message_count as i64,
+                write.messages.len() as i64,
+                serde_json::to_string(write.listing)?,
+                write.meta_json,
+            ],
+        )?;
+        tx.execute("DELETE FROM session_text WHERE session_id = ?1", [id])?;
+        tx.execute(
+            "INSERT INTO session_text (text, session_id) VALUES (?1, ?2)",
+            params![listing_text(write.listing), id],
+        )?;
+
+        {
+            let mut insert_message = tx.prepare_cached(
+                "INSERT INTO messages (session_id, seq, id, role, timestamp, body)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            )?;
+            let mut insert_text = tx.prepare_cached(
+                "INSERT INTO message_text (text, session_id, seq) VALUES (?1, ?2, ?3)",
+            )?;
+            let mut insert_usage = tx.prepare_cached(
+                "INSERT INTO usage (session_id, seq, message_id, timestamp, input_tokens,
+                    output_tokens, cache_read_input_tokens, cache_creation_input_tokens)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            )?;
+            for (seq, message) in write.messages.iter().enumerate().skip(first_new) {
+                let seq = seq as i64;
+                let message_timestamp = message.timestamp.as_ref().map(timestamp);
+                let role = serde_json::to_value(&message.role)?;
+                insert_message.execute(params![
+                    id,
+                    seq,
+                    message.id,
+                    role.as_str().unwrap_or_default(),
+                    message_timestamp,
+                    serde_json::to_string(message)?,
+                ])?;
+                let text = message_text(message);
+                if !text.is_empty() {
+                    insert_text.execute(params![text, id, seq])?;
+                }
+                if let Some(usage) = &message.token_usage {
+                    insert_usage.execute(params![
+                        id,
+                        seq,
+                        message.id,
+                        message_timestamp,
+                        usage.input_tokens as i64,
+                        usage.output_tokens as i64,
+                        usage.cache_read_input_tokens.map(|tokens| tokens as i64),
+                        usage
+                            .cache_creation_input_tokens
+                            .map(|tokens| tokens as i64),
+                    ])?;
+                }
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Metadata JSON and messages of a stored session.
+    pub fn read_session(&self, session_id: &str) -> Result<Option<(String, Vec<StoredMessage>)>> {
+        let conn = self.conn();
+        let Some(meta): Option<String> = conn
+            .query_row(
+                "SELECT meta FROM sessions WHERE id = ?1",
+                [session_id],
+                |row| row.get(0),
+            )
+            .optional()?
+        else {
+            return Ok(None);
+        };
+        let mut statement =
+            conn.prepare_cached("SELECT body FROM messages WHERE session_id = ?1 ORDER BY seq")?;
+        let mut messages = Vec::new();
+        for body in statement.query_map([session_id], |row| row.get::<_, String>(0))? {
+            messages.push(serde_json::from_str(&body?)?);
+        }
+        Ok(Some((meta, messages)))
+    }
+
+    pub fn contains_session(&self, session_id: &str) -> Result<bool> {
+        let found: Option<i64> = self
+            .conn()
+            .query_row(
+                "SELECT 1 FROM sessions WHERE id = ?1",
+                [session_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(found.is_some())
+    }
+
+    /// When a stored session was last saved.
+    pub fn session_updated_at(&self, session_id: &str) -> Result<Option<DateTime<Utc>>> {
+        let updated_at: Option<String> = self
+            .conn()
+            .query_row(
+                "SELECT updated_at FROM sessions WHERE id = ?1",
+                [session_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        updated_at
+            .map(|text| Ok(DateTime::parse_from_rfc3339(&text)?.with_timezone(&Utc)))
+            .transpose()
+    }
+
+    /// Every stored session ID, newest first.
+    pub fn session_ids(&self) -> Result<Vec<String>> {
+        let conn = self.conn();
+        let mut statement = conn.prepare("SELECT id FROM sessions ORDER BY updated_at DESC")?;
+        let ids = statement
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+        Ok(ids)
+    }
+
+    /// When a stored document was last written.
+    pub fn document_updated_at(&self, path: &Path) -> Result<Option<DateTime<Utc>>> {
+        let updated_at: Option<String> = self
+            .conn()
+            .query_row(
+                "SELECT updated_at FROM memories WHERE path = ?1",
+                [document_key(path)?],
+                |row| row.get(0),
+            )
+            .optional()?;
+        updated_at
+            .map(|text| Ok(DateTime::parse_from_rfc3339(&text)?.with_timezone(&Utc)))
+            .transpose()
+    }
+
+    /// Every stored document path, for exporting back to files.
+    pub fn document_paths(&self) -> Result<Vec<PathBuf>> {
+        let conn = self.conn();
+        let mut statement = conn.prepare("SELECT path FROM memories ORDER BY path")?;
+        let keys = statement
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+        let base = super::jcode_dir()?;
+        Ok(keys.into_iter().map(|key| base.join(key)).collect())
+    }
+}
+
+impl Store for SqliteStore {
+    fn kind(&self) -> StorageBackend {
+        StorageBackend::Sqlite
+    }
+
+    fn session_exists(&self, session_id: &str) -> bool {
+        self.contains_session(session_id).unwrap_or(false)
+            || session_path(session_id)
+                .map(|path| path.exists())
+                .unwrap_or(false)
+    }
+
+    /// Sessions not yet migrated are still read from their files; the next
+    /// save stores them in the database.
+    fn load_session(&self, session_id: &str) -> Result<Session> {
+        match self.read_session(session_id)? {
+            Some((meta, messages)) => Session::from_stored_rows(&meta, messages),
+            None => Session::load_from_path(&session_path(session_id)?),
+        }
+    }
+
+    fn save_session(&self, session: &mut Session) -> Result<()> {
+        session.save_to_sqlite(self)
+    }
+
+    fn list_sessions(
+        &self,
+        limit: usize,
+        include_saved: bool,
+    ) -> Result<Option<Vec<SessionListing>>> {
+        let conn = self.conn();
+        let mut statement = conn.prepare_cached(
+            "SELECT listing FROM sessions
+             WHERE visible_messages > 0
+               AND ((?2 AND saved) OR id IN (
+                    SELECT id FROM sessions WHERE visible_messages > 0
+                    ORDER BY updated_at DESC LIMIT ?1))
+             ORDER BY updated_at DESC",
+        )?;
+        let mut listings = Vec::new();
+        for listing in statement.query_map(params![limit as i64, include_saved], |row| {
+            row.get::<_, String>(0)
+        })? {
+            listings.push(serde_json::from_str(&listing?)?);
+        }
+        Ok(Some(listings))
+    }
+
+    fn search_sessions(&self, terms: &[String], limit: usize) -> Result<Option<Vec<String>>> {
+        let Some(query) = fts_query(terms) else {
+            return Ok(Some(Vec::new()));
+        };
+        let conn = self.conn();
+        let mut statement = conn.prepare_cached(
+            "SELECT s.id FROM sessions s
+             WHERE s.id IN (SELECT session_id FROM message_text WHERE message_text MATCH ?1)
+                OR s.id IN (SELECT session_id FROM session_text WHERE session_text MATCH ?1)
+             ORDER BY s.updated_at DESC
+             LIMIT ?2",
+        )?;
+        let ids = statement
+            .query_map(params![query, limit as i64], |row| row.get(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+        Ok(Some(ids))
+    }
+
+    fn load_document(&self, path: &Path) -> Result<Option<Vec<u8>>> {
+        let graph: Option<String> = self
+            .conn()
+            .query_row(
+                "SELECT graph FROM memories WHERE path = ?1",
+                [document_key(path)?],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(graph.map(String::into_bytes))
+    }
+
+    fn save_document(&self, path: &Path, json: &[u8]) -> Result<()> {
+        let json = std::str::from_utf8(json).context("document is not UTF-8 JSON")?;
+        self.conn().execute(
+            "INSERT INTO memories (path, graph, updated_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT (path) DO UPDATE SET
+                graph = excluded.graph,
+                updated_at = excluded.updated_at",
+            params![document_key(path)?, json, timestamp(&Utc::now())],
+        )?;
+        Ok(())
+    }
+}
+
+/// Fixed-width UTC timestamps so text ordering matches time ordering.
+fn timestamp(time: &DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+/// Documents are keyed relative to the jcode directory so a moved
+/// `JCODE_HOME` keeps its memories.
+fn document_key(path: &Path) -> Result<String> {
+    let base = super::jcode_dir()?;
+    let key = path.strip_prefix(&base).unwrap_or(path);
+    Ok(key.to_string_lossy().replace('\\', "/"))
+}
+
+fn listing_text(listing: &SessionListing) -> String {
+    [
+        Some(listing.id.as_str()),
+        listing.custom_title.as_deref(),
+        listing.title.as_deref(),
+        listing.short_name.as_deref(),
+        listing.working_dir.as_deref(),
+        listing.save_label.as_deref(),
+        listing.model.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+fn message_text(message: &StoredMessage) -> String {
+    let mut text = String::new();
+    for block in &message.content {
+        let part = match block {
+            ContentBlock::Text { text, .. } => text.as_str(),
+            ContentBlock::ToolResult { content, .. } => content.as_str(),
+            ContentBlock::ToolUse { name, input, .. } => {
+                text.push_str(name);
+                text.push(' ');
+                text.push_str(&input.to_string());
+                text.push('\n');
+                continue;
+            }
+            _ => continue,
+        };
+        text.push_str(part);
+        text.push('\n');
+    }
+    text
+}
+
+/// FTS5 query matching any term as a prefix. Terms are quoted so punctuation
+/// and FTS operators in user queries are taken literally.
+fn fts_query(terms: &[String]) -> Option<String> {
+    let quoted: Vec<String> = terms
+        .iter()
+        .map(|term| term.trim())
+        .filter(|term| !term.is_empty())
+        .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+        .collect();
+    (!quoted.is_empty()).then(|| quoted.join(" OR "))
+}
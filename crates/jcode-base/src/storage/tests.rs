@@ -138,3 +138,99 @@ fn write_text_secret_sets_owner_only_modes() {
     assert_eq!(dir_mode, 0o700);
     assert_eq!(file_mode, 0o600);
 }
+
+fn with_temp_jcode_home(test: impl FnOnce(&Path)) {
+    let _guard = lock_test_env();
+    let prev_home = std::env::var_os("JCODE_HOME");
+    let temp = tempfile::TempDir::new().expect("create temp dir");
+    crate::env::set_var("JCODE_HOME", temp.path());
+
+    test(temp.path());
+
+    if let Some(prev_home) = prev_home {
+        crate::env::set_var("JCODE_HOME", prev_home);
+    } else {
+        crate::env::remove_var("JCODE_HOME");
+    }
+}
+
+fn text(text: &str) -> Vec<crate::message::ContentBlock> {
+    vec![crate::message::ContentBlock::Text {
+        text: text.to_string(),
+        cache_control: None,
+    }]
+}
+
+#[test]
+fn sqlite_store_appends_new_messages_and_rewrites_edited_history() {
+    use crate::message::Role;
+    use crate::session::Session;
+
+    with_temp_jcode_home(|home| {
+        let store = SqliteStore::open(&home.join(SQLITE_DB_FILE_NAME)).expect("open store");
+        let mut session = Session::create_with_id(
+            "session_sqlite_roundtrip".to_string(),
+            None,
+            Some("Parser cleanup".to_string()),
+        );
+        session.add_message(Role::User, text("find the flaky parser test"));
+        session.add_message(Role::Assistant, text("done, it races on tempdir"));
+        session.save_to_sqlite(&store).expect("first save");
+
+        session.add_message(Role::User, text("now fix it"));
+        session.save_to_sqlite(&store).expect("append save");
+        let (_, messages) = store
+            .read_session(&session.id)
+            .expect("read session")
+            .expect("session stored");
+        assert_eq!(messages.len(), 3);
+
+        let listings = store
+            .list_sessions(10, false)
+            .expect("list sessions")
+            .expect("sqlite keeps an index");
+        assert_eq!(listings.len(), 1);
+        assert_eq!(listings[0].visible_message_count, 3);
+        assert_eq!(listings[0].user_message_count, 2);
+        assert_eq!(
+            listings[0].first_user_prompt.as_deref(),
+            Some("find the flaky parser test")
+        );
+        assert_eq!(
+            store.search_sessions(&["tempd".to_string()], 10).unwrap(),
+            Some(vec![session.id.clone()])
+        );
+
+        session.truncate_messages(1);
+        session.save_to_sqlite(&store).expect("rewrite save");
+        let loaded = store.load_session(&session.id).expect("load session");
+        assert_eq!(loaded.messages.len(), 1);
+        assert_eq!(loaded.title.as_deref(), Some("Parser cleanup"));
+        assert_eq!(
+            store.search_sessions(&["tempdir".to_string()], 10).unwrap(),
+            Some(Vec::new())
+        );
+        assert_eq!(
+            store.search_sessions(&["parser".to_string()], 10).unwrap(),
+            Some(vec![session.id.clone()])
+        );
+    });
+}
+
+#[test]
+fn sqlite_store_keeps_documents_keyed_under_jcode_home() {
+    with_temp_jcode_home(|home| {
+        let store = SqliteStore::open(&home.join(SQLITE_DB_FILE_NAME)).expect("open store");
+        let path = home.join("memory").join("global.json");
+        let graph = br#"{"graph_version":1,"memories":{}}"#;
+
+        assert_eq!(store.load_document(&path).unwrap(), None);
+        store.save_document(&path, graph).expect("save document");
+        assert_eq!(
+            store.load_document(&path).unwrap().as_deref(),
+            Some(&graph[..])
+        );
+        assert_eq!(store.document_paths().unwrap(), vec![path.clone()]);
+        assert!(!path.exists(), "documents stay in the database");
+    });
+}
@@ -1,30 +1,82 @@
 use crate::logging;
 use serde::Deserialize;
+use serde::de::DeserializeOwned;
 
 const API_BASE: &str = "https://api.telegram.org/bot";
 
+/// Longest text Telegram accepts in one message, in UTF-16 code units.
+pub const MAX_MESSAGE_LEN: usize = 4096;
+
 #[derive(Debug, Deserialize)]
 struct TelegramResponse<T> {
     ok: bool,
     #[serde(default)]
     description: Option<String>,
+    #[serde(default)]
+    parameters: Option<ResponseParameters>,
     result: Option<T>,
 }
 
+#[derive(Debug, Deserialize)]
+struct ResponseParameters {
+    #[serde(default)]
+    retry_after: Option<u64>,
+}
+
+/// A failed Bot API call. `retry_after` is set when Telegram is rate limiting.
+#[derive(Debug)]
+pub struct TelegramApiError {
+    pub method: &'static str,
+    pub description: String,
+    pub retry_after: Option<u64>,
+}
+
+impl std::fmt::Display for TelegramApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Telegram {} error: {}", self.method, self.description)
+    }
+}
+
+impl std::error::Error for TelegramApiError {}
+
 #[derive(Debug, Deserialize)]
 pub struct Update {
     pub update_id: i64,
     pub message: Option<TelegramMessage>,
+    #[serde(default)]
+    pub callback_query: Option<CallbackQuery>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct TelegramMessage {
+    #[serde(default)]
+    pub message_id: i64,
+    #[serde(default)]
+    pub from: Option<User>,
     pub text: Option<String>,
     pub chat: Chat,
     #[serde(rename = "date")]
     pub _date: i64,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct User {
+    pub id: i64,
+    #[serde(default)]
+    pub username: Option<String>,
+}
+
+/// A press of an inline keyboard button.
+#[derive(Debug, Deserialize)]
+pub struct CallbackQuery {
+    pub id: String,
+    pub from: User,
+    #[serde(default)]
+    pub message: Option<TelegramMessage>,
+    #[serde(default)]
+    pub data: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct Chat {
     pub id: i64,
@@ -68,11 +120,12 @@ pub async fn get_updates(
     bot_token: &str,
     offset: Option<i64>,
     timeout_secs: u64,
+    allowed_updates: &[&str],
 ) -> anyhow::Result<Vec<Update>> {
     let url = format!("{}{}/getUpdates", API_BASE, bot_token);
     let mut params = serde_json::json!({
         "timeout": timeout_secs,
-        "allowed_updates": ["message"],
+        "allowed_updates": allowed_updates,
     });
 
     if let Some(off) = offset {
@@ -98,6 +151,137 @@ pub async fn get_updates(
     Ok(body.result.unwrap_or_default())
 }
 
+async fn call_api<T: DeserializeOwned>(
+    client: &reqwest::Client,
+    bot_token: &str,
+    method: &'static str,
+    params: &serde_json::Value,
+) -> anyhow::Result<T> {
+    let url = format!("{}{}/{}", API_BASE, bot_token, method);
+    let body: TelegramResponse<T> = client.post(&url).json(params).send().await?.json().await?;
+    match body.result {
+        Some(result) if body.ok => Ok(result),
+        _ => Err(TelegramApiError {
+            method,
+            description: body.description.unwrap_or_default(),
+            retry_after: body.parameters.and_then(|params| params.retry_after),
+        }
+        .into()),
+    }
+}
+
+/// Send plain text (no parse mode, so partial output can't break formatting)
+/// and return the new message's ID.
+pub async fn send_text(
+    client: &reqwest::Client,
+    bot_token: &str,
+    chat_id: i64,
+    text: &str,
+    reply_markup: Option<serde_json::Value>,
+) -> anyhow::Result<i64> {
+    let mut params = serde_json::json!({
+        "chat_id": chat_id,
+        "text": text,
+        "disable_web_page_preview": true,
+    });
+    if let Some(markup) = reply_markup {
+        params["reply_markup"] = markup;
+    }
+    let message: TelegramMessage = call_api(client, bot_token, "sendMessage", &params).await?;
+    Ok(message.message_id)
+}
+
+/// Replace the text of a message sent by the bot. Edits that leave the text
+/// unchanged are not errors.
+pub async fn edit_message_text(
+    client: &reqwest::Client,
+    bot_token: &str,
+    chat_id: i64,
+    message_id: i64,
+    text: &str,
+    reply_markup: Option<serde_json::Value>,
+) -> anyhow::Result<()> {
+    let mut params = serde_json::json!({
+        "chat_id": chat_id,
+        "message_id": message_id,
+        "text": text,
+        "disable_web_page_preview": true,
+    });
+    if let Some(markup) = reply_markup {
+        params["reply_markup"] = markup;
+    }
+    match call_api::<serde_json::Value>(client, bot_token, "editMessageText", &params).await {
+        Err(err)
+            if err
+                .downcast_ref::<TelegramApiError>()
+                .is_some_and(|err| err.description.contains("message is not modified")) =>
+        {
+            Ok(())
+        }
+        result => result.map(|_| ()),
+    }
+}
+
+/// Dismiss the loading state of a pressed inline button, optionally with a
+/// short toast.
+pub async fn answer_callback_query(
+    client: &reqwest::Client,
+    bot_token: &str,
+    callback_query_id: &str,
+    text: Option<&str>,
+) -> anyhow::Result<()> {
+    let mut params = serde_json::json!({ "callback_query_id": callback_query_id });
+    if let Some(text) = text {
+        params["text"] = serde_json::json!(text);
+    }
+    call_api::<bool>(client, bot_token, "answerCallbackQuery", &params).await?;
+    Ok(())
+}
+
+/// One row of inline keyboard buttons, given as `(label, callback_data)`.
+pub fn inline_keyboard(buttons: &[(&str, String)]) -> serde_json::Value {
+    let row: Vec<serde_json::Value> = buttons
+        .iter()
+        .map(|(label, data)| serde_json::json!({ "text": label, "callback_data": data }))
+        .collect();
+    serde_json::json!({ "inline_keyboard": [row] })
+}
+
+/// Split `text` into pieces Telegram accepts, preferring line breaks, then
+/// spaces, then any character boundary.
+pub fn split_message(text: &str, max_len: usize) -> Vec<String> {
+    let mut pieces = Vec::new();
+    let mut rest = text;
+    while utf16_len(rest) > max_len {
+        let mut hard_end = 0;
+        let mut units = 0;
+        for (idx, ch) in rest.char_indices() {
+            units += ch.len_utf16();
+            if units > max_len {
+                break;
+            }
+            hard_end = idx + ch.len_utf8();
+        }
+        let head = &rest[..hard_end];
+        let end = head
+            .rfind('\n')
+            .or_else(|| head.rfind(' '))
+            .filter(|&idx| idx > 0)
+            .map(|idx| idx + 1)
+            .unwrap_or(hard_end);
+        pieces.push(rest[..end].trim_end().to_string());
+        rest = &rest[end..];
+    }
+    if !rest.is_empty() || pieces.is_empty() {
+        pieces.push(rest.to_string());
+    }
+    pieces
+}
+
+fn utf16_len(text: &str) -> usize {
+    text.chars().map(char::len_utf16).sum()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -117,6 +301,41 @@ mod tests {
         assert_eq!(update.message.unwrap().text.unwrap(), "hello");
     }
 
+    #[test]
+    fn test_parse_callback_query_update() {
+        let json = r#"{
+            "update_id": 124,
+            "callback_query": {
+                "id": "cb1",
+                "from": {"id": 42, "username": "me"},
+                "message": {"message_id": 7, "chat": {"id": 456}, "date": 1700000000},
+                "data": "perm:y:req_1"
+            }
+        }"#;
+        let update: Update = serde_json::from_str(json).unwrap();
+        assert!(update.message.is_none());
+        let query = update.callback_query.unwrap();
+        assert_eq!(query.from.id, 42);
+        assert_eq!(query.message.unwrap().message_id, 7);
+        assert_eq!(query.data.as_deref(), Some("perm:y:req_1"));
+    }
+
+    #[test]
+    fn test_split_message_prefers_line_breaks_and_respects_limit() {
+        assert_eq!(split_message("short", 10), vec!["short"]);
+        assert_eq!(split_message("", 10), vec![""]);
+
+        let pieces = split_message("first line\nsecond line", 15);
+        assert_eq!(pieces, vec!["first line", "second line"]);
+
+        let pieces = split_message(&"x".repeat(25), 10);
+        assert_eq!(pieces, vec!["x".repeat(10), "x".repeat(10), "x".repeat(5)]);
+
+        // Emoji take two UTF-16 units, which is what Telegram counts.
+        let pieces = split_message(&"😀".repeat(6), 4);
+        assert_eq!(pieces, vec!["😀😀", "😀😀", "😀😀"]);
+    }
+
     #[test]
     fn test_parse_response() {
         let json = r#"{"ok": true, "result": []}"#;
@@ -124,4 +343,17 @@ mod tests {
         assert!(resp.ok);
         assert!(resp.result.unwrap().is_empty());
     }
+
+    #[test]
+    fn test_parse_rate_limited_response() {
+        let json = r#"{
+            "ok": false,
+            "error_code": 429,
+            "description": "Too Many Requests: retry after 3",
+            "parameters": {"retry_after": 3}
+        }"#;
+        let resp: TelegramResponse<serde_json::Value> = serde_json::from_str(json).unwrap();
+        assert!(!resp.ok);
+        assert_eq!(resp.parameters.unwrap().retry_after, Some(3));
+    }
 }
@@ -1,9 +1,16 @@
 use crate::storage;
 use anyhow::Result;
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 
 pub use jcode_task_types::TodoItem;
 
+/// Project-scoped todo file, relative to the session working directory.
+pub const PROJECT_TODOS_FILE: &str = ".jcode/todos.json";
+
+/// Attribution used for todos added by hand from the TUI or CLI.
+pub const MANUAL_TODO_SESSION: &str = "manual";
+
 pub fn load_todos(session_id: &str) -> Result<Vec<TodoItem>> {
     let path = todo_path(session_id)?;
     if !path.exists() {
@@ -25,3 +32,347 @@ fn todo_path(session_id: &str) -> Result<PathBuf> {
     let base = storage::jcode_dir()?;
     Ok(base.join("todos").join(format!("{}.json", session_id)))
 }
+
+/// A todo in the project-scoped file, attributed to the session that wrote it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProjectTodo {
+    #[serde(flatten)]
+    pub item: TodoItem,
+    pub session_id: String,
+    #[serde(default)]
+    pub updated_at_ms: u64,
+}
+
+impl ProjectTodo {
+    pub fn is_open(&self) -> bool {
+        is_open_status(&self.item.status)
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ProjectTodoFile {
+    #[serde(default)]
+    todos: Vec<ProjectTodo>,
+}
+
+pub fn project_todos_path(project_dir: &Path) -> PathBuf {
+    project_dir.join(PROJECT_TODOS_FILE)
+}
+
+pub fn load_project_todos(project_dir: &Path) -> Result<Vec<ProjectTodo>> {
+    let path = project_todos_path(project_dir);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let file: ProjectTodoFile = storage::read_json(&path)?;
+    Ok(file.todos)
+}
+
+fn save_project_todos(project_dir: &Path, todos: Vec<ProjectTodo>) -> Result<()> {
+    storage::write_json(&project_todos_path(project_dir), &ProjectTodoFile { todos })
+}
+
+/// Exclusive lock on a project's todo file, held across each load-modify-save
+/// so sessions and the CLI editing the same project do not drop each other's
+/// changes. Lives under `~/.jcode/todos/`, keyed by the project path, so it
+/// never shows up in the checkout. Released on drop.
+pub(crate) struct ProjectTodosLock {
+    _file: std::fs::File,
+}
+
+pub(crate) fn lock_project_todos(project_dir: &Path) -> Result<ProjectTodosLock> {
+    use sha2::{Digest, Sha256};
+
+    let key = std::fs::canonicalize(project_dir).unwrap_or_else(|_| project_dir.to_path_buf());
+    let hash = hex::encode(Sha256::digest(key.to_string_lossy().as_bytes()));
+    let dir = storage::jcode_dir()?.join("todos");
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("project-{}.lock", &hash[..16]));
+    let file = std::fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&path)?;
+    file.lock()
+        .map_err(|error| anyhow::anyhow!("Failed to lock {}: {}", path.display(), error))?;
+    Ok(ProjectTodosLock { _file: file })
+}
+
+/// Open project todos, in-progress first, then by priority.
+pub fn open_project_todos(project_dir: &Path) -> Vec<ProjectTodo> {
+    let mut todos: Vec<ProjectTodo> = load_project_todos(project_dir)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(ProjectTodo::is_open)
+        .collect();
+    todos.sort_by(|a, b| {
+        status_rank(&a.item.status)
+            .cmp(&status_rank(&b.item.status))
+            .then_with(|| priority_rank(&a.item.priority).cmp(&priority_rank(&b.item.priority)))
+            .then_with(|| a.updated_at_ms.cmp(&b.updated_at_ms))
+    });
+    todos
+}
+
+/// Save a session's todo list and mirror it into the project file, replacing
+/// the items previously attributed to that session.
+pub fn save_session_todos_in_project(
+    project_dir: &Path,
+    session_id: &str,
+    todos: &[TodoItem],
+) -> Result<()> {
+    let _lock = lock_project_todos(project_dir)?;
+    save_session_todos_locked(project_dir, session_id, todos)
+}
+
+fn save_session_todos_locked(
+    project_dir: &Path,
+    session_id: &str,
+    todos: &[TodoItem],
+) -> Result<()> {
+    save_todos(session_id, todos)?;
+    let now = now_ms();
+    let mut project = load_project_todos(project_dir).unwrap_or_default();
+    let previous: Vec<ProjectTodo> = project
+        .iter()
+        .filter(|todo| todo.session_id == session_id)
+        .cloned()
+        .collect();
+    project.retain(|todo| todo.session_id != session_id);
+    project.extend(todos.iter().map(|item| {
+        let unchanged = previous
+            .iter()
+            .find(|old| old.item == *item)
+            .map(|old| old.updated_at_ms);
+        ProjectTodo {
+            item: item.clone(),
+            session_id: session_id.to_string(),
+            updated_at_ms: unchanged.unwrap_or(now),
+        }
+    }));
+    save_project_todos(project_dir, project)
+}
+
+/// Replace the whole project list, e.g. after importing edits from a synced
+/// file, and rewrite the list of every session whose items changed.
+pub fn replace_project_todos(project_dir: &Path, todos: Vec<ProjectTodo>) -> Result<()> {
+    let lock = lock_project_todos(project_dir)?;
+    replace_project_todos_locked(project_dir, todos, &lock)
+}
+
+/// [`replace_project_todos`] for a caller that already holds the project
+/// lock across its own read of the list.
+pub(crate) fn replace_project_todos_locked(
+    project_dir: &Path,
+    todos: Vec<ProjectTodo>,
+    _lock: &ProjectTodosLock,
+) -> Result<()> {
+    let previous = load_project_todos(project_dir).unwrap_or_default();
+    let mut sessions: Vec<&str> = previous
+        .iter()
+        .chain(todos.iter())
+        .map(|todo| todo.session_id.as_str())
+        .filter(|session_id| *session_id != MANUAL_TODO_SESSION)
+        .collect();
+    sessions.sort_unstable();
+    sessions.dedup();
+    for session_id in sessions {
+        let before: Vec<&TodoItem> = previous
+            .iter()
+            .filter(|todo| todo.session_id == session_id)
+            .map(|todo| &todo.item)
+            .collect();
+        let after: Vec<TodoItem> = todos
+            .iter()
+            .filter(|todo| todo.session_id == session_id)
+            .map(|todo| todo.item.clone())
+            .collect();
+        if before.len() != after.len() || before.iter().zip(&after).any(|(a, b)| *a != b) {
+            save_todos(session_id, &after)?;
+        }
+    }
+    save_project_todos(project_dir, todos)
+}
+
+/// Add a todo by hand. It is attributed to `session_id` and also appended to
+/// that session's own list so the agent sees it on its next `todo` read.
+pub fn add_project_todo(project_dir: &Path, session_id: &str, content: &str) -> Result<TodoItem> {
+    let _lock = lock_project_todos(project_dir)?;
+    let mut session_todos = if session_id == MANUAL_TODO_SESSION {
+        Vec::new()
+    } else {
+        load_todos(session_id)?
+    };
+    let project = load_project_todos(project_dir).unwrap_or_default();
+    let mut next = project.len() + session_todos.len() + 1;
+    let id = loop {
+        let candidate = format!("manual-{next}");
+        let taken = project.iter().any(|todo| todo.item.id == candidate)
+            || session_todos.iter().any(|todo| todo.id == candidate);
+        if !taken {
+            break candidate;
+        }
+        next += 1;
+    };
+    let item = TodoItem {
+        content: content.trim().to_string(),
+        status: "pending".to_string(),
+        priority: "medium".to_string(),
+        id,
+        group: None,
+        confidence: None,
+        completion_confidence: None,
+        blocked_by: Vec::new(),
+        assigned_to: None,
+    };
+    if session_id == MANUAL_TODO_SESSION {
+        let mut project = project;
+        project.push(ProjectTodo {
+            item: item.clone(),
+            session_id: session_id.to_string(),
+            updated_at_ms: now_ms(),
+        });
+        save_project_todos(project_dir, project)?;
+    } else {
+        session_todos.push(item.clone());
+        save_session_todos_locked(project_dir, session_id, &session_todos)?;
+    }
+    Ok(item)
+}
+
+/// Set the status of a project todo, keeping its session's list in step.
+/// Returns false when no such todo exists.
+pub fn set_project_todo_status(
+    project_dir: &Path,
+    session_id: &str,
+    todo_id: &str,
+    status: &str,
+) -> Result<bool> {
+    edit_project_todo(
+        project_dir,
+        session_id,
+        todo_id,
+        ProjectTodoEdit::SetStatus(status),
+    )
+}
+
+/// Delete a project todo, keeping its session's list in step. Returns false
+/// when no such todo exists.
+pub fn remove_project_todo(project_dir: &Path, session_id: &str, todo_id: &str) -> Result<bool> {
+    edit_project_todo(project_dir, session_id, todo_id, ProjectTodoEdit::Remove)
+}
+
+#[derive(Clone, Copy)]
+enum ProjectTodoEdit<'a> {
+    SetStatus(&'a str),
+    Remove,
+}
+
+fn edit_project_todo(
+    project_dir: &Path,
+    session_id: &str,
+    todo_id: &str,
+    edit: ProjectTodoEdit<'_>,
+) -> Result<bool> {
+    let _lock = lock_project_todos(project_dir)?;
+    let mut project = load_project_todos(project_dir)?;
+    let Some(index) = project
+        .iter()
+        .position(|todo| todo.session_id == session_id && todo.item.id == todo_id)
+    else {
+        return Ok(false);
+    };
+    match edit {
+        ProjectTodoEdit::SetStatus(status) => {
+            project[index].item.status = status.to_string();
+            project[index].updated_at_ms = now_ms();
+        }
+        ProjectTodoEdit::Remove => {
+            project.remove(index);
+        }
+    }
+
+    if session_id != MANUAL_TODO_SESSION {
+        let mut session_todos = load_todos(session_id)?;
+        if let Some(session_index) = session_todos.iter().position(|todo| todo.id == todo_id) {
+            match edit {
+                ProjectTodoEdit::SetStatus(status) => {
+                    session_todos[session_index].status = status.to_string();
+                }
+                ProjectTodoEdit::Remove => {
+                    session_todos.remove(session_index);
+                }
+            }
+            save_todos(session_id, &session_todos)?;
+        }
+    }
+
+    save_project_todos(project_dir, project)?;
+    Ok(true)
+}
+
+/// Summary of open todos left by other sessions, for the dynamic system
+/// prompt. Capped at `max_chars` characters; returns `None` when there is
+/// nothing to say.
+pub fn project_todos_prompt_summary(
+    project_dir: &Path,
+    current_session_id: &str,
+    max_chars: usize,
+) -> Option<String> {
+    let open: Vec<ProjectTodo> = open_project_todos(project_dir)
+        .into_iter()
+        .filter(|todo| todo.session_id != current_session_id)
+        .collect();
+    if open.is_empty() {
+        return None;
+    }
+
+    let mut out = String::from(
+        "# Open Project Todos\n\nUnfinished todos from earlier sessions in this project (.jcode/todos.json). Pick them up if relevant to the user's request.\n",
+    );
+    let mut chars = out.chars().count();
+    for (shown, todo) in open.iter().enumerate() {
+        let line = format!(
+            "- [{}] ({}) {}\n",
+            todo.item.status, todo.item.priority, todo.item.content
+        );
+        let line_chars = line.chars().count();
+        if chars + line_chars > max_chars {
+            out.push_str(&format!("- … and {} more\n", open.len() - shown));
+            break;
+        }
+        chars += line_chars;
+        out.push_str(&line);
+    }
+    Some(out)
+}
+
+fn is_open_status(status: &str) -> bool {
+    status != "completed" && status != "cancelled"
+}
+
+fn status_rank(status: &str) -> u8 {
+    match status {
+        "in_progress" => 0,
+        _ => 1,
+    }
+}
+
+fn priority_rank(priority: &str) -> u8 {
+    match priority {
+        "high" => 0,
+        "medium" => 1,
+        _ => 2,
+    }
+}
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|dur| dur.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+#[path = "todo_tests.rs"]
+mod todo_tests;
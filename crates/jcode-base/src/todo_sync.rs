@@ -0,0 +1,341 @@
+//! Two-way sync between the project todos and a markdown file.
+//!
+//! With `[todo] sync_file = "TODO.md"`, the project todos in
+//! `.jcode/todos.json` are mirrored into a fenced section of that file so people
+//! who don't run jcode can follow along. Only the text between
+//! [`SECTION_START`] and [`SECTION_END`] is ever rewritten; the rest of the file
+//! is left byte-for-byte intact.
+//!
+//! The hash of the section as of the last sync is kept in
+//! `.jcode/todo_sync.json`. Comparing it against the file and the store tells
+//! which side changed, so our own writes are never read back as human edits.
+//! When both sides changed since the last sync, the more recently modified one
+//! wins and the conflict is logged.
+
+use crate::storage;
+use crate::todo::{self, MANUAL_TODO_SESSION, ProjectTodo, TodoItem};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Component, Path};
+use std::time::SystemTime;
+
+pub const SECTION_START: &str = "<!-- jcode:todos:start -->";
+pub const SECTION_END: &str = "<!-- jcode:todos:end -->";
+
+const SECTION_NOTE: &str = "<!-- Managed by jcode. Check a box or edit an item to update it; new `- [ ]` lines become todos. -->";
+const SYNC_STATE_FILE: &str = ".jcode/todo_sync.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TodoSyncOutcome {
+    /// File and store already agree.
+    Unchanged,
+    /// The store was written out to the file.
+    WroteFile,
+    /// Edits in the file were imported into the store.
+    ImportedFile,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TodoSyncState {
+    #[serde(default)]
+    section_hash: Option<String>,
+}
+
+/// Sync the configured file for `project_dir`, if `[todo] sync_file` is set.
+/// Failures are logged, never returned: sync must not break the caller.
+pub fn sync_configured(project_dir: &Path) {
+    let Some(sync_file) = crate::config::config()
+        .todo
+        .sync_file
+        .clone()
+        .filter(|file| !file.trim().is_empty())
+    else {
+        return;
+    };
+    if let Err(error) = sync_todo_file(project_dir, Path::new(sync_file.trim())) {
+        crate::logging::warn(&format!(
+            "[todo-sync] failed to sync {} in {}: {}",
+            sync_file,
+            project_dir.display(),
+            error
+        ));
+    }
+}
+
+/// Reconcile `sync_file` (relative to `project_dir`) with the project todos.
+/// The project lock is held throughout, so a todo write from another session
+/// cannot land between reading the store and acting on it. A `sync_file`
+/// that is absolute or climbs out with `..` is rejected.
+pub fn sync_todo_file(project_dir: &Path, sync_file: &Path) -> Result<TodoSyncOutcome> {
+    if !is_project_relative(sync_file) {
+        anyhow::bail!(
+            "[todo] sync_file must be a path inside the project, got {}",
+            sync_file.display()
+        );
+    }
+    let lock = todo::lock_project_todos(project_dir)?;
+    let path = project_dir.join(sync_file);
+    let existing = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(error) => return Err(error.into()),
+    };
+    let file_section = extract_section(&existing);
+    let stored = todo::load_project_todos(project_dir)?;
+    if file_section.is_none() && stored.is_empty() {
+        // Nothing to publish yet; don't create an empty section.
+        return Ok(TodoSyncOutcome::Unchanged);
+    }
+    let rendered = render_section(&stored);
+
+    let state_path = project_dir.join(SYNC_STATE_FILE);
+    let last_hash = if state_path.exists() {
+        storage::read_json::<TodoSyncState>(&state_path)
+            .unwrap_or_default()
+            .section_hash
+    } else {
+        None
+    };
+    let rendered_hash = section_hash(&rendered);
+    let file_hash = file_section.map(section_hash);
+
+    if file_hash.as_deref() == Some(rendered_hash.as_str()) {
+        if last_hash.as_deref() != Some(rendered_hash.as_str()) {
+            save_state(&state_path, rendered_hash)?;
+        }
+        return Ok(TodoSyncOutcome::Unchanged);
+    }
+
+    let file_changed = file_hash.is_some() && file_hash != last_hash;
+    let store_changed = last_hash.as_deref() != Some(rendered_hash.as_str());
+    let import = match (file_changed, store_changed) {
+        (true, false) => true,
+        (true, true) => {
+            let file_newer = modified(&path) >= modified(&todo::project_todos_path(project_dir));
+            crate::logging::warn(&format!(
+                "[todo-sync] {} and {} both changed since the last sync; keeping the newer {}",
+                path.display(),
+                todo::PROJECT_TODOS_FILE,
+                if file_newer {
+                    "file edits"
+                } else {
+                    "jcode todos"
+                }
+            ));
+            file_newer
+        }
+        _ => false,
+    };
+
+    if import {
+        let imported = parse_section(file_section.unwrap_or_default(), &stored);
+        let normalized = render_section(&imported);
+        todo::replace_project_todos_locked(project_dir, imported, &lock)?;
+        if Some(normalized.as_str()) != file_section {
+            write_section(&path, &existing, &normalized)?;
+        }
+        save_state(&state_path, section_hash(&normalized))?;
+        return Ok(TodoSyncOutcome::ImportedFile);
+    }
+
+    write_section(&path, &existing, &rendered)?;
+    save_state(&state_path, rendered_hash)?;
+    Ok(TodoSyncOutcome::WroteFile)
+}
+
+fn is_project_relative(path: &Path) -> bool {
+    path.components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+/// Body between the markers, if the file has a complete section.
+fn extract_section(text: &str) -> Option<&str> {
+    let start = text.find(SECTION_START)? + SECTION_START.len();
+    let end = start + text[start..].find(SECTION_END)?;
+    Some(text[start..end].trim_start_matches(['\r', '\n']))
+}
+
+fn render_section(todos: &[ProjectTodo]) -> String {
+    let mut out = format!("{}\n", SECTION_NOTE);
+    for todo in todos {
+        let checked = if todo.item.status == "completed" || todo.item.status == "cancelled" {
+            'x'
+        } else {
+            ' '
+        };
+        out.push_str(&format!(
+            "- [{}] {} <!-- id={} session={} status={} priority={} -->\n",
+            checked,
+            todo.item.content.replace(['\r', '\n'], " ").trim(),
+            todo.item.id,
+            todo.session_id,
+            todo.item.status,
+            todo.item.priority,
+        ));
+    }
+    out
+}
+
+/// Parse the section back into todos. Lines carrying an `id=` comment update
+/// the matching stored todo; bare `- [ ] text` lines become new manual todos.
+fn parse_section(section: &str, stored: &[ProjectTodo]) -> Vec<ProjectTodo> {
+    let now = now_ms();
+    let mut next_id = 1usize;
+    let mut out: Vec<ProjectTodo> = Vec::new();
+    for line in section.lines() {
+        let Some((checked, rest)) = parse_checkbox(line.trim()) else {
+            continue;
+        };
+        let (content, meta) = match rest.rfind("<!--") {
+            Some(index) if rest.trim_end().ends_with("-->") => (
+                rest[..index].trim(),
+                rest[index + 4..].trim_end().trim_end_matches("-->"),
+            ),
+            _ => (rest.trim(), ""),
+        };
+        if content.is_empty() {
+            continue;
+        }
+        let field = |key| meta_field(meta, key);
+        let session_id = field("session").unwrap_or(MANUAL_TODO_SESSION).to_string();
+        let previous = field("id").and_then(|id| {
+            stored
+                .iter()
+                .find(|todo| todo.item.id == id && todo.session_id == session_id)
+        });
+
+        let meta_status = field("status").unwrap_or("pending");
+        let status = match (checked, meta_status) {
+            (true, "cancelled") => "cancelled",
+            (true, _) => "completed",
+            (false, "pending" | "in_progress") => meta_status,
+            (false, _) => "pending",
+        };
+        let id = match field("id") {
+            Some(id) => id.to_string(),
+            None => loop {
+                let candidate = format!("md-{next_id}");
+                next_id += 1;
+                let taken = stored
+                    .iter()
+                    .chain(out.iter())
+                    .any(|todo| todo.item.id == candidate && todo.session_id == session_id);
+                if !taken {
+                    break candidate;
+                }
+            },
+        };
+        let mut item = previous
+            .map(|todo| todo.item.clone())
+            .unwrap_or_else(|| TodoItem {
+                content: String::new(),
+                status: String::new(),
+                priority: "medium".to_string(),
+                id,
+                group: None,
+                confidence: None,
+                completion_confidence: None,
+                blocked_by: Vec::new(),
+                assigned_to: None,
+            });
+        item.content = content.to_string();
+        item.status = status.to_string();
+        if let Some(priority) = field("priority") {
+            item.priority = priority.to_string();
+        }
+        let updated_at_ms = match previous {
+            Some(todo) if todo.item == item => todo.updated_at_ms,
+            _ => now,
+        };
+        out.push(ProjectTodo {
+            item,
+            session_id,
+            updated_at_ms,
+        });
+    }
+    out
+}
+
+fn meta_field<'a>(meta: &'a str, key: &str) -> Option<&'a str> {
+    meta.split_whitespace()
+        .find_map(|pair| pair.strip_prefix(key)?.strip_prefix('='))
+        .filter(|value| !value.is_empty())
+}
+
+fn parse_checkbox(line: &str) -> Option<(bool, &str)> {
+    let rest = line
+        .strip_prefix("- [")
+        .or_else(|| line.strip_prefix("* ["))?;
+    let mut chars = rest.chars();
+    let mark = chars.next()?;
+    let rest = chars.as_str().strip_prefix(']')?;
+    match mark {
+        ' ' => Some((false, rest)),
+        'x' | 'X' => Some((true, rest)),
+        _ => None,
+    }
+}
+
+/// Replace the section body in `existing` (or append a new section) and write
+/// the result atomically. Text outside the markers is preserved as-is.
+fn write_section(path: &Path, existing: &str, body: &str) -> Result<()> {
+    let fenced = format!("{}\n{}{}", SECTION_START, body, SECTION_END);
+    // Look for the end marker after the start marker, as `extract_section`
+    // does, so a stray end marker earlier in the file is left alone.
+    let section = existing.find(SECTION_START).and_then(|start| {
+        let end = start + existing[start..].find(SECTION_END)?;
+        Some((start, end))
+    });
+    let updated = match section {
+        Some((start, end)) => format!(
+            "{}{}{}",
+            &existing[..start],
+            fenced,
+            &existing[end + SECTION_END.len()..]
+        ),
+        _ if existing.trim().is_empty() => format!("# TODO\n\n{}\n", fenced),
+        _ => {
+            let separator = if existing.ends_with("\n\n") {
+                ""
+            } else if existing.ends_with('\n') {
+                "\n"
+            } else {
+                "\n\n"
+            };
+            format!("{}{}{}\n", existing, separator, fenced)
+        }
+    };
+    storage::write_bytes_without_backup(path, updated.as_bytes())
+}
+
+fn save_state(state_path: &Path, section_hash: String) -> Result<()> {
+    storage::write_json_fast(
+        state_path,
+        &TodoSyncState {
+            section_hash: Some(section_hash),
+        },
+    )
+}
+
+fn section_hash(section: &str) -> String {
+    let normalized = section.replace("\r\n", "\n");
+    hex::encode(Sha256::digest(normalized.trim_end().as_bytes()))
+}
+
+fn modified(path: &Path) -> SystemTime {
+    std::fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .unwrap_or(SystemTime::UNIX_EPOCH)
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|dur| dur.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+#[path = "todo_sync_tests.rs"]
+mod todo_sync_tests;
@@ -0,0 +1,200 @@
+use super::*;
+
+fn with_temp_project(test: impl FnOnce(&Path)) {
+    let _guard = crate::storage::lock_test_env();
+    let temp = tempfile::TempDir::new().expect("temp dir");
+    let prev_home = std::env::var_os("JCODE_HOME");
+    crate::env::set_var("JCODE_HOME", temp.path().join("home"));
+
+    let project = temp.path().join("project");
+    std::fs::create_dir_all(&project).expect("project dir");
+    test(&project);
+
+    if let Some(prev_home) = prev_home {
+        crate::env::set_var("JCODE_HOME", prev_home);
+    } else {
+        crate::env::remove_var("JCODE_HOME");
+    }
+}
+
+fn item(id: &str, content: &str, status: &str) -> TodoItem {
+    TodoItem {
+        content: content.to_string(),
+        status: status.to_string(),
+        priority: "high".to_string(),
+        id: id.to_string(),
+        group: None,
+        confidence: None,
+        completion_confidence: None,
+        blocked_by: Vec::new(),
+        assigned_to: None,
+    }
+}
+
+#[test]
+fn writes_section_without_touching_rest_of_file() {
+    with_temp_project(|project| {
+        let path = project.join("TODO.md");
+        std::fs::write(&path, "# Notes\n\nKeep this paragraph.\n").expect("seed file");
+        todo::save_session_todos_in_project(
+            project,
+            "ses_a",
+            &[item("1", "Wire parser", "pending")],
+        )
+        .expect("save todos");
+
+        let outcome = sync_todo_file(project, Path::new("TODO.md")).expect("sync");
+        assert_eq!(outcome, TodoSyncOutcome::WroteFile);
+        let text = std::fs::read_to_string(&path).expect("read file");
+        assert!(text.starts_with("# Notes\n\nKeep this paragraph.\n\n<!-- jcode:todos:start -->"));
+        assert!(text.contains("- [ ] Wire parser <!-- id=1 session=ses_a status=pending"));
+        assert!(text.ends_with("<!-- jcode:todos:end -->\n"));
+
+        // Our own write is not read back as a human edit.
+        let outcome = sync_todo_file(project, Path::new("TODO.md")).expect("resync");
+        assert_eq!(outcome, TodoSyncOutcome::Unchanged);
+    });
+}
+
+#[test]
+fn human_edits_are_imported_into_project_and_session() {
+    with_temp_project(|project| {
+        let path = project.join("TODO.md");
+        todo::save_session_todos_in_project(
+            project,
+            "ses_a",
+            &[item("1", "Wire parser", "pending")],
+        )
+        .expect("save todos");
+        sync_todo_file(project, Path::new("TODO.md")).expect("initial sync");
+
+        let text = std::fs::read_to_string(&path).expect("read file");
+        let edited = text
+            .replace("- [ ] Wire parser", "- [x] Wire parser")
+            .replace(
+                SECTION_END,
+                &format!("- [ ] Ask a teammate\n{}", SECTION_END),
+            );
+        std::fs::write(&path, format!("Intro line\n{}Footer\n", edited)).expect("edit file");
+
+        let outcome = sync_todo_file(project, Path::new("TODO.md")).expect("sync edits");
+        assert_eq!(outcome, TodoSyncOutcome::ImportedFile);
+
+        let session = todo::load_todos("ses_a").expect("session todos");
+        assert_eq!(session[0].status, "completed");
+        let project_todos = todo::load_project_todos(project).expect("project todos");
+        assert_eq!(project_todos.len(), 2);
+        assert_eq!(project_todos[1].item.content, "Ask a teammate");
+        assert_eq!(project_todos[1].session_id, MANUAL_TODO_SESSION);
+
+        let text = std::fs::read_to_string(&path).expect("reread file");
+        assert!(text.starts_with("Intro line\n"));
+        assert!(text.ends_with("Footer\n"));
+        assert!(text.contains("Ask a teammate <!-- id=md-1 session=manual"));
+        assert_eq!(
+            sync_todo_file(project, Path::new("TODO.md")).expect("resync"),
+            TodoSyncOutcome::Unchanged
+        );
+    });
+}
+
+#[test]
+fn no_file_is_created_without_todos() {
+    with_temp_project(|project| {
+        let outcome = sync_todo_file(project, Path::new("TODO.md")).expect("sync");
+        assert_eq!(outcome, TodoSyncOutcome::Unchanged);
+        assert!(!project.join("TODO.md").exists());
+    });
+}
+
+#[test]
+fn sync_waits_for_a_store_write_in_progress() {
+    with_temp_project(|project| {
+        let path = project.join("TODO.md");
+        todo::save_session_todos_in_project(
+            project,
+            "ses_a",
+            &[item("1", "Wire parser", "pending")],
+        )
+        .expect("save todos");
+        sync_todo_file(project, Path::new("TODO.md")).expect("initial sync");
+
+        // Another session is mid-write: it holds the lock and has not yet
+        // saved its new item.
+        let lock = todo::lock_project_todos(project).expect("lock project todos");
+        let sync = std::thread::spawn({
+            let project = project.to_path_buf();
+            move || sync_todo_file(&project, Path::new("TODO.md"))
+        });
+        std::thread::sleep(std::time::Duration::from_millis(200));
+        assert!(!sync.is_finished(), "sync must wait for the project lock");
+
+        let mut stored = todo::load_project_todos(project).expect("project todos");
+        stored.push(ProjectTodo {
+            item: item("2", "Add fuzz tests", "pending"),
+            session_id: "ses_b".to_string(),
+            updated_at_ms: 1,
+        });
+        crate::storage::write_json(
+            &todo::project_todos_path(project),
+            &serde_json::json!({ "todos": stored }),
+        )
+        .expect("write store");
+        drop(lock);
+
+        let outcome = sync.join().expect("sync thread").expect("sync");
+        assert_eq!(outcome, TodoSyncOutcome::WroteFile);
+        let text = std::fs::read_to_string(&path).expect("read file");
+        assert!(text.contains("Add fuzz tests <!-- id=2 session=ses_b"));
+    });
+}
+
+#[test]
+fn stray_end_marker_before_the_section_does_not_duplicate_it() {
+    with_temp_project(|project| {
+        let path = project.join("TODO.md");
+        std::fs::write(&path, format!("Docs mention {} here.\n", SECTION_END)).expect("seed");
+        todo::save_session_todos_in_project(
+            project,
+            "ses_a",
+            &[item("1", "Wire parser", "pending")],
+        )
+        .expect("save todos");
+        sync_todo_file(project, Path::new("TODO.md")).expect("first sync");
+
+        todo::save_session_todos_in_project(
+            project,
+            "ses_a",
+            &[item("1", "Wire parser", "completed")],
+        )
+        .expect("update todos");
+        let outcome = sync_todo_file(project, Path::new("TODO.md")).expect("second sync");
+        assert_eq!(outcome, TodoSyncOutcome::WroteFile);
+
+        let text = std::fs::read_to_string(&path).expect("read file");
+        assert!(text.starts_with(&format!("Docs mention {} here.\n", SECTION_END)));
+        assert_eq!(text.matches(SECTION_START).count(), 1, "{text}");
+        assert!(text.contains("- [x] Wire parser"));
+    });
+}
+
+#[test]
+fn sync_file_outside_the_project_is_rejected() {
+    with_temp_project(|project| {
+        todo::save_session_todos_in_project(
+            project,
+            "ses_a",
+            &[item("1", "Wire parser", "pending")],
+        )
+        .expect("save todos");
+        let outside = project.parent().expect("temp dir").join("outside.md");
+        for sync_file in [Path::new("../outside.md"), outside.as_path()] {
+            let error = sync_todo_file(project, sync_file).expect_err("path must be rejected");
+            assert!(error.to_string().contains("inside the project"), "{error}");
+        }
+        assert!(!outside.exists());
+
+        let outcome = sync_todo_file(project, Path::new("./TODO.md")).expect("sync inside");
+        assert_eq!(outcome, TodoSyncOutcome::WroteFile);
+    });
+}
@@ -0,0 +1,161 @@
+use super::*;
+
+fn todo(id: &str, content: &str, status: &str, priority: &str) -> TodoItem {
+    TodoItem {
+        content: content.to_string(),
+        status: status.to_string(),
+        priority: priority.to_string(),
+        id: id.to_string(),
+        group: None,
+        confidence: None,
+        completion_confidence: None,
+        blocked_by: Vec::new(),
+        assigned_to: None,
+    }
+}
+
+fn with_temp_home(test: impl FnOnce(&Path)) {
+    let _guard = crate::storage::lock_test_env();
+    let temp = tempfile::TempDir::new().expect("temp dir");
+    let prev_home = std::env::var_os("JCODE_HOME");
+    crate::env::set_var("JCODE_HOME", temp.path().join("home"));
+
+    let project = temp.path().join("project");
+    std::fs::create_dir_all(&project).expect("project dir");
+    test(&project);
+
+    if let Some(prev_home) = prev_home {
+        crate::env::set_var("JCODE_HOME", prev_home);
+    } else {
+        crate::env::remove_var("JCODE_HOME");
+    }
+}
+
+#[test]
+fn session_saves_are_mirrored_into_project_file_with_attribution() {
+    with_temp_home(|project| {
+        save_session_todos_in_project(
+            project,
+            "ses_a",
+            &[todo("1", "wire parser", "pending", "low")],
+        )
+        .expect("save a");
+        save_session_todos_in_project(
+            project,
+            "ses_b",
+            &[
+                todo("1", "fix flaky test", "in_progress", "medium"),
+                todo("2", "ship it", "completed", "high"),
+            ],
+        )
+        .expect("save b");
+
+        assert_eq!(load_todos("ses_b").expect("session file").len(), 2);
+        let all = load_project_todos(project).expect("project file");
+        assert_eq!(all.len(), 3);
+
+        let open = open_project_todos(project);
+        assert_eq!(open.len(), 2);
+        assert_eq!(open[0].item.content, "fix flaky test");
+        assert_eq!(open[0].session_id, "ses_b");
+        assert_eq!(open[1].session_id, "ses_a");
+
+        // Rewriting a session's list replaces only that session's items.
+        save_session_todos_in_project(project, "ses_b", &[]).expect("clear b");
+        let all = load_project_todos(project).expect("project file");
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].session_id, "ses_a");
+    });
+}
+
+#[test]
+fn manual_add_complete_and_remove_keep_session_in_step() {
+    with_temp_home(|project| {
+        let added = add_project_todo(project, "ses_a", "  write docs ").expect("add");
+        assert_eq!(added.content, "write docs");
+        assert_eq!(load_todos("ses_a").expect("session").len(), 1);
+
+        assert!(
+            set_project_todo_status(project, "ses_a", &added.id, "completed").expect("complete")
+        );
+        assert_eq!(load_todos("ses_a").expect("session")[0].status, "completed");
+        assert!(open_project_todos(project).is_empty());
+
+        let manual = add_project_todo(project, MANUAL_TODO_SESSION, "triage").expect("add");
+        assert_ne!(manual.id, added.id);
+        assert!(remove_project_todo(project, MANUAL_TODO_SESSION, &manual.id).expect("remove"));
+        assert!(!remove_project_todo(project, MANUAL_TODO_SESSION, &manual.id).expect("again"));
+        assert_eq!(load_project_todos(project).expect("project").len(), 1);
+    });
+}
+
+#[test]
+fn prompt_summary_skips_current_session_and_respects_cap() {
+    with_temp_home(|project| {
+        save_session_todos_in_project(
+            project,
+            "ses_current",
+            &[todo("1", "mine", "pending", "high")],
+        )
+        .expect("save current");
+        assert!(project_todos_prompt_summary(project, "ses_current", 2_000).is_none());
+
+        let others: Vec<TodoItem> = (0..20)
+            .map(|index| {
+                todo(
+                    &index.to_string(),
+                    &format!("leftover task number {index}"),
+                    "pending",
+                    "medium",
+                )
+            })
+            .collect();
+        save_session_todos_in_project(project, "ses_old", &others).expect("save old");
+
+        let summary = project_todos_prompt_summary(project, "ses_current", 400).expect("summary");
+        assert!(summary.starts_with("# Open Project Todos"));
+        assert!(!summary.contains("mine"));
+        assert!(summary.contains("leftover task number 0"));
+        assert!(summary.contains("more\n"));
+    });
+}
+
+#[test]
+fn concurrent_manual_adds_keep_every_todo() {
+    with_temp_home(|project| {
+        std::thread::scope(|scope| {
+            for thread in 0..4 {
+                scope.spawn(move || {
+                    for index in 0..5 {
+                        add_project_todo(
+                            project,
+                            MANUAL_TODO_SESSION,
+                            &format!("task {thread}-{index}"),
+                        )
+                        .expect("add");
+                    }
+                });
+            }
+        });
+        let all = load_project_todos(project).expect("project");
+        assert_eq!(all.len(), 20);
+        let mut ids: Vec<&str> = all.iter().map(|todo| todo.item.id.as_str()).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), 20);
+    });
+}
+
+#[test]
+fn prompt_summary_cap_counts_characters() {
+    with_temp_home(|project| {
+        let wide: Vec<TodoItem> = (0..3)
+            .map(|index| todo(&index.to_string(), &"é".repeat(40), "pending", "medium"))
+            .collect();
+        save_session_todos_in_project(project, "ses_old", &wide).expect("save");
+
+        let summary = project_todos_prompt_summary(project, "ses_current", 400).expect("summary");
+        assert!(!summary.contains("more\n"), "{summary}");
+        assert!(summary.chars().count() <= 400);
+    });
+}
@@ -5,21 +5,36 @@
 use crate::auth;
 mod accessors;
 mod api_keys;
+mod background;
 mod cache;
 mod display;
+mod forecast;
 mod model;
 mod openai_helpers;
+mod origin;
 mod provider_fetch;
 pub use accessors::*;
 use api_keys::enqueue_api_key_usage_tasks;
+pub use background::{
+    BACKGROUND_USAGE_NAME, BackgroundDay, BackgroundModelUsage, background_usage_report,
+    background_usage_today, record_background_usage,
+};
 use cache::*;
 pub use jcode_usage_types::{ProviderUsage, ProviderUsageProgress, UsageLimit};
 pub use model::*;
+pub use origin::{
+    ORIGIN_USAGE_NAME, OriginUsage, UsageOrigin, attribute_to_session, origin_usage_entry,
+    origin_usage_report, record_origin_usage, take_pending_origin_usage,
+};
 use provider_fetch::*;
 
 use anyhow::{Context, Result};
 pub use display::{format_reset_time, format_usage_bar};
 use display::{format_token_count, humanize_key, provider_usage_cache_is_fresh};
+pub use forecast::{
+    UsageForecast, UsageSample, UsageWindowHistory, forecast_window, record_usage_samples,
+    stored_usage_forecasts, stored_usage_windows, usage_forecasts,
+};
 use openai_helpers::{parse_openai_usage_payload, usage_percent_to_ratio};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -45,6 +60,13 @@ const RATE_LIMIT_BACKOFF: Duration = Duration::from_secs(900);
 /// Minimum interval between /usage command fetches (per provider).
 const PROVIDER_USAGE_CACHE_TTL: Duration = Duration::from_secs(120);
 
+/// Window for the recent rate-limit count shown in `/usage`.
+const RATE_LIMIT_REPORT_WINDOW_SECS: u64 = 3_600;
+
+/// Window for the stalled-stream count shown in `/usage`. Wider than the
+/// rate-limit window since stalls are rarer and matter when they recur.
+const STREAM_STALL_REPORT_WINDOW_SECS: u64 = 86_400;
+
 /// Cached provider usage reports (used by /usage command).
 /// Keyed by provider display name.
 static PROVIDER_USAGE_CACHE: std::sync::OnceLock<
@@ -208,7 +230,8 @@ where
     let mut completed = 0usize;
     while let Some(joined) = tasks.join_next().await {
         completed += 1;
-        if let Ok(Some(report)) = joined {
+        if let Ok(Some(mut report)) = joined {
+            forecast::attach_forecast(&mut report);
             upsert_provider_usage(&mut results, report);
         }
 
@@ -264,8 +287,22 @@ fn sort_reports_most_recent_first(results: &mut [ProviderUsage]) {
     });
 }
 
+/// Record a provider rate-limit incident against a login/credential (an
+/// activity-ledger source key) so `/usage` can report recent throttling.
+pub fn record_rate_limit(source_key: &str) {
+    crate::metrics::record_rate_limit(source_key);
+    crate::provider_activity::record_rate_limit(source_key);
+}
+
+/// Record a stalled provider stream against a login/credential so `/usage`
+/// can point out chronic stallers.
+pub fn record_stream_stall(source_key: &str) {
+    crate::provider_activity::record_stream_stall(source_key);
+}
+
 /// Stamp a report with last-used recency from the activity ledger: sets the
-/// sort key and appends a human-readable "Last used" detail line.
+/// sort key and appends human-readable "Last used" / "Rate limits" /
+/// "Stalled streams" lines.
 fn attach_activity(report: &mut ProviderUsage, source_key: &str) {
     if let Some(used) = crate::provider_activity::last_used_unix_secs(source_key) {
         report.last_used_unix_secs = Some(used);
@@ -274,6 +311,30 @@ fn attach_activity(report: &mut ProviderUsage, source_key: &str) {
             crate::provider_activity::format_relative_age(used),
         ));
     }
+    let rate_limits =
+        crate::provider_activity::rate_limits_within(source_key, RATE_LIMIT_REPORT_WINDOW_SECS);
+    if rate_limits > 0 {
+        report.extra_info.push((
+            "Rate limits".to_string(),
+            format!(
+                "{} rate limit{} in the last hour",
+                rate_limits,
+                if rate_limits == 1 { "" } else { "s" }
+            ),
+        ));
+    }
+    let stalls =
+        crate::provider_activity::stream_stalls_within(source_key, STREAM_STALL_REPORT_WINDOW_SECS);
+    if stalls > 0 {
+        report.extra_info.push((
+            "Stalled streams".to_string(),
+            format!(
+                "{} stalled stream{} in the last 24 hours",
+                stalls,
+                if stalls == 1 { "" } else { "s" }
+            ),
+        ));
+    }
 }
 
 fn enqueue_provider_usage_tasks(tasks: &mut tokio::task::JoinSet<Option<ProviderUsage>>) -> usize {
@@ -0,0 +1,157 @@
+//! Token usage of background work (compaction and aside summaries,
+//! `/remember` condensing, sidecar calls through the live provider).
+//!
+//! Each call adds to today's per-model counters in
+//! `~/.jcode/background_usage.json`. The file is shared across processes, so
+//! `/usage` in any client shows what the server's background work cost too.
+//! The same days also hold per-origin counters for every provider call (see
+//! `usage::origin`).
+
+use super::{OriginUsage, ProviderUsage, format_token_count};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Days of counters kept in the file.
+const RETENTION_DAYS: usize = 7;
+
+pub const BACKGROUND_USAGE_NAME: &str = "Background tasks";
+
+static LEDGER_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackgroundModelUsage {
+    pub calls: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+}
+
+/// One local day of background usage.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackgroundDay {
+    #[serde(default)]
+    pub models: BTreeMap<String, BackgroundModelUsage>,
+    /// Calls that ran on the session model because the background model
+    /// could not be used.
+    #[serde(default)]
+    pub fallbacks: u64,
+    /// Every counted provider call, keyed by `UsageOrigin::label`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub origins: BTreeMap<String, OriginUsage>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct BackgroundUsageStore {
+    /// Keyed by local date (`YYYY-MM-DD`).
+    #[serde(default)]
+    days: BTreeMap<String, BackgroundDay>,
+}
+
+fn ledger_path() -> PathBuf {
+    crate::storage::jcode_dir()
+        .unwrap_or_else(|_| PathBuf::from(".").join(".jcode"))
+        .join("background_usage.json")
+}
+
+fn today() -> String {
+    chrono::Local::now().format("%Y-%m-%d").to_string()
+}
+
+/// Count one background call against `model`. `fell_back` marks a call that
+/// ran on the session model after the background model failed.
+pub fn record_background_usage(
+    model: &str,
+    input_tokens: Option<u64>,
+    output_tokens: Option<u64>,
+    fell_back: bool,
+) {
+    update_ledger(|days, day| {
+        record_into(days, day, model, input_tokens, output_tokens, fell_back)
+    });
+}
+
+/// Apply `update` to the stored days under the ledger lock; it gets today's
+/// date key.
+pub(super) fn update_ledger(update: impl FnOnce(&mut BTreeMap<String, BackgroundDay>, &str)) {
+    let _guard = LEDGER_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let path = ledger_path();
+    let mut store: BackgroundUsageStore = crate::storage::read_json(&path).unwrap_or_default();
+    update(&mut store.days, &today());
+    let _ = crate::storage::write_json(&path, &store);
+}
+
+pub(super) fn record_into(
+    days: &mut BTreeMap<String, BackgroundDay>,
+    day: &str,
+    model: &str,
+    input_tokens: Option<u64>,
+    output_tokens: Option<u64>,
+    fell_back: bool,
+) {
+    let entry = days.entry(day.to_string()).or_default();
+    let usage = entry.models.entry(model.to_string()).or_default();
+    usage.calls += 1;
+    usage.input_tokens += input_tokens.unwrap_or(0);
+    usage.output_tokens += output_tokens.unwrap_or(0);
+    if fell_back {
+        entry.fallbacks += 1;
+    }
+    prune_days(days);
+}
+
+pub(super) fn prune_days(days: &mut BTreeMap<String, BackgroundDay>) {
+    while days.len() > RETENTION_DAYS {
+        days.pop_first();
+    }
+}
+
+/// "N calls · X in · Y out" for a usage row.
+pub(super) fn format_call_usage(calls: u64, input_tokens: u64, output_tokens: u64) -> String {
+    format!(
+        "{} call{} · {} in · {} out",
+        calls,
+        if calls == 1 { "" } else { "s" },
+        format_token_count(input_tokens),
+        format_token_count(output_tokens)
+    )
+}
+
+/// Today's background usage across all processes.
+pub fn background_usage_today() -> BackgroundDay {
+    let store: BackgroundUsageStore = crate::storage::read_json(&ledger_path()).unwrap_or_default();
+    store.days.get(&today()).cloned().unwrap_or_default()
+}
+
+/// The "Background tasks" entry for `/usage`, or `None` before any
+/// background call today.
+pub fn background_usage_report() -> Option<ProviderUsage> {
+    background_report_for(&background_usage_today())
+}
+
+pub(super) fn background_report_for(day: &BackgroundDay) -> Option<ProviderUsage> {
+    if day.models.is_empty() {
+        return None;
+    }
+    let mut extra_info: Vec<(String, String)> = day
+        .models
+        .iter()
+        .map(|(model, usage)| {
+            (
+                model.clone(),
+                format_call_usage(usage.calls, usage.input_tokens, usage.output_tokens),
+            )
+        })
+        .collect();
+    if day.fallbacks > 0 {
+        extra_info.push((
+            "Fell back to session model".to_string(),
+            day.fallbacks.to_string(),
+        ));
+    }
+    Some(ProviderUsage {
+        provider_name: format!("{} (today)", BACKGROUND_USAGE_NAME),
+        extra_info,
+        ..ProviderUsage::default()
+    })
+}
@@ -0,0 +1,328 @@
+//! Usage history and burn-rate forecasts for subscription windows.
+//!
+//! Every successful `/usage` fetch appends one sample per limit window to
+//! `~/.jcode/usage_history.json`. The history is shared across processes, so
+//! the TUI, `jcode usage forecast` and the ambient scheduler all forecast from
+//! the same samples without fetching anything themselves.
+
+use super::ProviderUsage;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Samples older than this are pruned on the next write.
+const HISTORY_RETENTION_SECS: u64 = 8 * 86_400;
+
+/// A new sample within this many seconds of the last one only replaces it.
+const MIN_SAMPLE_INTERVAL_SECS: u64 = 60;
+
+/// Burn rate is measured over at most this much recent history.
+const BURN_LOOKBACK_SECS: u64 = 3 * 3_600;
+
+/// Less history than this is too noisy to project from.
+const MIN_BURN_SPAN_SECS: u64 = 10 * 60;
+
+/// Hourly buckets shown in the consumption sparkline.
+const SPARKLINE_HOURS: usize = 24;
+
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+static HISTORY_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct UsageSample {
+    pub at_unix_secs: u64,
+    pub usage_percent: f32,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UsageWindowHistory {
+    pub provider_name: String,
+    pub limit_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resets_at: Option<String>,
+    #[serde(default)]
+    pub samples: Vec<UsageSample>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct UsageHistoryStore {
+    #[serde(default)]
+    windows: BTreeMap<String, UsageWindowHistory>,
+}
+
+/// Where one limit window is heading at the current burn rate.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct UsageForecast {
+    pub provider_name: String,
+    pub limit_name: String,
+    pub usage_percent: f32,
+    /// Reset time as reported by the provider (RFC 3339, UTC).
+    pub resets_at: Option<String>,
+    /// Percentage points consumed per hour over the recent history.
+    pub burn_percent_per_hour: Option<f32>,
+    /// Seconds until the window reaches 100% at the current burn rate.
+    pub hits_limit_in_secs: Option<u64>,
+    /// Whether the window resets before the projected 100%.
+    pub resets_first: bool,
+    /// Percentage points consumed in each of the last 24 hours, oldest first.
+    pub last_24h_percent: Vec<f32>,
+}
+
+impl UsageForecast {
+    /// One-line projection, e.g. "at current burn rate you will hit 100% in ~2h 10m".
+    pub fn summary(&self) -> String {
+        if self.usage_percent >= 100.0 {
+            return "limit reached".to_string();
+        }
+        match (self.burn_percent_per_hour, self.hits_limit_in_secs) {
+            (None, _) => "not enough history yet for a forecast".to_string(),
+            (Some(_), None) => "no recent consumption".to_string(),
+            (Some(_), Some(_)) if self.resets_first => {
+                "resets before you hit 100% at current burn rate".to_string()
+            }
+            (Some(_), Some(secs)) => format!(
+                "at current burn rate you will hit 100% in ~{}",
+                format_eta(secs)
+            ),
+        }
+    }
+
+    /// Exact reset time in local time, e.g. "2026-10-18 14:05 (in 2h 10m)".
+    pub fn resets_at_local(&self) -> Option<String> {
+        let resets_at = self.resets_at.as_deref()?;
+        let reset = chrono::DateTime::parse_from_rfc3339(resets_at).ok()?;
+        Some(format!(
+            "{} (in {})",
+            reset.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M"),
+            super::format_reset_time(resets_at)
+        ))
+    }
+
+    pub fn sparkline(&self) -> String {
+        let max = self
+            .last_24h_percent
+            .iter()
+            .copied()
+            .fold(0.0_f32, f32::max);
+        self.last_24h_percent
+            .iter()
+            .map(|value| {
+                if max <= 0.0 || *value <= 0.0 {
+                    SPARK_LEVELS[0]
+                } else {
+                    let level = ((value / max) * 7.0).ceil() as usize;
+                    SPARK_LEVELS[level.clamp(1, 7)]
+                }
+            })
+            .collect()
+    }
+
+    pub fn last_24h_total(&self) -> f32 {
+        self.last_24h_percent.iter().sum()
+    }
+}
+
+fn history_path() -> PathBuf {
+    crate::storage::jcode_dir()
+        .unwrap_or_else(|_| PathBuf::from(".").join(".jcode"))
+        .join("usage_history.json")
+}
+
+fn load_store() -> UsageHistoryStore {
+    crate::storage::read_json(&history_path()).unwrap_or_default()
+}
+
+fn now_unix_secs() -> u64 {
+    chrono::Utc::now().timestamp().max(0) as u64
+}
+
+/// Report names carry a " ✦" marker on the active account; history should
+/// not split when the active account changes.
+fn history_key(provider_name: &str, limit_name: &str) -> String {
+    format!("{}::{}", provider_name.trim_end_matches(" ✦"), limit_name)
+}
+
+/// Append the report's current limit percentages to the usage history.
+pub fn record_usage_samples(report: &ProviderUsage) {
+    if report.error.is_some() || report.limits.is_empty() {
+        return;
+    }
+    let _guard = HISTORY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let now = now_unix_secs();
+    let mut store = load_store();
+    for limit in &report.limits {
+        let key = history_key(&report.provider_name, &limit.name);
+        let window = store
+            .windows
+            .entry(key)
+            .or_insert_with(|| UsageWindowHistory {
+                provider_name: report.provider_name.trim_end_matches(" ✦").to_string(),
+                limit_name: limit.name.clone(),
+                ..Default::default()
+            });
+        window.resets_at = limit.resets_at.clone();
+        push_sample(
+            &mut window.samples,
+            UsageSample {
+                at_unix_secs: now,
+                usage_percent: limit.usage_percent,
+            },
+        );
+    }
+    let cutoff = now.saturating_sub(HISTORY_RETENTION_SECS);
+    for window in store.windows.values_mut() {
+        window
+            .samples
+            .retain(|sample| sample.at_unix_secs >= cutoff);
+    }
+    store.windows.retain(|_, window| !window.samples.is_empty());
+    let _ = crate::storage::write_json(&history_path(), &store);
+}
+
+fn push_sample(samples: &mut Vec<UsageSample>, sample: UsageSample) {
+    if let Some(last) = samples.last_mut()
+        && sample.at_unix_secs.saturating_sub(last.at_unix_secs) < MIN_SAMPLE_INTERVAL_SECS
+    {
+        *last = sample;
+        return;
+    }
+    samples.push(sample);
+}
+
+/// Every window in the usage history, with its raw samples.
+pub fn stored_usage_windows() -> Vec<UsageWindowHistory> {
+    load_store().windows.into_values().collect()
+}
+
+/// Forecasts for every window in the usage history.
+pub fn stored_usage_forecasts() -> Vec<UsageForecast> {
+    let now = now_unix_secs();
+    load_store()
+        .windows
+        .values()
+        .map(|window| forecast_window(window, now))
+        .collect()
+}
+
+/// Forecasts for the limits in `reports`, in report order.
+pub fn usage_forecasts(reports: &[ProviderUsage]) -> Vec<UsageForecast> {
+    let now = now_unix_secs();
+    let store = load_store();
+    reports
+        .iter()
+        .flat_map(|report| {
+            report.limits.iter().filter_map(|limit| {
+                store
+                    .windows
+                    .get(&history_key(&report.provider_name, &limit.name))
+                    .map(|window| forecast_window(window, now))
+            })
+        })
+        .collect()
+}
+
+/// Record the report's samples and append its forecast lines to `extra_info`.
+pub(super) fn attach_forecast(report: &mut ProviderUsage) {
+    record_usage_samples(report);
+    for forecast in usage_forecasts(std::slice::from_ref(report)) {
+        let mut parts = Vec::new();
+        if let Some(reset) = forecast.resets_at_local() {
+            parts.push(format!("resets {}", reset));
+        }
+        parts.push(forecast.summary());
+        report.extra_info.push((
+            format!("{} forecast", forecast.limit_name),
+            parts.join(" · "),
+        ));
+        if forecast.last_24h_total() > 0.0 {
+            report.extra_info.push((
+                format!("{} last 24h", forecast.limit_name),
+                format!(
+                    "{} {:.0}% used",
+                    forecast.sparkline(),
+                    forecast.last_24h_total()
+                ),
+            ));
+        }
+    }
+}
+
+/// Project one window from its samples as of `now`.
+pub fn forecast_window(window: &UsageWindowHistory, now: u64) -> UsageForecast {
+    let samples = &window.samples;
+    let mut forecast = UsageForecast {
+        provider_name: window.provider_name.clone(),
+        limit_name: window.limit_name.clone(),
+        usage_percent: samples.last().map(|s| s.usage_percent).unwrap_or(0.0),
+        resets_at: window.resets_at.clone(),
+        last_24h_percent: hourly_consumption(samples, now),
+        ..Default::default()
+    };
+
+    // Only samples since the latest reset (a drop in usage) describe the
+    // current window.
+    let since_reset = samples
+        .windows(2)
+        .rposition(|pair| pair[1].usage_percent + 0.5 < pair[0].usage_percent)
+        .map(|idx| idx + 1)
+        .unwrap_or(0);
+    let lookback_start = now.saturating_sub(BURN_LOOKBACK_SECS);
+    let recent: Vec<&UsageSample> = samples[since_reset..]
+        .iter()
+        .filter(|sample| sample.at_unix_secs >= lookback_start)
+        .collect();
+    let (Some(first), Some(last)) = (recent.first(), recent.last()) else {
+        return forecast;
+    };
+    let span = last.at_unix_secs.saturating_sub(first.at_unix_secs);
+    if span < MIN_BURN_SPAN_SECS {
+        return forecast;
+    }
+
+    let rate = (last.usage_percent - first.usage_percent).max(0.0) / (span as f32 / 3_600.0);
+    forecast.burn_percent_per_hour = Some(rate);
+    if rate > 0.01 {
+        let remaining = (100.0 - forecast.usage_percent).max(0.0);
+        let secs = (remaining / rate * 3_600.0) as u64;
+        forecast.hits_limit_in_secs = Some(secs);
+        forecast.resets_first = window
+            .resets_at
+            .as_deref()
+            .and_then(|reset| chrono::DateTime::parse_from_rfc3339(reset).ok())
+            .is_some_and(|reset| reset.timestamp() <= now.saturating_add(secs) as i64);
+    }
+    forecast
+}
+
+/// Percentage points consumed per hour over the last 24 hours. Drops (window
+/// resets) do not count as consumption.
+fn hourly_consumption(samples: &[UsageSample], now: u64) -> Vec<f32> {
+    let mut buckets = vec![0.0_f32; SPARKLINE_HOURS];
+    let start = now.saturating_sub(SPARKLINE_HOURS as u64 * 3_600);
+    for pair in samples.windows(2) {
+        let at = pair[1].at_unix_secs;
+        if at <= start || at > now {
+            continue;
+        }
+        let delta = pair[1].usage_percent - pair[0].usage_percent;
+        if delta > 0.0 {
+            let idx = ((at - start - 1) / 3_600) as usize;
+            buckets[idx.min(SPARKLINE_HOURS - 1)] += delta;
+        }
+    }
+    buckets
+}
+
+fn format_eta(secs: u64) -> String {
+    let minutes = secs / 60;
+    let (days, hours, minutes) = (minutes / 1_440, (minutes / 60) % 24, minutes % 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes.max(1))
+    }
+}
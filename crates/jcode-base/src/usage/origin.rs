@@ -0,0 +1,185 @@
+//! Token usage split by what made the provider call.
+//!
+//! Interactive turns, subagents, compaction, summaries, memory capture and
+//! ambient runs all bill against the same account. Each counted call is tagged
+//! with a [`UsageOrigin`] and added to today's per-origin counters in the
+//! background usage ledger. Calls made on behalf of a session are also queued
+//! for that session and folded into `Session::origin_usage` on its next save,
+//! so `/session stats` can show what the orchestration around a conversation
+//! cost next to the conversation itself.
+
+use super::ProviderUsage;
+use super::background::{BackgroundDay, format_call_usage, prune_days, update_ledger};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, OnceLock};
+
+pub const ORIGIN_USAGE_NAME: &str = "Usage by origin";
+
+/// What a provider call was made for.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum UsageOrigin {
+    /// A user-driven turn of the session's own agent.
+    #[default]
+    Interactive,
+    /// A `task` subagent, by subagent type.
+    Subagent(String),
+    Compaction,
+    Aside,
+    Handoff,
+    /// Memory extraction, relevance checks and `/remember` condensing.
+    Memory,
+    Ambient,
+    /// Background analysis of repeated tool failures and failed turns.
+    AutoDebug,
+}
+
+impl UsageOrigin {
+    pub const INTERACTIVE: &'static str = "interactive";
+
+    /// Stable key used in the ledger and in `Session::origin_usage`.
+    pub fn label(&self) -> String {
+        match self {
+            Self::Interactive => Self::INTERACTIVE.to_string(),
+            Self::Subagent(name) => format!("subagent:{}", name),
+            Self::Compaction => "compaction".to_string(),
+            Self::Aside => "aside".to_string(),
+            Self::Handoff => "handoff".to_string(),
+            Self::Memory => "memory".to_string(),
+            Self::Ambient => "ambient".to_string(),
+            Self::AutoDebug => "auto-debug".to_string(),
+        }
+    }
+
+    /// Whether the call was overhead around the conversation rather than
+    /// the conversation itself.
+    pub fn is_overhead(&self) -> bool {
+        !matches!(self, Self::Interactive)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OriginUsage {
+    pub calls: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+}
+
+impl OriginUsage {
+    pub fn new(calls: u64, input_tokens: u64, output_tokens: u64) -> Self {
+        Self {
+            calls,
+            input_tokens,
+            output_tokens,
+        }
+    }
+
+    pub fn add(&mut self, other: OriginUsage) {
+        self.calls += other.calls;
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+    }
+
+    pub fn summary(&self) -> String {
+        format_call_usage(self.calls, self.input_tokens, self.output_tokens)
+    }
+}
+
+type PendingSessionUsage = HashMap<String, BTreeMap<String, OriginUsage>>;
+
+fn pending_session_usage() -> &'static Mutex<PendingSessionUsage> {
+    static PENDING: OnceLock<Mutex<PendingSessionUsage>> = OnceLock::new();
+    PENDING.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Count one provider call in today's per-origin counters.
+pub fn record_origin_usage(origin: &UsageOrigin, usage: OriginUsage) {
+    let label = origin.label();
+    update_ledger(|days, day| record_origin_into(days, day, &label, usage));
+}
+
+/// Count overhead usage against `session_id`. It is applied to the session
+/// the next time that session is saved in this process. Does not touch the
+/// ledger; callers that made the call themselves also use
+/// [`record_origin_usage`].
+pub fn attribute_to_session(session_id: &str, origin: &UsageOrigin, usage: OriginUsage) {
+    if session_id.is_empty() || !origin.is_overhead() {
+        return;
+    }
+    let mut pending = pending_session_usage()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    pending
+        .entry(session_id.to_string())
+        .or_default()
+        .entry(origin.label())
+        .or_default()
+        .add(usage);
+}
+
+/// Usage queued for `session_id` by [`attribute_to_session`] since the last
+/// call.
+pub fn take_pending_origin_usage(session_id: &str) -> BTreeMap<String, OriginUsage> {
+    pending_session_usage()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(session_id)
+        .unwrap_or_default()
+}
+
+pub(super) fn record_origin_into(
+    days: &mut BTreeMap<String, BackgroundDay>,
+    day: &str,
+    label: &str,
+    usage: OriginUsage,
+) {
+    days.entry(day.to_string())
+        .or_default()
+        .origins
+        .entry(label.to_string())
+        .or_default()
+        .add(usage);
+    prune_days(days);
+}
+
+/// The "Usage by origin" entry for `/usage`, or `None` before any counted
+/// call today.
+pub fn origin_usage_report() -> Option<ProviderUsage> {
+    origin_report_for(&super::background_usage_today())
+}
+
+pub(super) fn origin_report_for(day: &BackgroundDay) -> Option<ProviderUsage> {
+    origin_usage_entry(format!("{} (today)", ORIGIN_USAGE_NAME), &day.origins)
+}
+
+/// A `/usage` entry listing `origins` with interactive first, or `None` when
+/// there is nothing to show.
+pub fn origin_usage_entry(
+    name: String,
+    origins: &BTreeMap<String, OriginUsage>,
+) -> Option<ProviderUsage> {
+    if origins.is_empty() {
+        return None;
+    }
+    let mut rows: Vec<(&String, &OriginUsage)> = origins.iter().collect();
+    rows.sort_by_key(|(label, _)| label.as_str() != UsageOrigin::INTERACTIVE);
+    let mut extra_info: Vec<(String, String)> = rows
+        .into_iter()
+        .map(|(label, usage)| (label.clone(), usage.summary()))
+        .collect();
+    let overhead = origins
+        .iter()
+        .filter(|(label, _)| label.as_str() != UsageOrigin::INTERACTIVE)
+        .fold(OriginUsage::default(), |mut total, (_, usage)| {
+            total.add(*usage);
+            total
+        });
+    if overhead.calls > 0 && origins.contains_key(UsageOrigin::INTERACTIVE) {
+        extra_info.push(("Overhead total".to_string(), overhead.summary()));
+    }
+    Some(ProviderUsage {
+        provider_name: name,
+        extra_info,
+        ..ProviderUsage::default()
+    })
+}
@@ -661,11 +661,57 @@ pub(super) async fn fetch_copilot_usage_report() -> Option<ProviderUsage> {
         ),
     ));
 
+    if let Some(quota) = usage
+        .premium_quota
+        .as_ref()
+        .filter(|quota| quota.month == usage.month.month)
+    {
+        let value = match (quota.entitlement, quota.used()) {
+            (Some(entitlement), Some(used)) => format!(
+                "{} / {} used ({:.1}% left)",
+                used, entitlement, quota.percent_remaining
+            ),
+            _ => "unlimited".to_string(),
+        };
+        extra_info.push(("Premium quota".to_string(), value));
+    }
+
+    let budget = crate::copilot_usage::budget_status();
+    if let Some(status) = budget {
+        limits.push(UsageLimit {
+            name: "Premium budget".to_string(),
+            usage_percent: status.usage_percent(),
+            resets_at: None,
+        });
+        let state = match status.level() {
+            crate::copilot_usage::PremiumBudgetLevel::Ok => String::new(),
+            crate::copilot_usage::PremiumBudgetLevel::Conserve => {
+                " (conserving premium requests)".to_string()
+            }
+            crate::copilot_usage::PremiumBudgetLevel::Exhausted => format!(
+                " (premium models blocked; use {})",
+                jcode_provider_copilot::INCLUDED_MODELS.join(", ")
+            ),
+        };
+        extra_info.push((
+            "Premium budget".to_string(),
+            format!(
+                "{} / {} this month, {} left{}",
+                status.used,
+                status.budget,
+                status.remaining(),
+                state
+            ),
+        ));
+    }
+
     Some(ProviderUsage {
         provider_name: "GitHub Copilot".to_string(),
         limits,
         extra_info,
-        hard_limit_reached: false,
+        hard_limit_reached: budget.is_some_and(|status| {
+            status.level() == crate::copilot_usage::PremiumBudgetLevel::Exhausted
+        }),
         error: None,
         last_used_unix_secs: None,
     })
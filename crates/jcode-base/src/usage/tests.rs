@@ -681,3 +681,169 @@ fn test_activity_sweeper_skips_sources_with_dedicated_reports() {
         "some-custom-endpoint"
     ));
 }
+
+fn forecast_history(resets_at: Option<&str>, samples: &[(u64, f32)]) -> UsageWindowHistory {
+    UsageWindowHistory {
+        provider_name: "Anthropic (Claude)".to_string(),
+        limit_name: "5-hour window".to_string(),
+        resets_at: resets_at.map(str::to_string),
+        samples: samples
+            .iter()
+            .map(|&(at_unix_secs, usage_percent)| UsageSample {
+                at_unix_secs,
+                usage_percent,
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn test_forecast_projects_time_to_full_from_recent_burn_rate() {
+    let now = 1_800_000_000;
+    // 20 points over the last hour, 40% left: two hours to go.
+    let history = forecast_history(None, &[(now - 3_600, 40.0), (now, 60.0)]);
+    let forecast = forecast_window(&history, now);
+
+    assert_eq!(forecast.burn_percent_per_hour, Some(20.0));
+    assert_eq!(forecast.hits_limit_in_secs, Some(7_200));
+    assert!(!forecast.resets_first);
+    assert_eq!(
+        forecast.summary(),
+        "at current burn rate you will hit 100% in ~2h 0m"
+    );
+    assert_eq!(forecast.last_24h_total(), 20.0);
+    assert!(forecast.sparkline().ends_with('█'));
+    assert_eq!(forecast.sparkline().chars().count(), 24);
+}
+
+#[test]
+fn test_forecast_ignores_samples_before_window_reset() {
+    let now = 1_800_000_000;
+    let resets_at = chrono::DateTime::from_timestamp(now as i64 + 600, 0)
+        .expect("reset timestamp")
+        .to_rfc3339();
+    // Usage dropped from 90% to 5% on reset; only the climb to 35% counts.
+    let history = forecast_history(
+        Some(&resets_at),
+        &[
+            (now - 7_200, 90.0),
+            (now - 3_600, 5.0),
+            (now - 1_800, 20.0),
+            (now, 35.0),
+        ],
+    );
+    let forecast = forecast_window(&history, now);
+
+    assert_eq!(forecast.burn_percent_per_hour, Some(30.0));
+    assert!(forecast.resets_first);
+    assert_eq!(
+        forecast.summary(),
+        "resets before you hit 100% at current burn rate"
+    );
+    assert_eq!(forecast.last_24h_total(), 30.0);
+}
+
+#[test]
+fn test_forecast_needs_enough_history() {
+    let now = 1_800_000_000;
+    let history = forecast_history(None, &[(now - 120, 10.0), (now, 11.0)]);
+    let forecast = forecast_window(&history, now);
+
+    assert_eq!(forecast.burn_percent_per_hour, None);
+    assert_eq!(forecast.summary(), "not enough history yet for a forecast");
+}
+
+#[test]
+fn test_background_usage_counts_per_model_and_prunes_old_days() {
+    let mut days = std::collections::BTreeMap::new();
+    for day in 1..=8 {
+        background::record_into(
+            &mut days,
+            &format!("2026-01-0{}", day),
+            "claude-haiku-4-5",
+            None,
+            None,
+            false,
+        );
+    }
+    assert_eq!(days.len(), 7);
+    assert!(!days.contains_key("2026-01-01"));
+
+    let today = "2026-01-08";
+    background::record_into(
+        &mut days,
+        today,
+        "claude-haiku-4-5",
+        Some(1_500),
+        Some(200),
+        false,
+    );
+    background::record_into(&mut days, today, "claude-opus-4-6", None, None, true);
+
+    let report = background::background_report_for(&days[today]).expect("report");
+    assert_eq!(report.provider_name, "Background tasks (today)");
+    assert_eq!(
+        report.extra_info,
+        vec![
+            (
+                "claude-haiku-4-5".to_string(),
+                "2 calls · 1.5k in · 200 out".to_string()
+            ),
+            (
+                "claude-opus-4-6".to_string(),
+                "1 call · 0 in · 0 out".to_string()
+            ),
+            ("Fell back to session model".to_string(), "1".to_string()),
+        ]
+    );
+    assert!(background::background_report_for(&BackgroundDay::default()).is_none());
+}
+
+#[test]
+fn test_origin_usage_counts_per_origin_with_interactive_first() {
+    let mut days = std::collections::BTreeMap::new();
+    let today = "2026-01-08";
+    origin::record_origin_into(
+        &mut days,
+        today,
+        &UsageOrigin::Compaction.label(),
+        OriginUsage::new(1, 4_000, 300),
+    );
+    origin::record_origin_into(
+        &mut days,
+        today,
+        &UsageOrigin::Interactive.label(),
+        OriginUsage::new(2, 20_000, 1_000),
+    );
+    origin::record_origin_into(
+        &mut days,
+        today,
+        &UsageOrigin::Subagent("explore".to_string()).label(),
+        OriginUsage::new(3, 6_000, 700),
+    );
+
+    let report = origin::origin_report_for(&days[today]).expect("report");
+    assert_eq!(report.provider_name, "Usage by origin (today)");
+    assert_eq!(
+        report.extra_info,
+        vec![
+            (
+                "interactive".to_string(),
+                "2 calls · 20.0k in · 1.0k out".to_string()
+            ),
+            (
+                "compaction".to_string(),
+                "1 call · 4.0k in · 300 out".to_string()
+            ),
+            (
+                "subagent:explore".to_string(),
+                "3 calls · 6.0k in · 700 out".to_string()
+            ),
+            (
+                "Overhead total".to_string(),
+                "4 calls · 10.0k in · 1.0k out".to_string()
+            ),
+        ]
+    );
+    assert!(origin::origin_report_for(&BackgroundDay::default()).is_none());
+}
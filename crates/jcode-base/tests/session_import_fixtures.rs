@@ -0,0 +1,194 @@
+//! `jcode sessions import` against checked-in Claude Code and Codex
+//! transcripts (`tests/fixtures/session_import/`).
+//!
+//! The fixtures cover each format's message, tool-call and reasoning shapes
+//! plus a block jcode cannot represent, so a format drift shows up here
+//! rather than as silently missing history. Imports land in a sandboxed
+//! `JCODE_HOME`.
+
+use jcode_base::import::{SessionImportSource, import_session_file, session_import_files};
+use jcode_base::message::{ContentBlock, Role};
+use jcode_base::session::{SESSION_ORIGIN_IMPORTED, Session};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Serializes tests that mutate `JCODE_HOME`.
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+struct EnvVarGuard {
+    key: &'static str,
+    prev: Option<std::ffi::OsString>,
+}
+
+impl EnvVarGuard {
+    fn set_path(key: &'static str, value: &Path) -> Self {
+        let prev = std::env::var_os(key);
+        jcode_base::env::set_var(key, value);
+        Self { key, prev }
+    }
+}
+
+impl Drop for EnvVarGuard {
+    fn drop(&mut self) {
+        if let Some(prev) = self.prev.take() {
+            jcode_base::env::set_var(self.key, prev);
+        } else {
+            jcode_base::env::remove_var(self.key);
+        }
+    }
+}
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/session_import")
+        .join(name)
+}
+
+fn blocks(session: &Session) -> Vec<(Role, &ContentBlock)> {
+    session
+        .messages
+        .iter()
+        .flat_map(|message| {
+            message
+                .content
+                .iter()
+                .map(|block| (message.role.clone(), block))
+        })
+        .collect()
+}
+
+fn texts(session: &Session) -> Vec<&str> {
+    blocks(session)
+        .into_iter()
+        .filter_map(|(_, block)| match block {
+            ContentBlock::Text { text, .. } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn claude_code_fixture_imports_tools_notes_and_metadata() {
+    let _guard = ENV_LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let temp = tempfile::TempDir::new().unwrap();
+    let _home = EnvVarGuard::set_path("JCODE_HOME", temp.path());
+
+    let session = import_session_file(
+        SessionImportSource::ClaudeCode,
+        &fixture("claude_code.jsonl"),
+    )
+    .unwrap()
+    .expect("fixture has messages");
+
+    assert_eq!(session.id, "imported_cc_cc-fixture-1");
+    assert_eq!(session.origin.as_deref(), Some(SESSION_ORIGIN_IMPORTED));
+    assert_eq!(session.working_dir.as_deref(), Some("/home/dev/parser"));
+    assert_eq!(session.model.as_deref(), Some("claude-opus-4-6"));
+    assert_eq!(
+        session.title.as_deref(),
+        Some("The parser test fails on empty input, can you fix it?")
+    );
+
+    let blocks = blocks(&session);
+    assert!(blocks.iter().any(|(role, block)| *role == Role::Assistant
+        && matches!(block, ContentBlock::ToolUse { id, name, input, .. }
+            if id == "toolu_01" && name == "Bash" && input["command"] == "cargo test parser")));
+    assert!(blocks.iter().any(|(role, block)| *role == Role::User
+        && matches!(block, ContentBlock::ToolResult { tool_use_id, is_error, .. }
+            if tool_use_id == "toolu_01" && *is_error == Some(true))));
+    assert!(blocks.iter().any(|(_, block)| matches!(block,
+        ContentBlock::Reasoning { text } if text == "Run the test first.")));
+
+    let texts = texts(&session);
+    assert!(texts.contains(&"[image not imported]"));
+    assert!(texts.contains(&"[Claude Code content block not imported]"));
+    assert!(texts.contains(&"The empty case returns early now; the test passes."));
+
+    let loaded = Session::load(&session.id).unwrap();
+    assert_eq!(loaded.messages.len(), session.messages.len());
+    assert_eq!(loaded.origin.as_deref(), Some(SESSION_ORIGIN_IMPORTED));
+}
+
+#[test]
+fn codex_fixture_imports_tool_calls_reasoning_and_notes() {
+    let _guard = ENV_LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let temp = tempfile::TempDir::new().unwrap();
+    let _home = EnvVarGuard::set_path("JCODE_HOME", temp.path());
+
+    let session = import_session_file(SessionImportSource::Codex, &fixture("codex_rollout.jsonl"))
+        .unwrap()
+        .expect("codex sessions always import");
+
+    assert_eq!(session.id, "imported_codex_codex-fixture-1");
+    assert_eq!(session.origin.as_deref(), Some(SESSION_ORIGIN_IMPORTED));
+    assert_eq!(session.working_dir.as_deref(), Some("/home/dev/service"));
+    assert_eq!(session.model.as_deref(), Some("gpt-5-codex"));
+    assert_eq!(
+        session.title.as_deref(),
+        Some("Add a health check endpoint")
+    );
+
+    let blocks = blocks(&session);
+    assert!(blocks.iter().any(|(role, block)| *role == Role::Assistant
+        && matches!(block, ContentBlock::ToolUse { id, name, input, .. }
+            if id == "call_1" && name == "shell" && input["command"][0] == "rg")));
+    assert!(blocks.iter().any(|(_, block)| matches!(block,
+        ContentBlock::ToolUse { id, name, input, .. }
+            if id == "call_2" && name == "apply_patch"
+                && input["input"] == "*** Begin Patch\n*** End Patch")));
+    assert!(blocks.iter().any(|(role, block)| *role == Role::User
+        && matches!(block, ContentBlock::ToolResult { tool_use_id, content, .. }
+            if tool_use_id == "call_1" && content.contains("Router::new()"))));
+    assert!(blocks.iter().any(|(_, block)| matches!(block,
+        ContentBlock::Reasoning { text } if text == "Look at the router first.")));
+
+    let texts = texts(&session);
+    assert!(texts.contains(&"[Codex web_search_call not imported]"));
+    assert_eq!(texts.last(), Some(&"Added GET /health returning 200."));
+    assert_eq!(
+        texts
+            .iter()
+            .filter(|text| **text == "Add a health check endpoint")
+            .count(),
+        1,
+        "event_msg lines repeat response items and are skipped"
+    );
+
+    // Each tool result directly follows the assistant turn that called it.
+    for (index, message) in session.messages.iter().enumerate() {
+        if message
+            .content
+            .iter()
+            .any(|block| matches!(block, ContentBlock::ToolResult { .. }))
+        {
+            assert_eq!(session.messages[index - 1].role, Role::Assistant);
+        }
+    }
+
+    let loaded = Session::load(&session.id).unwrap();
+    assert_eq!(loaded.messages.len(), session.messages.len());
+}
+
+#[test]
+fn import_files_walks_directories_and_rejects_missing_paths() {
+    let dir = fixture("");
+    let files = session_import_files(SessionImportSource::Codex, Some(&dir)).unwrap();
+    assert_eq!(
+        files,
+        vec![fixture("claude_code.jsonl"), fixture("codex_rollout.jsonl")]
+    );
+
+    let single = fixture("codex_rollout.jsonl");
+    assert_eq!(
+        session_import_files(SessionImportSource::Codex, Some(&single)).unwrap(),
+        vec![single]
+    );
+
+    let missing = dir.join("missing");
+    let error = session_import_files(SessionImportSource::ClaudeCode, Some(&missing)).unwrap_err();
+    assert!(error.to_string().contains("No Claude Code transcripts"));
+}
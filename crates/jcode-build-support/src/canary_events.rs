@@ -0,0 +1,342 @@
+//! Canary track record feeding the promotion decision.
+//!
+//! Every build running as the manifest's canary appends structured events to
+//! `builds/canary-events.jsonl`: crashes (panic or fatal signal), rollbacks of
+//! its pending activation, and clean exits with their uptime. `jcode promote`
+//! refuses a canary with crash or rollback events, or with too few clean
+//! session-hours, unless forced; `jcode debug canary` prints the same record.
+
+use super::{
+    BuildManifest, CanaryStatus, advance_shared_server_if_tracking_stable, builds_dir,
+    read_stable_version, running_installed_version, update_stable_symlink, version_binary_path,
+};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CanaryEventKind {
+    /// The process panicked or was killed by a fatal signal
+    Crash,
+    /// The build's pending activation was rolled back
+    Rollback,
+    /// A session on the build exited normally
+    CleanExit,
+}
+
+/// One line of `canary-events.jsonl`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CanaryEvent {
+    pub kind: CanaryEventKind,
+    pub build_hash: String,
+    pub at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signal: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub panic_message: Option<String>,
+    /// Process uptime when the event was recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uptime_secs: Option<u64>,
+}
+
+impl CanaryEvent {
+    pub fn new(kind: CanaryEventKind, build_hash: &str) -> Self {
+        Self {
+            kind,
+            build_hash: build_hash.to_string(),
+            at: Utc::now(),
+            session_id: None,
+            signal: None,
+            panic_message: None,
+            uptime_secs: None,
+        }
+    }
+
+    /// One-line description for evidence listings.
+    pub fn describe(&self) -> String {
+        let mut parts = vec![format!("{:?}", self.kind).to_lowercase()];
+        if let Some(signal) = &self.signal {
+            parts.push(format!("signal {}", signal));
+        }
+        if let Some(message) = &self.panic_message {
+            parts.push(format!("panic: {}", message.lines().next().unwrap_or("")));
+        }
+        if let Some(session_id) = &self.session_id {
+            parts.push(format!("session {}", session_id));
+        }
+        if let Some(uptime) = self.uptime_secs {
+            parts.push(format!("after {}", format_uptime(uptime)));
+        }
+        format!(
+            "{} {}",
+            self.at.format("%Y-%m-%d %H:%M:%S"),
+            parts.join(", ")
+        )
+    }
+}
+
+pub fn canary_events_path() -> Result<PathBuf> {
+    Ok(builds_dir()?.join("canary-events.jsonl"))
+}
+
+pub fn append_canary_event(event: &CanaryEvent) -> Result<()> {
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(canary_events_path()?)?;
+    writeln!(file, "{}", serde_json::to_string(event)?)?;
+    Ok(())
+}
+
+/// All recorded events, oldest first. Unreadable lines are skipped.
+pub fn load_canary_events() -> Result<Vec<CanaryEvent>> {
+    let path = canary_events_path()?;
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return Ok(Vec::new());
+    };
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// The installed version this process runs, when it is the current canary.
+pub fn running_canary_build() -> Option<String> {
+    let version = running_installed_version()?;
+    let manifest = BuildManifest::load().ok()?;
+    (manifest.canary.as_deref() == Some(version.as_str())).then_some(version)
+}
+
+/// Record `event` against this process's build when it is the canary.
+/// `build_hash` is filled in here. A no-op for any other build.
+pub fn record_canary_event_for_running_build(mut event: CanaryEvent) -> Result<()> {
+    let Some(version) = running_canary_build() else {
+        return Ok(());
+    };
+    event.build_hash = version;
+    append_canary_event(&event)
+}
+
+/// Record `event` against `version` when it is the current canary. For
+/// processes observed from outside, such as a spawned server that died on a
+/// signal it could not handle. A no-op for any other build.
+pub fn record_canary_event_for_version(version: &str, mut event: CanaryEvent) -> Result<()> {
+    if BuildManifest::load()?.canary.as_deref() != Some(version) {
+        return Ok(());
+    }
+    event.build_hash = version.to_string();
+    append_canary_event(&event)
+}
+
+/// What the event log says about one build.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanaryTrackRecord {
+    pub build_hash: String,
+    /// Distinct sessions that recorded any event on the build
+    pub sessions: usize,
+    pub crashes: Vec<CanaryEvent>,
+    pub rollbacks: Vec<CanaryEvent>,
+    pub clean_exits: usize,
+    pub clean_uptime_secs: u64,
+    pub mean_uptime_secs: Option<u64>,
+}
+
+impl CanaryTrackRecord {
+    pub fn from_events(build_hash: &str, events: &[CanaryEvent]) -> Self {
+        let events: Vec<&CanaryEvent> = events
+            .iter()
+            .filter(|event| event.build_hash == build_hash)
+            .collect();
+        let of_kind = |kind| {
+            events
+                .iter()
+                .filter(|event| event.kind == kind)
+                .map(|event| (*event).clone())
+                .collect::<Vec<_>>()
+        };
+        let clean: Vec<&&CanaryEvent> = events
+            .iter()
+            .filter(|event| event.kind == CanaryEventKind::CleanExit)
+            .collect();
+        let uptimes: Vec<u64> = events.iter().filter_map(|e| e.uptime_secs).collect();
+        let sessions: BTreeSet<&str> = events
+            .iter()
+            .filter_map(|event| event.session_id.as_deref())
+            .collect();
+        Self {
+            build_hash: build_hash.to_string(),
+            sessions: sessions.len(),
+            crashes: of_kind(CanaryEventKind::Crash),
+            rollbacks: of_kind(CanaryEventKind::Rollback),
+            clean_exits: clean.len(),
+            clean_uptime_secs: clean.iter().filter_map(|e| e.uptime_secs).sum(),
+            mean_uptime_secs: (!uptimes.is_empty())
+                .then(|| uptimes.iter().sum::<u64>() / uptimes.len() as u64),
+        }
+    }
+
+    pub fn clean_session_hours(&self) -> f64 {
+        self.clean_uptime_secs as f64 / 3600.0
+    }
+
+    /// Why the build should not be promoted yet; empty when nothing blocks it.
+    pub fn promotion_blockers(&self, min_clean_hours: f64) -> Vec<String> {
+        let mut blockers = Vec::new();
+        if !self.crashes.is_empty() {
+            blockers.push(format!("{} crash event(s)", self.crashes.len()));
+        }
+        if !self.rollbacks.is_empty() {
+            blockers.push(format!("{} rollback(s)", self.rollbacks.len()));
+        }
+        if self.clean_session_hours() < min_clean_hours {
+            blockers.push(format!(
+                "{:.1} clean session-hours, {:.1} required",
+                self.clean_session_hours(),
+                min_clean_hours
+            ));
+        }
+        blockers
+    }
+}
+
+/// What [`promote_canary`] changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanaryPromotion {
+    pub version: String,
+    pub previous_stable: Option<String>,
+    /// Blockers overridden with `--force`
+    pub forced_over: Vec<String>,
+    pub moved_shared_server: bool,
+}
+
+/// Promote the current canary to stable when its track record allows it.
+pub fn promote_canary(min_clean_hours: f64, force: bool) -> Result<CanaryPromotion> {
+    let _lock = BuildManifest::lock()?;
+    let mut manifest = BuildManifest::load()?;
+    let Some(version) = manifest.canary.clone() else {
+        anyhow::bail!("No canary build recorded");
+    };
+    let binary = version_binary_path(&version)?;
+    if !binary.exists() {
+        anyhow::bail!("Canary {} is not installed ({:?} missing)", version, binary);
+    }
+
+    let record = CanaryTrackRecord::from_events(&version, &load_canary_events()?);
+    let blockers = record.promotion_blockers(min_clean_hours);
+    if !blockers.is_empty() && !force {
+        anyhow::bail!(
+            "Refusing to promote canary {}: {}. Pass --force to promote anyway.",
+            version,
+            blockers.join("; ")
+        );
+    }
+
+    let previous_stable = read_stable_version()?;
+    let moved_shared_server = advance_shared_server_if_tracking_stable(&version)?;
+    update_stable_symlink(&version)?;
+
+    // Reload: the stable promotion above updated the manifest on disk.
+    manifest = BuildManifest::load()?;
+    manifest.canary_status = Some(CanaryStatus::Passed);
+    manifest.save()?;
+
+    Ok(CanaryPromotion {
+        version,
+        previous_stable,
+        forced_over: if force { blockers } else { Vec::new() },
+        moved_shared_server,
+    })
+}
+
+/// `git diff --stat` between two installed build labels, using their commits.
+pub fn build_diff_stat(repo_dir: &Path, from: &str, to: &str) -> Result<String> {
+    // Dirty labels look like `<hash>-dirty-<fingerprint>`.
+    let commit = |label: &str| label.split("-dirty-").next().unwrap_or(label).to_string();
+    let output = Command::new("git")
+        .args(["diff", "--stat", &commit(from), &commit(to)])
+        .current_dir(repo_dir)
+        .output()?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git diff --stat failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .trim_end()
+        .to_string())
+}
+
+pub fn format_uptime(secs: u64) -> String {
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m{:02}s", secs / 60, secs % 60),
+        _ => format!("{}h{:02}m", secs / 3600, (secs % 3600) / 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: CanaryEventKind, build: &str, session: &str, uptime: u64) -> CanaryEvent {
+        CanaryEvent {
+            session_id: Some(session.to_string()),
+            uptime_secs: Some(uptime),
+            ..CanaryEvent::new(kind, build)
+        }
+    }
+
+    #[test]
+    fn track_record_counts_only_the_requested_build() {
+        let events = vec![
+            event(CanaryEventKind::CleanExit, "abc", "s1", 3600),
+            event(CanaryEventKind::CleanExit, "abc", "s2", 1800),
+            event(CanaryEventKind::Crash, "old", "s3", 10),
+        ];
+        let record = CanaryTrackRecord::from_events("abc", &events);
+        assert_eq!(record.sessions, 2);
+        assert_eq!(record.clean_exits, 2);
+        assert!(record.crashes.is_empty());
+        assert_eq!(record.mean_uptime_secs, Some(2700));
+        assert!((record.clean_session_hours() - 1.5).abs() < f64::EPSILON);
+        assert!(record.promotion_blockers(1.0).is_empty());
+        assert_eq!(record.promotion_blockers(2.0).len(), 1);
+    }
+
+    #[test]
+    fn crashes_and_rollbacks_block_promotion() {
+        let mut crash = event(CanaryEventKind::Crash, "abc", "s1", 42);
+        crash.signal = Some("SIGSEGV".to_string());
+        let events = vec![
+            event(CanaryEventKind::CleanExit, "abc", "s1", 7200),
+            crash,
+            event(CanaryEventKind::Rollback, "abc", "s2", 0),
+        ];
+        let record = CanaryTrackRecord::from_events("abc", &events);
+        let blockers = record.promotion_blockers(1.0);
+        assert_eq!(
+            blockers,
+            vec!["1 crash event(s)".to_string(), "1 rollback(s)".to_string()]
+        );
+        assert!(record.crashes[0].describe().contains("signal SIGSEGV"));
+    }
+
+    #[test]
+    fn events_round_trip_as_json_lines() {
+        let mut crash = CanaryEvent::new(CanaryEventKind::Crash, "abc");
+        crash.panic_message = Some("boom".to_string());
+        let line = serde_json::to_string(&crash).unwrap();
+        assert!(line.contains("\"kind\":\"crash\""));
+        assert!(!line.contains("signal"));
+        let parsed: CanaryEvent = serde_json::from_str(&line).unwrap();
+        assert_eq!(parsed, crash);
+    }
+}
@@ -1,8 +1,16 @@
+mod canary_events;
 mod paths;
 mod platform_support;
+mod rollback;
 mod source_state;
 mod storage_helpers;
+mod test_selection;
 
+pub use canary_events::{
+    CanaryEvent, CanaryEventKind, CanaryPromotion, CanaryTrackRecord, append_canary_event,
+    build_diff_stat, canary_events_path, format_uptime, load_canary_events, promote_canary,
+    record_canary_event_for_running_build, record_canary_event_for_version, running_canary_build,
+};
 pub use paths::{
     SELFDEV_CARGO_PROFILE, binary_name, binary_stem, client_update_candidate,
     current_binary_build_time_string, current_binary_built_at, find_dev_binary,
@@ -12,6 +20,10 @@ pub use paths::{
     shared_server_update_candidate, update_launcher_symlink_to_current,
     update_launcher_symlink_to_stable, version_matches_installed_channel,
 };
+pub use rollback::{
+    InstalledVersion, RollbackOutcome, installed_versions, record_session_on_running_build,
+    resolve_rollback_target, rollback_stable, running_installed_version,
+};
 pub use source_state::{
     current_build_info, current_git_diff, current_git_hash, current_git_hash_full,
     current_source_state, ensure_source_state_matches, get_commit_message, is_working_tree_dirty,
@@ -25,6 +37,9 @@ pub use storage_helpers::{
     shared_server_binary_path, shared_server_version_file, stable_binary_path, stable_version_file,
     version_binary_path, write_build_progress,
 };
+pub use test_selection::{
+    TestSelection, TestSelectionRules, WorkspaceGraph, select_rebuild_tests, select_tests_for_paths,
+};
 
 use anyhow::Result;
 use chrono::Utc;
@@ -61,6 +76,46 @@ pub struct BuildManifest {
     /// Pending activation being validated across reload/resume.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub pending_activation: Option<PendingActivation>,
+    /// Recent stable promotions, newest first, for `jcode rollback`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stable_history: Vec<StableRecord>,
+}
+
+thread_local! {
+    /// Manifest locks held by this thread, so nested helpers (a promotion
+    /// that updates channel symlinks) reuse the outer lock instead of waiting
+    /// on it.
+    static MANIFEST_LOCK_DEPTH: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// Exclusive lock on the build manifest and channel symlinks, held across a
+/// load-modify-save so concurrent sessions, rebuilds and promotions do not
+/// drop each other's writes. Released on drop.
+pub struct ManifestLock {
+    _file: Option<std::fs::File>,
+}
+
+impl Drop for ManifestLock {
+    fn drop(&mut self) {
+        MANIFEST_LOCK_DEPTH.with(|depth| depth.set(depth.get().saturating_sub(1)));
+    }
+}
+
+/// Previous stable promotions kept in the manifest.
+const STABLE_HISTORY_LIMIT: usize = 10;
+/// Sessions remembered per stable build.
+const STABLE_SESSIONS_LIMIT: usize = 20;
+
+/// One promotion of a version to the stable channel.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StableRecord {
+    /// Installed version label (`versions/<version>`)
+    pub version: String,
+    /// When the version became stable
+    pub promoted_at: chrono::DateTime<Utc>,
+    /// Top-level sessions started or resumed on this build, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sessions: Vec<String>,
 }
 
 impl BuildManifest {
@@ -80,6 +135,34 @@ impl BuildManifest {
         storage::write_json(&path, self)
     }
 
+    /// Take the manifest lock, blocking until other processes release it.
+    /// Re-entrant within a thread.
+    pub fn lock() -> Result<ManifestLock> {
+        if MANIFEST_LOCK_DEPTH.with(|depth| depth.get()) > 0 {
+            MANIFEST_LOCK_DEPTH.with(|depth| depth.set(depth.get() + 1));
+            return Ok(ManifestLock { _file: None });
+        }
+        let path = manifest_path()?.with_extension("lock");
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)?;
+        file.lock()
+            .map_err(|error| anyhow::anyhow!("Failed to lock {}: {}", path.display(), error))?;
+        MANIFEST_LOCK_DEPTH.with(|depth| depth.set(1));
+        Ok(ManifestLock { _file: Some(file) })
+    }
+
+    /// Load, modify and save the manifest under [`BuildManifest::lock`].
+    pub fn update<T>(f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        let _lock = Self::lock()?;
+        let mut manifest = Self::load()?;
+        let result = f(&mut manifest)?;
+        manifest.save()?;
+        Ok(result)
+    }
+
     /// Check if we should use stable or canary for a given session
     pub fn binary_for_session(&self, session_id: &str) -> BinaryChoice {
         // If this session is the canary tester, use canary
@@ -159,9 +242,58 @@ impl BuildManifest {
         self.history.truncate(20);
         self.save()
     }
+
+    /// Record `version` becoming stable. Re-promoting an older version (a
+    /// rollback) moves its record back to the front, keeping its sessions.
+    pub fn note_stable_promotion(&mut self, version: &str) {
+        self.stable = Some(version.to_string());
+        if self
+            .stable_history
+            .first()
+            .is_some_and(|record| record.version == version)
+        {
+            return;
+        }
+        let sessions = self
+            .stable_history
+            .iter()
+            .position(|record| record.version == version)
+            .map(|index| self.stable_history.remove(index).sessions)
+            .unwrap_or_default();
+        self.stable_history.insert(
+            0,
+            StableRecord {
+                version: version.to_string(),
+                promoted_at: Utc::now(),
+                sessions,
+            },
+        );
+        self.stable_history.truncate(STABLE_HISTORY_LIMIT);
+    }
+
+    /// Remember that `session_id` ran on `version`. Returns false when the
+    /// version is not a recorded stable build or the session is already known.
+    pub fn note_session_on_version(&mut self, version: &str, session_id: &str) -> bool {
+        let Some(record) = self
+            .stable_history
+            .iter_mut()
+            .find(|record| record.version == version)
+        else {
+            return false;
+        };
+        if record.sessions.iter().any(|known| known == session_id) {
+            return false;
+        }
+        record.sessions.push(session_id.to_string());
+        if record.sessions.len() > STABLE_SESSIONS_LIMIT {
+            record.sessions.remove(0);
+        }
+        true
+    }
 }
 
 pub fn complete_pending_activation_for_session(session_id: &str) -> Result<Option<String>> {
+    let _lock = BuildManifest::lock()?;
     let mut manifest = BuildManifest::load()?;
     let Some(pending) = manifest.pending_activation.clone() else {
         return Ok(None);
@@ -180,6 +312,7 @@ pub fn complete_pending_activation_for_session(session_id: &str) -> Result<Optio
 }
 
 pub fn rollback_pending_activation_for_session(session_id: &str) -> Result<Option<String>> {
+    let _lock = BuildManifest::lock()?;
     let mut manifest = BuildManifest::load()?;
     let Some(pending) = manifest.pending_activation.clone() else {
         return Ok(None);
@@ -198,6 +331,11 @@ pub fn rollback_pending_activation_for_session(session_id: &str) -> Result<Optio
     manifest.canary_status = Some(CanaryStatus::Failed);
     manifest.pending_activation = None;
     manifest.save()?;
+    // The event log only feeds `jcode promote`; never fail the rollback on it.
+    let _ = append_canary_event(&CanaryEvent {
+        session_id: Some(session_id.to_string()),
+        ..CanaryEvent::new(CanaryEventKind::Rollback, &pending.new_version)
+    });
     Ok(Some(pending.new_version))
 }
 
@@ -632,6 +770,7 @@ pub fn smoke_test_server_binary(binary: &Path) -> Result<()> {
 }
 
 fn update_channel_symlink(channel: &str, version: &str) -> Result<PathBuf> {
+    let _lock = BuildManifest::lock()?;
     let channel_dir = builds_dir()?.join(channel);
     storage::ensure_dir(&channel_dir)?;
 
@@ -654,13 +793,20 @@ fn update_channel_symlink(channel: &str, version: &str) -> Result<PathBuf> {
 
 /// Update stable symlink to point to a version and publish stable-version marker.
 pub fn update_stable_symlink(version: &str) -> Result<PathBuf> {
+    let _lock = BuildManifest::lock()?;
     let stable_link = update_channel_symlink("stable", version)?;
     std::fs::write(stable_version_file()?, version)?;
+    // Rollback history is best-effort; the marker above is authoritative.
+    if let Ok(mut manifest) = BuildManifest::load() {
+        manifest.note_stable_promotion(version);
+        let _ = manifest.save();
+    }
     Ok(stable_link)
 }
 
 /// Update current symlink to point to a version and publish current-version marker.
 pub fn update_current_symlink(version: &str) -> Result<PathBuf> {
+    let _lock = BuildManifest::lock()?;
     let current_link = update_channel_symlink("current", version)?;
     std::fs::write(current_version_file()?, version)?;
     Ok(current_link)
@@ -669,6 +815,7 @@ pub fn update_current_symlink(version: &str) -> Result<PathBuf> {
 /// Update the shared server symlink to point to a version and publish the
 /// shared-server-version marker.
 pub fn update_shared_server_symlink(version: &str) -> Result<PathBuf> {
+    let _lock = BuildManifest::lock()?;
     let shared_link = update_channel_symlink("shared-server", version)?;
     std::fs::write(shared_server_version_file()?, version)?;
     Ok(shared_link)
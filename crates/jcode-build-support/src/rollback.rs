@@ -0,0 +1,176 @@
+//! Rolling the stable channel back to a previously installed build.
+//!
+//! Every stable promotion is recorded in [`BuildManifest::stable_history`]
+//! (see [`update_stable_symlink`]), together with the top-level sessions that
+//! ran on each build. Rolling back just re-promotes an older immutable version:
+//! running TUI sessions notice the stable marker change and migrate at their
+//! next safe point through the usual auto-migration path.
+
+use super::{
+    BuildManifest, advance_shared_server_if_tracking_stable, builds_dir, read_current_version,
+    read_stable_version, update_current_symlink, update_launcher_symlink_to_current,
+    update_stable_symlink, version_binary_path,
+};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+
+/// An immutable build under `builds/versions/`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstalledVersion {
+    pub version: String,
+    /// Install time (binary mtime), when readable
+    pub installed_at: Option<DateTime<Utc>>,
+}
+
+/// What [`rollback_stable`] changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RollbackOutcome {
+    pub previous: Option<String>,
+    pub version: String,
+    /// `current` (and the launcher) pointed at the rolled-back build and were
+    /// moved too.
+    pub moved_current: bool,
+    /// The shared server channel was tracking stable and moved with it.
+    pub moved_shared_server: bool,
+}
+
+/// Installed versions, newest first.
+pub fn installed_versions() -> Result<Vec<InstalledVersion>> {
+    let dir = builds_dir()?.join("versions");
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Ok(Vec::new());
+    };
+    let mut versions = Vec::new();
+    for entry in entries.flatten() {
+        let version = entry.file_name().to_string_lossy().to_string();
+        let Ok(binary) = version_binary_path(&version) else {
+            continue;
+        };
+        let Ok(metadata) = std::fs::metadata(&binary) else {
+            continue;
+        };
+        versions.push(InstalledVersion {
+            version,
+            installed_at: metadata.modified().ok().map(DateTime::<Utc>::from),
+        });
+    }
+    versions.sort_by(|a, b| {
+        b.installed_at
+            .cmp(&a.installed_at)
+            .then_with(|| a.version.cmp(&b.version))
+    });
+    Ok(versions)
+}
+
+/// Pick the version to roll back to. With no request, that is the most
+/// recent previous stable that is still installed. A request matches a
+/// version label or hash prefix, preferring stable history over other
+/// installed versions.
+pub fn resolve_rollback_target(
+    manifest: &BuildManifest,
+    requested: Option<&str>,
+) -> Result<String> {
+    let current = read_stable_version()?;
+    let installed = installed_versions()?;
+    let is_installed = |version: &str| installed.iter().any(|v| v.version == version);
+
+    let Some(requested) = requested.map(str::trim).filter(|r| !r.is_empty()) else {
+        return manifest
+            .stable_history
+            .iter()
+            .map(|record| record.version.as_str())
+            .find(|version| Some(*version) != current.as_deref() && is_installed(version))
+            .map(str::to_string)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No previous stable build recorded; pass a version (see `jcode rollback --list`)"
+                )
+            });
+    };
+
+    let history = manifest
+        .stable_history
+        .iter()
+        .map(|record| record.version.as_str());
+    let others = installed.iter().map(|v| v.version.as_str());
+    let mut matches: Vec<&str> = Vec::new();
+    for version in history.chain(others) {
+        if version.starts_with(requested) && is_installed(version) && !matches.contains(&version) {
+            matches.push(version);
+        }
+    }
+    if matches.contains(&requested) {
+        return Ok(requested.to_string());
+    }
+    match matches.as_slice() {
+        [] => anyhow::bail!("No installed build matches '{}'", requested),
+        [only] => Ok(only.to_string()),
+        many => anyhow::bail!("'{}' is ambiguous: {}", requested, many.join(", ")),
+    }
+}
+
+/// Point `stable` back at `version`. The shared server follows when it was
+/// tracking stable, and `current` plus the launcher follow when they were on
+/// the build being rolled back, so new launches avoid it too.
+pub fn rollback_stable(version: &str) -> Result<RollbackOutcome> {
+    let _lock = BuildManifest::lock()?;
+    let binary = version_binary_path(version)?;
+    if !binary.exists() {
+        anyhow::bail!(
+            "Version {} is not installed ({:?} missing)",
+            version,
+            binary
+        );
+    }
+    let previous = read_stable_version()?;
+    let moved_current =
+        previous.is_some() && read_current_version()?.as_deref() == previous.as_deref();
+
+    // Must run before the stable marker moves (see the function docs).
+    let moved_shared_server = advance_shared_server_if_tracking_stable(version)?;
+    update_stable_symlink(version)?;
+    if moved_current {
+        update_current_symlink(version)?;
+        update_launcher_symlink_to_current()?;
+    }
+
+    Ok(RollbackOutcome {
+        previous,
+        version: version.to_string(),
+        moved_current,
+        moved_shared_server,
+    })
+}
+
+/// The installed version this process is running from, when it was launched
+/// from `builds/versions/<version>/` (directly or through a channel symlink).
+pub fn running_installed_version() -> Option<String> {
+    let exe = std::env::current_exe().ok()?;
+    let exe = std::fs::canonicalize(&exe).unwrap_or(exe);
+    // Avoid `builds_dir()`, which creates the directory.
+    let versions_dir = jcode_storage::jcode_dir()
+        .ok()?
+        .join("builds")
+        .join("versions");
+    let versions_dir = std::fs::canonicalize(&versions_dir).unwrap_or(versions_dir);
+    let relative = exe.strip_prefix(&versions_dir).ok()?;
+    relative
+        .components()
+        .next()
+        .map(|component| component.as_os_str().to_string_lossy().to_string())
+}
+
+/// Note in the manifest that `session_id` ran on this process's build, so
+/// `jcode rollback --list` can show which sessions a bad build touched. A
+/// no-op for builds that are not installed stable versions (e.g. `target/`).
+pub fn record_session_on_running_build(session_id: &str) -> Result<()> {
+    let Some(version) = running_installed_version() else {
+        return Ok(());
+    };
+    let _lock = BuildManifest::lock()?;
+    let mut manifest = BuildManifest::load()?;
+    if manifest.note_session_on_version(&version, session_id) {
+        manifest.save()?;
+    }
+    Ok(())
+}
@@ -0,0 +1,596 @@
+//! Pick the release tests a self-dev rebuild has to run.
+//!
+//! Files changed since the last promoted (stable) build are mapped to the
+//! workspace packages that own them, widened to every package that depends on
+//! those (directly or transitively), and joined with a small smoke set. Any
+//! change whose impact cannot be pinned to a package (manifests, lockfile,
+//! build scripts, the binary entry point, unknown paths) falls back to the
+//! full suite. The release build that precedes the tests still compiles the
+//! whole workspace, so selection only trims test execution.
+
+use super::read_stable_version;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::process::Command;
+
+/// Name of the root package, which owns `src/`, `tests/`, `examples/`, and
+/// `benches/`.
+const ROOT_PACKAGE: &str = "jcode";
+
+/// Path rules used to map changed files onto test targets.
+///
+/// Path patterns are matched against repo-relative paths: an entry ending in
+/// `/` matches everything below that directory, `*.ext` matches by extension,
+/// a bare file name (no `/`) matches that file in any directory, and anything
+/// else must match exactly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestSelectionRules {
+    /// Changes that make the mapping uncertain and force the full suite.
+    pub full_suite_paths: Vec<String>,
+    /// Changes that never need tests (docs, assets, scripts).
+    pub ignored_paths: Vec<String>,
+    /// Explicit path → packages mappings, checked before the crate layout.
+    pub path_packages: BTreeMap<String, Vec<String>>,
+    /// Packages tested on every selective run.
+    pub smoke_packages: Vec<String>,
+}
+
+impl Default for TestSelectionRules {
+    fn default() -> Self {
+        let strings = |items: &[&str]| items.iter().map(|item| item.to_string()).collect();
+        Self {
+            full_suite_paths: strings(&[
+                "Cargo.toml",
+                "Cargo.lock",
+                "build.rs",
+                "src/main.rs",
+                ".cargo/",
+                "rust-toolchain",
+                "rust-toolchain.toml",
+            ]),
+            ignored_paths: strings(&[
+                "*.md",
+                "LICENSE",
+                "docs/",
+                "assets/",
+                "ios/",
+                "packaging/",
+                "scripts/",
+                "telemetry-worker/",
+                ".github/",
+            ]),
+            path_packages: BTreeMap::new(),
+            smoke_packages: strings(&["jcode-protocol", "jcode-config-types"]),
+        }
+    }
+}
+
+/// Which tests a rebuild runs, and why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestSelection {
+    /// Packages to test, or `None` for the full suite.
+    pub packages: Option<Vec<String>>,
+    /// Human-readable explanation shown in rebuild output.
+    pub reason: String,
+}
+
+impl TestSelection {
+    pub fn full(reason: impl Into<String>) -> Self {
+        Self {
+            packages: None,
+            reason: reason.into(),
+        }
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.packages.is_none()
+    }
+
+    /// Arguments for `cargo`, keeping the single-threaded harness the full
+    /// suite has always used.
+    pub fn cargo_test_args(&self) -> Vec<String> {
+        let mut args = vec!["test".to_string(), "--release".to_string()];
+        for package in self.packages.iter().flatten() {
+            args.push("-p".to_string());
+            args.push(package.clone());
+        }
+        args.push("--".to_string());
+        args.push("--test-threads=1".to_string());
+        args
+    }
+
+    /// One-line description, e.g. `3 packages (jcode, jcode-tui, ...): ...`.
+    pub fn summary(&self) -> String {
+        match &self.packages {
+            None => format!("full suite: {}", self.reason),
+            Some(packages) => format!(
+                "{} package{} ({}): {}",
+                packages.len(),
+                if packages.len() == 1 { "" } else { "s" },
+                packages.join(", "),
+                self.reason
+            ),
+        }
+    }
+}
+
+/// Workspace packages keyed by directory, with reverse path-dependency edges.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkspaceGraph {
+    /// Repo-relative crate directory (`crates/jcode-tui/`) → package name.
+    crate_dirs: BTreeMap<String, String>,
+    /// Package → packages that depend on it through a `path` dependency.
+    dependents: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl WorkspaceGraph {
+    /// Read `crates/*/Cargo.toml` and the root manifest under `repo_dir`.
+    pub fn load(repo_dir: &Path) -> Self {
+        let mut manifests = Vec::new();
+        if let Ok(root) = std::fs::read_to_string(repo_dir.join("Cargo.toml")) {
+            manifests.push((String::new(), root));
+        }
+        if let Ok(entries) = std::fs::read_dir(repo_dir.join("crates")) {
+            for entry in entries.flatten() {
+                let dir = entry.file_name().to_string_lossy().to_string();
+                if let Ok(manifest) = std::fs::read_to_string(entry.path().join("Cargo.toml")) {
+                    manifests.push((format!("crates/{dir}/"), manifest));
+                }
+            }
+        }
+        Self::from_manifests(&manifests)
+    }
+
+    /// Build the graph from `(repo-relative dir, Cargo.toml contents)` pairs;
+    /// the root manifest uses an empty dir.
+    pub fn from_manifests(manifests: &[(String, String)]) -> Self {
+        let parsed: Vec<(&String, toml::Table)> = manifests
+            .iter()
+            .filter_map(|(dir, manifest)| Some((dir, manifest.parse::<toml::Table>().ok()?)))
+            .collect();
+        let workspace_paths = parsed
+            .iter()
+            .find(|(dir, _)| dir.is_empty())
+            .map(|(_, root)| workspace_path_dependencies(root))
+            .unwrap_or_default();
+
+        let mut graph = Self::default();
+        let mut dir_names = BTreeMap::new();
+        for (dir, manifest) in &parsed {
+            if let Some(name) = manifest_package_name(manifest) {
+                if !dir.is_empty() {
+                    graph.crate_dirs.insert((*dir).clone(), name.clone());
+                }
+                dir_names.insert(last_path_component(dir), name);
+            }
+        }
+        for (dir, manifest) in &parsed {
+            let Some(name) = manifest_package_name(manifest) else {
+                continue;
+            };
+            for dependency_dir in manifest_path_dependencies(manifest, &workspace_paths) {
+                if dependency_dir == last_path_component(dir) {
+                    continue;
+                }
+                if let Some(dependency) = dir_names.get(&dependency_dir) {
+                    graph
+                        .dependents
+                        .entry(dependency.clone())
+                        .or_default()
+                        .insert(name.clone());
+                }
+            }
+        }
+        graph
+    }
+
+    fn contains(&self, package: &str) -> bool {
+        package == ROOT_PACKAGE || self.crate_dirs.values().any(|name| name == package)
+    }
+
+    fn package_count(&self) -> usize {
+        self.crate_dirs.len() + 1
+    }
+
+    fn package_for_path(&self, path: &str) -> Option<String> {
+        if let Some((_, name)) = self
+            .crate_dirs
+            .iter()
+            .find(|(dir, _)| path.starts_with(dir.as_str()))
+        {
+            return Some(name.clone());
+        }
+        ["src/", "tests/", "examples/", "benches/"]
+            .iter()
+            .any(|prefix| path.starts_with(prefix))
+            .then(|| ROOT_PACKAGE.to_string())
+    }
+
+    fn with_dependents(&self, packages: BTreeSet<String>) -> BTreeSet<String> {
+        let mut closed = packages.clone();
+        let mut pending: Vec<String> = packages.into_iter().collect();
+        while let Some(package) = pending.pop() {
+            for dependent in self.dependents.get(&package).into_iter().flatten() {
+                if closed.insert(dependent.clone()) {
+                    pending.push(dependent.clone());
+                }
+            }
+        }
+        closed
+    }
+}
+
+/// Select the tests for a rebuild of `repo_dir`, diffing against the last
+/// promoted build. Falls back to the full suite whenever the diff is
+/// unavailable.
+pub fn select_rebuild_tests(repo_dir: &Path, rules: &TestSelectionRules) -> TestSelection {
+    let baseline = match read_stable_version() {
+        Ok(Some(version)) => version,
+        Ok(None) => return TestSelection::full("no promoted build to diff against"),
+        Err(error) => {
+            return TestSelection::full(format!("could not read the promoted build: {error}"));
+        }
+    };
+    // Dirty labels look like `<hash>-dirty-<fingerprint>`; diff against the
+    // commit, which can only widen the selection.
+    let commit = baseline.split("-dirty-").next().unwrap_or(&baseline);
+    match changed_paths_since(repo_dir, commit) {
+        Ok(changed) => {
+            select_tests_for_paths(&changed, &WorkspaceGraph::load(repo_dir), rules, &baseline)
+        }
+        Err(error) => TestSelection::full(format!("could not diff against {baseline}: {error}")),
+    }
+}
+
+/// Map `changed` (repo-relative paths) to a test selection.
+pub fn select_tests_for_paths(
+    changed: &[String],
+    graph: &WorkspaceGraph,
+    rules: &TestSelectionRules,
+    baseline: &str,
+) -> TestSelection {
+    let mut touched = BTreeSet::new();
+    let mut relevant = 0usize;
+    for path in changed {
+        if let Some((_, packages)) = rules
+            .path_packages
+            .iter()
+            .find(|(pattern, _)| path_matches(path, pattern))
+        {
+            touched.extend(packages.iter().cloned());
+            relevant += 1;
+            continue;
+        }
+        if rules
+            .full_suite_paths
+            .iter()
+            .any(|pattern| path_matches(path, pattern))
+        {
+            return TestSelection::full(format!("{path} changed"));
+        }
+        if rules
+            .ignored_paths
+            .iter()
+            .any(|pattern| path_matches(path, pattern))
+        {
+            continue;
+        }
+        match graph.package_for_path(path) {
+            Some(package) => {
+                touched.insert(package);
+                relevant += 1;
+            }
+            None => return TestSelection::full(format!("no package mapping for {path}")),
+        }
+    }
+
+    let affected = graph.with_dependents(touched);
+    let changed_packages = affected.len();
+    let mut selected = affected;
+    selected.extend(
+        rules
+            .smoke_packages
+            .iter()
+            .filter(|package| graph.contains(package))
+            .cloned(),
+    );
+    if selected.len() >= graph.package_count() {
+        return TestSelection::full(format!("changes since {baseline} reach every package"));
+    }
+
+    let reason = if relevant == 0 {
+        format!("no code changes since {baseline}; smoke set only")
+    } else {
+        format!(
+            "{relevant} changed file{} since {baseline} -> {changed_packages} affected package{}, plus smoke set",
+            if relevant == 1 { "" } else { "s" },
+            if changed_packages == 1 { "" } else { "s" },
+        )
+    };
+    TestSelection {
+        packages: Some(selected.into_iter().collect()),
+        reason,
+    }
+}
+
+fn path_matches(path: &str, pattern: &str) -> bool {
+    if let Some(dir) = pattern.strip_suffix('/') {
+        return path.starts_with(&format!("{dir}/"));
+    }
+    if let Some(extension) = pattern.strip_prefix("*.") {
+        return path.ends_with(&format!(".{extension}"));
+    }
+    if !pattern.contains('/') {
+        return path.rsplit('/').next() == Some(pattern);
+    }
+    path == pattern
+}
+
+/// Tracked changes against `commit` plus untracked files, repo-relative.
+fn changed_paths_since(repo_dir: &Path, commit: &str) -> anyhow::Result<Vec<String>> {
+    let mut paths = BTreeSet::new();
+    for args in [
+        &["diff", "--name-only", commit][..],
+        &["ls-files", "--others", "--exclude-standard"][..],
+    ] {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(repo_dir)
+            .output()?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "git {} failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        paths.extend(
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string),
+        );
+    }
+    Ok(paths.into_iter().collect())
+}
+
+const DEPENDENCY_TABLES: &[&str] = &["dependencies", "dev-dependencies", "build-dependencies"];
+
+fn manifest_package_name(manifest: &toml::Table) -> Option<String> {
+    manifest
+        .get("package")?
+        .get("name")?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// `[workspace.dependencies]` key → path, for members that declare a
+/// dependency with `workspace = true`.
+fn workspace_path_dependencies(root: &toml::Table) -> BTreeMap<String, String> {
+    root.get("workspace")
+        .and_then(|workspace| workspace.get("dependencies"))
+        .and_then(toml::Value::as_table)
+        .into_iter()
+        .flatten()
+        .filter_map(|(key, spec)| Some((key.clone(), spec.get("path")?.as_str()?.to_string())))
+        .collect()
+}
+
+/// Last path component of every path dependency in `manifest`: normal, dev and
+/// build dependencies, also under `[target.'cfg(..)']`, written inline or as
+/// `[dependencies.foo]` tables, or inherited from the workspace.
+fn manifest_path_dependencies(
+    manifest: &toml::Table,
+    workspace_paths: &BTreeMap<String, String>,
+) -> Vec<String> {
+    let targets = manifest
+        .get("target")
+        .and_then(toml::Value::as_table)
+        .into_iter()
+        .flat_map(|targets| targets.values().filter_map(toml::Value::as_table));
+    std::iter::once(manifest)
+        .chain(targets)
+        .flat_map(|section| {
+            DEPENDENCY_TABLES
+                .iter()
+                .filter_map(move |table| section.get(*table)?.as_table())
+        })
+        .flatten()
+        .filter_map(|(key, spec)| {
+            let path = match spec.get("path").and_then(toml::Value::as_str) {
+                Some(path) => path,
+                None if spec.get("workspace").and_then(toml::Value::as_bool) == Some(true) => {
+                    workspace_paths.get(key)?.as_str()
+                }
+                None => return None,
+            };
+            Some(last_path_component(path))
+        })
+        .collect()
+}
+
+fn last_path_component(path: &str) -> String {
+    path.trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph() -> WorkspaceGraph {
+        let manifest = |name: &str, deps: &[&str]| {
+            let mut text = format!("[package]\nname = \"{name}\"\n\n[dependencies]\n");
+            for dep in deps {
+                text.push_str(&format!("{dep} = {{ path = \"../{dep}\" }}\n"));
+            }
+            text
+        };
+        WorkspaceGraph::from_manifests(&[
+            (
+                String::new(),
+                "[package]\nname = \"jcode\"\n\n[dependencies]\njcode-tui = { path = \"crates/jcode-tui\" }\n"
+                    .to_string(),
+            ),
+            (
+                "crates/jcode-tui/".to_string(),
+                manifest("jcode-tui", &["jcode-base"]),
+            ),
+            (
+                "crates/jcode-base/".to_string(),
+                manifest("jcode-base", &["jcode-protocol"]),
+            ),
+            (
+                "crates/jcode-protocol/".to_string(),
+                manifest("jcode-protocol", &[]),
+            ),
+            (
+                "crates/jcode-pdf/".to_string(),
+                manifest("jcode-pdf", &[]),
+            ),
+            (
+                "crates/jcode-config-types/".to_string(),
+                manifest("jcode-config-types", &[]),
+            ),
+        ])
+    }
+
+    fn rules() -> TestSelectionRules {
+        TestSelectionRules {
+            smoke_packages: vec!["jcode-pdf".to_string()],
+            ..TestSelectionRules::default()
+        }
+    }
+
+    fn paths(items: &[&str]) -> Vec<String> {
+        items.iter().map(|item| item.to_string()).collect()
+    }
+
+    #[test]
+    fn path_dependencies_cover_tables_targets_and_workspace_inheritance() {
+        let root: toml::Table = r#"
+[workspace.dependencies]
+jcode-core = { path = "crates/jcode-core" }
+serde = "1"
+"#
+        .parse()
+        .unwrap();
+        let member: toml::Table = r#"
+[package]
+name = "jcode-tui"
+
+[dependencies]
+jcode-base = {path="../jcode-base"}
+jcode-core = { workspace = true }
+serde = { workspace = true }
+
+[dependencies.jcode-protocol]
+path = "../jcode-protocol"
+
+[dev-dependencies]
+# jcode-ignored = { path = "../jcode-ignored" }
+jcode-test-support = { path = "../jcode-test-support" }
+
+[target.'cfg(unix)'.dependencies]
+jcode-unix = { path = "../jcode-unix" }
+"#
+        .parse()
+        .unwrap();
+
+        let mut deps = manifest_path_dependencies(&member, &workspace_path_dependencies(&root));
+        deps.sort();
+        assert_eq!(
+            deps,
+            [
+                "jcode-base",
+                "jcode-core",
+                "jcode-protocol",
+                "jcode-test-support",
+                "jcode-unix"
+            ]
+        );
+        assert_eq!(manifest_package_name(&member).as_deref(), Some("jcode-tui"));
+    }
+
+    #[test]
+    fn crate_change_selects_dependents_and_smoke_set() {
+        let selection = select_tests_for_paths(
+            &paths(&["crates/jcode-tui/src/tui/app.rs", "README.md"]),
+            &graph(),
+            &rules(),
+            "abc1234",
+        );
+        assert_eq!(
+            selection.packages,
+            Some(paths(&["jcode", "jcode-pdf", "jcode-tui"]))
+        );
+        assert!(selection.reason.contains("1 changed file since abc1234"));
+        assert_eq!(
+            selection.cargo_test_args(),
+            paths(&[
+                "test",
+                "--release",
+                "-p",
+                "jcode",
+                "-p",
+                "jcode-pdf",
+                "-p",
+                "jcode-tui",
+                "--",
+                "--test-threads=1"
+            ])
+        );
+    }
+
+    #[test]
+    fn uncertain_changes_fall_back_to_full_suite() {
+        for changed in [
+            "Cargo.lock",
+            "crates/jcode-tui/Cargo.toml",
+            "src/main.rs",
+            "mystery/file.txt",
+        ] {
+            let selection =
+                select_tests_for_paths(&paths(&[changed]), &graph(), &rules(), "abc1234");
+            assert!(selection.is_full(), "{changed} should force the full suite");
+            assert!(selection.reason.contains(changed));
+        }
+    }
+
+    #[test]
+    fn selection_reaching_every_package_runs_full_suite() {
+        let selection = select_tests_for_paths(
+            &paths(&[
+                "crates/jcode-protocol/src/lib.rs",
+                "crates/jcode-config-types/src/lib.rs",
+            ]),
+            &graph(),
+            &rules(),
+            "abc1234",
+        );
+        assert!(selection.is_full());
+    }
+
+    #[test]
+    fn path_override_wins_over_full_suite_rules() {
+        let mut rules = rules();
+        rules
+            .path_packages
+            .insert("build.rs".to_string(), paths(&["jcode-protocol"]));
+        let selection = select_tests_for_paths(&paths(&["build.rs"]), &graph(), &rules, "abc");
+        assert_eq!(
+            selection.packages,
+            Some(paths(&[
+                "jcode",
+                "jcode-base",
+                "jcode-pdf",
+                "jcode-protocol",
+                "jcode-tui"
+            ]))
+        );
+    }
+}
@@ -853,3 +853,100 @@ fn repair_never_downgrades_when_stable_is_older() {
         );
     });
 }
+
+#[test]
+fn stable_history_keeps_previous_stables_and_sessions() {
+    let mut manifest = BuildManifest::default();
+    manifest.note_stable_promotion("aaa1111");
+    assert!(manifest.note_session_on_version("aaa1111", "session_a"));
+    assert!(!manifest.note_session_on_version("aaa1111", "session_a"));
+    assert!(!manifest.note_session_on_version("unknown", "session_a"));
+    manifest.note_stable_promotion("bbb2222");
+    manifest.note_stable_promotion("bbb2222");
+    manifest.note_stable_promotion("aaa1111");
+
+    let versions: Vec<_> = manifest
+        .stable_history
+        .iter()
+        .map(|record| record.version.as_str())
+        .collect();
+    assert_eq!(versions, vec!["aaa1111", "bbb2222"]);
+    assert_eq!(manifest.stable_history[0].sessions, vec!["session_a"]);
+    assert_eq!(manifest.stable.as_deref(), Some("aaa1111"));
+
+    for index in 0..20 {
+        manifest.note_stable_promotion(&format!("v{index}"));
+    }
+    assert_eq!(manifest.stable_history.len(), 10);
+}
+
+#[test]
+fn concurrent_manifest_updates_keep_every_write() {
+    with_temp_jcode_home(|| {
+        BuildManifest::update(|manifest| {
+            manifest.note_stable_promotion("aaa1111");
+            Ok(())
+        })
+        .expect("seed manifest");
+
+        let threads: Vec<_> = (0..4)
+            .map(|thread| {
+                std::thread::spawn(move || {
+                    for index in 0..5 {
+                        BuildManifest::update(|manifest| {
+                            // Nested locking (as in promotions) must not block.
+                            let _inner = BuildManifest::lock()?;
+                            manifest
+                                .note_session_on_version("aaa1111", &format!("s{thread}-{index}"));
+                            Ok(())
+                        })
+                        .expect("update manifest");
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().expect("join");
+        }
+
+        let manifest = BuildManifest::load().expect("manifest");
+        assert_eq!(manifest.stable_history[0].sessions.len(), 20);
+    });
+}
+
+#[test]
+fn rollback_restores_previous_stable_and_current() {
+    with_temp_jcode_home(|| {
+        let exe = std::env::current_exe().unwrap();
+        for version in ["good111", "bad2222"] {
+            install_binary_at_version(&exe, version).expect("install version");
+        }
+        update_stable_symlink("good111").expect("stable good");
+        update_shared_server_symlink("bad2222").expect("shared bad");
+        update_stable_symlink("bad2222").expect("stable bad");
+        update_current_symlink("bad2222").expect("current bad");
+
+        let manifest = BuildManifest::load().expect("manifest");
+        assert_eq!(manifest.stable_history.len(), 2);
+        let target = resolve_rollback_target(&manifest, None).expect("default target");
+        assert_eq!(target, "good111");
+        assert_eq!(
+            resolve_rollback_target(&manifest, Some("bad")).expect("prefix"),
+            "bad2222"
+        );
+        assert!(resolve_rollback_target(&manifest, Some("zzz")).is_err());
+
+        let outcome = rollback_stable(&target).expect("rollback");
+        assert_eq!(outcome.previous.as_deref(), Some("bad2222"));
+        assert!(outcome.moved_current);
+        assert!(outcome.moved_shared_server);
+        assert_eq!(read_stable_version().unwrap().as_deref(), Some("good111"));
+        assert_eq!(read_current_version().unwrap().as_deref(), Some("good111"));
+        assert_eq!(
+            read_shared_server_version().unwrap().as_deref(),
+            Some("good111")
+        );
+        let manifest = BuildManifest::load().expect("manifest");
+        assert_eq!(manifest.stable_history[0].version, "good111");
+    });
+}
@@ -1,4 +1,4 @@
-use jcode_message_types::{ContentBlock, Message, Role};
+use jcode_message_types::{ContentBlock, Message, Role, web_search_result_text};
 use std::collections::HashSet;
 use std::hash::{Hash, Hasher};
 
@@ -105,6 +105,8 @@ pub struct CompactionEvent {
     pub messages_compacted: Option<usize>,
     pub summary_chars: Option<usize>,
     pub active_messages: Option<usize>,
+    /// Tool results shortened to recover from a context overflow
+    pub tool_results_trimmed: Option<usize>,
 }
 
 /// What happened when ensure_context_fits was called
@@ -193,6 +195,21 @@ pub fn build_compaction_conversation_text(
                 ContentBlock::OpenAICompaction { .. } => {
                     conversation_text.push_str("[OpenAI native compaction]\n")
                 }
+                ContentBlock::ServerToolUse { name, input, .. } => {
+                    conversation_text.push_str(&format!("[Tool: {} - {}]\n", name, input));
+                }
+                ContentBlock::WebSearchResult {
+                    results,
+                    error_code,
+                    ..
+                } => {
+                    let text = web_search_result_text(results, error_code.as_deref());
+                    conversation_text.push_str(&format!(
+                        "[Result: {}]\n",
+                        truncate_str_boundary(&text, 500)
+                    ));
+                }
+                ContentBlock::Citations { .. } => {}
             }
         }
         conversation_text.push('\n');
@@ -324,6 +341,12 @@ pub fn content_char_count(content: &[ContentBlock]) -> usize {
             // compactions.
             ContentBlock::Image { .. } => IMAGE_TOKEN_COST * CHARS_PER_TOKEN,
             ContentBlock::OpenAICompaction { encrypted_content } => encrypted_content.len(),
+            ContentBlock::ServerToolUse { input, .. } => input.to_string().len() + 50,
+            ContentBlock::WebSearchResult { results, .. } => {
+                results.iter().map(|hit| hit.payload_bytes()).sum::<usize>() + 20
+            }
+            // Citations are display-only and never replayed to a provider.
+            ContentBlock::Citations { .. } => 0,
         })
         .sum()
 }
@@ -583,6 +606,170 @@ fn contains_independent_status_code(haystack: &str, code: &str) -> bool {
     })
 }
 
+/// Whether a provider error means the request exceeded the model's context
+/// window. Covers the wording each provider family uses: Anthropic
+/// ("prompt is too long: N tokens > M maximum"), OpenAI and OpenAI-compatible
+/// gateways ("maximum context length is N tokens", `context_length_exceeded`),
+/// Gemini ("input token count (N) exceeds the maximum number of tokens
+/// allowed"), and Bedrock ("Input is too long for requested model").
+pub fn is_context_overflow_error(error: &str) -> bool {
+    let lower = error.to_lowercase();
+    lower.contains("context length")
+        || lower.contains("context_length_exceeded")
+        || lower.contains("context window")
+        || lower.contains("maximum context")
+        || lower.contains("max context")
+        || lower.contains("token limit")
+        || lower.contains("too many tokens")
+        || lower.contains("prompt is too long")
+        || lower.contains("input is too long")
+        || lower.contains("request too large")
+        || lower.contains("length limit")
+        || lower.contains("maximum tokens")
+        || lower.contains("maximum number of tokens")
+        || lower.contains("reduce the length of the messages")
+        || (lower.contains("exceeded") && lower.contains("tokens"))
+}
+
+/// Token counts quoted in a context-overflow error, as `(used, limit)`.
+///
+/// Providers disagree on the order ("N tokens > M maximum" versus "maximum
+/// context length is M tokens ... resulted in N tokens"), but an overflow
+/// always quotes a request larger than the limit, so the larger of the first
+/// two token-sized numbers is the request and the smaller is the limit.
+pub fn context_overflow_token_counts(error: &str) -> Option<(u64, u64)> {
+    let mut numbers = Vec::new();
+    let mut current = String::new();
+    for ch in error.chars().chain(std::iter::once(' ')) {
+        if ch.is_ascii_digit() {
+            current.push(ch);
+        } else if ch == ',' && !current.is_empty() {
+            // Thousands separator, e.g. "128,000".
+        } else if !current.is_empty() {
+            if let Ok(value) = current.parse::<u64>()
+                && value >= 1_000
+            {
+                numbers.push(value);
+            }
+            current.clear();
+        }
+        if numbers.len() == 2 {
+            break;
+        }
+    }
+    match numbers.as_slice() {
+        [a, b] if a != b => Some(((*a).max(*b), (*a).min(*b))),
+        _ => None,
+    }
+}
+
+/// A message's share of the request, for explaining an overflow.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageContribution {
+    /// Position in the slice passed to [`largest_messages`].
+    pub index: usize,
+    /// Short description, e.g. `tool result (read)` or `user message`.
+    pub label: String,
+    pub tokens: usize,
+}
+
+/// The `limit` largest messages by estimated tokens, largest first.
+pub fn largest_messages(messages: &[Message], limit: usize) -> Vec<MessageContribution> {
+    let mut tool_names: std::collections::HashMap<&str, &str> = std::collections::HashMap::new();
+    for message in messages {
+        for block in &message.content {
+            if let ContentBlock::ToolUse { id, name, .. } = block {
+                tool_names.insert(id.as_str(), name.as_str());
+            }
+        }
+    }
+
+    let mut contributions: Vec<MessageContribution> = messages
+        .iter()
+        .enumerate()
+        .map(|(index, message)| MessageContribution {
+            index,
+            label: describe_message(message, &tool_names),
+            tokens: message_char_count(message) / CHARS_PER_TOKEN,
+        })
+        .collect();
+    contributions.sort_by(|a, b| b.tokens.cmp(&a.tokens).then(a.index.cmp(&b.index)));
+    contributions.truncate(limit);
+    contributions
+}
+
+fn describe_message(
+    message: &Message,
+    tool_names: &std::collections::HashMap<&str, &str>,
+) -> String {
+    let mut tools = Vec::new();
+    let mut images = 0usize;
+    for block in &message.content {
+        let name = match block {
+            ContentBlock::ToolResult { tool_use_id, .. } => tool_names
+                .get(tool_use_id.as_str())
+                .copied()
+                .unwrap_or("unknown"),
+            ContentBlock::ToolUse { name, .. } => name.as_str(),
+            ContentBlock::Image { .. } => {
+                images += 1;
+                continue;
+            }
+            _ => continue,
+        };
+        if !tools.contains(&name) {
+            tools.push(name);
+        }
+    }
+
+    let mut label = match (&message.role, tools.is_empty()) {
+        (Role::User, false) => format!("tool result ({})", tools.join(", ")),
+        (Role::Assistant, false) => format!("tool call ({})", tools.join(", ")),
+        (Role::User, true) => "user message".to_string(),
+        (Role::Assistant, true) => "assistant message".to_string(),
+    };
+    if images > 0 {
+        let noun = if images == 1 { "image" } else { "images" };
+        label.push_str(&format!(" + {images} {noun}"));
+    }
+    label
+}
+
+/// Shorten tool results oldest-first until `contents` fits `target_total_chars`.
+///
+/// Results at or after `pinned_from` belong to the turn in progress and are
+/// never touched; earlier ones keep a head and tail of
+/// `max_chars` (see [`emergency_truncated_tool_result`]). Returns the number of
+/// tool results that were shortened.
+pub fn trim_oldest_tool_results_in_contents(
+    contents: &mut [&mut Vec<ContentBlock>],
+    pinned_from: usize,
+    target_total_chars: usize,
+    max_chars: usize,
+) -> usize {
+    let mut total: usize = contents
+        .iter()
+        .map(|content| content_char_count(content))
+        .sum();
+    let mut trimmed = 0;
+    for blocks in contents.iter_mut().take(pinned_from) {
+        for block in blocks.iter_mut() {
+            if total <= target_total_chars {
+                return trimmed;
+            }
+            if let ContentBlock::ToolResult { content, .. } = block
+                && content.len() > max_chars
+            {
+                let before = content.len();
+                *content = emergency_truncated_tool_result(content, max_chars);
+                total = total.saturating_sub(before.saturating_sub(content.len()));
+                trimmed += 1;
+            }
+        }
+    }
+    trimmed
+}
+
 /// Strip oversized inline images from `messages`, oldest-first, until the total
 /// remaining base64 image payload fits within `target_total_chars`.
 ///
@@ -888,6 +1075,97 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn detects_context_overflow_errors_and_token_counts() {
+        let anthropic = "prompt is too long: 215,342 tokens > 200000 maximum";
+        let openai = "This model's maximum context length is 128000 tokens. However, your messages resulted in 130532 tokens.";
+        let gemini = "The input token count (1200000) exceeds the maximum number of tokens allowed (1048576).";
+        for error in [anthropic, openai, gemini] {
+            assert!(is_context_overflow_error(error), "{error}");
+        }
+        assert!(!is_context_overflow_error(
+            "rate limit exceeded, retry after 20s"
+        ));
+
+        assert_eq!(
+            context_overflow_token_counts(anthropic),
+            Some((215_342, 200_000))
+        );
+        assert_eq!(
+            context_overflow_token_counts(openai),
+            Some((130_532, 128_000))
+        );
+        assert_eq!(
+            context_overflow_token_counts(gemini),
+            Some((1_200_000, 1_048_576))
+        );
+        assert_eq!(context_overflow_token_counts("prompt is too long"), None);
+    }
+
+    fn tool_exchange(id: &str, name: &str, output_len: usize) -> [Message; 2] {
+        [
+            Message {
+                role: Role::Assistant,
+                content: vec![ContentBlock::ToolUse {
+                    id: id.to_string(),
+                    name: name.to_string(),
+                    input: serde_json::json!({}),
+                    thought_signature: None,
+                }],
+                timestamp: None,
+                tool_duration_ms: None,
+            },
+            Message {
+                role: Role::User,
+                content: vec![ContentBlock::ToolResult {
+                    tool_use_id: id.to_string(),
+                    content: "x".repeat(output_len),
+                    is_error: None,
+                }],
+                timestamp: None,
+                tool_duration_ms: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn largest_messages_names_tools_and_sorts_by_size() {
+        let mut messages = vec![Message::user("hi")];
+        messages.extend(tool_exchange("call_1", "read", 8_000));
+        messages.extend(tool_exchange("call_2", "bash", 40_000));
+
+        let top = largest_messages(&messages, 2);
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].index, 4);
+        assert_eq!(top[0].label, "tool result (bash)");
+        assert_eq!(top[0].tokens, (40_000 + 20) / CHARS_PER_TOKEN);
+        assert_eq!(top[1].label, "tool result (read)");
+    }
+
+    #[test]
+    fn trims_oldest_unpinned_tool_results_first() {
+        let mut messages = vec![Message::user("task")];
+        messages.extend(tool_exchange("call_1", "read", 20_000));
+        messages.extend(tool_exchange("call_2", "read", 20_000));
+        messages.push(Message::user("next task"));
+        messages.extend(tool_exchange("call_3", "read", 20_000));
+        let pinned_from = 5;
+
+        let mut contents: Vec<&mut Vec<ContentBlock>> =
+            messages.iter_mut().map(|m| &mut m.content).collect();
+        let trimmed =
+            trim_oldest_tool_results_in_contents(&mut contents, pinned_from, 50_000, 4_000);
+        assert_eq!(trimmed, 1);
+
+        let result_len = |message: &Message| match &message.content[0] {
+            ContentBlock::ToolResult { content, .. } => content.len(),
+            other => panic!("expected tool result, got {other:?}"),
+        };
+        assert!(result_len(&messages[2]) < 4_100);
+        assert_eq!(result_len(&messages[4]), 20_000);
+        assert_eq!(result_len(&messages[6]), 20_000);
+    }
+
     fn image_msg(data_len: usize) -> Message {
         Message {
             role: Role::User,
@@ -325,6 +325,14 @@ pub const KEYBINDING_DEFAULTS: &[KeybindingDefault] = &[
         macos: PlatformDefault::dev("cmd+shift+;"),
         other: PlatformDefault::dev("alt+shift+;"),
     },
+    KeybindingDefault {
+        id: "interject",
+        description: "Interject a message into the running turn",
+        // Only armed while a turn is streaming; when idle Alt+Enter keeps
+        // inserting a newline.
+        macos: PlatformDefault::dev("alt+enter"),
+        other: PlatformDefault::dev("alt+enter"),
+    },
     KeybindingDefault {
         id: "open_resume",
         description: "Open the /resume session picker",
@@ -333,6 +341,92 @@ pub const KEYBINDING_DEFAULTS: &[KeybindingDefault] = &[
         macos: PlatformDefault::dev("cmd+b"),
         other: PlatformDefault::dev("alt+r"),
     },
+    KeybindingDefault {
+        id: "image_open",
+        description: "Open the focused image or diagram in the system viewer",
+        macos: PlatformDefault::dev("alt+o"),
+        other: PlatformDefault::dev("alt+o"),
+    },
+    KeybindingDefault {
+        id: "remember_message",
+        description: "Save the focused assistant answer as a memory",
+        macos: PlatformDefault::dev("alt+p"),
+        other: PlatformDefault::dev("alt+p"),
+    },
+    KeybindingDefault {
+        id: "copy_message",
+        description: "Copy the focused answer or tool output to the clipboard",
+        macos: PlatformDefault::dev("alt+q"),
+        other: PlatformDefault::dev("alt+q"),
+    },
+    KeybindingDefault {
+        id: "file_picker",
+        description: "Open the workspace file picker to attach files as context",
+        macos: PlatformDefault::dev("ctrl+f"),
+        other: PlatformDefault::dev("ctrl+f"),
+    },
+    KeybindingDefault {
+        id: "composer_word_back",
+        description: "Move the composer cursor back one word",
+        // Option+B arrives as `∫` on macOS; Alt bindings already match it.
+        macos: PlatformDefault::dev("alt+b,ctrl+left"),
+        other: PlatformDefault::dev("alt+b,ctrl+left"),
+    },
+    KeybindingDefault {
+        id: "composer_word_forward",
+        description: "Move the composer cursor forward one word",
+        macos: PlatformDefault::dev("alt+f,ctrl+right"),
+        other: PlatformDefault::dev("alt+f,ctrl+right"),
+    },
+    KeybindingDefault {
+        id: "composer_kill_line_end",
+        description: "Kill to the end of the composer line",
+        // Only while the draft is non-empty; an empty composer keeps Ctrl+K
+        // for prompt navigation.
+        macos: PlatformDefault::dev("ctrl+k"),
+        other: PlatformDefault::dev("ctrl+k"),
+    },
+    KeybindingDefault {
+        id: "composer_kill_line_start",
+        description: "Kill to the start of the composer line",
+        macos: PlatformDefault::dev("ctrl+u"),
+        other: PlatformDefault::dev("ctrl+u"),
+    },
+    KeybindingDefault {
+        id: "composer_kill_word_back",
+        description: "Kill the previous word in the composer",
+        macos: PlatformDefault::dev("ctrl+w"),
+        other: PlatformDefault::dev("ctrl+w"),
+    },
+    KeybindingDefault {
+        id: "composer_yank",
+        description: "Yank the most recent kill into the composer",
+        // Shares Ctrl+Y with `fallback_switch`, which only claims it while a
+        // fallback offer is armed.
+        macos: PlatformDefault::dev("ctrl+y"),
+        other: PlatformDefault::dev("ctrl+y"),
+    },
+    KeybindingDefault {
+        id: "composer_yank_pop",
+        description: "Replace the just-yanked text with an older kill",
+        // Readline's Alt+Y is `copy_selection_toggle` here.
+        macos: PlatformDefault::unbound(KeybindingProvenance::Dev),
+        other: PlatformDefault::unbound(KeybindingProvenance::Dev),
+    },
+    KeybindingDefault {
+        id: "composer_undo",
+        description: "Undo the last composer edit",
+        // Kitty-protocol terminals report Ctrl+_ as Ctrl+Shift+-.
+        macos: PlatformDefault::dev("ctrl+_,ctrl+shift+-"),
+        other: PlatformDefault::dev("ctrl+_,ctrl+shift+-"),
+    },
+    KeybindingDefault {
+        id: "composer_transpose",
+        description: "Transpose the characters around the composer cursor",
+        // Readline's Ctrl+T is the queue-mode toggle here.
+        macos: PlatformDefault::unbound(KeybindingProvenance::Dev),
+        other: PlatformDefault::unbound(KeybindingProvenance::Dev),
+    },
 ];
 
 /// Look up a keybinding action by id.
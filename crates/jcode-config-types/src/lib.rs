@@ -283,9 +283,38 @@ impl CrossProviderFailoverMode {
     }
 }
 
+/// What to do when the active model has no native tool calling.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolFallbackMode {
+    /// Describe the tools in the system prompt and parse fenced `tool` blocks
+    /// from the reply.
+    #[default]
+    Textual,
+    /// End the turn with an error suggesting a tool-capable model.
+    Refuse,
+}
+
+impl ToolFallbackMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Textual => "textual",
+            Self::Refuse => "refuse",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "textual" | "text" => Some(Self::Textual),
+            "refuse" | "off" => Some(Self::Refuse),
+            _ => None,
+        }
+    }
+}
+
 /// Compaction configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct CompactionConfig {
     /// Compaction mode: reactive (default), proactive, or semantic
     pub mode: CompactionMode,
@@ -316,6 +345,10 @@ pub struct CompactionConfig {
 
     /// [semantic] Number of recent turns to look at for building the "current goal" embedding
     pub goal_window_turns: usize,
+
+    /// Stub out earlier file reads superseded by a later read or edit of the same path
+    /// in outbound requests (the session keeps the full results). Off by default.
+    pub prune_superseded_reads: bool,
 }
 
 impl Default for CompactionConfig {
@@ -331,6 +364,7 @@ impl Default for CompactionConfig {
             topic_shift_threshold: 0.45,
             relevance_keep_threshold: 0.65,
             goal_window_turns: 5,
+            prune_superseded_reads: false,
         }
     }
 }
@@ -354,7 +388,7 @@ pub enum NamedProviderAuth {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct NamedProviderModelConfig {
     pub id: String,
     #[serde(
@@ -371,7 +405,7 @@ pub struct NamedProviderModelConfig {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct NamedProviderConfig {
     #[serde(rename = "type")]
     pub provider_type: NamedProviderType,
@@ -438,7 +472,7 @@ impl Default for NamedProviderConfig {
 
 /// Remembered trust decisions for external auth sources managed by other tools.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct AuthConfig {
     /// External auth source ids that the user has approved jcode to read/use.
     pub trusted_external_sources: Vec<String>,
@@ -449,7 +483,7 @@ pub struct AuthConfig {
 
 /// Agent-specific model defaults.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct AgentsConfig {
     /// Optional default model override for spawned swarm/subagent sessions.
     ///
@@ -467,6 +501,13 @@ pub struct AgentsConfig {
     pub swarm_gallery_max_pct: Option<u8>,
     /// Optional default model override for the memory sidecar.
     pub memory_model: Option<String>,
+    /// Model for background work (compaction summaries, aside summaries,
+    /// `/remember` condensing, sidecar calls routed through the provider).
+    ///
+    /// Leave unset to use the provider's cheap default (Claude Haiku on
+    /// Anthropic, GPT-5 mini on OpenAI and Copilot). Use `"inherit"` to run
+    /// background work on the session's model.
+    pub background_model: Option<String>,
     /// Whether memory should use the sidecar for relevance/extraction.
     ///
     /// Defaults to `true`: the LLM precision-judge path is the only memory mode
@@ -568,6 +609,7 @@ impl Default for AgentsConfig {
             swarm_spawn_mode: SwarmSpawnMode::default(),
             swarm_gallery_max_pct: None,
             memory_model: None,
+            background_model: None,
             memory_sidecar_enabled: default_memory_sidecar_enabled(),
             memory_rerank_cadence: default_memory_rerank_cadence(),
             memory_rerank_votes: default_memory_rerank_votes(),
@@ -622,7 +664,7 @@ impl SwarmSpawnMode {
 
 /// Terminal window/pane spawning configuration.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct TerminalConfig {
     /// External command that takes over headed session spawns (new terminal
     /// windows for swarm agents, resume-in-new-terminal, self-dev, restarts).
@@ -706,6 +748,58 @@ pub struct HooksConfig {
     /// Max milliseconds to wait for the pre_tool gate before failing open
     /// (default: 5000). Env override: JCODE_HOOK_PRE_TOOL_TIMEOUT_MS.
     pub pre_tool_timeout_ms: u64,
+    /// Matcher-scoped hooks (`[[hooks.rules]]`, or `[[hooks]]` when the file
+    /// has no `[hooks]` table). Projects can add more in `.jcode/hooks.toml`.
+    pub rules: Vec<HookRule>,
+}
+
+/// A hook that runs only for matching tool calls (or at turn end).
+///
+/// The command receives the event as JSON on stdin. For `pre_tool`, any
+/// non-zero exit, timeout, or spawn failure blocks the tool call and the
+/// hook's stderr is returned to the model.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HookRule {
+    /// "pre_tool", "post_tool", or "turn_end".
+    pub event: String,
+    /// Command to run, parsed shell-style like the other hooks.
+    pub command: String,
+    /// Tool-name globs (e.g. `["edit", "write", "mcp__*"]`). Empty matches
+    /// every tool. Ignored for `turn_end`.
+    #[serde(default)]
+    pub tools: Vec<String>,
+    /// Path globs matched against the files a tool call touches, relative to
+    /// the session working directory (e.g. `["*.rs"]`, `["generated/**"]`).
+    /// Patterns without a `/` match the file name. Empty matches any call.
+    /// Ignored for `turn_end`.
+    #[serde(default)]
+    pub paths: Vec<String>,
+    /// Max milliseconds to wait for the command (default: 10000).
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+}
+
+/// Deserialize `hooks` from either the `[hooks]` table or a bare `[[hooks]]`
+/// array of rules.
+pub fn deserialize_hooks_config<'de, D>(deserializer: D) -> Result<HooksConfig, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+
+    // Dispatch on the shape ourselves instead of using an untagged enum, so a
+    // typo inside a rule reports the real field error rather than "did not
+    // match any variant".
+    match serde_json::Value::deserialize(deserializer)? {
+        value @ serde_json::Value::Array(_) => {
+            let rules = Vec::<HookRule>::deserialize(value).map_err(D::Error::custom)?;
+            Ok(HooksConfig {
+                rules,
+                ..HooksConfig::default()
+            })
+        }
+        value => HooksConfig::deserialize(value).map_err(D::Error::custom),
+    }
 }
 
 impl Default for HooksConfig {
@@ -718,13 +812,14 @@ impl Default for HooksConfig {
             pre_tool: None,
             post_tool: None,
             pre_tool_timeout_ms: 5000,
+            rules: Vec::new(),
         }
     }
 }
 
 /// Automatic end-of-turn code review configuration.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct AutoReviewConfig {
     /// Enable autoreview by default for new/resumed sessions (default: false)
     pub enabled: bool,
@@ -734,7 +829,7 @@ pub struct AutoReviewConfig {
 
 /// Automatic end-of-turn execution judging configuration.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct AutoJudgeConfig {
     /// Enable autojudge by default for new/resumed sessions (default: false)
     pub enabled: bool,
@@ -742,9 +837,120 @@ pub struct AutoJudgeConfig {
     pub model: Option<String>,
 }
 
+/// Background diagnosis of repeated tool failures and failed turns.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct AutoDebugConfig {
+    /// Analyze a tool that keeps failing the same way and inject the diagnosis
+    /// into the next turn (default: true)
+    pub enabled: bool,
+    /// Failures of one tool with a similar error before an analysis runs (default: 3)
+    pub failure_threshold: u32,
+    /// Analyses per session, counting ones recorded before a resume (default: 3)
+    pub max_analyses_per_session: u32,
+    /// When a turn ends in an error, add a three-line post-mortem (attempted,
+    /// failed, next step) to the session as a system note (default: true)
+    pub post_mortems: bool,
+}
+
+impl Default for AutoDebugConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            failure_threshold: 3,
+            max_analyses_per_session: 3,
+            post_mortems: true,
+        }
+    }
+}
+
+/// Skill behaviour beyond explicit `/name` invocation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct SkillsConfig {
+    /// Load skills whose `triggers` match the session's files, messages, or
+    /// tool use (default: true)
+    pub auto_load: bool,
+    /// Skills auto-loaded at once (default: 3)
+    pub max_auto_loaded: usize,
+    /// Estimated tokens all auto-loaded skill instructions may use (default: 6000)
+    pub auto_load_token_budget: usize,
+}
+
+impl Default for SkillsConfig {
+    fn default() -> Self {
+        Self {
+            auto_load: true,
+            max_auto_loaded: 3,
+            auto_load_token_budget: 6000,
+        }
+    }
+}
+
+/// Where sessions and memories are persisted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    /// One JSON snapshot plus append journal per session under `sessions/`.
+    #[default]
+    Files,
+    /// A single `jcode.db` SQLite database with indexed sessions and messages.
+    Sqlite,
+}
+
+impl StorageBackend {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Files => "files",
+            Self::Sqlite => "sqlite",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "files" | "file" | "json" => Some(Self::Files),
+            "sqlite" | "sqlite3" | "db" => Some(Self::Sqlite),
+            _ => None,
+        }
+    }
+}
+
+/// Storage backend selection
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct StorageConfig {
+    /// "files" (default) or "sqlite"; switch with `jcode storage migrate`
+    pub backend: StorageBackend,
+}
+
+/// Telegram bot bridge (`jcode telegram`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TelegramConfig {
+    /// Bot token from @BotFather; falls back to `[safety] telegram_bot_token`
+    pub bot_token: Option<String>,
+    /// Telegram user IDs allowed to drive sessions; everyone else is ignored
+    pub allowed_user_ids: Vec<i64>,
+    /// Route every chat to this session instead of creating one per chat
+    pub session: Option<String>,
+    /// Minimum time between edits of a streaming reply (default: 1500)
+    pub edit_interval_ms: u64,
+}
+
+impl Default for TelegramConfig {
+    fn default() -> Self {
+        Self {
+            bot_token: None,
+            allowed_user_ids: Vec::new(),
+            session: None,
+            edit_interval_ms: 1500,
+        }
+    }
+}
+
 /// Keybinding configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct KeybindingsConfig {
     /// Scroll up key (default: "ctrl+k")
     pub scroll_up: String,
@@ -797,6 +1003,8 @@ pub struct KeybindingsConfig {
     pub diff_mode_cycle: String,
     /// Toggle the info widget (default: "alt+i")
     pub info_widget_toggle: String,
+    /// Collapse/expand the todo checklist above the input (default: "alt+x")
+    pub todo_checklist_toggle: String,
     /// Focus/unfocus the inline swarm panel for keyboard navigation (default:
     /// "alt+n"). Active only when `agents.swarm_spawn_mode = "inline"` and the
     /// session manages swarm agents.
@@ -804,9 +1012,47 @@ pub struct KeybindingsConfig {
     /// Spawn a fresh jcode session in a new terminal window (default: unbound).
     /// Example: "alt+enter".
     pub new_terminal: String,
+    /// While a turn is streaming, open an inline input whose text is injected
+    /// into the running turn as a soft interrupt (default: "alt+enter").
+    /// Set "" to disable.
+    pub interject: String,
     /// Open the `/resume` session picker (default: "cmd+b" on macOS, "alt+r"
     /// elsewhere). Set "" to disable.
     pub open_resume: String,
+    /// Open the focused inline image or diagram in the system image viewer
+    /// (default: "alt+o"). Set "" to disable.
+    pub image_open: String,
+    /// Save the focused assistant answer as a memory, like `/remember`
+    /// (default: "alt+p"). Set "" to disable.
+    pub remember_message: String,
+    /// Copy the focused assistant answer or tool output to the clipboard
+    /// through OSC 52, which reaches the local terminal over SSH and tmux
+    /// (default: "alt+q"). Set "" to disable.
+    pub copy_message: String,
+    /// Open the workspace file picker to attach files or insert `@mentions`
+    /// (default: "ctrl+f"). Alt+F and Ctrl+Right still move forward a word.
+    /// Set "" to disable and give Ctrl+F back to the composer.
+    pub file_picker: String,
+    /// Composer: move back one word (default: "alt+b,ctrl+left").
+    pub composer_word_back: String,
+    /// Composer: move forward one word (default: "alt+f,ctrl+right").
+    pub composer_word_forward: String,
+    /// Composer: kill to the end of the line into the kill ring (default: "ctrl+k").
+    pub composer_kill_line_end: String,
+    /// Composer: kill to the start of the line into the kill ring (default: "ctrl+u").
+    pub composer_kill_line_start: String,
+    /// Composer: kill the previous word into the kill ring (default: "ctrl+w").
+    pub composer_kill_word_back: String,
+    /// Composer: yank the most recent kill (default: "ctrl+y").
+    pub composer_yank: String,
+    /// Composer: replace the just-yanked text with an older kill (default:
+    /// unbound, since Alt+Y toggles copy mode).
+    pub composer_yank_pop: String,
+    /// Composer: undo the last input edit (default: "ctrl+_,ctrl+shift+-").
+    pub composer_undo: String,
+    /// Composer: transpose the characters around the cursor (default: unbound,
+    /// since Ctrl+T toggles queue mode).
+    pub composer_transpose: String,
     /// Session picker Enter action: "current-terminal" (default) or "new-terminal".
     /// Ctrl+Enter performs the alternate action.
     pub session_picker_enter: SessionPickerResumeAction,
@@ -847,8 +1093,10 @@ impl Default for KeybindingsConfig {
             typing_scroll_lock_toggle: get("typing_scroll_lock_toggle", "alt+s"),
             diff_mode_cycle: get("diff_mode_cycle", "alt+g"),
             info_widget_toggle: get("info_widget_toggle", "alt+i"),
+            todo_checklist_toggle: get("todo_checklist_toggle", "alt+x"),
             swarm_panel_focus: get("swarm_panel_focus", "alt+n"),
             new_terminal: get("new_terminal", ""),
+            interject: get("interject", "alt+enter"),
             open_resume: get(
                 "open_resume",
                 if cfg!(target_os = "macos") {
@@ -857,6 +1105,19 @@ impl Default for KeybindingsConfig {
                     "alt+r"
                 },
             ),
+            image_open: get("image_open", "alt+o"),
+            remember_message: get("remember_message", "alt+p"),
+            copy_message: get("copy_message", "alt+q"),
+            file_picker: get("file_picker", "ctrl+f"),
+            composer_word_back: get("composer_word_back", "alt+b,ctrl+left"),
+            composer_word_forward: get("composer_word_forward", "alt+f,ctrl+right"),
+            composer_kill_line_end: get("composer_kill_line_end", "ctrl+k"),
+            composer_kill_line_start: get("composer_kill_line_start", "ctrl+u"),
+            composer_kill_word_back: get("composer_kill_word_back", "ctrl+w"),
+            composer_yank: get("composer_yank", "ctrl+y"),
+            composer_yank_pop: get("composer_yank_pop", ""),
+            composer_undo: get("composer_undo", "ctrl+_,ctrl+shift+-"),
+            composer_transpose: get("composer_transpose", ""),
             session_picker_enter: SessionPickerResumeAction::CurrentTerminal,
         }
     }
@@ -865,7 +1126,7 @@ impl Default for KeybindingsConfig {
 /// How to display file diffs from edit/write tools
 /// Display/UI configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct NativeScrollbarConfig {
     /// Show a native terminal scrollbar in the chat viewport (default: true)
     pub chat: bool,
@@ -888,7 +1149,7 @@ fn default_true() -> bool {
 
 /// Display/UI configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct DisplayConfig {
     /// How to display file diffs (off/inline/full-inline/pinned/file, default: inline)
     pub diff_mode: DiffDisplayMode,
@@ -899,6 +1160,14 @@ pub struct DisplayConfig {
     pub queue_mode: bool,
     /// Automatically reload the remote server when a newer server binary is detected (default: true)
     pub auto_server_reload: bool,
+    /// Seconds of warning before a session migrates to a newer binary, so it can be deferred (default: 300)
+    pub migration_grace_secs: u64,
+    /// Terminal title layout. Placeholders: {state} (● streaming, ◐ tool
+    /// running, ✋ approval pending, ✓ idle), {state_label}, {icon}, {server},
+    /// {session}, {suffix} (default: "{state} {icon} {server} {session}{suffix}")
+    pub title_format: String,
+    /// Also write the title to ~/.jcode/status/<session> for tmux status lines (default: false)
+    pub tmux_status: bool,
     /// Capture mouse events (default: true). Enables scroll wheel but disables terminal selection.
     pub mouse_capture: bool,
     /// Enable debug socket for external control (default: false)
@@ -926,6 +1195,9 @@ pub struct DisplayConfig {
     pub disabled_animations: Vec<String>,
     /// Wrap long lines in the pinned diff pane (default: true)
     pub diff_line_wrap: bool,
+    /// Width of the right-hand side pane (file view, pinned diffs, side
+    /// panel) as a percentage of the chat area: 25-100 (default: 40)
+    pub side_pane_ratio: u8,
     /// Performance tier override: auto/full/reduced/minimal (default: auto)
     pub performance: String,
     /// FPS for animations (startup, idle donut): 1-120 (default: 60)
@@ -943,6 +1215,10 @@ pub struct DisplayConfig {
     /// just the one-line summary (default: false)
     #[serde(default)]
     pub show_agentgrep_output: bool,
+    /// Show a dim model/timing/token footer under assistant messages restored
+    /// from session history (default: true)
+    #[serde(default = "default_true")]
+    pub message_footers: bool,
     /// Native terminal scrollbar configuration for scrollable panes
     pub native_scrollbars: NativeScrollbarConfig,
     /// Surface occasional "learn this keybinding" nudges when the user keeps
@@ -960,6 +1236,9 @@ impl Default for DisplayConfig {
             pin_images: true,
             queue_mode: false,
             auto_server_reload: true,
+            migration_grace_secs: 300,
+            title_format: "{state} {icon} {server} {session}{suffix}".to_string(),
+            tmux_status: false,
             mouse_capture: true,
             debug_socket: false,
             centered: false,
@@ -971,6 +1250,7 @@ impl Default for DisplayConfig {
             prompt_entry_animation: true,
             disabled_animations: Vec::new(),
             diff_line_wrap: true,
+            side_pane_ratio: 40,
             performance: String::new(),
             animation_fps: 60,
             redraw_fps: 60,
@@ -978,6 +1258,7 @@ impl Default for DisplayConfig {
             compact_notifications: false,
             copy_badge_alt_label: String::new(),
             show_agentgrep_output: false,
+            message_footers: true,
             native_scrollbars: NativeScrollbarConfig::default(),
             keybinding_hints: true,
         }
@@ -1022,7 +1303,7 @@ impl DisplayConfig {
 
 /// Runtime feature toggles
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct FeatureConfig {
     /// Enable memory retrieval/extraction features (default: true)
     pub memory: bool,
@@ -1093,7 +1374,7 @@ impl WebSearchEngine {
 
 /// Configuration for the websearch tool.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct WebSearchConfig {
     /// Preferred engine when the tool input does not specify one.
     pub engine: WebSearchEngine,
@@ -1128,7 +1409,7 @@ impl Default for WebSearchConfig {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct ProviderConfig {
     /// Default model to use (e.g. "claude-opus-4-8", "copilot:claude-opus-4.6")
     pub default_model: Option<String>,
@@ -1142,6 +1423,16 @@ pub struct ProviderConfig {
     pub openai_transport: Option<String>,
     /// OpenAI service tier override (priority|flex)
     pub openai_service_tier: Option<String>,
+    /// Run OpenAI API-key requests in Responses background mode so a dropped
+    /// HTTPS stream resumes from its last event instead of regenerating the
+    /// response. Stores responses server-side. Default: false. Overridable
+    /// via `JCODE_OPENAI_RESUMABLE_STREAMS`.
+    pub openai_resumable_streams: bool,
+    /// Switch a turn to background mode after its first reconnect, and keep
+    /// later turns on it, even when `openai_resumable_streams` is off. Stores
+    /// those responses server-side. Default: true. Overridable via
+    /// `JCODE_OPENAI_BACKGROUND_AFTER_RECONNECT`.
+    pub openai_background_after_reconnect: bool,
     /// OpenAI native compaction mode: "auto", "explicit", or "off".
     pub openai_native_compaction_mode: String,
     /// Token threshold at which OpenAI auto native compaction should trigger.
@@ -1153,6 +1444,8 @@ pub struct ProviderConfig {
     /// Whether jcode should automatically try another account on the same provider
     /// before falling back to a different provider.
     pub same_provider_account_failover: bool,
+    /// How the agent uses tools when the active model has no native tool calling.
+    pub tool_fallback: ToolFallbackMode,
     /// Copilot premium request mode: "normal", "one", or "zero"
     /// "zero" means all requests are free (no premium requests consumed)
     pub copilot_premium: Option<String>,
@@ -1161,6 +1454,26 @@ pub struct ProviderConfig {
     /// that think silently for minutes before emitting tokens. Default: 180.
     /// Overridable per-launch via `JCODE_STREAM_IDLE_TIMEOUT_SECS`.
     pub stream_idle_timeout_secs: u64,
+    /// Max seconds between streamed chunks once a response has started before
+    /// the stream is treated as stalled and retried. 0 disables the check.
+    /// Default: 90. Overridable via `JCODE_PROVIDER_IDLE_TIMEOUT_SECS`.
+    pub idle_timeout_secs: u64,
+    /// Longest provider rate-limit wait (from `retry-after` / reset headers)
+    /// the agent sleeps through before retrying the same request on its own.
+    /// Longer waits surface as an error with a client-side retry. 0 disables
+    /// the in-turn wait. Default: 90.
+    pub rate_limit_max_wait_secs: u64,
+    /// Pin sampling for reproducible runs: temperature 0 / top_p 1 where the
+    /// API accepts them, a fixed seed, and a dated model snapshot instead of
+    /// an alias. Parts a provider cannot honor are logged as warnings.
+    /// Overridable via `JCODE_DETERMINISTIC` or `jcode run --deterministic`.
+    pub deterministic: bool,
+    /// Seed sent in deterministic mode to APIs that accept one. Default: 0.
+    pub deterministic_seed: u64,
+    /// Anthropic-specific settings (`[provider.anthropic]`).
+    pub anthropic: AnthropicProviderConfig,
+    /// Copilot-specific settings (`[provider.copilot]`).
+    pub copilot: CopilotProviderConfig,
 }
 
 impl Default for ProviderConfig {
@@ -1172,20 +1485,69 @@ impl Default for ProviderConfig {
             anthropic_reasoning_effort: None,
             openai_transport: None,
             openai_service_tier: Some("priority".to_string()),
+            openai_resumable_streams: false,
+            openai_background_after_reconnect: true,
             openai_native_compaction_mode: "auto".to_string(),
             openai_native_compaction_threshold_tokens: 200_000,
             preserve_reasoning_context: true,
             cross_provider_failover: CrossProviderFailoverMode::Countdown,
             same_provider_account_failover: true,
+            tool_fallback: ToolFallbackMode::Textual,
             copilot_premium: None,
             stream_idle_timeout_secs: 180,
+            idle_timeout_secs: 90,
+            rate_limit_max_wait_secs: 90,
+            deterministic: false,
+            deterministic_seed: 0,
+            anthropic: AnthropicProviderConfig::default(),
+            copilot: CopilotProviderConfig::default(),
+        }
+    }
+}
+
+/// GitHub Copilot settings (`[provider.copilot]`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CopilotProviderConfig {
+    /// Monthly cap on premium requests. At 80% jcode conserves premium
+    /// requests; at 100% premium models are refused.
+    pub monthly_premium_budget: Option<u64>,
+}
+
+/// Anthropic Messages API settings (`[provider.anthropic]`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AnthropicProviderConfig {
+    /// Server-side tools Anthropic runs itself, e.g. `["web_search"]`.
+    /// Only requested for models that support them.
+    pub server_tools: Vec<AnthropicServerTool>,
+}
+
+/// A server-side tool executed by the Anthropic API rather than by jcode.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AnthropicServerTool {
+    WebSearch,
+}
+
+impl AnthropicServerTool {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::WebSearch => "web_search",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "web_search" | "websearch" => Some(Self::WebSearch),
+            _ => None,
         }
     }
 }
 
 /// Ambient mode configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct AmbientConfig {
     /// Enable ambient mode (default: false)
     pub enabled: bool,
@@ -1203,6 +1565,15 @@ pub struct AmbientConfig {
     pub max_interval_minutes: u32,
     /// Pause ambient when user has active session (default: true)
     pub pause_on_active_session: bool,
+    /// Skip cycles while interactive use consumed more than this many
+    /// percentage points of a subscription window in the past hour, and
+    /// shrink them above half of it (default: 10)
+    pub interactive_usage_threshold_percent: f32,
+    /// Never let ambient cycles push a subscription window past this
+    /// percentage (default: 70)
+    pub usage_reserve_percent: f32,
+    /// Defer cycles into hours that were historically quiet (default: true)
+    pub prefer_quiet_hours: bool,
     /// Enable proactive work vs garden-only (default: true)
     pub proactive_work: bool,
     /// Proactive work branch prefix (default: "ambient/")
@@ -1222,6 +1593,9 @@ impl Default for AmbientConfig {
             min_interval_minutes: 5,
             max_interval_minutes: 120,
             pause_on_active_session: true,
+            interactive_usage_threshold_percent: 10.0,
+            usage_reserve_percent: 70.0,
+            prefer_quiet_hours: true,
             proactive_work: true,
             work_branch_prefix: "ambient/".to_string(),
             visible: true,
@@ -1235,7 +1609,7 @@ impl Default for AmbientConfig {
 /// section controls lightweight local desktop notifications for the normal
 /// interactive TUI, e.g. "agent finished a long turn".
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct NotificationsConfig {
     /// Send a desktop notification when an agent turn completes (default: true).
     /// Notifications fire only for long turns (see thresholds below) and, by
@@ -1254,6 +1628,13 @@ pub struct NotificationsConfig {
     /// (e.g. "Glass", "Ping", "Hero"). Empty string disables the sound.
     /// Ignored on non-macOS platforms. Default: "Glass".
     pub turn_complete_sound: String,
+    /// Backends each event type goes to, keyed by event (`turn_complete`,
+    /// `approval_needed`, `cycle_summary`) with backend names as values
+    /// (`desktop`, `ntfy`, `email`, `telegram`, `discord`, `jade_relay`, or a
+    /// webhook name). Events without an entry keep the built-in routing.
+    pub routes: std::collections::BTreeMap<String, Vec<String>>,
+    /// Generic webhooks that receive a templated JSON POST
+    pub webhooks: Vec<WebhookConfig>,
 }
 
 impl Default for NotificationsConfig {
@@ -1264,18 +1645,50 @@ impl Default for NotificationsConfig {
             turn_complete_todo_min_secs: 30,
             turn_complete_only_when_unfocused: true,
             turn_complete_sound: "Glass".to_string(),
+            routes: std::collections::BTreeMap::new(),
+            webhooks: Vec::new(),
+        }
+    }
+}
+
+/// A notification webhook (`[[notifications.webhooks]]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WebhookConfig {
+    /// Backend name used in `routes` (default: "webhook")
+    pub name: String,
+    /// URL the payload is POSTed to
+    pub url: String,
+    /// JSON payload template. `{{event}}`, `{{title}}`, `{{body}}`,
+    /// `{{details}}`, and `{{priority}}` are replaced with JSON-escaped text.
+    /// Empty uses a flat object with those fields.
+    pub template: String,
+    /// Extra request headers, e.g. `Authorization`
+    pub headers: std::collections::BTreeMap<String, String>,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            name: "webhook".to_string(),
+            url: String::new(),
+            template: String::new(),
+            headers: std::collections::BTreeMap::new(),
         }
     }
 }
 
 /// Safety system & notification configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct SafetyConfig {
     /// ntfy.sh topic name (required for push notifications)
     pub ntfy_topic: Option<String>,
     /// ntfy.sh server URL (default: https://ntfy.sh)
     pub ntfy_server: String,
+    /// ntfy access token or full `Authorization` header value, for protected
+    /// topics on self-hosted servers (prefer JCODE_NTFY_TOKEN env var)
+    pub ntfy_token: Option<String>,
     /// Enable desktop notifications via notify-send (default: true)
     pub desktop_notifications: bool,
     /// Enable email notifications (default: false)
@@ -1332,6 +1745,9 @@ pub struct SafetyConfig {
     pub jade_relay_launch_enabled: bool,
     /// Default working directory for remotely launched headed sessions
     pub jade_relay_launch_working_dir: Option<String>,
+    /// Seconds an `ask_user` question waits for an answer when no client is
+    /// attached before the agent proceeds on its own (default: 300)
+    pub ask_user_timeout_secs: u64,
 }
 
 impl Default for SafetyConfig {
@@ -1339,6 +1755,7 @@ impl Default for SafetyConfig {
         Self {
             ntfy_topic: None,
             ntfy_server: "https://ntfy.sh".to_string(),
+            ntfy_token: None,
             desktop_notifications: true,
             email_enabled: false,
             email_to: None,
@@ -1367,13 +1784,14 @@ impl Default for SafetyConfig {
             jade_relay_reply_enabled: false,
             jade_relay_launch_enabled: false,
             jade_relay_launch_working_dir: None,
+            ask_user_timeout_secs: 300,
         }
     }
 }
 
 /// WebSocket gateway configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct GatewayConfig {
     /// Enable the WebSocket gateway (default: false)
     pub enabled: bool,
@@ -1393,9 +1811,18 @@ impl Default for GatewayConfig {
     }
 }
 
+/// Server daemon configuration (`[server]`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    /// Address for the Prometheus-style metrics listener, e.g.
+    /// `"127.0.0.1:9187"`. Unset (default) serves no metrics.
+    pub metrics_listen: Option<String>,
+}
+
 /// Power-management configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct PowerConfig {
     /// Prevent the machine from going to sleep (idle/lid suspend) while any
     /// jcode session is actively streaming/processing. The display is still
@@ -1414,6 +1841,522 @@ impl Default for PowerConfig {
     }
 }
 
+/// Limits on the agent loop. Unset (or 0) means no limit.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct AgentLimitsConfig {
+    /// Maximum model calls (agent loop iterations) for one request.
+    pub max_turns: Option<u32>,
+    /// Maximum tool calls executed from one model reply.
+    pub max_tool_calls_per_turn: Option<u32>,
+    /// Maximum wall-clock seconds for one request.
+    pub max_wall_seconds: Option<u64>,
+    /// Maximum subagent tasks run at once from one `subagent` call with a
+    /// `tasks` list. Unset (or 0) uses [`DEFAULT_MAX_PARALLEL_SUBAGENTS`].
+    pub max_parallel_subagents: Option<usize>,
+}
+
+pub const DEFAULT_MAX_PARALLEL_SUBAGENTS: usize = 4;
+
+impl AgentLimitsConfig {
+    pub fn max_parallel_subagents(&self) -> usize {
+        self.max_parallel_subagents
+            .filter(|max| *max > 0)
+            .unwrap_or(DEFAULT_MAX_PARALLEL_SUBAGENTS)
+    }
+}
+
+/// A named agent preset from `[profiles.<name>]`, selected with
+/// `jcode --profile <name>`, `/profile <name>`, or a subagent task's `profile`.
+/// Unset fields keep the session's current setting.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(default)]
+pub struct AgentProfileConfig {
+    /// Provider route prefix for `model`, e.g. `"claude-api"` or `"openrouter"`.
+    pub provider: Option<String>,
+    /// Model to switch to when the profile is selected.
+    pub model: Option<String>,
+    /// Tool allow-list, same rules as `[tools] enabled`. Empty keeps `[tools]`.
+    pub tools: Vec<String>,
+    /// Tools to hide on top of `[tools] disabled`.
+    pub disabled_tools: Vec<String>,
+    /// Instruction file appended to the system prompt. Relative paths resolve
+    /// against the session working directory.
+    pub system_prompt_file: Option<String>,
+    /// `"plan"` starts in plan mode so tool use waits for an approved plan;
+    /// `"auto"` leaves plan mode.
+    pub approval: Option<String>,
+    /// Overrides `[agent] max_turns`.
+    pub max_turns: Option<u32>,
+    /// Overrides `[agent] max_tool_calls_per_turn`.
+    pub max_tool_calls_per_turn: Option<u32>,
+    /// Overrides `[agent] max_wall_seconds`.
+    pub max_wall_seconds: Option<u64>,
+    /// Keys not recognized above, reported by [`AgentProfileConfig::validate`].
+    #[serde(flatten, skip_serializing)]
+    pub unknown_keys: std::collections::BTreeMap<String, serde_json::Value>,
+}
+
+impl AgentProfileConfig {
+    pub const APPROVAL_MODES: &'static [&'static str] = &["auto", "plan"];
+
+    /// Problems with this profile, each prefixed with `[profiles.<name>]`.
+    pub fn validate(&self, name: &str) -> Vec<String> {
+        let mut errors: Vec<String> = self
+            .unknown_keys
+            .keys()
+            .map(|key| format!("[profiles.{name}] unknown key `{key}`"))
+            .collect();
+        if self.provider.is_some() && self.model.is_none() {
+            errors.push(format!("[profiles.{name}] `provider` requires `model`"));
+        }
+        if let Some(approval) = self.approval.as_deref()
+            && !Self::APPROVAL_MODES.contains(&approval)
+        {
+            errors.push(format!(
+                "[profiles.{name}] `approval` must be one of {}, got `{approval}`",
+                Self::APPROVAL_MODES.join(", ")
+            ));
+        }
+        errors
+    }
+
+    /// The model switch request for this profile, e.g. `claude-api:claude-opus-4-6`.
+    pub fn model_request(&self) -> Option<String> {
+        let model = self.model.as_deref()?.trim();
+        match self.provider.as_deref().map(str::trim) {
+            Some(provider) if !provider.is_empty() => Some(format!("{provider}:{model}")),
+            _ => Some(model.to_string()),
+        }
+    }
+
+    /// Whether the profile asks for plan mode (`Some(true)`), asks to leave it
+    /// (`Some(false)`), or leaves it alone (`None`).
+    pub fn plan_mode(&self) -> Option<bool> {
+        self.approval.as_deref().map(|approval| approval == "plan")
+    }
+
+    /// `[agent]` limits with this profile's overrides applied.
+    pub fn apply_limits(&self, base: &AgentLimitsConfig) -> AgentLimitsConfig {
+        AgentLimitsConfig {
+            max_turns: self.max_turns.or(base.max_turns),
+            max_tool_calls_per_turn: self
+                .max_tool_calls_per_turn
+                .or(base.max_tool_calls_per_turn),
+            max_wall_seconds: self.max_wall_seconds.or(base.max_wall_seconds),
+            ..base.clone()
+        }
+    }
+}
+
+/// Project todo configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct TodoConfig {
+    /// Markdown file, relative to the project root, that mirrors the project
+    /// todos in a fenced section (e.g. `TODO.md`). Edits to that section are
+    /// read back at session start and turn boundaries. Off when unset.
+    pub sync_file: Option<String>,
+}
+
+/// Per-turn git commits from `[git]`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct GitConfig {
+    /// Commit the files the agent's tools modified at the end of every turn.
+    /// `/autocommit` overrides it per session.
+    pub auto_commit: bool,
+    /// Branch that receives the commits. Created from `HEAD` when missing and
+    /// advanced without checking it out. Unset commits onto the current branch.
+    pub auto_commit_branch: Option<String>,
+    /// Record which tool call wrote which lines of each file in
+    /// `.jcode/provenance.jsonl`, for `jcode blame`.
+    pub provenance: bool,
+}
+
+/// Memory injection limits and ranking from `[memory]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MemoryConfig {
+    /// Approximate token cap for the memories injected into one request.
+    pub injection_token_budget: usize,
+    /// Most memories injected into one request.
+    pub injection_max_entries: usize,
+    /// Age (days) at which a memory's recency score halves.
+    pub recency_half_life_days: f32,
+    /// Weights of the ranking components.
+    pub weights: MemoryRankWeights,
+    /// Category score per memory category (`correction`, `preference`,
+    /// `fact`, `entity`, or a custom name). Unlisted categories score 0.5.
+    pub category_weights: std::collections::BTreeMap<String, f32>,
+}
+
+impl Default for MemoryConfig {
+    fn default() -> Self {
+        Self {
+            injection_token_budget: 600,
+            injection_max_entries: 5,
+            recency_half_life_days: 30.0,
+            weights: MemoryRankWeights::default(),
+            category_weights: [
+                ("correction", 1.0),
+                ("preference", 0.8),
+                ("fact", 0.6),
+                ("entity", 0.5),
+            ]
+            .into_iter()
+            .map(|(name, weight)| (name.to_string(), weight))
+            .collect(),
+        }
+    }
+}
+
+/// Relative weights of the memory ranking score. Each component is in 0..=1
+/// and the score is their weighted mean.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MemoryRankWeights {
+    /// Embedding similarity to the current user message.
+    pub similarity: f32,
+    /// How recently the memory was written or reinforced.
+    pub recency: f32,
+    /// How often the memory has been reinforced.
+    pub strength: f32,
+    /// The category score from `category_weights`.
+    pub category: f32,
+}
+
+impl Default for MemoryRankWeights {
+    fn default() -> Self {
+        Self {
+            similarity: 0.6,
+            recency: 0.15,
+            strength: 0.1,
+            category: 0.15,
+        }
+    }
+}
+
+/// Copies of assistant output from `[output]`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct OutputConfig {
+    /// File that the final assistant message of every turn is appended to,
+    /// with a timestamp and session header. `~/` expands to the home
+    /// directory. Off when unset; `/tee` overrides it per session.
+    pub tee_file: Option<String>,
+}
+
+/// Outbound redaction from `[privacy]`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct PrivacyConfig {
+    /// Replace emails, IP addresses, AWS keys, JWTs and known secret formats
+    /// in user messages and tool results with placeholders such as
+    /// `<EMAIL_1>` before they are sent to the provider. Originals stay in a
+    /// local per-session mapping.
+    pub redact: bool,
+    /// Extra regexes redacted as `<CUSTOM_n>`. When a pattern has a capture
+    /// group, only the first group is replaced.
+    pub redact_patterns: Vec<String>,
+}
+
+/// Static system prompt assembly from `[prompt]`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct PromptConfig {
+    /// Layers of the static system prompt, in order. `base` is the built-in
+    /// prompt, `global` is `~/.jcode/JCODE.md`, `project` is the first
+    /// instruction file found walking up from the working directory, and
+    /// `nested` adds instruction files from subdirectories the agent works in.
+    /// Leave a name out to skip that layer.
+    pub sources: Vec<String>,
+    /// Instruction file names checked in each directory, in priority order.
+    pub project_files: Vec<String>,
+    /// Cap on the combined size of instruction-file layers, in chars. Layers
+    /// past the cap are truncated or dropped, with a warning.
+    pub max_instruction_chars: usize,
+    /// Add a git snapshot (branch, ahead/behind, dirty files, recent commits)
+    /// to the dynamic part of the prompt, taken once per turn. A `place` in
+    /// `[prompt.sections] git` overrides this.
+    pub git_context: bool,
+    /// Describe the machine (OS, shell, CPUs, memory, tool versions) in the
+    /// static prompt. Probed once per server start or `/env refresh`. A
+    /// `place` in `[prompt.sections] environment` overrides this.
+    pub environment: bool,
+    /// Tools whose versions the environment description lists. A bare name is
+    /// probed with `--version`; an entry with arguments (`go version`) runs
+    /// as written.
+    pub environment_tools: Vec<String>,
+    /// Which half of the split prompt each optional section goes in, and how
+    /// large it may get.
+    pub sections: PromptSectionsConfig,
+}
+
+/// Where a `[prompt.sections]` entry goes. The static half is the cached
+/// prefix, so anything placed there rewrites the cache whenever it changes.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum PromptPlacement {
+    Static,
+    Dynamic,
+    Off,
+}
+
+impl PromptPlacement {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Static => "static",
+            Self::Dynamic => "dynamic",
+            Self::Off => "off",
+        }
+    }
+}
+
+/// One optional system prompt section.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct PromptSectionConfig {
+    /// `static`, `dynamic` or `off`. Unset keeps the section's default.
+    pub place: Option<PromptPlacement>,
+    /// Cap on the section's size in estimated tokens (4 chars each). Longer
+    /// text is truncated with a note.
+    pub max_tokens: Option<usize>,
+}
+
+/// `[prompt.sections]`: placement and budgets for the optional sections.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct PromptSectionsConfig {
+    /// Today's date. Off by default.
+    pub date: PromptSectionConfig,
+    /// The `[prompt] git_context` snapshot. Dynamic when `git_context` is on.
+    pub git: PromptSectionConfig,
+    /// Recalled memories. Dynamic by default.
+    pub memory: PromptSectionConfig,
+    /// Open project todos. Dynamic by default.
+    pub todos: PromptSectionConfig,
+    /// The machine description. Static when `environment` is on.
+    pub environment: PromptSectionConfig,
+    /// Tokens the session has used so far. Off by default.
+    pub usage: PromptSectionConfig,
+}
+
+/// The sections `[prompt.sections]` places.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PromptSection {
+    Date,
+    Git,
+    Memory,
+    Todos,
+    Environment,
+    Usage,
+}
+
+impl PromptSection {
+    pub const ALL: [Self; 6] = [
+        Self::Date,
+        Self::Git,
+        Self::Memory,
+        Self::Todos,
+        Self::Environment,
+        Self::Usage,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Date => "date",
+            Self::Git => "git",
+            Self::Memory => "memory",
+            Self::Todos => "todos",
+            Self::Environment => "environment",
+            Self::Usage => "usage",
+        }
+    }
+
+    /// Whether the section's text can change from one turn to the next.
+    /// The date changes once a day and the environment once per probe.
+    pub fn changes_per_turn(self) -> bool {
+        matches!(self, Self::Git | Self::Memory | Self::Todos | Self::Usage)
+    }
+}
+
+pub const PROMPT_SOURCES: &[&str] = &["base", "global", "project", "nested"];
+
+pub const DEFAULT_ENVIRONMENT_TOOLS: &[&str] = &[
+    "git", "rg", "fd", "node", "npm", "python3", "cargo", "rustc", "make", "docker",
+];
+
+impl Default for PromptConfig {
+    fn default() -> Self {
+        Self {
+            sources: PROMPT_SOURCES.iter().map(|s| s.to_string()).collect(),
+            project_files: ["JCODE.md", "AGENTS.md", "CLAUDE.md"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            max_instruction_chars: 100_000,
+            git_context: false,
+            environment: true,
+            environment_tools: DEFAULT_ENVIRONMENT_TOOLS
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            sections: PromptSectionsConfig::default(),
+        }
+    }
+}
+
+impl PromptConfig {
+    pub fn section(&self, section: PromptSection) -> &PromptSectionConfig {
+        match section {
+            PromptSection::Date => &self.sections.date,
+            PromptSection::Git => &self.sections.git,
+            PromptSection::Memory => &self.sections.memory,
+            PromptSection::Todos => &self.sections.todos,
+            PromptSection::Environment => &self.sections.environment,
+            PromptSection::Usage => &self.sections.usage,
+        }
+    }
+
+    /// Where `section` goes: its `place`, or the default that keeps the
+    /// older `git_context` and `environment` switches working.
+    pub fn placement(&self, section: PromptSection) -> PromptPlacement {
+        if let Some(place) = self.section(section).place {
+            return place;
+        }
+        match section {
+            PromptSection::Date | PromptSection::Usage => PromptPlacement::Off,
+            PromptSection::Git if self.git_context => PromptPlacement::Dynamic,
+            PromptSection::Git => PromptPlacement::Off,
+            PromptSection::Memory | PromptSection::Todos => PromptPlacement::Dynamic,
+            PromptSection::Environment if self.environment => PromptPlacement::Static,
+            PromptSection::Environment => PromptPlacement::Off,
+        }
+    }
+
+    pub fn section_enabled(&self, section: PromptSection) -> bool {
+        self.placement(section) != PromptPlacement::Off
+    }
+
+    /// Placements that defeat prompt caching: a section that changes every
+    /// turn in the static half rewrites the cached prefix on each request.
+    pub fn section_warnings(&self) -> Vec<String> {
+        PromptSection::ALL
+            .into_iter()
+            .filter(|section| {
+                section.changes_per_turn() && self.placement(*section) == PromptPlacement::Static
+            })
+            .map(|section| {
+                format!(
+                    "[prompt.sections] {} changes every turn, so placing it in static rewrites \
+                     the cached prompt prefix on each request; use `dynamic` instead",
+                    section.as_str()
+                )
+            })
+            .collect()
+    }
+}
+
+/// Self-dev `/rebuild` test gate and `jcode promote` canary gate from
+/// `[rebuild]`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct RebuildConfig {
+    /// Run only the tests affected by changes since the last promoted build,
+    /// falling back to the full suite when the mapping is uncertain. Disable to
+    /// always run everything (same as `/rebuild --full-tests`).
+    pub selective_tests: bool,
+    /// Packages tested on every selective run. Unset keeps the built-in smoke
+    /// set.
+    pub smoke_packages: Option<Vec<String>>,
+    /// Extra paths that force the full suite when changed.
+    pub full_suite_paths: Vec<String>,
+    /// Extra paths whose changes never need tests.
+    pub ignored_paths: Vec<String>,
+    /// Path pattern → packages to test when it changes. Checked before the
+    /// built-in rules, so it can also narrow a path that would otherwise force
+    /// the full suite.
+    pub test_map: std::collections::BTreeMap<String, Vec<String>>,
+    /// Clean session-hours the canary must log before `jcode promote` accepts
+    /// it without `--force`.
+    pub promote_min_clean_hours: f64,
+}
+
+impl Default for RebuildConfig {
+    fn default() -> Self {
+        Self {
+            selective_tests: true,
+            smoke_packages: None,
+            full_suite_paths: Vec::new(),
+            ignored_paths: Vec::new(),
+            test_map: std::collections::BTreeMap::new(),
+            promote_min_clean_hours: 1.0,
+        }
+    }
+}
+
+/// Release-based updates from `[update]`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct UpdateConfig {
+    /// Channel for `jcode update` and background update checks: "stable"
+    /// (tagged releases) or "nightly" (latest prerelease). Unset falls back to
+    /// `[features] update_channel`.
+    pub channel: Option<UpdateChannel>,
+}
+
+/// What the egress policy does with hosts missing from `[network] allowed_hosts`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NetworkMode {
+    /// Deny the connection and log it.
+    Enforce,
+    /// Log and count the connection but let it through.
+    Observe,
+}
+
+impl NetworkMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Enforce => "enforce",
+            Self::Observe => "observe",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "enforce" | "deny" => Some(Self::Enforce),
+            "observe" | "log" => Some(Self::Observe),
+            _ => None,
+        }
+    }
+}
+
+/// Outbound network destinations from `[network]`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct NetworkConfig {
+    /// Enforce or only observe the allowlist. Unset enforces once
+    /// `allowed_hosts` is set and only observes while it uses the defaults.
+    pub mode: Option<NetworkMode>,
+    /// Hosts jcode may connect to: exact names, `*.example.com` for a domain
+    /// and its subdomains, or `*` for anything. Empty uses the defaults: the
+    /// configured providers, MCP endpoints, notification targets, and the
+    /// services jcode itself talks to. Loopback is always allowed.
+    pub allowed_hosts: Vec<String>,
+}
+
+impl NetworkConfig {
+    pub fn effective_mode(&self) -> NetworkMode {
+        self.mode.unwrap_or(if self.allowed_hosts.is_empty() {
+            NetworkMode::Observe
+        } else {
+            NetworkMode::Enforce
+        })
+    }
+}
+
 /// A single global launch hotkey: a chord plus the directory it opens jcode in.
 ///
 /// `dir` is usually an absolute path, but a few sentinels keep dynamic targets
@@ -1447,7 +2390,7 @@ pub struct LaunchHotkeyEntry {
 /// `Cmd+'`, and the next repos on `Cmd+[` / `Cmd+]` / `Cmd+\`. Once baked the
 /// mapping is static and does not move around as the user's activity changes.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct LaunchHotkeysConfig {
     /// Whether the global launch hotkeys are installed at all. `None` means
     /// "not decided yet" (fall back to the legacy auto-install gating); `Some`
@@ -266,6 +266,12 @@ pub(crate) fn run_headless_chat_smoke(message: String) -> Result<()> {
                     serde_json::json!({"event": "system_notice", "title": title, "message": message})
                 );
             }
+            session_launch::DesktopSessionEvent::PermissionInbox { entries } => {
+                println!(
+                    "{}",
+                    serde_json::json!({"event": "permission_inbox", "pending": entries.len()})
+                );
+            }
             session_launch::DesktopSessionEvent::SessionCloseRequested { reason } => {
                 anyhow::bail!(
                     "desktop chat smoke session close requested; session_id={}; reason={}",
@@ -28,6 +28,12 @@ fn env_flag_text_enabled(value: &str) -> bool {
     )
 }
 
+/// Footers under restored assistant messages follow the same
+/// `JCODE_MESSAGE_FOOTERS` switch as the TUI and default to on.
+pub(crate) fn desktop_message_footers_enabled() -> bool {
+    std::env::var_os("JCODE_MESSAGE_FOOTERS").is_none_or(env_flag_enabled)
+}
+
 pub(crate) fn desktop_frame_profile_mode() -> Option<String> {
     std::env::var("JCODE_DESKTOP_FRAME_PROFILE").ok()
 }
@@ -214,6 +214,7 @@ pub(crate) fn desktop_session_event_type_name(
         session_launch::DesktopSessionEvent::SessionCloseRequested { .. } => {
             "session_close_requested"
         }
+        session_launch::DesktopSessionEvent::PermissionInbox { .. } => "permission_inbox",
         session_launch::DesktopSessionEvent::Reloading { .. } => "reloading",
         session_launch::DesktopSessionEvent::Reloaded { .. } => "reloaded",
         session_launch::DesktopSessionEvent::Done => "done",
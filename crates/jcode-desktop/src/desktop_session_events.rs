@@ -225,6 +225,16 @@ fn desktop_session_event_payload_bytes(event: &session_launch::DesktopSessionEve
             title.len() + message.as_deref().unwrap_or_default().len()
         }
         session_launch::DesktopSessionEvent::SessionCloseRequested { reason } => reason.len(),
+        session_launch::DesktopSessionEvent::PermissionInbox { entries } => entries
+            .iter()
+            .map(|entry| {
+                entry.request_id.len()
+                    + entry.action.len()
+                    + entry.description.len()
+                    + entry.origin.len()
+                    + 16
+            })
+            .sum(),
         session_launch::DesktopSessionEvent::Done => 0,
     }
 }
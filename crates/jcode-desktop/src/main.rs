@@ -1166,6 +1166,25 @@ async fn run() -> Result<()> {
                             window.set_title(&app.status_title());
                             window.request_redraw();
                         }
+                        KeyOutcome::ResolvePermission {
+                            request_id,
+                            approved,
+                        } => {
+                            if app
+                                .send_single_session_permission_decision(request_id.clone(), approved)
+                                .is_err()
+                                && let Err(error) = session_launch::spawn_permission_decision(
+                                    request_id,
+                                    approved,
+                                    app.single_session_live_id(),
+                                    session_event_tx.clone(),
+                                )
+                            {
+                                apply_single_session_error(&mut app, error);
+                            }
+                            window.set_title(&app.status_title());
+                            window.request_redraw();
+                        }
                         KeyOutcome::SendStdinResponse { request_id, input } => {
                             if let Err(error) = app.send_single_session_stdin_response(request_id, input)
                             {
@@ -2031,6 +2050,21 @@ impl DesktopApp {
         }
     }
 
+    fn send_single_session_permission_decision(
+        &mut self,
+        request_id: String,
+        approved: bool,
+    ) -> anyhow::Result<()> {
+        match self {
+            Self::SingleSession(app) => {
+                app.send_permission_decision_via_active_session(request_id, approved)
+            }
+            Self::Workspace(_) => {
+                anyhow::bail!("permission decisions require single-session mode")
+            }
+        }
+    }
+
     fn set_single_session_handle(&mut self, handle: session_launch::DesktopSessionHandle) {
         if let Self::SingleSession(app) = self {
             app.set_session_handle(handle);
@@ -3430,6 +3430,61 @@ fn single_session_slash_server_setting_commands_return_control_outcomes() {
     assert_eq!(submit("/clear"), KeyOutcome::ClearServerSession);
 }
 
+#[test]
+fn single_session_permission_inbox_lists_and_resolves_entries() {
+    let entry = |id: &str| session_launch::DesktopPermissionEntry {
+        request_id: id.to_string(),
+        action: "bash".to_string(),
+        description: format!("run {id}"),
+        origin: "ambient cycle cycle_1".to_string(),
+        created_at_ms: 0,
+        expires_at_ms: None,
+    };
+    let mut app = SingleSessionApp::new(None);
+    app.apply_session_event(session_launch::DesktopSessionEvent::PermissionInbox {
+        entries: vec![entry("perm-a"), entry("perm-b")],
+    });
+    assert_eq!(
+        app.status.as_deref(),
+        Some("2 pending approvals · /permissions")
+    );
+    assert!(
+        app.status_title().ends_with("· 2 pending approvals"),
+        "{}",
+        app.status_title()
+    );
+
+    app.handle_key(KeyInput::Character("/permissions".to_string()));
+    assert_eq!(app.handle_key(KeyInput::SubmitDraft), KeyOutcome::Redraw);
+    let listing = app
+        .body_styled_lines()
+        .iter()
+        .map(|line| line.text.clone())
+        .collect::<Vec<_>>()
+        .join("\n");
+    assert!(listing.contains("bash: run perm-a"), "{listing}");
+    assert!(listing.contains("ambient cycle cycle_1"), "{listing}");
+
+    app.handle_key(KeyInput::Character("/permissions deny 2".to_string()));
+    assert_eq!(
+        app.handle_key(KeyInput::SubmitDraft),
+        KeyOutcome::ResolvePermission {
+            request_id: "perm-b".to_string(),
+            approved: false
+        }
+    );
+    app.handle_key(KeyInput::Character("/permissions allow perm-a".to_string()));
+    assert_eq!(
+        app.handle_key(KeyInput::SubmitDraft),
+        KeyOutcome::ResolvePermission {
+            request_id: "perm-a".to_string(),
+            approved: true
+        }
+    );
+    assert!(app.permission_inbox.is_empty());
+    assert!(!app.status_title().contains("pending"));
+}
+
 #[test]
 fn single_session_slash_setting_status_uses_runtime_metadata() {
     let mut app = SingleSessionApp::new(None);
@@ -2,10 +2,12 @@ use crate::workspace::SessionCard;
 pub use crate::workspace::SessionTranscriptMessage;
 use anyhow::{Context, Result};
 use jcode_tui_messages::{
-    TranscriptPreviewLabels, latest_user_transcript_preview, transcript_preview_lines,
+    StoredTokenUsage, TranscriptPreviewLabels, latest_user_transcript_preview,
+    transcript_preview_lines,
 };
 use serde::{Deserialize, Deserializer};
 use serde_json::Value;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -53,7 +55,10 @@ pub fn load_session_transcript_by_id(
     let direct_path = sessions_dir.join(format!("{session_id}.json"));
     if direct_path.exists() {
         let session = load_stored_session(&direct_path)?;
-        return Ok(Some(session_transcript_messages(&session)));
+        return Ok(Some(session_transcript_messages(
+            &session,
+            crate::desktop_config::desktop_message_footers_enabled(),
+        )));
     }
 
     if !sessions_dir.exists() {
@@ -92,13 +97,52 @@ pub fn load_session_transcript_by_id(
             })
             .unwrap_or_default();
         if id == session_id {
-            return Ok(Some(session_transcript_messages(&session)));
+            return Ok(Some(session_transcript_messages(
+                &session,
+                crate::desktop_config::desktop_message_footers_enabled(),
+            )));
         }
     }
 
     Ok(None)
 }
 
+/// Session token totals split into the conversation's own turns and the
+/// overhead spent on its behalf (subagents, compaction, summaries, memory
+/// capture).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SessionTokenSplit {
+    pub interactive_input: u64,
+    pub interactive_output: u64,
+    pub overhead_input: u64,
+    pub overhead_output: u64,
+}
+
+pub fn load_session_token_split(session_id: &str) -> Result<Option<SessionTokenSplit>> {
+    let path = jcode_sessions_dir()?.join(format!("{session_id}.json"));
+    if !path.exists() {
+        return Ok(None);
+    }
+    Ok(Some(session_token_split(&load_stored_session(&path)?)))
+}
+
+fn session_token_split(session: &StoredSession) -> SessionTokenSplit {
+    let mut split = SessionTokenSplit::default();
+    for usage in session
+        .messages
+        .iter()
+        .filter_map(|message| message.token_usage.as_ref())
+    {
+        split.interactive_input += usage.input_tokens;
+        split.interactive_output += usage.output_tokens;
+    }
+    for usage in session.origin_usage.values() {
+        split.overhead_input += usage.input_tokens;
+        split.overhead_output += usage.output_tokens;
+    }
+    split
+}
+
 /// A full, uncapped transcript loaded straight from disk, used by the
 /// real-transcript scroll benchmark so we profile the production render path
 /// against the user's actual session content rather than synthetic fixtures.
@@ -146,7 +190,7 @@ pub fn load_largest_real_transcripts(
             Ok(session) => session,
             Err(_) => continue,
         };
-        let messages = session_transcript_messages(&session);
+        let messages = session_transcript_messages(&session, false);
         if messages.len() < min_messages {
             continue;
         }
@@ -262,6 +306,16 @@ struct StoredSession {
     updated_at: Option<String>,
     #[serde(default)]
     messages: Vec<StoredMessage>,
+    #[serde(default)]
+    origin_usage: BTreeMap<String, StoredOriginUsage>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct StoredOriginUsage {
+    #[serde(default)]
+    input_tokens: u64,
+    #[serde(default)]
+    output_tokens: u64,
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -270,6 +324,8 @@ struct StoredMessage {
     role: Option<String>,
     #[serde(default, deserialize_with = "deserialize_message_content")]
     content: Vec<StoredContentBlock>,
+    #[serde(default)]
+    token_usage: Option<StoredTokenUsage>,
 }
 
 #[derive(Clone, Debug, Default, Deserialize)]
@@ -349,7 +405,7 @@ fn load_session_card(path: &Path, modified: SystemTime) -> Result<Option<Session
     let short_name =
         stored_string(session.short_name.as_deref()).unwrap_or_else(|| short_session_name(&id));
     let message_count = session.messages.len();
-    let transcript_messages = session_transcript_messages(&session);
+    let transcript_messages = session_transcript_messages(&session, false);
     let title = stored_string(session.custom_title.as_deref())
         .or_else(|| stored_string(session.title.as_deref()))
         .or_else(|| latest_user_preview(&transcript_messages))
@@ -441,19 +497,35 @@ fn recent_message_preview_lines(
     )
 }
 
-fn session_transcript_messages(messages: &StoredSession) -> Vec<SessionTranscriptMessage> {
-    messages
-        .messages
-        .iter()
-        .filter_map(|message| {
-            let role = transcript_display_role(message.role.as_deref());
-            let content = message_transcript_text(message)?;
-            if should_skip_desktop_transcript_message(&role, &content) {
-                return None;
-            }
-            Some(SessionTranscriptMessage { role, content })
-        })
-        .collect()
+/// Chat messages of a stored session. With `usage_footers` set, assistant
+/// responses that recorded their model and usage are followed by a meta footer.
+fn session_transcript_messages(
+    messages: &StoredSession,
+    usage_footers: bool,
+) -> Vec<SessionTranscriptMessage> {
+    let mut transcript = Vec::new();
+    for message in &messages.messages {
+        let role = transcript_display_role(message.role.as_deref());
+        let Some(content) = message_transcript_text(message) else {
+            continue;
+        };
+        if should_skip_desktop_transcript_message(&role, &content) {
+            continue;
+        }
+        let footer = message
+            .token_usage
+            .as_ref()
+            .filter(|_| usage_footers && role == "assistant")
+            .and_then(StoredTokenUsage::summary_line);
+        transcript.push(SessionTranscriptMessage { role, content });
+        if let Some(footer) = footer {
+            transcript.push(SessionTranscriptMessage {
+                role: "meta".to_string(),
+                content: footer,
+            });
+        }
+    }
+    transcript
 }
 
 fn session_card_transcript_messages(
@@ -597,7 +669,7 @@ mod tests {
             ]
         }));
 
-        let messages = session_transcript_messages(&session);
+        let messages = session_transcript_messages(&session, false);
 
         assert_eq!(
             latest_user_preview(&messages),
@@ -616,7 +688,7 @@ mod tests {
             ]
         }));
 
-        let messages = session_transcript_messages(&session);
+        let messages = session_transcript_messages(&session, false);
 
         assert_eq!(
             recent_message_preview_lines(&messages, 4, SESSION_PREVIEW_CHAR_LIMIT),
@@ -636,7 +708,7 @@ mod tests {
             ]
         }));
 
-        let messages = session_transcript_messages(&session);
+        let messages = session_transcript_messages(&session, false);
 
         assert_eq!(
             messages,
@@ -657,6 +729,64 @@ mod tests {
         );
     }
 
+    #[test]
+    fn session_transcript_messages_add_usage_footers_when_enabled() {
+        let session = stored_session(json!({
+            "messages": [
+                {"role": "user", "content": [{"type": "text", "text": "compare"}]},
+                {
+                    "role": "assistant",
+                    "content": [{"type": "text", "text": "answer"}],
+                    "token_usage": {
+                        "input_tokens": 1200,
+                        "output_tokens": 80,
+                        "model": "gpt-5",
+                        "provider": "openai",
+                        "duration_ms": 2500
+                    }
+                }
+            ]
+        }));
+
+        let with_footers = session_transcript_messages(&session, true);
+        assert_eq!(
+            with_footers.last(),
+            Some(&SessionTranscriptMessage {
+                role: "meta".to_string(),
+                content: "gpt-5 · openai · 2.5s · ↑1k ↓80".to_string(),
+            })
+        );
+        assert_eq!(session_transcript_messages(&session, false).len(), 2);
+    }
+
+    #[test]
+    fn session_token_split_separates_overhead_origins() {
+        let session = stored_session(json!({
+            "messages": [
+                {"role": "user", "content": [{"type": "text", "text": "go"}]},
+                {
+                    "role": "assistant",
+                    "content": [{"type": "text", "text": "done"}],
+                    "token_usage": {"input_tokens": 1000, "output_tokens": 50}
+                }
+            ],
+            "origin_usage": {
+                "compaction": {"calls": 1, "input_tokens": 400, "output_tokens": 30},
+                "subagent:general": {"calls": 2, "input_tokens": 600, "output_tokens": 70}
+            }
+        }));
+
+        assert_eq!(
+            session_token_split(&session),
+            SessionTokenSplit {
+                interactive_input: 1000,
+                interactive_output: 50,
+                overhead_input: 1000,
+                overhead_output: 100,
+            }
+        );
+    }
+
     #[test]
     fn load_session_card_filters_startup_reminder_from_preview_and_transcript() -> Result<()> {
         let dir = std::env::temp_dir().join(format!(
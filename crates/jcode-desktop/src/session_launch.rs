@@ -109,6 +109,18 @@ pub struct DesktopModelChoice {
     pub available: bool,
 }
 
+/// One pending decision in the shared permission inbox.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DesktopPermissionEntry {
+    pub request_id: String,
+    pub action: String,
+    pub description: String,
+    /// "ambient cycle <id>" or "session <id>".
+    pub origin: String,
+    pub created_at_ms: u64,
+    pub expires_at_ms: Option<u64>,
+}
+
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum DesktopSessionStatus {
     StartingSharedServer,
@@ -311,6 +323,9 @@ pub enum DesktopSessionEvent {
     SessionCloseRequested {
         reason: String,
     },
+    PermissionInbox {
+        entries: Vec<DesktopPermissionEntry>,
+    },
     Reloading {
         new_socket: Option<String>,
     },
@@ -346,6 +361,15 @@ impl DesktopSessionHandle {
             .send(DesktopSessionCommand::SetReasoningEffort { effort })
             .context("failed to send reasoning effort change to desktop session worker")
     }
+
+    pub fn send_permission_decision(&self, request_id: String, approved: bool) -> Result<()> {
+        self.command_tx
+            .send(DesktopSessionCommand::PermissionDecision {
+                request_id,
+                approved,
+            })
+            .context("failed to send permission decision to desktop session worker")
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -353,6 +377,7 @@ enum DesktopSessionCommand {
     Cancel,
     StdinResponse { request_id: String, input: String },
     SetReasoningEffort { effort: String },
+    PermissionDecision { request_id: String, approved: bool },
 }
 
 pub fn launch_resume_session(session_id: &str, title: &str) -> Result<()> {
@@ -793,6 +818,47 @@ pub fn spawn_clear_server_session(
     )
 }
 
+#[cfg(unix)]
+pub fn spawn_permission_decision(
+    request_id: String,
+    approved: bool,
+    target_session_id: Option<String>,
+    event_tx: DesktopSessionEventSender,
+) -> Result<()> {
+    spawn_control_request(
+        "jcode-desktop-permission-decision",
+        target_session_id,
+        event_tx,
+        DesktopSessionStatus::external("answering permission request"),
+        move |id| {
+            json!({
+                "type": "permission_decision",
+                "id": id,
+                "request_id": request_id,
+                "approved": approved,
+            })
+        },
+        &["done"],
+        "answering permission request",
+    )
+}
+
+#[cfg(not(unix))]
+pub fn spawn_permission_decision(
+    _request_id: String,
+    _approved: bool,
+    _target_session_id: Option<String>,
+    event_tx: DesktopSessionEventSender,
+) -> Result<()> {
+    send_desktop_event_ref(
+        Some(&event_tx),
+        DesktopSessionEvent::Error(
+            "desktop permission decisions are not implemented on this platform yet".to_string(),
+        ),
+    );
+    Ok(())
+}
+
 #[cfg(not(unix))]
 pub fn spawn_clear_server_session(
     _target_session_id: Option<String>,
@@ -1202,6 +1268,7 @@ fn desktop_session_event_kind(event: &DesktopSessionEvent) -> &'static str {
         DesktopSessionEvent::TokenUsage { .. } => "tokens",
         DesktopSessionEvent::SystemNotice { .. } => "system_notice",
         DesktopSessionEvent::SessionCloseRequested { .. } => "session_close_requested",
+        DesktopSessionEvent::PermissionInbox { .. } => "permission_inbox",
         DesktopSessionEvent::Reloading { .. } => "reloading",
         DesktopSessionEvent::Reloaded { .. } => "reloaded",
         DesktopSessionEvent::Done => "done",
@@ -1,6 +1,8 @@
 use serde_json::Value;
 
-use super::{DesktopModelChoice, DesktopSessionEvent, DesktopSessionStatus};
+use super::{
+    DesktopModelChoice, DesktopPermissionEntry, DesktopSessionEvent, DesktopSessionStatus,
+};
 
 pub(super) fn desktop_event_from_server_value(value: &Value) -> Option<DesktopSessionEvent> {
     match value.get("type").and_then(Value::as_str)? {
@@ -47,6 +49,14 @@ pub(super) fn desktop_event_from_server_value(value: &Value) -> Option<DesktopSe
                 upstream_provider: None,
             }
         }),
+        "rate_limited" => value
+            .get("retry_after_secs")
+            .and_then(Value::as_u64)
+            .map(|secs| DesktopSessionEvent::RuntimeMetadata {
+                connection_type: None,
+                status_detail: Some(format!("rate limited · retrying in {secs}s")),
+                upstream_provider: None,
+            }),
         "upstream_provider" => optional_server_str(value, "provider")
             .or_else(|| optional_server_str(value, "provider_name"))
             .map(|upstream_provider| DesktopSessionEvent::RuntimeMetadata {
@@ -220,6 +230,9 @@ pub(super) fn desktop_event_from_server_value(value: &Value) -> Option<DesktopSe
                 .unwrap_or("server requested the session be closed")
                 .to_string(),
         }),
+        "permission_inbox" => Some(DesktopSessionEvent::PermissionInbox {
+            entries: permission_entries_from_server_value(value),
+        }),
         "message_end" | "kv_cache_request" => None,
         "generated_image" => Some(DesktopSessionEvent::SystemNotice {
             title: "generated image".to_string(),
@@ -318,6 +331,37 @@ pub(super) fn model_catalog_event_from_server_value(value: &Value) -> Option<Des
     })
 }
 
+fn permission_entries_from_server_value(value: &Value) -> Vec<DesktopPermissionEntry> {
+    let Some(entries) = value.get("entries").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let kind = match optional_server_str(entry, "origin") {
+                Some("session") => "session",
+                _ => "ambient cycle",
+            };
+            let origin = match non_empty_server_str(entry, "origin_id") {
+                Some(id) => format!("{kind} {id}"),
+                None => kind.to_string(),
+            };
+            Some(DesktopPermissionEntry {
+                request_id: non_empty_server_str(entry, "request_id")?.to_string(),
+                action: optional_server_str(entry, "action")
+                    .unwrap_or("unknown")
+                    .to_string(),
+                description: optional_server_str(entry, "description")
+                    .unwrap_or_default()
+                    .to_string(),
+                origin,
+                created_at_ms: server_u64(entry, "created_at_ms").unwrap_or(0),
+                expires_at_ms: server_u64(entry, "expires_at_ms"),
+            })
+        })
+        .collect()
+}
+
 pub(super) fn model_choices_from_server_value(value: &Value) -> Vec<DesktopModelChoice> {
     let mut choices = Vec::new();
     if let Some(routes) = value
@@ -879,6 +879,21 @@ pub(super) fn drain_worker_commands(
                 )?;
                 *next_request_id += 1;
             }
+            DesktopSessionCommand::PermissionDecision {
+                request_id,
+                approved,
+            } => {
+                write_json_line(
+                    writer,
+                    json!({
+                        "type": "permission_decision",
+                        "id": *next_request_id,
+                        "request_id": request_id,
+                        "approved": approved,
+                    }),
+                )?;
+                *next_request_id += 1;
+            }
         }
     }
     Ok(cancel_request_ids)
@@ -416,6 +416,73 @@ fn desktop_session_handle_sends_reasoning_effort_command() {
     );
 }
 
+#[test]
+fn desktop_session_handle_sends_permission_decision_command() {
+    let (command_tx, command_rx) = mpsc::channel();
+    let handle = DesktopSessionHandle { command_tx };
+
+    handle
+        .send_permission_decision("perm-1".to_string(), false)
+        .unwrap();
+
+    assert_eq!(
+        command_rx.try_recv(),
+        Ok(DesktopSessionCommand::PermissionDecision {
+            request_id: "perm-1".to_string(),
+            approved: false
+        })
+    );
+}
+
+#[test]
+fn desktop_event_parser_maps_permission_inbox_entries() {
+    assert_eq!(
+        desktop_event_from_server_value(&json!({
+            "type": "permission_inbox",
+            "entries": [
+                {
+                    "request_id": "perm-1",
+                    "origin": "ambient",
+                    "origin_id": "cycle_7",
+                    "action": "create_pull_request",
+                    "description": "Open a PR",
+                    "created_at_ms": 1000
+                },
+                {
+                    "request_id": "safe-mode-approval-2",
+                    "origin": "session",
+                    "origin_id": "session_abc",
+                    "action": "bash",
+                    "description": "bash: rm -rf build",
+                    "created_at_ms": 2000,
+                    "expires_at_ms": 902000
+                },
+                { "origin": "session", "action": "missing id" }
+            ]
+        })),
+        Some(DesktopSessionEvent::PermissionInbox {
+            entries: vec![
+                DesktopPermissionEntry {
+                    request_id: "perm-1".to_string(),
+                    action: "create_pull_request".to_string(),
+                    description: "Open a PR".to_string(),
+                    origin: "ambient cycle cycle_7".to_string(),
+                    created_at_ms: 1000,
+                    expires_at_ms: None,
+                },
+                DesktopPermissionEntry {
+                    request_id: "safe-mode-approval-2".to_string(),
+                    action: "bash".to_string(),
+                    description: "bash: rm -rf build".to_string(),
+                    origin: "session session_abc".to_string(),
+                    created_at_ms: 2000,
+                    expires_at_ms: Some(902000),
+                },
+            ]
+        })
+    );
+}
+
 #[test]
 fn desktop_session_worker_slots_are_bounded_and_released() -> Result<()> {
     let counter = AtomicUsize::new(0);
@@ -38,6 +38,7 @@ pub(crate) use tool_lines::*;
 mod commands;
 mod input;
 mod overlays;
+mod permissions;
 mod transcript;
 
 pub(crate) const SINGLE_SESSION_FONT_FAMILY: &str = "JetBrainsMono Nerd Font";
@@ -188,6 +189,10 @@ const DESKTOP_SLASH_COMMANDS: &[(&str, &str)] = &[
         "compact context or set compaction mode",
     ),
     ("/rename <title|--clear>", "rename the current session"),
+    (
+        "/permissions [allow|deny <n>]",
+        "list or answer pending permission requests",
+    ),
     ("/usage", "desktop parity notice for TUI usage overlay"),
     ("/todo", "desktop parity notice for TUI todo panel"),
     ("/todos", "alias for /todo"),
@@ -298,6 +303,8 @@ pub(crate) struct SingleSessionApp {
     pub(crate) model_picker: ModelPickerState,
     pub(crate) session_switcher: SessionSwitcherState,
     pub(crate) stdin_response: Option<StdinResponseState>,
+    /// Pending approvals from every session and ambient cycle.
+    pub(crate) permission_inbox: Vec<crate::session_launch::DesktopPermissionEntry>,
     slash_suggestions: SlashSuggestionState,
     runtime_settings: SingleSessionRuntimeSettings,
     welcome: SingleSessionWelcomeState,
@@ -1103,6 +1110,7 @@ impl SingleSessionApp {
             model_picker: ModelPickerState::default(),
             session_switcher: SessionSwitcherState::default(),
             stdin_response: None,
+            permission_inbox: Vec::new(),
             slash_suggestions: SlashSuggestionState::default(),
             runtime_settings: SingleSessionRuntimeSettings::default(),
             welcome,
@@ -1302,7 +1310,10 @@ impl SingleSessionApp {
     }
 
     pub(crate) fn status_title(&self) -> String {
-        format!("Jcode · {}", self.title())
+        match self.permission_inbox_badge() {
+            Some(badge) => format!("Jcode · {} · {badge}", self.title()),
+            None => format!("Jcode · {}", self.title()),
+        }
     }
 
     pub(crate) fn title(&self) -> String {
@@ -247,6 +247,7 @@ impl SingleSessionApp {
                     }
                 }
             }
+            "/permissions" => self.handle_permissions_command(args),
             "/compact" => {
                 self.draft.clear();
                 self.draft_cursor = 0;
@@ -323,7 +324,7 @@ impl SingleSessionApp {
                 self.draft_cursor = 0;
                 self.composer.input_undo_stack.clear();
                 let usage = self.runtime_settings.token_usage.as_ref();
-                let message = usage
+                let mut message = usage
                     .map(|usage| {
                         format!(
                             "desktop /usage overlay is not implemented yet · latest tokens: input={} output={}",
@@ -333,6 +334,19 @@ impl SingleSessionApp {
                     .unwrap_or_else(|| {
                         "desktop /usage overlay is not implemented yet · no token usage received for this session".to_string()
                     });
+                if let Some(split) = self
+                    .current_session_id()
+                    .and_then(|session_id| session_data::load_session_token_split(session_id).ok())
+                    .flatten()
+                {
+                    message.push_str(&format!(
+                        " · session: interactive input={} output={} · overhead input={} output={}",
+                        split.interactive_input,
+                        split.interactive_output,
+                        split.overhead_input,
+                        split.overhead_output
+                    ));
+                }
                 self.set_status(SingleSessionStatus::Info(message));
                 KeyOutcome::Redraw
             }
@@ -0,0 +1,175 @@
+//! The shared permission inbox in the desktop app. The server pushes every
+//! pending approval (ambient cycles and sessions alike) as `permission_inbox`
+//! events; `/permissions` lists them and `/permissions allow|deny <n|id>`
+//! answers one through the server, which records it for every other surface.
+
+use super::*;
+use crate::session_launch::DesktopPermissionEntry;
+
+const PERMISSIONS_USAGE: &str = "usage: /permissions [allow|deny <number|request id>]";
+
+impl SingleSessionApp {
+    pub(crate) fn apply_permission_inbox(&mut self, entries: Vec<DesktopPermissionEntry>) {
+        let listed = |list: &[DesktopPermissionEntry], id: &str| {
+            list.iter().any(|entry| entry.request_id == id)
+        };
+
+        // The approval this window is prompting for was answered elsewhere.
+        if let Some(pending) = self
+            .stdin_response
+            .as_ref()
+            .map(|state| state.request_id.clone())
+            && listed(&self.permission_inbox, &pending)
+            && !listed(&entries, &pending)
+        {
+            self.stdin_response = None;
+            self.clear_tool_stdin_prompts();
+            self.set_status(SingleSessionStatus::Info(
+                "approval answered elsewhere".to_string(),
+            ));
+        }
+
+        let own_prompt = self
+            .stdin_response
+            .as_ref()
+            .map(|state| state.request_id.as_str());
+        let added = entries
+            .iter()
+            .filter(|entry| !listed(&self.permission_inbox, &entry.request_id))
+            .filter(|entry| own_prompt != Some(entry.request_id.as_str()))
+            .count();
+        self.permission_inbox = entries;
+        if added > 0 {
+            self.set_status(SingleSessionStatus::Info(format!(
+                "{} · /permissions",
+                pending_permissions_label(self.permission_inbox.len())
+            )));
+        }
+    }
+
+    /// Window title suffix while approvals are pending.
+    pub(crate) fn permission_inbox_badge(&self) -> Option<String> {
+        (!self.permission_inbox.is_empty())
+            .then(|| pending_permissions_label(self.permission_inbox.len()))
+    }
+
+    pub(super) fn handle_permissions_command(&mut self, args: &str) -> KeyOutcome {
+        self.draft.clear();
+        self.draft_cursor = 0;
+        self.composer.input_undo_stack.clear();
+        let words: Vec<&str> = args.split_whitespace().collect();
+        let (approved, target) = match words.as_slice() {
+            [] => {
+                self.messages
+                    .push(SingleSessionMessage::meta(permission_inbox_listing(
+                        &self.permission_inbox,
+                        unix_now_ms(),
+                    )));
+                self.scroll_body_to_bottom();
+                return KeyOutcome::Redraw;
+            }
+            ["allow", target] => (true, *target),
+            ["deny", target] => (false, *target),
+            _ => {
+                self.set_status(SingleSessionStatus::Info(PERMISSIONS_USAGE.to_string()));
+                return KeyOutcome::Redraw;
+            }
+        };
+
+        let position = match target.parse::<usize>() {
+            Ok(number) => number.checked_sub(1),
+            Err(_) => self
+                .permission_inbox
+                .iter()
+                .position(|entry| entry.request_id == target),
+        };
+        let Some(entry) = position
+            .filter(|index| *index < self.permission_inbox.len())
+            .map(|index| self.permission_inbox.remove(index))
+        else {
+            self.set_status(SingleSessionStatus::Info(format!(
+                "no pending permission request {target}"
+            )));
+            return KeyOutcome::Redraw;
+        };
+
+        if self
+            .stdin_response
+            .as_ref()
+            .is_some_and(|state| state.request_id == entry.request_id)
+        {
+            self.stdin_response = None;
+            self.clear_tool_stdin_prompts();
+        }
+        self.set_status(SingleSessionStatus::Info(format!(
+            "{} {}",
+            if approved { "allowed" } else { "denied" },
+            entry.action
+        )));
+        KeyOutcome::ResolvePermission {
+            request_id: entry.request_id,
+            approved,
+        }
+    }
+
+    pub(crate) fn send_permission_decision_via_active_session(
+        &mut self,
+        request_id: String,
+        approved: bool,
+    ) -> anyhow::Result<()> {
+        let Some(handle) = &self.runtime.session_handle else {
+            anyhow::bail!("no active desktop session to receive the permission decision");
+        };
+        handle.send_permission_decision(request_id, approved)
+    }
+}
+
+fn pending_permissions_label(count: usize) -> String {
+    format!(
+        "{count} pending approval{}",
+        if count == 1 { "" } else { "s" }
+    )
+}
+
+fn permission_inbox_listing(entries: &[DesktopPermissionEntry], now_ms: u64) -> String {
+    if entries.is_empty() {
+        return "permission inbox: nothing pending".to_string();
+    }
+    let mut listing = format!("permission inbox ({}):", entries.len());
+    for (index, entry) in entries.iter().enumerate() {
+        let expiry = match entry.expires_at_ms {
+            Some(at) if at > now_ms => format!("expires in {}", format_span(at - now_ms)),
+            Some(_) => "expired".to_string(),
+            None => "no expiry".to_string(),
+        };
+        listing.push_str(&format!(
+            "\n{}. {}: {}\n   {} · {} old · {} · {}",
+            index + 1,
+            entry.action,
+            entry.description,
+            entry.origin,
+            format_span(now_ms.saturating_sub(entry.created_at_ms)),
+            expiry,
+            entry.request_id
+        ));
+    }
+    listing.push_str(&format!("\n{PERMISSIONS_USAGE}"));
+    listing
+}
+
+fn format_span(ms: u64) -> String {
+    let secs = ms / 1000;
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3600 => format!("{}m", secs / 60),
+        3600..86_400 => format!("{}h", secs / 3600),
+        _ => format!("{}d", secs / 86_400),
+    }
+}
+
+fn unix_now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or(0)
+}
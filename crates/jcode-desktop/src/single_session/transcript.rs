@@ -296,6 +296,9 @@ impl SingleSessionApp {
                 self.messages.push(SingleSessionMessage::meta(line));
                 self.set_status(SingleSessionStatus::Info(title));
             }
+            DesktopSessionEvent::PermissionInbox { entries } => {
+                self.apply_permission_inbox(entries);
+            }
             DesktopSessionEvent::SessionCloseRequested { reason } => {
                 self.finish_streaming_response();
                 self.is_processing = false;
@@ -157,6 +157,10 @@ pub enum KeyOutcome {
         request_id: String,
         input: String,
     },
+    ResolvePermission {
+        request_id: String,
+        approved: bool,
+    },
     AttachClipboardImage,
     PasteText,
     ForceReload,
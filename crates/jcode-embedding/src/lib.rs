@@ -441,6 +441,7 @@ fn download_model(model_dir: &Path) -> Result<()> {
 fn download_model_blocking(model_dir: &Path) -> Result<()> {
     let client = reqwest::blocking::Client::builder()
         .user_agent(concat!("jcode-embedding/", env!("CARGO_PKG_VERSION")))
+        .dns_resolver(jcode_provider_core::egress::resolver())
         .timeout(std::time::Duration::from_secs(300))
         .build()?;
 
@@ -144,6 +144,8 @@ pub enum ClaudeCodeContentBlock {
         #[serde(default)]
         is_error: Option<bool>,
     },
+    /// A pasted or attached image. Only its presence is kept.
+    Image {},
     #[serde(other)]
     Unknown,
 }
@@ -0,0 +1,184 @@
+//! Per-module level filtering.
+//!
+//! `JCODE_LOG` takes comma-separated directives: a bare level sets the default
+//! (`warn`), `target=level` overrides it for one module and its submodules
+//! (`provider=trace,tool=debug`). A directive also covers the `jcode-<name>`
+//! crates of the same family, so `provider` reaches `provider_openai::stream`.
+//! `JCODE_TRACE` (set by `--trace`) raises the default to `debug`.
+
+use super::LogLevel;
+use std::sync::RwLock;
+
+static LEVEL_FILTER: RwLock<Option<LevelFilter>> = RwLock::new(None);
+
+/// Crates whose module tree mirrors the root crate's `crate::` paths, so their
+/// files map to targets without a crate prefix.
+const LAYER_CRATES: &[&str] = &["base", "app-core", "tui"];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LevelFilter {
+    /// `None` means logging is off by default
+    default: Option<LogLevel>,
+    directives: Vec<(String, Option<LogLevel>)>,
+}
+
+impl LevelFilter {
+    /// Parse `JCODE_LOG`-style directives on top of `default`. Unknown levels
+    /// are ignored.
+    pub fn parse(spec: &str, default: LogLevel) -> Self {
+        let mut filter = Self {
+            default: Some(default),
+            directives: Vec::new(),
+        };
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            match directive.split_once('=') {
+                Some((target, level)) => {
+                    if let Some(level) = parse_filter_level(level) {
+                        let target = target.trim().replace('-', "_");
+                        filter
+                            .directives
+                            .retain(|(existing, _)| *existing != target);
+                        filter.directives.push((target, level));
+                    }
+                }
+                None => {
+                    if let Some(level) = parse_filter_level(directive) {
+                        filter.default = level;
+                    }
+                }
+            }
+        }
+        filter
+    }
+
+    pub fn from_env() -> Self {
+        let default = if std::env::var("JCODE_TRACE").is_ok() {
+            LogLevel::Debug
+        } else {
+            LogLevel::Info
+        };
+        Self::parse(&std::env::var("JCODE_LOG").unwrap_or_default(), default)
+    }
+
+    pub fn enabled(&self, level: LogLevel, target: &str) -> bool {
+        let threshold = self
+            .directives
+            .iter()
+            .filter(|(directive, _)| target_matches(target, directive))
+            .max_by_key(|(directive, _)| directive.len())
+            .map(|(_, level)| *level)
+            .unwrap_or(self.default);
+        threshold.is_some_and(|threshold| level.severity() <= threshold.severity())
+    }
+}
+
+fn parse_filter_level(value: &str) -> Option<Option<LogLevel>> {
+    if value.trim().eq_ignore_ascii_case("off") {
+        return Some(None);
+    }
+    LogLevel::parse(value).map(Some)
+}
+
+fn target_matches(target: &str, directive: &str) -> bool {
+    target
+        .strip_prefix(directive)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with("::") || rest.starts_with('_'))
+}
+
+pub(crate) fn level_enabled(level: LogLevel, target: &str) -> bool {
+    if let Ok(guard) = LEVEL_FILTER.read()
+        && let Some(filter) = guard.as_ref()
+    {
+        return filter.enabled(level, target);
+    }
+    let filter = LevelFilter::from_env();
+    let enabled = filter.enabled(level, target);
+    if let Ok(mut guard) = LEVEL_FILTER.write() {
+        *guard = Some(filter);
+    }
+    enabled
+}
+
+/// Re-read `JCODE_LOG`/`JCODE_TRACE`, e.g. after CLI flags changed them.
+pub fn reload_level_filter() {
+    if let Ok(mut guard) = LEVEL_FILTER.write() {
+        *guard = None;
+    }
+}
+
+/// Module path for a source file as reported by `Location::caller()`, e.g.
+/// `crates/jcode-app-core/src/provider/openai.rs` → `provider::openai` and
+/// `crates/jcode-provider-openai/src/stream.rs` → `provider_openai::stream`.
+pub fn target_from_file(file: &str) -> String {
+    let file = file.replace('\\', "/");
+    let (crate_dir, relative) = match file.rfind("/src/") {
+        Some(index) => (&file[..index], &file[index + "/src/".len()..]),
+        None => ("", file.strip_prefix("src/").unwrap_or(&file)),
+    };
+    let crate_prefix = crate_dir
+        .rsplit('/')
+        .next()
+        .and_then(|name| name.strip_prefix("jcode-"))
+        .filter(|name| !LAYER_CRATES.contains(name))
+        .map(|name| name.replace('-', "_"));
+
+    let module = relative.trim_end_matches(".rs");
+    let module = module.strip_suffix("/mod").unwrap_or(module);
+    let module = match module {
+        "lib" | "main" => "",
+        other => other,
+    };
+    let module = module.replace('/', "::").replace('-', "_");
+    match (crate_prefix, module.is_empty()) {
+        (Some(prefix), true) => prefix,
+        (Some(prefix), false) => format!("{}::{}", prefix, module),
+        (None, _) => module,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn targets_follow_module_paths() {
+        assert_eq!(
+            target_from_file("crates/jcode-app-core/src/provider/openai.rs"),
+            "provider::openai"
+        );
+        assert_eq!(
+            target_from_file("crates/jcode-provider-openai/src/stream.rs"),
+            "provider_openai::stream"
+        );
+        assert_eq!(
+            target_from_file("crates/jcode-logging/src/lib.rs"),
+            "logging"
+        );
+        assert_eq!(target_from_file("src/cli/startup.rs"), "cli::startup");
+        assert_eq!(
+            target_from_file("crates/jcode-base/src/tool/mod.rs"),
+            "tool"
+        );
+    }
+
+    #[test]
+    fn module_directives_override_the_default() {
+        let filter = LevelFilter::parse("warn,provider=trace,tool=debug", LogLevel::Info);
+        assert!(!filter.enabled(LogLevel::Info, "agent"));
+        assert!(filter.enabled(LogLevel::Warn, "agent"));
+        assert!(filter.enabled(LogLevel::Trace, "provider::openai"));
+        assert!(filter.enabled(LogLevel::Trace, "provider_openai::stream"));
+        assert!(!filter.enabled(LogLevel::Trace, "providers"));
+        assert!(filter.enabled(LogLevel::Debug, "tool::bash"));
+        assert!(!filter.enabled(LogLevel::Trace, "tool::bash"));
+    }
+
+    #[test]
+    fn longest_directive_wins_and_off_silences() {
+        let filter = LevelFilter::parse("server=off,server::reload=debug", LogLevel::Info);
+        assert!(!filter.enabled(LogLevel::Error, "server::client_session"));
+        assert!(filter.enabled(LogLevel::Debug, "server::reload"));
+        assert!(filter.enabled(LogLevel::Info, "agent"));
+        assert!(!filter.enabled(LogLevel::Debug, "agent"));
+    }
+}
@@ -1,6 +1,8 @@
 //! Logging infrastructure for jcode
 //!
-//! Logs to ~/.jcode/logs/ with automatic rotation
+//! Logs to ~/.jcode/logs/ as structured JSONL records (`jcode-<date>.jsonl`)
+//! with a human-readable mirror (`jcode-<date>.log`) for tailing. Files rotate
+//! by size and are pruned by age.
 //!
 //! Supports thread-local context for server, session, provider, and model info.
 
@@ -9,7 +11,8 @@ use std::cell::RefCell;
 use std::collections::{BTreeMap, HashMap};
 use std::fs::{self, File, OpenOptions};
 use std::io::Write;
-use std::path::PathBuf;
+use std::panic::Location;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Mutex, OnceLock};
 use std::time::{Duration, Instant};
@@ -19,12 +22,25 @@ static CLEANUP_STARTED: AtomicBool = AtomicBool::new(false);
 static TASK_LOG_CONTEXTS: OnceLock<Mutex<HashMap<String, LogContext>>> = OnceLock::new();
 static RATE_LIMITS: OnceLock<Mutex<HashMap<String, RateLimitState>>> = OnceLock::new();
 
+mod filter;
+mod record;
+
+pub use filter::{LevelFilter, reload_level_filter, target_from_file};
+pub use record::{LogRecord, RecordFilter, structured_log_path, structured_log_segments};
+
+/// Size at which a log file is rotated to `jcode-<date>.<n>.log`.
+const MAX_LOG_FILE_BYTES: u64 = 32 * 1024 * 1024;
+
+/// Rotated segments kept per file and day; older ones are deleted on rotation.
+const MAX_ROTATED_SEGMENTS: usize = 4;
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum LogLevel {
     Info,
     Warn,
     Error,
     Debug,
+    Trace,
 }
 
 impl LogLevel {
@@ -34,11 +50,45 @@ impl LogLevel {
             Self::Warn => "WARN",
             Self::Error => "ERROR",
             Self::Debug => "DEBUG",
+            Self::Trace => "TRACE",
+        }
+    }
+
+    /// Lowercase name used in structured records and `JCODE_LOG`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warn => "warn",
+            Self::Error => "error",
+            Self::Debug => "debug",
+            Self::Trace => "trace",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "error" => Some(Self::Error),
+            "warn" | "warning" => Some(Self::Warn),
+            "info" => Some(Self::Info),
+            "debug" => Some(Self::Debug),
+            "trace" => Some(Self::Trace),
+            _ => None,
         }
     }
 
-    fn is_enabled(self) -> bool {
-        !matches!(self, Self::Debug) || std::env::var("JCODE_TRACE").is_ok()
+    /// Lower is more severe.
+    pub fn severity(self) -> u8 {
+        match self {
+            Self::Error => 0,
+            Self::Warn => 1,
+            Self::Info => 2,
+            Self::Debug => 3,
+            Self::Trace => 4,
+        }
+    }
+
+    fn is_enabled_for(self, target: &str) -> bool {
+        filter::level_enabled(self, target)
     }
 }
 
@@ -103,15 +153,6 @@ pub fn set_provider_info(provider: &str, model: &str) {
     });
 }
 
-/// Get the current context as a prefix string
-fn context_prefix() -> String {
-    if let Some(task_ctx) = task_context_snapshot() {
-        return context_prefix_for(&task_ctx);
-    }
-
-    LOG_CONTEXT.with(|c| context_prefix_for(&c.borrow()))
-}
-
 fn current_task_id() -> Option<String> {
     tokio::task::try_id().map(|id| id.to_string())
 }
@@ -176,35 +217,30 @@ fn context_prefix_for(ctx: &LogContext) -> String {
 }
 
 pub struct Logger {
-    file: File,
+    text: LogFile,
+    records: Option<LogFile>,
 }
 
-fn log_dir() -> Option<PathBuf> {
-    jcode_storage::logs_dir().ok()
+/// One append-only log file that rotates itself once it grows past
+/// [`MAX_LOG_FILE_BYTES`].
+struct LogFile {
+    path: PathBuf,
+    file: File,
+    len: u64,
 }
 
-impl Logger {
-    fn new() -> Option<Self> {
-        let log_dir = log_dir()?;
-        jcode_storage::ensure_dir(&log_dir).ok()?;
-
-        // Use date-based log file
-        let date = Local::now().format("%Y-%m-%d");
-        let path = log_dir.join(format!("jcode-{}.log", date));
-
+impl LogFile {
+    fn open(path: PathBuf) -> Option<Self> {
         let file = OpenOptions::new()
             .create(true)
             .append(true)
             .open(&path)
             .ok()?;
-
-        Some(Self { file })
+        let len = file.metadata().map(|meta| meta.len()).unwrap_or(0);
+        Some(Self { path, file, len })
     }
 
-    fn write(&mut self, level: &str, message: &str) {
-        let timestamp = Local::now().format("%Y-%m-%d %H:%M:%S%.3f");
-        let ctx = context_prefix();
-        let line = format!("[{}] [{}] {}{}\n", timestamp, level, ctx, message);
+    fn append(&mut self, line: &str) {
         if let Err(err) = self.file.write_all(line.as_bytes()) {
             eprintln!("jcode logger write failed: {err}");
             return;
@@ -212,6 +248,128 @@ impl Logger {
         if let Err(err) = self.file.flush() {
             eprintln!("jcode logger flush failed: {err}");
         }
+        self.len += line.len() as u64;
+        if self.len > MAX_LOG_FILE_BYTES {
+            self.rotate();
+        }
+    }
+
+    fn rotate(&mut self) {
+        // Indices only grow, so pruning old segments never frees a slot that
+        // a newer segment would then land below.
+        let mut indices = rotated_log_indices(&self.path);
+        let index = indices.last().map_or(1, |last| last + 1);
+        // Fails when another process rotated it first; either way the live
+        // path is reopened below.
+        if fs::rename(&self.path, rotated_log_path(&self.path, index)).is_ok() {
+            indices.push(index);
+        }
+        let stale = indices.len().saturating_sub(MAX_ROTATED_SEGMENTS);
+        for index in &indices[..stale] {
+            let _ = fs::remove_file(rotated_log_path(&self.path, *index));
+        }
+        if let Some(reopened) = Self::open(self.path.clone()) {
+            *self = reopened;
+        }
+    }
+}
+
+/// `jcode-<date>.log` → `jcode-<date>.<index>.log`; higher indices are newer.
+fn rotated_log_path(path: &Path, index: usize) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_string())
+        .unwrap_or_default();
+    path.with_file_name(format!("{}.{}.{}", stem, index, extension))
+}
+
+/// Indices of the existing rotated segments of `path`, oldest first.
+fn rotated_log_indices(path: &Path) -> Vec<usize> {
+    let (Some(dir), Some(stem)) = (path.parent(), path.file_stem()) else {
+        return Vec::new();
+    };
+    let prefix = format!("{}.", stem.to_string_lossy());
+    let suffix = path
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_default();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut indices: Vec<usize> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            name.strip_prefix(&prefix)?
+                .strip_suffix(&suffix)?
+                .parse()
+                .ok()
+        })
+        .collect();
+    indices.sort_unstable();
+    indices
+}
+
+fn log_dir() -> Option<PathBuf> {
+    jcode_storage::logs_dir().ok()
+}
+
+impl Logger {
+    fn new() -> Option<Self> {
+        let log_dir = log_dir()?;
+        jcode_storage::ensure_dir(&log_dir).ok()?;
+
+        // Use date-based log files
+        let today = Local::now().date_naive();
+        let text = LogFile::open(log_dir.join(format!("jcode-{}.log", today.format("%Y-%m-%d"))))?;
+        let records = LogFile::open(record::structured_log_path_for(&log_dir, today));
+
+        Some(Self { text, records })
+    }
+
+    /// Write one line to the mirror and one record to the JSONL stream.
+    /// `event` carries the record's message and fields for structured events;
+    /// plain lines use `message` as-is.
+    fn write(
+        &mut self,
+        level: LogLevel,
+        label: &str,
+        target: &str,
+        message: &str,
+        event: Option<(&str, &BTreeMap<String, String>)>,
+    ) {
+        let now = Local::now();
+        let ctx = current_context_snapshot();
+        self.text.append(&format!(
+            "[{}] [{}] {}{}\n",
+            now.format("%Y-%m-%d %H:%M:%S%.3f"),
+            label,
+            context_prefix_for(&ctx),
+            message
+        ));
+        if let Some(records) = self.records.as_mut() {
+            let (message, fields) = match event {
+                Some((name, fields)) => (name.to_string(), fields.clone()),
+                None => (message.to_string(), BTreeMap::new()),
+            };
+            let record = LogRecord {
+                timestamp: now.to_rfc3339_opts(chrono::SecondsFormat::Millis, false),
+                level,
+                kind: (label != level.as_str()).then(|| label.to_ascii_lowercase()),
+                target: target.to_string(),
+                session_id: ctx.session,
+                server: ctx.server,
+                provider: ctx.provider,
+                model: ctx.model,
+                message,
+                fields,
+            };
+            records.append(&format!("{}\n", record.to_json_line()));
+        }
     }
 }
 
@@ -238,42 +396,21 @@ pub fn init() {
 }
 
 /// Log an info message
-#[expect(
-    clippy::collapsible_if,
-    reason = "Logger lock + optional logger branching is intentionally straightforward"
-)]
+#[track_caller]
 pub fn info(message: &str) {
-    if let Ok(mut guard) = LOGGER.lock() {
-        if let Some(logger) = guard.as_mut() {
-            logger.write("INFO", message);
-        }
-    }
+    log_at(LogLevel::Info, message, Location::caller());
 }
 
 /// Log an error message
-#[expect(
-    clippy::collapsible_if,
-    reason = "Logger lock + optional logger branching is intentionally straightforward"
-)]
+#[track_caller]
 pub fn error(message: &str) {
-    if let Ok(mut guard) = LOGGER.lock() {
-        if let Some(logger) = guard.as_mut() {
-            logger.write("ERROR", message);
-        }
-    }
+    log_at(LogLevel::Error, message, Location::caller());
 }
 
 /// Log a warning message
-#[expect(
-    clippy::collapsible_if,
-    reason = "Logger lock + optional logger branching is intentionally straightforward"
-)]
+#[track_caller]
 pub fn warn(message: &str) {
-    if let Ok(mut guard) = LOGGER.lock() {
-        if let Some(logger) = guard.as_mut() {
-            logger.write("WARN", message);
-        }
-    }
+    log_at(LogLevel::Warn, message, Location::caller());
 }
 
 /// Truncate a value for inclusion in a log line, appending an ellipsis marker
@@ -288,21 +425,31 @@ pub fn truncate_for_log(value: &str, max_chars: usize) -> String {
     format!("{}… [{} chars total]", truncated, value.chars().count())
 }
 
-/// Log a debug message (only if JCODE_TRACE is set)
-#[expect(
-    clippy::collapsible_if,
-    reason = "Debug logging keeps env gating and logger access explicit"
-)]
+/// Log a debug message (only if JCODE_TRACE or a `JCODE_LOG` directive enables it)
+#[track_caller]
 pub fn debug(message: &str) {
-    if std::env::var("JCODE_TRACE").is_ok() {
-        if let Ok(mut guard) = LOGGER.lock() {
-            if let Some(logger) = guard.as_mut() {
-                logger.write("DEBUG", message);
-            }
-        }
+    log_at(LogLevel::Debug, message, Location::caller());
+}
+
+/// Log a trace message (only if a `JCODE_LOG` directive enables it)
+#[track_caller]
+pub fn trace(message: &str) {
+    log_at(LogLevel::Trace, message, Location::caller());
+}
+
+#[track_caller]
+fn caller_target() -> String {
+    target_from_file(Location::caller().file())
+}
+
+fn log_at(level: LogLevel, message: &str, location: &Location<'_>) {
+    let target = target_from_file(location.file());
+    if level.is_enabled_for(&target) {
+        write_level(level, level.as_str(), &target, message, None);
     }
 }
 
+#[track_caller]
 pub fn event_info<K, V, I>(event_name: &str, fields: I)
 where
     K: AsRef<str>,
@@ -312,6 +459,7 @@ where
     event(LogLevel::Info, event_name, fields);
 }
 
+#[track_caller]
 pub fn event_warn<K, V, I>(event_name: &str, fields: I)
 where
     K: AsRef<str>,
@@ -321,6 +469,7 @@ where
     event(LogLevel::Warn, event_name, fields);
 }
 
+#[track_caller]
 pub fn event_error<K, V, I>(event_name: &str, fields: I)
 where
     K: AsRef<str>,
@@ -330,6 +479,7 @@ where
     event(LogLevel::Error, event_name, fields);
 }
 
+#[track_caller]
 pub fn event_debug<K, V, I>(event_name: &str, fields: I)
 where
     K: AsRef<str>,
@@ -339,18 +489,21 @@ where
     event(LogLevel::Debug, event_name, fields);
 }
 
+#[track_caller]
 pub fn event<K, V, I>(level: LogLevel, event: &str, fields: I)
 where
     K: AsRef<str>,
     V: ToString,
     I: IntoIterator<Item = (K, V)>,
 {
-    if !level.is_enabled() {
+    let target = target_from_file(Location::caller().file());
+    if !level.is_enabled_for(&target) {
         return;
     }
-    write_level(level, &format_structured_event(event, fields));
+    write_event(level, &target, event, fields);
 }
 
+#[track_caller]
 pub fn event_rate_limited<K, V, I>(
     level: LogLevel,
     rate_key: &str,
@@ -362,7 +515,8 @@ pub fn event_rate_limited<K, V, I>(
     V: ToString,
     I: IntoIterator<Item = (K, V)>,
 {
-    if !level.is_enabled() {
+    let target = target_from_file(Location::caller().file());
+    if !level.is_enabled_for(&target) {
         return;
     }
 
@@ -406,18 +560,43 @@ pub fn event_rate_limited<K, V, I>(
     if suppressed > 0 {
         fields.push(("suppressed".to_string(), suppressed.to_string()));
     }
-    write_level(level, &format_structured_event(event, fields));
+    write_event(level, &target, event, fields);
+}
+
+fn write_event<K, V, I>(level: LogLevel, target: &str, event: &str, fields: I)
+where
+    K: AsRef<str>,
+    V: ToString,
+    I: IntoIterator<Item = (K, V)>,
+{
+    let (event, ordered) = sanitize_event(event, fields);
+    let message = format_event_line(&event, &ordered);
+    write_level(
+        level,
+        level.as_str(),
+        target,
+        &message,
+        Some((&event, &ordered)),
+    );
 }
 
-fn write_level(level: LogLevel, message: &str) {
+fn write_level(
+    level: LogLevel,
+    label: &str,
+    target: &str,
+    message: &str,
+    event: Option<(&str, &BTreeMap<String, String>)>,
+) {
     if let Ok(mut guard) = LOGGER.lock()
         && let Some(logger) = guard.as_mut()
     {
-        logger.write(level.as_str(), message);
+        logger.write(level, label, target, message, event);
     }
 }
 
-fn format_structured_event<K, V, I>(event: &str, fields: I) -> String
+/// Sanitized event name and its fields, ordered by key, with credential-like
+/// values redacted.
+fn sanitize_event<K, V, I>(event: &str, fields: I) -> (String, BTreeMap<String, String>)
 where
     K: AsRef<str>,
     V: ToString,
@@ -431,22 +610,41 @@ where
         let value = redact_auth_field(raw_key, &value.to_string());
         ordered.insert(key, value);
     }
+    (event, ordered)
+}
 
+#[cfg(test)]
+fn format_structured_event<K, V, I>(event: &str, fields: I) -> String
+where
+    K: AsRef<str>,
+    V: ToString,
+    I: IntoIterator<Item = (K, V)>,
+{
+    let (event, ordered) = sanitize_event(event, fields);
+    format_event_line(&event, &ordered)
+}
+
+/// The mirror-log rendering of an event: `EVENT k=v ...`, or `EVENT_JSON`
+/// when `JCODE_LOG_JSON` is set.
+fn format_event_line(event: &str, ordered: &BTreeMap<String, String>) -> String {
     if structured_json_enabled() {
         let mut object = serde_json::Map::new();
-        object.insert("event".to_string(), serde_json::Value::String(event));
+        object.insert(
+            "event".to_string(),
+            serde_json::Value::String(event.to_string()),
+        );
         for (key, value) in ordered {
-            object.insert(key, serde_json::Value::String(value));
+            object.insert(key.clone(), serde_json::Value::String(value.clone()));
         }
         return format!("EVENT_JSON {}", serde_json::Value::Object(object));
     }
 
     let mut parts = Vec::with_capacity(ordered.len() + 1);
-    parts.push(format!("event={}", format_log_field_value(&event)));
+    parts.push(format!("event={}", format_log_field_value(event)));
     parts.extend(
         ordered
-            .into_iter()
-            .map(|(key, value)| format!("{}={}", key, format_log_field_value(&value))),
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, format_log_field_value(value))),
     );
     format!("EVENT {}", parts.join(" "))
 }
@@ -493,6 +691,7 @@ fn format_log_field_value(value: &str) -> String {
 /// Callers should pass only non-secret metadata. This function still redacts any
 /// field whose key looks credential-like so accidental tokens/keys do not land in
 /// logs.
+#[track_caller]
 pub fn auth_event(event: &str, provider: &str, fields: &[(&str, &str)]) {
     let mut parts = vec![
         format!("event={}", sanitize_log_value(event)),
@@ -506,18 +705,11 @@ pub fn auth_event(event: &str, provider: &str, fields: &[(&str, &str)]) {
         ));
     }
     let msg = format!("AUTH {}", parts.join(" "));
-    if let Ok(mut guard) = LOGGER.lock()
-        && let Some(logger) = guard.as_mut()
-    {
-        logger.write("AUTH", &msg);
-    }
+    write_level(LogLevel::Info, "AUTH", &caller_target(), &msg, None);
 }
 
 /// Log a tool call
-#[expect(
-    clippy::collapsible_if,
-    reason = "Logger lock + optional logger branching is intentionally straightforward"
-)]
+#[track_caller]
 pub fn tool_call(name: &str, input: &str, output: &str) {
     let msg = format!(
         "TOOL[{}] input={} output={}",
@@ -525,25 +717,14 @@ pub fn tool_call(name: &str, input: &str, output: &str) {
         truncate(input, 200),
         truncate(output, 500)
     );
-    if let Ok(mut guard) = LOGGER.lock() {
-        if let Some(logger) = guard.as_mut() {
-            logger.write("TOOL", &msg);
-        }
-    }
+    write_level(LogLevel::Info, "TOOL", &caller_target(), &msg, None);
 }
 
 /// Log a crash/panic for auto-debug
-#[expect(
-    clippy::collapsible_if,
-    reason = "Logger lock + optional logger branching is intentionally straightforward"
-)]
+#[track_caller]
 pub fn crash(error: &str, context: &str) {
     let msg = format!("CRASH: {} | Context: {}", error, context);
-    if let Ok(mut guard) = LOGGER.lock() {
-        if let Some(logger) = guard.as_mut() {
-            logger.write("CRASH", &msg);
-        }
-    }
+    write_level(LogLevel::Error, "CRASH", &caller_target(), &msg, None);
 }
 
 /// Get the session ID from the current logging context (thread-local or task-local).
@@ -561,7 +742,8 @@ pub fn log_path() -> Option<PathBuf> {
     Some(log_dir.join(format!("jcode-{}.log", date)))
 }
 
-/// Remove daily `jcode-*.log` / `jcode-desktop-*.log` files older than 7 days.
+/// Remove daily `jcode-*.log` / `jcode-*.jsonl` / `jcode-desktop-*.log` files
+/// (including rotated segments) older than 7 days.
 ///
 /// Scoped deliberately to the date-stamped log files this logger produces. The
 /// log directory also holds non-log data (e.g. `memory/`, `memory-events-*.jsonl`)
@@ -584,7 +766,7 @@ fn cleanup_old_logs_in(log_dir: &std::path::Path, now: chrono::DateTime<Local>)
         let name = entry.file_name();
         let Some(name) = name.to_str() else { continue };
         let is_jcode_log = (name.starts_with("jcode-") || name.starts_with("jcode-desktop-"))
-            && name.ends_with(".log");
+            && (name.ends_with(".log") || name.ends_with(".jsonl"));
         if !is_jcode_log {
             continue;
         }
@@ -763,6 +945,8 @@ mod tests {
         // Old log files that SHOULD be deleted.
         let old_log = write("jcode-2000-01-01.log", true);
         let old_desktop = write("jcode-desktop-2000-01-01.log", true);
+        let old_records = write("jcode-2000-01-01.jsonl", true);
+        let old_segment = write("jcode-2000-01-01.1.log", true);
         // Recent log file that SHOULD survive.
         let new_log = write("jcode-2099-01-01.log", false);
         // Non-log data that SHOULD survive even though it is old.
@@ -776,6 +960,14 @@ mod tests {
 
         assert!(!old_log.exists(), "old jcode log should be deleted");
         assert!(!old_desktop.exists(), "old desktop log should be deleted");
+        assert!(
+            !old_records.exists(),
+            "old structured log should be deleted"
+        );
+        assert!(
+            !old_segment.exists(),
+            "old rotated segment should be deleted"
+        );
         assert!(new_log.exists(), "recent jcode log must survive");
         assert!(old_memory.exists(), "memory-events jsonl must survive");
         assert!(old_other.exists(), "unrelated files must survive");
@@ -783,4 +975,78 @@ mod tests {
 
         fs::remove_dir_all(&dir).ok();
     }
+
+    #[test]
+    fn rotation_moves_the_live_file_to_the_next_segment() {
+        let dir = std::env::temp_dir().join(format!(
+            "jcode-log-rotate-test-{}-{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::SystemTime::UNIX_EPOCH)
+                .map(|d| d.as_nanos())
+                .unwrap_or(0)
+        ));
+        fs::create_dir_all(&dir).expect("create temp log dir");
+        let live = dir.join("jcode-2026-10-17.log");
+
+        let mut file = LogFile::open(live.clone()).expect("open live log");
+        file.append("first\n");
+        file.rotate();
+        file.append("second\n");
+        file.rotate();
+
+        assert_eq!(
+            rotated_log_path(&live, 1),
+            dir.join("jcode-2026-10-17.1.log")
+        );
+        assert_eq!(
+            fs::read_to_string(rotated_log_path(&live, 1)).unwrap(),
+            "first\n"
+        );
+        assert_eq!(
+            fs::read_to_string(rotated_log_path(&live, 2)).unwrap(),
+            "second\n"
+        );
+        assert_eq!(file.len, 0);
+        assert_eq!(fs::read_to_string(&live).unwrap(), "");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn rotation_past_the_segment_limit_keeps_the_newest_in_order() {
+        let dir = std::env::temp_dir().join(format!(
+            "jcode-log-rotate-limit-test-{}-{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::SystemTime::UNIX_EPOCH)
+                .map(|d| d.as_nanos())
+                .unwrap_or(0)
+        ));
+        fs::create_dir_all(&dir).expect("create temp log dir");
+        let live = dir.join("jcode-2026-10-17.log");
+
+        let mut file = LogFile::open(live.clone()).expect("open live log");
+        let rotations = MAX_ROTATED_SEGMENTS + 3;
+        for rotation in 1..=rotations {
+            file.append(&format!("segment {rotation}\n"));
+            file.rotate();
+        }
+
+        let indices = rotated_log_indices(&live);
+        assert_eq!(
+            indices,
+            ((rotations - MAX_ROTATED_SEGMENTS + 1)..=rotations).collect::<Vec<_>>()
+        );
+        let contents: Vec<String> = indices
+            .iter()
+            .map(|index| fs::read_to_string(rotated_log_path(&live, *index)).unwrap())
+            .collect();
+        let expected: Vec<String> = ((rotations - MAX_ROTATED_SEGMENTS + 1)..=rotations)
+            .map(|rotation| format!("segment {rotation}\n"))
+            .collect();
+        assert_eq!(contents, expected);
+
+        fs::remove_dir_all(&dir).ok();
+    }
 }
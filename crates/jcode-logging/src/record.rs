@@ -0,0 +1,238 @@
+//! Structured log records, written one per line to `jcode-<date>.jsonl`
+//! alongside the human-readable `jcode-<date>.log` mirror.
+
+use super::{LogLevel, log_dir};
+use chrono::{Local, NaiveDate};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogRecord {
+    /// RFC 3339 local time with milliseconds
+    pub timestamp: String,
+    pub level: LogLevel,
+    /// Special line kinds (`tool`, `auth`, `crash`) logged at `level`
+    pub kind: Option<String>,
+    /// Module path of the call site, e.g. `provider::openai`
+    pub target: String,
+    pub session_id: Option<String>,
+    pub server: Option<String>,
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    /// Free text, or the event name for structured events
+    pub message: String,
+    pub fields: BTreeMap<String, String>,
+}
+
+impl LogRecord {
+    pub fn to_json_line(&self) -> String {
+        let mut object = Map::new();
+        object.insert("ts".to_string(), self.timestamp.clone().into());
+        object.insert("level".to_string(), self.level.name().into());
+        let optional = [
+            ("kind", &self.kind),
+            ("session_id", &self.session_id),
+            ("server", &self.server),
+            ("provider", &self.provider),
+            ("model", &self.model),
+        ];
+        for (key, value) in optional {
+            if let Some(value) = value {
+                object.insert(key.to_string(), value.clone().into());
+            }
+        }
+        object.insert("target".to_string(), self.target.clone().into());
+        object.insert("message".to_string(), self.message.clone().into());
+        if !self.fields.is_empty() {
+            let fields = self
+                .fields
+                .iter()
+                .map(|(key, value)| (key.clone(), Value::String(value.clone())))
+                .collect();
+            object.insert("fields".to_string(), Value::Object(fields));
+        }
+        Value::Object(object).to_string()
+    }
+
+    pub fn parse(line: &str) -> Option<Self> {
+        let value: Value = serde_json::from_str(line).ok()?;
+        let text = |key: &str| value.get(key).and_then(Value::as_str).map(str::to_string);
+        Some(Self {
+            timestamp: text("ts")?,
+            level: LogLevel::parse(value.get("level")?.as_str()?)?,
+            kind: text("kind"),
+            target: text("target").unwrap_or_default(),
+            session_id: text("session_id"),
+            server: text("server"),
+            provider: text("provider"),
+            model: text("model"),
+            message: text("message").unwrap_or_default(),
+            fields: value
+                .get("fields")
+                .and_then(Value::as_object)
+                .map(|fields| {
+                    fields
+                        .iter()
+                        .map(|(key, value)| {
+                            let value = value
+                                .as_str()
+                                .map(str::to_string)
+                                .unwrap_or_else(|| value.to_string());
+                            (key.clone(), value)
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+        })
+    }
+
+    /// One line for `jcode logs`: time, level, target, session, message, fields.
+    pub fn format_pretty(&self) -> String {
+        let time = chrono::DateTime::parse_from_rfc3339(&self.timestamp)
+            .map(|ts| ts.format("%m-%d %H:%M:%S%.3f").to_string())
+            .unwrap_or_else(|_| self.timestamp.clone());
+        let label = self
+            .kind
+            .as_deref()
+            .map(str::to_ascii_uppercase)
+            .unwrap_or_else(|| self.level.as_str().to_string());
+        let mut line = format!("{} {:<5} {}", time, label, self.target);
+        if let Some(session) = &self.session_id {
+            line.push_str(&format!(" [{}]", session));
+        }
+        line.push(' ');
+        line.push_str(&self.message);
+        for (key, value) in &self.fields {
+            line.push_str(&format!(
+                " {}={}",
+                key,
+                super::format_log_field_value(value)
+            ));
+        }
+        line
+    }
+}
+
+/// Which records `jcode logs` shows.
+#[derive(Clone, Debug, Default)]
+pub struct RecordFilter {
+    pub session_id: Option<String>,
+    /// Least severe level shown
+    pub min_level: Option<LogLevel>,
+    /// Module prefix, e.g. `provider`
+    pub target: Option<String>,
+}
+
+impl RecordFilter {
+    pub fn matches(&self, record: &LogRecord) -> bool {
+        if let Some(session_id) = &self.session_id
+            && record.session_id.as_deref() != Some(session_id.as_str())
+        {
+            return false;
+        }
+        if let Some(min_level) = self.min_level
+            && record.level.severity() > min_level.severity()
+        {
+            return false;
+        }
+        if let Some(target) = &self.target
+            && !record.target.starts_with(target.as_str())
+        {
+            return false;
+        }
+        true
+    }
+}
+
+pub(crate) fn structured_log_path_for(log_dir: &Path, date: NaiveDate) -> PathBuf {
+    log_dir.join(format!("jcode-{}.jsonl", date.format("%Y-%m-%d")))
+}
+
+/// Today's structured log file
+pub fn structured_log_path() -> Option<PathBuf> {
+    Some(structured_log_path_for(
+        &log_dir()?,
+        Local::now().date_naive(),
+    ))
+}
+
+/// All structured log files for `date`, oldest first: rotated segments, then
+/// the live file.
+pub fn structured_log_segments(date: NaiveDate) -> Vec<PathBuf> {
+    let Some(log_dir) = log_dir() else {
+        return Vec::new();
+    };
+    let live = structured_log_path_for(&log_dir, date);
+    let mut segments: Vec<PathBuf> = super::rotated_log_indices(&live)
+        .into_iter()
+        .map(|index| super::rotated_log_path(&live, index))
+        .collect();
+    if live.exists() {
+        segments.push(live);
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record() -> LogRecord {
+        LogRecord {
+            timestamp: "2026-10-17T09:30:00.125+02:00".to_string(),
+            level: LogLevel::Warn,
+            kind: None,
+            target: "provider::openai".to_string(),
+            session_id: Some("session_fox_1".to_string()),
+            server: None,
+            provider: Some("openai".to_string()),
+            model: None,
+            message: "stream_retry".to_string(),
+            fields: BTreeMap::from([("attempt".to_string(), "2".to_string())]),
+        }
+    }
+
+    #[test]
+    fn records_round_trip_through_json_lines() {
+        let line = record().to_json_line();
+        assert!(line.contains("\"level\":\"warn\""));
+        assert!(!line.contains("\"server\""));
+        assert_eq!(LogRecord::parse(&line), Some(record()));
+        assert_eq!(LogRecord::parse("[12:00] [INFO] plain text"), None);
+    }
+
+    #[test]
+    fn filter_matches_session_level_and_target() {
+        let record = record();
+        let by_session = RecordFilter {
+            session_id: Some("session_fox_1".to_string()),
+            ..RecordFilter::default()
+        };
+        assert!(by_session.matches(&record));
+        let other_session = RecordFilter {
+            session_id: Some("session_owl_2".to_string()),
+            ..RecordFilter::default()
+        };
+        assert!(!other_session.matches(&record));
+        let errors_only = RecordFilter {
+            min_level: Some(LogLevel::Error),
+            ..RecordFilter::default()
+        };
+        assert!(!errors_only.matches(&record));
+        let provider = RecordFilter {
+            target: Some("provider".to_string()),
+            min_level: Some(LogLevel::Warn),
+            ..RecordFilter::default()
+        };
+        assert!(provider.matches(&record));
+    }
+
+    #[test]
+    fn pretty_lines_show_level_target_session_and_fields() {
+        assert_eq!(
+            record().format_pretty(),
+            "10-17 09:30:00.125 WARN  provider::openai [session_fox_1] stream_retry attempt=2"
+        );
+    }
+}
@@ -655,6 +655,40 @@ impl MemoryGraph {
         graph
     }
 
+    /// Add every memory from `other` whose ID this graph does not have yet,
+    /// with its tags and its links to memories now present. Existing memories
+    /// are left untouched, matching `jcode memory import` without
+    /// `--overwrite`. Returns the number of memories added.
+    pub fn merge_missing_from(&mut self, other: &MemoryGraph) -> usize {
+        let mut ids: Vec<&String> = other.memories.keys().collect();
+        ids.sort();
+        let mut added = std::collections::HashSet::new();
+        for id in ids {
+            if !self.memories.contains_key(id) {
+                self.add_memory(other.memories[id].clone());
+                added.insert(id.as_str());
+            }
+        }
+        for (from, edges) in &other.edges {
+            for edge in edges {
+                let links_memory = matches!(
+                    edge.kind,
+                    EdgeKind::RelatesTo { .. } | EdgeKind::Contradicts | EdgeKind::DerivedFrom
+                );
+                let touches_added =
+                    added.contains(from.as_str()) || added.contains(edge.target.as_str());
+                if links_memory
+                    && touches_added
+                    && self.memories.contains_key(from)
+                    && self.memories.contains_key(&edge.target)
+                {
+                    self.add_edge(from, &edge.target, edge.kind.clone());
+                }
+            }
+        }
+        added.len()
+    }
+
     /// Check if this graph was migrated from legacy format
     pub fn is_migrated(&self) -> bool {
         self.graph_version == GRAPH_VERSION
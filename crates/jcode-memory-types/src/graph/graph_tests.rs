@@ -311,3 +311,33 @@ fn test_graph_serialization_roundtrip() {
         "Edge count should match after roundtrip"
     );
 }
+
+#[test]
+fn test_merge_missing_from_keeps_existing_and_links_new() {
+    let mut local = MemoryGraph::new();
+    let shared = make_test_memory("Local wording");
+    let shared_id = local.add_memory(shared.clone());
+
+    let mut incoming = MemoryGraph::new();
+    let mut changed = shared;
+    changed.content = "Backup wording".to_string();
+    incoming.add_memory(changed);
+    let new_id =
+        incoming.add_memory(make_test_memory("Only in backup").with_tags(vec!["ci".into()]));
+    incoming.link_memories(&new_id, &shared_id, 0.7);
+
+    assert_eq!(local.merge_missing_from(&incoming), 1);
+    assert_eq!(
+        local.get_memory(&shared_id).unwrap().content,
+        "Local wording"
+    );
+    assert!(local.tags.contains_key("tag:ci"));
+    assert!(
+        local
+            .get_edges(&new_id)
+            .iter()
+            .any(|edge| edge.target == shared_id)
+    );
+
+    assert_eq!(local.merge_missing_from(&incoming), 0);
+}
@@ -650,7 +650,7 @@ fn selected_entries_for_prompt(entries: &[MemoryEntry], limit: usize) -> Vec<&Me
 }
 
 pub fn format_entries_for_prompt(entries: &[MemoryEntry], limit: usize) -> Option<String> {
-    format_entries_for_prompt_with_header(entries, limit, false, false)
+    format_entries_for_prompt_with_header(entries, limit, false, false, false)
 }
 
 pub fn format_relevant_prompt(entries: &[MemoryEntry], limit: usize) -> Option<String> {
@@ -658,7 +658,31 @@ pub fn format_relevant_prompt(entries: &[MemoryEntry], limit: usize) -> Option<S
 }
 
 pub fn format_relevant_display_prompt(entries: &[MemoryEntry], limit: usize) -> Option<String> {
-    format_entries_for_prompt_with_header(entries, limit, true, true)
+    format_entries_for_prompt_with_header(entries, limit, true, true, false)
+}
+
+/// Like [`format_relevant_prompt`], with a provenance line under each memory.
+pub fn format_injection_prompt(entries: &[MemoryEntry], limit: usize) -> Option<String> {
+    format_entries_for_prompt_with_header(entries, limit, true, false, true)
+}
+
+/// Like [`format_relevant_display_prompt`], with a provenance line under each
+/// memory.
+pub fn format_injection_display_prompt(entries: &[MemoryEntry], limit: usize) -> Option<String> {
+    format_entries_for_prompt_with_header(entries, limit, true, true, true)
+}
+
+/// One-line origin of a memory: where it came from, when it last changed, and
+/// how often it was reinforced.
+pub fn memory_provenance(entry: &MemoryEntry) -> String {
+    let mut parts = vec![
+        format!("source: {}", entry.source.as_deref().unwrap_or("manual")),
+        format!("updated {}", entry.updated_at.format("%Y-%m-%d")),
+    ];
+    if entry.strength > 1 {
+        parts.push(format!("reinforced {}×", entry.strength));
+    }
+    format!("({})", parts.join(" · "))
 }
 
 fn format_entries_for_prompt_with_header(
@@ -666,6 +690,7 @@ fn format_entries_for_prompt_with_header(
     limit: usize,
     include_header: bool,
     include_updated_at_comments: bool,
+    include_provenance: bool,
 ) -> Option<String> {
     let mut sections: HashMap<MemoryCategory, Vec<&MemoryEntry>> = HashMap::new();
 
@@ -695,6 +720,9 @@ fn format_entries_for_prompt_with_header(
         output.push_str(&format!("## {title}\n"));
         for (idx, item) in items.into_iter().enumerate() {
             output.push_str(&format!("{}. {}\n", idx + 1, item.content.trim()));
+            if include_provenance {
+                output.push_str(&format!("   {}\n", memory_provenance(item)));
+            }
             if include_updated_at_comments {
                 output.push_str(&format!(
                     "<!-- updated_at: {} -->\n",
@@ -173,6 +173,119 @@ pub enum ContentBlock {
     OpenAICompaction {
         encrypted_content: String,
     },
+    /// Provider-executed tool call (Anthropic server tools such as
+    /// `web_search`). jcode never runs it locally; it is kept so the call can
+    /// be replayed alongside its result on later turns.
+    ServerToolUse {
+        id: String,
+        name: String,
+        input: serde_json::Value,
+    },
+    /// Result of a server-side `web_search` call. `error_code` is set instead of
+    /// `results` when the search failed on the provider side.
+    WebSearchResult {
+        tool_use_id: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        results: Vec<WebSearchHit>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error_code: Option<String>,
+    },
+    /// Source citations the provider attached to this message's text. Display
+    /// only; never replayed to any provider.
+    Citations {
+        citations: Vec<Citation>,
+    },
+}
+
+/// One hit returned by a provider-side web search.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct WebSearchHit {
+    pub url: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_age: Option<String>,
+    /// Opaque page content the provider requires when the result is replayed.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub encrypted_content: String,
+}
+
+/// A source the provider cited for part of an assistant response.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Citation {
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cited_text: Option<String>,
+}
+
+impl WebSearchHit {
+    /// Bytes held by this hit, for context and memory accounting.
+    pub fn payload_bytes(&self) -> usize {
+        self.url.len()
+            + self.title.len()
+            + self.page_age.as_ref().map(String::len).unwrap_or(0)
+            + self.encrypted_content.len()
+    }
+}
+
+impl Citation {
+    /// Bytes held by this citation, for memory accounting.
+    pub fn payload_bytes(&self) -> usize {
+        self.url.len()
+            + self.title.as_ref().map(String::len).unwrap_or(0)
+            + self.cited_text.as_ref().map(String::len).unwrap_or(0)
+    }
+}
+
+/// Plain-text listing of web search hits, shown wherever a provider-side search
+/// stands in for a regular tool result.
+pub fn web_search_result_text(results: &[WebSearchHit], error_code: Option<&str>) -> String {
+    if let Some(code) = error_code {
+        return format!("Web search failed: {code}");
+    }
+    if results.is_empty() {
+        return "No web search results".to_string();
+    }
+    results
+        .iter()
+        .enumerate()
+        .map(|(idx, hit)| {
+            let title = hit.title.trim();
+            let title = if title.is_empty() { &hit.url } else { title };
+            format!("{}. {}\n   {}", idx + 1, title, hit.url)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Markdown "Sources" footer listing each cited URL once, in first-cited order.
+/// Empty when there is nothing to cite.
+pub fn citation_sources_markdown(citations: &[Citation]) -> String {
+    let mut seen = std::collections::HashSet::new();
+    let mut out = String::new();
+    for citation in citations {
+        if !seen.insert(citation.url.as_str()) {
+            continue;
+        }
+        let title = citation
+            .title
+            .as_deref()
+            .map(str::trim)
+            .filter(|title| !title.is_empty())
+            .unwrap_or(&citation.url);
+        out.push_str(&format!(
+            "\n{}. [{}]({})",
+            seen.len(),
+            title.replace(']', "\\]"),
+            citation.url
+        ));
+    }
+    if out.is_empty() {
+        return out;
+    }
+    format!("\n\n**Sources**{out}")
 }
 
 impl Message {
@@ -562,6 +675,53 @@ pub struct InputShellResult {
     pub failed_to_start: bool,
 }
 
+/// What a request pinned for reproducible runs (`[provider] deterministic`),
+/// recorded with each response so eval harnesses can assert on it.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct DeterminismParams {
+    /// Model id sent to the API, the dated snapshot where one is known
+    pub model: String,
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    #[serde(default)]
+    pub top_p: Option<f32>,
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Backend configuration the API reported (OpenAI `system_fingerprint`)
+    #[serde(default)]
+    pub system_fingerprint: Option<String>,
+    /// Parts of deterministic mode the provider could not honor
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unsupported: Vec<String>,
+}
+
+impl DeterminismParams {
+    /// Sent as `model` with no sampling parameters pinned.
+    pub fn unsupported(model: impl Into<String>) -> Self {
+        Self {
+            model: model.into(),
+            unsupported: vec![
+                "temperature".to_string(),
+                "top_p".to_string(),
+                "seed".to_string(),
+            ],
+            ..Self::default()
+        }
+    }
+
+    /// Warning naming the parts `provider` could not honor, if any.
+    pub fn warning(&self, provider: &str) -> Option<String> {
+        (!self.unsupported.is_empty()).then(|| {
+            format!(
+                "deterministic mode: {} cannot pin {} for {}",
+                provider,
+                self.unsupported.join(", "),
+                self.model
+            )
+        })
+    }
+}
+
 /// Connection phase for status bar transparency.
 #[derive(Debug, Clone, PartialEq)]
 pub enum ConnectionPhase {
@@ -596,6 +756,11 @@ impl std::fmt::Display for ConnectionPhase {
 pub enum StreamEvent {
     /// Text content delta
     TextDelta(String),
+    /// Text content delta at byte `offset` into the text this response has
+    /// streamed. Providers that can re-deliver text after a transparent
+    /// reconnect send this so consumers can drop the overlap; feed events
+    /// through a [`StreamTextCursor`] to turn them back into `TextDelta`s.
+    TextDeltaAt { offset: usize, text: String },
     /// Tool use started
     ToolUseStart { id: String, name: String },
     /// Tool input delta (JSON fragment)
@@ -667,6 +832,9 @@ pub enum StreamEvent {
     },
     /// Provider session ID (for conversation resume)
     SessionId(String),
+    /// The response is being generated server-side in background mode under
+    /// this id, so a restarted jcode can re-attach to it until it finishes.
+    BackgroundResponse { response_id: String },
     /// Compaction occurred (context was summarized)
     Compaction {
         trigger: String,
@@ -676,18 +844,128 @@ pub enum StreamEvent {
     },
     /// Upstream provider info (e.g., which provider OpenRouter routed to)
     UpstreamProvider { provider: String },
+    /// Backend configuration that served the response (OpenAI
+    /// `system_fingerprint`), for reproducible-run metadata
+    SystemFingerprint(String),
     /// Native tool call from a provider bridge that needs execution by jcode
     NativeToolCall {
         request_id: String,
         tool_name: String,
         input: serde_json::Value,
     },
+    /// Provider-executed (server-side) tool call with its complete input
+    ServerToolUse {
+        id: String,
+        name: String,
+        input: serde_json::Value,
+    },
+    /// Result of a provider-executed web search
+    WebSearchResult {
+        tool_use_id: String,
+        results: Vec<WebSearchHit>,
+        error_code: Option<String>,
+    },
+    /// Source citation attached to the text currently being streamed
+    Citation(Citation),
+    /// Server-side tool invocations billed for this response
+    ServerToolUsage { web_search_requests: u64 },
+}
+
+/// How much text a response stream has delivered, so chunks a provider
+/// re-sends after reconnecting are trimmed instead of duplicated.
+#[derive(Debug, Clone, Default)]
+pub struct StreamTextCursor {
+    received: usize,
+}
+
+impl StreamTextCursor {
+    /// Bytes of text delivered so far.
+    pub fn received(&self) -> usize {
+        self.received
+    }
+
+    /// Pass `event` on with [`StreamEvent::TextDeltaAt`] rewritten to a
+    /// `TextDelta` of only the text not delivered yet, or `None` when all of
+    /// it was. A chunk past the end of what was delivered is kept whole: the
+    /// gap is lost, and duplicating nothing beats stalling the stream.
+    pub fn normalize(&mut self, event: StreamEvent) -> Option<StreamEvent> {
+        match event {
+            StreamEvent::TextDelta(text) => {
+                self.received += text.len();
+                Some(StreamEvent::TextDelta(text))
+            }
+            StreamEvent::TextDeltaAt { offset, mut text } => {
+                let end = offset + text.len();
+                if end <= self.received {
+                    return None;
+                }
+                let mut overlap = self.received.saturating_sub(offset);
+                while !text.is_char_boundary(overlap) {
+                    overlap += 1;
+                }
+                text.drain(..overlap);
+                self.received = end;
+                Some(StreamEvent::TextDelta(text))
+            }
+            StreamEvent::RetryRollback { .. } => {
+                self.received = 0;
+                Some(event)
+            }
+            other => Some(other),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn assemble(events: Vec<StreamEvent>) -> String {
+        let mut cursor = StreamTextCursor::default();
+        let mut text = String::new();
+        for event in events {
+            match cursor.normalize(event) {
+                Some(StreamEvent::TextDelta(delta)) => text.push_str(&delta),
+                Some(StreamEvent::RetryRollback { .. }) => text.clear(),
+                _ => {}
+            }
+        }
+        text
+    }
+
+    fn at(offset: usize, text: &str) -> StreamEvent {
+        StreamEvent::TextDeltaAt {
+            offset,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn text_cursor_drops_replayed_and_overlapping_chunks() {
+        let events = vec![
+            at(0, "The quick "),
+            at(10, "brown fox"),
+            // Reconnect replays the last chunk, then resends with overlap.
+            at(10, "brown fox"),
+            at(4, "quick brown fox jumps"),
+            at(25, " over"),
+            at(0, "The"),
+        ];
+        assert_eq!(assemble(events), "The quick brown fox jumps over");
+    }
+
+    #[test]
+    fn text_cursor_keeps_plain_deltas_and_resets_on_rollback() {
+        let events = vec![
+            StreamEvent::TextDelta("héllo".to_string()),
+            at(1, "éllo wörld"),
+            StreamEvent::RetryRollback { attempt: 2, max: 3 },
+            at(0, "fresh"),
+            at(3, "sh start"),
+        ];
+        assert_eq!(assemble(events), "fresh start");
+    }
+
     fn text_of(message: &Message) -> &str {
         match message.content.first() {
             Some(ContentBlock::Text { text, .. }) => text,
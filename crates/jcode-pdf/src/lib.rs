@@ -4,3 +4,8 @@ use std::path::Path;
 pub fn extract_text(path: &Path) -> Result<String> {
     Ok(pdf_extract::extract_text(path)?)
 }
+
+/// Text of each page, in order.
+pub fn extract_pages(path: &Path) -> Result<Vec<String>> {
+    Ok(pdf_extract::extract_text_by_pages(path)?)
+}
@@ -0,0 +1,227 @@
+//! Plan-mode proposals.
+//!
+//! While a session is in plan mode the agent may only inspect the workspace
+//! and must describe what it intends to do through the `plan_propose` tool.
+//! The resulting [`PlanProposal`] is stored on the session together with the
+//! user's decision so the approved plan can be replayed as context.
+
+use serde::{Deserialize, Serialize};
+
+/// Tools the agent may use while plan mode is active.
+pub const PLAN_MODE_TOOLS: &[&str] = &["read", "agentgrep", "ls", "tree", "plan_propose"];
+
+/// Name of the tool the agent uses to submit a plan.
+pub const PLAN_PROPOSE_TOOL: &str = "plan_propose";
+
+/// A structured plan submitted by the agent for approval.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PlanProposal {
+    pub title: String,
+    pub steps: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub commands: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub risks: Vec<String>,
+}
+
+impl PlanProposal {
+    /// Render the proposal as markdown inside a ```` ```plan ```` fence so the
+    /// TUI shows it as a plan card.
+    pub fn to_markdown(&self) -> String {
+        format!("```plan\n{}```", self.markdown_body())
+    }
+
+    /// Render the proposal as plain markdown, without the plan fence.
+    pub fn markdown_body(&self) -> String {
+        let mut out = String::new();
+        let title = self.title.trim();
+        out.push_str("# ");
+        out.push_str(if title.is_empty() { "Plan" } else { title });
+        out.push('\n');
+        for (index, step) in self.steps.iter().enumerate() {
+            out.push_str(&format!("{}. {}\n", index + 1, step.trim()));
+        }
+        push_section(&mut out, "Files", &self.files);
+        push_section(&mut out, "Commands", &self.commands);
+        push_section(&mut out, "Risks", &self.risks);
+        out
+    }
+}
+
+fn push_section(out: &mut String, heading: &str, items: &[String]) {
+    if items.is_empty() {
+        return;
+    }
+    out.push_str(&format!("\n## {heading}\n"));
+    for item in items {
+        out.push_str(&format!("- {}\n", item.trim()));
+    }
+}
+
+/// The user's verdict on the latest proposal.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanDecision {
+    Approve,
+    Revise,
+    Reject,
+}
+
+/// Review state of the latest proposal.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanProposalStatus {
+    #[default]
+    Pending,
+    Approved,
+    Rejected,
+}
+
+/// Plan-mode state persisted on a session.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SessionPlanMode {
+    /// Whether tool use is currently restricted to [`PLAN_MODE_TOOLS`].
+    pub active: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proposal: Option<PlanProposal>,
+    #[serde(default)]
+    pub status: PlanProposalStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proposed_at_unix_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decided_at_unix_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feedback: Option<String>,
+}
+
+impl SessionPlanMode {
+    /// Record a freshly proposed plan, replacing any previous one.
+    pub fn record_proposal(&mut self, proposal: PlanProposal, now_unix_ms: u64) {
+        self.proposal = Some(proposal);
+        self.status = PlanProposalStatus::Pending;
+        self.proposed_at_unix_ms = Some(now_unix_ms);
+        self.decided_at_unix_ms = None;
+        self.feedback = None;
+    }
+
+    /// Apply the user's decision. Approving or rejecting leaves plan mode;
+    /// asking for a revision keeps it active so the agent can re-propose.
+    pub fn decide(&mut self, decision: PlanDecision, feedback: Option<String>, now_unix_ms: u64) {
+        self.feedback = feedback.filter(|text| !text.trim().is_empty());
+        self.decided_at_unix_ms = Some(now_unix_ms);
+        match decision {
+            PlanDecision::Approve => {
+                self.status = PlanProposalStatus::Approved;
+                self.active = false;
+            }
+            PlanDecision::Revise => {
+                self.status = PlanProposalStatus::Pending;
+                self.active = true;
+            }
+            PlanDecision::Reject => {
+                self.status = PlanProposalStatus::Rejected;
+                self.active = false;
+            }
+        }
+    }
+}
+
+/// System-prompt addendum appended while plan mode is active.
+pub fn plan_mode_system_prompt() -> &'static str {
+    "# Plan Mode\n\
+     Plan mode is active. Do not modify files or run commands. Investigate the \
+     workspace with the read, agentgrep, and ls tools only, then call \
+     `plan_propose` exactly once with the ordered steps, the files you expect to \
+     touch, the commands you intend to run, and any risks. After calling \
+     `plan_propose`, stop and wait for the user to approve, revise, or reject \
+     the plan."
+}
+
+/// Message sent back to the agent once the user has decided on a plan.
+pub fn plan_decision_prompt(
+    decision: PlanDecision,
+    plan_markdown: Option<&str>,
+    feedback: Option<&str>,
+) -> String {
+    let feedback = feedback.map(str::trim).filter(|text| !text.is_empty());
+    let mut out = match decision {
+        PlanDecision::Approve => String::from(
+            "The plan below was approved and plan mode is now off. Execute it step by step.",
+        ),
+        PlanDecision::Revise => String::from(
+            "The plan needs changes. Plan mode is still active: address the feedback and call `plan_propose` again.",
+        ),
+        PlanDecision::Reject => {
+            String::from("The plan was rejected and plan mode is now off. Do not carry it out.")
+        }
+    };
+    if let Some(feedback) = feedback {
+        out.push_str("\n\nFeedback: ");
+        out.push_str(feedback);
+    }
+    if decision == PlanDecision::Approve
+        && let Some(plan) = plan_markdown
+    {
+        out.push_str("\n\n");
+        out.push_str(plan.trim());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> PlanProposal {
+        PlanProposal {
+            title: "Add login".to_string(),
+            steps: vec!["Add route".to_string(), "Add tests".to_string()],
+            files: vec!["src/auth.rs".to_string()],
+            commands: vec!["cargo test".to_string()],
+            risks: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn markdown_renders_plan_fence_with_sections() {
+        let markdown = sample().to_markdown();
+        assert!(markdown.starts_with("```plan\n# Add login\n1. Add route\n2. Add tests\n"));
+        assert!(markdown.contains("## Files\n- src/auth.rs\n"));
+        assert!(markdown.contains("## Commands\n- cargo test\n"));
+        assert!(!markdown.contains("## Risks"));
+        assert!(markdown.ends_with("```"));
+    }
+
+    #[test]
+    fn decisions_update_mode_and_status() {
+        let mut mode = SessionPlanMode {
+            active: true,
+            ..Default::default()
+        };
+        mode.record_proposal(sample(), 10);
+        assert_eq!(mode.status, PlanProposalStatus::Pending);
+
+        mode.decide(PlanDecision::Revise, Some("smaller".to_string()), 20);
+        assert!(mode.active);
+        assert_eq!(mode.feedback.as_deref(), Some("smaller"));
+
+        mode.decide(PlanDecision::Approve, None, 30);
+        assert!(!mode.active);
+        assert_eq!(mode.status, PlanProposalStatus::Approved);
+        assert_eq!(mode.decided_at_unix_ms, Some(30));
+    }
+
+    #[test]
+    fn approval_prompt_includes_plan() {
+        let plan = sample().to_markdown();
+        let prompt = plan_decision_prompt(PlanDecision::Approve, Some(&plan), None);
+        assert!(prompt.contains("approved"));
+        assert!(prompt.contains("```plan"));
+
+        let prompt = plan_decision_prompt(PlanDecision::Reject, Some(&plan), Some("no"));
+        assert!(prompt.contains("Feedback: no"));
+        assert!(!prompt.contains("```plan"));
+    }
+}
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+/// One model's answer in a `/compare` run.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CompareRun {
+    pub model: String,
+    #[serde(default)]
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_tokens: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_tokens: Option<u64>,
+    /// From sending the request to the end of the response stream.
+    pub latency_ms: u64,
+    /// Set when the model failed; `text` holds whatever arrived before.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl CompareRun {
+    /// Latency and token counts, e.g. "2.4s · 1200 in · 340 out".
+    pub fn stats_line(&self) -> String {
+        let mut parts = vec![format!("{:.1}s", self.latency_ms as f64 / 1000.0)];
+        if let Some(tokens) = self.input_tokens {
+            parts.push(format!("{} in", tokens));
+        }
+        if let Some(tokens) = self.output_tokens {
+            parts.push(format!("{} out", tokens));
+        }
+        if self.error.is_some() {
+            parts.push("failed".to_string());
+        }
+        parts.join(" · ")
+    }
+}
+
+/// Step of a `/compare` run
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum CompareAction {
+    /// Send `prompt` to every model with the session's context and no tools
+    Run { models: Vec<String>, prompt: String },
+    /// Add `prompt` and `runs[chosen]` to the conversation; the other runs
+    /// are kept as alternatives
+    Commit {
+        prompt: String,
+        chosen: usize,
+        runs: Vec<CompareRun>,
+    },
+}
@@ -10,22 +10,29 @@
 use serde::{Deserialize, Serialize};
 
 mod comm_format;
+mod compare;
 mod notifications;
+mod permission_inbox;
+mod turn_queue;
 
 pub use comm_format::*;
-pub use notifications::{FeatureToggle, NotificationType};
+pub use compare::{CompareAction, CompareRun};
+pub use notifications::{AsideAction, FeatureToggle, HandoffAction, NotificationType};
+pub use permission_inbox::{PermissionInboxEntry, PermissionOrigin};
+pub use turn_queue::{TurnQueueItem, TurnQueueKind};
 
 use jcode_batch_types::BatchProgress;
 use jcode_message_types::{InputShellResult, ToolCall};
 use jcode_plan::{PlanItem, VersionedPlan, next_runnable_item_ids, summarize_plan_graph};
+use jcode_session_types::StoredTokenUsage;
 use jcode_side_panel_types::{SidePanelSnapshot, snapshot_is_empty};
 
 #[path = "protocol_memory.rs"]
 mod memory_snapshots;
 
 pub use memory_snapshots::{
-    MemoryActivitySnapshot, MemoryPipelineSnapshot, MemoryStateSnapshot, MemoryStepResultSnapshot,
-    MemoryStepStatusSnapshot,
+    MemoryActivitySnapshot, MemoryInjectionReport, MemoryPipelineSnapshot, MemoryScoreBreakdown,
+    MemoryStateSnapshot, MemoryStepResultSnapshot, MemoryStepStatusSnapshot,
 };
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
@@ -55,6 +62,9 @@ pub struct HistoryMessage {
     pub tool_calls: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_data: Option<ToolCall>,
+    /// Model, timing and token usage behind an assistant message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<StoredTokenUsage>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -64,6 +74,34 @@ pub struct SessionActivitySnapshot {
     pub current_tool_name: Option<String>,
 }
 
+/// Per-request agent limits (`[agent]` config) and how close the request came
+/// to them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AgentLimitStatus {
+    /// Model calls made so far.
+    pub turns: u32,
+    /// Tool calls executed so far.
+    pub tool_calls: u32,
+    pub elapsed_secs: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_turns: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tool_calls_per_turn: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_wall_seconds: Option<u64>,
+    /// Config key of the limit that stopped the request, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub breached: Option<String>,
+}
+
+/// A skill the agent loaded on its own because its `triggers` matched.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ActiveSkillStatus {
+    pub name: String,
+    /// What matched, e.g. "file `infra/main.tf` matches `**/*.tf`".
+    pub trigger: String,
+}
+
 #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
 pub struct TokenUsageTotals {
     pub messages_with_token_usage: usize,
@@ -75,6 +113,22 @@ pub struct TokenUsageTotals {
     pub cache_reported_input_tokens: u64,
     pub cache_read_input_tokens: u64,
     pub cache_creation_input_tokens: u64,
+    /// Provider-side web searches billed across the session.
+    #[serde(default)]
+    pub web_search_requests: u64,
+    /// Estimated request tokens saved by superseded-read pruning.
+    #[serde(default)]
+    pub pruned_context_tokens: u64,
+    /// Values replaced by `[privacy]` redaction before reaching the provider.
+    #[serde(default)]
+    pub redactions: u64,
+    /// Tokens spent around the conversation on its behalf (subagents,
+    /// compaction, summaries, memory capture). Not part of `input_tokens` /
+    /// `output_tokens`, which cover the session's own turns.
+    #[serde(default)]
+    pub overhead_input_tokens: u64,
+    #[serde(default)]
+    pub overhead_output_tokens: u64,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -458,8 +512,13 @@ impl Request {
             Request::Message { id, .. } => *id,
             Request::Cancel { id } => *id,
             Request::BackgroundTool { id } => *id,
+            Request::CancelMcpCall { id } => *id,
             Request::SoftInterrupt { id, .. } => *id,
             Request::CancelSoftInterrupts { id } => *id,
+            Request::QueueMessage { id, .. } => *id,
+            Request::TurnQueue { id } => *id,
+            Request::TurnQueueRemove { id, .. } => *id,
+            Request::TurnQueueMove { id, .. } => *id,
             Request::Clear { id } => *id,
             Request::Rewind { id, .. } => *id,
             Request::RewindUndo { id } => *id,
@@ -483,13 +542,26 @@ impl Request {
             Request::SetModel { id, .. } => *id,
             Request::SetRoute { id, .. } => *id,
             Request::SetSubagentModel { id, .. } => *id,
+            Request::SetProfile { id, .. } => *id,
+            Request::SetSafeMode { id, .. } => *id,
+            Request::SetApprovalGate { id, .. } => *id,
             Request::RunSubagent { id, .. } => *id,
             Request::SetReasoningEffort { id, .. } => *id,
             Request::SetServiceTier { id, .. } => *id,
             Request::SetTransport { id, .. } => *id,
             Request::SetPremiumMode { id, .. } => *id,
             Request::SetFeature { id, .. } => *id,
+            Request::PlanDecision { id, .. } => *id,
+            Request::Aside { id, .. } => *id,
+            Request::Compare { id, .. } => *id,
+            Request::Handoff { id, .. } => *id,
+            Request::Environment { id, .. } => *id,
+            Request::PromptSections { id } => *id,
             Request::SetCompactionMode { id, .. } => *id,
+            Request::UpdateWorkspaceRoots { id, .. } => *id,
+            Request::SetToolScope { id, .. } => *id,
+            Request::SetInstructions { id, .. } => *id,
+            Request::SetMirror { id, .. } => *id,
             Request::RenameSession { id, .. } => *id,
             Request::Split { id } => *id,
             Request::Transfer { id } => *id,
@@ -499,6 +571,7 @@ impl Request {
             Request::SwitchAnthropicAccount { id, .. } => *id,
             Request::SwitchOpenAiAccount { id, .. } => *id,
             Request::StdinResponse { id, .. } => *id,
+            Request::PermissionDecision { id, .. } => *id,
             Request::AgentRegister { id, .. } => *id,
             Request::AgentTask { id, .. } => *id,
             Request::AgentCapabilities { id } => *id,
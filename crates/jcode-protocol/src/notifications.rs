@@ -40,4 +40,32 @@ pub enum FeatureToggle {
     Swarm,
     Autoreview,
     Autojudge,
+    Plan,
+    Autocommit,
+}
+
+/// Step in the lifecycle of a `/aside` scratch thread
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum AsideAction {
+    /// Open a new aside; later messages go to it until it is closed
+    Start,
+    /// Draft a summary of the open aside for the user to edit
+    Summarize,
+    /// Close the open aside, appending `summary` to the main conversation
+    Close {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        summary: Option<String>,
+    },
+}
+
+/// Step of a `/handoff` to a fresh session
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum HandoffAction {
+    /// Draft a summary of the session for the user to edit
+    Summarize,
+    /// Start the child session from the edited `summary` and leave a closing
+    /// note in this one
+    Create { summary: String },
 }
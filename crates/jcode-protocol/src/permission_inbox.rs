@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+
+/// Where a pending permission decision came from.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionOrigin {
+    /// Queued by `request_permission` during an ambient cycle.
+    Ambient,
+    /// Raised by a tool call in an interactive session (safe mode approvals,
+    /// clipboard reads).
+    Session,
+}
+
+/// One pending decision in the shared permission inbox.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PermissionInboxEntry {
+    pub request_id: String,
+    pub origin: PermissionOrigin,
+    /// Ambient cycle id or session id, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin_id: Option<String>,
+    pub action: String,
+    pub description: String,
+    /// Unix milliseconds.
+    pub created_at_ms: u64,
+    /// Unix milliseconds after which the request is dropped as denied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at_ms: Option<u64>,
+}
+
+impl PermissionInboxEntry {
+    /// "ambient cycle <id>" or "session <id>".
+    pub fn origin_label(&self) -> String {
+        let kind = match self.origin {
+            PermissionOrigin::Ambient => "ambient cycle",
+            PermissionOrigin::Session => "session",
+        };
+        match self.origin_id.as_deref() {
+            Some(id) => format!("{} {}", kind, id),
+            None => kind.to_string(),
+        }
+    }
+
+    /// Origin, age and expiry, e.g. "session abc · 2m old · expires in 13m".
+    pub fn status_line(&self, now_ms: u64) -> String {
+        let age = format_span(now_ms.saturating_sub(self.created_at_ms));
+        let expiry = match self.expires_at_ms {
+            Some(at) if at > now_ms => format!("expires in {}", format_span(at - now_ms)),
+            Some(_) => "expired".to_string(),
+            None => "no expiry".to_string(),
+        };
+        format!("{} · {} old · {}", self.origin_label(), age, expiry)
+    }
+}
+
+/// Compact duration such as "45s", "12m", "3h" or "2d".
+fn format_span(ms: u64) -> String {
+    let secs = ms / 1000;
+    if secs < 60 {
+        format!("{}s", secs)
+    } else if secs < 3600 {
+        format!("{}m", secs / 60)
+    } else if secs < 86_400 {
+        format!("{}h", secs / 3600)
+    } else {
+        format!("{}d", secs / 86_400)
+    }
+}
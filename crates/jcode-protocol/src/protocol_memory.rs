@@ -54,3 +54,35 @@ pub struct MemoryActivitySnapshot {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub pipeline: Option<MemoryPipelineSnapshot>,
 }
+
+/// Score breakdown of one memory considered for injection. Components are in
+/// 0..=1; `score` is their weighted mean under `[memory.weights]`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MemoryScoreBreakdown {
+    pub id: String,
+    pub category: String,
+    /// Memory content, shortened for display
+    pub preview: String,
+    pub similarity: f32,
+    pub recency: f32,
+    pub strength: f32,
+    pub category_weight: f32,
+    pub score: f32,
+    /// Approximate tokens the memory adds to the prompt
+    pub tokens: usize,
+    /// Why a candidate was left out; `None` for injected memories
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cut_reason: Option<String>,
+}
+
+/// What one memory injection included and what it left out.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct MemoryInjectionReport {
+    pub token_budget: usize,
+    pub max_entries: usize,
+    pub tokens_used: usize,
+    pub injected: Vec<MemoryScoreBreakdown>,
+    /// Highest-scoring candidates that were not injected
+    #[serde(default)]
+    pub missed: Vec<MemoryScoreBreakdown>,
+}
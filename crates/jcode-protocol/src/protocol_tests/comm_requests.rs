@@ -66,6 +66,26 @@ fn test_stdin_response_deserialize_from_json() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_permission_decision_deserialize_from_json() -> Result<()> {
+    let json = r#"{"type":"permission_decision","id":6,"request_id":"req_1","approved":false}"#;
+    let decoded = parse_request_json(json)?;
+    assert_eq!(decoded.id(), 6);
+    let Request::PermissionDecision {
+        request_id,
+        approved,
+        message,
+        ..
+    } = decoded
+    else {
+        return Err(anyhow!("expected PermissionDecision"));
+    };
+    assert_eq!(request_id, "req_1");
+    assert!(!approved);
+    assert_eq!(message, None);
+    Ok(())
+}
+
 #[test]
 fn test_stdin_request_event_roundtrip() -> Result<()> {
     let event = ServerEvent::StdinRequest {
@@ -5,6 +5,7 @@ fn test_request_roundtrip() -> Result<()> {
         content: "hello".to_string(),
         images: vec![],
         system_reminder: None,
+        focus_secs: None,
     };
     let json = serde_json::to_string(&req)?;
     let decoded = parse_request_json(&json)?;
@@ -168,6 +169,35 @@ fn test_rename_session_clear_request_roundtrip_omits_title() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_set_tool_scope_request_roundtrip() -> Result<()> {
+    let req = Request::SetToolScope {
+        id: 12,
+        path: Some("packages/web".to_string()),
+    };
+    let json = serde_json::to_string(&req)?;
+    assert!(json.contains("\"type\":\"set_tool_scope\""));
+    assert!(json.contains("\"path\":\"packages/web\""));
+    let decoded = parse_request_json(&json)?;
+    assert_eq!(decoded.id(), 12);
+    let Request::SetToolScope { path, .. } = decoded else {
+        return Err(anyhow!("wrong request type"));
+    };
+    assert_eq!(path.as_deref(), Some("packages/web"));
+    Ok(())
+}
+
+#[test]
+fn test_set_mirror_request_roundtrip() -> Result<()> {
+    let req = Request::SetMirror { id: 13, path: None };
+    let json = serde_json::to_string(&req)?;
+    assert_eq!(json, r#"{"type":"set_mirror","id":13}"#);
+    let decoded = parse_request_json(&json)?;
+    assert_eq!(decoded.id(), 13);
+    assert!(matches!(decoded, Request::SetMirror { path: None, .. }));
+    Ok(())
+}
+
 #[test]
 fn test_event_roundtrip() -> Result<()> {
     let event = ServerEvent::TextDelta {
@@ -244,6 +274,32 @@ fn test_status_detail_event_roundtrip() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_rate_limited_event_roundtrip() -> Result<()> {
+    let event = ServerEvent::RateLimited {
+        provider: "Claude".to_string(),
+        retry_after_secs: 42,
+        attempt: 1,
+        max_attempts: 3,
+    };
+    let json = encode_event(&event);
+    assert!(json.contains("\"type\":\"rate_limited\""));
+    let decoded = parse_event_json(json.trim())?;
+    let ServerEvent::RateLimited {
+        provider,
+        retry_after_secs,
+        attempt,
+        max_attempts,
+    } = decoded
+    else {
+        return Err(anyhow!("wrong event type"));
+    };
+    assert_eq!(provider, "Claude");
+    assert_eq!(retry_after_secs, 42);
+    assert_eq!((attempt, max_attempts), (1, 3));
+    Ok(())
+}
+
 #[test]
 fn test_generated_image_event_roundtrip() -> Result<()> {
     let event = ServerEvent::GeneratedImage {
@@ -366,6 +422,7 @@ fn test_history_event_roundtrip_preserves_side_panel_snapshot() -> Result<()> {
             content: "hello".to_string(),
             tool_calls: None,
             tool_data: None,
+            usage: None,
         }],
         images: Vec::new(),
         provider_name: Some("openai".to_string()),
@@ -382,6 +439,11 @@ fn test_history_event_roundtrip_preserves_side_panel_snapshot() -> Result<()> {
             cache_reported_input_tokens: 100,
             cache_read_input_tokens: 80,
             cache_creation_input_tokens: 10,
+            web_search_requests: 0,
+            pruned_context_tokens: 0,
+            redactions: 0,
+            overhead_input_tokens: 0,
+            overhead_output_tokens: 0,
         }),
         all_sessions: Vec::new(),
         client_count: None,
@@ -396,6 +458,7 @@ fn test_history_event_roundtrip_preserves_side_panel_snapshot() -> Result<()> {
         status_detail: None,
         upstream_provider: None,
         resolved_credential: None,
+        model_route: None,
         reasoning_effort: None,
         service_tier: None,
         subagent_model: None,
@@ -457,6 +520,7 @@ fn test_compacted_history_event_roundtrip() -> Result<()> {
             content: "older response".to_string(),
             tool_calls: None,
             tool_data: None,
+            usage: None,
         }],
         images: Vec::new(),
         compacted_total: 128,
@@ -518,6 +582,103 @@ fn test_side_panel_state_event_roundtrip() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_clipboard_write_event_roundtrip() -> Result<()> {
+    let event = ServerEvent::ClipboardWrite {
+        text: "line one\nline two".to_string(),
+    };
+    let json = encode_event(&event);
+    assert!(json.contains("\"type\":\"clipboard_write\""));
+    let decoded = parse_event_json(json.trim())?;
+    let ServerEvent::ClipboardWrite { text } = decoded else {
+        return Err(anyhow!("expected ClipboardWrite event"));
+    };
+    assert_eq!(text, "line one\nline two");
+    Ok(())
+}
+
+#[test]
+fn test_permission_inbox_event_roundtrip() -> Result<()> {
+    let entry = PermissionInboxEntry {
+        request_id: "safe-approval-call_1-0".to_string(),
+        origin: PermissionOrigin::Session,
+        origin_id: Some("session_abc".to_string()),
+        action: "bash".to_string(),
+        description: "bash: cargo test".to_string(),
+        created_at_ms: 1_000,
+        expires_at_ms: Some(1_000 + 15 * 60_000),
+    };
+    let event = ServerEvent::PermissionInbox {
+        entries: vec![entry.clone()],
+    };
+    let json = encode_event(&event);
+    assert!(json.contains("\"type\":\"permission_inbox\""));
+    assert!(json.contains("\"origin\":\"session\""));
+    let ServerEvent::PermissionInbox { entries } = parse_event_json(json.trim())? else {
+        return Err(anyhow!("expected PermissionInbox event"));
+    };
+    assert_eq!(entries, vec![entry.clone()]);
+    assert_eq!(
+        entry.status_line(1_000 + 2 * 60_000),
+        "session session_abc · 2m old · expires in 13m"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_turn_queue_roundtrip() -> Result<()> {
+    let item = TurnQueueItem {
+        id: "int-4".to_string(),
+        kind: TurnQueueKind::SoftInterrupt,
+        origin: "background_task".to_string(),
+        preview: "tests finished".to_string(),
+        urgent: true,
+        due_at: None,
+    };
+    let json = encode_event(&ServerEvent::TurnQueue {
+        items: vec![item.clone()],
+    });
+    assert!(json.contains("\"type\":\"turn_queue\""));
+    assert!(json.contains("\"kind\":\"soft_interrupt\""));
+    assert!(!json.contains("due_at"));
+    let ServerEvent::TurnQueue { items } = parse_event_json(json.trim())? else {
+        return Err(anyhow!("expected TurnQueue event"));
+    };
+    assert_eq!(items, vec![item.clone()]);
+    assert_eq!(
+        item.status_line(),
+        "int-4 · interrupt from background_task · urgent"
+    );
+
+    let req = Request::TurnQueueMove {
+        id: 9,
+        item_id: "msg-2".to_string(),
+        position: 1,
+    };
+    let decoded = parse_request_json(&serde_json::to_string(&req)?)?;
+    assert_eq!(decoded.id(), 9);
+    let Request::TurnQueueMove {
+        item_id, position, ..
+    } = decoded
+    else {
+        return Err(anyhow!("expected TurnQueueMove"));
+    };
+    assert_eq!((item_id.as_str(), position), ("msg-2", 1));
+
+    let json = encode_event(&ServerEvent::QueuedTurnStarted {
+        id: 12,
+        content: "run the tests".to_string(),
+        images: Vec::new(),
+    });
+    assert!(json.contains("\"type\":\"queued_turn_started\""));
+    assert!(!json.contains("images"));
+    let ServerEvent::QueuedTurnStarted { id, content, .. } = parse_event_json(json.trim())? else {
+        return Err(anyhow!("expected QueuedTurnStarted event"));
+    };
+    assert_eq!((id, content.as_str()), (12, "run the tests"));
+    Ok(())
+}
+
 #[test]
 fn test_error_event_retry_after_roundtrip() -> Result<()> {
     let event = ServerEvent::Error {
@@ -558,3 +719,22 @@ fn test_error_event_retry_after_back_compat_default() -> Result<()> {
     assert_eq!(retry_after_secs, None);
     Ok(())
 }
+
+#[test]
+fn test_set_instructions_request_roundtrip() -> Result<()> {
+    let req = Request::SetInstructions {
+        id: 14,
+        text: Some("Keep diffs small.\nRun tests first.".to_string()),
+    };
+    let json = serde_json::to_string(&req)?;
+    let decoded = parse_request_json(&json)?;
+    assert_eq!(decoded.id(), 14);
+    let Request::SetInstructions { text, .. } = decoded else {
+        return Err(anyhow!("wrong request type"));
+    };
+    assert_eq!(text.as_deref(), Some("Keep diffs small.\nRun tests first."));
+
+    let clear = serde_json::to_string(&Request::SetInstructions { id: 15, text: None })?;
+    assert_eq!(clear, r#"{"type":"set_instructions","id":15}"#);
+    Ok(())
+}
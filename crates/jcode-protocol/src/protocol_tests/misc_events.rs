@@ -160,6 +160,8 @@ fn test_protocol_enum_roundtrips_cover_wire_names() -> Result<()> {
         (FeatureToggle::Swarm, "swarm"),
         (FeatureToggle::Autoreview, "autoreview"),
         (FeatureToggle::Autojudge, "autojudge"),
+        (FeatureToggle::Plan, "plan"),
+        (FeatureToggle::Autocommit, "autocommit"),
     ];
     for (feature, wire) in feature_toggles {
         let json = serde_json::to_string(&feature)?;
@@ -195,6 +197,273 @@ fn test_set_feature_roundtrip() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_plan_decision_roundtrip() -> Result<()> {
+    let req = Request::PlanDecision {
+        id: 78,
+        decision: jcode_plan::proposal::PlanDecision::Revise,
+        feedback: Some("split step 2".to_string()),
+    };
+    let json = serde_json::to_string(&req)?;
+    assert!(json.contains("\"type\":\"plan_decision\""));
+    assert!(json.contains("\"decision\":\"revise\""));
+    let decoded = parse_request_json(&json)?;
+    assert_eq!(decoded.id(), 78);
+    Ok(())
+}
+
+#[test]
+fn test_aside_roundtrip() -> Result<()> {
+    let req = Request::Aside {
+        id: 81,
+        action: AsideAction::Close {
+            summary: Some("use the v2 parser".to_string()),
+        },
+    };
+    let json = serde_json::to_string(&req)?;
+    assert!(json.contains("\"type\":\"aside\""));
+    assert!(json.contains("\"action\":\"close\""));
+    let decoded = parse_request_json(&json)?;
+    assert_eq!(decoded.id(), 81);
+    let Request::Aside { action, .. } = decoded else {
+        return Err(anyhow!("expected Aside request"));
+    };
+    assert_eq!(
+        action,
+        AsideAction::Close {
+            summary: Some("use the v2 parser".to_string())
+        }
+    );
+
+    let start = parse_request_json(r#"{"type":"aside","id":82,"action":"start"}"#)?;
+    assert!(matches!(
+        start,
+        Request::Aside {
+            action: AsideAction::Start,
+            ..
+        }
+    ));
+
+    let event = ServerEvent::AsideChanged {
+        id: 83,
+        active: true,
+        summary: Some("draft".to_string()),
+        error: None,
+    };
+    let json = encode_event(&event);
+    assert!(json.contains("\"type\":\"aside_changed\""));
+    let decoded = parse_event_json(json.trim())?;
+    assert!(matches!(
+        decoded,
+        ServerEvent::AsideChanged {
+            id: 83,
+            active: true,
+            ..
+        }
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_compare_roundtrip() -> Result<()> {
+    let req = Request::Compare {
+        id: 84,
+        action: CompareAction::Run {
+            models: vec!["claude-opus-4-6".to_string(), "gpt-5.4".to_string()],
+            prompt: "why is this test flaky?".to_string(),
+        },
+    };
+    let json = serde_json::to_string(&req)?;
+    assert!(json.contains("\"type\":\"compare\""));
+    assert!(json.contains("\"action\":\"run\""));
+    assert_eq!(parse_request_json(&json)?.id(), 84);
+
+    let run = CompareRun {
+        model: "gpt-5.4".to_string(),
+        text: "A race in the fixture.".to_string(),
+        input_tokens: Some(1200),
+        output_tokens: Some(340),
+        latency_ms: 2_400,
+        error: None,
+    };
+    assert_eq!(run.stats_line(), "2.4s · 1200 in · 340 out");
+    let commit = parse_request_json(&serde_json::to_string(&Request::Compare {
+        id: 85,
+        action: CompareAction::Commit {
+            prompt: "why is this test flaky?".to_string(),
+            chosen: 0,
+            runs: vec![run.clone()],
+        },
+    })?)?;
+    let Request::Compare {
+        action: CompareAction::Commit { chosen, runs, .. },
+        ..
+    } = commit
+    else {
+        return Err(anyhow!("expected compare commit"));
+    };
+    assert_eq!((chosen, runs), (0, vec![run.clone()]));
+
+    let event = ServerEvent::CompareResult {
+        id: 84,
+        prompt: "why is this test flaky?".to_string(),
+        runs: vec![run],
+        error: None,
+    };
+    let json = encode_event(&event);
+    assert!(json.contains("\"type\":\"compare_result\""));
+    assert!(matches!(
+        parse_event_json(json.trim())?,
+        ServerEvent::CompareResult { id: 84, .. }
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_handoff_roundtrip() -> Result<()> {
+    let req = Request::Handoff {
+        id: 86,
+        action: HandoffAction::Create {
+            summary: "## Goal\n- finish the parser".to_string(),
+        },
+    };
+    let json = serde_json::to_string(&req)?;
+    assert!(json.contains("\"type\":\"handoff\""));
+    assert!(json.contains("\"action\":\"create\""));
+    let decoded = parse_request_json(&json)?;
+    assert_eq!(decoded.id(), 86);
+    let Request::Handoff { action, .. } = decoded else {
+        return Err(anyhow!("expected Handoff request"));
+    };
+    assert_eq!(
+        action,
+        HandoffAction::Create {
+            summary: "## Goal\n- finish the parser".to_string()
+        }
+    );
+
+    let event = ServerEvent::HandoffChanged {
+        id: 86,
+        summary: None,
+        new_session_id: Some("session_fox_123".to_string()),
+        new_session_name: Some("fox".to_string()),
+        error: None,
+    };
+    let json = encode_event(&event);
+    assert!(json.contains("\"type\":\"handoff_changed\""));
+    assert!(!json.contains("\"summary\""));
+    let ServerEvent::HandoffChanged { new_session_id, .. } = parse_event_json(json.trim())? else {
+        return Err(anyhow!("expected HandoffChanged event"));
+    };
+    assert_eq!(new_session_id.as_deref(), Some("session_fox_123"));
+    Ok(())
+}
+
+#[test]
+fn test_environment_roundtrip() -> Result<()> {
+    let decoded = parse_request_json(r#"{"type":"environment","id":87}"#)?;
+    assert_eq!(decoded.id(), 87);
+    assert!(matches!(
+        decoded,
+        Request::Environment { refresh: false, .. }
+    ));
+
+    let event = ServerEvent::EnvironmentChanged {
+        id: 87,
+        descriptor: Some("# Environment\n\nCPUs: 8".to_string()),
+        refreshed: true,
+    };
+    let json = encode_event(&event);
+    assert!(json.contains("\"type\":\"environment_changed\""));
+    let ServerEvent::EnvironmentChanged {
+        descriptor,
+        refreshed,
+        ..
+    } = parse_event_json(json.trim())?
+    else {
+        return Err(anyhow!("expected EnvironmentChanged event"));
+    };
+    assert_eq!(descriptor.as_deref(), Some("# Environment\n\nCPUs: 8"));
+    assert!(refreshed);
+    Ok(())
+}
+
+#[test]
+fn test_prompt_sections_roundtrip() -> Result<()> {
+    let decoded = parse_request_json(r#"{"type":"prompt_sections","id":91}"#)?;
+    assert_eq!(decoded.id(), 91);
+    assert!(matches!(decoded, Request::PromptSections { .. }));
+
+    let event = ServerEvent::PromptSections {
+        id: 91,
+        report: "Sections ([prompt.sections])\n- git dynamic ~120 tokens\n".to_string(),
+    };
+    let json = encode_event(&event);
+    assert!(json.contains("\"type\":\"prompt_sections\""));
+    let ServerEvent::PromptSections { id, report } = parse_event_json(json.trim())? else {
+        return Err(anyhow!("expected PromptSections event"));
+    };
+    assert_eq!(id, 91);
+    assert!(report.contains("git dynamic"));
+    Ok(())
+}
+
+#[test]
+fn test_set_profile_roundtrip() -> Result<()> {
+    let req = Request::SetProfile {
+        id: 79,
+        profile: Some("review".to_string()),
+    };
+    let json = serde_json::to_string(&req)?;
+    assert!(json.contains("\"type\":\"set_profile\""));
+    assert!(json.contains("\"profile\":\"review\""));
+    let decoded = parse_request_json(&json)?;
+    assert_eq!(decoded.id(), 79);
+
+    let cleared = parse_request_json(r#"{"type":"set_profile","id":80}"#)?;
+    assert!(matches!(
+        cleared,
+        Request::SetProfile {
+            id: 80,
+            profile: None
+        }
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_set_safe_mode_roundtrip() -> Result<()> {
+    let req = Request::SetSafeMode {
+        id: 81,
+        enabled: true,
+    };
+    let json = serde_json::to_string(&req)?;
+    assert!(json.contains("\"type\":\"set_safe_mode\""));
+    assert!(json.contains("\"enabled\":true"));
+    let decoded = parse_request_json(&json)?;
+    assert_eq!(decoded.id(), 81);
+    Ok(())
+}
+
+#[test]
+fn test_set_approval_gate_roundtrip() -> Result<()> {
+    let req = Request::SetApprovalGate {
+        id: 82,
+        enabled: true,
+    };
+    let json = serde_json::to_string(&req)?;
+    assert!(json.contains("\"type\":\"set_approval_gate\""));
+    let decoded = parse_request_json(&json)?;
+    assert!(matches!(
+        decoded,
+        Request::SetApprovalGate {
+            id: 82,
+            enabled: true
+        }
+    ));
+    Ok(())
+}
+
 #[test]
 fn test_set_route_deserializes_as_set_model_compat_alias() -> Result<()> {
     // Legacy/desktop compatibility shape: a bare model string under the
@@ -385,6 +654,7 @@ fn test_message_request_roundtrip_preserves_images_and_system_reminder() -> Resu
             ("image/jpeg".to_string(), "BBB".to_string()),
         ],
         system_reminder: Some("be concise".to_string()),
+        focus_secs: None,
     };
     let json = serde_json::to_string(&req)?;
     let decoded = parse_request_json(&json)?;
@@ -393,6 +663,7 @@ fn test_message_request_roundtrip_preserves_images_and_system_reminder() -> Resu
         content,
         images,
         system_reminder,
+        ..
     } = decoded
     else {
         return Err(anyhow!("expected Message"));
@@ -435,3 +706,241 @@ fn test_provider_guardrail_event_roundtrip() -> Result<()> {
     assert!(stop_reason.is_none());
     Ok(())
 }
+
+#[test]
+fn test_post_mortem_event_roundtrip() -> Result<()> {
+    let event = ServerEvent::PostMortem {
+        summary: "[Post-mortem of the failed turn]\nAttempted: a\nFailed: b\nNext: c".to_string(),
+    };
+    let json = encode_event(&event);
+    assert!(json.contains("\"type\":\"post_mortem\""));
+    let ServerEvent::PostMortem { summary } = parse_event_json(json.trim())? else {
+        return Err(anyhow!("expected PostMortem event"));
+    };
+    assert!(summary.ends_with("Next: c"));
+    Ok(())
+}
+
+#[test]
+fn test_agent_limits_event_roundtrip() -> Result<()> {
+    let event = ServerEvent::AgentLimits {
+        status: AgentLimitStatus {
+            turns: 12,
+            tool_calls: 40,
+            elapsed_secs: 95,
+            max_turns: Some(12),
+            max_tool_calls_per_turn: None,
+            max_wall_seconds: Some(600),
+            breached: Some("max_turns".to_string()),
+        },
+    };
+    let json = encode_event(&event);
+    assert!(json.contains("\"type\":\"agent_limits\""));
+    assert!(!json.contains("max_tool_calls_per_turn"));
+    let decoded = parse_event_json(json.trim())?;
+    let ServerEvent::AgentLimits { status } = decoded else {
+        return Err(anyhow!("expected AgentLimits event"));
+    };
+    assert_eq!(status.turns, 12);
+    assert_eq!(status.max_wall_seconds, Some(600));
+    assert_eq!(status.breached.as_deref(), Some("max_turns"));
+    Ok(())
+}
+
+#[test]
+fn test_focus_message_and_checkpoint_roundtrip() -> Result<()> {
+    let req = Request::Message {
+        id: 9,
+        content: "migrate the config loader".to_string(),
+        images: vec![],
+        system_reminder: None,
+        focus_secs: Some(1200),
+    };
+    let json = serde_json::to_string(&req)?;
+    assert!(json.contains("\"focus_secs\":1200"));
+    let Request::Message { focus_secs, .. } = parse_request_json(&json)? else {
+        return Err(anyhow!("expected Message"));
+    };
+    assert_eq!(focus_secs, Some(1200));
+    let plain = parse_request_json(r#"{"type":"message","id":1,"content":"hi"}"#)?;
+    assert!(matches!(
+        plain,
+        Request::Message {
+            focus_secs: None,
+            ..
+        }
+    ));
+
+    let event = ServerEvent::FocusCheckpoint {
+        budget_secs: 1200,
+        elapsed_secs: 1234,
+        open_todos: 3,
+        summary: "Loader migrated; tests for env overrides remain.".to_string(),
+    };
+    let json = encode_event(&event);
+    assert!(json.contains("\"type\":\"focus_checkpoint\""));
+    let ServerEvent::FocusCheckpoint {
+        elapsed_secs,
+        open_todos,
+        ..
+    } = parse_event_json(json.trim())?
+    else {
+        return Err(anyhow!("expected FocusCheckpoint event"));
+    };
+    assert_eq!(elapsed_secs, 1234);
+    assert_eq!(open_todos, 3);
+    Ok(())
+}
+
+#[test]
+fn test_auto_commit_event_roundtrip() -> Result<()> {
+    let event = ServerEvent::AutoCommit {
+        sha: "4f2a9c1".to_string(),
+        branch: "jcode/auto".to_string(),
+        subject: "Fix the flaky watcher test".to_string(),
+        files: 2,
+    };
+    let json = encode_event(&event);
+    assert!(json.contains("\"type\":\"auto_commit\""));
+    let decoded = parse_event_json(json.trim())?;
+    let ServerEvent::AutoCommit {
+        sha,
+        branch,
+        subject,
+        files,
+    } = decoded
+    else {
+        return Err(anyhow!("expected AutoCommit event"));
+    };
+    assert_eq!(sha, "4f2a9c1");
+    assert_eq!(branch, "jcode/auto");
+    assert_eq!(subject, "Fix the flaky watcher test");
+    assert_eq!(files, 2);
+    Ok(())
+}
+
+#[test]
+fn test_memory_injected_report_roundtrip() -> Result<()> {
+    let breakdown = |id: &str, cut_reason: Option<&str>| MemoryScoreBreakdown {
+        id: id.to_string(),
+        category: "correction".to_string(),
+        preview: "Run tests with cargo nextest".to_string(),
+        similarity: 0.8,
+        recency: 0.5,
+        strength: 0.25,
+        category_weight: 1.0,
+        score: 0.72,
+        tokens: 18,
+        cut_reason: cut_reason.map(str::to_string),
+    };
+    let report = MemoryInjectionReport {
+        token_budget: 600,
+        max_entries: 5,
+        tokens_used: 18,
+        injected: vec![breakdown("mem-1", None)],
+        missed: vec![breakdown("mem-2", Some("token budget"))],
+    };
+    let event = ServerEvent::MemoryInjected {
+        count: 1,
+        prompt: "# Memory".to_string(),
+        display_prompt: None,
+        prompt_chars: 8,
+        computed_age_ms: 40,
+        report: Some(report.clone()),
+    };
+    let json = encode_event(&event);
+    let decoded = parse_event_json(json.trim())?;
+    let ServerEvent::MemoryInjected {
+        report: decoded_report,
+        ..
+    } = decoded
+    else {
+        return Err(anyhow!("expected MemoryInjected event"));
+    };
+    assert_eq!(decoded_report, Some(report));
+
+    // Older servers omit the report.
+    let legacy = parse_event_json(
+        r##"{"type":"memory_injected","count":1,"prompt":"# Memory","prompt_chars":8}"##,
+    )?;
+    assert!(matches!(
+        legacy,
+        ServerEvent::MemoryInjected { report: None, .. }
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_active_skills_event_roundtrip() -> Result<()> {
+    let event = ServerEvent::ActiveSkills {
+        skills: vec![ActiveSkillStatus {
+            name: "terraform".to_string(),
+            trigger: "file `infra/main.tf` matches `**/*.tf`".to_string(),
+        }],
+    };
+    let json = encode_event(&event);
+    assert!(json.contains("\"type\":\"active_skills\""));
+    let decoded = parse_event_json(json.trim())?;
+    let ServerEvent::ActiveSkills { skills } = decoded else {
+        return Err(anyhow!("expected ActiveSkills event"));
+    };
+    assert_eq!(skills.len(), 1);
+    assert_eq!(skills[0].name, "terraform");
+    Ok(())
+}
+
+#[test]
+fn test_resume_streaming_event_roundtrip() -> Result<()> {
+    let event = ServerEvent::ResumeStreaming {
+        session_id: "session_abc".to_string(),
+        text: "Half of the answ".to_string(),
+        current_tool_name: Some("bash".to_string()),
+    };
+    let json = encode_event(&event);
+    assert!(json.contains("\"type\":\"resume_streaming\""));
+    let decoded = parse_event_json(json.trim())?;
+    let ServerEvent::ResumeStreaming {
+        session_id,
+        text,
+        current_tool_name,
+    } = decoded
+    else {
+        return Err(anyhow!("expected ResumeStreaming event"));
+    };
+    assert_eq!(session_id, "session_abc");
+    assert_eq!(text, "Half of the answ");
+    assert_eq!(current_tool_name.as_deref(), Some("bash"));
+
+    let bare = parse_event_json(r#"{"type":"resume_streaming","session_id":"s"}"#)?;
+    assert!(matches!(
+        bare,
+        ServerEvent::ResumeStreaming { text, current_tool_name: None, .. } if text.is_empty()
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_migration_pending_event_roundtrip() -> Result<()> {
+    let event = ServerEvent::MigrationPending {
+        from_hash: "3f160da".to_string(),
+        to_hash: "0.15.0".to_string(),
+        reason: "newer server binary installed".to_string(),
+        at: 1_700_000_300_000,
+    };
+    let json = encode_event(&event);
+    assert!(json.contains("\"type\":\"migration_pending\""));
+    let ServerEvent::MigrationPending {
+        from_hash,
+        to_hash,
+        reason,
+        at,
+    } = parse_event_json(json.trim())?
+    else {
+        return Err(anyhow!("expected MigrationPending event"));
+    };
+    assert_eq!(from_hash, "3f160da");
+    assert_eq!(to_hash, "0.15.0");
+    assert_eq!(reason, "newer server binary installed");
+    assert_eq!(at, 1_700_000_300_000);
+    Ok(())
+}
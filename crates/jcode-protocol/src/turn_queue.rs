@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+/// What kind of work a turn queue entry will start.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TurnQueueKind {
+    /// A user message waiting for the current turn to finish.
+    Message,
+    /// A soft interrupt waiting for the next safe injection point.
+    SoftInterrupt,
+    /// An ambient schedule entry that will wake this session.
+    ScheduledFollowUp,
+}
+
+impl TurnQueueKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            TurnQueueKind::Message => "message",
+            TurnQueueKind::SoftInterrupt => "interrupt",
+            TurnQueueKind::ScheduledFollowUp => "scheduled",
+        }
+    }
+}
+
+/// One pending item in a session's turn queue. Items of one kind run in list
+/// order; scheduled follow-ups run at `due_at` and can only be removed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TurnQueueItem {
+    /// Stable id for removal and reordering, e.g. "msg-3" or "int-12".
+    pub id: String,
+    pub kind: TurnQueueKind,
+    /// Who queued it: "user", "system", "background_task", "client" or
+    /// "schedule".
+    pub origin: String,
+    /// First line of the content, truncated.
+    pub preview: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub urgent: bool,
+    /// RFC 3339 time a scheduled follow-up is due.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_at: Option<String>,
+}
+
+impl TurnQueueItem {
+    /// "msg-3 · message from user", with "urgent" or the due time appended.
+    pub fn status_line(&self) -> String {
+        let mut line = format!("{} · {} from {}", self.id, self.kind.label(), self.origin);
+        if self.urgent {
+            line.push_str(" · urgent");
+        }
+        if let Some(due_at) = &self.due_at {
+            line.push_str(&format!(" · due {}", due_at));
+        }
+        line
+    }
+}
@@ -31,6 +31,7 @@ fn is_zero_u8(value: &u8) -> bool {
 /// Client request to server
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
+#[cfg_attr(not(feature = "exhaustive"), non_exhaustive)]
 pub enum Request {
     /// Send a message to the agent
     #[serde(rename = "message")]
@@ -41,6 +42,10 @@ pub enum Request {
         images: Vec<(String, String)>,
         #[serde(default, skip_serializing_if = "Option::is_none")]
         system_reminder: Option<String>,
+        /// Wall-clock budget of a `/focus` turn. The agent is asked for a
+        /// progress summary at the halfway mark and the turn ends at expiry.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        focus_secs: Option<u64>,
     },
 
     /// Cancel current generation
@@ -51,6 +56,10 @@ pub enum Request {
     #[serde(rename = "background_tool")]
     BackgroundTool { id: u64 },
 
+    /// Cancel the MCP tool call currently running, leaving the turn going
+    #[serde(rename = "cancel_mcp_call")]
+    CancelMcpCall { id: u64 },
+
     /// Soft interrupt: inject message at next safe point without cancelling
     #[serde(rename = "soft_interrupt")]
     SoftInterrupt {
@@ -65,6 +74,35 @@ pub enum Request {
     #[serde(rename = "cancel_soft_interrupts")]
     CancelSoftInterrupts { id: u64 },
 
+    /// Queue a message to run as its own turn once the session is idle. Runs
+    /// immediately when nothing is processing.
+    #[serde(rename = "queue_message")]
+    QueueMessage {
+        id: u64,
+        content: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        images: Vec<(String, String)>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        system_reminder: Option<String>,
+    },
+
+    /// List the session's pending turns (answered with `turn_queue`)
+    #[serde(rename = "turn_queue")]
+    TurnQueue { id: u64 },
+
+    /// Drop a pending turn queue item before it runs
+    #[serde(rename = "turn_queue_remove")]
+    TurnQueueRemove { id: u64, item_id: String },
+
+    /// Move a queued message or soft interrupt to a 1-based position among
+    /// items of the same kind
+    #[serde(rename = "turn_queue_move")]
+    TurnQueueMove {
+        id: u64,
+        item_id: String,
+        position: usize,
+    },
+
     /// Clear conversation history
     #[serde(rename = "clear")]
     Clear { id: u64 },
@@ -235,6 +273,26 @@ pub enum Request {
         model: Option<String>,
     },
 
+    /// Select or clear the session's `[profiles.<name>]` preset. Applied once
+    /// the current turn, if any, finishes.
+    #[serde(rename = "set_profile")]
+    SetProfile {
+        id: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        profile: Option<String>,
+    },
+
+    /// Turn the session's `jcode --safe` restrictions on or off. Applied once
+    /// the current turn, if any, finishes.
+    #[serde(rename = "set_safe_mode")]
+    SetSafeMode { id: u64, enabled: bool },
+
+    /// Ask the client before tool calls outside the auto-allowed tier, without
+    /// the other safe mode restrictions. Lasts as long as the live session and
+    /// is not saved with it.
+    #[serde(rename = "set_approval_gate")]
+    SetApprovalGate { id: u64, enabled: bool },
+
     /// Launch a subagent immediately in the active session.
     #[serde(rename = "run_subagent")]
     RunSubagent {
@@ -276,6 +334,53 @@ pub enum Request {
         enabled: bool,
     },
 
+    /// Record the user's decision on the plan proposed in plan mode
+    #[serde(rename = "plan_decision")]
+    PlanDecision {
+        id: u64,
+        decision: jcode_plan::proposal::PlanDecision,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        feedback: Option<String>,
+    },
+
+    /// Open, summarize, or close a `/aside` scratch thread
+    #[serde(rename = "aside")]
+    Aside {
+        id: u64,
+        #[serde(flatten)]
+        action: AsideAction,
+    },
+
+    /// Run a `/compare` prompt against several models, or keep one answer
+    #[serde(rename = "compare")]
+    Compare {
+        id: u64,
+        #[serde(flatten)]
+        action: CompareAction,
+    },
+
+    /// Draft a `/handoff` summary, or start the child session from it
+    #[serde(rename = "handoff")]
+    Handoff {
+        id: u64,
+        #[serde(flatten)]
+        action: HandoffAction,
+    },
+
+    /// Show the environment section of the system prompt, re-probing it
+    /// first when `refresh` is set
+    #[serde(rename = "environment")]
+    Environment {
+        id: u64,
+        #[serde(default)]
+        refresh: bool,
+    },
+
+    /// Report which half of the system prompt each `[prompt.sections]`
+    /// section went in, with sizes and the measured cache impact
+    #[serde(rename = "prompt_sections")]
+    PromptSections { id: u64 },
+
     /// Set the compaction mode for this session
     #[serde(rename = "set_compaction_mode")]
     SetCompactionMode {
@@ -283,6 +388,44 @@ pub enum Request {
         mode: jcode_config_types::CompactionMode,
     },
 
+    /// Attach or detach extra workspace roots for this session. With both
+    /// lists empty the server just reports the current roots.
+    #[serde(rename = "update_workspace_roots")]
+    UpdateWorkspaceRoots {
+        id: u64,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        add: Vec<String>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        remove: Vec<String>,
+    },
+
+    /// Scope the session's tools to a subdirectory (`/cd`), or back to the
+    /// working directory when `path` is omitted.
+    #[serde(rename = "set_tool_scope")]
+    SetToolScope {
+        id: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        path: Option<String>,
+    },
+
+    /// Replace the session's standing `/instructions`, or clear them when
+    /// `text` is omitted.
+    #[serde(rename = "set_instructions")]
+    SetInstructions {
+        id: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        text: Option<String>,
+    },
+
+    /// Record the `/mirror` file in the session so resume picks the mirror
+    /// back up, or forget it when `path` is omitted.
+    #[serde(rename = "set_mirror")]
+    SetMirror {
+        id: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        path: Option<String>,
+    },
+
     /// Set or clear the active session's custom display title.
     #[serde(rename = "rename_session")]
     RenameSession {
@@ -343,6 +486,16 @@ pub enum Request {
         input: String,
     },
 
+    /// Approve or deny an entry in the shared permission inbox
+    #[serde(rename = "permission_decision")]
+    PermissionDecision {
+        id: u64,
+        request_id: String,
+        approved: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        message: Option<String>,
+    },
+
     // === Agent-to-agent communication ===
     /// Register as an external agent
     #[serde(rename = "agent_register")]
@@ -677,6 +830,7 @@ pub enum Request {
     clippy::large_enum_variant,
     reason = "wire protocol prioritizes straightforward serde payloads over boxing every larger event variant"
 )]
+#[cfg_attr(not(feature = "exhaustive"), non_exhaustive)]
 pub enum ServerEvent {
     /// Acknowledgment of request
     #[serde(rename = "ack")]
@@ -809,6 +963,16 @@ pub enum ServerEvent {
     #[serde(rename = "status_detail")]
     StatusDetail { detail: String },
 
+    /// The provider rate-limited the request and the agent is waiting
+    /// `retry_after_secs` before resending it within the same turn.
+    #[serde(rename = "rate_limited")]
+    RateLimited {
+        provider: String,
+        retry_after_secs: u64,
+        attempt: u32,
+        max_attempts: u32,
+    },
+
     /// Provider has finished the visible assistant message, but the turn may still be
     /// finalizing bookkeeping such as session IDs or completion trailers.
     #[serde(rename = "message_end")]
@@ -893,6 +1057,15 @@ pub enum ServerEvent {
         message: String,
     },
 
+    /// A post-mortem of the turn that just failed was added to the session
+    /// as a system note. Sent after `Error`, once the background analysis
+    /// finishes.
+    #[serde(rename = "post_mortem")]
+    PostMortem {
+        /// The note text, including its header line.
+        summary: String,
+    },
+
     /// Relevant memory was injected into the conversation
     #[serde(rename = "memory_injected")]
     MemoryInjected {
@@ -910,6 +1083,9 @@ pub enum ServerEvent {
         /// Age of the precomputed memory payload at injection time
         #[serde(default)]
         computed_age_ms: u64,
+        /// Ranking of the injected memories and the near misses
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        report: Option<MemoryInjectionReport>,
     },
 
     /// Memory activity state update for remote clients.
@@ -945,8 +1121,45 @@ pub enum ServerEvent {
         /// Count of recent messages still kept verbatim after compaction
         #[serde(default, skip_serializing_if = "Option::is_none")]
         active_messages: Option<usize>,
+        /// Number of older tool results shortened to recover from a context overflow
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tool_results_trimmed: Option<usize>,
+    },
+
+    /// Agent limit usage for the request that is about to finish. Sent just
+    /// before `done` when any `[agent]` limit is configured.
+    #[serde(rename = "agent_limits")]
+    AgentLimits { status: AgentLimitStatus },
+
+    /// The `/focus` budget of the request that is about to finish ran out.
+    /// Sent just before `done`, after progress was saved to the todos.
+    #[serde(rename = "focus_checkpoint")]
+    FocusCheckpoint {
+        budget_secs: u64,
+        elapsed_secs: u64,
+        /// Todos still open after the checkpoint
+        open_todos: usize,
+        /// The agent's closing progress summary
+        summary: String,
+    },
+
+    /// Commit made by `[git] auto_commit` for the request that is about to
+    /// finish. Sent just before `done`.
+    #[serde(rename = "auto_commit")]
+    AutoCommit {
+        /// Abbreviated commit hash
+        sha: String,
+        /// Branch the commit landed on (`HEAD` when detached)
+        branch: String,
+        subject: String,
+        /// Number of files in the commit
+        files: usize,
     },
 
+    /// Skills auto-loaded for this session, sent whenever the set changes.
+    #[serde(rename = "active_skills")]
+    ActiveSkills { skills: Vec<ActiveSkillStatus> },
+
     /// Message/turn completed
     #[serde(rename = "done")]
     Done { id: u64 },
@@ -1079,6 +1292,10 @@ pub enum ServerEvent {
         /// render usage/billing without re-deriving it from the provider name.
         #[serde(default, skip_serializing_if = "Option::is_none")]
         resolved_credential: Option<jcode_provider_core::ResolvedCredential>,
+        /// Effective model route (model, pinned upstream provider, API method)
+        /// the session is running on.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        model_route: Option<jcode_session_types::SessionModelRoute>,
         /// Reasoning effort for providers that expose it
         #[serde(skip_serializing_if = "Option::is_none")]
         reasoning_effort: Option<String>,
@@ -1120,10 +1337,60 @@ pub enum ServerEvent {
         compacted_hidden_prompts: usize,
     },
 
+    /// Sent after `History` when a client attaches to a session whose turn is
+    /// still running, e.g. after the previous client crashed mid-stream. The
+    /// rest of the turn streams as usual and ends with `Done { id: 0 }`.
+    #[serde(rename = "resume_streaming")]
+    ResumeStreaming {
+        session_id: String,
+        /// Assistant text streamed so far that is not yet in the history.
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        text: String,
+        /// Tool running when the client attached.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        current_tool_name: Option<String>,
+    },
+
     /// Side panel state changed for the active session
     #[serde(rename = "side_panel_state")]
     SidePanelState { snapshot: SidePanelSnapshot },
 
+    /// The `clipboard` tool asked the client to copy text for the user
+    #[serde(rename = "clipboard_write")]
+    ClipboardWrite { text: String },
+
+    /// Every pending permission decision, sent on subscribe and whenever the
+    /// inbox changes
+    #[serde(rename = "permission_inbox")]
+    PermissionInbox { entries: Vec<PermissionInboxEntry> },
+
+    /// Pending turns of the session: queued messages, soft interrupts and
+    /// scheduled follow-ups. Sent on subscribe, on request and whenever the
+    /// queue changes
+    #[serde(rename = "turn_queue")]
+    TurnQueue { items: Vec<TurnQueueItem> },
+
+    /// A `queue_message` turn started. The rest of the turn streams as usual
+    /// and ends with `Done { id }`
+    #[serde(rename = "queued_turn_started")]
+    QueuedTurnStarted {
+        id: u64,
+        content: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        images: Vec<(String, String)>,
+    },
+
+    /// The server found a newer binary and will move its sessions onto it at
+    /// `at` (Unix ms). Sent once per target build and again on subscribe, so
+    /// clients can warn the user and offer to migrate now or defer
+    #[serde(rename = "migration_pending")]
+    MigrationPending {
+        from_hash: String,
+        to_hash: String,
+        reason: String,
+        at: u64,
+    },
+
     /// Server is reloading (clients should reconnect)
     #[serde(rename = "reloading")]
     Reloading {
@@ -1147,7 +1414,8 @@ pub enum ServerEvent {
         output: Option<String>,
     },
 
-    /// Model changed (response to cycle_model)
+    /// Model changed (response to cycle_model, or sent on resume when the
+    /// session's stored route could not be restored)
     #[serde(rename = "model_changed")]
     ModelChanged {
         id: u64,
@@ -1156,6 +1424,10 @@ pub enum ServerEvent {
         provider_name: Option<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
         error: Option<String>,
+        /// Set when a resumed session fell back from its stored route, or
+        /// when the provider retired the model mid-session (`id` 0).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        route_fallback: Option<jcode_session_types::ModelRouteFallback>,
     },
 
     /// Reasoning effort changed (response to set_reasoning_effort)
@@ -1188,6 +1460,22 @@ pub enum ServerEvent {
         error: Option<String>,
     },
 
+    /// Environment section of the system prompt (response to environment);
+    /// `None` when `[prompt] environment` is off
+    #[serde(rename = "environment_changed")]
+    EnvironmentChanged {
+        id: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        descriptor: Option<String>,
+        /// Whether the server re-probed before answering
+        #[serde(default)]
+        refreshed: bool,
+    },
+
+    /// `/prompt sections` report (response to prompt_sections)
+    #[serde(rename = "prompt_sections")]
+    PromptSections { id: u64, report: String },
+
     /// Compaction mode changed (response to set_compaction_mode)
     #[serde(rename = "compaction_mode_changed")]
     CompactionModeChanged {
@@ -1197,6 +1485,66 @@ pub enum ServerEvent {
         error: Option<String>,
     },
 
+    /// Workspace roots changed (response to update_workspace_roots)
+    #[serde(rename = "workspace_roots_changed")]
+    WorkspaceRootsChanged {
+        id: u64,
+        /// Extra roots attached after the update, beyond the working directory
+        roots: Vec<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+
+    /// Tool scope changed (response to set_tool_scope)
+    #[serde(rename = "tool_scope_changed")]
+    ToolScopeChanged {
+        id: u64,
+        /// `/cd` scope after the request; `None` when tools run in the
+        /// working directory
+        #[serde(skip_serializing_if = "Option::is_none")]
+        scope: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+
+    /// Aside state changed (response to aside)
+    #[serde(rename = "aside_changed")]
+    AsideChanged {
+        id: u64,
+        /// Whether an aside is open after the request
+        active: bool,
+        /// Drafted summary (response to a summarize action)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        summary: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+
+    /// Answers to a `/compare` run, in the order the models were given
+    #[serde(rename = "compare_result")]
+    CompareResult {
+        id: u64,
+        prompt: String,
+        runs: Vec<CompareRun>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+
+    /// Response to a handoff request: the drafted summary, or the child
+    /// session the client should switch to
+    #[serde(rename = "handoff_changed")]
+    HandoffChanged {
+        id: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        summary: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        new_session_id: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        new_session_name: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+
     /// Available models updated (pushed after auth changes)
     #[serde(rename = "available_models_updated")]
     AvailableModelsUpdated {
@@ -1,4 +1,6 @@
-use jcode_message_types::{ContentBlock, Message, Role, ToolDefinition, sanitize_tool_id};
+use jcode_message_types::{
+    ContentBlock, Message, Role, ToolDefinition, WebSearchHit, sanitize_tool_id,
+};
 use jcode_provider_core::anthropic_map_tool_name_for_oauth as map_tool_name_for_oauth;
 use serde::Serialize;
 use serde_json::{Value, json};
@@ -261,12 +263,91 @@ pub fn format_content_blocks(blocks: &[ContentBlock], is_oauth: bool) -> Vec<Api
                     });
                 }
             }
+            ContentBlock::ServerToolUse { id, name, input } => {
+                result.push(ApiContentBlock::ServerToolUse {
+                    id: id.clone(),
+                    name: name.clone(),
+                    input: input.clone(),
+                });
+            }
+            ContentBlock::WebSearchResult {
+                tool_use_id,
+                results,
+                error_code,
+            } => {
+                result.push(ApiContentBlock::WebSearchToolResult {
+                    tool_use_id: tool_use_id.clone(),
+                    content: web_search_result_content(results, error_code.as_deref()),
+                });
+            }
             _ => {}
         }
     }
     result
 }
 
+/// Rebuild the `web_search_tool_result` content the API originally returned,
+/// so earlier searches (and their citations) stay valid on replay.
+fn web_search_result_content(results: &[WebSearchHit], error_code: Option<&str>) -> Value {
+    if let Some(error_code) = error_code {
+        return json!({
+            "type": "web_search_tool_result_error",
+            "error_code": error_code,
+        });
+    }
+    Value::Array(
+        results
+            .iter()
+            .map(|hit| {
+                let mut item = json!({
+                    "type": "web_search_result",
+                    "url": hit.url,
+                    "title": hit.title,
+                    "encrypted_content": hit.encrypted_content,
+                });
+                if let Some(page_age) = &hit.page_age {
+                    item["page_age"] = json!(page_age);
+                }
+                item
+            })
+            .collect(),
+    )
+}
+
+/// Parse a `web_search_tool_result` content value into hits, or the error
+/// code when the search failed.
+pub fn parse_web_search_result_content(content: &Value) -> (Vec<WebSearchHit>, Option<String>) {
+    if content.get("type").and_then(Value::as_str) == Some("web_search_tool_result_error") {
+        let code = content
+            .get("error_code")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown")
+            .to_string();
+        return (Vec::new(), Some(code));
+    }
+    let hits = content
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| {
+                    let url = item.get("url").and_then(Value::as_str)?;
+                    let text = |key: &str| item.get(key).and_then(Value::as_str);
+                    Some(WebSearchHit {
+                        url: url.to_string(),
+                        title: text("title").unwrap_or_default().to_string(),
+                        page_age: text("page_age").map(str::to_string),
+                        encrypted_content: text("encrypted_content")
+                            .unwrap_or_default()
+                            .to_string(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    (hits, None)
+}
+
 /// Convert tool definitions to Anthropic API format
 /// Adds cache_control to the last tool for prompt caching
 /// Local tool names that are represented by the curated Claude-Code builtin
@@ -399,7 +480,7 @@ pub struct ApiRequest {
     pub system: Option<ApiSystem>,
     pub messages: Vec<ApiMessage>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub tools: Option<Vec<ApiTool>>,
+    pub tools: Option<Vec<ApiToolParam>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<ApiMetadata>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -671,6 +752,14 @@ pub enum ApiContentBlock {
     Thinking { thinking: String, signature: String },
     #[serde(rename = "image")]
     Image { source: ApiImageSource },
+    #[serde(rename = "server_tool_use")]
+    ServerToolUse {
+        id: String,
+        name: String,
+        input: Value,
+    },
+    #[serde(rename = "web_search_tool_result")]
+    WebSearchToolResult { tool_use_id: String, content: Value },
 }
 
 #[derive(Serialize, Clone)]
@@ -706,6 +795,33 @@ pub struct ApiTool {
     pub cache_control: Option<CacheControlParam>,
 }
 
+/// Server-side tool executed by the Anthropic API itself (e.g. web search).
+#[derive(Serialize, Clone)]
+pub struct ApiServerTool {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_uses: Option<u32>,
+}
+
+/// One entry of the request `tools` array.
+#[derive(Serialize, Clone)]
+#[serde(untagged)]
+pub enum ApiToolParam {
+    Client(ApiTool),
+    Server(ApiServerTool),
+}
+
+/// Anthropic's hosted web search tool.
+pub fn web_search_server_tool() -> ApiServerTool {
+    ApiServerTool {
+        kind: "web_search_20250305".to_string(),
+        name: "web_search".to_string(),
+        max_uses: None,
+    }
+}
+
 #[cfg(test)]
 mod cache_prefix_invariant_tests {
     //! Deterministic proof that injecting a trailing memory message can never move
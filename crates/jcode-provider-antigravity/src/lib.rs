@@ -22,6 +22,8 @@ pub const FETCH_MODELS_API_URL: &str =
     "https://cloudcode-pa.googleapis.com/v1internal:fetchAvailableModels";
 pub const GENERATE_CONTENT_API_URL: &str =
     "https://cloudcode-pa.googleapis.com/v1internal:generateContent";
+pub const STREAM_GENERATE_CONTENT_API_URL: &str =
+    "https://cloudcode-pa.googleapis.com/v1internal:streamGenerateContent?alt=sse";
 const VERSION_ENV: &str = "JCODE_ANTIGRAVITY_VERSION";
 pub const ANTIGRAVITY_VERSION: &str = "1.18.3";
 pub const X_GOOG_API_CLIENT: &str = "google-cloud-sdk vscode_cloudshelleditor/0.1";
@@ -404,9 +406,13 @@ pub fn is_retryable_empty_turn(response: &CodeAssistGenerateResponse) -> bool {
     if produced_output {
         return false;
     }
-    candidate
-        .finish_reason
-        .as_deref()
+    is_abnormal_finish_reason(candidate.finish_reason.as_deref())
+}
+
+/// Whether a candidate finish reason is anything other than a normal terminal
+/// reason (`STOP`, `MAX_TOKENS`, unspecified). A missing reason is not abnormal.
+pub fn is_abnormal_finish_reason(reason: Option<&str>) -> bool {
+    reason
         .map(|reason| {
             !matches!(
                 reason.to_ascii_uppercase().as_str(),
@@ -29,8 +29,8 @@ use jcode_message_types::{Message as JMessage, ToolDefinition};
 #[cfg(feature = "aws-sdk")]
 use jcode_provider_core::summarize_model_catalog_refresh;
 use jcode_provider_core::{
-    DEFAULT_CONTEXT_LIMIT, EventStream, ModelCatalogRefreshSummary, ModelRoute, Provider,
-    RouteCheapnessEstimate, RouteCostConfidence, RouteCostSource,
+    DEFAULT_CONTEXT_LIMIT, EventStream, ModelCapabilities, ModelCatalogRefreshSummary, ModelRoute,
+    Provider, RouteCheapnessEstimate, RouteCostConfidence, RouteCostSource,
 };
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "aws-sdk")]
@@ -765,6 +765,17 @@ impl BedrockProvider {
             || id.starts_with("nvidia.")
     }
 
+    /// Capabilities from the static Bedrock model table.
+    pub fn model_capabilities(model: &str) -> ModelCapabilities {
+        let info = Self::model_info(model);
+        ModelCapabilities {
+            tools: info.supports_tools,
+            vision: info.supports_vision,
+            max_output_tokens: Some(info.max_output_tokens),
+            ..ModelCapabilities::assumed(Some("bedrock".to_string()), Some(info.context_tokens))
+        }
+    }
+
     fn model_info(model: &str) -> BedrockModelInfo {
         let id = Self::normalize_model_id(model).to_ascii_lowercase();
         if id.contains("claude-opus-4") || id.contains("claude-sonnet-4") {
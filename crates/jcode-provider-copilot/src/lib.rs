@@ -38,10 +38,19 @@ pub struct PersistedCatalog {
     pub fetched_at_rfc3339: String,
 }
 
+/// Models paid Copilot plans serve without spending premium requests.
+pub const INCLUDED_MODELS: &[&str] = &["gpt-4.1", "gpt-4o", "gpt-5-mini"];
+
 pub fn is_known_display_model(model: &str) -> bool {
     FALLBACK_MODELS.contains(&model)
 }
 
+/// Whether a user-initiated request to `model` spends a premium request.
+pub fn is_premium_model(model: &str) -> bool {
+    let normalized = model.trim().to_ascii_lowercase();
+    !INCLUDED_MODELS.contains(&normalized.as_str())
+}
+
 pub fn max_token_parameter_for_model(model: &str) -> &'static str {
     let normalized = model.trim().to_ascii_lowercase();
     if normalized.starts_with("gpt-5") {
@@ -0,0 +1,254 @@
+//! Outbound network policy (`[network]`).
+//!
+//! Every HTTP client jcode builds resolves hostnames through [`resolver`], which
+//! checks the host against the installed [`EgressPolicy`] before any DNS lookup
+//! happens. Allowed and denied destinations are counted for the lifetime of the
+//! process and reported by `jcode debug network`.
+//!
+//! The check runs at name resolution, so requests to literal IP addresses and
+//! the final hop behind an HTTP proxy are not seen here; the proxy host itself
+//! is.
+
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, LazyLock, Mutex, RwLock};
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use serde::Serialize;
+
+/// Host patterns jcode may connect to, and whether off-list hosts are denied.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EgressPolicy {
+    /// When false, off-list destinations are logged and counted but allowed.
+    pub enforce: bool,
+    /// Exact hostnames, `*.example.com` suffix patterns, or `*` for any host.
+    pub allowed_hosts: Vec<String>,
+}
+
+impl EgressPolicy {
+    pub fn allows(&self, host: &str) -> bool {
+        let host = normalize_host(host);
+        is_loopback_host(&host)
+            || self
+                .allowed_hosts
+                .iter()
+                .any(|pattern| host_matches(pattern, &host))
+    }
+}
+
+/// Per-host counters since the process started.
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+pub struct ObservedDestination {
+    pub host: String,
+    pub allowed: u64,
+    /// Off-list lookups let through because the policy is not enforcing.
+    pub unlisted: u64,
+    pub denied: u64,
+}
+
+impl ObservedDestination {
+    pub fn total(&self) -> u64 {
+        self.allowed + self.unlisted + self.denied
+    }
+}
+
+/// Error returned to the HTTP client when the policy blocks a host.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EgressDenied {
+    pub host: String,
+}
+
+impl std::fmt::Display for EgressDenied {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "network policy blocked connection to {}; add it to [network] allowed_hosts in ~/.jcode/config.toml or set [network] mode = \"observe\"",
+            self.host
+        )
+    }
+}
+
+impl std::error::Error for EgressDenied {}
+
+static POLICY: LazyLock<RwLock<Option<EgressPolicy>>> = LazyLock::new(|| RwLock::new(None));
+static OBSERVED: LazyLock<Mutex<BTreeMap<String, ObservedDestination>>> =
+    LazyLock::new(|| Mutex::new(BTreeMap::new()));
+
+/// Install the process-wide policy. Until this is called every host is
+/// allowed (and still counted).
+pub fn set_policy(policy: EgressPolicy) {
+    *POLICY
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(policy);
+}
+
+pub fn policy() -> Option<EgressPolicy> {
+    POLICY
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+/// Check `host` against the installed policy and record the outcome.
+pub fn check_host(host: &str) -> Result<(), EgressDenied> {
+    let host = normalize_host(host);
+    let policy = policy();
+    let listed = policy.as_ref().is_none_or(|policy| policy.allows(&host));
+    let enforce = policy.as_ref().is_some_and(|policy| policy.enforce);
+
+    let first_miss = {
+        let mut observed = OBSERVED
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let entry = observed
+            .entry(host.clone())
+            .or_insert_with(|| ObservedDestination {
+                host: host.clone(),
+                ..ObservedDestination::default()
+            });
+        let first_miss = entry.unlisted == 0 && entry.denied == 0;
+        if listed {
+            entry.allowed += 1;
+        } else if enforce {
+            entry.denied += 1;
+        } else {
+            entry.unlisted += 1;
+        }
+        !listed && first_miss
+    };
+
+    if listed {
+        return Ok(());
+    }
+    // Log the first miss per host; later ones only show up in the counters.
+    if first_miss {
+        if enforce {
+            jcode_logging::warn(&format!(
+                "[network] Denied connection to {} (not in [network] allowed_hosts)",
+                host
+            ));
+        } else {
+            jcode_logging::info(&format!(
+                "[network] Connection to unlisted host {} (observe mode, allowed)",
+                host
+            ));
+        }
+    }
+    if enforce {
+        Err(EgressDenied { host })
+    } else {
+        Ok(())
+    }
+}
+
+/// Destinations seen since the process started, busiest first.
+pub fn observed_destinations() -> Vec<ObservedDestination> {
+    let mut destinations: Vec<ObservedDestination> = OBSERVED
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .values()
+        .cloned()
+        .collect();
+    destinations.sort_by(|a, b| b.total().cmp(&a.total()).then(a.host.cmp(&b.host)));
+    destinations
+}
+
+/// Hostname of `url`, lowercased, or `None` when it does not parse or has no
+/// host.
+pub fn host_from_url(url: &str) -> Option<String> {
+    reqwest::Url::parse(url.trim())
+        .ok()?
+        .host_str()
+        .map(normalize_host)
+        .filter(|host| !host.is_empty())
+}
+
+/// DNS resolver that applies the egress policy before resolving.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EgressResolver;
+
+impl Resolve for EgressResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            check_host(&host)?;
+            let addrs: Vec<SocketAddr> =
+                tokio::net::lookup_host((host.as_str(), 0)).await?.collect();
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Resolver to pass to `ClientBuilder::dns_resolver` on every client jcode
+/// builds, async or blocking.
+pub fn resolver() -> Arc<EgressResolver> {
+    Arc::new(EgressResolver)
+}
+
+fn normalize_host(host: &str) -> String {
+    host.trim()
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .trim_end_matches('.')
+        .to_ascii_lowercase()
+}
+
+fn is_loopback_host(host: &str) -> bool {
+    host == "localhost"
+        || host.ends_with(".localhost")
+        || host
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback())
+}
+
+fn host_matches(pattern: &str, host: &str) -> bool {
+    let pattern = normalize_host(pattern);
+    if pattern == "*" {
+        return true;
+    }
+    match pattern.strip_prefix("*.") {
+        Some(suffix) => {
+            host == suffix
+                || host
+                    .strip_suffix(suffix)
+                    .is_some_and(|prefix| prefix.ends_with('.'))
+        }
+        None => host == pattern,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn policy_matches_exact_suffix_and_loopback_hosts() {
+        let policy = EgressPolicy {
+            enforce: true,
+            allowed_hosts: vec!["api.anthropic.com".to_string(), "*.github.com".to_string()],
+        };
+        assert!(policy.allows("API.Anthropic.com."));
+        assert!(policy.allows("github.com"));
+        assert!(policy.allows("api.github.com"));
+        assert!(!policy.allows("notgithub.com"));
+        assert!(!policy.allows("anthropic.com"));
+        assert!(policy.allows("localhost"));
+        assert!(policy.allows("127.0.0.1"));
+        assert!(policy.allows("[::1]"));
+
+        let open = EgressPolicy {
+            enforce: true,
+            allowed_hosts: vec!["*".to_string()],
+        };
+        assert!(open.allows("example.org"));
+    }
+
+    #[test]
+    fn host_from_url_extracts_lowercase_host() {
+        assert_eq!(
+            host_from_url("https://API.example.com:8443/v1").as_deref(),
+            Some("api.example.com")
+        );
+        assert_eq!(host_from_url("not a url"), None);
+    }
+}
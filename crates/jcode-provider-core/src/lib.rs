@@ -1,6 +1,7 @@
 pub mod anthropic;
 pub mod auth_mode;
 pub mod catalog_refresh;
+pub mod egress;
 pub mod failover;
 pub mod fallback_pick;
 pub mod fingerprint;
@@ -8,6 +9,7 @@ pub mod model_id;
 pub mod models;
 pub mod openai_schema;
 pub mod pricing;
+pub mod retired_models;
 pub mod selection;
 
 pub use anthropic::{
@@ -35,6 +37,10 @@ pub use models::{
     normalize_copilot_model_name, provider_for_model as core_provider_for_model,
     provider_for_model_with_hint as core_provider_for_model_with_hint, provider_key_from_hint,
 };
+pub use retired_models::{
+    MODEL_SUCCESSORS, is_model_retired_error, model_successor, nearest_models,
+    retired_model_message,
+};
 pub use selection::{
     ActiveProvider, ProviderAvailability, auto_default_provider, cli_provider_arg_for_session_key,
     dedupe_model_routes, explicit_model_provider_prefix, fallback_sequence,
@@ -46,7 +52,8 @@ use anyhow::Result;
 use async_trait::async_trait;
 use futures::Stream;
 use jcode_message_types::{
-    ContentBlock, Message, Role, StreamEvent, ToolDefinition, messages_with_dynamic_system_context,
+    ContentBlock, DeterminismParams, Message, Role, StreamEvent, StreamTextCursor, ToolDefinition,
+    messages_with_dynamic_system_context,
 };
 use serde::{Deserialize, Serialize};
 use std::pin::Pin;
@@ -147,6 +154,21 @@ pub trait Provider: Send + Sync {
         None
     }
 
+    /// Activity-ledger key (e.g. `claude:oauth:work`, `openai:api-key`) for the
+    /// login the next request will use, so per-credential incidents such as
+    /// rate limits land in the same `/usage` row as that login's quota.
+    /// `None` when the provider does not track activity.
+    fn usage_source_key(&self) -> Option<String> {
+        None
+    }
+
+    /// What the next request pins when `[provider] deterministic` is on: the
+    /// model id sent and the sampling parameters the API accepts, with `seed`
+    /// where it takes one. The default pins nothing beyond the model.
+    fn deterministic_params(&self, _seed: u64) -> DeterminismParams {
+        DeterminismParams::unsupported(self.model())
+    }
+
     /// Whether this provider path can safely receive `ContentBlock::Image` inputs.
     fn supports_image_input(&self) -> bool {
         false
@@ -205,6 +227,14 @@ pub trait Provider: Send + Sync {
         None
     }
 
+    /// Upstream provider the current model is explicitly pinned to (the
+    /// `@provider` half of an OpenRouter `model@provider` request). Unlike
+    /// [`Provider::preferred_provider`] this ignores automatic routing
+    /// preferences, so it is safe to persist and re-apply on resume.
+    fn pinned_provider(&self) -> Option<String> {
+        None
+    }
+
     /// Get all model routes for the unified picker.
     fn model_routes(&self) -> Vec<ModelRoute> {
         Vec::new()
@@ -306,6 +336,11 @@ pub trait Provider: Send + Sync {
     /// Invalidate any cached credentials.
     async fn invalidate_credentials(&self) {}
 
+    /// Re-attach the next request to a server-side response that was still
+    /// generating when the session was last saved (OpenAI background mode).
+    /// Callers only do so when that request is the one the response answers.
+    fn restore_background_response(&self, _response_id: &str) {}
+
     /// Set Copilot premium request conservation mode.
     fn set_premium_mode(&self, _mode: PremiumMode) {}
 
@@ -378,11 +413,12 @@ pub trait Provider: Send + Sync {
 
         let response = self.complete(&messages, &[], system, None).await?;
         let mut result = String::new();
+        let mut cursor = StreamTextCursor::default();
         tokio::pin!(response);
 
         while let Some(event) = response.next().await {
-            match event {
-                Ok(StreamEvent::TextDelta(text)) => result.push_str(&text),
+            match event.map(|event| cursor.normalize(event)) {
+                Ok(Some(StreamEvent::TextDelta(text))) => result.push_str(&text),
                 Ok(_) => {}
                 Err(err) => return Err(err),
             }
@@ -390,6 +426,70 @@ pub trait Provider: Send + Sync {
 
         Ok(result)
     }
+
+    /// Simple completion on a fork of this provider switched to `model`, so
+    /// background work can use a cheaper model than the session's. Fails if
+    /// the provider cannot switch to `model`.
+    async fn complete_simple_with_model(
+        &self,
+        model: &str,
+        prompt: &str,
+        system: &str,
+    ) -> Result<SimpleCompletion> {
+        use futures::StreamExt;
+
+        let provider = self.fork();
+        provider.set_model(model)?;
+        let messages = vec![Message {
+            role: Role::User,
+            content: vec![ContentBlock::Text {
+                text: prompt.to_string(),
+                cache_control: None,
+            }],
+            timestamp: None,
+            tool_duration_ms: None,
+        }];
+
+        let response = provider.complete(&messages, &[], system, None).await?;
+        let mut completion = SimpleCompletion {
+            model: provider.model(),
+            ..SimpleCompletion::default()
+        };
+        let mut cursor = StreamTextCursor::default();
+        tokio::pin!(response);
+
+        while let Some(event) = response.next().await {
+            match event.map(|event| cursor.normalize(event)) {
+                Ok(Some(StreamEvent::TextDelta(text))) => completion.text.push_str(&text),
+                Ok(Some(StreamEvent::RetryRollback { .. })) => completion.text.clear(),
+                Ok(Some(StreamEvent::TokenUsage {
+                    input_tokens,
+                    output_tokens,
+                    ..
+                })) => {
+                    completion.input_tokens = input_tokens.or(completion.input_tokens);
+                    completion.output_tokens = output_tokens.or(completion.output_tokens);
+                }
+                Ok(Some(StreamEvent::Error { message, .. })) => {
+                    return Err(anyhow::anyhow!(message));
+                }
+                Ok(_) => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(completion)
+    }
+}
+
+/// Text and token counts from [`Provider::complete_simple_with_model`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SimpleCompletion {
+    pub text: String,
+    /// The model that answered, as reported by the switched fork.
+    pub model: String,
+    pub input_tokens: Option<u64>,
+    pub output_tokens: Option<u64>,
 }
 
 /// Premium request conservation mode for Copilot-compatible providers.
@@ -454,7 +554,8 @@ impl NativeToolResult {
 /// Canonical User-Agent for generic outbound Jcode HTTP requests.
 pub const JCODE_USER_AGENT: &str = concat!("jcode/", env!("CARGO_PKG_VERSION"));
 
-/// Shared HTTP client for all generic provider requests. Creating a `reqwest::Client` is expensive
+/// Shared HTTP client for all generic provider requests. Hostnames resolve through the
+/// [`egress`] policy. Creating a `reqwest::Client` is expensive
 /// (~10ms due to TLS init, connection pool setup), so we reuse a single instance. Provider-specific
 /// transports may override the User-Agent on individual requests when they intentionally need to
 /// match an official client.
@@ -465,6 +566,7 @@ pub fn shared_http_client() -> reqwest::Client {
         .get_or_init(|| {
             reqwest::Client::builder()
                 .user_agent(JCODE_USER_AGENT)
+                .dns_resolver(egress::resolver())
                 .connect_timeout(Duration::from_secs(15))
                 .tcp_keepalive(Some(Duration::from_secs(30)))
                 // Proactively detect half-dead pooled HTTP/2 connections before we
@@ -482,6 +584,7 @@ pub fn shared_http_client() -> reqwest::Client {
                     eprintln!("jcode: failed to build shared provider HTTP client: {err}");
                     match reqwest::Client::builder()
                         .user_agent(JCODE_USER_AGENT)
+                        .dns_resolver(egress::resolver())
                         .build()
                     {
                         Ok(client) => client,
@@ -509,6 +612,7 @@ pub fn shared_http_client() -> reqwest::Client {
 pub fn fresh_transport_client() -> reqwest::Client {
     reqwest::Client::builder()
         .user_agent(JCODE_USER_AGENT)
+        .dns_resolver(egress::resolver())
         .connect_timeout(Duration::from_secs(15))
         .tcp_keepalive(Some(Duration::from_secs(30)))
         .http2_keep_alive_interval(Some(Duration::from_secs(30)))
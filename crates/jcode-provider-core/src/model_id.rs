@@ -49,6 +49,63 @@ pub fn strip_date_suffix(model: &str) -> &str {
     }
 }
 
+/// Whether `model` ends in a release date, either Anthropic `-YYYYMMDD` or
+/// OpenAI `-YYYY-MM-DD`.
+pub fn has_date_suffix(model: &str) -> bool {
+    if strip_date_suffix(model) != model {
+        return true;
+    }
+    let bytes = model.as_bytes();
+    bytes.len() > 11 && {
+        let tail = &bytes[bytes.len() - 11..];
+        tail[0] == b'-'
+            && tail[5] == b'-'
+            && tail[8] == b'-'
+            && [1, 2, 3, 4, 6, 7, 9, 10]
+                .iter()
+                .all(|&index| tail[index].is_ascii_digit())
+    }
+}
+
+/// Dated snapshots behind the rolling aliases jcode knows about.
+const DATED_SNAPSHOTS: &[(&str, &str)] = &[
+    ("claude-opus-4-5", "claude-opus-4-5-20251101"),
+    ("claude-haiku-4-5", "claude-haiku-4-5-20251001"),
+    ("claude-sonnet-4-5", "claude-sonnet-4-5-20250929"),
+    ("claude-opus-4-1", "claude-opus-4-1-20250805"),
+    ("claude-opus-4-0", "claude-opus-4-20250514"),
+    ("claude-sonnet-4-0", "claude-sonnet-4-20250514"),
+    ("claude-3-7-sonnet-latest", "claude-3-7-sonnet-20250219"),
+    ("claude-3-5-haiku-latest", "claude-3-5-haiku-20241022"),
+    ("gpt-5", "gpt-5-2025-08-07"),
+    ("gpt-5-mini", "gpt-5-mini-2025-08-07"),
+    ("gpt-5-nano", "gpt-5-nano-2025-08-07"),
+    ("gpt-4.1", "gpt-4.1-2025-04-14"),
+    ("gpt-4.1-mini", "gpt-4.1-mini-2025-04-14"),
+    ("gpt-4.1-nano", "gpt-4.1-nano-2025-04-14"),
+    ("gpt-4o", "gpt-4o-2024-08-06"),
+    ("gpt-4o-mini", "gpt-4o-mini-2024-07-18"),
+    ("o3", "o3-2025-04-16"),
+    ("o4-mini", "o4-mini-2025-04-16"),
+];
+
+/// The dated snapshot a rolling alias currently points at
+/// (`claude-sonnet-4-5` -> `claude-sonnet-4-5-20250929`), so reproducible
+/// runs keep hitting the same weights when the alias moves. The `[1m]`
+/// suffix is kept. Ids that are already dated or unknown come back as-is.
+pub fn pin_dated_snapshot(model: &str) -> String {
+    let (base, long_context) = split_long_context(model.trim());
+    let pinned = DATED_SNAPSHOTS
+        .iter()
+        .find(|(alias, _)| alias.eq_ignore_ascii_case(base))
+        .map_or(base, |(_, snapshot)| snapshot);
+    if long_context {
+        format!("{pinned}{LONG_CONTEXT_SUFFIX}")
+    } else {
+        pinned.to_string()
+    }
+}
+
 /// Final path segment of a slash-qualified id
 /// (`openrouter/anthropic/claude-x` -> `claude-x`). Ids without `/` are
 /// returned unchanged.
@@ -105,6 +162,29 @@ mod tests {
         assert_eq!(strip_date_suffix("gpt-4-1106"), "gpt-4-1106");
     }
 
+    #[test]
+    fn pin_dated_snapshot_resolves_known_aliases_only() {
+        assert_eq!(
+            pin_dated_snapshot("claude-sonnet-4-5"),
+            "claude-sonnet-4-5-20250929"
+        );
+        assert_eq!(
+            pin_dated_snapshot("claude-sonnet-4-5[1m]"),
+            "claude-sonnet-4-5-20250929[1m]"
+        );
+        assert_eq!(pin_dated_snapshot("gpt-4o"), "gpt-4o-2024-08-06");
+        assert_eq!(pin_dated_snapshot("gpt-4o-2024-11-20"), "gpt-4o-2024-11-20");
+        assert_eq!(pin_dated_snapshot("my-local-model"), "my-local-model");
+    }
+
+    #[test]
+    fn has_date_suffix_accepts_both_date_styles() {
+        assert!(has_date_suffix("claude-haiku-4-5-20251001"));
+        assert!(has_date_suffix("gpt-4o-2024-08-06"));
+        assert!(!has_date_suffix("gpt-4o"));
+        assert!(!has_date_suffix("claude-opus-4-1"));
+    }
+
     #[test]
     fn slash_base_takes_last_segment() {
         assert_eq!(
@@ -45,10 +45,106 @@ pub const ALL_OPENAI_MODELS: &[&str] = &[
 /// Default context window size when model-specific data isn't known.
 pub const DEFAULT_CONTEXT_LIMIT: usize = 200_000;
 
+/// What a model can do, as far as jcode knows. Anything not known to be
+/// missing is assumed present, except vision, which must be known.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ModelCapabilities {
     pub provider: Option<String>,
     pub context_window: Option<usize>,
+    /// Native tool (function) calling.
+    pub tools: bool,
+    /// Image input.
+    pub vision: bool,
+    /// A separate system prompt; without one it is folded into the first
+    /// user message.
+    pub system_prompt: bool,
+    pub streaming: bool,
+    pub max_output_tokens: Option<usize>,
+}
+
+impl ModelCapabilities {
+    /// Capabilities for a model with no known gaps.
+    pub fn assumed(provider: Option<String>, context_window: Option<usize>) -> Self {
+        Self {
+            provider,
+            context_window,
+            tools: true,
+            vision: false,
+            system_prompt: true,
+            streaming: true,
+            max_output_tokens: None,
+        }
+    }
+
+    /// Fill in what jcode knows statically about `model`'s family: image
+    /// input, and the few families that lack tools, system prompts, or
+    /// streaming. Matched on the lowercased id without a `vendor/` prefix,
+    /// so the same family resolves the same way through any gateway.
+    pub fn apply_known_model_features(&mut self, model: &str) {
+        let normalized = model.trim().to_ascii_lowercase();
+        let (base, _) = crate::model_id::split_long_context(&normalized);
+        let id = crate::model_id::slash_base(base);
+
+        if model_family_has_vision(id) {
+            self.vision = true;
+        }
+        if MODELS_WITHOUT_TOOLS
+            .iter()
+            .any(|prefix| id.starts_with(prefix))
+        {
+            self.tools = false;
+        }
+        if MODELS_WITHOUT_SYSTEM_PROMPT
+            .iter()
+            .any(|prefix| id.starts_with(prefix))
+        {
+            self.system_prompt = false;
+        }
+        if MODELS_WITHOUT_STREAMING
+            .iter()
+            .any(|prefix| id.starts_with(prefix))
+        {
+            self.streaming = false;
+        }
+    }
+
+    /// Compact icons for model listings: `⚒` tools (`⊘` none), `◉` vision.
+    pub fn badges(&self) -> String {
+        let mut badges = vec![if self.tools { "⚒" } else { "⊘" }];
+        if self.vision {
+            badges.push("◉");
+        }
+        badges.join(" ")
+    }
+}
+
+/// Model id prefixes without native tool calling.
+const MODELS_WITHOUT_TOOLS: &[&str] = &[
+    "o1-mini",
+    "o1-preview",
+    "gpt-5-chat",
+    "gpt-4o-search",
+    "gpt-4o-mini-search",
+    "sonar",
+];
+
+/// Model id prefixes that reject a system prompt.
+const MODELS_WITHOUT_SYSTEM_PROMPT: &[&str] = &["o1-mini", "o1-preview"];
+
+/// Model id prefixes that only answer non-streaming requests.
+const MODELS_WITHOUT_STREAMING: &[&str] = &["o1-pro"];
+
+fn model_family_has_vision(id: &str) -> bool {
+    const VISION_PREFIXES: &[&str] = &[
+        "claude-", "gpt-4o", "gpt-4.1", "gpt-5", "o1", "o3", "o4", "gemini-", "gemma-3", "grok-4",
+        "pixtral",
+    ];
+    (VISION_PREFIXES.iter().any(|prefix| id.starts_with(prefix))
+        && !["o1-mini", "o1-preview", "o3-mini"]
+            .iter()
+            .any(|prefix| id.starts_with(prefix)))
+        || id.contains("-vl")
+        || id.contains("vision")
 }
 
 fn normalize_provider_id(provider: &str) -> String {
@@ -387,6 +483,27 @@ pub fn normalize_copilot_model_name(model: &str) -> Option<&'static str> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn known_model_features_flag_tool_less_and_vision_families() {
+        let mut caps = ModelCapabilities::assumed(None, None);
+        caps.apply_known_model_features("openai/o1-mini");
+        assert!(!caps.tools);
+        assert!(!caps.system_prompt);
+        assert!(!caps.vision);
+        assert_eq!(caps.badges(), "⊘");
+
+        let mut caps = ModelCapabilities::assumed(None, None);
+        caps.apply_known_model_features("claude-opus-4-6[1m]");
+        assert!(caps.tools && caps.vision && caps.system_prompt && caps.streaming);
+        assert_eq!(caps.badges(), "⚒ ◉");
+
+        let mut caps = ModelCapabilities::assumed(None, None);
+        caps.apply_known_model_features("gpt-5.1-chat-latest");
+        assert!(caps.tools);
+        caps.apply_known_model_features("gpt-5-chat-latest");
+        assert!(!caps.tools);
+    }
+
     #[test]
     fn context_limit_handles_claude_1m_aliases() {
         assert_eq!(
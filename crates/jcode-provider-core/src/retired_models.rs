@@ -0,0 +1,242 @@
+//! Retired and renamed model ids.
+//!
+//! Providers retire model ids on their own schedule, and the failure shows up
+//! as an opaque 404 or 400 in the middle of a session. [`is_model_retired_error`]
+//! recognizes those rejections across providers, [`model_successor`] picks the
+//! id to move the session to, and [`nearest_models`] suggests alternatives when
+//! no successor is known.
+
+use crate::model_id::{canonical, slash_base, strip_date_suffix};
+
+/// Known retirements: old id and the model that replaced it. Lookups follow
+/// chains, so an entry only has to name the next generation.
+pub const MODEL_SUCCESSORS: &[(&str, &str)] = &[
+    ("claude-3-haiku", "claude-3-5-haiku"),
+    ("claude-3-5-haiku", "claude-haiku-4-5"),
+    ("claude-3-sonnet", "claude-3-5-sonnet"),
+    ("claude-3-5-sonnet", "claude-3-7-sonnet"),
+    ("claude-3-7-sonnet", "claude-sonnet-4-5"),
+    ("claude-sonnet-4", "claude-sonnet-4-5"),
+    ("claude-sonnet-4-0", "claude-sonnet-4-5"),
+    ("claude-sonnet-4-5", "claude-sonnet-4-6"),
+    ("claude-3-opus", "claude-opus-4-1"),
+    ("claude-opus-4", "claude-opus-4-1"),
+    ("claude-opus-4-0", "claude-opus-4-1"),
+    ("claude-opus-4-1", "claude-opus-4-5"),
+    ("claude-opus-4-5", "claude-opus-4-6"),
+    ("claude-opus-4-6", "claude-opus-4-8"),
+    ("gpt-4", "gpt-4o"),
+    ("gpt-4-turbo", "gpt-4o"),
+    ("gpt-4o", "gpt-4.1"),
+    ("gpt-4.1", "gpt-5"),
+    ("gpt-4o-mini", "gpt-4.1-mini"),
+    ("gpt-4.1-mini", "gpt-5-mini"),
+    ("gpt-4.1-nano", "gpt-5-nano"),
+    ("o1", "o3"),
+    ("o1-mini", "o3-mini"),
+    ("o3", "gpt-5"),
+    ("o3-mini", "o4-mini"),
+    ("o4-mini", "gpt-5-mini"),
+    ("codex-mini-latest", "gpt-5-codex-mini"),
+    ("gpt-5-codex", "gpt-5.1-codex"),
+    ("gpt-5.1-codex", "gpt-5.2-codex"),
+    ("gpt-5.2-codex", "gpt-5.3-codex"),
+    ("gemini-1.5-pro", "gemini-2.5-pro"),
+    ("gemini-1.5-flash", "gemini-2.5-flash"),
+    ("gemini-2.0-flash", "gemini-2.5-flash"),
+    ("gemini-2.0-flash-lite", "gemini-2.5-flash-lite"),
+];
+
+/// Key used to match ids against [`MODEL_SUCCESSORS`]: the last path segment,
+/// lowercased, without `[1m]` or a release date.
+fn successor_key(model: &str) -> String {
+    strip_date_suffix(&canonical(slash_base(model))).to_string()
+}
+
+/// Whether a provider error says the requested model id no longer exists:
+/// retired, deprecated, renamed or unknown. Access and quota errors that
+/// merely mention a model do not count.
+pub fn is_model_retired_error(message: &str) -> bool {
+    let lower = message.to_ascii_lowercase();
+    let names_missing_model = [
+        "model_not_found",
+        "unknown model",
+        "invalid model id",
+        "is not a valid model",
+    ]
+    .iter()
+    .any(|needle| lower.contains(needle));
+    // Anthropic phrases a renamed id as "X is not available. Please use Y."
+    let renamed = lower.contains("is not available") && lower.contains("please use");
+    let mentions_model = lower.contains("model") || lower.contains("engine");
+    let retired = [
+        "not_found_error",
+        "has been deprecated",
+        "was deprecated",
+        "decommissioned",
+        "has been retired",
+        "was retired",
+        "no longer available",
+        "no longer exists",
+        "does not exist",
+    ]
+    .iter()
+    .any(|needle| lower.contains(needle));
+    names_missing_model || renamed || (mentions_model && retired)
+}
+
+/// The model a session on `model` should move to after `model` was retired.
+///
+/// Follows [`MODEL_SUCCESSORS`] and returns the newest id in the chain that
+/// appears in `available` (the account's current model snapshot), spelled as
+/// the snapshot spells it. With an empty snapshot the newest id in the chain
+/// is returned as is. Provider prefixes such as `anthropic/` are kept.
+pub fn model_successor(model: &str, available: &[String]) -> Option<String> {
+    let prefix = model
+        .rsplit_once('/')
+        .map(|(prefix, _)| format!("{}/", prefix))
+        .unwrap_or_default();
+    let mut chain: Vec<&str> = Vec::new();
+    let mut key = successor_key(model);
+    while let Some((_, next)) = MODEL_SUCCESSORS.iter().find(|(old, _)| *old == key) {
+        if chain.contains(next) {
+            break;
+        }
+        chain.push(next);
+        key = successor_key(next);
+    }
+    if available.is_empty() {
+        return chain.last().map(|next| format!("{}{}", prefix, next));
+    }
+    chain.iter().rev().find_map(|next| {
+        available
+            .iter()
+            .find(|candidate| successor_key(candidate) == *next)
+            .cloned()
+    })
+}
+
+/// Up to `limit` ids from `available` that look most like `model`, best
+/// first: most shared name tokens (`claude`, `opus`, `4`), then the longest
+/// shared prefix. Snapshot order breaks ties.
+pub fn nearest_models(model: &str, available: &[String], limit: usize) -> Vec<String> {
+    let key = successor_key(model);
+    let tokens: Vec<&str> = key.split(['-', '.', '_']).collect();
+    let mut scored: Vec<(usize, usize, usize, &String)> = available
+        .iter()
+        .enumerate()
+        .filter(|(_, candidate)| successor_key(candidate) != key)
+        .map(|(index, candidate)| {
+            let candidate_key = successor_key(candidate);
+            let shared_tokens = candidate_key
+                .split(['-', '.', '_'])
+                .filter(|token| tokens.contains(token))
+                .count();
+            let shared_prefix = key
+                .bytes()
+                .zip(candidate_key.bytes())
+                .take_while(|(a, b)| a == b)
+                .count();
+            (shared_tokens, shared_prefix, index, candidate)
+        })
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then(b.1.cmp(&a.1)).then(a.2.cmp(&b.2)));
+    scored
+        .into_iter()
+        .take(limit)
+        .map(|(_, _, _, candidate)| candidate.clone())
+        .collect()
+}
+
+/// Error text for a retired model with no known successor, naming the
+/// closest models the account can still use.
+pub fn retired_model_message(model: &str, provider: &str, nearest: &[String]) -> String {
+    let mut message = format!(
+        "{} no longer serves the model '{}' and jcode does not know its successor.",
+        provider, model
+    );
+    if nearest.is_empty() {
+        message.push_str(" Pick another model with /model.");
+    } else {
+        message.push_str(&format!(
+            " Closest available: {}. Switch with /model.",
+            nearest.join(", ")
+        ));
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn models(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn detects_retirement_errors_but_not_access_errors() {
+        assert!(is_model_retired_error(
+            "anthropic api error (404 not found): {\"type\":\"error\",\"error\":{\"type\":\"not_found_error\",\"message\":\"model: claude-3-opus-20240229\"}}"
+        ));
+        assert!(is_model_retired_error(
+            "The model `gpt-4-32k` has been deprecated, learn more here"
+        ));
+        assert!(is_model_retired_error(
+            "The model `o1-preview` does not exist or you do not have access to it."
+        ));
+        assert!(is_model_retired_error(
+            "anthropic api error (404 not found): {\"type\":\"error\",\"error\":{\"type\":\"not_found_error\",\"message\":\"claude fable 5 is not available. please use opus 4.8.\"}}"
+        ));
+        assert!(!is_model_retired_error(
+            "temperature is not supported with this model"
+        ));
+        assert!(!is_model_retired_error(
+            "429 Too Many Requests: rate limit for model gpt-5"
+        ));
+        assert!(!is_model_retired_error("404 Not Found: /v1/files/abc"));
+    }
+
+    #[test]
+    fn successor_follows_chain_to_newest_available() {
+        let available = models(&["claude-opus-4-5", "claude-opus-4-6", "claude-sonnet-4-6"]);
+        assert_eq!(
+            model_successor("claude-3-opus-20240229", &available).as_deref(),
+            Some("claude-opus-4-6")
+        );
+        assert_eq!(
+            model_successor("claude-3-opus", &[]).as_deref(),
+            Some("claude-opus-4-8")
+        );
+        assert_eq!(
+            model_successor("anthropic/claude-3.5-sonnet", &[]),
+            None,
+            "OpenRouter's dotted ids are not in the table"
+        );
+        assert_eq!(
+            model_successor("openrouter/gpt-4o", &[]).as_deref(),
+            Some("openrouter/gpt-5")
+        );
+        assert_eq!(
+            model_successor("gpt-4o", &models(&["gemini-2.5-pro"])),
+            None
+        );
+        assert_eq!(model_successor("my-finetune", &[]), None);
+    }
+
+    #[test]
+    fn nearest_models_prefers_same_family() {
+        let available = models(&[
+            "gpt-5",
+            "claude-haiku-4-5",
+            "claude-opus-4-8",
+            "claude-opus-4-6",
+        ]);
+        assert_eq!(
+            nearest_models("claude-opus-3", &available, 2),
+            models(&["claude-opus-4-8", "claude-opus-4-6"])
+        );
+        let message = retired_model_message("claude-opus-3", "Anthropic", &available[2..]);
+        assert!(message.contains("Closest available: claude-opus-4-8, claude-opus-4-6."));
+    }
+}
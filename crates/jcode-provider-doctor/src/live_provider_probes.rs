@@ -12,7 +12,9 @@
 use anyhow::{Context, anyhow, ensure};
 use serde::Deserialize;
 
-use jcode_base::message::{ContentBlock, Message, Role, StreamEvent, ToolDefinition};
+use jcode_base::message::{
+    ContentBlock, Message, Role, StreamEvent, StreamTextCursor, ToolDefinition,
+};
 use jcode_base::provider::Provider;
 use jcode_base::provider::anthropic::AnthropicProvider;
 use jcode_base::provider::antigravity::AntigravityProvider;
@@ -756,9 +758,14 @@ async fn consume_native_stream(
     tokio::time::timeout(timeout, async move {
         let mut outcome = NativeClaudeStreamOutcome::default();
         let mut pending_tool: Option<NativeClaudeToolCall> = None;
+        let mut text_cursor = StreamTextCursor::default();
         while let Some(event) = stream.next().await {
             outcome.total_events += 1;
-            match event.context("native provider stream event error")? {
+            let event = event.context("native provider stream event error")?;
+            let Some(event) = text_cursor.normalize(event) else {
+                continue;
+            };
+            match event {
                 StreamEvent::TextDelta(text) => {
                     outcome.chunk_count += 1;
                     outcome.text.push_str(&text);
@@ -342,7 +342,10 @@ pub fn build_contents(messages: &[Message]) -> Vec<GeminiContent> {
                             ..Default::default()
                         });
                     }
-                    ContentBlock::OpenAICompaction { .. } => {}
+                    ContentBlock::OpenAICompaction { .. }
+                    | ContentBlock::ServerToolUse { .. }
+                    | ContentBlock::WebSearchResult { .. }
+                    | ContentBlock::Citations { .. } => {}
                 }
             }
             if parts.is_empty() {
@@ -8,6 +8,7 @@ use serde_json::Value;
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::pin::Pin;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::task::{Context as TaskContext, Poll};
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -758,6 +759,68 @@ fn handle_openai_image_generation_item(
     })
 }
 
+/// How far a Responses stream got: the response being streamed, the last
+/// event `sequence_number`, and the bytes of text delivered. Shared across
+/// reconnects, so a resumed stream skips events it already delivered and
+/// stamps text with offsets consumers can de-duplicate by.
+#[derive(Debug, Clone, Default)]
+pub struct ResponseStreamCursor {
+    pub response_id: Option<String>,
+    pub sequence_number: Option<u64>,
+    pub text_offset: usize,
+}
+
+#[derive(Deserialize)]
+struct ResponseEventEnvelope {
+    sequence_number: Option<u64>,
+    response: Option<ResponseEnvelope>,
+}
+
+#[derive(Deserialize)]
+struct ResponseEnvelope {
+    id: Option<String>,
+}
+
+impl ResponseStreamCursor {
+    /// Record the envelope of a raw event. Returns false for an event at or
+    /// before the cursor, which a reconnected stream is delivering again.
+    pub fn observe(&mut self, data: &str) -> bool {
+        let Ok(envelope) = serde_json::from_str::<ResponseEventEnvelope>(data) else {
+            return true;
+        };
+        if let Some(id) = envelope.response.and_then(|response| response.id) {
+            self.response_id = Some(id);
+        }
+        if let Some(sequence_number) = envelope.sequence_number {
+            if self
+                .sequence_number
+                .is_some_and(|last| sequence_number <= last)
+            {
+                return false;
+            }
+            self.sequence_number = Some(sequence_number);
+        }
+        true
+    }
+
+    /// Turn a `TextDelta` into a `TextDeltaAt` at the current text offset.
+    pub fn stamp(&mut self, event: StreamEvent) -> StreamEvent {
+        match event {
+            StreamEvent::TextDelta(text) => {
+                let offset = self.text_offset;
+                self.text_offset += text.len();
+                StreamEvent::TextDeltaAt { offset, text }
+            }
+            other => other,
+        }
+    }
+
+    /// The response and last sequence number to resume from, once known.
+    pub fn resume_point(&self) -> Option<(&str, u64)> {
+        Some((self.response_id.as_deref()?, self.sequence_number?))
+    }
+}
+
 pub struct OpenAIResponsesStream {
     inner: Pin<Box<dyn Stream<Item = Result<Bytes, reqwest::Error>> + Send>>,
     buffer: String,
@@ -765,6 +828,7 @@ pub struct OpenAIResponsesStream {
     saw_text_delta: bool,
     streaming_tool_calls: HashMap<String, StreamingToolCallState>,
     completed_tool_items: HashSet<String>,
+    cursor: Option<Arc<Mutex<ResponseStreamCursor>>>,
 }
 
 impl OpenAIResponsesStream {
@@ -776,10 +840,31 @@ impl OpenAIResponsesStream {
             saw_text_delta: false,
             streaming_tool_calls: HashMap::new(),
             completed_tool_items: HashSet::new(),
+            cursor: None,
         }
     }
 
+    /// Track progress in `cursor`: drop events it already covers and emit
+    /// text as offset-stamped `TextDeltaAt`s.
+    pub fn with_cursor(mut self, cursor: Arc<Mutex<ResponseStreamCursor>>) -> Self {
+        self.cursor = Some(cursor);
+        self
+    }
+
     fn parse_next_event(&mut self) -> Option<StreamEvent> {
+        let event = self.parse_next_raw_event()?;
+        match &self.cursor {
+            Some(cursor) => Some(
+                cursor
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .stamp(event),
+            ),
+            None => Some(event),
+        }
+    }
+
+    fn parse_next_raw_event(&mut self) -> Option<StreamEvent> {
         if let Some(event) = self.pending.pop_front() {
             return Some(event);
         }
@@ -800,6 +885,14 @@ impl OpenAIResponsesStream {
             }
 
             let data = data_lines.join("\n");
+            if let Some(cursor) = &self.cursor
+                && !cursor
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .observe(&data)
+            {
+                continue;
+            }
             if let Some(event) = parse_openai_response_event(
                 &data,
                 &mut self.saw_text_delta,
@@ -973,6 +1066,77 @@ mod tests {
         );
     }
 
+    fn sse_stream(
+        events: &[Value],
+        cursor: &Arc<Mutex<ResponseStreamCursor>>,
+    ) -> OpenAIResponsesStream {
+        let body: String = events
+            .iter()
+            .map(|event| format!("data: {}\n\n", event))
+            .collect();
+        let chunks: Vec<Result<Bytes, reqwest::Error>> = vec![Ok(Bytes::from(body))];
+        OpenAIResponsesStream::new(futures::stream::iter(chunks)).with_cursor(Arc::clone(cursor))
+    }
+
+    fn text_delta(sequence_number: u64, delta: &str) -> Value {
+        serde_json::json!({
+            "type": "response.output_text.delta",
+            "sequence_number": sequence_number,
+            "delta": delta,
+        })
+    }
+
+    #[test]
+    fn resumed_stream_skips_replayed_events_and_continues_offsets() {
+        use futures::StreamExt;
+
+        let cursor = Arc::new(Mutex::new(ResponseStreamCursor::default()));
+        let created = serde_json::json!({
+            "type": "response.created",
+            "sequence_number": 0,
+            "response": { "id": "resp_1", "status": "in_progress" },
+        });
+        let first = sse_stream(
+            &[created, text_delta(1, "Hello"), text_delta(2, " wor")],
+            &cursor,
+        );
+        // The reconnect repeats events the first connection already delivered.
+        let second = sse_stream(
+            &[
+                text_delta(2, " wor"),
+                text_delta(3, "ld"),
+                serde_json::json!({
+                    "type": "response.completed",
+                    "sequence_number": 4,
+                    "response": { "id": "resp_1", "status": "completed" },
+                }),
+            ],
+            &cursor,
+        );
+
+        let events: Vec<StreamEvent> = futures::executor::block_on(
+            first
+                .chain(second)
+                .map(|event| event.expect("stream event"))
+                .collect(),
+        );
+        let stamped: Vec<(usize, &str)> = events
+            .iter()
+            .filter_map(|event| match event {
+                StreamEvent::TextDeltaAt { offset, text } => Some((*offset, text.as_str())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(stamped, vec![(0, "Hello"), (5, " wor"), (9, "ld")]);
+        assert!(matches!(
+            events.last(),
+            Some(StreamEvent::MessageEnd { .. })
+        ));
+        let cursor = cursor.lock().unwrap();
+        assert_eq!(cursor.resume_point(), Some(("resp_1", 4)));
+        assert_eq!(cursor.text_offset, 11);
+    }
+
     #[test]
     fn plain_text_fallback_notice_is_still_dropped() {
         let mut saw_text_delta = false;
@@ -22,6 +22,8 @@ pub struct OpenRouterStream {
     tool_call_accumulators: std::collections::BTreeMap<u64, ToolCallAccumulator>,
     /// Track if we've emitted the provider info (only emit once)
     provider_emitted: bool,
+    /// Track if we've emitted the `system_fingerprint` (only emit once)
+    fingerprint_emitted: bool,
     model: String,
     provider_pin: Arc<Mutex<Option<ProviderPin>>>,
     reasoning_buffer: String,
@@ -48,6 +50,7 @@ impl OpenRouterStream {
             pending: VecDeque::new(),
             tool_call_accumulators: std::collections::BTreeMap::new(),
             provider_emitted: false,
+            fingerprint_emitted: false,
             model,
             provider_pin,
             reasoning_buffer: String::new(),
@@ -245,6 +248,19 @@ impl OpenRouterStream {
                 });
             }
 
+            // OpenAI-compatible backends report which configuration served
+            // the request; reproducible runs record it.
+            if !self.fingerprint_emitted
+                && let Some(fingerprint) = parsed
+                    .get("system_fingerprint")
+                    .and_then(|f| f.as_str())
+                    .filter(|f| !f.is_empty())
+            {
+                self.fingerprint_emitted = true;
+                self.pending
+                    .push_back(StreamEvent::SystemFingerprint(fingerprint.to_string()));
+            }
+
             // Check for error
             if let Some(error) = parsed.get("error") {
                 let message = error
@@ -465,6 +481,29 @@ mod tests {
         assert!(matches!(event, Some(StreamEvent::ThinkingDelta(text)) if text == "thinking"));
     }
 
+    #[test]
+    fn parse_next_event_emits_system_fingerprint_once() {
+        let provider_pin = Arc::new(std::sync::Mutex::new(None));
+        let mut stream = OpenRouterStream::new(
+            futures::stream::empty(),
+            "gpt-4o-2024-08-06".to_string(),
+            provider_pin,
+        );
+        let chunk = "data: {\"system_fingerprint\":\"fp_44709d6fcb\",\"choices\":[{\"delta\":{\"content\":\"hi\"}}]}\n\n";
+        stream.buffer = format!("{chunk}{chunk}");
+
+        assert!(matches!(
+            stream.parse_next_event(),
+            Some(StreamEvent::SystemFingerprint(fingerprint)) if fingerprint == "fp_44709d6fcb"
+        ));
+        assert!(
+            matches!(stream.parse_next_event(), Some(StreamEvent::TextDelta(text)) if text == "hi")
+        );
+        assert!(
+            matches!(stream.parse_next_event(), Some(StreamEvent::TextDelta(text)) if text == "hi")
+        );
+    }
+
     #[test]
     fn parse_next_event_propagates_finish_reason_to_message_end() {
         let provider_pin = Arc::new(std::sync::Mutex::new(None));
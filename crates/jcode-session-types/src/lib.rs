@@ -52,6 +52,9 @@ pub struct RenderedMessage {
     pub content: String,
     pub tool_calls: Vec<String>,
     pub tool_data: Option<ToolCall>,
+    /// Model, timing and token usage behind an assistant message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<StoredTokenUsage>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -167,12 +170,64 @@ pub enum SessionImproveMode {
     RefactorPlan,
 }
 
+/// Model route a session runs on: the model, the upstream provider it is
+/// pinned to (OpenRouter `model@provider`), and the API method that reaches
+/// it (e.g. "openrouter", "openai-api", "openai-compatible:nvidia-nim").
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SessionModelRoute {
+    pub model: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_method: Option<String>,
+}
+
+impl SessionModelRoute {
+    /// Model request string carrying the provider pin, e.g. `model@provider`.
+    pub fn model_spec(&self) -> String {
+        match self.provider.as_deref().filter(|p| !p.trim().is_empty()) {
+            Some(provider) => format!("{}@{}", self.model, provider),
+            None => self.model.clone(),
+        }
+    }
+}
+
+/// A stored route that could not be restored as-is on resume, and the route
+/// the session fell back to instead.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ModelRouteFallback {
+    pub requested: SessionModelRoute,
+    pub effective: SessionModelRoute,
+    /// Why the requested route is unusable (e.g. "not available for your account").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl ModelRouteFallback {
+    /// One-line description, e.g.
+    /// "requested gpt-5.3-codex → using gpt-5.2-codex (not available for your account)".
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "requested {} → using {}",
+            self.requested.model_spec(),
+            self.effective.model_spec()
+        );
+        if let Some(reason) = self.reason.as_deref().filter(|r| !r.trim().is_empty()) {
+            summary.push_str(&format!(" ({})", reason.trim()));
+        }
+        summary
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitState {
     pub root: String,
     pub head: Option<String>,
     pub branch: Option<String>,
     pub dirty: Option<bool>,
+    /// `origin` remote URL, used to find the repository again if it moves.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -241,7 +296,7 @@ pub enum StoredDisplayRole {
     BackgroundTask,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct StoredTokenUsage {
     pub input_tokens: u64,
     pub output_tokens: u64,
@@ -249,6 +304,119 @@ pub struct StoredTokenUsage {
     pub cache_read_input_tokens: Option<u64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cache_creation_input_tokens: Option<u64>,
+    /// Provider-side web searches billed for this response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub web_search_requests: Option<u64>,
+    /// Estimated tokens removed from the request by superseded-read pruning.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pruned_context_tokens: Option<u64>,
+    /// Values replaced by `[privacy]` redaction in the messages added since
+    /// the previous request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redactions: Option<u64>,
+    /// Model that produced this response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Provider that served this response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    /// Wall-clock time of the API call, from request to final stream event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    /// What `[provider] deterministic` pinned for this response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub determinism: Option<jcode_message_types::DeterminismParams>,
+}
+
+impl StoredTokenUsage {
+    /// Fold a later response into this one, as when one visible answer spans
+    /// several tool-calling API calls. Model attribution and determinism keep
+    /// the latest.
+    pub fn accumulate(&mut self, other: &StoredTokenUsage) {
+        fn add(a: Option<u64>, b: Option<u64>) -> Option<u64> {
+            match (a, b) {
+                (None, None) => None,
+                (a, b) => Some(a.unwrap_or(0) + b.unwrap_or(0)),
+            }
+        }
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cache_read_input_tokens =
+            add(self.cache_read_input_tokens, other.cache_read_input_tokens);
+        self.cache_creation_input_tokens = add(
+            self.cache_creation_input_tokens,
+            other.cache_creation_input_tokens,
+        );
+        self.web_search_requests = add(self.web_search_requests, other.web_search_requests);
+        self.pruned_context_tokens = add(self.pruned_context_tokens, other.pruned_context_tokens);
+        self.redactions = add(self.redactions, other.redactions);
+        self.duration_ms = add(self.duration_ms, other.duration_ms);
+        if other.model.is_some() {
+            self.model = other.model.clone();
+        }
+        if other.provider.is_some() {
+            self.provider = other.provider.clone();
+        }
+        if other.determinism.is_some() {
+            self.determinism = other.determinism.clone();
+        }
+    }
+
+    /// One-line attribution for the response, e.g.
+    /// `claude-sonnet-4 · anthropic · 4.2s · ↑12k ↓850 · cache 10k`.
+    pub fn summary_line(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        if let Some(model) = self.model.as_deref().filter(|model| !model.is_empty()) {
+            parts.push(model.to_string());
+        }
+        if let Some(provider) = self
+            .provider
+            .as_deref()
+            .filter(|provider| !provider.is_empty())
+        {
+            parts.push(provider.to_string());
+        }
+        if let Some(duration_ms) = self.duration_ms {
+            parts.push(Message::format_duration(duration_ms));
+        }
+        if self.input_tokens > 0 || self.output_tokens > 0 {
+            parts.push(format!(
+                "↑{} ↓{}",
+                compact_token_count(self.input_tokens),
+                compact_token_count(self.output_tokens)
+            ));
+        }
+        if let Some(cache_read) = self.cache_read_input_tokens.filter(|tokens| *tokens > 0) {
+            parts.push(format!("cache {}", compact_token_count(cache_read)));
+        }
+        (!parts.is_empty()).then(|| parts.join(" · "))
+    }
+}
+
+/// Format a token count compactly (e.g. 63000 -> "63k").
+fn compact_token_count(tokens: u64) -> String {
+    if tokens >= 1_000_000 {
+        format!("{:.1}M", tokens as f64 / 1_000_000.0)
+    } else if tokens >= 1_000 {
+        format!("{:.0}k", tokens as f64 / 1_000.0)
+    } else {
+        tokens.to_string()
+    }
+}
+
+/// Usage of one model across a session, aggregated from assistant responses.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModelUsageStats {
+    /// Model name, or `None` for responses recorded before attribution existed.
+    pub model: Option<String>,
+    pub provider: Option<String>,
+    pub responses: usize,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_input_tokens: u64,
+    pub duration_ms: u64,
+    /// Responses that carried a duration, for averaging `duration_ms`.
+    pub timed_responses: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
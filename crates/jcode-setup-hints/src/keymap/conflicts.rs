@@ -181,11 +181,67 @@ pub fn jcode_bindings(cfg: &KeybindingsConfig) -> Vec<JcodeBinding> {
             "Toggle info widget",
             cfg.info_widget_toggle.as_str(),
         ),
+        (
+            "todo_checklist_toggle",
+            "Collapse todo checklist",
+            cfg.todo_checklist_toggle.as_str(),
+        ),
         (
             "new_terminal",
             "Spawn new terminal session",
             cfg.new_terminal.as_str(),
         ),
+        (
+            "interject",
+            "Interject into running turn",
+            cfg.interject.as_str(),
+        ),
+        (
+            "image_open",
+            "Open focused image in viewer",
+            cfg.image_open.as_str(),
+        ),
+        (
+            "remember_message",
+            "Save focused answer as memory",
+            cfg.remember_message.as_str(),
+        ),
+        (
+            "copy_message",
+            "Copy focused message",
+            cfg.copy_message.as_str(),
+        ),
+        ("file_picker", "Open file picker", cfg.file_picker.as_str()),
+        (
+            "composer_kill_line_end",
+            "Kill to end of line",
+            cfg.composer_kill_line_end.as_str(),
+        ),
+        (
+            "composer_kill_line_start",
+            "Kill to start of line",
+            cfg.composer_kill_line_start.as_str(),
+        ),
+        (
+            "composer_kill_word_back",
+            "Kill previous word",
+            cfg.composer_kill_word_back.as_str(),
+        ),
+        (
+            "composer_yank",
+            "Yank from kill ring",
+            cfg.composer_yank.as_str(),
+        ),
+        (
+            "composer_yank_pop",
+            "Cycle yanked text",
+            cfg.composer_yank_pop.as_str(),
+        ),
+        (
+            "composer_transpose",
+            "Transpose characters",
+            cfg.composer_transpose.as_str(),
+        ),
     ];
 
     let mut out = Vec::new();
@@ -201,7 +257,7 @@ pub fn jcode_bindings(cfg: &KeybindingsConfig) -> Vec<JcodeBinding> {
     }
 
     // Multi-binding (comma-separated list) fields.
-    let multi: [(&str, &str, &str); 4] = [
+    let multi: [(&str, &str, &str); 7] = [
         (
             "workspace_left",
             "Move to left workspace",
@@ -222,6 +278,21 @@ pub fn jcode_bindings(cfg: &KeybindingsConfig) -> Vec<JcodeBinding> {
             "Move to right workspace",
             cfg.workspace_right.as_str(),
         ),
+        (
+            "composer_word_back",
+            "Move back one word",
+            cfg.composer_word_back.as_str(),
+        ),
+        (
+            "composer_word_forward",
+            "Move forward one word",
+            cfg.composer_word_forward.as_str(),
+        ),
+        (
+            "composer_undo",
+            "Undo input edit",
+            cfg.composer_undo.as_str(),
+        ),
     ];
     for (field, action, raw) in multi {
         for piece in raw.split(',') {
@@ -260,6 +260,38 @@ pub fn write_bytes(path: &Path, bytes: &[u8]) -> Result<()> {
     write_bytes_inner(path, bytes, true)
 }
 
+/// Temp file + rename in the same directory. Unlike [`write_bytes`] this
+/// leaves no `.bak` behind, for files that live in the user's own tree.
+pub fn write_bytes_without_backup(path: &Path, bytes: &[u8]) -> Result<()> {
+    let parent = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    ensure_dir(parent)?;
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "file".to_string());
+    let nonce: u32 = rand::random();
+    let tmp_path = parent.join(format!(
+        ".{}.jcode-tmp.{}.{}",
+        file_name,
+        std::process::id(),
+        nonce
+    ));
+    let result = (|| -> Result<()> {
+        let mut file = std::fs::File::create(&tmp_path)?;
+        file.write_all(bytes)?;
+        file.sync_all()?;
+        std::fs::rename(&tmp_path, path)?;
+        Ok(())
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp_path);
+    }
+    result
+}
+
 fn write_json_inner<T: Serialize + ?Sized>(path: &Path, value: &T, durable: bool) -> Result<()> {
     let bytes = serde_json::to_vec(value)?;
     write_bytes_inner(path, &bytes, durable)
@@ -857,6 +857,7 @@ pub fn record_command_family(command: &str) {
 fn post_payload(payload: serde_json::Value, timeout: Duration) -> bool {
     let client = match reqwest::blocking::Client::builder()
         .user_agent(jcode_provider_core::JCODE_USER_AGENT)
+        .dns_resolver(jcode_provider_core::egress::resolver())
         .timeout(timeout)
         .build()
     {
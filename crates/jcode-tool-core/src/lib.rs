@@ -32,6 +32,9 @@ pub struct ToolContext {
     pub message_id: String,
     pub tool_call_id: String,
     pub working_dir: Option<PathBuf>,
+    /// Additional workspace roots beyond `working_dir`, for sessions spanning
+    /// several repositories.
+    pub workspace_roots: Vec<PathBuf>,
     pub stdin_request_tx: Option<tokio::sync::mpsc::UnboundedSender<StdinInputRequest>>,
     pub graceful_shutdown_signal: Option<InterruptSignal>,
     pub execution_mode: ToolExecutionMode,
@@ -50,23 +53,76 @@ impl ToolContext {
             message_id: self.message_id.clone(),
             tool_call_id,
             working_dir: self.working_dir.clone(),
+            workspace_roots: self.workspace_roots.clone(),
             stdin_request_tx: self.stdin_request_tx.clone(),
             graceful_shutdown_signal: self.graceful_shutdown_signal.clone(),
             execution_mode: self.execution_mode,
         }
     }
 
+    /// Resolve a tool path argument against the session's workspace.
+    ///
+    /// Relative paths resolve against `working_dir`. A `name:relative/path`
+    /// prefix addresses an extra workspace root by its directory name, and a
+    /// bare relative path that only exists under one extra root resolves there.
     pub fn resolve_path(&self, path: &Path) -> PathBuf {
         if path.is_absolute() {
-            path.to_path_buf()
-        } else if let Some(ref base) = self.working_dir {
-            base.join(path)
-        } else {
-            path.to_path_buf()
+            return path.to_path_buf();
         }
+        if let Some(resolved) = self.resolve_root_prefixed_path(path) {
+            return resolved;
+        }
+        let Some(ref base) = self.working_dir else {
+            return path.to_path_buf();
+        };
+        let primary = base.join(path);
+        if self.workspace_roots.is_empty() || primary.exists() {
+            return primary;
+        }
+        let mut matches = self
+            .workspace_roots
+            .iter()
+            .map(|root| root.join(path))
+            .filter(|candidate| candidate.exists());
+        match (matches.next(), matches.next()) {
+            (Some(only), None) => only,
+            _ => primary,
+        }
+    }
+
+    /// Directories a workspace-wide search should cover: `working_dir` first,
+    /// then each extra workspace root.
+    pub fn search_roots(&self) -> Vec<PathBuf> {
+        let mut roots: Vec<PathBuf> = self.working_dir.iter().cloned().collect();
+        for root in &self.workspace_roots {
+            if !roots.contains(root) {
+                roots.push(root.clone());
+            }
+        }
+        roots
+    }
+
+    fn resolve_root_prefixed_path(&self, path: &Path) -> Option<PathBuf> {
+        let raw = path.to_str()?;
+        let (name, rest) = raw.split_once(':')?;
+        let root = self
+            .workspace_roots
+            .iter()
+            .chain(self.working_dir.iter())
+            .find(|root| workspace_root_name(root).as_deref() == Some(name))?;
+        Some(root.join(rest.trim_start_matches(['/', '\\'])))
     }
 }
 
+/// Name used to address a workspace root in `name:relative/path` form.
+///
+/// Single-character names are rejected so Windows drive letters are never
+/// mistaken for root prefixes.
+pub fn workspace_root_name(root: &Path) -> Option<String> {
+    let name = root.file_name()?.to_str()?;
+    (name.chars().count() > 1).then(|| name.to_string())
+}
+
 /// A tool that can be executed by the agent.
 #[async_trait]
 pub trait Tool: Send + Sync {
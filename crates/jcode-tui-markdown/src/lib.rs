@@ -481,7 +481,7 @@ fn mermaid_should_register_active() -> bool {
     !matches!(effective_diagram_mode(), DiagramDisplayMode::None)
 }
 
-fn mermaid_rendering_enabled() -> bool {
+pub fn mermaid_rendering_enabled() -> bool {
     // Temporarily disable Mermaid for users while the renderer is unstable.
     // Developers can opt in explicitly to keep iterating on the feature.
     std::env::var("JCODE_ENABLE_MERMAID").is_ok_and(|value| value == "1")
@@ -1,5 +1,5 @@
 use jcode_message_types::ToolCall;
-use jcode_session_types::RenderedMessage;
+use jcode_session_types::{RenderedMessage, StoredTokenUsage};
 use serde_json::Value;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
@@ -53,6 +53,18 @@ impl DisplayMessage {
         }
     }
 
+    /// Create a user message injected into a running turn (soft interrupt).
+    pub fn interjection(content: impl Into<String>) -> Self {
+        Self {
+            role: "interjection".to_string(),
+            content: content.into(),
+            tool_calls: Vec::new(),
+            duration_secs: None,
+            title: None,
+            tool_data: None,
+        }
+    }
+
     /// Create a display-only usage card. This is shown in the transcript UI but
     /// is not part of provider/model context.
     pub fn usage(content: impl Into<String>) -> Self {
@@ -79,6 +91,19 @@ impl DisplayMessage {
         }
     }
 
+    /// Create a display-only `/aside` card. This is shown in the transcript UI
+    /// but is not part of provider/model context.
+    pub fn aside(title: impl Into<String>, content: impl Into<String>) -> Self {
+        Self {
+            role: "aside".to_string(),
+            content: content.into(),
+            tool_calls: Vec::new(),
+            duration_secs: None,
+            title: Some(title.into()),
+            tool_data: None,
+        }
+    }
+
     /// Create a memory injection message (bordered box display).
     pub fn memory(title: impl Into<String>, content: impl Into<String>) -> Self {
         Self {
@@ -175,6 +200,12 @@ impl DisplayMessage {
         }
     }
 
+    /// Create the dim attribution footer shown under a restored assistant
+    /// message, or `None` when the usage carries nothing worth showing.
+    pub fn usage_footer(usage: &StoredTokenUsage) -> Option<Self> {
+        usage.summary_line().map(Self::meta)
+    }
+
     /// Create a display-only collapsing reasoning trace ("current" mode). The
     /// content is sentinel-wrapped dim/italic markup; this message height-collapses
     /// toward a one-line summary and is excluded from provider/model context.
@@ -244,13 +275,23 @@ impl DisplayMessage {
     }
 }
 
+/// Convert rendered history into transcript messages. With `usage_footers`
+/// set, each assistant message carrying usage is followed by its footer.
 pub fn display_messages_from_rendered_messages(
     messages: impl IntoIterator<Item = RenderedMessage>,
+    usage_footers: bool,
 ) -> Vec<DisplayMessage> {
-    messages
-        .into_iter()
-        .map(DisplayMessage::from_rendered_message)
-        .collect()
+    let mut out = Vec::new();
+    for mut item in messages {
+        let footer = item
+            .usage
+            .take()
+            .filter(|_| usage_footers)
+            .and_then(|usage| DisplayMessage::usage_footer(&usage));
+        out.push(DisplayMessage::from_rendered_message(item));
+        out.extend(footer);
+    }
+    out
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -423,6 +464,7 @@ mod tests {
             content: "done".to_string(),
             tool_calls: vec!["read".to_string()],
             tool_data: None,
+            usage: None,
         };
 
         let display = DisplayMessage::from_rendered_message(rendered);
@@ -432,6 +474,36 @@ mod tests {
         assert!(display.tool_data.is_none());
     }
 
+    #[test]
+    fn rendered_usage_becomes_a_meta_footer_when_enabled() {
+        let rendered = RenderedMessage {
+            role: "assistant".to_string(),
+            content: "done".to_string(),
+            tool_calls: Vec::new(),
+            tool_data: None,
+            usage: Some(StoredTokenUsage {
+                input_tokens: 12_400,
+                output_tokens: 850,
+                cache_read_input_tokens: Some(10_000),
+                model: Some("claude-sonnet-4".to_string()),
+                provider: Some("anthropic".to_string()),
+                duration_ms: Some(4_200),
+                ..StoredTokenUsage::default()
+            }),
+        };
+
+        let with_footer = display_messages_from_rendered_messages(vec![rendered.clone()], true);
+        assert_eq!(with_footer.len(), 2);
+        assert_eq!(with_footer[1].role, "meta");
+        assert_eq!(
+            with_footer[1].content,
+            "claude-sonnet-4 · anthropic · 4.2s · ↑12k ↓850 · cache 10k"
+        );
+
+        let without_footer = display_messages_from_rendered_messages(vec![rendered], false);
+        assert_eq!(without_footer.len(), 1);
+    }
+
     #[test]
     fn transcript_preview_lines_share_desktop_labeling() {
         let messages = [
@@ -13,7 +13,11 @@ use ratatui::{
 };
 use serde_json::{Map, Value};
 use std::io::IsTerminal;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+/// How often the viewer re-reads the shared inbox, so requests answered in
+/// the TUI, the desktop app or Telegram drop out here too.
+const INBOX_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
 
 struct PermissionsApp {
     requests: Vec<PermissionRequest>,
@@ -50,10 +54,27 @@ impl PermissionsApp {
         self.selected = self.selected.saturating_sub(1);
     }
 
+    /// Replace the list with the current inbox, keeping the selection on the
+    /// same request when it is still pending.
+    fn sync_with_inbox(&mut self, latest: Vec<PermissionRequest>) {
+        if self.done {
+            return;
+        }
+        let selected_id = self.selected_request().map(|req| req.id.clone());
+        self.requests = latest;
+        self.selected = selected_id
+            .and_then(|id| self.requests.iter().position(|req| req.id == id))
+            .unwrap_or_else(|| self.selected.min(self.requests.len().saturating_sub(1)));
+        if self.requests.is_empty() {
+            self.deny_input = None;
+            self.done = true;
+        }
+    }
+
     fn approve_selected(&mut self) {
         if let Some(req) = self.requests.get(self.selected) {
             let id = req.id.clone();
-            let _ = safety::record_permission_via_file(&id, true, "permissions_tui", None);
+            let _ = safety::record_decision(&id, true, "permissions_tui", None);
             self.requests.remove(self.selected);
             self.approved_count += 1;
             if self.selected >= self.requests.len() && self.selected > 0 {
@@ -68,7 +89,7 @@ impl PermissionsApp {
     fn deny_selected(&mut self, reason: Option<String>) {
         if let Some(req) = self.requests.get(self.selected) {
             let id = req.id.clone();
-            let _ = safety::record_permission_via_file(&id, false, "permissions_tui", reason);
+            let _ = safety::record_decision(&id, false, "permissions_tui", reason);
             self.requests.remove(self.selected);
             self.denied_count += 1;
             if self.selected >= self.requests.len() && self.selected > 0 {
@@ -83,7 +104,7 @@ impl PermissionsApp {
     fn approve_all(&mut self) {
         while !self.requests.is_empty() {
             let id = self.requests[0].id.clone();
-            let _ = safety::record_permission_via_file(&id, true, "permissions_tui", None);
+            let _ = safety::record_decision(&id, true, "permissions_tui", None);
             self.requests.remove(0);
             self.approved_count += 1;
         }
@@ -94,7 +115,7 @@ impl PermissionsApp {
     fn deny_all(&mut self) {
         while !self.requests.is_empty() {
             let id = self.requests[0].id.clone();
-            let _ = safety::record_permission_via_file(&id, false, "permissions_tui", None);
+            let _ = safety::record_decision(&id, false, "permissions_tui", None);
             self.requests.remove(0);
             self.denied_count += 1;
         }
@@ -258,6 +279,17 @@ impl PermissionsApp {
             label_style,
             value_style,
         );
+        let from = req
+            .to_inbox_entry()
+            .status_line(Utc::now().timestamp_millis().max(0) as u64);
+        push_wrapped_field(
+            &mut lines,
+            " From: ",
+            &from,
+            area.width,
+            label_style,
+            value_style,
+        );
         push_wrapped_field(
             &mut lines,
             " Why: ",
@@ -523,7 +555,12 @@ impl PermissionsApp {
                 anyhow::anyhow!("failed to initialize terminal: {}", msg)
             })?;
 
+        let mut last_refresh = Instant::now();
         let result = loop {
+            if last_refresh.elapsed() >= INBOX_REFRESH_INTERVAL {
+                last_refresh = Instant::now();
+                self.sync_with_inbox(safety::inbox());
+            }
             terminal.draw(|frame| self.render(frame))?;
 
             if event::poll(Duration::from_millis(100))?
@@ -833,7 +870,7 @@ fn truncate(s: &str, max_len: usize) -> String {
 pub fn run_permissions() -> Result<()> {
     let system = safety::SafetySystem::new();
     let expired = system.expire_dead_session_requests("permissions_tui_gc")?;
-    let requests = system.pending_requests();
+    let requests = safety::inbox();
 
     if requests.is_empty() {
         if !expired.is_empty() {
@@ -26,6 +26,15 @@ pub fn system_message_color() -> Color {
 pub fn queued_color() -> Color {
     rgb(255, 193, 7)
 }
+pub fn interjection_color() -> Color {
+    rgb(255, 184, 108)
+}
+pub fn urgent_interjection_color() -> Color {
+    rgb(255, 110, 110)
+}
+pub fn aside_color() -> Color {
+    rgb(120, 200, 190)
+}
 pub fn asap_color() -> Color {
     rgb(110, 210, 255)
 }
@@ -49,26 +49,48 @@ pub enum AppRuntimeMode {
     TestHarness,
 }
 
+mod ask_user;
 mod auth;
 mod auth_account_picker_saved_accounts;
+mod auto_commit;
 mod catchup;
+pub(crate) mod command_registry;
 mod commands;
+mod commands_aside;
+mod commands_changes;
+mod commands_env;
+mod commands_focus;
 mod commands_improve;
+mod commands_instructions;
+mod commands_migrate;
+mod commands_mirror;
 mod commands_overnight;
 mod commands_plan;
+mod commands_profile;
+mod commands_queue;
+mod commands_redaction;
 mod commands_review;
+mod commands_safe;
+mod commands_session_stats;
+mod compare;
 mod conversation_state;
 mod copy_selection;
 mod debug;
 mod dictation;
 mod event_wrappers;
+mod file_attach;
+mod file_view;
+mod handoff;
 mod handterm_native_scroll;
 pub(crate) mod helpers;
 mod hotkey_feedback;
 mod inline_interactive;
 mod input;
 mod input_help;
+mod interjection;
 mod local;
+mod memory_pin;
+mod message_copy;
 mod misc_ui;
 mod model_context;
 mod navigation;
@@ -77,12 +99,14 @@ pub(crate) mod onboarding_flow;
 mod onboarding_flow_control;
 mod onboarding_repair;
 mod onboarding_sim;
+mod permission_inbox;
 mod productivity;
 mod remote;
 mod remote_notifications;
 mod replay;
 pub(crate) mod run_shell;
 mod runtime_memory;
+mod saved_prompts;
 mod shortcut_hints;
 mod split_view;
 mod state_ui;
@@ -91,6 +115,7 @@ mod state_ui_maintenance;
 mod state_ui_messages;
 mod state_ui_runtime;
 mod state_ui_storage;
+mod todo_checklist;
 mod todos_view;
 mod tui_lifecycle;
 mod tui_lifecycle_runtime;
@@ -98,6 +123,7 @@ mod tui_state;
 mod turn;
 mod turn_memory;
 mod turn_notify;
+mod turn_tee;
 mod ui_prefs;
 
 pub(crate) use self::state_ui_storage::compact_display_messages_for_storage;
@@ -150,8 +176,6 @@ struct TurnReasoningTrace {
 #[derive(Debug, Clone)]
 struct LocalRewindUndoSnapshot {
     messages: Vec<StoredMessage>,
-    provider_session_id: Option<String>,
-    session_provider_session_id: Option<String>,
     visible_message_count: usize,
 }
 
@@ -612,6 +636,14 @@ struct CommandCandidatesCache {
     candidates: Vec<(String, &'static str)>,
 }
 
+/// Recent session names offered as `/resume` arguments, re-read from disk
+/// once it goes stale.
+#[derive(Clone, Debug)]
+struct ResumeCandidatesCache {
+    loaded_at: Instant,
+    names: Vec<String>,
+}
+
 /// Session-wide token and cache accounting accumulated across all turns.
 ///
 /// Grouped out of [`App`] to keep the cohesive token/cache totals together. The
@@ -725,6 +757,7 @@ pub struct App {
     pending_history_anchor: Option<HistoryScrollAnchor>,
     input: String,
     command_candidates_cache: RefCell<Option<CommandCandidatesCache>>,
+    resume_candidates_cache: RefCell<Option<ResumeCandidatesCache>>,
     cursor_pos: usize,
     scroll_offset: usize,
     /// Pauses auto-scroll when user scrolls up during streaming
@@ -790,6 +823,10 @@ pub struct App {
     pending_turn: bool,
     // When armed by /poke, automatically continue prompting until todos are complete.
     auto_poke_incomplete_todos: bool,
+    // `[output] tee_file`, overridden for this session by `/tee`.
+    tee_file: Option<std::path::PathBuf>,
+    // `[git] auto_commit`, toggled for this session by `/autocommit`.
+    auto_commit_enabled: bool,
     // When armed by /overnight, automatically continue guarded follow-up turns until wake/wrap.
     overnight_auto_poke: Option<OvernightAutoPokeState>,
     // Pending cross-provider resend after a failover warning/countdown.
@@ -827,6 +864,8 @@ pub struct App {
     // measured per-attempt instead of inheriting the whole-turn elapsed time
     // (which would immediately render yellow on later round-trips of a turn).
     connection_phase_started: Option<Instant>,
+    // When the running MCP tool call times out, for the status-line countdown
+    mcp_call_deadline: Option<Instant>,
     // Semantic stream buffer for chunked output
     stream_buffer: StreamBuffer,
     // Track thinking start time for extended thinking display
@@ -1022,6 +1061,11 @@ pub struct App {
     pub is_replay: bool,
     // Suppress terminal title updates for off-screen/silent replay instances.
     suppress_terminal_title_updates: bool,
+    // `[display] title_format` and `tmux_status`, plus the last title written so
+    // state changes only touch the terminal when the title actually changes.
+    title_format: String,
+    tmux_status: bool,
+    last_terminal_title: Option<String>,
     /// Override for elapsed time during headless video replay.
     pub replay_elapsed_override: Option<Duration>,
     /// Sim-time at which processing started (video replay only)
@@ -1049,12 +1093,23 @@ pub struct App {
     swarm_plan_swarm_id: Option<String>,
     // Number of connected clients (remote mode only)
     remote_client_count: Option<usize>,
+    // Agent limit usage of the last request (sent when `[agent]` limits are set)
+    last_agent_limits: Option<crate::protocol::AgentLimitStatus>,
+    // Ranking report of the last memory injection, for `/context memories`
+    last_memory_injection: Option<crate::protocol::MemoryInjectionReport>,
+    // Skills the server auto-loaded for this session, with what triggered each
+    remote_active_skills: Vec<crate::protocol::ActiveSkillStatus>,
     // Build version tracking for auto-migration
     known_stable_version: Option<String>,
     // Last time we checked for stable version
     last_version_check: Option<Instant>,
-    // Pending migration to new stable version
-    pending_migration: Option<String>,
+    // Migration onto a newer binary, announced ahead of time (see `/migrate`)
+    pending_migration: Option<commands_migrate::PendingMigration>,
+    // `/mirror` bookkeeping; the path itself lives in `session.mirror_path`.
+    // A remote client records path changes on the server once it is idle.
+    mirror_last_write: Option<Instant>,
+    mirror_last_hash: Option<u64>,
+    mirror_sync_pending: bool,
     // Session to resume on connect (remote mode)
     resume_session_id: Option<String>,
     // Exit code to use when quitting (for canary wrapper communication)
@@ -1117,6 +1172,8 @@ pub struct App {
     side_panel_image_zoom_percent: u8,
     diff_pane_focus: bool,
     diff_pane_auto_scroll: bool,
+    // File pinned with `/view <path>`; `None` follows the latest edit
+    file_view_pin: Option<String>,
     side_panel: crate::side_panel::SidePanelSnapshot,
     observe_mode_enabled: bool,
     observe_page_markdown: String,
@@ -1130,6 +1187,8 @@ pub struct App {
     todos_view_markdown: String,
     todos_view_updated_at_ms: u64,
     todos_view_rendered_hash: u64,
+    // Live todo checklist above the input
+    todo_checklist: crate::tui::todo_checklist::TodoChecklist,
     last_side_panel_refresh: Option<Instant>,
     // Most recently persisted focus target for dictation routing.
     last_client_focus_recorded_at: Option<Instant>,
@@ -1211,8 +1270,19 @@ pub struct App {
     new_terminal_key: OptionalBinding,
     // Optional configured keybinding for opening the /resume session picker
     open_resume_key: OptionalBinding,
+    // Configured keybinding for the inline interjection input (Alt+Enter by default)
+    interject_key: OptionalBinding,
+    // Configured keybinding that opens the focused image in the system viewer
+    image_open_key: OptionalBinding,
+    // Configured keybinding that saves the focused assistant answer as a memory
+    remember_message_key: OptionalBinding,
+    copy_message_key: OptionalBinding,
+    // Configured keybinding that opens the workspace file picker
+    file_picker_key: OptionalBinding,
     // Optional configured keybinding for accepting the post-error fallback offer
     fallback_switch_key: OptionalBinding,
+    // Configurable readline-style composer editing chords (kill/yank/undo/...)
+    composer_edit_keys: super::keybind::ComposerEditKeys,
     // Active external dictation session, if one is running
     dictation_session: Option<dictation::ActiveDictation>,
     // Whether an external dictation command is currently running
@@ -1229,6 +1299,9 @@ pub struct App {
     stashed_input: Option<(String, usize)>,
     // Undo history for in-progress input editing (Ctrl+Z)
     input_undo_stack: Vec<(String, usize)>,
+    // Kill ring for composer kills (Ctrl+K/U/W) and yanks (Ctrl+Y), kept for
+    // the whole session so text killed from one draft can be yanked into the next
+    kill_ring: super::line_editor::KillRing,
     // Short-lived notice for status feedback (model switch, cycle diff mode, etc.)
     status_notice: Option<(String, Instant)>,
     // Distinct learned-keybinding nudge ("you keep doing X the slow way, press
@@ -1252,12 +1325,34 @@ pub struct App {
     // the remote History bootstrap clears the transcript for a brand-new session,
     // which otherwise makes the card flash for a moment and disappear.
     pending_startup_notice: Option<(String, String)>,
+    // `jcode --profile <name>`: agent profile to select once connected.
+    pending_startup_profile: Option<String>,
+    // `jcode --safe`: turn on safe mode once connected.
+    pending_startup_safe_mode: bool,
+    // Safe mode or clipboard read approval waiting for a `y` or `n` from the
+    // input box.
+    pending_safe_mode_approval: Option<String>,
+    // `ask_user` question from the agent, answered by the next message typed
+    // into the input box.
+    pending_ask_user: Option<String>,
+    // Shared permission inbox (every pending approval, whatever its origin)
+    // and the selected row while the Ctrl+P panel is open.
+    permission_inbox: Vec<crate::protocol::PermissionInboxEntry>,
+    permission_inbox_selected: Option<usize>,
+    // Local mode reads the inbox itself; remote clients get server events.
+    last_permission_inbox_poll: Option<Instant>,
+    // The server's turn queue for this session, and whether the next snapshot
+    // should be printed because `/queue` asked for it.
+    remote_turn_queue: Vec<crate::protocol::TurnQueueItem>,
+    turn_queue_display_pending: bool,
     // Experimental feature warnings already shown in this session.
     experimental_feature_warnings_seen: HashSet<String>,
     // Active first-use experimental warning for the currently running tool.
     active_experimental_feature_notice: Option<String>,
     // Message to interleave during processing (set via Ctrl+Enter in queue mode)
     interleave_message: Option<String>,
+    // Inline interjection being composed in the input while a turn runs
+    interjection: Option<super::InterjectionPriority>,
     // Message sent as soft interrupt but not yet injected (shown in queue preview until injected)
     pending_soft_interrupts: Vec<String>,
     // Soft interrupts written to the socket but not yet acknowledged by the server.
@@ -1291,6 +1386,7 @@ pub struct App {
     queue_mode: bool,
     // Automatically reload the remote server when a newer server binary is detected.
     auto_server_reload: bool,
+    migration_grace_secs: u64,
     // After an interrupt, wait one redraw before auto-dispatching queued followups so
     // the queued preview can render in the interrupted state first.
     pending_queued_dispatch: bool,
@@ -1333,6 +1429,15 @@ pub struct App {
     pending_login: Option<PendingLogin>,
     /// Pending account picker follow-up input (new label or setting value)
     pending_account_input: Option<auth::PendingAccountInput>,
+    /// `/remember` flow: condensing an answer, or its draft awaiting submit
+    pending_memory_pin: Option<memory_pin::PendingMemoryPin>,
+    /// `/prompt` waiting for variable values typed into the input box.
+    pending_saved_prompt: Option<saved_prompts::PendingSavedPrompt>,
+    /// `/aside end` flow: drafting the summary, or its draft awaiting submit
+    pending_aside_summary: Option<commands_aside::PendingAsideSummary>,
+    /// `/handoff` flow: drafting the summary, its draft awaiting submit, or
+    /// waiting for the server to create the new session
+    pending_handoff: Option<handoff::PendingHandoff>,
     /// Pending SSH remote target prompt. Stores the friendly remote name.
     pending_ssh_remote_name: Option<String>,
     /// One-shot flag: force the next paint to clear the terminal first.
@@ -1360,6 +1465,10 @@ pub struct App {
     help_scroll: Option<usize>,
     model_status_scroll: Option<usize>,
     model_status_content: String,
+    /// `/compare` answers waiting for the user to keep one (None = not visible)
+    compare_view: Option<crate::tui::CompareView>,
+    /// Prompt of the `/compare` run still waiting for answers
+    compare_in_flight: Option<String>,
     /// Session picker overlay (None = not visible)
     session_picker_overlay: Option<RefCell<super::session_picker::SessionPicker>>,
     session_picker_mode: SessionPickerMode,
@@ -1369,6 +1478,10 @@ pub struct App {
     in_flight_catchup_resume: Option<PendingCatchupResume>,
     /// Login picker overlay (None = not visible)
     login_picker_overlay: Option<RefCell<super::login_picker::LoginPicker>>,
+    /// Workspace file picker overlay (None = not visible)
+    file_picker_overlay: Option<RefCell<super::file_picker::FilePicker>>,
+    /// Attach/mention toggle last used in the file picker
+    file_picker_mode: super::file_picker::FilePickerMode,
     /// Account picker overlay (None = not visible)
     account_picker_overlay: Option<RefCell<super::account_picker::AccountPicker>>,
     /// Usage overlay (None = not visible)
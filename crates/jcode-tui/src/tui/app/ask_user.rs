@@ -0,0 +1,28 @@
+//! Questions from the agent's `ask_user` tool. The question is shown in the
+//! transcript and the next message typed into the input box answers it
+//! instead of starting a new turn. Commands still run while it waits.
+
+use super::{App, DisplayMessage};
+
+impl App {
+    /// Show a question from the agent and remember it so the next message
+    /// answers it.
+    pub(super) fn note_ask_user_request(&mut self, request_id: String, prompt: &str) {
+        self.push_display_message(
+            DisplayMessage::system(prompt.to_string()).with_title("Question from the agent"),
+        );
+        self.set_status_notice("The agent is waiting for your answer");
+        self.pending_ask_user = Some(request_id);
+    }
+
+    /// If a question is pending and `input` is not a command, take its request
+    /// id so `input` can be sent back as the answer.
+    pub(super) fn take_ask_user_answer(&mut self, input: &str) -> Option<String> {
+        if input.is_empty() || input.starts_with('/') {
+            return None;
+        }
+        let request_id = self.pending_ask_user.take()?;
+        self.set_status_notice("Answer sent");
+        Some(request_id)
+    }
+}
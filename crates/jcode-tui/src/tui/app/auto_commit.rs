@@ -0,0 +1,63 @@
+//! `/autocommit` and the `[git] auto_commit` notices.
+//!
+//! The server commits the files each turn modified and reports the commit with
+//! `ServerEvent::AutoCommit` before the turn's `Done`; this module renders it.
+
+use super::{App, DisplayMessage};
+
+pub(super) const USAGE: &str = "Usage: /autocommit [on|off|status]";
+
+pub(super) fn auto_commit_status_message(app: &App) -> String {
+    let config = &crate::config::config().git;
+    let branch = match config.auto_commit_branch.as_deref() {
+        Some(branch) => format!("Commits go to branch `{}`.", branch),
+        None => "Commits go to the current branch.".to_string(),
+    };
+    format!(
+        "Auto-commit: {} (config default: {})\n{}",
+        if app.auto_commit_enabled {
+            "enabled"
+        } else {
+            "disabled"
+        },
+        if config.auto_commit {
+            "enabled"
+        } else {
+            "disabled"
+        },
+        branch,
+    )
+}
+
+impl App {
+    pub(super) fn set_auto_commit_feature_enabled(&mut self, enabled: bool) {
+        self.auto_commit_enabled = enabled;
+        self.set_status_notice(if enabled {
+            "Auto-commit: ON"
+        } else {
+            "Auto-commit: OFF"
+        });
+        self.push_display_message(DisplayMessage::system(format!(
+            "Auto-commit {} for this session.",
+            if enabled { "enabled" } else { "disabled" }
+        )));
+    }
+
+    pub(super) fn note_auto_commit(
+        &mut self,
+        sha: &str,
+        branch: &str,
+        subject: &str,
+        files: usize,
+    ) {
+        self.push_display_message(DisplayMessage::system(format!(
+            "Auto-committed {} on {}: {} ({} file{})",
+            sha,
+            branch,
+            subject,
+            files,
+            if files == 1 { "" } else { "s" }
+        )));
+        self.set_status_notice(format!("Committed {}", sha));
+    }
+}
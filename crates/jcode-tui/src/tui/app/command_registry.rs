@@ -0,0 +1,370 @@
+//! Central slash-command registry.
+//!
+//! Every built-in command declares its name, argument hint, and one-line help
+//! here. The command palette, `/help`, and `/help <command>` all read from this
+//! table, so a new command only has to be registered once to show up
+//! everywhere.
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct RegisteredCommand {
+    pub(crate) name: &'static str,
+    /// Argument hint shown next to the command in the palette, e.g. `[on|off]`.
+    pub(crate) args: &'static str,
+    pub(crate) help: &'static str,
+    pub(crate) hidden: bool,
+}
+
+impl RegisteredCommand {
+    const fn public(name: &'static str, help: &'static str) -> Self {
+        Self {
+            name,
+            args: "",
+            help,
+            hidden: false,
+        }
+    }
+
+    const fn remote(name: &'static str, help: &'static str) -> Self {
+        Self {
+            name,
+            args: "",
+            help,
+            hidden: false,
+        }
+    }
+
+    const fn hidden(name: &'static str, help: &'static str) -> Self {
+        Self {
+            name,
+            args: "",
+            help,
+            hidden: true,
+        }
+    }
+
+    const fn args(mut self, args: &'static str) -> Self {
+        self.args = args;
+        self
+    }
+
+    /// `name args` usage line, or just the name for argument-less commands.
+    pub(crate) fn usage(&self) -> String {
+        if self.args.is_empty() {
+            self.name.to_string()
+        } else {
+            format!("{} {}", self.name, self.args)
+        }
+    }
+}
+
+pub(super) const REGISTERED_COMMANDS: &[RegisteredCommand] = &[
+    RegisteredCommand::public("/help", "Show help and keyboard shortcuts").args("[command]"),
+    RegisteredCommand::public("/?", "Show help and keyboard shortcuts").args("[command]"),
+    RegisteredCommand::public("/commands", "Alias for /help"),
+    RegisteredCommand::public("/model", "List or switch models").args("[name[@provider]]"),
+    RegisteredCommand::public("/models", "Alias for /model").args("[name[@provider]]"),
+    RegisteredCommand::public(
+        "/provider-test-coverage",
+        "Show live-test evidence for the current provider/model",
+    )
+    .args("[provider model]"),
+    RegisteredCommand::hidden("/model-status", "Alias for /provider-test-coverage"),
+    RegisteredCommand::public("/refresh-model-list", "Refresh provider model catalogs"),
+    RegisteredCommand::public("/agents", "Configure models for agent roles")
+        .args("[swarm|review|judge|memory|ambient]"),
+    RegisteredCommand::public("/subagent", "Launch a subagent manually")
+        .args("[--type <kind>] [--model <name>] <prompt>"),
+    RegisteredCommand::public("/observe", "Show the latest tool context in the side panel")
+        .args("[on|off|status]"),
+    RegisteredCommand::public("/todos", "Show session and project todos in the side panel")
+        .args("[on|off|status|add|done|rm]"),
+    RegisteredCommand::public("/splitview", "Mirror the current chat in the side panel")
+        .args("[on|off|status]"),
+    RegisteredCommand::public("/split-view", "Alias for /splitview"),
+    RegisteredCommand::public("/btw", "Ask a side question in the side panel").args("<question>"),
+    RegisteredCommand::public("/ssh", "Connect to a remote machine using system SSH")
+        .args("<host>"),
+    RegisteredCommand::public("/git", "Show git status for the session working directory")
+        .args("[status]"),
+    RegisteredCommand::public("/hotkeys", "List hotkeys with your personal usage"),
+    RegisteredCommand::hidden("/keys", "Alias for /hotkeys"),
+    RegisteredCommand::public("/commit", "Make logical commits from current changes"),
+    RegisteredCommand::public(
+        "/commit-push",
+        "Make logical commits from current changes, then push",
+    ),
+    RegisteredCommand::public(
+        "/cut-release",
+        "Commit + push current changes, bump version, and cut a release",
+    ),
+    RegisteredCommand::hidden("/commit-push-release", "Alias for /cut-release"),
+    RegisteredCommand::public("/transcript", "Open the current session transcript file")
+        .args("[path]"),
+    RegisteredCommand::public("/subagent-model", "Show/change subagent model policy")
+        .args("[name|inherit|show]"),
+    RegisteredCommand::public("/autoreview", "Show/toggle automatic end-of-turn review")
+        .args("[on|off|now|status]"),
+    RegisteredCommand::public("/autojudge", "Show/toggle automatic end-of-turn judging")
+        .args("[on|off|now|status]"),
+    RegisteredCommand::public("/autocommit", "Show/toggle a git commit after each turn")
+        .args("[on|off|status]"),
+    RegisteredCommand::public("/review", "Launch a one-shot headed review session"),
+    RegisteredCommand::public("/judge", "Launch a one-shot headed judge session"),
+    RegisteredCommand::public("/effort", "Show/change reasoning effort (Alt+left/right)")
+        .args("[none|low|medium|high|xhigh]"),
+    RegisteredCommand::public("/fast", "Toggle fast mode").args("[on|off|status|default ...]"),
+    RegisteredCommand::public("/transport", "Show/change connection transport")
+        .args("[auto|https|websocket]"),
+    RegisteredCommand::public("/alignment", "Show/change default text alignment")
+        .args("[centered|left|status]"),
+    RegisteredCommand::public(
+        "/compact-notifications",
+        "Show/toggle single-line swarm/file-activity notifications",
+    )
+    .args("[on|off|status]"),
+    RegisteredCommand::public(
+        "/show-agentgrep-output",
+        "Show/toggle full agentgrep search output inline in chat",
+    )
+    .args("[on|off|status]"),
+    RegisteredCommand::public(
+        "/reasoning",
+        "Show/change reasoning display (off/full/current)",
+    )
+    .args("[off|full|current]"),
+    RegisteredCommand::public("/clear", "Clear conversation history"),
+    RegisteredCommand::public("/rewind", "Rewind conversation to previous message")
+        .args("[N|undo]"),
+    RegisteredCommand::public("/poke", "Poke model to resume with incomplete todos")
+        .args("[on|off|status]"),
+    RegisteredCommand::public("/plan", "Plan read-only, then approve before executing")
+        .args("[goal|approve|edit|reject|off|status]"),
+    RegisteredCommand::public("/aside", "Side question in a read-only scratch thread")
+        .args("[question|end|discard|status]"),
+    RegisteredCommand::public("/compare", "Ask two models the same thing, keep one answer")
+        .args("<model-a> <model-b> <prompt>"),
+    RegisteredCommand::public("/profile", "Switch named config profile").args("[name|off]"),
+    RegisteredCommand::public("/safe", "Approvals, sandboxed bash and a turn cap")
+        .args("[on|off confirm|status]"),
+    RegisteredCommand::public("/improve", "Autonomously improve the repository")
+        .args("[focus|plan|resume|status|stop]"),
+    RegisteredCommand::public("/refactor", "Run a safe refactor loop")
+        .args("[focus|plan|resume|status|stop]"),
+    RegisteredCommand::public("/compact", "Compact context")
+        .args("[mode [reactive|proactive|semantic]]"),
+    RegisteredCommand::public("/fix", "Recover when the model cannot continue"),
+    RegisteredCommand::public("/dictate", "Run configured external dictation command"),
+    RegisteredCommand::public("/dictation", "Alias for /dictate"),
+    RegisteredCommand::public("/memory", "Toggle memory feature").args("[on|off|status]"),
+    RegisteredCommand::public("/remember", "Save an assistant answer as a memory").args("[n]"),
+    RegisteredCommand::public("/test", "Verify a claim/current changes with layered tests")
+        .args("[claim]"),
+    RegisteredCommand::public(
+        "/initiatives",
+        "Open initiatives overview / resume tracked initiatives",
+    )
+    .args("[resume|show <id>]"),
+    RegisteredCommand::public("/goals", "Legacy alias for /initiatives").args("[resume|show <id>]"),
+    RegisteredCommand::public("/swarm", "Toggle swarm feature").args("[on|off|status]"),
+    RegisteredCommand::public("/overnight", "Run a supervised overnight coordinator")
+        .args("<hours>[h|m] [mission] | status|log|review|cancel"),
+    RegisteredCommand::public("/context", "Show the full session context snapshot")
+        .args("[memories]"),
+    RegisteredCommand::public(
+        "/prompt",
+        "Send a saved prompt, or show the assembled system prompt",
+    )
+    .args("[show|sections|list|save <name>|<name> [var=value ...]]"),
+    RegisteredCommand::public(
+        "/skills",
+        "Show loaded skills and jcode-endorsed recommendations",
+    ),
+    RegisteredCommand::public("/version", "Show current version"),
+    RegisteredCommand::public("/changelog", "Show recent changes in this build"),
+    RegisteredCommand::public("/info", "Show session info and tokens"),
+    RegisteredCommand::public("/turninfo", "Show agent limits and last request usage"),
+    RegisteredCommand::public("/usage", "Show connected provider usage limits"),
+    RegisteredCommand::public(
+        "/productivity",
+        "Generate a shareable usage report + dashboard image",
+    ),
+    RegisteredCommand::public("/wrapped", "Alias for /productivity"),
+    RegisteredCommand::public("/feedback", "Send feedback about jcode"),
+    RegisteredCommand::public("/subscription", "Show jcode subscription status").args("[status]"),
+    RegisteredCommand::public("/config", "Show or edit configuration").args("[init|edit]"),
+    RegisteredCommand::public("/tee", "Append each turn's final response to a file")
+        .args("[<path>|off|status]"),
+    RegisteredCommand::public("/log", "Mark the current location in the jcode logs")
+        .args("mark [note]"),
+    RegisteredCommand::public(
+        "/keys",
+        "Show keybinding conflicts with your terminal and OS (/keys refresh to rescan)",
+    )
+    .args("[refresh]"),
+    RegisteredCommand::public(
+        "/diff",
+        "Cycle or set diff display mode (off/inline/full/pinned/file)",
+    )
+    .args("[off|inline|full|pinned|file]"),
+    RegisteredCommand::public(
+        "/view",
+        "Split off a live file view that follows edits or shows a pinned file",
+    )
+    .args("[<path>|follow|off|save|status]"),
+    RegisteredCommand::public(
+        "/onboarding-preview",
+        "Preview the first-run onboarding screen",
+    ),
+    RegisteredCommand::public(
+        "/onboarding-sim",
+        "Walk through every first-run onboarding screen (Cmd+5)",
+    ),
+    RegisteredCommand::public("/reload", "Reload into newest available binary"),
+    RegisteredCommand::public("/migrate", "Migrate now or defer a pending update")
+        .args("[now|defer [minutes]]"),
+    RegisteredCommand::public("/mirror", "Keep a live markdown copy of the transcript")
+        .args("<path>|off"),
+    RegisteredCommand::public("/restart", "Restart with current binary"),
+    RegisteredCommand::public("/rebuild", "Background rebuild and auto reload"),
+    RegisteredCommand::public("/selfdev", "Open a new self-dev jcode session")
+        .args("[prompt|status|enter]"),
+    RegisteredCommand::public("/update", "Background update and auto reload"),
+    RegisteredCommand::public(
+        "/resume",
+        "Open session picker, or resume a session by name",
+    )
+    .args("[session]"),
+    RegisteredCommand::public("/sessions", "Alias for /resume").args("[session]"),
+    RegisteredCommand::public(
+        "/session",
+        "Alias for /resume; stats shows usage per model, footers toggles message footers",
+    )
+    .args("[stats|footers [on|off]]"),
+    RegisteredCommand::public("/catchup", "Open Catch Up picker").args("[next|list]"),
+    RegisteredCommand::public("/back", "Return to the previous Catch Up session"),
+    RegisteredCommand::public("/save", "Bookmark session for easy access").args("[label]"),
+    RegisteredCommand::public("/unsave", "Remove bookmark from session"),
+    RegisteredCommand::public("/rename", "Rename current session").args("<name>|--clear"),
+    RegisteredCommand::public("/root", "List, attach, or detach extra workspace roots")
+        .args("[add <path>|remove <name>|list]"),
+    RegisteredCommand::public("/cd", "Scope tools to a subdirectory of the workspace")
+        .args("[path|-]"),
+    RegisteredCommand::public(
+        "/instructions",
+        "Standing instructions sent with every turn",
+    )
+    .args("[text|add <text>|edit|clear]"),
+    RegisteredCommand::public("/fork", "Fork session into a new window (optional prompt)")
+        .args("[prompt]"),
+    RegisteredCommand::hidden("/split", "Alias for /fork"),
+    RegisteredCommand::public("/transfer", "Compact context into a fresh handoff session"),
+    RegisteredCommand::public("/handoff", "Continue in a fresh session from a summary"),
+    RegisteredCommand::public("/env", "Show the environment section of the system prompt")
+        .args("[refresh]"),
+    RegisteredCommand::public("/focus", "Work on a task for a fixed time, then report")
+        .args("<duration> <task>"),
+    RegisteredCommand::public("/redaction", "Show or test redaction of outgoing messages")
+        .args("[show|test <text>]"),
+    RegisteredCommand::public("/changes", "Review or revert this session's file changes")
+        .args("[diff <n>|revert|stage|commit <n...|all>]"),
+    RegisteredCommand::public("/queue", "List, remove or reorder pending turns")
+        .args("[list|rm <item>|move <item> <position>]"),
+    RegisteredCommand::public("/workspace", "Niri-style session workspace"),
+    RegisteredCommand::public("/quit", "Exit jcode"),
+    RegisteredCommand::public("/auth", "Show authentication status").args("[doctor|provider]"),
+    RegisteredCommand::public("/login", "Login to a provider").args("[provider]"),
+    RegisteredCommand::public("/logout", "Log out of a provider").args("[provider]"),
+    RegisteredCommand::public("/account", "Open the combined account picker")
+        .args("[provider] [settings|login|switch <label>|remove <label>]"),
+    RegisteredCommand::public("/accounts", "Alias for /account"),
+    RegisteredCommand::public("/cache", "Show cache stats or set cache TTL").args("[stats|1h|5m]"),
+    RegisteredCommand::public(
+        "/premium",
+        "Show or override the Copilot premium-request mode for this session",
+    )
+    .args("[normal|conserve|off|status]"),
+    RegisteredCommand::public("/debug-visual", "Toggle visual debug overlay"),
+    RegisteredCommand::public("/screenshot-mode", "Toggle screenshot capture mode"),
+    RegisteredCommand::public("/screenshot", "Capture a screenshot debug state"),
+    RegisteredCommand::public("/record", "Record a demo capture"),
+    RegisteredCommand::remote("/client-reload", "Force reload client binary"),
+    RegisteredCommand::remote("/server-reload", "Force reload server binary"),
+    RegisteredCommand::remote(
+        "/continue",
+        "Continue every interrupted live session that would auto-resume",
+    ),
+    RegisteredCommand::remote("/resumeall", "Alias for /continue"),
+    RegisteredCommand::hidden("/z", "Secret premium-mode command"),
+    RegisteredCommand::hidden("/zz", "Secret premium-mode command"),
+    RegisteredCommand::hidden("/zzz", "Secret premium-mode command"),
+    RegisteredCommand::hidden("/zstatus", "Secret premium-mode status command"),
+];
+
+/// Look up a registered command by name. Accepts the name with or without the
+/// leading slash and ignores case.
+pub(crate) fn lookup(name: &str) -> Option<&'static RegisteredCommand> {
+    let name = name.trim().trim_start_matches('/');
+    if name.is_empty() {
+        return None;
+    }
+    REGISTERED_COMMANDS
+        .iter()
+        .filter(|command| command.name[1..].eq_ignore_ascii_case(name))
+        // Prefer the public entry when a name is registered as both a hidden
+        // alias and a public command (e.g. `/keys`).
+        .min_by_key(|command| command.hidden)
+}
+
+/// Argument hint for a palette entry. Only bare command names get a hint;
+/// entries that already carry arguments (`/model gpt-5`) return `None`.
+pub(crate) fn args_hint(command: &str) -> Option<&'static str> {
+    if command.contains(' ') {
+        return None;
+    }
+    lookup(command)
+        .map(|command| command.args)
+        .filter(|args| !args.is_empty())
+}
+
+/// Fallback `/help <command>` text for commands without a hand-written long
+/// help entry.
+pub(crate) fn registry_help(topic: &str) -> Option<String> {
+    let command = lookup(topic)?;
+    Some(format!("{}\n{}.", command.usage(), command.help))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_names_are_slash_prefixed() {
+        for command in REGISTERED_COMMANDS {
+            assert!(command.name.starts_with('/'), "{}", command.name);
+            assert!(!command.name.contains(' '), "{}", command.name);
+        }
+    }
+
+    #[test]
+    fn lookup_prefers_public_entry_and_ignores_slash() {
+        let keys = lookup("keys").expect("/keys registered");
+        assert!(!keys.hidden);
+        assert_eq!(keys.args, "[refresh]");
+        assert!(lookup("/MODEL").is_some());
+        assert!(lookup("not-a-command").is_none());
+    }
+
+    #[test]
+    fn args_hint_only_for_bare_commands() {
+        assert_eq!(args_hint("/effort"), Some("[none|low|medium|high|xhigh]"));
+        assert_eq!(args_hint("/effort high"), None);
+        assert_eq!(args_hint("/quit"), None);
+    }
+
+    #[test]
+    fn registry_help_includes_usage_and_summary() {
+        let help = registry_help("/ssh").expect("/ssh help");
+        assert!(help.starts_with("/ssh <host>\n"));
+        assert!(help.contains("system SSH"));
+    }
+}
@@ -1,3 +1,11 @@
+pub(super) use super::commands_aside::{
+    ASIDE_BUSY_NOTICE, AsideCommand, PendingAsideSummary, handle_aside_command_local,
+    parse_aside_command,
+};
+pub(super) use super::commands_env::{handle_env_command_local, parse_env_command};
+pub(super) use super::commands_focus::{
+    FocusCommand, format_focus_budget, handle_focus_command_local, parse_focus_command,
+};
 pub(super) use super::commands_improve::{
     build_improve_prompt, build_improve_resume_prompt, build_refactor_prompt,
     build_refactor_resume_prompt, format_improve_status, format_refactor_status,
@@ -6,8 +14,19 @@ pub(super) use super::commands_improve::{
     parse_refactor_command, refactor_launch_notice, refactor_mode_for, refactor_stop_notice,
     refactor_stop_prompt, restore_improve_mode, session_improve_mode_for,
 };
+pub(super) use super::commands_instructions::{
+    InstructionsCommand, handle_instructions_command_local, parse_instructions_command,
+};
 pub(super) use super::commands_plan::{
-    build_plan_prompt, handle_plan_command_local, parse_plan_command, plan_launch_notice,
+    PLAN_BUSY_NOTICE, PlanCommand, build_plan_prompt, handle_plan_command_local,
+    parse_plan_command, plan_launch_notice,
+};
+pub(super) use super::commands_profile::{
+    ProfileCommand, handle_profile_command_local, parse_profile_command,
+};
+pub(super) use super::commands_queue::{
+    QueueCommand, check_remote_edit, edit_local_queue, handle_queue_command_local,
+    parse_queue_command,
 };
 #[cfg(test)]
 pub(super) use super::commands_review::queue_autojudge_remote;
@@ -20,7 +39,15 @@ pub(super) use super::commands_review::{
     launch_prompt_in_new_session_local, maybe_trigger_autojudge_local,
     maybe_trigger_autoreview_local, preferred_one_shot_review_override,
     prepare_review_spawned_session, queue_review_spawn_remote, reset_current_session,
+    switch_current_session,
+};
+pub(super) use super::commands_safe::{
+    SAFE_OFF_CONFIRM_HINT, SafeCommand, handle_safe_command_local, parse_safe_command,
 };
+pub(super) use super::compare::{
+    CompareCommand, handle_compare_command_local, parse_compare_command,
+};
+pub(super) use super::handoff::is_handoff_command;
 pub(super) use super::todos_view::handle_todos_view_command;
 use super::{App, DisplayMessage, LocalRewindUndoSnapshot, ProcessingStatus};
 use crate::bus::{Bus, BusEvent, GitStatusCompleted, ManualToolCompleted, ToolEvent, ToolStatus};
@@ -49,6 +76,18 @@ pub(super) enum PokeCommand {
     Status,
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum PremiumCommand {
+    Status,
+    Set(crate::provider::copilot::PremiumMode),
+}
+
+pub(super) enum WorkspaceRootCommand {
+    List,
+    Add(String),
+    Remove(String),
+}
+
 pub(super) enum PokeActivation {
     EnabledNoIncomplete,
     Queued,
@@ -69,6 +108,90 @@ pub(super) fn parse_poke_command(trimmed: &str) -> Option<Result<PokeCommand, St
     }
 }
 
+pub(super) fn parse_premium_command(trimmed: &str) -> Option<Result<PremiumCommand, String>> {
+    use crate::provider::copilot::PremiumMode;
+    match trimmed {
+        "/premium" | "/premium status" => Some(Ok(PremiumCommand::Status)),
+        "/premium normal" => Some(Ok(PremiumCommand::Set(PremiumMode::Normal))),
+        "/premium conserve" => Some(Ok(PremiumCommand::Set(PremiumMode::OnePerSession))),
+        "/premium off" => Some(Ok(PremiumCommand::Set(PremiumMode::Zero))),
+        _ if trimmed.starts_with("/premium ") => Some(Err(
+            "Usage: /premium [normal|conserve|off|status]".to_string(),
+        )),
+        _ => None,
+    }
+}
+
+pub(super) fn parse_root_command(trimmed: &str) -> Option<Result<WorkspaceRootCommand, String>> {
+    const USAGE: &str = "Usage: /root [add <path>|remove <name>|list]";
+    if trimmed == "/root" || trimmed == "/root list" {
+        return Some(Ok(WorkspaceRootCommand::List));
+    }
+    let rest = trimmed.strip_prefix("/root ")?.trim();
+    let (action, arg) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let arg = arg.trim();
+    Some(match action {
+        "add" if !arg.is_empty() => Ok(WorkspaceRootCommand::Add(arg.to_string())),
+        "remove" | "rm" if !arg.is_empty() => Ok(WorkspaceRootCommand::Remove(arg.to_string())),
+        _ => Err(USAGE.to_string()),
+    })
+}
+
+pub(super) fn workspace_roots_message(working_dir: Option<&str>, roots: &[String]) -> String {
+    let mut lines = vec!["Workspace roots:".to_string()];
+    if let Some(dir) = working_dir {
+        lines.push(format!("  {} (primary)", dir));
+    }
+    for root in roots {
+        let name = crate::tool::workspace_root_name(std::path::Path::new(root))
+            .unwrap_or_else(|| root.clone());
+        lines.push(format!("  {}: {}", name, root));
+    }
+    if roots.is_empty() {
+        lines.push("  No extra roots. Attach one with /root add <path>.".to_string());
+    }
+    lines.join("\n")
+}
+
+/// Parse `/cd [path|-]`. The inner `None` means back to the working
+/// directory.
+pub(super) fn parse_cd_command(trimmed: &str) -> Option<Option<String>> {
+    if trimmed == "/cd" {
+        return Some(None);
+    }
+    let path = trimmed.strip_prefix("/cd ")?.trim();
+    Some((!path.is_empty()).then(|| path.to_string()))
+}
+
+pub(super) fn tool_scope_message(working_dir: Option<&str>, scope: Option<&str>) -> String {
+    match (scope, working_dir) {
+        (Some(scope), _) => format!("Tools now run in {}", scope),
+        (None, Some(dir)) => format!("Tools now run in the working directory {}", dir),
+        (None, None) => "Tools now run in the working directory".to_string(),
+    }
+}
+
+pub(super) fn premium_mode_label(mode: crate::provider::copilot::PremiumMode) -> &'static str {
+    use crate::provider::copilot::PremiumMode;
+    match mode {
+        PremiumMode::Normal => "normal",
+        PremiumMode::OnePerSession => "conserve (one premium per session)",
+        PremiumMode::Zero => "off (zero premium requests)",
+    }
+}
+
+pub(super) fn premium_status_message(app: &App) -> String {
+    let mut message = format!(
+        "Premium mode: {}.",
+        premium_mode_label(app.provider.premium_mode())
+    );
+    if let Some(label) = crate::copilot_usage::premium_status_label() {
+        message.push_str(&format!(" Copilot {}.", label));
+    }
+    message.push_str(" Override for this session with /premium normal|conserve|off.");
+    message
+}
+
 pub(super) fn is_poke_message(message: &str) -> bool {
     (message.starts_with("You have ")
         && message.contains(" incomplete todo")
@@ -395,6 +518,9 @@ pub(super) fn create_transfer_session_from_parent(
     child.messages.clear();
     child.compaction = compaction;
     child.working_dir = parent.working_dir.clone();
+    child.workspace_roots = parent.workspace_roots.clone();
+    child.tool_scope = parent.tool_scope.clone();
+    child.instructions = parent.instructions.clone();
     child.model = parent.model.clone();
     child.provider_key = parent.provider_key.clone();
     child.subagent_model = parent.subagent_model.clone();
@@ -711,7 +837,13 @@ fn launch_manual_subagent(app: &mut App, spec: ManualSubagentSpec) {
 
     let registry = app.registry.clone();
     let session_id = app.session.id.clone();
-    let working_dir = app.session.working_dir.clone();
+    let working_dir = app.session.tool_working_dir().map(str::to_string);
+    let workspace_roots: Vec<PathBuf> = app
+        .session
+        .workspace_roots
+        .iter()
+        .map(PathBuf::from)
+        .collect();
     let tool_call_for_task = tool_call.clone();
     tokio::spawn(async move {
         Bus::global().publish(BusEvent::ToolUpdated(ToolEvent {
@@ -728,6 +860,7 @@ fn launch_manual_subagent(app: &mut App, spec: ManualSubagentSpec) {
             message_id: message_id.clone(),
             tool_call_id: tool_call_for_task.id.clone(),
             working_dir: working_dir.as_deref().map(PathBuf::from),
+            workspace_roots,
             stdin_request_tx: None,
             graceful_shutdown_signal: None,
             execution_mode: crate::tool::ToolExecutionMode::Direct,
@@ -1649,6 +1782,11 @@ pub(super) fn handle_session_command(app: &mut App, trimmed: &str) -> bool {
         || handle_btw_command(app, trimmed)
         || handle_fork_command(app, trimmed)
         || handle_transcript_command(app, trimmed)
+        || super::commands_session_stats::handle_session_stats_command(app, trimmed)
+        || super::commands_redaction::handle_redaction_command(app, trimmed)
+        || super::commands_changes::handle_changes_command(app, trimmed)
+        || super::commands_migrate::handle_migrate_command(app, trimmed)
+        || super::commands_mirror::handle_mirror_command(app, trimmed)
         || handle_git_command(app, trimmed)
         || handle_catchup_command(app, trimmed)
         || handle_back_command(app, trimmed)
@@ -1682,11 +1820,71 @@ pub(super) fn handle_session_command(app: &mut App, trimmed: &str) -> bool {
         return true;
     }
 
+    if let Some(name) = trimmed
+        .strip_prefix("/resume ")
+        .or_else(|| trimmed.strip_prefix("/sessions "))
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+    {
+        match crate::session::find_session_by_name_or_id(name) {
+            Ok(session_id) => app.handle_session_picker_current_terminal_selection(&[
+                crate::tui::session_picker::ResumeTarget::JcodeSession { session_id },
+            ]),
+            Err(error) => app.push_display_message(DisplayMessage::error(error.to_string())),
+        }
+        return true;
+    }
+
     if let Some(command) = parse_plan_command(trimmed) {
         handle_plan_command_local(app, command);
         return true;
     }
 
+    if let Some(command) = parse_aside_command(trimmed) {
+        handle_aside_command_local(app, command);
+        return true;
+    }
+
+    if let Some(command) = parse_compare_command(trimmed) {
+        handle_compare_command_local(app, command);
+        return true;
+    }
+
+    if is_handoff_command(trimmed) {
+        app.start_handoff_locally();
+        return true;
+    }
+
+    if let Some(refresh) = parse_env_command(trimmed) {
+        handle_env_command_local(app, refresh);
+        return true;
+    }
+
+    if let Some(command) = parse_focus_command(trimmed) {
+        handle_focus_command_local(app, command);
+        return true;
+    }
+
+    if let Some(command) = parse_profile_command(trimmed) {
+        handle_profile_command_local(app, command);
+        return true;
+    }
+
+    if let Some(command) = parse_queue_command(trimmed) {
+        handle_queue_command_local(app, command);
+        return true;
+    }
+
+    if let Some(command) = parse_safe_command(trimmed) {
+        handle_safe_command_local(app, command);
+        return true;
+    }
+
+    if let Some(command) = parse_instructions_command(trimmed) {
+        handle_instructions_command_local(app, command);
+        return true;
+    }
+
     if let Some(command) = parse_improve_command(trimmed) {
         match command {
             Ok(command) => handle_improve_command_local(app, command),
@@ -1927,8 +2125,10 @@ pub(super) fn handle_session_command(app: &mut App, trimmed: &str) -> bool {
         let current_count = app.session.visible_conversation_message_count();
         let restored = snapshot.visible_message_count.saturating_sub(current_count);
         app.session.replace_messages(snapshot.messages);
-        app.provider_session_id = snapshot.provider_session_id;
-        app.session.provider_session_id = snapshot.session_provider_session_id;
+        // History was rewritten again; never resume the pre-rewind provider
+        // conversation, which may have moved on since.
+        app.provider_session_id = None;
+        app.session.provider_session_id = None;
         app.session.updated_at = chrono::Utc::now();
         let provider_messages = app.session.messages_for_provider_uncached();
         app.replace_provider_messages(provider_messages);
@@ -1987,8 +2187,6 @@ pub(super) fn handle_session_command(app: &mut App, trimmed: &str) -> bool {
                 let removed = visible_count - n;
                 app.rewind_undo_snapshot = Some(LocalRewindUndoSnapshot {
                     messages: app.session.messages.clone(),
-                    provider_session_id: app.provider_session_id.clone(),
-                    session_provider_session_id: app.session.provider_session_id.clone(),
                     visible_message_count: visible_count,
                 });
                 if let Some(stored_len) = app.session.stored_len_for_visible_conversation_message(n)
@@ -2706,7 +2904,7 @@ fn parse_alignment_value(raw: &str) -> Option<bool> {
     }
 }
 
-fn parse_on_off_value(raw: &str) -> Option<bool> {
+pub(super) fn parse_on_off_value(raw: &str) -> Option<bool> {
     match raw.trim().to_ascii_lowercase().as_str() {
         "on" | "compact" | "true" | "1" | "yes" | "enable" | "enabled" => Some(true),
         "off" | "full" | "false" | "0" | "no" | "disable" | "disabled" => Some(false),
@@ -2963,6 +3161,10 @@ pub(super) fn handle_config_command(app: &mut App, trimmed: &str) -> bool {
         return true;
     }
 
+    if super::turn_tee::handle_tee_command(app, trimmed) {
+        return true;
+    }
+
     if trimmed == "/compact mode" || trimmed == "/compact mode status" {
         let mode = app
             .registry
@@ -0,0 +1,217 @@
+//! `/aside`: a read-only scratch thread beside the main conversation.
+//!
+//! The server runs the aside on a child agent and keeps its messages out of the
+//! main transcript. This side parses the command, mirrors whether an aside is
+//! open into the client session, and lets the user edit the drafted summary in
+//! the input box before it is handed back to the main conversation.
+
+use super::{App, DisplayMessage};
+
+pub(super) const ASIDE_BUSY_NOTICE: &str =
+    "Finish or interrupt the current turn before opening or closing an aside.";
+
+const ASIDE_LOCAL_NOTICE: &str = "/aside requires a live jcode server connection in remote mode.";
+
+/// A parsed `/aside` command.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(super) enum AsideCommand {
+    /// Open an aside, optionally sending its first question.
+    Start {
+        prompt: Option<String>,
+    },
+    /// Draft a summary of the aside for the user to edit and hand back.
+    End,
+    /// Close the aside without adding anything to the main conversation.
+    Discard,
+    Status,
+}
+
+/// Where `/aside end` is in handing back the summary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum PendingAsideSummary {
+    /// Waiting for the server to draft the summary.
+    Drafting,
+    /// The draft is in the input box; the next submit hands it back.
+    Editing,
+}
+
+pub(super) fn parse_aside_command(trimmed: &str) -> Option<AsideCommand> {
+    let rest = trimmed.strip_prefix("/aside")?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some(match rest.trim() {
+        "" => AsideCommand::Start { prompt: None },
+        "end" => AsideCommand::End,
+        "discard" => AsideCommand::Discard,
+        "status" => AsideCommand::Status,
+        prompt => AsideCommand::Start {
+            prompt: Some(prompt.to_string()),
+        },
+    })
+}
+
+impl App {
+    /// Whether the next submit is the edited aside summary rather than a prompt.
+    pub(super) fn aside_summary_editing(&self) -> bool {
+        self.pending_aside_summary == Some(PendingAsideSummary::Editing)
+    }
+
+    pub(super) fn aside_status_message(&self) -> String {
+        match self.session.active_aside() {
+            Some(aside) => format!(
+                "Aside: open since {}\nUse /aside end to hand back a summary, or /aside discard.",
+                aside
+                    .started_at
+                    .with_timezone(&chrono::Local)
+                    .format("%H:%M")
+            ),
+            None => "Aside: none open\nUse /aside [question] to open a read-only scratch thread."
+                .to_string(),
+        }
+    }
+
+    /// Mirror an opened aside into the client session and show its card.
+    pub(super) fn note_aside_started(&mut self) {
+        self.session.begin_aside();
+        self.pending_aside_summary = None;
+        self.push_display_message(DisplayMessage::aside(
+            "Aside",
+            "Scratch thread opened. It sees the project context and read-only tools; \
+its messages stay out of the main conversation.\n/aside end drafts a summary to hand back · /aside discard drops it.",
+        ));
+        self.set_status_notice("Aside: open");
+    }
+
+    /// Mirror a closed aside and show what, if anything, was handed back.
+    pub(super) fn note_aside_closed(&mut self, summary: Option<&str>) {
+        self.session.end_aside(summary.map(str::to_string));
+        self.pending_aside_summary = None;
+        let (title, content) = match summary {
+            Some(summary) => ("Aside summary", summary.to_string()),
+            None => (
+                "Aside discarded",
+                "Nothing was added to the main conversation.".to_string(),
+            ),
+        };
+        self.push_display_message(DisplayMessage::aside(title, content));
+        self.set_status_notice("Aside: closed");
+    }
+
+    /// Apply an `AsideChanged` reply from the server.
+    pub(super) fn handle_aside_changed(
+        &mut self,
+        active: bool,
+        summary: Option<String>,
+        error: Option<String>,
+    ) {
+        if let Some(error) = error {
+            self.pending_aside_summary = None;
+            self.sync_aside_active(active);
+            self.push_display_message(DisplayMessage::error(format!("Aside: {}", error)));
+            return;
+        }
+        match summary {
+            Some(summary) if self.pending_aside_summary == Some(PendingAsideSummary::Drafting) => {
+                self.open_aside_summary_draft(summary);
+            }
+            _ => self.sync_aside_active(active),
+        }
+    }
+
+    fn sync_aside_active(&mut self, active: bool) {
+        if active && self.session.active_aside().is_none() {
+            self.session.begin_aside();
+        } else if !active && self.session.active_aside().is_some() {
+            self.session.end_aside(None);
+            self.pending_aside_summary = None;
+        }
+    }
+
+    fn open_aside_summary_draft(&mut self, summary: String) {
+        if !self.input.is_empty() {
+            if self.stashed_input.is_some() {
+                self.pending_aside_summary = None;
+                self.push_display_message(DisplayMessage::error(
+                    "Aside summary cancelled: the input box and stash are both in use. Clear one and run /aside end again.",
+                ));
+                return;
+            }
+            let input = std::mem::take(&mut self.input);
+            self.stashed_input = Some((input, self.cursor_pos));
+        }
+        self.pending_aside_summary = Some(PendingAsideSummary::Editing);
+        self.input = summary;
+        self.cursor_pos = self.input.len();
+        self.clear_input_undo_history();
+        self.push_display_message(DisplayMessage::system(
+            "Edit the aside summary in the input box. Press Enter to add it to the main conversation and close the aside, submit it empty to close without a summary, or `/cancel` to keep the aside open.",
+        ));
+        self.set_status_notice("Aside → edit summary and press Enter");
+    }
+
+    /// Take the submitted summary while [`Self::aside_summary_editing`] is
+    /// true. Returns `None` when the user cancelled and the aside stays open,
+    /// otherwise the summary to hand back (`Some(None)` closes without one).
+    pub(super) fn take_aside_summary(&mut self, input: String) -> Option<Option<String>> {
+        self.pending_aside_summary = None;
+        let input = input.trim();
+        if input == "/cancel" {
+            self.push_display_message(DisplayMessage::system(
+                "Aside summary cancelled; the aside is still open.",
+            ));
+            self.set_status_notice("Aside: still open");
+            return None;
+        }
+        Some((!input.is_empty()).then(|| input.to_string()))
+    }
+}
+
+pub(super) fn handle_aside_command_local(app: &mut App, command: AsideCommand) {
+    match command {
+        AsideCommand::Status => {
+            app.push_display_message(DisplayMessage::system(app.aside_status_message()));
+        }
+        _ => app.push_display_message(DisplayMessage::error(ASIDE_LOCAL_NOTICE)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_aside_accepts_bare_question_and_keywords() {
+        assert_eq!(
+            parse_aside_command("/aside"),
+            Some(AsideCommand::Start { prompt: None })
+        );
+        assert_eq!(
+            parse_aside_command("/aside  explain this borrow error "),
+            Some(AsideCommand::Start {
+                prompt: Some("explain this borrow error".to_string())
+            })
+        );
+        assert_eq!(parse_aside_command("/aside end"), Some(AsideCommand::End));
+        assert_eq!(
+            parse_aside_command("/aside discard"),
+            Some(AsideCommand::Discard)
+        );
+        assert_eq!(
+            parse_aside_command("/aside status"),
+            Some(AsideCommand::Status)
+        );
+        assert_eq!(
+            parse_aside_command("/aside end of file handling?"),
+            Some(AsideCommand::Start {
+                prompt: Some("end of file handling?".to_string())
+            })
+        );
+    }
+
+    #[test]
+    fn parse_aside_rejects_other_commands() {
+        assert_eq!(parse_aside_command("/asides"), None);
+        assert_eq!(parse_aside_command("aside end"), None);
+    }
+}
@@ -0,0 +1,308 @@
+//! `/changes`: everything this session's tools changed, diffed against the
+//! session-start snapshots. Reads and writes local files only, so it works
+//! the same with or without a server.
+
+use super::commands::{active_session_id, active_working_dir};
+use super::{App, DisplayMessage};
+use crate::session_changes::{self, FileChange};
+
+const CHANGES_USAGE: &str = "Usage: `/changes [list]`, `/changes diff <n>`, or \
+     `/changes revert|stage|commit <n...|all>`";
+
+/// Which files of the `/changes` list a bulk action applies to.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(super) enum ChangeSelection {
+    All,
+    /// 1-based positions in the list
+    Files(Vec<usize>),
+}
+
+/// A parsed `/changes` command.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(super) enum ChangesCommand {
+    List,
+    Diff(usize),
+    Revert(ChangeSelection),
+    Stage(ChangeSelection),
+    Commit(ChangeSelection),
+}
+
+/// Parse `/changes [list]`, `/changes diff <n>` and
+/// `/changes revert|stage|commit <n...|all>`. Returns `None` for other input
+/// and an error message for a malformed `/changes`.
+pub(super) fn parse_changes_command(trimmed: &str) -> Option<Result<ChangesCommand, String>> {
+    let rest = trimmed.strip_prefix("/changes")?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let args: Vec<&str> = rest.split_whitespace().collect();
+    Some(parse_changes_args(&args).ok_or_else(|| CHANGES_USAGE.to_string()))
+}
+
+fn parse_changes_args(args: &[&str]) -> Option<ChangesCommand> {
+    match args {
+        [] | ["list"] => Some(ChangesCommand::List),
+        ["diff", index] => parse_index(index).map(ChangesCommand::Diff),
+        ["revert", rest @ ..] => parse_selection(rest).map(ChangesCommand::Revert),
+        ["stage", rest @ ..] => parse_selection(rest).map(ChangesCommand::Stage),
+        ["commit", rest @ ..] => parse_selection(rest).map(ChangesCommand::Commit),
+        _ => None,
+    }
+}
+
+fn parse_index(raw: &str) -> Option<usize> {
+    raw.parse::<usize>().ok().filter(|index| *index > 0)
+}
+
+fn parse_selection(args: &[&str]) -> Option<ChangeSelection> {
+    match args {
+        [] => None,
+        ["all"] => Some(ChangeSelection::All),
+        _ => args
+            .iter()
+            .map(|arg| parse_index(arg.trim_end_matches(',')))
+            .collect::<Option<Vec<_>>>()
+            .map(ChangeSelection::Files),
+    }
+}
+
+/// The selected entries of `changes`, or an error naming a position that is
+/// not in the list.
+pub(super) fn select_changes(
+    changes: &[FileChange],
+    selection: &ChangeSelection,
+) -> Result<Vec<FileChange>, String> {
+    let ChangeSelection::Files(indices) = selection else {
+        return Ok(changes.to_vec());
+    };
+    let mut selected: Vec<FileChange> = Vec::new();
+    for index in indices {
+        let change = changes.get(index - 1).ok_or_else(|| {
+            format!(
+                "No file {} in the list. Run `/changes` to see the current list.",
+                index
+            )
+        })?;
+        if !selected.contains(change) {
+            selected.push(change.clone());
+        }
+    }
+    Ok(selected)
+}
+
+fn plural(count: usize) -> &'static str {
+    if count == 1 { "" } else { "s" }
+}
+
+fn outside_repo_note(outside: &[std::path::PathBuf]) -> String {
+    if outside.is_empty() {
+        return String::new();
+    }
+    format!(
+        "\n\n{} file{} not in a git repository left as is.",
+        outside.len(),
+        plural(outside.len())
+    )
+}
+
+pub(super) fn handle_changes_command(app: &mut App, trimmed: &str) -> bool {
+    let Some(command) = parse_changes_command(trimmed) else {
+        return false;
+    };
+    let command = match command {
+        Ok(command) => command,
+        Err(error) => {
+            app.push_display_message(DisplayMessage::error(error));
+            return true;
+        }
+    };
+    let session_id = active_session_id(app);
+    let base = active_working_dir(app);
+    let changes = match session_changes::changes(&session_id) {
+        Ok(changes) => changes,
+        Err(error) => {
+            app.push_display_message(DisplayMessage::error(format!(
+                "Failed to read session changes: {:#}",
+                error
+            )));
+            return true;
+        }
+    };
+    let summary =
+        |changes: &[FileChange]| session_changes::format_summary(changes, base.as_deref());
+
+    match command {
+        ChangesCommand::List => {
+            let mut out = summary(&changes);
+            if !changes.is_empty() {
+                out.push_str(
+                    "\n\nUse `/changes diff <n>` to view a file, or \
+                     `/changes revert|stage|commit <n...|all>`.",
+                );
+            }
+            app.push_display_message(DisplayMessage::system(out));
+            app.set_status_notice("Changes");
+        }
+        ChangesCommand::Diff(index) => {
+            match select_changes(&changes, &ChangeSelection::Files(vec![index])) {
+                Ok(selected) => {
+                    let change = &selected[0];
+                    app.push_display_message(DisplayMessage::system(format!(
+                        "{} (+{} -{}, {} since the session started):\n\n```diff\n{}```",
+                        session_changes::display_path(&change.path, base.as_deref()),
+                        change.added,
+                        change.removed,
+                        change.status_label(),
+                        change.unified_diff()
+                    )));
+                    app.set_status_notice("Changes: diff");
+                }
+                Err(error) => app.push_display_message(DisplayMessage::error(error)),
+            }
+        }
+        ChangesCommand::Revert(selection) => {
+            let selected = match select_changes(&changes, &selection) {
+                Ok(selected) => selected,
+                Err(error) => {
+                    app.push_display_message(DisplayMessage::error(error));
+                    return true;
+                }
+            };
+            let mut reverted = 0;
+            for change in &selected {
+                match session_changes::revert(change) {
+                    Ok(()) => reverted += 1,
+                    Err(error) => {
+                        app.push_display_message(DisplayMessage::error(format!("{:#}", error)))
+                    }
+                }
+            }
+            let remaining = session_changes::changes(&session_id).unwrap_or_default();
+            app.push_display_message(DisplayMessage::system(format!(
+                "Reverted {} file{} to their session-start content.\n\n{}",
+                reverted,
+                plural(reverted),
+                summary(&remaining)
+            )));
+            app.set_status_notice("Changes: reverted");
+        }
+        ChangesCommand::Stage(selection) => {
+            let result = select_changes(&changes, &selection).and_then(|selected| {
+                session_changes::stage(&selected).map_err(|error| format!("{:#}", error))
+            });
+            match result {
+                Ok((staged, outside)) => {
+                    app.push_display_message(DisplayMessage::system(format!(
+                        "Staged {} file{}.{}",
+                        staged,
+                        plural(staged),
+                        outside_repo_note(&outside)
+                    )));
+                    app.set_status_notice("Changes: staged");
+                }
+                Err(error) => app.push_display_message(DisplayMessage::error(error)),
+            }
+        }
+        ChangesCommand::Commit(selection) => {
+            let result = select_changes(&changes, &selection).and_then(|selected| {
+                session_changes::commit(&session_id, &selected)
+                    .map_err(|error| format!("{:#}", error))
+            });
+            match result {
+                Ok((commits, outside)) if commits.is_empty() => {
+                    app.push_display_message(DisplayMessage::system(format!(
+                        "Nothing to commit.{}",
+                        outside_repo_note(&outside)
+                    )));
+                }
+                Ok((commits, outside)) => {
+                    let lines: Vec<String> = commits
+                        .iter()
+                        .map(|commit| {
+                            format!(
+                                "- {} on {}: {} ({} file{})",
+                                commit.sha,
+                                commit.branch,
+                                commit.subject,
+                                commit.files.len(),
+                                plural(commit.files.len())
+                            )
+                        })
+                        .collect();
+                    app.push_display_message(DisplayMessage::system(format!(
+                        "Committed:\n{}{}",
+                        lines.join("\n"),
+                        outside_repo_note(&outside)
+                    )));
+                    app.set_status_notice("Changes: committed");
+                }
+                Err(error) => app.push_display_message(DisplayMessage::error(error)),
+            }
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn change(name: &str) -> FileChange {
+        FileChange {
+            path: PathBuf::from(name),
+            original: Some("a\n".to_string()),
+            current: Some("b\n".to_string()),
+            added: 1,
+            removed: 1,
+        }
+    }
+
+    #[test]
+    fn parse_changes_reads_subcommands() {
+        assert_eq!(
+            parse_changes_command("/changes"),
+            Some(Ok(ChangesCommand::List))
+        );
+        assert_eq!(
+            parse_changes_command("/changes diff 2"),
+            Some(Ok(ChangesCommand::Diff(2)))
+        );
+        assert_eq!(
+            parse_changes_command("/changes revert 1, 3"),
+            Some(Ok(ChangesCommand::Revert(ChangeSelection::Files(vec![
+                1, 3
+            ]))))
+        );
+        assert_eq!(
+            parse_changes_command("/changes commit all"),
+            Some(Ok(ChangesCommand::Commit(ChangeSelection::All)))
+        );
+        assert_eq!(
+            parse_changes_command("/changes stage"),
+            Some(Err(CHANGES_USAGE.to_string()))
+        );
+        assert_eq!(
+            parse_changes_command("/changes diff 0"),
+            Some(Err(CHANGES_USAGE.to_string()))
+        );
+        assert_eq!(parse_changes_command("/changeset"), None);
+    }
+
+    #[test]
+    fn select_changes_resolves_positions() {
+        let changes = [change("a.rs"), change("b.rs"), change("c.rs")];
+        let picked = select_changes(&changes, &ChangeSelection::Files(vec![3, 1, 3])).unwrap();
+        assert_eq!(
+            picked.iter().map(|c| c.path.clone()).collect::<Vec<_>>(),
+            [PathBuf::from("c.rs"), PathBuf::from("a.rs")]
+        );
+        assert_eq!(
+            select_changes(&changes, &ChangeSelection::All)
+                .unwrap()
+                .len(),
+            3
+        );
+        assert!(select_changes(&changes, &ChangeSelection::Files(vec![4])).is_err());
+    }
+}
@@ -0,0 +1,70 @@
+use super::{App, DisplayMessage};
+
+/// Parse `/env` (show the environment section of the system prompt) and
+/// `/env refresh` (re-probe it first). Returns whether to refresh.
+pub(super) fn parse_env_command(trimmed: &str) -> Option<bool> {
+    let rest = trimmed.strip_prefix("/env")?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    match rest.trim() {
+        "" => Some(false),
+        "refresh" => Some(true),
+        _ => None,
+    }
+}
+
+impl App {
+    /// Show the environment section the model sees, or why there is none.
+    pub(super) fn show_environment_descriptor(
+        &mut self,
+        descriptor: Option<String>,
+        refreshed: bool,
+    ) {
+        let Some(descriptor) = descriptor else {
+            self.push_display_message(DisplayMessage::system(
+                "The environment section is off. Set `[prompt] environment = true` to include it.",
+            ));
+            return;
+        };
+        let note = if refreshed {
+            "Re-probed. Sessions pick this up on their next turn, which rebuilds the cached prompt prefix once."
+        } else {
+            "Probed at server start. Use /env refresh after installing or upgrading tools."
+        };
+        self.push_display_message(DisplayMessage::system(format!(
+            "{}\n\n{}",
+            descriptor, note
+        )));
+        if refreshed {
+            self.set_status_notice("Environment → refreshed");
+        }
+    }
+}
+
+/// Local mode: the descriptor lives in this process.
+pub(super) fn handle_env_command_local(app: &mut App, refresh: bool) {
+    let refreshed = refresh
+        && crate::config::config()
+            .prompt
+            .section_enabled(crate::config::PromptSection::Environment);
+    let descriptor = if refreshed {
+        Some(crate::prompt::refresh_environment_descriptor())
+    } else {
+        crate::prompt::environment_descriptor()
+    };
+    app.show_environment_descriptor(descriptor, refreshed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_env_accepts_only_refresh() {
+        assert_eq!(parse_env_command("/env"), Some(false));
+        assert_eq!(parse_env_command("/env  refresh"), Some(true));
+        assert_eq!(parse_env_command("/env reload"), None);
+        assert_eq!(parse_env_command("/envs"), None);
+    }
+}
@@ -0,0 +1,158 @@
+//! `/focus <duration> <task>`: run the agent on a task with a wall-clock
+//! budget. The server times the turn; this side parses the command.
+
+use super::{App, DisplayMessage};
+
+const FOCUS_USAGE: &str =
+    "Usage: `/focus <duration> <task>` with `90s`, `20m` or `1h`; a bare number is minutes.";
+
+const FOCUS_LOCAL_NOTICE: &str = "/focus requires a live jcode server connection in remote mode.";
+
+/// Longest focus budget accepted.
+const MAX_FOCUS_SECS: u64 = 8 * 60 * 60;
+
+/// A parsed `/focus` command.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(super) struct FocusCommand {
+    pub budget_secs: u64,
+    pub task: String,
+}
+
+/// Parse `/focus <duration> <task>`. Returns `None` for other input and an
+/// error message for a malformed `/focus`.
+pub(super) fn parse_focus_command(trimmed: &str) -> Option<Result<FocusCommand, String>> {
+    let rest = trimmed.strip_prefix("/focus")?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let rest = rest.trim_start();
+    let (duration, task) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let task = task.trim();
+    if duration.is_empty() || task.is_empty() {
+        return Some(Err(FOCUS_USAGE.to_string()));
+    }
+    Some(
+        parse_focus_duration(duration).map(|budget_secs| FocusCommand {
+            budget_secs,
+            task: task.to_string(),
+        }),
+    )
+}
+
+fn parse_focus_duration(raw: &str) -> Result<u64, String> {
+    let (number, unit_secs) = if let Some(secs) = raw.strip_suffix('s') {
+        (secs, 1.0)
+    } else if let Some(minutes) = raw.strip_suffix('m') {
+        (minutes, 60.0)
+    } else if let Some(hours) = raw.strip_suffix('h') {
+        (hours, 3600.0)
+    } else {
+        (raw, 60.0)
+    };
+    let value: f64 = number
+        .parse()
+        .map_err(|_| format!("Invalid focus duration `{}`. {}", raw, FOCUS_USAGE))?;
+    let secs = (value * unit_secs).round();
+    if !secs.is_finite() || secs < 1.0 || secs > MAX_FOCUS_SECS as f64 {
+        return Err("Focus duration must be between 1 second and 8 hours.".to_string());
+    }
+    Ok(secs as u64)
+}
+
+/// `90s`, `20m` or `1h30m`.
+pub(super) fn format_focus_budget(secs: u64) -> String {
+    match (secs / 3600, secs % 3600 / 60, secs % 60) {
+        (0, 0, secs) => format!("{}s", secs),
+        (0, minutes, 0) => format!("{}m", minutes),
+        (0, minutes, secs) => format!("{}m{:02}s", minutes, secs),
+        (hours, 0, _) => format!("{}h", hours),
+        (hours, minutes, _) => format!("{}h{:02}m", hours, minutes),
+    }
+}
+
+impl App {
+    /// Show that a `/focus` run hit its budget and where its progress went.
+    pub(super) fn note_focus_checkpoint(
+        &mut self,
+        budget_secs: u64,
+        elapsed_secs: u64,
+        open_todos: usize,
+        summary: &str,
+    ) {
+        self.push_display_message(DisplayMessage::system(format!(
+            "Focus time is up ({} of {}). {} open todo{} saved; run `/focus` again \
+             to continue.\n\n{}",
+            format_focus_budget(elapsed_secs),
+            format_focus_budget(budget_secs),
+            open_todos,
+            if open_todos == 1 { "" } else { "s" },
+            summary
+        )));
+        self.set_status_notice("Focus: checkpoint saved");
+    }
+}
+
+/// Local mode has no server-side agent loop to time.
+pub(super) fn handle_focus_command_local(app: &mut App, command: Result<FocusCommand, String>) {
+    let message = match command {
+        Ok(_) => FOCUS_LOCAL_NOTICE.to_string(),
+        Err(error) => error,
+    };
+    app.push_display_message(DisplayMessage::error(message));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_focus_reads_duration_and_task() {
+        assert_eq!(
+            parse_focus_command("/focus 20m  migrate the config loader "),
+            Some(Ok(FocusCommand {
+                budget_secs: 1200,
+                task: "migrate the config loader".to_string(),
+            }))
+        );
+        assert_eq!(
+            parse_focus_command("/focus 1.5h fix flaky tests").map(|c| c.unwrap().budget_secs),
+            Some(5400)
+        );
+        assert_eq!(
+            parse_focus_command("/focus 90s x").map(|c| c.unwrap().budget_secs),
+            Some(90)
+        );
+        assert_eq!(
+            parse_focus_command("/focus 15 x").map(|c| c.unwrap().budget_secs),
+            Some(900)
+        );
+    }
+
+    #[test]
+    fn parse_focus_rejects_bad_input() {
+        assert_eq!(
+            parse_focus_command("/focus"),
+            Some(Err(FOCUS_USAGE.to_string()))
+        );
+        assert_eq!(
+            parse_focus_command("/focus 20m"),
+            Some(Err(FOCUS_USAGE.to_string()))
+        );
+        assert!(matches!(
+            parse_focus_command("/focus soon fix it"),
+            Some(Err(error)) if error.starts_with("Invalid focus duration `soon`")
+        ));
+        assert!(parse_focus_command("/focus 9h fix it").unwrap().is_err());
+        assert!(parse_focus_command("/focus 0m fix it").unwrap().is_err());
+        assert_eq!(parse_focus_command("/focused 20m x"), None);
+    }
+
+    #[test]
+    fn format_focus_budget_uses_largest_units() {
+        assert_eq!(format_focus_budget(45), "45s");
+        assert_eq!(format_focus_budget(1200), "20m");
+        assert_eq!(format_focus_budget(90), "1m30s");
+        assert_eq!(format_focus_budget(5400), "1h30m");
+        assert_eq!(format_focus_budget(7200), "2h");
+    }
+}
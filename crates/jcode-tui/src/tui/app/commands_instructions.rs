@@ -0,0 +1,170 @@
+use super::{App, DisplayMessage};
+
+/// A parsed `/instructions` command.
+///
+/// Standing instructions are stored on the session and sent verbatim with
+/// every turn. Anything after `/instructions` that is not a subcommand
+/// replaces them, so `/instructions Keep diffs small` works as well as
+/// `/instructions set Keep diffs small`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(super) enum InstructionsCommand {
+    Show,
+    Edit,
+    Clear,
+    Set(String),
+    Add(String),
+}
+
+pub(super) fn parse_instructions_command(trimmed: &str) -> Option<InstructionsCommand> {
+    let rest = trimmed.strip_prefix("/instructions")?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let rest = rest.trim();
+    let (word, text) = rest
+        .split_once(char::is_whitespace)
+        .map_or((rest, ""), |(word, text)| (word, text.trim()));
+    Some(match (word, text) {
+        ("", _) | ("show" | "add", "") => InstructionsCommand::Show,
+        ("edit", "") => InstructionsCommand::Edit,
+        // An emptied `/instructions edit` submits a bare `set`.
+        ("clear" | "off" | "set", "") => InstructionsCommand::Clear,
+        ("set", text) if !text.is_empty() => InstructionsCommand::Set(text.to_string()),
+        ("add", text) if !text.is_empty() => InstructionsCommand::Add(text.to_string()),
+        _ => InstructionsCommand::Set(rest.to_string()),
+    })
+}
+
+impl App {
+    fn instructions_status_message(&self) -> String {
+        match self.session.instructions.as_deref() {
+            Some(text) => format!(
+                "Session instructions ({}, sent with every turn):\n\n{}\n\n\
+                 `/instructions edit` loads them into the input, `/instructions add <text>` \
+                 appends and `/instructions clear` removes them.",
+                crate::util::format_approx_token_count(crate::util::estimate_tokens(text)),
+                text
+            ),
+            None => "No session instructions. `/instructions <text>` sets standing \
+                     instructions that are sent with every turn, survive compaction and \
+                     are passed on to subagents."
+                .to_string(),
+        }
+    }
+
+    /// Run the parts of `/instructions` that stay in the TUI. Returns the new
+    /// instructions to store when the command changes them.
+    pub(super) fn instructions_update(
+        &mut self,
+        command: InstructionsCommand,
+    ) -> Option<Option<String>> {
+        match command {
+            InstructionsCommand::Show => {
+                self.push_display_message(DisplayMessage::system(
+                    self.instructions_status_message(),
+                ));
+                None
+            }
+            InstructionsCommand::Edit => {
+                self.input = match self.session.instructions.as_deref() {
+                    Some(text) => format!("/instructions set {}", text),
+                    None => "/instructions set ".to_string(),
+                };
+                self.cursor_pos = self.input.len();
+                self.set_status_notice("Editing session instructions: Enter saves");
+                None
+            }
+            InstructionsCommand::Clear => {
+                if self.session.instructions.is_none() {
+                    self.push_display_message(DisplayMessage::system(
+                        "No session instructions to clear.",
+                    ));
+                    return None;
+                }
+                Some(None)
+            }
+            InstructionsCommand::Set(text) => Some(Some(text)),
+            InstructionsCommand::Add(text) => {
+                Some(Some(match self.session.instructions.as_deref() {
+                    Some(existing) => format!("{}\n{}", existing, text),
+                    None => text,
+                }))
+            }
+        }
+    }
+
+    pub(super) fn note_instructions_changed(&mut self, text: Option<String>) {
+        self.session.instructions = text;
+        let Some(text) = self.session.instructions.as_deref() else {
+            self.set_status_notice("Session instructions cleared");
+            self.push_display_message(DisplayMessage::system("Session instructions cleared."));
+            return;
+        };
+        let tokens = crate::util::estimate_tokens(text);
+        self.set_status_notice("Session instructions saved");
+        self.push_display_message(DisplayMessage::system(format!(
+            "Session instructions saved ({}). They are sent with every turn from now on.",
+            crate::util::format_approx_token_count(tokens)
+        )));
+        if tokens > crate::prompt::SESSION_INSTRUCTIONS_MAX_TOKENS {
+            self.push_display_message(DisplayMessage::error(format!(
+                "Session instructions are over the {} budget; the model only sees the \
+                 first part. Trim them with /instructions edit.",
+                crate::util::format_approx_token_count(
+                    crate::prompt::SESSION_INSTRUCTIONS_MAX_TOKENS
+                )
+            )));
+        }
+    }
+}
+
+pub(super) fn handle_instructions_command_local(app: &mut App, command: InstructionsCommand) {
+    let Some(text) = app.instructions_update(command) else {
+        return;
+    };
+    app.note_instructions_changed(text);
+    if let Err(error) = app.session.save() {
+        app.push_display_message(DisplayMessage::error(format!(
+            "Failed to save session instructions: {}",
+            error
+        )));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_subcommands_and_bare_text() {
+        assert_eq!(
+            parse_instructions_command("/instructions"),
+            Some(InstructionsCommand::Show)
+        );
+        assert_eq!(
+            parse_instructions_command("/instructions edit"),
+            Some(InstructionsCommand::Edit)
+        );
+        assert_eq!(
+            parse_instructions_command("/instructions clear"),
+            Some(InstructionsCommand::Clear)
+        );
+        assert_eq!(
+            parse_instructions_command("/instructions add  Run tests first"),
+            Some(InstructionsCommand::Add("Run tests first".to_string()))
+        );
+        assert_eq!(
+            parse_instructions_command("/instructions set Line one\nLine two"),
+            Some(InstructionsCommand::Set("Line one\nLine two".to_string()))
+        );
+        assert_eq!(
+            parse_instructions_command("/instructions edit the docs only"),
+            Some(InstructionsCommand::Set("edit the docs only".to_string()))
+        );
+        assert_eq!(
+            parse_instructions_command("/instructions set "),
+            Some(InstructionsCommand::Clear)
+        );
+        assert_eq!(parse_instructions_command("/instructionsx"), None);
+    }
+}
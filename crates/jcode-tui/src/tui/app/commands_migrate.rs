@@ -0,0 +1,347 @@
+//! `/migrate`: moving the session onto a newer binary. A shared server
+//! announces its migration with `ServerEvent::MigrationPending`; a local
+//! session notices a newly promoted stable build itself. Either way the user
+//! is warned a grace period ahead and can migrate now or defer, and a deferred
+//! migration asks again when the deferral ends.
+
+use super::{App, DisplayMessage};
+
+const MIGRATE_USAGE: &str = "Usage: `/migrate`, `/migrate now` or `/migrate defer [minutes]`";
+
+const DEFAULT_DEFER_MINUTES: u64 = 30;
+
+/// Least warning a client gives, even when the server announced the
+/// migration long before this client attached.
+const MIN_NOTICE_MS: u64 = 30_000;
+
+pub(super) fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// What runs the migration once it is due.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum MigrationTarget {
+    /// Reload the shared server, which carries its sessions over
+    Server,
+    /// Re-exec this client into the stable binary
+    Stable,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(super) struct PendingMigration {
+    pub target: MigrationTarget,
+    pub from_hash: String,
+    /// Empty until the server names the build
+    pub to_hash: String,
+    pub reason: String,
+    /// Unix ms when the migration runs
+    pub due_ms: u64,
+    /// Unix ms when a deferred migration asks again
+    pub deferred_until_ms: Option<u64>,
+}
+
+impl PendingMigration {
+    pub(super) fn new(
+        target: MigrationTarget,
+        from_hash: impl Into<String>,
+        to_hash: impl Into<String>,
+        reason: impl Into<String>,
+        due_ms: u64,
+    ) -> Self {
+        Self {
+            target,
+            from_hash: from_hash.into(),
+            to_hash: to_hash.into(),
+            reason: reason.into(),
+            due_ms,
+            deferred_until_ms: None,
+        }
+    }
+
+    fn target_label(&self) -> &str {
+        if self.to_hash.is_empty() {
+            "a newer build"
+        } else {
+            &self.to_hash
+        }
+    }
+
+    /// Whether `other` announces the same migration, so a deferral survives
+    /// the server re-sending it on reconnect.
+    fn same_migration(&self, other: &Self) -> bool {
+        self.target == other.target && (self.to_hash == other.to_hash || self.to_hash.is_empty())
+    }
+}
+
+fn format_wait(ms: u64) -> String {
+    let secs = ms.div_ceil(1000);
+    if secs >= 60 {
+        format!("{}m", secs.div_ceil(60))
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+fn prompt_text(migration: &PendingMigration, now_ms: u64) -> String {
+    format!(
+        "⬆ This session will migrate from {} to {} in {} because {}.\n\n\
+         Run `/migrate now` to switch right away, or `/migrate defer` to be asked again in {} minutes.",
+        migration.from_hash,
+        migration.target_label(),
+        format_wait(migration.due_ms.saturating_sub(now_ms)),
+        migration.reason,
+        DEFAULT_DEFER_MINUTES
+    )
+}
+
+/// The status-line banner for `migration` at `now_ms`.
+pub(super) fn banner_text(migration: &PendingMigration, now_ms: u64) -> String {
+    match migration.deferred_until_ms {
+        Some(until) => format!(
+            "⬆ migration to {} deferred · asks again in {}",
+            migration.target_label(),
+            format_wait(until.saturating_sub(now_ms))
+        ),
+        None => format!(
+            "⬆ migrating to {} in {} · /migrate now · /migrate defer",
+            migration.target_label(),
+            format_wait(migration.due_ms.saturating_sub(now_ms))
+        ),
+    }
+}
+
+/// A parsed `/migrate` command.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(super) enum MigrateCommand {
+    Status,
+    Now,
+    Defer { minutes: u64 },
+}
+
+/// Parse `/migrate`, `/migrate now` and `/migrate defer [minutes]`. Returns
+/// `None` for other input and an error message for a malformed `/migrate`.
+pub(super) fn parse_migrate_command(trimmed: &str) -> Option<Result<MigrateCommand, String>> {
+    let rest = trimmed.strip_prefix("/migrate")?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let args: Vec<&str> = rest.split_whitespace().collect();
+    let command = match args.as_slice() {
+        [] | ["status"] => Some(MigrateCommand::Status),
+        ["now"] => Some(MigrateCommand::Now),
+        ["defer"] => Some(MigrateCommand::Defer {
+            minutes: DEFAULT_DEFER_MINUTES,
+        }),
+        ["defer", minutes] => minutes
+            .trim_end_matches('m')
+            .parse::<u64>()
+            .ok()
+            .filter(|minutes| *minutes > 0)
+            .map(|minutes| MigrateCommand::Defer { minutes }),
+        _ => None,
+    };
+    Some(command.ok_or_else(|| MIGRATE_USAGE.to_string()))
+}
+
+impl App {
+    /// Warn about `migration`, or refresh the details of the one already
+    /// pending without undoing a deferral.
+    pub(super) fn announce_migration(&mut self, mut migration: PendingMigration) {
+        let now = now_ms();
+        migration.due_ms = migration.due_ms.max(now + MIN_NOTICE_MS);
+        if let Some(pending) = self.pending_migration.as_mut()
+            && pending.same_migration(&migration)
+        {
+            pending.to_hash = migration.to_hash;
+            pending.from_hash = migration.from_hash;
+            pending.reason = migration.reason;
+            return;
+        }
+        self.push_display_message(
+            DisplayMessage::system(prompt_text(&migration, now)).with_title("Migration"),
+        );
+        self.set_status_notice(format!("Migration to {} pending", migration.target_label()));
+        self.pending_migration = Some(migration);
+    }
+
+    /// The server noticed a newer binary without naming it yet, e.g. an
+    /// older server that does not send `MigrationPending`.
+    pub(super) fn announce_server_update(&mut self) {
+        let from_hash = self
+            .remote_server_version
+            .clone()
+            .unwrap_or_else(|| "the running server".to_string());
+        let due_ms = now_ms() + self.migration_grace_secs.saturating_mul(1000);
+        self.announce_migration(PendingMigration::new(
+            MigrationTarget::Server,
+            from_hash,
+            "",
+            "a newer server binary is installed",
+            due_ms,
+        ));
+    }
+
+    /// Ask again about a deferred migration once the deferral ends, giving a
+    /// fresh grace period. Returns true when it re-prompted.
+    pub(super) fn refresh_pending_migration(&mut self) -> bool {
+        let now = now_ms();
+        let Some(pending) = self.pending_migration.as_mut() else {
+            return false;
+        };
+        if !pending.deferred_until_ms.is_some_and(|until| now >= until) {
+            return false;
+        }
+        pending.deferred_until_ms = None;
+        pending.due_ms = now + self.migration_grace_secs.saturating_mul(1000);
+        let text = prompt_text(pending, now);
+        self.push_display_message(DisplayMessage::system(text).with_title("Migration"));
+        self.set_status_notice("Migration pending");
+        true
+    }
+
+    /// The pending migration once it is due and no turn is running or queued.
+    pub(super) fn take_due_migration(&mut self) -> Option<PendingMigration> {
+        let pending = self.pending_migration.as_ref()?;
+        let due = pending.deferred_until_ms.is_none() && now_ms() >= pending.due_ms;
+        if !due || self.is_processing || !self.queued_messages.is_empty() {
+            return None;
+        }
+        self.pending_migration.take()
+    }
+
+    /// Hand a due server migration to the reload path.
+    pub(super) fn begin_server_migration(&mut self, migration: PendingMigration) {
+        crate::logging::info(&format!(
+            "Migrating server from {} to {} ({})",
+            migration.from_hash,
+            migration.target_label(),
+            migration.reason
+        ));
+        self.pending_server_reload = true;
+    }
+}
+
+pub(super) fn handle_migrate_command(app: &mut App, trimmed: &str) -> bool {
+    let Some(command) = parse_migrate_command(trimmed) else {
+        return false;
+    };
+    let command = match command {
+        Ok(command) => command,
+        Err(error) => {
+            app.push_display_message(DisplayMessage::error(error));
+            return true;
+        }
+    };
+    let now = now_ms();
+    let Some(pending) = app.pending_migration.as_mut() else {
+        app.push_display_message(DisplayMessage::system(
+            "No migration is pending. `/reload` switches to the newest installed binary."
+                .to_string(),
+        ));
+        return true;
+    };
+    match command {
+        MigrateCommand::Status => {
+            let text = match pending.deferred_until_ms {
+                Some(_) => banner_text(pending, now),
+                None => prompt_text(pending, now),
+            };
+            app.push_display_message(DisplayMessage::system(text).with_title("Migration"));
+        }
+        MigrateCommand::Now => {
+            pending.deferred_until_ms = None;
+            pending.due_ms = now;
+            let busy = app.is_processing || !app.queued_messages.is_empty();
+            app.set_status_notice(if busy {
+                "Migrating after the current turn"
+            } else {
+                "Migrating now"
+            });
+        }
+        MigrateCommand::Defer { minutes } => {
+            pending.deferred_until_ms = Some(now + minutes.saturating_mul(60_000));
+            app.push_display_message(DisplayMessage::system(format!(
+                "Migration deferred. You will be asked again in {} minute{}.",
+                minutes,
+                if minutes == 1 { "" } else { "s" }
+            )));
+            app.set_status_notice("Migration deferred");
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stable(to_hash: &str, due_ms: u64) -> PendingMigration {
+        PendingMigration::new(
+            MigrationTarget::Stable,
+            "abc1234",
+            to_hash,
+            "a new stable build was promoted",
+            due_ms,
+        )
+    }
+
+    #[test]
+    fn parse_migrate_reads_subcommands() {
+        assert_eq!(
+            parse_migrate_command("/migrate"),
+            Some(Ok(MigrateCommand::Status))
+        );
+        assert_eq!(
+            parse_migrate_command("/migrate now"),
+            Some(Ok(MigrateCommand::Now))
+        );
+        assert_eq!(
+            parse_migrate_command("/migrate defer"),
+            Some(Ok(MigrateCommand::Defer { minutes: 30 }))
+        );
+        assert_eq!(
+            parse_migrate_command("/migrate defer 90m"),
+            Some(Ok(MigrateCommand::Defer { minutes: 90 }))
+        );
+        assert_eq!(
+            parse_migrate_command("/migrate defer 0"),
+            Some(Err(MIGRATE_USAGE.to_string()))
+        );
+        assert_eq!(parse_migrate_command("/migrated"), None);
+    }
+
+    #[test]
+    fn banner_counts_down_and_shows_deferral() {
+        let mut migration = stable("0.15.0", 250_000);
+        assert_eq!(
+            banner_text(&migration, 10_000),
+            "⬆ migrating to 0.15.0 in 4m · /migrate now · /migrate defer"
+        );
+        assert_eq!(
+            banner_text(&migration, 241_000),
+            "⬆ migrating to 0.15.0 in 9s · /migrate now · /migrate defer"
+        );
+        migration.deferred_until_ms = Some(1_810_000);
+        assert_eq!(
+            banner_text(&migration, 10_000),
+            "⬆ migration to 0.15.0 deferred · asks again in 30m"
+        );
+    }
+
+    #[test]
+    fn reannouncing_keeps_a_deferral() {
+        let mut deferred = stable("", 1_000);
+        deferred.target = MigrationTarget::Server;
+        deferred.deferred_until_ms = Some(5_000);
+        let mut named = stable("0.15.0", 2_000);
+        named.target = MigrationTarget::Server;
+        assert!(deferred.same_migration(&named));
+        assert!(!named.same_migration(&stable("0.15.0", 2_000)));
+        let mut newer = named.clone();
+        newer.to_hash = "0.15.1".to_string();
+        assert!(!named.same_migration(&newer));
+    }
+}
@@ -0,0 +1,292 @@
+//! `/mirror <path>`: a read-only markdown copy of the transcript that
+//! teammates or another editor pane can watch without running jcode. The file
+//! is rewritten atomically, at most once a second and only when something
+//! changed, including text that is still streaming. The path is stored in the
+//! session, so resuming it picks the mirror back up.
+
+use super::{App, DisplayMessage};
+use crate::session::MarkdownTranscript;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+const MIN_WRITE_INTERVAL: Duration = Duration::from_secs(1);
+
+const HEADER: &str = "<!-- Auto-generated by jcode /mirror. Do not edit: this file is rewritten \
+                      as the session changes. -->";
+
+/// A parsed `/mirror` command.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(super) enum MirrorCommand {
+    Status,
+    Off,
+    Start(String),
+}
+
+pub(super) fn parse_mirror_command(trimmed: &str) -> Option<MirrorCommand> {
+    let rest = trimmed.strip_prefix("/mirror")?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some(match rest.trim() {
+        "" | "status" => MirrorCommand::Status,
+        "off" => MirrorCommand::Off,
+        path => MirrorCommand::Start(path.to_string()),
+    })
+}
+
+fn resolve_mirror_path(raw: &str) -> PathBuf {
+    let path = crate::output_tee::expand_home(raw);
+    std::path::absolute(&path).unwrap_or(path)
+}
+
+/// What the mirror shows besides the transcript itself.
+pub(super) struct MirrorSnapshot<'a> {
+    pub title: &'a str,
+    pub messages: &'a [DisplayMessage],
+    pub streaming_text: &'a str,
+    pub model: &'a str,
+    pub tokens: Option<(u64, u64)>,
+}
+
+/// The mirror file for `snapshot`. Tool calls are folded to one line each.
+pub(super) fn render_mirror(snapshot: &MirrorSnapshot<'_>) -> String {
+    let mut md = MarkdownTranscript::new();
+    md.paragraph(HEADER);
+    md.paragraph(&format!("# {}", snapshot.title));
+    let mut in_assistant_turn = false;
+    for message in snapshot.messages {
+        match message.role.as_str() {
+            "user" | "interjection" => {
+                md.heading("User");
+                md.paragraph(&message.content);
+                in_assistant_turn = false;
+            }
+            "assistant" => {
+                if !in_assistant_turn {
+                    md.heading("Assistant");
+                    in_assistant_turn = true;
+                }
+                if !message.content.trim().is_empty() {
+                    md.paragraph(&message.content);
+                }
+            }
+            "tool" => {
+                if !in_assistant_turn {
+                    md.heading("Assistant");
+                    in_assistant_turn = true;
+                }
+                let summary = match &message.tool_data {
+                    Some(tool) => {
+                        let detail = crate::tui::ui::tools_ui::get_tool_summary(tool);
+                        if detail.is_empty() {
+                            format!("🔧 {}", tool.name)
+                        } else {
+                            format!("🔧 {} · {}", tool.name, detail)
+                        }
+                    }
+                    None => format!("🔧 {}", message.title.as_deref().unwrap_or("tool")),
+                };
+                md.tool_summary(&escape_html(&summary), &message.content);
+            }
+            "error" => md.note(&format!("Error: {}", message.content.trim())),
+            _ => {}
+        }
+    }
+    if !snapshot.streaming_text.trim().is_empty() {
+        if !in_assistant_turn {
+            md.heading("Assistant");
+        }
+        md.paragraph(snapshot.streaming_text);
+        md.note("(streaming…)");
+    }
+    md.paragraph("---");
+    md.note(&match snapshot.tokens {
+        Some((input, output)) => format!(
+            "{} · {} input / {} output tokens",
+            snapshot.model, input, output
+        ),
+        None => snapshot.model.to_string(),
+    });
+    md.finish()
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+impl App {
+    fn mirror_tokens(&self) -> Option<(u64, u64)> {
+        self.remote_total_tokens.or_else(|| {
+            let totals = self.session.token_usage_totals();
+            (totals.messages_with_token_usage > 0)
+                .then_some((totals.input_tokens, totals.output_tokens))
+        })
+    }
+
+    /// Rewrite the mirror file when it is due and the transcript changed.
+    /// Returns true when the status line changed.
+    pub(super) fn write_mirror_if_due(&mut self) -> bool {
+        let Some(path) = self.session.mirror_path.clone() else {
+            return false;
+        };
+        if self
+            .mirror_last_write
+            .is_some_and(|last| last.elapsed() < MIN_WRITE_INTERVAL)
+        {
+            return false;
+        }
+        let first_write = self.mirror_last_write.is_none();
+        self.mirror_last_write = Some(Instant::now());
+
+        let model = crate::tui::TuiState::provider_model(self);
+        let content = render_mirror(&MirrorSnapshot {
+            title: self.session.display_title_or_name(),
+            messages: &self.display_messages,
+            streaming_text: &self.streaming.streaming_text,
+            model: &model,
+            tokens: self.mirror_tokens(),
+        });
+        let mut hasher = DefaultHasher::new();
+        content.hash(&mut hasher);
+        let hash = hasher.finish();
+        if self.mirror_last_hash == Some(hash) {
+            return false;
+        }
+
+        match crate::storage::write_bytes_without_backup(
+            std::path::Path::new(&path),
+            content.as_bytes(),
+        ) {
+            Ok(()) => {
+                self.mirror_last_hash = Some(hash);
+                if first_write {
+                    self.set_status_notice(format!("Mirroring to {}", path));
+                    return true;
+                }
+                false
+            }
+            Err(error) => {
+                crate::logging::warn(&format!("Failed to write mirror {}: {}", path, error));
+                // Report once per failure streak rather than every second.
+                let report = self.mirror_last_hash.is_some() || first_write;
+                self.mirror_last_hash = None;
+                if report {
+                    self.set_status_notice(format!("Mirror write failed: {}", error));
+                }
+                report
+            }
+        }
+    }
+
+    fn set_mirror_path(&mut self, path: Option<String>) {
+        self.session.mirror_path = path;
+        self.mirror_last_write = None;
+        self.mirror_last_hash = None;
+        if self.is_remote {
+            self.mirror_sync_pending = true;
+        } else if let Err(error) = self.session.save() {
+            self.push_display_message(DisplayMessage::error(format!(
+                "Failed to save mirror setting: {}",
+                error
+            )));
+        }
+    }
+}
+
+pub(super) fn handle_mirror_command(app: &mut App, trimmed: &str) -> bool {
+    let Some(command) = parse_mirror_command(trimmed) else {
+        return false;
+    };
+    match command {
+        MirrorCommand::Status => {
+            let text = match app.session.mirror_path.as_deref() {
+                Some(path) => format!("Mirroring this session to {}. `/mirror off` stops.", path),
+                None => "Not mirroring. `/mirror <path>` keeps a markdown copy of the \
+                         transcript there, updated as the session runs."
+                    .to_string(),
+            };
+            app.push_display_message(DisplayMessage::system(text));
+        }
+        MirrorCommand::Off => {
+            let Some(path) = app.session.mirror_path.clone() else {
+                app.push_display_message(DisplayMessage::system("Not mirroring.".to_string()));
+                return true;
+            };
+            app.set_mirror_path(None);
+            app.push_display_message(DisplayMessage::system(format!(
+                "Stopped mirroring. {} keeps its last contents.",
+                path
+            )));
+            app.set_status_notice("Mirror off");
+        }
+        MirrorCommand::Start(raw) => {
+            let path = resolve_mirror_path(&raw);
+            if path.is_dir() {
+                app.push_display_message(DisplayMessage::error(format!(
+                    "{} is a directory. Give a file path, e.g. `/mirror {}/session.md`.",
+                    path.display(),
+                    raw.trim_end_matches('/')
+                )));
+                return true;
+            }
+            let path = path.display().to_string();
+            app.set_mirror_path(Some(path.clone()));
+            app.push_display_message(DisplayMessage::system(format!(
+                "Mirroring this session to {} (rewritten at most once a second). \
+                 `/mirror off` stops.",
+                path
+            )));
+            app.write_mirror_if_due();
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::ToolCall;
+
+    #[test]
+    fn parse_mirror_reads_subcommands() {
+        assert_eq!(parse_mirror_command("/mirror"), Some(MirrorCommand::Status));
+        assert_eq!(
+            parse_mirror_command("/mirror off"),
+            Some(MirrorCommand::Off)
+        );
+        assert_eq!(
+            parse_mirror_command("/mirror ~/share/fox.md"),
+            Some(MirrorCommand::Start("~/share/fox.md".to_string()))
+        );
+        assert_eq!(parse_mirror_command("/mirrors"), None);
+    }
+
+    #[test]
+    fn render_folds_tools_and_shows_streaming_text() {
+        let tool = ToolCall {
+            id: "call-1".to_string(),
+            name: "bash".to_string(),
+            input: serde_json::json!({"command": "cargo check"}),
+            ..ToolCall::default()
+        };
+        let user = DisplayMessage::user("fix the build");
+        let tool = DisplayMessage::tool("ok", tool);
+        let messages = vec![user, tool];
+        let out = render_mirror(&MirrorSnapshot {
+            title: "fox",
+            messages: &messages,
+            streaming_text: "The build passes",
+            model: "claude-sonnet-4-5",
+            tokens: Some((1200, 80)),
+        });
+        assert!(out.starts_with(HEADER));
+        assert!(out.contains("# fox\n\n### User\n\nfix the build\n\n### Assistant\n\n<details>"));
+        assert!(out.contains("<summary>🔧 bash · $ cargo check</summary>"));
+        assert!(out.contains("The build passes\n\n*(streaming…)*"));
+        assert!(out.ends_with("---\n\n*claude-sonnet-4-5 · 1200 input / 80 output tokens*\n\n"));
+    }
+}
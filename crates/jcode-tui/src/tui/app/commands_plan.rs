@@ -1,25 +1,52 @@
-use super::commands_improve::{interrupt_and_queue_synthetic_message, start_synthetic_user_turn};
+use super::commands_improve::start_synthetic_user_turn;
 use super::{App, DisplayMessage};
+use crate::plan::proposal::{
+    PlanDecision, PlanProposal, PlanProposalStatus, SessionPlanMode, plan_decision_prompt,
+};
 
-/// A parsed `/plan` command. Planning is a one-shot, plan-only action: it never
-/// edits files and is not a resumable loop like `/improve` or `/refactor`.
+/// A parsed `/plan` command.
+///
+/// `/plan [goal]` enters plan mode: the agent may only read the workspace and
+/// must submit a plan through `plan_propose`, which the user then approves,
+/// sends back for edits, or rejects.
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub(super) struct PlanCommand {
-    pub goal: Option<String>,
+pub(super) enum PlanCommand {
+    Start {
+        goal: Option<String>,
+    },
+    Decide {
+        decision: PlanDecision,
+        feedback: Option<String>,
+    },
+    Off,
+    Status,
 }
 
 pub(super) fn parse_plan_command(trimmed: &str) -> Option<PlanCommand> {
     let rest = trimmed.strip_prefix("/plan")?;
-    // Only treat `/plan` and `/plan <goal>` as a plan command, not `/planfoo`.
+    // Only treat `/plan` and `/plan <args>` as a plan command, not `/planfoo`.
     if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
         return None;
     }
-    let goal = rest.trim();
-    Some(PlanCommand {
-        goal: if goal.is_empty() {
-            None
-        } else {
-            Some(goal.to_string())
+    let rest = rest.trim();
+    let (word, tail) = rest
+        .split_once(char::is_whitespace)
+        .map(|(word, tail)| (word, tail.trim()))
+        .unwrap_or((rest, ""));
+    let feedback = (!tail.is_empty()).then(|| tail.to_string());
+    let decide = |decision| PlanCommand::Decide {
+        decision,
+        feedback: feedback.clone(),
+    };
+    Some(match word {
+        "" => PlanCommand::Start { goal: None },
+        "approve" => decide(PlanDecision::Approve),
+        "edit" => decide(PlanDecision::Revise),
+        "reject" => decide(PlanDecision::Reject),
+        "off" if tail.is_empty() => PlanCommand::Off,
+        "status" if tail.is_empty() => PlanCommand::Status,
+        _ => PlanCommand::Start {
+            goal: Some(rest.to_string()),
         },
     })
 }
@@ -34,44 +61,162 @@ pub(super) fn build_plan_prompt(goal: Option<&str>) -> String {
         "You are entering planning mode.\n\
 \n\
 {}\
-Your job is to produce a clear, concrete, actionable plan. Do NOT implement anything yet: do not edit files, write patches, or change git state. You may freely read, search, run read-only commands, and analyze the codebase so the plan is grounded in how things actually work.\n\
+Your job is to produce a clear, concrete, actionable plan. Do NOT implement anything yet: do not edit files, write patches, or change git state. Read and search the codebase so the plan is grounded in how things actually work.\n\
 \n\
-When the plan is ready, present it directly in your reply inside a fenced code block whose language is `plan` (```plan ... ```). The UI renders that block as a dedicated plan card. Structure the plan inside the block with these sections: a top-level `# <short plan title>` heading, then Goal, Scope / affected areas, Approach (concrete ordered steps), Validation (how each part will be verified), and Open questions / decisions.\n\
+When the plan is ready, submit it with the `plan_propose` tool: a short title, concrete ordered steps, the files you expect to touch, the commands you intend to run, and the risks or open questions. The UI renders it as a plan card for the user to approve, edit, or reject.\n\
 \n\
-Keep it tight and high-signal. Avoid speculative rewrites and busywork. After presenting the plan card, stop and wait for the user. Do not start implementing.\n\
+Keep it tight and high-signal. Avoid speculative rewrites and busywork. After calling `plan_propose`, stop and wait for the user. Do not start implementing.\n\
 \n\
 Only once the user approves, use the `todo` tool to turn the plan into an executable todo list and then begin the work.",
         goal_line,
     )
 }
 
-pub(super) fn plan_launch_notice(goal: Option<&str>, interrupted: bool) -> String {
-    let prefix = if interrupted {
-        "👉 Interrupting and planning"
-    } else {
-        "🧭 Planning"
-    };
+pub(super) fn plan_launch_notice(goal: Option<&str>) -> String {
     match goal.map(str::trim).filter(|goal| !goal.is_empty()) {
-        Some(goal) => format!("{} {}... (plan-only; no edits)", prefix, goal),
-        None => format!("{}... (plan-only; no edits)", prefix),
+        Some(goal) => format!("🧭 Plan mode: planning {}... (read-only tools)", goal),
+        None => "🧭 Plan mode: planning... (read-only tools)".to_string(),
     }
 }
 
-pub(super) fn handle_plan_command_local(app: &mut App, command: PlanCommand) {
-    let prompt = build_plan_prompt(command.goal.as_deref());
-    if app.is_processing {
-        interrupt_and_queue_synthetic_message(
-            app,
+pub(super) const PLAN_BUSY_NOTICE: &str =
+    "Finish or interrupt the current turn before changing plan mode.";
+
+pub(super) const PLAN_DECISION_HINT: &str =
+    "Plan proposed · /plan approve · /plan edit <notes> · /plan reject [reason]";
+
+/// What to do after a `/plan approve|edit|reject`: the notice to show and the
+/// follow-up message to send to the agent, if any.
+pub(super) struct PlanDecisionOutcome {
+    pub notice: String,
+    pub prompt: Option<String>,
+}
+
+impl App {
+    pub(super) fn plan_mode_active(&self) -> bool {
+        self.session
+            .plan_mode
+            .as_ref()
+            .is_some_and(|mode| mode.active)
+    }
+
+    pub(super) fn set_plan_mode_active(&mut self, active: bool) {
+        self.session
+            .plan_mode
+            .get_or_insert_with(SessionPlanMode::default)
+            .active = active;
+    }
+
+    /// Mirror a `plan_propose` call into the session and prompt for a decision.
+    pub(super) fn note_plan_proposal(&mut self, input: &serde_json::Value) {
+        let Ok(proposal) = serde_json::from_value::<PlanProposal>(input.clone()) else {
+            return;
+        };
+        let markdown = proposal.to_markdown();
+        self.session
+            .plan_mode
+            .get_or_insert_with(SessionPlanMode::default)
+            .record_proposal(proposal, now_unix_ms());
+        self.push_display_message(DisplayMessage::assistant(markdown));
+        self.push_display_message(DisplayMessage::system(PLAN_DECISION_HINT.to_string()));
+        self.set_status_notice("Plan awaiting approval");
+    }
+
+    /// Validate and apply a plan decision to the client-side session mirror.
+    pub(super) fn apply_plan_decision(
+        &mut self,
+        decision: PlanDecision,
+        feedback: Option<String>,
+    ) -> Result<PlanDecisionOutcome, String> {
+        let Some(mode) = self.session.plan_mode.as_mut() else {
+            return Err("No plan has been proposed. Use /plan <goal> first.".to_string());
+        };
+        let Some(plan) = mode.proposal.as_ref().map(PlanProposal::to_markdown) else {
+            return Err("No plan has been proposed yet.".to_string());
+        };
+        if mode.status != PlanProposalStatus::Pending {
+            return Err("The latest plan has already been decided.".to_string());
+        }
+        if decision == PlanDecision::Revise && feedback.is_none() {
+            return Err("Usage: /plan edit <notes>".to_string());
+        }
+        let prompt = match decision {
+            PlanDecision::Reject => None,
+            _ => Some(plan_decision_prompt(
+                decision,
+                Some(&plan),
+                feedback.as_deref(),
+            )),
+        };
+        mode.decide(decision, feedback, now_unix_ms());
+        let notice = match decision {
+            PlanDecision::Approve => "✅ Plan approved. Plan mode off; executing.",
+            PlanDecision::Revise => "✏️ Plan sent back for revision.",
+            PlanDecision::Reject => "❌ Plan rejected. Plan mode off.",
+        };
+        Ok(PlanDecisionOutcome {
+            notice: notice.to_string(),
             prompt,
-            "Interrupting for /plan...",
-            plan_launch_notice(command.goal.as_deref(), true),
-        );
-    } else {
-        app.push_display_message(DisplayMessage::system(plan_launch_notice(
-            command.goal.as_deref(),
-            false,
-        )));
-        start_synthetic_user_turn(app, prompt);
+        })
+    }
+
+    pub(super) fn plan_status_message(&self) -> String {
+        let Some(mode) = self.session.plan_mode.as_ref() else {
+            return "Plan mode: off\nUse /plan <goal> to plan before executing.".to_string();
+        };
+        let mut out = format!("Plan mode: {}", if mode.active { "on" } else { "off" });
+        if let Some(proposal) = mode.proposal.as_ref() {
+            let status = match mode.status {
+                PlanProposalStatus::Pending => "awaiting approval",
+                PlanProposalStatus::Approved => "approved",
+                PlanProposalStatus::Rejected => "rejected",
+            };
+            out.push_str(&format!(
+                "\nLatest plan: {} ({} steps, {})",
+                proposal.title,
+                proposal.steps.len(),
+                status
+            ));
+        }
+        out
+    }
+}
+
+fn now_unix_ms() -> u64 {
+    chrono::Utc::now().timestamp_millis().max(0) as u64
+}
+
+pub(super) fn handle_plan_command_local(app: &mut App, command: PlanCommand) {
+    match command {
+        PlanCommand::Status => {
+            app.push_display_message(DisplayMessage::system(app.plan_status_message()));
+        }
+        _ if app.is_processing => {
+            app.push_display_message(DisplayMessage::error(PLAN_BUSY_NOTICE.to_string()));
+        }
+        PlanCommand::Start { goal } => {
+            app.set_plan_mode_active(true);
+            let _ = app.session.save();
+            app.push_display_message(DisplayMessage::system(plan_launch_notice(goal.as_deref())));
+            start_synthetic_user_turn(app, build_plan_prompt(goal.as_deref()));
+        }
+        PlanCommand::Off => {
+            app.set_plan_mode_active(false);
+            let _ = app.session.save();
+            app.push_display_message(DisplayMessage::system("Plan mode off.".to_string()));
+        }
+        PlanCommand::Decide { decision, feedback } => {
+            match app.apply_plan_decision(decision, feedback) {
+                Ok(outcome) => {
+                    let _ = app.session.save();
+                    app.push_display_message(DisplayMessage::system(outcome.notice));
+                    if let Some(prompt) = outcome.prompt {
+                        start_synthetic_user_turn(app, prompt);
+                    }
+                }
+                Err(error) => app.push_display_message(DisplayMessage::error(error)),
+            }
+        }
     }
 }
 
@@ -83,20 +228,49 @@ mod tests {
     fn parse_plan_accepts_bare_and_goal_forms() {
         assert_eq!(
             parse_plan_command("/plan"),
-            Some(PlanCommand { goal: None })
+            Some(PlanCommand::Start { goal: None })
         );
         assert_eq!(
             parse_plan_command("/plan   "),
-            Some(PlanCommand { goal: None })
+            Some(PlanCommand::Start { goal: None })
         );
         assert_eq!(
             parse_plan_command("/plan add a compact mode"),
-            Some(PlanCommand {
+            Some(PlanCommand::Start {
                 goal: Some("add a compact mode".to_string())
             })
         );
     }
 
+    #[test]
+    fn parse_plan_accepts_decisions_and_mode_commands() {
+        assert_eq!(
+            parse_plan_command("/plan approve"),
+            Some(PlanCommand::Decide {
+                decision: PlanDecision::Approve,
+                feedback: None,
+            })
+        );
+        assert_eq!(
+            parse_plan_command("/plan edit  skip the migration"),
+            Some(PlanCommand::Decide {
+                decision: PlanDecision::Revise,
+                feedback: Some("skip the migration".to_string()),
+            })
+        );
+        assert_eq!(parse_plan_command("/plan off"), Some(PlanCommand::Off));
+        assert_eq!(
+            parse_plan_command("/plan status"),
+            Some(PlanCommand::Status)
+        );
+        assert_eq!(
+            parse_plan_command("/plan off-by-one fix"),
+            Some(PlanCommand::Start {
+                goal: Some("off-by-one fix".to_string())
+            })
+        );
+    }
+
     #[test]
     fn parse_plan_rejects_other_commands() {
         assert_eq!(parse_plan_command("/planner foo"), None);
@@ -105,11 +279,11 @@ mod tests {
     }
 
     #[test]
-    fn build_plan_prompt_is_plan_only_and_targets_plan_card() {
+    fn build_plan_prompt_is_plan_only_and_uses_plan_propose() {
         let prompt = build_plan_prompt(Some("ship feature x"));
         assert!(prompt.contains("Goal: ship feature x"));
         assert!(prompt.contains("Do NOT implement anything yet"));
-        assert!(prompt.contains("```plan"));
+        assert!(prompt.contains("`plan_propose`"));
         assert!(prompt.contains("`todo`"));
 
         let bare = build_plan_prompt(None);
@@ -0,0 +1,124 @@
+use super::{App, DisplayMessage};
+
+/// A parsed `/profile` command.
+///
+/// `/profile <name>` selects a `[profiles.<name>]` preset from config.toml,
+/// `/profile off` clears it, and a bare `/profile` lists what is available.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(super) enum ProfileCommand {
+    Set(String),
+    Off,
+    Status,
+}
+
+pub(super) fn parse_profile_command(trimmed: &str) -> Option<ProfileCommand> {
+    let rest = trimmed.strip_prefix("/profile")?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some(match rest.trim() {
+        "" | "status" => ProfileCommand::Status,
+        "off" | "none" => ProfileCommand::Off,
+        name => ProfileCommand::Set(name.to_string()),
+    })
+}
+
+pub(super) const PROFILE_NEXT_TURN_NOTICE: &str = "applies at the next turn";
+
+impl App {
+    pub(super) fn active_profile(&self) -> Option<&str> {
+        self.session.profile.as_deref()
+    }
+
+    pub(super) fn profile_status_message(&self) -> String {
+        let config = crate::config::config();
+        let mut out = format!(
+            "Profile: {}",
+            self.active_profile().unwrap_or("none (config defaults)")
+        );
+        if config.profiles.is_empty() {
+            out.push_str("\nNo [profiles.<name>] sections in config.toml.");
+        } else {
+            out.push_str("\nAvailable:");
+            for (name, profile) in &config.profiles {
+                let model = profile
+                    .model_request()
+                    .map(|model| format!(" · {}", model))
+                    .unwrap_or_default();
+                out.push_str(&format!("\n  {}{}", name, model));
+            }
+            out.push_str("\nUse /profile <name> to switch, /profile off to clear.");
+        }
+        out
+    }
+
+    /// Check a profile name against config before sending it anywhere.
+    pub(super) fn validate_profile_selection(&self, name: &str) -> Result<(), String> {
+        let config = crate::config::config();
+        let profile = config.profile(name).map_err(|error| error.to_string())?;
+        match profile.validate(name).into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    pub(super) fn note_profile_changed(&mut self, profile: Option<String>) {
+        let busy = if self.is_processing {
+            format!(" ({})", PROFILE_NEXT_TURN_NOTICE)
+        } else {
+            String::new()
+        };
+        let message = match profile.as_deref() {
+            Some(name) => format!("Profile: {}{}", name, busy),
+            None => format!("Profile cleared{}", busy),
+        };
+        self.session.profile = profile;
+        self.set_status_notice(message.clone());
+        self.push_display_message(DisplayMessage::system(message));
+    }
+}
+
+pub(super) fn handle_profile_command_local(app: &mut App, command: ProfileCommand) {
+    match command {
+        ProfileCommand::Status => {
+            app.push_display_message(DisplayMessage::system(app.profile_status_message()));
+        }
+        ProfileCommand::Off => {
+            app.note_profile_changed(None);
+            let _ = app.session.save();
+        }
+        ProfileCommand::Set(name) => match app.validate_profile_selection(&name) {
+            Ok(()) => {
+                app.note_profile_changed(Some(name));
+                let _ = app.session.save();
+            }
+            Err(error) => app.push_display_message(DisplayMessage::error(error)),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_profile_accepts_name_off_and_status() {
+        assert_eq!(
+            parse_profile_command("/profile review"),
+            Some(ProfileCommand::Set("review".to_string()))
+        );
+        assert_eq!(
+            parse_profile_command("/profile off"),
+            Some(ProfileCommand::Off)
+        );
+        assert_eq!(
+            parse_profile_command("/profile"),
+            Some(ProfileCommand::Status)
+        );
+        assert_eq!(
+            parse_profile_command("/profile  status "),
+            Some(ProfileCommand::Status)
+        );
+        assert_eq!(parse_profile_command("/profiles"), None);
+    }
+}
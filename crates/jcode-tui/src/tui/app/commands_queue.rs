@@ -0,0 +1,270 @@
+//! `/queue`: pending turns of the session. The server owns the queue of
+//! messages, soft interrupts and scheduled follow-ups; messages typed while a
+//! turn runs wait in this client until it ends and are listed as `local-N`.
+
+use super::{App, DisplayMessage};
+use crate::protocol::{TurnQueueItem, TurnQueueKind};
+
+const QUEUE_USAGE: &str =
+    "Usage: `/queue [list]`, `/queue rm <item>` or `/queue move <item> <position>`";
+
+const LOCAL_PREFIX: &str = "local-";
+
+const QUEUE_LOCAL_NOTICE: &str =
+    "Soft interrupts and scheduled follow-ups are listed when connected to a jcode server.";
+
+/// A parsed `/queue` command.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(super) enum QueueCommand {
+    List,
+    Remove(String),
+    Move { item_id: String, position: usize },
+}
+
+/// Parse `/queue [list]`, `/queue rm <item>` and `/queue move <item> <n>`.
+/// Returns `None` for other input and an error message for a malformed
+/// `/queue`.
+pub(super) fn parse_queue_command(trimmed: &str) -> Option<Result<QueueCommand, String>> {
+    let rest = trimmed.strip_prefix("/queue")?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let args: Vec<&str> = rest.split_whitespace().collect();
+    Some(parse_queue_args(&args).ok_or_else(|| QUEUE_USAGE.to_string()))
+}
+
+fn parse_queue_args(args: &[&str]) -> Option<QueueCommand> {
+    match args {
+        [] | ["list"] => Some(QueueCommand::List),
+        ["rm" | "remove", item_id] => Some(QueueCommand::Remove(item_id.to_string())),
+        ["move", item_id, position] => {
+            let position = position
+                .parse::<usize>()
+                .ok()
+                .filter(|position| *position > 0)?;
+            Some(QueueCommand::Move {
+                item_id: item_id.to_string(),
+                position,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Apply `/queue rm|move` to a `local-N` message waiting in this client.
+/// Returns `None` when the command does not target a local item.
+pub(super) fn edit_local_queue(
+    queued: &mut Vec<String>,
+    command: &QueueCommand,
+) -> Option<Result<(), String>> {
+    let (QueueCommand::Remove(item_id) | QueueCommand::Move { item_id, .. }) = command else {
+        return None;
+    };
+    let index = item_id.strip_prefix(LOCAL_PREFIX)?.parse::<usize>().ok();
+    let Some(index) = index
+        .filter(|n| (1..=queued.len()).contains(n))
+        .map(|n| n - 1)
+    else {
+        return Some(Err(format!("No queued message `{}`.", item_id)));
+    };
+    let message = queued.remove(index);
+    if let QueueCommand::Move { position, .. } = command {
+        queued.insert((*position).min(queued.len() + 1) - 1, message);
+    }
+    Some(Ok(()))
+}
+
+/// Check a server item id against the last snapshot before sending an edit.
+pub(super) fn check_remote_edit(
+    items: &[TurnQueueItem],
+    command: &QueueCommand,
+) -> Result<(), String> {
+    let (QueueCommand::Remove(item_id) | QueueCommand::Move { item_id, .. }) = command else {
+        return Ok(());
+    };
+    let Some(item) = items.iter().find(|item| &item.id == item_id) else {
+        return Err(format!(
+            "No pending item `{}`. Run `/queue` to see the current queue.",
+            item_id
+        ));
+    };
+    if matches!(command, QueueCommand::Move { .. }) && item.kind == TurnQueueKind::ScheduledFollowUp
+    {
+        return Err(
+            "Scheduled follow-ups run at their due time; remove them with `/queue rm` instead."
+                .to_string(),
+        );
+    }
+    Ok(())
+}
+
+/// The server queue followed by messages waiting in this client.
+pub(super) fn format_turn_queue(items: &[TurnQueueItem], local: &[String]) -> String {
+    if items.is_empty() && local.is_empty() {
+        return "Nothing is queued.".to_string();
+    }
+    let mut out = String::new();
+    if !items.is_empty() {
+        out.push_str("Queued on the server:");
+        for item in items {
+            out.push_str(&format!("\n- {} — {}", item.status_line(), item.preview));
+        }
+    }
+    if !local.is_empty() {
+        if !out.is_empty() {
+            out.push_str("\n\n");
+        }
+        out.push_str("Waiting in this client (sent together when the turn ends):");
+        for (index, message) in local.iter().enumerate() {
+            let line = message.trim().lines().next().unwrap_or_default();
+            out.push_str(&format!(
+                "\n- {}{} — {}",
+                LOCAL_PREFIX,
+                index + 1,
+                crate::util::truncate_str(line, 80)
+            ));
+        }
+    }
+    out.push_str("\n\nUse `/queue rm <item>` or `/queue move <item> <position>` before they run.");
+    out
+}
+
+impl App {
+    /// Record the server's turn queue, printing it when `/queue` asked.
+    pub(super) fn apply_turn_queue(&mut self, items: Vec<TurnQueueItem>) {
+        self.remote_turn_queue = items;
+        if std::mem::take(&mut self.turn_queue_display_pending) {
+            self.show_turn_queue();
+        }
+    }
+
+    pub(super) fn show_turn_queue(&mut self) {
+        self.push_display_message(DisplayMessage::system(format_turn_queue(
+            &self.remote_turn_queue,
+            &self.queued_messages,
+        )));
+        self.set_status_notice("Queue");
+    }
+}
+
+/// Local mode has no server queue; only messages waiting in this client can
+/// be listed and edited.
+pub(super) fn handle_queue_command_local(app: &mut App, command: Result<QueueCommand, String>) {
+    let command = match command {
+        Ok(command) => command,
+        Err(error) => {
+            app.push_display_message(DisplayMessage::error(error));
+            return;
+        }
+    };
+    match edit_local_queue(&mut app.queued_messages, &command) {
+        Some(Err(error)) => app.push_display_message(DisplayMessage::error(error)),
+        Some(Ok(())) => app.show_turn_queue(),
+        None if command == QueueCommand::List => {
+            app.show_turn_queue();
+            app.push_display_message(DisplayMessage::system(QUEUE_LOCAL_NOTICE.to_string()));
+        }
+        None => app.push_display_message(DisplayMessage::error(QUEUE_LOCAL_NOTICE.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: &str, kind: TurnQueueKind) -> TurnQueueItem {
+        TurnQueueItem {
+            id: id.to_string(),
+            kind,
+            origin: "user".to_string(),
+            preview: "run the tests".to_string(),
+            urgent: false,
+            due_at: None,
+        }
+    }
+
+    #[test]
+    fn parse_queue_reads_subcommands() {
+        assert_eq!(parse_queue_command("/queue"), Some(Ok(QueueCommand::List)));
+        assert_eq!(
+            parse_queue_command("/queue list"),
+            Some(Ok(QueueCommand::List))
+        );
+        assert_eq!(
+            parse_queue_command("/queue rm int-4"),
+            Some(Ok(QueueCommand::Remove("int-4".to_string())))
+        );
+        assert_eq!(
+            parse_queue_command("/queue move msg-2 1"),
+            Some(Ok(QueueCommand::Move {
+                item_id: "msg-2".to_string(),
+                position: 1,
+            }))
+        );
+        assert_eq!(
+            parse_queue_command("/queue move msg-2 0"),
+            Some(Err(QUEUE_USAGE.to_string()))
+        );
+        assert_eq!(
+            parse_queue_command("/queue rm"),
+            Some(Err(QUEUE_USAGE.to_string()))
+        );
+        assert_eq!(parse_queue_command("/queued"), None);
+    }
+
+    #[test]
+    fn local_items_are_removed_and_reordered() {
+        let mut queued = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let move_c = QueueCommand::Move {
+            item_id: "local-3".to_string(),
+            position: 1,
+        };
+        assert_eq!(edit_local_queue(&mut queued, &move_c), Some(Ok(())));
+        assert_eq!(queued, ["c", "a", "b"]);
+        let remove_a = QueueCommand::Remove("local-2".to_string());
+        assert_eq!(edit_local_queue(&mut queued, &remove_a), Some(Ok(())));
+        assert_eq!(queued, ["c", "b"]);
+        assert!(matches!(
+            edit_local_queue(&mut queued, &QueueCommand::Remove("local-9".to_string())),
+            Some(Err(_))
+        ));
+        assert_eq!(
+            edit_local_queue(&mut queued, &QueueCommand::Remove("msg-1".to_string())),
+            None
+        );
+        assert_eq!(edit_local_queue(&mut queued, &QueueCommand::List), None);
+    }
+
+    #[test]
+    fn remote_edits_are_checked_against_the_snapshot() {
+        let items = [
+            item("msg-1", TurnQueueKind::Message),
+            item("sched-x", TurnQueueKind::ScheduledFollowUp),
+        ];
+        assert!(check_remote_edit(&items, &QueueCommand::Remove("sched-x".to_string())).is_ok());
+        assert!(check_remote_edit(&items, &QueueCommand::Remove("int-2".to_string())).is_err());
+        assert!(
+            check_remote_edit(
+                &items,
+                &QueueCommand::Move {
+                    item_id: "sched-x".to_string(),
+                    position: 1,
+                },
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn format_lists_server_and_local_items() {
+        let out = format_turn_queue(
+            &[item("msg-1", TurnQueueKind::Message)],
+            &["later\nmore".to_string()],
+        );
+        assert!(
+            out.starts_with("Queued on the server:\n- msg-1 · message from user — run the tests")
+        );
+        assert!(out.contains("\n- local-1 — later"));
+        assert_eq!(format_turn_queue(&[], &[]), "Nothing is queued.");
+    }
+}
@@ -0,0 +1,209 @@
+//! `/redaction`: the `[privacy]` placeholder mapping of the current session
+//! and a dry run of the redaction pass. Both read local state only, so they
+//! work the same with or without a server.
+
+use super::commands::active_session_id;
+use super::{App, DisplayMessage};
+use crate::redaction::{RedactionKind, RedactionMap, Redactor};
+
+const REDACTION_USAGE: &str = "Usage: `/redaction [show]` or `/redaction test <text>`";
+
+/// A parsed `/redaction` command.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(super) enum RedactionCommand {
+    Show,
+    Test(String),
+}
+
+/// Parse `/redaction [show]` and `/redaction test <text>`. Returns `None` for
+/// other input and an error message for a malformed `/redaction`.
+pub(super) fn parse_redaction_command(trimmed: &str) -> Option<Result<RedactionCommand, String>> {
+    let rest = trimmed.strip_prefix("/redaction")?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let rest = rest.trim();
+    if rest.is_empty() || rest == "show" {
+        return Some(Ok(RedactionCommand::Show));
+    }
+    let text = rest
+        .strip_prefix("test")
+        .filter(|text| text.starts_with(char::is_whitespace))
+        .map(str::trim)
+        .filter(|text| !text.is_empty());
+    Some(
+        text.map(|text| RedactionCommand::Test(text.to_string()))
+            .ok_or_else(|| REDACTION_USAGE.to_string()),
+    )
+}
+
+pub(super) fn handle_redaction_command(app: &mut App, trimmed: &str) -> bool {
+    let Some(command) = parse_redaction_command(trimmed) else {
+        return false;
+    };
+    match command {
+        Ok(RedactionCommand::Show) => {
+            let map = RedactionMap::load(&active_session_id(app));
+            let redacted = if app.is_remote {
+                app.remote_token_usage_totals
+                    .map(|totals| totals.redactions)
+            } else {
+                Some(app.session.token_usage_totals().redactions)
+            };
+            app.push_display_message(DisplayMessage::system(format_redaction_map(
+                &map,
+                crate::redaction::enabled(),
+                redacted,
+            )));
+            app.set_status_notice("Redaction: mapping");
+        }
+        Ok(RedactionCommand::Test(text)) => {
+            // Number placeholders after the session's existing ones, without
+            // saving anything.
+            let mut map = RedactionMap::load(&active_session_id(app));
+            app.push_display_message(DisplayMessage::system(format_redaction_test(
+                &crate::redaction::configured_redactor(),
+                &mut map,
+                &text,
+                crate::redaction::enabled(),
+            )));
+            app.set_status_notice("Redaction: test");
+        }
+        Err(error) => app.push_display_message(DisplayMessage::error(error)),
+    }
+    true
+}
+
+fn enabled_line(enabled: bool) -> &'static str {
+    if enabled {
+        "Redaction is on (`[privacy] redact`)."
+    } else {
+        "Redaction is off. Set `[privacy] redact = true` to scrub outgoing messages."
+    }
+}
+
+/// The session's placeholders and originals, with the redaction count from
+/// the session's token usage when known.
+pub(super) fn format_redaction_map(
+    map: &RedactionMap,
+    enabled: bool,
+    redacted: Option<u64>,
+) -> String {
+    let mut out = enabled_line(enabled).to_string();
+    if let Some(redacted) = redacted {
+        out.push_str(&format!(
+            " {} value{} redacted in requests this session.",
+            redacted,
+            if redacted == 1 { "" } else { "s" }
+        ));
+    }
+    if map.is_empty() {
+        out.push_str("\n\nNo placeholders recorded for this session.");
+        return out;
+    }
+    out.push_str("\n\nPlaceholders (kept locally, never sent):");
+    for entry in &map.entries {
+        out.push_str(&format!("\n- `{}` → {}", entry.placeholder, entry.original));
+    }
+    out
+}
+
+/// What a redaction pass would do to `text`.
+pub(super) fn format_redaction_test(
+    redactor: &Redactor,
+    map: &mut RedactionMap,
+    text: &str,
+    enabled: bool,
+) -> String {
+    let matches = redactor.find(text);
+    let (redacted, count) = redactor.redact(text, map);
+    let mut out = if count == 0 {
+        "Nothing would be redacted.".to_string()
+    } else {
+        let by_kind = RedactionKind::ALL
+            .iter()
+            .filter_map(|kind| {
+                let n = matches.iter().filter(|m| m.kind == *kind).count();
+                (n > 0).then(|| format!("{} {}", n, kind.label()))
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "Would redact {} value{} ({}):\n\n```\n{}\n```",
+            count,
+            if count == 1 { "" } else { "s" },
+            by_kind,
+            redacted
+        )
+    };
+    out.push_str("\n\n");
+    out.push_str(enabled_line(enabled));
+    for (pattern, error) in redactor.invalid_patterns() {
+        out.push_str(&format!(
+            "\n\nInvalid `redact_patterns` entry `{}` is ignored: {}",
+            pattern, error
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_redaction_reads_show_and_test() {
+        assert_eq!(
+            parse_redaction_command("/redaction"),
+            Some(Ok(RedactionCommand::Show))
+        );
+        assert_eq!(
+            parse_redaction_command("/redaction show"),
+            Some(Ok(RedactionCommand::Show))
+        );
+        assert_eq!(
+            parse_redaction_command("/redaction test  mail ops@example.com "),
+            Some(Ok(RedactionCommand::Test(
+                "mail ops@example.com".to_string()
+            )))
+        );
+        assert_eq!(
+            parse_redaction_command("/redaction test"),
+            Some(Err(REDACTION_USAGE.to_string()))
+        );
+        assert_eq!(
+            parse_redaction_command("/redaction testing"),
+            Some(Err(REDACTION_USAGE.to_string()))
+        );
+        assert_eq!(parse_redaction_command("/redactions"), None);
+    }
+
+    #[test]
+    fn redaction_test_counts_by_kind_and_lists_bad_patterns() {
+        let redactor = Redactor::new(&["(oops".to_string()]);
+        let mut map = RedactionMap::default();
+        let out = format_redaction_test(
+            &redactor,
+            &mut map,
+            "ops@example.com and dev@example.com on 10.0.0.7",
+            false,
+        );
+        assert!(out.starts_with("Would redact 3 values (2 EMAIL, 1 IPV4):"));
+        assert!(out.contains("<EMAIL_1> and <EMAIL_2> on <IPV4_1>"));
+        assert!(out.contains("Redaction is off."));
+        assert!(out.contains("`(oops` is ignored"));
+    }
+
+    #[test]
+    fn redaction_map_lists_placeholders() {
+        let mut map = RedactionMap::default();
+        map.placeholder_for(RedactionKind::Email, "ops@example.com");
+        let out = format_redaction_map(&map, true, Some(1));
+        assert!(out.starts_with("Redaction is on (`[privacy] redact`). 1 value redacted"));
+        assert!(out.ends_with("- `<EMAIL_1>` → ops@example.com"));
+        assert!(
+            format_redaction_map(&RedactionMap::default(), false, None)
+                .ends_with("No placeholders recorded for this session.")
+        );
+    }
+}
@@ -268,6 +268,18 @@ fn apply_judge_visible_context_if_needed(session: &mut Session, title_override:
 }
 
 pub(super) fn reset_current_session(app: &mut App) {
+    let mut session = Session::create(None, None);
+    session.model = Some(app.provider.model());
+    session.provider_key = crate::session::derive_session_provider_key(app.provider.name());
+    session.autoreview_enabled = Some(app.autoreview_enabled);
+    session.autojudge_enabled = Some(app.autojudge_enabled);
+    session.ensure_initial_session_context_message();
+    switch_current_session(app, session);
+}
+
+/// Close and save the current session, then continue in `session` with a
+/// cleared view.
+pub(super) fn switch_current_session(app: &mut App, mut session: Session) {
     app.session.mark_closed();
     let _ = app.session.save();
     app.clear_provider_messages();
@@ -277,13 +289,7 @@ pub(super) fn reset_current_session(app: &mut App) {
     app.pending_images.clear();
     app.active_skill = None;
     app.improve_mode = None;
-    let mut session = Session::create(None, None);
     session.mark_active();
-    session.model = Some(app.provider.model());
-    session.provider_key = crate::session::derive_session_provider_key(app.provider.name());
-    session.autoreview_enabled = Some(app.autoreview_enabled);
-    session.autojudge_enabled = Some(app.autojudge_enabled);
-    session.ensure_initial_session_context_message();
     app.session = session;
     app.set_side_panel_snapshot(crate::side_panel::SidePanelSnapshot::default());
     app.last_side_panel_focus_id = None;
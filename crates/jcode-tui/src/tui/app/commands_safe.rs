@@ -0,0 +1,141 @@
+use super::{App, DisplayMessage};
+
+/// A parsed `/safe` command.
+///
+/// `/safe on` turns on the `jcode --safe` defaults for this session. Turning
+/// them off takes an explicit `/safe off confirm`; a bare `/safe off` only
+/// explains that.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(super) enum SafeCommand {
+    On,
+    Off { confirmed: bool },
+    Status,
+}
+
+pub(super) fn parse_safe_command(trimmed: &str) -> Option<SafeCommand> {
+    let rest = trimmed.strip_prefix("/safe")?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let words: Vec<&str> = rest.split_whitespace().collect();
+    Some(match words.as_slice() {
+        [] | ["status"] => SafeCommand::Status,
+        ["on"] => SafeCommand::On,
+        ["off"] => SafeCommand::Off { confirmed: false },
+        ["off", "confirm"] => SafeCommand::Off { confirmed: true },
+        _ => return None,
+    })
+}
+
+pub(super) const SAFE_OFF_CONFIRM_HINT: &str =
+    "Safe mode stays on. Type /safe off confirm to allow tools to run without approval.";
+
+impl App {
+    pub(super) fn safe_status_message(&self) -> String {
+        if self.session.safe_mode {
+            "Safe mode: on\n\
+             Tool calls that can change files, run commands or reach the network ask for approval (reply y or n).\n\
+             Bash runs in a read-only sandbox where bwrap or sandbox-exec is available.\n\
+             Memory writes and ambient scheduling are off, and each request is capped at 25 model calls.\n\
+             Use /safe off confirm to turn it off."
+                .to_string()
+        } else {
+            "Safe mode: off\nUse /safe on (or start with jcode --safe) for approvals, sandboxed bash, no memory writes and a turn cap."
+                .to_string()
+        }
+    }
+
+    pub(super) fn note_safe_mode_changed(&mut self, enabled: bool) {
+        self.session.safe_mode = enabled;
+        if !enabled {
+            self.pending_safe_mode_approval = None;
+        }
+        let message = if enabled {
+            "Safe mode on: risky tool calls need your approval"
+        } else {
+            "Safe mode off"
+        };
+        self.set_status_notice(message);
+        self.push_display_message(DisplayMessage::system(message));
+    }
+
+    /// Show a tool approval request from a safe mode session and remember it
+    /// so the next `y` or `n` answers it.
+    pub(super) fn note_safe_mode_approval_request(&mut self, request_id: String, prompt: &str) {
+        self.push_display_message(DisplayMessage::system(format!(
+            "Safe mode: allow {}?\nReply y to allow or n to deny.",
+            prompt
+        )));
+        self.set_status_notice("Safe mode: approval needed (y/n)");
+        self.pending_safe_mode_approval = Some(request_id);
+    }
+
+    /// Ask the user to allow the `clipboard` tool to read the clipboard for
+    /// this session. Answered like a safe mode approval.
+    pub(super) fn note_clipboard_read_approval_request(&mut self, request_id: String) {
+        self.push_display_message(DisplayMessage::system(
+            "The agent wants to read your clipboard, which may contain passwords or tokens.\nReply y to allow it for this session or n to deny.",
+        ));
+        self.set_status_notice("Clipboard read: approval needed (y/n)");
+        self.pending_safe_mode_approval = Some(request_id);
+    }
+
+    /// If a safe mode approval is pending and `input` answers it, take the
+    /// request id along with the answer to send back.
+    pub(super) fn take_safe_mode_approval_answer(
+        &mut self,
+        input: &str,
+    ) -> Option<(String, &'static str)> {
+        let answer = match input.trim().to_ascii_lowercase().as_str() {
+            "y" | "yes" => "y",
+            "n" | "no" => "n",
+            _ => return None,
+        };
+        let request_id = self.pending_safe_mode_approval.take()?;
+        let clipboard =
+            request_id.starts_with(crate::tool::clipboard::CLIPBOARD_READ_APPROVAL_PREFIX);
+        self.set_status_notice(match (clipboard, answer == "y") {
+            (false, true) => "Safe mode: allowed",
+            (false, false) => "Safe mode: denied",
+            (true, true) => "Clipboard read: allowed for this session",
+            (true, false) => "Clipboard read: denied",
+        });
+        Some((request_id, answer))
+    }
+}
+
+pub(super) fn handle_safe_command_local(app: &mut App, command: SafeCommand) {
+    match command {
+        SafeCommand::Status => {
+            app.push_display_message(DisplayMessage::system(app.safe_status_message()));
+        }
+        SafeCommand::Off { confirmed: false } => {
+            app.push_display_message(DisplayMessage::system(SAFE_OFF_CONFIRM_HINT));
+        }
+        SafeCommand::On | SafeCommand::Off { confirmed: true } => {
+            app.note_safe_mode_changed(command == SafeCommand::On);
+            let _ = app.session.save();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_safe_requires_confirm_to_turn_off() {
+        assert_eq!(parse_safe_command("/safe"), Some(SafeCommand::Status));
+        assert_eq!(parse_safe_command("/safe on"), Some(SafeCommand::On));
+        assert_eq!(
+            parse_safe_command("/safe off"),
+            Some(SafeCommand::Off { confirmed: false })
+        );
+        assert_eq!(
+            parse_safe_command("/safe  off confirm"),
+            Some(SafeCommand::Off { confirmed: true })
+        );
+        assert_eq!(parse_safe_command("/safe maybe"), None);
+        assert_eq!(parse_safe_command("/safety"), None);
+    }
+}
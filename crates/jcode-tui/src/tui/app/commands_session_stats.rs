@@ -0,0 +1,164 @@
+use super::commands::{active_session_id, parse_on_off_value};
+use super::helpers::format_tokens;
+use super::{App, DisplayMessage};
+use crate::message::Message;
+use crate::session::{ModelUsageStats, Session};
+use crate::usage::{OriginUsage, UsageOrigin};
+use std::collections::BTreeMap;
+
+/// `/session stats` and `/session footers [status|on|off]`.
+pub(super) fn handle_session_stats_command(app: &mut App, trimmed: &str) -> bool {
+    if trimmed == "/session stats" {
+        show_session_stats(app);
+        return true;
+    }
+
+    let Some(rest) = trimmed.strip_prefix("/session footers") else {
+        return false;
+    };
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return false;
+    }
+    let rest = rest.trim();
+
+    if rest.is_empty() || matches!(rest, "show" | "status") {
+        let current = crate::config::config().display.message_footers;
+        app.push_display_message(DisplayMessage::system(format!(
+            "Message footers are currently {}.\n\nWhen on, assistant messages restored from session history show a dim line with the model, provider, duration and token usage.\n\nUse /session footers on or /session footers off to change it.",
+            if current { "on" } else { "off" }
+        )));
+        return true;
+    }
+
+    let Some(enabled) = parse_on_off_value(rest) else {
+        app.push_display_message(DisplayMessage::error(
+            "Usage: /session footers (show), /session footers on, or /session footers off"
+                .to_string(),
+        ));
+        return true;
+    };
+
+    app.set_status_notice(format!(
+        "Message footers: {}",
+        if enabled { "on" } else { "off" }
+    ));
+    match crate::config::Config::set_message_footers(enabled) {
+        Ok(()) => app.push_display_message(DisplayMessage::system(format!(
+            "Saved message footers: {}. Applies the next time session history loads (/resume or reload).",
+            if enabled { "on" } else { "off" }
+        ))),
+        Err(error) => app.push_display_message(DisplayMessage::error(format!(
+            "Failed to save message footers {}: {}",
+            if enabled { "on" } else { "off" },
+            error
+        ))),
+    }
+
+    true
+}
+
+fn show_session_stats(app: &mut App) {
+    let session_id = active_session_id(app);
+    let (stats, origins) = if !app.is_remote && app.session.id == session_id {
+        app.session.apply_pending_origin_usage();
+        (
+            app.session.model_usage_stats(),
+            app.session.origin_usage_stats(),
+        )
+    } else {
+        match Session::load(&session_id) {
+            Ok(session) => (session.model_usage_stats(), session.origin_usage_stats()),
+            Err(error) => {
+                app.push_display_message(DisplayMessage::error(format!(
+                    "Failed to load session {} for stats: {}",
+                    session_id, error
+                )));
+                return;
+            }
+        }
+    };
+
+    let mut text = format_session_stats(&stats);
+    if let Some(origins) = format_origin_stats(&origins) {
+        text.push_str("\n\n");
+        text.push_str(&origins);
+    }
+    app.push_display_message(DisplayMessage::system(text));
+    app.set_status_notice("Session stats");
+}
+
+/// The per-origin table, or `None` when the session has no overhead usage.
+pub(super) fn format_origin_stats(origins: &BTreeMap<String, OriginUsage>) -> Option<String> {
+    let overhead = origins
+        .iter()
+        .filter(|(label, _)| label.as_str() != UsageOrigin::INTERACTIVE)
+        .fold(OriginUsage::default(), |mut total, (_, usage)| {
+            total.add(*usage);
+            total
+        });
+    if overhead.calls == 0 {
+        return None;
+    }
+
+    let row = |usage: &OriginUsage| {
+        format!(
+            "{} call{} · ↑{} ↓{}",
+            usage.calls,
+            if usage.calls == 1 { "" } else { "s" },
+            format_tokens(usage.input_tokens),
+            format_tokens(usage.output_tokens)
+        )
+    };
+    let mut out = String::from("**Session stats by origin**\n");
+    if let Some(interactive) = origins.get(UsageOrigin::INTERACTIVE) {
+        out.push_str(&format!("\n- interactive: {}", row(interactive)));
+    }
+    for (label, usage) in origins
+        .iter()
+        .filter(|(label, _)| label.as_str() != UsageOrigin::INTERACTIVE)
+    {
+        out.push_str(&format!("\n- {}: {}", label, row(usage)));
+    }
+    out.push_str(&format!("\n- overhead total: {}", row(&overhead)));
+    Some(out)
+}
+
+pub(super) fn format_session_stats(stats: &[ModelUsageStats]) -> String {
+    if stats.is_empty() {
+        return "No assistant responses with recorded usage in this session yet.".to_string();
+    }
+
+    let mut out = String::from("**Session stats by model**\n");
+    for entry in stats {
+        let name = match (entry.model.as_deref(), entry.provider.as_deref()) {
+            (Some(model), Some(provider)) => format!("{} ({})", model, provider),
+            (Some(model), None) => model.to_string(),
+            (None, _) => "unattributed (recorded before model tracking)".to_string(),
+        };
+        let mut parts = vec![format!(
+            "{} response{}",
+            entry.responses,
+            if entry.responses == 1 { "" } else { "s" }
+        )];
+        parts.push(format!(
+            "↑{} ↓{}",
+            format_tokens(entry.input_tokens),
+            format_tokens(entry.output_tokens)
+        ));
+        if entry.cache_read_input_tokens > 0 {
+            parts.push(format!(
+                "cache {}",
+                format_tokens(entry.cache_read_input_tokens)
+            ));
+        }
+        if entry.timed_responses > 0 {
+            parts.push(format!(
+                "{} total, {} avg",
+                Message::format_duration(entry.duration_ms),
+                Message::format_duration(entry.duration_ms / entry.timed_responses as u64)
+            ));
+        }
+        out.push_str(&format!("\n- {}: {}", name, parts.join(" · ")));
+    }
+    out
+}
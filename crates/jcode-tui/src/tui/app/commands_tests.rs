@@ -1,3 +1,4 @@
+use super::parse_cd_command;
 use super::parse_diff_mode_name;
 use super::parse_manual_subagent_spec;
 
@@ -37,6 +38,17 @@ fn parse_diff_mode_name_rejects_unknown() {
     assert_eq!(parse_diff_mode_name(""), None);
 }
 
+#[test]
+fn parse_cd_command_reads_path_previous_and_reset() {
+    assert_eq!(parse_cd_command("/cd"), Some(None));
+    assert_eq!(
+        parse_cd_command("/cd packages/web"),
+        Some(Some("packages/web".to_string()))
+    );
+    assert_eq!(parse_cd_command("/cd -"), Some(Some("-".to_string())));
+    assert_eq!(parse_cd_command("/cdx"), None);
+}
+
 #[test]
 fn parse_manual_subagent_spec_accepts_flags_and_prompt() {
     let spec = parse_manual_subagent_spec(
@@ -0,0 +1,312 @@
+//! `/compare <model-a> <model-b> <prompt>`: ask two models the same thing and
+//! keep the better answer.
+//!
+//! The runs share the session's history and system prompt but get no tools.
+//! Remote clients ask the server to run them; local mode runs them here and
+//! reports back over the Bus. The answers open in a side-by-side overlay;
+//! keeping one adds the prompt and that answer to the conversation, and the
+//! session stores the others as alternatives.
+
+use super::*;
+use crate::bus::{Bus, BusEvent, CompareFinished};
+use crate::protocol::CompareRun;
+use crate::tui::CompareView;
+
+const USAGE: &str = "Usage: `/compare <model-a> <model-b> <prompt>` runs the prompt against both models without tools and lets you keep one answer.";
+const SCROLL_PAGE: usize = 20;
+
+/// A parsed `/compare` command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct CompareCommand {
+    pub(super) models: Vec<String>,
+    pub(super) prompt: String,
+}
+
+/// What a key press did while the compare overlay was open.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum CompareKey {
+    Handled,
+    /// Close the overlay without keeping anything.
+    Discard,
+    Keep {
+        prompt: String,
+        chosen: usize,
+        runs: Vec<CompareRun>,
+    },
+}
+
+pub(super) fn parse_compare_command(trimmed: &str) -> Option<Result<CompareCommand, String>> {
+    let rest = trimmed.strip_prefix("/compare")?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let mut words = rest.trim_start().splitn(3, char::is_whitespace);
+    let (Some(model_a), Some(model_b), Some(prompt)) = (words.next(), words.next(), words.next())
+    else {
+        return Some(Err(USAGE.to_string()));
+    };
+    let prompt = prompt.trim();
+    if model_a.is_empty() || model_b.is_empty() || prompt.is_empty() {
+        return Some(Err(USAGE.to_string()));
+    }
+    Some(Ok(CompareCommand {
+        models: vec![model_a.to_string(), model_b.to_string()],
+        prompt: prompt.to_string(),
+    }))
+}
+
+pub(super) fn compare_key(view: &mut CompareView, code: KeyCode) -> CompareKey {
+    let last = view.runs.len().saturating_sub(1);
+    match code {
+        KeyCode::Esc | KeyCode::Char('q') => return CompareKey::Discard,
+        KeyCode::Enter => {
+            return CompareKey::Keep {
+                prompt: view.prompt.clone(),
+                chosen: view.selected,
+                runs: view.runs.clone(),
+            };
+        }
+        KeyCode::Left | KeyCode::Char('h') | KeyCode::BackTab => {
+            view.selected = view.selected.saturating_sub(1);
+        }
+        KeyCode::Right | KeyCode::Char('l') | KeyCode::Tab => {
+            view.selected = (view.selected + 1).min(last);
+        }
+        KeyCode::Char(digit @ '1'..='9') => {
+            let index = digit as usize - '1' as usize;
+            if index <= last {
+                view.selected = index;
+            }
+        }
+        KeyCode::Down | KeyCode::Char('j') => view.scroll = view.scroll.saturating_add(1),
+        KeyCode::Up | KeyCode::Char('k') => view.scroll = view.scroll.saturating_sub(1),
+        KeyCode::PageDown | KeyCode::Char(' ') => {
+            view.scroll = view.scroll.saturating_add(SCROLL_PAGE);
+        }
+        KeyCode::PageUp => view.scroll = view.scroll.saturating_sub(SCROLL_PAGE),
+        KeyCode::Home | KeyCode::Char('g') => view.scroll = 0,
+        _ => {}
+    }
+    CompareKey::Handled
+}
+
+impl App {
+    /// Refuse a new comparison while a turn or another comparison runs.
+    pub(super) fn compare_blocked(&mut self) -> bool {
+        let notice = if self.is_processing {
+            "Finish or interrupt the current turn before comparing models."
+        } else if self.compare_in_flight.is_some() || self.compare_view.is_some() {
+            "A comparison is already open."
+        } else {
+            return false;
+        };
+        self.push_display_message(DisplayMessage::error(notice));
+        true
+    }
+
+    pub(super) fn note_compare_started(&mut self, command: &CompareCommand) {
+        self.compare_in_flight = Some(command.prompt.clone());
+        self.set_status_notice(format!(
+            "Compare → waiting for {}…",
+            command.models.join(" and ")
+        ));
+    }
+
+    /// Open the overlay with the answers, or report why there are none.
+    pub(super) fn handle_compare_result(
+        &mut self,
+        prompt: String,
+        runs: Vec<CompareRun>,
+        error: Option<String>,
+    ) {
+        if self.compare_in_flight.take().as_deref() != Some(prompt.as_str()) {
+            return;
+        }
+        if let Some(error) = error {
+            self.push_display_message(DisplayMessage::error(format!("Compare: {}", error)));
+            return;
+        }
+        if runs.iter().all(|run| run.error.is_some()) {
+            let failures: Vec<String> = runs
+                .iter()
+                .map(|run| format!("{}: {}", run.model, run.error.as_deref().unwrap_or("")))
+                .collect();
+            self.push_display_message(DisplayMessage::error(format!(
+                "Compare: every model failed\n{}",
+                failures.join("\n")
+            )));
+            return;
+        }
+        let selected = runs.iter().position(|run| run.error.is_none()).unwrap_or(0);
+        self.compare_view = Some(CompareView {
+            prompt,
+            runs,
+            selected,
+            scroll: 0,
+        });
+        self.set_status_notice("Compare → pick an answer to keep");
+    }
+
+    /// Key handling while the overlay is open. Returns the answer to keep,
+    /// if the user picked one.
+    pub(super) fn handle_compare_view_key(&mut self, code: KeyCode) -> Option<CompareKey> {
+        let view = self.compare_view.as_mut()?;
+        match compare_key(view, code) {
+            CompareKey::Handled => Some(CompareKey::Handled),
+            CompareKey::Discard => {
+                self.compare_view = None;
+                self.set_status_notice("Compare → discarded");
+                Some(CompareKey::Handled)
+            }
+            CompareKey::Keep { chosen, runs, .. } if runs[chosen].error.is_some() => {
+                self.set_status_notice(format!("{} has no answer to keep", runs[chosen].model));
+                Some(CompareKey::Handled)
+            }
+            keep => {
+                self.compare_view = None;
+                Some(keep)
+            }
+        }
+    }
+
+    /// Show the kept exchange in the transcript.
+    pub(super) fn note_compare_kept(&mut self, prompt: &str, chosen: usize, runs: &[CompareRun]) {
+        let kept = &runs[chosen];
+        self.push_display_message(DisplayMessage::user(prompt.to_string()));
+        self.push_display_message(DisplayMessage::assistant(kept.text.clone()));
+        let others = runs.len() - 1;
+        self.set_status_notice(format!(
+            "Compare → kept {}; {} alternative{} saved",
+            kept.model,
+            others,
+            if others == 1 { "" } else { "s" }
+        ));
+    }
+
+    /// Local mode: run the comparison against forks of this client's provider.
+    fn start_compare_locally(&mut self, command: CompareCommand) {
+        let (mut messages, compaction_event) = self.messages_for_provider();
+        if let Some(event) = compaction_event {
+            self.handle_compaction_event(event);
+        }
+        messages.push(Message::user(&command.prompt));
+        let request = crate::agent::CompareRequest::new(
+            self.provider.clone(),
+            command.models.clone(),
+            messages,
+            self.build_system_prompt_split(None),
+        );
+        self.note_compare_started(&command);
+        let session_id = self.session.id.clone();
+        let prompt = command.prompt;
+        tokio::spawn(async move {
+            let runs = request.run().await;
+            Bus::global().publish(BusEvent::CompareFinished(CompareFinished {
+                session_id,
+                prompt,
+                runs,
+            }));
+        });
+    }
+
+    pub(super) fn handle_compare_finished(&mut self, event: CompareFinished) {
+        if event.session_id == self.session.id {
+            self.handle_compare_result(event.prompt, event.runs, None);
+        }
+    }
+
+    /// Local mode: add the exchange to the session and keep the other answers
+    /// as alternatives.
+    pub(super) fn keep_compare_locally(
+        &mut self,
+        prompt: String,
+        chosen: usize,
+        mut runs: Vec<CompareRun>,
+    ) {
+        self.note_compare_kept(&prompt, chosen, &runs);
+        let kept = runs.remove(chosen);
+        let user_blocks = vec![ContentBlock::Text {
+            text: prompt.clone(),
+            cache_control: None,
+        }];
+        let assistant_blocks = vec![ContentBlock::Text {
+            text: kept.text.clone(),
+            cache_control: None,
+        }];
+        self.add_provider_message(Message::user(&prompt));
+        self.session.add_message(Role::User, user_blocks);
+        self.add_provider_message(Message::assistant_text(&kept.text));
+        let message_id = self.session.add_message(Role::Assistant, assistant_blocks);
+        self.session.record_alternatives(&message_id, runs);
+        let _ = self.session.save();
+    }
+}
+
+pub(super) fn handle_compare_command_local(app: &mut App, command: Result<CompareCommand, String>) {
+    match command {
+        Err(usage) => app.push_display_message(DisplayMessage::error(usage)),
+        Ok(_) if app.compare_blocked() => {}
+        Ok(command) => app.start_compare_locally(command),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(model: &str, error: Option<&str>) -> CompareRun {
+        CompareRun {
+            model: model.to_string(),
+            text: format!("answer from {}", model),
+            input_tokens: None,
+            output_tokens: None,
+            latency_ms: 1_000,
+            error: error.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn parse_takes_two_models_and_the_rest_as_prompt() {
+        assert_eq!(
+            parse_compare_command("/compare opus gpt-5.4  why does this  deadlock? "),
+            Some(Ok(CompareCommand {
+                models: vec!["opus".to_string(), "gpt-5.4".to_string()],
+                prompt: "why does this  deadlock?".to_string(),
+            }))
+        );
+        assert!(matches!(
+            parse_compare_command("/compare opus gpt-5.4"),
+            Some(Err(_))
+        ));
+        assert!(matches!(parse_compare_command("/compare"), Some(Err(_))));
+        assert_eq!(parse_compare_command("/compares a b c"), None);
+    }
+
+    #[test]
+    fn keys_pick_a_column_and_keep_it() {
+        let mut view = CompareView {
+            prompt: "q".to_string(),
+            runs: vec![run("a", None), run("b", None)],
+            selected: 0,
+            scroll: 0,
+        };
+        assert_eq!(compare_key(&mut view, KeyCode::Right), CompareKey::Handled);
+        assert_eq!(view.selected, 1);
+        compare_key(&mut view, KeyCode::Right);
+        assert_eq!(view.selected, 1, "selection stops at the last column");
+        compare_key(&mut view, KeyCode::Char('1'));
+        compare_key(&mut view, KeyCode::Char('9'));
+        assert_eq!(view.selected, 0);
+        compare_key(&mut view, KeyCode::Char('j'));
+        assert_eq!(view.scroll, 1);
+        assert_eq!(compare_key(&mut view, KeyCode::Esc), CompareKey::Discard);
+        assert_eq!(
+            compare_key(&mut view, KeyCode::Enter),
+            CompareKey::Keep {
+                prompt: "q".to_string(),
+                chosen: 0,
+                runs: view.runs.clone(),
+            }
+        );
+    }
+}
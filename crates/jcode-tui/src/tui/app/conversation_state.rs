@@ -98,8 +98,11 @@ impl App {
         event: &crate::compaction::CompactionEvent,
         context_limit: u64,
     ) -> String {
-        let mut message =
-            "📦 Emergency compaction - older messages were dropped to recover from context pressure. Recent context was kept.".to_string();
+        let mut message = if event.trigger == "auto_recovery" {
+            "📦 Context window exceeded - older context was trimmed and the same message was retried.".to_string()
+        } else {
+            "📦 Emergency compaction - older messages were dropped to recover from context pressure. Recent context was kept.".to_string()
+        };
         let details = Self::format_compaction_detail_segments(event, context_limit, true);
         if !details.is_empty() {
             message.push_str("\n\n");
@@ -156,6 +159,19 @@ impl App {
             ));
         }
 
+        if let Some(trimmed) = event.tool_results_trimmed {
+            let noun = if trimmed == 1 {
+                "tool result"
+            } else {
+                "tool results"
+            };
+            details.push(format!(
+                "shortened {} old {}",
+                Self::format_compaction_number(trimmed as u64),
+                noun
+            ));
+        }
+
         if let Some(summary_chars) = event.summary_chars.filter(|chars| *chars > 0) {
             details.push(format!(
                 "summary {} chars",
@@ -387,7 +403,7 @@ impl App {
                 self.session.id, err
             ));
         }
-        let message = if event.messages_dropped.is_some() {
+        let message = if event.messages_dropped.is_some() || event.tool_results_trimmed.is_some() {
             self.set_status_notice("Emergency compaction");
             Self::format_emergency_compaction_message(&event, self.context_limit)
         } else {
@@ -401,6 +417,16 @@ impl App {
         self.status_notice = Some((text.into(), Instant::now()));
     }
 
+    /// Select a `[profiles.<name>]` preset on the server after connecting.
+    pub fn set_startup_profile(&mut self, profile: impl Into<String>) {
+        self.pending_startup_profile = Some(profile.into());
+    }
+
+    /// Turn on safe mode on the server after connecting.
+    pub fn set_startup_safe_mode(&mut self) {
+        self.pending_startup_safe_mode = true;
+    }
+
     /// Stash a persistent startup notice card and show it immediately.
     ///
     /// The card is also re-applied once the remote History bootstrap clears the
@@ -679,8 +679,8 @@ impl App {
             let raw = cmd.strip_prefix("inject:").unwrap_or("");
             let (role, content) = if let Some((r, c)) = raw.split_once(':') {
                 let role = match r {
-                    "user" | "assistant" | "system" | "background_task" | "tool" | "error"
-                    | "meta" => r,
+                    "user" | "assistant" | "system" | "background_task" | "interjection"
+                    | "tool" | "error" | "meta" => r,
                     _ => "assistant",
                 };
                 if role == "assistant" && r != "assistant" {
@@ -923,7 +923,7 @@ impl App {
         }
     }
 
-    /// Check for new stable version and trigger migration if at safe point
+    /// Check for a new stable version and announce the migration onto it
     pub(in crate::tui::app) fn check_stable_version(&mut self) -> bool {
         // Only check every 5 seconds to avoid excessive file reads
         let should_check = self
@@ -959,51 +959,54 @@ impl App {
             return false;
         }
 
-        // New stable version detected
+        // New stable version detected: warn ahead of the migration, which
+        // runs at the first safe point once the grace period ends
         self.known_stable_version = Some(current_stable.clone());
-
-        // Check if we're at a safe point to migrate
-        let at_safe_point = !self.is_processing && self.queued_messages.is_empty();
-
-        if at_safe_point {
-            // Trigger migration
-            self.pending_migration = Some(current_stable);
-            return true;
-        }
-
-        false
+        let due_ms =
+            super::commands_migrate::now_ms() + self.migration_grace_secs.saturating_mul(1000);
+        self.announce_migration(super::commands_migrate::PendingMigration::new(
+            super::commands_migrate::MigrationTarget::Stable,
+            jcode_build_meta::GIT_HASH,
+            current_stable,
+            "a new stable build was promoted",
+            due_ms,
+        ));
+        true
     }
 
-    /// Execute pending migration to new stable version
-    pub(in crate::tui::app) fn execute_migration(&mut self) -> bool {
-        if let Some(ref version) = self.pending_migration.take() {
-            let stable_binary = match crate::build::stable_binary_path() {
-                Ok(p) if p.exists() => p,
-                _ => return false,
-            };
+    /// Execute a due migration to the new stable version
+    pub(in crate::tui::app) fn execute_migration(
+        &mut self,
+        migration: super::commands_migrate::PendingMigration,
+    ) -> bool {
+        let version = &migration.to_hash;
+        let stable_binary = match crate::build::stable_binary_path() {
+            Ok(p) if p.exists() => p,
+            _ => return false,
+        };
 
-            // Save session before migration
-            if let Err(e) = self.session.save() {
-                let msg = format!("Failed to save session before migration: {}", e);
-                crate::logging::error(&msg);
-                self.push_display_message(DisplayMessage::error(msg));
-                self.set_status_notice("Migration aborted");
-                return false;
-            }
+        // Record the version boundary and save the session before migration
+        self.session
+            .record_migration(&migration.from_hash, version, &migration.reason);
+        if let Err(e) = self.session.save() {
+            let msg = format!("Failed to save session before migration: {}", e);
+            crate::logging::error(&msg);
+            self.push_display_message(DisplayMessage::error(msg));
+            self.set_status_notice("Migration aborted");
+            return false;
+        }
 
-            // Request reload to stable version
-            self.save_input_for_reload(&self.session.id.clone());
-            self.reload_requested = Some(self.session.id.clone());
+        // Request reload to stable version
+        self.save_input_for_reload(&self.session.id.clone());
+        self.reload_requested = Some(self.session.id.clone());
 
-            // The actual exec happens in main.rs when run() returns
-            // We store the binary path in an env var for the reload handler
-            crate::env::set_var("JCODE_MIGRATE_BINARY", stable_binary);
+        // The actual exec happens in main.rs when run() returns
+        // We store the binary path in an env var for the reload handler
+        crate::env::set_var("JCODE_MIGRATE_BINARY", stable_binary);
 
-            crate::logging::info(&format!("Migrating to stable version {}...", version));
-            self.set_status_notice(format!("Migrating to stable {}...", version));
-            self.should_quit = true;
-            return true;
-        }
-        false
+        crate::logging::info(&format!("Migrating to stable version {}...", version));
+        self.set_status_notice(format!("Migrating to stable {}...", version));
+        self.should_quit = true;
+        true
     }
 }
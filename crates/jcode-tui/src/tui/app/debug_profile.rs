@@ -211,6 +211,7 @@ impl App {
             .iter()
             .map(|(text, _)| text.capacity())
             .sum();
+        let kill_ring_bytes = self.kill_ring.heap_bytes();
         let stashed_input_bytes = self
             .stashed_input
             .as_ref()
@@ -405,7 +406,9 @@ impl App {
             + self
                 .pending_migration
                 .as_ref()
-                .map(|value| value.capacity())
+                .map(|value| {
+                    value.from_hash.capacity() + value.to_hash.capacity() + value.reason.capacity()
+                })
                 .unwrap_or(0)
             + self
                 .resume_session_id
@@ -420,6 +423,7 @@ impl App {
             "in_flight_catchup_resume_bytes": in_flight_catchup_resume_bytes,
             "input_undo_stack_bytes": input_undo_stack_bytes,
             "stashed_input_bytes": stashed_input_bytes,
+            "kill_ring_bytes": kill_ring_bytes,
             "pending_soft_interrupts_bytes": pending_soft_interrupts_bytes,
             "pending_soft_interrupt_requests_bytes": pending_soft_interrupt_requests_bytes,
             "reload_info_bytes": reload_info_bytes,
@@ -469,6 +473,8 @@ impl App {
                 "undo_entries": self.input_undo_stack.len(),
                 "undo_stack_bytes": input_undo_stack_bytes,
                 "stashed_input_bytes": stashed_input_bytes,
+                "kill_ring_entries": self.kill_ring.len(),
+                "kill_ring_bytes": kill_ring_bytes,
             },
             "remote_state_extra": {
                 "remote_sessions_count": self.remote_sessions.len(),
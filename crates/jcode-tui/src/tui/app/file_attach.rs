@@ -0,0 +1,101 @@
+use super::*;
+use crate::tui::file_picker::{FilePicker, OverlayAction};
+
+impl App {
+    /// Open the workspace file picker over the session working directory. The
+    /// index is fetched off the UI thread and picked up by
+    /// [`Self::poll_file_picker_load`].
+    pub(super) fn open_file_picker(&mut self) {
+        let root = super::commands::active_working_dir(self)
+            .filter(|dir| dir.is_dir())
+            .or_else(|| std::env::current_dir().ok());
+        let Some(root) = root else {
+            self.set_status_notice("No working directory to browse");
+            return;
+        };
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let index_root = root.clone();
+        input::spawn_blocking_or_thread(move || {
+            let _ = tx.send(crate::file_index::get(&index_root, true));
+        });
+        self.file_picker_overlay = Some(RefCell::new(FilePicker::new(
+            root,
+            self.file_picker_mode,
+            rx,
+        )));
+    }
+
+    pub(super) fn poll_file_picker_load(&mut self) -> bool {
+        self.file_picker_overlay
+            .as_ref()
+            .is_some_and(|picker| picker.borrow_mut().poll_index())
+    }
+
+    pub(crate) fn handle_file_picker_key(
+        &mut self,
+        code: KeyCode,
+        modifiers: KeyModifiers,
+    ) -> anyhow::Result<()> {
+        let action = {
+            let Some(picker_cell) = self.file_picker_overlay.as_ref() else {
+                return Ok(());
+            };
+            let mut picker = picker_cell.borrow_mut();
+            let action = picker.handle_overlay_key(code, modifiers)?;
+            self.file_picker_mode = picker.mode();
+            action
+        };
+
+        match action {
+            OverlayAction::Continue => {}
+            OverlayAction::Close => {
+                self.file_picker_overlay = None;
+            }
+            OverlayAction::Attach(blocks) => {
+                self.file_picker_overlay = None;
+                let count = blocks.len();
+                let placeholders: Vec<String> = blocks
+                    .iter()
+                    .map(|block| input::paste_placeholder(block))
+                    .collect();
+                self.pasted_contents.extend(blocks);
+                self.insert_picked_text(&placeholders.join(" "));
+                self.set_status_notice(format!(
+                    "Attached {} item{}",
+                    count,
+                    if count == 1 { "" } else { "s" }
+                ));
+            }
+            OverlayAction::Mention(paths) => {
+                self.file_picker_overlay = None;
+                let mentions: Vec<String> = paths.iter().map(|path| mention_token(path)).collect();
+                self.insert_picked_text(&mentions.join(" "));
+            }
+        }
+        Ok(())
+    }
+
+    /// Insert picker output at the cursor as its own word.
+    fn insert_picked_text(&mut self, text: &str) {
+        let needs_space = self.input[..self.cursor_pos]
+            .chars()
+            .next_back()
+            .is_some_and(|c| !c.is_whitespace());
+        let text = if needs_space {
+            format!(" {} ", text)
+        } else {
+            format!("{} ", text)
+        };
+        input::insert_input_text(self, &text);
+    }
+}
+
+/// `@path`, quoted when the path contains whitespace.
+fn mention_token(path: &str) -> String {
+    if path.chars().any(char::is_whitespace) {
+        format!("@\"{}\"", path)
+    } else {
+        format!("@{}", path)
+    }
+}
@@ -0,0 +1,121 @@
+//! `/view`: the file view split (`display.diff_mode = "file"`).
+//!
+//! The right pane follows the most recently edited file with its latest diff
+//! highlighted, or shows a file pinned with `/view <path>`. Pinning only lasts
+//! for the session; `/view save` writes the split itself to config.toml.
+
+use super::{App, DisplayMessage};
+use crate::config::DiffDisplayMode;
+use std::path::PathBuf;
+
+const USAGE: &str = "Usage: /view [<path>|follow|off|save|status]";
+
+impl App {
+    pub(super) fn set_file_view_pin(&mut self, pin: Option<String>) {
+        self.file_view_pin = pin;
+        self.diff_mode = DiffDisplayMode::File;
+        self.diff_pane_scroll = usize::MAX;
+        self.diff_pane_auto_scroll = true;
+    }
+
+    pub(super) fn adjust_side_pane_ratio(&mut self, delta: i8) {
+        let next = self.diagram_pane_ratio_target as i16 + delta as i16;
+        self.diagram_pane_ratio_user_adjusted = true;
+        self.set_diagram_pane_ratio(next, true, false);
+        self.set_status_notice(format!("Side pane: {}%", self.diagram_pane_ratio_target));
+    }
+}
+
+fn file_view_status_message(app: &App) -> String {
+    let target = match app.file_view_pin.as_deref() {
+        Some(path) => format!("pinned to {}", path),
+        None => "following the most recently edited file".to_string(),
+    };
+    format!(
+        "File view: {}\nPane: {}, {}% of the chat width\n\n/view <path> pins a file, /view follow goes back to the latest edit, /view save keeps this layout in config.toml. With the pane focused, +/- resize it.",
+        if app.diff_mode.is_file() { "on" } else { "off" },
+        target,
+        app.diagram_pane_ratio_target,
+    )
+}
+
+/// Default diff mode to fall back to when the file view is turned off.
+fn non_file_diff_mode() -> DiffDisplayMode {
+    match crate::config::config().display.diff_mode {
+        DiffDisplayMode::File => DiffDisplayMode::Inline,
+        mode => mode,
+    }
+}
+
+fn resolve_view_path(app: &App, raw: &str) -> PathBuf {
+    let path = crate::output_tee::expand_home(raw);
+    if path.is_absolute() {
+        return path;
+    }
+    match super::commands::active_working_dir(app) {
+        Some(dir) => dir.join(path),
+        None => std::path::absolute(&path).unwrap_or(path),
+    }
+}
+
+fn save_file_view_layout(app: &mut App) {
+    let ratio = app.diagram_pane_ratio_target;
+    match crate::config::Config::set_display_layout(app.diff_mode, ratio) {
+        Ok(()) => {
+            app.set_status_notice("File view layout saved");
+            app.push_display_message(DisplayMessage::system(format!(
+                "Saved default layout: diffs {}, side pane {}%.",
+                app.diff_mode.label(),
+                ratio
+            )));
+        }
+        Err(error) => app.push_display_message(DisplayMessage::error(format!(
+            "Failed to save the file view layout: {}",
+            error
+        ))),
+    }
+}
+
+/// `/view [<path>|follow|off|save|status]`.
+pub(super) fn handle_view_command(app: &mut App, trimmed: &str) -> bool {
+    if trimmed != "/view" && !trimmed.starts_with("/view ") {
+        return false;
+    }
+    let arg = trimmed.strip_prefix("/view").unwrap_or_default().trim();
+    match arg {
+        "" | "status" => {
+            app.push_display_message(DisplayMessage::system(file_view_status_message(app)));
+        }
+        "follow" | "on" => {
+            app.set_file_view_pin(None);
+            app.set_status_notice("File view: following edits");
+        }
+        "off" => {
+            app.file_view_pin = None;
+            app.diff_mode = non_file_diff_mode();
+            if !app.diff_pane_visible() {
+                app.diff_pane_focus = false;
+            }
+            app.set_status_notice(format!("Diffs: {}", app.diff_mode.label()));
+        }
+        "save" => save_file_view_layout(app),
+        path if path.starts_with('-') => {
+            app.push_display_message(DisplayMessage::error(USAGE.to_string()));
+        }
+        path => {
+            let resolved = resolve_view_path(app, path);
+            if !resolved.is_file() {
+                app.push_display_message(DisplayMessage::error(format!(
+                    "Not a file: {}\n{}",
+                    resolved.display(),
+                    USAGE
+                )));
+                return true;
+            }
+            let label = resolved.display().to_string();
+            app.set_file_view_pin(Some(label.clone()));
+            app.set_status_notice(format!("File view: {}", label));
+        }
+    }
+    true
+}
@@ -0,0 +1,219 @@
+//! `/handoff`: continue in a fresh session that starts from an edited summary
+//! of this one.
+//!
+//! Remote clients ask the server to draft the summary and create the child
+//! session, then resume it. Local mode drafts here, reports back over the Bus
+//! and switches `app.session` in place. Either way the draft sits in the input
+//! box until the user submits or cancels it.
+
+use super::*;
+use crate::bus::{Bus, BusEvent, HandoffDrafted};
+
+const HANDOFF_BUSY_NOTICE: &str = "Finish or interrupt the current turn before handing off.";
+
+/// Where a `/handoff` is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum PendingHandoff {
+    /// Waiting for the summary draft.
+    Drafting,
+    /// The draft is in the input box; the next submit creates the session.
+    Editing,
+    /// Waiting for the server to create the session.
+    Creating,
+}
+
+pub(super) fn is_handoff_command(trimmed: &str) -> bool {
+    trimmed == "/handoff"
+}
+
+impl App {
+    /// Whether the next submit is the edited handoff summary rather than a
+    /// prompt.
+    pub(super) fn handoff_editing(&self) -> bool {
+        self.pending_handoff == Some(PendingHandoff::Editing)
+    }
+
+    /// Refuse a new handoff while a turn or another handoff is in progress.
+    pub(super) fn handoff_blocked(&mut self) -> bool {
+        match self.pending_handoff {
+            Some(PendingHandoff::Editing) => {
+                self.set_status_notice("Submit or /cancel the current handoff draft first");
+                return true;
+            }
+            Some(_) => {
+                self.set_status_notice("Handoff → still in progress…");
+                return true;
+            }
+            None => {}
+        }
+        if self.is_processing {
+            self.push_display_message(DisplayMessage::error(HANDOFF_BUSY_NOTICE));
+            return true;
+        }
+        false
+    }
+
+    pub(super) fn note_handoff_drafting(&mut self) {
+        self.pending_handoff = Some(PendingHandoff::Drafting);
+        self.set_status_notice("Handoff → drafting summary…");
+    }
+
+    /// Local mode: draft the summary with a fork of this client's provider.
+    pub(super) fn start_handoff_locally(&mut self) {
+        if self.handoff_blocked() {
+            return;
+        }
+        let request =
+            match crate::agent::HandoffSummaryRequest::new(self.provider.fork(), &self.session) {
+                Ok(request) => request,
+                Err(error) => {
+                    self.push_display_message(DisplayMessage::error(format!("Handoff: {}", error)));
+                    return;
+                }
+            };
+        self.note_handoff_drafting();
+        let session_id = self.session.id.clone();
+        tokio::spawn(async move {
+            let result = request
+                .run()
+                .await
+                .map_err(|error| crate::util::format_error_chain(&error));
+            Bus::global().publish(BusEvent::HandoffDrafted(HandoffDrafted {
+                session_id,
+                result,
+            }));
+        });
+    }
+
+    pub(super) fn handle_handoff_drafted(&mut self, event: HandoffDrafted) {
+        if event.session_id != self.session.id
+            || self.pending_handoff != Some(PendingHandoff::Drafting)
+        {
+            return;
+        }
+        match event.result {
+            Ok(summary) => self.open_handoff_draft(summary),
+            Err(error) => {
+                self.pending_handoff = None;
+                self.push_display_message(DisplayMessage::error(format!("Handoff: {}", error)));
+            }
+        }
+    }
+
+    /// Apply a `HandoffChanged` reply from the server.
+    pub(super) fn handle_handoff_changed(
+        &mut self,
+        summary: Option<String>,
+        new_session_id: Option<String>,
+        new_session_name: Option<String>,
+        error: Option<String>,
+    ) {
+        if let Some(error) = error {
+            self.pending_handoff = None;
+            self.push_display_message(DisplayMessage::error(format!("Handoff: {}", error)));
+            return;
+        }
+        match (summary, new_session_id) {
+            (Some(summary), _) if self.pending_handoff == Some(PendingHandoff::Drafting) => {
+                self.open_handoff_draft(summary);
+            }
+            (_, Some(session_id)) if self.pending_handoff == Some(PendingHandoff::Creating) => {
+                self.pending_handoff = None;
+                let name = new_session_name.unwrap_or_else(|| session_id.clone());
+                self.push_display_message(DisplayMessage::system(format!(
+                    "Handed off to {}. This session is closed with a note pointing there.",
+                    name
+                )));
+                self.workspace_client.queue_resume_session(session_id);
+                self.set_status_notice(format!("Switching → {}", name));
+            }
+            _ => {}
+        }
+    }
+
+    fn open_handoff_draft(&mut self, summary: String) {
+        if !self.input.is_empty() {
+            if self.stashed_input.is_some() {
+                self.pending_handoff = None;
+                self.push_display_message(DisplayMessage::error(
+                    "Handoff cancelled: the input box and stash are both in use. Clear one and run /handoff again.",
+                ));
+                return;
+            }
+            let input = std::mem::take(&mut self.input);
+            self.stashed_input = Some((input, self.cursor_pos));
+        }
+        self.pending_handoff = Some(PendingHandoff::Editing);
+        self.input = summary;
+        self.cursor_pos = self.input.len();
+        self.clear_input_undo_history();
+        self.push_display_message(DisplayMessage::system(
+            "Edit the handoff summary in the input box. Press Enter to continue in a new session that starts from it, or submit it empty or `/cancel` to stay here.",
+        ));
+        self.set_status_notice("Handoff → edit summary and press Enter");
+    }
+
+    /// Take the submitted summary while [`Self::handoff_editing`] is true.
+    /// Returns `None` when the user cancelled.
+    pub(super) fn take_handoff_summary(&mut self, input: String) -> Option<String> {
+        self.pending_handoff = None;
+        let input = input.trim();
+        if input.is_empty() || input == "/cancel" {
+            self.push_display_message(DisplayMessage::system(
+                "Handoff cancelled; staying in this session.",
+            ));
+            self.set_status_notice("Handoff: cancelled");
+            return None;
+        }
+        Some(input.to_string())
+    }
+
+    /// Remote mode: the server creates the session; wait for its id.
+    pub(super) fn note_handoff_creating(&mut self) {
+        self.pending_handoff = Some(PendingHandoff::Creating);
+        self.set_status_notice("Handoff → creating session…");
+    }
+
+    /// Local mode: create the child session, close this one with a note
+    /// pointing at it, and continue there.
+    pub(super) fn finish_handoff_locally(&mut self, summary: String) {
+        let child = match crate::agent::create_handoff_session(&self.session, &summary) {
+            Ok(child) => child,
+            Err(error) => {
+                self.push_display_message(DisplayMessage::error(format!(
+                    "Handoff: {}",
+                    crate::util::format_error_chain(&error)
+                )));
+                return;
+            }
+        };
+        let parent_name = self.session.display_name().to_string();
+        self.session
+            .append_handoff_notice(&child.id, child.display_name());
+        let name = child.display_name().to_string();
+        commands::switch_current_session(self, child);
+        for message in jcode_tui_messages::display_messages_from_rendered_messages(
+            crate::session::render_messages(&self.session),
+            crate::config::config().display.message_footers,
+        ) {
+            self.push_display_message(message);
+        }
+        self.push_display_message(DisplayMessage::system(format!(
+            "Continuing in {} from the handoff summary of {}.",
+            name, parent_name
+        )));
+        self.set_status_notice(format!("Handoff → {}", name));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handoff_takes_no_arguments() {
+        assert!(is_handoff_command("/handoff"));
+        assert!(!is_handoff_command("/handoff now"));
+        assert!(!is_handoff_command("/handoffs"));
+    }
+}
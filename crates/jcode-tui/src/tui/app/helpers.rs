@@ -283,19 +283,7 @@ pub(super) fn is_context_limit_error(error: &str) -> bool {
     if crate::provider::openai_request::is_openai_encrypted_content_too_large_error(error) {
         return true;
     }
-    let lower = error.to_lowercase();
-    lower.contains("context length")
-        || lower.contains("context window")
-        || lower.contains("maximum context")
-        || lower.contains("max context")
-        || lower.contains("token limit")
-        || lower.contains("too many tokens")
-        || lower.contains("prompt is too long")
-        || lower.contains("input is too long")
-        || lower.contains("request too large")
-        || lower.contains("length limit")
-        || lower.contains("maximum tokens")
-        || (lower.contains("exceeded") && lower.contains("tokens"))
+    crate::compaction::is_context_overflow_error(error)
 }
 
 /// Whether `error` is a provider HTTP 413 "request too large" / payload-size
@@ -418,6 +406,29 @@ fn copy_to_clipboard_osc52(text: &str) -> bool {
     out.write_all(seq.as_bytes()).is_ok() && out.flush().is_ok()
 }
 
+/// Copy text the agent asked for with the `clipboard` tool: OSC 52 over SSH,
+/// where the local clipboard belongs to the wrong machine, and arboard
+/// otherwise, falling back to the usual chain.
+pub(super) fn copy_to_clipboard_for_tool(text: &str) -> bool {
+    if running_over_ssh() {
+        return copy_to_clipboard_osc52(text);
+    }
+    arboard::Clipboard::new()
+        .and_then(|mut cb| cb.set_text(text.to_string()))
+        .is_ok()
+        || copy_to_clipboard(text)
+}
+
+/// Copy through OSC 52 first so the text reaches the user's own terminal
+/// through SSH and tmux, then fall back to the local clipboard.
+pub(super) fn copy_to_clipboard_osc52_first(text: &str) -> bool {
+    copy_to_clipboard_osc52(text) || copy_to_clipboard(text)
+}
+
+fn running_over_ssh() -> bool {
+    std::env::var_os("SSH_CONNECTION").is_some() || std::env::var_os("SSH_TTY").is_some()
+}
+
 pub(super) fn effort_display_label(effort: &str) -> &str {
     match effort {
         "swarm" => "Swarm (light fan-out)",
@@ -98,6 +98,11 @@ pub(super) struct RegistryInputs<'a> {
     pub dictation: &'a OptionalBinding,
     pub new_terminal: &'a OptionalBinding,
     pub open_resume: &'a OptionalBinding,
+    pub interject: &'a OptionalBinding,
+    pub image_open: &'a OptionalBinding,
+    pub remember_message: &'a OptionalBinding,
+    pub copy_message: &'a OptionalBinding,
+    pub file_picker: &'a OptionalBinding,
     pub fallback_switch: &'a OptionalBinding,
     /// Workspace navigation only dispatches in remote/client mode.
     pub remote: bool,
@@ -145,6 +150,11 @@ pub(super) fn build_registry(inputs: &RegistryInputs<'_>) -> Vec<KnownHotkey> {
         "info_widget_toggle",
         "toggle the info widget",
     );
+    push(
+        inputs.toggles.todo_checklist.binding().cloned(),
+        "todo_checklist_toggle",
+        "collapse the todo checklist",
+    );
     push(
         inputs.toggles.swarm_panel_focus.binding().cloned(),
         "swarm_panel_focus",
@@ -165,6 +175,31 @@ pub(super) fn build_registry(inputs: &RegistryInputs<'_>) -> Vec<KnownHotkey> {
         "open_resume",
         "open the session picker",
     );
+    push(
+        inputs.interject.binding.clone(),
+        "interject",
+        "interject into the running turn",
+    );
+    push(
+        inputs.image_open.binding.clone(),
+        "image_open",
+        "open the focused image",
+    );
+    push(
+        inputs.remember_message.binding.clone(),
+        "remember_message",
+        "save the focused answer as a memory",
+    );
+    push(
+        inputs.copy_message.binding.clone(),
+        "copy_message",
+        "copy the focused message",
+    );
+    push(
+        inputs.file_picker.binding.clone(),
+        "file_picker",
+        "open the file picker",
+    );
     // Context-armed accept key (fallback offer / update merge). Quiet: it only
     // acts when an offer is on screen, which already explains itself.
     // Pushed directly (not via `push`), so re-create the closure afterwards to
@@ -330,7 +365,7 @@ pub(super) fn build_registry(inputs: &RegistryInputs<'_>) -> Vec<KnownHotkey> {
     out.push(KnownHotkey::new(
         ctrl('p'),
         "auto_poke_toggle",
-        "toggle auto-poke",
+        "toggle auto-poke (permission inbox when requests are pending)",
     ));
     out.push(KnownHotkey::new(
         ctrl('t'),
@@ -703,6 +738,11 @@ impl App {
             dictation: &self.dictation_key,
             new_terminal: &self.new_terminal_key,
             open_resume: &self.open_resume_key,
+            interject: &self.interject_key,
+            image_open: &self.image_open_key,
+            remember_message: &self.remember_message_key,
+            copy_message: &self.copy_message_key,
+            file_picker: &self.file_picker_key,
             fallback_switch: &self.fallback_switch_key,
             remote,
         })
@@ -875,6 +915,26 @@ mod tests {
             binding: Some(alt('r')),
             label: Some("Alt+R".to_string()),
         };
+        let interject = OptionalBinding {
+            binding: Some(alt('i')),
+            label: Some("Alt+I".to_string()),
+        };
+        let image_open = OptionalBinding {
+            binding: Some(alt('o')),
+            label: Some("Alt+O".to_string()),
+        };
+        let remember_message = OptionalBinding {
+            binding: Some(alt('p')),
+            label: Some("Alt+P".to_string()),
+        };
+        let copy_message = OptionalBinding {
+            binding: Some(alt('q')),
+            label: Some("Alt+Q".to_string()),
+        };
+        let file_picker = OptionalBinding {
+            binding: Some(ctrl('f')),
+            label: Some("Ctrl+F".to_string()),
+        };
         let fallback_switch = OptionalBinding {
             binding: Some(ctrl('y')),
             label: Some("Ctrl+Y".to_string()),
@@ -889,6 +949,11 @@ mod tests {
             dictation: &dictation,
             new_terminal: &new_terminal,
             open_resume: &open_resume,
+            interject: &interject,
+            image_open: &image_open,
+            remember_message: &remember_message,
+            copy_message: &copy_message,
+            file_picker: &file_picker,
             fallback_switch: &fallback_switch,
             remote,
         })
@@ -1040,6 +1105,22 @@ mod tests {
             ("workspace_right", Some(&["workspace_right"])),
             ("new_terminal", Some(&["new_terminal"])),
             ("open_resume", Some(&["open_resume"])),
+            ("interject", Some(&["interject"])),
+            ("image_open", Some(&["image_open"])),
+            ("remember_message", Some(&["remember_message"])),
+            ("copy_message", Some(&["copy_message"])),
+            ("file_picker", Some(&["file_picker"])),
+            // Composer editing chords are everyday typing keys handled before
+            // any feedback fall-through; annotating them would only be noise.
+            ("composer_word_back", None),
+            ("composer_word_forward", None),
+            ("composer_kill_line_end", None),
+            ("composer_kill_line_start", None),
+            ("composer_kill_word_back", None),
+            ("composer_yank", None),
+            ("composer_yank_pop", None),
+            ("composer_undo", None),
+            ("composer_transpose", None),
         ];
 
         let registry = test_inputs_registry(true);
@@ -1119,6 +1200,7 @@ mod tests {
             ),
             ("diff_mode_cycle", toggles.diff_mode_cycle.binding()),
             ("info_widget_toggle", toggles.info_widget.binding()),
+            ("todo_checklist_toggle", toggles.todo_checklist.binding()),
             ("swarm_panel_focus", toggles.swarm_panel_focus.binding()),
         ];
         for (name, binding) in toggle_bindings {
@@ -2772,6 +2772,7 @@ impl App {
                                     self.session.model = Some(active_model.clone());
                                     self.session.route_api_method =
                                         Some(route_selection.api_method.clone());
+                                    self.record_session_model_route();
                                     let _ = self.session.save();
                                     crate::logging::event_info(
                                         "model_picker_select_applied",
@@ -1,5 +1,7 @@
 #![cfg_attr(test, allow(clippy::items_after_test_module))]
 
+use super::compare::CompareKey;
+use super::permission_inbox::PermissionInboxKey;
 use super::{
     App, ContentBlock, DisplayMessage, Message, ProcessingStatus, Role, SendAction, SkillRegistry,
     commands, ctrl_bracket_fallback_to_esc, is_context_limit_error,
@@ -9,6 +11,8 @@ use crate::bus::{
     Bus, BusEvent, ClipboardPasteCompleted, ClipboardPasteContent, ClipboardPasteKind,
     InputShellCompleted,
 };
+use crate::tui::keybind::ComposerEditAction;
+use crate::tui::line_editor::{self, EditCommand};
 use crate::util::truncate_str;
 use anyhow::Result;
 use crossterm::event::{EventStream, KeyCode, KeyEvent, KeyModifiers};
@@ -223,7 +227,7 @@ fn spawn_clipboard_paste(app: &App, kind: ClipboardPasteKind) {
     });
 }
 
-fn spawn_blocking_or_thread<F>(task: F)
+pub(super) fn spawn_blocking_or_thread<F>(task: F)
 where
     F: FnOnce() + Send + 'static,
 {
@@ -234,7 +238,7 @@ where
     }
 }
 
-fn read_clipboard_text() -> Option<String> {
+pub(super) fn read_clipboard_text() -> Option<String> {
     if std::env::var("WAYLAND_DISPLAY").is_ok()
         && let Some(text) = read_wayland_clipboard_text()
     {
@@ -604,12 +608,8 @@ pub(super) fn handle_text_paste(app: &mut App, text: String) {
     if line_count < 5 {
         insert_input_text(app, &text);
     } else {
+        let placeholder = paste_placeholder(&text);
         app.pasted_contents.push(text);
-        let placeholder = format!(
-            "[pasted {} line{}]",
-            line_count,
-            if line_count == 1 { "" } else { "s" }
-        );
         insert_input_text(app, &placeholder);
     }
 }
@@ -748,10 +748,27 @@ pub(super) fn handle_multiline_input_navigation(
     code: KeyCode,
     modifiers: KeyModifiers,
 ) -> bool {
-    if !modifiers.is_empty()
-        || !matches!(code, KeyCode::Up | KeyCode::Down)
-        || !app.input.contains('\n')
-    {
+    if !modifiers.is_empty() || !matches!(code, KeyCode::Up | KeyCode::Down) {
+        return false;
+    }
+
+    // Move by the soft-wrapped rows the input box last rendered, so long
+    // drafts can be walked row by row before Up/Down fall back to history.
+    let wrap_width = crate::tui::ui::last_input_line_width();
+    if wrap_width > 0 {
+        let target = line_editor::vertical_target(
+            &app.input,
+            app.cursor_pos,
+            wrap_width,
+            code == KeyCode::Up,
+        );
+        if let Some(target) = target {
+            app.cursor_pos = target;
+        }
+        return target.is_some();
+    }
+
+    if !app.input.contains('\n') {
         return false;
     }
 
@@ -995,10 +1012,18 @@ pub(super) fn retrieve_pending_message_for_edit(app: &mut App) -> bool {
 }
 
 pub(super) fn send_action(app: &App, alternate_shortcut: bool) -> SendAction {
-    if !app.is_processing {
+    if app.input.trim().starts_with('/') || app.input.trim().starts_with('!') {
         return SendAction::Submit;
     }
-    if app.input.trim().starts_with('/') || app.input.trim().starts_with('!') {
+    // During a rate-limit wait, sending or interleaving would only hit the
+    // same limit; queue behind the pending retry instead.
+    if app
+        .rate_limit_reset
+        .is_some_and(|reset| reset > Instant::now())
+    {
+        return SendAction::Queue;
+    }
+    if !app.is_processing {
         return SendAction::Submit;
     }
     if alternate_shortcut {
@@ -1119,6 +1144,46 @@ impl App {
             .unwrap_or(false)
     }
 
+    /// Whether the configured `keybindings.image_open` chord matches this key.
+    pub(crate) fn image_open_key_matches(&self, code: KeyCode, modifiers: KeyModifiers) -> bool {
+        self.image_open_key
+            .binding
+            .as_ref()
+            .map(|binding| binding.matches(code, modifiers))
+            .unwrap_or(false)
+    }
+
+    /// Whether the configured `keybindings.remember_message` chord matches this key.
+    pub(crate) fn remember_message_key_matches(
+        &self,
+        code: KeyCode,
+        modifiers: KeyModifiers,
+    ) -> bool {
+        self.remember_message_key
+            .binding
+            .as_ref()
+            .map(|binding| binding.matches(code, modifiers))
+            .unwrap_or(false)
+    }
+
+    /// Whether the configured `keybindings.copy_message` chord matches this key.
+    pub(crate) fn copy_message_key_matches(&self, code: KeyCode, modifiers: KeyModifiers) -> bool {
+        self.copy_message_key
+            .binding
+            .as_ref()
+            .map(|binding| binding.matches(code, modifiers))
+            .unwrap_or(false)
+    }
+
+    /// Whether the configured `keybindings.file_picker` chord matches this key.
+    pub(crate) fn file_picker_key_matches(&self, code: KeyCode, modifiers: KeyModifiers) -> bool {
+        self.file_picker_key
+            .binding
+            .as_ref()
+            .map(|binding| binding.matches(code, modifiers))
+            .unwrap_or(false)
+    }
+
     /// Whether the configured `keybindings.fallback_switch` chord matches this key.
     pub(crate) fn fallback_switch_key_matches(
         &self,
@@ -1263,6 +1328,10 @@ pub(super) fn handle_control_key(app: &mut App, code: KeyCode) -> bool {
             app.toggle_input_stash();
             true
         }
+        KeyCode::Char('p') if app.permission_inbox_open() || !app.permission_inbox.is_empty() => {
+            app.toggle_permission_inbox();
+            true
+        }
         KeyCode::Char('p') => {
             super::commands::toggle_auto_poke_hotkey_local(app);
             true
@@ -1302,20 +1371,61 @@ pub(super) fn handle_control_key(app: &mut App, code: KeyCode) -> bool {
 }
 
 pub(super) fn delete_input_to_start(app: &mut App) {
-    if app.cursor_pos > 0 {
-        app.remember_input_undo_state();
-    }
-    app.input.drain(..app.cursor_pos);
-    app.cursor_pos = 0;
-    app.sync_model_picker_preview_from_input();
+    apply_edit_command(app, EditCommand::KillLineStart);
 }
 
 pub(super) fn delete_input_to_end(app: &mut App) {
-    if app.cursor_pos < app.input.len() {
-        app.remember_input_undo_state();
+    apply_edit_command(app, EditCommand::KillLineEnd);
+}
+
+/// Run a [`line_editor`] command against the draft, recording an undo entry
+/// and feeding the session kill ring. Returns `true` when the text changed.
+pub(super) fn apply_edit_command(app: &mut App, command: EditCommand) -> bool {
+    let before = command
+        .mutates()
+        .then(|| (app.input.clone(), app.cursor_pos));
+    let changed = line_editor::apply(
+        &mut app.input,
+        &mut app.cursor_pos,
+        &mut app.kill_ring,
+        command,
+    );
+    if !changed {
+        if command == EditCommand::Yank && app.kill_ring.is_empty() {
+            app.set_status_notice("Kill ring is empty");
+        }
+        return false;
+    }
+    if let Some((input, cursor_pos)) = before {
+        app.remember_input_undo_snapshot(input, cursor_pos);
     }
-    app.input.truncate(app.cursor_pos);
+    app.reset_tab_completion();
     app.sync_model_picker_preview_from_input();
+    true
+}
+
+/// Handle a configured composer editing chord (`keybindings.composer_*`).
+/// Commands that need a draft fall through on an empty input so the same
+/// chord keeps its empty-composer meaning (e.g. Ctrl+K prompt navigation).
+pub(super) fn handle_composer_edit_key(
+    app: &mut App,
+    code: KeyCode,
+    modifiers: KeyModifiers,
+) -> bool {
+    match app.composer_edit_keys.action_for(code, modifiers) {
+        Some(ComposerEditAction::Undo) => {
+            app.undo_input_change();
+            true
+        }
+        Some(ComposerEditAction::Edit(command)) => {
+            if command.needs_text() && app.input.is_empty() {
+                return false;
+            }
+            apply_edit_command(app, command);
+            true
+        }
+        None => false,
+    }
 }
 
 pub(super) fn handle_super_key(app: &mut App, code: KeyCode) -> bool {
@@ -1358,13 +1468,7 @@ pub(super) fn handle_super_key(app: &mut App, code: KeyCode) -> bool {
 }
 
 pub(super) fn delete_input_word_back(app: &mut App) {
-    let start = app.find_word_boundary_back();
-    if start < app.cursor_pos {
-        app.remember_input_undo_state();
-    }
-    app.input.drain(start..app.cursor_pos);
-    app.cursor_pos = start;
-    app.sync_model_picker_preview_from_input();
+    apply_edit_command(app, EditCommand::KillWordBack);
 }
 
 pub(super) fn handle_alt_key(app: &mut App, code: KeyCode) -> bool {
@@ -1379,12 +1483,7 @@ pub(super) fn handle_alt_key(app: &mut App, code: KeyCode) -> bool {
             true
         }
         KeyCode::Char('d') => {
-            let end = app.find_word_boundary_forward();
-            if app.cursor_pos < end {
-                app.remember_input_undo_state();
-            }
-            app.input.drain(app.cursor_pos..end);
-            app.sync_model_picker_preview_from_input();
+            apply_edit_command(app, EditCommand::KillWordForward);
             true
         }
         // macOS terminals vary between Backspace, Delete, and DEL for Option+Delete.
@@ -1401,6 +1500,11 @@ pub(super) fn handle_alt_key(app: &mut App, code: KeyCode) -> bool {
             app.copy_chat_viewport_context_to_clipboard();
             true
         }
+        KeyCode::Char('e') if app.mcp_call_remaining().is_some() => {
+            crate::mcp::cancel_session_calls(&app.session.id);
+            app.set_status_notice("Cancelling MCP call...");
+            true
+        }
         _ => false,
     }
 }
@@ -1543,16 +1647,11 @@ pub(super) fn handle_pre_control_shortcuts(
     code: KeyCode,
     modifiers: KeyModifiers,
 ) -> bool {
-    // Plain Ctrl+K kills to end of line (emacs habit). Ctrl+Shift+K must fall
+    // Readline-style editing chords (plain Ctrl+K kills to end of line, Ctrl+Y
+    // yanks, ...). Bindings match modifiers exactly, so Ctrl+Shift+K still falls
     // through to the scroll handler: with the Kitty keyboard protocol enabled,
-    // terminals report Ctrl+Shift+K as Char('k') + CONTROL|SHIFT, so without the
-    // Shift guard this would swallow the scroll-up chord and wipe the draft.
-    if modifiers.contains(KeyModifiers::CONTROL)
-        && !modifiers.contains(KeyModifiers::SHIFT)
-        && matches!(code, KeyCode::Char('k'))
-        && !app.input.is_empty()
-    {
-        delete_input_to_end(app);
+    // terminals report it as Char('k') + CONTROL|SHIFT.
+    if handle_composer_edit_key(app, code, modifiers) {
         return true;
     }
 
@@ -1594,6 +1693,10 @@ pub(super) fn handle_pre_control_shortcuts(
         app.set_status_notice(status);
         return true;
     }
+    if app.toggle_keys.todo_checklist.matches(code, modifiers) {
+        app.toggle_todo_checklist();
+        return true;
+    }
     if app.dictation_key_matches(code, modifiers) {
         app.handle_dictation_trigger();
         return true;
@@ -1625,6 +1728,22 @@ pub(super) fn handle_pre_control_shortcuts(
         app.open_session_picker();
         return true;
     }
+    if app.image_open_key_matches(code, modifiers) {
+        app.open_focused_image();
+        return true;
+    }
+    if app.remember_message_key_matches(code, modifiers) {
+        app.start_memory_pin(None);
+        return true;
+    }
+    if app.copy_message_key_matches(code, modifiers) {
+        app.copy_focused_message();
+        return true;
+    }
+    if app.file_picker_key_matches(code, modifiers) {
+        app.open_file_picker();
+        return true;
+    }
     if let Some(direction) = app.model_switch_keys.direction_for(code, modifiers) {
         app.record_keybinding_fast(super::shortcut_hints::LearnableAction::ModelSwitch);
         app.cycle_model(direction);
@@ -1823,6 +1942,11 @@ pub(super) fn handle_modal_key(
         return Ok(true);
     }
 
+    if app.file_picker_overlay.is_some() {
+        app.handle_file_picker_key(code, modifiers)?;
+        return Ok(true);
+    }
+
     if app.account_picker_overlay.is_some() {
         if let Some(command) = app.next_account_picker_action(code, modifiers)? {
             app.handle_account_picker_command(command);
@@ -1830,6 +1954,30 @@ pub(super) fn handle_modal_key(
         return Ok(true);
     }
 
+    if let Some(key) = app.handle_compare_view_key(code) {
+        if let CompareKey::Keep {
+            prompt,
+            chosen,
+            runs,
+        } = key
+        {
+            app.keep_compare_locally(prompt, chosen, runs);
+        }
+        return Ok(true);
+    }
+
+    match app.handle_permission_inbox_key(code, modifiers) {
+        PermissionInboxKey::Ignored => {}
+        PermissionInboxKey::Handled => return Ok(true),
+        PermissionInboxKey::Resolve {
+            request_id,
+            approved,
+        } => {
+            app.resolve_permission_locally(&request_id, approved);
+            return Ok(true);
+        }
+    }
+
     if app.copy_selection_mode {
         if modifiers.contains(KeyModifiers::CONTROL)
             && matches!(code, KeyCode::Char('c') | KeyCode::Char('d'))
@@ -2049,7 +2197,12 @@ fn attach_image(app: &mut App, media_type: String, base64_data: String) {
     app.set_status_notice(format!("Pasted {} ({} KB)", media_type, size_kb));
 }
 
-fn paste_placeholder(content: &str) -> String {
+/// Composer placeholder for a stored paste or file picker attachment; also
+/// what `expand_paste_placeholders` looks for on submit.
+pub(super) fn paste_placeholder(content: &str) -> String {
+    if let Some(placeholder) = crate::tui::file_picker::attachment_placeholder(content) {
+        return placeholder;
+    }
     let line_count = content.lines().count().max(1);
     format!(
         "[pasted {} line{}]",
@@ -2187,6 +2340,17 @@ impl App {
             return Ok(());
         }
 
+        match self.handle_interjection_key(code, modifiers) {
+            super::interjection::InterjectionKey::Send(content, _) => {
+                // Local sessions have no soft-interrupt queue; the interleave
+                // path restarts the turn with the message instead.
+                stage_local_interleave(self, content);
+                return Ok(());
+            }
+            super::interjection::InterjectionKey::Handled => return Ok(()),
+            super::interjection::InterjectionKey::Unhandled => {}
+        }
+
         self.normalize_diagram_state();
         let diagram_available = self.diagram_available();
 
@@ -2986,6 +3150,23 @@ impl App {
             return;
         }
 
+        if self.memory_pin_editing() {
+            self.submit_memory_pin(input);
+            return;
+        }
+
+        if self.saved_prompt_pending() {
+            self.submit_saved_prompt_variable(input);
+            return;
+        }
+
+        if self.handoff_editing() {
+            if let Some(summary) = self.take_handoff_summary(input) {
+                self.finish_handoff_locally(summary);
+            }
+            return;
+        }
+
         if let Some(name) = self.pending_ssh_remote_name.take() {
             commands::handle_pending_ssh_remote_target(self, name, input);
             return;
@@ -3000,12 +3181,15 @@ impl App {
             || commands::handle_config_command(self, trimmed)
             || commands::handle_log_command(self, trimmed)
             || commands::handle_diff_command(self, trimmed)
+            || super::file_view::handle_view_command(self, trimmed)
             || commands::handle_model_status_command(self, trimmed)
             || super::debug::handle_debug_command(self, trimmed)
             || super::model_context::handle_model_command(self, trimmed)
             || super::commands::handle_usage_command(self, trimmed)
             || super::productivity::handle_productivity_command(self, trimmed)
+            || super::memory_pin::handle_remember_command(self, trimmed)
             || super::commands::handle_feedback_command(self, trimmed)
+            || super::saved_prompts::handle_saved_prompt_command(self, trimmed)
             || super::state_ui::handle_info_command(self, trimmed)
             || super::auth::handle_auth_command(self, trimmed)
             || super::tui_lifecycle_runtime::handle_dev_command(self, trimmed);
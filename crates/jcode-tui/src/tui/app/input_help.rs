@@ -44,11 +44,14 @@ impl App {
                 "/observe\nToggle transient observe mode for the side panel.\n\n/observe on\nEnable observe mode and focus the observe page.\n\n/observe off\nDisable observe mode.\n\n/observe status\nShow whether observe mode is enabled.\n\nObserve mode shows only the latest tool call or tool result added to context, and it is not persisted to disk."
             }
             "todos" => {
-                "/todos\nToggle a transient todo screen in the side panel.\n\n/todos on\nEnable the dedicated todo screen and focus it.\n\n/todos off\nDisable the dedicated todo screen.\n\n/todos status\nShow whether the dedicated todo screen is enabled.\n\nThis view shows only the current session's todo list and refreshes as it changes."
+                "/todos\nToggle a transient todo screen in the side panel.\n\n/todos on\nEnable the dedicated todo screen and focus it.\n\n/todos off\nDisable the dedicated todo screen.\n\n/todos status\nShow whether the dedicated todo screen is enabled.\n\n/todos add <text>\nAdd an open todo to the project list (.jcode/todos.json).\n\n/todos done <n>\nMark project todo number n completed.\n\n/todos rm <n>\nDelete project todo number n.\n\nThis view shows the current session's todo list plus the numbered open todos shared across sessions in this project, and refreshes as they change."
             }
             "splitview" | "split-view" => {
                 "/splitview\nToggle a transient split view that mirrors the current chat in the side panel.\n\n/splitview on\nEnable split view and focus the mirrored chat page.\n\n/splitview off\nDisable split view.\n\n/splitview status\nShow whether split view is enabled.\n\nThis gives the side panel its own scroll position for the same conversation so you can read older context while keeping the main composer active."
             }
+            "view" => {
+                "/view\nShow the file view status.\n\n/view <path>\nSplit the screen and pin <path> in the right pane. The pane refreshes whenever an edit/write/multiedit tool changes the file.\n\n/view follow\nFollow the most recently edited file, with its latest diff highlighted (the default).\n\n/view off\nClose the file view and go back to the configured diff mode.\n\n/view save\nWrite the current diff mode and pane width to config.toml.\n\nCtrl+L focuses the pane and Ctrl+H returns to chat. With the pane focused, +/- resize it; Ctrl+1..4 jump to 25/50/75/100%. Terminals narrower than 80 columns collapse the split."
+            }
             "btw" => {
                 "/btw <question>\nAsk a side question without derailing the current session.\n\nForks (splits) the session into a new window with the full conversation cloned, and the forked session starts by answering the question. The original session keeps working uninterrupted."
             }
@@ -92,6 +95,12 @@ impl App {
                 "/fast\nShow whether fast mode is enabled, plus the saved default.\n\n/fast on\nEnable fast mode (service_tier = priority) for the current session.\n\n/fast off\nDisable fast mode for the current session.\n\n/fast status\nShow current fast-mode status.\n\n/fast default on\nSave fast mode as the default on startup.\n\n/fast default off\nSave fast mode as the default off on startup.\n\n/fast default status\nShow the saved fast-mode default."
             }
             "memory" => "/memory [on|off|status]\nToggle memory features for this session.",
+            "autocommit" => {
+                "/autocommit [on|off|status]\nCommit the files the agent modified at the end of each turn, for review and rollback. Other staged changes are left out of the commit. Overrides [git] auto_commit for this session; [git] auto_commit_branch picks the branch."
+            }
+            "tee" => {
+                "/tee <path>\nAppend the final assistant message of each turn to <path> for this session, under a timestamp and session header. Overrides [output] tee_file.\n\n/tee off\nStop appending for this session.\n\n/tee status\nShow the current tee file."
+            }
             "log" => {
                 "/log mark [note]\nWrite a distinctive JCODE_LOG_MARK line to ~/.jcode/logs/jcode-YYYY-MM-DD.log with the current session, provider, model, working directory, and optional note. Use this to mark a spot for agents to inspect later."
             }
@@ -118,8 +127,38 @@ impl App {
             "transfer" => {
                 "/transfer\nCompact the current session into a summary-only handoff, copy the current todo list to a fresh session, and open that transferred session in a new window.\n\nIf a turn is currently running, jcode first soft-pauses the current session at the next safe point, then performs the transfer."
             }
+            "env" => {
+                "/env\nShow the environment section of the system prompt: OS, shell, CPU count, memory and the versions of the tools in `[prompt] environment_tools`. It is probed once when the server starts and sent with every session so the model doesn't check these with bash.\n\n/env refresh\nProbe again, for example after installing a tool. Each session's cached prompt prefix is rebuilt once on its next turn.\n\nTurn the section off with `[prompt] environment = false`."
+            }
+            "focus" => {
+                "/focus <duration> <task>\nRun the agent on a task with a wall-clock budget, e.g. `/focus 20m migrate the config loader`. Durations take `s`, `m` or `h`; a bare number is minutes, up to 8 hours.\n\nAt the halfway mark the agent reports progress and says whether continuing is worthwhile. When time is up it records what is done and left in the todos and replies with a summary; the turn then ends and a checkpoint is added to the session (and to the mission, if one is set).\n\nRequires a server connection."
+            }
+            "redaction" => {
+                "/redaction [show]\nShow whether `[privacy] redact` is on, how many values were redacted in this session's requests, and each placeholder with its original. The mapping is stored locally and never sent.\n\n/redaction test <text>\nShow what a redaction pass would replace in the text, by kind, without saving anything.\n\nWith redaction on, emails, IPv4/IPv6 addresses, AWS keys, JWTs, known secret formats and matches of `[privacy] redact_patterns` in your messages and in tool results are replaced with placeholders like `<EMAIL_1>` before they reach the provider. Tool calls that use a placeholder get the original back when they run."
+            }
+            "changes" => {
+                "/changes [list]\nList every file this session's tools changed, with lines added and removed since the session first touched it. The baseline is a snapshot taken before the session's first edit of each file, not git, so this also works outside repositories and for sessions that have ended.\n\n/changes diff <n>\nShow file n as a colored diff against its session-start content.\n\n/changes revert <n...|all>\nPut the selected files back to their session-start content; files the session created are deleted.\n\n/changes stage <n...|all>\nRun `git add` on the selected files.\n\n/changes commit <n...|all>\nCommit the selected files, one commit per repository, with a message built from the session title and its tool calls. Anything else you have staged stays staged.\n\n`jcode sessions changes <id>` prints the same list from the command line."
+            }
+            "queue" => {
+                "/queue [list]\nList the session's pending turns: messages queued on the server, soft interrupts waiting for the next safe point (with who sent them: user, system or background task), and scheduled follow-ups that will resume this session. Messages you type while a turn runs wait in this client as `local-N` and are sent together when the turn ends.\n\n/queue rm <item>\nDrop an item before it runs. Removing a scheduled follow-up cancels the schedule.\n\n/queue move <item> <position>\nMove a message or soft interrupt to a 1-based position among items of its kind. Scheduled follow-ups run at their due time and can only be removed.\n\nThe list stays in sync across clients attached to the session. The debug socket offers the same as `queue:list`, `queue:remove` and `queue:move`."
+            }
+            "handoff" => {
+                "/handoff\nDraft a structured summary of this session (goal, decisions, open todos, files touched, gotchas) with the background model and place it in the input box. Edit it and press Enter to create a new session in the same working directory that starts from the summary, with this session as its parent and the same model and todos. jcode switches to the new session, and this one gets a closing note pointing at it.\n\nSubmit the draft empty or type /cancel to stay in this session."
+            }
             "plan" => {
-                "/plan [goal]\nDraft a plan without implementing anything. The model inspects the repo, then presents a structured plan (Goal, Scope, Approach, Validation, Open questions) as a dedicated plan card in the conversation.\n\nNothing is edited: it stops after presenting the plan. Once you approve, it converts the plan into a todo list and starts the work.\n\n/plan with no goal plans the task currently in focus."
+                "/plan [goal]\nEnter plan mode. The model may only read, search, and list files; it investigates the repo, then submits a structured plan (steps, files touched, commands to run, risks) with the plan_propose tool, shown as a plan card.\n\n/plan approve\nApprove the plan. Plan mode turns off and the approved plan is sent back as context so execution starts.\n\n/plan edit <notes>\nSend the plan back for revision with your notes. Plan mode stays on.\n\n/plan reject [reason]\nReject the plan and leave plan mode without executing anything.\n\n/plan off\nLeave plan mode without a decision.\n\n/plan status\nShow whether plan mode is on and the state of the latest plan.\n\n/plan with no goal plans the task currently in focus. The plan and its approval are stored in the session."
+            }
+            "aside" => {
+                "/aside [question]\nOpen a scratch thread beside the main conversation. It starts from the same project context and can only read, search, and list files. Its messages are kept apart from the main conversation and stored under their own key in the session file. Prompts go to the aside until it is closed.\n\n/aside end\nDraft a short summary of the aside and place it in the input box. Edit it and press Enter to add it to the main conversation and close the aside, submit it empty to close without a summary, or type /cancel to keep the aside open.\n\n/aside discard\nClose the aside without adding anything to the main conversation.\n\n/aside status\nShow whether an aside is open.\n\nRequires a live jcode server connection."
+            }
+            "compare" => {
+                "/compare <model-a> <model-b> <prompt>\nSend the prompt to both models at once with the conversation so far and the same system prompt, but no tools, so nothing runs on your machine. The answers open side by side with latency and token counts.\n\nUse ←/→ or 1-9 to pick an answer, j/k to scroll, Enter to keep it and Esc to discard both. Keeping an answer adds your prompt and that answer to the conversation; the other answer is stored under alternatives in the session file.\n\njcode run --compare <model-a>,<model-b> <prompt> prints both answers for scripted evals."
+            }
+            "profile" => {
+                "/profile <name>\nSwitch to a [profiles.<name>] preset from config.toml: its model, tool set, extra system prompt file, approval mode, and limits. If a turn is running, the switch applies at the next turn.\n\n/profile off\nClear the active profile and return to config defaults.\n\n/profile\nShow the active profile and list the configured ones.\n\nThe active profile is stored in the session and shown in the status bar."
+            }
+            "safe" => {
+                "/safe on\nTurn on safe mode for this session, as if started with jcode --safe. Tool calls that can change files, run commands or reach the network wait for you to reply y or n. Bash runs in a read-only sandbox (bwrap on Linux, sandbox-exec on macOS) except for the working directory. Memory writes and ambient scheduling are off, and each request is capped at 25 model calls.\n\n/safe off confirm\nTurn safe mode off. A bare /safe off only explains this.\n\n/safe\nShow whether safe mode is on.\n\nSafe mode is stored in the session and shown as SAFE in the status bar."
             }
             "improve" => {
                 "/improve [focus]\nStart an autonomous repo-improvement loop. The model inspects the project, writes a ranked todo list, implements the highest-leverage safe improvements, validates them, then keeps going until further work has diminishing returns.\n\n/improve plan [focus]\nGenerate a ranked improve todo list only, without editing files.\n\n/improve resume\nResume the last saved improve mode for this session using the current improve todos.\n\n/improve status\nShow the inferred status of the current improve run and todo batch.\n\n/improve stop\nAsk the model to stop after the next safe point, update todos, and summarize remaining work."
@@ -127,6 +166,12 @@ impl App {
             "refactor" => {
                 "/refactor [focus]\nStart a refactor loop aimed at moving the repo toward a practical 10/10. The main agent inspects the project, writes a ranked refactor todo list, implements the best safe refactors itself, validates each batch, and asks one independent read-only subagent to review each meaningful batch before continuing.\n\n/refactor plan [focus]\nGenerate a ranked refactor todo list only, without editing files.\n\n/refactor resume\nResume the last saved refactor mode for this session using the current refactor todos.\n\n/refactor status\nShow the inferred status of the current refactor run and todo batch.\n\n/refactor stop\nAsk the model to stop after the next safe point, update todos, and summarize remaining work."
             }
+            "migrate" => {
+                "/migrate\nShow the pending migration onto a newer binary. A shared server announces one when a newer server binary is installed; a local session announces one when a new stable build is promoted. The session migrates at the first idle moment after [display] migration_grace_secs (default 300), and the status line counts down until then.\n\n/migrate now\nMigrate as soon as the current turn finishes.\n\n/migrate defer [minutes]\nPut the migration off for 30 minutes, or the given number. When the time is up you are asked again with a fresh grace period.\n\nThe session transcript records each migration with the versions on both sides."
+            }
+            "mirror" => {
+                "/mirror <path>\nKeep a read-only markdown copy of this session's transcript at <path> for teammates or another editor pane to watch. It includes text still streaming, one folded line per tool call and a footer with the model and token totals. The file is replaced atomically at most once a second, and only when something changed.\n\n/mirror off\nStop updating the file. It keeps its last contents.\n\n/mirror\nShow where the session is mirrored.\n\nThe path is stored in the session, so resuming it picks the mirror back up."
+            }
             "reload" => {
                 "/reload\nReload into the newest available binary if one is ready. This is fast and does not rebuild."
             }
@@ -134,7 +179,7 @@ impl App {
                 "/restart\nRestart jcode with the current binary. Session is preserved.\nUseful after config changes, MCP server updates, or env var changes."
             }
             "rebuild" => {
-                "/rebuild\nRun git pull --ff-only, cargo build --release, and release tests in the background. jcode stays usable and reloads automatically when the build is ready."
+                "/rebuild\nRun git pull --ff-only, cargo build --release, and release tests in the background. jcode stays usable and reloads automatically when the build is ready. Only tests for crates affected since the last promoted build run (plus a smoke set); changes to manifests, the lockfile, build scripts, or main.rs run the full suite. Configure in [rebuild].\n\n/rebuild --full-tests\nSame, but always run the full test suite."
             }
             "selfdev" => {
                 "/selfdev\nSpawn a new self-dev jcode session in a separate terminal.\n\n/selfdev <prompt>\nSpawn a new self-dev session and auto-deliver the prompt to it.\n\n/selfdev status\nShow current self-dev/build status."
@@ -143,11 +188,20 @@ impl App {
                 "/fork\nFork the current session into a new window. Clones the full conversation history so both sessions continue from the same point.\n\n/fork <prompt>\nFork the session and start the new window by answering the prompt. The original session keeps working uninterrupted.\n\n/split\nAlias for /fork."
             }
             "resume" | "sessions" => {
-                "/resume\nOpen the interactive session picker. Browse and search all sessions, preview conversation history, and resume the highlighted session. By default, Enter resumes in the current terminal and Ctrl+Enter opens a new terminal; keybindings.session_picker_enter can swap those actions.{resume_shortcut}\n\nPress Esc to return to your current session."
+                "/resume [session]\nWith a session name or id, resume that session directly; Tab completes recent session names. Without one, open the interactive session picker. Browse and search all sessions, preview conversation history, and resume the highlighted session. By default, Enter resumes in the current terminal and Ctrl+Enter opens a new terminal; keybindings.session_picker_enter can swap those actions.{resume_shortcut}\n\nPress Esc to return to your current session."
+            }
+            "session" => {
+                "/session\nAlias for /resume.\n\n/session stats\nAggregate the current session's assistant responses per model: response count, input/output tokens, cache hits, and total/average response time.\n\n/session footers [on|off]\nShow or hide the dim model, provider, duration and token footer under assistant messages restored from history."
             }
             "info" => "/info\nShow session metadata and token usage.",
             "context" => {
-                "/context\nShow the full session context snapshot: prompt/context composition, compaction state, model/provider/runtime details, queued work, todos, and side-panel state."
+                "/context\nShow the full session context snapshot: prompt/context composition, compaction state, model/provider/runtime details, queued work, todos, and side-panel state.\n\n/context memories\nShow the memories injected into the last request with their score breakdown (similarity, recency, strength, category, weighted under [memory.weights]) and estimated tokens, then the candidates that just missed the cut and why: entry limit, token budget, or the relevance filter."
+            }
+            "remember" => {
+                "/remember\nSave the focused assistant answer (the one under the top of the chat viewport, or the latest while following the bottom) as a durable memory. Also bound to keybindings.remember_message (Alt+P).\n\n/remember <n>\nSave the n-th most recent assistant answer (1 = latest).\n\nThe answer is condensed by the session model and placed in the input box. Edit the text; the first line sets category (fact, preference, correction, entity), scope (project or global) and comma-separated tags. Enter stores it with the session and message index as its source and echoes the memory id; /cancel aborts."
+            }
+            "prompt" => {
+                "/prompt show\nShow the static system prompt as this session assembles it: each [prompt] sources layer (built-in base, ~/.jcode/JCODE.md, the nearest JCODE.md/AGENTS.md/CLAUDE.md walking up from the working directory, and instruction files in subdirectories the agent has worked in) with its file path and estimated tokens, any size-cap warnings, then the full text.\n\n/prompt sections\nShow where each optional section (date, git, memory, todos, environment, usage) goes under [prompt.sections]: static (the cached prefix), dynamic or off, its size in the last request and its token budget. Also lists placements that bust the cache every turn and, after a layout change, the measured cache hit rate before and after.\n\n/prompt <name> [var=value ...]\nSend the saved prompt ~/.jcode/prompts/<name>.md, or .jcode/prompts/<name>.md in the project (which wins). `{{variable}}` placeholders are filled from the var=value arguments; any left over are asked for one at a time in the input box (/cancel aborts). Built-ins: {{branch}}, {{diff}} (git diff HEAD), {{clipboard}} and {{file:path}}. The expanded text is what is sent and stored in the session.\n\n/prompt list\nList saved prompts with their variables and file paths.\n\n/prompt save <name>\nSave your last sent message as ~/.jcode/prompts/<name>.md."
             }
             "usage" => {
                 "/usage\nFetch and display usage limits for connected providers. This command only reports real connected-provider usage windows and reset times."
@@ -192,7 +246,7 @@ impl App {
             "continue" | "resumeall" | "resume-all" if self.is_remote => {
                 "/continue\nContinue every interrupted live session that would auto-resume on a reload.\n\nThe server walks all currently-live sessions and, for each idle one that still owes the model a reply (a turn that errored or was interrupted mid-generation), injects the standard \"continue where you left off\" reminder so it picks back up. Sessions that are busy, fresh, or already complete are left untouched.\n\nAlias: /resumeall."
             }
-            _ => return None,
+            _ => return super::command_registry::registry_help(&topic),
         };
         let help = help.replace(
             "{effort_keys}",
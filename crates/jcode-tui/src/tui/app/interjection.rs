@@ -0,0 +1,102 @@
+//! Inline interjection input for the running turn.
+//!
+//! While a turn is streaming, the interject key (Alt+Enter by default) turns
+//! the composer into a message for the agent that is already working. It is
+//! delivered as a soft interrupt: a normal interjection lands once the current
+//! tool batch has finished, an urgent one skips the remaining tools as soon as
+//! the in-flight tool is done. Esc only closes the input; it never cancels
+//! the turn from here.
+
+use super::App;
+use super::input;
+use crate::tui::InterjectionPriority;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// What a key press means while the interjection input is open.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum InterjectionKey {
+    /// Deliver the composed text with this priority
+    Send(String, InterjectionPriority),
+    /// Consumed by the interjection input
+    Handled,
+    /// Not an interjection key; continue with normal input handling
+    Unhandled,
+}
+
+impl App {
+    /// Whether the configured `keybindings.interject` chord matches this key.
+    pub(super) fn interject_key_matches(&self, code: KeyCode, modifiers: KeyModifiers) -> bool {
+        self.interject_key
+            .binding
+            .as_ref()
+            .map(|binding| binding.matches(code, modifiers))
+            .unwrap_or(false)
+    }
+
+    /// Priority of the open interjection input. It only exists while a turn
+    /// is running; once the turn ends the draft is an ordinary prompt again.
+    pub(super) fn active_interjection(&self) -> Option<InterjectionPriority> {
+        self.interjection.filter(|_| self.is_processing)
+    }
+
+    /// Open the interjection input, or flip its priority when already open.
+    pub(super) fn toggle_interjection(&mut self) {
+        let priority = match self.active_interjection() {
+            None => InterjectionPriority::Normal,
+            Some(InterjectionPriority::Normal) => InterjectionPriority::Urgent,
+            Some(InterjectionPriority::Urgent) => InterjectionPriority::Normal,
+        };
+        self.interjection = Some(priority);
+        self.set_status_notice(match priority {
+            InterjectionPriority::Normal => "↳ Interjection: lands after the current tools",
+            InterjectionPriority::Urgent => "↯ Urgent interjection: skips the remaining tools",
+        });
+    }
+
+    pub(super) fn close_interjection(&mut self) {
+        if self.interjection.take().is_some() {
+            self.set_status_notice("Interjection closed");
+        }
+    }
+
+    /// Open the interjection input on the interject key while a turn runs,
+    /// and route key presses to it while it is open.
+    pub(super) fn handle_interjection_key(
+        &mut self,
+        code: KeyCode,
+        modifiers: KeyModifiers,
+    ) -> InterjectionKey {
+        if !self.is_processing {
+            // The turn ended while composing: keep the draft as a normal prompt.
+            self.interjection = None;
+            return InterjectionKey::Unhandled;
+        }
+        if self.interject_key_matches(code, modifiers) {
+            self.toggle_interjection();
+            return InterjectionKey::Handled;
+        }
+        let Some(priority) = self.interjection else {
+            return InterjectionKey::Unhandled;
+        };
+        match code {
+            KeyCode::Esc => {
+                self.close_interjection();
+                InterjectionKey::Handled
+            }
+            KeyCode::Tab if modifiers.is_empty() => {
+                self.toggle_interjection();
+                InterjectionKey::Handled
+            }
+            // Slash commands still run as commands.
+            KeyCode::Enter if modifiers.is_empty() && !self.input.trim_start().starts_with('/') => {
+                if self.input.trim().is_empty() {
+                    return InterjectionKey::Handled;
+                }
+                let prepared = input::take_prepared_input(self);
+                self.interjection = None;
+                InterjectionKey::Send(prepared.expanded, priority)
+            }
+            _ => InterjectionKey::Unhandled,
+        }
+    }
+}
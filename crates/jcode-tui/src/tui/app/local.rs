@@ -82,9 +82,14 @@ pub(super) fn handle_tick(app: &mut App) -> bool {
         needs_redraw = true;
     }
     needs_redraw |= app.refresh_todos_view_if_needed();
+    needs_redraw |= app.refresh_todo_checklist();
     needs_redraw |= app.refresh_side_panel_linked_content_if_due();
+    needs_redraw |= app.poll_local_permission_inbox();
+    app.refresh_terminal_title_state();
+    needs_redraw |= app.write_mirror_if_due();
     needs_redraw |= app.poll_model_picker_load();
     needs_redraw |= app.poll_session_picker_load();
+    needs_redraw |= app.poll_file_picker_load();
     needs_redraw |= app.onboarding_tick();
     needs_redraw |= app.poll_compaction_completion();
     needs_redraw |= app.maybe_refresh_overnight_display_card();
@@ -94,8 +99,9 @@ pub(super) fn handle_tick(app: &mut App) -> bool {
     app.check_debug_command();
     needs_redraw |= app.check_stable_version();
     needs_redraw |= app.maybe_finish_background_client_reload();
-    if app.pending_migration.is_some() && !app.is_processing {
-        app.execute_migration();
+    needs_redraw |= app.refresh_pending_migration();
+    if let Some(migration) = app.take_due_migration() {
+        app.execute_migration(migration);
         needs_redraw = true;
     }
     if let Some(reset_time) = app.rate_limit_reset
@@ -167,6 +173,22 @@ pub(super) fn handle_bus_event(
             app.handle_productivity_report_ready(event);
             true
         }
+        Ok(BusEvent::MemoryPinDrafted(event)) => {
+            app.handle_memory_pin_drafted(event);
+            true
+        }
+        Ok(BusEvent::MemoryPinSaved(event)) => {
+            app.handle_memory_pin_saved(event);
+            true
+        }
+        Ok(BusEvent::HandoffDrafted(event)) => {
+            app.handle_handoff_drafted(event);
+            true
+        }
+        Ok(BusEvent::CompareFinished(event)) => {
+            app.handle_compare_finished(event);
+            true
+        }
         Ok(BusEvent::MermaidRenderCompleted) => true,
         Ok(BusEvent::UsageReport(results)) => {
             app.handle_usage_report(results);
@@ -210,6 +232,7 @@ pub(super) fn handle_bus_event(
                 )
             });
             app.session.model = Some(model.clone());
+            app.record_session_model_route();
             let _ = app.session.save();
             app.push_display_message(crate::tui::DisplayMessage::system(message));
             app.set_status_notice(format!("Model → {}", model));
@@ -258,6 +281,14 @@ pub(super) fn handle_bus_event(
                 false
             }
         }
+        Ok(BusEvent::ClipboardWriteRequested(request)) => {
+            if request.session_id == app.session.id {
+                app.handle_clipboard_write(&request.text);
+                true
+            } else {
+                false
+            }
+        }
         Ok(BusEvent::TodoUpdated(event)) => {
             if event.session_id == app.session.id {
                 app.refresh_todos_view_now()
@@ -491,6 +522,7 @@ pub(super) fn finish_turn(app: &mut App) {
     app.thinking_prefix_emitted = false;
     app.thinking_buffer.clear();
     app.note_runtime_memory_event_force("turn_completed", "local_turn_finished");
+    app.tee_turn_output();
     let followup_scheduled = app.schedule_auto_poke_followup_if_needed()
         || app.schedule_overnight_poke_followup_if_needed();
     if !followup_scheduled {
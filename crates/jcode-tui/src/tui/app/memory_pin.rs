@@ -0,0 +1,418 @@
+//! `/remember`: promote an assistant answer into a durable memory.
+//!
+//! The chosen answer is condensed off the UI thread with the session's
+//! provider, then placed in the input box under a header line that picks the
+//! category, scope and tags. Submitting stores it through `MemoryManager` with
+//! the session and transcript index as its source, so later sessions can
+//! recall it and its provenance line points back here.
+
+use super::*;
+use crate::bus::{Bus, BusEvent, MemoryPinDrafted, MemoryPinSaved};
+use crate::memory::{MemoryCategory, MemoryEntry, MemoryManager, TrustLevel};
+use crate::tui::ui;
+
+const USAGE: &str = "Usage: `/remember [n]` saves the focused assistant answer, or the n-th most recent one (1 = latest).";
+const CONDENSE_TIMEOUT: Duration = Duration::from_secs(60);
+const CONDENSE_SYSTEM_PROMPT: &str = "You turn an assistant answer into one durable memory for a coding agent. \
+Keep the facts, decisions, commands and file paths a future session would need; drop narration, \
+pleasantries and anything only meaningful inside this conversation. \
+Reply with the memory text alone: one to three plain sentences, no preamble, no markdown headings.";
+
+/// A `/remember` flow in progress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum PendingMemoryPin {
+    /// Waiting for the condensed draft of the answer at `message_index`.
+    Condensing { message_index: usize },
+    /// The draft is in the input box; the next submit stores it.
+    Editing { message_index: usize },
+}
+
+/// The edited draft: an optional `key=value` header line, then the memory.
+#[derive(Debug, Clone, PartialEq)]
+struct PinDraft {
+    category: MemoryCategory,
+    global: bool,
+    tags: Vec<String>,
+    content: String,
+}
+
+/// Handle `/remember` and `/remember <n>`.
+pub(super) fn handle_remember_command(app: &mut App, trimmed: &str) -> bool {
+    let Some(rest) = trimmed.strip_prefix("/remember") else {
+        return false;
+    };
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return false;
+    }
+    let rank = match rest.trim() {
+        "" => None,
+        arg => match arg.parse::<usize>() {
+            Ok(rank) if rank > 0 => Some(rank),
+            _ => {
+                app.push_display_message(DisplayMessage::error(USAGE));
+                return true;
+            }
+        },
+    };
+    app.start_memory_pin(rank);
+    true
+}
+
+impl App {
+    /// Whether the next submit is a `/remember` draft rather than a prompt.
+    pub(super) fn memory_pin_editing(&self) -> bool {
+        matches!(
+            self.pending_memory_pin,
+            Some(PendingMemoryPin::Editing { .. })
+        )
+    }
+
+    /// Start pinning the n-th most recent assistant answer, or the focused
+    /// one when `rank` is `None`.
+    pub(super) fn start_memory_pin(&mut self, rank: Option<usize>) {
+        match self.pending_memory_pin {
+            Some(PendingMemoryPin::Condensing { .. }) => {
+                self.set_status_notice("Remember → still condensing…");
+                return;
+            }
+            Some(PendingMemoryPin::Editing { .. }) => {
+                self.set_status_notice("Save or /cancel the current memory draft first");
+                return;
+            }
+            None => {}
+        }
+        let target = match rank {
+            Some(rank) => recent_assistant_index(&self.display_messages, rank),
+            None => self.focused_assistant_index(),
+        };
+        let Some(message_index) = target else {
+            self.push_display_message(DisplayMessage::error(match rank {
+                Some(rank) => format!("No assistant answer #{} to remember.", rank),
+                None => "No assistant answer to remember yet.".to_string(),
+            }));
+            return;
+        };
+
+        let provider = self.memory_pin_provider();
+        let content = self.display_messages[message_index].content.clone();
+        let session_id = self.session.id.clone();
+        // Remote sessions are saved by the server, so only local ones can
+        // pick up the usage.
+        let usage_session_id = (!self.is_remote).then(|| session_id.clone());
+        self.pending_memory_pin = Some(PendingMemoryPin::Condensing { message_index });
+        self.set_status_notice("Remember → condensing answer…");
+        tokio::spawn(async move {
+            let result = tokio::time::timeout(
+                CONDENSE_TIMEOUT,
+                crate::background_model::complete_simple(
+                    provider.as_ref(),
+                    &crate::usage::UsageOrigin::Memory,
+                    usage_session_id.as_deref(),
+                    &content,
+                    CONDENSE_SYSTEM_PROMPT,
+                ),
+            )
+            .await
+            .map_err(|_| format!("timed out after {}s", CONDENSE_TIMEOUT.as_secs()))
+            .and_then(|reply| reply.map_err(|error| error.to_string()))
+            .map(|reply| reply.trim().to_string())
+            .and_then(|reply| {
+                if reply.is_empty() {
+                    Err("empty response".to_string())
+                } else {
+                    Ok(reply)
+                }
+            });
+            Bus::global().publish(BusEvent::MemoryPinDrafted(MemoryPinDrafted {
+                session_id,
+                message_index,
+                result,
+            }));
+        });
+    }
+
+    /// Local mode forks the live provider. In remote mode `self.provider` is a
+    /// placeholder, so build a local one pinned to the session's model.
+    fn memory_pin_provider(&self) -> Arc<dyn crate::provider::Provider> {
+        if !self.is_remote {
+            return self.provider.fork();
+        }
+        let provider: Arc<dyn crate::provider::Provider> =
+            Arc::new(crate::provider::MultiProvider::new_fast());
+        if let Some(model) = self.remote_provider_model.as_deref() {
+            let _ = provider.set_model(model);
+        }
+        provider
+    }
+
+    /// The assistant answer under the top of the viewport, or the latest one
+    /// while following the bottom of the chat.
+    fn focused_assistant_index(&self) -> Option<usize> {
+        self.focused_message_index(is_pinnable)
+    }
+
+    /// The last message accepted by `accept` in the turn under the top of the
+    /// viewport, or the latest one while following the bottom of the chat.
+    pub(super) fn focused_message_index(
+        &self,
+        accept: fn(&DisplayMessage) -> bool,
+    ) -> Option<usize> {
+        let latest = || self.display_messages.iter().rposition(accept);
+        if !self.auto_scroll_paused {
+            return latest();
+        }
+        let positions = ui::last_user_prompt_positions();
+        let prompt_rank = positions
+            .iter()
+            .rposition(|&line| line <= self.scroll_offset)?;
+        let prompt_index = self
+            .display_messages
+            .iter()
+            .enumerate()
+            .filter(|(_, message)| message.role == "user")
+            .nth(prompt_rank)
+            .map(|(index, _)| index)?;
+        let turn_end = self.display_messages[prompt_index + 1..]
+            .iter()
+            .position(|message| message.role == "user")
+            .map(|offset| prompt_index + 1 + offset)
+            .unwrap_or(self.display_messages.len());
+        (prompt_index + 1..turn_end)
+            .rev()
+            .find(|&index| accept(&self.display_messages[index]))
+            .or_else(latest)
+    }
+
+    pub(super) fn handle_memory_pin_drafted(&mut self, event: MemoryPinDrafted) {
+        if event.session_id != self.session.id
+            || self.pending_memory_pin
+                != Some(PendingMemoryPin::Condensing {
+                    message_index: event.message_index,
+                })
+        {
+            return;
+        }
+        let Some(original) = self
+            .display_messages
+            .get(event.message_index)
+            .map(|message| message.content.trim().to_string())
+        else {
+            self.pending_memory_pin = None;
+            return;
+        };
+        let draft = match event.result {
+            Ok(draft) => draft,
+            Err(error) => {
+                self.push_display_message(DisplayMessage::system(format!(
+                    "Could not condense the answer ({}); editing the original text instead.",
+                    error
+                )));
+                original
+            }
+        };
+
+        if !self.input.is_empty() {
+            if self.stashed_input.is_some() {
+                self.pending_memory_pin = None;
+                self.push_display_message(DisplayMessage::error(
+                    "Memory pin cancelled: the input box and stash are both in use. Clear one and run /remember again.",
+                ));
+                return;
+            }
+            let input = std::mem::take(&mut self.input);
+            self.stashed_input = Some((input, self.cursor_pos));
+        }
+        self.pending_memory_pin = Some(PendingMemoryPin::Editing {
+            message_index: event.message_index,
+        });
+        self.input = format!("category=fact scope=project tags=\n{}", draft);
+        self.cursor_pos = self.input.len();
+        self.clear_input_undo_history();
+        self.push_display_message(DisplayMessage::system(
+            "Edit the memory in the input box. The first line sets `category` (fact, preference, correction, entity), `scope` (project or global) and comma-separated `tags`. Press Enter to save, or `/cancel` to abort.",
+        ));
+        self.set_status_notice("Remember → edit and press Enter");
+    }
+
+    /// Store the edited draft. Called with the submitted input while
+    /// [`Self::memory_pin_editing`] is true.
+    pub(super) fn submit_memory_pin(&mut self, input: String) {
+        let Some(PendingMemoryPin::Editing { message_index }) = self.pending_memory_pin.take()
+        else {
+            return;
+        };
+        if input.trim() == "/cancel" {
+            self.push_display_message(DisplayMessage::system("Memory pin cancelled."));
+            self.set_status_notice("Remember: cancelled");
+            return;
+        }
+        let draft = match parse_pin_draft(&input) {
+            Ok(draft) => draft,
+            Err(error) => {
+                self.push_display_message(DisplayMessage::error(error));
+                self.pending_memory_pin = Some(PendingMemoryPin::Editing { message_index });
+                self.input = input;
+                self.cursor_pos = self.input.len();
+                return;
+            }
+        };
+
+        let source_session = self
+            .remote_session_id
+            .clone()
+            .unwrap_or_else(|| self.session.id.clone());
+        let entry = MemoryEntry::new(draft.category, draft.content)
+            .with_tags(draft.tags)
+            .with_source(format!("{}#{}", source_session, message_index))
+            .with_trust(TrustLevel::High);
+        let scope = if draft.global { "global" } else { "project" };
+        let working_dir = commands::active_working_dir(self);
+        let session_id = self.session.id.clone();
+        self.set_status_notice("Remember → saving…");
+        std::thread::spawn(move || {
+            let mut manager = MemoryManager::new();
+            if let Some(dir) = working_dir {
+                manager = manager.with_project_dir(dir);
+            }
+            let result = if scope == "global" {
+                manager.remember_global(entry)
+            } else {
+                manager.remember_project(entry)
+            }
+            .map_err(|error| error.to_string());
+            Bus::global().publish(BusEvent::MemoryPinSaved(MemoryPinSaved {
+                session_id,
+                scope: scope.to_string(),
+                result,
+            }));
+        });
+    }
+
+    pub(super) fn handle_memory_pin_saved(&mut self, event: MemoryPinSaved) {
+        if event.session_id != self.session.id {
+            return;
+        }
+        match event.result {
+            Ok(id) => {
+                self.push_display_message(DisplayMessage::system(format!(
+                    "🧠 Saved {} memory `{}`. It is available to future sessions.",
+                    event.scope, id
+                )));
+                self.set_status_notice(format!("Remembered · {}", id));
+            }
+            Err(error) => {
+                self.push_display_message(DisplayMessage::error(format!(
+                    "Failed to save memory: {}",
+                    error
+                )));
+                self.set_status_notice("Remember failed");
+            }
+        }
+    }
+}
+
+fn is_pinnable(message: &DisplayMessage) -> bool {
+    message.role == "assistant" && !message.content.trim().is_empty()
+}
+
+/// Index of the `rank`-th most recent assistant answer (1 = latest).
+fn recent_assistant_index(messages: &[DisplayMessage], rank: usize) -> Option<usize> {
+    messages
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, message)| is_pinnable(message))
+        .nth(rank.checked_sub(1)?)
+        .map(|(index, _)| index)
+}
+
+fn parse_pin_draft(input: &str) -> std::result::Result<PinDraft, String> {
+    let mut draft = PinDraft {
+        category: MemoryCategory::Fact,
+        global: false,
+        tags: Vec::new(),
+        content: input.trim().to_string(),
+    };
+    let (first_line, body) = input.split_once('\n').unwrap_or((input, ""));
+    let header: Vec<_> = first_line
+        .split_whitespace()
+        .map(|token| token.split_once('='))
+        .collect();
+    let is_header = !header.is_empty()
+        && header.iter().all(
+            |pair| matches!(pair, Some((key, _)) if matches!(*key, "category" | "scope" | "tags")),
+        );
+    if is_header {
+        for (key, value) in header.into_iter().flatten() {
+            match key {
+                "category" => draft.category = MemoryCategory::from_extracted(value),
+                "scope" => {
+                    draft.global = match value.to_ascii_lowercase().as_str() {
+                        "project" => false,
+                        "global" => true,
+                        other => {
+                            return Err(format!(
+                                "Unknown scope `{}`: use `project` or `global`.",
+                                other
+                            ));
+                        }
+                    }
+                }
+                _ => {
+                    draft.tags = value
+                        .split(',')
+                        .map(|tag| tag.trim().to_string())
+                        .filter(|tag| !tag.is_empty())
+                        .collect()
+                }
+            }
+        }
+        draft.content = body.trim().to_string();
+    }
+    if draft.content.is_empty() {
+        return Err("The memory text is empty. Write it below the header line.".to_string());
+    }
+    Ok(draft)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_header_and_body() {
+        let draft = parse_pin_draft(
+            "category=preference scope=global tags=rust, testing\nRun tests with cargo nextest.\n",
+        )
+        .expect("draft");
+        assert_eq!(draft.category, MemoryCategory::Preference);
+        assert!(draft.global);
+        assert_eq!(draft.tags, ["rust", "testing"]);
+        assert_eq!(draft.content, "Run tests with cargo nextest.");
+    }
+
+    #[test]
+    fn text_without_header_is_a_project_fact() {
+        let draft = parse_pin_draft("The build needs protoc 25.").expect("draft");
+        assert_eq!(draft.category, MemoryCategory::Fact);
+        assert!(!draft.global);
+        assert_eq!(draft.content, "The build needs protoc 25.");
+
+        assert!(parse_pin_draft("scope=team\nText").is_err());
+        assert!(parse_pin_draft("category=fact scope=project tags=\n  ").is_err());
+    }
+
+    #[test]
+    fn recent_assistant_index_skips_other_roles() {
+        let messages = vec![
+            DisplayMessage::user("q1"),
+            DisplayMessage::assistant("a1"),
+            DisplayMessage::user("q2"),
+            DisplayMessage::assistant("a2"),
+            DisplayMessage::system("note"),
+        ];
+        assert_eq!(recent_assistant_index(&messages, 1), Some(3));
+        assert_eq!(recent_assistant_index(&messages, 2), Some(1));
+        assert_eq!(recent_assistant_index(&messages, 3), None);
+    }
+}
@@ -0,0 +1,61 @@
+//! Clipboard copies that are not text selections: the focused message or tool
+//! output (`keybindings.copy_message`), and text the agent sent with the
+//! `clipboard` tool.
+
+use super::{App, DisplayMessage};
+
+impl App {
+    /// Copy the focused assistant answer or tool output. OSC 52 goes first so
+    /// this works through nested SSH and tmux sessions.
+    pub(super) fn copy_focused_message(&mut self) {
+        let Some(index) = self.focused_message_index(is_copyable) else {
+            self.set_status_notice("Nothing to copy yet");
+            return;
+        };
+        let message = &self.display_messages[index];
+        let label = if message.role == "tool" {
+            "tool output"
+        } else {
+            "answer"
+        };
+        let content = message.content.clone();
+        if super::helpers::copy_to_clipboard_osc52_first(&content) {
+            self.set_status_notice(format!(
+                "Copied {} ({} chars)",
+                label,
+                content.chars().count()
+            ));
+        } else {
+            self.set_status_notice(format!("Failed to copy {}", label));
+        }
+    }
+
+    /// Apply a `clipboard` tool write for this session.
+    pub(super) fn handle_clipboard_write(&mut self, text: &str) {
+        if super::helpers::copy_to_clipboard_for_tool(text) {
+            self.set_status_notice(format!(
+                "Agent copied {} chars to the clipboard",
+                text.chars().count()
+            ));
+        } else {
+            self.set_status_notice("Agent clipboard copy failed");
+        }
+    }
+}
+
+fn is_copyable(message: &DisplayMessage) -> bool {
+    matches!(message.role.as_str(), "assistant" | "tool") && !message.content.trim().is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copyable_messages_are_answers_and_tool_outputs() {
+        assert!(is_copyable(&DisplayMessage::assistant("done")));
+        assert!(!is_copyable(&DisplayMessage::assistant("  ")));
+        assert!(!is_copyable(&DisplayMessage::user("question")));
+        assert!(!is_copyable(&DisplayMessage::system("note")));
+    }
+}
@@ -128,6 +128,22 @@ impl App {
         self.set_status_notice("Usage → refreshing");
     }
 
+    /// The active session's per-origin usage for `/usage`.
+    fn session_origin_usage_report(&self) -> Option<crate::usage::ProviderUsage> {
+        let session_id = super::commands::active_session_id(self);
+        let origins = if !self.is_remote && self.session.id == session_id {
+            self.session.origin_usage_stats()
+        } else {
+            crate::session::Session::load(&session_id)
+                .ok()?
+                .origin_usage_stats()
+        };
+        crate::usage::origin_usage_entry(
+            format!("{} (this session)", crate::usage::ORIGIN_USAGE_NAME),
+            &origins,
+        )
+    }
+
     pub(super) fn request_usage_report(&mut self) {
         use crate::bus::{Bus, BusEvent};
 
@@ -136,11 +152,19 @@ impl App {
         }
         self.usage_report_refreshing = true;
 
+        let session_origins = self.session_origin_usage_report();
         let publish = || async move {
-            let results = crate::usage::fetch_all_provider_usage_progressive(|progress| {
+            let background = crate::usage::background_usage_report()
+                .into_iter()
+                .chain(crate::usage::origin_usage_report())
+                .chain(session_origins)
+                .collect::<Vec<_>>();
+            let mut results = crate::usage::fetch_all_provider_usage_progressive(|mut progress| {
+                progress.results.extend(background.clone());
                 Bus::global().publish(BusEvent::UsageReportProgress(progress));
             })
             .await;
+            results.extend(background);
             Bus::global().publish(BusEvent::UsageReport(results));
         };
 
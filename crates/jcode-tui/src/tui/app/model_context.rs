@@ -47,10 +47,20 @@ impl App {
                 self.session.provider_key.as_deref(),
             );
         self.session.model = Some(active_model.clone());
+        self.record_session_model_route();
         let _ = self.session.save();
         active_model
     }
 
+    /// Persist the full active route (including any `@provider` pin) so a
+    /// resumed session restores it, not just the bare model.
+    pub(super) fn record_session_model_route(&mut self) {
+        self.session.model_route = Some(crate::provider::current_model_route(
+            self.provider.as_ref(),
+            self.session.route_api_method.as_deref(),
+        ));
+    }
+
     fn apply_provider_switch_for_failover(
         &mut self,
         prompt: &crate::provider::ProviderFailoverPrompt,
@@ -344,6 +354,7 @@ impl App {
                     );
                 self.session.model = Some(active_model.clone());
                 self.session.route_api_method = Some(offer.selection.api_method.clone());
+                self.record_session_model_route();
                 let _ = self.session.save();
                 self.push_display_message(DisplayMessage::system(format!(
                     "↪ Switched to {} and resending (was {}).",
@@ -1076,6 +1087,15 @@ impl App {
         if provider.hard_limit_reached {
             return format!("! {} - hard limit", provider.provider_name);
         }
+        if provider
+            .provider_name
+            .starts_with(crate::usage::BACKGROUND_USAGE_NAME)
+            || provider
+                .provider_name
+                .starts_with(crate::usage::ORIGIN_USAGE_NAME)
+        {
+            return provider.provider_name.clone();
+        }
 
         let max_percent = provider
             .limits
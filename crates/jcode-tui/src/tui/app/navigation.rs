@@ -460,6 +460,8 @@ impl App {
             KeyCode::Char('0') if self.side_panel.focused_page().is_some() => {
                 self.reset_side_panel_image_zoom();
             }
+            KeyCode::Char('+') | KeyCode::Char('=') => self.adjust_side_pane_ratio(5),
+            KeyCode::Char('-') | KeyCode::Char('_') => self.adjust_side_pane_ratio(-5),
             KeyCode::Esc => {
                 self.set_diff_pane_focus(false);
             }
@@ -814,7 +816,7 @@ impl App {
         }
     }
 
-    fn set_diagram_pane_ratio(&mut self, next: i16, animate: bool, announce: bool) {
+    pub(super) fn set_diagram_pane_ratio(&mut self, next: i16, animate: bool, announce: bool) {
         let (min_ratio, max_ratio) = self.diagram_pane_ratio_limits();
         let next = next.clamp(min_ratio as i16, max_ratio as i16) as u8;
         let current_target = self.diagram_pane_ratio_target;
@@ -1056,6 +1058,29 @@ impl App {
         }
     }
 
+    /// Open the inline image or diagram that has focus in the chat (the one
+    /// filling most of the viewport) with the system image viewer.
+    pub(super) fn open_focused_image(&mut self) {
+        let Some(path) = crate::tui::ui::focused_image_path() else {
+            self.set_status_notice("No image on screen");
+            return;
+        };
+        if !path.exists() {
+            self.set_status_notice("Image file not found on disk");
+            return;
+        }
+        match super::helpers::open_path_or_url_detached(&path) {
+            Ok(_) => {
+                let name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| path.display().to_string());
+                self.set_status_notice(format!("Opened {} in viewer", name));
+            }
+            Err(e) => self.set_status_notice(format!("Failed to open: {}", e)),
+        }
+    }
+
     pub(super) fn handle_diagram_ctrl_key(
         &mut self,
         code: KeyCode,
@@ -1270,6 +1295,10 @@ impl App {
             picker_cell.borrow_mut().handle_overlay_mouse(mouse);
             finish_mouse_event!(false, "login_picker_overlay");
         }
+        if let Some(ref picker_cell) = self.file_picker_overlay {
+            picker_cell.borrow_mut().handle_overlay_mouse(mouse);
+            finish_mouse_event!(false, "file_picker_overlay");
+        }
         if let Some(ref picker_cell) = self.account_picker_overlay {
             picker_cell.borrow_mut().handle_overlay_mouse(mouse);
             finish_mouse_event!(false, "account_picker_overlay");
@@ -1336,6 +1365,13 @@ impl App {
             self.set_diff_pane_focus(false);
         }
 
+        if matches!(mouse.kind, MouseEventKind::Down(MouseButton::Left))
+            && !self.copy_selection_mode
+            && self.handle_todo_checklist_click(mouse.column, mouse.row)
+        {
+            finish_mouse_event!(false, "todo_checklist_click");
+        }
+
         if let Some(scroll_only) = self.handle_copy_selection_mouse(mouse) {
             finish_mouse_event!(scroll_only, "copy_selection");
         }
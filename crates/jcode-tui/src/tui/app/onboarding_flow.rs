@@ -14,6 +14,10 @@
 //!      "Start a new session" row alongside the resumable
 //!      sessions. Nothing auto-selects; the user resumes a
 //!      session or starts fresh explicitly.
+//!   2a. `Setup` - on an install with no config.toml yet, a one-screen form
+//!      right after login: default model, memory, ambient mode, layout,
+//!      and an optional `~/.jcode/JCODE.md`. Continue writes a commented
+//!      config.toml; Esc leaves the defaults and writes nothing.
 //!   3. `Suggestions` - the existing prompt-suggestion cards. Reached when
 //!      they choose "Start a new session", when there is no
 //!      external OAuth, or as the terminal resting state.
@@ -221,6 +225,161 @@ impl ImportReview {
     }
 }
 
+/// One row of the first-run [`SetupWizard`] form, in display order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SetupRow {
+    Model,
+    Memory,
+    Ambient,
+    Layout,
+    Instructions,
+}
+
+impl SetupRow {
+    pub(crate) const ALL: [SetupRow; 5] = [
+        SetupRow::Model,
+        SetupRow::Memory,
+        SetupRow::Ambient,
+        SetupRow::Layout,
+        SetupRow::Instructions,
+    ];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            SetupRow::Model => "Default model",
+            SetupRow::Memory => "Memory",
+            SetupRow::Ambient => "Ambient mode",
+            SetupRow::Layout => "Layout",
+            SetupRow::Instructions => "~/.jcode/JCODE.md",
+        }
+    }
+}
+
+/// Single-screen first-run settings form, shown after login on an install
+/// that has no config.toml yet. Every row starts at the value jcode would use
+/// anyway, and the "Continue" pill starts focused, so one Enter accepts the
+/// defaults. Modeled on [`ImportReview`]: the cursor cycles through the rows
+/// and the pill, Left/Right change the focused row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct SetupWizard {
+    /// Default model to persist; `None` keeps the provider's own default.
+    pub(crate) model: Option<String>,
+    /// `[features] memory`: memory recall and automatic capture.
+    pub(crate) memory: bool,
+    /// `[ambient] enabled`: the background maintenance agent.
+    pub(crate) ambient: bool,
+    /// `[display] centered`: jcode has no color themes, so layout is the one
+    /// appearance choice offered here.
+    pub(crate) centered: bool,
+    /// Create `~/.jcode/JCODE.md` from the template. Only offered (and
+    /// defaulted on) when the file does not exist yet.
+    pub(crate) create_instructions: bool,
+    /// Whether `~/.jcode/JCODE.md` already exists, which locks that row.
+    pub(crate) instructions_exist: bool,
+    /// Index into [`SetupRow::ALL`] of the focused row.
+    pub(crate) cursor: usize,
+    /// When `true`, the "Continue" pill is focused instead of a row.
+    pub(crate) continue_focused: bool,
+}
+
+impl SetupWizard {
+    pub(crate) fn new(instructions_exist: bool) -> Self {
+        Self {
+            model: None,
+            memory: true,
+            ambient: false,
+            centered: false,
+            create_instructions: !instructions_exist,
+            instructions_exist,
+            cursor: 0,
+            continue_focused: true,
+        }
+    }
+
+    /// The focused row, or `None` while the "Continue" pill is focused.
+    pub(crate) fn current_row(&self) -> Option<SetupRow> {
+        if self.continue_focused {
+            return None;
+        }
+        SetupRow::ALL.get(self.cursor).copied()
+    }
+
+    /// Continue -> last row -> ... -> first row -> Continue.
+    pub(crate) fn cursor_up(&mut self) {
+        if self.continue_focused {
+            self.continue_focused = false;
+            self.cursor = SetupRow::ALL.len() - 1;
+        } else if self.cursor == 0 {
+            self.continue_focused = true;
+        } else {
+            self.cursor -= 1;
+        }
+    }
+
+    /// First row -> ... -> last row -> Continue -> first row.
+    pub(crate) fn cursor_down(&mut self) {
+        if self.continue_focused {
+            self.continue_focused = false;
+            self.cursor = 0;
+        } else if self.cursor + 1 >= SetupRow::ALL.len() {
+            self.continue_focused = true;
+        } else {
+            self.cursor += 1;
+        }
+    }
+
+    /// Flip the focused on/off row. The model row cycles instead (see
+    /// [`Self::cycle_model`]) and the JCODE.md row is fixed once the file
+    /// exists.
+    pub(crate) fn toggle_current(&mut self) {
+        match self.current_row() {
+            Some(SetupRow::Memory) => self.memory = !self.memory,
+            Some(SetupRow::Ambient) => self.ambient = !self.ambient,
+            Some(SetupRow::Layout) => self.centered = !self.centered,
+            Some(SetupRow::Instructions) if !self.instructions_exist => {
+                self.create_instructions = !self.create_instructions
+            }
+            _ => {}
+        }
+    }
+
+    /// Step the model choice through "provider default" followed by
+    /// `choices` (the live model list), wrapping at either end.
+    pub(crate) fn cycle_model(&mut self, choices: &[String], forward: bool) {
+        let options = choices.len() + 1;
+        let current = self
+            .model
+            .as_ref()
+            .and_then(|model| choices.iter().position(|choice| choice == model))
+            .map(|idx| idx + 1)
+            .unwrap_or(0);
+        let next = if forward {
+            (current + 1) % options
+        } else {
+            (current + options - 1) % options
+        };
+        self.model = next.checked_sub(1).map(|idx| choices[idx].clone());
+    }
+
+    /// The `table.key` entries Continue writes to config.toml, as TOML
+    /// literals. Every setting is written, defaults included, so the file
+    /// records what was chosen.
+    pub(crate) fn config_values(&self) -> Vec<(&'static str, String)> {
+        let mut values = vec![
+            ("features.memory", self.memory.to_string()),
+            ("ambient.enabled", self.ambient.to_string()),
+            ("display.centered", self.centered.to_string()),
+        ];
+        if let Some(model) = &self.model {
+            values.push((
+                "provider.default_model",
+                serde_json::Value::String(model.clone()).to_string(),
+            ));
+        }
+        values
+    }
+}
+
 /// The current phase of the onboarding flow.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) enum OnboardingPhase {
@@ -245,6 +404,9 @@ pub(crate) enum OnboardingPhase {
         /// Which option is highlighted (true = "Yes, log in to OpenAI").
         yes_highlighted: bool,
     },
+    /// First-run settings form (see [`SetupWizard`]). Entered after login
+    /// when the flow started without a config.toml.
+    Setup(SetupWizard),
     /// Legacy phase kept for compatibility with older replay/test fixtures.
     /// New onboarding skips explicit model selection and uses the default route;
     /// users can still run `/model` later.
@@ -330,6 +492,9 @@ impl OnboardingPendingValidation {
 #[derive(Clone, Debug)]
 pub(crate) struct OnboardingFlow {
     pub(crate) phase: OnboardingPhase,
+    /// Show the [`OnboardingPhase::Setup`] form once login completes. Set
+    /// when the flow starts on an install with no config.toml.
+    pub(crate) setup_pending: bool,
 }
 
 impl OnboardingFlow {
@@ -339,6 +504,7 @@ impl OnboardingFlow {
     pub(crate) fn begin() -> Self {
         Self {
             phase: OnboardingPhase::ModelSelect,
+            setup_pending: false,
         }
     }
 
@@ -356,7 +522,10 @@ impl OnboardingFlow {
                 yes_highlighted: true,
             },
         };
-        Self { phase }
+        Self {
+            phase,
+            setup_pending: false,
+        }
     }
 
     /// Whether the flow is actively driving the UI.
@@ -515,6 +684,7 @@ mod tests {
     fn done_phase_is_inactive() {
         let flow = OnboardingFlow {
             phase: OnboardingPhase::Done,
+            setup_pending: false,
         };
         assert!(!flow.is_active());
     }
@@ -528,6 +698,7 @@ mod tests {
                 yes_highlighted: true,
                 shown_at: past,
             },
+            setup_pending: false,
         };
         // The continue prompt now shares the longer DECISION_TIMEOUT with the
         // import and telemetry prompts (not the short AUTO_ADVANCE).
@@ -543,6 +714,7 @@ mod tests {
                 yes_highlighted: true,
                 shown_at: Instant::now(),
             },
+            setup_pending: false,
         };
         let remaining = flow.decision_seconds_remaining().unwrap();
         assert!(
@@ -551,6 +723,53 @@ mod tests {
         assert!(!flow.decision_timed_out());
     }
 
+    #[test]
+    fn setup_wizard_defaults_cycle_and_serialize() {
+        let mut wizard = SetupWizard::new(false);
+        // Continue starts focused, so one Enter accepts the defaults.
+        assert!(wizard.continue_focused);
+        assert_eq!(wizard.current_row(), None);
+        assert!(wizard.create_instructions);
+
+        wizard.cursor_down();
+        assert_eq!(wizard.current_row(), Some(SetupRow::Model));
+        let models = vec!["gpt-5.5".to_string(), "claude-opus-4-8".to_string()];
+        wizard.cycle_model(&models, true);
+        assert_eq!(wizard.model.as_deref(), Some("gpt-5.5"));
+        wizard.cycle_model(&models, false);
+        assert_eq!(wizard.model, None);
+        wizard.cycle_model(&models, false);
+        assert_eq!(wizard.model.as_deref(), Some("claude-opus-4-8"));
+
+        wizard.cursor_down();
+        wizard.toggle_current();
+        assert!(!wizard.memory);
+        wizard.cursor_up();
+        wizard.cursor_up();
+        assert!(wizard.continue_focused);
+
+        assert_eq!(
+            wizard.config_values(),
+            vec![
+                ("features.memory", "false".to_string()),
+                ("ambient.enabled", "false".to_string()),
+                ("display.centered", "false".to_string()),
+                ("provider.default_model", "\"claude-opus-4-8\"".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn setup_wizard_keeps_existing_instructions_file() {
+        let mut wizard = SetupWizard::new(true);
+        assert!(!wizard.create_instructions);
+        wizard.continue_focused = false;
+        wizard.cursor = SetupRow::ALL.len() - 1;
+        assert_eq!(wizard.current_row(), Some(SetupRow::Instructions));
+        wizard.toggle_current();
+        assert!(!wizard.create_instructions);
+    }
+
     #[test]
     fn external_oauth_present_requires_nonempty_file() {
         let dir = std::env::temp_dir().join(format!("jcode-onb-test-{}", std::process::id()));
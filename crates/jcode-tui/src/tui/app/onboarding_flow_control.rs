@@ -6,6 +6,7 @@
 
 use super::onboarding_flow::{
     ExternalCli, ImportReview, OnboardingFlow, OnboardingPendingValidation, OnboardingPhase,
+    SetupRow, SetupWizard,
 };
 use super::{App, DisplayMessage, SessionPickerMode};
 use crate::tui::session_picker::{self, SessionFilterMode, SessionPicker};
@@ -72,6 +73,11 @@ impl App {
             self.onboarding_startup_checked = true;
             return;
         }
+        // `--no-onboarding`, or a launch without a terminal on both ends.
+        if std::env::var_os("JCODE_NO_ONBOARDING").is_some() && !self.onboarding_preview_mode {
+            self.onboarding_startup_checked = true;
+            return;
+        }
         // Don't hijack a session that already has real activity (resume,
         // restored input, or a genuine conversation already on screen). These
         // are settled states, so we can commit the guard.
@@ -114,6 +120,12 @@ impl App {
             self.begin_onboarding_flow();
         } else {
             self.begin_onboarding_flow_at_login();
+            // No credentials and no config.toml: a genuinely first run, so
+            // offer the settings form once login completes.
+            let no_config = crate::config::Config::path().is_some_and(|path| !path.exists());
+            if let Some(flow) = self.onboarding_flow.as_mut() {
+                flow.setup_pending = no_config;
+            }
         }
     }
 
@@ -264,6 +276,108 @@ impl App {
         // Prompt/transcript content sharing is opt-in and off by default; we
         // intentionally don't prompt for it during onboarding.
         crate::telemetry::set_content_sharing_enabled(false);
+        if let Some(flow) = self.onboarding_flow.as_mut() {
+            if std::mem::take(&mut flow.setup_pending) {
+                let instructions_exist = crate::prompt::global_instructions_path()
+                    .map(|path| path.exists())
+                    .unwrap_or(true);
+                flow.phase = OnboardingPhase::Setup(SetupWizard::new(instructions_exist));
+                self.update_onboarding_setup_status();
+                return;
+            }
+            flow.phase = OnboardingPhase::ModelSelect;
+        }
+        self.onboarding_after_model_select();
+    }
+
+    /// Handle a key on the first-run settings form. Returns true if consumed.
+    ///   - Up / Down / k / j / Tab -> move between rows and the Continue pill
+    ///   - Left / Right / h / l / Space -> change the focused row
+    ///   - Enter -> on a row, change it; on Continue, save and move on
+    ///   - Esc -> keep the defaults and write nothing
+    fn handle_onboarding_setup_key(&mut self, code: KeyCode) -> bool {
+        let models = self.model_choice_ids();
+        let mut finish = None;
+        {
+            let Some(OnboardingPhase::Setup(wizard)) =
+                self.onboarding_flow.as_mut().map(|flow| &mut flow.phase)
+            else {
+                return false;
+            };
+            match code {
+                KeyCode::Up | KeyCode::Char('k') | KeyCode::BackTab => wizard.cursor_up(),
+                KeyCode::Down | KeyCode::Char('j') | KeyCode::Tab => wizard.cursor_down(),
+                KeyCode::Left | KeyCode::Char('h') | KeyCode::Right | KeyCode::Char('l') => {
+                    let forward = matches!(code, KeyCode::Right | KeyCode::Char('l'));
+                    match wizard.current_row() {
+                        Some(SetupRow::Model) => wizard.cycle_model(&models, forward),
+                        Some(_) => wizard.toggle_current(),
+                        None => {}
+                    }
+                }
+                KeyCode::Enter | KeyCode::Char(' ') => match wizard.current_row() {
+                    Some(SetupRow::Model) => wizard.cycle_model(&models, true),
+                    Some(_) => wizard.toggle_current(),
+                    None if code == KeyCode::Enter => finish = Some(true),
+                    None => {}
+                },
+                KeyCode::Esc => finish = Some(false),
+                _ => return false,
+            }
+        }
+        match finish {
+            Some(save) => self.onboarding_finish_setup(save),
+            None => self.update_onboarding_setup_status(),
+        }
+        true
+    }
+
+    /// Refresh the status notice for the settings form.
+    fn update_onboarding_setup_status(&mut self) {
+        self.set_status_notice(
+            "First-run settings - arrows move, Left/Right change, Enter on Continue saves",
+        );
+    }
+
+    /// Leave the settings form. With `save`, write the choices to a commented
+    /// config.toml (and JCODE.md when chosen); either way continue into the
+    /// usual post-login flow.
+    fn onboarding_finish_setup(&mut self, save: bool) {
+        let Some(OnboardingPhase::Setup(wizard)) = self.onboarding_phase().cloned() else {
+            return;
+        };
+        if save {
+            match crate::config::Config::set_file_keys(&wizard.config_values()) {
+                Ok(path) => self.push_display_message(DisplayMessage::system(format!(
+                    "Saved your settings to {}. Run /config to review them.",
+                    path.display()
+                ))),
+                Err(err) => self.push_display_message(DisplayMessage::error(format!(
+                    "Could not write config.toml: {err}"
+                ))),
+            }
+            if wizard.create_instructions {
+                match crate::prompt::create_global_instructions_file() {
+                    Ok(Some(path)) => self.push_display_message(DisplayMessage::system(format!(
+                        "Created {}. Whatever you write there is sent with every session.",
+                        path.display()
+                    ))),
+                    Ok(None) => {}
+                    Err(err) => self.push_display_message(DisplayMessage::error(format!(
+                        "Could not create JCODE.md: {err}"
+                    ))),
+                }
+            }
+            // Apply the choices to this session too; the config reload covers
+            // everything else.
+            self.set_centered(wizard.centered);
+            if let Some(model) = wizard.model.as_deref()
+                && !self.is_remote
+                && self.provider.set_model(model).is_ok()
+            {
+                self.finalize_model_switch(model);
+            }
+        }
         if let Some(flow) = self.onboarding_flow.as_mut() {
             flow.phase = OnboardingPhase::ModelSelect;
         }
@@ -368,6 +482,7 @@ impl App {
             && self.inline_interactive_state.is_none()
             && self.session_picker_overlay.is_none()
             && self.login_picker_overlay.is_none()
+            && self.file_picker_overlay.is_none()
             && self.account_picker_overlay.is_none()
             && matches!(
                 self.onboarding_phase(),
@@ -440,6 +555,12 @@ impl App {
             Some(OnboardingPhase::ContinuePrompt { .. }) => {
                 self.handle_onboarding_continue_choice_key(code)
             }
+            Some(OnboardingPhase::Setup(_)) => {
+                if self.inline_interactive_state.is_some() {
+                    return false;
+                }
+                self.handle_onboarding_setup_key(code)
+            }
             _ => false,
         }
     }
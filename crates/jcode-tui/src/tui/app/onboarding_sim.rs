@@ -15,7 +15,9 @@
 //! (`onboarding_welcome_kind`), so what you see here is what first-run users see.
 
 use super::App;
-use super::onboarding_flow::{ExternalCli, ImportReview, OnboardingFlow, OnboardingPhase};
+use super::onboarding_flow::{
+    ExternalCli, ImportReview, OnboardingFlow, OnboardingPhase, SetupWizard,
+};
 use crossterm::event::{KeyCode, KeyModifiers};
 use std::time::Instant;
 
@@ -125,6 +127,10 @@ impl App {
                     shown_at: Instant::now(),
                 },
             },
+            SimScreen {
+                title: "First-run settings (no config.toml yet)",
+                phase: OnboardingPhase::Setup(SetupWizard::new(false)),
+            },
             SimScreen {
                 title: "Suggestions (resting / all set)",
                 phase: OnboardingPhase::Suggestions,
@@ -150,6 +156,7 @@ impl App {
         let screen = screens.remove(index);
         self.onboarding_flow = Some(OnboardingFlow {
             phase: screen.phase,
+            setup_pending: false,
         });
     }
 
@@ -211,6 +218,8 @@ impl App {
                     // current row instead of a Yes/No pill.
                     review.set_current(yes);
                 }
+                // On the settings form, preview flipping the focused row.
+                OnboardingPhase::Setup(wizard) => wizard.toggle_current(),
                 _ => {}
             }
         }
@@ -218,19 +227,27 @@ impl App {
 
     /// Move the import checkbox cursor (only meaningful on the import screen).
     fn onboarding_sim_move_cursor(&mut self, down: bool) -> bool {
-        if let Some(flow) = self.onboarding_flow.as_mut()
-            && let OnboardingPhase::Login {
+        match self.onboarding_flow.as_mut().map(|flow| &mut flow.phase) {
+            Some(OnboardingPhase::Login {
                 import: Some(review),
-            } = &mut flow.phase
-        {
-            if down {
-                review.cursor_down();
-            } else {
-                review.cursor_up();
+            }) => {
+                if down {
+                    review.cursor_down();
+                } else {
+                    review.cursor_up();
+                }
+                true
             }
-            return true;
+            Some(OnboardingPhase::Setup(wizard)) => {
+                if down {
+                    wizard.cursor_down();
+                } else {
+                    wizard.cursor_up();
+                }
+                true
+            }
+            _ => false,
         }
-        false
     }
 
     /// Intercept keys while the simulator is active. Returns true when consumed.
@@ -254,7 +271,7 @@ impl App {
         }
         let on_import_screen = matches!(
             self.onboarding_flow.as_ref().map(|f| &f.phase),
-            Some(OnboardingPhase::Login { import: Some(_) })
+            Some(OnboardingPhase::Login { import: Some(_) } | OnboardingPhase::Setup(_))
         );
         match code {
             KeyCode::Esc | KeyCode::Char('q') => {
@@ -0,0 +1,259 @@
+//! The shared permission inbox: a status-line badge plus a Ctrl+P panel
+//! listing every pending approval, from ambient cycles and from sessions.
+//!
+//! Remote clients receive the list as `ServerEvent::PermissionInbox`; local
+//! mode reads it from the safety queue on tick. Answers go through
+//! `safety::record_decision` (directly, or via the server), so every other
+//! surface sees them within a second.
+
+use super::App;
+use crate::protocol::PermissionInboxEntry;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const LOCAL_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const PANEL_TITLE: &str = "Permission inbox";
+const PANEL_HINT: &str = "↑↓ select · a allow · d deny · Esc close";
+
+/// What a key press did while the inbox panel was open.
+pub(super) enum PermissionInboxKey {
+    /// The panel is closed or the key belongs to someone else.
+    Ignored,
+    Handled,
+    Resolve {
+        request_id: String,
+        approved: bool,
+    },
+}
+
+impl App {
+    /// Replace the inbox with a fresh snapshot. Returns whether anything
+    /// changed.
+    pub(super) fn apply_permission_inbox(&mut self, entries: Vec<PermissionInboxEntry>) -> bool {
+        if entries == self.permission_inbox {
+            return false;
+        }
+        let listed = |list: &[PermissionInboxEntry], id: &str| {
+            list.iter().any(|entry| entry.request_id == id)
+        };
+
+        // Our own y/n prompt was answered from another surface.
+        if let Some(pending) = self.pending_safe_mode_approval.clone()
+            && listed(&self.permission_inbox, &pending)
+            && !listed(&entries, &pending)
+        {
+            self.pending_safe_mode_approval = None;
+            self.set_status_notice("Approval answered elsewhere");
+        }
+        if let Some(pending) = self.pending_ask_user.clone()
+            && listed(&self.permission_inbox, &pending)
+            && !listed(&entries, &pending)
+        {
+            self.pending_ask_user = None;
+            self.set_status_notice("Question answered elsewhere");
+        }
+
+        let new_from_elsewhere = entries
+            .iter()
+            .filter(|entry| !listed(&self.permission_inbox, &entry.request_id))
+            .filter(|entry| self.pending_safe_mode_approval.as_deref() != Some(&entry.request_id))
+            .filter(|entry| self.pending_ask_user.as_deref() != Some(&entry.request_id))
+            .count();
+        self.permission_inbox = entries;
+        if new_from_elsewhere > 0 && self.permission_inbox_selected.is_none() {
+            let count = self.permission_inbox.len();
+            self.set_status_notice(format!(
+                "{} permission request{} pending · Ctrl+P",
+                count,
+                if count == 1 { "" } else { "s" }
+            ));
+        }
+        self.refresh_permission_inbox_view();
+        true
+    }
+
+    /// Local mode: re-read the safety queue at most once a second.
+    pub(super) fn poll_local_permission_inbox(&mut self) -> bool {
+        let now = Instant::now();
+        if self
+            .last_permission_inbox_poll
+            .is_some_and(|last| now.duration_since(last) < LOCAL_POLL_INTERVAL)
+        {
+            return false;
+        }
+        self.last_permission_inbox_poll = Some(now);
+        let entries = crate::safety::inbox()
+            .iter()
+            .map(crate::safety::PermissionRequest::to_inbox_entry)
+            .collect();
+        self.apply_permission_inbox(entries)
+    }
+
+    pub(super) fn permission_inbox_open(&self) -> bool {
+        self.permission_inbox_selected.is_some() && self.inline_view_state.is_some()
+    }
+
+    /// Ctrl+P with requests pending: open the panel, or close it if open.
+    pub(super) fn toggle_permission_inbox(&mut self) {
+        if self.permission_inbox_open() {
+            self.close_permission_inbox();
+        } else if self.permission_inbox.is_empty() {
+            self.set_status_notice("No pending permission requests");
+        } else {
+            self.permission_inbox_selected = Some(0);
+            self.refresh_permission_inbox_view();
+        }
+    }
+
+    fn close_permission_inbox(&mut self) {
+        self.permission_inbox_selected = None;
+        self.inline_view_state = None;
+    }
+
+    pub(super) fn handle_permission_inbox_key(
+        &mut self,
+        code: KeyCode,
+        modifiers: KeyModifiers,
+    ) -> PermissionInboxKey {
+        let Some(selected) = self
+            .permission_inbox_selected
+            .filter(|_| self.permission_inbox_open())
+        else {
+            return PermissionInboxKey::Ignored;
+        };
+        if modifiers.contains(KeyModifiers::CONTROL) {
+            return PermissionInboxKey::Ignored;
+        }
+        let resolve = |approved: bool| {
+            self.permission_inbox
+                .get(selected)
+                .map(|entry| PermissionInboxKey::Resolve {
+                    request_id: entry.request_id.clone(),
+                    approved,
+                })
+                .unwrap_or(PermissionInboxKey::Handled)
+        };
+        match code {
+            KeyCode::Char('a') | KeyCode::Char('y') => return resolve(true),
+            KeyCode::Char('d') | KeyCode::Char('n') => return resolve(false),
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.permission_inbox_selected = Some(selected.saturating_sub(1));
+                self.refresh_permission_inbox_view();
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.permission_inbox_selected = Some(selected + 1);
+                self.refresh_permission_inbox_view();
+            }
+            KeyCode::Esc => self.close_permission_inbox(),
+            _ => {}
+        }
+        PermissionInboxKey::Handled
+    }
+
+    /// Reflect an answer given from this client before the next snapshot
+    /// arrives.
+    pub(super) fn note_permission_resolved(&mut self, request_id: &str, approved: bool) {
+        self.permission_inbox
+            .retain(|entry| entry.request_id != request_id);
+        if self.pending_safe_mode_approval.as_deref() == Some(request_id) {
+            self.pending_safe_mode_approval = None;
+        }
+        if self.pending_ask_user.as_deref() == Some(request_id) {
+            self.pending_ask_user = None;
+        }
+        self.set_status_notice(if approved {
+            "Permission allowed"
+        } else {
+            "Permission denied"
+        });
+        self.refresh_permission_inbox_view();
+    }
+
+    /// Local mode: record the answer straight into the safety queue.
+    pub(super) fn resolve_permission_locally(&mut self, request_id: &str, approved: bool) {
+        match crate::safety::record_decision(request_id, approved, "tui", None) {
+            Ok(()) => self.note_permission_resolved(request_id, approved),
+            Err(error) => self.set_status_notice(format!("Permission inbox: {}", error)),
+        }
+    }
+
+    fn refresh_permission_inbox_view(&mut self) {
+        let Some(selected) = self.permission_inbox_selected else {
+            return;
+        };
+        if self.permission_inbox.is_empty() {
+            self.close_permission_inbox();
+            return;
+        }
+        let selected = selected.min(self.permission_inbox.len() - 1);
+        self.permission_inbox_selected = Some(selected);
+        self.inline_view_state = Some(permission_inbox_view(
+            &self.permission_inbox,
+            selected,
+            unix_now_ms(),
+        ));
+    }
+}
+
+fn permission_inbox_view(
+    entries: &[PermissionInboxEntry],
+    selected: usize,
+    now_ms: u64,
+) -> crate::tui::InlineViewState {
+    let mut lines = Vec::with_capacity(entries.len() * 2);
+    for (index, entry) in entries.iter().enumerate() {
+        let marker = if index == selected { "▸" } else { " " };
+        lines.push(format!(
+            "{} {}: {}",
+            marker, entry.action, entry.description
+        ));
+        lines.push(format!("    {}", entry.status_line(now_ms)));
+    }
+    crate::tui::InlineViewState {
+        title: format!("{} ({})", PANEL_TITLE, entries.len()),
+        status: Some(PANEL_HINT.to_string()),
+        lines,
+    }
+}
+
+fn unix_now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::PermissionOrigin;
+
+    fn entry(id: &str, origin: PermissionOrigin) -> PermissionInboxEntry {
+        PermissionInboxEntry {
+            request_id: id.to_string(),
+            origin,
+            origin_id: Some("abc".to_string()),
+            action: "bash".to_string(),
+            description: "rm -rf build".to_string(),
+            created_at_ms: 1_000,
+            expires_at_ms: Some(1_000 + 15 * 60 * 1000),
+        }
+    }
+
+    #[test]
+    fn view_marks_the_selected_entry_and_shows_origin_age_and_expiry() {
+        let entries = vec![
+            entry("a", PermissionOrigin::Ambient),
+            entry("b", PermissionOrigin::Session),
+        ];
+        let view = permission_inbox_view(&entries, 1, 1_000 + 2 * 60 * 1000);
+        assert_eq!(view.title, "Permission inbox (2)");
+        assert_eq!(view.lines[0], "  bash: rm -rf build");
+        assert_eq!(
+            view.lines[1],
+            "    ambient cycle abc · 2m old · expires in 13m"
+        );
+        assert_eq!(view.lines[2], "▸ bash: rm -rf build");
+        assert_eq!(view.lines[3], "    session abc · 2m old · expires in 13m");
+    }
+}
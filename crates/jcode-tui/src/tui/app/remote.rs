@@ -51,9 +51,10 @@ use workspace::{handle_workspace_command, handle_workspace_navigation_key};
 // through the `remote` facade instead of private submodule paths.
 #[allow(unused_imports)]
 pub(super) use input_dispatch::{
-    apply_remote_transcript_event, apply_transcript_event, begin_remote_send,
-    begin_remote_split_launch, finish_remote_split_launch, history_matches_pending_startup_prompt,
-    route_prepared_input_to_new_remote_session, submit_prepared_remote_input,
+    apply_remote_transcript_event, apply_transcript_event, begin_remote_focus_send,
+    begin_remote_send, begin_remote_split_launch, finish_remote_split_launch,
+    history_matches_pending_startup_prompt, route_prepared_input_to_new_remote_session,
+    submit_prepared_remote_input,
 };
 pub(super) use key_handling::{
     handle_remote_char_input, handle_remote_key, handle_remote_key_event, send_interleave_now,
@@ -97,10 +98,23 @@ pub(super) async fn handle_tick(app: &mut App, remote: &mut RemoteConnection) ->
     }
 
     needs_redraw |= app.refresh_todos_view_if_needed();
+    needs_redraw |= app.refresh_todo_checklist();
     needs_redraw |= app.refresh_side_panel_linked_content_if_due();
     needs_redraw |= app.poll_model_picker_load();
     needs_redraw |= app.poll_session_picker_load();
+    needs_redraw |= app.poll_file_picker_load();
     needs_redraw |= app.onboarding_tick();
+    app.refresh_terminal_title_state();
+    needs_redraw |= app.write_mirror_if_due();
+    if app.mirror_sync_pending && !app.is_processing {
+        app.mirror_sync_pending = false;
+        if let Err(error) = remote.set_mirror(app.session.mirror_path.clone()).await {
+            app.push_display_message(DisplayMessage::error(format!(
+                "Failed to save mirror setting: {}",
+                error
+            )));
+        }
+    }
 
     let _ = check_debug_command(app, remote).await;
 
@@ -499,6 +513,14 @@ pub(super) async fn handle_bus_event(
             true
         }
         Ok(BusEvent::MermaidRenderCompleted) => true,
+        Ok(BusEvent::MemoryPinDrafted(event)) => {
+            app.handle_memory_pin_drafted(event);
+            true
+        }
+        Ok(BusEvent::MemoryPinSaved(event)) => {
+            app.handle_memory_pin_saved(event);
+            true
+        }
         Ok(BusEvent::UsageReportProgress(progress)) => {
             app.handle_usage_report_progress(progress);
             true
@@ -1018,6 +1040,10 @@ pub(super) async fn process_remote_followups(app: &mut App, remote: &mut RemoteC
     // history would stay unloaded forever, and every typed prompt would be stuck
     // behind "Loading session..." until the user restarted (server/client binary
     // mismatch reload-handoff stall).
+    app.refresh_pending_migration();
+    if let Some(migration) = app.take_due_migration() {
+        app.begin_server_migration(migration);
+    }
     if app.pending_server_reload && !app.is_processing {
         dispatch_pending_server_reload(app, remote).await;
         return;
@@ -1190,6 +1216,12 @@ pub(super) async fn process_remote_followups(app: &mut App, remote: &mut RemoteC
         return;
     }
 
+    // Follow-ups queued during a rate-limit wait go out after the scheduled
+    // retry, not ahead of it into the same limit window.
+    if app.rate_limit_reset.is_some() && app.rate_limit_pending_message.is_some() {
+        return;
+    }
+
     if let Some(interleave_msg) = app.interleave_message.take() {
         if !interleave_msg.trim().is_empty() {
             app.push_display_message(DisplayMessage {
@@ -1471,6 +1503,7 @@ fn handle_disconnected_local_command(app: &mut App, trimmed: &str) -> bool {
         || super::commands::handle_config_command(app, trimmed)
         || super::commands::handle_log_command(app, trimmed)
         || super::commands::handle_diff_command(app, trimmed)
+        || super::file_view::handle_view_command(app, trimmed)
         || super::commands::handle_debug_command(app, trimmed)
         || super::commands::handle_model_command(app, trimmed)
         || super::commands::handle_usage_command(app, trimmed)
@@ -1499,6 +1532,22 @@ fn queue_message_for_reconnect(app: &mut App) {
         return;
     }
 
+    if app.memory_pin_editing() {
+        let prepared = input::take_prepared_input(app);
+        app.submit_memory_pin(prepared.expanded);
+        return;
+    }
+
+    if app.aside_summary_editing() {
+        app.set_status_notice("Reconnect to hand back the aside summary");
+        return;
+    }
+
+    if app.handoff_editing() {
+        app.set_status_notice("Reconnect to create the handoff session");
+        return;
+    }
+
     if trimmed.starts_with('/') {
         if handle_disconnected_local_command(app, &trimmed) {
             return;
@@ -1546,7 +1595,9 @@ fn handle_disconnected_key_internal(
     let mut modifiers = modifiers;
     ctrl_bracket_fallback_to_esc(&mut code, &mut modifiers);
 
-    if input::handle_navigation_shortcuts(app, code, modifiers) {
+    if input::handle_navigation_shortcuts(app, code, modifiers)
+        || input::handle_composer_edit_key(app, code, modifiers)
+    {
         return Ok(());
     }
 
@@ -1625,6 +1676,21 @@ fn handle_disconnected_key_internal(
         return Ok(());
     }
 
+    if app.image_open_key_matches(code, modifiers) {
+        app.open_focused_image();
+        return Ok(());
+    }
+
+    if app.remember_message_key_matches(code, modifiers) {
+        app.start_memory_pin(None);
+        return Ok(());
+    }
+
+    if app.copy_message_key_matches(code, modifiers) {
+        app.copy_focused_message();
+        return Ok(());
+    }
+
     if code == KeyCode::Enter && modifiers.intersects(KeyModifiers::SHIFT | KeyModifiers::ALT) {
         input::insert_input_text(app, "\n");
         return Ok(());
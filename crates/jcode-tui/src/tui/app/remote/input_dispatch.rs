@@ -22,12 +22,62 @@ pub(in crate::tui::app) async fn begin_remote_send(
             system_reminder.clone(),
         )
         .await?;
+    note_remote_send_started(
+        app,
+        remote,
+        msg_id,
+        PendingRemoteMessage {
+            content,
+            images,
+            is_system,
+            system_reminder,
+            auto_retry,
+            retry_attempts,
+            retry_at: None,
+        },
+    );
+    Ok(msg_id)
+}
+
+/// Send a `/focus` task with its wall-clock budget. It is not auto-retried:
+/// a resend runs as a plain turn, without the budget.
+pub(in crate::tui::app) async fn begin_remote_focus_send(
+    app: &mut App,
+    remote: &mut RemoteConnection,
+    task: String,
+    budget_secs: u64,
+) -> Result<u64> {
+    let msg_id = remote.send_focus_message(task.clone(), budget_secs).await?;
+    note_remote_send_started(
+        app,
+        remote,
+        msg_id,
+        PendingRemoteMessage {
+            content: task,
+            images: vec![],
+            is_system: false,
+            system_reminder: None,
+            auto_retry: false,
+            retry_attempts: 0,
+            retry_at: None,
+        },
+    );
+    Ok(msg_id)
+}
+
+fn note_remote_send_started(
+    app: &mut App,
+    remote: &mut RemoteConnection,
+    msg_id: u64,
+    pending: PendingRemoteMessage,
+) {
+    let is_system = pending.is_system;
     app.current_message_id = Some(msg_id);
     app.is_processing = true;
     app.status = ProcessingStatus::Sending;
     app.status_detail = None;
     app.processing_started = Some(Instant::now());
-    if !content.is_empty() {
+    if !pending.content.is_empty() {
         if is_system {
             app.visible_turn_started.get_or_insert_with(Instant::now);
         } else {
@@ -40,19 +90,10 @@ pub(in crate::tui::app) async fn begin_remote_send(
     app.thought_line_inserted = false;
     app.thinking_prefix_emitted = false;
     app.thinking_buffer.clear();
-    app.rate_limit_pending_message = Some(PendingRemoteMessage {
-        content,
-        images,
-        is_system,
-        system_reminder,
-        auto_retry,
-        retry_attempts,
-        retry_at: None,
-    });
+    app.rate_limit_pending_message = Some(pending);
     app.autoreview_after_current_turn = !is_system;
     app.autojudge_after_current_turn = !is_system;
     remote.reset_call_output_tokens_seen();
-    Ok(msg_id)
 }
 
 pub(in crate::tui::app) fn restore_prepared_remote_input(
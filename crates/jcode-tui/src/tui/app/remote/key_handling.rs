@@ -1,7 +1,13 @@
 use super::*;
+use crate::protocol::CompareAction;
+use crate::tui::InterjectionPriority;
 use crate::tui::app as app_mod;
 use crate::tui::app::PendingRemoteRewindNotice;
+use crate::tui::app::compare::CompareKey;
+use crate::tui::app::interjection::InterjectionKey;
+use crate::tui::app::permission_inbox::PermissionInboxKey;
 use crate::tui::core;
+use crate::tui::line_editor::EditCommand;
 
 pub(in crate::tui::app) fn handle_remote_char_input(app: &mut App, c: char) {
     input::handle_text_input(app, &c.to_string());
@@ -31,6 +37,31 @@ pub(in crate::tui::app) async fn send_interleave_now(
     }
 }
 
+/// Deliver an inline interjection as a soft interrupt with its priority.
+async fn send_interjection(
+    app: &mut App,
+    content: String,
+    priority: InterjectionPriority,
+    remote: &mut RemoteConnection,
+) {
+    let msg_clone = content.clone();
+    match remote.soft_interrupt(content, priority.is_urgent()).await {
+        Err(e) => {
+            app.push_display_message(DisplayMessage::error(format!(
+                "Failed to send interjection: {}",
+                e
+            )));
+        }
+        Ok(request_id) => {
+            app.track_pending_soft_interrupt(request_id, msg_clone);
+            app.set_status_notice(match priority {
+                InterjectionPriority::Normal => "↳ Interjection sent",
+                InterjectionPriority::Urgent => "↯ Urgent interjection sent",
+            });
+        }
+    }
+}
+
 pub(in crate::tui::app) async fn handle_remote_update_command(
     app: &mut App,
     remote: &mut RemoteConnection,
@@ -202,6 +233,274 @@ async fn handle_remote_rewind_command(
     Ok(true)
 }
 
+async fn handle_remote_focus_command(
+    app: &mut App,
+    remote: &mut RemoteConnection,
+    command: Result<app_mod::commands::FocusCommand, String>,
+) -> Result<()> {
+    let command = match command {
+        Ok(command) => command,
+        Err(error) => {
+            app.push_display_message(DisplayMessage::error(error));
+            return Ok(());
+        }
+    };
+    if app.is_processing {
+        app.push_display_message(DisplayMessage::error(
+            "Finish or interrupt the current turn before starting a focus run.".to_string(),
+        ));
+        return Ok(());
+    }
+    app.commit_pending_streaming_assistant_message();
+    app.push_display_message(DisplayMessage::user(command.task.clone()));
+    app.set_status_notice(format!(
+        "Focus: {} budget",
+        app_mod::commands::format_focus_budget(command.budget_secs)
+    ));
+    let _ = begin_remote_focus_send(app, remote, command.task, command.budget_secs).await;
+    Ok(())
+}
+
+async fn handle_remote_plan_command(
+    app: &mut App,
+    remote: &mut RemoteConnection,
+    command: app_mod::commands::PlanCommand,
+) -> Result<()> {
+    use app_mod::commands::PlanCommand;
+
+    if command == PlanCommand::Status {
+        app.push_display_message(DisplayMessage::system(app.plan_status_message()));
+        return Ok(());
+    }
+    if app.is_processing {
+        app.push_display_message(DisplayMessage::error(
+            app_mod::commands::PLAN_BUSY_NOTICE.to_string(),
+        ));
+        return Ok(());
+    }
+    match command {
+        PlanCommand::Start { goal } => {
+            if !app.plan_mode_active() {
+                remote
+                    .set_feature(crate::protocol::FeatureToggle::Plan, true)
+                    .await?;
+                app.set_plan_mode_active(true);
+            }
+            app.set_status_notice("Plan mode: ON");
+            app.push_display_message(DisplayMessage::system(
+                app_mod::commands::plan_launch_notice(goal.as_deref()),
+            ));
+            let prompt = app_mod::commands::build_plan_prompt(goal.as_deref());
+            let _ = begin_remote_send(app, remote, prompt, vec![], true, None, true, 0).await;
+        }
+        PlanCommand::Off => {
+            remote
+                .set_feature(crate::protocol::FeatureToggle::Plan, false)
+                .await?;
+            app.set_plan_mode_active(false);
+            app.set_status_notice("Plan mode: OFF");
+            app.push_display_message(DisplayMessage::system("Plan mode off.".to_string()));
+        }
+        PlanCommand::Decide { decision, feedback } => {
+            match app.apply_plan_decision(decision, feedback.clone()) {
+                Ok(outcome) => {
+                    remote.plan_decision(decision, feedback).await?;
+                    app.push_display_message(DisplayMessage::system(outcome.notice));
+                    if let Some(prompt) = outcome.prompt {
+                        let _ = begin_remote_send(app, remote, prompt, vec![], true, None, true, 0)
+                            .await;
+                    }
+                }
+                Err(error) => app.push_display_message(DisplayMessage::error(error)),
+            }
+        }
+        PlanCommand::Status => {}
+    }
+    Ok(())
+}
+
+async fn handle_remote_aside_command(
+    app: &mut App,
+    remote: &mut RemoteConnection,
+    command: app_mod::commands::AsideCommand,
+) -> Result<()> {
+    use crate::protocol::AsideAction;
+    use app_mod::commands::AsideCommand;
+
+    if command == AsideCommand::Status {
+        app.push_display_message(DisplayMessage::system(app.aside_status_message()));
+        return Ok(());
+    }
+    if app.is_processing {
+        app.push_display_message(DisplayMessage::error(
+            app_mod::commands::ASIDE_BUSY_NOTICE.to_string(),
+        ));
+        return Ok(());
+    }
+    let open = app.session.active_aside().is_some();
+    match command {
+        AsideCommand::Start { prompt } => {
+            if !open {
+                remote.aside(AsideAction::Start).await?;
+                app.note_aside_started();
+            }
+            if let Some(prompt) = prompt {
+                app.push_display_message(DisplayMessage::user(prompt.clone()));
+                let _ = begin_remote_send(app, remote, prompt, vec![], false, None, false, 0).await;
+            }
+        }
+        _ if !open => {
+            app.push_display_message(DisplayMessage::error(
+                "No aside is open. Use /aside [question] to start one.".to_string(),
+            ));
+        }
+        AsideCommand::End => {
+            if app.pending_aside_summary.is_some() {
+                app.set_status_notice("Aside → summary already in progress");
+                return Ok(());
+            }
+            remote.aside(AsideAction::Summarize).await?;
+            app.pending_aside_summary = Some(app_mod::commands::PendingAsideSummary::Drafting);
+            app.set_status_notice("Aside → drafting summary…");
+        }
+        AsideCommand::Discard => {
+            remote.aside(AsideAction::Close { summary: None }).await?;
+            app.note_aside_closed(None);
+        }
+        AsideCommand::Status => {}
+    }
+    Ok(())
+}
+
+async fn handle_remote_compare_command(
+    app: &mut App,
+    remote: &mut RemoteConnection,
+    command: Result<app_mod::commands::CompareCommand, String>,
+) -> Result<()> {
+    let command = match command {
+        Ok(command) => command,
+        Err(usage) => {
+            app.push_display_message(DisplayMessage::error(usage));
+            return Ok(());
+        }
+    };
+    if app.compare_blocked() {
+        return Ok(());
+    }
+    remote
+        .compare(CompareAction::Run {
+            models: command.models.clone(),
+            prompt: command.prompt.clone(),
+        })
+        .await?;
+    app.note_compare_started(&command);
+    Ok(())
+}
+
+async fn handle_remote_profile_command(
+    app: &mut App,
+    remote: &mut RemoteConnection,
+    command: app_mod::commands::ProfileCommand,
+) -> Result<()> {
+    use app_mod::commands::ProfileCommand;
+
+    let profile = match command {
+        ProfileCommand::Status => {
+            app.push_display_message(DisplayMessage::system(app.profile_status_message()));
+            return Ok(());
+        }
+        ProfileCommand::Off => None,
+        ProfileCommand::Set(name) => {
+            if let Err(error) = app.validate_profile_selection(&name) {
+                app.push_display_message(DisplayMessage::error(error));
+                return Ok(());
+            }
+            Some(name)
+        }
+    };
+    // The server applies the switch once the current turn releases the agent.
+    remote.set_profile(profile.clone()).await?;
+    app.note_profile_changed(profile);
+    Ok(())
+}
+
+async fn handle_remote_queue_command(
+    app: &mut App,
+    remote: &mut RemoteConnection,
+    command: Result<app_mod::commands::QueueCommand, String>,
+) -> Result<()> {
+    use app_mod::commands::QueueCommand;
+
+    let command = match command {
+        Ok(command) => command,
+        Err(usage) => {
+            app.push_display_message(DisplayMessage::error(usage));
+            return Ok(());
+        }
+    };
+    match app_mod::commands::edit_local_queue(&mut app.queued_messages, &command) {
+        Some(Ok(())) => app.show_turn_queue(),
+        Some(Err(error)) => app.push_display_message(DisplayMessage::error(error)),
+        None => {
+            if let Err(error) =
+                app_mod::commands::check_remote_edit(&app.remote_turn_queue, &command)
+            {
+                app.push_display_message(DisplayMessage::error(error));
+                return Ok(());
+            }
+            match command {
+                QueueCommand::List => remote.turn_queue().await?,
+                QueueCommand::Remove(item_id) => remote.turn_queue_remove(&item_id).await?,
+                QueueCommand::Move { item_id, position } => {
+                    remote.turn_queue_move(&item_id, position).await?
+                }
+            }
+            // Print the queue when the server's snapshot arrives.
+            app.turn_queue_display_pending = true;
+        }
+    }
+    Ok(())
+}
+
+async fn handle_remote_safe_command(
+    app: &mut App,
+    remote: &mut RemoteConnection,
+    command: app_mod::commands::SafeCommand,
+) -> Result<()> {
+    use app_mod::commands::SafeCommand;
+
+    let enabled = match command {
+        SafeCommand::Status => {
+            app.push_display_message(DisplayMessage::system(app.safe_status_message()));
+            return Ok(());
+        }
+        SafeCommand::Off { confirmed: false } => {
+            app.push_display_message(DisplayMessage::system(
+                app_mod::commands::SAFE_OFF_CONFIRM_HINT,
+            ));
+            return Ok(());
+        }
+        SafeCommand::Off { confirmed: true } => false,
+        SafeCommand::On => true,
+    };
+    remote.set_safe_mode(enabled).await?;
+    app.note_safe_mode_changed(enabled);
+    Ok(())
+}
+
+async fn handle_remote_instructions_command(
+    app: &mut App,
+    remote: &mut RemoteConnection,
+    command: app_mod::commands::InstructionsCommand,
+) -> Result<()> {
+    let Some(text) = app.instructions_update(command) else {
+        return Ok(());
+    };
+    remote.set_instructions(text.clone()).await?;
+    app.note_instructions_changed(text);
+    Ok(())
+}
+
 impl App {
     pub(super) async fn handle_account_picker_command_remote(
         &mut self,
@@ -300,6 +599,10 @@ async fn handle_remote_key_internal(
         return app.handle_login_picker_key(code, modifiers);
     }
 
+    if app.file_picker_overlay.is_some() {
+        return app.handle_file_picker_key(code, modifiers);
+    }
+
     if app.account_picker_overlay.is_some() {
         if let Some(command) = app.next_account_picker_action(code, modifiers)? {
             app.handle_account_picker_command_remote(remote, command)
@@ -308,6 +611,44 @@ async fn handle_remote_key_internal(
         return Ok(());
     }
 
+    if let Some(key) = app.handle_compare_view_key(code) {
+        if let CompareKey::Keep {
+            prompt,
+            chosen,
+            runs,
+        } = key
+        {
+            app.note_compare_kept(&prompt, chosen, &runs);
+            let action = CompareAction::Commit {
+                prompt,
+                chosen,
+                runs,
+            };
+            if let Err(error) = remote.compare(action).await {
+                app.push_display_message(DisplayMessage::error(format!(
+                    "Compare: failed to keep the answer: {}",
+                    error
+                )));
+            }
+        }
+        return Ok(());
+    }
+
+    match app.handle_permission_inbox_key(code, modifiers) {
+        PermissionInboxKey::Ignored => {}
+        PermissionInboxKey::Handled => return Ok(()),
+        PermissionInboxKey::Resolve {
+            request_id,
+            approved,
+        } => {
+            match remote.send_permission_decision(&request_id, approved).await {
+                Ok(()) => app.note_permission_resolved(&request_id, approved),
+                Err(error) => app.set_status_notice(format!("Permission inbox: {}", error)),
+            }
+            return Ok(());
+        }
+    }
+
     if let Some(ref picker) = app.inline_interactive_state
         && !picker.preview
     {
@@ -355,6 +696,35 @@ async fn handle_remote_key_internal(
         return Ok(());
     }
 
+    if app.image_open_key_matches(code, modifiers) {
+        app.open_focused_image();
+        return Ok(());
+    }
+
+    if app.remember_message_key_matches(code, modifiers) {
+        app.start_memory_pin(None);
+        return Ok(());
+    }
+
+    if app.copy_message_key_matches(code, modifiers) {
+        app.copy_focused_message();
+        return Ok(());
+    }
+
+    if app.file_picker_key_matches(code, modifiers) {
+        app.open_file_picker();
+        return Ok(());
+    }
+
+    match app.handle_interjection_key(code, modifiers) {
+        InterjectionKey::Send(content, priority) => {
+            send_interjection(app, content, priority, remote).await;
+            return Ok(());
+        }
+        InterjectionKey::Handled => return Ok(()),
+        InterjectionKey::Unhandled => {}
+    }
+
     if handle_workspace_navigation_key(app, code, modifiers, remote).await? {
         return Ok(());
     }
@@ -410,6 +780,10 @@ async fn handle_remote_key_internal(
         app.toggle_typing_scroll_lock();
         return Ok(());
     }
+    if app.toggle_keys.todo_checklist.matches(code, modifiers) {
+        app.toggle_todo_checklist();
+        return Ok(());
+    }
     if app.centered_toggle_keys.matches(code, modifiers) {
         app.record_keybinding_fast(crate::tui::app::shortcut_hints::LearnableAction::Alignment);
         app.toggle_centered_mode();
@@ -436,6 +810,11 @@ async fn handle_remote_key_internal(
                 app.cursor_pos = app.find_word_boundary_back();
                 return Ok(());
             }
+            KeyCode::Char('e') if app.mcp_call_remaining().is_some() => {
+                remote.cancel_mcp_call().await?;
+                app.set_status_notice("Cancelling MCP call...");
+                return Ok(());
+            }
             // Alt/Option+Left/Right move by word, matching Alt+B / Alt+F.
             KeyCode::Left => {
                 app.cursor_pos = app.find_word_boundary_back();
@@ -446,11 +825,7 @@ async fn handle_remote_key_internal(
                 return Ok(());
             }
             KeyCode::Char('d') => {
-                let end = app.find_word_boundary_forward();
-                if app.cursor_pos < end {
-                    app.remember_input_undo_state();
-                }
-                app.input.drain(app.cursor_pos..end);
+                input::apply_edit_command(app, EditCommand::KillWordForward);
                 return Ok(());
             }
             KeyCode::Backspace | KeyCode::Delete | KeyCode::Char('\u{7f}') => {
@@ -499,6 +874,10 @@ async fn handle_remote_key_internal(
         return Ok(());
     }
 
+    if input::handle_composer_edit_key(app, code, modifiers) {
+        return Ok(());
+    }
+
     if let Some(amount) = app.scroll_keys.scroll_amount(code, modifiers) {
         if amount < 0 {
             app.scroll_up((-amount) as usize);
@@ -640,6 +1019,12 @@ async fn handle_remote_key_internal(
                 app.toggle_input_stash();
                 return Ok(());
             }
+            KeyCode::Char('p')
+                if app.permission_inbox_open() || !app.permission_inbox.is_empty() =>
+            {
+                app.toggle_permission_inbox();
+                return Ok(());
+            }
             KeyCode::Char('p') => {
                 if app.auto_poke_incomplete_todos {
                     let cleared = app_mod::commands::disable_auto_poke(app);
@@ -835,8 +1220,46 @@ async fn handle_remote_key_internal(
             }
             if !app.input.is_empty() {
                 let prepared = input::take_prepared_input(app);
+                if app.memory_pin_editing() {
+                    app.submit_memory_pin(prepared.expanded);
+                    return Ok(());
+                }
+                if app.saved_prompt_pending() {
+                    app.submit_saved_prompt_variable(prepared.expanded);
+                    return Ok(());
+                }
+                if app.handoff_editing() {
+                    if let Some(summary) = app.take_handoff_summary(prepared.expanded) {
+                        remote
+                            .handoff(crate::protocol::HandoffAction::Create { summary })
+                            .await?;
+                        app.note_handoff_creating();
+                    }
+                    return Ok(());
+                }
+                if app.aside_summary_editing() {
+                    if let Some(summary) = app.take_aside_summary(prepared.expanded) {
+                        remote
+                            .aside(crate::protocol::AsideAction::Close {
+                                summary: summary.clone(),
+                            })
+                            .await?;
+                        app.note_aside_closed(summary.as_deref());
+                    }
+                    return Ok(());
+                }
                 let trimmed = prepared.expanded.trim();
 
+                if let Some((request_id, answer)) = app.take_safe_mode_approval_answer(trimmed) {
+                    remote.send_stdin_response(&request_id, answer).await?;
+                    return Ok(());
+                }
+
+                if let Some(request_id) = app.take_ask_user_answer(trimmed) {
+                    remote.send_stdin_response(&request_id, trimmed).await?;
+                    return Ok(());
+                }
+
                 if let Some(topic) = trimmed
                     .strip_prefix("/help ")
                     .or_else(|| trimmed.strip_prefix("/? "))
@@ -928,12 +1351,12 @@ async fn handle_remote_key_internal(
                     return Ok(());
                 }
 
-                if trimmed == "/rebuild" {
+                if let Some(full_tests) = App::parse_rebuild_command(trimmed) {
                     let session_id = app
                         .remote_session_id
                         .clone()
                         .unwrap_or_else(|| crate::id::new_id("ses"));
-                    app.start_background_client_rebuild(session_id);
+                    app.start_background_client_rebuild(session_id, full_tests);
                     return Ok(());
                 }
 
@@ -1586,6 +2009,29 @@ async fn handle_remote_key_internal(
                     return Ok(());
                 }
 
+                if trimmed == "/autocommit" || trimmed == "/autocommit status" {
+                    app.push_display_message(DisplayMessage::system(
+                        app_mod::auto_commit::auto_commit_status_message(app),
+                    ));
+                    return Ok(());
+                }
+
+                if trimmed == "/autocommit on" || trimmed == "/autocommit off" {
+                    let enabled = trimmed == "/autocommit on";
+                    remote
+                        .set_feature(crate::protocol::FeatureToggle::Autocommit, enabled)
+                        .await?;
+                    app.set_auto_commit_feature_enabled(enabled);
+                    return Ok(());
+                }
+
+                if trimmed.starts_with("/autocommit ") {
+                    app.push_display_message(DisplayMessage::error(
+                        app_mod::auto_commit::USAGE.to_string(),
+                    ));
+                    return Ok(());
+                }
+
                 if trimmed == "/clear" {
                     remote.clear().await?;
                     app.clear_provider_messages();
@@ -1637,9 +2083,7 @@ async fn handle_remote_key_internal(
                     || trimmed == "/observe off"
                     || trimmed == "/observe status"
                     || trimmed == "/todos"
-                    || trimmed == "/todos on"
-                    || trimmed == "/todos off"
-                    || trimmed == "/todos status"
+                    || trimmed.starts_with("/todos ")
                     || trimmed == "/splitview"
                     || trimmed == "/splitview on"
                     || trimmed == "/splitview off"
@@ -1648,6 +2092,14 @@ async fn handle_remote_key_internal(
                     || trimmed == "/split-view on"
                     || trimmed == "/split-view off"
                     || trimmed == "/split-view status"
+                    || trimmed == "/session stats"
+                    || trimmed.starts_with("/session footers")
+                    || trimmed == "/redaction"
+                    || trimmed.starts_with("/redaction ")
+                    || trimmed == "/changes"
+                    || trimmed.starts_with("/changes ")
+                    || trimmed == "/migrate"
+                    || trimmed.starts_with("/migrate ")
                 {
                     let _ = app_mod::commands::handle_session_command(app, trimmed);
                     return Ok(());
@@ -1960,6 +2412,63 @@ async fn handle_remote_key_internal(
                     return Ok(());
                 }
 
+                if let Some(command) = app_mod::commands::parse_premium_command(trimmed) {
+                    match command {
+                        Err(error) => app.push_display_message(DisplayMessage::error(error)),
+                        Ok(app_mod::commands::PremiumCommand::Status) => {
+                            app.push_display_message(DisplayMessage::system(
+                                app_mod::commands::premium_status_message(app),
+                            ));
+                        }
+                        Ok(app_mod::commands::PremiumCommand::Set(mode)) => {
+                            app.provider.set_premium_mode(mode);
+                            let _ = remote.set_premium_mode(mode as u8).await;
+                            let label = app_mod::commands::premium_mode_label(mode);
+                            app.set_status_notice(format!("Premium: {}", label));
+                            app.push_display_message(DisplayMessage::system(format!(
+                                "Premium mode for this session: {}.",
+                                label,
+                            )));
+                        }
+                    }
+                    return Ok(());
+                }
+
+                if let Some(command) = app_mod::commands::parse_root_command(trimmed) {
+                    let (add, remove) = match command {
+                        Err(error) => {
+                            app.push_display_message(DisplayMessage::error(error));
+                            return Ok(());
+                        }
+                        Ok(app_mod::commands::WorkspaceRootCommand::List) => {
+                            (Vec::new(), Vec::new())
+                        }
+                        Ok(app_mod::commands::WorkspaceRootCommand::Add(path)) => {
+                            (vec![path], Vec::new())
+                        }
+                        Ok(app_mod::commands::WorkspaceRootCommand::Remove(name)) => {
+                            (Vec::new(), vec![name])
+                        }
+                    };
+                    if let Err(error) = remote.update_workspace_roots(add, remove).await {
+                        app.push_display_message(DisplayMessage::error(format!(
+                            "Failed to update workspace roots: {}",
+                            error
+                        )));
+                    }
+                    return Ok(());
+                }
+
+                if let Some(path) = app_mod::commands::parse_cd_command(trimmed) {
+                    if let Err(error) = remote.set_tool_scope(path).await {
+                        app.push_display_message(DisplayMessage::error(format!(
+                            "Failed to change tool directory: {}",
+                            error
+                        )));
+                    }
+                    return Ok(());
+                }
+
                 if trimmed == "/z" || trimmed == "/zz" || trimmed == "/zzz" {
                     use crate::provider::copilot::PremiumMode;
                     let current = app.provider.premium_mode();
@@ -2070,24 +2579,68 @@ async fn handle_remote_key_internal(
                 }
 
                 if let Some(command) = app_mod::commands::parse_plan_command(trimmed) {
-                    let prompt = app_mod::commands::build_plan_prompt(command.goal.as_deref());
-                    if app.is_processing {
-                        remote.cancel_with_reason("slash_plan").await?;
-                        app.set_status_notice("Interrupting for /plan...");
-                        app.push_display_message(DisplayMessage::system(
-                            app_mod::commands::plan_launch_notice(command.goal.as_deref(), true),
-                        ));
-                        app.queued_messages.push(prompt);
-                    } else {
-                        app.push_display_message(DisplayMessage::system(
-                            app_mod::commands::plan_launch_notice(command.goal.as_deref(), false),
-                        ));
-                        let _ = begin_remote_send(app, remote, prompt, vec![], true, None, true, 0)
-                            .await;
+                    handle_remote_plan_command(app, remote, command).await?;
+                    return Ok(());
+                }
+
+                if let Some(command) = app_mod::commands::parse_aside_command(trimmed) {
+                    handle_remote_aside_command(app, remote, command).await?;
+                    return Ok(());
+                }
+
+                if let Some(command) = app_mod::commands::parse_compare_command(trimmed) {
+                    handle_remote_compare_command(app, remote, command).await?;
+                    return Ok(());
+                }
+
+                if app_mod::commands::is_handoff_command(trimmed) {
+                    if !app.handoff_blocked() {
+                        remote
+                            .handoff(crate::protocol::HandoffAction::Summarize)
+                            .await?;
+                        app.note_handoff_drafting();
+                    }
+                    return Ok(());
+                }
+
+                if let Some(command) = app_mod::commands::parse_focus_command(trimmed) {
+                    handle_remote_focus_command(app, remote, command).await?;
+                    return Ok(());
+                }
+
+                if trimmed == "/prompt sections" {
+                    remote.prompt_sections().await?;
+                    return Ok(());
+                }
+
+                if let Some(refresh) = app_mod::commands::parse_env_command(trimmed) {
+                    remote.environment(refresh).await?;
+                    if refresh {
+                        app.set_status_notice("Environment → probing…");
                     }
                     return Ok(());
                 }
 
+                if let Some(command) = app_mod::commands::parse_profile_command(trimmed) {
+                    handle_remote_profile_command(app, remote, command).await?;
+                    return Ok(());
+                }
+
+                if let Some(command) = app_mod::commands::parse_queue_command(trimmed) {
+                    handle_remote_queue_command(app, remote, command).await?;
+                    return Ok(());
+                }
+
+                if let Some(command) = app_mod::commands::parse_safe_command(trimmed) {
+                    handle_remote_safe_command(app, remote, command).await?;
+                    return Ok(());
+                }
+
+                if let Some(command) = app_mod::commands::parse_instructions_command(trimmed) {
+                    handle_remote_instructions_command(app, remote, command).await?;
+                    return Ok(());
+                }
+
                 if let Some(command) = app_mod::commands::parse_improve_command(trimmed) {
                     match command {
                         Err(error) => app.push_display_message(DisplayMessage::error(error)),
@@ -690,6 +690,16 @@ pub(in crate::tui::app) async fn handle_post_connect<B: ratatui::backend::Backen
     state.initial_server_start = false;
     state.server_reload_in_progress = false;
 
+    if let Some(profile) = app.pending_startup_profile.take() {
+        remote.set_profile(Some(profile.clone())).await?;
+        app.session.profile = Some(profile);
+    }
+
+    if std::mem::take(&mut app.pending_startup_safe_mode) {
+        remote.set_safe_mode(true).await?;
+        app.session.safe_mode = true;
+    }
+
     if same_session_reload_fast_path {
         crate::logging::info(
             "Same-session reload fast path: skipping blocking History wait and reusing local display state",
@@ -33,6 +33,9 @@ pub(super) fn handle_tool_done(
     app.commit_pending_streaming_assistant_message();
     crate::tui::mermaid::clear_streaming_preview_diagram();
     let is_batch = tool_call.name == "batch";
+    let plan_input = (tool_call.name == crate::plan::proposal::PLAN_PROPOSE_TOOL
+        && error.is_none())
+    .then(|| tool_call.input.clone());
     app.observe_tool_result(&tool_call, &output, error.is_some(), None);
     app.note_tool_completed(&tool_call, error.is_some());
     app.push_display_message(DisplayMessage {
@@ -43,6 +46,9 @@ pub(super) fn handle_tool_done(
         title: None,
         tool_data: Some(tool_call),
     });
+    if let Some(input) = plan_input {
+        app.note_plan_proposal(&input);
+    }
     if is_batch {
         app.batch_progress = None;
     }
@@ -294,6 +294,7 @@ pub(in crate::tui::app) fn handle_server_event(
             | ServerEvent::ConnectionType { .. }
             | ServerEvent::ConnectionPhase { .. }
             | ServerEvent::StatusDetail { .. }
+            | ServerEvent::RateLimited { .. }
             | ServerEvent::MessageEnd
             | ServerEvent::RetryRollback { .. }
             | ServerEvent::UpstreamProvider { .. }
@@ -382,6 +383,8 @@ pub(in crate::tui::app) fn handle_server_event(
                 crate::memory::set_state(crate::tui::info_widget::MemoryState::Embedding);
             }
             app.status = ProcessingStatus::RunningTool(name.clone());
+            // Progress from an earlier batch/subagent call must not show for this one.
+            app.batch_progress = None;
             app.streaming_tool_calls.push(ToolCall {
                 id,
                 name,
@@ -416,6 +419,7 @@ pub(in crate::tui::app) fn handle_server_event(
                 tc.refresh_intent_from_input();
             }
             remote.handle_tool_exec(&id, &name);
+            app.start_mcp_call_countdown(&name);
             app.observe_tool_call(&tool_call);
             eager_stream_redraw
                 || app.side_panel.focused_page_id.as_deref()
@@ -638,6 +642,83 @@ pub(in crate::tui::app) fn handle_server_event(
             app.status_detail = Some(detail);
             eager_stream_redraw
         }
+        ServerEvent::RateLimited {
+            provider,
+            retry_after_secs,
+            attempt,
+            max_attempts,
+        } => {
+            // The server is sleeping out a provider rate limit inside the
+            // turn. Drive the status-bar countdown from `rate_limit_reset`
+            // while the turn stays in flight; input submitted meanwhile is
+            // queued (see `send_action`) rather than interleaved.
+            app.rate_limit_reset = Some(Instant::now() + Duration::from_secs(retry_after_secs));
+            app.last_stream_activity = Some(Instant::now());
+            let content = format!(
+                "⏳ {} rate limited - retrying automatically in {}s (attempt {}/{}). Messages you send now are queued.",
+                provider, retry_after_secs, attempt, max_attempts
+            );
+            // Successive waits in one turn update a single notice in place.
+            let last_is_rate_limit_notice = app.display_messages.last().is_some_and(|message| {
+                message.role == "system" && message.title.as_deref() == Some("Rate limit")
+            });
+            if last_is_rate_limit_notice {
+                app.replace_display_message_title_and_content(
+                    app.display_messages.len() - 1,
+                    Some("Rate limit".to_string()),
+                    content,
+                );
+            } else {
+                app.push_display_message(DisplayMessage {
+                    role: "system".to_string(),
+                    content,
+                    tool_calls: Vec::new(),
+                    duration_secs: None,
+                    title: Some("Rate limit".to_string()),
+                    tool_data: None,
+                });
+            }
+            app.set_status_notice(format!("Rate limited; retrying in {}s", retry_after_secs));
+            true
+        }
+        ServerEvent::AgentLimits { status } => {
+            app.last_agent_limits = Some(status);
+            false
+        }
+        ServerEvent::FocusCheckpoint {
+            budget_secs,
+            elapsed_secs,
+            open_todos,
+            summary,
+        } => {
+            app.note_focus_checkpoint(budget_secs, elapsed_secs, open_todos, &summary);
+            false
+        }
+        ServerEvent::AutoCommit {
+            sha,
+            branch,
+            subject,
+            files,
+        } => {
+            app.note_auto_commit(&sha, &branch, &subject, files);
+            false
+        }
+        ServerEvent::ActiveSkills { skills } => {
+            let added: Vec<String> = skills
+                .iter()
+                .filter(|skill| {
+                    !app.remote_active_skills
+                        .iter()
+                        .any(|known| known.name == skill.name)
+                })
+                .map(|skill| format!("/{}", skill.name))
+                .collect();
+            if !added.is_empty() {
+                app.set_status_notice(format!("Skill auto-loaded: {}", added.join(", ")));
+            }
+            app.remote_active_skills = skills;
+            false
+        }
         ServerEvent::MessageEnd => {
             app.pause_streaming_tps(true);
             app.stream_message_ended = true;
@@ -751,6 +832,11 @@ pub(in crate::tui::app) fn handle_server_event(
             app.set_status_notice(format!("Provider guardrail: {}", label));
             true
         }
+        ServerEvent::PostMortem { summary } => {
+            app.push_display_message(DisplayMessage::system(summary));
+            app.set_status_notice("Post-mortem added to the session");
+            true
+        }
         ServerEvent::Done { id } => {
             let mut auto_poked = false;
             let mut completed_current_message = false;
@@ -829,6 +915,7 @@ pub(in crate::tui::app) fn handle_server_event(
                 remote.clear_pending();
                 remote.reset_call_output_tokens_seen();
                 app.note_runtime_memory_event_force("turn_completed", "remote_turn_finished");
+                app.tee_turn_output();
                 auto_poked = app.schedule_auto_poke_followup_if_needed()
                     || app.schedule_overnight_poke_followup_if_needed();
                 if !auto_poked {
@@ -1124,6 +1211,7 @@ pub(in crate::tui::app) fn handle_server_event(
             status_detail,
             upstream_provider,
             resolved_credential,
+            model_route,
             reasoning_effort,
             service_tier,
             compaction_mode,
@@ -1214,6 +1302,7 @@ pub(in crate::tui::app) fn handle_server_event(
             let session_changed = prev_session_id.as_deref() != Some(session_id.as_str());
 
             if session_changed {
+                app.remote_active_skills.clear();
                 app.rate_limit_pending_message = None;
                 app.rate_limit_reset = None;
                 app.connection_type = None;
@@ -1283,9 +1372,17 @@ pub(in crate::tui::app) fn handle_server_event(
                 autoreview_enabled.unwrap_or(crate::config::config().autoreview.enabled);
             app.autojudge_enabled =
                 autojudge_enabled.unwrap_or(crate::config::config().autojudge.enabled);
+            // Before the first response reports where OpenRouter routed, show
+            // the provider the session is pinned to.
+            let pinned_provider = model_route
+                .as_ref()
+                .and_then(|route| route.provider.clone());
             if upstream_provider.is_some() {
                 app.upstream_provider = upstream_provider;
+            } else if pinned_provider.is_some() {
+                app.upstream_provider = pinned_provider;
             }
+            app.session.model_route = model_route;
             if session_changed || resolved_credential.is_some() {
                 app.remote_resolved_credential = resolved_credential;
             }
@@ -1348,8 +1445,7 @@ pub(in crate::tui::app) fn handle_server_event(
                 .sync_after_history(&session_id, &app.remote_sessions);
 
             if server_has_update == Some(true) && !app.pending_server_reload {
-                app.pending_server_reload = true;
-                app.set_status_notice("Server update available");
+                app.announce_server_update();
             }
             app.remote_server_short_name = server_name;
             if let Some(icon) = server_icon {
@@ -1412,18 +1508,7 @@ pub(in crate::tui::app) fn handle_server_event(
                         "Preserving locally restored display history for metadata-only History bootstrap",
                     );
                 } else {
-                    let restored_messages = messages
-                        .into_iter()
-                        .map(|msg| DisplayMessage {
-                            role: msg.role,
-                            content: msg.content,
-                            tool_calls: msg.tool_calls.unwrap_or_default(),
-                            duration_secs: None,
-                            title: None,
-                            tool_data: msg.tool_data,
-                        })
-                        .collect();
-                    app.replace_display_messages(restored_messages);
+                    app.replace_display_messages(display_messages_from_history(messages));
                 }
 
                 if history_matches_pending_startup_prompt(app) {
@@ -1543,6 +1628,45 @@ pub(in crate::tui::app) fn handle_server_event(
 
             false
         }
+        ServerEvent::ResumeStreaming {
+            session_id,
+            text,
+            current_tool_name,
+        } => {
+            if app.remote_session_id.as_deref() != Some(session_id.as_str()) {
+                crate::logging::info(&format!(
+                    "Ignoring resumed stream for inactive session {}",
+                    session_id
+                ));
+                return false;
+            }
+            // The previous client of this session went away mid-turn. Show the
+            // partial answer and let the rest of the stream and the turn's
+            // `Done { id: 0 }` settle it like any adopted turn.
+            crate::logging::info(&format!(
+                "Resuming live turn stream for session {} ({} chars, tool={:?})",
+                session_id,
+                text.len(),
+                current_tool_name
+            ));
+            let ops = app.stream_buffer.flush();
+            app.apply_stream_ops(ops);
+            app.replace_streaming_text(text);
+            app.is_processing = true;
+            app.processing_started.get_or_insert_with(Instant::now);
+            app.last_stream_activity = Some(Instant::now());
+            app.status = match current_tool_name.clone() {
+                Some(tool_name) => ProcessingStatus::RunningTool(tool_name),
+                None => ProcessingStatus::Streaming,
+            };
+            app.remote_resume_activity = Some(RemoteResumeActivity {
+                session_id,
+                observed_at: Instant::now(),
+                current_tool_name,
+            });
+            app.set_status_notice("Reattached to the running turn");
+            true
+        }
         ServerEvent::CompactedHistory {
             session_id,
             messages,
@@ -1560,19 +1684,8 @@ pub(in crate::tui::app) fn handle_server_event(
                 ));
                 return false;
             }
-            let restored_messages = messages
-                .into_iter()
-                .map(|msg| DisplayMessage {
-                    role: msg.role,
-                    content: msg.content,
-                    tool_calls: msg.tool_calls.unwrap_or_default(),
-                    duration_secs: None,
-                    title: None,
-                    tool_data: msg.tool_data,
-                })
-                .collect();
             app.apply_compacted_history_window(
-                restored_messages,
+                display_messages_from_history(messages),
                 images,
                 compacted_total,
                 compacted_visible,
@@ -1616,6 +1729,48 @@ pub(in crate::tui::app) fn handle_server_event(
             app.update_pinned_images_auto_hide();
             true
         }
+        ServerEvent::ClipboardWrite { text } => {
+            app.handle_clipboard_write(&text);
+            false
+        }
+        ServerEvent::PermissionInbox { entries } => {
+            app.apply_permission_inbox(entries);
+            false
+        }
+        ServerEvent::TurnQueue { items } => {
+            app.apply_turn_queue(items);
+            false
+        }
+        ServerEvent::QueuedTurnStarted { id, content, .. } => {
+            // The server started a message queued with `queue_message`; show
+            // it and adopt the turn so its `Done { id }` settles it.
+            app.push_display_message(DisplayMessage::user(content));
+            app.current_message_id = Some(id);
+            app.is_processing = true;
+            app.status = ProcessingStatus::Sending;
+            app.status_detail = None;
+            app.processing_started = Some(Instant::now());
+            app.visible_turn_started = Some(Instant::now());
+            app.last_stream_activity = Some(Instant::now());
+            app.remote_resume_activity = None;
+            app.reset_streaming_tps();
+            true
+        }
+        ServerEvent::MigrationPending {
+            from_hash,
+            to_hash,
+            reason,
+            at,
+        } => {
+            app.announce_migration(app_mod::commands_migrate::PendingMigration::new(
+                app_mod::commands_migrate::MigrationTarget::Server,
+                from_hash,
+                to_hash,
+                reason,
+                at,
+            ));
+            true
+        }
         ServerEvent::SidePanelState { snapshot } => {
             app.set_side_panel_snapshot(snapshot);
             false
@@ -1695,10 +1850,11 @@ pub(in crate::tui::app) fn handle_server_event(
             false
         }
         ServerEvent::ModelChanged {
+            id,
             model,
             provider_name,
             error,
-            ..
+            route_fallback,
         } => {
             app.remote_model_switch_in_flight = false;
             if let Some(err) = error {
@@ -1717,11 +1873,28 @@ pub(in crate::tui::app) fn handle_server_event(
                     app.remote_provider_name = Some(pname.clone());
                 }
                 app.invalidate_model_picker_cache();
-                app.push_display_message(DisplayMessage::system(format!(
-                    "✓ Switched to model: {}",
-                    model
-                )));
-                app.set_status_notice(format!("Model → {}", model));
+                if let Some(fallback) = route_fallback {
+                    // The resumed session's stored route is unusable, or the
+                    // provider retired the model mid-session (`id` 0); say so
+                    // rather than presenting the fallback as a normal switch.
+                    let when = if id == 0 { "mid-session" } else { "on resume" };
+                    app.push_display_message(DisplayMessage::system(format!(
+                        "⚠ Model route changed {}: {}",
+                        when,
+                        fallback.summary()
+                    )));
+                    app.set_status_notice(format!(
+                        "Model → {} (requested {})",
+                        model,
+                        fallback.requested.model_spec()
+                    ));
+                } else {
+                    app.push_display_message(DisplayMessage::system(format!(
+                        "✓ Switched to model: {}",
+                        model
+                    )));
+                    app.set_status_notice(format!("Model → {}", model));
+                }
             }
             false
         }
@@ -1831,6 +2004,80 @@ pub(in crate::tui::app) fn handle_server_event(
             }
             false
         }
+        ServerEvent::WorkspaceRootsChanged { roots, error, .. } => {
+            if let Some(err) = error {
+                app.push_display_message(DisplayMessage::error(format!(
+                    "Failed to update workspace roots: {}",
+                    err
+                )));
+            }
+            app.session.workspace_roots = roots;
+            app.push_display_message(DisplayMessage::system(
+                app_mod::commands::workspace_roots_message(
+                    app.session.working_dir.as_deref(),
+                    &app.session.workspace_roots,
+                ),
+            ));
+            false
+        }
+        ServerEvent::ToolScopeChanged { scope, error, .. } => {
+            if let Some(err) = error {
+                app.push_display_message(DisplayMessage::error(format!(
+                    "Failed to change tool directory: {}",
+                    err
+                )));
+                return false;
+            }
+            app.session.tool_scope = scope;
+            app.set_status_notice("Tool directory changed");
+            app.push_display_message(DisplayMessage::system(
+                app_mod::commands::tool_scope_message(
+                    app.session.working_dir.as_deref(),
+                    app.session.tool_scope.as_deref(),
+                ),
+            ));
+            false
+        }
+        ServerEvent::AsideChanged {
+            active,
+            summary,
+            error,
+            ..
+        } => {
+            app.handle_aside_changed(active, summary, error);
+            false
+        }
+        ServerEvent::HandoffChanged {
+            summary,
+            new_session_id,
+            new_session_name,
+            error,
+            ..
+        } => {
+            app.handle_handoff_changed(summary, new_session_id, new_session_name, error);
+            false
+        }
+        ServerEvent::CompareResult {
+            prompt,
+            runs,
+            error,
+            ..
+        } => {
+            app.handle_compare_result(prompt, runs, error);
+            false
+        }
+        ServerEvent::EnvironmentChanged {
+            descriptor,
+            refreshed,
+            ..
+        } => {
+            app.show_environment_descriptor(descriptor, refreshed);
+            false
+        }
+        ServerEvent::PromptSections { report, .. } => {
+            app.push_display_message(DisplayMessage::system(report).with_title("Prompt sections"));
+            false
+        }
         ServerEvent::CompactionModeChanged { mode, error, .. } => {
             if let Some(err) = error {
                 app.push_display_message(DisplayMessage::error(format!(
@@ -1883,15 +2130,23 @@ pub(in crate::tui::app) fn handle_server_event(
                 app.push_turn_footer(duration);
             }
             app.mark_soft_interrupt_injected(&content);
-            let role = display_role.unwrap_or_else(|| "user".to_string());
-            app.push_display_message(DisplayMessage {
-                role,
-                content: content.clone(),
-                tool_calls: vec![],
-                duration_secs: None,
-                title: None,
-                tool_data: None,
-            });
+            let message = match display_role {
+                Some(role) => DisplayMessage {
+                    role,
+                    content: content.clone(),
+                    tool_calls: vec![],
+                    duration_secs: None,
+                    title: None,
+                    tool_data: None,
+                },
+                // No display role means the user typed it mid-turn.
+                None => {
+                    let mut message = DisplayMessage::interjection(content.clone());
+                    message.title = tools_skipped.map(|n| format!("{} tool(s) skipped", n));
+                    message
+                }
+            };
+            app.push_display_message(message);
             if let Some(n) = tools_skipped {
                 app.set_status_notice(format!("⚡ {} tool(s) skipped", n));
             }
@@ -1903,7 +2158,11 @@ pub(in crate::tui::app) fn handle_server_event(
             display_prompt,
             prompt_chars: _,
             computed_age_ms,
+            report,
         } => {
+            if report.is_some() {
+                app.last_memory_injection = report;
+            }
             if app.memory_enabled {
                 let plural = if count == 1 { "memory" } else { "memories" };
                 let display_prompt = if let Some(display_prompt) = display_prompt {
@@ -2050,6 +2309,7 @@ pub(in crate::tui::app) fn handle_server_event(
             messages_compacted,
             summary_chars,
             active_messages,
+            tool_results_trimmed,
         } => {
             app.handle_compaction_event(crate::compaction::CompactionEvent {
                 trigger,
@@ -2061,6 +2321,7 @@ pub(in crate::tui::app) fn handle_server_event(
                 messages_compacted,
                 summary_chars,
                 active_messages,
+                tool_results_trimmed,
             });
             false
         }
@@ -2190,6 +2451,24 @@ pub(in crate::tui::app) fn handle_server_event(
             }
             false
         }
+        ServerEvent::StdinRequest {
+            request_id, prompt, ..
+        } if request_id.starts_with(crate::tool::safe_mode::SAFE_MODE_APPROVAL_PREFIX) => {
+            app.note_safe_mode_approval_request(request_id, &prompt);
+            false
+        }
+        ServerEvent::StdinRequest { request_id, .. }
+            if request_id.starts_with(crate::tool::clipboard::CLIPBOARD_READ_APPROVAL_PREFIX) =>
+        {
+            app.note_clipboard_read_approval_request(request_id);
+            false
+        }
+        ServerEvent::StdinRequest {
+            request_id, prompt, ..
+        } if request_id.starts_with(crate::tool::ask_user::ASK_USER_PREFIX) => {
+            app.note_ask_user_request(request_id, &prompt);
+            false
+        }
         ServerEvent::StdinRequest { .. } => {
             app.set_status_notice("⌨ Interactive terminal detected (command will timeout)");
             false
@@ -2198,6 +2477,33 @@ pub(in crate::tui::app) fn handle_server_event(
     }
 }
 
+/// Convert server history into transcript messages, following each assistant
+/// message that carries usage with its footer when `display.message_footers`
+/// is on.
+fn display_messages_from_history(
+    messages: Vec<crate::protocol::HistoryMessage>,
+) -> Vec<DisplayMessage> {
+    let usage_footers = crate::config::config().display.message_footers;
+    let mut restored = Vec::with_capacity(messages.len());
+    for msg in messages {
+        let footer = msg
+            .usage
+            .as_ref()
+            .filter(|_| usage_footers)
+            .and_then(DisplayMessage::usage_footer);
+        restored.push(DisplayMessage {
+            role: msg.role,
+            content: msg.content,
+            tool_calls: msg.tool_calls.unwrap_or_default(),
+            duration_secs: None,
+            title: None,
+            tool_data: msg.tool_data,
+        });
+        restored.extend(footer);
+    }
+    restored
+}
+
 fn runtime_activity_status_notice(message: &str) -> String {
     message
         .lines()
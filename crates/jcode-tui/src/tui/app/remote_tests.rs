@@ -839,3 +839,29 @@ fn remote_history_watchdog_advises_restart_after_giving_up() {
     assert!(!redraw2);
     assert_eq!(app.display_messages().len(), before + 1);
 }
+
+#[test]
+fn ask_user_question_is_answered_by_the_next_message() {
+    let rt = tokio::runtime::Runtime::new().expect("runtime");
+    let _guard = rt.enter();
+    let mut app = create_test_app();
+    let mut remote = crate::tui::backend::RemoteConnection::dummy();
+    let request_id = format!("{}call-1-0", crate::tool::ask_user::ASK_USER_PREFIX);
+
+    handle_server_event(
+        &mut app,
+        ServerEvent::StdinRequest {
+            request_id: request_id.clone(),
+            prompt: "Which approach?\n\n  1. Migrate\n  2. Shim".to_string(),
+            is_password: false,
+            tool_call_id: "call-1".to_string(),
+        },
+        &mut remote,
+    );
+
+    let last = app.display_messages().last().expect("question shown");
+    assert!(last.content.starts_with("Which approach?"));
+    assert_eq!(app.take_ask_user_answer("/help"), None);
+    assert_eq!(app.take_ask_user_answer("2"), Some(request_id));
+    assert_eq!(app.take_ask_user_answer("another message"), None);
+}
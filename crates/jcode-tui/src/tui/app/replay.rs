@@ -94,6 +94,272 @@ pub(super) async fn run_replay(
     })
 }
 
+/// Read-only transcript loop for `jcode view`. Shares the live TUI renderer,
+/// scrolling, mouse handling, and copy mode, but never reads composer input.
+pub(super) async fn run_transcript_view(
+    mut app: App,
+    mut terminal: DefaultTerminal,
+) -> Result<RunResult> {
+    let mut event_stream = EventStream::new();
+    let mut redraw_period = super::super::redraw_interval(&app);
+    let mut redraw_interval = interval(redraw_period);
+    let mut resume_offered = false;
+    let mut resume_requested = false;
+    let mut search = TranscriptSearch::default();
+
+    loop {
+        let desired_redraw = super::super::redraw_interval(&app);
+        if desired_redraw != redraw_period {
+            redraw_period = desired_redraw;
+            redraw_interval = interval(redraw_period);
+        }
+
+        terminal.draw(|frame| crate::tui::ui::draw(frame, &app))?;
+
+        if app.should_quit {
+            break;
+        }
+
+        tokio::select! {
+            _ = redraw_interval.tick() => {}
+            event = event_stream.next() => {
+                match event {
+                    Some(Ok(event)) => handle_transcript_view_input(
+                        &mut app,
+                        event,
+                        &mut resume_offered,
+                        &mut resume_requested,
+                        &mut search,
+                    ),
+                    Some(Err(_)) | None => app.should_quit = true,
+                }
+            }
+        }
+    }
+
+    Ok(RunResult {
+        reload_session: None,
+        rebuild_session: None,
+        update_session: None,
+        restart_session: resume_requested.then(|| app.session.id.clone()),
+        exit_code: None,
+        session_id: Some(app.session.id.clone()),
+    })
+}
+
+/// Incremental search state for the transcript view. `query` survives after
+/// the prompt closes so `n`/`N` can keep stepping through matches.
+#[derive(Debug, Default)]
+struct TranscriptSearch {
+    query: String,
+    editing: bool,
+    /// Scroll position when the prompt opened, restored on Esc.
+    origin: Option<(usize, bool)>,
+}
+
+/// Find the next wrapped line containing `query` (case-insensitive), starting
+/// at `from` and wrapping around the document. `forward == false` searches
+/// upward from `from`.
+fn find_transcript_match(
+    line_count: usize,
+    line_text: impl Fn(usize) -> Option<String>,
+    query: &str,
+    from: usize,
+    forward: bool,
+) -> Option<usize> {
+    if query.is_empty() || line_count == 0 {
+        return None;
+    }
+    let needle = query.to_lowercase();
+    let from = from.min(line_count - 1);
+    (0..line_count)
+        .map(|step| {
+            if forward {
+                (from + step) % line_count
+            } else {
+                (from + line_count - step) % line_count
+            }
+        })
+        .find(|&line| line_text(line).is_some_and(|text| text.to_lowercase().contains(&needle)))
+}
+
+fn count_transcript_matches(
+    line_count: usize,
+    line_text: impl Fn(usize) -> Option<String>,
+    query: &str,
+    upto: usize,
+) -> (usize, usize) {
+    let needle = query.to_lowercase();
+    let mut index = 0;
+    let mut total = 0;
+    for line in 0..line_count {
+        if line_text(line).is_some_and(|text| text.to_lowercase().contains(&needle)) {
+            total += 1;
+            if line <= upto {
+                index = total;
+            }
+        }
+    }
+    (index, total)
+}
+
+/// Jump to the next (or previous) match of the current query and report the
+/// position in the status line. `from_current` keeps the current line as a
+/// candidate, which is what typing into the prompt wants.
+fn jump_to_transcript_match(
+    app: &mut App,
+    search: &TranscriptSearch,
+    forward: bool,
+    from_current: bool,
+) {
+    let prompt = if search.editing { "/" } else { "search: " };
+    if search.query.is_empty() {
+        app.set_status_notice(prompt.to_string());
+        return;
+    }
+    let line_count = crate::tui::ui::copy_viewport_line_count().unwrap_or(0);
+    let current = crate::tui::ui::copy_viewport_visible_range()
+        .map(|(start, _)| start)
+        .unwrap_or(0);
+    let from = match (from_current, forward) {
+        (true, _) => current,
+        (false, true) => current + 1,
+        (false, false) => current + line_count.max(1) - 1,
+    };
+    let line_text = crate::tui::ui::copy_viewport_line_text;
+    match find_transcript_match(line_count, line_text, &search.query, from, forward) {
+        Some(line) => {
+            app.pending_history_anchor = None;
+            app.scroll_offset = line;
+            app.auto_scroll_paused = true;
+            let (index, total) =
+                count_transcript_matches(line_count, line_text, &search.query, line);
+            app.set_status_notice(format!(
+                "{prompt}{} — match {index}/{total} · n next · N previous",
+                search.query
+            ));
+        }
+        None => app.set_status_notice(format!("{prompt}{} — no matches", search.query)),
+    }
+}
+
+fn handle_transcript_search_key(app: &mut App, key: KeyCode, search: &mut TranscriptSearch) {
+    match key {
+        KeyCode::Esc => {
+            search.editing = false;
+            search.query.clear();
+            if let Some((offset, paused)) = search.origin.take() {
+                app.scroll_offset = offset;
+                app.auto_scroll_paused = paused;
+            }
+            app.set_status_notice(super::tui_lifecycle_runtime::TRANSCRIPT_VIEW_HINT);
+        }
+        KeyCode::Enter => {
+            search.editing = false;
+            search.origin = None;
+            if search.query.is_empty() {
+                app.set_status_notice(super::tui_lifecycle_runtime::TRANSCRIPT_VIEW_HINT);
+            } else {
+                jump_to_transcript_match(app, search, true, true);
+            }
+        }
+        KeyCode::Backspace => {
+            search.query.pop();
+            jump_to_transcript_match(app, search, true, true);
+        }
+        KeyCode::Char(c) => {
+            search.query.push(c);
+            jump_to_transcript_match(app, search, true, true);
+        }
+        _ => {}
+    }
+}
+
+fn handle_transcript_view_input(
+    app: &mut App,
+    event: Event,
+    resume_offered: &mut bool,
+    resume_requested: &mut bool,
+    search: &mut TranscriptSearch,
+) {
+    let key = match event {
+        Event::Key(key) if key.kind == KeyEventKind::Press => key,
+        Event::Mouse(mouse) => {
+            app.handle_mouse_event(mouse);
+            return;
+        }
+        _ => return,
+    };
+
+    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+        app.should_quit = true;
+        return;
+    }
+
+    if *resume_offered {
+        *resume_offered = false;
+        if matches!(
+            key.code,
+            KeyCode::Char('y') | KeyCode::Char('r') | KeyCode::Enter
+        ) {
+            *resume_requested = true;
+            app.should_quit = true;
+        } else {
+            app.set_status_notice(super::tui_lifecycle_runtime::TRANSCRIPT_VIEW_HINT);
+        }
+        return;
+    }
+
+    if search.editing {
+        handle_transcript_search_key(app, key.code, search);
+        return;
+    }
+
+    if app.copy_selection_mode {
+        let _ = app.handle_copy_selection_key(key.code, key.modifiers);
+        return;
+    }
+
+    match key.code {
+        KeyCode::Char('q') | KeyCode::Esc => app.should_quit = true,
+        KeyCode::Char('/') => {
+            search.editing = true;
+            search.query.clear();
+            search.origin = Some((app.scroll_offset, app.auto_scroll_paused));
+            app.set_status_notice("/");
+        }
+        KeyCode::Char('n') if !search.query.is_empty() => {
+            jump_to_transcript_match(app, search, true, false);
+        }
+        KeyCode::Char('N') if !search.query.is_empty() => {
+            jump_to_transcript_match(app, search, false, false);
+        }
+        KeyCode::Char('r') => {
+            *resume_offered = true;
+            app.set_status_notice(
+                "Resume this session for real? y/Enter to resume, any other key to stay",
+            );
+        }
+        KeyCode::Char('y') if key.modifiers.is_empty() => app.enter_copy_selection_mode(),
+        _ if app
+            .toggle_keys
+            .copy_selection
+            .matches(key.code, key.modifiers) =>
+        {
+            app.toggle_copy_selection_mode();
+        }
+        _ => {
+            if let Some(amount) = app.scroll_keys.scroll_amount(key.code, key.modifiers) {
+                if amount < 0 {
+                    app.scroll_up((-amount) as usize);
+                } else {
+                    app.scroll_down(amount as usize);
+                }
+            }
+        }
+    }
+}
+
 pub(super) async fn run_swarm_replay(
     mut terminal: DefaultTerminal,
     panes: Vec<PaneReplayInput>,
@@ -483,7 +749,7 @@ pub(super) fn update_replay_elapsed_override(app: &mut App, sim_time_ms: f64) {
 
 #[cfg(test)]
 mod tests {
-    use super::schedule_replay_events;
+    use super::{count_transcript_matches, find_transcript_match, schedule_replay_events};
     use crate::replay::{ReplayEvent, TimelineEvent, TimelineEventKind};
 
     #[test]
@@ -519,4 +785,19 @@ mod tests {
         assert!(matches!(scheduled[2].1, ReplayEvent::Server(_)));
         assert!(matches!(scheduled[3].1, ReplayEvent::Server(_)));
     }
+
+    #[test]
+    fn transcript_search_steps_forward_and_back_with_wraparound() {
+        let lines = ["alpha", "Needle one", "beta", "gamma needle", "delta"];
+        let text = |i: usize| lines.get(i).map(|line| line.to_string());
+
+        assert_eq!(find_transcript_match(5, text, "needle", 0, true), Some(1));
+        assert_eq!(find_transcript_match(5, text, "needle", 2, true), Some(3));
+        assert_eq!(find_transcript_match(5, text, "needle", 4, true), Some(1));
+        assert_eq!(find_transcript_match(5, text, "needle", 2, false), Some(1));
+        assert_eq!(find_transcript_match(5, text, "needle", 0, false), Some(3));
+        assert_eq!(find_transcript_match(5, text, "missing", 0, true), None);
+        assert_eq!(find_transcript_match(5, text, "", 0, true), None);
+        assert_eq!(count_transcript_matches(5, text, "NEEDLE", 3), (2, 2));
+    }
 }
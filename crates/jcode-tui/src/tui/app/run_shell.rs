@@ -616,6 +616,11 @@ impl App {
         replay::run_replay(self, terminal, timeline, speed).await
     }
 
+    /// Run the read-only transcript viewer used by `jcode view`.
+    pub async fn run_transcript_view(self, terminal: DefaultTerminal) -> Result<RunResult> {
+        replay::run_transcript_view(self, terminal).await
+    }
+
     /// Run an interactive swarm replay, rendering multiple sessions in tiled panes.
     pub async fn run_swarm_replay(
         terminal: DefaultTerminal,
@@ -0,0 +1,221 @@
+//! `/prompt <name> [var=value ...]`: send a saved prompt from
+//! `~/.jcode/prompts/` or the project's `.jcode/prompts/`.
+//!
+//! Variables the prompt uses but the command does not set are asked for one
+//! at a time through the input box. The prompt is expanded before it is
+//! queued, so the session stores the expanded text. `/prompt list` lists the
+//! prompts and `/prompt save <name>` saves the last sent message as one.
+
+use super::*;
+use crate::saved_prompt::{self, SavedPrompt};
+use std::collections::BTreeMap;
+
+const USAGE: &str = "Usage: `/prompt <name> [var=value ...]` sends a saved prompt, `/prompt list` lists them, `/prompt save <name>` saves your last message as one, `/prompt show` shows the system prompt and `/prompt sections` its optional sections.";
+
+/// Subcommands that cannot be used as prompt names.
+const RESERVED_NAMES: &[&str] = &["show", "sections", "list", "save"];
+
+/// A `/prompt` waiting for variable values.
+#[derive(Debug, Clone)]
+pub(super) struct PendingSavedPrompt {
+    prompt: SavedPrompt,
+    vars: BTreeMap<String, String>,
+    /// Variables still to ask for. The first one is being asked.
+    missing: Vec<String>,
+}
+
+/// Handle `/prompt list`, `/prompt save <name>` and `/prompt <name> ...`.
+/// `/prompt`, `/prompt show` and `/prompt sections` are system prompt reports.
+pub(super) fn handle_saved_prompt_command(app: &mut App, trimmed: &str) -> bool {
+    let Some(rest) = trimmed.strip_prefix("/prompt ") else {
+        return false;
+    };
+    let mut words = rest.split_whitespace();
+    match words.next() {
+        None | Some("show") | Some("sections") => false,
+        Some("list") => {
+            show_saved_prompts(app);
+            true
+        }
+        Some("save") => {
+            match (words.next(), words.next()) {
+                (Some(name), None) => save_last_message(app, name),
+                _ => app.push_display_message(DisplayMessage::error(USAGE)),
+            }
+            true
+        }
+        Some(name) => {
+            start_saved_prompt(app, name, words);
+            true
+        }
+    }
+}
+
+fn show_saved_prompts(app: &mut App) {
+    let working_dir = commands::active_working_dir(app);
+    let prompts = saved_prompt::list(working_dir.as_deref());
+    let mut out = String::new();
+    if prompts.is_empty() {
+        out.push_str("No saved prompts yet. Save your last message with `/prompt save <name>`, or add `<name>.md` files to:\n");
+        for dir in saved_prompt::prompt_dirs(working_dir.as_deref()) {
+            out.push_str(&format!("- {}\n", dir.display()));
+        }
+    } else {
+        for prompt in &prompts {
+            let vars: String = saved_prompt::missing_variables(&prompt.template, &BTreeMap::new())
+                .iter()
+                .map(|var| format!(" {}=…", var))
+                .collect();
+            out.push_str(&format!(
+                "- /prompt {}{}\n  {}\n",
+                prompt.name,
+                vars,
+                prompt.path.display()
+            ));
+        }
+    }
+    app.push_display_message(DisplayMessage::system(out).with_title("Saved prompts"));
+    app.set_status_notice(format!("Saved prompts: {}", prompts.len()));
+}
+
+fn save_last_message(app: &mut App, name: &str) {
+    if RESERVED_NAMES.contains(&name) {
+        app.push_display_message(DisplayMessage::error(format!(
+            "`{}` is a /prompt subcommand; pick another name.",
+            name
+        )));
+        return;
+    }
+    let Some(text) = app
+        .display_messages()
+        .iter()
+        .rev()
+        .find(|message| message.role == "user")
+        .map(|message| message.content.clone())
+    else {
+        app.push_display_message(DisplayMessage::error(
+            "No sent message to save yet.".to_string(),
+        ));
+        return;
+    };
+    match saved_prompt::save(name, &text) {
+        Ok(path) => {
+            app.push_display_message(DisplayMessage::system(format!(
+                "Saved your last message as `/prompt {}` in {}. Add `{{{{variable}}}}` placeholders there to fill in on each use.",
+                name,
+                path.display()
+            )));
+            app.set_status_notice(format!("Saved prompt · {}", name));
+        }
+        Err(error) => app.push_display_message(DisplayMessage::error(format!(
+            "Failed to save prompt: {}",
+            error
+        ))),
+    }
+}
+
+fn start_saved_prompt<'a>(app: &mut App, name: &str, args: impl Iterator<Item = &'a str>) {
+    let working_dir = commands::active_working_dir(app);
+    let prompt = match saved_prompt::find(name, working_dir.as_deref()) {
+        Ok(prompt) => prompt,
+        Err(error) => {
+            app.push_display_message(DisplayMessage::error(format!(
+                "{}. Use /prompt list to see saved prompts.",
+                error
+            )));
+            return;
+        }
+    };
+    let vars = match saved_prompt::parse_assignments(args) {
+        Ok(vars) => vars,
+        Err(error) => {
+            app.push_display_message(DisplayMessage::error(format!("{}. {}", error, USAGE)));
+            return;
+        }
+    };
+    let missing = saved_prompt::missing_variables(&prompt.template, &vars);
+    let pending = PendingSavedPrompt {
+        prompt,
+        vars,
+        missing,
+    };
+    app.continue_saved_prompt(pending);
+}
+
+impl App {
+    /// Whether the next submit is a value for a `/prompt` variable.
+    pub(super) fn saved_prompt_pending(&self) -> bool {
+        self.pending_saved_prompt.is_some()
+    }
+
+    /// Use the submitted input as the value of the variable being asked for.
+    pub(super) fn submit_saved_prompt_variable(&mut self, input: String) {
+        let Some(mut pending) = self.pending_saved_prompt.take() else {
+            return;
+        };
+        if input.trim() == "/cancel" {
+            self.push_display_message(DisplayMessage::system(format!(
+                "/prompt {} cancelled.",
+                pending.prompt.name
+            )));
+            self.set_status_notice("Prompt: cancelled");
+            return;
+        }
+        if !pending.missing.is_empty() {
+            let name = pending.missing.remove(0);
+            pending.vars.insert(name, input.trim().to_string());
+        }
+        self.continue_saved_prompt(pending);
+    }
+
+    /// Ask for the next missing variable, or expand and send the prompt.
+    fn continue_saved_prompt(&mut self, pending: PendingSavedPrompt) {
+        if let Some(next) = pending.missing.first() {
+            self.push_display_message(DisplayMessage::system(format!(
+                "`/prompt {}` needs `{}`. Type its value and press Enter, or `/cancel`.",
+                pending.prompt.name, next
+            )));
+            self.set_status_notice(format!("Prompt {} → {}?", pending.prompt.name, next));
+            self.pending_saved_prompt = Some(pending);
+            return;
+        }
+
+        let working_dir = commands::active_working_dir(self);
+        let expanded = saved_prompt::expand(
+            &pending.prompt.template,
+            &pending.vars,
+            &saved_prompt::PromptContext {
+                working_dir: working_dir.as_deref(),
+                clipboard: &input::read_clipboard_text,
+            },
+        );
+        let expanded = match expanded {
+            Ok(expanded) if !expanded.trim().is_empty() => expanded,
+            Ok(_) => {
+                self.push_display_message(DisplayMessage::error(format!(
+                    "/prompt {} expanded to an empty message.",
+                    pending.prompt.name
+                )));
+                return;
+            }
+            Err(error) => {
+                self.push_display_message(DisplayMessage::error(format!(
+                    "/prompt {}: {}",
+                    pending.prompt.name, error
+                )));
+                return;
+            }
+        };
+
+        self.queued_messages.push(expanded);
+        if self.is_processing {
+            self.set_status_notice(format!(
+                "Queued /prompt {} after the current turn",
+                pending.prompt.name
+            ));
+        } else {
+            self.pending_queued_dispatch = true;
+            self.set_status_notice(format!("Sending /prompt {}", pending.prompt.name));
+        }
+    }
+}
@@ -1092,6 +1092,101 @@ fn push_cache_baseline(lines: &mut Vec<String>, label: &str, baseline: Option<&K
     }
 }
 
+fn format_agent_limits_info(
+    last: Option<&crate::protocol::AgentLimitStatus>,
+    config: &crate::config::AgentLimitsConfig,
+) -> String {
+    fn usage<T: std::fmt::Display>(used: T, max: Option<T>, unit: &str) -> String {
+        match max {
+            Some(max) => format!("{used}{unit} / {max}{unit}"),
+            None => format!("{used}{unit} (no limit)"),
+        }
+    }
+
+    let Some(status) = last else {
+        let configured = |value: Option<u64>| {
+            value
+                .filter(|value| *value > 0)
+                .map(|value| value.to_string())
+                .unwrap_or_else(|| "unset".to_string())
+        };
+        return format!(
+            "No request with agent limits has finished in this session yet.\n\n\
+             Configured ([agent] in config.toml):\n\
+             max_turns: {}\n\
+             max_tool_calls_per_turn: {}\n\
+             max_wall_seconds: {}",
+            configured(config.max_turns.map(u64::from)),
+            configured(config.max_tool_calls_per_turn.map(u64::from)),
+            configured(config.max_wall_seconds),
+        );
+    };
+
+    let mut lines = vec![
+        "Last request:".to_string(),
+        format!("Turns: {}", usage(status.turns, status.max_turns, "")),
+        match status.max_tool_calls_per_turn {
+            Some(max) => format!("Tool calls: {} ({max} per turn)", status.tool_calls),
+            None => format!("Tool calls: {} (no limit)", status.tool_calls),
+        },
+        format!(
+            "Wall clock: {}",
+            usage(status.elapsed_secs, status.max_wall_seconds, "s")
+        ),
+    ];
+    match &status.breached {
+        Some(limit) => lines.push(format!("Stopped by: {limit}")),
+        None => lines.push("Stopped by: (within limits)".to_string()),
+    }
+    lines.join("\n")
+}
+
+fn format_memory_injection_report(
+    report: Option<&crate::protocol::MemoryInjectionReport>,
+) -> String {
+    fn row(entry: &crate::protocol::MemoryScoreBreakdown) -> String {
+        let mut line = format!(
+            "{:.2}  sim {:.2} · rec {:.2} · str {:.2} · cat {:.2}  ~{} tok  [{}] {}",
+            entry.score,
+            entry.similarity,
+            entry.recency,
+            entry.strength,
+            entry.category_weight,
+            entry.tokens,
+            entry.category,
+            entry.preview
+        );
+        if let Some(reason) = &entry.cut_reason {
+            line.push_str(&format!("  ({reason})"));
+        }
+        line
+    }
+
+    let Some(report) = report else {
+        return "No memories have been injected in this session yet.\n\n\
+                Limits and ranking weights are set under [memory] in config.toml."
+            .to_string();
+    };
+
+    let mut lines = vec![
+        format!(
+            "Injected {} of max {} · ~{} of {} tokens",
+            report.injected.len(),
+            report.max_entries,
+            report.tokens_used,
+            report.token_budget
+        ),
+        String::new(),
+    ];
+    lines.extend(report.injected.iter().map(row));
+    if !report.missed.is_empty() {
+        lines.push(String::new());
+        lines.push("Just missed:".to_string());
+        lines.extend(report.missed.iter().map(row));
+    }
+    lines.join("\n")
+}
+
 fn format_cache_stats(app: &App) -> String {
     let remote_usage = app.remote_token_usage_totals;
     let remote_cache_reported = remote_usage
@@ -1323,6 +1418,22 @@ fn format_cache_stats(app: &App) -> String {
         "- total_output_tokens: {}",
         bold_count(history_output_tokens)
     ));
+    let (overhead_input_tokens, overhead_output_tokens) = if app.is_remote {
+        remote_usage
+            .map(|usage| (usage.overhead_input_tokens, usage.overhead_output_tokens))
+            .unwrap_or((0, 0))
+    } else {
+        let totals = app.session.token_usage_totals();
+        (totals.overhead_input_tokens, totals.overhead_output_tokens)
+    };
+    lines.push(format!(
+        "- overhead_input_tokens: {}",
+        bold_count(overhead_input_tokens)
+    ));
+    lines.push(format!(
+        "- overhead_output_tokens: {}",
+        bold_count(overhead_output_tokens)
+    ));
     lines.push(format!(
         "- total_input_tokens_including_unrecorded_live: {}",
         bold_count(history_input_tokens.saturating_add(live_unrecorded_input_tokens))
@@ -1620,20 +1731,41 @@ fn build_skills_report(app: &App) -> String {
     let mut out = String::new();
 
     let active = app.active_skill().map(|s| s.to_string());
+    let auto_loaded = &app.remote_active_skills;
+    let marker_for = |name: &str| {
+        if active.as_deref() == Some(name) {
+            " (active)"
+        } else if auto_loaded.iter().any(|skill| skill.name == name) {
+            " (auto-loaded)"
+        } else {
+            ""
+        }
+    };
+
+    // Active skills: the one invoked with /name plus any the agent loaded
+    // because their triggers matched.
+    out.push_str("Active skills\n");
+    if let Some(name) = active.as_deref() {
+        out.push_str(&format!("- /{} (invoked)\n", name));
+    }
+    for skill in auto_loaded {
+        out.push_str(&format!(
+            "- /{} (auto-loaded: {})\n",
+            skill.name, skill.trigger
+        ));
+    }
+    if active.is_none() && auto_loaded.is_empty() {
+        out.push_str("- none\n");
+    }
 
     // Loaded skills. In remote mode we only have names; locally we have full
     // skill metadata (description + path).
-    out.push_str("Loaded skills\n");
+    out.push_str("\nLoaded skills\n");
     if app.is_remote && !app.remote_skills.is_empty() {
         let mut names = app.remote_skills.clone();
         names.sort();
         for name in &names {
-            let marker = if active.as_deref() == Some(name.as_str()) {
-                " (active)"
-            } else {
-                ""
-            };
-            out.push_str(&format!("- /{}{}\n", name, marker));
+            out.push_str(&format!("- /{}{}\n", name, marker_for(name)));
         }
     } else {
         let snapshot = app.current_skills_snapshot();
@@ -1645,12 +1777,7 @@ fn build_skills_report(app: &App) -> String {
             );
         } else {
             for skill in skills {
-                let marker = if active.as_deref() == Some(skill.name.as_str()) {
-                    " (active)"
-                } else {
-                    ""
-                };
-                out.push_str(&format!("- /{}{}\n", skill.name, marker));
+                out.push_str(&format!("- /{}{}\n", skill.name, marker_for(&skill.name)));
                 out.push_str(&format!("    {}\n", skill.description));
                 out.push_str(&format!("    path: {}\n", skill.path.display()));
             }
@@ -1718,7 +1845,94 @@ fn build_skills_report(app: &App) -> String {
     out.trim_end().to_string()
 }
 
+/// `/prompt show`: the static system prompt as this session assembles it,
+/// layer by layer with token estimates, followed by the full text.
+fn build_prompt_report(app: &App) -> String {
+    let working_dir = app
+        .session
+        .working_dir
+        .as_ref()
+        .map(std::path::PathBuf::from);
+    let tool_inputs = app
+        .display_messages
+        .iter()
+        .filter_map(|message| message.tool_data.as_ref())
+        .map(|tool| &tool.input);
+    let scoped_dirs =
+        crate::prompt::scoped_dirs_from_tool_inputs(tool_inputs, working_dir.as_deref());
+    let (split, info) = crate::prompt::build_system_prompt_split_scoped(
+        None,
+        &[],
+        app.session.is_canary,
+        None,
+        working_dir.as_deref(),
+        &scoped_dirs,
+    );
+
+    let mut out = String::from("Layers ([prompt] sources)\n");
+    for layer in &info.prompt_layers.layers {
+        out.push_str(&format!(
+            "- {} [{}]: ~{} tokens{}\n",
+            layer.label,
+            layer.kind.as_str(),
+            layer.estimated_tokens(),
+            if layer.truncated { " (truncated)" } else { "" }
+        ));
+        if let Some(path) = layer.path.as_ref() {
+            out.push_str(&format!("    {}\n", path.display()));
+        }
+    }
+    if info.prompt_layers.layers.is_empty() {
+        out.push_str("- none\n");
+    }
+    if !info.prompt_layers.warnings.is_empty() {
+        out.push_str("\nWarnings\n");
+        for warning in &info.prompt_layers.warnings {
+            out.push_str(&format!("- {}\n", warning));
+        }
+    }
+    out.push_str(&format!(
+        "\nAssembled static prompt: ~{} tokens (skills list, profile, and per-turn context not shown)\n\n{}",
+        crate::util::estimate_tokens(&split.static_part),
+        split.static_part
+    ));
+    out
+}
+
+/// `/prompt sections` in local mode: the sections of the prompt this process
+/// builds. Remote sessions ask the server, whose agent also adds git, todos
+/// and usage and measures the cache impact.
+fn build_prompt_sections_report(app: &App) -> String {
+    let working_dir = app
+        .session
+        .working_dir
+        .as_ref()
+        .map(std::path::PathBuf::from);
+    let (split, _info) = crate::prompt::build_system_prompt_split(
+        None,
+        &[],
+        app.session.is_canary,
+        None,
+        working_dir.as_deref(),
+    );
+    crate::prompt::sections_report(&crate::config::config().prompt, &split, None)
+}
+
 pub(super) fn handle_info_command(app: &mut App, trimmed: &str) -> bool {
+    if trimmed == "/prompt" || trimmed == "/prompt show" {
+        app.push_display_message(
+            DisplayMessage::system(build_prompt_report(app)).with_title("System prompt"),
+        );
+        return true;
+    }
+
+    if trimmed == "/prompt sections" {
+        app.push_display_message(
+            DisplayMessage::system(build_prompt_sections_report(app)).with_title("Prompt sections"),
+        );
+        return true;
+    }
+
     if trimmed == "/skills" {
         // Sync from disk first so skills added by agent-side `skill_manage
         // reload_all` (which only updates the server process registry) show up
@@ -1915,6 +2129,33 @@ pub(super) fn handle_info_command(app: &mut App, trimmed: &str) -> bool {
         return true;
     }
 
+    if trimmed == "/turninfo" {
+        app.push_display_message(DisplayMessage {
+            role: "system".to_string(),
+            content: format_agent_limits_info(
+                app.last_agent_limits.as_ref(),
+                &crate::config::config().agent,
+            ),
+            tool_calls: vec![],
+            duration_secs: None,
+            title: Some("Agent limits".to_string()),
+            tool_data: None,
+        });
+        return true;
+    }
+
+    if trimmed == "/context memories" {
+        app.push_display_message(DisplayMessage {
+            role: "system".to_string(),
+            content: format_memory_injection_report(app.last_memory_injection.as_ref()),
+            tool_calls: vec![],
+            duration_secs: None,
+            title: Some("Memory injection".to_string()),
+            tool_data: None,
+        });
+        return true;
+    }
+
     if trimmed == "/context" {
         let cwd = std::env::current_dir()
             .map(|p| p.display().to_string())
@@ -1,253 +1,17 @@
+use super::command_registry::REGISTERED_COMMANDS;
 use super::*;
-use crate::tui::core;
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
-#[derive(Clone, Copy)]
-struct RegisteredCommand {
-    name: &'static str,
-    help: &'static str,
-    hidden: bool,
-}
-
-impl RegisteredCommand {
-    const fn public(name: &'static str, help: &'static str) -> Self {
-        Self {
-            name,
-            help,
-            hidden: false,
-        }
-    }
-
-    const fn remote(name: &'static str, help: &'static str) -> Self {
-        Self {
-            name,
-            help,
-            hidden: false,
-        }
-    }
-
-    const fn hidden(name: &'static str, help: &'static str) -> Self {
-        Self {
-            name,
-            help,
-            hidden: true,
-        }
-    }
-}
-
-const REGISTERED_COMMANDS: &[RegisteredCommand] = &[
-    RegisteredCommand::public("/help", "Show help and keyboard shortcuts"),
-    RegisteredCommand::public("/?", "Show help and keyboard shortcuts"),
-    RegisteredCommand::public("/commands", "Alias for /help"),
-    RegisteredCommand::public("/model", "List or switch models"),
-    RegisteredCommand::public("/models", "Alias for /model"),
-    RegisteredCommand::public(
-        "/provider-test-coverage",
-        "Show live-test evidence for the current provider/model",
-    ),
-    RegisteredCommand::hidden("/model-status", "Alias for /provider-test-coverage"),
-    RegisteredCommand::public("/refresh-model-list", "Refresh provider model catalogs"),
-    RegisteredCommand::public("/agents", "Configure models for agent roles"),
-    RegisteredCommand::public("/subagent", "Launch a subagent manually"),
-    RegisteredCommand::public("/observe", "Show the latest tool context in the side panel"),
-    RegisteredCommand::public(
-        "/todos",
-        "Show the current session todo list in the side panel",
-    ),
-    RegisteredCommand::public("/splitview", "Mirror the current chat in the side panel"),
-    RegisteredCommand::public("/split-view", "Alias for /splitview"),
-    RegisteredCommand::public("/btw", "Ask a side question in the side panel"),
-    RegisteredCommand::public("/ssh", "Connect to a remote machine using system SSH"),
-    RegisteredCommand::public("/git", "Show git status for the session working directory"),
-    RegisteredCommand::public("/hotkeys", "List hotkeys with your personal usage"),
-    RegisteredCommand::hidden("/keys", "Alias for /hotkeys"),
-    RegisteredCommand::public("/commit", "Make logical commits from current changes"),
-    RegisteredCommand::public(
-        "/commit-push",
-        "Make logical commits from current changes, then push",
-    ),
-    RegisteredCommand::public(
-        "/cut-release",
-        "Commit + push current changes, bump version, and cut a release",
-    ),
-    RegisteredCommand::hidden("/commit-push-release", "Alias for /cut-release"),
-    RegisteredCommand::public("/transcript", "Open the current session transcript file"),
-    RegisteredCommand::public("/subagent-model", "Show/change subagent model policy"),
-    RegisteredCommand::public("/autoreview", "Show/toggle automatic end-of-turn review"),
-    RegisteredCommand::public("/autojudge", "Show/toggle automatic end-of-turn judging"),
-    RegisteredCommand::public("/review", "Launch a one-shot headed review session"),
-    RegisteredCommand::public("/judge", "Launch a one-shot headed judge session"),
-    RegisteredCommand::public("/effort", "Show/change reasoning effort (Alt+left/right)"),
-    RegisteredCommand::public("/fast", "Toggle fast mode"),
-    RegisteredCommand::public("/transport", "Show/change connection transport"),
-    RegisteredCommand::public("/alignment", "Show/change default text alignment"),
-    RegisteredCommand::public(
-        "/compact-notifications",
-        "Show/toggle single-line swarm/file-activity notifications",
-    ),
-    RegisteredCommand::public(
-        "/show-agentgrep-output",
-        "Show/toggle full agentgrep search output inline in chat",
-    ),
-    RegisteredCommand::public(
-        "/reasoning",
-        "Show/change reasoning display (off/full/current)",
-    ),
-    RegisteredCommand::public("/clear", "Clear conversation history"),
-    RegisteredCommand::public("/rewind", "Rewind conversation to previous message"),
-    RegisteredCommand::public("/poke", "Poke model to resume with incomplete todos"),
-    RegisteredCommand::public("/plan", "Create a plan-only response as a plan card"),
-    RegisteredCommand::public("/improve", "Autonomously improve the repository"),
-    RegisteredCommand::public("/refactor", "Run a safe refactor loop"),
-    RegisteredCommand::public("/compact", "Compact context"),
-    RegisteredCommand::public("/fix", "Recover when the model cannot continue"),
-    RegisteredCommand::public("/dictate", "Run configured external dictation command"),
-    RegisteredCommand::public("/dictation", "Alias for /dictate"),
-    RegisteredCommand::public("/memory", "Toggle memory feature"),
-    RegisteredCommand::public("/test", "Verify a claim/current changes with layered tests"),
-    RegisteredCommand::public(
-        "/initiatives",
-        "Open initiatives overview / resume tracked initiatives",
-    ),
-    RegisteredCommand::public("/goals", "Legacy alias for /initiatives"),
-    RegisteredCommand::public("/swarm", "Toggle swarm feature"),
-    RegisteredCommand::public("/overnight", "Run a supervised overnight coordinator"),
-    RegisteredCommand::public("/context", "Show the full session context snapshot"),
-    RegisteredCommand::public(
-        "/skills",
-        "Show loaded skills and jcode-endorsed recommendations",
-    ),
-    RegisteredCommand::public("/version", "Show current version"),
-    RegisteredCommand::public("/changelog", "Show recent changes in this build"),
-    RegisteredCommand::public("/info", "Show session info and tokens"),
-    RegisteredCommand::public("/usage", "Show connected provider usage limits"),
-    RegisteredCommand::public(
-        "/productivity",
-        "Generate a shareable usage report + dashboard image",
-    ),
-    RegisteredCommand::public("/wrapped", "Alias for /productivity"),
-    RegisteredCommand::public("/feedback", "Send feedback about jcode"),
-    RegisteredCommand::public("/subscription", "Show jcode subscription status"),
-    RegisteredCommand::public("/config", "Show or edit configuration"),
-    RegisteredCommand::public("/log", "Mark the current location in the jcode logs"),
-    RegisteredCommand::public(
-        "/keys",
-        "Show keybinding conflicts with your terminal and OS (/keys refresh to rescan)",
-    ),
-    RegisteredCommand::public(
-        "/diff",
-        "Cycle or set diff display mode (off/inline/full/pinned/file)",
-    ),
-    RegisteredCommand::public(
-        "/onboarding-preview",
-        "Preview the first-run onboarding screen",
-    ),
-    RegisteredCommand::public(
-        "/onboarding-sim",
-        "Walk through every first-run onboarding screen (Cmd+5)",
-    ),
-    RegisteredCommand::public("/reload", "Reload into newest available binary"),
-    RegisteredCommand::public("/restart", "Restart with current binary"),
-    RegisteredCommand::public("/rebuild", "Background rebuild and auto reload"),
-    RegisteredCommand::public("/selfdev", "Open a new self-dev jcode session"),
-    RegisteredCommand::public("/update", "Background update and auto reload"),
-    RegisteredCommand::public("/resume", "Open session picker"),
-    RegisteredCommand::public("/sessions", "Alias for /resume"),
-    RegisteredCommand::public("/session", "Alias for /resume"),
-    RegisteredCommand::public("/catchup", "Open Catch Up picker"),
-    RegisteredCommand::public("/back", "Return to the previous Catch Up session"),
-    RegisteredCommand::public("/save", "Bookmark session for easy access"),
-    RegisteredCommand::public("/unsave", "Remove bookmark from session"),
-    RegisteredCommand::public("/rename", "Rename current session"),
-    RegisteredCommand::public("/fork", "Fork session into a new window (optional prompt)"),
-    RegisteredCommand::hidden("/split", "Alias for /fork"),
-    RegisteredCommand::public("/transfer", "Compact context into a fresh handoff session"),
-    RegisteredCommand::public("/workspace", "Niri-style session workspace"),
-    RegisteredCommand::public("/quit", "Exit jcode"),
-    RegisteredCommand::public("/auth", "Show authentication status"),
-    RegisteredCommand::public("/login", "Login to a provider"),
-    RegisteredCommand::public("/logout", "Log out of a provider"),
-    RegisteredCommand::public("/account", "Open the combined account picker"),
-    RegisteredCommand::public("/accounts", "Alias for /account"),
-    RegisteredCommand::public("/cache", "Show cache stats or set cache TTL"),
-    RegisteredCommand::public("/debug-visual", "Toggle visual debug overlay"),
-    RegisteredCommand::public("/screenshot-mode", "Toggle screenshot capture mode"),
-    RegisteredCommand::public("/screenshot", "Capture a screenshot debug state"),
-    RegisteredCommand::public("/record", "Record a demo capture"),
-    RegisteredCommand::remote("/client-reload", "Force reload client binary"),
-    RegisteredCommand::remote("/server-reload", "Force reload server binary"),
-    RegisteredCommand::remote(
-        "/continue",
-        "Continue every interrupted live session that would auto-resume",
-    ),
-    RegisteredCommand::remote("/resumeall", "Alias for /continue"),
-    RegisteredCommand::hidden("/z", "Secret premium-mode command"),
-    RegisteredCommand::hidden("/zz", "Secret premium-mode command"),
-    RegisteredCommand::hidden("/zzz", "Secret premium-mode command"),
-    RegisteredCommand::hidden("/zstatus", "Secret premium-mode status command"),
-];
-
 impl App {
     /// Find word boundary going backward (for Ctrl+W, Alt+B)
     pub(super) fn find_word_boundary_back(&self) -> usize {
-        if self.cursor_pos == 0 {
-            return 0;
-        }
-        let mut pos = self.cursor_pos;
-
-        // Move back one char
-        pos = core::prev_char_boundary(&self.input, pos);
-
-        // Skip trailing whitespace
-        while pos > 0 {
-            let ch = self.input[pos..].chars().next().unwrap_or(' ');
-            if !ch.is_whitespace() {
-                break;
-            }
-            pos = core::prev_char_boundary(&self.input, pos);
-        }
-
-        // Skip word characters
-        while pos > 0 {
-            let prev = core::prev_char_boundary(&self.input, pos);
-            let ch = self.input[prev..].chars().next().unwrap_or(' ');
-            if ch.is_whitespace() {
-                break;
-            }
-            pos = prev;
-        }
-
-        pos
+        crate::tui::line_editor::word_boundary_back(&self.input, self.cursor_pos)
     }
 
     /// Find word boundary going forward (for Alt+F, Alt+D)
     pub(super) fn find_word_boundary_forward(&self) -> usize {
-        let len = self.input.len();
-        if self.cursor_pos >= len {
-            return len;
-        }
-        let mut pos = self.cursor_pos;
-
-        // Skip current word
-        while pos < len {
-            let ch = self.input[pos..].chars().next().unwrap_or(' ');
-            if ch.is_whitespace() {
-                break;
-            }
-            pos = core::next_char_boundary(&self.input, pos);
-        }
-
-        // Skip whitespace
-        while pos < len {
-            let ch = self.input[pos..].chars().next().unwrap_or(' ');
-            if !ch.is_whitespace() {
-                break;
-            }
-            pos = core::next_char_boundary(&self.input, pos);
-        }
-
-        pos
+        crate::tui::line_editor::word_boundary_forward(&self.input, self.cursor_pos)
     }
 
     pub fn input(&self) -> &str {
@@ -348,6 +112,14 @@ impl App {
     }
 
     fn model_suggestion_candidates(&self) -> Vec<(String, &'static str)> {
+        self.model_choice_ids()
+            .into_iter()
+            .map(|model| (format!("/model {}", model), "Switch to model"))
+            .collect()
+    }
+
+    /// Model ids the user can switch to right now, current model first.
+    pub(super) fn model_choice_ids(&self) -> Vec<String> {
         fn push_unique(
             seen: &mut std::collections::HashSet<String>,
             entries: &mut Vec<String>,
@@ -387,9 +159,6 @@ impl App {
         }
 
         models
-            .into_iter()
-            .map(|model| (format!("/model {}", model), "Switch to model"))
-            .collect()
     }
 
     fn model_provider_suggestion_candidates(&self, model: &str) -> Vec<(String, &'static str)> {
@@ -457,6 +226,74 @@ impl App {
         suggestions
     }
 
+    /// `/resume <name>` candidates: the most recently updated sessions.
+    fn resume_suggestion_candidates(&self, command: &str) -> Vec<(String, &'static str)> {
+        const RESUME_SUGGESTION_LIMIT: usize = 30;
+        const RESUME_CANDIDATES_TTL: Duration = Duration::from_secs(10);
+
+        let fresh = self
+            .resume_candidates_cache
+            .borrow()
+            .as_ref()
+            .is_some_and(|cache| cache.loaded_at.elapsed() < RESUME_CANDIDATES_TTL);
+        if !fresh {
+            let mut sessions: Vec<(SystemTime, String)> = crate::storage::jcode_dir()
+                .ok()
+                .and_then(|dir| std::fs::read_dir(dir.join("sessions")).ok())
+                .into_iter()
+                .flatten()
+                .flatten()
+                .filter_map(|entry| {
+                    let path = entry.path();
+                    if path.extension().is_none_or(|ext| ext != "json") {
+                        return None;
+                    }
+                    let stem = path.file_stem()?.to_str()?.to_string();
+                    let modified = entry.metadata().and_then(|meta| meta.modified()).ok()?;
+                    Some((modified, stem))
+                })
+                .collect();
+            sessions.sort_by(|a, b| b.0.cmp(&a.0));
+            let current = self.active_client_session_id();
+            let stems: Vec<String> = sessions
+                .into_iter()
+                .map(|(_, stem)| stem)
+                .filter(|stem| Some(stem.as_str()) != current)
+                .take(RESUME_SUGGESTION_LIMIT)
+                .collect();
+            // Sessions can share a short name; those fall back to the full id
+            // so each candidate still resumes the session it was listed for.
+            let mut name_counts = std::collections::HashMap::new();
+            for stem in &stems {
+                if let Some(name) = crate::id::extract_session_name(stem) {
+                    *name_counts.entry(name).or_insert(0usize) += 1;
+                }
+            }
+            let names = stems
+                .iter()
+                .map(|stem| match crate::id::extract_session_name(stem) {
+                    Some(name) if name_counts.get(name) == Some(&1) => name.to_string(),
+                    _ => stem.clone(),
+                })
+                .collect();
+            *self.resume_candidates_cache.borrow_mut() = Some(ResumeCandidatesCache {
+                loaded_at: Instant::now(),
+                names,
+            });
+        }
+        self.resume_candidates_cache
+            .borrow()
+            .as_ref()
+            .map(|cache| {
+                cache
+                    .names
+                    .iter()
+                    .map(|name| (format!("{command} {name}"), "Resume this session"))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     /// Get command suggestions based on current input (or base input for cycling)
     pub(super) fn get_suggestions_for(&self, input: &str) -> Vec<(String, &'static str)> {
         let input = input.trim_start();
@@ -488,6 +325,12 @@ impl App {
             return self.rank_suggestions(input, suggestions);
         }
 
+        for command in ["/resume", "/sessions"] {
+            if prefix.starts_with(&format!("{command} ")) {
+                return self.rank_suggestions(input, self.resume_suggestion_candidates(command));
+            }
+        }
+
         if prefix.starts_with("/agents ") {
             return self.rank_suggestions(
                 input,
@@ -851,6 +694,37 @@ impl App {
             );
         }
 
+        if prefix.starts_with("/context ") {
+            return self.rank_suggestions(
+                input,
+                vec![(
+                    "/context memories".into(),
+                    "Show the last memory injection and near misses",
+                )],
+            );
+        }
+
+        if prefix.starts_with("/autocommit ") {
+            return self.rank_suggestions(
+                input,
+                vec![
+                    ("/autocommit on".into(), "Commit changes after each turn"),
+                    ("/autocommit off".into(), "Stop committing after each turn"),
+                    ("/autocommit status".into(), "Show auto-commit status"),
+                ],
+            );
+        }
+
+        if prefix.starts_with("/tee ") {
+            return self.rank_suggestions(
+                input,
+                vec![
+                    ("/tee off".into(), "Stop appending responses to a file"),
+                    ("/tee status".into(), "Show where responses are appended"),
+                ],
+            );
+        }
+
         if prefix.starts_with("/improve ") {
             return self.rank_suggestions(
                 input,
@@ -976,6 +850,26 @@ impl App {
             );
         }
 
+        if prefix.starts_with("/session ") {
+            return self.rank_suggestions(
+                input,
+                vec![
+                    (
+                        "/session stats".into(),
+                        "Show responses, tokens and time per model in this session",
+                    ),
+                    (
+                        "/session footers on".into(),
+                        "Show model and usage footers under restored messages",
+                    ),
+                    (
+                        "/session footers off".into(),
+                        "Hide footers under restored messages",
+                    ),
+                ],
+            );
+        }
+
         if prefix.starts_with("/show-agentgrep-output ") {
             return self.rank_suggestions(
                 input,
@@ -1233,6 +1127,57 @@ impl App {
                     yes_highlighted: *yes_highlighted,
                 }
             }
+            Some(OnboardingPhase::Setup(wizard)) => {
+                use crate::tui::app::onboarding_flow::SetupRow;
+                let on_off = |on: bool| if on { "on" } else { "off" }.to_string();
+                let rows = SetupRow::ALL
+                    .iter()
+                    .map(|&row| {
+                        let (value, editable) = match row {
+                            SetupRow::Model => (
+                                wizard
+                                    .model
+                                    .clone()
+                                    .unwrap_or_else(|| "provider default".to_string()),
+                                true,
+                            ),
+                            SetupRow::Memory => (on_off(wizard.memory), true),
+                            SetupRow::Ambient => (on_off(wizard.ambient), true),
+                            SetupRow::Layout => (
+                                if wizard.centered {
+                                    "centered"
+                                } else {
+                                    "left-aligned"
+                                }
+                                .to_string(),
+                                true,
+                            ),
+                            SetupRow::Instructions if wizard.instructions_exist => {
+                                ("already exists".to_string(), false)
+                            }
+                            SetupRow::Instructions => (
+                                if wizard.create_instructions {
+                                    "create from template"
+                                } else {
+                                    "skip"
+                                }
+                                .to_string(),
+                                true,
+                            ),
+                        };
+                        crate::tui::SetupPromptRow {
+                            label: row.label().to_string(),
+                            value,
+                            editable,
+                        }
+                    })
+                    .collect();
+                OnboardingWelcomeKind::Setup(crate::tui::SetupPrompt {
+                    rows,
+                    cursor: wizard.cursor,
+                    continue_focused: wizard.continue_focused,
+                })
+            }
             Some(OnboardingPhase::ModelSelect) => OnboardingWelcomeKind::Suggestions,
             Some(OnboardingPhase::ContinuePrompt {
                 cli,
@@ -1252,7 +1197,8 @@ impl App {
     }
 
     /// Whether the guided onboarding flow is in a phase that should take over
-    /// the welcome screen body (login, OpenAI-login prompt, or continue prompt).
+    /// the welcome screen body (login, OpenAI-login prompt, settings form, or
+    /// continue prompt).
     /// The transcript-pick phase uses the session-picker overlay instead, and
     /// the suggestions phase is the default welcome body.
     fn onboarding_flow_drives_welcome(&self) -> bool {
@@ -1261,6 +1207,7 @@ impl App {
             self.onboarding_phase(),
             Some(OnboardingPhase::Login { .. })
                 | Some(OnboardingPhase::LoginOpenAi { .. })
+                | Some(OnboardingPhase::Setup(_))
                 | Some(OnboardingPhase::ContinuePrompt { .. })
         )
     }
@@ -1402,7 +1349,13 @@ impl App {
     }
 
     pub(super) fn remember_input_undo_state(&mut self) {
-        let snapshot = (self.input.clone(), self.cursor_pos.min(self.input.len()));
+        self.remember_input_undo_snapshot(self.input.clone(), self.cursor_pos);
+    }
+
+    /// Push an undo entry for a draft captured before an edit was applied.
+    pub(super) fn remember_input_undo_snapshot(&mut self, input: String, cursor_pos: usize) {
+        let cursor_pos = cursor_pos.min(input.len());
+        let snapshot = (input, cursor_pos);
         if self.input_undo_stack.last() == Some(&snapshot) {
             return;
         }
@@ -1462,6 +1415,7 @@ impl App {
                 | "/usage"
                 | "/subscription"
                 | "/poke"
+                | "/premium"
                 | "/memory"
                 | "/test"
                 | "/initiatives"
@@ -1470,6 +1424,8 @@ impl App {
                 | "/goals show"
                 | "/swarm"
                 | "/plan"
+                | "/aside"
+                | "/compare"
                 | "/improve"
                 | "/refactor"
                 | "/rewind"
@@ -1482,6 +1438,14 @@ impl App {
                 | "/config"
                 | "/save"
                 | "/rename"
+                | "/root"
+                | "/cd"
+                | "/instructions"
+                | "/tee"
+                | "/view"
+                | "/autocommit"
+                | "/context"
+                | "/remember"
                 | "/cache"
         )
     }
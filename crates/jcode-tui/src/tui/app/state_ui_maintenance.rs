@@ -33,7 +33,7 @@ impl App {
         }
         if action == crate::bus::ClientMaintenanceAction::Rebuild {
             content.push_str(
-                "\n\nPipeline: git pull --ff-only → cargo build --release → cargo test --release -- --test-threads=1",
+                "\n\nPipeline: git pull --ff-only → cargo build --release → cargo test --release (crates affected since the last promoted build; `/rebuild --full-tests` for everything)",
             );
         }
         content
@@ -81,25 +81,49 @@ impl App {
         true
     }
 
-    pub(super) fn start_background_client_rebuild(&mut self, session_id: String) {
-        self.start_background_client_maintenance(
-            crate::bus::ClientMaintenanceAction::Rebuild,
-            session_id,
+    pub(super) fn start_background_client_rebuild(&mut self, session_id: String, full_tests: bool) {
+        let action = crate::bus::ClientMaintenanceAction::Rebuild;
+        if !self.claim_background_client_maintenance(action) {
+            return;
+        }
+        self.set_status_notice("Starting background rebuild...");
+        self.set_client_maintenance_message(
+            action,
+            Self::client_maintenance_card_message(
+                action,
+                "starting background rebuild",
+                if full_tests {
+                    "Running in the background with the full test suite. jcode will reload automatically after the rebuild succeeds."
+                } else {
+                    "Running in the background. jcode will reload automatically after the rebuild succeeds."
+                },
+            ),
         );
+        crate::session_rebuild::spawn_background_session_rebuild(session_id, full_tests);
+    }
+
+    /// Parse `/rebuild [--full-tests]`, returning whether the full test suite
+    /// was requested.
+    pub(super) fn parse_rebuild_command(trimmed: &str) -> Option<bool> {
+        match trimmed.strip_prefix("/rebuild")?.trim() {
+            "" => Some(false),
+            "--full-tests" => Some(true),
+            _ => None,
+        }
     }
 
     pub(super) fn start_background_client_update(&mut self, session_id: String) {
-        self.start_background_client_maintenance(
-            crate::bus::ClientMaintenanceAction::Update,
-            session_id,
-        );
+        if self.claim_background_client_maintenance(crate::bus::ClientMaintenanceAction::Update) {
+            crate::update::spawn_background_session_update(session_id);
+        }
     }
 
-    fn start_background_client_maintenance(
+    /// Mark `action` as the running background maintenance job. Returns false
+    /// (after telling the user) when another job is already running.
+    fn claim_background_client_maintenance(
         &mut self,
         action: crate::bus::ClientMaintenanceAction,
-        session_id: String,
-    ) {
+    ) -> bool {
         if let Some(current) = self.background_client_action {
             let message = Self::client_maintenance_busy_message(current, action);
             self.set_status_notice(&message);
@@ -107,29 +131,12 @@ impl App {
                 current,
                 Self::client_maintenance_card_message(current, "already running", message),
             );
-            return;
+            return false;
         }
 
         self.background_client_action = Some(action);
         self.pending_background_client_reload = None;
-
-        match action {
-            crate::bus::ClientMaintenanceAction::Update => {
-                crate::update::spawn_background_session_update(session_id);
-            }
-            crate::bus::ClientMaintenanceAction::Rebuild => {
-                self.set_status_notice("Starting background rebuild...");
-                self.set_client_maintenance_message(
-                    action,
-                    Self::client_maintenance_card_message(
-                        action,
-                        "starting background rebuild",
-                        "Running in the background. jcode will reload automatically after the rebuild succeeds.",
-                    ),
-                );
-                crate::session_rebuild::spawn_background_session_rebuild(session_id);
-            }
-        }
+        true
     }
 
     pub(super) fn handle_update_status(&mut self, status: crate::bus::UpdateStatus) {
@@ -53,6 +53,25 @@ fn stored_message_visible_text(message: &crate::session::StoredMessage) -> Strin
                 parts.push(format!("[image:{}]", media_type));
             }
             ContentBlock::OpenAICompaction { .. } => {}
+            ContentBlock::ServerToolUse { name, input, .. } => {
+                parts.push(format!("[tool:{} {}]", name, input));
+            }
+            ContentBlock::WebSearchResult {
+                results,
+                error_code,
+                ..
+            } => {
+                parts.push(crate::message::web_search_result_text(
+                    results,
+                    error_code.as_deref(),
+                ));
+            }
+            ContentBlock::Citations { citations } => {
+                let sources = crate::message::citation_sources_markdown(citations);
+                if !sources.is_empty() {
+                    parts.push(sources.trim().to_string());
+                }
+            }
         }
     }
     parts.join("\n\n")
@@ -319,6 +319,34 @@ impl App {
         self.mcp_server_names.clone()
     }
 
+    /// Start the timeout countdown when `tool_name` is an MCP tool, using the
+    /// server's `timeout_secs` from mcp.json.
+    pub(super) fn start_mcp_call_countdown(&mut self, tool_name: &str) {
+        self.mcp_call_deadline = tool_name
+            .strip_prefix("mcp__")
+            .and_then(|rest| rest.split_once("__"))
+            .map(|(server, _)| {
+                let timeout = crate::mcp::McpConfig::load()
+                    .servers
+                    .get(server)
+                    .map(|config| config.call_timeout())
+                    .unwrap_or(Duration::from_secs(
+                        crate::mcp::DEFAULT_TOOL_CALL_TIMEOUT_SECS,
+                    ));
+                Instant::now() + timeout
+            });
+    }
+
+    /// Time left before the running MCP tool call times out.
+    pub(super) fn mcp_call_remaining(&self) -> Option<Duration> {
+        match &self.status {
+            ProcessingStatus::RunningTool(name) if name.starts_with("mcp__") => self
+                .mcp_call_deadline
+                .map(|deadline| deadline.saturating_duration_since(Instant::now())),
+            _ => None,
+        }
+    }
+
     /// Scroll to the previous user prompt (scroll up - earlier in conversation)
     pub fn scroll_to_prev_prompt(&mut self) {
         let positions = ui::last_user_prompt_positions();
@@ -467,6 +467,11 @@ fn cache_stats_uses_remote_history_token_usage_totals() {
         cache_reported_input_tokens: 1_000_000,
         cache_read_input_tokens: 600_000,
         cache_creation_input_tokens: 50_000,
+        web_search_requests: 0,
+        pruned_context_tokens: 0,
+        redactions: 0,
+        overhead_input_tokens: 0,
+        overhead_output_tokens: 0,
     });
 
     assert!(super::state_ui::handle_info_command(
@@ -518,6 +523,83 @@ fn version_command_shows_remote_server_identity_and_update_status() {
     assert!(content.contains("reload recommended"), "{content}");
 }
 
+#[test]
+fn turninfo_command_shows_last_request_limit_usage() {
+    let mut app = create_test_app();
+    app.last_agent_limits = Some(crate::protocol::AgentLimitStatus {
+        turns: 20,
+        tool_calls: 57,
+        elapsed_secs: 312,
+        max_turns: Some(20),
+        max_tool_calls_per_turn: None,
+        max_wall_seconds: Some(1800),
+        breached: Some("max_turns".to_string()),
+    });
+
+    assert!(super::state_ui::handle_info_command(&mut app, "/turninfo"));
+    let content = app.display_messages().last().unwrap().content.clone();
+    assert!(content.contains("Turns: 20 / 20"), "{content}");
+    assert!(content.contains("Tool calls: 57 (no limit)"), "{content}");
+    assert!(content.contains("Wall clock: 312s / 1800s"), "{content}");
+    assert!(content.contains("Stopped by: max_turns"), "{content}");
+}
+
+#[test]
+fn context_memories_command_shows_last_injection_ranking() {
+    let mut app = create_test_app();
+    assert!(super::state_ui::handle_info_command(
+        &mut app,
+        "/context memories"
+    ));
+    let content = app.display_messages().last().unwrap().content.clone();
+    assert!(
+        content.contains("No memories have been injected"),
+        "{content}"
+    );
+
+    let breakdown = |preview: &str, score: f32, cut_reason: Option<&str>| {
+        crate::protocol::MemoryScoreBreakdown {
+            id: preview.to_lowercase(),
+            category: "fact".to_string(),
+            preview: preview.to_string(),
+            similarity: 0.9,
+            recency: 0.5,
+            strength: 0.33,
+            category_weight: 0.6,
+            score,
+            tokens: 12,
+            cut_reason: cut_reason.map(str::to_string),
+        }
+    };
+    app.last_memory_injection = Some(crate::protocol::MemoryInjectionReport {
+        token_budget: 600,
+        max_entries: 1,
+        tokens_used: 12,
+        injected: vec![breakdown("Uses nextest", 0.74, None)],
+        missed: vec![breakdown("Prefers tabs", 0.61, Some("entry limit"))],
+    });
+
+    assert!(super::state_ui::handle_info_command(
+        &mut app,
+        "/context memories"
+    ));
+    let message = app.display_messages().last().unwrap();
+    assert_eq!(message.title.as_deref(), Some("Memory injection"));
+    let content = &message.content;
+    assert!(
+        content.contains("Injected 1 of max 1 · ~12 of 600 tokens"),
+        "{content}"
+    );
+    assert!(
+        content.contains(
+            "0.74  sim 0.90 · rec 0.50 · str 0.33 · cat 0.60  ~12 tok  [fact] Uses nextest"
+        ),
+        "{content}"
+    );
+    assert!(content.contains("Just missed:"), "{content}");
+    assert!(content.contains("Prefers tabs  (entry limit)"), "{content}");
+}
+
 #[test]
 fn skills_command_lists_loaded_and_endorsed_skills() {
     let mut app = create_test_app();
@@ -562,6 +644,55 @@ fn skills_command_lists_loaded_and_endorsed_skills() {
     );
 }
 
+#[test]
+fn prompt_command_asks_for_missing_variables_and_queues_the_expanded_prompt() {
+    let _env_guard = crate::storage::lock_test_env();
+    let home = tempfile::tempdir().expect("home");
+    let project = tempfile::tempdir().expect("project");
+    crate::env::set_var("JCODE_HOME", home.path());
+    let prompts_dir = project.path().join(".jcode").join("prompts");
+    std::fs::create_dir_all(&prompts_dir).expect("prompts dir");
+    std::fs::write(
+        prompts_dir.join("review.md"),
+        "Review against {{base}}, focusing on {{focus}}. {{file:NOTES.md}}",
+    )
+    .expect("write prompt");
+    std::fs::write(project.path().join("NOTES.md"), "Be strict.").expect("write notes");
+
+    let mut app = create_test_app();
+    app.session.working_dir = Some(project.path().display().to_string());
+
+    app.input = "/prompt review base=main".to_string();
+    app.submit_input();
+    assert!(app.saved_prompt_pending());
+    assert!(app.queued_messages().is_empty());
+    assert!(
+        app.display_messages()
+            .last()
+            .unwrap()
+            .content
+            .contains("needs `focus`")
+    );
+
+    app.input = "error handling".to_string();
+    app.submit_input();
+    assert!(!app.saved_prompt_pending());
+    assert_eq!(
+        app.queued_messages(),
+        ["Review against main, focusing on error handling. Be strict."]
+    );
+
+    app.input = "/prompt list".to_string();
+    app.submit_input();
+    let content = app.display_messages().last().unwrap().content.clone();
+    assert!(
+        content.contains("- /prompt review base=… focus=…"),
+        "{content}"
+    );
+
+    crate::env::remove_var("JCODE_HOME");
+}
+
 #[test]
 fn skills_command_marks_active_skill_in_remote_mode() {
     let mut app = create_test_app();
@@ -581,6 +712,30 @@ fn skills_command_marks_active_skill_in_remote_mode() {
     );
 }
 
+#[test]
+fn skills_command_shows_auto_loaded_skills_with_trigger() {
+    let mut app = create_test_app();
+    app.is_remote = true;
+    app.remote_skills = vec!["terraform".to_string(), "optimization".to_string()];
+    app.remote_active_skills = vec![crate::protocol::ActiveSkillStatus {
+        name: "terraform".to_string(),
+        trigger: "file `infra/main.tf` matches `**/*.tf`".to_string(),
+    }];
+
+    assert!(super::state_ui::handle_info_command(&mut app, "/skills"));
+    let content = app.display_messages().last().unwrap().content.clone();
+
+    assert!(
+        content.contains("- /terraform (auto-loaded: file `infra/main.tf` matches `**/*.tf`)"),
+        "{content}"
+    );
+    assert!(
+        content.contains("- /terraform (auto-loaded)\n"),
+        "{content}"
+    );
+    assert!(content.contains("- /optimization\n"), "{content}");
+}
+
 /// Regression for issue #431: skills added on disk after startup (e.g. by the
 /// agent-side `skill_manage reload_all`, which only refreshes the server
 /// process registry) must show up in `/skills` without a session restart.
@@ -678,6 +833,7 @@ fn stale_server_history_is_deferred_before_remote_state_is_applied() {
                 content: "stale answer".to_string(),
                 tool_calls: None,
                 tool_data: None,
+                usage: None,
             }],
             images: vec![],
             provider_name: Some("stale-provider".to_string()),
@@ -704,6 +860,7 @@ fn stale_server_history_is_deferred_before_remote_state_is_applied() {
             status_detail: Some("stale-status".to_string()),
             upstream_provider: Some("stale-upstream".to_string()),
             resolved_credential: None,
+            model_route: None,
             reasoning_effort: Some("high".to_string()),
             service_tier: Some("stale-tier".to_string()),
             compaction_mode: crate::config::CompactionMode::Reactive,
@@ -768,6 +925,7 @@ fn deferred_stale_server_history_captures_session_id_for_reload_handoff() {
                 content: "stale answer".to_string(),
                 tool_calls: None,
                 tool_data: None,
+                usage: None,
             }],
             images: vec![],
             provider_name: Some("stale-provider".to_string()),
@@ -796,6 +954,7 @@ fn deferred_stale_server_history_captures_session_id_for_reload_handoff() {
             status_detail: None,
             upstream_provider: None,
             resolved_credential: None,
+            model_route: None,
             reasoning_effort: None,
             service_tier: None,
             compaction_mode: crate::config::CompactionMode::Reactive,
@@ -850,6 +1009,7 @@ fn ancient_server_history_is_deferred_via_client_side_release_check() {
                 content: "ancient answer".to_string(),
                 tool_calls: None,
                 tool_data: None,
+                usage: None,
             }],
             images: vec![],
             provider_name: Some("ancient-provider".to_string()),
@@ -878,6 +1038,7 @@ fn ancient_server_history_is_deferred_via_client_side_release_check() {
             status_detail: Some("ancient-status".to_string()),
             upstream_provider: Some("ancient-upstream".to_string()),
             resolved_credential: None,
+            model_route: None,
             reasoning_effort: Some("high".to_string()),
             service_tier: Some("ancient-tier".to_string()),
             compaction_mode: crate::config::CompactionMode::Reactive,
@@ -960,6 +1121,7 @@ fn older_server_reporting_no_update_is_still_deferred_via_client_check() {
             status_detail: None,
             upstream_provider: None,
             resolved_credential: None,
+            model_route: None,
             reasoning_effort: None,
             service_tier: None,
             compaction_mode: crate::config::CompactionMode::Reactive,
@@ -1062,6 +1224,7 @@ fn older_server_history_repairs_stale_shared_server_channel_end_to_end() {
             status_detail: None,
             upstream_provider: None,
             resolved_credential: None,
+            model_route: None,
             reasoning_effort: None,
             service_tier: None,
             compaction_mode: crate::config::CompactionMode::Reactive,
@@ -1138,6 +1301,7 @@ fn current_release_server_history_is_not_deferred_by_client_check() {
             status_detail: None,
             upstream_provider: None,
             resolved_credential: None,
+            model_route: None,
             reasoning_effort: None,
             service_tier: None,
             compaction_mode: crate::config::CompactionMode::Reactive,
@@ -1185,6 +1349,47 @@ fn remote_done_finalizes_resumed_activity_without_current_message_id() {
     assert!(app.last_api_completed.is_some());
 }
 
+#[test]
+fn remote_resume_streaming_reattaches_to_running_turn_until_done() {
+    let mut app = create_test_app();
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let _guard = rt.enter();
+    let mut remote = crate::tui::backend::RemoteConnection::dummy();
+
+    app.is_remote = true;
+    app.remote_session_id = Some("session_crashed_client".to_string());
+
+    app.handle_server_event(
+        crate::protocol::ServerEvent::ResumeStreaming {
+            session_id: "session_crashed_client".to_string(),
+            text: "Partial ".to_string(),
+            current_tool_name: None,
+        },
+        &mut remote,
+    );
+    assert!(app.is_processing);
+    assert!(matches!(app.status, ProcessingStatus::Streaming));
+    assert_eq!(app.streaming.streaming_text, "Partial ");
+
+    app.handle_server_event(
+        crate::protocol::ServerEvent::TextDelta {
+            text: "answer".to_string(),
+        },
+        &mut remote,
+    );
+    app.handle_server_event(crate::protocol::ServerEvent::Done { id: 0 }, &mut remote);
+
+    assert!(!app.is_processing);
+    assert!(matches!(app.status, ProcessingStatus::Idle));
+    let last = app
+        .display_messages()
+        .iter()
+        .rev()
+        .find(|message| message.role == "assistant")
+        .expect("resumed answer committed");
+    assert_eq!(last.content, "Partial answer");
+}
+
 #[test]
 fn oversized_pasted_submit_is_rejected_and_preserves_input() {
     let mut app = create_test_app();
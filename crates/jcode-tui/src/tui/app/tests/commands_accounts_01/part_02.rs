@@ -111,6 +111,45 @@ fn test_alignment_invalid_usage_shows_error() {
     assert!(last.content.contains("Usage: /alignment"));
 }
 
+#[test]
+fn test_session_stats_command_aggregates_usage_per_model() {
+    let mut app = create_test_app();
+    for (model, input_tokens) in [("gpt-5", 1_000), ("claude-sonnet-4", 2_000), ("gpt-5", 500)] {
+        app.session.add_message_ext(
+            crate::message::Role::Assistant,
+            vec![crate::message::ContentBlock::Text {
+                text: format!("answer from {model}"),
+                cache_control: None,
+            }],
+            None,
+            Some(crate::session::StoredTokenUsage {
+                input_tokens,
+                output_tokens: 100,
+                model: Some(model.to_string()),
+                provider: Some("test".to_string()),
+                duration_ms: Some(2_000),
+                ..crate::session::StoredTokenUsage::default()
+            }),
+        );
+    }
+    app.input = "/session stats".to_string();
+
+    app.submit_input();
+
+    let last = app.display_messages().last().expect("missing response");
+    assert_eq!(last.role, "system");
+    assert!(
+        last.content
+            .contains("- gpt-5 (test): 2 responses · ↑2k ↓200 · 4.0s total, 2.0s avg"),
+        "unexpected stats: {}",
+        last.content
+    );
+    assert!(
+        last.content
+            .contains("- claude-sonnet-4 (test): 1 response ·")
+    );
+}
+
 #[test]
 fn test_compact_notifications_command_persists_and_applies_immediately() {
     with_temp_jcode_home(|| {
@@ -289,3 +328,58 @@ fn test_usage_enter_requests_report_with_inline_view() {
     );
     assert!(app.usage_report_refreshing);
 }
+
+#[test]
+fn test_view_command_pins_file_and_off_restores_diff_mode() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    std::fs::write(temp.path().join("notes.md"), "hello\n").expect("write file");
+    let mut app = create_test_app();
+    app.diff_mode = crate::config::DiffDisplayMode::Inline;
+    app.session.working_dir = Some(temp.path().display().to_string());
+
+    app.input = "/view notes.md".to_string();
+    app.submit_input();
+    assert!(app.diff_mode.is_file());
+    assert_eq!(
+        app.file_view_pin.as_deref(),
+        Some(temp.path().join("notes.md").display().to_string().as_str())
+    );
+
+    app.input = "/view missing.md".to_string();
+    app.submit_input();
+    let last = app.display_messages().last().expect("error message");
+    assert_eq!(last.role, "error");
+    assert!(last.content.contains("Not a file"));
+    assert!(app.file_view_pin.is_some(), "a bad path keeps the old pin");
+
+    app.input = "/view follow".to_string();
+    app.submit_input();
+    assert!(app.file_view_pin.is_none());
+    assert!(app.diff_mode.is_file());
+
+    app.input = "/view off".to_string();
+    app.submit_input();
+    assert!(!app.diff_mode.is_file());
+}
+
+#[test]
+fn test_view_save_writes_layout_to_config() {
+    with_temp_jcode_home(|| {
+        let mut app = create_test_app();
+        app.adjust_side_pane_ratio(10);
+        app.input = "/view follow".to_string();
+        app.submit_input();
+        app.input = "/view save".to_string();
+        app.submit_input();
+
+        let saved = crate::config::Config::load().display;
+        assert_eq!(saved.diff_mode, crate::config::DiffDisplayMode::File);
+        assert_eq!(saved.side_pane_ratio, app.diagram_pane_ratio_target);
+        let last = app.display_messages().last().expect("missing response");
+        assert!(
+            last.content.contains("Saved default layout"),
+            "{}",
+            last.content
+        );
+    });
+}
@@ -47,14 +47,15 @@ fn test_refactor_command_starts_refactor_loop() {
 }
 
 #[test]
-fn test_plan_command_is_plan_only_and_presents_plan_card() {
+fn test_plan_command_enters_plan_mode_and_requests_plan_propose() {
     let mut app = create_test_app();
     app.input = "/plan add a compact message mode".to_string();
     app.submit_input();
 
-    // /plan is a one-shot, not a resumable improve/refactor loop.
+    // /plan is not a resumable improve/refactor loop.
     assert_eq!(app.improve_mode, None);
     assert!(app.is_processing());
+    assert!(app.plan_mode_active());
 
     let msg = app.session.messages.last().expect("missing plan prompt");
     assert!(matches!(
@@ -62,7 +63,7 @@ fn test_plan_command_is_plan_only_and_presents_plan_card() {
         ContentBlock::Text { text, .. }
             if text.contains("You are entering planning mode")
                 && text.contains("Do NOT implement anything yet")
-                && text.contains("```plan")
+                && text.contains("`plan_propose`")
                 && text.contains("`todo`")
                 && text.contains("Goal: add a compact message mode")
     ));
@@ -71,7 +72,62 @@ fn test_plan_command_is_plan_only_and_presents_plan_card() {
         .display_messages()
         .last()
         .expect("missing plan launch notice");
-    assert!(display.content.contains("Planning add a compact message mode"));
+    assert!(
+        display
+            .content
+            .contains("planning add a compact message mode")
+    );
+}
+
+#[test]
+fn test_plan_approve_leaves_plan_mode_and_sends_approved_plan() {
+    let mut app = create_test_app();
+    app.set_plan_mode_active(true);
+    app.note_plan_proposal(&serde_json::json!({
+        "title": "Compact mode",
+        "steps": ["Add toggle", "Render compact rows"],
+        "files": ["src/tui/ui.rs"]
+    }));
+    assert!(
+        app.display_messages()
+            .iter()
+            .any(|msg| msg.content.starts_with("```plan\n# Compact mode"))
+    );
+
+    app.input = "/plan approve".to_string();
+    app.submit_input();
+
+    assert!(!app.plan_mode_active());
+    let mode = app.session.plan_mode.as_ref().expect("plan mode state");
+    assert_eq!(
+        mode.status,
+        crate::plan::proposal::PlanProposalStatus::Approved
+    );
+    let msg = app
+        .session
+        .messages
+        .last()
+        .expect("missing approval prompt");
+    assert!(matches!(
+        &msg.content[0],
+        ContentBlock::Text { text, .. }
+            if text.contains("approved") && text.contains("2. Render compact rows")
+    ));
+}
+
+#[test]
+fn test_plan_edit_requires_notes() {
+    let mut app = create_test_app();
+    app.set_plan_mode_active(true);
+    app.note_plan_proposal(&serde_json::json!({"title": "T", "steps": ["one"]}));
+
+    app.input = "/plan edit".to_string();
+    app.submit_input();
+
+    assert!(app.plan_mode_active());
+    assert!(!app.is_processing());
+    let display = app.display_messages().last().expect("missing usage error");
+    assert!(display.content.contains("Usage: /plan edit <notes>"));
 }
 
 #[test]
@@ -56,6 +56,7 @@ fn classify_phase_surface(phase: &OnboardingPhase) -> ScreenSurface {
         OnboardingPhase::Login { .. } => ScreenSurface::WelcomeBody,
         OnboardingPhase::LoginOpenAi { .. } => ScreenSurface::WelcomeBody,
         OnboardingPhase::ContinuePrompt { .. } => ScreenSurface::WelcomeBody,
+        OnboardingPhase::Setup(_) => ScreenSurface::WelcomeBody,
         OnboardingPhase::Suggestions => ScreenSurface::WelcomeBody,
         OnboardingPhase::TranscriptPick { .. } => ScreenSurface::PickerOverlay,
         // ModelSelect immediately auto-advances; it never rests on screen.
@@ -68,7 +69,7 @@ fn classify_phase_surface(phase: &OnboardingPhase) -> ScreenSurface {
 /// sync with the enum by the same wildcard-free discipline as the classifier.
 fn all_onboarding_phases() -> Vec<(&'static str, OnboardingPhase)> {
     use crate::external_auth::ExternalAuthReviewCandidate;
-    use crate::tui::app::onboarding_flow::ImportReview;
+    use crate::tui::app::onboarding_flow::{ImportReview, SetupWizard};
     let now = std::time::Instant::now();
     let review = ImportReview::new(vec![
         ExternalAuthReviewCandidate::fixture("OpenAI/Codex", "Codex auth.json"),
@@ -79,6 +80,7 @@ fn all_onboarding_phases() -> Vec<(&'static str, OnboardingPhase)> {
         ("Login{import}", OnboardingPhase::Login { import: Some(review) }),
         ("Login{recovery}", OnboardingPhase::Login { import: None }),
         ("LoginOpenAi", OnboardingPhase::LoginOpenAi { yes_highlighted: true }),
+        ("Setup", OnboardingPhase::Setup(SetupWizard::new(false))),
         ("ModelSelect", OnboardingPhase::ModelSelect),
         (
             "ContinuePrompt",
@@ -138,6 +140,9 @@ fn entry_paths() -> Vec<Path> {
             reaches_ready: true,
             steps: vec![
                 Step { phase: "LoginOpenAi", keystrokes: 1, is_decision: true, external_boundary: true },
+                // No config.toml yet: the settings form opens with Continue
+                // focused, so one Enter keeps the defaults.
+                Step { phase: "Setup", keystrokes: 1, is_decision: false, external_boundary: false },
                 Step { phase: "Suggestions", keystrokes: 0, is_decision: false, external_boundary: false },
             ],
         },
@@ -156,6 +161,7 @@ fn entry_paths() -> Vec<Path> {
             reaches_ready: true,
             steps: vec![
                 Step { phase: "Login{import}", keystrokes: 1, is_decision: true, external_boundary: false },
+                Step { phase: "Setup", keystrokes: 1, is_decision: false, external_boundary: false },
                 Step { phase: "Suggestions", keystrokes: 0, is_decision: false, external_boundary: false },
             ],
         },
@@ -167,6 +173,7 @@ fn entry_paths() -> Vec<Path> {
                 // Single-screen checkbox list, all pre-checked: one Enter imports
                 // every detected login at once (no per-candidate page).
                 Step { phase: "Login{import}", keystrokes: 1, is_decision: true, external_boundary: false },
+                Step { phase: "Setup", keystrokes: 1, is_decision: false, external_boundary: false },
                 Step { phase: "Suggestions", keystrokes: 0, is_decision: false, external_boundary: false },
             ],
         },
@@ -361,7 +368,7 @@ fn tier3_screen_score_w(m: &ScreenMetrics, w: &Tier3Weights) -> f64 {
 /// Screens we score for Tier 3. Each is a real, user-visible welcome screen.
 fn tier3_screens() -> Vec<ScreenMetrics> {
     use crate::external_auth::ExternalAuthReviewCandidate;
-    use crate::tui::app::onboarding_flow::ImportReview;
+    use crate::tui::app::onboarding_flow::{ImportReview, SetupWizard};
     let now = std::time::Instant::now();
     let review =
         ImportReview::new(vec![ExternalAuthReviewCandidate::fixture("OpenAI/Codex", "Codex auth.json")])
@@ -370,6 +377,7 @@ fn tier3_screens() -> Vec<ScreenMetrics> {
         render_phase_screen("LoginOpenAi", OnboardingPhase::LoginOpenAi { yes_highlighted: true }),
         render_phase_screen("Login{import}", OnboardingPhase::Login { import: Some(review) }),
         render_phase_screen("Login{recovery}", OnboardingPhase::Login { import: None }),
+        render_phase_screen("Setup", OnboardingPhase::Setup(SetupWizard::new(false))),
         render_phase_screen(
             "ContinuePrompt",
             OnboardingPhase::ContinuePrompt {
@@ -598,6 +606,7 @@ enum GraphNode {
     LoginOpenAi,
     LoginImport,
     LoginRecovery,
+    Setup,
     ModelSelect,
     ContinuePrompt,
     TranscriptPick,
@@ -612,6 +621,7 @@ fn phase_to_node(phase: &OnboardingPhase) -> GraphNode {
         OnboardingPhase::LoginOpenAi { .. } => GraphNode::LoginOpenAi,
         OnboardingPhase::Login { import: Some(_) } => GraphNode::LoginImport,
         OnboardingPhase::Login { import: None } => GraphNode::LoginRecovery,
+        OnboardingPhase::Setup(_) => GraphNode::Setup,
         OnboardingPhase::ModelSelect => GraphNode::ModelSelect,
         OnboardingPhase::ContinuePrompt { .. } => GraphNode::ContinuePrompt,
         OnboardingPhase::TranscriptPick { .. } => GraphNode::TranscriptPick,
@@ -642,6 +652,9 @@ fn node_props(n: GraphNode) -> NodeProps {
         LoginImport => NodeProps { is_decision: true, has_default: true, is_ready: false, is_terminal: false },
         // Recovery fallback: a single Enter opens the provider picker.
         LoginRecovery => NodeProps { is_decision: true, has_default: false, is_ready: false, is_terminal: false },
+        // Settings form: every row is pre-set and Continue is focused, so
+        // one Enter accepts the defaults.
+        Setup => NodeProps { is_decision: false, has_default: true, is_ready: false, is_terminal: false },
         // Transient: auto-advances, the user never chooses here.
         ModelSelect => NodeProps { is_decision: false, has_default: true, is_ready: false, is_terminal: false },
         // Continue prompt auto-opens the resume menu on timeout (default Yes).
@@ -681,6 +694,12 @@ fn flow_edges() -> Vec<Edge> {
         Edge { from: LoginImport, to: LoginRecovery, keystrokes: 1 },
         // Recovery: Enter opens the provider picker, ending at suggestions.
         Edge { from: LoginRecovery, to: Suggestions, keystrokes: 1 },
+        // Without a config.toml, a completed login opens the settings form
+        // first; Continue (or Esc) then carries on to the usual destinations.
+        Edge { from: LoginOpenAi, to: Setup, keystrokes: 1 },
+        Edge { from: LoginImport, to: Setup, keystrokes: 1 },
+        Edge { from: Setup, to: Suggestions, keystrokes: 1 },
+        Edge { from: Setup, to: TranscriptPick, keystrokes: 1 },
         // Transient model-select auto-advances with no keystroke.
         Edge { from: ModelSelect, to: Suggestions, keystrokes: 0 },
         // Continue prompt: Yes -> resume picker; No -> suggestions.
@@ -783,6 +802,7 @@ fn entry_node_for(path: &Path) -> GraphNode {
         Some("LoginOpenAi") => GraphNode::LoginOpenAi,
         Some("Login{import}") => GraphNode::LoginImport,
         Some("Login{recovery}") => GraphNode::LoginRecovery,
+        Some("Setup") => GraphNode::Setup,
         Some("Suggestions") => GraphNode::Suggestions,
         Some("TranscriptPick") => GraphNode::TranscriptPick,
         Some("ContinuePrompt") => GraphNode::ContinuePrompt,
@@ -841,6 +861,7 @@ fn tier5_metrics() -> Tier5Metrics {
         GraphNode::LoginOpenAi,
         GraphNode::LoginImport,
         GraphNode::LoginRecovery,
+        GraphNode::Setup,
         GraphNode::ModelSelect,
         GraphNode::ContinuePrompt,
         GraphNode::TranscriptPick,
@@ -1418,6 +1439,9 @@ fn tier8_metrics() -> Tier8Metrics {
         match p {
             OnboardingPhase::LoginOpenAi { .. } => false,
             OnboardingPhase::Login { .. } => false,
+            // Writes config.toml and JCODE.md, but only on a first run where
+            // neither exists, and Esc writes nothing.
+            OnboardingPhase::Setup(_) => false,
             OnboardingPhase::ModelSelect => false,
             OnboardingPhase::ContinuePrompt { .. } => false,
             OnboardingPhase::TranscriptPick { .. } => false,
@@ -1914,7 +1938,7 @@ fn onboarding_eval_scorecard() {
         let phase_coverage = phases.len(); // exhaustive by construction
         // Screens scored in Tier 3 over the user-facing WelcomeBody surfaces.
         // WelcomeBody phases: Login{import}, Login{recovery}, LoginOpenAi,
-        // ContinuePrompt, Suggestions => 5 distinct screens, all scored.
+        // Setup, ContinuePrompt, Suggestions => 6 distinct screens, all scored.
         let scored_welcome_screens = screens.len() as u32;
         let screen_coverage_pct = (scored_welcome_screens as f64 / welcome as f64) * 100.0;
         let path_coverage = paths.len();
@@ -2269,6 +2293,7 @@ fn onboarding_eval_graph_fidelity() {
             GraphNode::LoginOpenAi,
             GraphNode::LoginImport,
             GraphNode::LoginRecovery,
+            GraphNode::Setup,
             GraphNode::ModelSelect,
             GraphNode::ContinuePrompt,
             GraphNode::TranscriptPick,
@@ -2287,6 +2312,7 @@ fn onboarding_eval_graph_fidelity() {
         for n in [
             GraphNode::LoginOpenAi,
             GraphNode::LoginImport,
+            GraphNode::Setup,
             GraphNode::ModelSelect,
             GraphNode::Suggestions,
             GraphNode::Done,
@@ -2939,7 +2965,7 @@ fn detect_feature_classes(text: &str) -> Vec<FeatureClass> {
 /// Every user-facing welcome screen, rendered to text, for the Layer C probe.
 fn all_welcome_screen_texts() -> Vec<(&'static str, String)> {
     use crate::external_auth::ExternalAuthReviewCandidate;
-    use crate::tui::app::onboarding_flow::ImportReview;
+    use crate::tui::app::onboarding_flow::{ImportReview, SetupWizard};
     let now = std::time::Instant::now();
     let review =
         ImportReview::new(vec![ExternalAuthReviewCandidate::fixture("OpenAI/Codex", "Codex auth.json")])
@@ -2948,6 +2974,7 @@ fn all_welcome_screen_texts() -> Vec<(&'static str, String)> {
         ("LoginOpenAi", OnboardingPhase::LoginOpenAi { yes_highlighted: true }),
         ("Login{import}", OnboardingPhase::Login { import: Some(review) }),
         ("Login{recovery}", OnboardingPhase::Login { import: None }),
+        ("Setup", OnboardingPhase::Setup(SetupWizard::new(false))),
         (
             "ContinuePrompt",
             OnboardingPhase::ContinuePrompt { cli: ExternalCli::Codex, yes_highlighted: true, shown_at: now },
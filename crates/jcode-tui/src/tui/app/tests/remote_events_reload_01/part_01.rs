@@ -179,6 +179,7 @@ fn test_handle_server_event_history_clears_connection_type_on_session_change_whe
             status_detail: None,
             upstream_provider: None,
             resolved_credential: None,
+            model_route: None,
             reasoning_effort: None,
             service_tier: None,
             compaction_mode: crate::config::CompactionMode::Reactive,
@@ -232,6 +233,7 @@ fn test_handle_server_event_history_preserves_connection_type_for_same_session_w
             status_detail: None,
             upstream_provider: None,
             resolved_credential: None,
+            model_route: None,
             reasoning_effort: None,
             service_tier: None,
             compaction_mode: crate::config::CompactionMode::Reactive,
@@ -288,6 +290,7 @@ fn test_handle_server_event_history_session_change_clears_pending_interleaves()
             status_detail: None,
             upstream_provider: None,
             resolved_credential: None,
+            model_route: None,
             reasoning_effort: None,
             service_tier: None,
             compaction_mode: crate::config::CompactionMode::Reactive,
@@ -1296,6 +1299,7 @@ fn test_pending_startup_notice_survives_history_bootstrap_for_fresh_session() {
             status_detail: None,
             upstream_provider: None,
             resolved_credential: None,
+            model_route: None,
             reasoning_effort: None,
             service_tier: None,
             compaction_mode: crate::config::CompactionMode::Reactive,
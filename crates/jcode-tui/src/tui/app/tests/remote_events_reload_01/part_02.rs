@@ -312,6 +312,7 @@ fn test_remote_rewind_completion_shows_undo_hint_after_history_refresh() {
                 content: "hello".to_string(),
                 tool_calls: None,
                 tool_data: None,
+                usage: None,
             }],
             images: vec![],
             provider_name: Some("mock".to_string()),
@@ -338,6 +339,7 @@ fn test_remote_rewind_completion_shows_undo_hint_after_history_refresh() {
             status_detail: None,
             upstream_provider: None,
             resolved_credential: None,
+            model_route: None,
             reasoning_effort: None,
             service_tier: None,
             compaction_mode: crate::config::CompactionMode::Reactive,
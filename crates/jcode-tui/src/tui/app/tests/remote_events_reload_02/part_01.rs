@@ -720,6 +720,73 @@ fn test_handle_server_event_soft_interrupt_injected_background_task_renders_card
     assert!(last.content.contains("**Background task** `abc123`"));
 }
 
+#[test]
+fn test_handle_server_event_urgent_interjection_renders_skipped_tools() {
+    let mut app = create_test_app();
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let _guard = rt.enter();
+    let mut remote = crate::tui::backend::RemoteConnection::dummy();
+
+    app.pending_soft_interrupts = vec!["stop, wrong file".to_string()];
+
+    app.handle_server_event(
+        crate::protocol::ServerEvent::SoftInterruptInjected {
+            content: "stop, wrong file".to_string(),
+            display_role: None,
+            point: "C".to_string(),
+            tools_skipped: Some(2),
+        },
+        &mut remote,
+    );
+
+    let last = app
+        .display_messages()
+        .last()
+        .expect("missing interjection message");
+    assert_eq!(last.role, "interjection");
+    assert_eq!(last.title.as_deref(), Some("2 tool(s) skipped"));
+    assert!(app.pending_soft_interrupts.is_empty());
+}
+
+#[test]
+fn test_interject_key_sends_only_while_processing() {
+    let mut app = create_test_app();
+    let alt_enter = KeyModifiers::ALT;
+    app.interject_key = crate::tui::keybind::OptionalBinding {
+        binding: Some(crate::tui::keybind::KeyBinding {
+            code: KeyCode::Enter,
+            modifiers: alt_enter,
+        }),
+        label: Some("Alt+Enter".to_string()),
+    };
+
+    app.input = "draft".to_string();
+    assert_eq!(
+        app.handle_interjection_key(KeyCode::Enter, alt_enter),
+        crate::tui::app::interjection::InterjectionKey::Unhandled
+    );
+    assert!(app.active_interjection().is_none());
+
+    app.is_processing = true;
+    assert_eq!(
+        app.handle_interjection_key(KeyCode::Enter, alt_enter),
+        crate::tui::app::interjection::InterjectionKey::Handled
+    );
+    assert_eq!(
+        app.handle_interjection_key(KeyCode::Tab, KeyModifiers::empty()),
+        crate::tui::app::interjection::InterjectionKey::Handled
+    );
+    assert_eq!(
+        app.handle_interjection_key(KeyCode::Enter, KeyModifiers::empty()),
+        crate::tui::app::interjection::InterjectionKey::Send(
+            "draft".to_string(),
+            crate::tui::InterjectionPriority::Urgent,
+        )
+    );
+    assert!(app.input.is_empty());
+    assert!(app.active_interjection().is_none());
+}
+
 #[test]
 fn test_handle_server_event_notification_background_task_scope_uses_card_rendering() {
     let _render_lock = scroll_render_test_lock();
@@ -281,6 +281,7 @@ fn test_handle_server_event_compaction_shows_completion_message_in_remote_mode()
             messages_compacted: Some(24),
             summary_chars: Some(987),
             active_messages: Some(10),
+            tool_results_trimmed: None,
         },
         &mut remote,
     );
@@ -301,6 +302,45 @@ fn test_handle_server_event_compaction_shows_completion_message_in_remote_mode()
     );
 }
 
+#[test]
+fn test_handle_server_event_context_overflow_recovery_explains_what_was_trimmed() {
+    let mut app = create_test_app();
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let _guard = rt.enter();
+    let mut remote = crate::tui::backend::RemoteConnection::dummy();
+
+    app.handle_server_event(
+        crate::protocol::ServerEvent::Compaction {
+            trigger: "auto_recovery".to_string(),
+            pre_tokens: Some(212_000),
+            post_tokens: Some(96_000),
+            tokens_saved: Some(116_000),
+            duration_ms: None,
+            messages_dropped: Some(14),
+            messages_compacted: None,
+            summary_chars: None,
+            active_messages: None,
+            tool_results_trimmed: Some(3),
+        },
+        &mut remote,
+    );
+
+    assert_eq!(
+        app.status_notice(),
+        Some("Emergency compaction".to_string())
+    );
+    let last = app
+        .display_messages()
+        .last()
+        .expect("missing recovery message");
+    assert!(last.content.starts_with(
+        "📦 Context window exceeded - older context was trimmed and the same message was retried."
+    ));
+    assert!(last.content.contains("before ~212,000 tokens"));
+    assert!(last.content.contains("dropped 14 messages"));
+    assert!(last.content.contains("shortened 3 old tool results"));
+}
+
 #[test]
 fn test_handle_server_event_compaction_mode_changed_updates_remote_mode() {
     let mut app = create_test_app();
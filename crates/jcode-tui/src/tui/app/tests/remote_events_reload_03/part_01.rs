@@ -138,6 +138,7 @@ fn test_handle_server_event_history_with_interruption_queues_continuation() {
                 content: "I was working on something".to_string(),
                 tool_calls: None,
                 tool_data: None,
+                usage: None,
             }],
             images: vec![],
             provider_name: Some("claude".to_string()),
@@ -164,6 +165,7 @@ fn test_handle_server_event_history_with_interruption_queues_continuation() {
             status_detail: None,
             upstream_provider: None,
             resolved_credential: None,
+            model_route: None,
             reasoning_effort: None,
             service_tier: None,
             compaction_mode: crate::config::CompactionMode::Reactive,
@@ -211,6 +213,7 @@ fn test_handle_server_event_history_uses_server_owned_reload_recovery_directive(
             content: "Reconnect me from server history".to_string(),
             tool_calls: None,
             tool_data: None,
+            usage: None,
         }],
         images: vec![],
         provider_name: Some("claude".to_string()),
@@ -240,6 +243,7 @@ fn test_handle_server_event_history_uses_server_owned_reload_recovery_directive(
         status_detail: None,
         upstream_provider: None,
         resolved_credential: None,
+        model_route: None,
         reasoning_effort: None,
         service_tier: None,
         compaction_mode: crate::config::CompactionMode::Reactive,
@@ -292,6 +296,7 @@ fn test_handle_server_event_history_without_interruption_does_not_queue() {
                 content: "Normal response".to_string(),
                 tool_calls: None,
                 tool_data: None,
+                usage: None,
             }],
             images: vec![],
             provider_name: Some("claude".to_string()),
@@ -318,6 +323,7 @@ fn test_handle_server_event_history_without_interruption_does_not_queue() {
             status_detail: None,
             upstream_provider: None,
             resolved_credential: None,
+            model_route: None,
             reasoning_effort: None,
             service_tier: None,
             compaction_mode: crate::config::CompactionMode::Reactive,
@@ -355,6 +361,7 @@ fn test_handle_server_event_history_after_reload_reports_no_continuation_needed(
                 content: "Finished before reload".to_string(),
                 tool_calls: None,
                 tool_data: None,
+                usage: None,
             }],
             images: vec![],
             provider_name: Some("claude".to_string()),
@@ -381,6 +388,7 @@ fn test_handle_server_event_history_after_reload_reports_no_continuation_needed(
             status_detail: None,
             upstream_provider: None,
             resolved_credential: None,
+            model_route: None,
             reasoning_effort: None,
             service_tier: None,
             compaction_mode: crate::config::CompactionMode::Reactive,
@@ -682,6 +690,7 @@ fn test_handle_server_event_history_restores_side_panel_snapshot() {
             status_detail: None,
             upstream_provider: None,
             resolved_credential: None,
+            model_route: None,
             reasoning_effort: None,
             service_tier: None,
             compaction_mode: crate::config::CompactionMode::Reactive,
@@ -739,6 +748,7 @@ fn test_handle_server_event_history_restores_active_resume_processing_state() {
             status_detail: None,
             upstream_provider: None,
             resolved_credential: None,
+            model_route: None,
             reasoning_effort: None,
             service_tier: None,
             compaction_mode: crate::config::CompactionMode::Reactive,
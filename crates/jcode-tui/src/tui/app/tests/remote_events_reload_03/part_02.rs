@@ -61,6 +61,7 @@ fn test_metadata_only_history_preserves_fast_restored_startup_state() {
             status_detail: None,
             upstream_provider: None,
             resolved_credential: None,
+            model_route: None,
             reasoning_effort: None,
             service_tier: None,
             compaction_mode: crate::config::CompactionMode::Reactive,
@@ -112,6 +113,7 @@ fn test_duplicate_history_for_same_session_is_ignored_after_fast_path_restore()
                 content: "server history replay".to_string(),
                 tool_calls: None,
                 tool_data: None,
+                usage: None,
             }],
             images: vec![],
             provider_name: Some("claude".to_string()),
@@ -138,6 +140,7 @@ fn test_duplicate_history_for_same_session_is_ignored_after_fast_path_restore()
             status_detail: None,
             upstream_provider: None,
             resolved_credential: None,
+            model_route: None,
             reasoning_effort: None,
             service_tier: None,
             compaction_mode: crate::config::CompactionMode::Reactive,
@@ -306,18 +309,21 @@ fn test_compacted_history_event_applies_expanded_window() {
                     content: "Earlier conversation compacted - 64 older historical messages hidden. Showing 64 of 128 compacted messages. Scroll to the top to load more.".to_string(),
                     tool_calls: None,
                     tool_data: None,
+                    usage: None,
                 },
                 crate::protocol::HistoryMessage {
                     role: "assistant".to_string(),
                     content: "older response".to_string(),
                     tool_calls: None,
                     tool_data: None,
+                    usage: None,
                 },
                 crate::protocol::HistoryMessage {
                     role: "user".to_string(),
                     content: "current prompt".to_string(),
                     tool_calls: None,
                     tool_data: None,
+                    usage: None,
                 },
             ],
             images: vec![],
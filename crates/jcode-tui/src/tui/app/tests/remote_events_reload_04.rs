@@ -1138,6 +1138,11 @@ fn test_resumed_session_seeds_cost_from_history_token_totals() {
         cache_reported_input_tokens: 1_000,
         cache_read_input_tokens: 40_000,
         cache_creation_input_tokens: 100_000,
+        web_search_requests: 0,
+        pruned_context_tokens: 0,
+        redactions: 0,
+        overhead_input_tokens: 0,
+        overhead_output_tokens: 0,
     };
     app.seed_cost_from_history_totals(&totals);
 
@@ -1766,3 +1771,74 @@ fn test_remote_fork_without_prompt_splits_immediately() {
         "bare /fork should split immediately like /split"
     );
 }
+
+#[test]
+fn test_remote_rate_limited_event_starts_countdown_and_queues_input() {
+    let mut app = create_test_app();
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let _guard = rt.enter();
+    let mut remote = crate::tui::backend::RemoteConnection::dummy();
+
+    app.is_processing = true;
+    app.status = ProcessingStatus::Streaming;
+
+    app.handle_server_event(
+        crate::protocol::ServerEvent::RateLimited {
+            provider: "Claude".to_string(),
+            retry_after_secs: 20,
+            attempt: 1,
+            max_attempts: 3,
+        },
+        &mut remote,
+    );
+
+    assert!(app.rate_limit_reset.is_some());
+    assert_eq!(app.send_action(false), SendAction::Queue);
+
+    app.handle_server_event(
+        crate::protocol::ServerEvent::RateLimited {
+            provider: "Claude".to_string(),
+            retry_after_secs: 30,
+            attempt: 2,
+            max_attempts: 3,
+        },
+        &mut remote,
+    );
+
+    let notices: Vec<_> = app
+        .display_messages()
+        .iter()
+        .filter(|m| m.role == "system" && m.content.contains("rate limited"))
+        .collect();
+    assert_eq!(notices.len(), 1, "repeat waits should update one notice");
+    assert!(notices[0].content.contains("attempt 2/3"));
+}
+
+#[test]
+fn test_queued_turn_started_shows_message_and_adopts_turn() {
+    let mut app = create_test_app();
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let _guard = rt.enter();
+    let mut remote = crate::tui::backend::RemoteConnection::dummy();
+    remote.mark_history_loaded();
+
+    app.handle_server_event(
+        crate::protocol::ServerEvent::QueuedTurnStarted {
+            id: 31,
+            content: "run the tests".to_string(),
+            images: vec![],
+        },
+        &mut remote,
+    );
+
+    assert!(app.is_processing);
+    assert_eq!(app.current_message_id, Some(31));
+    let last = app.display_messages().last().expect("missing queued message");
+    assert_eq!(last.role, "user");
+    assert_eq!(last.content, "run the tests");
+
+    app.handle_server_event(crate::protocol::ServerEvent::Done { id: 31 }, &mut remote);
+
+    assert!(!app.is_processing);
+    assert!(app.current_message_id.is_none());
+}
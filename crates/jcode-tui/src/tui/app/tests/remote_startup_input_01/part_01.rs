@@ -589,6 +589,101 @@ fn test_subagent_command_suggestions_include_manual_launch_and_model_policy() {
     assert!(autojudge.iter().any(|(cmd, _)| cmd == "/autojudge status"));
 }
 
+#[test]
+fn test_resume_suggestions_complete_recent_session_names() {
+    let _guard = crate::storage::lock_test_env();
+    let temp_home = tempfile::TempDir::new().expect("temp home");
+    let prev_home = std::env::var_os("JCODE_HOME");
+    crate::env::set_var("JCODE_HOME", temp_home.path());
+    let sessions = temp_home.path().join("sessions");
+    std::fs::create_dir_all(&sessions).expect("sessions dir");
+    for id in ["session_otter_1700000000000", "session_heron_1700000000001"] {
+        std::fs::write(sessions.join(format!("{id}.json")), "{}").expect("session file");
+    }
+
+    let app = create_test_app();
+    let suggestions = app.get_suggestions_for("/resume ");
+    assert!(suggestions.iter().any(|(cmd, _)| cmd == "/resume otter"));
+    assert!(suggestions.iter().any(|(cmd, _)| cmd == "/resume heron"));
+    let narrowed = app.get_suggestions_for("/resume her");
+    assert_eq!(
+        narrowed.first().map(|(cmd, _)| cmd.as_str()),
+        Some("/resume heron")
+    );
+
+    if let Some(prev_home) = prev_home {
+        crate::env::set_var("JCODE_HOME", prev_home);
+    } else {
+        crate::env::remove_var("JCODE_HOME");
+    }
+}
+
+#[test]
+fn test_resume_suggestions_skip_the_current_session() {
+    let _guard = crate::storage::lock_test_env();
+    let temp_home = tempfile::TempDir::new().expect("temp home");
+    let prev_home = std::env::var_os("JCODE_HOME");
+    crate::env::set_var("JCODE_HOME", temp_home.path());
+    let sessions = temp_home.path().join("sessions");
+    std::fs::create_dir_all(&sessions).expect("sessions dir");
+    for id in ["session_walrus_1700000000000", "session_heron_1700000000001"] {
+        std::fs::write(sessions.join(format!("{id}.json")), "{}").expect("session file");
+    }
+
+    let mut app = create_test_app();
+    app.session.id = "session_walrus_1700000000000".to_string();
+    let suggestions = app.get_suggestions_for("/resume ");
+    assert!(suggestions.iter().any(|(cmd, _)| cmd == "/resume heron"));
+    assert!(
+        !suggestions.iter().any(|(cmd, _)| cmd == "/resume walrus"),
+        "{:?}",
+        suggestions
+    );
+
+    if let Some(prev_home) = prev_home {
+        crate::env::set_var("JCODE_HOME", prev_home);
+    } else {
+        crate::env::remove_var("JCODE_HOME");
+    }
+}
+
+#[test]
+fn test_resume_suggestions_keep_sessions_that_share_a_name() {
+    let _guard = crate::storage::lock_test_env();
+    let temp_home = tempfile::TempDir::new().expect("temp home");
+    let prev_home = std::env::var_os("JCODE_HOME");
+    crate::env::set_var("JCODE_HOME", temp_home.path());
+    let sessions = temp_home.path().join("sessions");
+    std::fs::create_dir_all(&sessions).expect("sessions dir");
+    for id in [
+        "session_otter_1700000000000_a1b2",
+        "session_otter_1700000000001_c3d4",
+        "session_heron_1700000000002",
+    ] {
+        std::fs::write(sessions.join(format!("{id}.json")), "{}").expect("session file");
+    }
+
+    let app = create_test_app();
+    let suggestions = app.get_suggestions_for("/resume ");
+    let commands: Vec<&str> = suggestions.iter().map(|(cmd, _)| cmd.as_str()).collect();
+    assert!(
+        commands.contains(&"/resume session_otter_1700000000000_a1b2"),
+        "{commands:?}"
+    );
+    assert!(
+        commands.contains(&"/resume session_otter_1700000000001_c3d4"),
+        "{commands:?}"
+    );
+    assert!(!commands.contains(&"/resume otter"), "{commands:?}");
+    assert!(commands.contains(&"/resume heron"), "{commands:?}");
+
+    if let Some(prev_home) = prev_home {
+        crate::env::set_var("JCODE_HOME", prev_home);
+    } else {
+        crate::env::remove_var("JCODE_HOME");
+    }
+}
+
 fn configure_test_remote_models_with_copilot(app: &mut App) {
     app.is_remote = true;
     app.remote_provider_model = Some("claude-sonnet-4".to_string());
@@ -733,6 +828,7 @@ fn test_remote_model_switch_failure_shows_actionable_guidance() {
             model: "claude-opus-4.6".to_string(),
             provider_name: Some("Copilot".to_string()),
             error: Some("credentials expired".to_string()),
+            route_fallback: None,
         },
         &mut remote,
     );
@@ -806,6 +902,7 @@ fn test_remote_model_switch_failure_restores_deferred_prompt() {
             model: "Qwen/Qwen3-32B-TEE".to_string(),
             provider_name: Some("Chutes".to_string()),
             error: Some("model switch failed".to_string()),
+            route_fallback: None,
         },
         &mut remote,
     );
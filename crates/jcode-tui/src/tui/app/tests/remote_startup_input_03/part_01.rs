@@ -229,6 +229,48 @@ fn test_paste_expansion_on_submit() {
     assert!(app.pasted_contents.is_empty());
 }
 
+#[test]
+fn test_file_picker_attachment_expands_on_submit() {
+    let temp = tempfile::TempDir::new().unwrap();
+    std::fs::write(temp.path().join("notes.txt"), "remember the milk\n").unwrap();
+    let mut app = create_test_app();
+    app.session.working_dir = Some(temp.path().to_string_lossy().to_string());
+
+    app.open_file_picker();
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+    while !app.poll_file_picker_load() {
+        assert!(std::time::Instant::now() < deadline, "file index never arrived");
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+    for c in "notes".chars() {
+        app.handle_key(KeyCode::Char(c), KeyModifiers::empty())
+            .unwrap();
+    }
+    app.handle_key(KeyCode::Enter, KeyModifiers::empty())
+        .unwrap();
+
+    assert!(app.file_picker_overlay.is_none());
+    assert_eq!(app.input(), "[file notes.txt] ");
+
+    app.submit_input();
+
+    let provider_messages = app.materialized_provider_messages();
+    let user_message = provider_messages
+        .iter()
+        .rev()
+        .find(|message| message.role == Role::User)
+        .expect("expected submitted user message");
+    match &user_message.content[0] {
+        crate::message::ContentBlock::Text { text, .. } => {
+            assert_eq!(
+                text.trim_end(),
+                "<file path=\"notes.txt\">\nremember the milk\n</file>"
+            );
+        }
+        _ => panic!("Expected Text content block"),
+    }
+}
+
 #[test]
 fn test_multiple_pastes() {
     let mut app = create_test_app();
@@ -352,6 +394,44 @@ fn test_system_reminder_is_added_to_system_prompt_not_user_messages() {
     assert!(app.messages.is_empty());
 }
 
+#[test]
+fn test_instructions_command_persists_into_every_system_prompt() {
+    with_temp_jcode_home(|| {
+        let mut app = create_test_app();
+
+        app.input = "/instructions Keep diffs small.".to_string();
+        app.submit_input();
+        app.input = "/instructions add Run the tests before answering.".to_string();
+        app.submit_input();
+
+        assert_eq!(
+            app.session.instructions.as_deref(),
+            Some("Keep diffs small.\nRun the tests before answering.")
+        );
+        assert!(app.session_has_instructions());
+        let split = app.build_system_prompt_split(None);
+        assert!(split.dynamic_part.contains("# Session Instructions"));
+        assert!(
+            split
+                .dynamic_part
+                .contains("Keep diffs small.\nRun the tests before answering.")
+        );
+
+        app.input = "/instructions edit".to_string();
+        app.submit_input();
+        assert_eq!(
+            app.input,
+            "/instructions set Keep diffs small.\nRun the tests before answering."
+        );
+
+        app.input = "/instructions clear".to_string();
+        app.submit_input();
+        assert!(app.session.instructions.is_none());
+        let split = app.build_system_prompt_split(None);
+        assert!(!split.dynamic_part.contains("# Session Instructions"));
+    });
+}
+
 #[test]
 fn test_recover_session_without_tools_preserves_debug_and_canary_flags() {
     let mut app = create_test_app();
@@ -86,6 +86,7 @@ fn test_initial_history_bootstrap_preserves_restored_interleave_state() {
                 status_detail: None,
                 upstream_provider: None,
                 resolved_credential: None,
+                model_route: None,
                 reasoning_effort: None,
                 service_tier: None,
                 compaction_mode: crate::config::CompactionMode::Reactive,
@@ -151,6 +152,7 @@ fn test_initial_history_bootstrap_skips_resubmit_when_prompt_already_in_history(
                     content: "continue implementing the fix".to_string(),
                     tool_calls: None,
                     tool_data: None,
+                    usage: None,
                 }],
                 images: vec![],
                 provider_name: Some("claude".to_string()),
@@ -177,6 +179,7 @@ fn test_initial_history_bootstrap_skips_resubmit_when_prompt_already_in_history(
                 status_detail: None,
                 upstream_provider: None,
                 resolved_credential: None,
+                model_route: None,
                 reasoning_effort: None,
                 service_tier: None,
                 compaction_mode: crate::config::CompactionMode::Reactive,
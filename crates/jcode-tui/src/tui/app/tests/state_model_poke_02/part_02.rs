@@ -254,3 +254,24 @@ fn test_model_picker_preview_enter_selects_model() {
     assert!(app.input().is_empty());
     assert_eq!(app.cursor_pos(), 0);
 }
+
+#[test]
+fn test_tee_command_sets_and_clears_session_tee_file() {
+    let mut app = create_test_app();
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("responses.log");
+
+    app.input = format!("/tee {}", path.display());
+    app.submit_input();
+    assert_eq!(app.tee_file.as_deref(), Some(path.as_path()));
+
+    app.input = "/tee off".to_string();
+    app.submit_input();
+    assert_eq!(app.tee_file, None);
+
+    let suggestions = app.get_suggestions_for("/tee st");
+    assert_eq!(
+        suggestions.first().map(|(cmd, _)| cmd.as_str()),
+        Some("/tee status")
+    );
+}
@@ -0,0 +1,44 @@
+//! App-side hooks for the todo checklist: the per-tick refresh, the collapse
+//! toggle, and click handling.
+
+use super::App;
+use std::time::Instant;
+
+impl App {
+    /// Pull the session's current todos into the checklist. Reads go through
+    /// the info widget's todo cache, which `note_tool_completed` invalidates
+    /// whenever the todo tool runs, so this is cheap to call every tick.
+    pub(super) fn refresh_todo_checklist(&mut self) -> bool {
+        let todos = super::helpers::gather_todos_for_session(self.active_client_session_id());
+        self.todo_checklist.apply(todos, Instant::now())
+    }
+
+    pub(super) fn toggle_todo_checklist(&mut self) {
+        self.todo_checklist.collapsed = !self.todo_checklist.collapsed;
+        self.set_status_notice(if self.todo_checklist.collapsed {
+            "Todo checklist: collapsed"
+        } else {
+            "Todo checklist: expanded"
+        });
+    }
+
+    /// Handle a left click on the checklist: the header row collapses or
+    /// expands it, an item row copies the item's text. Returns whether the
+    /// click landed on the checklist.
+    pub(super) fn handle_todo_checklist_click(&mut self, column: u16, row: u16) -> bool {
+        let Some(hit) = crate::tui::ui::todo_checklist_hit(column, row) else {
+            return false;
+        };
+        match hit {
+            crate::tui::ui::TodoChecklistHit::Toggle => self.toggle_todo_checklist(),
+            crate::tui::ui::TodoChecklistHit::Copy(text) => {
+                if super::helpers::copy_to_clipboard(&text) {
+                    self.set_status_notice("Copied todo");
+                } else {
+                    self.set_status_notice("Failed to copy todo");
+                }
+            }
+        }
+        true
+    }
+}
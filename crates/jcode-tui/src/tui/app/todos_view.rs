@@ -2,9 +2,10 @@ use super::App;
 use crate::side_panel::{
     SidePanelPage, SidePanelPageFormat, SidePanelPageSource, SidePanelSnapshot,
 };
-use crate::todo::TodoItem;
+use crate::todo::{ProjectTodo, TodoItem};
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
 
 pub(super) const TODOS_VIEW_PAGE_ID: &str = "session_todos";
 const TODOS_VIEW_TITLE: &str = "Todos";
@@ -105,15 +106,26 @@ impl App {
         self.apply_side_panel_snapshot(snapshot);
     }
 
+    fn todos_project_dir(&self) -> Option<PathBuf> {
+        self.session.working_dir.as_deref().map(PathBuf::from)
+    }
+
     fn refresh_todos_view_cache(&mut self, force: bool) -> bool {
+        let project_todos = self
+            .todos_project_dir()
+            .map(|dir| crate::todo::open_project_todos(&dir))
+            .unwrap_or_default();
         let session_id = self.active_client_session_id();
         let todos = load_current_session_todos(session_id);
-        let next_hash = hash_todos_payload(session_id, &todos);
+        let next_hash =
+            hash_project_todos_payload(hash_todos_payload(session_id, &todos), &project_todos);
         if !force && self.todos_view_rendered_hash == next_hash {
             return false;
         }
 
-        self.todos_view_markdown = build_todos_view_markdown(session_id, &todos);
+        let mut markdown = build_todos_view_markdown(session_id, &todos);
+        push_project_todos_markdown(&mut markdown, session_id, &project_todos);
+        self.todos_view_markdown = markdown;
         self.todos_view_updated_at_ms = now_ms();
         self.todos_view_rendered_hash = next_hash;
         true
@@ -138,7 +150,7 @@ impl App {
 
 pub(super) fn todos_view_status_message(app: &App) -> String {
     format!(
-        "Todo screen: {}\n\nWhen enabled, the side panel shows a transient Todos page dedicated to the current session's todo list and refreshes as the list changes. It is not persisted to session side-panel storage.",
+        "Todo screen: {}\n\nWhen enabled, the side panel shows a transient Todos page with the current session's todo list and the project's open todos from `.jcode/todos.json`, and refreshes as they change. It is not persisted to session side-panel storage.\n\nManage project todos with `/todos add <text>`, `/todos done <n>`, and `/todos rm <n>`.",
         if app.todos_view_enabled() {
             "enabled"
         } else {
@@ -153,6 +165,15 @@ pub(super) fn handle_todos_view_command(app: &mut App, trimmed: &str) -> bool {
     }
 
     let arg = trimmed.strip_prefix("/todos").unwrap_or_default().trim();
+    let (word, rest) = arg
+        .split_once(char::is_whitespace)
+        .map(|(word, rest)| (word, rest.trim()))
+        .unwrap_or((arg, ""));
+    if matches!(word, "add" | "done" | "rm") {
+        handle_project_todo_command(app, word, rest);
+        return true;
+    }
+
     match arg {
         "" => {
             let enabled = !app.todos_view_enabled();
@@ -160,7 +181,7 @@ pub(super) fn handle_todos_view_command(app: &mut App, trimmed: &str) -> bool {
             if enabled {
                 app.set_status_notice("Todos: ON");
                 app.push_display_message(crate::tui::DisplayMessage::system(
-                    "Todo screen enabled. The side panel now shows only this session's todo list."
+                    "Todo screen enabled. The side panel now shows this session's todos and open project todos."
                         .to_string(),
                 ));
             } else {
@@ -174,7 +195,7 @@ pub(super) fn handle_todos_view_command(app: &mut App, trimmed: &str) -> bool {
             app.set_todos_view_enabled(true, true);
             app.set_status_notice("Todos: ON");
             app.push_display_message(crate::tui::DisplayMessage::system(
-                "Todo screen enabled. The side panel now shows only this session's todo list."
+                "Todo screen enabled. The side panel now shows this session's todos and open project todos."
                     .to_string(),
             ));
         }
@@ -191,15 +212,82 @@ pub(super) fn handle_todos_view_command(app: &mut App, trimmed: &str) -> bool {
             ));
         }
         _ => {
-            app.push_display_message(crate::tui::DisplayMessage::error(
-                "Usage: /todos [on|off|status]".to_string(),
-            ));
+            app.push_display_message(crate::tui::DisplayMessage::error(TODOS_USAGE.to_string()));
         }
     }
 
     true
 }
 
+const TODOS_USAGE: &str = "Usage: /todos [on|off|status|add <text>|done <n>|rm <n>]";
+
+fn handle_project_todo_command(app: &mut App, action: &str, rest: &str) {
+    let Some(project_dir) = app.todos_project_dir() else {
+        app.push_display_message(crate::tui::DisplayMessage::error(
+            "Project todos need a session working directory.".to_string(),
+        ));
+        return;
+    };
+
+    let result = if action == "add" {
+        if rest.is_empty() {
+            app.push_display_message(crate::tui::DisplayMessage::error(
+                "Usage: /todos add <text>".to_string(),
+            ));
+            return;
+        }
+        let session_id = app
+            .active_client_session_id()
+            .unwrap_or(crate::todo::MANUAL_TODO_SESSION)
+            .to_string();
+        crate::todo::add_project_todo(&project_dir, &session_id, rest)
+            .map(|item| format!("Added project todo: {}", item.content))
+    } else {
+        let open = crate::todo::open_project_todos(&project_dir);
+        let Some(todo) = rest
+            .parse::<usize>()
+            .ok()
+            .and_then(|number| number.checked_sub(1))
+            .and_then(|index| open.get(index))
+        else {
+            app.push_display_message(crate::tui::DisplayMessage::error(format!(
+                "No open project todo #{}. Run /todos to see the numbered list.",
+                rest
+            )));
+            return;
+        };
+        let (edited, verb) = if action == "done" {
+            (
+                crate::todo::set_project_todo_status(
+                    &project_dir,
+                    &todo.session_id,
+                    &todo.item.id,
+                    "completed",
+                ),
+                "Completed",
+            )
+        } else {
+            (
+                crate::todo::remove_project_todo(&project_dir, &todo.session_id, &todo.item.id),
+                "Deleted",
+            )
+        };
+        edited.map(|_| format!("{} project todo: {}", verb, todo.item.content))
+    };
+
+    match result {
+        Ok(message) => {
+            crate::todo_sync::sync_configured(&project_dir);
+            app.refresh_todos_view_now();
+            app.push_display_message(crate::tui::DisplayMessage::system(message));
+        }
+        Err(error) => app.push_display_message(crate::tui::DisplayMessage::error(format!(
+            "Failed to update project todos: {}",
+            error
+        ))),
+    }
+}
+
 fn load_current_session_todos(session_id: Option<&str>) -> Vec<TodoItem> {
     let Some(session_id) = session_id else {
         return Vec::new();
@@ -312,6 +400,47 @@ fn build_todos_view_markdown(session_id: Option<&str>, todos: &[TodoItem]) -> St
     markdown
 }
 
+/// Append the numbered open project todos, grouped by status. Numbers match
+/// `/todos done <n>` and `/todos rm <n>`.
+fn push_project_todos_markdown(
+    markdown: &mut String,
+    session_id: Option<&str>,
+    project_todos: &[ProjectTodo],
+) {
+    markdown.push_str("\n## Project open todos\n\n");
+    if project_todos.is_empty() {
+        markdown
+            .push_str("No open todos in `.jcode/todos.json`. Add one with `/todos add <text>`.\n");
+        return;
+    }
+    let mut heading = None;
+    for (index, todo) in project_todos.iter().enumerate() {
+        let next_heading = if todo.item.status == "in_progress" {
+            "In progress"
+        } else {
+            "Pending"
+        };
+        if heading != Some(next_heading) {
+            markdown.push_str(&format!("### {}\n\n", next_heading));
+            heading = Some(next_heading);
+        }
+        let owner = if Some(todo.session_id.as_str()) == session_id {
+            "this session".to_string()
+        } else {
+            crate::id::extract_session_name(&todo.session_id)
+                .unwrap_or(&todo.session_id)
+                .to_string()
+        };
+        markdown.push_str(&format!(
+            "{}. `[{}]` {} · _{}_\n",
+            index + 1,
+            todo.item.priority,
+            todo.item.content,
+            owner
+        ));
+    }
+}
+
 /// Group key for the side-panel view, treating empty/whitespace as ungrouped.
 fn todo_group_key(todo: &TodoItem) -> Option<String> {
     todo.group
@@ -480,6 +609,19 @@ fn hash_todos_payload(session_id: Option<&str>, todos: &[TodoItem]) -> u64 {
     hasher.finish()
 }
 
+fn hash_project_todos_payload(seed: u64, project_todos: &[ProjectTodo]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    seed.hash(&mut hasher);
+    for todo in project_todos {
+        todo.session_id.hash(&mut hasher);
+        todo.item.id.hash(&mut hasher);
+        todo.item.content.hash(&mut hasher);
+        todo.item.status.hash(&mut hasher);
+        todo.item.priority.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
 fn todos_view_placeholder_markdown() -> String {
     "# Todos\n\nWaiting for a session todo list.\n".to_string()
 }
@@ -608,6 +750,27 @@ mod tests {
         assert!(opt < scroll && scroll < other_idx, "{markdown}");
     }
 
+    #[test]
+    fn project_todos_markdown_numbers_items_and_names_owner() {
+        let project = vec![
+            ProjectTodo {
+                item: todo("1", "Ship parser", "in_progress", "high", None, None),
+                session_id: "session_test".to_string(),
+                updated_at_ms: 1,
+            },
+            ProjectTodo {
+                item: todo("2", "Write docs", "pending", "low", None, None),
+                session_id: crate::todo::MANUAL_TODO_SESSION.to_string(),
+                updated_at_ms: 2,
+            },
+        ];
+        let mut markdown = String::new();
+        push_project_todos_markdown(&mut markdown, Some("session_test"), &project);
+
+        assert!(markdown.contains("### In progress\n\n1. `[high]` Ship parser · _this session_"));
+        assert!(markdown.contains("### Pending\n\n2. `[low]` Write docs · _manual_"));
+    }
+
     #[test]
     fn todos_view_hash_changes_when_group_changes() {
         let mut todos = vec![todo("g", "Group hash", "pending", "high", Some(80), None)];
@@ -260,6 +260,7 @@ impl App {
         }
         let display = config().display.clone();
         let features = config().features.clone();
+        let side_pane_ratio = display.side_pane_ratio.clamp(25, 100);
         let autoreview_enabled = session
             .autoreview_enabled
             .unwrap_or(config().autoreview.enabled);
@@ -311,6 +312,7 @@ impl App {
             pending_history_anchor: None,
             input: String::new(),
             command_candidates_cache: RefCell::new(None),
+            resume_candidates_cache: RefCell::new(None),
             cursor_pos: 0,
             scroll_offset: 0,
             auto_scroll_paused: false,
@@ -347,6 +349,8 @@ impl App {
             last_turn_input_tokens: None,
             pending_turn: false,
             auto_poke_incomplete_todos: true,
+            tee_file: crate::output_tee::configured_tee_file(),
+            auto_commit_enabled: crate::config::config().git.auto_commit,
             overnight_auto_poke: None,
             pending_provider_failover: None,
             pending_fallback_offer: None,
@@ -361,6 +365,7 @@ impl App {
             last_resize_redraw: None,
             mcp_server_names: Vec::new(),
             connection_phase_started: None,
+            mcp_call_deadline: None,
             stream_buffer: StreamBuffer::new(),
             thinking_start: None,
             thought_line_inserted: false,
@@ -435,6 +440,9 @@ impl App {
             server_spawning: false,
             is_replay: false,
             suppress_terminal_title_updates: false,
+            title_format: display.title_format.clone(),
+            tmux_status: display.tmux_status,
+            last_terminal_title: None,
             replay_elapsed_override: None,
             replay_processing_started_ms: None,
             tool_call_ids: HashSet::new(),
@@ -451,7 +459,13 @@ impl App {
             known_stable_version: crate::build::read_stable_version().ok().flatten(),
             last_version_check: Some(Instant::now()),
             pending_migration: None,
+            mirror_last_write: None,
+            mirror_last_hash: None,
+            mirror_sync_pending: false,
             remote_client_count: None,
+            last_agent_limits: None,
+            last_memory_injection: None,
+            remote_active_skills: Vec::new(),
             resume_session_id: None,
             requested_exit_code: None,
             memory_enabled: features.memory,
@@ -470,9 +484,9 @@ impl App {
             diagram_index: 0,
             diagram_scroll_x: 0,
             diagram_scroll_y: 0,
-            diagram_pane_ratio: 40,
-            diagram_pane_ratio_from: 40,
-            diagram_pane_ratio_target: 40,
+            diagram_pane_ratio: side_pane_ratio,
+            diagram_pane_ratio_from: side_pane_ratio,
+            diagram_pane_ratio_target: side_pane_ratio,
             diagram_pane_ratio_user_adjusted: false,
             diagram_pane_anim_start: None,
             diagram_pane_enabled: true,
@@ -485,6 +499,7 @@ impl App {
             side_panel_image_zoom_percent: 100,
             diff_pane_focus: false,
             diff_pane_auto_scroll: true,
+            file_view_pin: None,
             side_panel: crate::side_panel::SidePanelSnapshot::default(),
             observe_mode_enabled: false,
             observe_page_markdown: String::new(),
@@ -498,6 +513,7 @@ impl App {
             todos_view_markdown: String::new(),
             todos_view_updated_at_ms: 0,
             todos_view_rendered_hash: 0,
+            todo_checklist: Default::default(),
             last_side_panel_refresh: None,
             last_client_focus_recorded_at: None,
             last_client_focus_session_id: None,
@@ -533,7 +549,13 @@ impl App {
             dictation_key: keybind::load_dictation_key(),
             new_terminal_key: keybind::load_new_terminal_key(),
             open_resume_key: keybind::load_open_resume_key(),
+            interject_key: keybind::load_interject_key(),
+            image_open_key: keybind::load_image_open_key(),
+            remember_message_key: keybind::load_remember_message_key(),
+            copy_message_key: keybind::load_copy_message_key(),
+            file_picker_key: keybind::load_file_picker_key(),
             fallback_switch_key: keybind::load_fallback_switch_key(),
+            composer_edit_keys: keybind::load_composer_edit_keys(),
             scroll_keys: keybind::load_scroll_keys(),
             dictation_session: None,
             dictation_in_flight: false,
@@ -543,6 +565,7 @@ impl App {
             typing_scroll_lock: false,
             stashed_input: None,
             input_undo_stack: Vec::new(),
+            kill_ring: Default::default(),
             status_notice: None,
             learn_hint: None,
             learn_hint_shown_this_session: false,
@@ -551,9 +574,19 @@ impl App {
             unknown_hotkey_seen: std::collections::HashMap::new(),
             last_unknown_hotkey_notice: None,
             pending_startup_notice: None,
+            pending_startup_profile: None,
+            pending_startup_safe_mode: false,
+            pending_safe_mode_approval: None,
+            pending_ask_user: None,
+            permission_inbox: Vec::new(),
+            permission_inbox_selected: None,
+            last_permission_inbox_poll: None,
+            remote_turn_queue: Vec::new(),
+            turn_queue_display_pending: false,
             experimental_feature_warnings_seen: HashSet::new(),
             active_experimental_feature_notice: None,
             interleave_message: None,
+            interjection: None,
             pending_soft_interrupts: Vec::new(),
             pending_soft_interrupt_requests: Vec::new(),
             autoreview_after_current_turn: false,
@@ -570,6 +603,7 @@ impl App {
             pending_local_transfer: None,
             queue_mode: display.queue_mode,
             auto_server_reload: display.auto_server_reload,
+            migration_grace_secs: display.migration_grace_secs,
             pending_queued_dispatch: false,
             tab_completion_state: None,
             command_suggestion_selected: 0,
@@ -590,6 +624,10 @@ impl App {
             ambient_system_prompt: None,
             pending_login: None,
             pending_account_input: None,
+            pending_memory_pin: None,
+            pending_saved_prompt: None,
+            pending_aside_summary: None,
+            pending_handoff: None,
             pending_ssh_remote_name: None,
             force_full_redraw: false,
             force_full_repaint: false,
@@ -600,6 +638,8 @@ impl App {
             changelog_scroll: None,
             help_scroll: None,
             model_status_scroll: None,
+            compare_view: None,
+            compare_in_flight: None,
             model_status_content: String::new(),
             session_picker_overlay: None,
             session_picker_mode: SessionPickerMode::Resume,
@@ -608,6 +648,8 @@ impl App {
             pending_catchup_resume: None,
             in_flight_catchup_resume: None,
             login_picker_overlay: None,
+            file_picker_overlay: None,
+            file_picker_mode: Default::default(),
             account_picker_overlay: None,
             usage_overlay: None,
             usage_report_refreshing: false,
@@ -632,9 +674,11 @@ impl App {
         session.mark_active();
         session.model = Some(provider.model());
         session.provider_key = crate::session::derive_session_provider_key(provider.name());
+        session.apply_startup_workspace_roots();
         session.ensure_initial_session_context_message();
         let display = config().display.clone();
         let features = config().features.clone();
+        let side_pane_ratio = display.side_pane_ratio.clamp(25, 100);
         let autoreview_enabled = session
             .autoreview_enabled
             .unwrap_or(config().autoreview.enabled);
@@ -716,6 +760,7 @@ impl App {
             pending_history_anchor: None,
             input: String::new(),
             command_candidates_cache: RefCell::new(None),
+            resume_candidates_cache: RefCell::new(None),
             cursor_pos: 0,
             scroll_offset: 0,
             auto_scroll_paused: false,
@@ -752,6 +797,8 @@ impl App {
             last_turn_input_tokens: None,
             pending_turn: false,
             auto_poke_incomplete_todos: true,
+            tee_file: crate::output_tee::configured_tee_file(),
+            auto_commit_enabled: crate::config::config().git.auto_commit,
             overnight_auto_poke: None,
             pending_provider_failover: None,
             pending_fallback_offer: None,
@@ -766,6 +813,7 @@ impl App {
             last_resize_redraw: None,
             mcp_server_names: Vec::new(), // Vec<(name, tool_count)>
             connection_phase_started: None,
+            mcp_call_deadline: None,
             stream_buffer: StreamBuffer::new(),
             thinking_start: None,
             thought_line_inserted: false,
@@ -840,6 +888,9 @@ impl App {
             server_spawning: false,
             is_replay: false,
             suppress_terminal_title_updates: false,
+            title_format: display.title_format.clone(),
+            tmux_status: display.tmux_status,
+            last_terminal_title: None,
             replay_elapsed_override: None,
             replay_processing_started_ms: None,
             tool_call_ids: HashSet::new(),
@@ -856,7 +907,13 @@ impl App {
             known_stable_version: crate::build::read_stable_version().ok().flatten(),
             last_version_check: Some(Instant::now()),
             pending_migration: None,
+            mirror_last_write: None,
+            mirror_last_hash: None,
+            mirror_sync_pending: false,
             remote_client_count: None,
+            last_agent_limits: None,
+            last_memory_injection: None,
+            remote_active_skills: Vec::new(),
             resume_session_id: None,
             requested_exit_code: None,
             memory_enabled: features.memory,
@@ -875,9 +932,9 @@ impl App {
             diagram_index: 0,
             diagram_scroll_x: 0,
             diagram_scroll_y: 0,
-            diagram_pane_ratio: 40,
-            diagram_pane_ratio_from: 40,
-            diagram_pane_ratio_target: 40,
+            diagram_pane_ratio: side_pane_ratio,
+            diagram_pane_ratio_from: side_pane_ratio,
+            diagram_pane_ratio_target: side_pane_ratio,
             diagram_pane_ratio_user_adjusted: false,
             diagram_pane_anim_start: None,
             diagram_pane_enabled: true,
@@ -890,6 +947,7 @@ impl App {
             side_panel_image_zoom_percent: 100,
             diff_pane_focus: false,
             diff_pane_auto_scroll: true,
+            file_view_pin: None,
             side_panel: crate::side_panel::SidePanelSnapshot::default(),
             observe_mode_enabled: false,
             observe_page_markdown: String::new(),
@@ -903,6 +961,7 @@ impl App {
             todos_view_markdown: String::new(),
             todos_view_updated_at_ms: 0,
             todos_view_rendered_hash: 0,
+            todo_checklist: Default::default(),
             last_side_panel_refresh: None,
             last_client_focus_recorded_at: None,
             last_client_focus_session_id: None,
@@ -938,7 +997,13 @@ impl App {
             dictation_key: keybind::load_dictation_key(),
             new_terminal_key: keybind::load_new_terminal_key(),
             open_resume_key: keybind::load_open_resume_key(),
+            interject_key: keybind::load_interject_key(),
+            image_open_key: keybind::load_image_open_key(),
+            remember_message_key: keybind::load_remember_message_key(),
+            copy_message_key: keybind::load_copy_message_key(),
+            file_picker_key: keybind::load_file_picker_key(),
             fallback_switch_key: keybind::load_fallback_switch_key(),
+            composer_edit_keys: keybind::load_composer_edit_keys(),
             scroll_keys: keybind::load_scroll_keys(),
             dictation_session: None,
             dictation_in_flight: false,
@@ -948,6 +1013,7 @@ impl App {
             typing_scroll_lock: false,
             stashed_input: None,
             input_undo_stack: Vec::new(),
+            kill_ring: Default::default(),
             status_notice: None,
             learn_hint: None,
             learn_hint_shown_this_session: false,
@@ -956,9 +1022,19 @@ impl App {
             unknown_hotkey_seen: std::collections::HashMap::new(),
             last_unknown_hotkey_notice: None,
             pending_startup_notice: None,
+            pending_startup_profile: None,
+            pending_startup_safe_mode: false,
+            pending_safe_mode_approval: None,
+            pending_ask_user: None,
+            permission_inbox: Vec::new(),
+            permission_inbox_selected: None,
+            last_permission_inbox_poll: None,
+            remote_turn_queue: Vec::new(),
+            turn_queue_display_pending: false,
             experimental_feature_warnings_seen: HashSet::new(),
             active_experimental_feature_notice: None,
             interleave_message: None,
+            interjection: None,
             pending_soft_interrupts: Vec::new(),
             pending_soft_interrupt_requests: Vec::new(),
             autoreview_after_current_turn: false,
@@ -975,6 +1051,7 @@ impl App {
             pending_local_transfer: None,
             queue_mode: display.queue_mode,
             auto_server_reload: display.auto_server_reload,
+            migration_grace_secs: display.migration_grace_secs,
             pending_queued_dispatch: false,
             tab_completion_state: None,
             command_suggestion_selected: 0,
@@ -995,6 +1072,10 @@ impl App {
             ambient_system_prompt: None,
             pending_login: None,
             pending_account_input: None,
+            pending_memory_pin: None,
+            pending_saved_prompt: None,
+            pending_aside_summary: None,
+            pending_handoff: None,
             pending_ssh_remote_name: None,
             force_full_redraw: false,
             force_full_repaint: false,
@@ -1005,6 +1086,8 @@ impl App {
             changelog_scroll: None,
             help_scroll: None,
             model_status_scroll: None,
+            compare_view: None,
+            compare_in_flight: None,
             model_status_content: String::new(),
             session_picker_overlay: None,
             session_picker_mode: SessionPickerMode::Resume,
@@ -1013,6 +1096,8 @@ impl App {
             pending_catchup_resume: None,
             in_flight_catchup_resume: None,
             login_picker_overlay: None,
+            file_picker_overlay: None,
+            file_picker_mode: Default::default(),
             account_picker_overlay: None,
             usage_overlay: None,
             usage_report_refreshing: false,
@@ -1068,8 +1153,10 @@ impl App {
         let render_start = Instant::now();
         let (rendered_messages, rendered_images) =
             crate::session::render_messages_and_images(&session);
-        let display_messages =
-            jcode_tui_messages::display_messages_from_rendered_messages(rendered_messages);
+        let display_messages = jcode_tui_messages::display_messages_from_rendered_messages(
+            rendered_messages,
+            crate::config::config().display.message_footers,
+        );
         self.replace_display_messages(display_messages);
         let render_ms = render_start.elapsed().as_millis();
 
@@ -1,5 +1,9 @@
 use super::*;
 use crate::tui::connection_type_icon;
+use crate::tui::terminal_title::{TitleFields, TitleState, render_title};
+
+pub(super) const TRANSCRIPT_VIEW_HINT: &str =
+    "read-only · q quit · / search · r resume · y copy mode";
 
 impl App {
     /// Create an App instance for replay mode (playing back a saved session)
@@ -11,6 +15,17 @@ impl App {
         Self::new_for_replay_with_title(session, false)
     }
 
+    /// Create an App for `jcode view`: the whole saved transcript is loaded at
+    /// once and rendered read-only through the same replay runtime (inert
+    /// provider, no server), without playback timing.
+    pub fn new_for_transcript_view(session: crate::session::Session) -> Self {
+        let messages = crate::tui::display_messages_from_session(&session);
+        let mut app = Self::new_for_replay_with_title(session, false);
+        app.replace_display_messages(messages);
+        app.set_status_notice(TRANSCRIPT_VIEW_HINT);
+        app
+    }
+
     fn new_for_replay_with_title(session: crate::session::Session, set_title: bool) -> Self {
         let provider: Arc<dyn Provider> =
             Arc::new(InertRuntimeProvider::new(AppRuntimeMode::Replay));
@@ -61,7 +76,23 @@ impl App {
         &self.session.id
     }
 
-    pub(super) fn update_terminal_title(&self) {
+    /// What the title glyph shows: approvals outrank tool runs, which
+    /// outrank streaming.
+    pub(super) fn terminal_title_state(&self) -> TitleState {
+        if self.pending_ask_user.is_some()
+            || self.pending_safe_mode_approval.is_some()
+            || !self.permission_inbox.is_empty()
+        {
+            return TitleState::AwaitingApproval;
+        }
+        match self.status {
+            ProcessingStatus::RunningTool(_) => TitleState::RunningTool,
+            ProcessingStatus::Idle if !self.is_processing => TitleState::Idle,
+            _ => TitleState::Streaming,
+        }
+    }
+
+    pub(super) fn update_terminal_title(&mut self) {
         if self.suppress_terminal_title_updates {
             return;
         }
@@ -93,6 +124,19 @@ impl App {
         } else {
             format!("jcode/{}", server_name.to_lowercase())
         };
+        let title = render_title(
+            &self.title_format,
+            &TitleFields {
+                state: self.terminal_title_state(),
+                icon,
+                server: &server_label,
+                session: &session_label,
+                suffix,
+            },
+        );
+        if self.last_terminal_title.as_deref() == Some(title.as_str()) {
+            return;
+        }
         if server_name.eq_ignore_ascii_case("jcode") {
             crate::process_title::set_client_display_title(&session_name, is_canary);
         } else {
@@ -104,11 +148,21 @@ impl App {
         }
         let _ = crossterm::execute!(
             std::io::stdout(),
-            crossterm::terminal::SetTitle(format!(
-                "{} {} {}{}",
-                icon, server_label, session_label, suffix
-            ))
+            crossterm::terminal::SetTitle(title.as_str())
         );
+        if self.tmux_status {
+            crate::tui::terminal_title::write_status_file(&session_name, &title);
+        }
+        self.last_terminal_title = Some(title);
+    }
+
+    /// Re-title the terminal when the session's state glyph changes.
+    /// Remote clients wait until the server has named the session.
+    pub(super) fn refresh_terminal_title_state(&mut self) {
+        if self.is_replay || (self.is_remote && self.last_terminal_title.is_none()) {
+            return;
+        }
+        self.update_terminal_title();
     }
 
     pub(super) fn reconnect_target_session_id(&self) -> Option<String> {
@@ -298,6 +352,7 @@ impl App {
 
             for item in jcode_tui_messages::display_messages_from_rendered_messages(
                 crate::session::render_messages(&session),
+                crate::config::config().display.message_footers,
             ) {
                 if item.role == "user" {
                     user_turns += 1;
@@ -320,36 +375,30 @@ impl App {
             // Clear the saved provider_session_id since it's no longer valid
             self.session.provider_session_id = None;
             let mut restored_model = false;
-            if let Some(model) = self.session.model.clone() {
-                let model_request =
-                    crate::provider::MultiProvider::model_switch_request_for_session_route(
-                        &model,
-                        self.session.provider_key.as_deref(),
-                        self.session.route_api_method.as_deref(),
-                    );
-                if let Err(e) = crate::provider::set_model_with_auth_refresh(
+            if self.session.model.is_some() {
+                match crate::provider::restore_session_model_route(
                     self.provider.as_ref(),
-                    &model_request,
+                    &self.session,
                 ) {
-                    self.push_display_message(DisplayMessage {
+                    None => restored_model = true,
+                    Some(fallback) => self.push_display_message(DisplayMessage {
                         role: "system".to_string(),
-                        content: format!(
-                            "⚠ Failed to restore model '{}' via '{}': {}",
-                            model, model_request, e
-                        ),
+                        content: format!("⚠ Model route changed on resume: {}", fallback.summary()),
                         tool_calls: vec![],
                         duration_secs: None,
                         title: None,
                         tool_data: None,
-                    });
-                } else {
-                    restored_model = true;
+                    }),
                 }
             }
 
             let active_model = self.provider.model();
             if restored_model || self.session.model.is_none() {
                 self.session.model = Some(active_model.clone());
+                self.session.model_route = Some(crate::provider::current_model_route(
+                    self.provider.as_ref(),
+                    self.session.route_api_method.as_deref(),
+                ));
             }
             self.update_context_limit_for_model(&active_model);
             // Mark session as active now that it's being used again
@@ -645,8 +694,8 @@ pub(super) fn handle_dev_command(app: &mut App, trimmed: &str) -> bool {
         return true;
     }
 
-    if trimmed == "/rebuild" {
-        app.start_background_client_rebuild(app.session.id.clone());
+    if let Some(full_tests) = App::parse_rebuild_command(trimmed) {
+        app.start_background_client_rebuild(app.session.id.clone(), full_tests);
         return true;
     }
 
@@ -655,6 +704,96 @@ pub(super) fn handle_dev_command(app: &mut App, trimmed: &str) -> bool {
         return true;
     }
 
+    if let Some(command) = super::commands::parse_premium_command(trimmed) {
+        match command {
+            Err(error) => app.push_display_message(DisplayMessage::error(error)),
+            Ok(super::commands::PremiumCommand::Status) => {
+                app.push_display_message(DisplayMessage::system(
+                    super::commands::premium_status_message(app),
+                ));
+            }
+            Ok(super::commands::PremiumCommand::Set(mode)) => {
+                app.provider.set_premium_mode(mode);
+                let label = super::commands::premium_mode_label(mode);
+                app.set_status_notice(format!("Premium: {}", label));
+                app.push_display_message(DisplayMessage::system(format!(
+                    "Premium mode for this session: {}.",
+                    label,
+                )));
+            }
+        }
+        return true;
+    }
+
+    if let Some(command) = super::commands::parse_root_command(trimmed) {
+        let messages_before = app.session.messages.len();
+        let outcome = match command {
+            Err(error) => Err(error),
+            Ok(super::commands::WorkspaceRootCommand::List) => Ok(None),
+            Ok(super::commands::WorkspaceRootCommand::Add(path)) => app
+                .session
+                .add_workspace_root(&path)
+                .map(|root| Some(format!("Root added: {}", root)))
+                .map_err(|error| format!("Failed to add workspace root: {}", error)),
+            Ok(super::commands::WorkspaceRootCommand::Remove(name)) => app
+                .session
+                .remove_workspace_root(&name)
+                .map(|root| Some(format!("Root removed: {}", root)))
+                .ok_or_else(|| format!("No workspace root named '{}'", name)),
+        };
+        match outcome {
+            Err(error) => app.push_display_message(DisplayMessage::error(error)),
+            Ok(notice) => {
+                if let Some(notice) = notice {
+                    // Mid-conversation changes append a reminder to the session;
+                    // mirror it into the provider transcript so the model sees it.
+                    let appended: Vec<_> = app.session.messages[messages_before..]
+                        .iter()
+                        .map(|message| message.to_message())
+                        .collect();
+                    for message in appended {
+                        app.add_provider_message(message);
+                    }
+                    let _ = app.session.save();
+                    app.set_status_notice(notice);
+                }
+                app.push_display_message(DisplayMessage::system(
+                    super::commands::workspace_roots_message(
+                        app.session.working_dir.as_deref(),
+                        &app.session.workspace_roots,
+                    ),
+                ));
+            }
+        }
+        return true;
+    }
+
+    if let Some(path) = super::commands::parse_cd_command(trimmed) {
+        let outcome = match path {
+            Some(path) => app.session.scope_tools(&path).map(|_| ()),
+            None => {
+                app.session.clear_tool_scope();
+                Ok(())
+            }
+        };
+        match outcome {
+            Err(error) => app.push_display_message(DisplayMessage::error(format!(
+                "Failed to change tool directory: {}",
+                error
+            ))),
+            Ok(()) => {
+                let _ = app.session.save();
+                let message = super::commands::tool_scope_message(
+                    app.session.working_dir.as_deref(),
+                    app.session.tool_scope.as_deref(),
+                );
+                app.set_status_notice("Tool directory changed");
+                app.push_display_message(DisplayMessage::system(message));
+            }
+        }
+        return true;
+    }
+
     if trimmed == "/z" || trimmed == "/zz" || trimmed == "/zzz" || trimmed == "/zstatus" {
         use crate::provider::copilot::PremiumMode;
         let current = app.provider.premium_mode();
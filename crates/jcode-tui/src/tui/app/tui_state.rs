@@ -706,6 +706,10 @@ impl crate::tui::TuiState for App {
             .or_else(|| self.elapsed())
     }
 
+    fn mcp_call_remaining(&self) -> Option<Duration> {
+        App::mcp_call_remaining(self)
+    }
+
     fn command_suggestions(&self) -> Vec<(String, &'static str)> {
         App::command_suggestions(self)
     }
@@ -791,6 +795,16 @@ impl crate::tui::TuiState for App {
         self.diff_mode
     }
 
+    fn file_view_pin(&self) -> Option<String> {
+        self.file_view_pin.clone()
+    }
+
+    fn todo_checklist(&self) -> Option<&crate::tui::todo_checklist::TodoChecklist> {
+        self.todo_checklist
+            .has_open_items()
+            .then_some(&self.todo_checklist)
+    }
+
     fn current_session_id(&self) -> Option<String> {
         if self.is_remote {
             self.remote_session_id.clone()
@@ -897,6 +911,12 @@ impl crate::tui::TuiState for App {
         })
     }
 
+    fn migration_banner(&self) -> Option<String> {
+        self.pending_migration.as_ref().map(|migration| {
+            super::commands_migrate::banner_text(migration, super::commands_migrate::now_ms())
+        })
+    }
+
     fn active_experimental_feature_notice(&self) -> Option<String> {
         self.active_experimental_feature_notice.clone()
     }
@@ -932,6 +952,14 @@ impl crate::tui::TuiState for App {
         self.route_next_prompt_to_new_session
     }
 
+    fn aside_active(&self) -> bool {
+        self.session.active_aside().is_some()
+    }
+
+    fn interjection(&self) -> Option<crate::tui::InterjectionPriority> {
+        self.active_interjection()
+    }
+
     fn has_stashed_input(&self) -> bool {
         self.stashed_input.is_some()
     }
@@ -1106,6 +1134,16 @@ impl crate::tui::TuiState for App {
                         ContentBlock::OpenAICompaction { encrypted_content } => {
                             user_chars += encrypted_content.len();
                         }
+                        ContentBlock::ServerToolUse { name, input, .. } => {
+                            tool_call_count += 1;
+                            tool_call_chars += name.len() + input.to_string().len();
+                        }
+                        ContentBlock::WebSearchResult { results, .. } => {
+                            tool_result_count += 1;
+                            tool_result_chars +=
+                                results.iter().map(|hit| hit.payload_bytes()).sum::<usize>();
+                        }
+                        ContentBlock::Citations { .. } => {}
                     }
                 }
             }
@@ -1140,11 +1178,13 @@ impl crate::tui::TuiState for App {
             + info.session_context_chars
             + info.project_agents_md_chars
             + info.global_agents_md_chars
+            + info.scoped_instructions_chars
             + info.skills_chars
             + info.selfdev_chars
             + info.memory_chars
             + info.prompt_overlay_chars
             + info.preferred_tools_chars
+            + info.environment_chars
             + info.tool_defs_chars
             + info.user_messages_chars
             + info.assistant_messages_chars
@@ -1710,6 +1750,10 @@ impl crate::tui::TuiState for App {
             .map(|scroll| (scroll, self.model_status_content.as_str()))
     }
 
+    fn compare_view(&self) -> Option<&crate::tui::CompareView> {
+        self.compare_view.as_ref()
+    }
+
     fn session_picker_overlay(
         &self,
     ) -> Option<&RefCell<crate::tui::session_picker::SessionPicker>> {
@@ -1720,6 +1764,10 @@ impl crate::tui::TuiState for App {
         self.login_picker_overlay.as_ref()
     }
 
+    fn file_picker_overlay(&self) -> Option<&RefCell<crate::tui::file_picker::FilePicker>> {
+        self.file_picker_overlay.as_ref()
+    }
+
     fn account_picker_overlay(
         &self,
     ) -> Option<&RefCell<crate::tui::account_picker::AccountPicker>> {
@@ -1734,6 +1782,26 @@ impl crate::tui::TuiState for App {
         self.session.working_dir.clone()
     }
 
+    fn tool_scope(&self) -> Option<String> {
+        self.session.tool_scope.clone()
+    }
+
+    fn session_profile(&self) -> Option<String> {
+        self.session.profile.clone()
+    }
+
+    fn session_safe_mode(&self) -> bool {
+        self.session.safe_mode
+    }
+
+    fn session_has_instructions(&self) -> bool {
+        self.session.instructions.is_some()
+    }
+
+    fn permission_inbox_count(&self) -> usize {
+        self.permission_inbox.len()
+    }
+
     fn now_millis(&self) -> u64 {
         self.app_started.elapsed().as_millis() as u64
     }
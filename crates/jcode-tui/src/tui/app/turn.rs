@@ -34,6 +34,7 @@ impl App {
         let mut redraw_interval = interval(redraw_period);
         let mut status_spinner_interval = super::run_shell::status_spinner_interval();
         let mut status_spinner_renderer = super::run_shell::StatusSpinnerRenderer::default();
+        let mut rate_limit_waits = 0u32;
 
         'turn_loop: loop {
             let desired_redraw = crate::tui::redraw_interval(self);
@@ -176,6 +177,8 @@ impl App {
                         }
                     }
                     _ = redraw_interval.tick() => {
+                        self.refresh_terminal_title_state();
+                        self.write_mirror_if_due();
                         status_spinner_renderer.draw_full(self, terminal)?;
                         super::run_shell::reset_status_spinner_interval(&mut status_spinner_interval, self);
                     }
@@ -225,6 +228,7 @@ impl App {
             ));
 
             let mut text_content = String::new();
+            let mut text_cursor = crate::message::StreamTextCursor::default();
             let mut tool_calls: Vec<ToolCall> = Vec::new();
             let mut current_tool: Option<ToolCall> = None;
             let mut current_tool_input = String::new();
@@ -242,6 +246,8 @@ impl App {
             let mut reasoning_content = String::new();
             let mut reasoning_signature = String::new();
             let mut openai_reasoning_items: Vec<ContentBlock> = Vec::new();
+            let mut server_tool_blocks: Vec<ContentBlock> = Vec::new();
+            let mut citations: Vec<crate::message::Citation> = Vec::new();
             let mut openai_native_compaction: Option<(String, usize)> = None;
 
             // Stream with input handling
@@ -269,6 +275,8 @@ impl App {
                         self.apply_stream_ops(ops);
                         // Poll for background compaction completion during streaming
                         self.poll_compaction_completion();
+                        self.refresh_terminal_title_state();
+                        self.write_mirror_if_due();
                         status_spinner_renderer.draw_full(self, terminal)?;
                         super::run_shell::reset_status_spinner_interval(&mut status_spinner_interval, self);
                     }
@@ -324,6 +332,11 @@ impl App {
                                                     name: tc.name.clone(),
                                                     input: tc.input.clone(), thought_signature: None, });
                                             }
+                                            crate::message::attach_server_tool_blocks(
+                                                &mut content_blocks,
+                                                &server_tool_blocks,
+                                                &citations,
+                                            );
                                             if !content_blocks.is_empty() {
                                                 let content_clone = content_blocks.clone();
                                                 self.add_provider_message(Message {
@@ -392,6 +405,11 @@ impl App {
                                                     name: tc.name.clone(),
                                                     input: tc.input.clone(), thought_signature: None, });
                                             }
+                                            crate::message::attach_server_tool_blocks(
+                                                &mut content_blocks,
+                                                &server_tool_blocks,
+                                                &citations,
+                                            );
                                             // Add partial assistant response to messages
                                             if !content_blocks.is_empty() {
                                                 self.add_provider_message(Message {
@@ -470,6 +488,10 @@ impl App {
                                 if first_event {
                                     first_event = false;
                                 }
+                                // Drop text a reconnected provider stream sent again.
+                                let Some(event) = text_cursor.normalize(event) else {
+                                    continue;
+                                };
                                 match event {
                                     StreamEvent::TextDelta(text) => {
                                         self.status = ProcessingStatus::Streaming;
@@ -676,6 +698,11 @@ impl App {
                                         }
                                     }
                                     StreamEvent::MessageEnd { .. } => {
+                                        let sources = crate::message::citation_sources_markdown(&citations);
+                                        if !sources.is_empty() {
+                                            let ops = self.stream_buffer.push_text(&sources);
+                                            self.apply_stream_ops(ops);
+                                        }
                                         self.pause_streaming_tps(true);
                                         self.stream_message_ended = true;
                                         saw_message_end = true;
@@ -705,6 +732,8 @@ impl App {
                                         reasoning_content.clear();
                                         reasoning_signature.clear();
                                         openai_reasoning_items.clear();
+                                        server_tool_blocks.clear();
+                                        citations.clear();
                                         openai_native_compaction = None;
                                         saw_message_end = false;
                                         self.rollback_streaming_attempt();
@@ -725,12 +754,45 @@ impl App {
                                             break;
                                         }
                                     }
-                                    StreamEvent::Error { message, .. } => {
+                                    StreamEvent::Error { message, retry_after_secs } => {
                                         let no_partial_output = text_content.is_empty()
                                             && tool_calls.is_empty()
                                             && current_tool.is_none()
                                             && self.streaming.streaming_text.is_empty()
                                             && !saw_message_end;
+                                        if no_partial_output
+                                            && let Some(wait) = crate::provider::in_turn_rate_limit_wait(
+                                                retry_after_secs,
+                                                rate_limit_waits,
+                                            )
+                                        {
+                                            rate_limit_waits += 1;
+                                            let source_key =
+                                                crate::provider::rate_limit_source_key(self.provider.as_ref());
+                                            tokio::task::spawn_blocking(move || {
+                                                crate::usage::record_rate_limit(&source_key)
+                                            });
+                                            self.push_display_message(DisplayMessage::system(format!(
+                                                "⏳ {} rate limited - retrying automatically in {}s (attempt {}/{}).",
+                                                self.provider.display_name(),
+                                                wait.as_secs(),
+                                                rate_limit_waits,
+                                                crate::provider::MAX_RATE_LIMIT_WAITS
+                                            )));
+                                            let reset_at = Instant::now() + wait;
+                                            self.rate_limit_reset = Some(reset_at);
+                                            while Instant::now() < reset_at {
+                                                status_spinner_renderer.draw_full(self, terminal)?;
+                                                tokio::time::sleep(
+                                                    reset_at
+                                                        .saturating_duration_since(Instant::now())
+                                                        .min(Duration::from_millis(250)),
+                                                )
+                                                .await;
+                                            }
+                                            self.rate_limit_reset = None;
+                                            continue 'turn_loop;
+                                        }
                                         if no_partial_output
                                             && let Some(reason) = crate::network_retry::classify_message(&message)
                                         {
@@ -863,6 +925,15 @@ impl App {
                                         // Store the upstream provider (e.g., Fireworks, Together)
                                         self.upstream_provider = Some(provider);
                                     }
+                                    StreamEvent::SystemFingerprint(_) => {
+                                        // Recorded by the agent's turn metadata, not shown here
+                                    }
+                                    StreamEvent::BackgroundResponse { .. } => {
+                                        // Only server sessions re-attach after a restart
+                                    }
+                                    StreamEvent::TextDeltaAt { .. } => {
+                                        // Rewritten to TextDelta by text_cursor above
+                                    }
                                     StreamEvent::ToolResult { tool_use_id, content, is_error } => {
                                         // SDK already executed this tool
                                         self.tool_result_ids.insert(tool_use_id.clone());
@@ -974,7 +1045,8 @@ impl App {
                                             session_id: self.session_id().to_string(),
                                             message_id: self.session_id().to_string(),
                                             tool_call_id: request_id.clone(),
-                                            working_dir: self.session.working_dir.as_deref().map(PathBuf::from),
+                                            working_dir: self.session.tool_working_dir().map(PathBuf::from),
+                                            workspace_roots: self.session.workspace_roots.iter().map(PathBuf::from).collect(),
                                             stdin_request_tx: None,
                                             graceful_shutdown_signal: None,
                                             execution_mode: crate::tool::ToolExecutionMode::AgentTurn,
@@ -999,6 +1071,52 @@ impl App {
                                             let _ = sender.send(native_result).await;
                                         }
                                     }
+                                    StreamEvent::ServerToolUse { id, name, input } => {
+                                        if self.reasoning_streaming {
+                                            self.close_reasoning_region(None);
+                                        }
+                                        self.commit_pending_streaming_assistant_message();
+                                        if !text_content.is_empty() && !text_content.ends_with('\n') {
+                                            text_content.push_str("\n\n");
+                                        }
+                                        let tool_call = ToolCall {
+                                            id: id.clone(),
+                                            name: name.clone(),
+                                            intent: ToolCall::intent_from_input(&input),
+                                            input: input.clone(),
+                                            thought_signature: None,
+                                        };
+                                        self.push_display_message(DisplayMessage {
+                                            role: "tool".to_string(),
+                                            content: name.clone(),
+                                            tool_calls: vec![],
+                                            duration_secs: None,
+                                            title: None,
+                                            tool_data: Some(tool_call),
+                                        });
+                                        server_tool_blocks.push(ContentBlock::ServerToolUse { id, name, input });
+                                    }
+                                    StreamEvent::WebSearchResult { tool_use_id, results, error_code } => {
+                                        let output = crate::message::web_search_result_text(
+                                            &results,
+                                            error_code.as_deref(),
+                                        );
+                                        self.replace_latest_tool_display_message(&tool_use_id, None, output);
+                                        server_tool_blocks.push(ContentBlock::WebSearchResult {
+                                            tool_use_id,
+                                            results,
+                                            error_code,
+                                        });
+                                    }
+                                    StreamEvent::Citation(citation) => {
+                                        citations.push(citation);
+                                    }
+                                    StreamEvent::ServerToolUsage { web_search_requests } => {
+                                        crate::logging::info(&format!(
+                                            "Provider web searches this response: {}",
+                                            web_search_requests
+                                        ));
+                                    }
                                 }
                             }
                             Some(Err(e)) => {
@@ -1087,6 +1205,11 @@ impl App {
                     thought_signature: None,
                 });
             }
+            crate::message::attach_server_tool_blocks(
+                &mut content_blocks,
+                &server_tool_blocks,
+                &citations,
+            );
 
             let assistant_message_id = if !content_blocks.is_empty() {
                 crate::telemetry::record_assistant_response();
@@ -1257,7 +1380,13 @@ impl App {
                     session_id: self.session.id.clone(),
                     message_id: message_id.clone(),
                     tool_call_id: tc.id.clone(),
-                    working_dir: self.session.working_dir.as_deref().map(PathBuf::from),
+                    working_dir: self.session.tool_working_dir().map(PathBuf::from),
+                    workspace_roots: self
+                        .session
+                        .workspace_roots
+                        .iter()
+                        .map(PathBuf::from)
+                        .collect(),
                     stdin_request_tx: None,
                     graceful_shutdown_signal: None,
                     execution_mode: crate::tool::ToolExecutionMode::AgentTurn,
@@ -1278,6 +1407,7 @@ impl App {
                 let tool_name = tc.name.clone();
                 let tool_input = tc.input.clone();
                 let tool_start = Instant::now();
+                self.start_mcp_call_countdown(&tool_name);
                 let mut tool_future = std::pin::pin!(registry.execute(&tool_name, tool_input, ctx));
 
                 // Subscribe to bus for subagent status updates
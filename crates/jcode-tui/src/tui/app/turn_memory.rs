@@ -11,6 +11,7 @@ impl App {
             return crate::prompt::SplitSystemPrompt {
                 static_part: prompt.clone(),
                 dynamic_part: String::new(),
+                ..Default::default()
             };
         }
 
@@ -35,6 +36,11 @@ impl App {
             None,
         );
         self.append_current_turn_system_reminder(&mut split);
+        crate::prompt::append_tool_scope(&mut split, self.session.tool_scope.as_deref());
+        crate::prompt::append_session_instructions(
+            &mut split,
+            self.session.instructions.as_deref(),
+        );
         crate::prompt::append_swarm_effort_directive(
             &mut split,
             self.provider.reasoning_effort().as_deref(),
@@ -175,6 +181,13 @@ impl App {
                     ContentBlock::OpenAICompaction { .. } => {
                         transcript.push_str("[OpenAI native compaction]\n");
                     }
+                    ContentBlock::ServerToolUse { name, .. } => {
+                        transcript.push_str(&format!("[Used tool: {}]\n", name));
+                    }
+                    ContentBlock::WebSearchResult { results, .. } => {
+                        transcript.push_str(&format!("[Web search: {} results]\n", results.len()));
+                    }
+                    ContentBlock::Citations { .. } => {}
                 }
             }
             transcript.push('\n');
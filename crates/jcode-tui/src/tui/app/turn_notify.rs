@@ -4,7 +4,8 @@
 //! the session has todos, since those indicate task-style work), the user gets
 //! a compact desktop notification: session name + duration in the title, todo
 //! progress and a short snippet of the final assistant text in the body. By
-//! default it fires only while the terminal window is unfocused.
+//! default it fires only while the terminal window is unfocused. It goes to
+//! the desktop unless `[notifications.routes]` sends `turn_complete` elsewhere.
 
 use super::App;
 use crate::todo::TodoItem;
@@ -64,13 +65,14 @@ impl App {
             self.last_assistant_text_for_notification().as_deref(),
         );
         let sound = cfg.turn_complete_sound.trim();
-        let sound = (!sound.is_empty()).then_some(sound);
-        crate::notifications::send_desktop_notification_rich(
-            &notification.title,
-            notification.subtitle.as_deref(),
-            &notification.body,
-            sound,
+        let mut message = crate::notifications::Notification::new(
+            crate::notifications::NotifyEvent::TurnComplete,
+            notification.title,
+            notification.body,
         );
+        message.subtitle = notification.subtitle;
+        message.sound = (!sound.is_empty()).then(|| sound.to_string());
+        crate::notifications::NotificationDispatcher::new().dispatch(message);
     }
 
     fn runtime_mode_allows_turn_notifications(&self) -> bool {
@@ -0,0 +1,108 @@
+//! `/tee` and the `[output] tee_file` log of finished turns.
+//!
+//! At the end of each turn the final assistant message is appended to the tee
+//! file under a timestamp and session header (see `crate::output_tee`). The
+//! write runs off the UI thread, and a failure only produces a warning.
+
+use super::{App, DisplayMessage};
+use std::path::PathBuf;
+
+const USAGE: &str = "Usage: /tee [<path>|off|status]";
+
+impl App {
+    /// Append this turn's final assistant message to the tee file, if one is
+    /// set. Call at turn completion, after the final message is committed.
+    pub(super) fn tee_turn_output(&mut self) {
+        if self.is_replay {
+            return;
+        }
+        let Some(path) = self.tee_file.clone() else {
+            return;
+        };
+        let Some(text) = self.last_turn_assistant_text() else {
+            return;
+        };
+        let session_id = self
+            .active_client_session_id()
+            .unwrap_or("unknown")
+            .to_string();
+        let session_name = crate::id::extract_session_name(&session_id).map(str::to_string);
+        tokio::task::spawn_blocking(move || {
+            if let Err(error) = crate::output_tee::append_response(
+                &path,
+                &session_id,
+                session_name.as_deref(),
+                &text,
+            ) {
+                crate::logging::warn(&format!(
+                    "[tee] failed to write {}: {:#}",
+                    path.display(),
+                    error
+                ));
+            }
+        });
+    }
+
+    /// The last assistant message after the latest user message, so a turn
+    /// that produced no answer never re-tees the previous one.
+    fn last_turn_assistant_text(&self) -> Option<String> {
+        for message in self.display_messages.iter().rev() {
+            match message.role.as_str() {
+                "user" => return None,
+                "assistant" if !message.content.trim().is_empty() => {
+                    return Some(message.content.clone());
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+}
+
+/// `/tee <path|off|status>`: change the tee file for this session.
+pub(super) fn handle_tee_command(app: &mut App, trimmed: &str) -> bool {
+    if trimmed != "/tee" && !trimmed.starts_with("/tee ") {
+        return false;
+    }
+    let arg = trimmed.strip_prefix("/tee").unwrap_or_default().trim();
+    match arg {
+        "" | "status" => {
+            let message = match &app.tee_file {
+                Some(path) => format!(
+                    "Appending each turn's final response to {}.\nUse /tee off to stop.",
+                    path.display()
+                ),
+                None => {
+                    "Tee is off. Use /tee <path> to append each turn's final response to a file."
+                        .to_string()
+                }
+            };
+            app.push_display_message(DisplayMessage::system(message));
+        }
+        "off" => {
+            app.tee_file = None;
+            app.set_status_notice("Tee: off");
+            app.push_display_message(DisplayMessage::system(
+                "Stopped appending responses for this session.".to_string(),
+            ));
+        }
+        path if path.starts_with('-') => {
+            app.push_display_message(DisplayMessage::error(USAGE.to_string()));
+        }
+        path => {
+            let path = resolve_tee_path(path);
+            app.set_status_notice("Tee: on");
+            app.push_display_message(DisplayMessage::system(format!(
+                "Appending each turn's final response to {} for this session.",
+                path.display()
+            )));
+            app.tee_file = Some(path);
+        }
+    }
+    true
+}
+
+fn resolve_tee_path(raw: &str) -> PathBuf {
+    let path = crate::output_tee::expand_home(raw);
+    std::path::absolute(&path).unwrap_or(path)
+}
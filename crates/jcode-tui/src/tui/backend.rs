@@ -6,7 +6,9 @@
 //! Also provides debug socket events for exposing full TUI state.
 
 use crate::message::ToolCall;
-use crate::protocol::{AuthChanged, FeatureToggle, Request, ServerEvent};
+use crate::protocol::{
+    AsideAction, AuthChanged, CompareAction, FeatureToggle, HandoffAction, Request, ServerEvent,
+};
 use crate::server;
 use crate::transport::{Stream, WriteHalf};
 use crate::tui::remote_diff::RemoteDiffTracker;
@@ -340,6 +342,16 @@ impl RemoteConnection {
         } else {
             bootstrap_request = "subscribe_resume";
         }
+        let startup_roots = crate::session::startup_workspace_roots();
+        if !startup_roots.is_empty() {
+            conn.send_request(Request::UpdateWorkspaceRoots {
+                id: conn.next_request_id,
+                add: startup_roots,
+                remove: Vec::new(),
+            })
+            .await?;
+            conn.next_request_id += 1;
+        }
         // Avoid a reconnect/reload thundering herd: every headed client used to
         // request the full expanded model catalog immediately after attach. On
         // large OpenRouter catalogs this is ~800KB per client and can make many
@@ -398,6 +410,7 @@ impl RemoteConnection {
             )),
             Request::CancelSoftInterrupts { id } => Some(base("cancel_soft_interrupts", *id)),
             Request::BackgroundTool { id } => Some(base("background_tool", *id)),
+            Request::CancelMcpCall { id } => Some(base("cancel_mcp_call", *id)),
             _ => None,
         }
     }
@@ -518,6 +531,23 @@ impl RemoteConnection {
         content: String,
         images: Vec<(String, String)>,
         system_reminder: Option<String>,
+    ) -> Result<u64> {
+        self.send_message_request(content, images, system_reminder, None)
+            .await
+    }
+
+    /// Send a `/focus` task with its wall-clock budget and return the request ID
+    pub async fn send_focus_message(&mut self, content: String, budget_secs: u64) -> Result<u64> {
+        self.send_message_request(content, vec![], None, Some(budget_secs))
+            .await
+    }
+
+    async fn send_message_request(
+        &mut self,
+        content: String,
+        images: Vec<(String, String)>,
+        system_reminder: Option<String>,
+        focus_secs: Option<u64>,
     ) -> Result<u64> {
         // Output token usage snapshots are cumulative within a single API call.
         // Reset per-call watermark before sending the next user request.
@@ -529,6 +559,7 @@ impl RemoteConnection {
             content,
             images,
             system_reminder,
+            focus_secs,
         };
         self.next_request_id += 1;
         self.send_request(request).await?;
@@ -673,6 +704,26 @@ impl RemoteConnection {
         self.send_request(request).await
     }
 
+    /// Select or clear the session's `[profiles.<name>]` preset on the server.
+    pub async fn set_profile(&mut self, profile: Option<String>) -> Result<()> {
+        let request = Request::SetProfile {
+            id: self.next_request_id,
+            profile,
+        };
+        self.next_request_id += 1;
+        self.send_request(request).await
+    }
+
+    /// Turn the session's `jcode --safe` defaults on or off on the server.
+    pub async fn set_safe_mode(&mut self, enabled: bool) -> Result<()> {
+        let request = Request::SetSafeMode {
+            id: self.next_request_id,
+            enabled,
+        };
+        self.next_request_id += 1;
+        self.send_request(request).await
+    }
+
     /// Launch a subagent immediately on the active remote session.
     pub async fn run_subagent(
         &mut self,
@@ -744,6 +795,71 @@ impl RemoteConnection {
         self.send_request(request).await
     }
 
+    /// Record the user's decision on the plan proposed in plan mode
+    pub async fn plan_decision(
+        &mut self,
+        decision: crate::plan::proposal::PlanDecision,
+        feedback: Option<String>,
+    ) -> Result<()> {
+        let request = Request::PlanDecision {
+            id: self.next_request_id,
+            decision,
+            feedback,
+        };
+        self.next_request_id += 1;
+        self.send_request(request).await
+    }
+
+    /// Start, summarize, or close the session's `/aside` scratch thread.
+    pub async fn aside(&mut self, action: AsideAction) -> Result<()> {
+        let request = Request::Aside {
+            id: self.next_request_id,
+            action,
+        };
+        self.next_request_id += 1;
+        self.send_request(request).await
+    }
+
+    /// Run a `/compare`, or keep one of its answers.
+    pub async fn compare(&mut self, action: CompareAction) -> Result<()> {
+        let request = Request::Compare {
+            id: self.next_request_id,
+            action,
+        };
+        self.next_request_id += 1;
+        self.send_request(request).await
+    }
+
+    /// Draft a `/handoff` summary, or create the child session from one.
+    pub async fn handoff(&mut self, action: HandoffAction) -> Result<()> {
+        let request = Request::Handoff {
+            id: self.next_request_id,
+            action,
+        };
+        self.next_request_id += 1;
+        self.send_request(request).await
+    }
+
+    /// Fetch the environment section of the system prompt, re-probing it
+    /// first when `refresh` is set.
+    pub async fn environment(&mut self, refresh: bool) -> Result<()> {
+        let request = Request::Environment {
+            id: self.next_request_id,
+            refresh,
+        };
+        self.next_request_id += 1;
+        self.send_request(request).await
+    }
+
+    /// Fetch the `/prompt sections` report for this session.
+    pub async fn prompt_sections(&mut self) -> Result<()> {
+        let request = Request::PromptSections {
+            id: self.next_request_id,
+        };
+        self.next_request_id += 1;
+        self.send_request(request).await
+    }
+
     /// Set compaction mode on the server for this session.
     pub async fn set_compaction_mode(&mut self, mode: crate::config::CompactionMode) -> Result<()> {
         let request = Request::SetCompactionMode {
@@ -754,6 +870,53 @@ impl RemoteConnection {
         self.send_request(request).await
     }
 
+    /// Attach or detach extra workspace roots for this session on the server.
+    pub async fn update_workspace_roots(
+        &mut self,
+        add: Vec<String>,
+        remove: Vec<String>,
+    ) -> Result<()> {
+        let request = Request::UpdateWorkspaceRoots {
+            id: self.next_request_id,
+            add,
+            remove,
+        };
+        self.next_request_id += 1;
+        self.send_request(request).await
+    }
+
+    /// Scope the session's tools to a subdirectory on the server, or back to
+    /// the working directory when `path` is `None`.
+    pub async fn set_tool_scope(&mut self, path: Option<String>) -> Result<()> {
+        let request = Request::SetToolScope {
+            id: self.next_request_id,
+            path,
+        };
+        self.next_request_id += 1;
+        self.send_request(request).await
+    }
+
+    /// Replace the standing `/instructions` on the server's copy of the
+    /// session, or clear them when `text` is `None`.
+    pub async fn set_instructions(&mut self, text: Option<String>) -> Result<()> {
+        let request = Request::SetInstructions {
+            id: self.next_request_id,
+            text,
+        };
+        self.next_request_id += 1;
+        self.send_request(request).await
+    }
+
+    /// Record the `/mirror` file in the server's copy of the session.
+    pub async fn set_mirror(&mut self, path: Option<String>) -> Result<()> {
+        let request = Request::SetMirror {
+            id: self.next_request_id,
+            path,
+        };
+        self.next_request_id += 1;
+        self.send_request(request).await
+    }
+
     /// Set or clear the custom session display title on the server.
     pub async fn rename_session(&mut self, title: Option<String>) -> Result<()> {
         let request = Request::RenameSession {
@@ -800,6 +963,22 @@ impl RemoteConnection {
         self.send_request(request).await
     }
 
+    /// Answer an entry in the shared permission inbox.
+    pub async fn send_permission_decision(
+        &mut self,
+        request_id: &str,
+        approved: bool,
+    ) -> Result<()> {
+        let request = Request::PermissionDecision {
+            id: self.next_request_id,
+            request_id: request_id.to_string(),
+            approved,
+            message: None,
+        };
+        self.next_request_id += 1;
+        self.send_request(request).await
+    }
+
     /// Cancel the current generation on the server
     pub async fn cancel(&mut self) -> Result<()> {
         self.cancel_with_reason("remote.cancel").await
@@ -825,6 +1004,16 @@ impl RemoteConnection {
             .await
     }
 
+    /// Cancel the MCP tool call currently running
+    pub async fn cancel_mcp_call(&mut self) -> Result<()> {
+        let request = Request::CancelMcpCall {
+            id: self.next_request_id,
+        };
+        self.next_request_id += 1;
+        self.send_request_with_interrupt_trigger(request, Some("cancel_mcp_call"))
+            .await
+    }
+
     /// Queue a soft interrupt message to be injected at the next safe point
     /// This doesn't cancel anything - the message is naturally incorporated
     pub async fn soft_interrupt(&mut self, content: String, urgent: bool) -> Result<u64> {
@@ -849,6 +1038,36 @@ impl RemoteConnection {
             .await
     }
 
+    /// Ask for the session's turn queue (answered with `ServerEvent::TurnQueue`)
+    pub async fn turn_queue(&mut self) -> Result<()> {
+        let request = Request::TurnQueue {
+            id: self.next_request_id,
+        };
+        self.next_request_id += 1;
+        self.send_request(request).await
+    }
+
+    /// Drop a pending turn queue item before it runs
+    pub async fn turn_queue_remove(&mut self, item_id: &str) -> Result<()> {
+        let request = Request::TurnQueueRemove {
+            id: self.next_request_id,
+            item_id: item_id.to_string(),
+        };
+        self.next_request_id += 1;
+        self.send_request(request).await
+    }
+
+    /// Move a queued message or soft interrupt to a 1-based position
+    pub async fn turn_queue_move(&mut self, item_id: &str, position: usize) -> Result<()> {
+        let request = Request::TurnQueueMove {
+            id: self.next_request_id,
+            item_id: item_id.to_string(),
+            position,
+        };
+        self.next_request_id += 1;
+        self.send_request(request).await
+    }
+
     /// Split the current session - ask server to clone conversation into a new session
     pub async fn split(&mut self) -> Result<u64> {
         let id = self.next_request_id;
@@ -0,0 +1,1099 @@
+//! Keyboard-driven workspace file picker.
+//!
+//! Fuzzy-filters the cached [`crate::file_index`] of the working directory,
+//! previews the focused file or directory on the right, and hands the marked
+//! paths back either as attachment blocks (stored like large pastes, so the
+//! composer only shows a short placeholder) or as `@path` mentions. The index
+//! is built off the UI thread and only the visible rows and one bounded
+//! preview are ever rendered, so the picker stays responsive on checkouts that
+//! hit [`crate::file_index::MAX_INDEX_ENTRIES`].
+
+use crate::file_index::{FileIndex, IndexEntry};
+use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph},
+};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::mpsc::{Receiver, TryRecvError};
+
+const PANEL_BG: Color = Color::Rgb(24, 28, 40);
+const PANEL_BORDER: Color = Color::Rgb(90, 95, 110);
+const SECTION_BORDER: Color = Color::Rgb(70, 78, 94);
+const SELECTED_BG: Color = Color::Rgb(38, 42, 56);
+const MARKED: Color = Color::Rgb(111, 214, 181);
+const DIR_COLOR: Color = Color::Rgb(130, 170, 255);
+const MUTED: Color = Color::Rgb(140, 146, 163);
+const MUTED_DARK: Color = Color::Rgb(100, 106, 122);
+const OVERLAY_PERCENT_X: u16 = 92;
+const OVERLAY_PERCENT_Y: u16 = 80;
+
+/// Bytes read from a file for its preview.
+const PREVIEW_MAX_BYTES: u64 = 64 * 1024;
+/// Lines shown in a preview.
+const PREVIEW_MAX_LINES: usize = 200;
+/// Bytes of a single attached file; the rest is cut with a note.
+const ATTACH_FILE_MAX_BYTES: u64 = 200 * 1024;
+/// Total bytes of file contents attached for one directory.
+const DIR_ATTACH_BUDGET_BYTES: u64 = 256 * 1024;
+/// Entries listed in a directory tree summary.
+const TREE_SUMMARY_MAX_ENTRIES: usize = 500;
+/// Leading bytes checked for NUL when deciding whether a file is binary.
+const BINARY_SNIFF_BYTES: usize = 8000;
+
+/// What confirming a selection does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FilePickerMode {
+    /// Attach file contents (or a directory summary) as context blocks.
+    #[default]
+    Attach,
+    /// Insert `@path` mentions into the composer.
+    Mention,
+}
+
+impl FilePickerMode {
+    fn toggled(self) -> Self {
+        match self {
+            Self::Attach => Self::Mention,
+            Self::Mention => Self::Attach,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Attach => "attach as context",
+            Self::Mention => "insert @mentions",
+        }
+    }
+}
+
+/// How a selected directory is attached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirAttach {
+    /// An indented listing of the entries under the directory.
+    TreeSummary,
+    /// Contents of the text files under the directory, in index order, until
+    /// [`DIR_ATTACH_BUDGET_BYTES`] is used up.
+    FilesUnderBudget,
+}
+
+impl DirAttach {
+    const ALL: [DirAttach; 2] = [DirAttach::TreeSummary, DirAttach::FilesUnderBudget];
+
+    fn label(self) -> String {
+        match self {
+            Self::TreeSummary => "Attach tree summary".to_string(),
+            Self::FilesUnderBudget => format!(
+                "Attach all files under budget ({})",
+                format_size(DIR_ATTACH_BUDGET_BYTES)
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    Browse,
+    /// Asking how to attach the directories in the selection.
+    DirChoice {
+        selected: usize,
+    },
+}
+
+pub enum OverlayAction {
+    Continue,
+    Close,
+    /// Attachment blocks to store and reference from the composer.
+    Attach(Vec<String>),
+    /// Paths, relative to the picker root, to insert as mentions.
+    Mention(Vec<String>),
+}
+
+pub struct FilePicker {
+    root: PathBuf,
+    index: Option<Arc<FileIndex>>,
+    index_rx: Option<Receiver<Arc<FileIndex>>>,
+    filter: String,
+    /// Index entries matching the filter, best first.
+    matches: Vec<usize>,
+    selected: usize,
+    /// Marked index entries, in the order they were marked.
+    marked: Vec<usize>,
+    mode: FilePickerMode,
+    stage: Stage,
+    preview: Option<(usize, Vec<Line<'static>>)>,
+    last_list_area: Option<Rect>,
+}
+
+impl FilePicker {
+    /// A picker over `root` whose index arrives on `index_rx` once the walk
+    /// finishes.
+    pub fn new(root: PathBuf, mode: FilePickerMode, index_rx: Receiver<Arc<FileIndex>>) -> Self {
+        Self {
+            root,
+            index: None,
+            index_rx: Some(index_rx),
+            filter: String::new(),
+            matches: Vec::new(),
+            selected: 0,
+            marked: Vec::new(),
+            mode,
+            stage: Stage::Browse,
+            preview: None,
+            last_list_area: None,
+        }
+    }
+
+    pub fn mode(&self) -> FilePickerMode {
+        self.mode
+    }
+
+    /// Pick up the index once the background walk delivers it. Returns whether
+    /// anything changed.
+    pub fn poll_index(&mut self) -> bool {
+        let Some(rx) = self.index_rx.as_ref() else {
+            return false;
+        };
+        match rx.try_recv() {
+            Ok(index) => {
+                self.index = Some(index);
+                self.index_rx = None;
+                self.apply_filter();
+                true
+            }
+            Err(TryRecvError::Empty) => false,
+            Err(TryRecvError::Disconnected) => {
+                self.index_rx = None;
+                true
+            }
+        }
+    }
+
+    fn entry(&self, idx: usize) -> Option<&IndexEntry> {
+        self.index.as_ref()?.entries.get(idx)
+    }
+
+    fn selected_entry_index(&self) -> Option<usize> {
+        self.matches.get(self.selected).copied()
+    }
+
+    fn apply_filter(&mut self) {
+        let Some(index) = self.index.as_ref() else {
+            return;
+        };
+        let query = self.filter.trim().to_lowercase();
+        if query.is_empty() {
+            self.matches = (0..index.entries.len()).collect();
+        } else {
+            let mut scored: Vec<(u32, usize)> = index
+                .entries
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, entry)| match_score(entry, &query).map(|score| (score, idx)))
+                .collect();
+            scored.sort_by(|a, b| {
+                b.0.cmp(&a.0)
+                    .then_with(|| {
+                        index.entries[a.1]
+                            .path
+                            .len()
+                            .cmp(&index.entries[b.1].path.len())
+                    })
+                    .then_with(|| a.1.cmp(&b.1))
+            });
+            self.matches = scored.into_iter().map(|(_, idx)| idx).collect();
+        }
+        self.selected = 0;
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let max = self.matches.len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(delta).min(max);
+    }
+
+    fn toggle_mark(&mut self) {
+        let Some(idx) = self.selected_entry_index() else {
+            return;
+        };
+        if let Some(pos) = self.marked.iter().position(|marked| *marked == idx) {
+            self.marked.remove(pos);
+        } else {
+            self.marked.push(idx);
+        }
+        self.move_selection(1);
+    }
+
+    /// Marked entries, or the focused one when nothing is marked.
+    fn selection(&self) -> Vec<usize> {
+        if self.marked.is_empty() {
+            self.selected_entry_index().into_iter().collect()
+        } else {
+            self.marked.clone()
+        }
+    }
+
+    fn confirm(&mut self, dir_attach: Option<DirAttach>) -> OverlayAction {
+        let selection = self.selection();
+        let Some(index) = self.index.clone() else {
+            return OverlayAction::Continue;
+        };
+        if selection.is_empty() {
+            return OverlayAction::Continue;
+        }
+        match self.mode {
+            FilePickerMode::Mention => OverlayAction::Mention(
+                selection
+                    .iter()
+                    .filter_map(|idx| index.entries.get(*idx))
+                    .map(|entry| entry.path.clone())
+                    .collect(),
+            ),
+            FilePickerMode::Attach => {
+                let has_dir = selection
+                    .iter()
+                    .any(|idx| index.entries.get(*idx).is_some_and(|entry| entry.is_dir));
+                match dir_attach {
+                    None if has_dir => {
+                        self.stage = Stage::DirChoice { selected: 0 };
+                        OverlayAction::Continue
+                    }
+                    _ => OverlayAction::Attach(attachment_blocks(
+                        &index,
+                        &selection,
+                        dir_attach.unwrap_or(DirAttach::TreeSummary),
+                    )),
+                }
+            }
+        }
+    }
+
+    pub fn handle_overlay_key(
+        &mut self,
+        code: KeyCode,
+        modifiers: KeyModifiers,
+    ) -> anyhow::Result<OverlayAction> {
+        let ctrl = modifiers.contains(KeyModifiers::CONTROL);
+        if ctrl && code == KeyCode::Char('c') {
+            return Ok(OverlayAction::Close);
+        }
+
+        if let Stage::DirChoice { selected } = self.stage {
+            let choice = match code {
+                KeyCode::Esc => {
+                    self.stage = Stage::Browse;
+                    None
+                }
+                KeyCode::Up | KeyCode::Down | KeyCode::Char('j') | KeyCode::Char('k') => {
+                    self.stage = Stage::DirChoice {
+                        selected: (selected + 1) % DirAttach::ALL.len(),
+                    };
+                    None
+                }
+                KeyCode::Char('t') => Some(DirAttach::TreeSummary),
+                KeyCode::Char('a') => Some(DirAttach::FilesUnderBudget),
+                KeyCode::Enter => Some(DirAttach::ALL[selected]),
+                _ => None,
+            };
+            if let Some(choice) = choice {
+                self.stage = Stage::Browse;
+                return Ok(self.confirm(Some(choice)));
+            }
+            return Ok(OverlayAction::Continue);
+        }
+
+        match code {
+            KeyCode::Esc => {
+                if !self.filter.is_empty() {
+                    self.filter.clear();
+                    self.apply_filter();
+                    return Ok(OverlayAction::Continue);
+                }
+                return Ok(OverlayAction::Close);
+            }
+            KeyCode::Enter => return Ok(self.confirm(None)),
+            KeyCode::Up => self.move_selection(-1),
+            KeyCode::Down => self.move_selection(1),
+            KeyCode::Char('p') if ctrl => self.move_selection(-1),
+            KeyCode::Char('n') if ctrl => self.move_selection(1),
+            KeyCode::PageUp => self.move_selection(-10),
+            KeyCode::PageDown => self.move_selection(10),
+            KeyCode::Home => self.selected = 0,
+            KeyCode::End => self.selected = self.matches.len().saturating_sub(1),
+            KeyCode::Tab | KeyCode::BackTab => self.mode = self.mode.toggled(),
+            KeyCode::Char(' ') => self.toggle_mark(),
+            KeyCode::Backspace => {
+                if self.filter.pop().is_some() {
+                    self.apply_filter();
+                }
+            }
+            KeyCode::Char(c) if !ctrl && !modifiers.contains(KeyModifiers::ALT) => {
+                self.filter.push(c);
+                self.apply_filter();
+            }
+            _ => {}
+        }
+        Ok(OverlayAction::Continue)
+    }
+
+    pub fn handle_overlay_mouse(&mut self, mouse: MouseEvent) {
+        let Some(list) = self.last_list_area else {
+            return;
+        };
+        let inside_list = mouse.column >= list.x
+            && mouse.column < list.x.saturating_add(list.width)
+            && mouse.row >= list.y
+            && mouse.row < list.y.saturating_add(list.height);
+        if !inside_list || self.stage != Stage::Browse {
+            return;
+        }
+        match mouse.kind {
+            MouseEventKind::ScrollUp => self.move_selection(-3),
+            MouseEventKind::ScrollDown => self.move_selection(3),
+            MouseEventKind::Down(MouseButton::Left) => {
+                let start = self.visible_window_start(list.height as usize);
+                let row = start + mouse.row.saturating_sub(list.y) as usize;
+                if row < self.matches.len() {
+                    self.selected = row;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn visible_window_start(&self, height: usize) -> usize {
+        let height = height.max(1);
+        self.selected
+            .saturating_sub(height.saturating_sub(1).min(height / 2))
+    }
+
+    pub fn render(&mut self, frame: &mut Frame) {
+        let area = centered_rect(OVERLAY_PERCENT_X, OVERLAY_PERCENT_Y, frame.area());
+        let root_name = self
+            .root
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.root.display().to_string());
+
+        let block = Block::default()
+            .title(format!(" Files · {} ", root_name))
+            .title_bottom(Line::from(vec![
+                hotkey(" Enter "),
+                Span::styled(" confirm  ", Style::default().fg(MUTED_DARK)),
+                hotkey(" Space "),
+                Span::styled(" mark  ", Style::default().fg(MUTED_DARK)),
+                hotkey(" Tab "),
+                Span::styled(" attach / mention  ", Style::default().fg(MUTED_DARK)),
+                hotkey(" type "),
+                Span::styled(" filter  ", Style::default().fg(MUTED_DARK)),
+                hotkey(" Esc "),
+                Span::styled(" clear / close ", Style::default().fg(MUTED_DARK)),
+            ]))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(PANEL_BORDER));
+        frame.render_widget(block, area);
+
+        let inner = Rect {
+            x: area.x + 1,
+            y: area.y + 1,
+            width: area.width.saturating_sub(2),
+            height: area.height.saturating_sub(2),
+        };
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(3)])
+            .split(inner);
+        self.render_header(frame, rows[0]);
+
+        let body = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+            .split(rows[1]);
+        self.render_list(frame, body[0]);
+        match self.stage {
+            Stage::Browse => self.render_preview(frame, body[1]),
+            Stage::DirChoice { selected } => self.render_dir_choice(frame, body[1], selected),
+        }
+    }
+
+    fn render_header(&self, frame: &mut Frame, area: Rect) {
+        let mut spans = vec![Span::styled("Filter ", Style::default().fg(MUTED_DARK))];
+        if self.filter.is_empty() {
+            spans.push(Span::styled(
+                "type part of a path",
+                Style::default().fg(Color::Gray).italic(),
+            ));
+        } else {
+            spans.push(Span::styled(
+                self.filter.clone(),
+                Style::default().fg(Color::White),
+            ));
+        }
+        let status = match self.index.as_ref() {
+            None => "  ·  indexing…".to_string(),
+            Some(index) => format!(
+                "  ·  {} of {}{}",
+                self.matches.len(),
+                index.entries.len(),
+                if index.truncated { "+" } else { "" }
+            ),
+        };
+        spans.push(Span::styled(status, Style::default().fg(MUTED_DARK)));
+        if !self.marked.is_empty() {
+            spans.push(Span::styled(
+                format!("  ·  {} marked", self.marked.len()),
+                Style::default().fg(MARKED),
+            ));
+        }
+        spans.push(Span::styled(
+            format!("  ·  Enter will {}", self.mode.label()),
+            Style::default().fg(MUTED),
+        ));
+        frame.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+
+    fn render_list(&mut self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .style(Style::default().bg(PANEL_BG))
+            .border_style(Style::default().fg(SECTION_BORDER));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        self.last_list_area = Some(inner);
+
+        let Some(index) = self.index.as_ref() else {
+            let text = if self.index_rx.is_some() {
+                format!("Indexing {}…", self.root.display())
+            } else {
+                "Could not index the working directory.".to_string()
+            };
+            frame.render_widget(
+                Paragraph::new(Span::styled(text, Style::default().fg(MUTED))),
+                inner,
+            );
+            return;
+        };
+        if self.matches.is_empty() {
+            frame.render_widget(
+                Paragraph::new(Span::styled(
+                    "No matching paths",
+                    Style::default().fg(MUTED),
+                )),
+                inner,
+            );
+            return;
+        }
+
+        let height = inner.height as usize;
+        let start = self.visible_window_start(height);
+        let lines: Vec<Line> = self
+            .matches
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(height)
+            .filter_map(|(row, idx)| {
+                let entry = index.entries.get(*idx)?;
+                let marked = self.marked.contains(idx);
+                let mut spans = vec![
+                    Span::styled(
+                        if marked { "● " } else { "  " },
+                        Style::default().fg(MARKED),
+                    ),
+                    Span::styled(
+                        if entry.is_dir {
+                            format!("{}/", entry.path)
+                        } else {
+                            entry.path.clone()
+                        },
+                        if entry.is_dir {
+                            Style::default().fg(DIR_COLOR)
+                        } else {
+                            Style::default().fg(Color::White)
+                        },
+                    ),
+                ];
+                if !entry.is_dir {
+                    spans.push(Span::styled(
+                        format!("  {}", format_size(entry.size)),
+                        Style::default().fg(MUTED_DARK),
+                    ));
+                }
+                let line = Line::from(spans);
+                Some(if row == self.selected {
+                    line.style(Style::default().bg(SELECTED_BG).bold())
+                } else {
+                    line
+                })
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+
+    fn render_preview(&mut self, frame: &mut Frame, area: Rect) {
+        let focused = self.selected_entry_index();
+        let title = focused
+            .and_then(|idx| self.entry(idx))
+            .map(|entry| format!(" {} ", entry.path))
+            .unwrap_or_default();
+        let block = Block::default()
+            .title(Span::styled(
+                title,
+                Style::default().fg(Color::White).bold(),
+            ))
+            .borders(Borders::ALL)
+            .style(Style::default().bg(PANEL_BG))
+            .border_style(Style::default().fg(SECTION_BORDER));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let Some(idx) = focused else {
+            return;
+        };
+        if self.preview.as_ref().map(|(cached, _)| *cached) != Some(idx) {
+            let lines = self
+                .index
+                .as_ref()
+                .map(|index| preview_lines(index, idx))
+                .unwrap_or_default();
+            self.preview = Some((idx, lines));
+        }
+        if let Some((_, lines)) = self.preview.as_ref() {
+            frame.render_widget(Paragraph::new(lines.clone()), inner);
+        }
+    }
+
+    fn render_dir_choice(&self, frame: &mut Frame, area: Rect, selected: usize) {
+        let block = Block::default()
+            .title(Span::styled(
+                " Attach directory ",
+                Style::default().fg(Color::White).bold(),
+            ))
+            .borders(Borders::ALL)
+            .style(Style::default().bg(PANEL_BG))
+            .border_style(Style::default().fg(SECTION_BORDER));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let mut lines = vec![
+            Line::from(Span::styled(
+                "How should selected directories be attached?",
+                Style::default().fg(MUTED),
+            )),
+            Line::from(""),
+        ];
+        for (idx, choice) in DirAttach::ALL.iter().enumerate() {
+            let key = match choice {
+                DirAttach::TreeSummary => " t ",
+                DirAttach::FilesUnderBudget => " a ",
+            };
+            let line = Line::from(vec![
+                hotkey(key),
+                Span::raw(" "),
+                Span::styled(choice.label(), Style::default().fg(Color::White)),
+            ]);
+            lines.push(if idx == selected {
+                line.style(Style::default().bg(SELECTED_BG).bold())
+            } else {
+                line
+            });
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "Enter confirms · Esc goes back",
+            Style::default().fg(MUTED_DARK),
+        )));
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+}
+
+/// Fuzzy score of `entry` for a lowercase `query`: every query character must
+/// appear in order in the path. Matches at word boundaries, runs of
+/// consecutive characters and matches inside the file name score higher.
+fn match_score(entry: &IndexEntry, query: &str) -> Option<u32> {
+    let path = entry.path.as_str();
+    let name_start = path.rfind('/').map_or(0, |idx| idx + 1);
+    let mut wanted = query.chars().peekable();
+    let mut score = 0u32;
+    let mut prev: Option<char> = None;
+    let mut prev_matched = false;
+    for (idx, ch) in path.char_indices() {
+        let Some(&want) = wanted.peek() else {
+            break;
+        };
+        let matched = ch.to_lowercase().eq(std::iter::once(want));
+        if matched {
+            score += 1;
+            let boundary = match prev {
+                None => true,
+                Some(p) => {
+                    matches!(p, '/' | '_' | '-' | '.' | ' ')
+                        || (p.is_lowercase() && ch.is_uppercase())
+                }
+            };
+            if boundary {
+                score += 8;
+            }
+            if prev_matched {
+                score += 5;
+            }
+            if idx >= name_start {
+                score += 2;
+            }
+            wanted.next();
+        }
+        prev_matched = matched;
+        prev = Some(ch);
+    }
+    if wanted.peek().is_some() {
+        return None;
+    }
+    let name = entry.name().to_lowercase();
+    if name.starts_with(query) {
+        score += 20;
+    } else if name.contains(query) {
+        score += 10;
+    }
+    Some(score)
+}
+
+/// Placeholder shown in the composer for an attachment block built by the
+/// picker, or `None` when `content` is not one.
+pub fn attachment_placeholder(content: &str) -> Option<String> {
+    let first = content.lines().next()?;
+    if first.starts_with("<file ") {
+        return Some(format!("[file {}]", tag_attr(first, "path")?));
+    }
+    if first.starts_with("<tree ") {
+        return Some(format!("[tree {}]", tag_attr(first, "path")?));
+    }
+    if first.starts_with("<files ") {
+        return Some(format!(
+            "[{} files under {}]",
+            tag_attr(first, "count")?,
+            tag_attr(first, "under")?
+        ));
+    }
+    None
+}
+
+fn tag_attr<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let start = tag.find(&format!(" {}=\"", name))? + name.len() + 3;
+    let len = tag[start..].find('"')?;
+    Some(&tag[start..start + len])
+}
+
+/// One attachment block per selected entry. Directories are attached as
+/// `dir_attach` says.
+pub fn attachment_blocks(
+    index: &FileIndex,
+    selection: &[usize],
+    dir_attach: DirAttach,
+) -> Vec<String> {
+    selection
+        .iter()
+        .filter_map(|idx| {
+            let entry = index.entries.get(*idx)?;
+            Some(if entry.is_dir {
+                match dir_attach {
+                    DirAttach::TreeSummary => tree_summary_block(index, *idx),
+                    DirAttach::FilesUnderBudget => files_under_budget_block(index, *idx),
+                }
+            } else {
+                file_block(&index.root, entry, ATTACH_FILE_MAX_BYTES)
+            })
+        })
+        .collect()
+}
+
+fn file_block(root: &Path, entry: &IndexEntry, max_bytes: u64) -> String {
+    match read_text(&root.join(&entry.path), max_bytes) {
+        Ok(Some((text, total))) if total > max_bytes => format!(
+            "<file path=\"{}\" truncated=\"true\">\n{}\n… (first {} of {})\n</file>",
+            entry.path,
+            text.trim_end_matches('\n'),
+            format_size(max_bytes),
+            format_size(total)
+        ),
+        Ok(Some((text, _))) => format!(
+            "<file path=\"{}\">\n{}\n</file>",
+            entry.path,
+            text.trim_end_matches('\n')
+        ),
+        Ok(None) => format!(
+            "<file path=\"{}\">\n(binary file, {}, not included)\n</file>",
+            entry.path,
+            format_size(entry.size)
+        ),
+        Err(err) => format!(
+            "<file path=\"{}\">\n(could not read: {})\n</file>",
+            entry.path, err
+        ),
+    }
+}
+
+/// Entries of the directory at `dir_idx`: the contiguous run after it in the
+/// depth-first index.
+fn subtree(index: &FileIndex, dir_idx: usize) -> &[IndexEntry] {
+    let Some(dir) = index.entries.get(dir_idx) else {
+        return &[];
+    };
+    let prefix = format!("{}/", dir.path);
+    let rest = &index.entries[dir_idx + 1..];
+    let len = rest
+        .iter()
+        .position(|entry| !entry.path.starts_with(&prefix))
+        .unwrap_or(rest.len());
+    &rest[..len]
+}
+
+fn tree_summary_block(index: &FileIndex, dir_idx: usize) -> String {
+    let dir = &index.entries[dir_idx];
+    let entries = subtree(index, dir_idx);
+    let base_depth = dir.path.matches('/').count() + 1;
+    let mut out = format!(
+        "<tree path=\"{}/\" entries=\"{}\">\n",
+        dir.path,
+        entries.len()
+    );
+    for entry in entries.iter().take(TREE_SUMMARY_MAX_ENTRIES) {
+        let depth = entry.path.matches('/').count() - base_depth;
+        out.push_str(&"  ".repeat(depth));
+        if entry.is_dir {
+            out.push_str(&format!("{}/\n", entry.name()));
+        } else {
+            out.push_str(&format!("{} ({})\n", entry.name(), format_size(entry.size)));
+        }
+    }
+    if entries.len() > TREE_SUMMARY_MAX_ENTRIES {
+        out.push_str(&format!(
+            "… {} more entries\n",
+            entries.len() - TREE_SUMMARY_MAX_ENTRIES
+        ));
+    }
+    out.push_str("</tree>");
+    out
+}
+
+fn files_under_budget_block(index: &FileIndex, dir_idx: usize) -> String {
+    let dir = &index.entries[dir_idx];
+    let mut remaining = DIR_ATTACH_BUDGET_BYTES;
+    let mut included = Vec::new();
+    let mut omitted = Vec::new();
+    for entry in subtree(index, dir_idx).iter().filter(|entry| !entry.is_dir) {
+        if entry.size > remaining {
+            omitted.push(entry.path.as_str());
+            continue;
+        }
+        match read_text(&index.root.join(&entry.path), remaining) {
+            Ok(Some((text, total))) if total <= remaining => {
+                remaining -= total;
+                included.push(format!(
+                    "<file path=\"{}\">\n{}\n</file>",
+                    entry.path,
+                    text.trim_end_matches('\n')
+                ));
+            }
+            _ => omitted.push(entry.path.as_str()),
+        }
+    }
+
+    let mut out = format!(
+        "<files under=\"{}/\" count=\"{}\">\n",
+        dir.path,
+        included.len()
+    );
+    for block in &included {
+        out.push_str(block);
+        out.push('\n');
+    }
+    if !omitted.is_empty() {
+        const LISTED: usize = 20;
+        out.push_str(&format!(
+            "Omitted {} binary or unreadable files or files over the {} budget: {}{}\n",
+            omitted.len(),
+            format_size(DIR_ATTACH_BUDGET_BYTES),
+            omitted
+                .iter()
+                .take(LISTED)
+                .copied()
+                .collect::<Vec<_>>()
+                .join(", "),
+            if omitted.len() > LISTED { ", …" } else { "" }
+        ));
+    }
+    out.push_str("</files>");
+    out
+}
+
+/// Up to `max_bytes` of `path` as text with the file's full size, or `None`
+/// for binary files.
+fn read_text(path: &Path, max_bytes: u64) -> std::io::Result<Option<(String, u64)>> {
+    let file = std::fs::File::open(path)?;
+    let total = file.metadata()?.len();
+    let mut bytes = Vec::new();
+    file.take(max_bytes).read_to_end(&mut bytes)?;
+    if bytes[..bytes.len().min(BINARY_SNIFF_BYTES)].contains(&0) {
+        return Ok(None);
+    }
+    Ok(Some((String::from_utf8_lossy(&bytes).into_owned(), total)))
+}
+
+fn preview_lines(index: &FileIndex, idx: usize) -> Vec<Line<'static>> {
+    let Some(entry) = index.entries.get(idx) else {
+        return Vec::new();
+    };
+    let muted = |text: String| Line::from(Span::styled(text, Style::default().fg(MUTED)));
+
+    if entry.is_dir {
+        let prefix_len = entry.path.len() + 1;
+        let entries = subtree(index, idx);
+        let children: Vec<&IndexEntry> = entries
+            .iter()
+            .filter(|child| !child.path[prefix_len..].contains('/'))
+            .collect();
+        let mut lines = vec![muted(format!(
+            "{} entries, {} inside",
+            children.len(),
+            entries.len()
+        ))];
+        lines.extend(children.iter().take(PREVIEW_MAX_LINES).map(|child| {
+            if child.is_dir {
+                Line::from(Span::styled(
+                    format!("{}/", child.name()),
+                    Style::default().fg(DIR_COLOR),
+                ))
+            } else {
+                Line::from(vec![
+                    Span::raw(child.name().to_string()),
+                    Span::styled(
+                        format!("  {}", format_size(child.size)),
+                        Style::default().fg(MUTED_DARK),
+                    ),
+                ])
+            }
+        }));
+        return lines;
+    }
+
+    let text = match read_text(&index.root.join(&entry.path), PREVIEW_MAX_BYTES) {
+        Ok(Some((text, _))) => text,
+        Ok(None) => return vec![muted(format!("Binary file, {}", format_size(entry.size)))],
+        Err(err) => return vec![muted(format!("Could not read: {}", err))],
+    };
+    let ext = Path::new(&entry.path)
+        .extension()
+        .and_then(|ext| ext.to_str());
+    let shown: String = text
+        .lines()
+        .take(PREVIEW_MAX_LINES)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let line_count = shown.lines().count();
+    let line_numbers: Vec<usize> = (1..=line_count).collect();
+    let number_width = line_count.to_string().len();
+    crate::tui::markdown::highlight_file_lines(&shown, ext, &line_numbers)
+        .into_iter()
+        .map(|(number, spans)| {
+            let mut line = vec![Span::styled(
+                format!("{:>width$} ", number, width = number_width),
+                Style::default().fg(MUTED_DARK),
+            )];
+            line.extend(spans);
+            Line::from(line)
+        })
+        .collect()
+}
+
+fn format_size(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{} B", bytes)
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MB", bytes as f64 / 1024.0 / 1024.0)
+    }
+}
+
+fn hotkey(text: &'static str) -> Span<'static> {
+    Span::styled(text, Style::default().fg(Color::White).bg(Color::DarkGray))
+}
+
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let popup = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup[1])[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspace() -> (tempfile::TempDir, Arc<FileIndex>) {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        std::fs::create_dir_all(root.join("src/tui")).unwrap();
+        std::fs::write(root.join("src/main.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(root.join("src/tui/file_picker.rs"), "// picker\n").unwrap();
+        std::fs::write(root.join("src/logo.png"), [0u8, 1, 2, 3]).unwrap();
+        std::fs::write(root.join("README.md"), "# readme\n").unwrap();
+        let index = Arc::new(FileIndex::build(root, true));
+        (temp, index)
+    }
+
+    fn entry_index(index: &FileIndex, path: &str) -> usize {
+        index
+            .entries
+            .iter()
+            .position(|entry| entry.path == path)
+            .unwrap()
+    }
+
+    fn picker(index: Arc<FileIndex>, mode: FilePickerMode) -> FilePicker {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut picker = FilePicker::new(index.root.clone(), mode, rx);
+        tx.send(index).unwrap();
+        assert!(picker.poll_index());
+        picker
+    }
+
+    fn key(picker: &mut FilePicker, code: KeyCode) -> OverlayAction {
+        picker.handle_overlay_key(code, KeyModifiers::NONE).unwrap()
+    }
+
+    fn type_filter(picker: &mut FilePicker, text: &str) {
+        for c in text.chars() {
+            key(picker, KeyCode::Char(c));
+        }
+    }
+
+    #[test]
+    fn match_score_prefers_file_name_and_boundary_matches() {
+        let entry = |path: &str| IndexEntry {
+            path: path.to_string(),
+            is_dir: false,
+            size: 0,
+            modified: None,
+        };
+        let name_hit = match_score(&entry("src/tui/file_picker.rs"), "picker").unwrap();
+        let scattered = match_score(&entry("src/protocol/ick/er.rs"), "picker").unwrap();
+        assert!(name_hit > scattered);
+        assert!(match_score(&entry("src/main.rs"), "picker").is_none());
+        assert!(match_score(&entry("src/FilePicker.rs"), "fp").is_some());
+    }
+
+    #[test]
+    fn typing_filters_and_space_marks_entries_for_mentions() {
+        let (_temp, index) = workspace();
+        let mut picker = picker(index, FilePickerMode::Mention);
+
+        type_filter(&mut picker, "picker");
+        assert_eq!(
+            picker
+                .entry(picker.selected_entry_index().unwrap())
+                .unwrap()
+                .path,
+            "src/tui/file_picker.rs"
+        );
+        key(&mut picker, KeyCode::Char(' '));
+        key(&mut picker, KeyCode::Esc);
+        type_filter(&mut picker, "readme");
+        key(&mut picker, KeyCode::Char(' '));
+
+        match key(&mut picker, KeyCode::Enter) {
+            OverlayAction::Mention(paths) => {
+                assert_eq!(paths, vec!["src/tui/file_picker.rs", "README.md"])
+            }
+            _ => panic!("expected mentions"),
+        }
+    }
+
+    #[test]
+    fn directories_ask_between_tree_summary_and_files_under_budget() {
+        let (_temp, index) = workspace();
+        let mut picker = picker(index, FilePickerMode::Mention);
+        key(&mut picker, KeyCode::Tab);
+        assert_eq!(picker.mode(), FilePickerMode::Attach);
+
+        type_filter(&mut picker, "src");
+        assert!(matches!(
+            key(&mut picker, KeyCode::Enter),
+            OverlayAction::Continue
+        ));
+        assert_eq!(picker.stage, Stage::DirChoice { selected: 0 });
+
+        match key(&mut picker, KeyCode::Char('a')) {
+            OverlayAction::Attach(blocks) => {
+                assert_eq!(blocks.len(), 1);
+                let block = &blocks[0];
+                assert!(block.starts_with("<files under=\"src/\" count=\"2\">"));
+                assert!(block.contains("<file path=\"src/main.rs\">\nfn main() {}\n</file>"));
+                assert!(block.contains("Omitted 1 binary"));
+                assert!(block.contains("src/logo.png"));
+                assert_eq!(
+                    attachment_placeholder(block).as_deref(),
+                    Some("[2 files under src/]")
+                );
+            }
+            _ => panic!("expected attachment"),
+        }
+    }
+
+    #[test]
+    fn tree_summary_indents_entries_and_names_its_placeholder() {
+        let (_temp, index) = workspace();
+        let src = entry_index(&index, "src");
+        let blocks = attachment_blocks(&index, &[src], DirAttach::TreeSummary);
+        assert_eq!(
+            blocks[0],
+            "<tree path=\"src/\" entries=\"4\">\n\
+             tui/\n  file_picker.rs (10 B)\n\
+             logo.png (4 B)\n\
+             main.rs (13 B)\n\
+             </tree>"
+        );
+        assert_eq!(
+            attachment_placeholder(&blocks[0]).as_deref(),
+            Some("[tree src/]")
+        );
+    }
+
+    #[test]
+    fn file_blocks_cap_size_and_skip_binary_contents() {
+        let (temp, index) = workspace();
+        std::fs::write(temp.path().join("big.txt"), "x".repeat(64)).unwrap();
+        let big = IndexEntry {
+            path: "big.txt".to_string(),
+            is_dir: false,
+            size: 64,
+            modified: None,
+        };
+        let block = file_block(temp.path(), &big, 16);
+        assert!(
+            block.starts_with("<file path=\"big.txt\" truncated=\"true\">\nxxxxxxxxxxxxxxxx\n")
+        );
+        assert_eq!(
+            attachment_placeholder(&block).as_deref(),
+            Some("[file big.txt]")
+        );
+
+        let logo = entry_index(&index, "src/logo.png");
+        let blocks = attachment_blocks(&index, &[logo], DirAttach::TreeSummary);
+        assert!(blocks[0].contains("(binary file, 4 B, not included)"));
+        assert_eq!(attachment_placeholder("plain pasted text"), None);
+    }
+}
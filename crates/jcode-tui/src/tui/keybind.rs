@@ -7,7 +7,7 @@ pub use jcode_tui_core::keybind::{
 };
 use jcode_tui_core::keybind::{
     format_binding, is_disabled, macos_option_char_to_ascii_key, parse_bindings_or_default,
-    parse_keybinding, parse_optional, parse_or_default,
+    parse_keybinding, parse_keybinding_list, parse_optional, parse_or_default,
 };
 
 // Re-export the per-platform keybinding registry + provenance + validation API
@@ -382,6 +382,7 @@ pub struct ToggleKeys {
     pub typing_scroll_lock: ToggleBinding,
     pub diff_mode_cycle: ToggleBinding,
     pub info_widget: ToggleBinding,
+    pub todo_checklist: ToggleBinding,
     pub swarm_panel_focus: ToggleBinding,
 }
 
@@ -394,6 +395,7 @@ pub fn load_toggle_keys() -> ToggleKeys {
         typing_scroll_lock: ToggleBinding::load(&cfg.keybindings.typing_scroll_lock_toggle, 's'),
         diff_mode_cycle: ToggleBinding::load(&cfg.keybindings.diff_mode_cycle, 'g'),
         info_widget: ToggleBinding::load(&cfg.keybindings.info_widget_toggle, 'i'),
+        todo_checklist: ToggleBinding::load(&cfg.keybindings.todo_checklist_toggle, 'x'),
         swarm_panel_focus: ToggleBinding::load_with_default(
             &cfg.keybindings.swarm_panel_focus,
             swarm_panel_focus_default(),
@@ -433,6 +435,22 @@ pub(crate) fn swarm_panel_focus_key_label() -> String {
     }
 }
 
+/// Label for the todo checklist collapse chord, or `None` when it is unbound.
+pub(crate) fn todo_checklist_key_label() -> Option<String> {
+    let cfg = config();
+    let default = KeyBinding {
+        code: KeyCode::Char('x'),
+        modifiers: KeyModifiers::ALT,
+    };
+    let default_label = format_binding(&default);
+    let (binding, _) = parse_optional(
+        &cfg.keybindings.todo_checklist_toggle,
+        default,
+        &default_label,
+    );
+    binding.map(|b| format_binding(&b))
+}
+
 pub(crate) fn shortcut_char_for_macos_option_key(
     code: KeyCode,
     modifiers: KeyModifiers,
@@ -555,6 +573,23 @@ pub fn load_new_terminal_key() -> OptionalBinding {
     }
 }
 
+/// Binding that opens the inline interjection input while a turn is running.
+/// Default: Alt+Enter. Set "" to disable.
+pub fn load_interject_key() -> OptionalBinding {
+    let cfg = config();
+    let raw = cfg.keybindings.interject.trim();
+    if raw.is_empty() || is_disabled(raw) {
+        return OptionalBinding::default();
+    }
+    match parse_keybinding(raw) {
+        Some(binding) => OptionalBinding {
+            label: Some(format_binding(&binding)),
+            binding: Some(binding),
+        },
+        None => OptionalBinding::default(),
+    }
+}
+
 /// Optional binding that opens the `/resume` session picker.
 /// Default: Cmd+B on macOS, Alt+R elsewhere. Set "" to disable.
 pub fn load_open_resume_key() -> OptionalBinding {
@@ -572,9 +607,207 @@ pub fn load_open_resume_key() -> OptionalBinding {
     }
 }
 
+/// Binding that opens the focused inline image or diagram in the system
+/// viewer. Default: Alt+O. Set "" to disable.
+pub fn load_image_open_key() -> OptionalBinding {
+    let cfg = config();
+    let raw = cfg.keybindings.image_open.trim();
+    if raw.is_empty() || is_disabled(raw) {
+        return OptionalBinding::default();
+    }
+    match parse_keybinding(raw) {
+        Some(binding) => OptionalBinding {
+            label: Some(format_binding(&binding)),
+            binding: Some(binding),
+        },
+        None => OptionalBinding::default(),
+    }
+}
+
+/// Binding that saves the focused assistant answer as a memory, like
+/// `/remember`. Default: Alt+P. Set "" to disable.
+pub fn load_remember_message_key() -> OptionalBinding {
+    let cfg = config();
+    let raw = cfg.keybindings.remember_message.trim();
+    if raw.is_empty() || is_disabled(raw) {
+        return OptionalBinding::default();
+    }
+    match parse_keybinding(raw) {
+        Some(binding) => OptionalBinding {
+            label: Some(format_binding(&binding)),
+            binding: Some(binding),
+        },
+        None => OptionalBinding::default(),
+    }
+}
+
+/// Binding that copies the focused assistant answer or tool output through
+/// OSC 52. Default: Alt+Q. Set "" to disable.
+pub fn load_copy_message_key() -> OptionalBinding {
+    let cfg = config();
+    let raw = cfg.keybindings.copy_message.trim();
+    if raw.is_empty() || is_disabled(raw) {
+        return OptionalBinding::default();
+    }
+    match parse_keybinding(raw) {
+        Some(binding) => OptionalBinding {
+            label: Some(format_binding(&binding)),
+            binding: Some(binding),
+        },
+        None => OptionalBinding::default(),
+    }
+}
+
+/// Binding that opens the workspace file picker. Default: Ctrl+F. Set "" to
+/// disable, which leaves Ctrl+F as readline forward-word.
+pub fn load_file_picker_key() -> OptionalBinding {
+    let cfg = config();
+    let raw = cfg.keybindings.file_picker.trim();
+    if raw.is_empty() || is_disabled(raw) {
+        return OptionalBinding::default();
+    }
+    match parse_keybinding(raw) {
+        Some(binding) => OptionalBinding {
+            label: Some(format_binding(&binding)),
+            binding: Some(binding),
+        },
+        None => OptionalBinding::default(),
+    }
+}
+
+/// What a configured composer editing chord does.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ComposerEditAction {
+    Edit(crate::tui::line_editor::EditCommand),
+    Undo,
+}
+
+/// Readline-style composer editing chords (`keybindings.composer_*`).
+#[derive(Clone, Debug, Default)]
+pub struct ComposerEditKeys {
+    bindings: Vec<(KeyBinding, ComposerEditAction)>,
+}
+
+impl ComposerEditKeys {
+    fn from_config(cfg: &crate::config::KeybindingsConfig) -> Self {
+        use crate::tui::line_editor::EditCommand;
+
+        let configured = [
+            (
+                &cfg.composer_word_back,
+                ComposerEditAction::Edit(EditCommand::WordBack),
+            ),
+            (
+                &cfg.composer_word_forward,
+                ComposerEditAction::Edit(EditCommand::WordForward),
+            ),
+            (
+                &cfg.composer_kill_line_end,
+                ComposerEditAction::Edit(EditCommand::KillLineEnd),
+            ),
+            (
+                &cfg.composer_kill_line_start,
+                ComposerEditAction::Edit(EditCommand::KillLineStart),
+            ),
+            (
+                &cfg.composer_kill_word_back,
+                ComposerEditAction::Edit(EditCommand::KillWordBack),
+            ),
+            (
+                &cfg.composer_yank,
+                ComposerEditAction::Edit(EditCommand::Yank),
+            ),
+            (
+                &cfg.composer_yank_pop,
+                ComposerEditAction::Edit(EditCommand::YankPop),
+            ),
+            (&cfg.composer_undo, ComposerEditAction::Undo),
+            (
+                &cfg.composer_transpose,
+                ComposerEditAction::Edit(EditCommand::TransposeChars),
+            ),
+        ];
+        let bindings = configured
+            .into_iter()
+            .flat_map(|(raw, action)| {
+                parse_keybinding_list(raw)
+                    .into_iter()
+                    .map(move |binding| (binding, action))
+            })
+            .collect();
+        Self { bindings }
+    }
+
+    pub fn action_for(&self, code: KeyCode, modifiers: KeyModifiers) -> Option<ComposerEditAction> {
+        self.bindings
+            .iter()
+            .find(|(binding, _)| binding.matches(code, modifiers))
+            .map(|(_, action)| *action)
+    }
+}
+
+/// Composer editing chords. An empty or "none" value disables that action.
+pub fn load_composer_edit_keys() -> ComposerEditKeys {
+    ComposerEditKeys::from_config(&config().keybindings)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tui::line_editor::EditCommand;
+
+    #[test]
+    fn composer_edit_keys_follow_readline_defaults() {
+        let keys = ComposerEditKeys::from_config(&crate::config::KeybindingsConfig::default());
+        let action = |code, modifiers| keys.action_for(code, modifiers);
+
+        assert_eq!(
+            action(KeyCode::Char('w'), KeyModifiers::CONTROL),
+            Some(ComposerEditAction::Edit(EditCommand::KillWordBack))
+        );
+        assert_eq!(
+            action(KeyCode::Char('y'), KeyModifiers::CONTROL),
+            Some(ComposerEditAction::Edit(EditCommand::Yank))
+        );
+        assert_eq!(
+            action(KeyCode::Right, KeyModifiers::CONTROL),
+            Some(ComposerEditAction::Edit(EditCommand::WordForward))
+        );
+        assert_eq!(
+            action(KeyCode::Char('_'), KeyModifiers::CONTROL),
+            Some(ComposerEditAction::Undo)
+        );
+        // Ctrl+Shift+K stays the scroll chord, and the unbound readline keys
+        // keep their jcode meanings.
+        assert_eq!(
+            action(
+                KeyCode::Char('k'),
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT
+            ),
+            None
+        );
+        assert_eq!(action(KeyCode::Char('t'), KeyModifiers::CONTROL), None);
+        assert_eq!(action(KeyCode::Char('y'), KeyModifiers::ALT), None);
+    }
+
+    #[test]
+    fn composer_edit_keys_can_be_remapped_and_disabled() {
+        let cfg = crate::config::KeybindingsConfig {
+            composer_transpose: "ctrl+t".to_string(),
+            composer_kill_word_back: "none".to_string(),
+            ..Default::default()
+        };
+        let keys = ComposerEditKeys::from_config(&cfg);
+
+        assert_eq!(
+            keys.action_for(KeyCode::Char('t'), KeyModifiers::CONTROL),
+            Some(ComposerEditAction::Edit(EditCommand::TransposeChars))
+        );
+        assert_eq!(
+            keys.action_for(KeyCode::Char('w'), KeyModifiers::CONTROL),
+            None
+        );
+    }
 
     #[test]
     fn new_terminal_alt_enter_binding_parses_and_matches() {
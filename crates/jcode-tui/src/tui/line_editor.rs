@@ -0,0 +1,609 @@
+//! Readline-style editing for the composer.
+//!
+//! Everything here operates on a plain `(text, byte cursor)` pair plus a
+//! [`KillRing`], so the editing rules are unit-testable without an `App`:
+//!
+//! * word motions (`Alt+B` / `Alt+F`),
+//! * kills scoped to the current logical line (`Ctrl+K` / `Ctrl+U`) and to
+//!   words (`Ctrl+W` / `Alt+D`); consecutive kills merge into one ring entry,
+//! * yank / yank-pop (`Ctrl+Y` / `Alt+Y` in readline),
+//! * character transposition,
+//! * vertical motion across soft-wrapped rows, using the same greedy wrapping
+//!   the input box renders with.
+//!
+//! Undo is not handled here: callers snapshot the draft before any command
+//! that [`EditCommand::mutates`] and reuse the composer's undo stack.
+
+use std::collections::VecDeque;
+use unicode_width::UnicodeWidthChar;
+
+use super::core::{next_char_boundary, prev_char_boundary};
+
+/// A single composer editing command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditCommand {
+    WordBack,
+    WordForward,
+    /// Kill to the end of the current line, or the newline itself when the
+    /// cursor already sits at the end of a line.
+    KillLineEnd,
+    /// Kill to the start of the current line, or the preceding newline when
+    /// the cursor already sits at the start of a line.
+    KillLineStart,
+    KillWordBack,
+    KillWordForward,
+    Yank,
+    /// Replace the text inserted by the last yank with the next-older ring
+    /// entry. Only valid directly after a yank or another yank-pop.
+    YankPop,
+    /// Swap the characters around the cursor and advance; at the end of a
+    /// line, swap the two characters before the cursor.
+    TransposeChars,
+}
+
+impl EditCommand {
+    /// Whether the command may change the text (and so needs an undo snapshot).
+    pub fn mutates(self) -> bool {
+        !matches!(self, EditCommand::WordBack | EditCommand::WordForward)
+    }
+
+    /// Whether the command only makes sense with a non-empty draft. Callers let
+    /// these keys fall through on an empty input so chords such as `Ctrl+K`
+    /// keep their empty-composer meaning (prompt navigation).
+    pub fn needs_text(self) -> bool {
+        !matches!(self, EditCommand::Yank | EditCommand::YankPop)
+    }
+}
+
+/// Kill ring shared by every composer edit in a session.
+#[derive(Debug, Default)]
+pub struct KillRing {
+    /// Most recent kill first.
+    entries: VecDeque<String>,
+    /// Draft shape right after the last kill; a kill starting from the same
+    /// shape extends that entry instead of pushing a new one.
+    last_kill: Option<DraftMark>,
+    last_yank: Option<YankMark>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DraftMark {
+    cursor: usize,
+    len: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct YankMark {
+    start: usize,
+    end: usize,
+    len: usize,
+    index: usize,
+}
+
+impl KillRing {
+    const LIMIT: usize = 32;
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// The entry the next yank would insert.
+    #[cfg(test)]
+    pub fn latest(&self) -> Option<&str> {
+        self.entries.front().map(String::as_str)
+    }
+
+    /// Approximate heap usage, for memory profiling.
+    pub fn heap_bytes(&self) -> usize {
+        self.entries.iter().map(String::capacity).sum()
+    }
+
+    fn record_kill(&mut self, killed: String, backward: bool, before: DraftMark, after: DraftMark) {
+        self.last_yank = None;
+        let extends = self.last_kill == Some(before);
+        match self.entries.front_mut() {
+            Some(entry) if extends => {
+                if backward {
+                    entry.insert_str(0, &killed);
+                } else {
+                    entry.push_str(&killed);
+                }
+            }
+            _ => {
+                self.entries.push_front(killed);
+                self.entries.truncate(Self::LIMIT);
+            }
+        }
+        self.last_kill = Some(after);
+    }
+}
+
+/// Apply `command` to `text` at `cursor`. Returns `true` when the text changed.
+pub fn apply(
+    text: &mut String,
+    cursor: &mut usize,
+    ring: &mut KillRing,
+    command: EditCommand,
+) -> bool {
+    *cursor = clamp_cursor(text, *cursor);
+    match command {
+        EditCommand::WordBack => {
+            *cursor = word_boundary_back(text, *cursor);
+            false
+        }
+        EditCommand::WordForward => {
+            *cursor = word_boundary_forward(text, *cursor);
+            false
+        }
+        EditCommand::KillLineEnd => {
+            let end = match text[*cursor..].find('\n') {
+                Some(0) => *cursor + 1,
+                Some(offset) => *cursor + offset,
+                None => text.len(),
+            };
+            kill(text, cursor, ring, *cursor..end, false)
+        }
+        EditCommand::KillLineStart => {
+            let start = match text[..*cursor].rfind('\n') {
+                Some(newline) if newline + 1 == *cursor => newline,
+                Some(newline) => newline + 1,
+                None => 0,
+            };
+            kill(text, cursor, ring, start..*cursor, true)
+        }
+        EditCommand::KillWordBack => {
+            let start = word_boundary_back(text, *cursor);
+            kill(text, cursor, ring, start..*cursor, true)
+        }
+        EditCommand::KillWordForward => {
+            let end = word_boundary_forward(text, *cursor);
+            kill(text, cursor, ring, *cursor..end, false)
+        }
+        EditCommand::Yank => yank(text, cursor, ring),
+        EditCommand::YankPop => yank_pop(text, cursor, ring),
+        EditCommand::TransposeChars => transpose_chars(text, cursor, ring),
+    }
+}
+
+fn kill(
+    text: &mut String,
+    cursor: &mut usize,
+    ring: &mut KillRing,
+    range: std::ops::Range<usize>,
+    backward: bool,
+) -> bool {
+    if range.is_empty() {
+        return false;
+    }
+    let before = DraftMark {
+        cursor: *cursor,
+        len: text.len(),
+    };
+    let killed: String = text.drain(range.clone()).collect();
+    *cursor = range.start;
+    let after = DraftMark {
+        cursor: *cursor,
+        len: text.len(),
+    };
+    ring.record_kill(killed, backward, before, after);
+    true
+}
+
+fn yank(text: &mut String, cursor: &mut usize, ring: &mut KillRing) -> bool {
+    let Some(entry) = ring.entries.front() else {
+        return false;
+    };
+    let start = *cursor;
+    text.insert_str(start, entry);
+    *cursor = start + entry.len();
+    ring.last_kill = None;
+    ring.last_yank = Some(YankMark {
+        start,
+        end: *cursor,
+        len: text.len(),
+        index: 0,
+    });
+    true
+}
+
+fn yank_pop(text: &mut String, cursor: &mut usize, ring: &mut KillRing) -> bool {
+    let Some(mark) = ring.last_yank else {
+        return false;
+    };
+    if mark.end != *cursor || mark.len != text.len() || ring.entries.len() < 2 {
+        return false;
+    }
+    let index = (mark.index + 1) % ring.entries.len();
+    let entry = &ring.entries[index];
+    text.replace_range(mark.start..mark.end, entry);
+    *cursor = mark.start + entry.len();
+    ring.last_yank = Some(YankMark {
+        start: mark.start,
+        end: *cursor,
+        len: text.len(),
+        index,
+    });
+    true
+}
+
+fn transpose_chars(text: &mut String, cursor: &mut usize, ring: &mut KillRing) -> bool {
+    if *cursor == 0 {
+        return false;
+    }
+    let at_line_end = *cursor == text.len() || text[*cursor..].starts_with('\n');
+    let (left, right) = if at_line_end {
+        let right = prev_char_boundary(text, *cursor);
+        (prev_char_boundary(text, right), right)
+    } else {
+        (prev_char_boundary(text, *cursor), *cursor)
+    };
+    let right_end = next_char_boundary(text, right);
+    if left == right || text[left..right_end].contains('\n') {
+        return false;
+    }
+    let swapped = format!("{}{}", &text[right..right_end], &text[left..right]);
+    text.replace_range(left..right_end, &swapped);
+    *cursor = right_end;
+    ring.last_kill = None;
+    ring.last_yank = None;
+    true
+}
+
+fn clamp_cursor(text: &str, cursor: usize) -> usize {
+    let mut cursor = cursor.min(text.len());
+    while !text.is_char_boundary(cursor) {
+        cursor -= 1;
+    }
+    cursor
+}
+
+/// Start of the word before `cursor`, skipping any whitespace in between.
+pub fn word_boundary_back(text: &str, cursor: usize) -> usize {
+    if cursor == 0 {
+        return 0;
+    }
+    let is_space_at = |pos: usize| text[pos..].chars().next().is_none_or(char::is_whitespace);
+
+    let mut pos = prev_char_boundary(text, cursor);
+    while pos > 0 && is_space_at(pos) {
+        pos = prev_char_boundary(text, pos);
+    }
+    while pos > 0 {
+        let prev = prev_char_boundary(text, pos);
+        if is_space_at(prev) {
+            break;
+        }
+        pos = prev;
+    }
+    pos
+}
+
+/// Start of the next word after `cursor`: skips the rest of the current word
+/// and the whitespace that follows it.
+pub fn word_boundary_forward(text: &str, cursor: usize) -> usize {
+    let len = text.len();
+    if cursor >= len {
+        return len;
+    }
+    let is_space_at = |pos: usize| text[pos..].chars().next().is_none_or(char::is_whitespace);
+
+    let mut pos = cursor;
+    while pos < len && !is_space_at(pos) {
+        pos = next_char_boundary(text, pos);
+    }
+    while pos < len && is_space_at(pos) {
+        pos = next_char_boundary(text, pos);
+    }
+    pos
+}
+
+/// One soft-wrapped row of the input box, in character indices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WrappedRow {
+    pub start_char: usize,
+    /// Exclusive; a row that ends at a wrap point shares this index with the
+    /// next row's `start_char`.
+    pub end_char: usize,
+    pub display_width: usize,
+}
+
+/// Greedily wrap `text` to `width` display columns, breaking at newlines and
+/// wherever the next character would overflow the row.
+pub fn wrap_rows(text: &str, width: usize) -> Vec<WrappedRow> {
+    let chars: Vec<char> = text.chars().collect();
+    if chars.is_empty() {
+        return vec![WrappedRow {
+            start_char: 0,
+            end_char: 0,
+            display_width: 0,
+        }];
+    }
+
+    let mut rows = Vec::new();
+    let mut pos = 0;
+    let mut char_count = 0;
+
+    while pos <= chars.len() {
+        let newline_pos = chars[pos..].iter().position(|&c| c == '\n');
+        let segment_end = match newline_pos {
+            Some(rel_pos) => pos + rel_pos,
+            None => chars.len(),
+        };
+
+        let segment = &chars[pos..segment_end];
+        let mut seg_pos = 0;
+        loop {
+            let mut display_width = 0;
+            let mut end = seg_pos;
+            while end < segment.len() {
+                let cw = segment[end].width().unwrap_or(0);
+                if display_width + cw > width {
+                    break;
+                }
+                display_width += cw;
+                end += 1;
+            }
+            if end == seg_pos && seg_pos < segment.len() {
+                end = seg_pos + 1;
+                display_width = segment[seg_pos].width().unwrap_or(0);
+            }
+
+            let start_char = char_count;
+            let end_char = char_count + (end - seg_pos);
+            rows.push(WrappedRow {
+                start_char,
+                end_char,
+                display_width,
+            });
+            char_count = end_char;
+
+            if end >= segment.len() {
+                break;
+            }
+            seg_pos = end;
+        }
+
+        if newline_pos.is_some() {
+            char_count += 1;
+            pos = segment_end + 1;
+        } else {
+            break;
+        }
+    }
+
+    rows
+}
+
+/// Byte offset one visual row above (`up`) or below the cursor when `text` is
+/// wrapped to `width` columns, keeping the display column where possible.
+/// Returns `None` when the cursor is already on the first/last row.
+pub fn vertical_target(text: &str, cursor: usize, width: usize, up: bool) -> Option<usize> {
+    if width == 0 {
+        return None;
+    }
+    let rows = wrap_rows(text, width);
+    let chars: Vec<char> = text.chars().collect();
+    let cursor_char = super::core::byte_offset_to_char_index(text, clamp_cursor(text, cursor));
+    let row_index = rows
+        .iter()
+        .position(|row| cursor_char >= row.start_char && cursor_char <= row.end_char)
+        .unwrap_or(rows.len() - 1);
+    let row = rows[row_index];
+    let column: usize = chars[row.start_char..cursor_char]
+        .iter()
+        .map(|c| c.width().unwrap_or(0))
+        .sum();
+
+    let target_index = if up {
+        row_index.checked_sub(1)?
+    } else {
+        Some(row_index + 1).filter(|index| *index < rows.len())?
+    };
+    let target = rows[target_index];
+    let mut target_char = target.start_char;
+    let mut used = 0;
+    for ch in &chars[target.start_char..target.end_char] {
+        let cw = ch.width().unwrap_or(0);
+        if used + cw > column {
+            break;
+        }
+        used += cw;
+        target_char += 1;
+    }
+    // Landing exactly on a wrap point would render on the row above; keep the
+    // cursor on the target row instead.
+    if target_char == target.end_char
+        && target_index + 1 < rows.len()
+        && rows[target_index + 1].start_char == target.end_char
+        && target_char > target.start_char
+    {
+        target_char -= 1;
+    }
+    Some(super::core::char_index_to_byte_offset(text, target_char))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A draft plus its kill ring, driven one command at a time.
+    struct Draft {
+        text: String,
+        cursor: usize,
+        ring: KillRing,
+    }
+
+    impl Draft {
+        fn new(text: &str, cursor: usize) -> Self {
+            Self {
+                text: text.to_string(),
+                cursor,
+                ring: KillRing::default(),
+            }
+        }
+
+        fn at_end(text: &str) -> Self {
+            Self::new(text, text.len())
+        }
+
+        fn press(&mut self, command: EditCommand) -> bool {
+            apply(&mut self.text, &mut self.cursor, &mut self.ring, command)
+        }
+    }
+
+    #[test]
+    fn word_motions_skip_whitespace_and_newlines() {
+        let text = "alpha  beta\ngamma";
+        assert_eq!(word_boundary_back(text, text.len()), "alpha  beta\n".len());
+        assert_eq!(word_boundary_back(text, 12), "alpha  ".len());
+        assert_eq!(word_boundary_forward(text, 0), "alpha  ".len());
+        assert_eq!(word_boundary_forward(text, 7), "alpha  beta\n".len());
+    }
+
+    #[test]
+    fn kill_line_end_is_scoped_to_the_current_line() {
+        let mut draft = Draft::new("one two\nthree", 3);
+        assert!(draft.press(EditCommand::KillLineEnd));
+        assert_eq!(draft.text, "one\nthree");
+        assert_eq!(draft.cursor, 3);
+        assert_eq!(draft.ring.latest(), Some(" two"));
+
+        // At the end of a line the newline itself is killed, joining the lines,
+        // and the consecutive kill extends the same ring entry.
+        assert!(draft.press(EditCommand::KillLineEnd));
+        assert_eq!(draft.text, "onethree");
+        assert_eq!(draft.ring.latest(), Some(" two\n"));
+        assert_eq!(draft.ring.len(), 1);
+    }
+
+    #[test]
+    fn kill_line_start_stops_at_the_line_start() {
+        let mut draft = Draft::new("first\nsecond line", 13);
+        assert!(draft.press(EditCommand::KillLineStart));
+        assert_eq!(draft.text, "first\nline");
+        assert_eq!(draft.cursor, "first\n".len());
+        assert_eq!(draft.ring.latest(), Some("second "));
+    }
+
+    #[test]
+    fn consecutive_backward_kills_prepend_to_one_entry() {
+        let mut draft = Draft::at_end("say hello world");
+        draft.press(EditCommand::KillWordBack);
+        draft.press(EditCommand::KillWordBack);
+        assert_eq!(draft.text, "say ");
+        assert_eq!(draft.ring.latest(), Some("hello world"));
+        assert_eq!(draft.ring.len(), 1);
+    }
+
+    #[test]
+    fn a_motion_between_kills_starts_a_new_entry() {
+        let mut draft = Draft::at_end("aa bb cc");
+        draft.press(EditCommand::KillWordBack);
+        draft.press(EditCommand::WordBack);
+        draft.press(EditCommand::KillWordForward);
+        assert_eq!(draft.text, "aa ");
+        assert_eq!(draft.ring.len(), 2);
+        assert_eq!(draft.ring.latest(), Some("bb "));
+    }
+
+    #[test]
+    fn yank_inserts_latest_kill_and_yank_pop_cycles_older_entries() {
+        let mut draft = Draft::at_end("first second");
+        draft.press(EditCommand::KillWordBack);
+        draft.press(EditCommand::WordBack);
+        draft.press(EditCommand::KillLineEnd);
+        assert_eq!(draft.text, "");
+        assert_eq!(draft.ring.len(), 2);
+
+        assert!(draft.press(EditCommand::Yank));
+        assert_eq!(draft.text, "first ");
+        assert_eq!(draft.cursor, draft.text.len());
+
+        assert!(draft.press(EditCommand::YankPop));
+        assert_eq!(draft.text, "second");
+        assert_eq!(draft.cursor, draft.text.len());
+
+        assert!(draft.press(EditCommand::YankPop));
+        assert_eq!(draft.text, "first ");
+    }
+
+    #[test]
+    fn yank_pop_requires_an_immediately_preceding_yank() {
+        let mut draft = Draft::at_end("a b");
+        draft.press(EditCommand::KillWordBack);
+        draft.press(EditCommand::WordBack);
+        draft.press(EditCommand::KillLineEnd);
+        assert!(!draft.press(EditCommand::YankPop));
+
+        draft.press(EditCommand::Yank);
+        draft.text.push('x');
+        draft.cursor = draft.text.len();
+        assert!(!draft.press(EditCommand::YankPop));
+    }
+
+    #[test]
+    fn yank_with_empty_ring_is_a_no_op() {
+        let mut draft = Draft::new("keep", 2);
+        assert!(!draft.press(EditCommand::Yank));
+        assert_eq!((draft.text.as_str(), draft.cursor), ("keep", 2));
+    }
+
+    #[test]
+    fn transpose_swaps_around_cursor_and_at_line_end() {
+        let transpose = |text: &str, cursor: usize| {
+            let mut draft = Draft::new(text, cursor);
+            draft.press(EditCommand::TransposeChars);
+            (draft.text, draft.cursor)
+        };
+        assert_eq!(transpose("abcd", 2), ("acbd".to_string(), 3));
+        assert_eq!(transpose("abcd", 4), ("abdc".to_string(), 4));
+        assert_eq!(transpose("ab\ncd", 2), ("ba\ncd".to_string(), 2));
+        assert_eq!(
+            transpose("é😀", "é".len()),
+            ("😀é".to_string(), "😀é".len())
+        );
+        assert_eq!(transpose("ab", 0), ("ab".to_string(), 0));
+        assert_eq!(transpose("a\n\n", 2), ("a\n\n".to_string(), 2));
+    }
+
+    #[test]
+    fn vertical_target_moves_between_wrapped_rows() {
+        // Width 4 wraps "abcdefghij" into "abcd" / "efgh" / "ij".
+        let text = "abcdefghij";
+        assert_eq!(vertical_target(text, 5, 4, true), Some(1));
+        assert_eq!(vertical_target(text, 1, 4, false), Some(5));
+        assert_eq!(vertical_target(text, 6, 4, false), Some(10));
+        assert_eq!(vertical_target(text, 1, 4, true), None);
+        assert_eq!(vertical_target(text, 9, 4, false), None);
+    }
+
+    #[test]
+    fn vertical_target_crosses_hard_newlines_and_clamps_column() {
+        let text = "long line\nab";
+        assert_eq!(vertical_target(text, 8, 40, false), Some(text.len()));
+        assert_eq!(vertical_target(text, text.len(), 40, true), Some(2));
+    }
+
+    #[test]
+    fn vertical_target_uses_display_width_for_wide_chars() {
+        // "你好" fills a width-4 row, so "世界" wraps onto the next one.
+        let text = "你好世界";
+        assert_eq!(
+            vertical_target(text, "你".len(), 4, false),
+            Some("你好世".len())
+        );
+    }
+
+    #[test]
+    fn wrap_rows_breaks_on_newlines_and_overflow() {
+        let rows: Vec<(usize, usize)> = wrap_rows("abcde\nf", 3)
+            .iter()
+            .map(|row| (row.start_char, row.end_char))
+            .collect();
+        assert_eq!(rows, vec![(0, 3), (3, 5), (6, 7)]);
+    }
+}
@@ -1,10 +1,10 @@
 pub use jcode_tui_markdown::{
     CopyTargetKind, IncrementalMarkdownRenderer, MarkdownDebugStats, MarkdownMemoryProfile,
     RawCopyTarget, center_code_blocks, debug_memory_profile, debug_stats, debug_stats_json,
-    extract_copy_targets_from_rendered_lines, highlight_file_lines, highlight_line, progress_bar,
-    progress_line, recenter_structured_blocks_for_display, render_markdown, render_markdown_lazy,
-    render_markdown_with_width, render_table_with_width, reset_debug_stats, set_center_code_blocks,
-    wrap_line, wrap_lines,
+    extract_copy_targets_from_rendered_lines, highlight_file_lines, highlight_line,
+    mermaid_rendering_enabled, progress_bar, progress_line, recenter_structured_blocks_for_display,
+    render_markdown, render_markdown_lazy, render_markdown_with_width, render_table_with_width,
+    reset_debug_stats, set_center_code_blocks, wrap_line, wrap_lines,
 };
 
 fn to_markdown_diagram_mode(
@@ -238,6 +238,21 @@ impl ProviderMessageMemoryStats {
                     self.openai_compaction_bytes += encrypted_content.len();
                     self.record_bytes(encrypted_content.len());
                 }
+                ContentBlock::ServerToolUse { input, .. } => {
+                    let bytes = crate::process_memory::estimate_json_bytes(input);
+                    self.tool_use_input_json_bytes += bytes;
+                    self.record_bytes(bytes);
+                }
+                ContentBlock::WebSearchResult { results, .. } => {
+                    let bytes = results.iter().map(|hit| hit.payload_bytes()).sum::<usize>();
+                    self.tool_result_bytes += bytes;
+                    self.record_bytes(bytes);
+                }
+                ContentBlock::Citations { citations } => {
+                    let bytes = citations.iter().map(|c| c.payload_bytes()).sum::<usize>();
+                    self.text_bytes += bytes;
+                    self.record_bytes(bytes);
+                }
             }
         }
     }
@@ -11,6 +11,7 @@ pub struct ContextSnapshot {
 pub mod backend;
 pub(crate) mod color_support;
 mod core;
+pub mod file_picker;
 pub(crate) mod fuzzy;
 // Terminal image display + metadata helpers now live in the dependency-free
 // `jcode-terminal-image` crate (shared with the `read` tool). Re-exported here
@@ -23,6 +24,7 @@ mod info_widget_overview;
 pub mod info_widget_stability;
 pub mod keybind;
 mod layout_utils;
+pub(crate) mod line_editor;
 pub mod login_picker;
 pub mod markdown;
 mod memory_profile;
@@ -35,7 +37,9 @@ pub mod screenshot;
 pub(crate) mod session_facts;
 pub mod session_picker;
 mod stream_buffer;
+pub mod terminal_title;
 pub mod test_harness;
+pub mod todo_checklist;
 mod ui;
 mod ui_diff;
 pub mod usage_overlay;
@@ -251,6 +255,10 @@ pub trait TuiState {
     fn connection_phase_elapsed(&self) -> Option<Duration> {
         self.elapsed()
     }
+    /// Time left before the running MCP tool call times out
+    fn mcp_call_remaining(&self) -> Option<Duration> {
+        None
+    }
     fn status(&self) -> ProcessingStatus;
     fn command_suggestions(&self) -> Vec<(String, &'static str)>;
     fn command_suggestion_selected(&self) -> usize {
@@ -288,6 +296,11 @@ pub trait TuiState {
     fn is_replay(&self) -> bool;
     /// Diff display mode (off/inline/full-inline/pinned/file)
     fn diff_mode(&self) -> crate::config::DiffDisplayMode;
+    /// File pinned in the file view with `/view <path>`. `None` means the
+    /// view follows the most recently edited file.
+    fn file_view_pin(&self) -> Option<String> {
+        None
+    }
     /// Current session ID (if available)
     fn current_session_id(&self) -> Option<String>;
     /// Session display name (memorable short name like "fox" or "oak")
@@ -317,6 +330,10 @@ pub trait TuiState {
     fn hotkey_feedback(&self) -> Option<String> {
         None
     }
+    /// Pending migration onto a newer binary, with how to migrate now or defer
+    fn migration_banner(&self) -> Option<String> {
+        None
+    }
     /// First-use experimental feature warning for the currently active operation.
     fn active_experimental_feature_notice(&self) -> Option<String> {
         None
@@ -339,6 +356,15 @@ pub trait TuiState {
     fn next_prompt_new_session_armed(&self) -> bool {
         false
     }
+    /// Priority of the inline interjection being composed, if the input is
+    /// currently an interjection into the running turn.
+    fn interjection(&self) -> Option<InterjectionPriority> {
+        None
+    }
+    /// Whether prompts currently go to an open `/aside` scratch thread.
+    fn aside_active(&self) -> bool {
+        false
+    }
     /// Whether there is a stashed input (saved via Ctrl+S)
     fn has_stashed_input(&self) -> bool;
     /// Context info (what's loaded in context window - static + dynamic)
@@ -361,6 +387,11 @@ pub trait TuiState {
     /// Get info widget data (todos, client count, etc.)
     fn info_widget_data(&self) -> info_widget::InfoWidgetData;
 
+    /// Live todo checklist shown above the input, when it has open items.
+    fn todo_checklist(&self) -> Option<&todo_checklist::TodoChecklist> {
+        None
+    }
+
     /// Whether the inline swarm gallery band should be shown above the chat.
     /// Active when `agents.swarm_spawn_mode = inline` and the swarm has members.
     fn inline_swarm_gallery_active(&self) -> bool {
@@ -484,10 +515,18 @@ pub trait TuiState {
     fn model_status_overlay(&self) -> Option<(usize, &str)> {
         None
     }
+    /// `/compare` answers waiting for the user to keep one
+    fn compare_view(&self) -> Option<&CompareView> {
+        None
+    }
     /// Session picker overlay for /resume command
     fn session_picker_overlay(&self) -> Option<&std::cell::RefCell<session_picker::SessionPicker>>;
     /// Login picker overlay for /login command
     fn login_picker_overlay(&self) -> Option<&std::cell::RefCell<login_picker::LoginPicker>>;
+    /// Workspace file picker overlay (Ctrl+F by default)
+    fn file_picker_overlay(&self) -> Option<&std::cell::RefCell<file_picker::FilePicker>> {
+        None
+    }
     /// Account picker overlay for /account command
     fn account_picker_overlay(&self) -> Option<&std::cell::RefCell<account_picker::AccountPicker>>;
     /// Usage overlay for /usage command
@@ -495,6 +534,20 @@ pub trait TuiState {
     /// Working directory for this session
     // ---- Misc ----
     fn working_dir(&self) -> Option<String>;
+    /// Subdirectory tools are scoped to with `/cd`, if any
+    fn tool_scope(&self) -> Option<String> {
+        None
+    }
+    /// Active `[profiles.<name>]` preset for this session
+    fn session_profile(&self) -> Option<String>;
+    /// Whether the session runs with `jcode --safe` defaults
+    fn session_safe_mode(&self) -> bool;
+    /// Whether standing `/instructions` are set for this session
+    fn session_has_instructions(&self) -> bool {
+        false
+    }
+    /// Pending entries in the shared permission inbox
+    fn permission_inbox_count(&self) -> usize;
     /// Monotonic clock for viewport animations
     fn now_millis(&self) -> u64;
     /// UI state for live copy badge highlighting / feedback
@@ -735,6 +788,21 @@ pub(crate) fn detect_kv_cache_problem(
     })
 }
 
+/// Priority of a message composed in the inline interjection input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterjectionPriority {
+    /// Injected once the current tool batch has finished
+    Normal,
+    /// Skips the remaining tools once the in-flight tool finishes
+    Urgent,
+}
+
+impl InterjectionPriority {
+    pub fn is_urgent(self) -> bool {
+        self == InterjectionPriority::Urgent
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PickerKind {
     Model,
@@ -781,10 +849,34 @@ pub enum OnboardingWelcomeKind {
         yes_highlighted: bool,
         seconds_left: u64,
     },
+    /// First-run settings form shown after login when no config.toml exists.
+    Setup(SetupPrompt),
     /// The starter prompt-suggestion cards (default).
     Suggestions,
 }
 
+/// Render-friendly snapshot of the first-run settings form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetupPrompt {
+    /// One entry per setting, in display order.
+    pub rows: Vec<SetupPromptRow>,
+    /// Index of the focused row; ignored while `continue_focused`.
+    pub cursor: usize,
+    /// When `true`, the "Continue" pill is focused and Enter saves.
+    pub continue_focused: bool,
+}
+
+/// One row of the first-run settings form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetupPromptRow {
+    /// Setting name (e.g. "Memory").
+    pub label: String,
+    /// Current choice as shown (e.g. "on", "provider default").
+    pub value: String,
+    /// Whether Left/Right can change this row.
+    pub editable: bool,
+}
+
 /// Render-friendly snapshot of the single-screen login-import checkbox list.
 /// Carries every detected login plus which ones are checked and which row the
 /// cursor is on, so the welcome card can draw the whole list at once.
@@ -860,6 +952,16 @@ impl InlineViewState {
     }
 }
 
+/// The answers of a `/compare` run, shown side by side.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompareView {
+    pub prompt: String,
+    pub runs: Vec<crate::protocol::CompareRun>,
+    /// Column that Enter keeps.
+    pub selected: usize,
+    pub scroll: usize,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum InlineUiStateRef<'a> {
     View(&'a InlineViewState),
@@ -1653,6 +1755,7 @@ pub use ui::{
 pub fn display_messages_from_session(session: &crate::session::Session) -> Vec<DisplayMessage> {
     let mut messages = jcode_tui_messages::display_messages_from_rendered_messages(
         crate::session::render_messages(session),
+        crate::config::config().display.message_footers,
     );
     app::compact_display_messages_for_storage(&mut messages);
     messages
@@ -967,9 +967,6 @@ fn collect_recent_session_candidates(
         let Some((stem, has_snapshot)) = session_file_stem_for_candidate(file_name) else {
             continue;
         };
-        if stem.starts_with("imported_") {
-            continue;
-        }
         raw.push((stem.to_string(), has_snapshot, entry.path()));
     }
 
@@ -1085,7 +1082,7 @@ fn collect_saved_session_candidates(sessions_dir: &Path) -> Result<Vec<String>>
         let Some((stem, has_snapshot)) = session_file_stem_for_candidate(file_name) else {
             continue;
         };
-        if !has_snapshot || stem.starts_with("imported_") {
+        if !has_snapshot {
             continue;
         }
         let path = sessions_dir.join(format!("{stem}.json"));
@@ -1585,24 +1582,13 @@ pub(super) fn crashed_sessions_from_all_sessions(
 }
 
 /// Parse a single jcode session snapshot (+ journal) into a [`SessionInfo`],
-/// returning `None` for empty/imported sessions or read/parse errors. Pulled out
-/// of `load_sessions` so the summary pass can run across a scoped thread pool.
+/// returning `None` for empty sessions or read/parse errors. Pulled out of
+/// `load_sessions` so the summary pass can run across a scoped thread pool.
 fn parse_jcode_session_info(
     sessions_dir: &Path,
     stem: &str,
     catchup_seen: &crate::catchup::CatchupSeenSnapshot,
 ) -> Option<SessionInfo> {
-    // Imported stems are filtered out by `collect_recent_session_candidates`, but
-    // keep the cheap defensive check so this helper is safe to call directly.
-    if stem.starts_with("imported_cc_")
-        || stem.starts_with("imported_codex_")
-        || stem.starts_with("imported_pi_")
-        || stem.starts_with("imported_opencode_")
-        || stem.starts_with("imported_cursor_")
-    {
-        return None;
-    }
-
     let path = sessions_dir.join(format!("{stem}.json"));
     let session = load_session_summary(&path).ok()?;
 
@@ -1678,6 +1664,73 @@ fn parse_jcode_session_info(
     })
 }
 
+/// [`SessionInfo`] for a session summarized by an indexed storage backend;
+/// mirrors [`parse_jcode_session_info`] without touching session files.
+fn session_info_from_listing(
+    listing: storage::SessionListing,
+    catchup_seen: &crate::catchup::CatchupSeenSnapshot,
+) -> SessionInfo {
+    let short_name = listing
+        .short_name
+        .clone()
+        .or_else(|| extract_session_name(&listing.id).map(|s| s.to_string()))
+        .unwrap_or_else(|| listing.id.clone());
+    let icon = session_icon(&short_name);
+    let needs_catchup =
+        catchup_seen.needs_catchup(&listing.id, listing.updated_at, &listing.status);
+    let source = classify_session_source(
+        &listing.id,
+        listing.provider_key.as_deref(),
+        listing.model.as_deref(),
+    );
+    let title = listing
+        .custom_title
+        .or(listing.title)
+        .unwrap_or_else(|| short_name.clone());
+    let search_index = build_search_index_from_summary(
+        &listing.id,
+        &short_name,
+        &title,
+        listing.working_dir.as_deref(),
+        listing.save_label.as_deref(),
+        &listing.search_text,
+    );
+
+    SessionInfo {
+        id: listing.id.clone(),
+        parent_id: listing.parent_id,
+        short_name,
+        icon: icon.to_string(),
+        title,
+        message_count: listing.visible_message_count,
+        user_message_count: listing.user_message_count,
+        assistant_message_count: listing.assistant_message_count,
+        created_at: listing.created_at,
+        last_message_time: listing.updated_at,
+        last_active_at: listing.last_active_at,
+        working_dir: listing.working_dir,
+        model: listing.model,
+        provider_key: listing.provider_key,
+        is_canary: listing.is_canary,
+        is_debug: listing.is_debug,
+        saved: listing.saved,
+        save_label: listing.save_label,
+        status: listing.status,
+        needs_catchup,
+        estimated_tokens: listing.estimated_tokens,
+        first_user_prompt: listing.first_user_prompt,
+        messages_preview: Vec::new(),
+        search_index,
+        server_name: None,
+        server_icon: None,
+        source,
+        resume_target: ResumeTarget::JcodeSession {
+            session_id: listing.id,
+        },
+        external_path: None,
+    }
+}
+
 pub fn load_sessions() -> Result<Vec<SessionInfo>> {
     let sessions_dir = storage::jcode_dir()?.join("sessions");
     let scan_limit = session_scan_limit();
@@ -1691,7 +1744,24 @@ pub fn load_sessions() -> Result<Vec<SessionInfo>> {
         return Ok(entry.sessions.clone());
     }
 
-    let candidates = if sessions_dir.exists() {
+    // An indexed backend answers the whole jcode-session query at once;
+    // otherwise gather candidates from the session files.
+    let indexed = match storage::store()
+        .list_sessions(scan_limit, include_old_saved_sessions_on_initial_load())
+    {
+        Ok(listings) => listings,
+        Err(err) => {
+            crate::logging::warn(&format!(
+                "Indexed session listing failed, scanning session files: {}",
+                err
+            ));
+            None
+        }
+    };
+
+    let candidates = if indexed.is_some() {
+        Vec::new()
+    } else if sessions_dir.exists() {
         // Keep startup responsive by avoiding `session_has_history` here. That helper parses
         // snapshots/journals, and `load_session_summary` below parses the same files again.
         // Instead, gather a recency-ordered candidate window cheaply from metadata and let the
@@ -1734,6 +1804,13 @@ pub fn load_sessions() -> Result<Vec<SessionInfo>> {
         // crosses `scan_limit`) can over-parse, so wasted work is bounded to a
         // single window's worth of candidates while still parallelizing widely.
         let mut sessions: Vec<SessionInfo> = Vec::new();
+        if let Some(listings) = indexed {
+            sessions.extend(
+                listings
+                    .into_iter()
+                    .map(|listing| session_info_from_listing(listing, catchup_ref)),
+            );
+        }
         let mut boundary = candidates.len();
         let window = scan_limit.max(1);
         let mut start = 0;
@@ -1781,7 +1858,7 @@ pub fn load_sessions() -> Result<Vec<SessionInfo>> {
         external.extend(cursor_handle.join().unwrap_or_default());
         (sessions, external)
     });
-    sessions.extend(external_sessions);
+    merge_external_sessions(&mut sessions, external_sessions);
 
     sessions.sort_by(|a, b| b.last_message_time.cmp(&a.last_message_time));
 
@@ -1797,6 +1874,28 @@ pub fn load_sessions() -> Result<Vec<SessionInfo>> {
     Ok(sessions)
 }
 
+/// Add external transcripts to the jcode list, showing each conversation
+/// once. A transcript that was already imported is represented by its jcode
+/// copy, unless the transcript has moved on since; then the external entry
+/// wins, and resuming it re-imports.
+fn merge_external_sessions(sessions: &mut Vec<SessionInfo>, external: Vec<SessionInfo>) {
+    for entry in external {
+        let Some(imported_id) = crate::import::imported_session_id_for_target(&entry.resume_target)
+        else {
+            sessions.push(entry);
+            continue;
+        };
+        let existing = sessions
+            .iter()
+            .position(|session| session.id == imported_id);
+        match existing {
+            Some(index) if sessions[index].last_message_time >= entry.last_message_time => {}
+            Some(index) => sessions[index] = entry,
+            None => sessions.push(entry),
+        }
+    }
+}
+
 fn load_external_claude_code_sessions(scan_limit: usize) -> Vec<SessionInfo> {
     let Ok(sessions) = crate::import::list_claude_code_sessions_lazy(scan_limit) else {
         return Vec::new();
@@ -209,6 +209,41 @@ fn load_sessions_includes_claude_code_sessions_from_external_home() {
     assert_eq!(session.working_dir.as_deref(), Some("/tmp/demo-project"));
 }
 
+#[test]
+fn load_sessions_lists_imported_claude_code_session_once() {
+    let _env_lock = crate::storage::lock_test_env();
+    let temp = tempfile::tempdir().expect("temp dir");
+    let _home = EnvVarGuard::set_path("JCODE_HOME", temp.path());
+
+    let project_dir = temp.path().join("external/.claude/projects/demo-project");
+    std::fs::create_dir_all(&project_dir).expect("create project dir");
+    let transcript_path = project_dir.join("claude-session-456.jsonl");
+    std::fs::write(
+        &transcript_path,
+        concat!(
+            "{\"type\":\"user\",\"uuid\":\"u1\",\"sessionId\":\"claude-session-456\",\"cwd\":\"/tmp/demo-project\",\"timestamp\":\"2026-04-04T12:00:00Z\",\"message\":{\"role\":\"user\",\"content\":\"Fix the flaky test\"}}\n",
+            "{\"type\":\"assistant\",\"uuid\":\"a1\",\"parentUuid\":\"u1\",\"sessionId\":\"claude-session-456\",\"timestamp\":\"2026-04-04T12:01:00Z\",\"message\":{\"role\":\"assistant\",\"content\":\"On it.\"}}\n"
+        ),
+    )
+    .expect("write transcript");
+    crate::import::import_session_from_file(&transcript_path, "claude-session-456")
+        .expect("import transcript");
+
+    let sessions = load_sessions().expect("load sessions");
+    let matching: Vec<_> = sessions
+        .iter()
+        .filter(|session| session.id.contains("claude-session-456"))
+        .collect();
+
+    assert_eq!(matching.len(), 1, "imported transcript listed once");
+    assert_eq!(matching[0].id, "imported_cc_claude-session-456");
+    assert_eq!(matching[0].source, SessionSource::ClaudeCode);
+    assert!(matches!(
+        matching[0].resume_target,
+        ResumeTarget::JcodeSession { .. }
+    ));
+}
+
 #[test]
 fn load_claude_code_preview_reads_transcript_messages() {
     let _env_lock = crate::storage::lock_test_env();
@@ -0,0 +1,205 @@
+//! The terminal title, prefixed with a glyph for what the session is doing so
+//! a row of tabs or tmux windows shows at a glance which sessions are
+//! streaming, running a tool, waiting for approval or idle.
+//!
+//! The layout comes from `[display] title_format`. With `[display]
+//! tmux_status` on, the same line is also written to
+//! `~/.jcode/status/<session>` for a tmux `status-right` script to read. The
+//! title jcode replaced is saved on the terminal's title stack at startup and
+//! restored on exit, and the status file is removed.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+pub const DEFAULT_TITLE_FORMAT: &str = "{state} {icon} {server} {session}{suffix}";
+
+/// Whether [`save_original`] pushed a title that [`restore`] should pop.
+static SAVED: AtomicBool = AtomicBool::new(false);
+
+/// The status file this process last wrote, removed on exit or when the
+/// session changes.
+static STATUS_FILE: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// What the session is doing, as shown in the title.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TitleState {
+    Idle,
+    Streaming,
+    RunningTool,
+    AwaitingApproval,
+}
+
+impl TitleState {
+    pub fn glyph(self) -> &'static str {
+        match self {
+            Self::Idle => "✓",
+            Self::Streaming => "●",
+            Self::RunningTool => "◐",
+            Self::AwaitingApproval => "✋",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Idle => "idle",
+            Self::Streaming => "streaming",
+            Self::RunningTool => "tool",
+            Self::AwaitingApproval => "approval",
+        }
+    }
+}
+
+/// The values `title_format` placeholders expand to.
+#[derive(Clone, Debug)]
+pub struct TitleFields<'a> {
+    pub state: TitleState,
+    pub icon: &'a str,
+    pub server: &'a str,
+    pub session: &'a str,
+    pub suffix: &'a str,
+}
+
+/// Expand `{state}`, `{state_label}`, `{icon}`, `{server}`, `{session}` and
+/// `{suffix}` in `format`. Unknown placeholders are kept as written, and
+/// braces inside the values are never expanded again.
+pub fn render_title(format: &str, fields: &TitleFields<'_>) -> String {
+    let mut title = String::with_capacity(format.len() + fields.session.len());
+    let mut rest = format;
+    while let Some(open) = rest.find('{') {
+        title.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let Some(close) = after.find('}') else {
+            title.push_str(&rest[open..]);
+            rest = "";
+            break;
+        };
+        let value = match &after[..close] {
+            "state" => Some(fields.state.glyph()),
+            "state_label" => Some(fields.state.label()),
+            "icon" => Some(fields.icon),
+            "server" => Some(fields.server),
+            "session" => Some(fields.session),
+            "suffix" => Some(fields.suffix),
+            _ => None,
+        };
+        match value {
+            Some(value) => title.push_str(value),
+            None => title.push_str(&rest[open..open + close + 2]),
+        }
+        rest = &after[close + 1..];
+    }
+    title.push_str(rest);
+    title.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Where the tmux status line for `session_name` is written.
+pub fn status_file_path(session_name: &str) -> Option<PathBuf> {
+    let file_name: String = session_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if file_name.is_empty() {
+        return None;
+    }
+    let dir = crate::storage::jcode_dir().ok()?.join("status");
+    Some(dir.join(file_name))
+}
+
+/// Write `line` to the status file for `session_name`, removing the file of a
+/// session this process showed before.
+pub fn write_status_file(session_name: &str, line: &str) {
+    let Some(path) = status_file_path(session_name) else {
+        return;
+    };
+    let mut current = STATUS_FILE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(previous) = current.as_ref().filter(|previous| **previous != path) {
+        let _ = std::fs::remove_file(previous);
+    }
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    if let Err(err) = std::fs::write(&path, format!("{}\n", line)) {
+        crate::logging::warn(&format!(
+            "Failed to write tmux status file {}: {}",
+            path.display(),
+            err
+        ));
+    }
+    *current = Some(path);
+}
+
+fn remove_status_file() {
+    let mut current = STATUS_FILE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(path) = current.take() {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// Push the terminal's current title onto its title stack so [`restore`] can
+/// bring it back. Terminals without a title stack ignore the sequence.
+pub fn save_original() {
+    let mut stdout = std::io::stdout();
+    if write!(stdout, "\x1b[22;0t")
+        .and_then(|_| stdout.flush())
+        .is_ok()
+    {
+        SAVED.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Clear jcode's title, pop the one saved at startup and remove the status
+/// file.
+pub fn restore() {
+    let _ = crossterm::execute!(std::io::stdout(), crossterm::terminal::SetTitle(""));
+    if SAVED.swap(false, Ordering::Relaxed) {
+        let mut stdout = std::io::stdout();
+        let _ = write!(stdout, "\x1b[23;0t").and_then(|_| stdout.flush());
+    }
+    remove_status_file();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(state: TitleState) -> TitleFields<'static> {
+        TitleFields {
+            state,
+            icon: "🦊",
+            server: "jcode",
+            session: "fox {server}",
+            suffix: "",
+        }
+    }
+
+    #[test]
+    fn default_format_prefixes_state_glyph() {
+        assert_eq!(
+            render_title(DEFAULT_TITLE_FORMAT, &fields(TitleState::RunningTool)),
+            "◐ 🦊 jcode fox {server}"
+        );
+        assert_eq!(
+            render_title(DEFAULT_TITLE_FORMAT, &fields(TitleState::AwaitingApproval)),
+            "✋ 🦊 jcode fox {server}"
+        );
+    }
+
+    #[test]
+    fn custom_format_keeps_unknown_placeholders() {
+        assert_eq!(
+            render_title(
+                "[{state_label}] {session} {model} {",
+                &fields(TitleState::Idle)
+            ),
+            "[idle] fox {server} {model} {"
+        );
+    }
+}
@@ -0,0 +1,167 @@
+//! State behind the live todo checklist pinned above the input.
+//!
+//! Mirrors the session's todo list (written by the `todo` tool) as a compact
+//! checklist so the plan stays visible without scrolling back to the last
+//! tool call. It remembers when each item entered `in_progress`, which is
+//! what turns a runaway item into a visible "in_progress for 9 minutes" stall.
+
+use crate::todo::TodoItem;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// How long an item may sit in `in_progress` before the checklist flags it.
+pub const TODO_STALL_AFTER: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Clone, Debug, Default)]
+pub struct TodoChecklist {
+    pub todos: Vec<TodoItem>,
+    /// When each in-progress item (keyed by [`todo_key`]) was first seen in
+    /// that state. Items already in progress when the list is first loaded
+    /// count from that moment.
+    in_progress_since: HashMap<String, Instant>,
+    pub collapsed: bool,
+    /// Stall label of the longest-running item as of the last refresh, so the
+    /// tick only redraws when a stall label actually changes.
+    shown_stall: Option<String>,
+}
+
+impl TodoChecklist {
+    /// Whether there is anything left to do. A fully completed (or empty) list
+    /// hides the checklist.
+    pub fn has_open_items(&self) -> bool {
+        self.todos.iter().any(todo_is_open)
+    }
+
+    pub fn in_progress_for(&self, todo: &TodoItem, now: Instant) -> Option<Duration> {
+        if todo.status != "in_progress" {
+            return None;
+        }
+        self.in_progress_since
+            .get(&todo_key(todo))
+            .map(|since| now.saturating_duration_since(*since))
+    }
+
+    /// Replace the list, keeping in-progress start times for items that stayed
+    /// in progress. Returns whether anything visible changed.
+    pub fn apply(&mut self, todos: Vec<TodoItem>, now: Instant) -> bool {
+        let changed = fingerprint(&self.todos) != fingerprint(&todos);
+        let mut since = HashMap::new();
+        for todo in todos.iter().filter(|todo| todo.status == "in_progress") {
+            let key = todo_key(todo);
+            let started = self.in_progress_since.get(&key).copied().unwrap_or(now);
+            since.insert(key, started);
+        }
+        self.in_progress_since = since;
+        self.todos = todos;
+
+        let stall = self
+            .todos
+            .iter()
+            .filter_map(|todo| self.in_progress_for(todo, now))
+            .max()
+            .and_then(todo_stall_label);
+        let ticked = stall != self.shown_stall;
+        self.shown_stall = stall;
+        changed || ticked
+    }
+}
+
+pub fn todo_is_open(todo: &TodoItem) -> bool {
+    matches!(todo.status.as_str(), "pending" | "in_progress")
+}
+
+/// `"in_progress for 9 minutes"` once an item has been running longer than
+/// [`TODO_STALL_AFTER`].
+pub fn todo_stall_label(elapsed: Duration) -> Option<String> {
+    if elapsed < TODO_STALL_AFTER {
+        return None;
+    }
+    let minutes = elapsed.as_secs() / 60;
+    Some(if minutes >= 120 {
+        format!("in_progress for {} hours", minutes / 60)
+    } else {
+        format!("in_progress for {} minutes", minutes)
+    })
+}
+
+fn todo_key(todo: &TodoItem) -> String {
+    if todo.id.trim().is_empty() {
+        todo.content.clone()
+    } else {
+        todo.id.clone()
+    }
+}
+
+fn fingerprint(todos: &[TodoItem]) -> Vec<(&str, &str, &str)> {
+    todos
+        .iter()
+        .map(|todo| {
+            (
+                todo.id.as_str(),
+                todo.status.as_str(),
+                todo.content.as_str(),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn todo(id: &str, status: &str) -> TodoItem {
+        TodoItem {
+            content: format!("task {}", id),
+            status: status.to_string(),
+            id: id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn apply_keeps_in_progress_start_until_status_changes() {
+        let start = Instant::now();
+        let mut checklist = TodoChecklist::default();
+        assert!(checklist.apply(vec![todo("a", "in_progress"), todo("b", "pending")], start));
+        assert!(checklist.has_open_items());
+
+        let later = start + Duration::from_secs(9 * 60);
+        // Same list nine minutes on: only the stall label appearing redraws.
+        assert!(checklist.apply(vec![todo("a", "in_progress"), todo("b", "pending")], later));
+        assert!(!checklist.apply(vec![todo("a", "in_progress"), todo("b", "pending")], later));
+        assert_eq!(
+            checklist
+                .in_progress_for(&checklist.todos[0], later)
+                .and_then(todo_stall_label)
+                .as_deref(),
+            Some("in_progress for 9 minutes")
+        );
+
+        // Moving on to the next item restarts the clock for it.
+        checklist.apply(
+            vec![todo("a", "completed"), todo("b", "in_progress")],
+            later,
+        );
+        assert_eq!(
+            checklist.in_progress_for(&checklist.todos[1], later),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(checklist.in_progress_for(&checklist.todos[0], later), None);
+
+        checklist.apply(vec![todo("a", "completed"), todo("b", "completed")], later);
+        assert!(!checklist.has_open_items());
+    }
+
+    #[test]
+    fn stall_label_waits_for_threshold() {
+        assert_eq!(todo_stall_label(Duration::from_secs(4 * 60)), None);
+        assert_eq!(
+            todo_stall_label(Duration::from_secs(5 * 60)).as_deref(),
+            Some("in_progress for 5 minutes")
+        );
+        assert_eq!(
+            todo_stall_label(Duration::from_secs(3 * 60 * 60)).as_deref(),
+            Some("in_progress for 3 hours")
+        );
+    }
+}
@@ -77,6 +77,10 @@ mod prepare;
 mod smoothness;
 #[path = "ui_todo_changes.rs"]
 mod todo_changes;
+#[path = "ui_todo_checklist.rs"]
+mod todo_checklist_ui;
+#[path = "ui_tool_media.rs"]
+pub(crate) mod tool_media_ui;
 #[path = "ui_tools.rs"]
 pub(crate) mod tools_ui;
 #[path = "ui_transitions.rs"]
@@ -114,6 +118,7 @@ use diagram_pane::{
 pub(crate) use diagram_pane::{pinned_diagram_debug_json, reset_pinned_diagram_debug_snapshot};
 use file_diff_ui::active_file_diff_context;
 use file_diff_ui::draw_file_diff_view;
+use file_diff_ui::file_view_edit_context;
 #[cfg(test)]
 use file_diff_ui::{
     FileDiffCacheKey, FileDiffViewCacheEntry, file_content_signature, file_diff_cache,
@@ -130,8 +135,9 @@ use memory_ui::{group_into_tiles, render_memory_tiles, split_by_display_width};
 use messages::get_cached_message_lines;
 #[cfg_attr(test, allow(unused_imports))]
 pub(crate) use messages::{
-    render_assistant_message, render_background_task_message, render_reasoning_message,
-    render_swarm_message, render_system_message, render_tool_message, render_usage_message,
+    render_aside_message, render_assistant_message, render_background_task_message,
+    render_interjection_message, render_reasoning_message, render_swarm_message,
+    render_system_message, render_tool_message, render_usage_message,
 };
 pub use pinned_ui::{
     SidePanelDebugStats, SidePanelMermaidProbe, SidePanelMermaidProbeRect,
@@ -144,6 +150,8 @@ pub(crate) use pinned_ui::{
 use pinned_ui::{
     collect_pinned_content_cached, draw_pinned_content_cached, draw_side_panel_markdown,
 };
+pub(crate) use todo_checklist_ui::{TodoChecklistHit, todo_checklist_hit};
+use todo_checklist_ui::{clear_todo_checklist_rows, draw_todo_checklist, todo_checklist_lines};
 #[cfg(test)]
 use transitions::extract_line_text;
 #[cfg(test)]
@@ -192,6 +200,10 @@ static LAST_TOTAL_WRAPPED_LINES: AtomicUsize = AtomicUsize::new(0);
 /// handlers adopt this so manual scrolling resumes from the on-screen position.
 #[cfg(not(test))]
 static LAST_RESOLVED_CHAT_SCROLL: AtomicUsize = AtomicUsize::new(0);
+/// Text width (in columns) of the input box on the most recent frame, so
+/// Up/Down can move the cursor by soft-wrapped rows.
+#[cfg(not(test))]
+static LAST_INPUT_LINE_WIDTH: AtomicUsize = AtomicUsize::new(0);
 /// Whether the tail-follow viewport is mid catch-up slide (a large content
 /// append is being scrolled into view over several frames instead of jumping).
 /// Drives the redraw loop so the slide completes promptly.
@@ -212,6 +224,7 @@ thread_local! {
     static TEST_LAST_DIFF_PANE_MAX_SCROLL: Cell<usize> = const { Cell::new(0) };
     static TEST_LAST_TOTAL_WRAPPED_LINES: Cell<usize> = const { Cell::new(0) };
     static TEST_LAST_RESOLVED_CHAT_SCROLL: Cell<usize> = const { Cell::new(0) };
+    static TEST_LAST_INPUT_LINE_WIDTH: Cell<usize> = const { Cell::new(0) };
     static TEST_TAIL_CATCHUP_ACTIVE: Cell<bool> = const { Cell::new(false) };
     static TEST_LAST_USER_PROMPT_POSITIONS: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
     static TEST_LAST_LAYOUT: RefCell<Option<LayoutSnapshot>> = const { RefCell::new(None) };
@@ -411,6 +424,31 @@ pub(crate) fn set_last_total_wrapped_lines(value: usize) {
     }
 }
 
+/// Text width of the input box on the most recent frame.
+/// Returns 0 if no frame has been rendered yet.
+pub fn last_input_line_width() -> usize {
+    #[cfg(test)]
+    {
+        return TEST_LAST_INPUT_LINE_WIDTH.with(Cell::get);
+    }
+    #[cfg(not(test))]
+    {
+        LAST_INPUT_LINE_WIDTH.load(Ordering::Relaxed)
+    }
+}
+
+pub(crate) fn set_last_input_line_width(value: usize) {
+    #[cfg(test)]
+    {
+        TEST_LAST_INPUT_LINE_WIDTH.with(|cell| cell.set(value));
+        return;
+    }
+    #[cfg(not(test))]
+    {
+        LAST_INPUT_LINE_WIDTH.store(value, Ordering::Relaxed);
+    }
+}
+
 /// The chat scroll offset the renderer actually used on the most recent frame
 /// (after clamping and after resolving any pending history anchor).
 pub fn last_resolved_chat_scroll() -> usize {
@@ -486,10 +524,11 @@ use status_support::{
 };
 use theme_support::{
     accent_color, activity_indicator, activity_indicator_frame_index, ai_color, ai_text,
-    animated_tool_color, asap_color, blend_color, dim_color, file_link_color, header_icon_color,
-    header_name_color, header_session_color, pending_color, prompt_entry_bg_color,
-    prompt_entry_color, prompt_entry_shimmer_color, queued_color, rainbow_prompt_color,
-    system_message_color, tool_color, user_bg, user_color, user_text,
+    animated_tool_color, asap_color, aside_color, blend_color, dim_color, file_link_color,
+    header_icon_color, header_name_color, header_session_color, interjection_color, pending_color,
+    prompt_entry_bg_color, prompt_entry_color, prompt_entry_shimmer_color, queued_color,
+    rainbow_prompt_color, system_message_color, tool_color, urgent_interjection_color, user_bg,
+    user_color, user_text,
 };
 
 pub(crate) use jcode_tui_markdown::{CopyTargetKind, RawCopyTarget};
@@ -2332,6 +2371,34 @@ pub(crate) fn inline_image_body_target_from_screen(
     (rel_col < right_edge).then_some(region.hash)
 }
 
+/// File behind the image block that currently has focus in the chat pane: the
+/// inline image or diagram occupying the most visible rows, or, when nothing is
+/// drawn graphically, the first tool-media fallback box on screen.
+pub(crate) fn focused_image_path() -> Option<std::path::PathBuf> {
+    let snapshot = copy_snapshot_for_pane(crate::tui::CopySelectionPane::Chat)?;
+    let (scroll, visible_end) = (snapshot.scroll, snapshot.visible_end);
+    if let CopyViewportData::ChatFrame { prepared } = &snapshot.data {
+        let mut best: Option<(usize, u64)> = None;
+        for region in &prepared.image_regions {
+            let visible = region
+                .end_line
+                .min(visible_end)
+                .saturating_sub(region.abs_line_idx.max(scroll));
+            if visible > 0 && best.is_none_or(|(rows, _)| visible > rows) {
+                best = Some((visible, region.hash));
+            }
+        }
+        if let Some((_, hash)) = best {
+            return tool_media_ui::open_target_for_hash(hash);
+        }
+    }
+    (scroll..visible_end).find_map(|abs_line| {
+        snapshot
+            .wrapped_plain_line(abs_line)
+            .and_then(tool_media_ui::open_target_for_fallback_line)
+    })
+}
+
 /// Debug dump of the live chat snapshot's inline-image regions plus the screen
 /// coordinates of each visible expand badge, so external drivers (debug
 /// socket) can compute real click targets against the running TUI.
@@ -2453,6 +2520,18 @@ fn draw_inner(frame: &mut Frame, app: &dyn TuiState) {
         return;
     }
 
+    if let Some(view) = app.compare_view() {
+        overlays::draw_compare_overlay(frame, area, view);
+        finalize_frame_metrics(
+            app,
+            total_start,
+            Duration::ZERO,
+            total_start.elapsed(),
+            None,
+        );
+        return;
+    }
+
     if let Some(picker_cell) = app.session_picker_overlay() {
         let mut picker = picker_cell.borrow_mut();
         picker.render(frame);
@@ -2479,6 +2558,19 @@ fn draw_inner(frame: &mut Frame, app: &dyn TuiState) {
         return;
     }
 
+    if let Some(picker_cell) = app.file_picker_overlay() {
+        let mut picker = picker_cell.borrow_mut();
+        picker.render(frame);
+        finalize_frame_metrics(
+            app,
+            total_start,
+            Duration::ZERO,
+            total_start.elapsed(),
+            None,
+        );
+        return;
+    }
+
     if let Some(picker_cell) = app.account_picker_overlay() {
         let mut picker = picker_cell.borrow_mut();
         picker.render(frame);
@@ -2527,7 +2619,12 @@ fn draw_inner(frame: &mut Frame, app: &dyn TuiState) {
     } else {
         false
     };
-    let has_file_diff_edits = diff_mode.is_file() && app.has_display_edit_tool_messages();
+    // Below this width the file view would squeeze the chat column too much to
+    // read either side, so the split collapses back to chat only.
+    const FILE_VIEW_MIN_SPLIT_WIDTH: u16 = 80;
+    let has_file_diff_edits = diff_mode.is_file()
+        && (app.has_display_edit_tool_messages() || app.file_view_pin().is_some())
+        && area.width >= FILE_VIEW_MIN_SPLIT_WIDTH;
     let has_right_side_pane_content =
         has_side_panel_content || has_pinned_content || has_file_diff_edits;
     // The side panel is itself a single right-hand auxiliary surface and can render
@@ -2710,6 +2807,23 @@ fn draw_inner(frame: &mut Frame, app: &dyn TuiState) {
     };
     let swarm_strip_height = swarm_strip_lines.len() as u16;
 
+    // Live todo checklist: stacked above the swarm strip, in the same band
+    // directly above the status line.
+    let todo_checklist_rows = match app.todo_checklist() {
+        Some(checklist) if chat_area.width >= 24 => {
+            let key_label = crate::tui::keybind::todo_checklist_key_label();
+            todo_checklist_lines(
+                checklist,
+                chat_area.width as usize,
+                Instant::now(),
+                key_label.as_deref(),
+            )
+        }
+        _ => Vec::new(),
+    };
+    let todo_checklist_height = todo_checklist_rows.len() as u16;
+    let status_band_height = todo_checklist_height + swarm_strip_height;
+
     // Calculate pending messages (queued + interleave) for numbering and layout
     let pending_count = input_ui::pending_prompt_count(app);
     let queued_height = pending_count.min(3) as u16;
@@ -2776,13 +2890,13 @@ fn draw_inner(frame: &mut Frame, app: &dyn TuiState) {
     let overscroll_height: u16 = if app.chat_overscroll_active() { 1 } else { 0 };
     let fixed_height = 1
         + queued_height
-        + swarm_strip_height
+        + status_band_height
         + notification_height
         + inline_block_height
         + inline_ui_gap_height
         + input_height
         + overscroll_height
-        + donut_height; // status + queued + swarm strip + notification + inline UI + gap + input + overscroll + donut
+        + donut_height; // status + queued + todos + swarm strip + notification + inline UI + gap + input + overscroll + donut
     let available_height = chat_area.height;
     let overflows = |prepared: &PreparedChatFrame| {
         (prepared.total_wrapped_lines().max(1) as u16) + fixed_height > available_height
@@ -2860,7 +2974,7 @@ fn draw_inner(frame: &mut Frame, app: &dyn TuiState) {
             vec![
                 Constraint::Length(content_height.max(1)), // 0 Messages (exact height)
                 Constraint::Length(queued_height),         // 1 Queued messages (above status)
-                Constraint::Length(swarm_strip_height),    // 2 Swarm strip (above status)
+                Constraint::Length(status_band_height),    // 2 Todos + swarm strip (above status)
                 Constraint::Length(1),                     // 3 Status line
                 Constraint::Length(notification_height),   // 4 Notification line
                 Constraint::Length(inline_block_height),   // 5 Inline UI
@@ -2873,7 +2987,7 @@ fn draw_inner(frame: &mut Frame, app: &dyn TuiState) {
             vec![
                 Constraint::Min(3),                       // 0 Messages (scrollable)
                 Constraint::Length(queued_height),        // 1 Queued messages (above status)
-                Constraint::Length(swarm_strip_height),   // 2 Swarm strip (above status)
+                Constraint::Length(status_band_height),   // 2 Todos + swarm strip (above status)
                 Constraint::Length(1),                    // 3 Status line
                 Constraint::Length(notification_height),  // 4 Notification line
                 Constraint::Length(inline_block_height),  // 5 Inline UI
@@ -2886,10 +3000,23 @@ fn draw_inner(frame: &mut Frame, app: &dyn TuiState) {
         .split(chat_area);
     record_status_area(chunks[3]);
 
+    let strip_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(todo_checklist_height),
+            Constraint::Length(swarm_strip_height),
+        ])
+        .split(chunks[2]);
+    if todo_checklist_height > 0 {
+        draw_todo_checklist(frame, strip_chunks[0], todo_checklist_rows);
+    } else {
+        clear_todo_checklist_rows();
+    }
+
     // Draw the inline swarm strip directly above the status line if present.
     if swarm_strip_height > 0 {
-        clear_area(frame, chunks[2]);
-        frame.render_widget(Paragraph::new(swarm_strip_lines.clone()), chunks[2]);
+        clear_area(frame, strip_chunks[1]);
+        frame.render_widget(Paragraph::new(swarm_strip_lines.clone()), strip_chunks[1]);
     }
 
     // Capture layout info for visual debug
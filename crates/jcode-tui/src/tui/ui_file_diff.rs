@@ -479,20 +479,52 @@ fn find_visible_edit_tool(
     best
 }
 
-pub(super) fn active_file_diff_context(
-    prepared: &PreparedChatFrame,
-    scroll: usize,
-    visible_height: usize,
-) -> Option<ActiveFileDiffContext> {
-    let range = find_visible_edit_tool(&prepared.edit_tool_ranges, scroll, visible_height)?;
-    Some(ActiveFileDiffContext {
+fn edit_context(range: &EditToolRange) -> ActiveFileDiffContext {
+    ActiveFileDiffContext {
         edit_index: range.edit_index + 1,
         msg_index: range.msg_index,
         file_path: range.file_path.clone(),
         start_line: range.start_line,
         end_line: range.end_line,
         expandable: range.expandable,
-    })
+    }
+}
+
+pub(super) fn active_file_diff_context(
+    prepared: &PreparedChatFrame,
+    scroll: usize,
+    visible_height: usize,
+) -> Option<ActiveFileDiffContext> {
+    let range = find_visible_edit_tool(&prepared.edit_tool_ranges, scroll, visible_height)?;
+    Some(edit_context(range))
+}
+
+/// Whether an edit tool's `file_path` (absolute, or relative to the session
+/// directory) names the file pinned with `/view`.
+fn edit_targets_pin(file_path: &str, pin: &str) -> bool {
+    !file_path.is_empty() && std::path::Path::new(pin).ends_with(file_path)
+}
+
+/// The edit the file view shows: the latest edit of the pinned file, the
+/// latest edit overall while the chat follows the live tail, or the edit under
+/// the chat viewport once the user scrolls back.
+pub(super) fn file_view_edit_context(
+    prepared: &PreparedChatFrame,
+    scroll: usize,
+    visible_height: usize,
+    following: bool,
+    pin: Option<&str>,
+) -> Option<ActiveFileDiffContext> {
+    let range = match pin {
+        Some(pin) => prepared
+            .edit_tool_ranges
+            .iter()
+            .rev()
+            .find(|range| edit_targets_pin(&range.file_path, pin))?,
+        None if following => prepared.edit_tool_ranges.last()?,
+        None => return active_file_diff_context(prepared, scroll, visible_height),
+    };
+    Some(edit_context(range))
 }
 
 pub(super) fn draw_file_diff_view(
@@ -520,30 +552,51 @@ pub(super) fn draw_file_diff_view(
             .saturating_sub(visible_height)
     };
 
-    let active_context = active_file_diff_context(prepared, scroll, visible_height);
-
-    let Some(active_context) = active_context else {
-        let Some(inner) = super::draw_right_rail_chrome(
-            frame,
-            area,
-            Line::from(vec![
-                Span::styled(" file ", Style::default().fg(tool_color())),
-                Span::styled(" ⇧Tab hide ", Style::default().fg(dim_color())),
-            ]),
-            super::right_rail_border_style(false, tool_color()),
-        ) else {
+    let pin = app.file_view_pin();
+    let active_context = file_view_edit_context(
+        prepared,
+        scroll,
+        visible_height,
+        !app.auto_scroll_paused(),
+        pin.as_deref(),
+    );
+
+    let (file_path, msg_index) = match (pin, active_context.as_ref()) {
+        // A pinned file with no edits in this session shows without a diff.
+        (Some(pin), context) => (
+            pin,
+            context
+                .map(|context| context.msg_index)
+                .unwrap_or(usize::MAX),
+        ),
+        (None, Some(context)) => (context.file_path.clone(), context.msg_index),
+        (None, None) => {
+            let Some(inner) = super::draw_right_rail_chrome(
+                frame,
+                area,
+                Line::from(vec![
+                    Span::styled(" file ", Style::default().fg(tool_color())),
+                    Span::styled(" ⇧Tab hide ", Style::default().fg(dim_color())),
+                ]),
+                super::right_rail_border_style(false, tool_color()),
+            ) else {
+                return;
+            };
+            let msg = Paragraph::new(vec![
+                Line::from(Span::styled(
+                    "No edits yet",
+                    Style::default().fg(dim_color()),
+                )),
+                Line::from(Span::styled(
+                    "/view <path> pins a file",
+                    Style::default().fg(dim_color()),
+                )),
+            ]);
+            frame.render_widget(msg, inner);
             return;
-        };
-        let msg = Paragraph::new(Line::from(Span::styled(
-            "No edits visible",
-            Style::default().fg(dim_color()),
-        )));
-        frame.render_widget(msg, inner);
-        return;
+        }
     };
-
-    let file_path = &active_context.file_path;
-    let msg_index = active_context.msg_index;
+    let file_path = &file_path;
     let cache_key = FileDiffCacheKey {
         file_path: file_path.clone(),
         msg_index,
@@ -630,10 +683,18 @@ pub(super) fn draw_file_diff_view(
         format!(" {}L ", total_lines),
         Style::default().fg(dim_color()),
     ));
-    title_parts.push(Span::styled(
-        format!(" edit#{} ", active_context.edit_index),
-        Style::default().fg(file_link_color()),
-    ));
+    if app.file_view_pin().is_some() {
+        title_parts.push(Span::styled(
+            " pinned ",
+            Style::default().fg(accent_color()),
+        ));
+    }
+    if let Some(context) = active_context.as_ref() {
+        title_parts.push(Span::styled(
+            format!(" edit#{} ", context.edit_index),
+            Style::default().fg(file_link_color()),
+        ));
+    }
     title_parts.push(Span::styled(
         " ⇧Tab hide ",
         Style::default().fg(dim_color()),
@@ -654,8 +715,10 @@ pub(super) fn draw_file_diff_view(
     let effective_scroll = if pane_scroll == usize::MAX && first_change_line != usize::MAX {
         let target = first_change_line.saturating_sub(inner.height as usize / 3);
         target.min(max_scroll)
-    } else if pane_scroll == usize::MAX {
+    } else if pane_scroll == usize::MAX && active_context.is_some() {
         max_scroll
+    } else if pane_scroll == usize::MAX {
+        0
     } else {
         pane_scroll.min(max_scroll)
     };
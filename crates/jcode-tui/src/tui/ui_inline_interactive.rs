@@ -94,7 +94,17 @@ fn picker_entry_display_name(entry: &crate::tui::PickerEntry) -> String {
         default_marker.to_string()
     };
 
-    format!("{}{}", entry.name, suffix)
+    format!("{}{}{}", entry.name, suffix, model_capability_badges(entry))
+}
+
+/// Capability icons for a model row, e.g. ` ⚒ ◉`; empty for other pickers.
+fn model_capability_badges(entry: &crate::tui::PickerEntry) -> String {
+    if !matches!(entry.action, crate::tui::PickerAction::Model) {
+        return String::new();
+    }
+    let provider = entry.active_option().map(|route| route.provider.as_str());
+    let capabilities = crate::provider::resolve_model_capabilities(&entry.name, provider);
+    format!(" {}", capabilities.badges())
 }
 
 fn picker_row_marker(is_row_selected: bool, unavailable: bool, limited: bool) -> &'static str {
@@ -171,7 +181,7 @@ fn model_picker_keybind_hint(picker: &crate::tui::InlineInteractiveState) -> Opt
             .any(|entry| matches!(entry.action, crate::tui::PickerAction::Model));
     if is_runtime_model_picker {
         Some(
-            " keys: Ctrl+O set default · Ctrl+N favorite · Shift+Tab switch active model to next favorite",
+            " keys: Ctrl+O set default · Ctrl+N favorite · Shift+Tab switch active model to next favorite · ⚒ tools ⊘ no tools ◉ vision",
         )
     } else {
         None
@@ -1135,6 +1145,19 @@ mod tests {
         assert!(picker_entry_display_name(entry).contains(" default"));
     }
 
+    #[test]
+    fn picker_entry_display_name_shows_capability_icons() {
+        let mut picker = sample_picker();
+        let entry = &mut picker.entries[0];
+        assert!(picker_entry_display_name(entry).ends_with(" ⚒ ◉"));
+
+        entry.name = "o1-mini".to_string();
+        assert!(picker_entry_display_name(entry).ends_with(" ⊘"));
+
+        entry.action = crate::tui::PickerAction::LogoutAll;
+        assert!(!picker_entry_display_name(entry).contains('⊘'));
+    }
+
     #[test]
     fn model_picker_shows_default_shortcut_hint() {
         let picker = sample_picker();
@@ -2,10 +2,12 @@ use super::inline_interactive_ui::format_elapsed;
 use super::tools_ui::{get_tool_summary, summarize_batch_running_tools_compact};
 use super::visual_debug::{self, FrameCaptureBuilder};
 use super::{
-    ProcessingStatus, TuiState, accent_color, ai_color, animated_tool_color, asap_color, dim_color,
-    pending_color, queued_color, rainbow_prompt_color, user_color,
+    ProcessingStatus, TuiState, accent_color, ai_color, animated_tool_color, asap_color,
+    aside_color, dim_color, interjection_color, pending_color, queued_color, rainbow_prompt_color,
+    urgent_interjection_color, user_color,
 };
 use crate::message::ConnectionPhase;
+use crate::tui::InterjectionPriority;
 use crate::tui::app;
 use crate::tui::color_support::rgb;
 use crate::tui::detect_kv_cache_problem;
@@ -113,6 +115,7 @@ fn command_suggestion_lines(
         let (cmd, desc) = &suggestions[0];
         let base = Style::default().fg(rgb(255, 213, 128));
         let mut spans = highlight(cmd, base);
+        push_command_args_hint(&mut spans, cmd);
         spans.push(Span::styled(format!("  {}", desc), base));
         lines.push(Line::from(spans));
     } else if !suggestions.is_empty() {
@@ -142,6 +145,7 @@ fn command_suggestion_lines(
                 Style::default().fg(rgb(128, 203, 196))
             };
             let mut spans = highlight(cmd, command_style);
+            push_command_args_hint(&mut spans, cmd);
             spans.push(Span::styled(format!("  {}", desc), description_style));
             if i == 0 && window_start > 0 {
                 spans.push(Span::styled(
@@ -161,6 +165,19 @@ fn command_suggestion_lines(
     lines
 }
 
+/// Append the registry's argument hint (e.g. `[on|off|status]`) after a bare
+/// command name so the palette shows what the command accepts.
+fn push_command_args_hint(spans: &mut Vec<Span<'static>>, cmd: &str) {
+    if let Some(args) = app::command_registry::args_hint(cmd) {
+        spans.push(Span::styled(
+            format!(" {}", args),
+            Style::default()
+                .fg(dim_color())
+                .add_modifier(Modifier::ITALIC),
+        ));
+    }
+}
+
 /// Extract the slash-command portion of the typed input that should be matched
 /// against suggestion command tokens for highlighting purposes.
 fn command_suggestion_needle(input: &str) -> Option<String> {
@@ -248,6 +265,8 @@ pub(super) fn input_hint_line_height(app: &dyn TuiState) -> u16 {
     u16::from(
         shell_mode_hint(mode).is_some()
             || app.next_prompt_new_session_armed()
+            || app.interjection().is_some()
+            || app.aside_active()
             || (app.is_processing() && !app.input().is_empty()),
     )
 }
@@ -261,8 +280,15 @@ pub(super) fn input_prompt(app: &dyn TuiState) -> (&'static str, Color) {
     let mode = composer_mode(app.input(), app.is_remote_mode());
     if mode.is_shell() {
         ("$ ", shell_mode_color())
+    } else if let Some(priority) = app.interjection() {
+        match priority {
+            InterjectionPriority::Normal => ("↳ ", interjection_color()),
+            InterjectionPriority::Urgent => ("↯ ", urgent_interjection_color()),
+        }
     } else if app.is_processing() {
         ("… ", queued_color())
+    } else if app.aside_active() {
+        ("⤷ ", aside_color())
     } else if app.active_skill().is_some() {
         ("» ", accent_color())
     } else {
@@ -643,7 +669,7 @@ pub(super) fn draw_status(frame: &mut Frame, app: &dyn TuiState, area: Rect, pen
     // Idle session facts (context bar + provider) are pinned to the right edge
     // so they read like a status readout rather than left-flush body text.
     let mut right_align_facts = false;
-    let line = if let Some(build_progress) = crate::build::read_build_progress() {
+    let mut line = if let Some(build_progress) = crate::build::read_build_progress() {
         let spinner = super::activity_indicator(elapsed, 12.5);
         Line::from(vec![
             Span::styled(spinner, Style::default().fg(rgb(255, 193, 7))),
@@ -813,12 +839,18 @@ pub(super) fn draw_status(frame: &mut Frame, app: &dyn TuiState, area: Rect, pen
 
                 let anim_color = animated_tool_color(elapsed);
                 let batch_prog = app.batch_progress();
-                let is_batch = name == "batch";
+                // A subagent task list reports progress like batch once it starts.
+                let is_batch = name == "batch" || (name == "subagent" && batch_prog.is_some());
                 // For batch: compute initial total from the streaming tool call input
                 let batch_total_initial = if is_batch {
+                    let list_key = if name == "batch" {
+                        "tool_calls"
+                    } else {
+                        "tasks"
+                    };
                     app.streaming_tool_calls()
                         .last()
-                        .and_then(|tc| tc.input.get("tool_calls"))
+                        .and_then(|tc| tc.input.get(list_key))
                         .and_then(|v| v.as_array())
                         .map(|a| a.len())
                 } else {
@@ -881,6 +913,19 @@ pub(super) fn draw_status(frame: &mut Frame, app: &dyn TuiState, area: Rect, pen
                     format!(" · {}", format_elapsed(elapsed)),
                     Style::default().fg(dim_color()),
                 ));
+                let mcp_remaining = app.mcp_call_remaining();
+                if let Some(remaining) = mcp_remaining {
+                    let secs = remaining.as_secs_f32().ceil();
+                    let color = if secs <= 10.0 {
+                        rgb(255, 193, 7)
+                    } else {
+                        dim_color()
+                    };
+                    spans.push(Span::styled(
+                        format!(" · times out in {}", format_elapsed(secs)),
+                        Style::default().fg(color),
+                    ));
+                }
 
                 if let Some(problem) = kv_cache_problem {
                     let miss_tokens = problem.affected_tokens.unwrap_or(0);
@@ -897,6 +942,12 @@ pub(super) fn draw_status(frame: &mut Frame, app: &dyn TuiState, area: Rect, pen
                     ));
                 }
 
+                if mcp_remaining.is_some() {
+                    spans.push(Span::styled(
+                        " · Alt+E cancel",
+                        Style::default().fg(rgb(100, 100, 100)),
+                    ));
+                }
                 spans.push(Span::styled(
                     " · Alt+B bg",
                     Style::default().fg(rgb(100, 100, 100)),
@@ -953,6 +1004,35 @@ pub(super) fn draw_status(frame: &mut Frame, app: &dyn TuiState, area: Rect, pen
         }
     };
 
+    let pending_permissions = app.permission_inbox_count();
+    if pending_permissions > 0 {
+        // Approvals waiting anywhere (ambient cycles, other sessions); Ctrl+P
+        // opens the inbox.
+        line.spans.insert(
+            0,
+            Span::styled(
+                format!("INBOX {} ", pending_permissions),
+                Style::default().fg(rgb(240, 190, 90)).bold(),
+            ),
+        );
+    }
+
+    if app.session_has_instructions() {
+        // Standing `/instructions` ride along with every turn; keep that visible.
+        line.spans.insert(
+            0,
+            Span::styled("INSTR ", Style::default().fg(rgb(140, 180, 255)).bold()),
+        );
+    }
+
+    if app.session_safe_mode() {
+        // `jcode --safe` stays visible whatever else the status line shows.
+        line.spans.insert(
+            0,
+            Span::styled("SAFE ", Style::default().fg(rgb(120, 220, 140)).bold()),
+        );
+    }
+
     crate::memory::check_staleness();
 
     let aligned_line = if app.centered_mode() {
@@ -1490,6 +1570,16 @@ pub(super) fn build_notification_spans(app: &dyn TuiState) -> Vec<Span<'static>>
         ));
     }
 
+    // Pending migration: stays up until the user migrates or defers, so the
+    // restart never comes as a surprise.
+    if let Some(banner) = app.migration_banner() {
+        push_sep(&mut spans);
+        spans.push(Span::styled(
+            banner,
+            Style::default().fg(rgb(255, 193, 7)).bold(),
+        ));
+    }
+
     if let Some(notice) = app.status_notice() {
         push_sep(&mut spans);
         spans.push(Span::styled(
@@ -1689,6 +1779,15 @@ pub(super) fn draw_overscroll_status(frame: &mut Frame, app: &dyn TuiState, area
         }
         spans.push(Span::styled(" ", Style::default().fg(rgb(140, 180, 255))));
         spans.push(Span::styled(dir, Style::default().fg(rgb(140, 140, 150))));
+        if let Some(scope) = app
+            .tool_scope()
+            .map(|scope| overscroll_scope_label(app.working_dir().as_deref(), &scope))
+        {
+            spans.push(Span::styled(
+                format!(" ▸ {}", scope),
+                Style::default().fg(rgb(140, 180, 255)),
+            ));
+        }
     }
 
     let total_width = area.width as usize;
@@ -1800,6 +1899,16 @@ fn overscroll_dir_label(path: &str) -> Option<String> {
     session_facts::dir_label_short(path)
 }
 
+/// The `/cd` scope relative to the working directory, e.g. `packages/web`.
+fn overscroll_scope_label(working_dir: Option<&str>, scope: &str) -> String {
+    working_dir
+        .and_then(|dir| std::path::Path::new(scope).strip_prefix(dir).ok())
+        .map(|relative| relative.display().to_string())
+        .filter(|relative| !relative.is_empty())
+        .or_else(|| session_facts::dir_label_short(scope))
+        .unwrap_or_else(|| scope.to_string())
+}
+
 /// Placeholder header strings used during remote startup; not real model names.
 fn overscroll_is_placeholder(model: &str) -> bool {
     let m = model.trim().to_ascii_lowercase();
@@ -1966,6 +2075,7 @@ pub(super) fn draw_input(
     if line_width == 0 {
         return;
     }
+    super::set_last_input_line_width(line_width);
 
     let (all_lines, cursor_line, cursor_col) = wrap_input_text(
         input_text,
@@ -1990,6 +2100,20 @@ pub(super) fn draw_input(
             shell_hint,
             Style::default().fg(shell_mode_color()),
         )));
+    } else if let Some(priority) = app.interjection() {
+        hint_shown = true;
+        let (hint, color) = match priority {
+            InterjectionPriority::Normal => (
+                "  ↳ Interjection · lands after the current tools · Tab: urgent · Esc: close",
+                interjection_color(),
+            ),
+            InterjectionPriority::Urgent => (
+                "  ↯ Urgent interjection · skips the remaining tools · Tab: normal · Esc: close",
+                urgent_interjection_color(),
+            ),
+        };
+        hint_line = Some(hint.trim().to_string());
+        lines.push(Line::from(Span::styled(hint, Style::default().fg(color))));
     } else if app.next_prompt_new_session_armed() {
         hint_shown = true;
         let hint = "  ↗ Next prompt opens a new session";
@@ -1998,6 +2122,14 @@ pub(super) fn draw_input(
             hint,
             Style::default().fg(rgb(120, 200, 255)),
         )));
+    } else if app.aside_active() {
+        hint_shown = true;
+        let hint = "  ⤷ Aside · read-only scratch thread · /aside end: hand back a summary";
+        hint_line = Some(hint.trim().to_string());
+        lines.push(Line::from(Span::styled(
+            hint,
+            Style::default().fg(aside_color()),
+        )));
     } else if app.is_processing() && !input_text.is_empty() {
         hint_shown = true;
         let hint = if app.queue_mode() {
@@ -2107,74 +2239,20 @@ struct WrappedInputSegment {
     display_width: usize,
 }
 
+/// Wrap the draft into display rows. Shares its wrapping with
+/// [`crate::tui::line_editor::wrap_rows`] so Up/Down cursor motion lands on the
+/// rows the user actually sees.
 fn wrap_input_segments(input: &str, line_width: usize) -> Vec<WrappedInputSegment> {
-    use unicode_width::UnicodeWidthChar;
-
     let chars: Vec<char> = input.chars().collect();
-    if chars.is_empty() {
-        return vec![WrappedInputSegment {
-            text: String::new(),
-            start_char: 0,
-            end_char: 0,
-            display_width: 0,
-        }];
-    }
-
-    let mut segments = Vec::new();
-    let mut pos = 0;
-    let mut char_count = 0;
-
-    while pos <= chars.len() {
-        let newline_pos = chars[pos..].iter().position(|&c| c == '\n');
-        let segment_end = match newline_pos {
-            Some(rel_pos) => pos + rel_pos,
-            None => chars.len(),
-        };
-
-        let segment = &chars[pos..segment_end];
-        let mut seg_pos = 0;
-        loop {
-            let mut display_width = 0;
-            let mut end = seg_pos;
-            while end < segment.len() {
-                let cw = segment[end].width().unwrap_or(0);
-                if display_width + cw > line_width {
-                    break;
-                }
-                display_width += cw;
-                end += 1;
-            }
-            if end == seg_pos && seg_pos < segment.len() {
-                end = seg_pos + 1;
-                display_width = segment[seg_pos].width().unwrap_or(0);
-            }
-
-            let text: String = segment[seg_pos..end].iter().collect();
-            let start_char = char_count;
-            let end_char = char_count + (end - seg_pos);
-            segments.push(WrappedInputSegment {
-                text,
-                start_char,
-                end_char,
-                display_width,
-            });
-            char_count = end_char;
-
-            if end >= segment.len() {
-                break;
-            }
-            seg_pos = end;
-        }
-
-        if newline_pos.is_some() {
-            char_count += 1;
-            pos = segment_end + 1;
-        } else {
-            break;
-        }
-    }
-
-    segments
+    crate::tui::line_editor::wrap_rows(input, line_width)
+        .into_iter()
+        .map(|row| WrappedInputSegment {
+            text: chars[row.start_char..row.end_char].iter().collect(),
+            start_char: row.start_char,
+            end_char: row.end_char,
+            display_width: row.display_width,
+        })
+        .collect()
 }
 
 fn cursor_col_for_segment(segment: &WrappedInputSegment, cursor_char_pos: usize) -> usize {
@@ -2420,7 +2498,8 @@ fn format_idle_input_hint(model: Option<String>, dir: Option<String>) -> Option<
 
 /// Idle status-line facts: surface the session facts that are *not* already
 /// shown by the info-widget HUD nor by the idle input hint (which owns model
-/// and dir). The status line therefore fills in context usage and provider.
+/// and dir). The status line therefore fills in the active profile, context
+/// usage, and provider.
 ///
 /// Returns styled spans (right-aligned by the caller) including a short glyph
 /// context bar that mirrors the overscroll bar but at a much shorter length.
@@ -2434,6 +2513,13 @@ fn idle_status_facts(app: &dyn TuiState) -> Option<Vec<Span<'static>>> {
     let mut spans: Vec<Span<'static>> = Vec::new();
     let sep = || Span::styled(" · ", Style::default().fg(rgb(100, 100, 110)));
 
+    if let Some(profile) = app.session_profile() {
+        spans.push(Span::styled(
+            format!("profile:{}", profile),
+            Style::default().fg(rgb(140, 180, 255)),
+        ));
+    }
+
     if ledger.is_missing(Fact::Provider) {
         let provider = data
             .provider_name
@@ -2441,6 +2527,9 @@ fn idle_status_facts(app: &dyn TuiState) -> Option<Vec<Span<'static>>> {
             .filter(|p| !p.is_empty())
             .unwrap_or_else(|| app.provider_name());
         if !provider.is_empty() && !overscroll_is_runtime_placeholder(&provider) {
+            if !spans.is_empty() {
+                spans.push(sep());
+            }
             spans.push(Span::styled(
                 overscroll_provider_display(&provider),
                 Style::default().fg(dim_color()),
@@ -2467,6 +2556,15 @@ fn idle_status_facts(app: &dyn TuiState) -> Option<Vec<Span<'static>>> {
         spans.extend(overscroll_context_bar(used, limit, 5));
     }
 
+    if data.auth_method == crate::tui::info_widget::AuthMethod::CopilotOAuth
+        && let Some(label) = crate::copilot_usage::premium_status_label()
+    {
+        if !spans.is_empty() {
+            spans.push(sep());
+        }
+        spans.push(Span::styled(label, Style::default().fg(dim_color())));
+    }
+
     if spans.is_empty() { None } else { Some(spans) }
 }
 
@@ -698,6 +698,37 @@ pub(crate) fn render_usage_message(
     )
 }
 
+/// Render an `/aside` card: the aside opening, its summary handed back to the
+/// main conversation, or a discard notice, framed in the aside color.
+pub(crate) fn render_aside_message(
+    msg: &DisplayMessage,
+    width: u16,
+    _diff_mode: crate::config::DiffDisplayMode,
+) -> Vec<Line<'static>> {
+    let border_style = Style::default().fg(aside_color());
+    let title = msg.title.as_deref().unwrap_or("Aside");
+    let content_width = (width.saturating_sub(8).max(24) as usize).min(96);
+    let text_style = Style::default().fg(dim_color());
+
+    let mut content = Vec::new();
+    for raw_line in msg.content.lines() {
+        let chunks = split_by_display_width(raw_line, content_width);
+        if chunks.is_empty() {
+            content.push(Line::from(""));
+        }
+        for chunk in chunks {
+            content.push(Line::from(Span::styled(chunk, text_style)));
+        }
+    }
+
+    render_rounded_box(
+        title,
+        content,
+        width.saturating_sub(4) as usize,
+        border_style,
+    )
+}
+
 pub(crate) fn render_overnight_message(
     msg: &DisplayMessage,
     width: u16,
@@ -1796,6 +1827,68 @@ pub(crate) fn render_swarm_message(
     wrapped_lines
 }
 
+/// Render a message the user interjected into a running turn. The text is
+/// kept verbatim; the title, when present, notes tools skipped by an urgent
+/// interjection.
+pub(crate) fn render_interjection_message(
+    msg: &DisplayMessage,
+    width: u16,
+    _diff_mode: crate::config::DiffDisplayMode,
+) -> Vec<Line<'static>> {
+    let centered = markdown::center_code_blocks();
+    let skipped = msg
+        .title
+        .as_deref()
+        .map(str::trim)
+        .filter(|t| !t.is_empty());
+    let rail_color = if skipped.is_some() {
+        urgent_interjection_color()
+    } else {
+        interjection_color()
+    };
+    let rail_style = Style::default().fg(rail_color);
+    let body_style = Style::default().fg(user_text());
+
+    let content_width = if centered {
+        centered_wrap_width(width.saturating_sub(6), true, 96)
+    } else {
+        width.saturating_sub(4) as usize
+    }
+    .max(1);
+
+    let mut header = vec![
+        Span::styled("│ ", rail_style),
+        Span::styled(
+            if skipped.is_some() {
+                "↯ Interjection"
+            } else {
+                "↳ Interjection"
+            },
+            rail_style.bold(),
+        ),
+    ];
+    if let Some(skipped) = skipped {
+        header.push(Span::styled(format!(" · {}", skipped), rail_style));
+    }
+
+    let mut lines = vec![Line::from(header)];
+    for line in render_plaintext_lines(msg.content.trim(), content_width) {
+        let mut spans = vec![Span::styled("│ ", rail_style)];
+        spans.extend(
+            line.spans
+                .into_iter()
+                .map(|span| Span::styled(span.content, body_style)),
+        );
+        lines.push(Line::from(spans));
+    }
+
+    if centered {
+        left_pad_lines_for_centered_mode(&mut lines, width);
+    }
+
+    lines
+}
+
 pub(super) fn edit_tool_inline_diff_is_expandable(
     tc: &ToolCall,
     content: &str,
@@ -2146,6 +2239,15 @@ pub(crate) fn render_tool_message(
         }
     }
 
+    // Mermaid fences in the result render as diagrams under the summary line,
+    // behind the same opt-in gate as assistant-authored diagrams.
+    if !is_error && !is_edit_tool && markdown::mermaid_rendering_enabled() {
+        let diagram_width = u16::try_from(row_width).unwrap_or(u16::MAX);
+        for source in super::tool_media_ui::mermaid_sources(&msg.content) {
+            lines.extend(super::tool_media_ui::mermaid_lines(&source, diagram_width));
+        }
+    }
+
     if diff_mode.is_inline() && is_edit_tool {
         let full_inline = diff_mode.is_full_inline();
         let file_path_for_ext = tc
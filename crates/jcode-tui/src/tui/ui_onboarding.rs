@@ -193,6 +193,59 @@ fn import_two_column_lines(prompt: &crate::tui::LoginImportPrompt) -> Vec<Line<'
     out
 }
 
+/// Render the first-run settings rows: a `> ` gutter on the focused row,
+/// labels padded to one column, and the focused value wrapped in `< >` to
+/// show Left/Right change it. Rows that cannot change are dimmed.
+fn setup_form_lines(prompt: &crate::tui::SetupPrompt) -> Vec<Line<'static>> {
+    let label_width = prompt
+        .rows
+        .iter()
+        .map(|row| row.label.chars().count())
+        .max()
+        .unwrap_or(0);
+    let value_width = prompt
+        .rows
+        .iter()
+        .map(|row| row.value.chars().count() + 4)
+        .max()
+        .unwrap_or(0);
+
+    prompt
+        .rows
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let focused = !prompt.continue_focused && i == prompt.cursor;
+            let value = if focused && row.editable {
+                format!("< {} >", row.value)
+            } else {
+                format!("  {}  ", row.value)
+            };
+            let value_style = if !row.editable {
+                Style::default().fg(dim_color())
+            } else if focused {
+                Style::default()
+                    .fg(welcome_accent())
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(rgb(210, 210, 210))
+            };
+            Line::from(vec![
+                Span::styled(
+                    if focused { "> " } else { "  " },
+                    Style::default().fg(welcome_accent()),
+                ),
+                Span::styled(
+                    format!("{:<label_width$}  ", row.label),
+                    Style::default().fg(dim_color()),
+                ),
+                Span::styled(format!("{:<value_width$}", value), value_style),
+            ])
+            .alignment(Alignment::Center)
+        })
+        .collect()
+}
+
 /// Grayed telemetry notice shown at the very top of the onboarding screen.
 fn telemetry_header_lines(width: u16) -> Vec<Line<'static>> {
     let align = Alignment::Center;
@@ -425,6 +478,31 @@ fn welcome_body_lines(app: &dyn TuiState) -> Vec<Line<'static>> {
             push_esc_skip_hint(&mut lines, align);
             return lines;
         }
+        OnboardingWelcomeKind::Setup(prompt) => {
+            lines.push(
+                Line::from(Span::styled(
+                    "A few settings before you start",
+                    Style::default()
+                        .fg(welcome_accent())
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .alignment(align),
+            );
+            lines.push(Line::from(""));
+            lines.extend(setup_form_lines(&prompt));
+            lines.push(Line::from(""));
+            // Continue starts focused, so one Enter keeps the defaults.
+            lines.push(continue_pill_line(prompt.continue_focused, align));
+            lines.push(Line::from(""));
+            lines.push(
+                Line::from(Span::styled(
+                    "Esc to skip (nothing is written; change settings later with /config).",
+                    Style::default().fg(dim_color()),
+                ))
+                .alignment(align),
+            );
+            return lines;
+        }
         OnboardingWelcomeKind::Suggestions => {}
     }
 
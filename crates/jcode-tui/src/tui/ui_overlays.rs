@@ -8,7 +8,7 @@ use crate::tui::TuiState;
 use crate::tui::info_widget::WidgetPlacement;
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Paragraph, Wrap},
 };
 
 fn selection_bg_for(base_bg: Option<Color>) -> Color {
@@ -288,25 +288,45 @@ pub(super) fn draw_help_overlay(frame: &mut Frame, area: Rect, scroll: usize, ap
     lines.push(help_entry("/config", "Show active configuration"));
     lines.push(help_entry("/config init", "Create default config file"));
     lines.push(help_entry("/config edit", "Open config in $EDITOR"));
+    lines.push(help_entry(
+        "/tee <path>|off",
+        "Append each turn's final response to a file",
+    ));
+    lines.push(help_entry(
+        "/autocommit [on|off]",
+        "Commit the agent's file changes after each turn",
+    ));
     lines.push(help_entry("/dictate", "Run configured external dictation"));
     lines.push(help_entry(
         "/git [status]",
         "Show branch and working tree status for the repo",
     ));
     lines.push(help_entry(
-        "/context",
-        "Show the full session context snapshot",
+        "/context [memories]",
+        "Show the session context, or the last memory injection",
+    ));
+    lines.push(help_entry(
+        "/remember [n]",
+        "Save an assistant answer as a memory",
     ));
     lines.push(help_entry(
         "/skills",
         "Show loaded skills and jcode-endorsed recommendations",
     ));
     lines.push(help_entry("/info", "Show session info and token usage"));
+    lines.push(help_entry(
+        "/turninfo",
+        "Show agent limits and how close the last request came",
+    ));
     lines.push(help_entry(
         "/keys",
         "Show keybinding conflicts with your terminal/OS",
     ));
     lines.push(help_entry("/usage", "Show connected provider usage limits"));
+    lines.push(help_entry(
+        "/premium",
+        "Copilot premium mode for this session (normal/conserve/off)",
+    ));
     lines.push(help_entry("/version", "Show version and build details"));
     lines.push(help_entry(
         "/changelog",
@@ -338,7 +358,11 @@ pub(super) fn draw_help_overlay(frame: &mut Frame, area: Rect, scroll: usize, ap
     ));
     lines.push(help_entry(
         "/plan [goal]",
-        "Draft a plan-only proposal as a plan card (no edits)",
+        "Plan read-only, then approve/edit/reject",
+    ));
+    lines.push(help_entry(
+        "/aside [question]",
+        "Side question in a read-only scratch thread (end/discard)",
     ));
     lines.push(help_entry(
         "/improve",
@@ -360,6 +384,10 @@ pub(super) fn draw_help_overlay(frame: &mut Frame, area: Rect, scroll: usize, ap
         "/splitview [on|off|status]",
         "Mirror the current chat in the side panel",
     ));
+    lines.push(help_entry(
+        "/view [<path>|follow|off|save]",
+        "Live file view beside the chat (follows edits or a pinned file)",
+    ));
     lines.push(help_entry(
         "/fork [prompt]",
         "Fork session into a new window (alias: /split)",
@@ -381,6 +409,10 @@ pub(super) fn draw_help_overlay(frame: &mut Frame, area: Rect, scroll: usize, ap
         "Return to the previous Catch Up source session",
     ));
     lines.push(help_entry("/resume", "Browse and resume previous sessions"));
+    lines.push(help_entry(
+        "/session stats",
+        "Show responses, tokens and time per model",
+    ));
     lines.push(help_entry(
         "/catchup [next]",
         "Jump into finished sessions with a side-panel brief",
@@ -394,6 +426,18 @@ pub(super) fn draw_help_overlay(frame: &mut Frame, area: Rect, scroll: usize, ap
         "/rename <name>|--clear",
         "Set or clear current session name",
     ));
+    lines.push(help_entry(
+        "/root [add <path>|remove <name>]",
+        "Attach or detach extra workspace roots",
+    ));
+    lines.push(help_entry(
+        "/cd [path|-]",
+        "Scope tools to a subdirectory of the workspace",
+    ));
+    lines.push(help_entry(
+        "/instructions [text|add|edit|clear]",
+        "Standing instructions sent with every turn",
+    ));
     lines.push(help_entry(
         "/unsave",
         "Remove bookmark from current session",
@@ -582,6 +626,11 @@ pub(super) fn draw_help_overlay(frame: &mut Frame, area: Rect, scroll: usize, ap
     lines.push(key_entry("Ctrl+Backspace", "Delete previous word in input"));
     lines.push(key_entry("Ctrl+B / Ctrl+F", "Move by word left / right"));
     lines.push(key_entry("Ctrl+Left / Right", "Move by word left / right"));
+    lines.push(key_entry(
+        "Ctrl+K / Ctrl+U / Ctrl+W",
+        "Kill to line end / line start / previous word",
+    ));
+    lines.push(key_entry("Ctrl+Y", "Yank last killed text"));
     lines.push(key_entry(
         "Shift+Enter / Alt+Enter",
         "Insert newline in input",
@@ -603,7 +652,15 @@ pub(super) fn draw_help_overlay(frame: &mut Frame, area: Rect, scroll: usize, ap
     ));
     lines.push(key_entry("Alt+Y", "Toggle chat selection/copy mode"));
     lines.push(key_entry("Alt+S", "Toggle typing scroll lock"));
-    lines.push(key_entry("Ctrl+P", "Toggle auto-poke for incomplete todos"));
+    lines.push(key_entry(
+        "Ctrl+P",
+        "Open the permission inbox when requests are pending, else toggle auto-poke",
+    ));
+    lines.push(key_entry(
+        "Alt+X",
+        "Collapse the todo checklist (click an item to copy it)",
+    ));
+    lines.push(key_entry("Alt+E", "Cancel the running MCP tool call"));
     lines.push(key_entry(
         &crate::tui::keybind::effort_switch_keys_label(),
         "Cycle effort (reasoning + swarm)",
@@ -620,6 +677,21 @@ pub(super) fn draw_help_overlay(frame: &mut Frame, area: Rect, scroll: usize, ap
     if let Some(label) = crate::tui::keybind::load_open_resume_key().label {
         lines.push(key_entry(&label, "Open the /resume session picker"));
     }
+    if let Some(label) = crate::tui::keybind::load_image_open_key().label {
+        lines.push(key_entry(
+            &label,
+            "Open the focused image in the system viewer",
+        ));
+    }
+    if let Some(label) = crate::tui::keybind::load_remember_message_key().label {
+        lines.push(key_entry(&label, "Save the focused answer as a memory"));
+    }
+    if let Some(label) = crate::tui::keybind::load_copy_message_key().label {
+        lines.push(key_entry(&label, "Copy the focused answer or tool output"));
+    }
+    if let Some(label) = crate::tui::keybind::load_file_picker_key().label {
+        lines.push(key_entry(&label, "Browse files to attach or @mention"));
+    }
     if let Some(label) = crate::tui::keybind::load_new_terminal_key().label {
         lines.push(key_entry(
             &label,
@@ -736,6 +808,100 @@ fn model_status_line_style(raw: &str, default: Style) -> Style {
     }
 }
 
+pub(super) fn draw_compare_overlay(frame: &mut Frame, area: Rect, view: &crate::tui::CompareView) {
+    clear_area(frame, area);
+
+    let dim_style = Style::default().fg(dim_color());
+    let outer = Block::default()
+        .borders(Borders::ALL)
+        .title(Span::styled(
+            " /compare ",
+            Style::default()
+                .fg(accent_color())
+                .add_modifier(Modifier::BOLD),
+        ))
+        .title_bottom(Line::from(Span::styled(
+            " ←/→ or 1-9 pick · Enter keep · j/k scroll · Esc discard both ",
+            dim_style,
+        )))
+        .border_style(dim_style);
+    let inner = outer.inner(area);
+    frame.render_widget(outer, area);
+    if inner.height < 3 || view.runs.is_empty() {
+        return;
+    }
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(1)])
+        .split(inner);
+    let prompt = view.prompt.lines().next().unwrap_or_default();
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(" › ", Style::default().fg(user_color())),
+            Span::styled(prompt.to_string(), Style::default().fg(user_text())),
+        ])),
+        rows[0],
+    );
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(
+            view.runs
+                .iter()
+                .map(|_| Constraint::Ratio(1, view.runs.len() as u32)),
+        )
+        .split(rows[1]);
+    for (index, (run, column)) in view.runs.iter().zip(columns.iter()).enumerate() {
+        let selected = index == view.selected;
+        let border = if selected {
+            Style::default().fg(accent_color())
+        } else {
+            dim_style
+        };
+        let title = format!(" {} · {} ", index + 1, run.model);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(border)
+            .title(Span::styled(
+                title,
+                border.add_modifier(if selected {
+                    Modifier::BOLD
+                } else {
+                    Modifier::empty()
+                }),
+            ))
+            .title_bottom(Line::from(Span::styled(
+                format!(" {} ", run.stats_line()),
+                dim_style,
+            )));
+        let mut lines: Vec<Line<'static>> = run
+            .text
+            .lines()
+            .map(|line| {
+                Line::from(Span::styled(
+                    line.to_string(),
+                    Style::default().fg(ai_text()),
+                ))
+            })
+            .collect();
+        if let Some(error) = &run.error {
+            if !lines.is_empty() {
+                lines.push(Line::from(""));
+            }
+            lines.push(Line::from(Span::styled(
+                format!("error: {}", error),
+                Style::default().fg(rgb(240, 110, 110)),
+            )));
+        }
+        let paragraph = Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false })
+            .scroll((view.scroll.min(u16::MAX as usize) as u16, 0));
+        frame.render_widget(paragraph, *column);
+    }
+}
+
 pub(super) fn draw_debug_overlay(
     frame: &mut Frame,
     placements: &[WidgetPlacement],
@@ -187,7 +187,7 @@ fn default_message_alignment(role: &str, centered: bool) -> ratatui::layout::Ali
     if centered
         && !matches!(
             role,
-            "tool" | "system" | "swarm" | "background_task" | "overnight"
+            "tool" | "system" | "swarm" | "background_task" | "overnight" | "interjection"
         )
     {
         ratatui::layout::Alignment::Center
@@ -424,15 +424,17 @@ fn empty_prepared_messages() -> PreparedMessages {
     }
 }
 
-fn active_batch_progress(app: &dyn TuiState) -> Option<crate::bus::BatchProgress> {
+fn active_batch_progress(app: &dyn TuiState) -> Option<(String, crate::bus::BatchProgress)> {
     match app.status() {
-        ProcessingStatus::RunningTool(name) if name == "batch" => app.batch_progress(),
+        ProcessingStatus::RunningTool(name) if tools_ui::tool_reports_batch_progress(&name) => {
+            app.batch_progress().map(|progress| (name, progress))
+        }
         _ => None,
     }
 }
 
 pub(super) fn active_batch_progress_hash(app: &dyn TuiState) -> u64 {
-    let Some(progress) = active_batch_progress(app) else {
+    let Some((_, progress)) = active_batch_progress(app) else {
         return 0;
     };
 
@@ -465,7 +467,7 @@ fn prepare_active_batch_progress(
     width: u16,
     prefix_blank: bool,
 ) -> PreparedMessages {
-    let Some(progress) = active_batch_progress(app) else {
+    let Some((tool_name, progress)) = active_batch_progress(app) else {
         return empty_prepared_messages();
     };
 
@@ -486,7 +488,7 @@ fn prepare_active_batch_progress(
 
     let mut header = vec![
         Span::styled(format!("  {} ", spinner), Style::default().fg(accent)),
-        Span::styled("batch", Style::default().fg(tool_color())),
+        Span::styled(tool_name, Style::default().fg(tool_color())),
         Span::styled(
             format!(" · {}/{} done", progress.completed, progress.total),
             Style::default().fg(dim_color()),
@@ -1144,6 +1146,22 @@ fn render_message_into(
                     ) {
                         acc.push_auto(line);
                     }
+                } else if app.pin_images()
+                    && !is_edit_tool
+                    && !tools_ui::tool_output_looks_failed(&msg.content)
+                {
+                    // Images referenced in the result text (paths or base64
+                    // payloads). Skipped when the tool already attached its
+                    // images, which would otherwise show twice.
+                    let images = super::tool_media_ui::resolve_images(&tc.id, &msg.content);
+                    for line in super::tool_media_ui::image_lines(
+                        &images,
+                        width,
+                        ctx.inline_images_visible,
+                        &super::inline_image_ui::AppExpandLevels(app),
+                    ) {
+                        acc.push_auto(line);
+                    }
                 }
             }
         }
@@ -1183,6 +1201,18 @@ fn render_message_into(
                 acc.push_auto(align_if_unset(line, align));
             }
         }
+        "interjection" => {
+            let content_width = width.saturating_sub(4);
+            let cached = get_cached_message_lines(
+                msg,
+                content_width,
+                app.diff_mode(),
+                render_interjection_message,
+            );
+            for line in cached {
+                acc.push_auto(align_if_unset(line, align));
+            }
+        }
         "swarm" => {
             let content_width = width.saturating_sub(4);
             let cached =
@@ -1239,6 +1269,14 @@ fn render_message_into(
                 acc.push_auto(align_if_unset(line, align));
             }
         }
+        "aside" => {
+            let content_width = width.saturating_sub(4);
+            let cached =
+                get_cached_message_lines(msg, content_width, app.diff_mode(), render_aside_message);
+            for line in cached {
+                acc.push_auto(align_if_unset(line, align));
+            }
+        }
         "overnight" => {
             let content_width = width.saturating_sub(4);
             let cached = get_cached_message_lines(
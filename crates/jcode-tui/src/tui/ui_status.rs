@@ -172,12 +172,12 @@ pub(super) fn format_status_for_debug(app: &dyn TuiState) -> String {
             format!("Waiting for network to retry ({})", listener)
         }
         ProcessingStatus::RunningTool(ref name) => {
-            if name == "batch"
+            if tools_ui::tool_reports_batch_progress(name)
                 && let Some(progress) = app.batch_progress()
             {
                 let completed = progress.completed;
                 let total = progress.total;
-                let mut status = format!("Running batch: {}/{} done", completed, total);
+                let mut status = format!("Running {}: {}/{} done", name, completed, total);
                 if let Some(running) =
                     tools_ui::summarize_batch_running_tools_compact(&progress.running)
                 {
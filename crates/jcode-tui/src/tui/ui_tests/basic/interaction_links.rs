@@ -127,3 +127,51 @@ fn test_active_file_diff_context_resolves_visible_edit() {
     assert_eq!(active.msg_index, 7);
     assert_eq!(active.file_path, "src/two.rs");
 }
+
+#[test]
+fn test_file_view_edit_context_follows_latest_or_pinned_edit() {
+    let prepared = PreparedMessages {
+        wrapped_lines: vec![Line::from("a"); 20],
+        wrapped_plain_lines: Arc::new(vec!["a".to_string(); 20]),
+        wrapped_copy_offsets: Arc::new(vec![0; 20]),
+        raw_plain_lines: Arc::new(Vec::new()),
+        wrapped_line_map: Arc::new(Vec::new()),
+        wrapped_user_indices: Vec::new(),
+        wrapped_user_prompt_starts: Vec::new(),
+        wrapped_user_prompt_ends: Vec::new(),
+        user_prompt_texts: Vec::new(),
+        image_regions: Vec::new(),
+        edit_tool_ranges: vec![
+            EditToolRange {
+                edit_index: 0,
+                msg_index: 3,
+                file_path: "src/one.rs".to_string(),
+                start_line: 2,
+                end_line: 5,
+                expandable: true,
+            },
+            EditToolRange {
+                edit_index: 1,
+                msg_index: 7,
+                file_path: "src/two.rs".to_string(),
+                start_line: 10,
+                end_line: 14,
+                expandable: true,
+            },
+        ],
+        copy_targets: Vec::new(),
+        message_boundaries: Vec::new(),
+    };
+
+    let prepared = PreparedChatFrame::from_single(Arc::new(prepared));
+    let latest = file_view_edit_context(&prepared, 0, 4, true, None).expect("latest edit");
+    assert_eq!(latest.file_path, "src/two.rs");
+
+    let scrolled = file_view_edit_context(&prepared, 0, 4, false, None).expect("visible edit");
+    assert_eq!(scrolled.file_path, "src/one.rs");
+
+    let pinned = file_view_edit_context(&prepared, 9, 4, true, Some("/repo/src/one.rs"))
+        .expect("pinned file edit");
+    assert_eq!(pinned.msg_index, 3);
+    assert!(file_view_edit_context(&prepared, 9, 4, true, Some("/repo/src/three.rs")).is_none());
+}
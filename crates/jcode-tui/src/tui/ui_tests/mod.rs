@@ -145,6 +145,9 @@ struct TestState {
     swarm_members: Vec<crate::protocol::SwarmMemberStatus>,
     swarm_panel_selected: usize,
     swarm_panel_focused: bool,
+    todo_checklist: Option<crate::tui::todo_checklist::TodoChecklist>,
+    safe_mode: bool,
+    permission_inbox: usize,
 }
 
 impl crate::tui::TuiState for TestState {
@@ -307,6 +310,9 @@ impl crate::tui::TuiState for TestState {
     fn swarm_panel_focused(&self) -> bool {
         self.swarm_panel_focused
     }
+    fn todo_checklist(&self) -> Option<&crate::tui::todo_checklist::TodoChecklist> {
+        self.todo_checklist.as_ref()
+    }
     fn remote_startup_phase_active(&self) -> bool {
         self.remote_startup_phase_active
     }
@@ -445,6 +451,15 @@ impl crate::tui::TuiState for TestState {
     fn working_dir(&self) -> Option<String> {
         None
     }
+    fn session_profile(&self) -> Option<String> {
+        None
+    }
+    fn session_safe_mode(&self) -> bool {
+        self.safe_mode
+    }
+    fn permission_inbox_count(&self) -> usize {
+        self.permission_inbox
+    }
     fn now_millis(&self) -> u64 {
         0
     }
@@ -495,7 +510,11 @@ mod onboarding;
 mod prepared_messages_tests;
 #[path = "rendering.rs"]
 mod rendering;
+#[path = "safe_mode.rs"]
+mod safe_mode;
 #[path = "swarm_buffer.rs"]
 mod swarm_buffer;
+#[path = "todo_checklist.rs"]
+mod todo_checklist;
 #[path = "tools.rs"]
 mod tools;
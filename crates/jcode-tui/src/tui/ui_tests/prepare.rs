@@ -348,6 +348,56 @@ fn test_prepare_messages_live_batch_spinner_advances_between_frames() {
     );
 }
 
+#[test]
+fn test_prepare_messages_live_parallel_subagents_use_subagent_header() {
+    let subcall = |index: usize, description: &str, state| crate::bus::BatchSubcallProgress {
+        index,
+        tool_call: ToolCall {
+            id: format!("batch-{index}-subagent"),
+            name: "subagent".to_string(),
+            input: serde_json::json!({"description": description, "subagent_type": "general"}),
+            intent: None,
+            thought_signature: None,
+        },
+        state,
+    };
+    let state = TestState {
+        status: ProcessingStatus::RunningTool("subagent".to_string()),
+        anim_elapsed: 0.0,
+        batch_progress: Some(crate::bus::BatchProgress {
+            session_id: "s".to_string(),
+            tool_call_id: "tc".to_string(),
+            total: 2,
+            completed: 0,
+            last_completed: None,
+            running: Vec::new(),
+            subcalls: vec![
+                subcall(1, "Audit auth", crate::bus::BatchSubcallState::Running),
+                subcall(2, "Audit billing", crate::bus::BatchSubcallState::Running),
+            ],
+        }),
+        ..Default::default()
+    };
+
+    let rendered: Vec<String> = prepare::prepare_messages(&state, 100, 20)
+        .materialize_all_lines()
+        .iter()
+        .map(extract_line_text)
+        .collect();
+
+    assert!(
+        rendered
+            .iter()
+            .any(|line| line.contains("subagent · 0/2 done")),
+        "rendered={rendered:?}"
+    );
+    assert!(
+        rendered.iter().any(|line| line.contains("Audit auth"))
+            && rendered.iter().any(|line| line.contains("Audit billing")),
+        "both running tasks should be listed: {rendered:?}"
+    );
+}
+
 #[test]
 fn test_prepare_messages_live_batch_centered_mode_uses_left_aligned_padding() {
     let state = TestState {
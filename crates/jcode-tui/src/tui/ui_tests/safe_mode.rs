@@ -0,0 +1,53 @@
+//! Full-draw checks for the `jcode --safe` and permission inbox status badges.
+
+use super::*;
+use crate::tui::ui::clear_flicker_frame_history_for_tests;
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+
+fn status_row(state: &TestState) -> String {
+    clear_flicker_frame_history_for_tests();
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).expect("test terminal");
+    terminal
+        .draw(|frame| crate::tui::ui::draw(frame, state))
+        .expect("draw status line");
+    let status_area = crate::tui::ui::last_status_area().expect("status area recorded");
+    let buf = terminal.backend().buffer();
+    (0..buf.area.width)
+        .map(|x| buf[(x, status_area.y)].symbol().to_string())
+        .collect()
+}
+
+#[test]
+fn safe_mode_badge_shows_on_the_status_line() {
+    let _lock = viewport_snapshot_test_lock();
+    let mut state = TestState {
+        display_messages: vec![DisplayMessage::assistant("hello")],
+        messages_version: 1,
+        safe_mode: true,
+        ..Default::default()
+    };
+    let row = status_row(&state);
+    assert!(row.contains("SAFE"), "{row:?}");
+
+    state.safe_mode = false;
+    let row = status_row(&state);
+    assert!(!row.contains("SAFE"), "{row:?}");
+}
+
+#[test]
+fn permission_inbox_badge_counts_pending_requests() {
+    let _lock = viewport_snapshot_test_lock();
+    let mut state = TestState {
+        display_messages: vec![DisplayMessage::assistant("hello")],
+        messages_version: 1,
+        permission_inbox: 2,
+        ..Default::default()
+    };
+    let row = status_row(&state);
+    assert!(row.contains("INBOX 2"), "{row:?}");
+
+    state.permission_inbox = 0;
+    let row = status_row(&state);
+    assert!(!row.contains("INBOX"), "{row:?}");
+}
@@ -0,0 +1,98 @@
+//! Full-draw checks for the live todo checklist above the status line.
+
+use super::*;
+use crate::todo::TodoItem;
+use crate::tui::todo_checklist::TodoChecklist;
+use crate::tui::ui::{TodoChecklistHit, clear_flicker_frame_history_for_tests, todo_checklist_hit};
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+
+fn checklist_todo(id: &str, content: &str, status: &str) -> TodoItem {
+    TodoItem {
+        content: content.to_string(),
+        status: status.to_string(),
+        id: id.to_string(),
+        ..Default::default()
+    }
+}
+
+fn draw_rows(state: &TestState) -> (Vec<String>, Rect) {
+    clear_flicker_frame_history_for_tests();
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).expect("test terminal");
+    terminal
+        .draw(|frame| crate::tui::ui::draw(frame, state))
+        .expect("draw with todo checklist");
+    let buf = terminal.backend().buffer();
+    let rows = (0..buf.area.height)
+        .map(|y| {
+            (0..buf.area.width)
+                .map(|x| buf[(x, y)].symbol().to_string())
+                .collect::<String>()
+        })
+        .collect();
+    let status_area = crate::tui::ui::last_status_area().expect("status area recorded");
+    (rows, status_area)
+}
+
+#[test]
+fn todo_checklist_draws_above_status_and_maps_clicks() {
+    let _lock = viewport_snapshot_test_lock();
+    let mut checklist = TodoChecklist::default();
+    let started = Instant::now()
+        .checked_sub(Duration::from_secs(9 * 60 + 5))
+        .expect("instant nine minutes ago");
+    checklist.apply(
+        vec![
+            checklist_todo("1", "Read the parser", "completed"),
+            checklist_todo("2", "Fix the tokenizer", "in_progress"),
+            checklist_todo("3", "Run the tests", "pending"),
+        ],
+        started,
+    );
+    let mut state = TestState {
+        display_messages: vec![DisplayMessage::assistant("working")],
+        messages_version: 1,
+        todo_checklist: Some(checklist.clone()),
+        ..Default::default()
+    };
+
+    let (rows, status_area) = draw_rows(&state);
+    let status_y = status_area.y as usize;
+    assert!(
+        rows[status_y - 4].contains("Todos 1/3"),
+        "{:?}",
+        rows[status_y - 4]
+    );
+    assert!(
+        rows[status_y - 4].contains("Alt+X"),
+        "{:?}",
+        rows[status_y - 4]
+    );
+    assert!(rows[status_y - 3].contains("✓ Read the parser"));
+    assert!(rows[status_y - 2].contains("▶ Fix the tokenizer"));
+    assert!(
+        rows[status_y - 2].contains("in_progress for 9 minutes"),
+        "{:?}",
+        rows[status_y - 2]
+    );
+    assert!(rows[status_y - 1].contains("○ Run the tests"));
+
+    assert_eq!(
+        todo_checklist_hit(4, status_area.y - 2),
+        Some(TodoChecklistHit::Copy("Fix the tokenizer".to_string()))
+    );
+    assert_eq!(
+        todo_checklist_hit(4, status_area.y - 4),
+        Some(TodoChecklistHit::Toggle)
+    );
+    assert_eq!(todo_checklist_hit(4, status_area.y), None);
+
+    checklist.collapsed = true;
+    state.todo_checklist = Some(checklist);
+    let (rows, status_area) = draw_rows(&state);
+    let summary = &rows[status_area.y as usize - 1];
+    assert!(summary.contains("Todos 1/3"), "{summary:?}");
+    assert!(summary.contains("▶ Fix the tokenizer"), "{summary:?}");
+    assert!(summary.contains("in_progress for 9 minutes"), "{summary:?}");
+    assert!(!rows[status_area.y as usize - 2].contains("Run the tests"));
+}
@@ -1,8 +1,9 @@
 pub(super) use jcode_tui_style::theme::{
-    accent_color, ai_color, ai_text, asap_color, blend_color, dim_color, file_link_color,
-    header_icon_color, header_name_color, header_session_color, pending_color,
-    prompt_entry_bg_color, prompt_entry_color, prompt_entry_shimmer_color, queued_color,
-    rainbow_prompt_color, system_message_color, tool_color, user_bg, user_color, user_text,
+    accent_color, ai_color, ai_text, asap_color, aside_color, blend_color, dim_color,
+    file_link_color, header_icon_color, header_name_color, header_session_color,
+    interjection_color, pending_color, prompt_entry_bg_color, prompt_entry_color,
+    prompt_entry_shimmer_color, queued_color, rainbow_prompt_color, system_message_color,
+    tool_color, urgent_interjection_color, user_bg, user_color, user_text,
 };
 use ratatui::prelude::*;
 
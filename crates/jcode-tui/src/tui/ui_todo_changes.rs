@@ -156,7 +156,7 @@ fn compute_diff(prev: Option<&[TodoItem]>, next: &[TodoItem]) -> TodoDiff {
     diff
 }
 
-fn status_icon(status: &str, blocked: bool) -> (&'static str, Color) {
+pub(super) fn status_icon(status: &str, blocked: bool) -> (&'static str, Color) {
     if blocked && status != "completed" {
         return ("⊳", rgb(180, 140, 100));
     }
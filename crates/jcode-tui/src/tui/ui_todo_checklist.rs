@@ -0,0 +1,187 @@
+//! Live todo checklist drawn directly above the status line.
+//!
+//! The checklist mirrors the session's todo list while it still has open
+//! items. Each drawn row is recorded so a mouse click can be mapped back to
+//! the item it shows: the header toggles collapse, an item copies its text.
+
+use super::*;
+use crate::tui::layout_utils;
+use crate::tui::todo_checklist::{TodoChecklist, todo_is_open, todo_stall_label};
+
+/// Item rows shown when expanded. Longer lists keep only their open items.
+const MAX_CHECKLIST_ITEMS: usize = 6;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum TodoChecklistHit {
+    Toggle,
+    Copy(String),
+}
+
+#[derive(Clone, Debug)]
+struct TodoChecklistRow {
+    area: Rect,
+    hit: TodoChecklistHit,
+}
+
+#[cfg(test)]
+thread_local! {
+    static TEST_TODO_CHECKLIST_ROWS: RefCell<Vec<TodoChecklistRow>> = const { RefCell::new(Vec::new()) };
+}
+
+#[cfg(not(test))]
+static TODO_CHECKLIST_ROWS: OnceLock<Mutex<Vec<TodoChecklistRow>>> = OnceLock::new();
+
+#[cfg(not(test))]
+fn todo_checklist_rows_state() -> &'static Mutex<Vec<TodoChecklistRow>> {
+    TODO_CHECKLIST_ROWS.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+fn record_todo_checklist_rows(rows: Vec<TodoChecklistRow>) {
+    #[cfg(test)]
+    {
+        TEST_TODO_CHECKLIST_ROWS.with(|snapshot| {
+            *snapshot.borrow_mut() = rows;
+        });
+        return;
+    }
+    #[cfg(not(test))]
+    {
+        if let Ok(mut snapshot) = todo_checklist_rows_state().lock() {
+            *snapshot = rows;
+        }
+    }
+}
+
+/// What a click at `(column, row)` hits in the checklist from the last frame.
+pub(crate) fn todo_checklist_hit(column: u16, row: u16) -> Option<TodoChecklistHit> {
+    let find = |rows: &[TodoChecklistRow]| {
+        rows.iter()
+            .find(|entry| layout_utils::point_in_rect(column, row, entry.area))
+            .map(|entry| entry.hit.clone())
+    };
+    #[cfg(test)]
+    {
+        return TEST_TODO_CHECKLIST_ROWS.with(|snapshot| find(&snapshot.borrow()));
+    }
+    #[cfg(not(test))]
+    {
+        todo_checklist_rows_state()
+            .lock()
+            .ok()
+            .and_then(|snapshot| find(&snapshot))
+    }
+}
+
+/// Build the checklist rows, each paired with what clicking it does.
+pub(super) fn todo_checklist_lines(
+    checklist: &TodoChecklist,
+    width: usize,
+    now: Instant,
+    key_label: Option<&str>,
+) -> Vec<(Line<'static>, TodoChecklistHit)> {
+    let todos = &checklist.todos;
+    let completed = todos.iter().filter(|t| t.status == "completed").count();
+    let dim = Style::default().fg(rgb(110, 110, 125));
+    let stall_style = Style::default().fg(rgb(230, 120, 100));
+    let active = todos.iter().find(|t| t.status == "in_progress");
+    let stall = active
+        .and_then(|todo| checklist.in_progress_for(todo, now))
+        .and_then(todo_stall_label);
+
+    let mut header = vec![
+        Span::styled(if checklist.collapsed { "▸ " } else { "▾ " }, dim),
+        Span::styled("Todos", Style::default().fg(rgb(170, 175, 205)).bold()),
+        Span::styled(format!(" {}/{}", completed, todos.len()), dim),
+    ];
+
+    if checklist.collapsed {
+        if let Some(todo) = active {
+            header.push(Span::styled(" · ", dim));
+            header.push(Span::styled(
+                format!("▶ {}", todo.content),
+                Style::default().fg(rgb(255, 200, 100)),
+            ));
+        }
+        let suffix = stall
+            .map(|label| Line::from(Span::styled(format!(" · {}", label), stall_style)))
+            .unwrap_or_default();
+        let line = truncate_line_preserving_suffix_to_width(&Line::from(header), &suffix, width);
+        return vec![(line, TodoChecklistHit::Toggle)];
+    }
+
+    let visible: Vec<&crate::todo::TodoItem> = if todos.len() > MAX_CHECKLIST_ITEMS {
+        todos
+            .iter()
+            .filter(|todo| todo_is_open(todo))
+            .take(MAX_CHECKLIST_ITEMS)
+            .collect()
+    } else {
+        todos.iter().collect()
+    };
+    let hidden = todos.len() - visible.len();
+    if hidden > 0 {
+        header.push(Span::styled(format!(" · {} more", hidden), dim));
+    }
+    if let Some(label) = key_label {
+        header.push(Span::styled(format!(" · {} collapse", label), dim));
+    }
+
+    let mut lines = vec![(
+        truncate_line_with_ellipsis_to_width(&Line::from(header), width),
+        TodoChecklistHit::Toggle,
+    )];
+    for todo in visible {
+        let (icon, icon_color) =
+            todo_changes::status_icon(&todo.status, !todo.blocked_by.is_empty());
+        let text_style = match todo.status.as_str() {
+            "in_progress" => Style::default().fg(rgb(255, 210, 130)).bold(),
+            "completed" | "cancelled" => Style::default().fg(rgb(100, 100, 110)),
+            _ => Style::default().fg(rgb(170, 170, 180)),
+        };
+        let prefix = Line::from(vec![
+            Span::styled(format!("  {} ", icon), Style::default().fg(icon_color)),
+            Span::styled(todo.content.clone(), text_style),
+        ]);
+        let suffix = checklist
+            .in_progress_for(todo, now)
+            .and_then(todo_stall_label)
+            .map(|label| Line::from(Span::styled(format!(" · {}", label), stall_style)))
+            .unwrap_or_default();
+        lines.push((
+            truncate_line_preserving_suffix_to_width(&prefix, &suffix, width),
+            TodoChecklistHit::Copy(todo.content.clone()),
+        ));
+    }
+    lines
+}
+
+/// Draw the checklist into `area` and remember its rows for click handling.
+pub(super) fn draw_todo_checklist(
+    frame: &mut Frame,
+    area: Rect,
+    rows: Vec<(Line<'static>, TodoChecklistHit)>,
+) {
+    let mut hits = Vec::with_capacity(rows.len());
+    let mut lines = Vec::with_capacity(rows.len());
+    for (idx, (line, hit)) in rows.into_iter().enumerate() {
+        let row_area = Rect::new(area.x, area.y + idx as u16, area.width, 1);
+        if row_area.bottom() > area.bottom() {
+            break;
+        }
+        hits.push(TodoChecklistRow {
+            area: row_area,
+            hit,
+        });
+        lines.push(line);
+    }
+    record_todo_checklist_rows(hits);
+    if area.height > 0 {
+        clear_area(frame, area);
+        frame.render_widget(Paragraph::new(lines), area);
+    }
+}
+
+/// Forget the rows from a previous frame once the checklist is gone.
+pub(super) fn clear_todo_checklist_rows() {
+    record_todo_checklist_rows(Vec::new());
+}
@@ -0,0 +1,494 @@
+//! Media embedded in tool result text.
+//!
+//! Tool results are summarised to a single transcript line, so a mermaid fence
+//! or a screenshot path printed by a tool used to be invisible unless the user
+//! copied the raw output. This module pulls that media out of the result text
+//! and hands it to the same machinery assistant diagrams and attached images
+//! already use:
+//! * **Mermaid** fences go through the deferred renderer, whose PNGs are cached
+//!   by content hash, so scrolling back over a diagram never re-renders it.
+//! * **Raster images** (a PNG/JPEG path on disk, or a `data:image/...;base64,`
+//!   payload) become anchored inline images with the usual fit placeholders.
+//!
+//! Terminals without an image protocol get a small box naming the file and its
+//! pixel size instead, which the open-in-viewer key can still act on.
+
+use super::inline_image_ui::{self, InlineImageItem};
+use crate::tui::color_support::rgb;
+use crate::tui::mermaid;
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex};
+
+/// Upper bound on media blocks pulled out of a single tool result, so a tool
+/// that lists a directory of screenshots cannot flood the transcript.
+const MAX_MEDIA_PER_RESULT: usize = 4;
+/// Skip referenced image files larger than this; they are almost certainly not
+/// something the user wants decoded into the transcript.
+const MAX_IMAGE_FILE_BYTES: u64 = 32 * 1024 * 1024;
+/// Shortest base64 payload treated as a real image rather than a doc example
+/// like `data:image/png;base64,...`.
+const MIN_DATA_URI_PAYLOAD: usize = 64;
+/// Extensions the inline decoder understands.
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg"];
+
+/// One piece of renderable media found in a tool result.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum ToolMedia {
+    /// Body of a ```` ```mermaid ```` fence.
+    Mermaid(String),
+    /// A path to a PNG/JPEG file, as written in the output (`~` not expanded).
+    ImagePath(String),
+    /// A `data:image/...;base64,` payload.
+    ImageData { media_type: String, data: String },
+}
+
+/// Scan `content` for mermaid fences, base64 image payloads and image paths,
+/// in that order, capped at [`MAX_MEDIA_PER_RESULT`]. Pure text scan: paths
+/// are not checked against the filesystem here.
+pub(crate) fn detect(content: &str) -> Vec<ToolMedia> {
+    let mut media: Vec<ToolMedia> = Vec::new();
+    if content.contains("```") {
+        media.extend(mermaid_blocks(content).into_iter().map(ToolMedia::Mermaid));
+    }
+    if content.contains("data:image/") {
+        media.extend(data_uris(content));
+    }
+    let lower = content.to_ascii_lowercase();
+    if IMAGE_EXTENSIONS
+        .iter()
+        .any(|ext| lower.contains(&format!(".{ext}")))
+    {
+        media.extend(image_paths(content).into_iter().map(ToolMedia::ImagePath));
+    }
+    media.truncate(MAX_MEDIA_PER_RESULT);
+    media
+}
+
+/// Mermaid sources only, for the per-message renderer.
+pub(crate) fn mermaid_sources(content: &str) -> Vec<String> {
+    detect(content)
+        .into_iter()
+        .filter_map(|media| match media {
+            ToolMedia::Mermaid(source) => Some(source),
+            _ => None,
+        })
+        .collect()
+}
+
+fn mermaid_blocks(content: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut current: Option<String> = None;
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        match current.as_mut() {
+            None => {
+                if let Some(lang) = trimmed.strip_prefix("```")
+                    && mermaid::is_mermaid_lang(lang.trim())
+                {
+                    current = Some(String::new());
+                }
+            }
+            Some(body) => {
+                if trimmed.starts_with("```") {
+                    if !body.trim().is_empty() {
+                        blocks.push(std::mem::take(body));
+                    }
+                    current = None;
+                } else {
+                    body.push_str(line);
+                    body.push('\n');
+                }
+            }
+        }
+    }
+    blocks
+}
+
+fn data_uris(content: &str) -> Vec<ToolMedia> {
+    let mut found = Vec::new();
+    let mut rest = content;
+    while let Some(start) = rest.find("data:image/") {
+        rest = &rest[start + "data:image/".len()..];
+        let Some((subtype, tail)) = rest.split_once(";base64,") else {
+            break;
+        };
+        let media_type = match subtype.to_ascii_lowercase().as_str() {
+            "png" => "image/png",
+            "jpeg" | "jpg" => "image/jpeg",
+            _ => continue,
+        };
+        let len = tail
+            .bytes()
+            .take_while(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'='))
+            .count();
+        if len >= MIN_DATA_URI_PAYLOAD {
+            let data = tail[..len].to_string();
+            if !found.iter().any(
+                |media| matches!(media, ToolMedia::ImageData { data: seen, .. } if *seen == data),
+            ) {
+                found.push(ToolMedia::ImageData {
+                    media_type: media_type.to_string(),
+                    data,
+                });
+            }
+        }
+        rest = &tail[len..];
+    }
+    found
+}
+
+fn image_paths(content: &str) -> Vec<String> {
+    let mut found: Vec<String> = Vec::new();
+    let tokens = content.split(|c: char| {
+        c.is_whitespace() || matches!(c, '"' | '\'' | '`' | '(' | ')' | '[' | ']' | '<' | '>')
+    });
+    for token in tokens {
+        let token = token.trim_end_matches(['.', ',', ';', ':']);
+        if token.contains("://") || token.starts_with("data:") {
+            continue;
+        }
+        if !is_supported_image_path(Path::new(token)) {
+            continue;
+        }
+        let has_dir = token.contains('/') || token.contains('\\');
+        if has_dir && !found.iter().any(|seen| seen == token) {
+            found.push(token.to_string());
+        }
+    }
+    found
+}
+
+fn is_supported_image_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| IMAGE_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+fn expand_home(raw: &str) -> PathBuf {
+    match raw.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir()
+            .map(|home| home.join(rest))
+            .unwrap_or_else(|| PathBuf::from(raw)),
+        None => PathBuf::from(raw),
+    }
+}
+
+/// A raster image from a tool result, ready for the inline image section.
+#[derive(Clone)]
+pub(crate) struct ToolImage {
+    pub item: InlineImageItem,
+    /// File backing the image: the referenced path, or for base64 payloads the
+    /// materialized cache copy when one was needed for the fallback box.
+    pub path: Option<PathBuf>,
+}
+
+/// Resolved images per tool call id. Resolution touches the filesystem, so a
+/// body rebuild must not redo it for every tool message.
+static RESOLVED: LazyLock<Mutex<HashMap<String, Arc<[ToolImage]>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+/// Most tool results carry no media, so entries are usually an empty slice;
+/// the bound only guards against unbounded growth in very long sessions.
+const RESOLVED_MAX: usize = 4096;
+
+/// Image id -> file the open-in-viewer key should launch.
+static OPEN_TARGETS: LazyLock<Mutex<HashMap<u64, PathBuf>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+const OPEN_TARGETS_MAX: usize = 512;
+
+/// Raster images found in the output of tool call `tool_id`, cached per call.
+pub(crate) fn resolve_images(tool_id: &str, content: &str) -> Arc<[ToolImage]> {
+    if let Ok(cache) = RESOLVED.lock()
+        && let Some(images) = cache.get(tool_id)
+    {
+        return images.clone();
+    }
+    let images: Arc<[ToolImage]> = detect(content)
+        .iter()
+        .filter_map(resolve_image)
+        .collect::<Vec<_>>()
+        .into();
+    if let Ok(mut cache) = RESOLVED.lock() {
+        if cache.len() >= RESOLVED_MAX {
+            cache.clear();
+        }
+        cache.insert(tool_id.to_string(), images.clone());
+    }
+    images
+}
+
+fn resolve_image(media: &ToolMedia) -> Option<ToolImage> {
+    match media {
+        ToolMedia::Mermaid(_) => None,
+        ToolMedia::ImagePath(raw) => {
+            let path = expand_home(raw);
+            let meta = std::fs::metadata(&path).ok()?;
+            if !meta.is_file() || meta.len() > MAX_IMAGE_FILE_BYTES {
+                return None;
+            }
+            let (width, height) = image::image_dimensions(&path).ok()?;
+            let path = path.canonicalize().unwrap_or(path);
+            // Registering the file by path lets the draw step read it straight
+            // from disk; no payload copy is kept in memory.
+            let id = mermaid::register_external_image(&path, width, height);
+            remember_open_target(id, &path);
+            Some(ToolImage {
+                item: InlineImageItem {
+                    id,
+                    width,
+                    height,
+                    label: path.display().to_string(),
+                },
+                path: Some(path),
+            })
+        }
+        ToolMedia::ImageData { media_type, data } => {
+            let (id, width, height) = mermaid::inline_image_dims(media_type, data)?;
+            inline_image_ui::register_payload(id, media_type, data);
+            let path = if mermaid::image_protocol_available() {
+                None
+            } else {
+                // The fallback box needs a real file to show and open.
+                mermaid::materialize_inline_image_by_id(id, media_type, data)?;
+                mermaid::get_cached_path(id)
+            };
+            if let Some(path) = &path {
+                remember_open_target(id, path);
+            }
+            Some(ToolImage {
+                item: InlineImageItem {
+                    id,
+                    width,
+                    height,
+                    label: format!("{media_type} (base64)"),
+                },
+                path,
+            })
+        }
+    }
+}
+
+fn remember_open_target(id: u64, path: &Path) {
+    if let Ok(mut targets) = OPEN_TARGETS.lock() {
+        if targets.len() >= OPEN_TARGETS_MAX && !targets.contains_key(&id) {
+            targets.clear();
+        }
+        targets.insert(id, path.to_path_buf());
+    }
+}
+
+/// File to open for the image or diagram drawn with `hash`: the original file
+/// for path references, otherwise the render cache copy.
+pub(crate) fn open_target_for_hash(hash: u64) -> Option<PathBuf> {
+    if let Some(path) = OPEN_TARGETS
+        .lock()
+        .ok()
+        .and_then(|targets| targets.get(&hash).cloned())
+    {
+        return Some(path);
+    }
+    inline_image_ui::materialize_visible(hash);
+    mermaid::get_cached_path(hash)
+}
+
+/// If `line` is the path row of a fallback box, return the file it names.
+pub(crate) fn open_target_for_fallback_line(line: &str) -> Option<PathBuf> {
+    let candidate = line.trim().strip_prefix("│ ")?.trim();
+    OPEN_TARGETS
+        .lock()
+        .ok()?
+        .values()
+        .find(|path| path.as_os_str() == candidate)
+        .cloned()
+}
+
+/// Transcript lines for a mermaid fence in a tool result. Uses the deferred
+/// renderer so the first pass never blocks; the finished PNG bumps the render
+/// epoch, which invalidates the cached tool message and redraws it here.
+pub(crate) fn mermaid_lines(source: &str, width: u16) -> Vec<Line<'static>> {
+    let Some(result) = mermaid::render_mermaid_deferred(source, Some(width)) else {
+        return vec![Line::from(Span::styled(
+            "↻ rendering mermaid diagram...",
+            Style::default().fg(super::dim_color()),
+        ))];
+    };
+    match result {
+        mermaid::RenderResult::Image {
+            path,
+            width: px_w,
+            height: px_h,
+            ..
+        } if !mermaid::image_protocol_available() => {
+            fallback_box_lines("mermaid diagram", Some(&path), px_w, px_h)
+        }
+        other => mermaid::result_to_lines(other, Some(width as usize)),
+    }
+}
+
+/// Lines for the raster images of one tool result: anchored inline images when
+/// the terminal can draw them, otherwise a fallback box per image.
+pub(crate) fn image_lines(
+    images: &[ToolImage],
+    width: u16,
+    images_visible: bool,
+    levels: &dyn inline_image_ui::ImageExpandLevels,
+) -> Vec<Line<'static>> {
+    if mermaid::image_protocol_available() {
+        let items: Vec<InlineImageItem> = images.iter().map(|image| image.item.clone()).collect();
+        return inline_image_ui::anchored_image_lines(&items, width, images_visible, levels);
+    }
+    let mut lines = Vec::new();
+    for image in images {
+        lines.extend(fallback_box_lines(
+            "image",
+            image.path.as_deref(),
+            image.item.width,
+            image.item.height,
+        ));
+    }
+    lines
+}
+
+/// ASCII stand-in for an image the terminal cannot draw: what it is, where the
+/// file lives, and how big it is.
+fn fallback_box_lines(
+    kind: &str,
+    path: Option<&Path>,
+    width: u32,
+    height: u32,
+) -> Vec<Line<'static>> {
+    let dim = Style::default().fg(super::dim_color());
+    let info = Style::default().fg(rgb(140, 170, 200));
+    let mut lines = vec![Line::from(Span::styled(format!("┌─ {kind} "), dim))];
+    if let Some(path) = path {
+        lines.push(Line::from(vec![
+            Span::styled("│ ", dim),
+            Span::styled(path.display().to_string(), info),
+        ]));
+    }
+    lines.push(Line::from(vec![
+        Span::styled("│ ", dim),
+        Span::styled(
+            format!("{width}×{height} px (image protocols not available)"),
+            dim,
+        ),
+    ]));
+    lines.push(Line::from(Span::styled("└─", dim)));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 1x1 transparent PNG.
+    const TINY_PNG_B64: &str = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mP8z8BQDwAEhQGAhKmMIQAAAABJRU5ErkJggg==";
+
+    #[test]
+    fn detects_mermaid_fences() {
+        let content = "Generated diagram:\n```mermaid\ngraph TD\n  A --> B\n```\n\n```rust\nfn main() {}\n```\n";
+        assert_eq!(
+            detect(content),
+            vec![ToolMedia::Mermaid("graph TD\n  A --> B\n".to_string())]
+        );
+    }
+
+    #[test]
+    fn ignores_unterminated_and_empty_fences() {
+        assert!(detect("```mermaid\n```\n").is_empty());
+        assert!(detect("```mermaid\ngraph TD\n  A --> B\n").is_empty());
+    }
+
+    #[test]
+    fn detects_image_paths_in_prose() {
+        let content =
+            "Saved screenshot to /tmp/shot.PNG.\nAlso wrote `~/out/chart.jpg` and (./rel/x.jpeg)";
+        assert_eq!(
+            detect(content),
+            vec![
+                ToolMedia::ImagePath("/tmp/shot.PNG".to_string()),
+                ToolMedia::ImagePath("~/out/chart.jpg".to_string()),
+                ToolMedia::ImagePath("./rel/x.jpeg".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_urls_bare_names_and_unsupported_formats() {
+        let content =
+            "https://example.com/a.png logo.png /tmp/anim.gif /tmp/icon.svg /tmp/notes.txt";
+        assert!(detect(content).is_empty());
+    }
+
+    #[test]
+    fn detects_base64_data_uris() {
+        let content = format!("<img src=\"data:image/png;base64,{TINY_PNG_B64}\">");
+        assert_eq!(
+            detect(&content),
+            vec![ToolMedia::ImageData {
+                media_type: "image/png".to_string(),
+                data: TINY_PNG_B64.to_string(),
+            }]
+        );
+        // Placeholder payloads in docs are not images.
+        assert!(detect("use data:image/png;base64,... here").is_empty());
+    }
+
+    #[test]
+    fn caps_media_per_result() {
+        let content = (0..10)
+            .map(|i| format!("/tmp/shot-{i}.png"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(detect(&content).len(), MAX_MEDIA_PER_RESULT);
+    }
+
+    #[test]
+    fn resolves_existing_image_files_and_drops_missing_ones() {
+        use base64::Engine as _;
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("tiny.png");
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(TINY_PNG_B64)
+            .expect("decode png");
+        std::fs::write(&path, bytes).expect("write png");
+        let content = format!(
+            "wrote {} and {}",
+            path.display(),
+            dir.path().join("missing.png").display()
+        );
+
+        let images = resolve_images("tool-media-resolve", &content);
+        assert_eq!(images.len(), 1);
+        assert_eq!((images[0].item.width, images[0].item.height), (1, 1));
+        let resolved = images[0].path.clone().expect("path image keeps its file");
+        assert_eq!(
+            open_target_for_hash(images[0].item.id),
+            Some(resolved.clone())
+        );
+        assert_eq!(
+            open_target_for_fallback_line(&format!("│ {}", resolved.display())),
+            Some(resolved)
+        );
+    }
+
+    #[test]
+    fn fallback_box_names_file_and_dimensions() {
+        let lines = fallback_box_lines("image", Some(Path::new("/tmp/shot.png")), 640, 480);
+        let text: Vec<String> = lines
+            .iter()
+            .map(jcode_tui_render::line_plain_text)
+            .collect();
+        assert_eq!(
+            text,
+            vec![
+                "┌─ image ".to_string(),
+                "│ /tmp/shot.png".to_string(),
+                "│ 640×480 px (image protocols not available)".to_string(),
+                "└─".to_string(),
+            ]
+        );
+    }
+}
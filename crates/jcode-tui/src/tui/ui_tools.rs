@@ -1007,7 +1007,7 @@ pub(super) fn get_tool_summary_with_budget(
                 other => other.to_string(),
             }
         }
-        "ls" => tool
+        "ls" | "tree" => tool
             .input
             .get("path")
             .and_then(|v| v.as_str())
@@ -1319,6 +1319,11 @@ pub(super) fn get_tool_summary_with_budget(
             format!("{} calls", count)
         }
         "subagent" => {
+            if let Some(tasks) = tool.input.get("tasks").and_then(|v| v.as_array())
+                && !tasks.is_empty()
+            {
+                return format!("{} tasks in parallel", tasks.len());
+            }
             let desc = tool
                 .input
                 .get("description")
@@ -1434,6 +1439,12 @@ pub(super) fn render_batch_subcall_line(
     Line::from(spans)
 }
 
+/// Tools that report per-subcall progress through `BatchProgress` while
+/// running: `batch`, and `subagent` when given a list of tasks.
+pub(super) fn tool_reports_batch_progress(name: &str) -> bool {
+    matches!(name, "batch" | "subagent")
+}
+
 pub(super) fn summarize_batch_running_tools_compact(running: &[ToolCall]) -> Option<String> {
     if running.is_empty() {
         return None;
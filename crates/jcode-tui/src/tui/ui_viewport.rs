@@ -389,7 +389,13 @@ pub(super) fn draw_messages(
     let expand_feedback_active = copy_badge_ui.expand_feedback_is_active(copy_badge_now);
 
     let active_file_context = if app.diff_mode().is_file() {
-        active_file_diff_context(prepared.as_ref(), scroll, visible_height)
+        file_view_edit_context(
+            prepared.as_ref(),
+            scroll,
+            visible_height,
+            !app.auto_scroll_paused(),
+            app.file_view_pin().as_deref(),
+        )
     } else {
         None
     };
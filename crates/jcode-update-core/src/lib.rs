@@ -37,10 +37,11 @@ pub struct GitHubRelease {
     pub _html_url: String,
     #[serde(rename = "published_at")]
     pub _published_at: Option<String>,
+    #[serde(default)]
+    pub prerelease: bool,
     pub assets: Vec<GitHubAsset>,
     #[serde(default)]
-    #[serde(rename = "target_commitish")]
-    pub _target_commitish: String,
+    pub target_commitish: String,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -33,6 +33,32 @@ pub struct CopilotUsageTracker {
     pub today: DayUsage,
     pub month: MonthUsage,
     pub all_time: AllTimeUsage,
+    /// Latest premium-request quota GitHub reported in response headers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub premium_quota: Option<CopilotPremiumQuota>,
+}
+
+/// Premium-request quota snapshot from an `x-quota-snapshot-*` response header.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct CopilotPremiumQuota {
+    /// Month (`YYYY-MM`) the snapshot was observed in
+    pub month: String,
+    /// Monthly premium-request entitlement; `None` when unlimited
+    pub entitlement: Option<u64>,
+    /// Percentage of the entitlement still available (0-100)
+    pub percent_remaining: f64,
+    /// When the entitlement resets (RFC3339), if reported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resets_at: Option<String>,
+}
+
+impl CopilotPremiumQuota {
+    /// Premium requests GitHub counts as used this month.
+    pub fn used(&self) -> Option<u64> {
+        let entitlement = self.entitlement?;
+        let used_fraction = (100.0 - self.percent_remaining.clamp(0.0, 100.0)) / 100.0;
+        Some((entitlement as f64 * used_fraction).round() as u64)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -84,6 +110,7 @@ pub fn classify_telemetry_tool_category(name: &str) -> TelemetryToolCategory {
         | "grep"
         | "agentgrep"
         | "ls"
+        | "tree"
         | "conversation_search"
         | "session_search" => TelemetryToolCategory::ReadSearch,
         "write" | "edit" | "multiedit" | "patch" | "apply_patch" => TelemetryToolCategory::Write,
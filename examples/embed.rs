@@ -0,0 +1,49 @@
+//! Embed jcode as a library: an agent with a custom provider and a custom tool.
+//! Run: cargo run --example embed
+use jcode::{AgentBuilder, EventStream, Provider, StreamEvent, Tool, ToolContext, ToolOutput};
+use std::sync::Arc;
+struct EchoProvider;
+#[async_trait::async_trait]
+impl Provider for EchoProvider {
+    async fn complete(
+        &self,
+        _: &[jcode::Message],
+        tools: &[jcode::ToolDefinition],
+        _: &str,
+        _: Option<&str>,
+    ) -> anyhow::Result<EventStream> {
+        let reply = StreamEvent::TextDelta(format!("I have {} tool(s).", tools.len()));
+        Ok(Box::pin(futures::stream::iter([Ok(reply)])))
+    }
+    fn name(&self) -> &str {
+        "echo"
+    }
+    fn fork(&self) -> Arc<dyn Provider> {
+        Arc::new(EchoProvider)
+    }
+}
+
+struct ClockTool;
+#[async_trait::async_trait]
+impl Tool for ClockTool {
+    fn name(&self) -> &str {
+        "clock"
+    }
+    fn description(&self) -> &str {
+        "Current UTC time"
+    }
+    fn parameters_schema(&self) -> serde_json::Value {
+        serde_json::json!({ "type": "object", "properties": {} })
+    }
+    async fn execute(&self, _: serde_json::Value, _: ToolContext) -> anyhow::Result<ToolOutput> {
+        Ok(ToolOutput::new(chrono::Utc::now().to_rfc3339()))
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let builder = AgentBuilder::new(Arc::new(EchoProvider)).system_prompt("Be terse.");
+    let mut agent = builder.tool(Arc::new(ClockTool)).max_turns(4).build().await;
+    println!("{}", agent.run_once_capture("Which tools?").await?);
+    Ok(())
+}
@@ -165,13 +165,13 @@ feature_args_from_profile() {
       return 0
       ;;
     minimal|none)
-      printf '%s\0' --no-default-features
+      printf '%s\0' --no-default-features --features tui
       ;;
     pdf)
-      printf '%s\0' --no-default-features --features pdf
+      printf '%s\0' --no-default-features --features tui,pdf
       ;;
     embeddings)
-      printf '%s\0' --no-default-features --features embeddings
+      printf '%s\0' --no-default-features --features tui,embeddings
       ;;
     full)
       printf '%s\0' --features embeddings,pdf,bedrock
@@ -81,6 +81,7 @@ async fn main() -> Result<()> {
         message_id: session_id.clone(),
         tool_call_id: String::new(),
         working_dir: Some(workspace.clone()),
+        workspace_roots: Vec::new(),
         stdin_request_tx: None,
         graceful_shutdown_signal: None,
         execution_mode: ToolExecutionMode::Direct,
@@ -5,11 +5,11 @@ use crate::transport::{ReadHalf, WriteHalf};
 use anyhow::{Context, Result};
 use serde_json::{Value, json};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, oneshot};
 
 const ACP_PROTOCOL_VERSION: u64 = 1;
 
@@ -54,6 +54,8 @@ struct JsonRpcMessage {
     id: Option<Value>,
     method: Option<String>,
     params: Value,
+    /// `result` or `error` of a response to one of our client requests.
+    response: Option<std::result::Result<Value, Value>>,
 }
 
 impl JsonRpcMessage {
@@ -79,6 +81,11 @@ impl JsonRpcMessage {
                 .and_then(Value::as_str)
                 .map(str::to_string),
             params: object.get("params").cloned().unwrap_or(Value::Null),
+            response: match (object.get("result"), object.get("error")) {
+                (_, Some(error)) => Some(Err(error.clone())),
+                (Some(result), None) => Some(Ok(result.clone())),
+                (None, None) => None,
+            },
         })
     }
 }
@@ -130,11 +137,21 @@ impl DaemonSession {
     }
 }
 
+type ClientResponse = std::result::Result<Value, Value>;
+
 #[derive(Clone)]
 struct AcpRuntime {
     stdout: Arc<Mutex<tokio::io::Stdout>>,
     sessions: Arc<Mutex<HashMap<String, Arc<DaemonSession>>>>,
+    /// Our requests to the client (e.g. `session/request_permission`) waiting
+    /// for a response, by JSON-RPC id.
+    client_requests: Arc<Mutex<HashMap<u64, oneshot::Sender<ClientResponse>>>>,
+    next_client_request_id: Arc<AtomicU64>,
+    /// Working directory from the `initialize` params, used by sessions that
+    /// do not name their own `cwd`.
+    initialize_cwd: Arc<Mutex<Option<PathBuf>>>,
     profile: AcpProfile,
+    approvals: bool,
     provider_choice: ProviderChoice,
     model: Option<String>,
     provider_profile: Option<String>,
@@ -143,6 +160,7 @@ struct AcpRuntime {
 impl AcpRuntime {
     fn new(
         profile: AcpProfile,
+        approvals: bool,
         provider_choice: ProviderChoice,
         model: Option<String>,
         provider_profile: Option<String>,
@@ -150,7 +168,11 @@ impl AcpRuntime {
         Self {
             stdout: Arc::new(Mutex::new(tokio::io::stdout())),
             sessions: Arc::new(Mutex::new(HashMap::new())),
+            client_requests: Arc::new(Mutex::new(HashMap::new())),
+            next_client_request_id: Arc::new(AtomicU64::new(1)),
+            initialize_cwd: Arc::new(Mutex::new(None)),
             profile,
+            approvals,
             provider_choice,
             model,
             provider_profile,
@@ -191,6 +213,10 @@ impl AcpRuntime {
 
     async fn handle_message(&self, message: JsonRpcMessage) -> Result<()> {
         let Some(method) = message.method.as_deref() else {
+            if let (Some(id), Some(response)) = (message.id.as_ref(), message.response) {
+                self.resolve_client_request(id, response).await;
+                return Ok(());
+            }
             if let Some(id) = message.id {
                 self.write_error_value(
                     id,
@@ -204,6 +230,16 @@ impl AcpRuntime {
 
         match method {
             "initialize" => {
+                match initialize_cwd(&message.params) {
+                    Ok(cwd) => *self.initialize_cwd.lock().await = cwd,
+                    Err(err) => {
+                        if let Some(id) = message.id {
+                            self.write_error_value(id, JSONRPC_INVALID_PARAMS, err)
+                                .await?;
+                        }
+                        return Ok(());
+                    }
+                }
                 if let Some(id) = message.id {
                     self.write_result(id, initialize_result(&message.params, self.profile))
                         .await?;
@@ -244,7 +280,7 @@ impl AcpRuntime {
         let Some(id) = message.id else {
             return Ok(());
         };
-        let cwd = match cwd_from_params(&message.params) {
+        let cwd = match cwd_from_params(&message.params, self.initialize_cwd.lock().await.clone()) {
             Ok(cwd) => cwd,
             Err(err) => {
                 self.write_error_value(id, JSONRPC_INVALID_PARAMS, err)
@@ -296,7 +332,7 @@ impl AcpRuntime {
                 return Ok(());
             }
         };
-        let cwd = match cwd_from_params(&message.params) {
+        let cwd = match cwd_from_params(&message.params, self.initialize_cwd.lock().await.clone()) {
             Ok(cwd) => cwd,
             Err(err) => {
                 self.write_error_value(id, JSONRPC_INVALID_PARAMS, err)
@@ -482,6 +518,9 @@ impl AcpRuntime {
             ServerEvent::History { session_id, .. } => session_id,
             other => anyhow::bail!("expected history after session creation, got {other:?}"),
         };
+        if self.approvals {
+            enable_approvals(&session).await?;
+        }
         Ok(DaemonSession::new(
             session_id,
             session.reader.into_inner().into_inner(),
@@ -537,6 +576,9 @@ impl AcpRuntime {
             }
         }
 
+        if self.approvals {
+            enable_approvals(&session).await?;
+        }
         Ok(DaemonSession::new(
             attached_id,
             session.reader.into_inner().into_inner(),
@@ -593,6 +635,7 @@ impl AcpRuntime {
                 content: text,
                 images,
                 system_reminder: None,
+                focus_secs: None,
             })
             .await;
         if let Err(err) = send_result {
@@ -626,6 +669,12 @@ impl AcpRuntime {
                         .await?;
                     return Ok(());
                 }
+                ServerEvent::StdinRequest {
+                    request_id,
+                    prompt,
+                    tool_call_id,
+                    ..
+                } => self.answer_stdin_request(&session, request_id, prompt, tool_call_id),
                 other => {
                     for update in mapper.map_event(other) {
                         self.write_notification(
@@ -647,6 +696,83 @@ impl AcpRuntime {
         Ok(())
     }
 
+    /// Answer a daemon stdin request. Tool approvals become
+    /// `session/request_permission` so the editor renders them natively; the
+    /// client has no terminal to type into, so any other stdin prompt gets an
+    /// empty line. Runs in the background so the turn keeps streaming.
+    fn answer_stdin_request(
+        &self,
+        session: &Arc<DaemonSession>,
+        request_id: String,
+        prompt: String,
+        tool_call_id: String,
+    ) {
+        let runtime = self.clone();
+        let session = Arc::clone(session);
+        tokio::spawn(async move {
+            let input = if request_id.starts_with(crate::tool::safe_mode::SAFE_MODE_APPROVAL_PREFIX)
+            {
+                let params = permission_request_params(&session.session_id, &tool_call_id, &prompt);
+                match runtime
+                    .request_client("session/request_permission", params)
+                    .await
+                {
+                    Ok(result) if permission_allowed(&result) => "yes",
+                    Ok(_) => "no",
+                    Err(err) => {
+                        crate::logging::warn(&format!(
+                            "ACP permission request {request_id} failed: {err:#}"
+                        ));
+                        "no"
+                    }
+                }
+            } else {
+                ""
+            };
+            let id = session.next_id();
+            let _ = session
+                .send(&Request::StdinResponse {
+                    id,
+                    request_id,
+                    input: input.to_string(),
+                })
+                .await;
+        });
+    }
+
+    /// Send a JSON-RPC request to the client and wait for its response.
+    async fn request_client(&self, method: &str, params: Value) -> Result<Value> {
+        let id = self.next_client_request_id.fetch_add(1, Ordering::Relaxed);
+        let (tx, rx) = oneshot::channel();
+        self.client_requests.lock().await.insert(id, tx);
+        let sent = self
+            .write_value(json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "method": method,
+                "params": params,
+            }))
+            .await;
+        if let Err(err) = sent {
+            self.client_requests.lock().await.remove(&id);
+            return Err(err);
+        }
+        match rx.await {
+            Ok(Ok(result)) => Ok(result),
+            Ok(Err(error)) => anyhow::bail!("client rejected {method}: {error}"),
+            Err(_) => anyhow::bail!("client went away before answering {method}"),
+        }
+    }
+
+    async fn resolve_client_request(&self, id: &Value, response: ClientResponse) {
+        let Some(id) = id.as_u64() else {
+            return;
+        };
+        if let Some(tx) = self.client_requests.lock().await.remove(&id) {
+            let _ = tx.send(response);
+        }
+    }
+
     async fn write_result(&self, id: Value, result: Value) -> Result<()> {
         self.write_value(json!({
             "jsonrpc": "2.0",
@@ -721,6 +847,18 @@ async fn wait_for_done(session: &DaemonSession, request_id: u64) -> Result<()> {
     }
 }
 
+/// Turn on the session's approval gate so tool calls outside the auto-allowed
+/// tier wait for approval. Unlike safe mode it changes nothing else and is not
+/// saved with the session, so it is set again on every load. The daemon
+/// clears it when this connection closes.
+async fn enable_approvals(session: &DaemonSession) -> Result<()> {
+    let id = session.next_id();
+    session
+        .send(&Request::SetApprovalGate { id, enabled: true })
+        .await?;
+    wait_for_done(session, id).await
+}
+
 async fn request_history(session: &DaemonSession) -> Result<ServerEvent> {
     let id = session.next_id();
     session.send(&Request::GetHistory { id }).await?;
@@ -929,10 +1067,35 @@ fn initialize_result(params: &Value, profile: AcpProfile) -> Value {
     })
 }
 
-fn cwd_from_params(params: &Value) -> std::result::Result<PathBuf, String> {
+/// Optional default working directory from the `initialize` params, given as
+/// `cwd` or `_meta.jcode.cwd`.
+fn initialize_cwd(params: &Value) -> std::result::Result<Option<PathBuf>, String> {
+    let cwd = params
+        .get("cwd")
+        .or_else(|| params.pointer("/_meta/jcode/cwd"))
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|cwd| !cwd.is_empty());
+    match cwd {
+        Some(cwd) if !Path::new(cwd).is_absolute() => {
+            Err(format!("ACP cwd must be absolute: {cwd}"))
+        }
+        Some(cwd) => Ok(Some(PathBuf::from(cwd))),
+        None => Ok(None),
+    }
+}
+
+/// The session's `cwd`, else the one from `initialize`, else ours.
+fn cwd_from_params(
+    params: &Value,
+    initialize_cwd: Option<PathBuf>,
+) -> std::result::Result<PathBuf, String> {
     let cwd = match params.get("cwd").and_then(Value::as_str) {
         Some(cwd) if !cwd.trim().is_empty() => PathBuf::from(cwd),
-        _ => std::env::current_dir().map_err(|err| err.to_string())?,
+        _ => match initialize_cwd {
+            Some(cwd) => cwd,
+            None => std::env::current_dir().map_err(|err| err.to_string())?,
+        },
     };
     if !cwd.is_absolute() {
         return Err(format!("ACP cwd must be absolute: {}", cwd.display()));
@@ -1034,6 +1197,41 @@ fn format_resource_block(resource: &Value) -> String {
     }
 }
 
+const PERMISSION_ALLOW_ONCE: &str = "allow_once";
+const PERMISSION_REJECT_ONCE: &str = "reject_once";
+
+fn permission_request_params(session_id: &str, tool_call_id: &str, prompt: &str) -> Value {
+    let tool_name = prompt.split(':').next().unwrap_or_default().trim();
+    json!({
+        "sessionId": session_id,
+        "toolCall": {
+            "toolCallId": tool_call_id,
+            "title": prompt,
+            "kind": tool_kind(tool_name),
+            "status": "pending",
+        },
+        "options": [
+            {
+                "optionId": PERMISSION_ALLOW_ONCE,
+                "name": "Allow",
+                "kind": "allow_once",
+            },
+            {
+                "optionId": PERMISSION_REJECT_ONCE,
+                "name": "Reject",
+                "kind": "reject_once",
+            },
+        ],
+    })
+}
+
+/// Whether a `session/request_permission` result selected the allow option.
+/// A cancelled prompt counts as a rejection.
+fn permission_allowed(result: &Value) -> bool {
+    let outcome = &result["outcome"];
+    outcome["outcome"] == "selected" && outcome["optionId"] == PERMISSION_ALLOW_ONCE
+}
+
 fn agent_message_chunk(text: String) -> Value {
     json!({
         "sessionUpdate": "agent_message_chunk",
@@ -1050,7 +1248,7 @@ fn tool_title(name: &str) -> String {
         "read" => "Reading file".to_string(),
         "write" => "Writing file".to_string(),
         "edit" | "multiedit" | "patch" | "apply_patch" => "Editing files".to_string(),
-        "agentgrep" | "grep" | "glob" | "ls" => "Searching workspace".to_string(),
+        "agentgrep" | "grep" | "glob" | "ls" | "tree" => "Searching workspace".to_string(),
         "webfetch" | "websearch" => "Fetching web content".to_string(),
         other => other.replace('_', " "),
     }
@@ -1061,7 +1259,13 @@ pub(crate) fn tool_kind(name: &str) -> &'static str {
         "read" => "read",
         "write" | "edit" | "multiedit" | "patch" | "apply_patch" => "edit",
         "bash" | "bg" | "selfdev" => "execute",
-        "agentgrep" | "grep" | "glob" | "ls" | "session_search" | "conversation_search" => "search",
+        "agentgrep"
+        | "grep"
+        | "glob"
+        | "ls"
+        | "tree"
+        | "session_search"
+        | "conversation_search" => "search",
         "webfetch" | "websearch" | "codesearch" => "fetch",
         _ => "other",
     }
@@ -1080,15 +1284,20 @@ pub(crate) async fn run_acp_command(
         crate::config::invalidate_config_cache();
     }
     let profile = AcpProfile::parse(&acp_config.profile);
-    AcpRuntime::new(profile, provider_choice, model, provider_profile)
-        .run()
-        .await
+    AcpRuntime::new(
+        profile,
+        acp_config.approvals,
+        provider_choice,
+        model,
+        provider_profile,
+    )
+    .run()
+    .await
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::path::Path;
 
     #[test]
     fn acp_tool_kind_maps_core_tools() {
@@ -1181,8 +1390,58 @@ mod tests {
     #[test]
     fn cwd_must_be_absolute() {
         let params = json!({"cwd": "relative"});
-        assert!(cwd_from_params(&params).is_err());
+        assert!(cwd_from_params(&params, None).is_err());
         let params = json!({"cwd": "/tmp"});
-        assert_eq!(cwd_from_params(&params).unwrap(), Path::new("/tmp"));
+        assert_eq!(cwd_from_params(&params, None).unwrap(), Path::new("/tmp"));
+    }
+
+    #[test]
+    fn session_cwd_falls_back_to_initialize_cwd() {
+        let init = json!({"protocolVersion": 1, "cwd": "/work/project"});
+        let default_cwd = initialize_cwd(&init).unwrap();
+        assert_eq!(default_cwd.as_deref(), Some(Path::new("/work/project")));
+        assert_eq!(
+            cwd_from_params(&json!({}), default_cwd.clone()).unwrap(),
+            Path::new("/work/project")
+        );
+        assert_eq!(
+            cwd_from_params(&json!({"cwd": "/tmp"}), default_cwd).unwrap(),
+            Path::new("/tmp")
+        );
+        let meta = json!({"_meta": {"jcode": {"cwd": "/srv"}}});
+        assert_eq!(
+            initialize_cwd(&meta).unwrap().as_deref(),
+            Some(Path::new("/srv"))
+        );
+        assert!(initialize_cwd(&json!({"cwd": "relative"})).is_err());
+        assert_eq!(initialize_cwd(&json!({})).unwrap(), None);
+    }
+
+    #[test]
+    fn permission_request_maps_approval_prompt_and_outcome() {
+        let params = permission_request_params("s1", "call_1", "bash: cargo test");
+        assert_eq!(params["sessionId"], "s1");
+        assert_eq!(params["toolCall"]["toolCallId"], "call_1");
+        assert_eq!(params["toolCall"]["title"], "bash: cargo test");
+        assert_eq!(params["toolCall"]["kind"], "execute");
+        assert_eq!(params["options"][0]["kind"], "allow_once");
+        assert_eq!(params["options"][1]["kind"], "reject_once");
+
+        let allowed = json!({"outcome": {"outcome": "selected", "optionId": "allow_once"}});
+        assert!(permission_allowed(&allowed));
+        let rejected = json!({"outcome": {"outcome": "selected", "optionId": "reject_once"}});
+        assert!(!permission_allowed(&rejected));
+        assert!(!permission_allowed(
+            &json!({"outcome": {"outcome": "cancelled"}})
+        ));
+    }
+
+    #[test]
+    fn json_rpc_parse_keeps_client_responses() {
+        let ok = JsonRpcMessage::parse(r#"{"jsonrpc":"2.0","id":3,"result":{"a":1}}"#).unwrap();
+        assert!(ok.method.is_none());
+        assert_eq!(ok.response, Some(Ok(json!({"a": 1}))));
+        let err = JsonRpcMessage::parse(r#"{"jsonrpc":"2.0","id":4,"error":{"code":-1}}"#).unwrap();
+        assert_eq!(err.response, Some(Err(json!({"code": -1}))));
     }
 }
@@ -26,6 +26,83 @@ pub(crate) enum ProviderAuthArg {
     None,
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+pub(crate) enum UpdateChannelArg {
+    /// Tagged GitHub releases
+    Stable,
+    /// The latest prerelease build of main
+    #[value(alias = "main")]
+    Nightly,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+pub(crate) enum StorageBackendArg {
+    /// Single SQLite database (`~/.jcode/jcode.db`)
+    #[value(alias = "db")]
+    Sqlite,
+    /// One JSON file per session and memory graph
+    #[value(alias = "file")]
+    Files,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+pub(crate) enum SessionImportSourceArg {
+    /// Claude Code transcripts (`~/.claude/projects`)
+    #[value(name = "claude-code", alias = "claude")]
+    ClaudeCode,
+    /// Codex CLI rollouts (`~/.codex/sessions`)
+    Codex,
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum RunOutputFormat {
+    /// Stream the response as plain text
+    #[default]
+    Text,
+    /// Print one JSON result object at the end; progress goes to stderr
+    Json,
+    /// Print one JSON event per line while the response streams
+    StreamJson,
+}
+
+impl RunOutputFormat {
+    /// Resolve `--output` together with the older `--json` / `--ndjson` flags.
+    pub fn resolve(output: Option<Self>, json: bool, ndjson: bool) -> Self {
+        match output {
+            Some(output) => output,
+            None if json => Self::Json,
+            None if ndjson => Self::StreamJson,
+            None => Self::Text,
+        }
+    }
+}
+
+/// Which session `jcode run` sends its message to.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum RunSession {
+    /// A new session, or the one named by the global `--resume`
+    #[default]
+    Default,
+    /// A new session that later `--continue` runs pick up
+    New,
+    /// The most recent `jcode run` session started in this directory
+    Continue,
+    /// A session by name or id
+    Named(String),
+}
+
+impl RunSession {
+    /// Resolve the `--new`, `--continue` and `--session` flags.
+    pub fn resolve(new: bool, continue_session: bool, session: Option<String>) -> Self {
+        match session {
+            Some(session) => Self::Named(session),
+            None if continue_session => Self::Continue,
+            None if new => Self::New,
+            None => Self::Default,
+        }
+    }
+}
+
 #[derive(Parser, Debug)]
 #[command(name = "jcode")]
 #[command(version = jcode_build_meta::VERSION)]
@@ -39,6 +116,11 @@ pub(crate) struct Args {
     #[arg(short = 'C', long, global = true)]
     pub(crate) cwd: Option<String>,
 
+    /// Extra workspace root for a session spanning several repositories
+    /// (repeatable). Files in it are addressed as `name:relative/path`.
+    #[arg(long = "root", value_name = "PATH", global = true)]
+    pub(crate) roots: Vec<String>,
+
     /// Skip the automatic update check
     #[arg(long, global = true)]
     pub(crate) no_update: bool,
@@ -47,14 +129,31 @@ pub(crate) struct Args {
     #[arg(long, global = true, default_value = "true")]
     pub(crate) auto_update: bool,
 
-    /// Log tool inputs/outputs and token usage to stderr
-    #[arg(long, global = true)]
-    pub(crate) trace: bool,
+    /// Log tool inputs/outputs and token usage to stderr, and raise file
+    /// logging to debug. `--trace=provider=trace,tool=debug` sets per-module
+    /// levels instead (same syntax as JCODE_LOG).
+    #[arg(
+        long,
+        global = true,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "debug",
+        value_name = "DIRECTIVES"
+    )]
+    pub(crate) trace: Option<String>,
 
     /// Suppress non-error CLI/status output for scripting and wrappers
     #[arg(long, global = true)]
     pub(crate) quiet: bool,
 
+    /// Refuse to start when config.toml has unknown or invalid keys
+    #[arg(long, global = true)]
+    pub(crate) strict_config: bool,
+
+    /// Skip the first-run onboarding flow (login, settings, JCODE.md)
+    #[arg(long, global = true)]
+    pub(crate) no_onboarding: bool,
+
     /// Resume a session by ID, or list sessions if no ID provided
     #[arg(long, global = true, num_args = 0..=1, default_missing_value = "")]
     pub(crate) resume: Option<String>,
@@ -84,6 +183,19 @@ pub(crate) struct Args {
     #[arg(long, global = true)]
     pub(crate) provider_profile: Option<String>,
 
+    /// Named agent profile from [profiles.<name>] in config.toml (model, tools,
+    /// extra system prompt, approval mode, limits). Not global: `jcode cloud`
+    /// has its own `--profile`, so pass it before the subcommand.
+    #[arg(long)]
+    pub(crate) profile: Option<String>,
+
+    /// Start with conservative defaults: approval before risky tool calls,
+    /// bash sandboxed to the working directory, no memory writes or ambient
+    /// scheduling, and a turn cap. Turn off in the session with
+    /// `/safe off confirm`.
+    #[arg(long)]
+    pub(crate) safe: bool,
+
     /// Tool profile to expose to the model: full, minimal/lite, or none.
     #[arg(long, global = true)]
     pub(crate) tool_profile: Option<String>,
@@ -124,6 +236,9 @@ pub(crate) enum Command {
     /// Run as an Agent Client Protocol (ACP) adapter backed by the Jcode daemon
     Acp,
 
+    /// Run a Telegram bot that drives sessions on the Jcode daemon
+    Telegram,
+
     /// Manage the background server daemon (e.g. `jcode server stop`).
     Server {
         #[command(subcommand)]
@@ -135,16 +250,78 @@ pub(crate) enum Command {
 
     /// Run a single message and exit
     Run {
-        /// Emit a machine-readable JSON result instead of streaming text
+        /// Output format. `json` and `stream-json` keep stdout machine-readable
+        #[arg(long, value_enum, value_name = "FORMAT", conflicts_with_all = ["json", "ndjson"])]
+        output: Option<RunOutputFormat>,
+
+        /// Same as `--output json`
         #[arg(long, conflicts_with = "ndjson")]
         json: bool,
 
-        /// Emit newline-delimited JSON events while the response streams
+        /// Same as `--output stream-json`
         #[arg(long, conflicts_with = "json")]
         ndjson: bool,
 
-        /// The message to send
-        message: String,
+        /// Run in plan mode (read-only tools), print the proposed plan, and exit
+        #[arg(long, conflicts_with = "ndjson")]
+        plan_only: bool,
+
+        /// Send the message to each model without tools and print every answer
+        /// with its latency and token counts; nothing is added to the session
+        #[arg(
+            long,
+            value_name = "MODEL_A,MODEL_B",
+            value_delimiter = ',',
+            num_args = 1,
+            conflicts_with_all = ["plan_only", "ndjson"]
+        )]
+        compare: Vec<String>,
+
+        /// Stop after this many model turns per request (overrides `[agent] max_turns`)
+        #[arg(long, value_name = "N")]
+        max_turns: Option<u32>,
+
+        /// Pin temperature, top_p, seed and a dated model snapshot for
+        /// reproducible runs (same as `[provider] deterministic = true`)
+        #[arg(long)]
+        deterministic: bool,
+
+        /// Include a text file as context (repeatable)
+        #[arg(long, value_name = "PATH")]
+        attach: Vec<String>,
+
+        /// Append the instructions in this file to the system prompt for this run
+        #[arg(long, value_name = "PATH")]
+        append_system: Option<String>,
+
+        /// Pipe the final response to this shell command's stdin after the run
+        #[arg(long, value_name = "COMMAND")]
+        tee_cmd: Option<String>,
+
+        /// Continue the most recent `jcode run` session in this directory
+        #[arg(long = "continue", conflicts_with_all = ["session", "new"])]
+        continue_session: bool,
+
+        /// Continue a session by name or id
+        #[arg(long, value_name = "NAME_OR_ID", conflicts_with = "new")]
+        session: Option<String>,
+
+        /// Start a fresh session that a later `--continue` picks up
+        #[arg(long)]
+        new: bool,
+
+        /// Send the saved prompt `~/.jcode/prompts/<NAME>.md` (or the project's
+        /// `.jcode/prompts/<NAME>.md`) instead of a message
+        #[arg(long, value_name = "NAME", conflicts_with = "message")]
+        prompt: Option<String>,
+
+        /// Set a `{{variable}}` in the saved prompt (repeatable)
+        #[arg(long, value_name = "NAME=VALUE", requires = "prompt")]
+        var: Vec<String>,
+
+        /// The message to send, or `-` to read it from stdin
+        #[arg(required_unless_present = "prompt")]
+        message: Option<String>,
     },
 
     /// Login to a provider via OAuth, API key, or local credentials
@@ -211,7 +388,11 @@ pub(crate) enum Command {
     Repl,
 
     /// Update jcode to the latest version
-    Update,
+    Update {
+        /// Switch the update channel (saved to `[update] channel`) before updating
+        #[arg(long, value_enum)]
+        channel: Option<UpdateChannelArg>,
+    },
 
     /// Show build/version information in human or JSON form
     Version {
@@ -220,11 +401,51 @@ pub(crate) enum Command {
         json: bool,
     },
 
+    /// Check the installation, credentials, server, config, MCP servers,
+    /// terminal, disk space and clock, with a fix for each problem
+    Doctor {
+        /// Emit JSON instead of plain text (for bug reports)
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Show usage limits for connected providers
     Usage {
         /// Emit JSON instead of plain text
         #[arg(long)]
         json: bool,
+
+        #[command(subcommand)]
+        action: Option<UsageCommand>,
+    },
+
+    /// List open todos shared across sessions in the current project
+    ///
+    /// Reads `.jcode/todos.json` in the current directory.
+    Todos {
+        /// Include completed and cancelled todos
+        #[arg(long)]
+        all: bool,
+
+        /// Emit JSON instead of plain text
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show which jcode session, turn and instruction wrote each line of a file
+    ///
+    /// Joins `git blame` with `.jcode/provenance.jsonl`, which `[git] provenance`
+    /// keeps up to date.
+    Blame {
+        /// File to blame
+        file: String,
+
+        /// Only this line (1-based)
+        line: Option<usize>,
+
+        /// Emit JSON instead of plain text
+        #[arg(long)]
+        json: bool,
     },
 
     /// Self-development mode: run as a canary session on the shared server
@@ -235,9 +456,26 @@ pub(crate) enum Command {
         build: bool,
     },
 
+    /// Roll the stable build back to a previously installed version
+    Rollback {
+        /// Version label or git hash prefix (default: the previous stable build)
+        version: Option<String>,
+
+        /// List recent stable builds and installed versions without changing anything
+        #[arg(long)]
+        list: bool,
+    },
+
+    /// Promote the self-dev canary build to stable once its track record is clean
+    Promote {
+        /// Promote even with crash/rollback events or too few clean session-hours
+        #[arg(long)]
+        force: bool,
+    },
+
     /// Debug socket CLI - interact with running jcode server
     Debug {
-        /// Debug command to run (list, start, sessions, create_session, message, tool, state, history, etc.)
+        /// Debug command to run (list, start, builds, canary, crash-report, notify-test, network, sessions, create_session, message, tool, state, history, etc.)
         #[arg(default_value = "help")]
         command: String,
 
@@ -256,6 +494,10 @@ pub(crate) enum Command {
         /// Wait for response to complete (for message command)
         #[arg(short, long)]
         wait: bool,
+
+        /// Pack the most recent crash report instead of writing a new one (for crash-report)
+        #[arg(long)]
+        last: bool,
     },
 
     /// Authentication status and validation helpers
@@ -271,9 +513,56 @@ pub(crate) enum Command {
     Memory(MemoryCommand),
 
     /// Session management commands
-    #[command(subcommand)]
+    #[command(subcommand, alias = "sessions")]
     Session(SessionCommand),
 
+    /// Scaffold and validate skills
+    #[command(subcommand, alias = "skills")]
+    Skill(SkillCommand),
+
+    /// Session and memory storage backend commands
+    #[command(subcommand)]
+    Storage(StorageCommand),
+
+    /// Back up or restore sessions, memories, skills, and config
+    #[command(subcommand)]
+    Backup(BackupCommand),
+
+    /// Read, change, or validate config.toml settings
+    #[command(subcommand)]
+    Config(ConfigCommand),
+
+    /// Review and trust a project's .jcode/hooks.toml rules
+    #[command(subcommand)]
+    Hooks(HooksCommand),
+
+    /// Show structured logs, filtered and pretty-printed
+    Logs {
+        /// Only records from this session (ID or memorable short name)
+        #[arg(long)]
+        session: Option<String>,
+
+        /// Least severe level to show: error, warn, info, debug, or trace
+        #[arg(long)]
+        level: Option<String>,
+
+        /// Only records whose module starts with this, e.g. provider
+        #[arg(long)]
+        target: Option<String>,
+
+        /// Show at most this many of the latest matching records
+        #[arg(short = 'n', long, default_value_t = 200)]
+        lines: usize,
+
+        /// Keep printing new records as they are written
+        #[arg(short, long)]
+        follow: bool,
+
+        /// Print the raw JSONL records
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Ambient mode management
     #[command(subcommand)]
     Ambient(AmbientCommand),
@@ -384,6 +673,15 @@ pub(crate) enum Command {
         no_centered: bool,
     },
 
+    /// Read a saved session in a read-only transcript view
+    ///
+    /// Reads the session file directly: no provider login, no server, and no
+    /// composer. Press `q` to exit or `r` to resume the session for real.
+    View {
+        /// Session ID, name, or path to session JSON file
+        session: String,
+    },
+
     /// Model management commands
     #[command(subcommand)]
     Model(ModelCommand),
@@ -492,6 +790,19 @@ pub(crate) enum Command {
     },
 }
 
+#[derive(Subcommand, Debug)]
+pub(crate) enum UsageCommand {
+    /// Project when each subscription window hits 100% at the current burn rate
+    ///
+    /// Forecasts come from usage samples recorded on every fetch and stored in
+    /// `~/.jcode/usage_history.json`.
+    Forecast {
+        /// Emit JSON instead of plain text
+        #[arg(long)]
+        json: bool,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 pub(crate) enum ServerCommand {
     /// Gracefully reload the running background server onto the newest binary.
@@ -798,6 +1109,157 @@ pub(crate) enum SessionCommand {
         #[arg(long)]
         json: bool,
     },
+
+    /// Upgrade saved sessions to the current on-disk schema
+    Migrate {
+        /// Session ID or memorable short name, e.g. fox
+        #[arg(required_unless_present = "all")]
+        session: Option<String>,
+
+        /// Migrate every saved session
+        #[arg(long, conflicts_with = "session")]
+        all: bool,
+    },
+
+    /// Convert Claude Code or Codex transcripts into resumable jcode sessions
+    Import {
+        /// CLI the transcripts come from
+        #[arg(long, value_enum)]
+        from: SessionImportSourceArg,
+
+        /// Transcript file or directory (defaults to the CLI's session directory)
+        path: Option<String>,
+    },
+
+    /// List the files a session's tools changed, against their session-start content
+    Changes {
+        /// Session ID or memorable short name, e.g. fox
+        session: String,
+
+        /// Print each file's diff after the list
+        #[arg(long)]
+        diff: bool,
+
+        /// Emit JSON instead of human-readable output
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum SkillCommand {
+    /// Create ~/.jcode/skills/<name>/ with a commented SKILL.md template
+    New {
+        /// Skill name (lowercase letters, digits, and `-`)
+        name: String,
+
+        /// Create it in ./.jcode/skills/ instead of ~/.jcode/skills/
+        #[arg(long)]
+        project: bool,
+    },
+
+    /// Validate skills and print actionable errors
+    Check {
+        /// Skill name or skill directory path (default: every installed skill)
+        name: Option<String>,
+    },
+
+    /// Translate Claude Code skills and slash commands into jcode skills
+    Import {
+        /// Claude Code directory to import (default: ./.claude, else ~/.claude)
+        #[arg(
+            long = "from-claude",
+            value_name = "PATH",
+            num_args = 0..=1,
+            default_missing_value = "",
+            required = true
+        )]
+        from_claude: Option<String>,
+
+        /// Import into ./.jcode/skills/ instead of ~/.jcode/skills/
+        #[arg(long)]
+        project: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum StorageCommand {
+    /// Copy sessions and memories to another backend and verify each one
+    Migrate {
+        /// Backend to copy into; the source is left in place
+        #[arg(long, value_enum)]
+        to: StorageBackendArg,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum ConfigCommand {
+    /// Print the effective value of a setting, e.g. `ambient.enabled`
+    Get {
+        /// Dotted key, e.g. `display.centered`
+        key: String,
+    },
+
+    /// Write a setting to config.toml, keeping the rest of the file as is
+    Set {
+        /// Dotted key, e.g. `display.centered`
+        key: String,
+
+        /// TOML value (`true`, `42`, `["a", "b"]`); anything else is stored as a string
+        value: String,
+    },
+
+    /// Validate the effective config and the files and credentials it refers to
+    Doctor,
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum HooksCommand {
+    /// Show the project's hook rules and whether they are trusted
+    Status {
+        /// Project directory (default: the current directory)
+        dir: Option<String>,
+    },
+
+    /// Let the current contents of .jcode/hooks.toml run; any later change
+    /// needs a new trust decision
+    Trust {
+        /// Project directory (default: the current directory)
+        dir: Option<String>,
+    },
+
+    /// Stop running the project's hook rules
+    Untrust {
+        /// Project directory (default: the current directory)
+        dir: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum BackupCommand {
+    /// Pack ~/.jcode state into a .tar.zst archive
+    Create {
+        /// Archive to write, e.g. jcode-backup.tar.zst
+        output: String,
+
+        /// Also pack OAuth tokens and API key files
+        #[arg(long)]
+        include_auth: bool,
+    },
+
+    /// Restore an archive made by `jcode backup create`
+    Restore {
+        /// Archive to restore from
+        input: String,
+
+        /// Show what would be created, merged, or kept without writing
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Replace differing local files instead of keeping them
+        #[arg(long)]
+        force: bool,
+    },
 }
 
 #[derive(Subcommand, Debug)]
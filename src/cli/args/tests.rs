@@ -119,6 +119,107 @@ fn session_rename_subcommand_parses() {
     }
 }
 
+#[test]
+fn sessions_migrate_subcommand_parses() {
+    let args = Args::try_parse_from(["jcode", "sessions", "migrate", "--all"]).unwrap();
+    match args.command {
+        Some(Command::Session(SessionCommand::Migrate { session, all })) => {
+            assert!(session.is_none());
+            assert!(all);
+        }
+        other => panic!("unexpected command: {:?}", other),
+    }
+
+    let args = Args::try_parse_from(["jcode", "session", "migrate", "fox"]).unwrap();
+    match args.command {
+        Some(Command::Session(SessionCommand::Migrate { session, all })) => {
+            assert_eq!(session.as_deref(), Some("fox"));
+            assert!(!all);
+        }
+        other => panic!("unexpected command: {:?}", other),
+    }
+
+    assert!(Args::try_parse_from(["jcode", "sessions", "migrate"]).is_err());
+}
+
+#[test]
+fn todos_subcommand_parses() {
+    let args = Args::try_parse_from(["jcode", "todos", "--all", "--json"]).unwrap();
+    match args.command {
+        Some(Command::Todos { all, json }) => assert!(all && json),
+        other => panic!("unexpected command: {:?}", other),
+    }
+}
+
+#[test]
+fn sessions_changes_subcommand_parses() {
+    let args = Args::try_parse_from(["jcode", "sessions", "changes", "fox", "--diff"]).unwrap();
+    match args.command {
+        Some(Command::Session(SessionCommand::Changes {
+            session,
+            diff,
+            json,
+        })) => {
+            assert_eq!(session, "fox");
+            assert!(diff);
+            assert!(!json);
+        }
+        other => panic!("unexpected command: {:?}", other),
+    }
+    assert!(Args::try_parse_from(["jcode", "sessions", "changes"]).is_err());
+}
+
+#[test]
+fn blame_subcommand_takes_an_optional_line() {
+    let args = Args::try_parse_from(["jcode", "blame", "src/main.rs", "42"]).unwrap();
+    match args.command {
+        Some(Command::Blame { file, line, json }) => {
+            assert_eq!(file, "src/main.rs");
+            assert_eq!(line, Some(42));
+            assert!(!json);
+        }
+        other => panic!("unexpected command: {:?}", other),
+    }
+    let args = Args::try_parse_from(["jcode", "blame", "--json", "src/main.rs"]).unwrap();
+    assert!(matches!(
+        args.command,
+        Some(Command::Blame {
+            line: None,
+            json: true,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn profile_flag_selects_agent_profile() {
+    let args = Args::try_parse_from(["jcode", "--profile", "review"]).unwrap();
+    assert_eq!(args.profile.as_deref(), Some("review"));
+
+    let args = Args::try_parse_from(["jcode", "--profile", "quickfix", "run", "hi"]).unwrap();
+    assert_eq!(args.profile.as_deref(), Some("quickfix"));
+    assert!(matches!(args.command, Some(Command::Run { .. })));
+}
+
+#[test]
+fn safe_flag_parses() {
+    let args = Args::try_parse_from(["jcode", "--safe"]).unwrap();
+    assert!(args.safe);
+    assert!(args.command.is_none());
+
+    let args = Args::try_parse_from(["jcode"]).unwrap();
+    assert!(!args.safe);
+}
+
+#[test]
+fn view_subcommand_parses() {
+    let args = Args::try_parse_from(["jcode", "view", "ses_fox"]).unwrap();
+    match args.command {
+        Some(Command::View { session }) => assert_eq!(session, "ses_fox"),
+        other => panic!("unexpected command: {:?}", other),
+    }
+}
+
 #[test]
 fn cloud_sessions_subcommands_parse() {
     let args = Args::try_parse_from([
@@ -471,18 +572,52 @@ fn acp_subcommand_parses() {
     }
 }
 
+#[test]
+fn telegram_subcommand_parses() {
+    let args = Args::try_parse_from(["jcode", "telegram"]).unwrap();
+    match args.command {
+        Some(Command::Telegram) => {}
+        other => panic!("unexpected command: {:?}", other),
+    }
+}
+
 #[test]
 fn run_json_subcommand_parses() {
     let args = Args::try_parse_from(["jcode", "run", "--json", "hello"]).unwrap();
     match args.command {
         Some(Command::Run {
+            output,
             json,
             ndjson,
+            plan_only,
+            compare,
+            max_turns,
+            attach,
+            append_system,
+            tee_cmd,
+            continue_session,
+            session,
+            new,
+            prompt,
+            var,
             message,
         }) => {
+            assert_eq!(prompt, None);
+            assert!(var.is_empty());
+            assert_eq!(output, None);
+            assert!(attach.is_empty());
+            assert_eq!(append_system, None);
+            assert_eq!(tee_cmd, None);
+            assert_eq!(
+                RunSession::resolve(new, continue_session, session),
+                RunSession::Default
+            );
             assert!(json);
             assert!(!ndjson);
-            assert_eq!(message, "hello");
+            assert!(!plan_only);
+            assert!(compare.is_empty());
+            assert_eq!(max_turns, None);
+            assert_eq!(message.as_deref(), Some("hello"));
         }
         other => panic!("unexpected command: {:?}", other),
     }
@@ -493,16 +628,242 @@ fn run_ndjson_subcommand_parses() {
     let args = Args::try_parse_from(["jcode", "run", "--ndjson", "hello"]).unwrap();
     match args.command {
         Some(Command::Run {
+            output,
             json,
             ndjson,
+            plan_only,
+            compare,
+            max_turns,
+            attach,
+            append_system,
+            tee_cmd,
+            continue_session,
+            session,
+            new,
+            prompt,
+            var,
             message,
         }) => {
+            assert_eq!(prompt, None);
+            assert!(var.is_empty());
+            assert_eq!(output, None);
+            assert!(attach.is_empty());
+            assert_eq!(append_system, None);
+            assert_eq!(tee_cmd, None);
+            assert_eq!(
+                RunSession::resolve(new, continue_session, session),
+                RunSession::Default
+            );
             assert!(!json);
             assert!(ndjson);
-            assert_eq!(message, "hello");
+            assert!(!plan_only);
+            assert!(compare.is_empty());
+            assert_eq!(max_turns, None);
+            assert_eq!(message.as_deref(), Some("hello"));
+        }
+        other => panic!("unexpected command: {:?}", other),
+    }
+}
+
+#[test]
+fn run_plan_only_subcommand_parses() {
+    let args = Args::try_parse_from(["jcode", "run", "--plan-only", "add auth"]).unwrap();
+    match args.command {
+        Some(Command::Run {
+            plan_only, message, ..
+        }) => {
+            assert!(plan_only);
+            assert_eq!(message.as_deref(), Some("add auth"));
+        }
+        other => panic!("unexpected command: {:?}", other),
+    }
+    assert!(Args::try_parse_from(["jcode", "run", "--plan-only", "--ndjson", "x"]).is_err());
+}
+
+#[test]
+fn run_compare_flag_splits_models() {
+    let args =
+        Args::try_parse_from(["jcode", "run", "--compare", "opus,gpt-5.4", "explain"]).unwrap();
+    match args.command {
+        Some(Command::Run {
+            compare, message, ..
+        }) => {
+            assert_eq!(compare, vec!["opus".to_string(), "gpt-5.4".to_string()]);
+            assert_eq!(message.as_deref(), Some("explain"));
+        }
+        other => panic!("unexpected command: {:?}", other),
+    }
+    assert!(
+        Args::try_parse_from(["jcode", "run", "--compare", "a,b", "--plan-only", "x"]).is_err()
+    );
+}
+
+#[test]
+fn run_max_turns_flag_parses() {
+    let args = Args::try_parse_from(["jcode", "run", "--max-turns", "20", "fix it"]).unwrap();
+    match args.command {
+        Some(Command::Run {
+            max_turns, message, ..
+        }) => {
+            assert_eq!(max_turns, Some(20));
+            assert_eq!(message.as_deref(), Some("fix it"));
+        }
+        other => panic!("unexpected command: {:?}", other),
+    }
+    assert!(Args::try_parse_from(["jcode", "run", "--max-turns", "many", "x"]).is_err());
+}
+
+#[test]
+fn run_deterministic_flag_parses() {
+    let args = Args::try_parse_from(["jcode", "run", "--deterministic", "eval"]).unwrap();
+    match args.command {
+        Some(Command::Run {
+            deterministic,
+            message,
+            ..
+        }) => {
+            assert!(deterministic);
+            assert_eq!(message.as_deref(), Some("eval"));
+        }
+        other => panic!("unexpected command: {:?}", other),
+    }
+}
+
+#[test]
+fn run_stdin_attach_and_append_system_flags_parse() {
+    let args = Args::try_parse_from([
+        "jcode",
+        "run",
+        "--attach",
+        "CONTRIBUTING.md",
+        "--attach",
+        "docs/style.md",
+        "--append-system",
+        "AGENTS.md",
+        "-",
+    ])
+    .unwrap();
+    match args.command {
+        Some(Command::Run {
+            attach,
+            append_system,
+            message,
+            ..
+        }) => {
+            assert_eq!(attach, vec!["CONTRIBUTING.md", "docs/style.md"]);
+            assert_eq!(append_system.as_deref(), Some("AGENTS.md"));
+            assert_eq!(message.as_deref(), Some("-"));
+        }
+        other => panic!("unexpected command: {:?}", other),
+    }
+}
+
+#[test]
+fn run_tee_cmd_flag_parses() {
+    let args =
+        Args::try_parse_from(["jcode", "run", "--tee-cmd", "tee -a log.md", "summarize"]).unwrap();
+    match args.command {
+        Some(Command::Run {
+            tee_cmd, message, ..
+        }) => {
+            assert_eq!(tee_cmd.as_deref(), Some("tee -a log.md"));
+            assert_eq!(message.as_deref(), Some("summarize"));
+        }
+        other => panic!("unexpected command: {:?}", other),
+    }
+}
+
+#[test]
+fn run_saved_prompt_flags_parse() {
+    let args = Args::try_parse_from([
+        "jcode",
+        "run",
+        "--prompt",
+        "review",
+        "--var",
+        "base=main",
+        "--var",
+        "focus=tests",
+    ])
+    .unwrap();
+    match args.command {
+        Some(Command::Run {
+            prompt,
+            var,
+            message,
+            ..
+        }) => {
+            assert_eq!(prompt.as_deref(), Some("review"));
+            assert_eq!(var, vec!["base=main", "focus=tests"]);
+            assert_eq!(message, None);
+        }
+        other => panic!("unexpected command: {:?}", other),
+    }
+    assert!(Args::try_parse_from(["jcode", "run"]).is_err());
+    assert!(Args::try_parse_from(["jcode", "run", "--prompt", "review", "hi"]).is_err());
+    assert!(Args::try_parse_from(["jcode", "run", "--var", "base=main", "hi"]).is_err());
+}
+
+#[test]
+fn run_session_flags_parse() {
+    let run_session = |argv: &[&str]| match Args::try_parse_from(argv).unwrap().command {
+        Some(Command::Run {
+            continue_session,
+            session,
+            new,
+            ..
+        }) => RunSession::resolve(new, continue_session, session),
+        other => panic!("unexpected command: {:?}", other),
+    };
+    assert_eq!(
+        run_session(&["jcode", "run", "--continue", "next step"]),
+        RunSession::Continue
+    );
+    assert_eq!(
+        run_session(&["jcode", "run", "--session", "fox", "next step"]),
+        RunSession::Named("fox".to_string())
+    );
+    assert_eq!(
+        run_session(&["jcode", "run", "--new", "start"]),
+        RunSession::New
+    );
+    assert!(Args::try_parse_from(["jcode", "run", "--continue", "--new", "x"]).is_err());
+    assert!(Args::try_parse_from(["jcode", "run", "--continue", "--session", "fox", "x"]).is_err());
+    assert!(Args::try_parse_from(["jcode", "run", "--session", "fox", "--new", "x"]).is_err());
+}
+
+#[test]
+fn run_output_flag_parses_and_resolves_legacy_flags() {
+    let args = Args::try_parse_from(["jcode", "run", "--output", "stream-json", "hi"]).unwrap();
+    match args.command {
+        Some(Command::Run {
+            output,
+            json,
+            ndjson,
+            ..
+        }) => {
+            assert_eq!(output, Some(RunOutputFormat::StreamJson));
+            assert_eq!(
+                RunOutputFormat::resolve(output, json, ndjson),
+                RunOutputFormat::StreamJson
+            );
         }
         other => panic!("unexpected command: {:?}", other),
     }
+    assert_eq!(
+        RunOutputFormat::resolve(None, true, false),
+        RunOutputFormat::Json
+    );
+    assert_eq!(
+        RunOutputFormat::resolve(None, false, true),
+        RunOutputFormat::StreamJson
+    );
+    assert_eq!(
+        RunOutputFormat::resolve(None, false, false),
+        RunOutputFormat::Text
+    );
+    assert!(Args::try_parse_from(["jcode", "run", "--output", "json", "--json", "x"]).is_err());
+    assert!(Args::try_parse_from(["jcode", "run", "--output", "yaml", "x"]).is_err());
 }
 
 #[test]
@@ -514,11 +875,35 @@ fn version_subcommand_parses() {
     }
 }
 
+#[test]
+fn doctor_subcommand_parses() {
+    let args = Args::try_parse_from(["jcode", "doctor", "--json"]).unwrap();
+    match args.command {
+        Some(Command::Doctor { json }) => assert!(json),
+        other => panic!("unexpected command: {:?}", other),
+    }
+}
+
 #[test]
 fn usage_subcommand_parses() {
     let args = Args::try_parse_from(["jcode", "usage", "--json"]).unwrap();
     match args.command {
-        Some(Command::Usage { json }) => assert!(json),
+        Some(Command::Usage { json, action }) => {
+            assert!(json);
+            assert!(action.is_none());
+        }
+        other => panic!("unexpected command: {:?}", other),
+    }
+}
+
+#[test]
+fn usage_forecast_subcommand_parses() {
+    let args = Args::try_parse_from(["jcode", "usage", "forecast", "--json"]).unwrap();
+    match args.command {
+        Some(Command::Usage {
+            json: false,
+            action: Some(UsageCommand::Forecast { json }),
+        }) => assert!(json),
         other => panic!("unexpected command: {:?}", other),
     }
 }
@@ -672,3 +1057,245 @@ fn onboarding_repair_brief_commands_are_valid_cli() {
     ])
     .expect("provider add --base-url --model --api-key-stdin must parse");
 }
+
+#[test]
+fn rollback_subcommand_parses_optional_version() {
+    let args = Args::try_parse_from(["jcode", "rollback"]).unwrap();
+    match args.command {
+        Some(Command::Rollback { version, list }) => {
+            assert_eq!(version, None);
+            assert!(!list);
+        }
+        other => panic!("unexpected command: {:?}", other),
+    }
+
+    let args = Args::try_parse_from(["jcode", "rollback", "abc1234", "--list"]).unwrap();
+    match args.command {
+        Some(Command::Rollback { version, list }) => {
+            assert_eq!(version.as_deref(), Some("abc1234"));
+            assert!(list);
+        }
+        other => panic!("unexpected command: {:?}", other),
+    }
+}
+
+#[test]
+fn update_subcommand_parses_channel() {
+    let args = Args::try_parse_from(["jcode", "update", "--channel", "nightly"]).unwrap();
+    match args.command {
+        Some(Command::Update { channel }) => {
+            assert_eq!(channel, Some(UpdateChannelArg::Nightly));
+        }
+        other => panic!("unexpected command: {:?}", other),
+    }
+
+    let args = Args::try_parse_from(["jcode", "update", "--channel", "main"]).unwrap();
+    assert!(matches!(
+        args.command,
+        Some(Command::Update {
+            channel: Some(UpdateChannelArg::Nightly)
+        })
+    ));
+    assert!(Args::try_parse_from(["jcode", "update", "--channel", "beta"]).is_err());
+}
+
+#[test]
+fn promote_subcommand_parses_force() {
+    let args = Args::try_parse_from(["jcode", "promote"]).unwrap();
+    assert!(matches!(
+        args.command,
+        Some(Command::Promote { force: false })
+    ));
+
+    let args = Args::try_parse_from(["jcode", "promote", "--force"]).unwrap();
+    assert!(matches!(
+        args.command,
+        Some(Command::Promote { force: true })
+    ));
+}
+
+#[test]
+fn logs_subcommand_parses_filters() {
+    let args = Args::try_parse_from(["jcode", "logs", "--session", "fox", "--level", "warn", "-f"])
+        .unwrap();
+    match args.command {
+        Some(Command::Logs {
+            session,
+            level,
+            target,
+            lines,
+            follow,
+            json,
+        }) => {
+            assert_eq!(session.as_deref(), Some("fox"));
+            assert_eq!(level.as_deref(), Some("warn"));
+            assert_eq!(target, None);
+            assert_eq!(lines, 200);
+            assert!(follow);
+            assert!(!json);
+        }
+        other => panic!("expected logs command, got {:?}", other),
+    }
+}
+
+#[test]
+fn trace_flag_takes_optional_directives() {
+    let args = Args::try_parse_from(["jcode", "--trace", "run", "hi"]).unwrap();
+    assert_eq!(args.trace.as_deref(), Some("debug"));
+    assert!(matches!(args.command, Some(Command::Run { .. })));
+
+    let args = Args::try_parse_from(["jcode", "--trace=provider=trace"]).unwrap();
+    assert_eq!(args.trace.as_deref(), Some("provider=trace"));
+
+    let args = Args::try_parse_from(["jcode"]).unwrap();
+    assert_eq!(args.trace, None);
+}
+
+#[test]
+fn debug_crash_report_accepts_last() {
+    let args = Args::try_parse_from(["jcode", "debug", "crash-report", "--last"]).unwrap();
+    match args.command {
+        Some(Command::Debug { command, last, .. }) => {
+            assert_eq!(command, "crash-report");
+            assert!(last);
+        }
+        other => panic!("expected debug command, got {:?}", other),
+    }
+}
+
+#[test]
+fn skill_subcommands_parse() {
+    let args = Args::try_parse_from(["jcode", "skill", "new", "deploy-docs", "--project"]).unwrap();
+    match args.command {
+        Some(Command::Skill(SkillCommand::New { name, project })) => {
+            assert_eq!(name, "deploy-docs");
+            assert!(project);
+        }
+        other => panic!("expected skill new command, got {:?}", other),
+    }
+
+    let args = Args::try_parse_from(["jcode", "skills", "check"]).unwrap();
+    assert!(matches!(
+        args.command,
+        Some(Command::Skill(SkillCommand::Check { name: None }))
+    ));
+
+    let args = Args::try_parse_from(["jcode", "skill", "import", "--from-claude"]).unwrap();
+    match args.command {
+        Some(Command::Skill(SkillCommand::Import {
+            from_claude,
+            project,
+        })) => {
+            assert_eq!(from_claude.as_deref(), Some(""));
+            assert!(!project);
+        }
+        other => panic!("expected skill import command, got {:?}", other),
+    }
+
+    let args = Args::try_parse_from([
+        "jcode",
+        "skill",
+        "import",
+        "--from-claude",
+        "../other/.claude",
+        "--project",
+    ])
+    .unwrap();
+    assert!(matches!(
+        args.command,
+        Some(Command::Skill(SkillCommand::Import {
+            from_claude: Some(ref path),
+            project: true,
+        })) if path == "../other/.claude"
+    ));
+    assert!(Args::try_parse_from(["jcode", "skill", "import"]).is_err());
+}
+
+#[test]
+fn storage_migrate_parses_target_backend() {
+    let args = Args::try_parse_from(["jcode", "storage", "migrate", "--to", "sqlite"]).unwrap();
+    assert!(matches!(
+        args.command,
+        Some(Command::Storage(StorageCommand::Migrate {
+            to: StorageBackendArg::Sqlite
+        }))
+    ));
+
+    let args = Args::try_parse_from(["jcode", "storage", "migrate", "--to", "files"]).unwrap();
+    assert!(matches!(
+        args.command,
+        Some(Command::Storage(StorageCommand::Migrate {
+            to: StorageBackendArg::Files
+        }))
+    ));
+
+    assert!(Args::try_parse_from(["jcode", "storage", "migrate"]).is_err());
+    assert!(Args::try_parse_from(["jcode", "storage", "migrate", "--to", "postgres"]).is_err());
+}
+
+#[test]
+fn backup_commands_parse_flags() {
+    let args = Args::try_parse_from(["jcode", "backup", "create", "state.tar.zst"]).unwrap();
+    assert!(matches!(
+        args.command,
+        Some(Command::Backup(BackupCommand::Create {
+            ref output,
+            include_auth: false,
+        })) if output == "state.tar.zst"
+    ));
+
+    let args = Args::try_parse_from([
+        "jcode",
+        "backup",
+        "restore",
+        "state.tar.zst",
+        "--dry-run",
+        "--force",
+    ])
+    .unwrap();
+    assert!(matches!(
+        args.command,
+        Some(Command::Backup(BackupCommand::Restore {
+            ref input,
+            dry_run: true,
+            force: true,
+        })) if input == "state.tar.zst"
+    ));
+
+    assert!(Args::try_parse_from(["jcode", "backup", "create"]).is_err());
+}
+
+#[test]
+fn hooks_commands_parse() {
+    let args = Args::try_parse_from(["jcode", "hooks", "trust"]).unwrap();
+    assert!(matches!(
+        args.command,
+        Some(Command::Hooks(HooksCommand::Trust { dir: None }))
+    ));
+    let args = Args::try_parse_from(["jcode", "hooks", "status", "../repo"]).unwrap();
+    assert!(matches!(
+        args.command,
+        Some(Command::Hooks(HooksCommand::Status { ref dir })) if dir.as_deref() == Some("../repo")
+    ));
+}
+
+#[test]
+fn config_commands_parse() {
+    let args =
+        Args::try_parse_from(["jcode", "config", "set", "display.centered", "true"]).unwrap();
+    assert!(matches!(
+        args.command,
+        Some(Command::Config(ConfigCommand::Set { ref key, ref value }))
+            if key == "display.centered" && value == "true"
+    ));
+    assert!(!args.strict_config);
+
+    let args = Args::try_parse_from(["jcode", "--strict-config", "config", "doctor"]).unwrap();
+    assert!(matches!(
+        args.command,
+        Some(Command::Config(ConfigCommand::Doctor))
+    ));
+    assert!(args.strict_config);
+
+    assert!(Args::try_parse_from(["jcode", "config", "get"]).is_err());
+}
@@ -10,12 +10,26 @@ use std::process::{Command as ProcessCommand, Stdio};
 
 use crate::{browser, gateway, memory, session, storage, tui};
 
+use super::args::{RunOutputFormat, RunSession, SessionImportSourceArg};
 use super::terminal::init_tui_runtime;
 
+mod backup;
+mod blame;
+mod canary;
+mod config;
+mod crash_report;
+mod doctor;
+mod hooks;
+mod logs;
 mod menubar;
+mod notify_test;
 mod provider_setup;
 mod report_info;
 mod restart;
+mod rollback;
+mod session_changes;
+mod skill;
+mod storage_migrate;
 
 pub(crate) use super::auth_test::run_post_login_validation;
 #[cfg(test)]
@@ -26,12 +40,25 @@ pub(crate) use super::auth_test::{
 pub use super::auth_test::{
     run_auth_test_command, run_auth_test_context_audit_command, run_auth_test_coverage_command,
 };
+pub use backup::{run_backup_create_command, run_backup_restore_command};
+pub use blame::run_blame_command;
+pub use canary::{print_canary_report, run_promote_command};
+pub use config::{run_config_doctor_command, run_config_get_command, run_config_set_command};
+pub use crash_report::run_crash_report_command;
+pub use doctor::run_doctor_command;
+pub use hooks::{run_hooks_status_command, run_hooks_trust_command, run_hooks_untrust_command};
+pub use logs::{LogsOptions, run_logs_command};
 pub use menubar::{ensure_menubar_helper_running, run_menubar_command};
+pub use notify_test::run_notify_test_command;
 pub(crate) use provider_setup::{ProviderAddOptions, run_provider_add_command};
 pub use restart::{
     maybe_run_pending_restart_restore_on_startup, run_restart_clear_command,
     run_restart_restore_command, run_restart_save_command, run_restart_status_command,
 };
+pub use rollback::{print_build_manifest, run_rollback_command};
+pub use session_changes::run_session_changes_command;
+pub use skill::{run_skill_check_command, run_skill_import_command, run_skill_new_command};
+pub use storage_migrate::run_storage_migrate_command;
 
 pub enum AmbientSubcommand {
     Status,
@@ -1509,6 +1536,117 @@ pub fn run_session_rename_command(
     Ok(())
 }
 
+pub fn run_session_migrate_command(session_ref: Option<&str>, all: bool) -> Result<()> {
+    let session_ids = if all {
+        saved_session_ids()?
+    } else {
+        let Some(session_ref) = session_ref else {
+            anyhow::bail!("Provide a session or use --all");
+        };
+        vec![session::find_session_by_name_or_id(session_ref)?]
+    };
+
+    let (mut migrated, mut current, mut newer, mut failed) = (0usize, 0usize, 0usize, 0usize);
+    for session_id in &session_ids {
+        match session::migrate_session(session_id) {
+            Ok(session::SessionSchemaStatus::Current) => current += 1,
+            Ok(session::SessionSchemaStatus::Migrated { from }) => {
+                migrated += 1;
+                println!(
+                    "✓ {}: schema v{} → v{}",
+                    session_id,
+                    from,
+                    session::SESSION_SCHEMA_VERSION
+                );
+            }
+            Ok(session::SessionSchemaStatus::Newer { version }) => {
+                newer += 1;
+                println!(
+                    "! {}: schema v{} is newer than this build (v{}); left untouched",
+                    session_id,
+                    version,
+                    session::SESSION_SCHEMA_VERSION
+                );
+            }
+            Err(error) => {
+                failed += 1;
+                println!("✗ {}: {}", session_id, error);
+            }
+        }
+    }
+    crate::tui::session_picker::invalidate_session_list_cache();
+
+    println!(
+        "{} migrated, {} already current, {} newer, {} failed",
+        migrated, current, newer, failed
+    );
+    if failed > 0 {
+        anyhow::bail!("{} session(s) could not be migrated", failed);
+    }
+    Ok(())
+}
+
+/// `jcode sessions import --from <cli> [path]`: convert every transcript
+/// under `path` into a saved jcode session tagged `imported`.
+pub fn run_session_import_command(from: SessionImportSourceArg, path: Option<&str>) -> Result<()> {
+    let source = match from {
+        SessionImportSourceArg::ClaudeCode => crate::import::SessionImportSource::ClaudeCode,
+        SessionImportSourceArg::Codex => crate::import::SessionImportSource::Codex,
+    };
+    let files = crate::import::session_import_files(source, path.map(Path::new))?;
+
+    let (mut imported, mut skipped, mut failed) = (0usize, 0usize, 0usize);
+    for file in &files {
+        match crate::import::import_session_file(source, file) {
+            Ok(Some(session)) => {
+                imported += 1;
+                println!(
+                    "✓ {} ({})",
+                    session.title.as_deref().unwrap_or("Untitled"),
+                    session.id
+                );
+            }
+            Ok(None) => skipped += 1,
+            Err(error) => {
+                failed += 1;
+                println!("✗ {}: {:#}", file.display(), error);
+            }
+        }
+    }
+    crate::tui::session_picker::invalidate_session_list_cache();
+
+    println!(
+        "{} {} session(s) imported, {} empty, {} failed",
+        imported,
+        source.label(),
+        skipped,
+        failed
+    );
+    if failed > 0 {
+        anyhow::bail!("{} transcript(s) could not be imported", failed);
+    }
+    Ok(())
+}
+
+fn saved_session_ids() -> Result<Vec<String>> {
+    let sessions_dir = storage::jcode_dir()?.join("sessions");
+    if !sessions_dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut session_ids = Vec::new();
+    for entry in std::fs::read_dir(&sessions_dir)?.flatten() {
+        let path = entry.path();
+        if path.is_file()
+            && path.extension().and_then(|ext| ext.to_str()) == Some("json")
+            && let Some(stem) = path.file_stem().and_then(|stem| stem.to_str())
+        {
+            session_ids.push(stem.to_string());
+        }
+    }
+    session_ids.sort();
+    Ok(session_ids)
+}
+
 async fn run_ambient_visible() -> Result<()> {
     use crate::ambient::VisibleCycleContext;
 
@@ -2034,13 +2172,48 @@ struct ModelListRouteReport {
     available: bool,
 }
 
+/// Result envelope printed by `jcode run --output json`.
 #[derive(Debug, Serialize)]
 struct RunCommandReport {
+    /// `ok` or `error`.
+    status: &'static str,
+    exit_code: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
     session_id: String,
     provider: String,
     model: String,
+    /// Final assistant text.
     text: String,
+    /// Model calls made across the run, including auto-poke follow-ups.
+    turns: u32,
+    /// Token usage summed across all model calls.
+    usage: crate::agent::TokenUsage,
+    tool_calls: Vec<RunToolCallSummary>,
+    elapsed_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    limits: Option<crate::protocol::AgentLimitStatus>,
+    /// Effective `--deterministic` settings of the last response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    determinism: Option<crate::message::DeterminismParams>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct RunToolCallSummary {
+    id: String,
+    name: String,
+    success: bool,
+    duration_ms: u64,
+}
+
+/// Collected from streamed events in `--output json` mode.
+#[derive(Debug, Default)]
+struct JsonRunState {
+    turns: u32,
     usage: crate::agent::TokenUsage,
+    tool_calls: Vec<RunToolCallSummary>,
+    tool_started: std::collections::HashMap<String, std::time::Instant>,
+    limits: Option<crate::protocol::AgentLimitStatus>,
 }
 
 #[derive(Debug, Default)]
@@ -2052,6 +2225,7 @@ struct NdjsonRunState {
     connection_phase: Option<String>,
     status_detail: Option<String>,
     usage: crate::agent::TokenUsage,
+    limits: Option<crate::protocol::AgentLimitStatus>,
 }
 
 pub fn run_auth_status_command(emit_json: bool) -> Result<()> {
@@ -2086,6 +2260,46 @@ pub async fn run_usage_command(emit_json: bool) -> Result<()> {
     report_info::run_usage_command(emit_json).await
 }
 
+pub async fn run_usage_forecast_command(emit_json: bool) -> Result<()> {
+    report_info::run_usage_forecast_command(emit_json).await
+}
+
+/// Print the project todos stored in `.jcode/todos.json` under the current
+/// directory: open items only unless `all` is set.
+pub fn run_todos_command(all: bool, emit_json: bool) -> Result<()> {
+    let project_dir = std::env::current_dir()?;
+    let todos = if all {
+        crate::todo::load_project_todos(&project_dir)?
+    } else {
+        crate::todo::open_project_todos(&project_dir)
+    };
+
+    if emit_json {
+        println!("{}", serde_json::to_string_pretty(&todos)?);
+        return Ok(());
+    }
+    if todos.is_empty() {
+        println!(
+            "No {}todos in {}",
+            if all { "" } else { "open " },
+            crate::todo::project_todos_path(&project_dir).display()
+        );
+        return Ok(());
+    }
+    for (index, todo) in todos.iter().enumerate() {
+        let owner = crate::id::extract_session_name(&todo.session_id).unwrap_or(&todo.session_id);
+        println!(
+            "{:>3}. [{}] ({}) {}  · {}",
+            index + 1,
+            todo.item.status,
+            todo.item.priority,
+            todo.item.content,
+            owner
+        );
+    }
+    Ok(())
+}
+
 /// Gracefully reload the running background server onto the newest binary.
 ///
 /// This is the preferred upgrade path (issue #291): instead of killing the
@@ -2410,18 +2624,45 @@ Re-run with `--force` if you really want to stop the server.";
     Ok(())
 }
 
+#[expect(
+    clippy::too_many_arguments,
+    reason = "jcode run maps its CLI flags straight through"
+)]
 pub async fn run_single_message_command(
     choice: &super::provider_init::ProviderChoice,
     model: Option<&str>,
     resume_session: Option<&str>,
-    message: &str,
-    emit_json: bool,
-    emit_ndjson: bool,
+    run_session: RunSession,
+    input: super::run_input::RunInput,
+    output: RunOutputFormat,
+    plan_only: bool,
+    compare: Vec<String>,
+    max_turns: Option<u32>,
+    profile: Option<&str>,
+    tee_cmd: Option<&str>,
 ) -> Result<()> {
-    let provider = if emit_json || emit_ndjson {
-        super::provider_init::init_provider_quiet(choice, model).await?
+    if plan_only && output == RunOutputFormat::StreamJson {
+        anyhow::bail!("--plan-only does not support --output stream-json");
+    }
+    if resume_session.is_some() && run_session != RunSession::Default {
+        anyhow::bail!("--resume cannot be combined with --continue, --session or --new");
+    }
+    let provider = if output != RunOutputFormat::Text {
+        super::provider_init::init_provider_quiet(choice, model).await
     } else {
-        super::provider_init::init_provider_for_validation(choice, model).await?
+        super::provider_init::init_provider_for_validation(choice, model).await
+    };
+    let provider = match provider {
+        Ok(provider) => provider,
+        Err(err) => {
+            return Err(report_run_setup_error(
+                output,
+                resume_session.unwrap_or_default(),
+                choice.as_arg_value(),
+                model.unwrap_or_default(),
+                err,
+            ));
+        }
     };
     let registry = crate::tool::Registry::new(provider.clone()).await;
     // Load MCP servers from ~/.jcode/mcp.json so headless `jcode run` has the
@@ -2444,24 +2685,210 @@ pub async fn run_single_message_command(
         wait_for_cold_cache_mcp_tools(&registry).await;
     }
     let mut agent = crate::agent::Agent::new(provider.clone(), registry);
-    restore_agent_session_if_requested(&mut agent, resume_session)?;
+    let restored = restore_agent_session_if_requested(&mut agent, resume_session)
+        .and_then(|()| restore_run_session(&mut agent, &run_session))
+        .and_then(|()| profile.map_or(Ok(()), |profile| agent.set_profile(Some(profile))));
+    if let Err(err) = restored {
+        return Err(report_run_setup_error(
+            output,
+            resume_session.unwrap_or(agent.session_id()),
+            provider.name(),
+            &provider.model(),
+            err,
+        ));
+    }
+    agent.set_max_turns(max_turns);
+    agent.set_appended_system_prompt(input.append_system);
+    if let Some(seed) = crate::provider::determinism::seed()
+        && let Some(warning) = provider.deterministic_params(seed).warning(provider.name())
+    {
+        eprintln!("Warning: {warning}");
+    }
+    let message = input.message.as_str();
+
+    if plan_only {
+        let emit_json = output == RunOutputFormat::Json;
+        return run_plan_only_command(&mut agent, message, emit_json).await;
+    }
+
+    if !compare.is_empty() {
+        let emit_json = output == RunOutputFormat::Json;
+        return run_compare_command(&mut agent, message, compare, emit_json).await;
+    }
+
+    let result = match output {
+        RunOutputFormat::Text => {
+            run_single_message_command_plain_with_auto_poke(&mut agent, message).await
+        }
+        RunOutputFormat::Json => {
+            run_single_message_command_json(&mut agent, provider.clone(), message).await
+        }
+        RunOutputFormat::StreamJson => {
+            run_single_message_command_ndjson(&mut agent, provider.clone(), message).await
+        }
+    };
+    if result.is_ok() {
+        tee_run_response(&agent, tee_cmd).await;
+    }
+    result
+}
+
+/// Failures before the first turn (provider init, `--resume`, `--continue`,
+/// `--profile`) still owe machine-readable output its envelope on stdout, so
+/// scripts see `status: error` instead of an empty stream.
+fn report_run_setup_error(
+    output: RunOutputFormat,
+    session_id: &str,
+    provider: &str,
+    model: &str,
+    error: anyhow::Error,
+) -> anyhow::Error {
+    if output == RunOutputFormat::Text {
+        return error;
+    }
+    let mut stdout = std::io::stdout().lock();
+    let message = format!("{error:#}");
+    if let Err(write_error) =
+        write_run_setup_error(&mut stdout, output, session_id, provider, model, &message)
+    {
+        eprintln!("Warning: failed to write the error envelope: {write_error:#}");
+    }
+    error
+}
 
+fn write_run_setup_error(
+    stdout: &mut impl Write,
+    output: RunOutputFormat,
+    session_id: &str,
+    provider: &str,
+    model: &str,
+    message: &str,
+) -> Result<()> {
+    if output == RunOutputFormat::StreamJson {
+        return write_json_line(
+            stdout,
+            &serde_json::json!({
+                "type": "error",
+                "session_id": session_id,
+                "provider": provider,
+                "model": model,
+                "message": message,
+            }),
+        );
+    }
+    let report = RunCommandReport {
+        status: "error",
+        exit_code: 1,
+        error: Some(message.to_string()),
+        session_id: session_id.to_string(),
+        provider: provider.to_string(),
+        model: model.to_string(),
+        text: String::new(),
+        turns: 0,
+        usage: crate::agent::TokenUsage::default(),
+        tool_calls: Vec::new(),
+        elapsed_ms: 0,
+        limits: None,
+        determinism: None,
+    };
+    serde_json::to_writer_pretty(&mut *stdout, &report)?;
+    stdout.write_all(b"\n")?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Copy the run's final response to `[output] tee_file` and `--tee-cmd`.
+/// Failures are warnings on stderr; the run itself already succeeded.
+async fn tee_run_response(agent: &crate::agent::Agent, tee_cmd: Option<&str>) {
+    let Some(text) = agent
+        .last_assistant_text()
+        .filter(|text| !text.trim().is_empty())
+    else {
+        return;
+    };
+    if let Some(path) = crate::output_tee::configured_tee_file() {
+        let session_id = agent.session_id();
+        let session_name = crate::id::extract_session_name(session_id);
+        if let Err(error) =
+            crate::output_tee::append_response(&path, session_id, session_name, &text)
+        {
+            eprintln!(
+                "Warning: failed to append response to {}: {:#}",
+                path.display(),
+                error
+            );
+        }
+    }
+    if let Some(command) = tee_cmd
+        && let Err(error) = crate::output_tee::pipe_to_command(command, &text).await
+    {
+        eprintln!("Warning: --tee-cmd failed: {:#}", error);
+    }
+}
+
+/// `jcode run --compare a,b`: ask each model the same thing without tools and
+/// print every answer under a header with its stats.
+async fn run_compare_command(
+    agent: &mut crate::agent::Agent,
+    message: &str,
+    models: Vec<String>,
+    emit_json: bool,
+) -> Result<()> {
+    let runs = agent.prepare_compare(message, models)?.run().await;
     if emit_json {
-        let text = run_single_message_command_capture_with_auto_poke(&mut agent, message).await?;
-        let report = RunCommandReport {
-            session_id: agent.session_id().to_string(),
-            provider: provider.name().to_string(),
-            model: provider.model(),
-            text,
-            usage: agent.last_usage().clone(),
-        };
+        let report = serde_json::json!({
+            "session_id": agent.session_id(),
+            "runs": runs,
+        });
         println!("{}", serde_json::to_string_pretty(&report)?);
-    } else if emit_ndjson {
-        run_single_message_command_ndjson(&mut agent, provider.clone(), message).await?;
     } else {
-        run_single_message_command_plain_with_auto_poke(&mut agent, message).await?;
+        for (index, run) in runs.iter().enumerate() {
+            if index > 0 {
+                println!("\n{}\n", "─".repeat(60));
+            }
+            println!("== {} · {} ==", run.model, run.stats_line());
+            if !run.text.is_empty() {
+                println!("{}", run.text);
+            }
+            if let Some(error) = &run.error {
+                eprintln!("{} failed: {}", run.model, error);
+            }
+        }
     }
+    if runs.iter().all(|run| run.error.is_some()) {
+        anyhow::bail!("every model failed");
+    }
+    Ok(())
+}
 
+/// `jcode run --plan-only`: run one turn in plan mode and print the plan the
+/// model proposed, without executing it.
+async fn run_plan_only_command(
+    agent: &mut crate::agent::Agent,
+    message: &str,
+    emit_json: bool,
+) -> Result<()> {
+    agent.set_plan_mode(true)?;
+    // A resumed session may already hold a proposal from an earlier run;
+    // only one submitted during this turn counts.
+    let previous_proposed_at = agent.plan_proposed_at_unix_ms();
+    let text = agent.run_once_capture(message).await?;
+    let proposal = agent
+        .plan_proposal()
+        .filter(|_| agent.plan_proposed_at_unix_ms() != previous_proposed_at)
+        .cloned();
+    let Some(proposal) = proposal else {
+        anyhow::bail!("The model did not propose a plan.\n{}", text.trim());
+    };
+    if emit_json {
+        let report = serde_json::json!({
+            "session_id": agent.session_id(),
+            "plan": proposal,
+        });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print!("{}", proposal.markdown_body());
+    }
     Ok(())
 }
 
@@ -2807,46 +3234,6 @@ async fn run_single_message_command_plain_with_auto_poke(
     Ok(())
 }
 
-async fn run_single_message_command_capture_with_auto_poke(
-    agent: &mut crate::agent::Agent,
-    message: &str,
-) -> Result<String> {
-    let mut next_message = message.to_string();
-    let max_turns = run_command_auto_poke_max_turns();
-    let mut outputs = Vec::new();
-    let mut turns_completed = 0usize;
-    let mut confidence_summary_sent = false;
-    loop {
-        outputs.push(agent.run_once_capture(&next_message).await?);
-        turns_completed += 1;
-        if !run_command_auto_poke_enabled() {
-            break;
-        }
-        let todos = run_todos(agent.session_id());
-        match build_run_auto_poke_follow_up_from_todos(&todos, confidence_summary_sent) {
-            Some(RunAutoPokeFollowUp::ConfidenceSummary { message, .. }) => {
-                confidence_summary_sent = true;
-                next_message = message;
-                continue;
-            }
-            Some(RunAutoPokeFollowUp::Incomplete { count, message }) => {
-                if run_command_auto_poke_limit_reached(turns_completed, max_turns) {
-                    if let Some(max_turns) = max_turns {
-                        outputs.push(format!(
-                            "Auto-poke stopped after {max_turns} turn(s) with {} incomplete todo(s).",
-                            count
-                        ));
-                    }
-                    break;
-                }
-                next_message = message;
-            }
-            None => break,
-        }
-    }
-    Ok(outputs.join("\n\n"))
-}
-
 fn restore_agent_session_if_requested(
     agent: &mut crate::agent::Agent,
     resume_session: Option<&str>,
@@ -2857,12 +3244,31 @@ fn restore_agent_session_if_requested(
     Ok(())
 }
 
+/// Point the agent at the session `--new`, `--continue` or `--session` asked
+/// for, and mark it so a later `--continue` from this directory finds it.
+/// `--continue` starts a new session when there is nothing to continue.
+fn restore_run_session(agent: &mut crate::agent::Agent, run_session: &RunSession) -> Result<()> {
+    let session_id = match run_session {
+        RunSession::Default => return Ok(()),
+        RunSession::New => None,
+        RunSession::Continue => {
+            let working_dir = std::env::current_dir()?.to_string_lossy().to_string();
+            session::find_latest_cli_run_session(&working_dir)?
+        }
+        RunSession::Named(name_or_id) => Some(session::find_session_by_name_or_id(name_or_id)?),
+    };
+    if let Some(session_id) = session_id {
+        agent.restore_session(&session_id)?;
+    }
+    agent.set_session_origin(session::SESSION_ORIGIN_CLI_RUN);
+    Ok(())
+}
+
 async fn run_single_message_command_ndjson(
     agent: &mut crate::agent::Agent,
     provider: std::sync::Arc<dyn crate::provider::Provider>,
     message: &str,
 ) -> Result<()> {
-    let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel();
     let session_id = agent.session_id().to_string();
     let mut stdout = std::io::stdout().lock();
     let mut state = NdjsonRunState {
@@ -2885,35 +3291,10 @@ async fn run_single_message_command_ndjson(
     let mut turns_completed = 0usize;
     let mut confidence_summary_sent = false;
     loop {
-        let turn_result = {
-            let mut run_future = std::pin::pin!(agent.run_once_streaming_mpsc(
-                &next_message,
-                Vec::new(),
-                None,
-                event_tx.clone(),
-            ));
-            let mut run_result: Option<Result<()>> = None;
-            loop {
-                tokio::select! {
-                    result = &mut run_future, if run_result.is_none() => {
-                        run_result = Some(result);
-                    }
-                    event = event_rx.recv() => {
-                        match event {
-                            Some(event) => emit_ndjson_event(&mut stdout, &mut state, event)?,
-                            None => break,
-                        }
-                    }
-                }
-                if run_result.is_some() {
-                    while let Ok(event) = event_rx.try_recv() {
-                        emit_ndjson_event(&mut stdout, &mut state, event)?;
-                    }
-                    break;
-                }
-            }
-            run_result.unwrap_or(Ok(()))
-        };
+        let turn_result = run_streaming_request(agent, &next_message, |event| {
+            emit_ndjson_event(&mut stdout, &mut state, event)
+        })
+        .await;
 
         if let Err(err) = turn_result {
             result = Err(err);
@@ -2987,6 +3368,7 @@ async fn run_single_message_command_ndjson(
                     "connection_type": state.connection_type,
                     "connection_phase": state.connection_phase,
                     "status_detail": state.status_detail,
+                    "limits": state.limits,
                 }),
             )?;
             Ok(())
@@ -3000,6 +3382,7 @@ async fn run_single_message_command_ndjson(
                     "provider": provider.name(),
                     "model": provider.model(),
                     "message": format!("{err:#}"),
+                    "limits": state.limits,
                 }),
             )?;
             Err(err)
@@ -3007,6 +3390,204 @@ async fn run_single_message_command_ndjson(
     }
 }
 
+/// Run one streaming request, handing each server event to `on_event` as it
+/// arrives. An error from `on_event` aborts the request.
+async fn run_streaming_request(
+    agent: &mut crate::agent::Agent,
+    message: &str,
+    mut on_event: impl FnMut(crate::protocol::ServerEvent) -> Result<()>,
+) -> Result<()> {
+    let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel();
+    let run_future = agent.run_once_streaming_mpsc(message, Vec::new(), None, event_tx.clone());
+    tokio::pin!(run_future);
+    let mut run_result: Option<Result<()>> = None;
+    loop {
+        tokio::select! {
+            result = &mut run_future, if run_result.is_none() => {
+                run_result = Some(result);
+            }
+            event = event_rx.recv() => {
+                match event {
+                    Some(event) => on_event(event)?,
+                    None => break,
+                }
+            }
+        }
+        if run_result.is_some() {
+            while let Ok(event) = event_rx.try_recv() {
+                on_event(event)?;
+            }
+            break;
+        }
+    }
+    run_result.unwrap_or(Ok(()))
+}
+
+/// `jcode run --output json`: stream human-readable progress to stderr and
+/// print a single [`RunCommandReport`] to stdout once the run finishes, also
+/// when it fails. Nothing else is written to stdout.
+async fn run_single_message_command_json(
+    agent: &mut crate::agent::Agent,
+    provider: std::sync::Arc<dyn crate::provider::Provider>,
+    message: &str,
+) -> Result<()> {
+    let started_at = std::time::Instant::now();
+    let start_message_index = agent.message_count();
+    let session_id = agent.session_id().to_string();
+    let progress = !super::output::quiet_enabled();
+    let mut state = JsonRunState::default();
+
+    let max_turns = run_command_auto_poke_max_turns();
+    let mut next_message = message.to_string();
+    let mut result: Result<()> = Ok(());
+    let mut turns_completed = 0usize;
+    let mut confidence_summary_sent = false;
+    loop {
+        let turn_result = run_streaming_request(agent, &next_message, |event| {
+            record_json_run_event(&mut state, event, progress);
+            Ok(())
+        })
+        .await;
+        if progress {
+            eprintln!();
+        }
+
+        if let Err(err) = turn_result {
+            result = Err(err);
+            break;
+        }
+        turns_completed += 1;
+        if !run_command_auto_poke_enabled() {
+            break;
+        }
+        let todos = run_todos(&session_id);
+        match build_run_auto_poke_follow_up_from_todos(&todos, confidence_summary_sent) {
+            Some(RunAutoPokeFollowUp::ConfidenceSummary { message, .. }) => {
+                confidence_summary_sent = true;
+                next_message = message;
+                super::output::stderr_info(
+                    "Auto-poking: todos complete; sending confidence summary follow-up. Set JCODE_RUN_AUTO_POKE=0 to disable.",
+                );
+            }
+            Some(RunAutoPokeFollowUp::Incomplete { count, message }) => {
+                if run_command_auto_poke_limit_reached(turns_completed, max_turns) {
+                    if let Some(max_turns) = max_turns {
+                        super::output::stderr_info(format!(
+                            "Auto-poke stopped after {max_turns} turn(s) with {count} incomplete todo(s)."
+                        ));
+                    }
+                    break;
+                }
+                next_message = message;
+                super::output::stderr_info(format!(
+                    "Auto-poking: {count} incomplete todo(s). Set JCODE_RUN_AUTO_POKE=0 to disable."
+                ));
+            }
+            None => break,
+        }
+    }
+
+    let report = RunCommandReport {
+        status: if result.is_ok() { "ok" } else { "error" },
+        exit_code: if result.is_ok() { 0 } else { 1 },
+        error: result.as_ref().err().map(|err| format!("{err:#}")),
+        session_id,
+        provider: provider.name().to_string(),
+        model: provider.model(),
+        text: agent
+            .latest_assistant_text_after(start_message_index)
+            .unwrap_or_default(),
+        turns: state.turns,
+        usage: state.usage,
+        tool_calls: state.tool_calls,
+        elapsed_ms: started_at.elapsed().as_millis() as u64,
+        limits: state.limits.or_else(|| agent.agent_limit_status()),
+        determinism: crate::provider::determinism::seed().map(|seed| {
+            agent
+                .last_determinism()
+                .unwrap_or_else(|| provider.deterministic_params(seed))
+        }),
+    };
+    let mut stdout = std::io::stdout().lock();
+    serde_json::to_writer_pretty(&mut stdout, &report)?;
+    stdout.write_all(b"\n")?;
+    stdout.flush()?;
+    result
+}
+
+/// Fold one event into the `--output json` summary, echoing progress to
+/// stderr when `progress` is set.
+fn record_json_run_event(
+    state: &mut JsonRunState,
+    event: crate::protocol::ServerEvent,
+    progress: bool,
+) {
+    use crate::protocol::ServerEvent;
+
+    match event {
+        ServerEvent::TextDelta { text } => {
+            if progress {
+                eprint!("{text}");
+            }
+        }
+        ServerEvent::ToolStart { id, name } => {
+            if progress {
+                eprint!("\n[{name}] ");
+            }
+            state.tool_started.insert(id, std::time::Instant::now());
+        }
+        ServerEvent::ToolDone {
+            id, name, error, ..
+        } => {
+            let duration_ms = state
+                .tool_started
+                .remove(&id)
+                .map(|started| started.elapsed().as_millis() as u64)
+                .unwrap_or(0);
+            if progress {
+                match &error {
+                    Some(error) => eprintln!("\n  → {name} failed: {error}"),
+                    None => eprintln!("\n  → {name} done ({duration_ms}ms)"),
+                }
+            }
+            state.tool_calls.push(RunToolCallSummary {
+                id,
+                name,
+                success: error.is_none(),
+                duration_ms,
+            });
+        }
+        ServerEvent::TokenUsage {
+            input,
+            output,
+            cache_read_input,
+            cache_creation_input,
+        } => {
+            let usage = &mut state.usage;
+            usage.input_tokens += input;
+            usage.output_tokens += output;
+            if let Some(tokens) = cache_read_input {
+                *usage.cache_read_input_tokens.get_or_insert(0) += tokens;
+            }
+            if let Some(tokens) = cache_creation_input {
+                *usage.cache_creation_input_tokens.get_or_insert(0) += tokens;
+            }
+        }
+        ServerEvent::RateLimited {
+            provider,
+            retry_after_secs,
+            ..
+        } => {
+            if progress {
+                eprintln!("\n  ⏳ {provider} rate limited; retrying in {retry_after_secs}s");
+            }
+        }
+        ServerEvent::MessageEnd => state.turns += 1,
+        ServerEvent::AgentLimits { status } => state.limits = Some(status),
+        _ => {}
+    }
+}
+
 fn emit_ndjson_event(
     stdout: &mut impl Write,
     state: &mut NdjsonRunState,
@@ -3100,6 +3681,21 @@ fn emit_ndjson_event(
                 &serde_json::json!({ "type": "status_detail", "detail": detail }),
             )
         }
+        ServerEvent::RateLimited {
+            provider,
+            retry_after_secs,
+            attempt,
+            max_attempts,
+        } => write_json_line(
+            stdout,
+            &serde_json::json!({
+                "type": "rate_limited",
+                "provider": provider,
+                "retry_after_secs": retry_after_secs,
+                "attempt": attempt,
+                "max_attempts": max_attempts,
+            }),
+        ),
         ServerEvent::MessageEnd => {
             write_json_line(stdout, &serde_json::json!({ "type": "message_end" }))
         }
@@ -3127,6 +3723,7 @@ fn emit_ndjson_event(
             messages_compacted,
             summary_chars,
             active_messages,
+            tool_results_trimmed,
         } => write_json_line(
             stdout,
             &serde_json::json!({
@@ -3140,6 +3737,7 @@ fn emit_ndjson_event(
                 "messages_compacted": messages_compacted,
                 "summary_chars": summary_chars,
                 "active_messages": active_messages,
+                "tool_results_trimmed": tool_results_trimmed,
             }),
         ),
         ServerEvent::MemoryInjected {
@@ -3190,6 +3788,13 @@ fn emit_ndjson_event(
                 "retry_after_secs": retry_after_secs,
             }),
         ),
+        ServerEvent::AgentLimits { status } => {
+            state.limits = Some(status.clone());
+            write_json_line(
+                stdout,
+                &serde_json::json!({ "type": "agent_limits", "status": status }),
+            )
+        }
         ServerEvent::Ack { .. } | ServerEvent::Done { .. } | ServerEvent::Pong { .. } => Ok(()),
         _ => Ok(()),
     }
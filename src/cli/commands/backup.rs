@@ -0,0 +1,84 @@
+use anyhow::Result;
+use std::path::Path;
+
+use crate::backup::{self, RestoreAction};
+use crate::update::format_bytes;
+
+/// `jcode backup create <file>`: pack sessions, memories, skills, config,
+/// todos, and usage history (plus credentials with `--include-auth`).
+pub fn run_backup_create_command(output: &str, include_auth: bool) -> Result<()> {
+    let output = Path::new(output);
+    let manifest = backup::create_backup(output, include_auth)?;
+
+    println!("Wrote {}:", output.display());
+    for (category, (files, bytes)) in manifest.totals() {
+        println!(
+            "  {:<12} {:>5} file(s)  {}",
+            category.label(),
+            files,
+            format_bytes(bytes)
+        );
+    }
+    if manifest.files.is_empty() {
+        println!("  (nothing to back up)");
+    }
+    if include_auth {
+        println!();
+        println!("This archive contains credentials. Keep it somewhere private.");
+    }
+    Ok(())
+}
+
+/// `jcode backup restore <file>`: plan the restore, print every decision, and
+/// apply it unless `--dry-run`.
+pub fn run_backup_restore_command(
+    input: &str,
+    dry_run: bool,
+    force: bool,
+    server_running: bool,
+) -> Result<()> {
+    if server_running && !dry_run {
+        anyhow::bail!(
+            "The jcode server is running. Close jcode (or run `jcode server stop --force`) before restoring, or preview with --dry-run."
+        );
+    }
+    let report = backup::restore_backup(Path::new(input), dry_run, force)?;
+
+    println!(
+        "Backup from {} (jcode {}), {} file(s):",
+        report.manifest.created_at.format("%Y-%m-%d %H:%M UTC"),
+        report.manifest.jcode_version,
+        report.manifest.files.len()
+    );
+    for item in &report.items {
+        if item.action == RestoreAction::Unchanged {
+            continue;
+        }
+        match &item.note {
+            Some(note) => println!("  {:<10} {}  ({})", item.action.label(), item.path, note),
+            None => println!("  {:<10} {}", item.action.label(), item.path),
+        }
+    }
+
+    println!();
+    let summary = [
+        RestoreAction::Create,
+        RestoreAction::Overwrite,
+        RestoreAction::Merge,
+        RestoreAction::KeepLocal,
+        RestoreAction::Unchanged,
+    ]
+    .into_iter()
+    .map(|action| format!("{} {}", report.count(action), action.label()))
+    .collect::<Vec<_>>()
+    .join(", ");
+    if report.applied {
+        println!("Restored: {}.", summary);
+    } else {
+        println!("Dry run, nothing written: {}.", summary);
+    }
+    if report.count(RestoreAction::KeepLocal) > 0 && !force {
+        println!("Conflicting local files were kept. Re-run with --force to replace them.");
+    }
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::provenance::{self, BlamedLine, ToolCallOrigin};
+
+/// Longest instruction excerpt printed per change.
+const INSTRUCTION_MAX_CHARS: usize = 100;
+
+/// `jcode blame <file> [line]`: `git blame` joined with the tool calls that
+/// `[git] provenance` recorded. Consecutive lines from the same commit and
+/// tool call are printed as one range.
+pub fn run_blame_command(file: &Path, line: Option<usize>, emit_json: bool) -> Result<()> {
+    let lines = provenance::blame(file, line)?;
+    let mut origins: HashMap<String, Option<ToolCallOrigin>> = HashMap::new();
+    for entry in lines.iter().filter_map(|line| line.provenance.as_ref()) {
+        origins
+            .entry(entry.tool_call_id.clone())
+            .or_insert_with(|| {
+                provenance::tool_call_origin(&entry.session_id, &entry.tool_call_id)
+            });
+    }
+    let origin = |line: &BlamedLine| {
+        line.provenance
+            .as_ref()
+            .and_then(|entry| origins.get(&entry.tool_call_id).cloned().flatten())
+    };
+
+    if emit_json {
+        let report: Vec<_> = lines
+            .iter()
+            .map(|line| {
+                let origin = origin(line);
+                serde_json::json!({
+                    "line": line.line,
+                    "commit": line.commit,
+                    "author": line.author,
+                    "summary": line.summary,
+                    "text": line.text,
+                    "provenance": line.provenance,
+                    "turn": origin.as_ref().map(|origin| origin.turn),
+                    "instruction": origin.map(|origin| origin.instruction),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    if line.is_some() {
+        for line in &lines {
+            println!("{:>5} {}", line.line, line.text);
+            print_attribution(line, origin(line).as_ref());
+        }
+        return Ok(());
+    }
+
+    let same_change = |a: &BlamedLine, b: &BlamedLine| {
+        a.commit == b.commit
+            && a.provenance.as_ref().map(|entry| &entry.tool_call_id)
+                == b.provenance.as_ref().map(|entry| &entry.tool_call_id)
+    };
+    for group in lines.chunk_by(same_change) {
+        let (first, last) = (&group[0], &group[group.len() - 1]);
+        if first.line == last.line {
+            println!("line {}", first.line);
+        } else {
+            println!("lines {}-{}", first.line, last.line);
+        }
+        print_attribution(first, origin(first).as_ref());
+    }
+    Ok(())
+}
+
+fn print_attribution(line: &BlamedLine, origin: Option<&ToolCallOrigin>) {
+    println!(
+        "  commit {} {} · {}",
+        line.commit, line.author, line.summary
+    );
+    let Some(entry) = &line.provenance else {
+        println!("  not written by jcode");
+        return;
+    };
+    let session = crate::id::extract_session_name(&entry.session_id).unwrap_or(&entry.session_id);
+    match origin {
+        Some(origin) => {
+            println!(
+                "  jcode {} turn {} · {} {}",
+                session, origin.turn, entry.tool, entry.tool_call_id
+            );
+            println!("  instruction: {}", excerpt(&origin.instruction));
+        }
+        None => println!(
+            "  jcode {} · {} {} (session not found)",
+            session, entry.tool, entry.tool_call_id
+        ),
+    }
+}
+
+fn excerpt(instruction: &str) -> String {
+    let line = instruction
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("");
+    if line.chars().count() <= INSTRUCTION_MAX_CHARS {
+        return line.to_string();
+    }
+    let mut excerpt: String = line.chars().take(INSTRUCTION_MAX_CHARS - 1).collect();
+    excerpt.push('…');
+    excerpt
+}
@@ -0,0 +1,114 @@
+use anyhow::Result;
+
+use crate::build;
+
+/// Evidence lines listed per event kind before eliding the rest.
+const EVIDENCE_LIMIT: usize = 5;
+
+pub fn run_promote_command(force: bool) -> Result<()> {
+    let manifest = build::BuildManifest::load()?;
+    let Some(canary) = manifest.canary.as_deref() else {
+        anyhow::bail!("No canary build recorded; nothing to promote");
+    };
+    let min_clean_hours = crate::config::config().rebuild.promote_min_clean_hours;
+    let record = build::CanaryTrackRecord::from_events(canary, &build::load_canary_events()?);
+    print_track_record(&record, min_clean_hours);
+    println!();
+
+    let promotion = build::promote_canary(min_clean_hours, force)?;
+    for blocker in &promotion.forced_over {
+        println!("! Promoted despite: {}", blocker);
+    }
+    println!(
+        "✓ Stable promoted: {} → {}",
+        promotion.previous_stable.as_deref().unwrap_or("(none)"),
+        promotion.version
+    );
+    if promotion.moved_shared_server {
+        println!(
+            "  shared server channel followed; run `jcode server reload` to restart the daemon on it"
+        );
+    }
+    Ok(())
+}
+
+/// `jcode debug canary`: the canary's track record and its diff against stable.
+pub fn print_canary_report() -> Result<()> {
+    let manifest = build::BuildManifest::load()?;
+    let Some(canary) = manifest.canary.as_deref() else {
+        println!("No canary build recorded.");
+        return Ok(());
+    };
+    println!(
+        "Canary {} ({})",
+        canary,
+        manifest
+            .canary_status
+            .as_ref()
+            .map(|status| format!("{:?}", status))
+            .unwrap_or_else(|| "-".to_string())
+    );
+    let min_clean_hours = crate::config::config().rebuild.promote_min_clean_hours;
+    let record = build::CanaryTrackRecord::from_events(canary, &build::load_canary_events()?);
+    print_track_record(&record, min_clean_hours);
+
+    println!();
+    let stable = build::read_stable_version()?;
+    match (stable.as_deref(), build::get_repo_dir()) {
+        (Some(stable), Some(repo_dir)) => {
+            println!("Diff against stable {}", stable);
+            match build::build_diff_stat(&repo_dir, stable, canary) {
+                Ok(stat) if stat.is_empty() => println!("  (no changes)"),
+                Ok(stat) => stat.lines().for_each(|line| println!("  {}", line)),
+                Err(error) => println!("  unavailable: {}", error),
+            }
+        }
+        (None, _) => println!("Diff against stable: no stable build recorded"),
+        (_, None) => println!("Diff against stable: jcode repository not found"),
+    }
+    Ok(())
+}
+
+fn print_track_record(record: &build::CanaryTrackRecord, min_clean_hours: f64) {
+    println!("Track record for {}", record.build_hash);
+    println!("  {:<20} {}", "sessions", record.sessions);
+    println!("  {:<20} {}", "clean exits", record.clean_exits);
+    println!(
+        "  {:<20} {:.1} (required {:.1})",
+        "clean session-hours",
+        record.clean_session_hours(),
+        min_clean_hours
+    );
+    println!(
+        "  {:<20} {}",
+        "mean uptime",
+        record
+            .mean_uptime_secs
+            .map(build::format_uptime)
+            .unwrap_or_else(|| "-".to_string())
+    );
+    println!("  {:<20} {}", "crashes", record.crashes.len());
+    println!("  {:<20} {}", "rollbacks", record.rollbacks.len());
+    for event in record
+        .crashes
+        .iter()
+        .chain(&record.rollbacks)
+        .take(EVIDENCE_LIMIT)
+    {
+        println!("    {}", event.describe());
+    }
+    let listed = record.crashes.len() + record.rollbacks.len();
+    if listed > EVIDENCE_LIMIT {
+        println!(
+            "    … {} more in {}",
+            listed - EVIDENCE_LIMIT,
+            events_path_label()
+        );
+    }
+}
+
+fn events_path_label() -> String {
+    build::canary_events_path()
+        .map(|path| path.display().to_string())
+        .unwrap_or_else(|_| "canary-events.jsonl".to_string())
+}
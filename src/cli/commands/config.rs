@@ -0,0 +1,397 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+use crate::config::{Config, ConfigIssue};
+use crate::mcp::McpConfig;
+use crate::skill::SkillRegistry;
+
+use crate::config::file_edit::{toml_value_literal, upsert_key};
+
+/// `jcode config get <key>`: print the effective value (file plus env overrides).
+pub fn run_config_get_command(key: &str) -> Result<()> {
+    let key = key.trim();
+    check_key(key)?;
+    let effective = toml::Value::try_from(Config::load())?;
+    match lookup(&effective, key) {
+        Some(toml::Value::String(value)) => println!("{}", value),
+        Some(toml::Value::Table(table)) => print!("{}", toml::to_string_pretty(table)?),
+        Some(value) => println!("{}", value),
+        None => println!("(unset)"),
+    }
+    Ok(())
+}
+
+/// `jcode config set <key> <value>`: update one key in config.toml in place,
+/// leaving comments and the rest of the file untouched.
+pub fn run_config_set_command(key: &str, raw_value: &str) -> Result<()> {
+    let key = key.trim();
+    check_key(key)?;
+    let Some((table, leaf)) = key.rsplit_once('.') else {
+        anyhow::bail!(
+            "`{}` is a whole section; set one of its keys, e.g. `{}.<key>`",
+            key,
+            key
+        );
+    };
+    let value = toml_value_literal(raw_value);
+
+    let path = Config::path().context("No config path")?;
+    let content = if path.exists() {
+        std::fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?
+    } else {
+        String::new()
+    };
+    let updated = upsert_key(&content, table, leaf, &value);
+
+    let (_, issues) = Config::parse_lenient(&updated, Some(&path))
+        .with_context(|| format!("setting {} would make {} invalid", key, path.display()))?;
+    if let Some(issue) = issues.iter().find(|issue| issue.key == key) {
+        anyhow::bail!("{}", issue);
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, updated)?;
+    Config::invalidate_cache();
+    println!("{} = {}  ({})", key, value, path.display());
+    Ok(())
+}
+
+/// `jcode config doctor`: validate config.toml and everything it points at.
+pub fn run_config_doctor_command() -> Result<()> {
+    let mut report = DoctorReport::default();
+
+    let path = Config::path();
+    report.section(&format!(
+        "Config file: {}",
+        path.as_deref()
+            .map(|path| path.display().to_string())
+            .unwrap_or_else(|| "(no config path)".to_string())
+    ));
+    match &path {
+        Some(path) if path.exists() => match Config::file_issues() {
+            Ok(issues) if issues.is_empty() => report.ok("no unknown or invalid keys"),
+            Ok(issues) => {
+                for issue in &issues {
+                    report.fail(issue.to_string());
+                }
+            }
+            Err(err) => report.fail(format!("{:#}", err)),
+        },
+        _ => report.ok("not present; using defaults"),
+    }
+
+    let config = Config::load();
+    for error in config.profile_errors() {
+        report.fail(error);
+    }
+    for warning in config.prompt.section_warnings() {
+        report.warn(warning);
+    }
+    for (name, profile) in &config.profiles {
+        // Relative prompt files resolve per session, so only home paths are checkable.
+        if let Some(file) = &profile.system_prompt_file
+            && let Some(relative) = file.strip_prefix("~/")
+            && let Ok(path) = crate::storage::user_home_path(relative)
+            && !path.exists()
+        {
+            report.fail(format!(
+                "[profiles.{}] system_prompt_file {} does not exist",
+                name,
+                path.display()
+            ));
+        }
+    }
+
+    report.section("Skill directories");
+    let cwd = std::env::current_dir().ok();
+    let mut any_skill_root = false;
+    for root in SkillRegistry::skill_roots(cwd.as_deref()) {
+        if !root.exists() {
+            continue;
+        }
+        any_skill_root = true;
+        if root.is_dir() {
+            report.ok(format!(
+                "{} ({} skills)",
+                root.display(),
+                count_skills(&root)
+            ));
+        } else {
+            report.fail(format!("{} exists but is not a directory", root.display()));
+        }
+    }
+    if !any_skill_root {
+        report.ok("none present");
+    }
+
+    report.section("MCP servers");
+    let mut mcp_files: Vec<PathBuf> = Vec::new();
+    if let Ok(jcode_dir) = crate::storage::jcode_dir() {
+        mcp_files.push(jcode_dir.join("mcp.json"));
+    }
+    mcp_files.extend(
+        [".jcode/mcp.json", ".mcp.json", ".claude/mcp.json"]
+            .into_iter()
+            .map(PathBuf::from),
+    );
+    let mut any_mcp_file = false;
+    for file in mcp_files.iter().filter(|file| file.exists()) {
+        any_mcp_file = true;
+        check_mcp_file(&mut report, file);
+    }
+    if !any_mcp_file {
+        report.ok("no mcp.json present");
+    }
+
+    report.section("Notifications");
+    check_notifications(&mut report, &config);
+
+    report.section("Providers");
+    check_providers(&mut report, &config);
+
+    println!();
+    if report.failures > 0 {
+        anyhow::bail!(
+            "config doctor found {} problem(s) and {} warning(s)",
+            report.failures,
+            report.warnings
+        );
+    }
+    println!("Config OK ({} warning(s)).", report.warnings);
+    Ok(())
+}
+
+#[derive(Default)]
+struct DoctorReport {
+    failures: usize,
+    warnings: usize,
+}
+
+impl DoctorReport {
+    fn section(&self, title: &str) {
+        println!("{}", title);
+    }
+
+    fn ok(&self, message: impl AsRef<str>) {
+        println!("  ✓ {}", message.as_ref());
+    }
+
+    fn warn(&mut self, message: impl AsRef<str>) {
+        self.warnings += 1;
+        println!("  ! {}", message.as_ref());
+    }
+
+    fn fail(&mut self, message: impl AsRef<str>) {
+        self.failures += 1;
+        println!("  ✗ {}", message.as_ref());
+    }
+}
+
+fn check_key(key: &str) -> Result<()> {
+    if key.is_empty() || key.split('.').any(str::is_empty) {
+        anyhow::bail!("Config keys look like `section.key`, e.g. `display.centered`");
+    }
+    Config::check_key(key).map_err(|issue: ConfigIssue| match issue.suggestion {
+        Some(suggestion) => anyhow::anyhow!(
+            "Unknown config key `{}` (did you mean `{}`?)",
+            key,
+            suggestion
+        ),
+        None => anyhow::anyhow!("Unknown config key `{}`", key),
+    })
+}
+
+fn lookup<'a>(value: &'a toml::Value, key: &str) -> Option<&'a toml::Value> {
+    key.split('.')
+        .try_fold(value, |value, segment| value.as_table()?.get(segment))
+}
+
+fn count_skills(root: &Path) -> usize {
+    std::fs::read_dir(root)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.path().join("SKILL.md").is_file())
+                .count()
+        })
+        .unwrap_or(0)
+}
+
+fn check_mcp_file(report: &mut DoctorReport, file: &Path) {
+    let config = match McpConfig::load_from_file(file) {
+        Ok(config) => config,
+        Err(err) => {
+            report.fail(format!("{}: {}", file.display(), err));
+            return;
+        }
+    };
+    report.ok(format!(
+        "{} ({} servers)",
+        file.display(),
+        config.servers.len()
+    ));
+    let mut names: Vec<&String> = config.servers.keys().collect();
+    names.sort();
+    for name in names {
+        let server = &config.servers[name];
+        if !server.is_stdio() {
+            report.warn(format!(
+                "{}: `{}` is not a stdio server; jcode skips it",
+                file.display(),
+                name
+            ));
+            continue;
+        }
+        let command = Path::new(server.command.trim());
+        if command.is_absolute() && !command.exists() {
+            report.fail(format!(
+                "{}: `{}` command {} does not exist",
+                file.display(),
+                name,
+                command.display()
+            ));
+        }
+    }
+}
+
+fn check_notifications(report: &mut DoctorReport, config: &Config) {
+    let safety = &config.safety;
+    let mut backends = vec!["desktop".to_string()];
+
+    if let Some(topic) = &safety.ntfy_topic {
+        backends.push("ntfy".to_string());
+        report.ok(format!("ntfy: {}/{}", safety.ntfy_server, topic));
+    }
+
+    if safety.email_enabled {
+        let missing: Vec<&str> = [
+            ("email_to", safety.email_to.is_none()),
+            ("email_smtp_host", safety.email_smtp_host.is_none()),
+            ("email_from", safety.email_from.is_none()),
+        ]
+        .into_iter()
+        .filter_map(|(name, missing)| missing.then_some(name))
+        .collect();
+        if missing.is_empty() {
+            backends.push("email".to_string());
+            report.ok(format!(
+                "email: {}:{}",
+                safety.email_smtp_host.as_deref().unwrap_or_default(),
+                safety.email_smtp_port
+            ));
+            if safety.email_password.is_none() {
+                report.warn("email: no email_password (or JCODE_EMAIL_PASSWORD) set");
+            }
+        } else {
+            report.fail(format!(
+                "email: email_enabled but {} not set",
+                missing.join(", ")
+            ));
+        }
+    }
+
+    let channels = [
+        (
+            "telegram",
+            safety.telegram_enabled,
+            safety.telegram_bot_token.is_some() && safety.telegram_chat_id.is_some(),
+            "telegram_bot_token and telegram_chat_id",
+        ),
+        (
+            "discord",
+            safety.discord_enabled,
+            safety.discord_bot_token.is_some() && safety.discord_channel_id.is_some(),
+            "discord_bot_token and discord_channel_id",
+        ),
+        (
+            "jade_relay",
+            safety.jade_relay_enabled,
+            safety.jade_relay_api_base.is_some()
+                && safety.jade_relay_token.is_some()
+                && safety.jade_relay_session_id.is_some(),
+            "jade_relay_api_base, jade_relay_token and jade_relay_session_id",
+        ),
+    ];
+    for (name, enabled, complete, needs) in channels {
+        if !enabled {
+            continue;
+        }
+        if complete {
+            backends.push(name.to_string());
+            report.ok(format!("{}: configured", name));
+        } else {
+            report.fail(format!("{}: enabled but needs {}", name, needs));
+        }
+    }
+
+    for webhook in &config.notifications.webhooks {
+        if webhook.url.trim().is_empty() {
+            report.fail(format!("webhook `{}`: no url", webhook.name));
+        } else {
+            backends.push(webhook.name.clone());
+            report.ok(format!("webhook `{}`: {}", webhook.name, webhook.url));
+        }
+    }
+
+    if config.telegram.bot_token.is_some() && config.telegram.allowed_user_ids.is_empty() {
+        report.warn("telegram bridge: bot_token set but allowed_user_ids is empty");
+    }
+
+    let events: Vec<&str> = crate::notifications::NotifyEvent::ALL
+        .iter()
+        .map(|event| event.as_str())
+        .collect();
+    for (event, names) in &config.notifications.routes {
+        if !events.contains(&event.as_str()) {
+            report.fail(format!(
+                "routes: unknown event `{}` (one of: {})",
+                event,
+                events.join(", ")
+            ));
+        }
+        for name in names {
+            if !backends.contains(name) {
+                report.fail(format!(
+                    "routes.{}: `{}` is not a configured backend",
+                    event, name
+                ));
+            }
+        }
+    }
+}
+
+fn check_providers(report: &mut DoctorReport, config: &Config) {
+    if config.providers.is_empty() {
+        report.ok("no named provider profiles");
+    }
+    for (name, provider) in &config.providers {
+        let mut problems = Vec::new();
+        if provider.base_url.trim().is_empty() {
+            problems.push("base_url is empty".to_string());
+        }
+        if let Some(env) = &provider.api_key_env
+            && provider.api_key.is_none()
+            && std::env::var_os(env).is_none()
+            && provider.env_file.is_none()
+        {
+            problems.push(format!("api_key_env {} is not set", env));
+        }
+        if let Some(file) = &provider.env_file {
+            match crate::storage::app_config_dir().map(|dir| dir.join(file)) {
+                Ok(path) if path.exists() => {}
+                Ok(path) => problems.push(format!("env_file {} does not exist", path.display())),
+                Err(err) => problems.push(format!("env_file {}: {}", file, err)),
+            }
+        }
+        if problems.is_empty() {
+            report.ok(format!("providers.{}: {}", name, provider.base_url));
+        } else {
+            for problem in problems {
+                report.fail(format!("providers.{}: {}", name, problem));
+            }
+        }
+    }
+}
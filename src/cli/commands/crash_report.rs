@@ -0,0 +1,30 @@
+use anyhow::{Context, Result};
+
+use crate::{crash_report, session};
+
+/// `jcode debug crash-report`: pack a crash report bundle into a `.tar.gz`.
+///
+/// With `last`, packs the newest existing bundle; otherwise writes a fresh one
+/// for `session_ref` (default: the most recently crashed session).
+pub fn run_crash_report_command(last: bool, session_ref: Option<&str>) -> Result<()> {
+    let bundle = if last {
+        crash_report::latest_bundle().context("No crash reports recorded yet")?
+    } else {
+        let session_id = match session_ref {
+            Some(session_ref) => Some(session::find_session_by_name_or_id(session_ref)?),
+            None => session::find_recent_crashed_sessions()
+                .into_iter()
+                .next()
+                .map(|(id, _)| id),
+        };
+        crash_report::write_bundle(&crash_report::CrashContext {
+            reason: "Requested with jcode debug crash-report".to_string(),
+            panic: None,
+            session_id,
+        })?
+    };
+    let archive = crash_report::archive_bundle(&bundle)?;
+    println!("Crash report: {}", archive.display());
+    println!("Attach it to the issue; secrets and file contents are redacted.");
+    Ok(())
+}
@@ -0,0 +1,647 @@
+//! `jcode doctor`: one-shot diagnostics for bug reports and support requests.
+//!
+//! Every check is read-only: no session is loaded or created, the server is
+//! only pinged, and MCP servers are started just long enough to list their
+//! tools. Each problem comes with a one-line fix.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::mcp::{McpClient, McpConfig};
+
+const MCP_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const SERVER_PING_TIMEOUT: Duration = Duration::from_secs(2);
+const CLOCK_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+const CLOCK_CHECK_URL: &str = "https://api.github.com";
+/// Skew beyond this breaks OAuth token expiry and signed requests.
+const CLOCK_SKEW_WARN_SECS: i64 = 30;
+const CLOCK_SKEW_FAIL_SECS: i64 = 300;
+const DISK_WARN_BYTES: u64 = 1024 * 1024 * 1024;
+const DISK_FAIL_BYTES: u64 = 200 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl CheckStatus {
+    fn glyph(self) -> &'static str {
+        match self {
+            Self::Pass => "✓",
+            Self::Warn => "!",
+            Self::Fail => "✗",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct DoctorCheck {
+    section: &'static str,
+    name: String,
+    status: CheckStatus,
+    detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fix: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct DoctorReport {
+    version: String,
+    git_hash: String,
+    checks: Vec<DoctorCheck>,
+    failures: usize,
+    warnings: usize,
+}
+
+impl DoctorReport {
+    fn push(
+        &mut self,
+        section: &'static str,
+        name: impl Into<String>,
+        status: CheckStatus,
+        detail: impl Into<String>,
+        fix: Option<String>,
+    ) {
+        match status {
+            CheckStatus::Pass => {}
+            CheckStatus::Warn => self.warnings += 1,
+            CheckStatus::Fail => self.failures += 1,
+        }
+        self.checks.push(DoctorCheck {
+            section,
+            name: name.into(),
+            status,
+            detail: detail.into(),
+            fix,
+        });
+    }
+
+    fn pass(&mut self, section: &'static str, name: impl Into<String>, detail: impl Into<String>) {
+        self.push(section, name, CheckStatus::Pass, detail, None);
+    }
+
+    fn warn(
+        &mut self,
+        section: &'static str,
+        name: impl Into<String>,
+        detail: impl Into<String>,
+        fix: impl Into<String>,
+    ) {
+        self.push(section, name, CheckStatus::Warn, detail, Some(fix.into()));
+    }
+
+    fn fail(
+        &mut self,
+        section: &'static str,
+        name: impl Into<String>,
+        detail: impl Into<String>,
+        fix: impl Into<String>,
+    ) {
+        self.push(section, name, CheckStatus::Fail, detail, Some(fix.into()));
+    }
+
+    fn print(&self) {
+        let mut section = "";
+        for check in &self.checks {
+            if check.section != section {
+                if !section.is_empty() {
+                    println!();
+                }
+                section = check.section;
+                println!("{}", section);
+            }
+            println!(
+                "  {} {}: {}",
+                check.status.glyph(),
+                check.name,
+                check.detail
+            );
+            if let Some(fix) = &check.fix {
+                println!("    → {}", fix);
+            }
+        }
+    }
+}
+
+/// `jcode doctor`: check the installation and environment and report
+/// pass/warn/fail per check, as text or as JSON for bug reports.
+pub async fn run_doctor_command(emit_json: bool) -> Result<()> {
+    let mut report = DoctorReport {
+        version: jcode_build_meta::VERSION.to_string(),
+        git_hash: jcode_build_meta::GIT_HASH.to_string(),
+        ..DoctorReport::default()
+    };
+
+    check_build(&mut report);
+    check_credentials(&mut report);
+    check_server(&mut report).await;
+    check_config(&mut report);
+    check_mcp(&mut report).await;
+    check_terminal(&mut report);
+    check_disk(&mut report);
+    check_clock(&mut report).await;
+
+    if emit_json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        report.print();
+        println!();
+    }
+    if report.failures > 0 {
+        anyhow::bail!(
+            "doctor found {} problem(s) and {} warning(s)",
+            report.failures,
+            report.warnings
+        );
+    }
+    if !emit_json {
+        println!("All checks passed ({} warning(s)).", report.warnings);
+    }
+    Ok(())
+}
+
+fn check_build(report: &mut DoctorReport) {
+    const SECTION: &str = "Build";
+    report.pass(
+        SECTION,
+        "version",
+        format!(
+            "{} ({}{})",
+            jcode_build_meta::VERSION,
+            jcode_build_meta::GIT_HASH,
+            if jcode_build_meta::is_release_build() {
+                ", release"
+            } else {
+                ""
+            }
+        ),
+    );
+
+    let exe = std::env::current_exe().ok();
+    let exe = exe.map(|exe| std::fs::canonicalize(&exe).unwrap_or(exe));
+    let exe_label = exe
+        .as_deref()
+        .map(|exe| exe.display().to_string())
+        .unwrap_or_else(|| "unknown path".to_string());
+    if let Some(version) = crate::build::running_installed_version() {
+        report.pass(
+            SECTION,
+            "install",
+            format!("installed build {} at {}", version, exe_label),
+        );
+    } else if let Some(repo) = exe
+        .as_deref()
+        .and_then(Path::parent)
+        .and_then(crate::build::find_repo_in_ancestors)
+    {
+        report.pass(
+            SECTION,
+            "install",
+            format!("repo build from {} ({})", repo.display(), exe_label),
+        );
+    } else {
+        report.pass(
+            SECTION,
+            "install",
+            format!("standalone binary at {}", exe_label),
+        );
+    }
+}
+
+fn check_credentials(report: &mut DoctorReport) {
+    const SECTION: &str = "Credentials";
+    let status = crate::auth::AuthStatus::check();
+    let mut configured = 0;
+    for provider in crate::provider_catalog::auth_status_login_providers() {
+        let assessment = status.assessment_for_provider(provider);
+        let name = provider.id.to_string();
+        let detail = format!(
+            "{} (expiry: {})",
+            assessment.method_detail,
+            assessment.expiry_confidence.label()
+        );
+        match assessment.state {
+            crate::auth::AuthState::Available => {
+                configured += 1;
+                report.pass(SECTION, name, detail);
+            }
+            crate::auth::AuthState::Expired => {
+                configured += 1;
+                report.warn(
+                    SECTION,
+                    name,
+                    format!("expired or incomplete: {}", detail),
+                    format!("jcode login --provider {}", provider.id),
+                );
+            }
+            crate::auth::AuthState::NotConfigured => {}
+        }
+    }
+    if configured == 0 {
+        report.fail(
+            SECTION,
+            "providers",
+            "no provider credentials found",
+            "run `jcode login` and pick a provider",
+        );
+    } else if !status.has_any_available() {
+        report.fail(
+            SECTION,
+            "providers",
+            "no provider has usable credentials",
+            "log in again with `jcode login --provider <id>`",
+        );
+    }
+}
+
+async fn check_server(report: &mut DoctorReport) {
+    const SECTION: &str = "Server";
+    let socket = crate::server::socket_path();
+    let socket_label = socket.display().to_string();
+    if !socket.exists() {
+        report.pass(
+            SECTION,
+            "socket",
+            format!(
+                "no server running at {} (one starts on demand)",
+                socket_label
+            ),
+        );
+        return;
+    }
+
+    let ready = tokio::time::timeout(SERVER_PING_TIMEOUT, crate::server::is_server_ready(&socket))
+        .await
+        .unwrap_or(false);
+    if !ready && !crate::server::has_live_listener(&socket).await {
+        report.warn(
+            SECTION,
+            "socket",
+            format!("{} exists but nothing is listening", socket_label),
+            format!(
+                "remove {} once no jcode server is running; the next jcode starts a fresh one",
+                socket_label
+            ),
+        );
+        return;
+    }
+    if ready {
+        report.pass(
+            SECTION,
+            "socket",
+            format!("server answered at {}", socket_label),
+        );
+    } else {
+        report.warn(
+            SECTION,
+            "socket",
+            format!(
+                "{} accepts connections but did not answer a ping",
+                socket_label
+            ),
+            "retry in a few seconds; the server may be reloading",
+        );
+    }
+
+    match crate::registry::find_server_by_socket_sync(&socket) {
+        Some(info) if info.git_hash == jcode_build_meta::GIT_HASH => report.pass(
+            SECTION,
+            "version",
+            format!("{} ({}), pid {}", info.version, info.git_hash, info.pid),
+        ),
+        Some(info) => report.warn(
+            SECTION,
+            "version",
+            format!(
+                "server runs {} ({}) but this binary is {} ({})",
+                info.version,
+                info.git_hash,
+                jcode_build_meta::VERSION,
+                jcode_build_meta::GIT_HASH
+            ),
+            "reload the server from a client with `/reload` so both run the same build",
+        ),
+        None => report.pass(SECTION, "version", "not in the server registry"),
+    }
+}
+
+fn check_config(report: &mut DoctorReport) {
+    const SECTION: &str = "Config";
+    let path = Config::path();
+    let path_label = path
+        .as_deref()
+        .map(|path| path.display().to_string())
+        .unwrap_or_else(|| "config.toml".to_string());
+    let fix = || {
+        format!(
+            "edit {} or run `jcode config doctor` for details",
+            path_label
+        )
+    };
+
+    let exists = path.as_deref().is_some_and(Path::exists);
+    let mut problems = 0;
+    if exists {
+        match Config::file_issues() {
+            Ok(issues) => {
+                for issue in &issues {
+                    problems += 1;
+                    report.fail(SECTION, "config.toml", issue.to_string(), fix());
+                }
+            }
+            Err(err) => {
+                problems += 1;
+                report.fail(SECTION, "config.toml", format!("{:#}", err), fix());
+            }
+        }
+    }
+    let config = Config::load();
+    for error in config.profile_errors() {
+        problems += 1;
+        report.fail(SECTION, "profiles", error, fix());
+    }
+    for warning in config.prompt.section_warnings() {
+        problems += 1;
+        report.warn(SECTION, "prompt.sections", warning, fix());
+    }
+    if problems == 0 {
+        let detail = if exists {
+            format!("{} is valid", path_label)
+        } else {
+            "no config.toml; using defaults".to_string()
+        };
+        report.pass(SECTION, "config.toml", detail);
+    }
+}
+
+/// The mcp.json files jcode reads, in override order. Claude Code and Codex
+/// configs are skipped: jcode imports them on first run, which writes.
+fn mcp_files() -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    if let Ok(jcode_dir) = crate::storage::jcode_dir() {
+        files.push(jcode_dir.join("mcp.json"));
+    }
+    files.extend(
+        [".jcode/mcp.json", ".mcp.json", ".claude/mcp.json"]
+            .into_iter()
+            .map(PathBuf::from),
+    );
+    files.retain(|file| file.exists());
+    files
+}
+
+async fn check_mcp(report: &mut DoctorReport) {
+    const SECTION: &str = "MCP servers";
+    let files = mcp_files();
+    if files.is_empty() {
+        report.pass(SECTION, "mcp.json", "none configured");
+        return;
+    }
+
+    // Later files override earlier ones, as when jcode loads them.
+    let mut servers = BTreeMap::new();
+    for file in &files {
+        match McpConfig::load_from_file(file) {
+            Ok(config) => {
+                for (name, server) in config.servers {
+                    servers.insert(name, (server, file.clone()));
+                }
+            }
+            Err(err) => report.fail(
+                SECTION,
+                file.display().to_string(),
+                err.to_string(),
+                format!("fix the JSON in {}", file.display()),
+            ),
+        }
+    }
+
+    for (name, (server, file)) in servers {
+        if !server.is_stdio() {
+            report.warn(
+                SECTION,
+                name,
+                "not a stdio server; jcode skips it",
+                format!(
+                    "remove it from {} or run it behind a stdio bridge",
+                    file.display()
+                ),
+            );
+            continue;
+        }
+        let fix = format!("check `command` and `args` for it in {}", file.display());
+        match tokio::time::timeout(
+            MCP_CONNECT_TIMEOUT,
+            McpClient::connect(name.clone(), &server),
+        )
+        .await
+        {
+            Ok(Ok(mut client)) => {
+                report.pass(
+                    SECTION,
+                    name,
+                    format!("connected, {} tools", client.tools().len()),
+                );
+                client.shutdown().await;
+            }
+            Ok(Err(err)) => report.fail(SECTION, name, format!("{:#}", err), fix),
+            Err(_) => report.fail(
+                SECTION,
+                name,
+                format!("no response within {}s", MCP_CONNECT_TIMEOUT.as_secs()),
+                fix,
+            ),
+        }
+    }
+}
+
+fn check_terminal(report: &mut DoctorReport) {
+    const SECTION: &str = "Terminal";
+    let term = std::env::var("TERM").unwrap_or_default();
+    let program = std::env::var("TERM_PROGRAM").unwrap_or_default();
+    let label = match (term.is_empty(), program.is_empty()) {
+        (true, true) => "unknown".to_string(),
+        (false, true) => term.clone(),
+        (true, false) => program.clone(),
+        (false, false) => format!("{} ({})", program, term),
+    };
+    report.pass(SECTION, "terminal", label);
+
+    let colorterm = std::env::var("COLORTERM").unwrap_or_default();
+    if colorterm.eq_ignore_ascii_case("truecolor") || colorterm.eq_ignore_ascii_case("24bit") {
+        report.pass(SECTION, "truecolor", format!("COLORTERM={}", colorterm));
+    } else {
+        report.warn(
+            SECTION,
+            "truecolor",
+            "COLORTERM does not advertise 24-bit color; colors fall back to 256",
+            "export COLORTERM=truecolor if your terminal supports it",
+        );
+    }
+
+    if !std::io::stdout().is_terminal() || !std::io::stdin().is_terminal() {
+        report.warn(
+            SECTION,
+            "kitty keyboard",
+            "not checked: stdin/stdout is not a terminal",
+            "run `jcode doctor` directly in the terminal you use jcode in",
+        );
+    } else {
+        match crossterm::terminal::supports_keyboard_enhancement() {
+            Ok(true) => report.pass(SECTION, "kitty keyboard", "supported"),
+            Ok(false) => report.warn(
+                SECTION,
+                "kitty keyboard",
+                "not supported; some modified keys (e.g. Shift+Enter) are indistinguishable",
+                "use a terminal with the kitty keyboard protocol (kitty, Ghostty, WezTerm, foot, Alacritty)",
+            ),
+            Err(err) => report.warn(
+                SECTION,
+                "kitty keyboard",
+                format!("query failed: {}", err),
+                "use a terminal with the kitty keyboard protocol (kitty, Ghostty, WezTerm, foot, Alacritty)",
+            ),
+        }
+    }
+
+    match crate::tui::image::ImageProtocol::detect() {
+        crate::tui::image::ImageProtocol::None => report.warn(
+            SECTION,
+            "graphics",
+            "no image protocol; images and diagrams render as text",
+            "use kitty, Ghostty, WezTerm or iTerm2 for inline images",
+        ),
+        protocol => report.pass(SECTION, "graphics", format!("{:?}", protocol)),
+    }
+}
+
+fn check_disk(report: &mut DoctorReport) {
+    const SECTION: &str = "Disk";
+    let dir = match crate::storage::jcode_dir() {
+        Ok(dir) => dir,
+        Err(err) => {
+            report.fail(
+                SECTION,
+                "~/.jcode",
+                format!("{:#}", err),
+                "set HOME, or JCODE_HOME to a writable directory",
+            );
+            return;
+        }
+    };
+    let name = dir.display().to_string();
+    // ~/.jcode may not exist yet; measure the filesystem it would live on.
+    let existing = dir.ancestors().find(|path| path.exists()).unwrap_or(&dir);
+    let Some(free) = free_bytes(existing) else {
+        report.pass(SECTION, name, "free space not checked on this platform");
+        return;
+    };
+    let detail = format!("{} free", crate::update::format_bytes(free));
+    let fix = || {
+        format!(
+            "free up space on the disk holding {} (old builds live in its builds/ directory)",
+            dir.display()
+        )
+    };
+    if free < DISK_FAIL_BYTES {
+        report.fail(SECTION, name, detail, fix());
+    } else if free < DISK_WARN_BYTES {
+        report.warn(SECTION, name, detail, fix());
+    } else {
+        report.pass(SECTION, name, detail);
+    }
+}
+
+#[cfg(unix)]
+#[allow(clippy::unnecessary_cast)] // statvfs field widths differ across platforms
+fn free_bytes(path: &Path) -> Option<u64> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = std::ffi::CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    let ret = unsafe { libc::statvfs(path.as_ptr(), &mut stat) };
+    (ret == 0).then(|| stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(not(unix))]
+fn free_bytes(_path: &Path) -> Option<u64> {
+    None
+}
+
+async fn check_clock(report: &mut DoctorReport) {
+    const SECTION: &str = "Clock";
+    let fix = "enable network time sync (e.g. `timedatectl set-ntp true`, or Date & Time settings)";
+    let request = crate::provider::shared_http_client()
+        .head(CLOCK_CHECK_URL)
+        .send();
+    let server_date = match tokio::time::timeout(CLOCK_CHECK_TIMEOUT, request).await {
+        Ok(Ok(response)) => response
+            .headers()
+            .get("date")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| chrono::DateTime::parse_from_rfc2822(value).ok()),
+        _ => None,
+    };
+    let Some(server_date) = server_date else {
+        report.warn(
+            SECTION,
+            "skew",
+            format!("could not reach {} to compare clocks", CLOCK_CHECK_URL),
+            "check network access; skew is only measured online",
+        );
+        return;
+    };
+    let skew = clock_skew_secs(chrono::Utc::now(), server_date.with_timezone(&chrono::Utc));
+    let detail = format!("{:+}s against {}", skew, CLOCK_CHECK_URL);
+    match skew.abs() {
+        secs if secs > CLOCK_SKEW_FAIL_SECS => report.fail(SECTION, "skew", detail, fix),
+        secs if secs > CLOCK_SKEW_WARN_SECS => report.warn(SECTION, "skew", detail, fix),
+        _ => report.pass(SECTION, "skew", detail),
+    }
+}
+
+/// Seconds the local clock is ahead of (positive) or behind `reference`.
+fn clock_skew_secs(
+    local: chrono::DateTime<chrono::Utc>,
+    reference: chrono::DateTime<chrono::Utc>,
+) -> i64 {
+    (local - reference).num_seconds()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_counts_and_serializes_statuses() {
+        let mut report = DoctorReport::default();
+        report.pass("Build", "version", "v1");
+        report.warn("Clock", "skew", "+45s", "sync the clock");
+        report.fail("Config", "config.toml", "bad key", "edit it");
+        assert_eq!((report.warnings, report.failures), (1, 1));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["checks"][0]["status"], "pass");
+        assert!(json["checks"][0].get("fix").is_none());
+        assert_eq!(json["checks"][1]["status"], "warn");
+        assert_eq!(json["checks"][2]["fix"], "edit it");
+    }
+
+    #[test]
+    fn clock_skew_is_signed() {
+        let reference = chrono::DateTime::parse_from_rfc2822("Sun, 18 Oct 2026 12:00:00 GMT")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let ahead = reference + chrono::Duration::seconds(42);
+        assert_eq!(clock_skew_secs(ahead, reference), 42);
+        assert_eq!(clock_skew_secs(reference, ahead), -42);
+    }
+}
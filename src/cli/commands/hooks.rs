@@ -0,0 +1,62 @@
+use anyhow::Result;
+use std::path::PathBuf;
+
+use crate::hooks::{self, PROJECT_HOOKS_FILE, ProjectHooksTrust};
+
+fn project_dir(dir: Option<&str>) -> Result<PathBuf> {
+    Ok(match dir {
+        Some(dir) => PathBuf::from(dir),
+        None => std::env::current_dir()?,
+    })
+}
+
+/// `jcode hooks status`: print the project's hooks file and its trust state.
+pub fn run_hooks_status_command(dir: Option<&str>) -> Result<()> {
+    let dir = project_dir(dir)?;
+    let path = dir.join(PROJECT_HOOKS_FILE);
+    match hooks::project_hooks_trust(&dir) {
+        ProjectHooksTrust::NoFile => println!("No project hook rules at {}", path.display()),
+        ProjectHooksTrust::Trusted => println!("{}: trusted, rules run", path.display()),
+        ProjectHooksTrust::Untrusted { changed } => {
+            println!(
+                "{}: {}, rules do not run",
+                path.display(),
+                if changed {
+                    "changed since it was trusted"
+                } else {
+                    "not trusted"
+                }
+            );
+            println!("Review the file, then run `jcode hooks trust` to let it run.");
+        }
+    }
+    Ok(())
+}
+
+/// `jcode hooks trust`: trust the current contents of the hooks file.
+pub fn run_hooks_trust_command(dir: Option<&str>) -> Result<()> {
+    let dir = project_dir(dir)?;
+    let path = hooks::trust_project_hooks(&dir)?;
+    println!(
+        "Trusted {}. Its rules run on matching tool calls until the file changes.",
+        path.display()
+    );
+    Ok(())
+}
+
+/// `jcode hooks untrust`: forget the trust decision for the project.
+pub fn run_hooks_untrust_command(dir: Option<&str>) -> Result<()> {
+    let dir = project_dir(dir)?;
+    if hooks::untrust_project_hooks(&dir)? {
+        println!(
+            "{} is no longer trusted; its rules will not run.",
+            dir.join(PROJECT_HOOKS_FILE).display()
+        );
+    } else {
+        println!(
+            "{} was not trusted.",
+            dir.join(PROJECT_HOOKS_FILE).display()
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use anyhow::{Context, Result};
+use chrono::Local;
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::time::Duration;
+
+use crate::{logging, session};
+
+/// How often `--follow` polls the live log file.
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub struct LogsOptions {
+    pub session: Option<String>,
+    pub level: Option<String>,
+    pub target: Option<String>,
+    pub lines: usize,
+    pub follow: bool,
+    pub json: bool,
+}
+
+pub fn run_logs_command(options: LogsOptions) -> Result<()> {
+    let min_level = options
+        .level
+        .as_deref()
+        .map(|level| {
+            logging::LogLevel::parse(level).with_context(|| {
+                format!(
+                    "Unknown log level '{}' (use error, warn, info, debug, or trace)",
+                    level
+                )
+            })
+        })
+        .transpose()?;
+    let filter = logging::RecordFilter {
+        session_id: options.session.as_deref().map(|session_ref| {
+            session::find_session_by_name_or_id(session_ref)
+                .unwrap_or_else(|_| session_ref.to_string())
+        }),
+        min_level,
+        target: options.target.clone(),
+    };
+
+    let segments = logging::structured_log_segments(Local::now().date_naive());
+    if segments.is_empty() && !options.follow {
+        println!("No structured logs for today yet.");
+        return Ok(());
+    }
+
+    let mut latest: VecDeque<(String, logging::LogRecord)> = VecDeque::new();
+    for path in &segments {
+        let Ok(file) = std::fs::File::open(path) else {
+            continue;
+        };
+        for line in BufReader::new(file).lines().map_while(Result::ok) {
+            if let Some(record) = logging::LogRecord::parse(&line)
+                && filter.matches(&record)
+            {
+                latest.push_back((line, record));
+                if latest.len() > options.lines {
+                    latest.pop_front();
+                }
+            }
+        }
+    }
+    for (line, record) in &latest {
+        print_record(line, record, options.json);
+    }
+
+    if options.follow {
+        follow_live_log(&filter, options.json)?;
+    }
+    Ok(())
+}
+
+fn print_record(line: &str, record: &logging::LogRecord, json: bool) {
+    if json {
+        println!("{}", line);
+    } else {
+        println!("{}", record.format_pretty());
+    }
+}
+
+/// Print matching records appended to today's log until interrupted, picking
+/// up the new file after size rotation or at midnight.
+fn follow_live_log(filter: &logging::RecordFilter, json: bool) -> Result<()> {
+    let mut path = logging::structured_log_path().context("Log directory is unavailable")?;
+    let mut offset = std::fs::metadata(&path).map(|meta| meta.len()).unwrap_or(0);
+    let mut pending = String::new();
+    loop {
+        std::thread::sleep(FOLLOW_POLL_INTERVAL);
+        if let Some(live) = logging::structured_log_path()
+            && live != path
+        {
+            path = live;
+            offset = 0;
+            pending.clear();
+        }
+        let len = std::fs::metadata(&path).map(|meta| meta.len()).unwrap_or(0);
+        if len < offset {
+            // Rotated: the live path is a fresh file.
+            offset = 0;
+            pending.clear();
+        }
+        if len == offset {
+            continue;
+        }
+
+        let mut file = std::fs::File::open(&path)?;
+        file.seek(SeekFrom::Start(offset))?;
+        let mut chunk = Vec::new();
+        file.read_to_end(&mut chunk)?;
+        offset += chunk.len() as u64;
+        pending.push_str(&String::from_utf8_lossy(&chunk));
+
+        while let Some(newline) = pending.find('\n') {
+            let line: String = pending.drain(..=newline).collect();
+            let line = line.trim_end();
+            if let Some(record) = logging::LogRecord::parse(line)
+                && filter.matches(&record)
+            {
+                print_record(line, &record, json);
+            }
+        }
+    }
+}
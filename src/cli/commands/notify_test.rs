@@ -0,0 +1,51 @@
+use anyhow::Result;
+
+use crate::notifications::{NotificationDispatcher, NotifyEvent};
+
+/// `jcode debug notify-test <event>`: send a sample notification for `event`
+/// through its configured route and report each backend's result.
+pub async fn run_notify_test_command(event: &str) -> Result<()> {
+    let valid = NotifyEvent::ALL
+        .iter()
+        .map(|event| event.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let Some(event) = NotifyEvent::parse(event) else {
+        if event.trim().is_empty() {
+            anyhow::bail!("Usage: jcode debug notify-test <event> (one of: {})", valid);
+        }
+        anyhow::bail!("Unknown notification event '{}' (one of: {})", event, valid);
+    };
+
+    let dispatcher = NotificationDispatcher::new();
+    let results = dispatcher
+        .dispatch_and_wait(dispatcher.test_notification(event))
+        .await;
+    if results.is_empty() {
+        anyhow::bail!(
+            "No notification backends are routed for {}; check [notifications.routes]",
+            event.as_str()
+        );
+    }
+
+    let mut failed = 0;
+    for (backend, result) in &results {
+        match result {
+            Ok(()) => println!("  ok      {}", backend),
+            Err(err) => {
+                failed += 1;
+                println!("  failed  {}: {}", backend, err);
+            }
+        }
+    }
+    if failed > 0 {
+        anyhow::bail!(
+            "{} of {} notification backends failed for {}",
+            failed,
+            results.len(),
+            event.as_str()
+        );
+    }
+    println!("Sent {} to {} backend(s).", event.as_str(), results.len());
+    Ok(())
+}
@@ -4,6 +4,7 @@ use std::io::Read;
 use std::path::PathBuf;
 
 use crate::cli::args::ProviderAuthArg;
+use crate::config::file_edit::{is_toml_header, join_lines, line_has_toml_key, split_lines_lossy};
 use crate::config::{
     Config, NamedProviderAuth, NamedProviderConfig, NamedProviderModelConfig, NamedProviderType,
 };
@@ -209,7 +210,7 @@ pub(crate) fn configure_provider_profile(
     }
     updated = append_profile_section(updated, &name, &profile);
 
-    toml::from_str::<Config>(&updated).with_context(|| {
+    Config::parse_lenient(&updated, None).with_context(|| {
         format!(
             "generated provider config for '{}' was not valid TOML",
             name
@@ -256,7 +257,7 @@ fn parse_config_or_default(content: &str) -> Result<Config> {
     if content.trim().is_empty() {
         Ok(Config::default())
     } else {
-        Ok(toml::from_str::<Config>(content)?)
+        Ok(Config::parse_lenient(content, None)?.0)
     }
 }
 
@@ -547,31 +548,6 @@ fn is_named_provider_header(line: &str, name: &str) -> bool {
         || inner == format!("{single_quoted}.models")
 }
 
-fn is_toml_header(line: &str) -> bool {
-    let trimmed = line.trim();
-    trimmed.starts_with('[') && trimmed.ends_with(']')
-}
-
-fn line_has_toml_key(line: &str, key: &str) -> bool {
-    let trimmed = line.trim_start();
-    let Some(rest) = trimmed.strip_prefix(key) else {
-        return false;
-    };
-    rest.trim_start().starts_with('=')
-}
-
-fn split_lines_lossy(content: &str) -> Vec<String> {
-    content.lines().map(ToString::to_string).collect()
-}
-
-fn join_lines(lines: Vec<String>) -> String {
-    let mut joined = lines.join("\n");
-    if !joined.is_empty() {
-        joined.push('\n');
-    }
-    joined
-}
-
 fn auth_label(auth: &NamedProviderAuth) -> &'static str {
     match auth {
         NamedProviderAuth::Bearer => "bearer",
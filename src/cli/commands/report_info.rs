@@ -444,7 +444,8 @@ pub(super) fn run_version_command(emit_json: bool) -> Result<()> {
 }
 
 pub(super) async fn run_usage_command(emit_json: bool) -> Result<()> {
-    let providers = crate::usage::fetch_all_provider_usage().await;
+    let mut providers = crate::usage::fetch_all_provider_usage().await;
+    providers.extend(crate::usage::background_usage_report());
 
     let report = UsageReport {
         providers: providers.iter().map(usage_provider_report).collect(),
@@ -511,6 +512,47 @@ pub(super) async fn run_usage_command(emit_json: bool) -> Result<()> {
     Ok(())
 }
 
+pub(super) async fn run_usage_forecast_command(emit_json: bool) -> Result<()> {
+    // Fetching records a fresh sample for every window before forecasting.
+    let providers = crate::usage::fetch_all_provider_usage().await;
+    let forecasts = crate::usage::usage_forecasts(&providers);
+
+    if emit_json {
+        println!("{}", serde_json::to_string_pretty(&forecasts)?);
+        return Ok(());
+    }
+
+    if forecasts.is_empty() {
+        println!("No usage windows to forecast");
+        return Ok(());
+    }
+
+    for (idx, forecast) in forecasts.iter().enumerate() {
+        if idx > 0 {
+            println!();
+        }
+        println!("{} · {}", forecast.provider_name, forecast.limit_name);
+        println!(
+            "usage: {}",
+            crate::usage::format_usage_bar(forecast.usage_percent, 15)
+        );
+        if let Some(reset) = forecast.resets_at_local() {
+            println!("resets: {}", reset);
+        }
+        if let Some(rate) = forecast.burn_percent_per_hour {
+            println!("burn rate: {:.1}%/h", rate);
+        }
+        println!("forecast: {}", forecast.summary());
+        println!(
+            "last 24h: {} {:.0}% used",
+            forecast.sparkline(),
+            forecast.last_24h_total()
+        );
+    }
+
+    Ok(())
+}
+
 fn select_auth_doctor_providers(
     provider_arg: Option<&str>,
     status: &crate::auth::AuthStatus,
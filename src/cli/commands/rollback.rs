@@ -0,0 +1,175 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+
+use crate::build;
+
+/// Stable builds shown by `jcode rollback`.
+const ROLLBACK_LIST_LIMIT: usize = 10;
+
+pub fn run_rollback_command(version: Option<&str>, list: bool) -> Result<()> {
+    let manifest = build::BuildManifest::load()?;
+    print_stable_history(&manifest);
+    if list {
+        return Ok(());
+    }
+
+    let target = build::resolve_rollback_target(&manifest, version)?;
+    let outcome = build::rollback_stable(&target)?;
+    println!();
+    println!(
+        "✓ Stable rolled back: {} → {}",
+        outcome.previous.as_deref().unwrap_or("(none)"),
+        outcome.version
+    );
+    if outcome.moved_current {
+        println!("  current and launcher now point at {}", outcome.version);
+    }
+    if outcome.moved_shared_server {
+        println!(
+            "  shared server channel followed; run `jcode server reload` to restart the daemon on it"
+        );
+    }
+    println!(
+        "  Running sessions migrate to {} at their next idle point.",
+        outcome.version
+    );
+    Ok(())
+}
+
+/// `jcode debug builds`: the whole build manifest plus channel markers.
+pub fn print_build_manifest() -> Result<()> {
+    let manifest = build::BuildManifest::load()?;
+
+    println!("Channels");
+    for (channel, version) in [
+        ("stable", build::read_stable_version()?),
+        ("current", build::read_current_version()?),
+        ("shared-server", build::read_shared_server_version()?),
+    ] {
+        println!("  {:<14} {}", channel, version.as_deref().unwrap_or("-"));
+    }
+    println!(
+        "  {:<14} {}",
+        "running",
+        build::running_installed_version()
+            .as_deref()
+            .unwrap_or("- (not an installed build)")
+    );
+
+    println!();
+    println!("Canary");
+    println!(
+        "  {:<14} {}",
+        "version",
+        manifest.canary.as_deref().unwrap_or("-")
+    );
+    println!(
+        "  {:<14} {}",
+        "session",
+        manifest.canary_session.as_deref().unwrap_or("-")
+    );
+    println!(
+        "  {:<14} {}",
+        "status",
+        manifest
+            .canary_status
+            .as_ref()
+            .map(|status| format!("{:?}", status))
+            .unwrap_or_else(|| "-".to_string())
+    );
+    if let Some(pending) = &manifest.pending_activation {
+        println!(
+            "  {:<14} {} for session {} (requested {}, previous current {})",
+            "pending",
+            pending.new_version,
+            pending.session_id,
+            format_time(pending.requested_at),
+            pending.previous_current_version.as_deref().unwrap_or("-")
+        );
+    }
+    if let Some(crash) = &manifest.last_crash {
+        println!(
+            "  {:<14} {} exited {} at {}",
+            "last crash",
+            crash.build_hash,
+            crash.exit_code,
+            format_time(crash.crashed_at)
+        );
+    }
+
+    println!();
+    print_stable_history(&manifest);
+
+    println!();
+    println!("Build history");
+    if manifest.history.is_empty() {
+        println!("  (none)");
+    } else {
+        println!(
+            "  {:<10} {:<17} {:<6} {}",
+            "HASH", "BUILT", "DIRTY", "COMMIT"
+        );
+        for info in &manifest.history {
+            println!(
+                "  {:<10} {:<17} {:<6} {}",
+                info.hash,
+                format_time(info.built_at),
+                if info.dirty { "yes" } else { "no" },
+                info.commit_message.as_deref().unwrap_or("")
+            );
+        }
+    }
+
+    println!();
+    println!("Installed versions");
+    let installed = build::installed_versions()?;
+    if installed.is_empty() {
+        println!("  (none)");
+    }
+    for version in installed {
+        println!(
+            "  {:<32} {}",
+            version.version,
+            version
+                .installed_at
+                .map(format_time)
+                .unwrap_or_else(|| "-".to_string())
+        );
+    }
+    Ok(())
+}
+
+fn print_stable_history(manifest: &build::BuildManifest) {
+    let current = build::read_stable_version().ok().flatten();
+    println!("Stable history (newest first)");
+    if manifest.stable_history.is_empty() {
+        println!("  (none recorded yet; installed versions are listed by `jcode debug builds`)");
+        return;
+    }
+    println!("  {:<2} {:<32} {:<17} SESSIONS", "", "VERSION", "PROMOTED");
+    for record in manifest.stable_history.iter().take(ROLLBACK_LIST_LIMIT) {
+        let marker = if current.as_deref() == Some(record.version.as_str()) {
+            "*"
+        } else {
+            ""
+        };
+        let sessions = if record.sessions.is_empty() {
+            "-".to_string()
+        } else {
+            record.sessions.join(", ")
+        };
+        println!(
+            "  {:<2} {:<32} {:<17} {}",
+            marker,
+            record.version,
+            format_time(record.promoted_at),
+            sessions
+        );
+    }
+}
+
+fn format_time(at: DateTime<Utc>) -> String {
+    at.with_timezone(&chrono::Local)
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+}
@@ -0,0 +1,69 @@
+use anyhow::Result;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+
+use crate::session;
+use crate::session_changes;
+
+/// `jcode sessions changes <session>`: the files a session's tools changed,
+/// with line counts against the session-start snapshots. `--diff` prints
+/// each file's diff after the list.
+pub fn run_session_changes_command(session_ref: &str, diff: bool, emit_json: bool) -> Result<()> {
+    let session_id = session::find_session_by_name_or_id(session_ref)?;
+    let changes = session_changes::changes(&session_id)?;
+
+    if emit_json {
+        let report: Vec<_> = changes
+            .iter()
+            .map(|change| {
+                serde_json::json!({
+                    "path": change.path,
+                    "status": change.status_label(),
+                    "added": change.added,
+                    "removed": change.removed,
+                    "diff": diff.then(|| change.unified_diff()),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    let base = session::Session::load(&session_id)
+        .ok()
+        .and_then(|session| session.working_dir)
+        .map(PathBuf::from);
+    println!("Session {}", session_id);
+    println!(
+        "{}",
+        session_changes::format_summary(&changes, base.as_deref())
+    );
+    if diff {
+        let color = std::io::stdout().is_terminal();
+        for change in &changes {
+            println!();
+            for line in change.unified_diff().lines() {
+                println!("{}", colorize_diff_line(line, color));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn colorize_diff_line(line: &str, color: bool) -> String {
+    let code = if line.starts_with("+++") || line.starts_with("---") {
+        "1"
+    } else if line.starts_with('+') {
+        "32"
+    } else if line.starts_with('-') {
+        "31"
+    } else if line.starts_with("@@") {
+        "36"
+    } else {
+        ""
+    };
+    if !color || code.is_empty() {
+        return line.to_string();
+    }
+    format!("\x1b[{}m{}\x1b[0m", code, line)
+}
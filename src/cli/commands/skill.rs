@@ -0,0 +1,177 @@
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+use crate::skill::{self, ImportAction, SkillRegistry};
+use crate::storage;
+
+/// `jcode skill new`: scaffold a skill directory from the commented template.
+pub fn run_skill_new_command(name: &str, project: bool) -> Result<()> {
+    let root = if project {
+        std::env::current_dir()?.join(".jcode").join("skills")
+    } else {
+        storage::jcode_dir()?.join("skills")
+    };
+    let dir = skill::scaffold_skill(&root, name)?;
+    println!("Created skill /{} in {}", name, dir.display());
+    println!("  SKILL.md   frontmatter (name, description) and instructions");
+    println!("  scripts/   optional helper scripts, linked from SKILL.md");
+    println!();
+    println!(
+        "Edit SKILL.md, then run `jcode skill check {}` to validate it.",
+        name
+    );
+    Ok(())
+}
+
+/// `jcode skill check`: validate one skill, or every skill in the standard
+/// locations, and fail if any has errors.
+pub fn run_skill_check_command(name: Option<&str>) -> Result<()> {
+    let dirs = match name {
+        Some(name) => resolve_skill_dirs(name)?,
+        None => installed_skill_dirs(),
+    };
+    if dirs.is_empty() {
+        println!("No skills found. Create one with `jcode skill new <name>`.");
+        return Ok(());
+    }
+
+    let mut failed = 0;
+    for dir in &dirs {
+        let check = skill::validate_skill_dir(dir);
+        let status = if !check.is_ok() {
+            failed += 1;
+            "FAIL"
+        } else if check.issues.is_empty() {
+            "ok"
+        } else {
+            "warn"
+        };
+        println!("{:<4}  /{}  {}", status, check.name, dir.display());
+        print!("{}", check.render_issues());
+    }
+
+    if failed > 0 {
+        anyhow::bail!(
+            "{} of {} skill(s) failed validation and will not load",
+            failed,
+            dirs.len()
+        );
+    }
+    println!("{} skill(s) checked, no errors.", dirs.len());
+    Ok(())
+}
+
+/// `jcode skill import --from-claude [path]`: translate a Claude Code
+/// `.claude/` directory into jcode skills. Re-running updates earlier imports.
+pub fn run_skill_import_command(from_claude: Option<&str>, project: bool) -> Result<()> {
+    let source = match from_claude.filter(|path| !path.is_empty()) {
+        Some(path) => skill::resolve_claude_dir(Path::new(path)),
+        None => {
+            let local = std::env::current_dir()?.join(".claude");
+            if local.is_dir() {
+                local
+            } else {
+                storage::user_home_path(".claude")?
+            }
+        }
+    };
+    let dest = if project {
+        std::env::current_dir()?.join(".jcode").join("skills")
+    } else {
+        storage::jcode_dir()?.join("skills")
+    };
+
+    println!("Importing {} into {}", source.display(), dest.display());
+    let report = skill::import_claude_dir(&source, &dest)?;
+    for item in &report.imported {
+        println!(
+            "{:<9}  /{}  ({} {})",
+            item.action.label(),
+            item.name,
+            item.kind.label(),
+            item.source.display()
+        );
+        for note in &item.notes {
+            println!("             note: {}", note);
+        }
+    }
+    for skipped in &report.skipped {
+        println!(
+            "skipped    {}: {}",
+            skipped.source.display(),
+            skipped.reason
+        );
+    }
+
+    println!(
+        "{} imported ({} created, {} updated, {} unchanged), {} skipped.",
+        report.imported.len(),
+        report.count(ImportAction::Created),
+        report.count(ImportAction::Updated),
+        report.count(ImportAction::Unchanged),
+        report.skipped.len()
+    );
+    Ok(())
+}
+
+/// `name` may be a path to a skill directory or a skill name looked up in the
+/// standard skill locations.
+fn resolve_skill_dirs(name: &str) -> Result<Vec<PathBuf>> {
+    let path = Path::new(name);
+    if path.is_dir() && (path.components().count() > 1 || path.join("SKILL.md").exists()) {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let name = name.trim_start_matches('/');
+    let roots = SkillRegistry::skill_roots(None);
+    let dirs: Vec<PathBuf> = dedup_roots(&roots)
+        .into_iter()
+        .map(|root| root.join(name))
+        .filter(|dir| dir.is_dir())
+        .collect();
+    if dirs.is_empty() {
+        let searched: Vec<String> = roots
+            .iter()
+            .map(|root| root.display().to_string())
+            .collect();
+        anyhow::bail!(
+            "No skill named '{}' (searched {})",
+            name,
+            searched.join(", ")
+        );
+    }
+    Ok(dirs)
+}
+
+fn installed_skill_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    for root in dedup_roots(&SkillRegistry::skill_roots(None)) {
+        let Ok(entries) = std::fs::read_dir(&root) else {
+            continue;
+        };
+        let mut found: Vec<PathBuf> = entries
+            .flatten()
+            .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .collect();
+        found.sort();
+        dirs.extend(found);
+    }
+    dirs
+}
+
+/// Skip roots that resolve to the same directory, e.g. `./.jcode/skills` when
+/// running from the home directory.
+fn dedup_roots(roots: &[PathBuf]) -> Vec<PathBuf> {
+    let mut seen = Vec::new();
+    let mut unique = Vec::new();
+    for root in roots {
+        let resolved = std::fs::canonicalize(root).unwrap_or_else(|_| root.clone());
+        if !seen.contains(&resolved) {
+            seen.push(resolved);
+            unique.push(root.clone());
+        }
+    }
+    unique
+}
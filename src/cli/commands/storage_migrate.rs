@@ -0,0 +1,53 @@
+use anyhow::Result;
+
+use crate::config::{StorageBackend, config};
+use crate::storage;
+
+use super::super::args::StorageBackendArg;
+
+/// `jcode storage migrate --to <backend>`: copy every session and memory
+/// graph into the target backend, verifying each by reading it back.
+pub fn run_storage_migrate_command(to: StorageBackendArg) -> Result<()> {
+    let target = match to {
+        StorageBackendArg::Sqlite => StorageBackend::Sqlite,
+        StorageBackendArg::Files => StorageBackend::Files,
+    };
+    let report = storage::migrate_storage(target)?;
+
+    let direction = match target {
+        StorageBackend::Sqlite => format!("into {}", report.database.display()),
+        StorageBackend::Files => format!("from {} to files", report.database.display()),
+    };
+    println!("Migrated {}:", direction);
+    println!(
+        "  sessions   {} copied, {} already up to date, {} skipped (read-only, from a newer jcode)",
+        report.sessions_migrated, report.sessions_current, report.sessions_skipped
+    );
+    println!(
+        "  memories   {} copied, {} already up to date, {} skipped (legacy format, upgraded on next load)",
+        report.memories_migrated, report.memories_current, report.memories_skipped
+    );
+
+    if !report.failures.is_empty() {
+        println!();
+        for failure in &report.failures {
+            println!("  failed     {}: {}", failure.item, failure.error);
+        }
+        anyhow::bail!(
+            "{} item(s) failed to migrate or verify; the source was left untouched",
+            report.failures.len()
+        );
+    }
+
+    println!();
+    if config().storage.backend == target {
+        println!("`[storage] backend` is already \"{}\".", target.as_str());
+    } else {
+        println!(
+            "All items verified. Set `backend = \"{}\"` under `[storage]` in ~/.jcode/config.toml (or JCODE_STORAGE_BACKEND={}) to switch.",
+            target.as_str(),
+            target.as_str()
+        );
+    }
+    Ok(())
+}
@@ -316,6 +316,101 @@ fn run_auto_poke_followup_sends_confidence_summary_once() {
     assert!(build_run_auto_poke_follow_up_from_todos(&todos, true).is_none());
 }
 
+#[test]
+fn json_run_state_sums_usage_and_summarizes_tool_calls() {
+    use crate::protocol::ServerEvent;
+
+    let mut state = JsonRunState::default();
+    let events = [
+        ServerEvent::ToolStart {
+            id: "t1".to_string(),
+            name: "read".to_string(),
+        },
+        ServerEvent::ToolDone {
+            id: "t1".to_string(),
+            name: "read".to_string(),
+            output: "ok".to_string(),
+            error: None,
+        },
+        ServerEvent::ToolStart {
+            id: "t2".to_string(),
+            name: "bash".to_string(),
+        },
+        ServerEvent::ToolDone {
+            id: "t2".to_string(),
+            name: "bash".to_string(),
+            output: "exit 1".to_string(),
+            error: Some("Tool error".to_string()),
+        },
+        ServerEvent::TokenUsage {
+            input: 100,
+            output: 20,
+            cache_read_input: Some(50),
+            cache_creation_input: None,
+        },
+        ServerEvent::MessageEnd,
+        ServerEvent::TokenUsage {
+            input: 30,
+            output: 5,
+            cache_read_input: Some(10),
+            cache_creation_input: None,
+        },
+        ServerEvent::MessageEnd,
+    ];
+    for event in events {
+        record_json_run_event(&mut state, event, false);
+    }
+
+    assert_eq!(state.turns, 2);
+    assert_eq!(state.usage.input_tokens, 130);
+    assert_eq!(state.usage.output_tokens, 25);
+    assert_eq!(state.usage.cache_read_input_tokens, Some(60));
+    assert_eq!(state.usage.cache_creation_input_tokens, None);
+    let summary: Vec<_> = state
+        .tool_calls
+        .iter()
+        .map(|call| (call.name.as_str(), call.success))
+        .collect();
+    assert_eq!(summary, vec![("read", true), ("bash", false)]);
+    assert!(state.tool_started.is_empty());
+}
+
+#[test]
+fn run_setup_errors_still_emit_a_json_envelope() {
+    let mut out = Vec::new();
+    write_run_setup_error(
+        &mut out,
+        RunOutputFormat::Json,
+        "session_missing",
+        "claude",
+        "",
+        "Session not found: session_missing",
+    )
+    .expect("write json envelope");
+    let report: serde_json::Value = serde_json::from_slice(&out).expect("one json object");
+    assert_eq!(report["status"], "error");
+    assert_eq!(report["exit_code"], 1);
+    assert_eq!(report["session_id"], "session_missing");
+    assert_eq!(report["error"], "Session not found: session_missing");
+    assert_eq!(report["turns"], 0);
+
+    let mut out = Vec::new();
+    write_run_setup_error(
+        &mut out,
+        RunOutputFormat::StreamJson,
+        "",
+        "openai",
+        "gpt-5",
+        "no credentials",
+    )
+    .expect("write ndjson error");
+    let text = String::from_utf8(out).expect("utf8");
+    assert_eq!(text.lines().count(), 1);
+    let event: serde_json::Value = serde_json::from_str(text.trim()).expect("json line");
+    assert_eq!(event["type"], "error");
+    assert_eq!(event["message"], "no credentials");
+}
+
 #[test]
 fn cli_provider_choice_filter_uses_typed_api_methods() {
     let routes = vec![
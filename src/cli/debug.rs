@@ -13,6 +13,8 @@ pub async fn run_debug_command(
     match command {
         "list" => return debug_list_servers().await,
         "start" => return debug_start_server(arg, socket_path).await,
+        "builds" => return super::commands::print_build_manifest(),
+        "canary" => return super::commands::print_canary_report(),
         _ => {}
     }
 
@@ -86,6 +88,16 @@ pub async fn run_debug_command(
                 eprintln!("Error: {}", output);
                 std::process::exit(1);
             }
+            if is_streaming_debug_command(&debug_cmd) {
+                // Streaming commands keep writing lines until one side hangs up.
+                loop {
+                    line.clear();
+                    if reader.read_line(&mut line).await? == 0 {
+                        break;
+                    }
+                    print!("{}", line);
+                }
+            }
         }
         Some("error") => {
             let message = response
@@ -103,6 +115,14 @@ pub async fn run_debug_command(
     Ok(())
 }
 
+fn is_streaming_debug_command(command: &str) -> bool {
+    ["events:subscribe", "events:tail"].iter().any(|prefix| {
+        command
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with([':', ' ']))
+    })
+}
+
 async fn debug_list_servers() -> Result<()> {
     let mut servers = Vec::new();
 
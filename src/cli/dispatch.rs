@@ -6,9 +6,10 @@ use std::process::{Command as ProcessCommand, Stdio};
 use std::time::Instant;
 
 use super::args::{
-    AmbientCommand, Args, AuthCommand, CloudCommand, CloudSessionsCommand, Command, MemoryCommand,
-    ModelCommand, ProviderCommand, RestartCommand, ServerCommand, SessionCommand,
-    TranscriptModeArg,
+    AmbientCommand, Args, AuthCommand, BackupCommand, CloudCommand, CloudSessionsCommand, Command,
+    ConfigCommand, HooksCommand, MemoryCommand, ModelCommand, ProviderCommand, RestartCommand,
+    RunOutputFormat, RunSession, ServerCommand, SessionCommand, SkillCommand, StorageCommand,
+    TranscriptModeArg, UpdateChannelArg, UsageCommand,
 };
 use crate::{
     agent, auth, build, provider, provider_catalog, server, session, setup_hints, startup_profile,
@@ -16,12 +17,14 @@ use crate::{
 };
 
 use super::{
-    acp, commands, debug, hot_exec, login, output, provider_init, selfdev, terminal, tui_launch,
+    acp, commands, debug, hot_exec, login, output, provider_init, selfdev, telegram_bridge,
+    terminal, tui_launch,
 };
 use provider_init::ProviderChoice;
 
 pub(crate) async fn run_main(mut args: Args) -> Result<()> {
     resolve_resume_arg(&mut args)?;
+    validate_agent_profiles(args.profile.as_deref())?;
 
     if let Some(profile_name) = args
         .provider_profile
@@ -90,6 +93,14 @@ pub(crate) async fn run_main(mut args: Args) -> Result<()> {
             )
             .await?;
         }
+        Some(Command::Telegram) => {
+            telegram_bridge::run_telegram_command(
+                args.provider,
+                args.model.clone(),
+                args.provider_profile.clone(),
+            )
+            .await?;
+        }
         Some(Command::Connect) => {
             tui_launch::run_client().await?;
         }
@@ -103,16 +114,48 @@ pub(crate) async fn run_main(mut args: Args) -> Result<()> {
         },
         Some(Command::Run {
             message,
+            output,
             json,
             ndjson,
+            plan_only,
+            compare,
+            max_turns,
+            deterministic,
+            attach,
+            append_system,
+            tee_cmd,
+            continue_session,
+            session,
+            new,
+            prompt,
+            var,
         }) => {
+            let message = match prompt {
+                Some(name) => super::run_input::expand_saved_prompt(&name, &var)?,
+                None => message.unwrap_or_default(),
+            };
+            let input = super::run_input::prepare_run_input(
+                &message,
+                &attach,
+                append_system.as_deref().map(std::path::Path::new),
+                std::io::stdin().lock(),
+            )?;
+            if deterministic {
+                crate::env::set_var("JCODE_DETERMINISTIC", "1");
+                crate::config::invalidate_config_cache();
+            }
             commands::run_single_message_command(
                 &args.provider,
                 args.model.as_deref(),
                 args.resume.as_deref(),
-                &message,
-                json,
-                ndjson,
+                RunSession::resolve(new, continue_session, session),
+                input,
+                RunOutputFormat::resolve(output, json, ndjson),
+                plan_only,
+                compare,
+                max_turns,
+                args.profile.as_deref(),
+                tee_cmd.as_deref(),
             )
             .await?;
         }
@@ -165,24 +208,54 @@ pub(crate) async fn run_main(mut args: Args) -> Result<()> {
             let mut agent = agent::Agent::new(provider, registry);
             agent.repl().await?;
         }
-        Some(Command::Update) => {
-            hot_exec::run_update()?;
+        Some(Command::Update { channel }) => {
+            hot_exec::run_update(channel.map(map_update_channel))?;
         }
         Some(Command::Version { json }) => {
             commands::run_version_command(json)?;
         }
-        Some(Command::Usage { json }) => {
-            commands::run_usage_command(json).await?;
+        Some(Command::Doctor { json }) => {
+            commands::run_doctor_command(json).await?;
+        }
+        Some(Command::Usage { json, action }) => match action {
+            Some(UsageCommand::Forecast { json }) => {
+                commands::run_usage_forecast_command(json).await?;
+            }
+            None => commands::run_usage_command(json).await?,
+        },
+        Some(Command::Todos { all, json }) => {
+            commands::run_todos_command(all, json)?;
+        }
+        Some(Command::Blame { file, line, json }) => {
+            commands::run_blame_command(std::path::Path::new(&file), line, json)?;
         }
         Some(Command::SelfDev { build }) => {
             selfdev::run_self_dev(build, args.resume).await?;
         }
+        Some(Command::Rollback { version, list }) => {
+            commands::run_rollback_command(version.as_deref(), list)?;
+        }
+        Some(Command::Promote { force }) => {
+            commands::run_promote_command(force)?;
+        }
+        Some(Command::Debug {
+            command,
+            session,
+            last,
+            ..
+        }) if command == "crash-report" => {
+            commands::run_crash_report_command(last, session.as_deref())?;
+        }
+        Some(Command::Debug { command, arg, .. }) if command == "notify-test" => {
+            commands::run_notify_test_command(&arg).await?;
+        }
         Some(Command::Debug {
             command,
             arg,
             session,
             socket,
             wait,
+            ..
         }) => {
             debug::run_debug_command(&command, &arg, session, socket, wait).await?;
         }
@@ -253,7 +326,70 @@ pub(crate) async fn run_main(mut args: Args) -> Result<()> {
                 clear,
                 json,
             } => commands::run_session_rename_command(&session, name.as_deref(), clear, json)?,
+            SessionCommand::Migrate { session, all } => {
+                commands::run_session_migrate_command(session.as_deref(), all)?
+            }
+            SessionCommand::Import { from, path } => {
+                commands::run_session_import_command(from, path.as_deref())?
+            }
+            SessionCommand::Changes {
+                session,
+                diff,
+                json,
+            } => commands::run_session_changes_command(&session, diff, json)?,
+        },
+        Some(Command::Skill(subcmd)) => match subcmd {
+            SkillCommand::New { name, project } => commands::run_skill_new_command(&name, project)?,
+            SkillCommand::Check { name } => commands::run_skill_check_command(name.as_deref())?,
+            SkillCommand::Import {
+                from_claude,
+                project,
+            } => commands::run_skill_import_command(from_claude.as_deref(), project)?,
+        },
+        Some(Command::Storage(subcmd)) => match subcmd {
+            StorageCommand::Migrate { to } => commands::run_storage_migrate_command(to)?,
+        },
+        Some(Command::Backup(subcmd)) => match subcmd {
+            BackupCommand::Create {
+                output,
+                include_auth,
+            } => commands::run_backup_create_command(&output, include_auth)?,
+            BackupCommand::Restore {
+                input,
+                dry_run,
+                force,
+            } => commands::run_backup_restore_command(
+                &input,
+                dry_run,
+                force,
+                server_is_running().await,
+            )?,
         },
+        Some(Command::Config(subcmd)) => match subcmd {
+            ConfigCommand::Get { key } => commands::run_config_get_command(&key)?,
+            ConfigCommand::Set { key, value } => commands::run_config_set_command(&key, &value)?,
+            ConfigCommand::Doctor => commands::run_config_doctor_command()?,
+        },
+        Some(Command::Hooks(subcmd)) => match subcmd {
+            HooksCommand::Status { dir } => commands::run_hooks_status_command(dir.as_deref())?,
+            HooksCommand::Trust { dir } => commands::run_hooks_trust_command(dir.as_deref())?,
+            HooksCommand::Untrust { dir } => commands::run_hooks_untrust_command(dir.as_deref())?,
+        },
+        Some(Command::Logs {
+            session,
+            level,
+            target,
+            lines,
+            follow,
+            json,
+        }) => commands::run_logs_command(commands::LogsOptions {
+            session,
+            level,
+            target,
+            lines,
+            follow,
+            json,
+        })?,
         Some(Command::Ambient(subcmd)) => {
             commands::run_ambient_command(map_ambient_subcommand(subcmd)).await?;
         }
@@ -323,6 +459,9 @@ pub(crate) async fn run_main(mut args: Args) -> Result<()> {
             )
             .await?;
         }
+        Some(Command::View { session }) => {
+            tui_launch::run_view_command(&session).await?;
+        }
         Some(Command::Model(subcmd)) => match subcmd {
             ModelCommand::List { json, verbose } => {
                 commands::run_model_command(&args.provider, args.model.as_deref(), json, verbose)
@@ -435,6 +574,20 @@ pub(crate) async fn run_main(mut args: Args) -> Result<()> {
     Ok(())
 }
 
+/// Reject malformed `[profiles.*]` sections (unknown keys, bad values) and an
+/// unknown `--profile` name before anything starts.
+fn validate_agent_profiles(selected: Option<&str>) -> Result<()> {
+    let config = crate::config::config();
+    let errors = config.profile_errors();
+    if !errors.is_empty() {
+        anyhow::bail!("Invalid profile config:\n  {}", errors.join("\n  "));
+    }
+    if let Some(name) = selected {
+        config.profile(name)?;
+    }
+    Ok(())
+}
+
 fn auth_doctor_provider_arg<'a>(
     positional_provider: Option<&'a str>,
     global_provider: &'a ProviderChoice,
@@ -457,6 +610,9 @@ fn resolve_resume_arg(args: &mut Args) -> Result<()> {
         let resume_id = resume_id.clone();
         match resolve_resume_id(&resume_id) {
             Ok(full_id) => {
+                if let Err(err) = relocate_resumed_working_dir(&full_id) {
+                    eprintln!("Could not relocate the session working directory: {}", err);
+                }
                 args.resume = Some(full_id);
             }
             Err(e) => {
@@ -519,6 +675,74 @@ where
     }
 }
 
+/// When a resumed session's working directory has been moved or renamed, offer
+/// likely new locations (same folder name nearby, same git remote) or let the
+/// user type one, then point the session there before the server loads it.
+/// Only asks on an interactive terminal and never during a reload handoff.
+fn relocate_resumed_working_dir(session_id: &str) -> Result<()> {
+    if !std::io::stdin().is_terminal() || std::env::var_os("JCODE_RESUMING").is_some() {
+        return Ok(());
+    }
+    let stub = session::Session::load_startup_stub(session_id)?;
+    if stub.missing_working_dir().is_none() {
+        return Ok(());
+    }
+    let mut session = session::Session::load(session_id)?;
+    let Some(old_dir) = session.missing_working_dir().map(std::path::PathBuf::from) else {
+        return Ok(());
+    };
+
+    let cwd = std::env::current_dir().ok();
+    let candidates =
+        session::relocation_candidates(&old_dir, session.last_git_remote(), cwd.as_slice());
+    eprintln!(
+        "The working directory of this session no longer exists:\n  {}",
+        old_dir.display()
+    );
+    if candidates.is_empty() {
+        eprintln!("No likely new location found.");
+    } else {
+        eprintln!("Possible new locations:");
+        for (index, candidate) in candidates.iter().enumerate() {
+            eprintln!("  {}) {}", index + 1, candidate.display());
+        }
+    }
+    eprint!("Enter a number or a path (leave empty to keep the old path): ");
+    std::io::Write::flush(&mut std::io::stderr())?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+
+    let Some(new_dir) = relocation_choice(answer.trim(), &candidates) else {
+        return Ok(());
+    };
+    let new_root = session.relocate_working_dir(&new_dir)?;
+    session.save()?;
+    std::env::set_current_dir(&new_root)?;
+    output::stderr_info(format!("Session now works in {}", new_root));
+    Ok(())
+}
+
+/// Interpret the relocation prompt answer: a 1-based candidate number, a path
+/// (`~/` expands to the home directory), or empty to keep the old path.
+fn relocation_choice(
+    answer: &str,
+    candidates: &[std::path::PathBuf],
+) -> Option<std::path::PathBuf> {
+    if answer.is_empty() {
+        return None;
+    }
+    if let Ok(index) = answer.parse::<usize>() {
+        return index
+            .checked_sub(1)
+            .and_then(|index| candidates.get(index))
+            .cloned();
+    }
+    match (answer.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => Some(home.join(rest)),
+        _ => Some(std::path::PathBuf::from(answer)),
+    }
+}
+
 fn resolve_resume_id(resume_id: &str) -> Result<String> {
     match session::find_session_by_name_or_id(resume_id) {
         Ok(full_id) => Ok(full_id),
@@ -705,16 +929,25 @@ fn map_transcript_mode(mode: TranscriptModeArg) -> crate::protocol::TranscriptMo
     }
 }
 
+fn map_update_channel(channel: UpdateChannelArg) -> crate::config::UpdateChannel {
+    match channel {
+        UpdateChannelArg::Stable => crate::config::UpdateChannel::Stable,
+        UpdateChannelArg::Nightly => crate::config::UpdateChannel::Main,
+    }
+}
+
 async fn run_default_command(args: Args) -> Result<()> {
     startup_profile::mark("run_main_none_branch");
 
     let explicit_provider_or_model = args.provider != ProviderChoice::Auto
         || args.model.is_some()
-        || args.provider_profile.is_some();
+        || args.provider_profile.is_some()
+        || args.profile.is_some();
     let explicit_tool_options = args.tool_profile.is_some()
         || args.tools.is_some()
         || args.disabled_tools.is_some()
-        || args.disable_base_tools;
+        || args.disable_base_tools
+        || args.safe;
     if args.resume.is_none()
         && !explicit_provider_or_model
         && !explicit_tool_options
@@ -856,6 +1089,8 @@ async fn run_default_command(args: Args) -> Result<()> {
         startup_hints,
         !server_running,
         args.fresh_spawn,
+        args.profile,
+        args.safe,
     )
     .await?;
 
@@ -1154,8 +1389,9 @@ pub(crate) async fn spawn_server(
 
     #[cfg(unix)]
     {
-        let _child = server::spawn_server_notify(&mut cmd).await?;
+        let child = server::spawn_server_notify(&mut cmd).await?;
         startup_profile::mark("server_ready");
+        terminal::watch_spawned_server(child);
     }
     #[cfg(not(unix))]
     {
@@ -121,6 +121,25 @@ fn resolve_resume_id_imports_raw_codex_session_ids() {
     crate::env::remove_var("JCODE_HOME");
 }
 
+#[test]
+fn relocation_choice_accepts_candidate_numbers_and_paths() {
+    let candidates = vec![
+        std::path::PathBuf::from("/work/app"),
+        std::path::PathBuf::from("/src/app"),
+    ];
+    assert_eq!(relocation_choice("", &candidates), None);
+    assert_eq!(
+        relocation_choice("2", &candidates),
+        Some(std::path::PathBuf::from("/src/app"))
+    );
+    assert_eq!(relocation_choice("0", &candidates), None);
+    assert_eq!(relocation_choice("3", &candidates), None);
+    assert_eq!(
+        relocation_choice("/elsewhere/app", &candidates),
+        Some(std::path::PathBuf::from("/elsewhere/app"))
+    );
+}
+
 #[test]
 fn resume_failure_defers_to_server_during_reload_handoff() {
     // Issue #328: when `--resume <id>` cannot be resolved locally but a reload/
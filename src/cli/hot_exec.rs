@@ -18,7 +18,7 @@ pub fn execute_requested_action(run_result: &RunResult) -> Result<()> {
     }
 
     if let Some(ref rebuild_session_id) = run_result.rebuild_session {
-        hot_rebuild(rebuild_session_id)?;
+        hot_rebuild(rebuild_session_id, false)?;
     }
 
     if let Some(ref update_session_id) = run_result.update_session {
@@ -239,7 +239,13 @@ fn claim_update_fetch_slot() -> bool {
 }
 
 pub fn check_for_updates() -> Option<bool> {
-    let repo_dir = get_repo_dir()?;
+    let Some(repo_dir) = get_repo_dir() else {
+        // No checkout to fetch (Homebrew, `cargo install`): ask GitHub releases
+        // on the configured channel instead.
+        return update::check_for_update_blocking()
+            .ok()
+            .map(|release| release.is_some());
+    };
 
     if claim_update_fetch_slot() {
         let fetch = ProcessCommand::new("git")
@@ -330,9 +336,17 @@ pub fn run_auto_update() -> Result<()> {
     ))
 }
 
-pub fn run_update() -> Result<()> {
-    if update::is_release_build() {
-        update::print_centered("Checking GitHub for latest release...");
+pub fn run_update(channel: Option<crate::config::UpdateChannel>) -> Result<()> {
+    if let Some(channel) = channel {
+        crate::config::Config::set_update_channel(channel)?;
+        update::print_centered(&format!("Update channel set to {}", channel));
+    }
+
+    if update::uses_release_updates() {
+        update::print_centered(&format!(
+            "Checking GitHub for the latest {} release...",
+            crate::config::config().update_channel()
+        ));
         match update::check_for_update_blocking() {
             Ok(Some(release)) => {
                 update::print_centered(&format!(
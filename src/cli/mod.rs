@@ -10,7 +10,9 @@ pub mod output;
 pub mod proctitle;
 pub mod provider_doctor;
 pub mod provider_init;
+pub mod run_input;
 pub mod selfdev;
 pub mod startup;
+pub mod telegram_bridge;
 pub mod terminal;
 pub mod tui_launch;
@@ -12,20 +12,29 @@ pub(crate) fn initial_title(args: &Args) -> String {
     match &args.command {
         Some(Command::Serve { .. }) => "jcode:server".to_string(),
         Some(Command::Acp) => "jcode acp".to_string(),
+        Some(Command::Telegram) => "jcode telegram".to_string(),
         Some(Command::Server { .. }) => "jcode server".to_string(),
         Some(Command::Connect) => "jcode:client".to_string(),
         Some(Command::Run { .. }) => "jcode run".to_string(),
         Some(Command::Login { .. }) => "jcode login".to_string(),
         Some(Command::Repl) => "jcode repl".to_string(),
-        Some(Command::Update) => "jcode update".to_string(),
+        Some(Command::Update { .. }) => "jcode update".to_string(),
         Some(Command::Version { .. }) => "jcode version".to_string(),
+        Some(Command::Doctor { .. }) => "jcode doctor".to_string(),
         Some(Command::Usage { .. }) => "jcode usage".to_string(),
         Some(Command::SelfDev { .. }) => "jcode:selfdev".to_string(),
+        Some(Command::Rollback { .. }) => "jcode rollback".to_string(),
+        Some(Command::Promote { .. }) => "jcode promote".to_string(),
         Some(Command::Debug { .. }) => "jcode debug".to_string(),
         Some(Command::Auth(_)) => "jcode auth".to_string(),
         Some(Command::Provider(_)) => "jcode provider".to_string(),
         Some(Command::Memory(_)) => "jcode memory".to_string(),
         Some(Command::Session(_)) => "jcode session".to_string(),
+        Some(Command::Skill(_)) => "jcode skill".to_string(),
+        Some(Command::Storage(_)) => "jcode storage".to_string(),
+        Some(Command::Backup(_)) => "jcode backup".to_string(),
+        Some(Command::Config(_)) => "jcode config".to_string(),
+        Some(Command::Logs { .. }) => "jcode logs".to_string(),
         Some(Command::Ambient(subcommand)) => match subcommand {
             AmbientCommand::RunVisible => "jcode ambient visible".to_string(),
             _ => "jcode ambient".to_string(),
@@ -46,6 +55,9 @@ pub(crate) fn initial_title(args: &Args) -> String {
         }
         Some(Command::Browser { .. }) => "jcode browser".to_string(),
         Some(Command::Replay { .. }) => "jcode replay".to_string(),
+        Some(Command::View { .. }) => "jcode view".to_string(),
+        Some(Command::Todos { .. }) => "jcode todos".to_string(),
+        Some(Command::Blame { .. }) => "jcode blame".to_string(),
         Some(Command::Model(_)) => "jcode model".to_string(),
         Some(Command::ProviderTestCoverage { .. }) => "jcode provider-test-coverage".to_string(),
         Some(Command::ProviderDoctor { .. }) => "jcode provider-doctor".to_string(),
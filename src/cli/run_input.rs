@@ -0,0 +1,149 @@
+//! Prompt assembly for `jcode run`: `-` reads the message from stdin,
+//! `--prompt` expands a saved prompt, `--attach` adds text files as context
+//! blocks, and `--append-system` adds a project instruction file to the system
+//! prompt for that run only.
+
+use crate::saved_prompt;
+use anyhow::{Context, Result};
+use std::io::Read;
+use std::path::Path;
+
+/// Largest single file accepted by `--attach`.
+pub const MAX_ATTACHMENT_BYTES: u64 = 256 * 1024;
+/// Largest combined size of all `--attach` files.
+pub const MAX_TOTAL_ATTACHMENT_BYTES: u64 = 1024 * 1024;
+/// Largest file accepted by `--append-system`.
+pub const MAX_APPEND_SYSTEM_BYTES: u64 = 64 * 1024;
+
+/// The resolved inputs for one `jcode run`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunInput {
+    /// User message, with any attachments prepended.
+    pub message: String,
+    /// Contents of the `--append-system` file, if one was given.
+    pub append_system: Option<String>,
+}
+
+/// Resolve the `jcode run` arguments into a message and optional system
+/// addendum. `stdin` is only read when `message` is `-`.
+pub fn prepare_run_input(
+    message: &str,
+    attach: &[impl AsRef<Path>],
+    append_system: Option<&Path>,
+    stdin: impl Read,
+) -> Result<RunInput> {
+    let message = if message == "-" {
+        read_stdin_message(stdin)?
+    } else {
+        message.to_string()
+    };
+
+    let mut blocks = Vec::with_capacity(attach.len() + 1);
+    let mut total_bytes = 0u64;
+    for path in attach {
+        let path = path.as_ref();
+        let content = read_text_file(path, MAX_ATTACHMENT_BYTES, "--attach")?;
+        total_bytes += content.len() as u64;
+        if total_bytes > MAX_TOTAL_ATTACHMENT_BYTES {
+            anyhow::bail!(
+                "--attach files exceed the combined limit of {} KiB",
+                MAX_TOTAL_ATTACHMENT_BYTES / 1024
+            );
+        }
+        blocks.push(attachment_block(path, &content));
+    }
+    blocks.push(message);
+
+    let append_system = append_system
+        .map(|path| read_text_file(path, MAX_APPEND_SYSTEM_BYTES, "--append-system"))
+        .transpose()?;
+
+    Ok(RunInput {
+        message: blocks.join("\n\n"),
+        append_system,
+    })
+}
+
+/// Expand the saved prompt `name` with `--var name=value` assignments.
+/// Unlike the TUI, a run cannot ask for missing variables, so they are an
+/// error.
+pub fn expand_saved_prompt(name: &str, vars: &[String]) -> Result<String> {
+    let vars = saved_prompt::parse_assignments(vars.iter().map(String::as_str))?;
+    let working_dir = std::env::current_dir().ok();
+    let prompt = saved_prompt::find(name, working_dir.as_deref())?;
+    let missing = saved_prompt::missing_variables(&prompt.template, &vars);
+    if !missing.is_empty() {
+        anyhow::bail!(
+            "saved prompt '{}' needs {}",
+            name,
+            missing
+                .iter()
+                .map(|var| format!("--var {}=...", var))
+                .collect::<Vec<_>>()
+                .join(" ")
+        );
+    }
+    saved_prompt::expand(
+        &prompt.template,
+        &vars,
+        &saved_prompt::PromptContext {
+            working_dir: working_dir.as_deref(),
+            clipboard: &saved_prompt::command_clipboard_text,
+        },
+    )
+}
+
+fn read_stdin_message(mut stdin: impl Read) -> Result<String> {
+    let mut bytes = Vec::new();
+    stdin
+        .read_to_end(&mut bytes)
+        .context("failed to read the message from stdin")?;
+    let text =
+        String::from_utf8(bytes).map_err(|_| anyhow::anyhow!("stdin is not valid UTF-8 text"))?;
+    if text.trim().is_empty() {
+        anyhow::bail!("no message on stdin (`jcode run -` reads the prompt from stdin)");
+    }
+    Ok(text)
+}
+
+/// Read a UTF-8 text file no larger than `max_bytes`. Binary files are
+/// rejected rather than mangled into the prompt.
+fn read_text_file(path: &Path, max_bytes: u64, flag: &str) -> Result<String> {
+    let metadata = std::fs::metadata(path)
+        .with_context(|| format!("{flag} {}: cannot read file", path.display()))?;
+    if !metadata.is_file() {
+        anyhow::bail!("{flag} {}: not a regular file", path.display());
+    }
+    if metadata.len() > max_bytes {
+        anyhow::bail!(
+            "{flag} {}: file is {} KiB, the limit is {} KiB",
+            path.display(),
+            metadata.len().div_ceil(1024),
+            max_bytes / 1024
+        );
+    }
+    let bytes = std::fs::read(path)
+        .with_context(|| format!("{flag} {}: cannot read file", path.display()))?;
+    let binary = || {
+        anyhow::anyhow!(
+            "{flag} {}: looks like a binary file; only text files can be attached",
+            path.display()
+        )
+    };
+    if bytes.contains(&0) {
+        return Err(binary());
+    }
+    String::from_utf8(bytes).map_err(|_| binary())
+}
+
+fn attachment_block(path: &Path, content: &str) -> String {
+    format!(
+        "<attachment path=\"{}\">\n{}\n</attachment>",
+        path.display(),
+        content.trim_end_matches('\n')
+    )
+}
+
+#[cfg(test)]
+#[path = "run_input_tests.rs"]
+mod tests;
@@ -0,0 +1,80 @@
+use super::*;
+
+const NO_FILES: &[&Path] = &[];
+
+#[test]
+fn dash_reads_message_from_stdin_and_prepends_attachments() {
+    let temp = tempfile::TempDir::new().expect("temp dir");
+    let guide = temp.path().join("CONTRIBUTING.md");
+    std::fs::write(&guide, "Run cargo fmt.\n").expect("write guide");
+
+    let input = prepare_run_input(
+        "-",
+        &[guide.clone()],
+        None,
+        "diff --git a/x b/x\n".as_bytes(),
+    )
+    .expect("prepare input");
+    assert_eq!(
+        input.message,
+        format!(
+            "<attachment path=\"{}\">\nRun cargo fmt.\n</attachment>\n\ndiff --git a/x b/x\n",
+            guide.display()
+        )
+    );
+    assert_eq!(input.append_system, None);
+}
+
+#[test]
+fn literal_message_does_not_read_stdin() {
+    let input = prepare_run_input("hello", NO_FILES, None, "ignored".as_bytes()).expect("prepare");
+    assert_eq!(input.message, "hello");
+}
+
+#[test]
+fn empty_stdin_is_an_error() {
+    let err = prepare_run_input("-", NO_FILES, None, "  \n".as_bytes()).unwrap_err();
+    assert!(err.to_string().contains("no message on stdin"));
+}
+
+#[test]
+fn binary_and_oversized_files_are_rejected() {
+    let temp = tempfile::TempDir::new().expect("temp dir");
+    let binary = temp.path().join("logo.png");
+    std::fs::write(&binary, [0x89, b'P', b'N', b'G', 0, 0, 1]).expect("write binary");
+    let err = prepare_run_input("x", &[binary], None, std::io::empty()).unwrap_err();
+    assert!(
+        err.to_string().contains("looks like a binary file"),
+        "{err}"
+    );
+
+    let large = temp.path().join("large.txt");
+    std::fs::write(&large, "a".repeat(MAX_ATTACHMENT_BYTES as usize + 1)).expect("write large");
+    let err = prepare_run_input("x", &[large], None, std::io::empty()).unwrap_err();
+    assert!(err.to_string().contains("the limit is 256 KiB"), "{err}");
+
+    let big_system = temp.path().join("AGENTS.md");
+    std::fs::write(
+        &big_system,
+        "b".repeat(MAX_APPEND_SYSTEM_BYTES as usize + 1),
+    )
+    .expect("write system file");
+    let err =
+        prepare_run_input("x", NO_FILES, Some(big_system.as_path()), std::io::empty()).unwrap_err();
+    assert!(err.to_string().starts_with("--append-system"), "{err}");
+}
+
+#[test]
+fn combined_attachment_size_is_capped() {
+    let temp = tempfile::TempDir::new().expect("temp dir");
+    let chunk = "c".repeat(MAX_ATTACHMENT_BYTES as usize);
+    let paths: Vec<_> = (0..5)
+        .map(|index| {
+            let path = temp.path().join(format!("part{index}.txt"));
+            std::fs::write(&path, &chunk).expect("write part");
+            path
+        })
+        .collect();
+    let err = prepare_run_input("x", &paths, None, std::io::empty()).unwrap_err();
+    assert!(err.to_string().contains("combined limit"), "{err}");
+}
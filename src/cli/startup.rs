@@ -1,11 +1,12 @@
 use anyhow::Result;
 use clap::Parser;
+use std::io::IsTerminal;
 use std::process::Command as ProcessCommand;
 
 use crate::{build, logging, perf, server, startup_profile, storage, telemetry, update};
 
 use super::{
-    args::{Args, Command},
+    args::{Args, Command, ConfigCommand},
     dispatch, hot_exec, output, terminal,
 };
 
@@ -38,6 +39,11 @@ pub async fn run() -> Result<()> {
     crate::config::on_config_reloaded(crate::auth::AuthStatus::invalidate_cache);
     crate::config::on_config_reloaded(|| crate::bus::Bus::global().publish_models_updated());
 
+    // Install the `[network]` egress allowlist before anything opens an HTTP
+    // connection, and rebuild it when config.toml changes.
+    crate::network_policy::install();
+    crate::config::on_config_reloaded(crate::network_policy::install);
+
     // Invert the legacy provider_catalog -> auth dependency: provider_catalog
     // consults registered fallback resolvers, and auth (the higher layer)
     // registers its external-CLI credential scan here.
@@ -101,11 +107,15 @@ pub async fn run() -> Result<()> {
 
     let args = parse_and_prepare_args()?;
     spawn_background_update_check(&args);
+    let interactive_session = matches!(args.command, None | Some(Command::Connect));
 
     if let Err(e) = dispatch::run_main(args).await {
         report_main_error(&e);
         return Err(e);
     }
+    if interactive_session {
+        terminal::record_canary_clean_exit();
+    }
 
     Ok(())
 }
@@ -121,8 +131,39 @@ fn parse_and_prepare_args() -> Result<Args> {
         logging::info(&format!("Changed working directory to: {}", cwd));
     }
 
-    if args.trace {
+    if !args.roots.is_empty() {
+        let roots = args
+            .roots
+            .iter()
+            .map(|root| {
+                std::fs::canonicalize(root)
+                    .map_err(|err| anyhow::anyhow!("Workspace root {}: {}", root, err))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        crate::env::set_var(
+            crate::session::WORKSPACE_ROOTS_ENV,
+            std::env::join_paths(roots)?,
+        );
+    }
+
+    report_config_issues(&args)?;
+
+    // First-run onboarding needs a person at a terminal. The TUI reads this
+    // when it decides whether to start the guided flow.
+    if args.no_onboarding || !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+        crate::env::set_var("JCODE_NO_ONBOARDING", "1");
+    }
+
+    if let Some(directives) = &args.trace {
         crate::env::set_var("JCODE_TRACE", "1");
+        // Appended so module directives already in JCODE_LOG stay in force;
+        // exported so spawned servers inherit the same levels.
+        let combined = match std::env::var("JCODE_LOG") {
+            Ok(existing) if !existing.trim().is_empty() => format!("{},{}", existing, directives),
+            _ => directives.clone(),
+        };
+        crate::env::set_var("JCODE_LOG", combined);
+        logging::reload_level_filter();
     }
 
     if let Some(ref socket) = args.socket {
@@ -134,6 +175,39 @@ fn parse_and_prepare_args() -> Result<Args> {
     Ok(args)
 }
 
+/// Print keys that config loading will ignore. `--strict-config` turns them
+/// into a startup error instead.
+fn report_config_issues(args: &Args) -> Result<()> {
+    // `jcode config doctor` reports the same issues in its own checklist.
+    if matches!(args.command, Some(Command::Config(ConfigCommand::Doctor))) {
+        return Ok(());
+    }
+    let issues = match crate::config::Config::file_issues() {
+        Ok(issues) => issues,
+        Err(err) if args.strict_config => return Err(err),
+        Err(err) => {
+            output::stderr_info(format!("Warning: {:#}; using default config", err));
+            return Ok(());
+        }
+    };
+    if issues.is_empty() {
+        return Ok(());
+    }
+    if args.strict_config {
+        let details: Vec<String> = issues.iter().map(ToString::to_string).collect();
+        anyhow::bail!(
+            "config.toml has {} problem(s) (--strict-config):\n{}",
+            issues.len(),
+            details.join("\n")
+        );
+    }
+    for issue in &issues {
+        output::stderr_info(format!("Warning: ignoring config key: {}", issue));
+    }
+    output::stderr_info("Run `jcode config doctor` for a full check.");
+    Ok(())
+}
+
 fn spawn_background_update_check(args: &Args) {
     let check_updates = should_spawn_background_update_check(args);
     let auto_update = should_auto_install_update(args);
@@ -142,7 +216,7 @@ fn spawn_background_update_check(args: &Args) {
         return;
     }
 
-    if update::is_release_build() {
+    if update::uses_release_updates() {
         std::thread::spawn(move || match update::check_and_maybe_update(auto_update) {
             update::UpdateCheckResult::UpdateAvailable {
                 current, latest, ..
@@ -214,7 +288,7 @@ fn should_spawn_background_update_check(args: &Args) -> bool {
         && !args.no_update
         && !matches!(
             args.command,
-            Some(Command::Update) | Some(Command::Serve { .. }) | Some(Command::Acp)
+            Some(Command::Update { .. }) | Some(Command::Serve { .. }) | Some(Command::Acp)
         )
         && args.resume.is_none()
 }
@@ -267,7 +341,10 @@ mod tests {
     #[test]
     fn update_command_still_skips_background_check_before_auto_install_logic() {
         let args = parse_args(&["jcode", "update"]);
-        assert!(matches!(args.command, Some(Command::Update)));
+        assert!(matches!(
+            args.command,
+            Some(Command::Update { channel: None })
+        ));
         assert!(!should_spawn_background_update_check(&args));
         assert!(should_auto_install_update(&args));
     }
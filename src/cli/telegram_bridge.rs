@@ -0,0 +1,1012 @@
+//! `jcode telegram`: drive server sessions from a Telegram bot.
+//!
+//! Messages from allowed users are sent to a session on the server (the
+//! configured `[telegram] session`, or one per chat, remembered across
+//! restarts). Replies stream back by editing the bot's message, with tool
+//! activity summarized underneath. Permission requests raised by a linked
+//! session arrive from the shared permission inbox as Approve/Deny buttons,
+//! and are closed when answered elsewhere. `/stop` detaches the chat.
+
+use super::dispatch;
+use super::provider_init::ProviderChoice;
+use crate::protocol::{Request, ServerEvent};
+use crate::safety::{self, PermissionRequest};
+use crate::telegram::{self, TelegramApiError, TelegramMessage};
+use crate::transport::{ReadHalf, WriteHalf};
+use crate::{logging, storage};
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::{Mutex, mpsc};
+
+const POLL_TIMEOUT_SECS: u64 = 30;
+const POLL_ERROR_BACKOFF: Duration = Duration::from_secs(10);
+const PERMISSION_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const MAX_SEND_ATTEMPTS: u32 = 3;
+/// Tool status lines kept under a reply; older ones collapse into a count.
+const MAX_TOOL_LINES: usize = 6;
+const TOOL_DETAIL_MAX_BYTES: usize = 60;
+const APPROVE_PREFIX: &str = "perm:y:";
+const DENY_PREFIX: &str = "perm:n:";
+const CLIENT_INSTANCE_ID: &str = "telegram";
+
+/// Bot API calls, retried when Telegram asks us to slow down.
+struct Bot {
+    client: reqwest::Client,
+    token: String,
+}
+
+impl Bot {
+    async fn send(
+        &self,
+        chat_id: i64,
+        text: &str,
+        markup: Option<serde_json::Value>,
+    ) -> Result<i64> {
+        with_rate_limit_retry(|| {
+            telegram::send_text(&self.client, &self.token, chat_id, text, markup.clone())
+        })
+        .await
+    }
+
+    async fn edit(
+        &self,
+        chat_id: i64,
+        message_id: i64,
+        text: &str,
+        markup: Option<serde_json::Value>,
+    ) -> Result<()> {
+        with_rate_limit_retry(|| {
+            telegram::edit_message_text(
+                &self.client,
+                &self.token,
+                chat_id,
+                message_id,
+                text,
+                markup.clone(),
+            )
+        })
+        .await
+    }
+
+    /// Best-effort notice; failures are only logged.
+    async fn notify(&self, chat_id: i64, text: &str) {
+        if let Err(err) = self.send(chat_id, text, None).await {
+            logging::warn(&format!(
+                "telegram bridge send failed chat_id={}: {}",
+                chat_id, err
+            ));
+        }
+    }
+}
+
+async fn with_rate_limit_retry<T, F, Fut>(mut call: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut attempt = 1;
+    loop {
+        match call().await {
+            Err(err) if attempt < MAX_SEND_ATTEMPTS => {
+                let Some(retry_after) = err
+                    .downcast_ref::<TelegramApiError>()
+                    .and_then(|err| err.retry_after)
+                else {
+                    return Err(err);
+                };
+                logging::info(&format!(
+                    "telegram rate limited; retrying in {}s",
+                    retry_after
+                ));
+                tokio::time::sleep(Duration::from_secs(retry_after)).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Chat → session links, persisted so per-chat sessions survive restarts.
+type Links = Arc<Mutex<BTreeMap<i64, String>>>;
+
+fn links_path() -> Result<PathBuf> {
+    Ok(storage::jcode_dir()?.join("telegram").join("chats.json"))
+}
+
+fn load_links() -> BTreeMap<i64, String> {
+    links_path()
+        .ok()
+        .filter(|path| path.exists())
+        .and_then(|path| storage::read_json(&path).ok())
+        .unwrap_or_default()
+}
+
+fn save_links(links: &BTreeMap<i64, String>) {
+    if let Err(err) = links_path().and_then(|path| storage::write_json(&path, links)) {
+        logging::warn(&format!("failed to save telegram chat links: {}", err));
+    }
+}
+
+struct ChatTask {
+    prompts: mpsc::UnboundedSender<String>,
+    busy: Arc<AtomicBool>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+struct Bridge {
+    bot: Arc<Bot>,
+    allowed_user_ids: HashSet<i64>,
+    designated_session: Option<String>,
+    edit_interval: Duration,
+    working_dir: String,
+    links: Links,
+    chats: HashMap<i64, ChatTask>,
+}
+
+impl Bridge {
+    async fn run(mut self) -> Result<()> {
+        tokio::spawn(permission_loop(
+            Arc::clone(&self.bot),
+            Arc::clone(&self.links),
+        ));
+
+        let mut offset: Option<i64> = None;
+        loop {
+            let updates = match telegram::get_updates(
+                &self.bot.client,
+                &self.bot.token,
+                offset,
+                POLL_TIMEOUT_SECS,
+                &["message", "callback_query"],
+            )
+            .await
+            {
+                Ok(updates) => updates,
+                Err(err) => {
+                    logging::error(&format!("Telegram bridge poll error: {}", err));
+                    tokio::time::sleep(POLL_ERROR_BACKOFF).await;
+                    continue;
+                }
+            };
+            for update in updates {
+                offset = Some(update.update_id + 1);
+                if let Some(message) = update.message {
+                    self.handle_message(message).await;
+                } else if let Some(query) = update.callback_query {
+                    self.handle_callback(query).await;
+                }
+            }
+        }
+    }
+
+    fn is_allowed(&self, user_id: Option<i64>) -> bool {
+        user_id.is_some_and(|id| self.allowed_user_ids.contains(&id))
+    }
+
+    async fn handle_message(&mut self, message: TelegramMessage) {
+        let chat_id = message.chat.id;
+        let user_id = message.from.as_ref().map(|user| user.id);
+        if !self.is_allowed(user_id) {
+            logging::warn(&format!(
+                "telegram bridge ignored message from unauthorized user_id={} chat_id={}",
+                user_id.map_or_else(|| "unknown".to_string(), |id| id.to_string()),
+                chat_id
+            ));
+            return;
+        }
+        let Some(text) = message.text.as_deref().map(str::trim) else {
+            self.bot
+                .notify(chat_id, "Only text messages are supported.")
+                .await;
+            return;
+        };
+        if text.is_empty() {
+            return;
+        }
+
+        match bot_command(text) {
+            Some("stop") => self.detach(chat_id).await,
+            Some("start") | Some("help") => {
+                self.bot
+                    .notify(
+                        chat_id,
+                        "Send a message to talk to jcode. Replies stream here, and permission \
+                         requests show up as buttons. /stop detaches this chat from its session.",
+                    )
+                    .await;
+            }
+            _ => self.route_prompt(chat_id, text.to_string()).await,
+        }
+    }
+
+    async fn route_prompt(&mut self, chat_id: i64, text: String) {
+        if self
+            .chats
+            .get(&chat_id)
+            .is_some_and(|chat| chat.task.is_finished())
+        {
+            self.chats.remove(&chat_id);
+        }
+        if !self.chats.contains_key(&chat_id) {
+            let target = match &self.designated_session {
+                Some(session) => Some(session.clone()),
+                None => self.links.lock().await.get(&chat_id).cloned(),
+            };
+            let (prompts, rx) = mpsc::unbounded_channel();
+            let busy = Arc::new(AtomicBool::new(false));
+            let task = tokio::spawn(run_chat(ChatContext {
+                bot: Arc::clone(&self.bot),
+                chat_id,
+                target,
+                working_dir: self.working_dir.clone(),
+                edit_interval: self.edit_interval,
+                links: Arc::clone(&self.links),
+                busy: Arc::clone(&busy),
+                prompts: rx,
+            }));
+            self.chats.insert(
+                chat_id,
+                ChatTask {
+                    prompts,
+                    busy,
+                    task,
+                },
+            );
+        }
+
+        let chat = &self.chats[&chat_id];
+        if chat.busy.load(Ordering::SeqCst) {
+            self.bot
+                .notify(chat_id, "⏳ Queued until the current reply finishes.")
+                .await;
+        }
+        let _ = chat.prompts.send(text);
+    }
+
+    async fn detach(&mut self, chat_id: i64) {
+        if let Some(chat) = self.chats.remove(&chat_id) {
+            chat.task.abort();
+        }
+        let removed = {
+            let mut links = self.links.lock().await;
+            let removed = links.remove(&chat_id);
+            if removed.is_some() {
+                save_links(&links);
+            }
+            removed
+        };
+        let reply = match removed {
+            Some(session_id) => format!(
+                "Detached from session {}. Send a message to start again.",
+                session_id
+            ),
+            None => "This chat is not attached to a session.".to_string(),
+        };
+        self.bot.notify(chat_id, &reply).await;
+    }
+
+    async fn handle_callback(&mut self, query: telegram::CallbackQuery) {
+        if !self.is_allowed(Some(query.from.id)) {
+            logging::warn(&format!(
+                "telegram bridge ignored button press from unauthorized user_id={}",
+                query.from.id
+            ));
+            return;
+        }
+        let Some((approved, request_id)) =
+            query.data.as_deref().and_then(parse_permission_callback)
+        else {
+            return;
+        };
+
+        let outcome = if approved { "Approved" } else { "Denied" };
+        let toast = match safety::record_decision(request_id, approved, "telegram_bot", None) {
+            Ok(()) => {
+                logging::info(&format!(
+                    "Permission {} via Telegram bot: {}",
+                    outcome.to_lowercase(),
+                    request_id
+                ));
+                outcome.to_string()
+            }
+            Err(err) => {
+                logging::error(&format!(
+                    "Failed to record permission from Telegram bot for {}: {}",
+                    request_id, err
+                ));
+                format!("Could not record decision: {}", err)
+            }
+        };
+        let _ = telegram::answer_callback_query(
+            &self.bot.client,
+            &self.bot.token,
+            &query.id,
+            Some(&toast),
+        )
+        .await;
+
+        if let Some(message) = query.message {
+            let text = format!(
+                "{}\n\n{} {}",
+                message.text.unwrap_or_default(),
+                if approved { "✅" } else { "❌" },
+                toast
+            );
+            let _ = self
+                .bot
+                .edit(message.chat.id, message.message_id, &text, None)
+                .await;
+        }
+    }
+}
+
+/// `/stop` or `/stop@my_bot` → `stop`.
+fn bot_command(text: &str) -> Option<&str> {
+    let command = text.strip_prefix('/')?.split_whitespace().next()?;
+    Some(command.split('@').next().unwrap_or(command))
+}
+
+fn parse_permission_callback(data: &str) -> Option<(bool, &str)> {
+    if let Some(id) = data.strip_prefix(APPROVE_PREFIX) {
+        Some((true, id))
+    } else {
+        data.strip_prefix(DENY_PREFIX).map(|id| (false, id))
+    }
+}
+
+/// Offer Approve/Deny buttons for inbox entries raised by linked sessions,
+/// and close a prompt once its entry leaves the inbox because it was answered
+/// on another surface or expired.
+async fn permission_loop(bot: Arc<Bot>, links: Links) {
+    // Request id -> (chat id, prompt message id, prompt text).
+    let mut prompted: HashMap<String, (i64, i64, String)> = HashMap::new();
+    loop {
+        tokio::time::sleep(PERMISSION_POLL_INTERVAL).await;
+        let inbox = tokio::task::spawn_blocking(safety::inbox)
+            .await
+            .unwrap_or_default();
+
+        let resolved: Vec<String> = prompted
+            .keys()
+            .filter(|id| !inbox.iter().any(|request| &request.id == *id))
+            .cloned()
+            .collect();
+        for request_id in resolved {
+            let Some((chat_id, message_id, text)) = prompted.remove(&request_id) else {
+                continue;
+            };
+            let note = match safety::decision_for(&request_id) {
+                // The button handler already updated the message.
+                Some(decision) if decision.decided_via == "telegram_bot" => continue,
+                Some(decision) if decision.approved => {
+                    format!("✅ Approved via {}", decision.decided_via)
+                }
+                Some(decision) => format!("❌ Denied via {}", decision.decided_via),
+                None => "Resolved elsewhere".to_string(),
+            };
+            let _ = bot
+                .edit(chat_id, message_id, &format!("{}\n\n{}", text, note), None)
+                .await;
+        }
+
+        let linked = links.lock().await.clone();
+        if linked.is_empty() {
+            continue;
+        }
+        let now_ms = chrono::Utc::now().timestamp_millis().max(0) as u64;
+        for request in inbox {
+            if prompted.contains_key(&request.id) {
+                continue;
+            }
+            let Some(chat_id) = safety::request_session_id(&request).and_then(|session_id| {
+                linked
+                    .iter()
+                    .find(|(_, linked_session)| **linked_session == session_id)
+                    .map(|(chat_id, _)| *chat_id)
+            }) else {
+                continue;
+            };
+            let text = permission_prompt(&request, now_ms);
+            let markup = telegram::inline_keyboard(&[
+                ("✅ Approve", format!("{}{}", APPROVE_PREFIX, request.id)),
+                ("❌ Deny", format!("{}{}", DENY_PREFIX, request.id)),
+            ]);
+            match bot.send(chat_id, &text, Some(markup)).await {
+                Ok(message_id) => {
+                    prompted.insert(request.id.clone(), (chat_id, message_id, text));
+                }
+                Err(err) => logging::warn(&format!(
+                    "failed to send telegram permission prompt {}: {}",
+                    request.id, err
+                )),
+            }
+        }
+    }
+}
+
+fn permission_prompt(request: &PermissionRequest, now_ms: u64) -> String {
+    let mut text = format!(
+        "🔐 Permission needed: {}\n{}\n{}",
+        request.action,
+        request.description,
+        request.to_inbox_entry().status_line(now_ms)
+    );
+    if !request.rationale.trim().is_empty() {
+        text.push_str(&format!("\n\nWhy: {}", request.rationale.trim()));
+    }
+    text
+}
+
+struct ChatContext {
+    bot: Arc<Bot>,
+    chat_id: i64,
+    target: Option<String>,
+    working_dir: String,
+    edit_interval: Duration,
+    links: Links,
+    busy: Arc<AtomicBool>,
+    prompts: mpsc::UnboundedReceiver<String>,
+}
+
+async fn run_chat(mut ctx: ChatContext) {
+    let mut conn = match DaemonConnection::open(ctx.target.as_deref(), &ctx.working_dir).await {
+        Ok(conn) => conn,
+        Err(err) => {
+            logging::error(&format!(
+                "telegram bridge could not attach chat_id={}: {}",
+                ctx.chat_id, err
+            ));
+            // Forget a remembered session that can no longer be resumed, so
+            // the next message starts a fresh one.
+            {
+                let mut links = ctx.links.lock().await;
+                if ctx.target.is_some() && links.get(&ctx.chat_id) == ctx.target.as_ref() {
+                    links.remove(&ctx.chat_id);
+                    save_links(&links);
+                }
+            }
+            ctx.bot
+                .notify(
+                    ctx.chat_id,
+                    &format!("⚠️ Could not attach to a jcode session: {}", err),
+                )
+                .await;
+            return;
+        }
+    };
+    {
+        let mut links = ctx.links.lock().await;
+        if links.insert(ctx.chat_id, conn.session_id.clone()).as_ref() != Some(&conn.session_id) {
+            save_links(&links);
+        }
+    }
+    if ctx.target.as_deref() != Some(conn.session_id.as_str()) {
+        ctx.bot
+            .notify(
+                ctx.chat_id,
+                &format!("🔗 Started session {}", conn.session_id),
+            )
+            .await;
+    }
+
+    while let Some(prompt) = ctx.prompts.recv().await {
+        ctx.busy.store(true, Ordering::SeqCst);
+        let result = conn
+            .run_prompt(&ctx.bot, ctx.chat_id, &prompt, ctx.edit_interval)
+            .await;
+        ctx.busy.store(false, Ordering::SeqCst);
+        if let Err(err) = result {
+            logging::error(&format!(
+                "telegram bridge prompt failed chat_id={}: {}",
+                ctx.chat_id, err
+            ));
+            ctx.bot.notify(ctx.chat_id, &format!("⚠️ {}", err)).await;
+            break;
+        }
+    }
+}
+
+/// One client connection to the server, attached to a single session.
+struct DaemonConnection {
+    session_id: String,
+    writer: WriteHalf,
+    events: mpsc::UnboundedReceiver<Result<ServerEvent>>,
+    next_id: u64,
+}
+
+impl DaemonConnection {
+    async fn open(target: Option<&str>, working_dir: &str) -> Result<Self> {
+        let stream = crate::server::connect_socket(&crate::server::socket_path()).await?;
+        let (reader, writer) = stream.into_split();
+        let mut conn = Self {
+            session_id: target.unwrap_or_default().to_string(),
+            writer,
+            events: spawn_event_reader(reader),
+            next_id: 1,
+        };
+
+        let id = conn.next_id();
+        match target {
+            Some(session_id) => {
+                conn.send(&Request::ResumeSession {
+                    id,
+                    session_id: session_id.to_string(),
+                    client_instance_id: Some(CLIENT_INSTANCE_ID.to_string()),
+                    client_has_local_history: false,
+                    allow_session_takeover: false,
+                })
+                .await?;
+            }
+            None => {
+                conn.send(&Request::Subscribe {
+                    id,
+                    working_dir: Some(working_dir.to_string()),
+                    selfdev: None,
+                    target_session_id: None,
+                    client_instance_id: Some(CLIENT_INSTANCE_ID.to_string()),
+                    client_has_local_history: false,
+                    allow_session_takeover: false,
+                    terminal_env: crate::terminal_launch::snapshot_client_terminal_env(),
+                })
+                .await?;
+            }
+        }
+        loop {
+            match conn.next_event().await? {
+                ServerEvent::History { session_id, .. } => conn.session_id = session_id,
+                ServerEvent::SessionId { session_id } => conn.session_id = session_id,
+                ServerEvent::Done { id: done } if done == id => break,
+                ServerEvent::Error {
+                    id: failed,
+                    message,
+                    ..
+                } if failed == id => anyhow::bail!(message),
+                _ => {}
+            }
+        }
+
+        if conn.session_id.is_empty() {
+            let id = conn.next_id();
+            conn.send(&Request::GetHistory { id }).await?;
+            loop {
+                match conn.next_event().await? {
+                    ServerEvent::History {
+                        id: event_id,
+                        session_id,
+                        ..
+                    } if event_id == id => {
+                        conn.session_id = session_id;
+                        break;
+                    }
+                    ServerEvent::Error {
+                        id: failed,
+                        message,
+                        ..
+                    } if failed == id => anyhow::bail!(message),
+                    _ => {}
+                }
+            }
+        }
+        Ok(conn)
+    }
+
+    fn next_id(&mut self) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    async fn send(&mut self, request: &Request) -> Result<()> {
+        let json = serde_json::to_string(request)? + "\n";
+        self.writer.write_all(json.as_bytes()).await?;
+        self.writer.flush().await?;
+        Ok(())
+    }
+
+    async fn next_event(&mut self) -> Result<ServerEvent> {
+        self.events
+            .recv()
+            .await
+            .context("jcode server disconnected")?
+    }
+
+    /// Send one prompt and stream the reply into Telegram until the turn ends.
+    async fn run_prompt(
+        &mut self,
+        bot: &Bot,
+        chat_id: i64,
+        prompt: &str,
+        edit_interval: Duration,
+    ) -> Result<()> {
+        let id = self.next_id();
+        self.send(&Request::Message {
+            id,
+            content: prompt.to_string(),
+            images: vec![],
+            system_reminder: None,
+            focus_secs: None,
+        })
+        .await?;
+
+        let mut view = ReplyView::default();
+        let mut messages = ReplyMessages::default();
+        messages.sync(bot, chat_id, &view.render()).await?;
+        let mut last_sync = Instant::now();
+        let mut dirty = false;
+        loop {
+            let event = match tokio::time::timeout(edit_interval, self.events.recv()).await {
+                Ok(Some(event)) => event?,
+                Ok(None) => anyhow::bail!("jcode server disconnected"),
+                Err(_) => {
+                    if dirty {
+                        messages.sync(bot, chat_id, &view.render()).await?;
+                        last_sync = Instant::now();
+                        dirty = false;
+                    }
+                    continue;
+                }
+            };
+            match event {
+                ServerEvent::Done { id: done } if done == id => break,
+                ServerEvent::Error {
+                    id: failed,
+                    message,
+                    ..
+                } if failed == id => {
+                    view.footer = Some(format!("⚠️ {}", message));
+                    break;
+                }
+                ServerEvent::Interrupted => {
+                    view.footer = Some("⏹ Interrupted".to_string());
+                    dirty = true;
+                }
+                event => dirty |= view.apply(event),
+            }
+            if dirty && last_sync.elapsed() >= edit_interval {
+                messages.sync(bot, chat_id, &view.render()).await?;
+                last_sync = Instant::now();
+                dirty = false;
+            }
+        }
+        messages.sync(bot, chat_id, &view.render()).await
+    }
+}
+
+/// Read server events on their own task, so waiting for one can time out
+/// without losing a partially read line.
+fn spawn_event_reader(reader: ReadHalf) -> mpsc::UnboundedReceiver<Result<ServerEvent>> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        let mut reader = BufReader::new(reader);
+        loop {
+            let mut line = String::new();
+            let event = match reader.read_line(&mut line).await {
+                Ok(0) => Err(anyhow::anyhow!("jcode server disconnected")),
+                Ok(_) => match serde_json::from_str::<ServerEvent>(&line) {
+                    Ok(event) => Ok(event),
+                    Err(err) => {
+                        logging::debug(&format!(
+                            "telegram bridge skipped undecodable server event: {}",
+                            err
+                        ));
+                        continue;
+                    }
+                },
+                Err(err) => Err(err.into()),
+            };
+            let closed = event.is_err();
+            if tx.send(event).is_err() || closed {
+                break;
+            }
+        }
+    });
+    rx
+}
+
+/// What a streaming reply currently shows: the assistant text, then compact
+/// tool status lines, then how the turn ended.
+#[derive(Default)]
+struct ReplyView {
+    text: String,
+    tools: Vec<ToolLine>,
+    current_tool: Option<usize>,
+    footer: Option<String>,
+}
+
+struct ToolLine {
+    id: String,
+    name: String,
+    input: String,
+    detail: Option<String>,
+    state: ToolState,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ToolState {
+    Running,
+    Done,
+    Failed,
+}
+
+impl ReplyView {
+    /// Fold one server event in; returns whether the rendering changed.
+    fn apply(&mut self, event: ServerEvent) -> bool {
+        match event {
+            ServerEvent::TextDelta { text } => {
+                self.text.push_str(&text);
+                !text.is_empty()
+            }
+            ServerEvent::TextReplace { text } => {
+                self.text = text;
+                true
+            }
+            ServerEvent::ToolStart { id, name } => {
+                self.tools.push(ToolLine {
+                    id,
+                    name,
+                    input: String::new(),
+                    detail: None,
+                    state: ToolState::Running,
+                });
+                self.current_tool = Some(self.tools.len() - 1);
+                true
+            }
+            ServerEvent::ToolInput { delta } => {
+                if let Some(tool) = self.current_tool.and_then(|idx| self.tools.get_mut(idx)) {
+                    tool.input.push_str(&delta);
+                }
+                false
+            }
+            ServerEvent::ToolExec { id, .. } => {
+                let Some(tool) = self.tools.iter_mut().find(|tool| tool.id == id) else {
+                    return false;
+                };
+                tool.detail = tool_detail(&tool.input);
+                tool.detail.is_some()
+            }
+            ServerEvent::ToolDone { id, error, .. } => {
+                let Some(tool) = self.tools.iter_mut().find(|tool| tool.id == id) else {
+                    return false;
+                };
+                if tool.detail.is_none() {
+                    tool.detail = tool_detail(&tool.input);
+                }
+                tool.state = if error.is_some() {
+                    ToolState::Failed
+                } else {
+                    ToolState::Done
+                };
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn render(&self) -> String {
+        let mut out = self.text.trim().to_string();
+        if out.is_empty() && self.footer.is_none() {
+            out.push('…');
+        }
+
+        let hidden = self.tools.len().saturating_sub(MAX_TOOL_LINES);
+        let mut lines = Vec::new();
+        if hidden > 0 {
+            lines.push(format!("… {} earlier tool call(s)", hidden));
+        }
+        for tool in &self.tools[hidden..] {
+            let icon = match tool.state {
+                ToolState::Running => "⏳",
+                ToolState::Done => "✓",
+                ToolState::Failed => "✗",
+            };
+            lines.push(match &tool.detail {
+                Some(detail) => format!("{} {}: {}", icon, tool.name, detail),
+                None => format!("{} {}", icon, tool.name),
+            });
+        }
+        if let Some(footer) = &self.footer {
+            lines.push(footer.clone());
+        }
+
+        if !lines.is_empty() {
+            if !out.is_empty() {
+                out.push_str("\n\n");
+            }
+            out.push_str(&lines.join("\n"));
+        }
+        out
+    }
+}
+
+/// The most telling argument of a tool call, shortened for a status line.
+fn tool_detail(input: &str) -> Option<String> {
+    let input: serde_json::Value = serde_json::from_str(input).ok()?;
+    let value = ["command", "file_path", "path", "pattern", "query", "url"]
+        .iter()
+        .find_map(|key| input.get(key).and_then(|value| value.as_str()))?;
+    let line = value.lines().next().unwrap_or_default().trim();
+    let short = crate::util::truncate_str(line, TOOL_DETAIL_MAX_BYTES);
+    Some(if short.len() < value.trim().len() {
+        format!("{}…", short)
+    } else {
+        short.to_string()
+    })
+}
+
+/// The Telegram messages a reply occupies; text past the length limit
+/// continues in follow-up messages.
+#[derive(Default)]
+struct ReplyMessages {
+    sent: Vec<(i64, String)>,
+}
+
+impl ReplyMessages {
+    async fn sync(&mut self, bot: &Bot, chat_id: i64, rendered: &str) -> Result<()> {
+        for (idx, piece) in telegram::split_message(rendered, telegram::MAX_MESSAGE_LEN)
+            .into_iter()
+            .enumerate()
+        {
+            match self.sent.get_mut(idx) {
+                Some((_, shown)) if *shown == piece => {}
+                Some((message_id, shown)) => {
+                    bot.edit(chat_id, *message_id, &piece, None).await?;
+                    *shown = piece;
+                }
+                None => {
+                    let message_id = bot.send(chat_id, &piece, None).await?;
+                    self.sent.push((message_id, piece));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+pub(crate) async fn run_telegram_command(
+    provider_choice: ProviderChoice,
+    model: Option<String>,
+    provider_profile: Option<String>,
+) -> Result<()> {
+    let config = crate::config::config();
+    let token = config
+        .telegram
+        .bot_token
+        .clone()
+        .or_else(|| config.safety.telegram_bot_token.clone())
+        .filter(|token| !token.trim().is_empty())
+        .context(
+            "no Telegram bot token: set `bot_token` under [telegram] in ~/.jcode/config.toml \
+             or JCODE_TELEGRAM_BOT_TOKEN",
+        )?;
+    anyhow::ensure!(
+        !config.telegram.allowed_user_ids.is_empty(),
+        "set `allowed_user_ids` under [telegram] in ~/.jcode/config.toml (or \
+         JCODE_TELEGRAM_ALLOWED_USER_IDS); the bot ignores everyone else"
+    );
+
+    if !dispatch::server_is_running().await {
+        dispatch::spawn_server(
+            &provider_choice,
+            model.as_deref(),
+            provider_profile.as_deref(),
+        )
+        .await?;
+    }
+
+    let working_dir = std::env::current_dir()?.display().to_string();
+    eprintln!(
+        "Telegram bridge running for {} allowed user(s); new sessions start in {}. Ctrl+C to stop.",
+        config.telegram.allowed_user_ids.len(),
+        working_dir
+    );
+    Bridge {
+        bot: Arc::new(Bot {
+            client: crate::provider::shared_http_client(),
+            token,
+        }),
+        allowed_user_ids: config.telegram.allowed_user_ids.iter().copied().collect(),
+        designated_session: config
+            .telegram
+            .session
+            .clone()
+            .filter(|session| !session.trim().is_empty()),
+        edit_interval: Duration::from_millis(config.telegram.edit_interval_ms.max(500)),
+        working_dir,
+        links: Arc::new(Mutex::new(load_links())),
+        chats: HashMap::new(),
+    }
+    .run()
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool_call(view: &mut ReplyView, id: &str, name: &str, input: &str, error: bool) {
+        view.apply(ServerEvent::ToolStart {
+            id: id.to_string(),
+            name: name.to_string(),
+        });
+        view.apply(ServerEvent::ToolInput {
+            delta: input.to_string(),
+        });
+        view.apply(ServerEvent::ToolExec {
+            id: id.to_string(),
+            name: name.to_string(),
+        });
+        view.apply(ServerEvent::ToolDone {
+            id: id.to_string(),
+            name: name.to_string(),
+            output: String::new(),
+            error: error.then(|| "exit 1".to_string()),
+        });
+    }
+
+    #[test]
+    fn reply_view_streams_text_then_tool_status_lines() {
+        let mut view = ReplyView::default();
+        assert_eq!(view.render(), "…");
+
+        view.apply(ServerEvent::TextDelta {
+            text: "Running the ".to_string(),
+        });
+        view.apply(ServerEvent::TextDelta {
+            text: "tests.".to_string(),
+        });
+        tool_call(
+            &mut view,
+            "t1",
+            "bash",
+            r#"{"command":"cargo test\necho done"}"#,
+            true,
+        );
+        tool_call(
+            &mut view,
+            "t2",
+            "read",
+            r#"{"file_path":"src/lib.rs"}"#,
+            false,
+        );
+        view.footer = Some("⏹ Interrupted".to_string());
+
+        assert_eq!(
+            view.render(),
+            "Running the tests.\n\n✗ bash: cargo test…\n✓ read: src/lib.rs\n⏹ Interrupted"
+        );
+    }
+
+    #[test]
+    fn reply_view_collapses_old_tool_lines() {
+        let mut view = ReplyView::default();
+        for idx in 0..MAX_TOOL_LINES + 2 {
+            tool_call(&mut view, &format!("t{idx}"), "grep", "{}", false);
+        }
+        let rendered = view.render();
+        assert!(rendered.starts_with("…\n\n… 2 earlier tool call(s)\n"));
+        assert_eq!(rendered.matches("✓ grep").count(), MAX_TOOL_LINES);
+    }
+
+    #[test]
+    fn commands_and_permission_buttons_parse() {
+        assert_eq!(bot_command("/stop"), Some("stop"));
+        assert_eq!(bot_command("/stop@jcode_bot now"), Some("stop"));
+        assert_eq!(bot_command("stop"), None);
+
+        assert_eq!(
+            parse_permission_callback("perm:y:req_1"),
+            Some((true, "req_1"))
+        );
+        assert_eq!(
+            parse_permission_callback("perm:n:req_1"),
+            Some((false, "req_1"))
+        );
+        assert_eq!(parse_permission_callback("other"), None);
+    }
+}
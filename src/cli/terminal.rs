@@ -1,8 +1,9 @@
 use anyhow::Result;
 use std::io::{self, IsTerminal, Write};
 use std::panic;
+use std::path::{Path, PathBuf};
 
-use crate::{id, session, telemetry, tui};
+use crate::{crash_report, id, session, telemetry, tui};
 
 pub struct TuiRuntimeState {
     mouse_capture: bool,
@@ -83,33 +84,138 @@ pub fn install_panic_hook() {
     panic::set_hook(Box::new(move |info| {
         default_hook(info);
 
+        let crash_report = crash_report::write_bundle_best_effort(&crash_report::CrashContext {
+            reason: "Panic".to_string(),
+            panic: Some(format!(
+                "{}\n\nBacktrace:\n{}",
+                info,
+                std::backtrace::Backtrace::force_capture()
+            )),
+            session_id: get_current_session(),
+        });
+
         if let Some(session_id) = get_current_session() {
-            print_session_resume_hint(&session_id);
+            print_session_resume_hint(&session_id, crash_report.as_deref());
 
             if let Some((provider, model)) = telemetry::current_provider_model() {
                 telemetry::record_crash(&provider, &model, telemetry::SessionEndReason::Panic);
             }
 
+            record_canary_crash(Some(session_id.clone()), None, Some(info.to_string()));
+
             if let Ok(mut session) = session::Session::load(&session_id) {
                 session.mark_crashed(Some(format!("Panic: {}", info)));
                 let _ = session.save();
             }
+        } else {
+            record_canary_crash(None, None, Some(info.to_string()));
+            if let Some(path) = &crash_report {
+                eprintln!("\x1b[33mCrash report:\x1b[0m {}", path.display());
+            }
         }
     }));
 }
 
-pub fn mark_current_session_crashed(message: String) {
-    if let Some(session_id) = get_current_session() {
-        if let Some((provider, model)) = telemetry::current_provider_model() {
-            telemetry::record_crash(&provider, &model, telemetry::SessionEndReason::Signal);
-        }
-        if let Ok(mut session) = session::Session::load(&session_id)
-            && matches!(session.status, session::SessionStatus::Active)
-        {
-            session.mark_crashed(Some(message));
-            let _ = session.save();
-        }
+/// Append a crash to the canary event log when this process runs the canary.
+fn record_canary_crash(
+    session_id: Option<String>,
+    signal: Option<String>,
+    panic_message: Option<String>,
+) {
+    let _ = crate::build::record_canary_event_for_running_build(crate::build::CanaryEvent {
+        session_id,
+        signal,
+        panic_message,
+        uptime_secs: crate::startup_profile::uptime().map(|uptime| uptime.as_secs()),
+        ..crate::build::CanaryEvent::new(crate::build::CanaryEventKind::Crash, "")
+    });
+}
+
+/// Wait on a server this process spawned and record a canary crash when it
+/// dies on a signal it cannot report itself (SIGSEGV, SIGABRT, an OOM kill).
+/// The crash is attributed to the shared-server channel's build, which a
+/// reloading server execs into. Only covers exits while this process runs.
+#[cfg(unix)]
+pub fn watch_spawned_server(mut child: std::process::Child) {
+    let started = std::time::Instant::now();
+    let _ = std::thread::Builder::new()
+        .name("jcode-server-watch".to_string())
+        .spawn(move || {
+            use std::os::unix::process::ExitStatusExt;
+
+            let Ok(status) = child.wait() else {
+                return;
+            };
+            let Some(sig) = status.signal().filter(|sig| is_crash_signal(*sig)) else {
+                return;
+            };
+            crate::logging::warn(&format!(
+                "Spawned server {} died on {}",
+                child.id(),
+                signal_name(sig)
+            ));
+            if let Ok(Some(version)) = crate::build::read_shared_server_version() {
+                let _ = crate::build::record_canary_event_for_version(
+                    &version,
+                    crate::build::CanaryEvent {
+                        signal: Some(signal_name(sig).to_string()),
+                        uptime_secs: Some(started.elapsed().as_secs()),
+                        ..crate::build::CanaryEvent::new(crate::build::CanaryEventKind::Crash, "")
+                    },
+                );
+            }
+        });
+}
+
+/// Signals that mean the process crashed rather than being asked to stop.
+/// SIGKILL is included because that is how the OOM killer ends a process.
+#[cfg(unix)]
+fn is_crash_signal(sig: i32) -> bool {
+    matches!(
+        sig,
+        libc::SIGSEGV
+            | libc::SIGBUS
+            | libc::SIGILL
+            | libc::SIGFPE
+            | libc::SIGABRT
+            | libc::SIGQUIT
+            | libc::SIGKILL
+    )
+}
+
+/// Record a clean exit of the current session for the canary track record.
+/// Only called for interactive sessions, so one-shot commands do not count
+/// toward clean session-hours.
+pub fn record_canary_clean_exit() {
+    let Some(session_id) = get_current_session() else {
+        return;
+    };
+    let _ = crate::build::record_canary_event_for_running_build(crate::build::CanaryEvent {
+        session_id: Some(session_id),
+        uptime_secs: crate::startup_profile::uptime().map(|uptime| uptime.as_secs()),
+        ..crate::build::CanaryEvent::new(crate::build::CanaryEventKind::CleanExit, "")
+    });
+}
+
+/// Mark the current session crashed and write a crash report for it, returning
+/// the report's directory. Sessions that already ended are left alone.
+pub fn mark_current_session_crashed(message: String) -> Option<PathBuf> {
+    let session_id = get_current_session()?;
+    if let Some((provider, model)) = telemetry::current_provider_model() {
+        telemetry::record_crash(&provider, &model, telemetry::SessionEndReason::Signal);
+    }
+    if let Ok(mut session) = session::Session::load(&session_id)
+        && matches!(session.status, session::SessionStatus::Active)
+    {
+        session.mark_crashed(Some(message.clone()));
+        let _ = session.save();
+        return crash_report::write_bundle_best_effort(&crash_report::CrashContext {
+            reason: message,
+            panic: None,
+            session_id: Some(session_id),
+        });
     }
+    None
 }
 
 pub fn panic_payload_to_string(payload: &(dyn std::any::Any + Send)) -> String {
@@ -145,6 +251,12 @@ pub fn show_crash_resume_hint() {
         eprintln!("\x1b[33m   Resume with:\x1b[0m  jcode --resume {}", id);
         eprintln!("\x1b[33m   List all:\x1b[0m     jcode --resume");
     }
+    if let Some(path) = crash_report::latest_bundle() {
+        eprintln!(
+            "\x1b[33m   Crash report:\x1b[0m {}  (pack with: jcode debug crash-report --last)",
+            path.display()
+        );
+    }
     eprintln!();
 }
 
@@ -156,6 +268,8 @@ fn init_tui_terminal() -> Result<ratatui::DefaultTerminal> {
     if is_resuming {
         init_tui_terminal_resume()
     } else {
+        // A resumed process inherits the title saved by the one it replaced.
+        tui::terminal_title::save_original();
         std::panic::catch_unwind(std::panic::AssertUnwindSafe(ratatui::init)).map_err(|payload| {
             anyhow::anyhow!(
                 "failed to initialize terminal: {}",
@@ -211,6 +325,7 @@ fn cleanup_tui_runtime(state: &TuiRuntimeState, restore_terminal: bool) {
             tui::disable_keyboard_enhancement();
         }
         ratatui::restore();
+        tui::terminal_title::restore();
     }
 
     crate::tui::mermaid::clear_image_state();
@@ -228,11 +343,15 @@ fn cleanup_tui_runtime_for_run_result(
     cleanup_tui_runtime(state, !will_exec);
 }
 
-pub fn print_session_resume_hint(session_id: &str) {
-    let _ = write_session_resume_hint(io::stderr().lock(), session_id);
+pub fn print_session_resume_hint(session_id: &str, crash_report: Option<&Path>) {
+    let _ = write_session_resume_hint(io::stderr().lock(), session_id, crash_report);
 }
 
-fn write_session_resume_hint(mut writer: impl Write, session_id: &str) -> io::Result<()> {
+fn write_session_resume_hint(
+    mut writer: impl Write,
+    session_id: &str,
+    crash_report: Option<&Path>,
+) -> io::Result<()> {
     let session_name = id::extract_session_name(session_id).unwrap_or(session_id);
     writeln!(writer)?;
     writeln!(
@@ -241,6 +360,9 @@ fn write_session_resume_hint(mut writer: impl Write, session_id: &str) -> io::Re
         session_name
     )?;
     writeln!(writer, "  jcode --resume {}", session_id)?;
+    if let Some(path) = crash_report {
+        writeln!(writer, "\x1b[33mCrash report:\x1b[0m {}", path.display())?;
+    }
     writeln!(writer)?;
     Ok(())
 }
@@ -275,6 +397,8 @@ pub fn signal_name(sig: i32) -> &'static str {
         13 => "SIGPIPE",
         14 => "SIGALRM",
         15 => "SIGTERM",
+        libc::SIGBUS => "SIGBUS",
+        libc::SIGFPE => "SIGFPE",
         _ => "unknown",
     }
 }
@@ -297,7 +421,16 @@ fn signal_crash_reason(sig: i32) -> String {
 
 #[cfg(unix)]
 fn handle_termination_signal(sig: i32) -> ! {
-    mark_current_session_crashed(signal_crash_reason(sig));
+    let crash_report = mark_current_session_crashed(signal_crash_reason(sig));
+    // Hangups, interrupts and terminations are user-driven; only SIGQUIT (the
+    // "dump core" quit) counts against a canary build.
+    if sig == libc::SIGQUIT {
+        record_canary_crash(
+            get_current_session(),
+            Some(signal_name(sig).to_string()),
+            None,
+        );
+    }
 
     let _ = crossterm::terminal::disable_raw_mode();
     let _ = crossterm::execute!(
@@ -307,7 +440,7 @@ fn handle_termination_signal(sig: i32) -> ! {
     );
 
     if let Some(session_id) = get_current_session() {
-        print_session_resume_hint(&session_id);
+        print_session_resume_hint(&session_id, crash_report.as_deref());
     }
 
     std::process::exit(128 + sig);
@@ -408,7 +541,7 @@ mod tests {
 
         if let Some(session_id) = get_current_session() {
             let mut output = Vec::new();
-            write_session_resume_hint(&mut output, &session_id).unwrap();
+            write_session_resume_hint(&mut output, &session_id, None).unwrap();
             let output = String::from_utf8(output).unwrap();
             let expected_cmd = format!("jcode --resume {}", session_id);
             assert!(output.contains(&expected_cmd));
@@ -419,6 +552,18 @@ mod tests {
         }
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn only_fatal_signals_count_as_server_crashes() {
+        for sig in [libc::SIGSEGV, libc::SIGABRT, libc::SIGBUS, libc::SIGKILL] {
+            assert!(is_crash_signal(sig), "{}", signal_name(sig));
+        }
+        for sig in [libc::SIGTERM, libc::SIGINT, libc::SIGHUP] {
+            assert!(!is_crash_signal(sig), "{}", signal_name(sig));
+        }
+        assert_eq!(signal_name(libc::SIGBUS), "SIGBUS");
+    }
+
     #[test]
     fn session_resume_hint_writer_reports_closed_stderr_without_panicking() {
         struct ClosedWriter;
@@ -433,7 +578,7 @@ mod tests {
             }
         }
 
-        let error = write_session_resume_hint(ClosedWriter, "session_closed_pipe")
+        let error = write_session_resume_hint(ClosedWriter, "session_closed_pipe", None)
             .expect_err("closed stderr should be reported as an I/O error");
         assert_eq!(error.kind(), io::ErrorKind::BrokenPipe);
     }
@@ -86,6 +86,8 @@ pub async fn run_tui_client(
     startup_hints: Option<setup_hints::StartupHints>,
     server_spawning: bool,
     fresh_spawn: bool,
+    agent_profile: Option<String>,
+    safe_mode: bool,
 ) -> Result<()> {
     startup_profile::mark("tui_client_enter");
     let (terminal, tui_runtime) = init_tui_runtime()?;
@@ -136,6 +138,12 @@ pub async fn run_tui_client(
     {
         apply_startup_hints(&mut app, hints);
     }
+    if let Some(profile) = agent_profile {
+        app.set_startup_profile(profile);
+    }
+    if safe_mode {
+        app.set_startup_safe_mode();
+    }
 
     startup_profile::mark("pre_run_remote");
     startup_profile::report_to_log();
@@ -159,7 +167,7 @@ pub async fn run_tui_client(
     if !has_requested_action(&run_result)
         && let Some(ref session_id) = run_result.session_id
     {
-        print_session_resume_hint(session_id);
+        print_session_resume_hint(session_id, None);
     }
 
     Ok(())
@@ -412,6 +420,35 @@ pub async fn run_replay_command(
     Ok(())
 }
 
+/// `jcode view <session>`: render a saved session read-only without touching
+/// providers or the server. If the user asks to resume from the view, exec into
+/// a normal `--resume` once the terminal has been restored.
+pub async fn run_view_command(session_id_or_path: &str) -> Result<()> {
+    let session = replay::load_session(session_id_or_path)?;
+    let session_name = session
+        .short_name
+        .clone()
+        .unwrap_or_else(|| session.id.clone());
+    let icon = id::session_icon(&session_name);
+
+    let (terminal, tui_runtime) = init_tui_runtime()?;
+    let _ = crossterm::execute!(
+        std::io::stdout(),
+        crossterm::terminal::SetTitle(format!("{} view: {}", icon, session_name))
+    );
+
+    let app = tui::App::new_for_transcript_view(session);
+    let result = app.run_transcript_view(terminal).await;
+
+    tui_runtime.finish(true);
+
+    let run_result = result?;
+    if has_requested_action(&run_result) {
+        execute_requested_action(&run_result)?;
+    }
+    Ok(())
+}
+
 // Session-launching helpers live in the core `session_launch` module so that
 // lower layers (server, restart_snapshot, tool) can relaunch sessions without
 // depending on `cli`. Re-exported here for the CLI's own callers.
@@ -15,17 +15,46 @@
 //! via `pub use jcode_tui::*`, so existing `crate::<module>` paths (e.g.
 //! `crate::config`, `crate::server`, `crate::tui`) keep resolving unchanged
 //! across the cli code that was not moved.
+//!
+//! # Library API
+//!
+//! Everything is reachable through the module tree, but only the items
+//! re-exported at the crate root are meant for embedders and kept stable:
+//!
+//! - [`Client`] connects to a running jcode server over its socket.
+//! - [`AgentBuilder`] builds an in-process [`Agent`] from a provider, tools,
+//!   a system prompt and limits.
+//! - [`Provider`] and [`Tool`] are the extension points for custom model
+//!   backends and custom tools.
+//!
+//! Protocol enums ([`protocol::Request`], [`protocol::ServerEvent`]) are
+//! `#[non_exhaustive]`, so matches on them need a wildcard arm. See
+//! `examples/embed.rs` for a complete agent with a custom provider and tool.
+//!
+//! The terminal UI, the cli and the `jcode` binary sit behind the default
+//! `tui` feature. With `default-features = false` the crate re-exports the
+//! application core alone, which carries the whole API above.
 
 // Re-export the presentation layer (and, transitively, the application core)
 // so `crate::tui`, `crate::video_export`, and `crate::<app-core module>` paths
 // resolve.
+#[cfg(feature = "tui")]
 pub use jcode_tui::*;
+#[cfg(not(feature = "tui"))]
+pub use jcode_app_core::*;
+
+// Intentional library surface for embedders (see the crate docs).
+pub use jcode_app_core::agent::{Agent, AgentBuilder};
+pub use jcode_app_core::message::{Message, StreamEvent, ToolDefinition};
+pub use jcode_app_core::provider::{EventStream, Provider};
+pub use jcode_app_core::server::Client;
+pub use jcode_app_core::tool::{Registry, Tool, ToolContext, ToolOutput};
 
 // Cli + entrypoint layer (kept in the root crate).
+#[cfg(feature = "tui")]
 pub mod cli;
 
-use anyhow::Result;
-
-pub async fn run() -> Result<()> {
+#[cfg(feature = "tui")]
+pub async fn run() -> anyhow::Result<()> {
     cli::startup::run().await
 }
@@ -11,6 +11,7 @@ mod binary_integration;
 mod burst_spawn;
 mod provider_behavior;
 mod reload_multiclient;
+mod run_input;
 mod safety;
 mod session_flow;
 mod transport;
@@ -0,0 +1,83 @@
+use crate::test_support::*;
+use jcode::cli::run_input::prepare_run_input;
+
+/// `git diff | jcode run --attach CONTRIBUTING.md --append-system AGENTS.md -`
+#[tokio::test]
+async fn run_input_combines_stdin_attachments_and_appended_system_prompt() -> Result<()> {
+    let _env = setup_test_env()?;
+    let project = tempfile::Builder::new()
+        .prefix("jcode-run-input-")
+        .tempdir()?;
+    let contributing = project.path().join("CONTRIBUTING.md");
+    std::fs::write(&contributing, "Keep commits small.\n")?;
+    let agents = project.path().join("AGENTS.md");
+    std::fs::write(&agents, "Review the diff for missing tests.\n")?;
+    let diff = "diff --git a/src/lib.rs b/src/lib.rs\n+pub fn added() {}\n";
+
+    let input = prepare_run_input(
+        "-",
+        &[&contributing],
+        Some(agents.as_path()),
+        diff.as_bytes(),
+    )?;
+
+    let provider = Arc::new(MockProvider::new());
+    provider.queue_response(vec![
+        StreamEvent::TextDelta("Looks good.".to_string()),
+        StreamEvent::MessageEnd {
+            stop_reason: Some("end_turn".to_string()),
+        },
+    ]);
+    let provider_for_check = provider.clone();
+    let provider_dyn: Arc<dyn jcode::provider::Provider> = provider;
+    let registry = Registry::new(provider_dyn.clone()).await;
+    let mut agent = Agent::new(provider_dyn, registry);
+    agent.set_appended_system_prompt(input.append_system);
+
+    let response = agent.run_once_capture(&input.message).await?;
+    assert_eq!(response, "Looks good.");
+
+    let system_prompts = provider_for_check.captured_system_prompts.lock().unwrap();
+    assert!(
+        system_prompts[0].contains("Review the diff for missing tests."),
+        "appended instructions missing from system prompt"
+    );
+
+    let requests = provider_for_check.captured_messages.lock().unwrap();
+    let user_text: String = requests[0]
+        .iter()
+        .filter(|message| message.role == Role::User)
+        .flat_map(|message| message.content.iter())
+        .filter_map(|block| match block {
+            ContentBlock::Text { text, .. } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert!(user_text.contains(&format!(
+        "<attachment path=\"{}\">\nKeep commits small.\n</attachment>",
+        contributing.display()
+    )));
+    assert!(user_text.contains("+pub fn added() {}"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn run_input_rejects_binary_attachment() -> Result<()> {
+    let _env = setup_test_env()?;
+    let project = tempfile::Builder::new()
+        .prefix("jcode-run-input-")
+        .tempdir()?;
+    let image = project.path().join("screenshot.png");
+    std::fs::write(
+        &image,
+        [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a, 0, 0],
+    )?;
+
+    let err = prepare_run_input("review", &[&image], None, std::io::empty()).unwrap_err();
+    assert!(
+        err.to_string().contains("looks like a binary file"),
+        "unexpected error: {err}"
+    );
+    Ok(())
+}
@@ -51,6 +51,7 @@ fn test_safety_permission_flow() {
         wait: false,
         created_at: chrono::Utc::now(),
         context: None,
+        expires_at: None,
     };
 
     let result = safety.request_permission(req);
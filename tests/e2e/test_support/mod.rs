@@ -353,6 +353,7 @@ impl WsTestClient {
             content: content.to_string(),
             images: vec![],
             system_reminder: None,
+            focus_secs: None,
         })
         .await
     }
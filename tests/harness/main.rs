@@ -0,0 +1,122 @@
+//! Scripted-session harness.
+//!
+//! Every `.yaml`/`.json` file in `tests/harness/scripts/` is a conversation:
+//! user messages, the model responses (text and tool calls with optional
+//! canned results) a scripted provider returns for each, and expectations on
+//! the final session, workspace files and memories. Adding a regression test
+//! for an agent-loop bug means adding a script, not Rust code.
+//!
+//! To turn a real session into a script:
+//! `JCODE_HARNESS_SESSION=<id or path> cargo test --test harness -- --ignored record_session`
+
+mod provider;
+mod recorder;
+mod runner;
+mod script;
+
+use anyhow::Result;
+use jcode::message::{ContentBlock, Role};
+use jcode::session::Session;
+use script::Script;
+use std::path::{Path, PathBuf};
+
+fn scripts_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/harness/scripts")
+}
+
+#[tokio::test]
+async fn scripted_sessions_pass() -> Result<()> {
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(scripts_dir())?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<_>>()?;
+    paths.sort();
+    assert!(!paths.is_empty(), "no scripts found");
+
+    let mut failures = Vec::new();
+    for path in paths {
+        let script = Script::load(&path)?;
+        if let Err(err) = runner::run_script(&script).await {
+            failures.push(format!("{}: {:#}", path.display(), err));
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn failing_expectations_are_reported() -> Result<()> {
+    let mut script = Script::load(&scripts_dir().join("write_file.yaml"))?;
+    script.expect.reply = Some("something else".to_string());
+    script.expect.absent_files.push("notes.md".to_string());
+
+    let err = runner::run_script(&script)
+        .await
+        .expect_err("mismatched expectations should fail");
+    let message = format!("{:#}", err);
+    assert!(message.contains("reply was"), "{}", message);
+    assert!(message.contains("notes.md exists"), "{}", message);
+    Ok(())
+}
+
+#[tokio::test]
+async fn recorded_session_replays_as_script() -> Result<()> {
+    let mut session = Session::create(None, None);
+    let text = |text: &str| ContentBlock::Text {
+        text: text.to_string(),
+        cache_control: None,
+    };
+    session.add_message(Role::User, vec![text("What is in plan.md?")]);
+    session.add_message(
+        Role::Assistant,
+        vec![
+            text("Let me look."),
+            ContentBlock::ToolUse {
+                id: "toolu_1".to_string(),
+                name: "read".to_string(),
+                input: serde_json::json!({ "file_path": "plan.md" }),
+                thought_signature: None,
+            },
+        ],
+    );
+    session.add_message(
+        Role::User,
+        vec![ContentBlock::ToolResult {
+            tool_use_id: "toolu_1".to_string(),
+            content: "1\tShip the harness".to_string(),
+            is_error: None,
+        }],
+    );
+    session.add_message(Role::Assistant, vec![text("It says: ship the harness.")]);
+
+    let script = recorder::script_from_session(&session);
+    assert_eq!(script.turns.len(), 1);
+    let responses = &script.turns[0].assistant;
+    assert_eq!(responses.len(), 2);
+    assert_eq!(
+        responses[0].tool_calls[0].result.as_deref(),
+        Some("1\tShip the harness")
+    );
+    assert_eq!(
+        script.expect.reply.as_deref(),
+        Some("It says: ship the harness.")
+    );
+
+    // plan.md does not exist in the replay workspace, so this only passes if
+    // the recorded result is replayed instead of running the read tool.
+    runner::run_script(&script).await
+}
+
+/// Converts `JCODE_HARNESS_SESSION` (a session id or a session file path) to a
+/// script on stdout.
+#[test]
+#[ignore = "manual: records a script from a real session"]
+fn record_session() -> Result<()> {
+    let target = std::env::var("JCODE_HARNESS_SESSION")?;
+    let session = if Path::new(&target).is_file() {
+        Session::load_from_path(Path::new(&target))?
+    } else {
+        Session::load(&target)?
+    };
+    print!("{}", recorder::script_from_session(&session).to_yaml()?);
+    Ok(())
+}
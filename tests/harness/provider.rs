@@ -0,0 +1,138 @@
+//! Provider that replays the scripted responses of one turn at a time.
+//!
+//! Mismatches are collected as failures instead of returned as provider
+//! errors, so the agent loop finishes normally and the runner can report
+//! every problem in a script at once.
+
+use crate::script::{Response, ToolCall};
+use anyhow::Result;
+use jcode::message::{ContentBlock, Message, StreamEvent, ToolDefinition};
+use jcode::provider::{EventStream, Provider};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+#[derive(Default)]
+struct ScriptState {
+    responses: VecDeque<Response>,
+    /// Tool calls from the previous response whose results are unchecked.
+    pending: Vec<(String, ToolCall)>,
+    next_call: usize,
+    failures: Vec<String>,
+}
+
+#[derive(Clone, Default)]
+pub struct ScriptedProvider {
+    state: Arc<Mutex<ScriptState>>,
+}
+
+impl ScriptedProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue the responses for the next user turn.
+    pub fn begin_turn(&self, responses: Vec<Response>) {
+        self.state.lock().unwrap().responses = responses.into();
+    }
+
+    pub fn unused_responses(&self) -> usize {
+        self.state.lock().unwrap().responses.len()
+    }
+
+    pub fn take_failures(&self) -> Vec<String> {
+        std::mem::take(&mut self.state.lock().unwrap().failures)
+    }
+}
+
+fn check_tool_results(
+    messages: &[Message],
+    pending: Vec<(String, ToolCall)>,
+    failures: &mut Vec<String>,
+) {
+    for (id, call) in pending {
+        let result = messages
+            .iter()
+            .rev()
+            .flat_map(|message| &message.content)
+            .find_map(|block| match block {
+                ContentBlock::ToolResult {
+                    tool_use_id,
+                    content,
+                    ..
+                } if *tool_use_id == id => Some(content.as_str()),
+                _ => None,
+            });
+        match (result, call.expect_result.as_deref()) {
+            (None, _) => failures.push(format!("no result for {} call {}", call.name, id)),
+            (Some(content), Some(expected)) if !content.contains(expected) => {
+                failures.push(format!(
+                    "{} result {:?} does not contain {:?}",
+                    call.name, content, expected
+                ))
+            }
+            _ => {}
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Provider for ScriptedProvider {
+    async fn complete(
+        &self,
+        messages: &[Message],
+        _tools: &[ToolDefinition],
+        _system: &str,
+        _resume_session_id: Option<&str>,
+    ) -> Result<EventStream> {
+        let mut state = self.state.lock().unwrap();
+        let pending = std::mem::take(&mut state.pending);
+        check_tool_results(messages, pending, &mut state.failures);
+
+        let mut events = Vec::new();
+        let Some(response) = state.responses.pop_front() else {
+            state
+                .failures
+                .push("provider called with no scripted response left".to_string());
+            events.push(StreamEvent::MessageEnd {
+                stop_reason: Some("end_turn".to_string()),
+            });
+            return Ok(Box::pin(futures::stream::iter(events.into_iter().map(Ok))));
+        };
+
+        if let Some(text) = response.text {
+            events.push(StreamEvent::TextDelta(text));
+        }
+        let stop_reason = if response.tool_calls.is_empty() {
+            "end_turn"
+        } else {
+            "tool_use"
+        };
+        for call in response.tool_calls {
+            state.next_call += 1;
+            let id = format!("call_{}", state.next_call);
+            events.push(StreamEvent::ToolUseStart {
+                id: id.clone(),
+                name: call.name.clone(),
+            });
+            events.push(StreamEvent::ToolInputDelta(call.input.to_string()));
+            events.push(StreamEvent::ToolUseEnd);
+            state.pending.push((id, call));
+        }
+        events.push(StreamEvent::MessageEnd {
+            stop_reason: Some(stop_reason.to_string()),
+        });
+        Ok(Box::pin(futures::stream::iter(events.into_iter().map(Ok))))
+    }
+
+    fn name(&self) -> &str {
+        "scripted"
+    }
+
+    fn model(&self) -> String {
+        "scripted".to_string()
+    }
+
+    fn fork(&self) -> Arc<dyn Provider> {
+        Arc::new(self.clone())
+    }
+}
@@ -0,0 +1,95 @@
+//! Converts a recorded session into a [`Script`].
+//!
+//! Every tool call keeps the result it produced during the recording as its
+//! canned `result`, so the replay never touches the filesystem or network.
+//! The final assistant reply and message count become the expectations.
+
+use crate::script::{Expectations, Response, Script, ToolCall, Turn};
+use jcode::message::{ContentBlock, Role};
+use jcode::session::Session;
+use std::collections::HashMap;
+
+pub fn script_from_session(session: &Session) -> Script {
+    let mut turns: Vec<Turn> = Vec::new();
+    // Tool use id -> (turn, response, call) indices.
+    let mut calls: HashMap<&str, (usize, usize, usize)> = HashMap::new();
+    let mut message_count = 0;
+
+    for message in &session.messages {
+        if message.display_role.is_some() {
+            continue;
+        }
+        message_count += 1;
+        let mut text = Vec::new();
+        match message.role {
+            Role::User => {
+                for block in &message.content {
+                    match block {
+                        ContentBlock::Text { text: part, .. } => text.push(part.as_str()),
+                        ContentBlock::ToolResult {
+                            tool_use_id,
+                            content,
+                            ..
+                        } => {
+                            if let Some(&(turn, response, call)) = calls.get(tool_use_id.as_str()) {
+                                turns[turn].assistant[response].tool_calls[call].result =
+                                    Some(content.clone());
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                if !text.is_empty() {
+                    turns.push(Turn {
+                        user: text.join("\n"),
+                        assistant: Vec::new(),
+                    });
+                }
+            }
+            Role::Assistant => {
+                let turn_index = turns.len().saturating_sub(1);
+                let Some(turn) = turns.last_mut() else {
+                    continue;
+                };
+                let mut response = Response::default();
+                for block in &message.content {
+                    match block {
+                        ContentBlock::Text { text: part, .. } => text.push(part.as_str()),
+                        ContentBlock::ToolUse {
+                            id, name, input, ..
+                        } => {
+                            calls.insert(
+                                id,
+                                (turn_index, turn.assistant.len(), response.tool_calls.len()),
+                            );
+                            response.tool_calls.push(ToolCall {
+                                name: name.clone(),
+                                input: input.clone(),
+                                ..ToolCall::default()
+                            });
+                        }
+                        _ => {}
+                    }
+                }
+                response.text = (!text.is_empty()).then(|| text.concat());
+                turn.assistant.push(response);
+            }
+        }
+    }
+
+    let reply = turns
+        .last()
+        .and_then(|turn| turn.assistant.last())
+        .and_then(|response| response.text.clone());
+    Script {
+        name: session.id.clone(),
+        description: Some(format!("Recorded from session {}", session.id)),
+        turns,
+        expect: Expectations {
+            reply,
+            message_count: Some(message_count),
+            ..Expectations::default()
+        },
+        ..Script::default()
+    }
+}
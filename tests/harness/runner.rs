@@ -0,0 +1,263 @@
+//! Drives an agent through a [`Script`] and checks its expectations.
+//!
+//! Each run gets its own `JCODE_HOME` and workspace tempdir. Built-in tools run
+//! for real against the workspace; the memory tool uses isolated test storage
+//! so no embedding model is loaded.
+
+use crate::provider::ScriptedProvider;
+use crate::script::{Expectations, Script};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use jcode::memory::MemoryManager;
+use jcode::provider::Provider;
+use jcode::tool::memory::MemoryTool;
+use jcode::tool::{Registry, Tool, ToolContext, ToolOutput};
+use jcode::{Agent, AgentBuilder};
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, VecDeque};
+use std::ffi::OsString;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+
+static ENV_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+
+const ISOLATED_ENV: &[(&str, &str)] = &[
+    ("JCODE_TEST_SESSION", "1"),
+    ("JCODE_MEMORY_ENABLED", "0"),
+    ("JCODE_MEMORY_SIDECAR_ENABLED", "0"),
+];
+
+/// Points `JCODE_HOME` at a tempdir for the lifetime of one script run.
+struct HarnessEnv {
+    _lock: MutexGuard<'static, ()>,
+    saved: Vec<(&'static str, Option<OsString>)>,
+    _home: tempfile::TempDir,
+}
+
+impl HarnessEnv {
+    fn new() -> Result<Self> {
+        let lock = match ENV_LOCK.get_or_init(|| Mutex::new(())).lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let home = tempfile::Builder::new()
+            .prefix("jcode-harness-home-")
+            .tempdir()?;
+        let runtime_dir = home.path().join("runtime");
+        std::fs::create_dir_all(&runtime_dir)?;
+
+        let mut saved = Vec::new();
+        let mut set = |name: &'static str, value: &Path| {
+            saved.push((name, std::env::var_os(name)));
+            jcode::env::set_var(name, value);
+        };
+        set("JCODE_HOME", home.path());
+        set("JCODE_RUNTIME_DIR", &runtime_dir);
+        for (name, value) in ISOLATED_ENV {
+            set(name, Path::new(value));
+        }
+        Ok(Self {
+            _lock: lock,
+            saved,
+            _home: home,
+        })
+    }
+}
+
+impl Drop for HarnessEnv {
+    fn drop(&mut self) {
+        for (name, prev) in self.saved.drain(..).rev() {
+            match prev {
+                Some(prev) => jcode::env::set_var(name, prev),
+                None => jcode::env::remove_var(name),
+            }
+        }
+    }
+}
+
+/// Returns scripted results in call order, falling back to the real tool for
+/// calls the script left without a `result`.
+struct CannedTool {
+    name: String,
+    real: Option<Arc<dyn Tool>>,
+    results: Mutex<VecDeque<Option<String>>>,
+}
+
+#[async_trait]
+impl Tool for CannedTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        self.real
+            .as_ref()
+            .map_or("Scripted tool.", |real| real.description())
+    }
+
+    fn parameters_schema(&self) -> Value {
+        self.real.as_ref().map_or_else(
+            || json!({ "type": "object" }),
+            |real| real.parameters_schema(),
+        )
+    }
+
+    async fn execute(&self, input: Value, ctx: ToolContext) -> Result<ToolOutput> {
+        let canned = self.results.lock().unwrap().pop_front().flatten();
+        match (canned, &self.real) {
+            (Some(output), _) => Ok(ToolOutput::new(output)),
+            (None, Some(real)) => real.execute(input, ctx).await,
+            (None, None) => anyhow::bail!("no scripted result left for {}", self.name),
+        }
+    }
+}
+
+async fn install_tools(registry: &Registry, script: &Script) {
+    registry
+        .register("memory".to_string(), Arc::new(MemoryTool::new_test()))
+        .await;
+
+    let mut results: BTreeMap<&str, Vec<Option<String>>> = BTreeMap::new();
+    let calls = script
+        .turns
+        .iter()
+        .flat_map(|turn| &turn.assistant)
+        .flat_map(|response| &response.tool_calls);
+    for call in calls {
+        results
+            .entry(call.name.as_str())
+            .or_default()
+            .push(call.result.clone());
+    }
+    for (name, results) in results {
+        if results.iter().all(Option::is_none) {
+            continue;
+        }
+        let real = registry.unregister(name).await;
+        let tool = CannedTool {
+            name: name.to_string(),
+            real,
+            results: Mutex::new(results.into()),
+        };
+        registry.register(name.to_string(), Arc::new(tool)).await;
+    }
+}
+
+/// Run `script` end to end. The error lists every mismatch found.
+pub async fn run_script(script: &Script) -> Result<()> {
+    let _env = HarnessEnv::new()?;
+    let workspace = tempfile::Builder::new()
+        .prefix("jcode-harness-workspace-")
+        .tempdir()?;
+    for (path, contents) in &script.files {
+        let path = workspace.path().join(path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, contents)?;
+    }
+
+    let provider = ScriptedProvider::new();
+    let provider_dyn: Arc<dyn Provider> = Arc::new(provider.clone());
+    let registry = Registry::new(provider_dyn.clone()).await;
+    install_tools(&registry, script).await;
+    let mut agent = AgentBuilder::new(provider_dyn)
+        .registry(registry)
+        .build()
+        .await;
+    agent.set_working_dir(&workspace.path().display().to_string());
+
+    let mut failures = Vec::new();
+    let mut reply = String::new();
+    for (index, turn) in script.turns.iter().enumerate() {
+        let turn_number = index + 1;
+        provider.begin_turn(turn.assistant.clone());
+        reply = agent
+            .run_once_capture(&turn.user)
+            .await
+            .with_context(|| format!("turn {} failed", turn_number))?;
+        for failure in provider.take_failures() {
+            failures.push(format!("turn {}: {}", turn_number, failure));
+        }
+        let unused = provider.unused_responses();
+        if unused > 0 {
+            failures.push(format!(
+                "turn {}: {} scripted response(s) never requested",
+                turn_number, unused
+            ));
+        }
+    }
+
+    check_expectations(
+        &script.expect,
+        &agent,
+        workspace.path(),
+        &reply,
+        &mut failures,
+    )?;
+    if !failures.is_empty() {
+        anyhow::bail!(
+            "script {:?} failed:\n  {}",
+            script.name,
+            failures.join("\n  ")
+        );
+    }
+    Ok(())
+}
+
+fn check_expectations(
+    expect: &Expectations,
+    agent: &Agent,
+    workspace: &Path,
+    reply: &str,
+    failures: &mut Vec<String>,
+) -> Result<()> {
+    if let Some(expected) = &expect.reply
+        && reply != expected
+    {
+        failures.push(format!("reply was {:?}, expected {:?}", reply, expected));
+    }
+
+    if let Some(expected) = expect.message_count {
+        let count = agent
+            .messages()
+            .iter()
+            .filter(|message| message.display_role.is_none())
+            .count();
+        if count != expected {
+            failures.push(format!(
+                "session has {} messages, expected {}",
+                count, expected
+            ));
+        }
+    }
+
+    for (path, expected) in &expect.files {
+        match std::fs::read_to_string(workspace.join(path)) {
+            Ok(contents) if contents == *expected => {}
+            Ok(contents) => failures.push(format!(
+                "{} contains {:?}, expected {:?}",
+                path, contents, expected
+            )),
+            Err(err) => failures.push(format!("{} could not be read: {}", path, err)),
+        }
+    }
+    for path in &expect.absent_files {
+        if workspace.join(path).exists() {
+            failures.push(format!("{} exists but should not", path));
+        }
+    }
+
+    if !expect.memories.is_empty() {
+        let memories = MemoryManager::new_test().list_all()?;
+        for expected in &expect.memories {
+            if !memories
+                .iter()
+                .any(|entry| entry.content.contains(expected))
+            {
+                failures.push(format!("no memory contains {:?}", expected));
+            }
+        }
+    }
+    Ok(())
+}
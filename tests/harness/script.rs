@@ -0,0 +1,98 @@
+//! Script format for scripted sessions.
+//!
+//! A script lists the user messages of a conversation and, for each one, the
+//! model responses the scripted provider should return in call order. Tool
+//! calls either run the real tool or return a canned `result`.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Script {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Files written into the workspace tempdir before the first turn.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub files: BTreeMap<String, String>,
+    pub turns: Vec<Turn>,
+    #[serde(default)]
+    pub expect: Expectations,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Turn {
+    pub user: String,
+    /// One entry per provider call made while handling `user`.
+    pub assistant: Vec<Response>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Response {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<ToolCall>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ToolCall {
+    pub name: String,
+    #[serde(default = "empty_object")]
+    pub input: Value,
+    /// Canned tool output. When absent the real tool runs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<String>,
+    /// Substring the tool result must contain, checked on the next provider call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expect_result: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Expectations {
+    /// Exact text of the final assistant reply.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply: Option<String>,
+    /// Number of stored session messages, excluding system-display messages.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_count: Option<usize>,
+    /// Exact contents of files in the workspace tempdir.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub files: BTreeMap<String, String>,
+    /// Paths that must not exist in the workspace tempdir.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub absent_files: Vec<String>,
+    /// Substrings that must each match the content of some stored memory.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub memories: Vec<String>,
+}
+
+fn empty_object() -> Value {
+    Value::Object(Default::default())
+}
+
+impl Script {
+    /// Load a `.json`, `.yaml` or `.yml` script.
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let script = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => serde_json::from_str(&raw).map_err(anyhow::Error::from),
+            Some("yaml" | "yml") => serde_yaml::from_str(&raw).map_err(anyhow::Error::from),
+            _ => anyhow::bail!("unsupported script extension: {}", path.display()),
+        };
+        script.with_context(|| format!("invalid script {}", path.display()))
+    }
+
+    pub fn to_yaml(&self) -> Result<String> {
+        Ok(serde_yaml::to_string(self)?)
+    }
+}